@@ -0,0 +1,178 @@
+// Derives the plumbing of an event enum from its variants.
+//
+// Event enums are internally tagged by serde, one variant per stored version, named after the
+// event and its version (`TimeEntryInitiatedV1`) and wrapping that version's payload struct.
+// `#[derive(DomainEvent)]` reads the type and version off each variant name and implements
+// `shared::core::domain_event::DomainEvent` (the catalog, `event_type`, `event_version` and,
+// for versions declared retired, upcasting) plus a `From` for each payload. Serialization
+// stays with serde's derives, so the tag remains the variant name.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{Data, DeriveInput, Fields, LitStr, Path, Token, parse_macro_input};
+
+/// Implements `DomainEvent` for an event enum. Retired versions are upcast by a function
+/// taking and returning the stored JSON, declared on the enum:
+/// `#[domain_event(upcast("TagCreatedV0", upcasters::tag_created_v0))]`.
+#[proc_macro_derive(DomainEvent, attributes(domain_event))]
+pub fn derive_domain_event(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+struct Variant {
+    ident: syn::Ident,
+    payload: syn::Type,
+    event_type: String,
+    event_version: i32,
+}
+
+fn expand(input: DeriveInput) -> syn::Result<TokenStream2> {
+    let Data::Enum(data) = &input.data else {
+        return Err(syn::Error::new_spanned(
+            &input.ident,
+            "DomainEvent can only be derived for an enum",
+        ));
+    };
+    let variants = data
+        .variants
+        .iter()
+        .map(|variant| {
+            let Fields::Unnamed(fields) = &variant.fields else {
+                return Err(syn::Error::new_spanned(
+                    variant,
+                    "an event variant wraps its payload struct, as in `NameV1(NameV1)`",
+                ));
+            };
+            let [field] = Vec::from_iter(&fields.unnamed)[..] else {
+                return Err(syn::Error::new_spanned(
+                    fields,
+                    "an event variant wraps exactly one payload struct",
+                ));
+            };
+            let (event_type, event_version) = split_version(&variant.ident.to_string())
+                .ok_or_else(|| {
+                    syn::Error::new_spanned(
+                        &variant.ident,
+                        "an event variant is named after its event and version, as in `NameV1`",
+                    )
+                })?;
+            Ok(Variant {
+                ident: variant.ident.clone(),
+                payload: field.ty.clone(),
+                event_type,
+                event_version,
+            })
+        })
+        .collect::<syn::Result<Vec<_>>>()?;
+    let upcasters = upcasters(&input)?;
+
+    let name = &input.ident;
+    let (impl_generics, type_generics, where_clause) = input.generics.split_for_impl();
+    let catalog = variants.iter().map(|variant| {
+        let tag = variant.ident.to_string();
+        let (event_type, event_version) = (&variant.event_type, variant.event_version);
+        quote! {
+            crate::shared::core::domain_event::EventRegistration {
+                tag: #tag,
+                event_type: #event_type,
+                event_version: #event_version,
+            }
+        }
+    });
+    let event_types = variants.iter().map(|variant| {
+        let (ident, event_type) = (&variant.ident, &variant.event_type);
+        quote! { Self::#ident(_) => #event_type }
+    });
+    let event_versions = variants.iter().map(|variant| {
+        let (ident, event_version) = (&variant.ident, variant.event_version);
+        quote! { Self::#ident(_) => #event_version }
+    });
+    let upcast = (!upcasters.is_empty()).then(|| {
+        let arms = upcasters
+            .iter()
+            .map(|(tag, upcaster)| quote! { Some(#tag) => #upcaster(stored) });
+        quote! {
+            fn upcast(stored: serde_json::Value) -> serde_json::Value {
+                match stored.get("type").and_then(serde_json::Value::as_str) {
+                    #(#arms,)*
+                    _ => stored,
+                }
+            }
+        }
+    });
+    let froms = variants.iter().map(|variant| {
+        let (ident, payload) = (&variant.ident, &variant.payload);
+        quote! {
+            impl #impl_generics From<#payload> for #name #type_generics #where_clause {
+                fn from(event: #payload) -> Self {
+                    Self::#ident(event)
+                }
+            }
+        }
+    });
+
+    Ok(quote! {
+        impl #impl_generics crate::shared::core::domain_event::DomainEvent
+            for #name #type_generics #where_clause
+        {
+            const CATALOG: &'static [crate::shared::core::domain_event::EventRegistration] =
+                &[#(#catalog),*];
+
+            fn event_type(&self) -> &'static str {
+                match self {
+                    #(#event_types,)*
+                }
+            }
+
+            fn event_version(&self) -> i32 {
+                match self {
+                    #(#event_versions,)*
+                }
+            }
+
+            #upcast
+        }
+
+        #(#froms)*
+    })
+}
+
+/// `TimeEntryInitiatedV1` into `("TimeEntryInitiated", 1)`.
+fn split_version(tag: &str) -> Option<(String, i32)> {
+    let digits = tag.len() - tag.trim_end_matches(|c: char| c.is_ascii_digit()).len();
+    let event_type = tag[..tag.len() - digits].strip_suffix('V')?;
+    if event_type.is_empty() || digits == 0 {
+        return None;
+    }
+    Some((
+        event_type.to_string(),
+        tag[tag.len() - digits..].parse().ok()?,
+    ))
+}
+
+/// The `upcast("Tag", path)` entries of the enum's `#[domain_event(...)]` attributes.
+fn upcasters(input: &DeriveInput) -> syn::Result<Vec<(LitStr, Path)>> {
+    let mut upcasters = Vec::new();
+    for attr in input
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("domain_event"))
+    {
+        attr.parse_nested_meta(|meta| {
+            if !meta.path.is_ident("upcast") {
+                return Err(meta.error("expected `upcast(\"RetiredTagV1\", path::to::upcaster)`"));
+            }
+            let content;
+            syn::parenthesized!(content in meta.input);
+            let tag: LitStr = content.parse()?;
+            content.parse::<Token![,]>()?;
+            upcasters.push((tag, content.parse()?));
+            Ok(())
+        })?;
+    }
+    Ok(upcasters)
+}
@@ -1,30 +1,146 @@
 pub mod shared {
     pub mod core {
+        pub mod calendar;
+        pub mod decider;
+        pub mod domain_event;
+        pub mod partitioning;
+        pub mod personal_data;
         pub mod primitives;
+        pub mod redaction;
+        pub mod stream_naming;
     }
+    pub mod auth {
+        pub mod rbac;
+    }
+    pub mod application {
+        #[cfg(feature = "server")]
+        pub mod archive_events_job;
+        #[cfg(feature = "server")]
+        pub mod command_bus;
+        #[cfg(feature = "server")]
+        pub mod event_sourced_handler;
+        #[cfg(feature = "server")]
+        pub mod forget_user;
+        #[cfg(feature = "server")]
+        pub mod jobs;
+        #[cfg(feature = "server")]
+        pub mod outbox_integrity;
+        pub mod server_time;
+        #[cfg(feature = "server")]
+        pub mod slo;
+        #[cfg(feature = "server")]
+        pub mod watchdog;
+    }
+    #[cfg(feature = "server")]
     pub mod infrastructure {
+        pub mod api_audit_store;
+        pub mod api_key_store;
+        pub mod attachment_storage;
+        pub mod calendar;
+        pub mod capacity;
+        pub mod clock;
+        pub mod cold_storage;
+        pub mod control_store;
+        pub mod deadline;
+        pub mod event_archiver;
+        pub mod event_bus;
         pub mod event_store;
+        pub mod feature_flags;
+        pub mod inbox;
+        pub mod intent_handlers;
         pub mod intent_outbox;
+        pub mod job_store;
+        pub mod key_provider;
+        pub mod key_store;
+        pub mod lease_store;
+        pub mod log_redaction;
+        pub mod message_broker;
+        pub mod notifier;
+        pub mod outbox_relay;
+        pub mod policy_store;
+        #[cfg(feature = "postgres")]
+        pub mod postgres;
         pub mod projection_store;
+        pub mod query_cache;
         pub mod request_context;
+        pub mod secrets;
+        pub mod stream_transfer;
+        pub mod user_directory;
     }
 }
 
 pub mod modules {
     pub mod time_entries {
         pub mod core {
+            pub mod attachments;
+            pub mod breaks;
+            pub mod day_capacity;
+            pub mod days_off;
             pub mod events;
             pub mod evolve;
+            pub mod hourly_rate;
             pub mod intents;
+            pub mod period_locks;
+            pub mod policies;
+            #[cfg(feature = "server")]
             pub mod projections;
             pub mod state;
+            pub mod tag;
+            pub mod time_interval;
+            pub mod user_time_entries;
         }
         pub mod use_cases {
+            pub mod add_time_entry_attachment {
+                pub mod command;
+                pub mod decide;
+                pub mod decision;
+                #[cfg(feature = "server")]
+                pub mod handler;
+                #[cfg(feature = "server")]
+                pub mod inbound {
+                    pub mod graphql;
+                }
+            }
+            pub mod add_time_entry_comment {
+                pub mod command;
+                pub mod decide;
+                pub mod decision;
+                #[cfg(feature = "server")]
+                pub mod handler;
+                #[cfg(feature = "server")]
+                pub mod inbound {
+                    pub mod graphql;
+                }
+            }
+            pub mod approve_time_entry {
+                pub mod command;
+                pub mod decide;
+                pub mod decision;
+                #[cfg(feature = "server")]
+                pub mod handler;
+                #[cfg(feature = "server")]
+                pub mod inbound {
+                    pub mod graphql;
+                }
+            }
+            pub mod reject_time_entry {
+                pub mod command;
+                pub mod decide;
+                pub mod decision;
+                #[cfg(feature = "server")]
+                pub mod handler;
+                #[cfg(feature = "server")]
+                pub mod inbound {
+                    pub mod graphql;
+                }
+            }
             pub mod set_started_at {
                 pub mod command;
                 pub mod decide;
                 pub mod decision;
+                #[cfg(feature = "server")]
                 pub mod handler;
+                #[cfg(feature = "server")]
                 pub mod inbound {
                     pub mod graphql;
                     pub mod http;
@@ -34,32 +150,202 @@ pub mod modules {
                 pub mod command;
                 pub mod decide;
                 pub mod decision;
+                #[cfg(feature = "server")]
                 pub mod handler;
+                #[cfg(feature = "server")]
+                pub mod inbound {
+                    pub mod graphql;
+                    pub mod http;
+                }
+            }
+            #[cfg(feature = "server")]
+            pub mod register_time_entry {
                 pub mod inbound {
                     pub mod graphql;
+                }
+            }
+            #[cfg(feature = "server")]
+            pub mod bulk_delete_time_entries {
+                pub mod confirmations;
+                pub mod inbound {
+                    pub mod http;
+                }
+                pub mod job;
+            }
+            #[cfg(feature = "server")]
+            pub mod compact_time_entries {
+                pub mod compactor;
+                pub mod inbound {
+                    pub mod http;
+                }
+                pub mod job;
+            }
+            #[cfg(feature = "server")]
+            pub mod archive_time_entries {
+                pub mod archiver;
+                pub mod inbound {
+                    pub mod http;
+                }
+                pub mod job;
+            }
+            pub mod auto_stop_timers {
+                pub mod command;
+                pub mod decide;
+                pub mod decision;
+                #[cfg(feature = "server")]
+                pub mod handler;
+                #[cfg(feature = "server")]
+                pub mod stopper;
+            }
+            #[cfg(feature = "server")]
+            pub mod outbox_integrity {
+                pub mod inbound {
+                    pub mod http;
+                }
+            }
+            #[cfg(feature = "server")]
+            pub mod sync {
+                pub mod inbound {
                     pub mod http;
                 }
             }
+            pub mod delete_time_entry {
+                pub mod command;
+                pub mod decide;
+                pub mod decision;
+                #[cfg(feature = "server")]
+                pub mod handler;
+                #[cfg(feature = "server")]
+                pub mod inbound {
+                    pub mod http;
+                }
+            }
+            #[cfg(feature = "server")]
+            pub mod erase_personal_data {
+                pub mod eraser;
+            }
+            #[cfg(feature = "server")]
+            pub mod export_time_entries {
+                pub mod exporter;
+                pub mod inbound {
+                    pub mod http;
+                }
+                pub mod job;
+            }
+            #[cfg(feature = "server")]
+            pub mod hours_balance {
+                pub mod inbound {
+                    pub mod http;
+                }
+                pub mod queries;
+            }
+            #[cfg(feature = "server")]
             pub mod list_time_entries {
                 pub mod inbound {
+                    pub mod api_v1;
                     pub mod graphql;
                     pub mod http;
+                    pub mod sse;
                 }
                 pub mod projection;
                 pub mod projector;
                 pub mod queries;
+                pub mod rebuild_job;
+                pub mod self_check;
+                pub mod shadow;
+                pub mod updates;
+            }
+            pub mod period_locks {
+                pub mod command;
+                pub mod decide;
+                #[cfg(feature = "server")]
+                pub mod handler;
+                #[cfg(feature = "server")]
+                pub mod inbound {
+                    pub mod http;
+                }
+            }
+            pub mod set_breaks {
+                pub mod command;
+                pub mod decide;
+                pub mod decision;
+                #[cfg(feature = "server")]
+                pub mod handler;
+                #[cfg(feature = "server")]
+                pub mod inbound {
+                    pub mod graphql;
+                    pub mod http;
+                }
+            }
+            pub mod set_hourly_rate {
+                pub mod command;
+                pub mod decide;
+                pub mod decision;
+                #[cfg(feature = "server")]
+                pub mod handler;
+                #[cfg(feature = "server")]
+                pub mod inbound {
+                    pub mod graphql;
+                    pub mod http;
+                }
             }
             pub mod set_time_entry_tags {
                 pub mod command;
                 pub mod decide;
                 pub mod decision;
+                #[cfg(feature = "server")]
                 pub mod handler;
+                #[cfg(feature = "server")]
                 pub mod inbound {
                     pub mod graphql;
                     pub mod http;
                 }
             }
+            #[cfg(feature = "server")]
+            pub mod time_entry_attachments {
+                pub mod inbound {
+                    pub mod graphql;
+                }
+                pub mod projection;
+                pub mod projector;
+                pub mod queries;
+            }
+            #[cfg(feature = "server")]
+            pub mod time_entry_comments {
+                pub mod inbound {
+                    pub mod graphql;
+                }
+                pub mod projection;
+                pub mod projector;
+                pub mod queries;
+            }
+            pub mod update_time_entry {
+                pub mod command;
+                pub mod decide;
+                pub mod decision;
+                #[cfg(feature = "server")]
+                pub mod handler;
+                #[cfg(feature = "server")]
+                pub mod inbound {
+                    pub mod http;
+                }
+            }
+            #[cfg(feature = "server")]
+            pub mod user_stats {
+                pub mod inbound {
+                    pub mod graphql;
+                }
+                pub mod projection;
+                pub mod projector;
+                pub mod queries;
+            }
+            #[cfg(feature = "server")]
+            pub mod user_time_entries {
+                pub mod day_totals;
+                pub mod sharded_handler;
+            }
         }
+        #[cfg(feature = "server")]
         pub mod adapters {
             pub mod outbound {
                 pub mod event_store;
@@ -67,10 +353,45 @@ pub mod modules {
             }
         }
     }
+    pub mod contracts {
+        pub mod core {
+            pub mod events;
+            pub mod evolve;
+            pub mod state;
+        }
+        pub mod use_cases {
+            pub mod set_contract {
+                pub mod command;
+                pub mod decide;
+                pub mod decision;
+                #[cfg(feature = "server")]
+                pub mod handler;
+                #[cfg(feature = "server")]
+                pub mod inbound {
+                    pub mod graphql;
+                    pub mod http;
+                }
+            }
+            #[cfg(feature = "server")]
+            pub mod utilization {
+                pub mod inbound {
+                    pub mod graphql;
+                }
+                pub mod queries;
+            }
+        }
+        #[cfg(feature = "server")]
+        pub mod adapters {
+            pub mod outbound {
+                pub mod event_store;
+            }
+        }
+    }
     pub mod tags {
         pub mod core {
             pub mod events;
             pub mod evolve;
+            #[cfg(feature = "server")]
             pub mod projections;
             pub mod state;
         }
@@ -79,7 +400,9 @@ pub mod modules {
                 pub mod command;
                 pub mod decide;
                 pub mod decision;
+                #[cfg(feature = "server")]
                 pub mod handler;
+                #[cfg(feature = "server")]
                 pub mod inbound {
                     pub mod graphql;
                     pub mod http;
@@ -89,7 +412,9 @@ pub mod modules {
                 pub mod command;
                 pub mod decide;
                 pub mod decision;
+                #[cfg(feature = "server")]
                 pub mod handler;
+                #[cfg(feature = "server")]
                 pub mod inbound {
                     pub mod graphql;
                     pub mod http;
@@ -99,7 +424,9 @@ pub mod modules {
                 pub mod command;
                 pub mod decide;
                 pub mod decision;
+                #[cfg(feature = "server")]
                 pub mod handler;
+                #[cfg(feature = "server")]
                 pub mod inbound {
                     pub mod graphql;
                     pub mod http;
@@ -109,7 +436,9 @@ pub mod modules {
                 pub mod command;
                 pub mod decide;
                 pub mod decision;
+                #[cfg(feature = "server")]
                 pub mod handler;
+                #[cfg(feature = "server")]
                 pub mod inbound {
                     pub mod graphql;
                     pub mod http;
@@ -119,12 +448,15 @@ pub mod modules {
                 pub mod command;
                 pub mod decide;
                 pub mod decision;
+                #[cfg(feature = "server")]
                 pub mod handler;
+                #[cfg(feature = "server")]
                 pub mod inbound {
                     pub mod graphql;
                     pub mod http;
                 }
             }
+            #[cfg(feature = "server")]
             pub mod list_tags {
                 pub mod inbound {
                     pub mod graphql;
@@ -135,21 +467,120 @@ pub mod modules {
                 pub mod queries;
             }
         }
+        #[cfg(feature = "server")]
         pub mod adapters {
             pub mod outbound {
                 pub mod event_store;
             }
         }
     }
+    pub mod templates {
+        pub mod core {
+            pub mod events;
+            pub mod evolve;
+            pub mod state;
+        }
+        pub mod use_cases {
+            #[cfg(feature = "server")]
+            pub mod apply_template {
+                pub mod inbound {
+                    pub mod graphql;
+                }
+            }
+            #[cfg(feature = "server")]
+            pub mod list_templates {
+                pub mod inbound {
+                    pub mod graphql;
+                }
+                pub mod projection;
+                pub mod projector;
+                pub mod queries;
+            }
+            pub mod save_template {
+                pub mod command;
+                pub mod decide;
+                pub mod decision;
+                #[cfg(feature = "server")]
+                pub mod handler;
+                #[cfg(feature = "server")]
+                pub mod inbound {
+                    pub mod graphql;
+                }
+            }
+        }
+    }
+    pub mod schedules {
+        pub mod core {
+            pub mod events;
+            pub mod evolve;
+            pub mod occurrence;
+            pub mod recurrence;
+            pub mod state;
+        }
+        pub mod use_cases {
+            pub mod cancel_schedule {
+                pub mod command;
+                pub mod decide;
+                pub mod decision;
+                #[cfg(feature = "server")]
+                pub mod handler;
+                #[cfg(feature = "server")]
+                pub mod inbound {
+                    pub mod graphql;
+                }
+            }
+            pub mod define_schedule {
+                pub mod command;
+                pub mod decide;
+                pub mod decision;
+                #[cfg(feature = "server")]
+                pub mod handler;
+                #[cfg(feature = "server")]
+                pub mod inbound {
+                    pub mod graphql;
+                }
+            }
+            #[cfg(feature = "server")]
+            pub mod list_schedules {
+                pub mod inbound {
+                    pub mod graphql;
+                }
+                pub mod projection;
+                pub mod projector;
+                pub mod queries;
+            }
+            pub mod record_occurrence {
+                pub mod command;
+                pub mod decide;
+                pub mod decision;
+                #[cfg(feature = "server")]
+                pub mod handler;
+                #[cfg(feature = "server")]
+                pub mod materializer;
+            }
+        }
+    }
 }
 
+#[cfg(feature = "server")]
 pub mod shell;
+#[cfg(feature = "server")]
+pub mod time_entries_client;
+
+#[cfg(any(test, feature = "test-util"))]
+pub mod test_support;
 
-#[cfg(test)]
+#[cfg(any(test, feature = "test-util"))]
 pub mod tests {
+    pub mod contracts;
     pub mod fixtures;
+    #[cfg(test)]
+    pub mod golden;
 
+    #[cfg(test)]
     pub mod e2e {
         pub mod list_time_entries_tests;
+        pub mod shell_tests;
+        pub mod test_app;
     }
 }
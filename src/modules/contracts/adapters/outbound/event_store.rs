@@ -0,0 +1,2 @@
+// Module-specific event store outbound adapter.
+// Inject a concrete EventStore implementation here when wiring in shell/mod.rs.
@@ -0,0 +1,28 @@
+use crate::shared::core::domain_event::DomainEvent;
+
+pub mod v1 {
+    pub mod contract_set;
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, PartialEq, Eq, DomainEvent)]
+#[serde(tag = "type")]
+pub enum ContractEvent {
+    ContractSetV1(v1::contract_set::ContractSetV1),
+}
+
+#[cfg(test)]
+mod contract_event_schema_tests {
+    use super::*;
+    use crate::tests::golden::{assert_catalog, assert_event_goldens};
+    use rstest::rstest;
+
+    #[rstest]
+    fn every_version_should_match_its_golden() {
+        assert_event_goldens::<ContractEvent>("contracts");
+    }
+
+    #[rstest]
+    fn the_catalog_should_list_every_version() {
+        assert_catalog::<ContractEvent>();
+    }
+}
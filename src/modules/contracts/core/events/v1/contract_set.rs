@@ -0,0 +1,45 @@
+use chrono::NaiveDate;
+
+/// Contracted weekly time of a user from `start_date` on, until a later contract starts. A
+/// contract with the same `start_date` replaces it.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, PartialEq, Eq)]
+pub struct ContractSetV1 {
+    pub user_id: String,
+    pub minutes_per_week: i64,
+    pub start_date: NaiveDate,
+    pub set_at: i64,
+    pub set_by: String,
+}
+
+#[cfg(test)]
+mod contract_set_event_tests {
+    use super::*;
+    use rstest::{fixture, rstest};
+
+    #[fixture]
+    fn event() -> ContractSetV1 {
+        ContractSetV1 {
+            user_id: "user-fixed-0001".to_string(),
+            minutes_per_week: 2400,
+            start_date: NaiveDate::from_ymd_opt(2026, 1, 1).unwrap(),
+            set_at: 1700000000000,
+            set_by: "admin-fixed-0001".to_string(),
+        }
+    }
+
+    #[rstest]
+    fn it_should_have_correct_fields(event: ContractSetV1) {
+        assert_eq!(event.user_id, "user-fixed-0001");
+        assert_eq!(event.minutes_per_week, 2400);
+        assert_eq!(event.set_at, 1700000000000);
+    }
+
+    #[rstest]
+    fn it_serializes_stable(event: ContractSetV1) {
+        let golden: serde_json::Value = serde_json::from_str(include_str!(
+            "../../../../../tests/fixtures/events/json/contract_set_v1.json"
+        ))
+        .unwrap();
+        assert_eq!(serde_json::to_value(&event).unwrap(), golden);
+    }
+}
@@ -0,0 +1,49 @@
+use crate::modules::contracts::core::events::ContractEvent;
+use crate::modules::contracts::core::state::ContractState;
+
+pub fn evolve(mut state: ContractState, event: ContractEvent) -> ContractState {
+    match event {
+        ContractEvent::ContractSetV1(e) => {
+            state
+                .minutes_per_week
+                .insert(e.start_date, e.minutes_per_week);
+        }
+    }
+    state
+}
+
+#[cfg(test)]
+mod contract_evolve_tests {
+    use super::*;
+    use crate::modules::contracts::core::events::v1::contract_set::ContractSetV1;
+    use chrono::NaiveDate;
+    use rstest::rstest;
+
+    fn contract_set(day: u32, minutes_per_week: i64) -> ContractEvent {
+        ContractEvent::ContractSetV1(ContractSetV1 {
+            user_id: "u-1".to_string(),
+            minutes_per_week,
+            start_date: NaiveDate::from_ymd_opt(2026, 1, day).unwrap(),
+            set_at: 0,
+            set_by: "admin-1".to_string(),
+        })
+    }
+
+    #[rstest]
+    fn it_should_keep_contracts_by_start_date_replacing_same_day() {
+        let state = [
+            contract_set(1, 2400),
+            contract_set(5, 1920),
+            contract_set(1, 2280),
+        ]
+        .into_iter()
+        .fold(ContractState::default(), evolve);
+        assert_eq!(
+            state.minutes_per_week.into_iter().collect::<Vec<_>>(),
+            vec![
+                (NaiveDate::from_ymd_opt(2026, 1, 1).unwrap(), 2280),
+                (NaiveDate::from_ymd_opt(2026, 1, 5).unwrap(), 1920),
+            ]
+        );
+    }
+}
@@ -0,0 +1,49 @@
+use chrono::NaiveDate;
+use std::collections::BTreeMap;
+
+pub fn contract_stream_id(user_id: &str) -> String {
+    format!("Contract-{user_id}")
+}
+
+/// A user's contracts, keyed by start date.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ContractState {
+    pub minutes_per_week: BTreeMap<NaiveDate, i64>,
+}
+
+impl ContractState {
+    /// The contracted weekly minutes in effect on `date`; `None` before the first contract.
+    pub fn minutes_per_week_on(&self, date: NaiveDate) -> Option<i64> {
+        self.minutes_per_week
+            .range(..=date)
+            .next_back()
+            .map(|(_, minutes)| *minutes)
+    }
+}
+
+#[cfg(test)]
+mod contract_state_tests {
+    use super::*;
+    use rstest::rstest;
+
+    fn date(month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, month, day).unwrap()
+    }
+
+    #[rstest]
+    fn it_should_name_the_stream_after_the_user() {
+        assert_eq!(contract_stream_id("u-1"), "Contract-u-1");
+    }
+
+    #[rstest]
+    #[case::before_first(date(1, 31), None)]
+    #[case::first_day(date(2, 1), Some(2400))]
+    #[case::between(date(5, 31), Some(2400))]
+    #[case::after_change(date(6, 1), Some(1920))]
+    fn it_should_pick_the_contract_in_effect(#[case] on: NaiveDate, #[case] expected: Option<i64>) {
+        let state = ContractState {
+            minutes_per_week: BTreeMap::from([(date(2, 1), 2400), (date(6, 1), 1920)]),
+        };
+        assert_eq!(state.minutes_per_week_on(on), expected);
+    }
+}
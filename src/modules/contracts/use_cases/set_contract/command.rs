@@ -0,0 +1,29 @@
+use chrono::NaiveDate;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SetContract {
+    pub user_id: String,
+    pub minutes_per_week: i64,
+    pub start_date: NaiveDate,
+    pub set_at: i64,
+    pub set_by: String,
+}
+
+/// Whole minutes for a (possibly fractional) number of weekly hours, e.g. 37.5.
+pub fn minutes_from_hours(hours_per_week: f64) -> i64 {
+    (hours_per_week * 60.0).round() as i64
+}
+
+#[cfg(test)]
+mod set_contract_command_tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(40.0, 2400)]
+    #[case(37.5, 2250)]
+    #[case(f64::NAN, 0)]
+    fn it_should_convert_hours_to_minutes(#[case] hours: f64, #[case] minutes: i64) {
+        assert_eq!(minutes_from_hours(hours), minutes);
+    }
+}
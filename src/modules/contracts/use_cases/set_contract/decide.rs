@@ -0,0 +1,99 @@
+use crate::modules::contracts::core::events::ContractEvent;
+use crate::modules::contracts::core::events::v1::contract_set::ContractSetV1;
+use crate::modules::contracts::core::evolve::evolve;
+use crate::modules::contracts::core::state::ContractState;
+use crate::modules::contracts::use_cases::set_contract::command::SetContract;
+use crate::modules::contracts::use_cases::set_contract::decision::{DecideError, Decision};
+use crate::shared::core::decider::Decider;
+use std::convert::Infallible;
+
+const MINUTES_PER_WEEK: i64 = 7 * 24 * 60;
+
+pub fn decide_set_contract(_state: &ContractState, command: SetContract) -> Decision {
+    if !(1..=MINUTES_PER_WEEK).contains(&command.minutes_per_week) {
+        return Decision::Rejected {
+            reason: DecideError::InvalidHours,
+        };
+    }
+    Decision::Accepted {
+        events: vec![ContractEvent::ContractSetV1(ContractSetV1 {
+            user_id: command.user_id,
+            minutes_per_week: command.minutes_per_week,
+            start_date: command.start_date,
+            set_at: command.set_at,
+            set_by: command.set_by,
+        })],
+        intents: vec![],
+    }
+}
+
+pub struct SetContractDecider;
+
+impl Decider for SetContractDecider {
+    type State = ContractState;
+    type Command = SetContract;
+    type Event = ContractEvent;
+    type Intent = Infallible;
+    type Error = DecideError;
+
+    fn initial_state() -> ContractState {
+        ContractState::default()
+    }
+
+    fn evolve(state: ContractState, event: ContractEvent) -> ContractState {
+        evolve(state, event)
+    }
+
+    fn decide(state: &ContractState, command: SetContract) -> Decision {
+        decide_set_contract(state, command)
+    }
+}
+
+#[cfg(test)]
+mod set_contract_decide_tests {
+    use super::*;
+    use chrono::NaiveDate;
+    use rstest::rstest;
+
+    fn command(minutes_per_week: i64) -> SetContract {
+        SetContract {
+            user_id: "u-1".to_string(),
+            minutes_per_week,
+            start_date: NaiveDate::from_ymd_opt(2026, 1, 1).unwrap(),
+            set_at: 1000,
+            set_by: "admin-1".to_string(),
+        }
+    }
+
+    #[rstest]
+    #[case::one_minute(1)]
+    #[case::full_week(MINUTES_PER_WEEK)]
+    fn it_should_accept_valid_hours(#[case] minutes_per_week: i64) {
+        let decision =
+            SetContractDecider::decide(&ContractState::default(), command(minutes_per_week));
+        let Decision::Accepted { events, .. } = decision else {
+            panic!("expected Accepted");
+        };
+        let state = events.into_iter().fold(
+            SetContractDecider::initial_state(),
+            SetContractDecider::evolve,
+        );
+        assert_eq!(
+            state.minutes_per_week_on(NaiveDate::from_ymd_opt(2026, 1, 1).unwrap()),
+            Some(minutes_per_week)
+        );
+    }
+
+    #[rstest]
+    #[case::zero(0)]
+    #[case::negative(-60)]
+    #[case::more_than_a_week(MINUTES_PER_WEEK + 1)]
+    fn it_should_reject_invalid_hours(#[case] minutes_per_week: i64) {
+        assert!(matches!(
+            SetContractDecider::decide(&ContractState::default(), command(minutes_per_week)),
+            Decision::Rejected {
+                reason: DecideError::InvalidHours
+            }
+        ));
+    }
+}
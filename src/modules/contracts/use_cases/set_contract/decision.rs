@@ -0,0 +1,11 @@
+use crate::modules::contracts::core::events::ContractEvent;
+use crate::shared::core::decider;
+use std::convert::Infallible;
+
+#[derive(Debug, thiserror::Error, PartialEq, Eq)]
+pub enum DecideError {
+    #[error("hours per week must be more than 0 and at most 168")]
+    InvalidHours,
+}
+
+pub type Decision = decider::Decision<ContractEvent, Infallible, DecideError>;
@@ -0,0 +1,114 @@
+use crate::modules::contracts::core::events::ContractEvent;
+use crate::modules::contracts::core::state::{ContractState, contract_stream_id};
+use crate::modules::contracts::use_cases::set_contract::command::SetContract;
+use crate::modules::contracts::use_cases::set_contract::decide::SetContractDecider;
+use crate::modules::contracts::use_cases::set_contract::decision::DecideError;
+use crate::shared::application::event_sourced_handler::{
+    EventSourcedError, EventSourcedHandler, NoIntents,
+};
+use crate::shared::core::decider::Decider;
+use crate::shared::infrastructure::event_store::{EventStore, EventStoreError};
+use std::convert::Infallible;
+
+pub type ApplicationError = EventSourcedError<DecideError, Infallible>;
+
+/// All contracts of `user_id`; empty when none were set.
+pub async fn load_contracts<TEventStore>(
+    event_store: &TEventStore,
+    user_id: &str,
+) -> Result<ContractState, EventStoreError>
+where
+    TEventStore: EventStore<ContractEvent> + ?Sized,
+{
+    let stream = event_store.load(&contract_stream_id(user_id)).await?;
+    Ok(stream.events.into_iter().fold(
+        SetContractDecider::initial_state(),
+        SetContractDecider::evolve,
+    ))
+}
+
+#[derive(Debug, Clone)]
+pub struct SetContractHandler<TEventStore>
+where
+    TEventStore: EventStore<ContractEvent> + Clone + Send + Sync + 'static,
+{
+    event_store: TEventStore,
+    inner: EventSourcedHandler<SetContractDecider, TEventStore, NoIntents>,
+}
+
+impl<TEventStore> SetContractHandler<TEventStore>
+where
+    TEventStore: EventStore<ContractEvent> + Clone + Send + Sync + 'static,
+{
+    pub fn new(event_store: TEventStore) -> Self {
+        Self {
+            event_store: event_store.clone(),
+            inner: EventSourcedHandler::new(event_store, NoIntents),
+        }
+    }
+
+    pub async fn handle(&self, command: SetContract) -> Result<(), ApplicationError> {
+        let stream_id = contract_stream_id(&command.user_id);
+        self.inner.handle(&stream_id, command).await
+    }
+
+    pub async fn contracts(&self, user_id: &str) -> Result<ContractState, EventStoreError> {
+        load_contracts(&self.event_store, user_id).await
+    }
+}
+
+#[cfg(test)]
+mod set_contract_handler_tests {
+    use super::*;
+    use crate::shared::infrastructure::event_store::in_memory::InMemoryEventStore;
+    use chrono::NaiveDate;
+    use rstest::rstest;
+
+    fn command(minutes_per_week: i64) -> SetContract {
+        SetContract {
+            user_id: "u-1".to_string(),
+            minutes_per_week,
+            start_date: NaiveDate::from_ymd_opt(2026, 1, 1).unwrap(),
+            set_at: 1000,
+            set_by: "admin-1".to_string(),
+        }
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_append_to_the_users_contract_stream() {
+        let event_store = InMemoryEventStore::<ContractEvent>::new();
+        let handler = SetContractHandler::new(event_store.clone());
+
+        handler.handle(command(2400)).await.unwrap();
+
+        assert_eq!(event_store.load("Contract-u-1").await.unwrap().version, 1);
+        let contracts = handler.contracts("u-1").await.unwrap();
+        assert_eq!(
+            contracts.minutes_per_week_on(NaiveDate::from_ymd_opt(2026, 3, 1).unwrap()),
+            Some(2400)
+        );
+        assert_eq!(
+            handler.contracts("u-2").await.unwrap(),
+            ContractState::default()
+        );
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_surface_rejections_and_store_failures() {
+        let event_store = InMemoryEventStore::<ContractEvent>::new();
+        let handler = SetContractHandler::new(event_store.clone());
+
+        assert!(matches!(
+            handler.handle(command(0)).await,
+            Err(ApplicationError::Domain(DecideError::InvalidHours))
+        ));
+        event_store.toggle_offline();
+        assert!(handler.contracts("u-1").await.is_err());
+        assert!(matches!(
+            handler.handle(command(2400)).await,
+            Err(ApplicationError::VersionConflict(_))
+        ));
+    }
+}
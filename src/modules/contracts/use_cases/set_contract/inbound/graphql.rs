@@ -0,0 +1,123 @@
+use async_graphql::{Context, Object, Result as GqlResult};
+use chrono::{NaiveDate, Utc};
+
+use crate::modules::contracts::use_cases::set_contract::command::{
+    SetContract, minutes_from_hours,
+};
+use crate::shared::infrastructure::request_context::RequestContext;
+use crate::shell::state::AppState;
+
+#[derive(Default)]
+pub struct SetContractMutation;
+
+#[Object]
+impl SetContractMutation {
+    /// Sets the user's weekly hours from `startDate` (`YYYY-MM-DD`) on. Admins only.
+    async fn set_contract(
+        &self,
+        context: &Context<'_>,
+        user_id: String,
+        hours_per_week: f64,
+        start_date: String,
+    ) -> GqlResult<bool> {
+        let req_ctx = context
+            .data::<RequestContext>()
+            .map_err(|_| async_graphql::Error::new("Unauthorized"))?;
+        if !req_ctx.principal().can_administer() {
+            return Err(async_graphql::Error::new("Forbidden"));
+        }
+        let start_date: NaiveDate = start_date
+            .parse()
+            .map_err(|_| async_graphql::Error::new("startDate must be YYYY-MM-DD"))?;
+        let state = context.data_unchecked::<AppState>();
+        let command = SetContract {
+            user_id,
+            minutes_per_week: minutes_from_hours(hours_per_week),
+            start_date,
+            set_at: Utc::now().timestamp_millis(),
+            set_by: req_ctx.user_id.clone(),
+        };
+
+        state
+            .set_contract_handler
+            .handle(command)
+            .await
+            .map_err(|e| async_graphql::Error::new(e.to_string()))?;
+
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+mod set_contract_graphql_inbound_tests {
+    use async_graphql::{EmptySubscription, Schema};
+
+    use crate::shared::auth::rbac::Role;
+    use crate::shared::infrastructure::request_context::RequestContext;
+    use crate::shell::graphql::{MutationRoot, QueryRoot};
+    use crate::tests::fixtures::tags::make_test_app_state;
+
+    fn make_schema_from_state(
+        state: crate::shell::state::AppState,
+    ) -> Schema<QueryRoot, MutationRoot, EmptySubscription> {
+        Schema::build(
+            QueryRoot::default(),
+            MutationRoot::default(),
+            EmptySubscription,
+        )
+        .data(state)
+        .finish()
+    }
+
+    fn req_ctx(role: Role) -> RequestContext {
+        RequestContext {
+            user_id: "admin-1".to_string(),
+            tenant_id: "tenant-test".to_string(),
+            role,
+            scope: Default::default(),
+        }
+    }
+
+    #[tokio::test]
+    async fn returns_true_on_success() {
+        let schema = make_schema_from_state(make_test_app_state());
+        let result = schema
+            .execute(
+                async_graphql::Request::new(
+                    r#"mutation { setContract(userId: "u-1", hoursPerWeek: 32, startDate: "2026-01-01") }"#,
+                )
+                .data(req_ctx(Role::Admin)),
+            )
+            .await;
+        assert!(result.errors.is_empty());
+        assert_eq!(result.data.to_string(), "{setContract: true}");
+    }
+
+    #[tokio::test]
+    async fn returns_forbidden_for_non_admins() {
+        let schema = make_schema_from_state(make_test_app_state());
+        let result = schema
+            .execute(
+                async_graphql::Request::new(
+                    r#"mutation { setContract(userId: "u-1", hoursPerWeek: 32, startDate: "2026-01-01") }"#,
+                )
+                .data(req_ctx(Role::Manager)),
+            )
+            .await;
+        assert_eq!(result.errors[0].message, "Forbidden");
+    }
+
+    #[tokio::test]
+    async fn returns_error_for_invalid_input() {
+        let schema = make_schema_from_state(make_test_app_state());
+        for query in [
+            r#"mutation { setContract(userId: "u-1", hoursPerWeek: 32, startDate: "01-01-2026") }"#,
+            r#"mutation { setContract(userId: "u-1", hoursPerWeek: 0, startDate: "2026-01-01") }"#,
+        ] {
+            let result = schema
+                .execute(async_graphql::Request::new(query).data(req_ctx(Role::Admin)))
+                .await;
+            assert!(!result.errors.is_empty());
+        }
+    }
+}
@@ -0,0 +1,147 @@
+use axum::{
+    Json,
+    extract::{Path, State, rejection::JsonRejection},
+    http::StatusCode,
+    response::IntoResponse,
+};
+use chrono::{NaiveDate, Utc};
+use serde::Deserialize;
+
+use crate::modules::contracts::use_cases::set_contract::command::{
+    SetContract, minutes_from_hours,
+};
+use crate::modules::contracts::use_cases::set_contract::handler::ApplicationError;
+use crate::shared::infrastructure::request_context::RequestContext;
+use crate::shell::state::AppState;
+
+#[derive(Deserialize)]
+pub struct SetContractBody {
+    pub hours_per_week: f64,
+    pub start_date: NaiveDate,
+}
+
+/// PUT /users/{user_id}/contract — sets the user's weekly hours from `start_date` on.
+/// Admins only.
+pub async fn handle(
+    State(state): State<AppState>,
+    request_ctx: RequestContext,
+    Path(user_id): Path<String>,
+    body: Result<Json<SetContractBody>, JsonRejection>,
+) -> impl IntoResponse {
+    if !request_ctx.principal().can_administer() {
+        return StatusCode::FORBIDDEN.into_response();
+    }
+    let Json(body) = match body {
+        Ok(b) => b,
+        Err(_) => return StatusCode::UNPROCESSABLE_ENTITY.into_response(),
+    };
+
+    let command = SetContract {
+        user_id,
+        minutes_per_week: minutes_from_hours(body.hours_per_week),
+        start_date: body.start_date,
+        set_at: Utc::now().timestamp_millis(),
+        set_by: request_ctx.user_id,
+    };
+
+    match state.set_contract_handler.handle(command).await {
+        Ok(()) => StatusCode::NO_CONTENT.into_response(),
+        Err(ApplicationError::Domain(_)) => StatusCode::UNPROCESSABLE_ENTITY.into_response(),
+        Err(_) => StatusCode::INTERNAL_SERVER_ERROR.into_response(),
+    }
+}
+
+#[cfg(test)]
+mod set_contract_http_inbound_tests {
+    use axum::{
+        Router,
+        body::Body,
+        http::{Request, StatusCode},
+        routing::put,
+    };
+    use chrono::NaiveDate;
+    use rstest::rstest;
+    use tower::ServiceExt;
+
+    use super::handle;
+    use crate::shell::state::AppState;
+    use crate::tests::fixtures::tags::make_test_app_state;
+
+    async fn put_contract(state: &AppState, role: &str, body: &str) -> StatusCode {
+        Router::new()
+            .route("/users/{user_id}/contract", put(handle))
+            .with_state(state.clone())
+            .oneshot(
+                Request::put("/users/u-1/contract")
+                    .header("content-type", "application/json")
+                    .header("x-user-id", "admin-1")
+                    .header("x-tenant-id", "tenant-test")
+                    .header("x-user-role", role)
+                    .body(Body::from(body.to_string()))
+                    .unwrap(),
+            )
+            .await
+            .unwrap()
+            .status()
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_set_the_contract() {
+        let state = make_test_app_state();
+
+        let status = put_contract(
+            &state,
+            "admin",
+            r#"{"hours_per_week":37.5,"start_date":"2026-01-01"}"#,
+        )
+        .await;
+
+        assert_eq!(status, StatusCode::NO_CONTENT);
+        let contracts = state.set_contract_handler.contracts("u-1").await.unwrap();
+        assert_eq!(
+            contracts.minutes_per_week_on(NaiveDate::from_ymd_opt(2026, 2, 1).unwrap()),
+            Some(2250)
+        );
+    }
+
+    #[rstest]
+    #[case::manager(
+        "manager",
+        r#"{"hours_per_week":40,"start_date":"2026-01-01"}"#,
+        StatusCode::FORBIDDEN
+    )]
+    #[case::malformed("admin", r#"{"hours_per_week":40}"#, StatusCode::UNPROCESSABLE_ENTITY)]
+    #[case::invalid_hours(
+        "admin",
+        r#"{"hours_per_week":0,"start_date":"2026-01-01"}"#,
+        StatusCode::UNPROCESSABLE_ENTITY
+    )]
+    #[tokio::test]
+    async fn it_should_reject_invalid_requests(
+        #[case] role: &str,
+        #[case] body: &str,
+        #[case] expected: StatusCode,
+    ) {
+        assert_eq!(
+            put_contract(&make_test_app_state(), role, body).await,
+            expected
+        );
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_return_500_when_the_store_is_offline() {
+        let state = make_test_app_state();
+        state.contract_event_store.toggle_offline();
+
+        let status = put_contract(
+            &state,
+            "admin",
+            r#"{"hours_per_week":40,"start_date":"2026-01-01"}"#,
+        )
+        .await;
+
+        assert_eq!(status, StatusCode::INTERNAL_SERVER_ERROR);
+    }
+}
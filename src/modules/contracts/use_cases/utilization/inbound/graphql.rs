@@ -0,0 +1,158 @@
+use async_graphql::{Context, Object, Result as GqlResult, SimpleObject};
+use chrono::NaiveDate;
+
+use crate::modules::contracts::use_cases::utilization::queries::Utilization;
+use crate::shared::infrastructure::request_context::RequestContext;
+use crate::shell::state::AppState;
+
+/// Longest range a single utilization may span.
+const MAX_DAYS: i64 = 366;
+
+#[derive(SimpleObject, Clone)]
+pub struct GqlUtilization {
+    pub user_id: String,
+    pub from: String,
+    pub to: String,
+    pub contracted_minutes: i64,
+    pub registered_minutes: i64,
+    /// Registered divided by contracted time; null without contracted time.
+    pub utilization: Option<f64>,
+}
+
+impl From<Utilization> for GqlUtilization {
+    fn from(u: Utilization) -> Self {
+        Self {
+            user_id: u.user_id,
+            from: u.from.to_string(),
+            to: u.to.to_string(),
+            contracted_minutes: u.contracted_minutes,
+            registered_minutes: u.registered_minutes,
+            utilization: u.utilization,
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct UtilizationQuery;
+
+#[Object]
+impl UtilizationQuery {
+    /// Registered versus contracted time between `from` and `to` (`YYYY-MM-DD`, inclusive).
+    /// `userId` defaults to the caller.
+    async fn utilization(
+        &self,
+        context: &Context<'_>,
+        user_id: Option<String>,
+        from: String,
+        to: String,
+    ) -> GqlResult<GqlUtilization> {
+        let req_ctx = context
+            .data::<RequestContext>()
+            .map_err(|_| async_graphql::Error::new("Unauthorized"))?;
+        let user_id = user_id.unwrap_or(req_ctx.user_id.clone());
+        if !req_ctx.principal().can_view_user(&user_id) {
+            return Err(async_graphql::Error::new("Forbidden"));
+        }
+        let (Ok(from), Ok(to)) = (from.parse::<NaiveDate>(), to.parse::<NaiveDate>()) else {
+            return Err(async_graphql::Error::new("from and to must be YYYY-MM-DD"));
+        };
+        if !(0..MAX_DAYS).contains(&(to - from).num_days()) {
+            return Err(async_graphql::Error::new(
+                "to must not be before from, and the range at most 366 days",
+            ));
+        }
+        let state = context.data_unchecked::<AppState>();
+        let utilization = state
+            .utilization_handler
+            .utilization(&user_id, from, to)
+            .await?;
+        Ok(utilization.into())
+    }
+}
+
+#[cfg(test)]
+mod utilization_graphql_tests {
+    use async_graphql::{EmptySubscription, Schema};
+
+    use crate::modules::contracts::use_cases::set_contract::command::SetContract;
+    use crate::shared::auth::rbac::Role;
+    use crate::shared::infrastructure::request_context::RequestContext;
+    use crate::shell::graphql::{MutationRoot, QueryRoot};
+    use crate::shell::state::AppState;
+    use crate::tests::fixtures::tags::make_test_app_state;
+
+    fn make_schema_from_state(
+        state: AppState,
+    ) -> Schema<QueryRoot, MutationRoot, EmptySubscription> {
+        Schema::build(
+            QueryRoot::default(),
+            MutationRoot::default(),
+            EmptySubscription,
+        )
+        .data(state)
+        .finish()
+    }
+
+    fn req_ctx(role: Role) -> RequestContext {
+        RequestContext {
+            user_id: "u-1".to_string(),
+            tenant_id: "tenant-test".to_string(),
+            role,
+            scope: Default::default(),
+        }
+    }
+
+    #[tokio::test]
+    async fn returns_the_callers_utilization() {
+        let state = make_test_app_state();
+        state
+            .set_contract_handler
+            .handle(SetContract {
+                user_id: "u-1".to_string(),
+                minutes_per_week: 2400,
+                start_date: "2026-01-01".parse().unwrap(),
+                set_at: 0,
+                set_by: "admin-1".to_string(),
+            })
+            .await
+            .unwrap();
+        let schema = make_schema_from_state(state);
+        let result = schema
+            .execute(
+                async_graphql::Request::new(
+                    r#"{ utilization(from: "2026-12-21", to: "2026-12-27") { userId contractedMinutes registeredMinutes utilization } }"#,
+                )
+                .data(req_ctx(Role::Employee)),
+            )
+            .await;
+        assert!(result.errors.is_empty(), "{:?}", result.errors);
+        assert_eq!(
+            result.data.to_string(),
+            "{utilization: {userId: \"u-1\", contractedMinutes: 2400, registeredMinutes: 0, utilization: 0.0}}"
+        );
+    }
+
+    #[tokio::test]
+    async fn returns_errors_for_forbidden_or_invalid_requests() {
+        let schema = make_schema_from_state(make_test_app_state());
+        for (query, message) in [
+            (
+                r#"{ utilization(userId: "u-2", from: "2026-12-21", to: "2026-12-27") { userId } }"#,
+                "Forbidden",
+            ),
+            (
+                r#"{ utilization(from: "21-12-2026", to: "2026-12-27") { userId } }"#,
+                "from and to must be YYYY-MM-DD",
+            ),
+            (
+                r#"{ utilization(from: "2026-12-27", to: "2026-12-21") { userId } }"#,
+                "to must not be before from, and the range at most 366 days",
+            ),
+        ] {
+            let result = schema
+                .execute(async_graphql::Request::new(query).data(req_ctx(Role::Employee)))
+                .await;
+            assert_eq!(result.errors[0].message, message);
+        }
+    }
+}
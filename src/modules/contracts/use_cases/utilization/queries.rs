@@ -0,0 +1,229 @@
+use chrono::NaiveDate;
+
+use crate::modules::contracts::core::events::ContractEvent;
+use crate::modules::contracts::use_cases::set_contract::handler::load_contracts;
+use crate::modules::time_entries::use_cases::hours_balance::queries::{
+    registered_minutes, working_days,
+};
+use crate::modules::time_entries::use_cases::list_time_entries::projection::ListTimeEntriesState;
+use crate::modules::time_entries::use_cases::user_time_entries::sharded_handler::SharedCalendar;
+use crate::shared::infrastructure::event_store::EventStore;
+use crate::shared::infrastructure::projection_store::ProjectionStore;
+
+pub const WORKING_DAYS_PER_WEEK: i64 = 5;
+
+/// Registered versus contracted time of one user over a date range.
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+pub struct Utilization {
+    pub user_id: String,
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+    /// Each working day outside holidays and absences counts a fifth of the weekly hours of
+    /// the contract in effect that day; days before the first contract count nothing.
+    pub contracted_minutes: i64,
+    pub registered_minutes: i64,
+    /// `registered / contracted`; `None` without contracted time.
+    pub utilization: Option<f64>,
+}
+
+#[derive(Clone)]
+pub struct UtilizationQueryHandler<TContracts, TStore>
+where
+    TContracts: EventStore<ContractEvent> + Send + Sync + 'static,
+    TStore: ProjectionStore<ListTimeEntriesState> + Send + Sync + 'static,
+{
+    contracts: TContracts,
+    store: TStore,
+    calendar: SharedCalendar,
+}
+
+impl<TContracts, TStore> UtilizationQueryHandler<TContracts, TStore>
+where
+    TContracts: EventStore<ContractEvent> + Send + Sync + 'static,
+    TStore: ProjectionStore<ListTimeEntriesState> + Send + Sync + 'static,
+{
+    pub fn new(contracts: TContracts, store: TStore, calendar: SharedCalendar) -> Self {
+        Self {
+            contracts,
+            store,
+            calendar,
+        }
+    }
+
+    /// `from` and `to` are inclusive UTC dates.
+    pub async fn utilization(
+        &self,
+        user_id: &str,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> anyhow::Result<Utilization> {
+        let contracts = load_contracts(&self.contracts, user_id).await?;
+        let days_off = self.calendar.days_off(user_id, from, to).await?;
+        let state = self.store.state().await?.unwrap_or_default();
+
+        let contracted_minutes = working_days(from, to, &days_off)
+            .into_iter()
+            .filter_map(|date| contracts.minutes_per_week_on(date))
+            .map(|minutes_per_week| minutes_per_week / WORKING_DAYS_PER_WEEK)
+            .sum();
+        let registered_minutes = registered_minutes(&state, user_id, from, to);
+
+        Ok(Utilization {
+            user_id: user_id.to_string(),
+            from,
+            to,
+            contracted_minutes,
+            registered_minutes,
+            utilization: (contracted_minutes > 0)
+                .then(|| registered_minutes as f64 / contracted_minutes as f64),
+        })
+    }
+}
+
+#[cfg(test)]
+mod utilization_query_handler_tests {
+    use super::*;
+    use crate::modules::contracts::core::events::v1::contract_set::ContractSetV1;
+    use crate::modules::time_entries::use_cases::list_time_entries::projection::{
+        TimeEntryRow, TimeEntryStatus,
+    };
+    use crate::shared::infrastructure::calendar::static_config::StaticCalendar;
+    use crate::shared::infrastructure::event_store::in_memory::InMemoryEventStore;
+    use crate::shared::infrastructure::projection_store::in_memory::InMemoryProjectionStore;
+    use rstest::rstest;
+    use std::sync::Arc;
+
+    fn date(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 12, day).unwrap()
+    }
+
+    async fn contracts(contracts: &[(u32, i64)]) -> InMemoryEventStore<ContractEvent> {
+        let store = InMemoryEventStore::<ContractEvent>::new();
+        let events: Vec<ContractEvent> = contracts
+            .iter()
+            .map(|(day, minutes_per_week)| {
+                ContractEvent::ContractSetV1(ContractSetV1 {
+                    user_id: "u-1".to_string(),
+                    minutes_per_week: *minutes_per_week,
+                    start_date: date(*day),
+                    set_at: 0,
+                    set_by: "admin-1".to_string(),
+                })
+            })
+            .collect();
+        store.append("Contract-u-1", 0, &events).await.unwrap();
+        store
+    }
+
+    async fn store_with_hours(hours: i64) -> InMemoryProjectionStore<ListTimeEntriesState> {
+        let store = InMemoryProjectionStore::<ListTimeEntriesState>::new();
+        let started_at = date(21)
+            .and_hms_opt(9, 0, 0)
+            .unwrap()
+            .and_utc()
+            .timestamp_millis();
+        let mut state = ListTimeEntriesState::default();
+        state.rows.insert(
+            "te-1".to_string(),
+            TimeEntryRow {
+                time_entry_id: "te-1".to_string(),
+                user_id: "u-1".to_string(),
+                started_at: Some(started_at),
+                ended_at: Some(started_at + hours * 3_600_000),
+                tag_ids: vec![],
+                status: TimeEntryStatus::Registered,
+                created_at: 0,
+                created_by: "u-1".to_string(),
+                updated_at: 0,
+                updated_by: "u-1".to_string(),
+                deleted_at: None,
+                hourly_rate: None,
+                last_event_id: None,
+                breaks: vec![],
+            },
+        );
+        store.save(state, 1).await.unwrap();
+        store
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_prorate_contracts_over_working_days() {
+        // 40h/week until Wednesday, 20h/week from Wednesday on; Friday is Christmas.
+        let calendar =
+            StaticCalendar::from_json(r#"{"holidays":[{"date":"2026-12-25","name":"Christmas"}]}"#)
+                .unwrap();
+        let handler = UtilizationQueryHandler::new(
+            contracts(&[(1, 2400), (23, 1200)]).await,
+            store_with_hours(12).await,
+            Arc::new(calendar),
+        );
+
+        let utilization = handler
+            .utilization("u-1", date(21), date(27))
+            .await
+            .unwrap();
+
+        assert_eq!(
+            utilization,
+            Utilization {
+                user_id: "u-1".to_string(),
+                from: date(21),
+                to: date(27),
+                contracted_minutes: 2 * 480 + 2 * 240,
+                registered_minutes: 12 * 60,
+                utilization: Some(0.5),
+            }
+        );
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_not_count_days_before_the_first_contract() {
+        let handler = UtilizationQueryHandler::new(
+            contracts(&[(28, 2400)]).await,
+            store_with_hours(8).await,
+            Arc::new(StaticCalendar::default()),
+        );
+
+        let utilization = handler
+            .utilization("u-1", date(21), date(27))
+            .await
+            .unwrap();
+
+        assert_eq!(utilization.contracted_minutes, 0);
+        assert_eq!(utilization.utilization, None);
+    }
+
+    #[rstest]
+    #[case::contracts(true, false, false)]
+    #[case::calendar(false, true, false)]
+    #[case::store(false, false, true)]
+    #[tokio::test]
+    async fn it_should_fail_when_a_source_is_offline(
+        #[case] contracts_offline: bool,
+        #[case] calendar_offline: bool,
+        #[case] store_offline: bool,
+    ) {
+        let contract_store = InMemoryEventStore::<ContractEvent>::new();
+        if contracts_offline {
+            contract_store.toggle_offline();
+        }
+        let calendar = StaticCalendar::default();
+        if calendar_offline {
+            calendar.toggle_offline();
+        }
+        let mut store = InMemoryProjectionStore::<ListTimeEntriesState>::new();
+        if store_offline {
+            store.toggle_offline();
+        }
+        let handler = UtilizationQueryHandler::new(contract_store, store, Arc::new(calendar));
+
+        assert!(
+            handler
+                .utilization("u-1", date(21), date(27))
+                .await
+                .is_err()
+        );
+    }
+}
@@ -0,0 +1,34 @@
+use crate::shared::core::domain_event::DomainEvent;
+
+pub mod v1 {
+    pub mod occurrence_materialized;
+    pub mod occurrence_skipped;
+    pub mod schedule_cancelled;
+    pub mod schedule_defined;
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, PartialEq, Eq, DomainEvent)]
+#[serde(tag = "type")]
+pub enum ScheduleEvent {
+    ScheduleDefinedV1(v1::schedule_defined::ScheduleDefinedV1),
+    ScheduleCancelledV1(v1::schedule_cancelled::ScheduleCancelledV1),
+    OccurrenceMaterializedV1(v1::occurrence_materialized::OccurrenceMaterializedV1),
+    OccurrenceSkippedV1(v1::occurrence_skipped::OccurrenceSkippedV1),
+}
+
+#[cfg(test)]
+mod schedule_event_schema_tests {
+    use super::*;
+    use crate::tests::golden::{assert_catalog, assert_event_goldens};
+    use rstest::rstest;
+
+    #[rstest]
+    fn every_version_should_match_its_golden() {
+        assert_event_goldens::<ScheduleEvent>("schedules");
+    }
+
+    #[rstest]
+    fn the_catalog_should_list_every_version() {
+        assert_catalog::<ScheduleEvent>();
+    }
+}
@@ -0,0 +1,46 @@
+use chrono::NaiveDate;
+
+/// The occurrence on `date` registered the entry `time_entry_id`.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, PartialEq, Eq)]
+pub struct OccurrenceMaterializedV1 {
+    pub schedule_id: String,
+    pub date: NaiveDate,
+    pub time_entry_id: String,
+    pub started_at: i64,
+    pub ended_at: i64,
+    pub materialized_at: i64,
+}
+
+#[cfg(test)]
+mod occurrence_materialized_event_tests {
+    use super::*;
+    use rstest::{fixture, rstest};
+
+    #[fixture]
+    fn event() -> OccurrenceMaterializedV1 {
+        OccurrenceMaterializedV1 {
+            schedule_id: "schedule-fixed-0001".to_string(),
+            date: NaiveDate::from_ymd_opt(2023, 11, 14).unwrap(),
+            time_entry_id: "te-fixed-0001".to_string(),
+            started_at: 1699952400000,
+            ended_at: 1699953300000,
+            materialized_at: 1700000000000,
+        }
+    }
+
+    #[rstest]
+    fn it_should_have_correct_fields(event: OccurrenceMaterializedV1) {
+        assert_eq!(event.schedule_id, "schedule-fixed-0001");
+        assert_eq!(event.time_entry_id, "te-fixed-0001");
+        assert_eq!(event.ended_at - event.started_at, 900_000);
+    }
+
+    #[rstest]
+    fn it_serializes_stable(event: OccurrenceMaterializedV1) {
+        let golden: serde_json::Value = serde_json::from_str(include_str!(
+            "../../../../../tests/fixtures/events/json/occurrence_materialized_v1.json"
+        ))
+        .unwrap();
+        assert_eq!(serde_json::to_value(&event).unwrap(), golden);
+    }
+}
@@ -0,0 +1,42 @@
+use chrono::NaiveDate;
+
+/// The occurrence on `date` registered nothing: the user had already logged
+/// `conflicting_time_entry_id` over the time it would take.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, PartialEq, Eq)]
+pub struct OccurrenceSkippedV1 {
+    pub schedule_id: String,
+    pub date: NaiveDate,
+    pub conflicting_time_entry_id: String,
+    pub skipped_at: i64,
+}
+
+#[cfg(test)]
+mod occurrence_skipped_event_tests {
+    use super::*;
+    use rstest::{fixture, rstest};
+
+    #[fixture]
+    fn event() -> OccurrenceSkippedV1 {
+        OccurrenceSkippedV1 {
+            schedule_id: "schedule-fixed-0001".to_string(),
+            date: NaiveDate::from_ymd_opt(2023, 11, 14).unwrap(),
+            conflicting_time_entry_id: "te-fixed-0001".to_string(),
+            skipped_at: 1700000000000,
+        }
+    }
+
+    #[rstest]
+    fn it_should_have_correct_fields(event: OccurrenceSkippedV1) {
+        assert_eq!(event.schedule_id, "schedule-fixed-0001");
+        assert_eq!(event.conflicting_time_entry_id, "te-fixed-0001");
+    }
+
+    #[rstest]
+    fn it_serializes_stable(event: OccurrenceSkippedV1) {
+        let golden: serde_json::Value = serde_json::from_str(include_str!(
+            "../../../../../tests/fixtures/events/json/occurrence_skipped_v1.json"
+        ))
+        .unwrap();
+        assert_eq!(serde_json::to_value(&event).unwrap(), golden);
+    }
+}
@@ -0,0 +1,37 @@
+/// The schedule stops occurring. Entries it already registered stay.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, PartialEq, Eq)]
+pub struct ScheduleCancelledV1 {
+    pub schedule_id: String,
+    pub cancelled_at: i64,
+    pub cancelled_by: String,
+}
+
+#[cfg(test)]
+mod schedule_cancelled_event_tests {
+    use super::*;
+    use rstest::{fixture, rstest};
+
+    #[fixture]
+    fn event() -> ScheduleCancelledV1 {
+        ScheduleCancelledV1 {
+            schedule_id: "schedule-fixed-0001".to_string(),
+            cancelled_at: 1700000000000,
+            cancelled_by: "user-fixed-0001".to_string(),
+        }
+    }
+
+    #[rstest]
+    fn it_should_have_correct_fields(event: ScheduleCancelledV1) {
+        assert_eq!(event.schedule_id, "schedule-fixed-0001");
+        assert_eq!(event.cancelled_by, "user-fixed-0001");
+    }
+
+    #[rstest]
+    fn it_serializes_stable(event: ScheduleCancelledV1) {
+        let golden: serde_json::Value = serde_json::from_str(include_str!(
+            "../../../../../tests/fixtures/events/json/schedule_cancelled_v1.json"
+        ))
+        .unwrap();
+        assert_eq!(serde_json::to_value(&event).unwrap(), golden);
+    }
+}
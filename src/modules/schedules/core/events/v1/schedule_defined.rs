@@ -0,0 +1,55 @@
+use crate::modules::schedules::core::recurrence::RecurrenceRule;
+use crate::modules::time_entries::core::tag::Tag;
+use chrono::{NaiveDate, NaiveTime};
+
+/// A user's recurring entry: from `starts_on`, on every day `rule` occurs, an entry starting
+/// at `start_time` (UTC) lasting `duration_ms` with `tags`.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, PartialEq, Eq)]
+pub struct ScheduleDefinedV1 {
+    pub schedule_id: String,
+    pub tenant_id: String,
+    pub user_id: String,
+    pub rule: RecurrenceRule,
+    pub starts_on: NaiveDate,
+    pub start_time: NaiveTime,
+    pub duration_ms: i64,
+    pub tags: Vec<Tag>,
+    pub defined_at: i64,
+}
+
+#[cfg(test)]
+mod schedule_defined_event_tests {
+    use super::*;
+    use rstest::{fixture, rstest};
+
+    #[fixture]
+    fn event() -> ScheduleDefinedV1 {
+        ScheduleDefinedV1 {
+            schedule_id: "schedule-fixed-0001".to_string(),
+            tenant_id: "tenant-hardcoded".to_string(),
+            user_id: "user-fixed-0001".to_string(),
+            rule: "FREQ=WEEKLY;BYDAY=MO,TU,WE,TH,FR".parse().unwrap(),
+            starts_on: NaiveDate::from_ymd_opt(2023, 11, 14).unwrap(),
+            start_time: NaiveTime::from_hms_opt(9, 0, 0).unwrap(),
+            duration_ms: 900_000,
+            tags: vec![Tag::parse("meeting").unwrap()],
+            defined_at: 1700000000000,
+        }
+    }
+
+    #[rstest]
+    fn it_should_have_correct_fields(event: ScheduleDefinedV1) {
+        assert_eq!(event.schedule_id, "schedule-fixed-0001");
+        assert_eq!(event.rule.by_day.len(), 5);
+        assert_eq!(event.duration_ms, 900_000);
+    }
+
+    #[rstest]
+    fn it_serializes_stable(event: ScheduleDefinedV1) {
+        let golden: serde_json::Value = serde_json::from_str(include_str!(
+            "../../../../../tests/fixtures/events/json/schedule_defined_v1.json"
+        ))
+        .unwrap();
+        assert_eq!(serde_json::to_value(&event).unwrap(), golden);
+    }
+}
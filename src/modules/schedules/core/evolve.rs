@@ -0,0 +1,131 @@
+use crate::modules::schedules::core::events::ScheduleEvent;
+use crate::modules::schedules::core::state::ScheduleState;
+use std::collections::BTreeSet;
+
+pub fn evolve(state: ScheduleState, event: ScheduleEvent) -> ScheduleState {
+    match (state, event) {
+        (ScheduleState::None, ScheduleEvent::ScheduleDefinedV1(e)) => ScheduleState::Active {
+            schedule_id: e.schedule_id,
+            tenant_id: e.tenant_id,
+            user_id: e.user_id,
+            rule: e.rule,
+            starts_on: e.starts_on,
+            start_time: e.start_time,
+            duration_ms: e.duration_ms,
+            tags: e.tags,
+            recorded: BTreeSet::new(),
+        },
+        (
+            ScheduleState::Active {
+                schedule_id,
+                tenant_id,
+                user_id,
+                ..
+            },
+            ScheduleEvent::ScheduleCancelledV1(_),
+        ) => ScheduleState::Cancelled {
+            schedule_id,
+            tenant_id,
+            user_id,
+        },
+        (mut state, ScheduleEvent::OccurrenceMaterializedV1(e)) => {
+            record(&mut state, e.date);
+            state
+        }
+        (mut state, ScheduleEvent::OccurrenceSkippedV1(e)) => {
+            record(&mut state, e.date);
+            state
+        }
+        (state, _) => state,
+    }
+}
+
+fn record(state: &mut ScheduleState, date: chrono::NaiveDate) {
+    if let ScheduleState::Active { recorded, .. } = state {
+        recorded.insert(date);
+    }
+}
+
+#[cfg(test)]
+mod schedule_evolve_tests {
+    use super::*;
+    use crate::modules::schedules::core::events::v1::occurrence_materialized::OccurrenceMaterializedV1;
+    use crate::modules::schedules::core::events::v1::occurrence_skipped::OccurrenceSkippedV1;
+    use crate::modules::schedules::core::events::v1::schedule_cancelled::ScheduleCancelledV1;
+    use crate::modules::schedules::core::events::v1::schedule_defined::ScheduleDefinedV1;
+    use chrono::{NaiveDate, NaiveTime};
+    use rstest::rstest;
+
+    fn date(value: &str) -> NaiveDate {
+        value.parse().unwrap()
+    }
+
+    fn defined() -> ScheduleEvent {
+        ScheduleEvent::ScheduleDefinedV1(ScheduleDefinedV1 {
+            schedule_id: "sch-1".to_string(),
+            tenant_id: "ten1".to_string(),
+            user_id: "u1".to_string(),
+            rule: "FREQ=DAILY".parse().unwrap(),
+            starts_on: date("2026-03-02"),
+            start_time: NaiveTime::from_hms_opt(9, 0, 0).unwrap(),
+            duration_ms: 900_000,
+            tags: vec![],
+            defined_at: 1000,
+        })
+    }
+
+    #[rstest]
+    fn it_should_record_materialized_and_skipped_dates() {
+        let state = [
+            defined(),
+            ScheduleEvent::OccurrenceMaterializedV1(OccurrenceMaterializedV1 {
+                schedule_id: "sch-1".to_string(),
+                date: date("2026-03-02"),
+                time_entry_id: "te-1".to_string(),
+                started_at: 0,
+                ended_at: 900_000,
+                materialized_at: 1000,
+            }),
+            ScheduleEvent::OccurrenceSkippedV1(OccurrenceSkippedV1 {
+                schedule_id: "sch-1".to_string(),
+                date: date("2026-03-03"),
+                conflicting_time_entry_id: "te-2".to_string(),
+                skipped_at: 1000,
+            }),
+        ]
+        .into_iter()
+        .fold(ScheduleState::None, evolve);
+
+        let ScheduleState::Active { recorded, .. } = state else {
+            panic!("expected Active");
+        };
+        assert_eq!(
+            recorded,
+            BTreeSet::from([date("2026-03-02"), date("2026-03-03")])
+        );
+    }
+
+    #[rstest]
+    fn a_cancelled_schedule_should_stay_cancelled() {
+        let state = [
+            defined(),
+            ScheduleEvent::ScheduleCancelledV1(ScheduleCancelledV1 {
+                schedule_id: "sch-1".to_string(),
+                cancelled_at: 2000,
+                cancelled_by: "u1".to_string(),
+            }),
+            defined(),
+        ]
+        .into_iter()
+        .fold(ScheduleState::None, evolve);
+
+        assert_eq!(
+            state,
+            ScheduleState::Cancelled {
+                schedule_id: "sch-1".to_string(),
+                tenant_id: "ten1".to_string(),
+                user_id: "u1".to_string(),
+            }
+        );
+    }
+}
@@ -0,0 +1,81 @@
+// Where an occurrence of a schedule falls, and the entry it registers.
+//
+// Each occurrence registers under an id derived from its schedule and date, so registering it
+// again, after a run that failed before recording it, addresses the entry it already started
+// rather than a second one. The id is a UUID v7 timestamped at the start of the occurrence,
+// with the rest hashed (FNV-1a, stable across builds) from the schedule and date.
+
+use crate::shared::core::primitives::TimeEntryId;
+use chrono::{NaiveDate, NaiveTime};
+
+/// When the occurrence on `date` starts and ends, in milliseconds since the epoch. Start
+/// times are in UTC.
+pub fn occurrence_interval(date: NaiveDate, start_time: NaiveTime, duration_ms: i64) -> (i64, i64) {
+    let started_at = date.and_time(start_time).and_utc().timestamp_millis();
+    (started_at, started_at + duration_ms)
+}
+
+pub fn occurrence_time_entry_id(
+    schedule_id: &str,
+    date: NaiveDate,
+    started_at: i64,
+) -> TimeEntryId {
+    let key = format!("{schedule_id}/{date}");
+    let mut random = [0u8; 10];
+    random[..8].copy_from_slice(&fnv1a(0, &key).to_be_bytes());
+    random[8..].copy_from_slice(&fnv1a(1, &key).to_be_bytes()[..2]);
+    let uuid =
+        uuid::Builder::from_unix_timestamp_millis(started_at.max(0) as u64, &random).into_uuid();
+    TimeEntryId::from(uuid.to_string())
+}
+
+fn fnv1a(seed: u8, value: &str) -> u64 {
+    const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0100_0000_01b3;
+    std::iter::once(seed)
+        .chain(value.bytes())
+        .fold(OFFSET_BASIS, |hash, byte| {
+            (hash ^ u64::from(byte)).wrapping_mul(PRIME)
+        })
+}
+
+#[cfg(test)]
+mod occurrence_tests {
+    use super::*;
+    use rstest::rstest;
+
+    fn date(value: &str) -> NaiveDate {
+        value.parse().unwrap()
+    }
+
+    #[rstest]
+    fn it_should_start_on_the_date_at_the_start_time_in_utc() {
+        let (started_at, ended_at) = occurrence_interval(
+            date("2026-03-02"),
+            NaiveTime::from_hms_opt(9, 0, 0).unwrap(),
+            900_000,
+        );
+
+        // 2026-03-02T09:00:00Z
+        assert_eq!((started_at, ended_at), (1772442000000, 1772442900000));
+    }
+
+    #[rstest]
+    fn it_should_derive_one_v7_id_per_schedule_and_date() {
+        let id = occurrence_time_entry_id("sch-1", date("2026-03-02"), 1772442000000);
+
+        assert!(TimeEntryId::parse_v7(id.as_str()).is_ok());
+        assert_eq!(
+            id,
+            occurrence_time_entry_id("sch-1", date("2026-03-02"), 1772442000000)
+        );
+        assert_ne!(
+            id,
+            occurrence_time_entry_id("sch-1", date("2026-03-03"), 1772442000000)
+        );
+        assert_ne!(
+            id,
+            occurrence_time_entry_id("sch-2", date("2026-03-02"), 1772442000000)
+        );
+    }
+}
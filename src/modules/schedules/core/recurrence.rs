@@ -0,0 +1,258 @@
+// A subset of iCalendar's RRULE, enough for recurring work: `FREQ=DAILY` or `FREQ=WEEKLY`,
+// with an optional `INTERVAL`, `BYDAY` and `UNTIL`, e.g. `FREQ=WEEKLY;BYDAY=MO,WE;UNTIL=20261231`.
+// Rules are counted from the day their schedule starts, which plays the part of DTSTART.
+// Weekly intervals count calendar weeks, Monday to Sunday, as RRULE does by default.
+
+use chrono::{Datelike, NaiveDate, Weekday};
+use std::fmt;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Frequency {
+    Daily,
+    Weekly,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct RecurrenceRule {
+    pub frequency: Frequency,
+    /// Every how many days or weeks; at least 1.
+    pub interval: u32,
+    /// The weekdays it occurs on, Monday first. Empty for every day of a daily rule, and for
+    /// the weekday the schedule starts on of a weekly one.
+    pub by_day: Vec<Weekday>,
+    /// The last day it may occur on, inclusive.
+    pub until: Option<NaiveDate>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum RecurrenceError {
+    #[error("recurrence rule needs a FREQ")]
+    MissingFrequency,
+
+    #[error("unsupported FREQ: {0}")]
+    UnsupportedFrequency(String),
+
+    #[error("INTERVAL must be a whole number of at least 1: {0}")]
+    InvalidInterval(String),
+
+    #[error("unknown weekday in BYDAY: {0}")]
+    UnknownWeekday(String),
+
+    #[error("UNTIL must be a date as YYYYMMDD: {0}")]
+    InvalidUntil(String),
+
+    #[error("unsupported recurrence rule part: {0}")]
+    UnsupportedPart(String),
+}
+
+impl RecurrenceRule {
+    /// Whether a schedule starting on `starts_on` occurs on `date`.
+    pub fn occurs_on(&self, starts_on: NaiveDate, date: NaiveDate) -> bool {
+        if date < starts_on || self.until.is_some_and(|until| date > until) {
+            return false;
+        }
+        let interval = i64::from(self.interval.max(1));
+        match self.frequency {
+            Frequency::Daily => {
+                (date - starts_on).num_days() % interval == 0
+                    && (self.by_day.is_empty() || self.by_day.contains(&date.weekday()))
+            }
+            Frequency::Weekly => {
+                let on_weekday = if self.by_day.is_empty() {
+                    date.weekday() == starts_on.weekday()
+                } else {
+                    self.by_day.contains(&date.weekday())
+                };
+                let weeks = (week_start(date) - week_start(starts_on)).num_weeks();
+                on_weekday && weeks % interval == 0
+            }
+        }
+    }
+}
+
+fn week_start(date: NaiveDate) -> NaiveDate {
+    date - chrono::Days::new(u64::from(date.weekday().num_days_from_monday()))
+}
+
+const WEEKDAYS: [(&str, Weekday); 7] = [
+    ("MO", Weekday::Mon),
+    ("TU", Weekday::Tue),
+    ("WE", Weekday::Wed),
+    ("TH", Weekday::Thu),
+    ("FR", Weekday::Fri),
+    ("SA", Weekday::Sat),
+    ("SU", Weekday::Sun),
+];
+
+impl FromStr for RecurrenceRule {
+    type Err = RecurrenceError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let mut frequency = None;
+        let mut interval = 1;
+        let mut by_day = Vec::new();
+        let mut until = None;
+        for part in value.trim().split(';').filter(|part| !part.is_empty()) {
+            let (key, value) = part
+                .split_once('=')
+                .ok_or_else(|| RecurrenceError::UnsupportedPart(part.to_string()))?;
+            match key.to_ascii_uppercase().as_str() {
+                "FREQ" => {
+                    frequency = Some(match value.to_ascii_uppercase().as_str() {
+                        "DAILY" => Frequency::Daily,
+                        "WEEKLY" => Frequency::Weekly,
+                        _ => return Err(RecurrenceError::UnsupportedFrequency(value.to_string())),
+                    })
+                }
+                "INTERVAL" => {
+                    interval = value
+                        .parse()
+                        .ok()
+                        .filter(|interval| *interval >= 1)
+                        .ok_or_else(|| RecurrenceError::InvalidInterval(value.to_string()))?
+                }
+                "BYDAY" => {
+                    for day in value.split(',') {
+                        let weekday = WEEKDAYS
+                            .iter()
+                            .find(|(code, _)| code.eq_ignore_ascii_case(day.trim()))
+                            .map(|(_, weekday)| *weekday)
+                            .ok_or_else(|| RecurrenceError::UnknownWeekday(day.to_string()))?;
+                        by_day.push(weekday);
+                    }
+                }
+                "UNTIL" => {
+                    until = Some(
+                        NaiveDate::parse_from_str(value, "%Y%m%d")
+                            .map_err(|_| RecurrenceError::InvalidUntil(value.to_string()))?,
+                    )
+                }
+                _ => return Err(RecurrenceError::UnsupportedPart(part.to_string())),
+            }
+        }
+        by_day.sort_by_key(Weekday::num_days_from_monday);
+        by_day.dedup();
+        Ok(Self {
+            frequency: frequency.ok_or(RecurrenceError::MissingFrequency)?,
+            interval,
+            by_day,
+            until,
+        })
+    }
+}
+
+impl fmt::Display for RecurrenceRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let frequency = match self.frequency {
+            Frequency::Daily => "DAILY",
+            Frequency::Weekly => "WEEKLY",
+        };
+        write!(f, "FREQ={frequency};INTERVAL={}", self.interval)?;
+        if !self.by_day.is_empty() {
+            let days: Vec<&str> = self
+                .by_day
+                .iter()
+                .filter_map(|weekday| {
+                    WEEKDAYS
+                        .iter()
+                        .find(|(_, day)| day == weekday)
+                        .map(|(code, _)| *code)
+                })
+                .collect();
+            write!(f, ";BYDAY={}", days.join(","))?;
+        }
+        if let Some(until) = self.until {
+            write!(f, ";UNTIL={}", until.format("%Y%m%d"))?;
+        }
+        Ok(())
+    }
+}
+
+impl TryFrom<String> for RecurrenceRule {
+    type Error = RecurrenceError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl From<RecurrenceRule> for String {
+    fn from(rule: RecurrenceRule) -> Self {
+        rule.to_string()
+    }
+}
+
+#[cfg(test)]
+mod recurrence_rule_tests {
+    use super::*;
+    use rstest::rstest;
+
+    fn date(value: &str) -> NaiveDate {
+        value.parse().unwrap()
+    }
+
+    fn rule(value: &str) -> RecurrenceRule {
+        value.parse().unwrap()
+    }
+
+    #[rstest]
+    #[case::daily("FREQ=DAILY", "FREQ=DAILY;INTERVAL=1")]
+    #[case::lowercase("freq=weekly;byday=fr,mo,mo", "FREQ=WEEKLY;INTERVAL=1;BYDAY=MO,FR")]
+    #[case::until(
+        "FREQ=WEEKLY;INTERVAL=2;UNTIL=20261231",
+        "FREQ=WEEKLY;INTERVAL=2;UNTIL=20261231"
+    )]
+    fn it_should_parse_and_normalize_rules(#[case] value: &str, #[case] normalized: &str) {
+        assert_eq!(rule(value).to_string(), normalized);
+    }
+
+    #[rstest]
+    #[case::empty("", RecurrenceError::MissingFrequency)]
+    #[case::monthly("FREQ=MONTHLY", RecurrenceError::UnsupportedFrequency("MONTHLY".into()))]
+    #[case::zero_interval("FREQ=DAILY;INTERVAL=0", RecurrenceError::InvalidInterval("0".into()))]
+    #[case::weekday("FREQ=WEEKLY;BYDAY=XX", RecurrenceError::UnknownWeekday("XX".into()))]
+    #[case::until("FREQ=DAILY;UNTIL=2026-12-31", RecurrenceError::InvalidUntil("2026-12-31".into()))]
+    #[case::count("FREQ=DAILY;COUNT=3", RecurrenceError::UnsupportedPart("COUNT=3".into()))]
+    fn it_should_reject_rules_it_cannot_expand(
+        #[case] value: &str,
+        #[case] expected: RecurrenceError,
+    ) {
+        assert_eq!(value.parse::<RecurrenceRule>(), Err(expected));
+    }
+
+    #[rstest]
+    // 2026-03-02 is a Monday.
+    #[case::weekdays("FREQ=WEEKLY;BYDAY=MO,TU,WE,TH,FR", "2026-03-06", true)]
+    #[case::weekend("FREQ=WEEKLY;BYDAY=MO,TU,WE,TH,FR", "2026-03-07", false)]
+    #[case::start_weekday("FREQ=WEEKLY", "2026-03-09", true)]
+    #[case::other_weekday("FREQ=WEEKLY", "2026-03-10", false)]
+    #[case::off_week("FREQ=WEEKLY;INTERVAL=2;BYDAY=FR", "2026-03-13", false)]
+    #[case::on_week("FREQ=WEEKLY;INTERVAL=2;BYDAY=FR", "2026-03-20", true)]
+    #[case::every_third_day("FREQ=DAILY;INTERVAL=3", "2026-03-05", true)]
+    #[case::between_third_days("FREQ=DAILY;INTERVAL=3", "2026-03-04", false)]
+    #[case::before_start("FREQ=DAILY", "2026-03-01", false)]
+    #[case::on_until("FREQ=DAILY;UNTIL=20260310", "2026-03-10", true)]
+    #[case::after_until("FREQ=DAILY;UNTIL=20260310", "2026-03-11", false)]
+    fn it_should_tell_which_days_it_occurs_on(
+        #[case] value: &str,
+        #[case] day: &str,
+        #[case] expected: bool,
+    ) {
+        assert_eq!(
+            rule(value).occurs_on(date("2026-03-02"), date(day)),
+            expected
+        );
+    }
+
+    #[rstest]
+    fn it_should_serialize_as_its_rule_text() {
+        let value = serde_json::to_value(rule("FREQ=WEEKLY;BYDAY=MO")).unwrap();
+
+        assert_eq!(value, serde_json::json!("FREQ=WEEKLY;INTERVAL=1;BYDAY=MO"));
+        assert!(
+            serde_json::from_value::<RecurrenceRule>(serde_json::json!("FREQ=YEARLY")).is_err()
+        );
+    }
+}
@@ -0,0 +1,77 @@
+use crate::modules::schedules::core::recurrence::RecurrenceRule;
+use crate::modules::time_entries::core::tag::Tag;
+use chrono::{NaiveDate, NaiveTime};
+use std::collections::BTreeSet;
+
+pub fn schedule_stream_id(schedule_id: &str) -> String {
+    format!("Schedule-{schedule_id}")
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ScheduleState {
+    None,
+    Active {
+        schedule_id: String,
+        tenant_id: String,
+        user_id: String,
+        rule: RecurrenceRule,
+        starts_on: NaiveDate,
+        start_time: NaiveTime,
+        duration_ms: i64,
+        tags: Vec<Tag>,
+        /// Dates whose occurrence was materialized or skipped.
+        recorded: BTreeSet<NaiveDate>,
+    },
+    Cancelled {
+        schedule_id: String,
+        tenant_id: String,
+        user_id: String,
+    },
+}
+
+impl ScheduleState {
+    /// Whether the schedule still has to materialize or skip its occurrence on `date`.
+    pub fn is_due_on(&self, date: NaiveDate) -> bool {
+        match self {
+            ScheduleState::Active {
+                rule,
+                starts_on,
+                recorded,
+                ..
+            } => rule.occurs_on(*starts_on, date) && !recorded.contains(&date),
+            ScheduleState::None | ScheduleState::Cancelled { .. } => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod schedule_state_tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    fn it_should_name_the_stream_after_the_schedule() {
+        assert_eq!(schedule_stream_id("sch-1"), "Schedule-sch-1");
+    }
+
+    #[rstest]
+    fn it_should_be_due_on_occurrences_not_yet_recorded() {
+        let date = |value: &str| value.parse::<NaiveDate>().unwrap();
+        let state = ScheduleState::Active {
+            schedule_id: "sch-1".to_string(),
+            tenant_id: "ten1".to_string(),
+            user_id: "u1".to_string(),
+            rule: "FREQ=DAILY".parse().unwrap(),
+            starts_on: date("2026-03-02"),
+            start_time: NaiveTime::from_hms_opt(9, 0, 0).unwrap(),
+            duration_ms: 900_000,
+            tags: vec![],
+            recorded: BTreeSet::from([date("2026-03-02")]),
+        };
+
+        assert!(!state.is_due_on(date("2026-03-01")));
+        assert!(!state.is_due_on(date("2026-03-02")));
+        assert!(state.is_due_on(date("2026-03-03")));
+        assert!(!ScheduleState::None.is_due_on(date("2026-03-03")));
+    }
+}
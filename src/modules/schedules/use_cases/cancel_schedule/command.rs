@@ -0,0 +1,7 @@
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CancelSchedule {
+    pub schedule_id: String,
+    pub tenant_id: String,
+    pub user_id: String,
+    pub cancelled_at: i64,
+}
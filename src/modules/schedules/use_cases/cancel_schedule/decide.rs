@@ -0,0 +1,134 @@
+use crate::modules::schedules::core::events::ScheduleEvent;
+use crate::modules::schedules::core::events::v1::schedule_cancelled::ScheduleCancelledV1;
+use crate::modules::schedules::core::evolve::evolve;
+use crate::modules::schedules::core::state::ScheduleState;
+use crate::modules::schedules::use_cases::cancel_schedule::command::CancelSchedule;
+use crate::modules::schedules::use_cases::cancel_schedule::decision::{DecideError, Decision};
+use crate::shared::core::decider::Decider;
+use std::convert::Infallible;
+
+/// Cancels one of the user's own schedules.
+pub fn decide_cancel(state: &ScheduleState, command: CancelSchedule) -> Decision {
+    let (tenant_id, user_id, cancelled) = match state {
+        ScheduleState::None => {
+            return Decision::Rejected {
+                reason: DecideError::NotFound,
+            };
+        }
+        ScheduleState::Active {
+            tenant_id, user_id, ..
+        } => (tenant_id, user_id, false),
+        ScheduleState::Cancelled {
+            tenant_id, user_id, ..
+        } => (tenant_id, user_id, true),
+    };
+    if *tenant_id != command.tenant_id || *user_id != command.user_id {
+        return Decision::Rejected {
+            reason: DecideError::NotFound,
+        };
+    }
+    if cancelled {
+        return Decision::Rejected {
+            reason: DecideError::AlreadyCancelled,
+        };
+    }
+    Decision::Accepted {
+        events: vec![ScheduleEvent::ScheduleCancelledV1(ScheduleCancelledV1 {
+            schedule_id: command.schedule_id,
+            cancelled_at: command.cancelled_at,
+            cancelled_by: command.user_id,
+        })],
+        intents: vec![],
+    }
+}
+
+pub struct CancelScheduleDecider;
+
+impl Decider for CancelScheduleDecider {
+    type State = ScheduleState;
+    type Command = CancelSchedule;
+    type Event = ScheduleEvent;
+    type Intent = Infallible;
+    type Error = DecideError;
+
+    fn initial_state() -> ScheduleState {
+        ScheduleState::None
+    }
+
+    fn evolve(state: ScheduleState, event: ScheduleEvent) -> ScheduleState {
+        evolve(state, event)
+    }
+
+    fn decide(state: &ScheduleState, command: CancelSchedule) -> Decision {
+        decide_cancel(state, command)
+    }
+}
+
+#[cfg(test)]
+mod cancel_schedule_decide_tests {
+    use super::*;
+    use chrono::NaiveTime;
+    use rstest::rstest;
+    use std::collections::BTreeSet;
+
+    fn active() -> ScheduleState {
+        ScheduleState::Active {
+            schedule_id: "sch-1".to_string(),
+            tenant_id: "ten1".to_string(),
+            user_id: "u1".to_string(),
+            rule: "FREQ=DAILY".parse().unwrap(),
+            starts_on: "2026-03-02".parse().unwrap(),
+            start_time: NaiveTime::from_hms_opt(9, 0, 0).unwrap(),
+            duration_ms: 900_000,
+            tags: vec![],
+            recorded: BTreeSet::new(),
+        }
+    }
+
+    fn command(user_id: &str) -> CancelSchedule {
+        CancelSchedule {
+            schedule_id: "sch-1".to_string(),
+            tenant_id: "ten1".to_string(),
+            user_id: user_id.to_string(),
+            cancelled_at: 2000,
+        }
+    }
+
+    #[rstest]
+    fn it_should_cancel_an_active_schedule() {
+        assert_eq!(
+            decide_cancel(&active(), command("u1")),
+            Decision::Accepted {
+                events: vec![ScheduleEvent::ScheduleCancelledV1(ScheduleCancelledV1 {
+                    schedule_id: "sch-1".to_string(),
+                    cancelled_at: 2000,
+                    cancelled_by: "u1".to_string(),
+                })],
+                intents: vec![],
+            }
+        );
+    }
+
+    #[rstest]
+    #[case::missing(ScheduleState::None, "u1", DecideError::NotFound)]
+    #[case::other_user(active(), "u2", DecideError::NotFound)]
+    #[case::cancelled(
+        ScheduleState::Cancelled {
+            schedule_id: "sch-1".to_string(),
+            tenant_id: "ten1".to_string(),
+            user_id: "u1".to_string(),
+        },
+        "u1",
+        DecideError::AlreadyCancelled
+    )]
+    fn it_should_reject_cancelling_what_is_not_an_own_active_schedule(
+        #[case] state: ScheduleState,
+        #[case] user_id: &str,
+        #[case] expected: DecideError,
+    ) {
+        assert_eq!(
+            decide_cancel(&state, command(user_id)),
+            Decision::Rejected { reason: expected }
+        );
+    }
+}
@@ -0,0 +1,15 @@
+use crate::modules::schedules::core::events::ScheduleEvent;
+use crate::shared::core::decider;
+use std::convert::Infallible;
+
+#[derive(Debug, thiserror::Error, PartialEq, Eq)]
+pub enum DecideError {
+    /// Also for schedules of other users, which are not theirs to see.
+    #[error("schedule not found")]
+    NotFound,
+
+    #[error("schedule already cancelled")]
+    AlreadyCancelled,
+}
+
+pub type Decision = decider::Decision<ScheduleEvent, Infallible, DecideError>;
@@ -0,0 +1,43 @@
+use crate::modules::schedules::core::events::ScheduleEvent;
+use crate::modules::schedules::core::state::schedule_stream_id;
+use crate::modules::schedules::use_cases::cancel_schedule::command::CancelSchedule;
+use crate::modules::schedules::use_cases::cancel_schedule::decide::CancelScheduleDecider;
+use crate::modules::schedules::use_cases::cancel_schedule::decision::DecideError;
+use crate::shared::application::event_sourced_handler::{
+    EventSourcedError, EventSourcedHandler, NoIntents,
+};
+use crate::shared::infrastructure::event_bus::SharedEventBus;
+use crate::shared::infrastructure::event_store::EventStore;
+use std::convert::Infallible;
+
+pub type ApplicationError = EventSourcedError<DecideError, Infallible>;
+
+#[derive(Debug, Clone)]
+pub struct CancelScheduleHandler<TEventStore>
+where
+    TEventStore: EventStore<ScheduleEvent> + Send + Sync + 'static,
+{
+    inner: EventSourcedHandler<CancelScheduleDecider, TEventStore, NoIntents>,
+}
+
+impl<TEventStore> CancelScheduleHandler<TEventStore>
+where
+    TEventStore: EventStore<ScheduleEvent> + Send + Sync + 'static,
+{
+    pub fn new(event_store: TEventStore) -> Self {
+        Self {
+            inner: EventSourcedHandler::new(event_store, NoIntents),
+        }
+    }
+
+    /// Announce the appended events on `event_bus`, for the projector to pick up.
+    pub fn with_event_bus(mut self, event_bus: SharedEventBus<ScheduleEvent>) -> Self {
+        self.inner = self.inner.with_event_bus(event_bus);
+        self
+    }
+
+    pub async fn handle(&self, command: CancelSchedule) -> Result<(), ApplicationError> {
+        let stream_id = schedule_stream_id(&command.schedule_id);
+        self.inner.handle(&stream_id, command).await
+    }
+}
@@ -0,0 +1,114 @@
+use async_graphql::{Context, Object, Result as GqlResult};
+use chrono::Utc;
+
+use crate::modules::schedules::use_cases::cancel_schedule::command::CancelSchedule;
+use crate::shared::infrastructure::request_context::RequestContext;
+use crate::shell::state::AppState;
+
+#[derive(Default)]
+pub struct CancelScheduleMutation;
+
+#[Object]
+impl CancelScheduleMutation {
+    /// Stops one of the caller's schedules. Entries it registered already stay.
+    async fn cancel_schedule(&self, context: &Context<'_>, schedule_id: String) -> GqlResult<bool> {
+        let req_ctx = context
+            .data::<RequestContext>()
+            .map_err(|_| async_graphql::Error::new("Unauthorized"))?;
+        if !req_ctx.principal().can_register_for(&req_ctx.user_id) {
+            return Err(async_graphql::Error::new("Forbidden"));
+        }
+        let state = context.data_unchecked::<AppState>();
+        let command = CancelSchedule {
+            schedule_id,
+            tenant_id: req_ctx.tenant_id.clone(),
+            user_id: req_ctx.user_id.clone(),
+            cancelled_at: Utc::now().timestamp_millis(),
+        };
+
+        state
+            .cancel_schedule_handler
+            .handle(command)
+            .await
+            .map_err(|e| async_graphql::Error::new(e.to_string()))?;
+
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+mod cancel_schedule_graphql_inbound_tests {
+    use async_graphql::{EmptySubscription, Schema};
+    use chrono::NaiveTime;
+
+    use crate::modules::schedules::use_cases::define_schedule::command::DefineSchedule;
+    use crate::shared::infrastructure::request_context::RequestContext;
+    use crate::shell::graphql::{MutationRoot, QueryRoot};
+    use crate::shell::state::AppState;
+    use crate::tests::fixtures::tags::make_test_app_state;
+
+    fn make_schema_from_state(
+        state: AppState,
+    ) -> Schema<QueryRoot, MutationRoot, EmptySubscription> {
+        Schema::build(
+            QueryRoot::default(),
+            MutationRoot::default(),
+            EmptySubscription,
+        )
+        .data(state)
+        .finish()
+    }
+
+    fn req_ctx(user_id: &str) -> RequestContext {
+        RequestContext {
+            user_id: user_id.to_string(),
+            tenant_id: "tenant-test".to_string(),
+            role: Default::default(),
+            scope: Default::default(),
+        }
+    }
+
+    const CANCEL: &str = r#"mutation { cancelSchedule(scheduleId: "sch-1") }"#;
+
+    #[tokio::test]
+    async fn cancels_only_the_callers_own_schedule_once() {
+        let state = make_test_app_state();
+        state
+            .define_schedule_handler
+            .handle(DefineSchedule {
+                schedule_id: "sch-1".to_string(),
+                tenant_id: "tenant-test".to_string(),
+                user_id: "u-1".to_string(),
+                rule: "FREQ=DAILY".parse().unwrap(),
+                starts_on: "2026-03-02".parse().unwrap(),
+                start_time: NaiveTime::from_hms_opt(9, 0, 0).unwrap(),
+                duration_ms: 900_000,
+                tags: vec![],
+                defined_at: 0,
+            })
+            .await
+            .unwrap();
+        let schema = make_schema_from_state(state);
+
+        let other = schema
+            .execute(async_graphql::Request::new(CANCEL).data(req_ctx("u-2")))
+            .await;
+        let own = schema
+            .execute(async_graphql::Request::new(CANCEL).data(req_ctx("u-1")))
+            .await;
+        let again = schema
+            .execute(async_graphql::Request::new(CANCEL).data(req_ctx("u-1")))
+            .await;
+
+        assert_eq!(
+            other.errors[0].message,
+            "domain rejected: schedule not found"
+        );
+        assert!(own.errors.is_empty(), "{:?}", own.errors);
+        assert_eq!(own.data.to_string(), "{cancelSchedule: true}");
+        assert_eq!(
+            again.errors[0].message,
+            "domain rejected: schedule already cancelled"
+        );
+    }
+}
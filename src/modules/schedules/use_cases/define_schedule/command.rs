@@ -0,0 +1,16 @@
+use crate::modules::schedules::core::recurrence::RecurrenceRule;
+use crate::modules::time_entries::core::tag::Tag;
+use chrono::{NaiveDate, NaiveTime};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DefineSchedule {
+    pub schedule_id: String,
+    pub tenant_id: String,
+    pub user_id: String,
+    pub rule: RecurrenceRule,
+    pub starts_on: NaiveDate,
+    pub start_time: NaiveTime,
+    pub duration_ms: i64,
+    pub tags: Vec<Tag>,
+    pub defined_at: i64,
+}
@@ -0,0 +1,138 @@
+use crate::modules::schedules::core::events::ScheduleEvent;
+use crate::modules::schedules::core::events::v1::schedule_defined::ScheduleDefinedV1;
+use crate::modules::schedules::core::evolve::evolve;
+use crate::modules::schedules::core::state::ScheduleState;
+use crate::modules::schedules::use_cases::define_schedule::command::DefineSchedule;
+use crate::modules::schedules::use_cases::define_schedule::decision::{DecideError, Decision};
+use crate::shared::core::decider::Decider;
+use std::convert::Infallible;
+
+const DAY_MS: i64 = 24 * 60 * 60 * 1000;
+
+/// Defines a schedule once; a schedule is cancelled and defined anew rather than edited.
+pub fn decide_define(state: &ScheduleState, command: DefineSchedule) -> Decision {
+    if *state != ScheduleState::None {
+        return Decision::Rejected {
+            reason: DecideError::AlreadyDefined,
+        };
+    }
+    if !(1..=DAY_MS).contains(&command.duration_ms) {
+        return Decision::Rejected {
+            reason: DecideError::InvalidDuration,
+        };
+    }
+    if command
+        .rule
+        .until
+        .is_some_and(|until| until < command.starts_on)
+    {
+        return Decision::Rejected {
+            reason: DecideError::EndsBeforeStart,
+        };
+    }
+    Decision::Accepted {
+        events: vec![ScheduleEvent::ScheduleDefinedV1(ScheduleDefinedV1 {
+            schedule_id: command.schedule_id,
+            tenant_id: command.tenant_id,
+            user_id: command.user_id,
+            rule: command.rule,
+            starts_on: command.starts_on,
+            start_time: command.start_time,
+            duration_ms: command.duration_ms,
+            tags: command.tags,
+            defined_at: command.defined_at,
+        })],
+        intents: vec![],
+    }
+}
+
+pub struct DefineScheduleDecider;
+
+impl Decider for DefineScheduleDecider {
+    type State = ScheduleState;
+    type Command = DefineSchedule;
+    type Event = ScheduleEvent;
+    type Intent = Infallible;
+    type Error = DecideError;
+
+    fn initial_state() -> ScheduleState {
+        ScheduleState::None
+    }
+
+    fn evolve(state: ScheduleState, event: ScheduleEvent) -> ScheduleState {
+        evolve(state, event)
+    }
+
+    fn decide(state: &ScheduleState, command: DefineSchedule) -> Decision {
+        decide_define(state, command)
+    }
+}
+
+#[cfg(test)]
+mod define_schedule_decide_tests {
+    use super::*;
+    use chrono::NaiveTime;
+    use rstest::rstest;
+
+    fn command() -> DefineSchedule {
+        DefineSchedule {
+            schedule_id: "sch-1".to_string(),
+            tenant_id: "ten1".to_string(),
+            user_id: "u1".to_string(),
+            rule: "FREQ=WEEKLY;BYDAY=MO".parse().unwrap(),
+            starts_on: "2026-03-02".parse().unwrap(),
+            start_time: NaiveTime::from_hms_opt(9, 0, 0).unwrap(),
+            duration_ms: 900_000,
+            tags: vec![],
+            defined_at: 1000,
+        }
+    }
+
+    #[rstest]
+    fn it_should_define_a_new_schedule() {
+        let decision = decide_define(&ScheduleState::None, command());
+
+        let Decision::Accepted { events, .. } = decision else {
+            panic!("expected Accepted");
+        };
+        assert!(matches!(
+            &events[..],
+            [ScheduleEvent::ScheduleDefinedV1(e)] if e.schedule_id == "sch-1"
+        ));
+    }
+
+    #[rstest]
+    #[case::zero(DefineSchedule { duration_ms: 0, ..command() }, DecideError::InvalidDuration)]
+    #[case::over_a_day(DefineSchedule { duration_ms: DAY_MS + 1, ..command() }, DecideError::InvalidDuration)]
+    #[case::ends_before_start(
+        DefineSchedule { rule: "FREQ=DAILY;UNTIL=20260301".parse().unwrap(), ..command() },
+        DecideError::EndsBeforeStart
+    )]
+    fn it_should_reject_schedules_that_cannot_occur(
+        #[case] command: DefineSchedule,
+        #[case] expected: DecideError,
+    ) {
+        assert_eq!(
+            decide_define(&ScheduleState::None, command),
+            Decision::Rejected { reason: expected }
+        );
+    }
+
+    #[rstest]
+    fn it_should_reject_defining_a_schedule_twice() {
+        let state = DefineScheduleDecider::evolve(
+            ScheduleState::None,
+            match decide_define(&ScheduleState::None, command()) {
+                Decision::Accepted { mut events, .. } => events.remove(0),
+                Decision::Rejected { .. } => panic!("expected Accepted"),
+            },
+        );
+
+        assert_eq!(
+            decide_define(&state, command()),
+            Decision::Rejected {
+                reason: DecideError::AlreadyDefined
+            }
+        );
+    }
+}
@@ -0,0 +1,17 @@
+use crate::modules::schedules::core::events::ScheduleEvent;
+use crate::shared::core::decider;
+use std::convert::Infallible;
+
+#[derive(Debug, thiserror::Error, PartialEq, Eq)]
+pub enum DecideError {
+    #[error("schedule already exists")]
+    AlreadyDefined,
+
+    #[error("duration must be more than 0 and at most 24 hours")]
+    InvalidDuration,
+
+    #[error("schedule ends before it starts")]
+    EndsBeforeStart,
+}
+
+pub type Decision = decider::Decision<ScheduleEvent, Infallible, DecideError>;
@@ -0,0 +1,80 @@
+use crate::modules::schedules::core::events::ScheduleEvent;
+use crate::modules::schedules::core::state::schedule_stream_id;
+use crate::modules::schedules::use_cases::define_schedule::command::DefineSchedule;
+use crate::modules::schedules::use_cases::define_schedule::decide::DefineScheduleDecider;
+use crate::modules::schedules::use_cases::define_schedule::decision::DecideError;
+use crate::shared::application::event_sourced_handler::{
+    EventSourcedError, EventSourcedHandler, NoIntents,
+};
+use crate::shared::infrastructure::event_bus::SharedEventBus;
+use crate::shared::infrastructure::event_store::EventStore;
+use std::convert::Infallible;
+
+pub type ApplicationError = EventSourcedError<DecideError, Infallible>;
+
+#[derive(Debug, Clone)]
+pub struct DefineScheduleHandler<TEventStore>
+where
+    TEventStore: EventStore<ScheduleEvent> + Send + Sync + 'static,
+{
+    inner: EventSourcedHandler<DefineScheduleDecider, TEventStore, NoIntents>,
+}
+
+impl<TEventStore> DefineScheduleHandler<TEventStore>
+where
+    TEventStore: EventStore<ScheduleEvent> + Send + Sync + 'static,
+{
+    pub fn new(event_store: TEventStore) -> Self {
+        Self {
+            inner: EventSourcedHandler::new(event_store, NoIntents),
+        }
+    }
+
+    /// Announce the appended events on `event_bus`, for the projector to pick up.
+    pub fn with_event_bus(mut self, event_bus: SharedEventBus<ScheduleEvent>) -> Self {
+        self.inner = self.inner.with_event_bus(event_bus);
+        self
+    }
+
+    pub async fn handle(&self, command: DefineSchedule) -> Result<(), ApplicationError> {
+        let stream_id = schedule_stream_id(&command.schedule_id);
+        self.inner.handle(&stream_id, command).await
+    }
+}
+
+#[cfg(test)]
+mod define_schedule_handler_tests {
+    use super::*;
+    use crate::shared::infrastructure::event_store::in_memory::InMemoryEventStore;
+    use chrono::NaiveTime;
+    use rstest::rstest;
+
+    fn command() -> DefineSchedule {
+        DefineSchedule {
+            schedule_id: "sch-1".to_string(),
+            tenant_id: "ten1".to_string(),
+            user_id: "u1".to_string(),
+            rule: "FREQ=DAILY".parse().unwrap(),
+            starts_on: "2026-03-02".parse().unwrap(),
+            start_time: NaiveTime::from_hms_opt(9, 0, 0).unwrap(),
+            duration_ms: 900_000,
+            tags: vec![],
+            defined_at: 1000,
+        }
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_append_to_the_schedules_stream_once() {
+        let event_store = InMemoryEventStore::<ScheduleEvent>::new();
+        let handler = DefineScheduleHandler::new(event_store.clone());
+
+        handler.handle(command()).await.unwrap();
+
+        assert_eq!(event_store.load("Schedule-sch-1").await.unwrap().version, 1);
+        assert!(matches!(
+            handler.handle(command()).await,
+            Err(ApplicationError::Domain(DecideError::AlreadyDefined))
+        ));
+    }
+}
@@ -0,0 +1,158 @@
+use async_graphql::{Context, InputObject, Object, Result as GqlResult};
+use chrono::{NaiveDate, NaiveTime, Utc};
+
+use crate::modules::schedules::core::recurrence::RecurrenceRule;
+use crate::modules::schedules::use_cases::define_schedule::command::DefineSchedule;
+use crate::modules::time_entries::core::tag::Tag;
+use crate::shared::infrastructure::request_context::RequestContext;
+use crate::shell::state::AppState;
+
+#[derive(InputObject)]
+pub struct DefineScheduleInput {
+    /// A new id is generated when absent.
+    pub schedule_id: Option<String>,
+    /// An RRULE with `FREQ=DAILY` or `FREQ=WEEKLY` and optionally `INTERVAL`, `BYDAY` and
+    /// `UNTIL`, e.g. `FREQ=WEEKLY;BYDAY=MO,TU,WE,TH,FR`.
+    pub rule: String,
+    /// The first day it may occur on, as `YYYY-MM-DD`.
+    pub starts_on: String,
+    /// When each entry starts, as `HH:MM` in UTC.
+    pub start_time: String,
+    pub duration_minutes: i64,
+    #[graphql(default)]
+    pub tags: Vec<String>,
+}
+
+#[derive(Default)]
+pub struct DefineScheduleMutation;
+
+#[Object]
+impl DefineScheduleMutation {
+    /// Defines a recurring entry for the caller. Each occurrence is registered once it has
+    /// ended, unless the caller logged time over it already. Answers with the schedule's id.
+    async fn define_schedule(
+        &self,
+        context: &Context<'_>,
+        input: DefineScheduleInput,
+    ) -> GqlResult<String> {
+        let rule = input
+            .rule
+            .parse::<RecurrenceRule>()
+            .map_err(|e| async_graphql::Error::new(e.to_string()))?;
+        let starts_on: NaiveDate = input
+            .starts_on
+            .parse()
+            .map_err(|_| async_graphql::Error::new("startsOn must be YYYY-MM-DD"))?;
+        let start_time = NaiveTime::parse_from_str(&input.start_time, "%H:%M")
+            .map_err(|_| async_graphql::Error::new("startTime must be HH:MM"))?;
+        let tags = Tag::parse_all(&input.tags)?;
+        let req_ctx = context
+            .data::<RequestContext>()
+            .map_err(|_| async_graphql::Error::new("Unauthorized"))?;
+        if !req_ctx.principal().can_register_for(&req_ctx.user_id) {
+            return Err(async_graphql::Error::new("Forbidden"));
+        }
+        let state = context.data_unchecked::<AppState>();
+        let schedule_id = input
+            .schedule_id
+            .unwrap_or_else(|| uuid::Uuid::now_v7().to_string());
+        let command = DefineSchedule {
+            schedule_id: schedule_id.clone(),
+            tenant_id: req_ctx.tenant_id.clone(),
+            user_id: req_ctx.user_id.clone(),
+            rule,
+            starts_on,
+            start_time,
+            duration_ms: input.duration_minutes.saturating_mul(60_000),
+            tags,
+            defined_at: Utc::now().timestamp_millis(),
+        };
+
+        state
+            .define_schedule_handler
+            .handle(command)
+            .await
+            .map_err(|e| async_graphql::Error::new(e.to_string()))?;
+
+        Ok(schedule_id)
+    }
+}
+
+#[cfg(test)]
+mod define_schedule_graphql_inbound_tests {
+    use async_graphql::{EmptySubscription, Schema};
+
+    use crate::shared::infrastructure::event_store::EventStore;
+    use crate::shared::infrastructure::request_context::RequestContext;
+    use crate::shell::graphql::{MutationRoot, QueryRoot};
+    use crate::shell::state::AppState;
+    use crate::tests::fixtures::tags::make_test_app_state;
+
+    fn make_schema_from_state(
+        state: AppState,
+    ) -> Schema<QueryRoot, MutationRoot, EmptySubscription> {
+        Schema::build(
+            QueryRoot::default(),
+            MutationRoot::default(),
+            EmptySubscription,
+        )
+        .data(state)
+        .finish()
+    }
+
+    fn req_ctx() -> RequestContext {
+        RequestContext {
+            user_id: "u-1".to_string(),
+            tenant_id: "tenant-test".to_string(),
+            role: Default::default(),
+            scope: Default::default(),
+        }
+    }
+
+    fn define(rule: &str, starts_on: &str, start_time: &str) -> String {
+        format!(
+            r#"mutation {{ defineSchedule(input: {{ scheduleId: "sch-1", rule: "{rule}", startsOn: "{starts_on}", startTime: "{start_time}", durationMinutes: 15, tags: ["Meeting"] }}) }}"#
+        )
+    }
+
+    #[tokio::test]
+    async fn defines_the_schedule_for_the_caller() {
+        let state = make_test_app_state();
+        let schema = make_schema_from_state(state.clone());
+
+        let result = schema
+            .execute(
+                async_graphql::Request::new(define("FREQ=WEEKLY;BYDAY=MO", "2026-03-02", "09:00"))
+                    .data(req_ctx()),
+            )
+            .await;
+
+        assert!(result.errors.is_empty(), "{:?}", result.errors);
+        assert_eq!(result.data.to_string(), r#"{defineSchedule: "sch-1"}"#);
+        assert_eq!(
+            state
+                .schedule_event_store
+                .load("Schedule-sch-1")
+                .await
+                .unwrap()
+                .version,
+            1
+        );
+    }
+
+    #[tokio::test]
+    async fn returns_error_for_invalid_input() {
+        let schema = make_schema_from_state(make_test_app_state());
+        for query in [
+            define("FREQ=MONTHLY", "2026-03-02", "09:00"),
+            define("FREQ=DAILY", "02-03-2026", "09:00"),
+            define("FREQ=DAILY", "2026-03-02", "9am"),
+            define("FREQ=DAILY;UNTIL=20260301", "2026-03-02", "09:00"),
+        ] {
+            let result = schema
+                .execute(async_graphql::Request::new(query.clone()).data(req_ctx()))
+                .await;
+            assert!(!result.errors.is_empty(), "{query}");
+        }
+    }
+}
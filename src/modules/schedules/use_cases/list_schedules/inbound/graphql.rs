@@ -0,0 +1,178 @@
+use async_graphql::{Context, Enum, Object, Result as GqlResult, SimpleObject};
+
+use crate::modules::schedules::use_cases::list_schedules::projection::{
+    OccurrenceRow, OccurrenceStatus, ScheduleRow,
+};
+use crate::shared::infrastructure::request_context::RequestContext;
+use crate::shell::state::AppState;
+
+#[derive(Debug, Enum, Copy, Clone, Eq, PartialEq)]
+#[graphql(name = "OccurrenceStatus")]
+pub enum GqlOccurrenceStatus {
+    Materialized,
+    Skipped,
+}
+
+impl From<OccurrenceStatus> for GqlOccurrenceStatus {
+    fn from(s: OccurrenceStatus) -> Self {
+        match s {
+            OccurrenceStatus::Materialized => GqlOccurrenceStatus::Materialized,
+            OccurrenceStatus::Skipped => GqlOccurrenceStatus::Skipped,
+        }
+    }
+}
+
+#[derive(SimpleObject, Clone)]
+#[graphql(name = "ScheduleOccurrence")]
+pub struct GqlOccurrence {
+    /// `YYYY-MM-DD`.
+    pub date: String,
+    pub status: GqlOccurrenceStatus,
+    /// The entry registered, or the entry already logged when skipped.
+    pub time_entry_id: String,
+}
+
+impl From<OccurrenceRow> for GqlOccurrence {
+    fn from(row: OccurrenceRow) -> Self {
+        Self {
+            date: row.date.to_string(),
+            status: row.status.into(),
+            time_entry_id: row.time_entry_id,
+        }
+    }
+}
+
+#[derive(SimpleObject, Clone)]
+#[graphql(name = "Schedule")]
+pub struct GqlSchedule {
+    pub schedule_id: String,
+    pub rule: String,
+    /// `YYYY-MM-DD`.
+    pub starts_on: String,
+    /// `HH:MM`, UTC.
+    pub start_time: String,
+    pub duration_minutes: i64,
+    pub tags: Vec<String>,
+    pub cancelled: bool,
+    /// Materialized and skipped occurrences, by date.
+    pub occurrences: Vec<GqlOccurrence>,
+}
+
+impl From<ScheduleRow> for GqlSchedule {
+    fn from(row: ScheduleRow) -> Self {
+        Self {
+            schedule_id: row.schedule_id,
+            rule: row.rule,
+            starts_on: row.starts_on.to_string(),
+            start_time: row.start_time.format("%H:%M").to_string(),
+            duration_minutes: row.duration_ms / 60_000,
+            tags: row.tags,
+            cancelled: row.cancelled,
+            occurrences: row.occurrences.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct ListSchedulesQuery;
+
+#[Object]
+impl ListSchedulesQuery {
+    /// The caller's own schedules, with what became of each occurrence so far.
+    async fn list_schedules(&self, context: &Context<'_>) -> GqlResult<Vec<GqlSchedule>> {
+        let req_ctx = context
+            .data::<RequestContext>()
+            .map_err(|_| async_graphql::Error::new("Unauthorized"))?;
+        let state = context.data_unchecked::<AppState>();
+        let schedules = state
+            .list_schedules_handler
+            .list_for_user(&req_ctx.tenant_id, &req_ctx.user_id)
+            .await?;
+        Ok(schedules.into_iter().map(Into::into).collect())
+    }
+}
+
+#[cfg(test)]
+mod list_schedules_graphql_inbound_tests {
+    use async_graphql::{EmptySubscription, Schema};
+    use chrono::NaiveTime;
+
+    use crate::modules::schedules::use_cases::list_schedules::projection::{
+        ListSchedulesState, OccurrenceRow, OccurrenceStatus, ScheduleRow,
+    };
+    use crate::modules::schedules::use_cases::list_schedules::queries::ListSchedulesQueryHandler;
+    use crate::shared::infrastructure::projection_store::ProjectionStore;
+    use crate::shared::infrastructure::projection_store::in_memory::InMemoryProjectionStore;
+    use crate::shared::infrastructure::request_context::RequestContext;
+    use crate::shell::graphql::{MutationRoot, QueryRoot};
+    use crate::shell::state::AppState;
+    use crate::tests::fixtures::tags::make_test_app_state;
+
+    fn make_schema_from_state(
+        state: AppState,
+    ) -> Schema<QueryRoot, MutationRoot, EmptySubscription> {
+        Schema::build(
+            QueryRoot::default(),
+            MutationRoot::default(),
+            EmptySubscription,
+        )
+        .data(state)
+        .finish()
+    }
+
+    fn req_ctx() -> RequestContext {
+        RequestContext {
+            user_id: "u-1".to_string(),
+            tenant_id: "tenant-test".to_string(),
+            role: Default::default(),
+            scope: Default::default(),
+        }
+    }
+
+    #[tokio::test]
+    async fn resolver_returns_the_callers_schedules_and_their_occurrences() {
+        let mut state = make_test_app_state();
+        let store = InMemoryProjectionStore::<ListSchedulesState>::new();
+        let mut projection = ListSchedulesState::default();
+        for (schedule_id, user_id) in [("sch-1", "u-1"), ("sch-2", "u-2")] {
+            projection.rows.insert(
+                schedule_id.to_string(),
+                ScheduleRow {
+                    schedule_id: schedule_id.to_string(),
+                    tenant_id: "tenant-test".to_string(),
+                    user_id: user_id.to_string(),
+                    rule: "FREQ=DAILY;INTERVAL=1".to_string(),
+                    starts_on: "2026-03-02".parse().unwrap(),
+                    start_time: NaiveTime::from_hms_opt(9, 30, 0).unwrap(),
+                    duration_ms: 900_000,
+                    tags: vec![],
+                    cancelled: false,
+                    occurrences: vec![OccurrenceRow {
+                        date: "2026-03-02".parse().unwrap(),
+                        status: OccurrenceStatus::Skipped,
+                        time_entry_id: "te-1".to_string(),
+                    }],
+                    last_event_id: None,
+                },
+            );
+        }
+        store.save(projection, 1).await.unwrap();
+        state.list_schedules_handler = ListSchedulesQueryHandler::new(store);
+        let schema = make_schema_from_state(state);
+
+        let result = schema
+            .execute(
+                async_graphql::Request::new(
+                    r#"{ listSchedules { scheduleId startTime durationMinutes occurrences { date status timeEntryId } } }"#,
+                )
+                .data(req_ctx()),
+            )
+            .await;
+
+        assert!(result.errors.is_empty(), "{:?}", result.errors);
+        assert_eq!(
+            result.data.to_string(),
+            r#"{listSchedules: [{scheduleId: "sch-1", startTime: "09:30", durationMinutes: 15, occurrences: [{date: "2026-03-02", status: SKIPPED, timeEntryId: "te-1"}]}]}"#
+        );
+    }
+}
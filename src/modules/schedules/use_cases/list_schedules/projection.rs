@@ -0,0 +1,195 @@
+use crate::modules::schedules::core::events::ScheduleEvent;
+use crate::shared::core::primitives::last_event_version;
+use chrono::{NaiveDate, NaiveTime};
+use std::collections::HashMap;
+
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// Every schedule with the occurrences recorded so far, keyed by schedule id.
+#[derive(Clone, Default)]
+pub struct ListSchedulesState {
+    pub rows: HashMap<String, ScheduleRow>,
+}
+
+impl ListSchedulesState {
+    /// Applies the event at `version` of `stream_id`, unless its row already reflects it.
+    pub fn apply(&mut self, stream_id: &str, version: i64, event: &ScheduleEvent) {
+        let last_event_id = Some(format!("{stream_id}:{version}"));
+        let (schedule_id, occurrence) = match event {
+            ScheduleEvent::ScheduleDefinedV1(e) => {
+                self.rows
+                    .entry(e.schedule_id.clone())
+                    .or_insert_with(|| ScheduleRow {
+                        schedule_id: e.schedule_id.clone(),
+                        tenant_id: e.tenant_id.clone(),
+                        user_id: e.user_id.clone(),
+                        rule: e.rule.to_string(),
+                        starts_on: e.starts_on,
+                        start_time: e.start_time,
+                        duration_ms: e.duration_ms,
+                        tags: e.tags.iter().map(|tag| tag.as_str().to_string()).collect(),
+                        cancelled: false,
+                        occurrences: vec![],
+                        last_event_id,
+                    });
+                return;
+            }
+            ScheduleEvent::ScheduleCancelledV1(e) => (&e.schedule_id, None),
+            ScheduleEvent::OccurrenceMaterializedV1(e) => (
+                &e.schedule_id,
+                Some(OccurrenceRow {
+                    date: e.date,
+                    status: OccurrenceStatus::Materialized,
+                    time_entry_id: e.time_entry_id.clone(),
+                }),
+            ),
+            ScheduleEvent::OccurrenceSkippedV1(e) => (
+                &e.schedule_id,
+                Some(OccurrenceRow {
+                    date: e.date,
+                    status: OccurrenceStatus::Skipped,
+                    time_entry_id: e.conflicting_time_entry_id.clone(),
+                }),
+            ),
+        };
+        let Some(row) = self.rows.get_mut(schedule_id) else {
+            return;
+        };
+        if row.has_applied(version) {
+            return;
+        }
+        match occurrence {
+            Some(occurrence) => {
+                let at = row
+                    .occurrences
+                    .partition_point(|recorded| recorded.date < occurrence.date);
+                row.occurrences.insert(at, occurrence);
+            }
+            None => row.cancelled = true,
+        }
+        row.last_event_id = last_event_id;
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct ScheduleRow {
+    pub schedule_id: String,
+    pub tenant_id: String,
+    pub user_id: String,
+    pub rule: String,
+    pub starts_on: NaiveDate,
+    pub start_time: NaiveTime,
+    pub duration_ms: i64,
+    pub tags: Vec<String>,
+    pub cancelled: bool,
+    /// By date.
+    pub occurrences: Vec<OccurrenceRow>,
+    pub last_event_id: Option<String>,
+}
+
+impl ScheduleRow {
+    /// Whether the event at `stream_version` is already reflected in this row.
+    pub fn has_applied(&self, stream_version: i64) -> bool {
+        last_event_version(self.last_event_id.as_deref())
+            .is_some_and(|applied| applied >= stream_version)
+    }
+
+    pub fn has_recorded(&self, date: NaiveDate) -> bool {
+        self.occurrences
+            .binary_search_by(|occurrence| occurrence.date.cmp(&date))
+            .is_ok()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum OccurrenceStatus {
+    Materialized,
+    Skipped,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct OccurrenceRow {
+    pub date: NaiveDate,
+    pub status: OccurrenceStatus,
+    /// The entry registered, or the one already logged when skipped.
+    pub time_entry_id: String,
+}
+
+#[cfg(test)]
+mod list_schedules_projection_model_tests {
+    use super::*;
+    use crate::modules::schedules::core::events::v1::occurrence_materialized::OccurrenceMaterializedV1;
+    use crate::modules::schedules::core::events::v1::occurrence_skipped::OccurrenceSkippedV1;
+    use crate::modules::schedules::core::events::v1::schedule_cancelled::ScheduleCancelledV1;
+    use crate::modules::schedules::core::events::v1::schedule_defined::ScheduleDefinedV1;
+    use rstest::rstest;
+
+    fn date(value: &str) -> NaiveDate {
+        value.parse().unwrap()
+    }
+
+    fn events() -> Vec<ScheduleEvent> {
+        vec![
+            ScheduleEvent::ScheduleDefinedV1(ScheduleDefinedV1 {
+                schedule_id: "sch-1".to_string(),
+                tenant_id: "ten1".to_string(),
+                user_id: "u1".to_string(),
+                rule: "FREQ=DAILY".parse().unwrap(),
+                starts_on: date("2026-03-02"),
+                start_time: NaiveTime::from_hms_opt(9, 0, 0).unwrap(),
+                duration_ms: 900_000,
+                tags: vec![],
+                defined_at: 1000,
+            }),
+            ScheduleEvent::OccurrenceSkippedV1(OccurrenceSkippedV1 {
+                schedule_id: "sch-1".to_string(),
+                date: date("2026-03-03"),
+                conflicting_time_entry_id: "te-2".to_string(),
+                skipped_at: 2000,
+            }),
+            ScheduleEvent::OccurrenceMaterializedV1(OccurrenceMaterializedV1 {
+                schedule_id: "sch-1".to_string(),
+                date: date("2026-03-02"),
+                time_entry_id: "te-1".to_string(),
+                started_at: 0,
+                ended_at: 900_000,
+                materialized_at: 3000,
+            }),
+            ScheduleEvent::ScheduleCancelledV1(ScheduleCancelledV1 {
+                schedule_id: "sch-1".to_string(),
+                cancelled_at: 4000,
+                cancelled_by: "u1".to_string(),
+            }),
+        ]
+    }
+
+    #[rstest]
+    fn it_should_list_occurrences_by_date_however_often_events_are_replayed() {
+        let mut state = ListSchedulesState::default();
+        for _ in 0..2 {
+            for (version, event) in (1..).zip(events()) {
+                state.apply("Schedule-sch-1", version, &event);
+            }
+        }
+
+        let row = &state.rows["sch-1"];
+        assert!(row.cancelled);
+        assert_eq!(
+            row.occurrences,
+            vec![
+                OccurrenceRow {
+                    date: date("2026-03-02"),
+                    status: OccurrenceStatus::Materialized,
+                    time_entry_id: "te-1".to_string(),
+                },
+                OccurrenceRow {
+                    date: date("2026-03-03"),
+                    status: OccurrenceStatus::Skipped,
+                    time_entry_id: "te-2".to_string(),
+                },
+            ]
+        );
+        assert!(row.has_recorded(date("2026-03-03")));
+        assert!(!row.has_recorded(date("2026-03-04")));
+    }
+}
@@ -0,0 +1,194 @@
+use crate::modules::schedules::core::events::ScheduleEvent;
+use crate::modules::schedules::use_cases::list_schedules::projection::{
+    ListSchedulesState, SCHEMA_VERSION,
+};
+use crate::modules::time_entries::use_cases::list_time_entries::projector::ProjectionTechnicalEvent;
+use crate::shared::infrastructure::event_store::StoredEvent;
+use crate::shared::infrastructure::event_store::in_memory::InMemoryEventStore;
+use crate::shared::infrastructure::projection_store::ProjectionStore;
+use tokio::sync::broadcast;
+
+/// Keeps the schedules list in step with the schedule feed. It rebuilds from the event log
+/// on a schema change and whenever it falls behind the channel.
+pub struct ListSchedulesProjector<TStore>
+where
+    TStore: ProjectionStore<ListSchedulesState> + Send + Sync + 'static,
+{
+    pub name: String,
+    pub store: TStore,
+    pub event_store: InMemoryEventStore<ScheduleEvent>,
+    pub technical_tx: broadcast::Sender<ProjectionTechnicalEvent>,
+}
+
+impl<TStore> ListSchedulesProjector<TStore>
+where
+    TStore: ProjectionStore<ListSchedulesState> + Send + Sync + 'static,
+{
+    pub fn new(
+        name: impl Into<String>,
+        store: TStore,
+        event_store: InMemoryEventStore<ScheduleEvent>,
+        technical_tx: broadcast::Sender<ProjectionTechnicalEvent>,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            store,
+            event_store,
+            technical_tx,
+        }
+    }
+
+    pub async fn run(self, mut receiver: broadcast::Receiver<StoredEvent<ScheduleEvent>>) {
+        let stored_schema = self.store.schema_version().await.unwrap_or(None);
+        if stored_schema != Some(SCHEMA_VERSION)
+            && let Err(reason) = self.rebuild().await
+        {
+            self.rebuild_failed(reason);
+            return;
+        }
+
+        loop {
+            match receiver.recv().await {
+                Ok(stored_event) => {
+                    let checkpoint = self.store.checkpoint().await.unwrap_or(0);
+                    if stored_event.global_position < checkpoint {
+                        continue;
+                    }
+                    let start = std::time::Instant::now();
+                    if self.apply_stored_event(&stored_event).await.is_err() {
+                        continue;
+                    }
+                    let _ = self
+                        .technical_tx
+                        .send(ProjectionTechnicalEvent::EventApplied {
+                            projection_name: self.name.clone(),
+                            checkpoint: stored_event.global_position + 1,
+                            duration_ms: start.elapsed().as_millis() as u64,
+                        });
+                }
+                Err(broadcast::error::RecvError::Lagged(_)) => {
+                    if let Err(reason) = self.rebuild().await {
+                        self.rebuild_failed(reason);
+                        return;
+                    }
+                }
+                Err(broadcast::error::RecvError::Closed) => break,
+            }
+        }
+    }
+
+    pub async fn rebuild(&self) -> anyhow::Result<()> {
+        let start = std::time::Instant::now();
+        let _ = self
+            .technical_tx
+            .send(ProjectionTechnicalEvent::RebuildStarted {
+                projection_name: self.name.clone(),
+                schema_version: SCHEMA_VERSION,
+                timestamp: chrono::Utc::now().timestamp_millis(),
+            });
+        self.store.clear().await?;
+        let all_events = self.event_store.load_all_from(0).await?;
+        let events_replayed = all_events.len() as u64;
+        for stored_event in all_events {
+            self.apply_stored_event(&stored_event).await?;
+        }
+        self.store.save_schema_version(SCHEMA_VERSION).await?;
+        let _ = self
+            .technical_tx
+            .send(ProjectionTechnicalEvent::RebuildCompleted {
+                projection_name: self.name.clone(),
+                events_replayed,
+                duration_ms: start.elapsed().as_millis() as u64,
+                timestamp: chrono::Utc::now().timestamp_millis(),
+            });
+        Ok(())
+    }
+
+    fn rebuild_failed(&self, reason: anyhow::Error) {
+        let _ = self
+            .technical_tx
+            .send(ProjectionTechnicalEvent::RebuildFailed {
+                projection_name: self.name.clone(),
+                reason: reason.to_string(),
+                timestamp: chrono::Utc::now().timestamp_millis(),
+            });
+    }
+
+    async fn apply_stored_event(
+        &self,
+        stored_event: &StoredEvent<ScheduleEvent>,
+    ) -> anyhow::Result<()> {
+        let mut state = self.store.state().await?.unwrap_or_default();
+        state.apply(
+            &stored_event.stream_id,
+            stored_event.stream_version,
+            &stored_event.event,
+        );
+        self.store
+            .save(state, stored_event.global_position + 1)
+            .await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod list_schedules_projector_tests {
+    use super::*;
+    use crate::modules::schedules::use_cases::define_schedule::command::DefineSchedule;
+    use crate::modules::schedules::use_cases::define_schedule::handler::DefineScheduleHandler;
+    use crate::modules::schedules::use_cases::list_schedules::queries::ListSchedulesQueryHandler;
+    use crate::shared::infrastructure::projection_store::in_memory::InMemoryProjectionStore;
+    use chrono::NaiveTime;
+    use rstest::rstest;
+
+    async fn define(event_store: InMemoryEventStore<ScheduleEvent>, schedule_id: &str) {
+        DefineScheduleHandler::new(event_store)
+            .handle(DefineSchedule {
+                schedule_id: schedule_id.to_string(),
+                tenant_id: "ten1".to_string(),
+                user_id: "u1".to_string(),
+                rule: "FREQ=DAILY".parse().unwrap(),
+                starts_on: "2026-03-02".parse().unwrap(),
+                start_time: NaiveTime::from_hms_opt(9, 0, 0).unwrap(),
+                duration_ms: 900_000,
+                tags: vec![],
+                defined_at: 1000,
+            })
+            .await
+            .unwrap();
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_rebuild_then_follow_the_feed() {
+        let (tx, _) = broadcast::channel::<StoredEvent<ScheduleEvent>>(16);
+        let event_store = InMemoryEventStore::<ScheduleEvent>::new_with_sender(tx.clone());
+        define(event_store.clone(), "sch-1").await;
+        let projection_store = InMemoryProjectionStore::<ListSchedulesState>::new();
+        let (tech_tx, mut tech_rx) = broadcast::channel(16);
+        let projector = ListSchedulesProjector::new(
+            "list_schedules",
+            projection_store.clone(),
+            event_store.clone(),
+            tech_tx,
+        );
+        let running = tokio::spawn(projector.run(tx.subscribe()));
+
+        define(event_store, "sch-2").await;
+        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+        running.abort();
+
+        let ids: Vec<String> = ListSchedulesQueryHandler::new(projection_store)
+            .list_for_user("ten1", "u1")
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|row| row.schedule_id)
+            .collect();
+        assert_eq!(ids, vec!["sch-1", "sch-2"]);
+        assert!(matches!(
+            tech_rx.try_recv(),
+            Ok(ProjectionTechnicalEvent::RebuildStarted { .. })
+        ));
+    }
+}
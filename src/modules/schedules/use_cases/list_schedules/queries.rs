@@ -0,0 +1,37 @@
+use crate::modules::schedules::use_cases::list_schedules::projection::{
+    ListSchedulesState, ScheduleRow,
+};
+use crate::shared::infrastructure::projection_store::ProjectionStore;
+
+#[derive(Clone)]
+pub struct ListSchedulesQueryHandler<TStore>
+where
+    TStore: ProjectionStore<ListSchedulesState> + Send + Sync + 'static,
+{
+    store: TStore,
+}
+
+impl<TStore> ListSchedulesQueryHandler<TStore>
+where
+    TStore: ProjectionStore<ListSchedulesState> + Send + Sync + 'static,
+{
+    pub fn new(store: TStore) -> Self {
+        Self { store }
+    }
+
+    /// The schedules `user_id` defined in `tenant_id`, cancelled ones included, by id.
+    pub async fn list_for_user(
+        &self,
+        tenant_id: &str,
+        user_id: &str,
+    ) -> anyhow::Result<Vec<ScheduleRow>> {
+        let state = self.store.state().await?.unwrap_or_default();
+        let mut items: Vec<_> = state
+            .rows
+            .into_values()
+            .filter(|row| row.tenant_id == tenant_id && row.user_id == user_id)
+            .collect();
+        items.sort_by(|a, b| a.schedule_id.cmp(&b.schedule_id));
+        Ok(items)
+    }
+}
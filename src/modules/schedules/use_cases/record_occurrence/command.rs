@@ -0,0 +1,21 @@
+use chrono::NaiveDate;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecordOccurrence {
+    pub schedule_id: String,
+    pub date: NaiveDate,
+    pub outcome: OccurrenceOutcome,
+    pub recorded_at: i64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OccurrenceOutcome {
+    /// The occurrence registered this entry.
+    Materialized {
+        time_entry_id: String,
+        started_at: i64,
+        ended_at: i64,
+    },
+    /// The user had already logged this entry over the occurrence.
+    Skipped { conflicting_time_entry_id: String },
+}
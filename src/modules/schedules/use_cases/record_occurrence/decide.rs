@@ -0,0 +1,144 @@
+use crate::modules::schedules::core::events::ScheduleEvent;
+use crate::modules::schedules::core::events::v1::occurrence_materialized::OccurrenceMaterializedV1;
+use crate::modules::schedules::core::events::v1::occurrence_skipped::OccurrenceSkippedV1;
+use crate::modules::schedules::core::evolve::evolve;
+use crate::modules::schedules::core::state::ScheduleState;
+use crate::modules::schedules::use_cases::record_occurrence::command::{
+    OccurrenceOutcome, RecordOccurrence,
+};
+use crate::modules::schedules::use_cases::record_occurrence::decision::{DecideError, Decision};
+use crate::shared::core::decider::Decider;
+use std::convert::Infallible;
+
+/// Records how an occurrence went, once per date.
+pub fn decide_record(state: &ScheduleState, command: RecordOccurrence) -> Decision {
+    if !matches!(state, ScheduleState::Active { .. }) {
+        return Decision::Rejected {
+            reason: DecideError::NotActive,
+        };
+    }
+    if !state.is_due_on(command.date) {
+        return Decision::Rejected {
+            reason: DecideError::NotDue,
+        };
+    }
+    let event = match command.outcome {
+        OccurrenceOutcome::Materialized {
+            time_entry_id,
+            started_at,
+            ended_at,
+        } => ScheduleEvent::OccurrenceMaterializedV1(OccurrenceMaterializedV1 {
+            schedule_id: command.schedule_id,
+            date: command.date,
+            time_entry_id,
+            started_at,
+            ended_at,
+            materialized_at: command.recorded_at,
+        }),
+        OccurrenceOutcome::Skipped {
+            conflicting_time_entry_id,
+        } => ScheduleEvent::OccurrenceSkippedV1(OccurrenceSkippedV1 {
+            schedule_id: command.schedule_id,
+            date: command.date,
+            conflicting_time_entry_id,
+            skipped_at: command.recorded_at,
+        }),
+    };
+    Decision::Accepted {
+        events: vec![event],
+        intents: vec![],
+    }
+}
+
+pub struct RecordOccurrenceDecider;
+
+impl Decider for RecordOccurrenceDecider {
+    type State = ScheduleState;
+    type Command = RecordOccurrence;
+    type Event = ScheduleEvent;
+    type Intent = Infallible;
+    type Error = DecideError;
+
+    fn initial_state() -> ScheduleState {
+        ScheduleState::None
+    }
+
+    fn evolve(state: ScheduleState, event: ScheduleEvent) -> ScheduleState {
+        evolve(state, event)
+    }
+
+    fn decide(state: &ScheduleState, command: RecordOccurrence) -> Decision {
+        decide_record(state, command)
+    }
+}
+
+#[cfg(test)]
+mod record_occurrence_decide_tests {
+    use super::*;
+    use chrono::{NaiveDate, NaiveTime};
+    use rstest::rstest;
+    use std::collections::BTreeSet;
+
+    fn date(value: &str) -> NaiveDate {
+        value.parse().unwrap()
+    }
+
+    fn active(recorded: &[&str]) -> ScheduleState {
+        ScheduleState::Active {
+            schedule_id: "sch-1".to_string(),
+            tenant_id: "ten1".to_string(),
+            user_id: "u1".to_string(),
+            rule: "FREQ=WEEKLY;BYDAY=MO,TU,WE,TH,FR".parse().unwrap(),
+            starts_on: date("2026-03-02"),
+            start_time: NaiveTime::from_hms_opt(9, 0, 0).unwrap(),
+            duration_ms: 900_000,
+            tags: vec![],
+            recorded: recorded
+                .iter()
+                .map(|day| date(day))
+                .collect::<BTreeSet<_>>(),
+        }
+    }
+
+    fn skipped(day: &str) -> RecordOccurrence {
+        RecordOccurrence {
+            schedule_id: "sch-1".to_string(),
+            date: date(day),
+            outcome: OccurrenceOutcome::Skipped {
+                conflicting_time_entry_id: "te-1".to_string(),
+            },
+            recorded_at: 1000,
+        }
+    }
+
+    #[rstest]
+    fn it_should_record_a_due_occurrence() {
+        assert_eq!(
+            decide_record(&active(&[]), skipped("2026-03-03")),
+            Decision::Accepted {
+                events: vec![ScheduleEvent::OccurrenceSkippedV1(OccurrenceSkippedV1 {
+                    schedule_id: "sch-1".to_string(),
+                    date: date("2026-03-03"),
+                    conflicting_time_entry_id: "te-1".to_string(),
+                    skipped_at: 1000,
+                })],
+                intents: vec![],
+            }
+        );
+    }
+
+    #[rstest]
+    #[case::missing(ScheduleState::None, "2026-03-03", DecideError::NotActive)]
+    #[case::recorded(active(&["2026-03-03"]), "2026-03-03", DecideError::NotDue)]
+    #[case::weekend(active(&[]), "2026-03-07", DecideError::NotDue)]
+    fn it_should_record_each_occurrence_at_most_once(
+        #[case] state: ScheduleState,
+        #[case] day: &str,
+        #[case] expected: DecideError,
+    ) {
+        assert_eq!(
+            decide_record(&state, skipped(day)),
+            Decision::Rejected { reason: expected }
+        );
+    }
+}
@@ -0,0 +1,14 @@
+use crate::modules::schedules::core::events::ScheduleEvent;
+use crate::shared::core::decider;
+use std::convert::Infallible;
+
+#[derive(Debug, thiserror::Error, PartialEq, Eq)]
+pub enum DecideError {
+    #[error("schedule is not active")]
+    NotActive,
+
+    #[error("schedule does not occur on that date, or its occurrence was already recorded")]
+    NotDue,
+}
+
+pub type Decision = decider::Decision<ScheduleEvent, Infallible, DecideError>;
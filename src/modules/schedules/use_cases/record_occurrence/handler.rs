@@ -0,0 +1,59 @@
+use crate::modules::schedules::core::events::ScheduleEvent;
+use crate::modules::schedules::core::state::{ScheduleState, schedule_stream_id};
+use crate::modules::schedules::use_cases::record_occurrence::command::RecordOccurrence;
+use crate::modules::schedules::use_cases::record_occurrence::decide::RecordOccurrenceDecider;
+use crate::modules::schedules::use_cases::record_occurrence::decision::DecideError;
+use crate::shared::application::event_sourced_handler::{
+    EventSourcedError, EventSourcedHandler, NoIntents,
+};
+use crate::shared::core::decider::Decider;
+use crate::shared::infrastructure::event_bus::SharedEventBus;
+use crate::shared::infrastructure::event_store::{EventStore, EventStoreError};
+use std::convert::Infallible;
+
+pub type ApplicationError = EventSourcedError<DecideError, Infallible>;
+
+#[derive(Debug, Clone)]
+pub struct RecordOccurrenceHandler<TEventStore>
+where
+    TEventStore: EventStore<ScheduleEvent> + Clone + Send + Sync + 'static,
+{
+    event_store: TEventStore,
+    inner: EventSourcedHandler<RecordOccurrenceDecider, TEventStore, NoIntents>,
+}
+
+impl<TEventStore> RecordOccurrenceHandler<TEventStore>
+where
+    TEventStore: EventStore<ScheduleEvent> + Clone + Send + Sync + 'static,
+{
+    pub fn new(event_store: TEventStore) -> Self {
+        Self {
+            event_store: event_store.clone(),
+            inner: EventSourcedHandler::new(event_store, NoIntents),
+        }
+    }
+
+    /// Announce the appended events on `event_bus`, for the projector to pick up.
+    pub fn with_event_bus(mut self, event_bus: SharedEventBus<ScheduleEvent>) -> Self {
+        self.inner = self.inner.with_event_bus(event_bus);
+        self
+    }
+
+    pub async fn handle(&self, command: RecordOccurrence) -> Result<(), ApplicationError> {
+        let stream_id = schedule_stream_id(&command.schedule_id);
+        self.inner.handle(&stream_id, command).await
+    }
+
+    /// The schedule as its stream has it, for checking an occurrence is still due before
+    /// registering its entry.
+    pub async fn schedule(&self, schedule_id: &str) -> Result<ScheduleState, EventStoreError> {
+        let stream = self
+            .event_store
+            .load(&schedule_stream_id(schedule_id))
+            .await?;
+        Ok(stream.events.into_iter().fold(
+            RecordOccurrenceDecider::initial_state(),
+            RecordOccurrenceDecider::evolve,
+        ))
+    }
+}
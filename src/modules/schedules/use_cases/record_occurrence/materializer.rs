@@ -0,0 +1,511 @@
+// Registers the entries recurring schedules call for.
+//
+// Schedules are found in the list schedules read model. An occurrence is registered once it
+// has ended, on the day it falls or the day after, so a run missed around midnight or during
+// a restart still catches it; older occurrences are left alone. The read model may lag, so
+// each occurrence is re-checked against the schedule's stream before anything is written.
+//
+// When the user already logged time overlapping the occurrence, running timers included, it
+// is skipped and the entry that overlapped recorded instead. Otherwise the entry is started,
+// ended and tagged under an id derived from the schedule and date, then the occurrence is
+// recorded. A run that fails in between registers the same entry again on the next run: the
+// entry it left behind is recognized by its id rather than taken for a conflict.
+
+use crate::modules::schedules::core::events::ScheduleEvent;
+use crate::modules::schedules::core::occurrence::{occurrence_interval, occurrence_time_entry_id};
+use crate::modules::schedules::core::recurrence::RecurrenceRule;
+use crate::modules::schedules::core::state::ScheduleState;
+use crate::modules::schedules::use_cases::list_schedules::projection::ListSchedulesState;
+use crate::modules::schedules::use_cases::record_occurrence::command::{
+    OccurrenceOutcome, RecordOccurrence,
+};
+use crate::modules::schedules::use_cases::record_occurrence::handler::RecordOccurrenceHandler;
+use crate::modules::time_entries::core::events::TimeEntryEvent;
+use crate::modules::time_entries::core::tag::Tag;
+use crate::modules::time_entries::use_cases::list_time_entries::projection::ListTimeEntriesState;
+use crate::modules::time_entries::use_cases::set_ended_at::command::SetEndedAt;
+use crate::modules::time_entries::use_cases::set_ended_at::handler::SetEndedAtHandler;
+use crate::modules::time_entries::use_cases::set_started_at::command::SetStartedAt;
+use crate::modules::time_entries::use_cases::set_started_at::handler::SetStartedAtHandler;
+use crate::modules::time_entries::use_cases::set_time_entry_tags::command::SetTimeEntryTags;
+use crate::modules::time_entries::use_cases::set_time_entry_tags::handler::SetTimeEntryTagsHandler;
+use crate::shared::core::primitives::TimeEntryId;
+use crate::shared::core::stream_naming::{DefaultStreamNaming, StreamNaming};
+use crate::shared::infrastructure::event_store::EventStore;
+use crate::shared::infrastructure::intent_outbox::DomainOutbox;
+use crate::shared::infrastructure::projection_store::ProjectionStore;
+use chrono::{DateTime, NaiveDate};
+use std::sync::Arc;
+
+/// How a run went: occurrences that registered an entry, and ones skipped for a conflict.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MaterializeRun {
+    pub materialized: u64,
+    pub skipped: u64,
+}
+
+/// The time entry commands an occurrence registers its entry with.
+#[derive(Clone)]
+pub struct EntryRegistration<TEventStore, TOutbox>
+where
+    TEventStore: EventStore<TimeEntryEvent> + Send + Sync + 'static,
+    TOutbox: DomainOutbox + Send + Sync + 'static,
+{
+    pub set_started_at: SetStartedAtHandler<TEventStore, TOutbox>,
+    pub set_ended_at: SetEndedAtHandler<TEventStore, TOutbox>,
+    pub set_tags: SetTimeEntryTagsHandler<TEventStore, TOutbox>,
+}
+
+pub struct ScheduleMaterializer<TSchedules, TEntries, TScheduleEvents, TEventStore, TOutbox>
+where
+    TSchedules: ProjectionStore<ListSchedulesState> + Send + Sync + 'static,
+    TEntries: ProjectionStore<ListTimeEntriesState> + Send + Sync + 'static,
+    TScheduleEvents: EventStore<ScheduleEvent> + Clone + Send + Sync + 'static,
+    TEventStore: EventStore<TimeEntryEvent> + Send + Sync + 'static,
+    TOutbox: DomainOutbox + Send + Sync + 'static,
+{
+    schedules: TSchedules,
+    entries: TEntries,
+    occurrences: RecordOccurrenceHandler<TScheduleEvents>,
+    registration: EntryRegistration<TEventStore, TOutbox>,
+    stream_naming: Arc<dyn StreamNaming>,
+}
+
+impl<TSchedules, TEntries, TScheduleEvents, TEventStore, TOutbox>
+    ScheduleMaterializer<TSchedules, TEntries, TScheduleEvents, TEventStore, TOutbox>
+where
+    TSchedules: ProjectionStore<ListSchedulesState> + Send + Sync + 'static,
+    TEntries: ProjectionStore<ListTimeEntriesState> + Send + Sync + 'static,
+    TScheduleEvents: EventStore<ScheduleEvent> + Clone + Send + Sync + 'static,
+    TEventStore: EventStore<TimeEntryEvent> + Send + Sync + 'static,
+    TOutbox: DomainOutbox + Send + Sync + 'static,
+{
+    pub fn new(
+        schedules: TSchedules,
+        entries: TEntries,
+        occurrences: RecordOccurrenceHandler<TScheduleEvents>,
+        registration: EntryRegistration<TEventStore, TOutbox>,
+    ) -> Self {
+        Self {
+            schedules,
+            entries,
+            occurrences,
+            registration,
+            stream_naming: Arc::new(DefaultStreamNaming),
+        }
+    }
+
+    /// Names the streams of the entries occurrences register.
+    pub fn with_stream_naming(mut self, stream_naming: Arc<dyn StreamNaming>) -> Self {
+        self.stream_naming = stream_naming;
+        self
+    }
+
+    /// Materializes or skips every occurrence of yesterday and today that ended by `now`.
+    /// An occurrence that fails is logged and retried on the next run; it does not hold up
+    /// the others.
+    pub async fn materialize_due(&self, now: i64) -> anyhow::Result<MaterializeRun> {
+        let today = DateTime::from_timestamp_millis(now)
+            .map(|at| at.date_naive())
+            .ok_or_else(|| anyhow::anyhow!("timestamp out of range: {now}"))?;
+        let dates = [today.pred_opt().unwrap_or(today), today];
+        let state = self.schedules.state().await?.unwrap_or_default();
+        let mut candidates: Vec<(String, NaiveDate)> = state
+            .rows
+            .values()
+            .filter(|row| !row.cancelled)
+            .flat_map(|row| {
+                let rule = row.rule.parse::<RecurrenceRule>().ok();
+                dates
+                    .into_iter()
+                    .filter(move |date| {
+                        rule.as_ref()
+                            .is_some_and(|rule| rule.occurs_on(row.starts_on, *date))
+                    })
+                    .filter(|date| !row.has_recorded(*date))
+                    .map(|date| (row.schedule_id.clone(), date))
+            })
+            .collect();
+        candidates.sort();
+
+        let mut run = MaterializeRun::default();
+        for (schedule_id, date) in candidates {
+            match self.materialize(&schedule_id, date, now).await {
+                Ok(Some(OccurrenceOutcome::Materialized { .. })) => run.materialized += 1,
+                Ok(Some(OccurrenceOutcome::Skipped { .. })) => run.skipped += 1,
+                Ok(None) => {}
+                Err(reason) => {
+                    tracing::warn!(%reason, %schedule_id, %date, "failed to materialize occurrence");
+                }
+            }
+        }
+        Ok(run)
+    }
+
+    async fn materialize(
+        &self,
+        schedule_id: &str,
+        date: NaiveDate,
+        now: i64,
+    ) -> anyhow::Result<Option<OccurrenceOutcome>> {
+        let schedule = self.occurrences.schedule(schedule_id).await?;
+        if !schedule.is_due_on(date) {
+            return Ok(None);
+        }
+        let ScheduleState::Active {
+            tenant_id,
+            user_id,
+            start_time,
+            duration_ms,
+            tags,
+            ..
+        } = schedule
+        else {
+            return Ok(None);
+        };
+        let (started_at, ended_at) = occurrence_interval(date, start_time, duration_ms);
+        if ended_at > now {
+            return Ok(None);
+        }
+        let time_entry_id = occurrence_time_entry_id(schedule_id, date, started_at);
+
+        let entries = self.entries.state().await?.unwrap_or_default();
+        let conflict = entries
+            .rows
+            .values()
+            .filter(|row| row.user_id == user_id && row.deleted_at.is_none())
+            .filter(|row| row.time_entry_id != time_entry_id.as_str())
+            .filter(|row| {
+                row.started_at.is_some_and(|start| start < ended_at)
+                    && row.ended_at.is_none_or(|end| end > started_at)
+            })
+            .map(|row| row.time_entry_id.clone())
+            .min();
+        let outcome = match conflict {
+            Some(conflicting_time_entry_id) => OccurrenceOutcome::Skipped {
+                conflicting_time_entry_id,
+            },
+            None => {
+                let registered = entries
+                    .rows
+                    .get(time_entry_id.as_str())
+                    .is_some_and(|row| row.ended_at.is_some());
+                if !registered {
+                    self.register(
+                        &tenant_id,
+                        &user_id,
+                        &time_entry_id,
+                        started_at,
+                        ended_at,
+                        tags,
+                    )
+                    .await?;
+                }
+                OccurrenceOutcome::Materialized {
+                    time_entry_id: time_entry_id.to_string(),
+                    started_at,
+                    ended_at,
+                }
+            }
+        };
+        self.occurrences
+            .handle(RecordOccurrence {
+                schedule_id: schedule_id.to_string(),
+                date,
+                outcome: outcome.clone(),
+                recorded_at: now,
+            })
+            .await?;
+        Ok(Some(outcome))
+    }
+
+    async fn register(
+        &self,
+        tenant_id: &str,
+        user_id: &str,
+        time_entry_id: &TimeEntryId,
+        started_at: i64,
+        ended_at: i64,
+        tags: Vec<Tag>,
+    ) -> anyhow::Result<()> {
+        let stream_id = self
+            .stream_naming
+            .time_entry(Some(tenant_id), time_entry_id);
+        self.registration
+            .set_started_at
+            .handle_for_tenant(
+                tenant_id,
+                &stream_id,
+                SetStartedAt::new(time_entry_id.clone(), user_id.into(), started_at),
+            )
+            .await?;
+        self.registration
+            .set_ended_at
+            .handle_for_tenant(
+                tenant_id,
+                &stream_id,
+                SetEndedAt::new(time_entry_id.clone(), user_id.into(), ended_at),
+            )
+            .await?;
+        if !tags.is_empty() {
+            self.registration
+                .set_tags
+                .handle_for_tenant(
+                    tenant_id,
+                    &stream_id,
+                    SetTimeEntryTags::new(time_entry_id.clone(), user_id.into(), tags),
+                )
+                .await?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod schedule_materializer_tests {
+    use super::*;
+    use crate::modules::schedules::use_cases::define_schedule::command::DefineSchedule;
+    use crate::modules::schedules::use_cases::define_schedule::handler::DefineScheduleHandler;
+    use crate::modules::time_entries::use_cases::list_time_entries::projection::{
+        TimeEntryRow, TimeEntryStatus,
+    };
+    use crate::shared::infrastructure::event_store::in_memory::InMemoryEventStore;
+    use crate::shared::infrastructure::intent_outbox::in_memory::InMemoryDomainOutbox;
+    use crate::shared::infrastructure::projection_store::in_memory::InMemoryProjectionStore;
+    use chrono::NaiveTime;
+    use rstest::rstest;
+
+    type Materializer = ScheduleMaterializer<
+        InMemoryProjectionStore<ListSchedulesState>,
+        InMemoryProjectionStore<ListTimeEntriesState>,
+        InMemoryEventStore<ScheduleEvent>,
+        InMemoryEventStore<TimeEntryEvent>,
+        InMemoryDomainOutbox,
+    >;
+
+    struct Setup {
+        materializer: Materializer,
+        schedule_events: InMemoryEventStore<ScheduleEvent>,
+        schedules: InMemoryProjectionStore<ListSchedulesState>,
+        entries: InMemoryProjectionStore<ListTimeEntriesState>,
+        entry_events: InMemoryEventStore<TimeEntryEvent>,
+    }
+
+    fn date() -> NaiveDate {
+        "2026-03-02".parse().unwrap()
+    }
+
+    /// 2026-03-02 at the given UTC time.
+    fn at(hour: u32, minute: u32) -> i64 {
+        date()
+            .and_time(NaiveTime::from_hms_opt(hour, minute, 0).unwrap())
+            .and_utc()
+            .timestamp_millis()
+    }
+
+    fn row(time_entry_id: &str, started_at: i64, ended_at: Option<i64>) -> TimeEntryRow {
+        TimeEntryRow {
+            time_entry_id: time_entry_id.to_string(),
+            user_id: "u1".to_string(),
+            started_at: Some(started_at),
+            ended_at,
+            tag_ids: vec![],
+            status: TimeEntryStatus::Draft,
+            created_at: 0,
+            created_by: "u1".to_string(),
+            updated_at: 0,
+            updated_by: "u1".to_string(),
+            deleted_at: None,
+            hourly_rate: None,
+            last_event_id: None,
+            breaks: vec![],
+        }
+    }
+
+    /// A daily schedule from 09:00 to 09:15, starting 2026-03-02, with its read model.
+    async fn setup(rows: Vec<TimeEntryRow>) -> Setup {
+        let schedule_events = InMemoryEventStore::<ScheduleEvent>::new();
+        DefineScheduleHandler::new(schedule_events.clone())
+            .handle(DefineSchedule {
+                schedule_id: "sch-1".to_string(),
+                tenant_id: "ten1".to_string(),
+                user_id: "u1".to_string(),
+                rule: "FREQ=DAILY".parse().unwrap(),
+                starts_on: date(),
+                start_time: NaiveTime::from_hms_opt(9, 0, 0).unwrap(),
+                duration_ms: 900_000,
+                tags: vec![],
+                defined_at: 0,
+            })
+            .await
+            .unwrap();
+        let schedules = InMemoryProjectionStore::<ListSchedulesState>::new();
+        refresh(&schedule_events, &schedules).await;
+
+        let entries = InMemoryProjectionStore::<ListTimeEntriesState>::new();
+        let mut state = ListTimeEntriesState::default();
+        for row in rows {
+            state.rows.insert(row.time_entry_id.clone(), row);
+        }
+        entries.save(state, 1).await.unwrap();
+
+        let entry_events = InMemoryEventStore::<TimeEntryEvent>::new();
+        let outbox = InMemoryDomainOutbox::new();
+        let materializer = ScheduleMaterializer::new(
+            schedules.clone(),
+            entries.clone(),
+            RecordOccurrenceHandler::new(schedule_events.clone()),
+            EntryRegistration {
+                set_started_at: SetStartedAtHandler::new(entry_events.clone(), outbox.clone()),
+                set_ended_at: SetEndedAtHandler::new(entry_events.clone(), outbox.clone()),
+                set_tags: SetTimeEntryTagsHandler::new(entry_events.clone(), outbox),
+            },
+        );
+        Setup {
+            materializer,
+            schedule_events,
+            schedules,
+            entries,
+            entry_events,
+        }
+    }
+
+    /// Rebuilds the schedules read model from the schedule's stream.
+    async fn refresh(
+        schedule_events: &InMemoryEventStore<ScheduleEvent>,
+        schedules: &InMemoryProjectionStore<ListSchedulesState>,
+    ) {
+        let stream = schedule_events.load("Schedule-sch-1").await.unwrap();
+        let mut state = ListSchedulesState::default();
+        for (index, event) in stream.events.iter().enumerate() {
+            state.apply("Schedule-sch-1", index as i64 + 1, event);
+        }
+        schedules.save(state, stream.version as u64).await.unwrap();
+    }
+
+    fn occurrence_id() -> TimeEntryId {
+        occurrence_time_entry_id("sch-1", date(), at(9, 0))
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_register_the_entry_of_an_ended_occurrence() {
+        let setup = setup(vec![]).await;
+
+        let run = setup.materializer.materialize_due(at(10, 0)).await.unwrap();
+
+        assert_eq!(
+            run,
+            MaterializeRun {
+                materialized: 1,
+                skipped: 0
+            }
+        );
+        let entry = setup
+            .entry_events
+            .load(&format!("TimeEntry-{}", occurrence_id().as_str()))
+            .await
+            .unwrap();
+        assert!(!entry.events.is_empty());
+        let schedule = setup.schedule_events.load("Schedule-sch-1").await.unwrap();
+        assert_eq!(
+            schedule.events.last(),
+            Some(&ScheduleEvent::OccurrenceMaterializedV1(
+                crate::modules::schedules::core::events::v1::occurrence_materialized::OccurrenceMaterializedV1 {
+                    schedule_id: "sch-1".to_string(),
+                    date: date(),
+                    time_entry_id: occurrence_id().to_string(),
+                    started_at: at(9, 0),
+                    ended_at: at(9, 15),
+                    materialized_at: at(10, 0),
+                }
+            ))
+        );
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_skip_an_occurrence_overlapping_time_already_logged() {
+        let setup = setup(vec![row("te-running", at(9, 10), None)]).await;
+
+        let run = setup.materializer.materialize_due(at(10, 0)).await.unwrap();
+
+        assert_eq!(
+            run,
+            MaterializeRun {
+                materialized: 0,
+                skipped: 1
+            }
+        );
+        let entry = setup
+            .entry_events
+            .load(&format!("TimeEntry-{}", occurrence_id().as_str()))
+            .await
+            .unwrap();
+        assert!(entry.events.is_empty());
+        let schedule = setup.schedule_events.load("Schedule-sch-1").await.unwrap();
+        assert!(matches!(
+            schedule.events.last(),
+            Some(ScheduleEvent::OccurrenceSkippedV1(e)) if e.conflicting_time_entry_id == "te-running"
+        ));
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_wait_until_the_occurrence_has_ended() {
+        let setup = setup(vec![]).await;
+
+        let run = setup.materializer.materialize_due(at(9, 10)).await.unwrap();
+
+        assert_eq!(run, MaterializeRun::default());
+        let schedule = setup.schedule_events.load("Schedule-sch-1").await.unwrap();
+        assert_eq!(schedule.events.len(), 1);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_materialize_an_occurrence_once_even_when_the_read_model_lags() {
+        let setup = setup(vec![]).await;
+        setup.materializer.materialize_due(at(10, 0)).await.unwrap();
+
+        let stale = setup.materializer.materialize_due(at(10, 5)).await.unwrap();
+        refresh(&setup.schedule_events, &setup.schedules).await;
+        let fresh = setup
+            .materializer
+            .materialize_due(at(10, 10))
+            .await
+            .unwrap();
+
+        assert_eq!(stale, MaterializeRun::default());
+        assert_eq!(fresh, MaterializeRun::default());
+        let schedule = setup.schedule_events.load("Schedule-sch-1").await.unwrap();
+        assert_eq!(schedule.events.len(), 2);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_not_take_its_own_entry_for_a_conflict_when_retried() {
+        let setup = setup(vec![]).await;
+        setup
+            .materializer
+            .register("ten1", "u1", &occurrence_id(), at(9, 0), at(9, 15), vec![])
+            .await
+            .unwrap();
+        let mut state = ListTimeEntriesState::default();
+        state.rows.insert(
+            occurrence_id().to_string(),
+            row(occurrence_id().as_str(), at(9, 0), Some(at(9, 15))),
+        );
+        setup.entries.save(state, 2).await.unwrap();
+
+        let run = setup.materializer.materialize_due(at(10, 0)).await.unwrap();
+
+        assert_eq!(
+            run,
+            MaterializeRun {
+                materialized: 1,
+                skipped: 0
+            }
+        );
+    }
+}
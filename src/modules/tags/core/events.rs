@@ -1,3 +1,5 @@
+use crate::shared::core::domain_event::DomainEvent;
+
 pub mod v1 {
     pub mod tag_color_set;
     pub mod tag_created;
@@ -6,7 +8,7 @@ pub mod v1 {
     pub mod tag_name_set;
 }
 
-#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, PartialEq, Eq, DomainEvent)]
 #[serde(tag = "type")]
 pub enum TagEvent {
     TagCreatedV1(v1::tag_created::TagCreatedV1),
@@ -15,3 +17,20 @@ pub enum TagEvent {
     TagColorSetV1(v1::tag_color_set::TagColorSetV1),
     TagDescriptionSetV1(v1::tag_description_set::TagDescriptionSetV1),
 }
+
+#[cfg(test)]
+mod tag_event_schema_tests {
+    use super::*;
+    use crate::tests::golden::{assert_catalog, assert_event_goldens};
+    use rstest::rstest;
+
+    #[rstest]
+    fn every_version_should_match_its_golden() {
+        assert_event_goldens::<TagEvent>("tags");
+    }
+
+    #[rstest]
+    fn the_catalog_should_list_every_version() {
+        assert_catalog::<TagEvent>();
+    }
+}
@@ -13,7 +13,6 @@ pub struct TagCreatedV1 {
 mod tag_created_event_tests {
     use super::*;
     use rstest::{fixture, rstest};
-    use std::fs;
 
     #[fixture]
     fn event() -> TagCreatedV1 {
@@ -39,9 +38,9 @@ mod tag_created_event_tests {
 
     #[rstest]
     fn it_serializes_stable(event: TagCreatedV1) {
-        let golden: serde_json::Value = serde_json::from_str(
-            &fs::read_to_string("./src/tests/fixtures/events/json/tag_created_v1.json").unwrap(),
-        )
+        let golden: serde_json::Value = serde_json::from_str(include_str!(
+            "../../../../../tests/fixtures/events/json/tag_created_v1.json"
+        ))
         .unwrap();
         assert_eq!(serde_json::to_value(&event).unwrap(), golden);
     }
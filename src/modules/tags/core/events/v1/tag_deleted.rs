@@ -10,7 +10,6 @@ pub struct TagDeletedV1 {
 mod tag_deleted_event_tests {
     use super::*;
     use rstest::{fixture, rstest};
-    use std::fs;
 
     #[fixture]
     fn event() -> TagDeletedV1 {
@@ -32,9 +31,9 @@ mod tag_deleted_event_tests {
 
     #[rstest]
     fn it_serializes_stable(event: TagDeletedV1) {
-        let golden: serde_json::Value = serde_json::from_str(
-            &fs::read_to_string("./src/tests/fixtures/events/json/tag_deleted_v1.json").unwrap(),
-        )
+        let golden: serde_json::Value = serde_json::from_str(include_str!(
+            "../../../../../tests/fixtures/events/json/tag_deleted_v1.json"
+        ))
         .unwrap();
         assert_eq!(serde_json::to_value(&event).unwrap(), golden);
     }
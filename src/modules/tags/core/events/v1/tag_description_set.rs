@@ -11,7 +11,6 @@ pub struct TagDescriptionSetV1 {
 mod tag_description_set_event_tests {
     use super::*;
     use rstest::{fixture, rstest};
-    use std::fs;
 
     #[fixture]
     fn event() -> TagDescriptionSetV1 {
@@ -33,10 +32,9 @@ mod tag_description_set_event_tests {
 
     #[rstest]
     fn it_serializes_stable(event: TagDescriptionSetV1) {
-        let golden: serde_json::Value = serde_json::from_str(
-            &fs::read_to_string("./src/tests/fixtures/events/json/tag_description_set_v1.json")
-                .unwrap(),
-        )
+        let golden: serde_json::Value = serde_json::from_str(include_str!(
+            "../../../../../tests/fixtures/events/json/tag_description_set_v1.json"
+        ))
         .unwrap();
         assert_eq!(serde_json::to_value(&event).unwrap(), golden);
     }
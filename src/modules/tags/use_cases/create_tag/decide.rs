@@ -1,8 +1,11 @@
 use crate::modules::tags::core::events::TagEvent;
 use crate::modules::tags::core::events::v1::tag_created::TagCreatedV1;
+use crate::modules::tags::core::evolve::evolve;
 use crate::modules::tags::core::state::TagState;
 use crate::modules::tags::use_cases::create_tag::command::CreateTag;
 use crate::modules::tags::use_cases::create_tag::decision::{DecideError, Decision};
+use crate::shared::core::decider::Decider;
+use std::convert::Infallible;
 
 pub fn decide_create(state: &TagState, command: CreateTag) -> Decision {
     match state {
@@ -16,6 +19,7 @@ pub fn decide_create(state: &TagState, command: CreateTag) -> Decision {
                 created_at: command.created_at,
                 created_by: command.created_by,
             })],
+            intents: vec![],
         },
         TagState::Created { .. } | TagState::Deleted { .. } => Decision::Rejected {
             reason: DecideError::TagAlreadyExists,
@@ -23,6 +27,28 @@ pub fn decide_create(state: &TagState, command: CreateTag) -> Decision {
     }
 }
 
+pub struct CreateTagDecider;
+
+impl Decider for CreateTagDecider {
+    type State = TagState;
+    type Command = CreateTag;
+    type Event = TagEvent;
+    type Intent = Infallible;
+    type Error = DecideError;
+
+    fn initial_state() -> TagState {
+        TagState::None
+    }
+
+    fn evolve(state: TagState, event: TagEvent) -> TagState {
+        evolve(state, event)
+    }
+
+    fn decide(state: &TagState, command: CreateTag) -> Decision {
+        decide_create(state, command)
+    }
+}
+
 #[cfg(test)]
 mod create_tag_decide_tests {
     use super::*;
@@ -47,7 +73,7 @@ mod create_tag_decide_tests {
     fn none_state_accepts_create(command: CreateTag) {
         let decision = decide_create(&TagState::None, command);
         match decision {
-            Decision::Accepted { events } => {
+            Decision::Accepted { events, .. } => {
                 assert_eq!(events.len(), 1);
                 assert!(matches!(&events[0], TagEvent::TagCreatedV1(_)));
             }
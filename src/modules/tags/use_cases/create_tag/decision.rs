@@ -1,4 +1,6 @@
 use crate::modules::tags::core::events::TagEvent;
+use crate::shared::core::decider;
+use std::convert::Infallible;
 
 #[derive(Debug, thiserror::Error, PartialEq, Eq)]
 pub enum DecideError {
@@ -6,7 +8,4 @@ pub enum DecideError {
     TagAlreadyExists,
 }
 
-pub enum Decision {
-    Accepted { events: Vec<TagEvent> },
-    Rejected { reason: DecideError },
-}
+pub type Decision = decider::Decision<TagEvent, Infallible, DecideError>;
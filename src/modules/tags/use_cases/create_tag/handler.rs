@@ -2,18 +2,47 @@ use crate::modules::tags::core::events::TagEvent;
 use crate::modules::tags::core::evolve::evolve;
 use crate::modules::tags::core::state::TagState;
 use crate::modules::tags::use_cases::create_tag::command::CreateTag;
-use crate::modules::tags::use_cases::create_tag::decide::decide_create;
-use crate::modules::tags::use_cases::create_tag::decision::{DecideError, Decision};
-use crate::shared::infrastructure::event_store::{EventStore, EventStoreError};
-use thiserror::Error;
-
-#[derive(Debug, Error)]
-pub enum ApplicationError {
-    #[error(transparent)]
-    VersionConflict(#[from] EventStoreError),
-
-    #[error("domain error: {0}")]
-    Domain(DecideError),
+use crate::modules::tags::use_cases::create_tag::decide::CreateTagDecider;
+use crate::modules::tags::use_cases::create_tag::decision::DecideError;
+use crate::shared::application::event_sourced_handler::{
+    EventSourcedError, EventSourcedHandler, NoIntents,
+};
+use crate::shared::infrastructure::event_store::EventStore;
+use crate::shared::infrastructure::event_store::paged::{DEFAULT_PAGE_SIZE, fold_paged};
+use std::convert::Infallible;
+
+pub type ApplicationError = EventSourcedError<DecideError, Infallible>;
+
+/// What creating a tag that already exists does.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DuplicateCreatePolicy {
+    /// Reject it with `TagAlreadyExists`.
+    #[default]
+    Conflict,
+    /// Succeed without appending when the existing tag matches the command, so a retried
+    /// create is harmless; a tag that differs, or was deleted, still conflicts.
+    Idempotent,
+}
+
+impl std::str::FromStr for DuplicateCreatePolicy {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "conflict" => Ok(Self::Conflict),
+            "idempotent" => Ok(Self::Idempotent),
+            other => Err(format!("unknown duplicate create policy: {other}")),
+        }
+    }
+}
+
+/// How a create that succeeded went.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Created {
+    /// The tag was created.
+    New,
+    /// An identical tag already existed; nothing was appended.
+    Existing,
 }
 
 #[derive(Debug, Clone)]
@@ -21,43 +50,82 @@ pub struct CreateTagHandler<TEventStore>
 where
     TEventStore: EventStore<TagEvent> + Send + Sync + 'static,
 {
+    inner: EventSourcedHandler<CreateTagDecider, TEventStore, NoIntents>,
     event_store: TEventStore,
+    duplicates: DuplicateCreatePolicy,
 }
 
 impl<TEventStore> CreateTagHandler<TEventStore>
 where
     TEventStore: EventStore<TagEvent> + Send + Sync + 'static,
 {
-    pub fn new(event_store: TEventStore) -> Self {
-        Self { event_store }
+    pub fn new(event_store: TEventStore) -> Self
+    where
+        TEventStore: Clone,
+    {
+        Self {
+            inner: EventSourcedHandler::new(event_store.clone(), NoIntents),
+            event_store,
+            duplicates: DuplicateCreatePolicy::default(),
+        }
+    }
+
+    pub fn with_duplicate_policy(mut self, duplicates: DuplicateCreatePolicy) -> Self {
+        self.duplicates = duplicates;
+        self
     }
 
     pub async fn handle(
         &self,
         stream_id: &str,
         command: CreateTag,
-    ) -> Result<(), ApplicationError> {
-        let stream = self
-            .event_store
-            .load(stream_id)
-            .await
-            .map_err(ApplicationError::VersionConflict)?;
-
-        let state = stream.events.iter().cloned().fold(TagState::None, evolve);
-
-        match decide_create(&state, command) {
-            Decision::Accepted { events } => {
-                self.event_store
-                    .append(stream_id, stream.version, &events)
-                    .await
-                    .map_err(ApplicationError::VersionConflict)?;
-                Ok(())
+    ) -> Result<Created, ApplicationError> {
+        let rejected = match self.inner.handle(stream_id, command.clone()).await {
+            Ok(()) => return Ok(Created::New),
+            Err(ApplicationError::Domain(DecideError::TagAlreadyExists))
+                if self.duplicates == DuplicateCreatePolicy::Idempotent =>
+            {
+                ApplicationError::Domain(DecideError::TagAlreadyExists)
             }
-            Decision::Rejected { reason } => Err(ApplicationError::Domain(reason)),
+            Err(e) => return Err(e),
+        };
+        let (state, _) = fold_paged(
+            &self.event_store,
+            stream_id,
+            DEFAULT_PAGE_SIZE,
+            TagState::None,
+            evolve,
+        )
+        .await?;
+        if matches_command(&state, &command) {
+            Ok(Created::Existing)
+        } else {
+            Err(rejected)
         }
     }
 }
 
+/// Whether the tag as it stands now is the one the command would create. Only what the
+/// caller chose counts: the creation time and creator are stamped per request, so a retry
+/// never repeats them. Renames, recolors and other edits since make the tag differ.
+fn matches_command(state: &TagState, command: &CreateTag) -> bool {
+    match state {
+        TagState::Created {
+            tenant_id,
+            name,
+            color,
+            description,
+            ..
+        } => {
+            *tenant_id == command.tenant_id
+                && *name == command.name
+                && *color == command.color
+                && *description == command.description
+        }
+        TagState::None | TagState::Deleted { .. } => false,
+    }
+}
+
 #[cfg(test)]
 mod create_tag_handler_tests {
     use super::*;
@@ -68,11 +136,8 @@ mod create_tag_handler_tests {
 
     type Setup = (&'static str, CreateTag, InMemoryEventStore<TagEvent>);
 
-    #[fixture]
-    fn setup() -> Setup {
-        let stream_id = "Tag-t1";
-        let event_store = InMemoryEventStore::<TagEvent>::new();
-        let command = CreateTag {
+    fn command() -> CreateTag {
+        CreateTag {
             tag_id: "t1".to_string(),
             tenant_id: "ten1".to_string(),
             name: "Work".to_string(),
@@ -80,8 +145,14 @@ mod create_tag_handler_tests {
             description: None,
             created_at: 1000,
             created_by: "u1".to_string(),
-        };
-        (stream_id, command, event_store)
+        }
+    }
+
+    #[fixture]
+    fn setup() -> Setup {
+        let stream_id = "Tag-t1";
+        let event_store = InMemoryEventStore::<TagEvent>::new();
+        (stream_id, command(), event_store)
     }
 
     #[rstest]
@@ -110,6 +181,92 @@ mod create_tag_handler_tests {
         ));
     }
 
+    #[rstest]
+    #[tokio::test]
+    async fn handle_create_returns_the_identical_existing_tag_when_idempotent(setup: Setup) {
+        let (stream_id, command, event_store) = setup;
+        let handler = CreateTagHandler::new(event_store.clone())
+            .with_duplicate_policy(DuplicateCreatePolicy::Idempotent);
+        handler.handle(stream_id, command.clone()).await.unwrap();
+
+        let retried = handler
+            .handle(
+                stream_id,
+                CreateTag {
+                    created_at: 2000,
+                    created_by: "u2".to_string(),
+                    ..command
+                },
+            )
+            .await;
+
+        assert_eq!(retried.unwrap(), Created::Existing);
+        let stream = event_store.load(stream_id).await.unwrap();
+        assert_eq!(stream.events.len(), 1);
+    }
+
+    #[rstest]
+    #[case::other_name(CreateTag { name: "Home".to_string(), ..command() })]
+    #[case::other_color(CreateTag { color: "#BAFFC9".to_string(), ..command() })]
+    #[case::other_description(CreateTag { description: Some("desk".to_string()), ..command() })]
+    #[case::other_tenant(CreateTag { tenant_id: "ten2".to_string(), ..command() })]
+    #[tokio::test]
+    async fn handle_create_still_conflicts_on_a_differing_payload_when_idempotent(
+        #[case] retried: CreateTag,
+    ) {
+        let handler = CreateTagHandler::new(InMemoryEventStore::<TagEvent>::new())
+            .with_duplicate_policy(DuplicateCreatePolicy::Idempotent);
+        handler.handle("Tag-t1", command()).await.unwrap();
+
+        let result = handler.handle("Tag-t1", retried).await;
+
+        assert!(matches!(
+            result,
+            Err(ApplicationError::Domain(DecideError::TagAlreadyExists))
+        ));
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn handle_create_still_conflicts_on_a_deleted_tag_when_idempotent(setup: Setup) {
+        use crate::modules::tags::core::events::v1::tag_deleted::TagDeletedV1;
+        let (stream_id, command, event_store) = setup;
+        let handler = CreateTagHandler::new(event_store.clone())
+            .with_duplicate_policy(DuplicateCreatePolicy::Idempotent);
+        handler.handle(stream_id, command.clone()).await.unwrap();
+        event_store
+            .append(
+                stream_id,
+                1,
+                &[TagEvent::TagDeletedV1(TagDeletedV1 {
+                    tag_id: "t1".to_string(),
+                    tenant_id: "ten1".to_string(),
+                    deleted_at: 2000,
+                    deleted_by: "u1".to_string(),
+                })],
+            )
+            .await
+            .unwrap();
+
+        let result = handler.handle(stream_id, command).await;
+
+        assert!(matches!(
+            result,
+            Err(ApplicationError::Domain(DecideError::TagAlreadyExists))
+        ));
+    }
+
+    #[rstest]
+    #[case("conflict", Ok(DuplicateCreatePolicy::Conflict))]
+    #[case("idempotent", Ok(DuplicateCreatePolicy::Idempotent))]
+    #[case("lenient", Err("unknown duplicate create policy: lenient".to_string()))]
+    fn it_should_parse_the_duplicate_create_policy(
+        #[case] value: &str,
+        #[case] expected: Result<DuplicateCreatePolicy, String>,
+    ) {
+        assert_eq!(value.parse::<DuplicateCreatePolicy>(), expected);
+    }
+
     #[rstest]
     #[tokio::test]
     async fn handle_create_fails_if_event_store_is_offline(setup: Setup) {
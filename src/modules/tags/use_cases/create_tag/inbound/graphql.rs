@@ -10,6 +10,7 @@ use crate::shell::state::AppState;
 mod create_tag_graphql_inbound_tests {
     use async_graphql::{EmptySubscription, Schema};
 
+    use crate::shared::auth::rbac::Scope;
     use crate::shared::infrastructure::request_context::RequestContext;
     use crate::shell::graphql::{MutationRoot, QueryRoot};
     use crate::tests::fixtures::tags::make_test_app_state;
@@ -30,6 +31,8 @@ mod create_tag_graphql_inbound_tests {
         RequestContext {
             user_id: "u-1".to_string(),
             tenant_id: "tenant-test".to_string(),
+            role: Default::default(),
+            scope: Default::default(),
         }
     }
 
@@ -49,6 +52,22 @@ mod create_tag_graphql_inbound_tests {
         assert!(data.len() > r#"{"createTag":""}"#.len());
     }
 
+    #[tokio::test]
+    async fn returns_forbidden_for_read_only_api_keys() {
+        let schema = make_schema_from_state(make_test_app_state());
+        let result = schema
+            .execute(
+                async_graphql::Request::new(r#"mutation { createTag(name: "my-tag") }"#).data(
+                    RequestContext {
+                        scope: Scope::ReadOnly,
+                        ..req_ctx()
+                    },
+                ),
+            )
+            .await;
+        assert_eq!(result.errors[0].message, "Forbidden");
+    }
+
     #[tokio::test]
     async fn returns_id_with_optional_color_and_description() {
         let schema = make_schema_from_state(make_test_app_state());
@@ -93,6 +112,9 @@ impl CreateTagMutation {
         let req_ctx = context
             .data::<RequestContext>()
             .map_err(|_| async_graphql::Error::new("Unauthorized"))?;
+        if !req_ctx.principal().can_write() {
+            return Err(async_graphql::Error::new("Forbidden"));
+        }
         let state = context.data_unchecked::<AppState>();
         let tag_id = Uuid::now_v7();
         let stream_id = format!("Tag-{tag_id}");
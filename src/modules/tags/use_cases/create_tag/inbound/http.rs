@@ -10,11 +10,11 @@ use uuid::Uuid;
 
 use crate::modules::tags::use_cases::create_tag::command::{CreateTag, pick_pastel_color};
 use crate::modules::tags::use_cases::create_tag::decision::DecideError;
-use crate::modules::tags::use_cases::create_tag::handler::ApplicationError;
+use crate::modules::tags::use_cases::create_tag::handler::{ApplicationError, Created};
 use crate::shared::infrastructure::request_context::RequestContext;
 use crate::shell::state::AppState;
 
-#[derive(Deserialize)]
+#[derive(Serialize, Deserialize)]
 pub struct CreateTagBody {
     pub tag_id: Option<String>,
     pub name: String,
@@ -22,7 +22,7 @@ pub struct CreateTagBody {
     pub description: Option<String>,
 }
 
-#[derive(Serialize)]
+#[derive(Serialize, Deserialize)]
 pub struct CreateTagResponse {
     pub tag_id: String,
 }
@@ -32,6 +32,9 @@ pub async fn handle(
     request_ctx: RequestContext,
     body: Result<Json<CreateTagBody>, JsonRejection>,
 ) -> impl IntoResponse {
+    if !request_ctx.principal().can_write() {
+        return StatusCode::FORBIDDEN.into_response();
+    }
     let Json(body) = match body {
         Ok(b) => b,
         Err(_) => return StatusCode::UNPROCESSABLE_ENTITY.into_response(),
@@ -58,8 +61,11 @@ pub async fn handle(
     };
 
     match state.create_tag_handler.handle(&stream_id, command).await {
-        Ok(()) => (
-            StatusCode::CREATED,
+        Ok(created) => (
+            match created {
+                Created::New => StatusCode::CREATED,
+                Created::Existing => StatusCode::OK,
+            },
             Json(CreateTagResponse {
                 tag_id: tag_id.to_string(),
             }),
@@ -184,6 +190,44 @@ mod create_tag_http_inbound_tests {
         assert_eq!(resp2.status(), StatusCode::CONFLICT);
     }
 
+    #[tokio::test]
+    async fn it_should_return_200_on_an_identical_duplicate_when_idempotent() {
+        use crate::modules::tags::use_cases::create_tag::handler::{
+            CreateTagHandler, DuplicateCreatePolicy,
+        };
+        let mut state = make_test_app_state();
+        state.create_tag_handler = CreateTagHandler::new(state.tag_event_store.clone())
+            .with_duplicate_policy(DuplicateCreatePolicy::Idempotent);
+        let known_id = uuid::Uuid::now_v7().to_string();
+        let post = |body: String| {
+            Request::post("/tags")
+                .header("content-type", "application/json")
+                .header("x-user-id", "u-1")
+                .header("x-tenant-id", "tenant-test")
+                .body(Body::from(body))
+                .unwrap()
+        };
+        let body =
+            |color: &str| format!(r#"{{"tag_id":"{known_id}","name":"Work","color":"{color}"}}"#);
+
+        let created = app(state.clone())
+            .oneshot(post(body("#FFB3BA")))
+            .await
+            .unwrap();
+        let retried = app(state.clone())
+            .oneshot(post(body("#FFB3BA")))
+            .await
+            .unwrap();
+        let differing = app(state).oneshot(post(body("#BAFFC9"))).await.unwrap();
+
+        assert_eq!(created.status(), StatusCode::CREATED);
+        assert_eq!(retried.status(), StatusCode::OK);
+        let bytes = retried.into_body().collect().await.unwrap().to_bytes();
+        let json: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+        assert_eq!(json["tag_id"], known_id);
+        assert_eq!(differing.status(), StatusCode::CONFLICT);
+    }
+
     #[tokio::test]
     async fn it_should_return_422_on_invalid_json() {
         let response = app(make_test_app_state())
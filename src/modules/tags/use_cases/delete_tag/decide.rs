@@ -1,8 +1,11 @@
 use crate::modules::tags::core::events::TagEvent;
 use crate::modules::tags::core::events::v1::tag_deleted::TagDeletedV1;
+use crate::modules::tags::core::evolve::evolve;
 use crate::modules::tags::core::state::TagState;
 use crate::modules::tags::use_cases::delete_tag::command::DeleteTag;
 use crate::modules::tags::use_cases::delete_tag::decision::{DecideError, Decision};
+use crate::shared::core::decider::Decider;
+use std::convert::Infallible;
 
 pub fn decide_delete(state: &TagState, command: DeleteTag) -> Decision {
     match state {
@@ -15,6 +18,7 @@ pub fn decide_delete(state: &TagState, command: DeleteTag) -> Decision {
                 deleted_at: command.deleted_at,
                 deleted_by: command.deleted_by,
             })],
+            intents: vec![],
         },
         TagState::None => Decision::Rejected {
             reason: DecideError::TagNotFound,
@@ -25,6 +29,28 @@ pub fn decide_delete(state: &TagState, command: DeleteTag) -> Decision {
     }
 }
 
+pub struct DeleteTagDecider;
+
+impl Decider for DeleteTagDecider {
+    type State = TagState;
+    type Command = DeleteTag;
+    type Event = TagEvent;
+    type Intent = Infallible;
+    type Error = DecideError;
+
+    fn initial_state() -> TagState {
+        TagState::None
+    }
+
+    fn evolve(state: TagState, event: TagEvent) -> TagState {
+        evolve(state, event)
+    }
+
+    fn decide(state: &TagState, command: DeleteTag) -> Decision {
+        decide_delete(state, command)
+    }
+}
+
 #[cfg(test)]
 mod delete_tag_decide_tests {
     use super::*;
@@ -74,7 +100,7 @@ mod delete_tag_decide_tests {
     fn created_state_accepts_delete(command: DeleteTag) {
         let decision = decide_delete(&created_state(), command);
         match decision {
-            Decision::Accepted { events } => {
+            Decision::Accepted { events, .. } => {
                 assert_eq!(events.len(), 1);
                 assert!(matches!(&events[0], TagEvent::TagDeletedV1(_)));
             }
@@ -15,6 +15,9 @@ pub async fn handle(
     request_ctx: RequestContext,
     Path(tag_id): Path<String>,
 ) -> impl IntoResponse {
+    if !request_ctx.principal().can_write() {
+        return StatusCode::FORBIDDEN.into_response();
+    }
     let stream_id = format!("Tag-{tag_id}");
     let command = DeleteTag {
         tag_id: tag_id.clone(),
@@ -29,6 +29,8 @@ mod list_tags_graphql_inbound_tests {
         RequestContext {
             user_id: "u-1".to_string(),
             tenant_id: "tenant-test".to_string(),
+            role: Default::default(),
+            scope: Default::default(),
         }
     }
 
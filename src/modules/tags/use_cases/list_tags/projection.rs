@@ -1,3 +1,5 @@
+use crate::shared::core::primitives::last_event_version;
+
 pub const SCHEMA_VERSION: u32 = 1;
 
 #[derive(Clone, Default)]
@@ -5,6 +7,25 @@ pub struct ListTagsState {
     pub rows: std::collections::HashMap<String, TagRow>,
 }
 
+impl ListTagsState {
+    /// Writes `row` unless the stored row already reflects the event that produced it.
+    /// Returns whether the row was written.
+    pub fn upsert(&mut self, row: TagRow) -> bool {
+        let already_applied = match last_event_version(row.last_event_id.as_deref()) {
+            Some(version) => self
+                .rows
+                .get(&row.tag_id)
+                .is_some_and(|existing| existing.has_applied(version)),
+            None => false,
+        };
+        if already_applied {
+            return false;
+        }
+        self.rows.insert(row.tag_id.clone(), row);
+        true
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 pub struct TagRow {
     pub tag_id: String,
@@ -16,6 +37,14 @@ pub struct TagRow {
     pub last_event_id: Option<String>,
 }
 
+impl TagRow {
+    /// Whether the event at `stream_version` is already reflected in this row.
+    pub fn has_applied(&self, stream_version: i64) -> bool {
+        last_event_version(self.last_event_id.as_deref())
+            .is_some_and(|applied| applied >= stream_version)
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 pub struct TagView {
     pub tag_id: String,
@@ -72,4 +101,31 @@ mod list_tags_projection_model_tests {
         let view = TagView::from(row);
         assert_eq!(view.description, Some("Client work".to_string()));
     }
+
+    #[rstest]
+    #[case::new_row(None, Some("Tag-t1:1"), true)]
+    #[case::replayed_event(Some("Tag-t1:3"), Some("Tag-t1:1"), false)]
+    #[case::same_event(Some("Tag-t1:3"), Some("Tag-t1:3"), false)]
+    #[case::newer_event(Some("Tag-t1:3"), Some("Tag-t1:4"), true)]
+    #[case::unversioned_incoming(Some("Tag-t1:3"), None, true)]
+    fn it_should_upsert_only_when_the_event_is_not_yet_applied(
+        #[case] existing: Option<&str>,
+        #[case] incoming: Option<&str>,
+        #[case] written: bool,
+    ) {
+        let mut state = ListTagsState::default();
+        if let Some(existing) = existing {
+            let mut row = make_row();
+            row.name = "Renamed".to_string();
+            row.last_event_id = Some(existing.to_string());
+            state.rows.insert("t1".to_string(), row);
+        }
+        let mut incoming_row = make_row();
+        incoming_row.last_event_id = incoming.map(str::to_string);
+
+        assert_eq!(state.upsert(incoming_row), written);
+
+        let expected_name = if written { "Work" } else { "Renamed" };
+        assert_eq!(state.rows["t1"].name, expected_name);
+    }
 }
@@ -139,6 +139,7 @@ where
 
     async fn apply_stored_event(&self, stored_event: &StoredEvent<TagEvent>) -> anyhow::Result<()> {
         let mut state = self.store.state().await?.unwrap_or_default();
+        let version = stored_event.stream_version;
         for mutation in apply(
             &stored_event.stream_id,
             stored_event.stream_version,
@@ -146,7 +147,7 @@ where
         ) {
             match mutation {
                 Mutation::Upsert(row) => {
-                    state.rows.insert(row.tag_id.clone(), row);
+                    state.upsert(row);
                 }
                 Mutation::MarkDeleted {
                     tag_id,
@@ -154,7 +155,11 @@ where
                     deleted_by: _,
                     last_event_id,
                 } => {
-                    if let Some(row) = state.rows.get_mut(&tag_id) {
+                    if let Some(row) = state
+                        .rows
+                        .get_mut(&tag_id)
+                        .filter(|row| !row.has_applied(version))
+                    {
                         row.deleted = true;
                         row.last_event_id = Some(last_event_id);
                     }
@@ -164,7 +169,11 @@ where
                     name,
                     last_event_id,
                 } => {
-                    if let Some(row) = state.rows.get_mut(&tag_id) {
+                    if let Some(row) = state
+                        .rows
+                        .get_mut(&tag_id)
+                        .filter(|row| !row.has_applied(version))
+                    {
                         row.name = name;
                         row.last_event_id = Some(last_event_id);
                     }
@@ -174,7 +183,11 @@ where
                     color,
                     last_event_id,
                 } => {
-                    if let Some(row) = state.rows.get_mut(&tag_id) {
+                    if let Some(row) = state
+                        .rows
+                        .get_mut(&tag_id)
+                        .filter(|row| !row.has_applied(version))
+                    {
                         row.color = color;
                         row.last_event_id = Some(last_event_id);
                     }
@@ -184,7 +197,11 @@ where
                     description,
                     last_event_id,
                 } => {
-                    if let Some(row) = state.rows.get_mut(&tag_id) {
+                    if let Some(row) = state
+                        .rows
+                        .get_mut(&tag_id)
+                        .filter(|row| !row.has_applied(version))
+                    {
                         row.description = description;
                         row.last_event_id = Some(last_event_id);
                     }
@@ -802,4 +819,52 @@ mod list_tags_projector_tests {
         let projector = ListTagsProjector::new("p", projection_store, event_store, tech_tx);
         projector.run(receiver).await;
     }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_not_revert_rows_when_events_are_replayed() {
+        use crate::modules::tags::use_cases::set_tag_name::command::SetTagName;
+        use crate::modules::tags::use_cases::set_tag_name::handler::SetTagNameHandler;
+
+        let event_store = InMemoryEventStore::<TagEvent>::new();
+        create_one_tag(event_store.clone()).await;
+        SetTagNameHandler::new(event_store.clone())
+            .handle(
+                "Tag-t1",
+                SetTagName {
+                    tag_id: "t1".to_string(),
+                    tenant_id: "ten1".to_string(),
+                    name: "Renamed".to_string(),
+                    set_at: 2000,
+                    set_by: "u1".to_string(),
+                },
+            )
+            .await
+            .unwrap();
+
+        let projection_store = InMemoryProjectionStore::<ListTagsState>::new();
+        let (tech_tx, _) = broadcast::channel(16);
+        let (closed_tx, receiver) = broadcast::channel::<StoredEvent<TagEvent>>(16);
+        drop(closed_tx);
+        ListTagsProjector::new(
+            "p",
+            projection_store.clone(),
+            event_store.clone(),
+            tech_tx.clone(),
+        )
+        .run(receiver)
+        .await;
+
+        let projector =
+            ListTagsProjector::new("p", projection_store.clone(), event_store.clone(), tech_tx);
+
+        for stored_event in event_store.load_all_from(0).await.unwrap() {
+            projector.apply_stored_event(&stored_event).await.unwrap();
+        }
+
+        let state = projection_store.state().await.unwrap().unwrap();
+        let row = state.rows.get("t1").unwrap();
+        assert_eq!(row.name, "Renamed");
+        assert_eq!(row.last_event_id, Some("Tag-t1:2".to_string()));
+    }
 }
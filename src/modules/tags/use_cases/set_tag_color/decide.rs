@@ -1,8 +1,11 @@
 use crate::modules::tags::core::events::TagEvent;
 use crate::modules::tags::core::events::v1::tag_color_set::TagColorSetV1;
+use crate::modules::tags::core::evolve::evolve;
 use crate::modules::tags::core::state::TagState;
 use crate::modules::tags::use_cases::set_tag_color::command::SetTagColor;
 use crate::modules::tags::use_cases::set_tag_color::decision::{DecideError, Decision};
+use crate::shared::core::decider::Decider;
+use std::convert::Infallible;
 
 pub fn decide_set_color(state: &TagState, command: SetTagColor) -> Decision {
     match state {
@@ -16,6 +19,7 @@ pub fn decide_set_color(state: &TagState, command: SetTagColor) -> Decision {
                 set_at: command.set_at,
                 set_by: command.set_by,
             })],
+            intents: vec![],
         },
         TagState::None => Decision::Rejected {
             reason: DecideError::TagNotFound,
@@ -26,6 +30,28 @@ pub fn decide_set_color(state: &TagState, command: SetTagColor) -> Decision {
     }
 }
 
+pub struct SetTagColorDecider;
+
+impl Decider for SetTagColorDecider {
+    type State = TagState;
+    type Command = SetTagColor;
+    type Event = TagEvent;
+    type Intent = Infallible;
+    type Error = DecideError;
+
+    fn initial_state() -> TagState {
+        TagState::None
+    }
+
+    fn evolve(state: TagState, event: TagEvent) -> TagState {
+        evolve(state, event)
+    }
+
+    fn decide(state: &TagState, command: SetTagColor) -> Decision {
+        decide_set_color(state, command)
+    }
+}
+
 #[cfg(test)]
 mod set_tag_color_decide_tests {
     use super::*;
@@ -76,7 +102,7 @@ mod set_tag_color_decide_tests {
     fn created_state_accepts_set_color(command: SetTagColor) {
         let decision = decide_set_color(&created_state(), command);
         match decision {
-            Decision::Accepted { events } => {
+            Decision::Accepted { events, .. } => {
                 assert_eq!(events.len(), 1);
                 assert!(matches!(&events[0], TagEvent::TagColorSetV1(_)));
             }
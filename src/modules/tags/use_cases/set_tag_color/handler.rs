@@ -1,27 +1,21 @@
 use crate::modules::tags::core::events::TagEvent;
-use crate::modules::tags::core::evolve::evolve;
-use crate::modules::tags::core::state::TagState;
 use crate::modules::tags::use_cases::set_tag_color::command::SetTagColor;
-use crate::modules::tags::use_cases::set_tag_color::decide::decide_set_color;
-use crate::modules::tags::use_cases::set_tag_color::decision::{DecideError, Decision};
-use crate::shared::infrastructure::event_store::{EventStore, EventStoreError};
-use thiserror::Error;
+use crate::modules::tags::use_cases::set_tag_color::decide::SetTagColorDecider;
+use crate::modules::tags::use_cases::set_tag_color::decision::DecideError;
+use crate::shared::application::event_sourced_handler::{
+    EventSourcedError, EventSourcedHandler, NoIntents,
+};
+use crate::shared::infrastructure::event_store::EventStore;
+use std::convert::Infallible;
 
-#[derive(Debug, Error)]
-pub enum ApplicationError {
-    #[error(transparent)]
-    VersionConflict(#[from] EventStoreError),
-
-    #[error("domain error: {0}")]
-    Domain(DecideError),
-}
+pub type ApplicationError = EventSourcedError<DecideError, Infallible>;
 
 #[derive(Debug, Clone)]
 pub struct SetTagColorHandler<TEventStore>
 where
     TEventStore: EventStore<TagEvent> + Send + Sync + 'static,
 {
-    event_store: TEventStore,
+    inner: EventSourcedHandler<SetTagColorDecider, TEventStore, NoIntents>,
 }
 
 impl<TEventStore> SetTagColorHandler<TEventStore>
@@ -29,7 +23,9 @@ where
     TEventStore: EventStore<TagEvent> + Send + Sync + 'static,
 {
     pub fn new(event_store: TEventStore) -> Self {
-        Self { event_store }
+        Self {
+            inner: EventSourcedHandler::new(event_store, NoIntents),
+        }
     }
 
     pub async fn handle(
@@ -37,24 +33,7 @@ where
         stream_id: &str,
         command: SetTagColor,
     ) -> Result<(), ApplicationError> {
-        let stream = self
-            .event_store
-            .load(stream_id)
-            .await
-            .map_err(ApplicationError::VersionConflict)?;
-
-        let state = stream.events.iter().cloned().fold(TagState::None, evolve);
-
-        match decide_set_color(&state, command) {
-            Decision::Accepted { events } => {
-                self.event_store
-                    .append(stream_id, stream.version, &events)
-                    .await
-                    .map_err(ApplicationError::VersionConflict)?;
-                Ok(())
-            }
-            Decision::Rejected { reason } => Err(ApplicationError::Domain(reason)),
-        }
+        self.inner.handle(stream_id, command).await
     }
 }
 
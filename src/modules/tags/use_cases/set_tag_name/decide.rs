@@ -1,8 +1,11 @@
 use crate::modules::tags::core::events::TagEvent;
 use crate::modules::tags::core::events::v1::tag_name_set::TagNameSetV1;
+use crate::modules::tags::core::evolve::evolve;
 use crate::modules::tags::core::state::TagState;
 use crate::modules::tags::use_cases::set_tag_name::command::SetTagName;
 use crate::modules::tags::use_cases::set_tag_name::decision::{DecideError, Decision};
+use crate::shared::core::decider::Decider;
+use std::convert::Infallible;
 
 pub fn decide_set_name(state: &TagState, command: SetTagName) -> Decision {
     match state {
@@ -16,6 +19,7 @@ pub fn decide_set_name(state: &TagState, command: SetTagName) -> Decision {
                 set_at: command.set_at,
                 set_by: command.set_by,
             })],
+            intents: vec![],
         },
         TagState::None => Decision::Rejected {
             reason: DecideError::TagNotFound,
@@ -26,6 +30,28 @@ pub fn decide_set_name(state: &TagState, command: SetTagName) -> Decision {
     }
 }
 
+pub struct SetTagNameDecider;
+
+impl Decider for SetTagNameDecider {
+    type State = TagState;
+    type Command = SetTagName;
+    type Event = TagEvent;
+    type Intent = Infallible;
+    type Error = DecideError;
+
+    fn initial_state() -> TagState {
+        TagState::None
+    }
+
+    fn evolve(state: TagState, event: TagEvent) -> TagState {
+        evolve(state, event)
+    }
+
+    fn decide(state: &TagState, command: SetTagName) -> Decision {
+        decide_set_name(state, command)
+    }
+}
+
 #[cfg(test)]
 mod set_tag_name_decide_tests {
     use super::*;
@@ -76,7 +102,7 @@ mod set_tag_name_decide_tests {
     fn created_state_accepts_set_name(command: SetTagName) {
         let decision = decide_set_name(&created_state(), command);
         match decision {
-            Decision::Accepted { events } => {
+            Decision::Accepted { events, .. } => {
                 assert_eq!(events.len(), 1);
                 assert!(matches!(&events[0], TagEvent::TagNameSetV1(_)));
             }
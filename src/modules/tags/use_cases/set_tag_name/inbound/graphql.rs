@@ -10,6 +10,7 @@ mod set_tag_name_graphql_inbound_tests {
     use async_graphql::{EmptySubscription, Schema};
 
     use crate::modules::tags::use_cases::create_tag::command::{CreateTag, pick_pastel_color};
+    use crate::shared::auth::rbac::Scope;
     use crate::shared::infrastructure::request_context::RequestContext;
     use crate::shell::graphql::{MutationRoot, QueryRoot};
     use crate::tests::fixtures::tags::make_test_app_state;
@@ -30,6 +31,8 @@ mod set_tag_name_graphql_inbound_tests {
         RequestContext {
             user_id: "u-1".to_string(),
             tenant_id: "tenant-test".to_string(),
+            role: Default::default(),
+            scope: Default::default(),
         }
     }
 
@@ -72,6 +75,25 @@ mod set_tag_name_graphql_inbound_tests {
         assert_eq!(result.data.to_string(), "{setTagName: true}");
     }
 
+    #[tokio::test]
+    async fn returns_forbidden_for_read_only_api_keys() {
+        let state = make_test_app_state();
+        let tag_id = seed_tag(&state).await;
+        let schema = make_schema_from_state(state);
+        let result = schema
+            .execute(
+                async_graphql::Request::new(format!(
+                    r#"mutation {{ setTagName(tagId: "{tag_id}", name: "new-name") }}"#
+                ))
+                .data(RequestContext {
+                    scope: Scope::ReadOnly,
+                    ..req_ctx()
+                }),
+            )
+            .await;
+        assert_eq!(result.errors[0].message, "Forbidden");
+    }
+
     #[tokio::test]
     async fn returns_error_when_event_store_offline() {
         let state = make_test_app_state();
@@ -103,6 +125,9 @@ impl SetTagNameMutation {
         let req_ctx = context
             .data::<RequestContext>()
             .map_err(|_| async_graphql::Error::new("Unauthorized"))?;
+        if !req_ctx.principal().can_write() {
+            return Err(async_graphql::Error::new("Forbidden"));
+        }
         let state = context.data_unchecked::<AppState>();
         let stream_id = format!("Tag-{tag_id}");
         let command = SetTagName {
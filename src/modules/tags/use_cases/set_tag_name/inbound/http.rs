@@ -23,6 +23,9 @@ pub async fn handle(
     Path(tag_id): Path<String>,
     body: Result<Json<SetTagNameBody>, JsonRejection>,
 ) -> impl IntoResponse {
+    if !request_ctx.principal().can_write() {
+        return StatusCode::FORBIDDEN.into_response();
+    }
     let Json(body) = match body {
         Ok(b) => b,
         Err(_) => return StatusCode::UNPROCESSABLE_ENTITY.into_response(),
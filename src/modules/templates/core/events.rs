@@ -0,0 +1,28 @@
+use crate::shared::core::domain_event::DomainEvent;
+
+pub mod v1 {
+    pub mod template_saved;
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, PartialEq, Eq, DomainEvent)]
+#[serde(tag = "type")]
+pub enum TemplateEvent {
+    TemplateSavedV1(v1::template_saved::TemplateSavedV1),
+}
+
+#[cfg(test)]
+mod template_event_schema_tests {
+    use super::*;
+    use crate::tests::golden::{assert_catalog, assert_event_goldens};
+    use rstest::rstest;
+
+    #[rstest]
+    fn every_version_should_match_its_golden() {
+        assert_event_goldens::<TemplateEvent>("templates");
+    }
+
+    #[rstest]
+    fn the_catalog_should_list_every_version() {
+        assert_catalog::<TemplateEvent>();
+    }
+}
@@ -0,0 +1,53 @@
+use crate::modules::time_entries::core::tag::Tag;
+
+/// A user's preset for entries they register often. Saving again under the same
+/// `template_id` replaces every field.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, PartialEq, Eq)]
+pub struct TemplateSavedV1 {
+    pub template_id: String,
+    pub tenant_id: String,
+    pub user_id: String,
+    pub name: String,
+    pub tags: Vec<Tag>,
+    pub description: Option<String>,
+    pub project: Option<String>,
+    pub default_duration_ms: i64,
+    pub saved_at: i64,
+}
+
+#[cfg(test)]
+mod template_saved_event_tests {
+    use super::*;
+    use rstest::{fixture, rstest};
+
+    #[fixture]
+    fn event() -> TemplateSavedV1 {
+        TemplateSavedV1 {
+            template_id: "template-fixed-0001".to_string(),
+            tenant_id: "tenant-hardcoded".to_string(),
+            user_id: "user-fixed-0001".to_string(),
+            name: "Standup".to_string(),
+            tags: vec![Tag::parse("meeting").unwrap()],
+            description: Some("Daily standup".to_string()),
+            project: Some("internal".to_string()),
+            default_duration_ms: 900_000,
+            saved_at: 1700000000000,
+        }
+    }
+
+    #[rstest]
+    fn it_should_have_correct_fields(event: TemplateSavedV1) {
+        assert_eq!(event.template_id, "template-fixed-0001");
+        assert_eq!(event.name, "Standup");
+        assert_eq!(event.default_duration_ms, 900_000);
+    }
+
+    #[rstest]
+    fn it_serializes_stable(event: TemplateSavedV1) {
+        let golden: serde_json::Value = serde_json::from_str(include_str!(
+            "../../../../../tests/fixtures/events/json/template_saved_v1.json"
+        ))
+        .unwrap();
+        assert_eq!(serde_json::to_value(&event).unwrap(), golden);
+    }
+}
@@ -0,0 +1,56 @@
+use crate::modules::templates::core::events::TemplateEvent;
+use crate::modules::templates::core::state::TemplateState;
+
+pub fn evolve(_state: TemplateState, event: TemplateEvent) -> TemplateState {
+    match event {
+        TemplateEvent::TemplateSavedV1(e) => TemplateState::Saved {
+            template_id: e.template_id,
+            tenant_id: e.tenant_id,
+            user_id: e.user_id,
+            name: e.name,
+            tags: e.tags,
+            description: e.description,
+            project: e.project,
+            default_duration_ms: e.default_duration_ms,
+        },
+    }
+}
+
+#[cfg(test)]
+mod template_evolve_tests {
+    use super::*;
+    use crate::modules::templates::core::events::v1::template_saved::TemplateSavedV1;
+    use rstest::rstest;
+
+    fn saved(name: &str, default_duration_ms: i64) -> TemplateEvent {
+        TemplateEvent::TemplateSavedV1(TemplateSavedV1 {
+            template_id: "tpl-1".to_string(),
+            tenant_id: "ten1".to_string(),
+            user_id: "u1".to_string(),
+            name: name.to_string(),
+            tags: vec![],
+            description: None,
+            project: None,
+            default_duration_ms,
+            saved_at: 1000,
+        })
+    }
+
+    #[rstest]
+    fn a_later_save_should_replace_the_template() {
+        let state = [saved("Standup", 900_000), saved("Review", 3_600_000)]
+            .into_iter()
+            .fold(TemplateState::None, evolve);
+
+        let TemplateState::Saved {
+            name,
+            default_duration_ms,
+            ..
+        } = state
+        else {
+            panic!("expected Saved");
+        };
+        assert_eq!(name, "Review");
+        assert_eq!(default_duration_ms, 3_600_000);
+    }
+}
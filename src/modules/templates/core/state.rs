@@ -0,0 +1,31 @@
+use crate::modules::time_entries::core::tag::Tag;
+
+pub fn template_stream_id(template_id: &str) -> String {
+    format!("Template-{template_id}")
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TemplateState {
+    None,
+    Saved {
+        template_id: String,
+        tenant_id: String,
+        user_id: String,
+        name: String,
+        tags: Vec<Tag>,
+        description: Option<String>,
+        project: Option<String>,
+        default_duration_ms: i64,
+    },
+}
+
+#[cfg(test)]
+mod template_state_tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    fn it_should_name_the_stream_after_the_template() {
+        assert_eq!(template_stream_id("tpl-1"), "Template-tpl-1");
+    }
+}
@@ -0,0 +1,268 @@
+// Registers an entry from a saved template: it starts on the given date at the given time of
+// day, in UTC, lasts the template's default duration and carries its tags. The template's
+// description and project stay with the template, since entries do not record either.
+
+use async_graphql::{Context, InputObject, Object, Result as GqlResult, SimpleObject};
+use chrono::{NaiveDate, NaiveTime};
+
+use crate::modules::templates::core::state::TemplateState;
+use crate::modules::time_entries::use_cases::set_ended_at::command::SetEndedAt;
+use crate::modules::time_entries::use_cases::set_started_at::command::SetStartedAt;
+use crate::modules::time_entries::use_cases::set_time_entry_tags::command::SetTimeEntryTags;
+use crate::shared::core::primitives::TimeEntryId;
+use crate::shared::infrastructure::request_context::RequestContext;
+use crate::shell::state::AppState;
+
+const DEFAULT_START_TIME: &str = "09:00";
+
+#[derive(InputObject)]
+pub struct ApplyTemplateInput {
+    pub template_id: String,
+    pub time_entry_id: String,
+    /// The day to register the entry on, as `YYYY-MM-DD`.
+    pub date: String,
+    /// When the entry starts, as `HH:MM` in UTC; 09:00 when absent.
+    pub start_time: Option<String>,
+}
+
+#[derive(SimpleObject)]
+#[graphql(name = "ApplyTemplatePayload")]
+pub struct GqlApplyTemplatePayload {
+    pub time_entry_id: String,
+    pub started_at: i64,
+    pub ended_at: i64,
+    pub tags: Vec<String>,
+}
+
+#[derive(Default)]
+pub struct ApplyTemplateMutation;
+
+#[Object]
+impl ApplyTemplateMutation {
+    /// Registers an entry on `date` from one of the caller's templates.
+    async fn apply_template(
+        &self,
+        context: &Context<'_>,
+        input: ApplyTemplateInput,
+    ) -> GqlResult<GqlApplyTemplatePayload> {
+        let time_entry_id = TimeEntryId::parse_v7(&input.time_entry_id)
+            .map_err(|_| async_graphql::Error::new("time_entry_id must be a valid UUID v7"))?;
+        let date: NaiveDate = input
+            .date
+            .parse()
+            .map_err(|_| async_graphql::Error::new("date must be YYYY-MM-DD"))?;
+        let start_time = NaiveTime::parse_from_str(
+            input.start_time.as_deref().unwrap_or(DEFAULT_START_TIME),
+            "%H:%M",
+        )
+        .map_err(|_| async_graphql::Error::new("startTime must be HH:MM"))?;
+        let req_ctx = context
+            .data::<RequestContext>()
+            .map_err(|_| async_graphql::Error::new("Unauthorized"))?;
+        if !req_ctx.principal().can_register_for(&req_ctx.user_id) {
+            return Err(async_graphql::Error::new("Forbidden"));
+        }
+        let state = context.data_unchecked::<AppState>();
+        let template = state
+            .save_template_handler
+            .template(&input.template_id)
+            .await
+            .map_err(|e| async_graphql::Error::new(e.to_string()))?;
+        let TemplateState::Saved {
+            tenant_id,
+            user_id,
+            tags,
+            default_duration_ms,
+            ..
+        } = template
+        else {
+            return Err(async_graphql::Error::new("template not found"));
+        };
+        if tenant_id != req_ctx.tenant_id || user_id != req_ctx.user_id {
+            return Err(async_graphql::Error::new("template not found"));
+        }
+
+        let started_at = date.and_time(start_time).and_utc().timestamp_millis();
+        let ended_at = started_at + default_duration_ms;
+        let stream_id = state
+            .stream_naming
+            .time_entry(Some(&req_ctx.tenant_id), &time_entry_id);
+        state
+            .set_started_at_handler
+            .handle_for_tenant(
+                &req_ctx.tenant_id,
+                &stream_id,
+                SetStartedAt::new(
+                    time_entry_id.clone(),
+                    req_ctx.user_id.clone().into(),
+                    started_at,
+                ),
+            )
+            .await
+            .map_err(|e| async_graphql::Error::new(e.to_string()))?;
+        state
+            .set_ended_at_handler
+            .handle_for_tenant(
+                &req_ctx.tenant_id,
+                &stream_id,
+                SetEndedAt::new(
+                    time_entry_id.clone(),
+                    req_ctx.user_id.clone().into(),
+                    ended_at,
+                ),
+            )
+            .await
+            .map_err(|e| async_graphql::Error::new(e.to_string()))?;
+        if !tags.is_empty() {
+            state
+                .set_time_entry_tags_handler
+                .handle_for_tenant(
+                    &req_ctx.tenant_id,
+                    &stream_id,
+                    SetTimeEntryTags::new(
+                        time_entry_id,
+                        req_ctx.user_id.clone().into(),
+                        tags.clone(),
+                    ),
+                )
+                .await
+                .map_err(|e| async_graphql::Error::new(e.to_string()))?;
+        }
+
+        Ok(GqlApplyTemplatePayload {
+            time_entry_id: input.time_entry_id,
+            started_at,
+            ended_at,
+            tags: tags.iter().map(|tag| tag.as_str().to_string()).collect(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod apply_template_graphql_inbound_tests {
+    use async_graphql::{EmptySubscription, Schema};
+
+    use crate::modules::templates::use_cases::save_template::command::SaveTemplate;
+    use crate::modules::time_entries::core::tag::Tag;
+    use crate::shared::core::stream_naming::{DefaultStreamNaming, StreamNaming};
+    use crate::shared::infrastructure::event_store::EventStore;
+    use crate::shared::infrastructure::request_context::RequestContext;
+    use crate::shell::graphql::{MutationRoot, QueryRoot};
+    use crate::shell::state::AppState;
+    use crate::tests::fixtures::tags::make_test_app_state;
+
+    fn make_schema_from_state(
+        state: AppState,
+    ) -> Schema<QueryRoot, MutationRoot, EmptySubscription> {
+        Schema::build(
+            QueryRoot::default(),
+            MutationRoot::default(),
+            EmptySubscription,
+        )
+        .data(state)
+        .finish()
+    }
+
+    fn req_ctx(user_id: &str) -> RequestContext {
+        RequestContext {
+            user_id: user_id.to_string(),
+            tenant_id: "tenant-test".to_string(),
+            role: Default::default(),
+            scope: Default::default(),
+        }
+    }
+
+    async fn state_with_template() -> AppState {
+        let state = make_test_app_state();
+        state
+            .save_template_handler
+            .handle(SaveTemplate {
+                template_id: "tpl-1".to_string(),
+                tenant_id: "tenant-test".to_string(),
+                user_id: "u-1".to_string(),
+                name: "Standup".to_string(),
+                tags: vec![Tag::parse("meeting").unwrap()],
+                description: Some("Daily standup".to_string()),
+                project: Some("internal".to_string()),
+                default_duration_ms: 900_000,
+                saved_at: 0,
+            })
+            .await
+            .unwrap();
+        state
+    }
+
+    fn apply(te_id: &str, start_time: &str) -> String {
+        format!(
+            r#"mutation {{ applyTemplate(input: {{ templateId: "tpl-1", timeEntryId: "{te_id}", date: "2026-03-02"{start_time} }}) {{ timeEntryId startedAt endedAt tags }} }}"#
+        )
+    }
+
+    #[tokio::test]
+    async fn registers_an_entry_from_the_template() {
+        let te_id = uuid::Uuid::now_v7().to_string();
+        let state = state_with_template().await;
+        let schema = make_schema_from_state(state.clone());
+
+        let result = schema
+            .execute(
+                async_graphql::Request::new(apply(&te_id, r#", startTime: "13:30""#))
+                    .data(req_ctx("u-1")),
+            )
+            .await;
+
+        assert!(result.errors.is_empty(), "{:?}", result.errors);
+        // 2026-03-02T13:30:00Z, plus fifteen minutes.
+        assert_eq!(
+            result.data.to_string(),
+            format!(
+                r#"{{applyTemplate: {{timeEntryId: "{te_id}", startedAt: 1772458200000, endedAt: 1772459100000, tags: ["meeting"]}}}}"#
+            )
+        );
+        let stream_id = DefaultStreamNaming.time_entry(None, &te_id);
+        assert!(state.event_store.load(&stream_id).await.unwrap().version > 0);
+    }
+
+    #[tokio::test]
+    async fn starts_at_nine_by_default() {
+        let te_id = uuid::Uuid::now_v7().to_string();
+        let schema = make_schema_from_state(state_with_template().await);
+
+        let result = schema
+            .execute(async_graphql::Request::new(apply(&te_id, "")).data(req_ctx("u-1")))
+            .await;
+
+        assert!(result.errors.is_empty(), "{:?}", result.errors);
+        let data = result.data.into_json().unwrap();
+        // 2026-03-02T09:00:00Z
+        assert_eq!(data["applyTemplate"]["startedAt"], 1772442000000_i64);
+    }
+
+    #[tokio::test]
+    async fn hides_templates_of_other_users() {
+        let te_id = uuid::Uuid::now_v7().to_string();
+        let schema = make_schema_from_state(state_with_template().await);
+
+        let result = schema
+            .execute(async_graphql::Request::new(apply(&te_id, "")).data(req_ctx("u-2")))
+            .await;
+
+        assert_eq!(result.errors[0].message, "template not found");
+    }
+
+    #[tokio::test]
+    async fn returns_error_for_invalid_input() {
+        let schema = make_schema_from_state(state_with_template().await);
+        let te_id = uuid::Uuid::now_v7().to_string();
+        for query in [
+            apply("not-a-uuid", ""),
+            apply(&te_id, r#", startTime: "25:00""#),
+            apply(&te_id, "").replace("2026-03-02", "02-03-2026"),
+        ] {
+            let result = schema
+                .execute(async_graphql::Request::new(query.clone()).data(req_ctx("u-1")))
+                .await;
+            assert!(!result.errors.is_empty(), "{query}");
+        }
+    }
+}
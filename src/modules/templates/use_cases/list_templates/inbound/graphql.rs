@@ -0,0 +1,126 @@
+use async_graphql::{Context, Object, Result as GqlResult};
+
+use crate::modules::templates::use_cases::list_templates::projection::TemplateView;
+use crate::shared::infrastructure::request_context::RequestContext;
+use crate::shell::state::AppState;
+
+#[derive(async_graphql::SimpleObject, Clone)]
+#[graphql(name = "Template")]
+pub struct GqlTemplate {
+    pub template_id: String,
+    pub name: String,
+    pub tags: Vec<String>,
+    pub description: Option<String>,
+    pub project: Option<String>,
+    pub default_duration_minutes: i64,
+}
+
+impl From<TemplateView> for GqlTemplate {
+    fn from(v: TemplateView) -> Self {
+        Self {
+            template_id: v.template_id,
+            name: v.name,
+            tags: v.tags,
+            description: v.description,
+            project: v.project,
+            default_duration_minutes: v.default_duration_ms / 60_000,
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct ListTemplatesQuery;
+
+#[Object]
+impl ListTemplatesQuery {
+    /// The caller's own templates, by name.
+    async fn list_templates(&self, context: &Context<'_>) -> GqlResult<Vec<GqlTemplate>> {
+        let req_ctx = context
+            .data::<RequestContext>()
+            .map_err(|_| async_graphql::Error::new("Unauthorized"))?;
+        let state = context.data_unchecked::<AppState>();
+        let templates = state
+            .list_templates_handler
+            .list_for_user(&req_ctx.tenant_id, &req_ctx.user_id)
+            .await?;
+        Ok(templates.into_iter().map(Into::into).collect())
+    }
+}
+
+#[cfg(test)]
+mod list_templates_graphql_inbound_tests {
+    use async_graphql::{EmptySubscription, Schema};
+
+    use crate::modules::templates::use_cases::list_templates::projection::{
+        ListTemplatesState, TemplateRow,
+    };
+    use crate::modules::templates::use_cases::list_templates::queries::ListTemplatesQueryHandler;
+    use crate::shared::infrastructure::projection_store::ProjectionStore;
+    use crate::shared::infrastructure::projection_store::in_memory::InMemoryProjectionStore;
+    use crate::shared::infrastructure::request_context::RequestContext;
+    use crate::shell::graphql::{MutationRoot, QueryRoot};
+    use crate::shell::state::AppState;
+    use crate::tests::fixtures::tags::make_test_app_state;
+
+    fn make_schema_from_state(
+        state: AppState,
+    ) -> Schema<QueryRoot, MutationRoot, EmptySubscription> {
+        Schema::build(
+            QueryRoot::default(),
+            MutationRoot::default(),
+            EmptySubscription,
+        )
+        .data(state)
+        .finish()
+    }
+
+    fn req_ctx() -> RequestContext {
+        RequestContext {
+            user_id: "u-1".to_string(),
+            tenant_id: "tenant-test".to_string(),
+            role: Default::default(),
+            scope: Default::default(),
+        }
+    }
+
+    #[tokio::test]
+    async fn resolver_returns_the_callers_templates() {
+        let mut state = make_test_app_state();
+        let store = InMemoryProjectionStore::<ListTemplatesState>::new();
+        let mut projection = ListTemplatesState::default();
+        for (template_id, user_id) in [("tpl-1", "u-1"), ("tpl-2", "u-2")] {
+            projection.rows.insert(
+                template_id.to_string(),
+                TemplateRow {
+                    template_id: template_id.to_string(),
+                    tenant_id: "tenant-test".to_string(),
+                    user_id: user_id.to_string(),
+                    name: "Standup".to_string(),
+                    tags: vec!["meeting".to_string()],
+                    description: None,
+                    project: Some("internal".to_string()),
+                    default_duration_ms: 900_000,
+                    last_event_id: None,
+                },
+            );
+        }
+        store.save(projection, 1).await.unwrap();
+        state.list_templates_handler = ListTemplatesQueryHandler::new(store);
+        let schema = make_schema_from_state(state);
+
+        let result = schema
+            .execute(
+                async_graphql::Request::new(
+                    r#"{ listTemplates { templateId tags project defaultDurationMinutes } }"#,
+                )
+                .data(req_ctx()),
+            )
+            .await;
+
+        assert!(result.errors.is_empty(), "{:?}", result.errors);
+        assert_eq!(
+            result.data.to_string(),
+            r#"{listTemplates: [{templateId: "tpl-1", tags: ["meeting"], project: "internal", defaultDurationMinutes: 15}]}"#
+        );
+    }
+}
@@ -0,0 +1,130 @@
+use crate::modules::templates::core::events::TemplateEvent;
+use crate::shared::core::primitives::last_event_version;
+use std::collections::HashMap;
+
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// Every saved template, keyed by template id.
+#[derive(Clone, Default)]
+pub struct ListTemplatesState {
+    pub rows: HashMap<String, TemplateRow>,
+}
+
+impl ListTemplatesState {
+    /// Applies the event at `version` of `stream_id`, unless its row already reflects it.
+    pub fn apply(&mut self, stream_id: &str, version: i64, event: &TemplateEvent) {
+        match event {
+            TemplateEvent::TemplateSavedV1(e) => {
+                if self
+                    .rows
+                    .get(&e.template_id)
+                    .is_some_and(|row| row.has_applied(version))
+                {
+                    return;
+                }
+                self.rows.insert(
+                    e.template_id.clone(),
+                    TemplateRow {
+                        template_id: e.template_id.clone(),
+                        tenant_id: e.tenant_id.clone(),
+                        user_id: e.user_id.clone(),
+                        name: e.name.clone(),
+                        tags: e.tags.iter().map(|tag| tag.as_str().to_string()).collect(),
+                        description: e.description.clone(),
+                        project: e.project.clone(),
+                        default_duration_ms: e.default_duration_ms,
+                        last_event_id: Some(format!("{stream_id}:{version}")),
+                    },
+                );
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct TemplateRow {
+    pub template_id: String,
+    pub tenant_id: String,
+    pub user_id: String,
+    pub name: String,
+    pub tags: Vec<String>,
+    pub description: Option<String>,
+    pub project: Option<String>,
+    pub default_duration_ms: i64,
+    pub last_event_id: Option<String>,
+}
+
+impl TemplateRow {
+    /// Whether the event at `stream_version` is already reflected in this row.
+    pub fn has_applied(&self, stream_version: i64) -> bool {
+        last_event_version(self.last_event_id.as_deref())
+            .is_some_and(|applied| applied >= stream_version)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct TemplateView {
+    pub template_id: String,
+    pub name: String,
+    pub tags: Vec<String>,
+    pub description: Option<String>,
+    pub project: Option<String>,
+    pub default_duration_ms: i64,
+}
+
+impl From<TemplateRow> for TemplateView {
+    fn from(row: TemplateRow) -> Self {
+        Self {
+            template_id: row.template_id,
+            name: row.name,
+            tags: row.tags,
+            description: row.description,
+            project: row.project,
+            default_duration_ms: row.default_duration_ms,
+        }
+    }
+}
+
+#[cfg(test)]
+mod list_templates_projection_model_tests {
+    use super::*;
+    use crate::modules::templates::core::events::v1::template_saved::TemplateSavedV1;
+    use crate::modules::time_entries::core::tag::Tag;
+    use rstest::rstest;
+
+    fn saved(name: &str) -> TemplateEvent {
+        TemplateEvent::TemplateSavedV1(TemplateSavedV1 {
+            template_id: "tpl-1".to_string(),
+            tenant_id: "ten1".to_string(),
+            user_id: "u1".to_string(),
+            name: name.to_string(),
+            tags: vec![Tag::parse("meeting").unwrap()],
+            description: None,
+            project: Some("internal".to_string()),
+            default_duration_ms: 900_000,
+            saved_at: 1000,
+        })
+    }
+
+    #[rstest]
+    fn it_should_keep_the_latest_save_however_often_events_are_replayed() {
+        let mut state = ListTemplatesState::default();
+
+        state.apply("Template-tpl-1", 1, &saved("Standup"));
+        state.apply("Template-tpl-1", 2, &saved("Daily"));
+        state.apply("Template-tpl-1", 1, &saved("Standup"));
+
+        let view = TemplateView::from(state.rows["tpl-1"].clone());
+        assert_eq!(
+            view,
+            TemplateView {
+                template_id: "tpl-1".to_string(),
+                name: "Daily".to_string(),
+                tags: vec!["meeting".to_string()],
+                description: None,
+                project: Some("internal".to_string()),
+                default_duration_ms: 900_000,
+            }
+        );
+    }
+}
@@ -0,0 +1,188 @@
+use crate::modules::templates::core::events::TemplateEvent;
+use crate::modules::templates::use_cases::list_templates::projection::{
+    ListTemplatesState, SCHEMA_VERSION,
+};
+use crate::modules::time_entries::use_cases::list_time_entries::projector::ProjectionTechnicalEvent;
+use crate::shared::infrastructure::event_store::StoredEvent;
+use crate::shared::infrastructure::event_store::in_memory::InMemoryEventStore;
+use crate::shared::infrastructure::projection_store::ProjectionStore;
+use tokio::sync::broadcast;
+
+/// Keeps the templates list in step with the template feed. It rebuilds from the event log
+/// on a schema change and whenever it falls behind the channel.
+pub struct ListTemplatesProjector<TStore>
+where
+    TStore: ProjectionStore<ListTemplatesState> + Send + Sync + 'static,
+{
+    pub name: String,
+    pub store: TStore,
+    pub event_store: InMemoryEventStore<TemplateEvent>,
+    pub technical_tx: broadcast::Sender<ProjectionTechnicalEvent>,
+}
+
+impl<TStore> ListTemplatesProjector<TStore>
+where
+    TStore: ProjectionStore<ListTemplatesState> + Send + Sync + 'static,
+{
+    pub fn new(
+        name: impl Into<String>,
+        store: TStore,
+        event_store: InMemoryEventStore<TemplateEvent>,
+        technical_tx: broadcast::Sender<ProjectionTechnicalEvent>,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            store,
+            event_store,
+            technical_tx,
+        }
+    }
+
+    pub async fn run(self, mut receiver: broadcast::Receiver<StoredEvent<TemplateEvent>>) {
+        let stored_schema = self.store.schema_version().await.unwrap_or(None);
+        if stored_schema != Some(SCHEMA_VERSION)
+            && let Err(reason) = self.rebuild().await
+        {
+            self.rebuild_failed(reason);
+            return;
+        }
+
+        loop {
+            match receiver.recv().await {
+                Ok(stored_event) => {
+                    let checkpoint = self.store.checkpoint().await.unwrap_or(0);
+                    if stored_event.global_position < checkpoint {
+                        continue;
+                    }
+                    let start = std::time::Instant::now();
+                    if self.apply_stored_event(&stored_event).await.is_err() {
+                        continue;
+                    }
+                    let _ = self
+                        .technical_tx
+                        .send(ProjectionTechnicalEvent::EventApplied {
+                            projection_name: self.name.clone(),
+                            checkpoint: stored_event.global_position + 1,
+                            duration_ms: start.elapsed().as_millis() as u64,
+                        });
+                }
+                Err(broadcast::error::RecvError::Lagged(_)) => {
+                    if let Err(reason) = self.rebuild().await {
+                        self.rebuild_failed(reason);
+                        return;
+                    }
+                }
+                Err(broadcast::error::RecvError::Closed) => break,
+            }
+        }
+    }
+
+    pub async fn rebuild(&self) -> anyhow::Result<()> {
+        let start = std::time::Instant::now();
+        let _ = self
+            .technical_tx
+            .send(ProjectionTechnicalEvent::RebuildStarted {
+                projection_name: self.name.clone(),
+                schema_version: SCHEMA_VERSION,
+                timestamp: chrono::Utc::now().timestamp_millis(),
+            });
+        self.store.clear().await?;
+        let all_events = self.event_store.load_all_from(0).await?;
+        let events_replayed = all_events.len() as u64;
+        for stored_event in all_events {
+            self.apply_stored_event(&stored_event).await?;
+        }
+        self.store.save_schema_version(SCHEMA_VERSION).await?;
+        let _ = self
+            .technical_tx
+            .send(ProjectionTechnicalEvent::RebuildCompleted {
+                projection_name: self.name.clone(),
+                events_replayed,
+                duration_ms: start.elapsed().as_millis() as u64,
+                timestamp: chrono::Utc::now().timestamp_millis(),
+            });
+        Ok(())
+    }
+
+    fn rebuild_failed(&self, reason: anyhow::Error) {
+        let _ = self
+            .technical_tx
+            .send(ProjectionTechnicalEvent::RebuildFailed {
+                projection_name: self.name.clone(),
+                reason: reason.to_string(),
+                timestamp: chrono::Utc::now().timestamp_millis(),
+            });
+    }
+
+    async fn apply_stored_event(
+        &self,
+        stored_event: &StoredEvent<TemplateEvent>,
+    ) -> anyhow::Result<()> {
+        let mut state = self.store.state().await?.unwrap_or_default();
+        state.apply(
+            &stored_event.stream_id,
+            stored_event.stream_version,
+            &stored_event.event,
+        );
+        self.store
+            .save(state, stored_event.global_position + 1)
+            .await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod list_templates_projector_tests {
+    use super::*;
+    use crate::modules::templates::use_cases::save_template::command::SaveTemplate;
+    use crate::modules::templates::use_cases::save_template::handler::SaveTemplateHandler;
+    use crate::shared::infrastructure::projection_store::in_memory::InMemoryProjectionStore;
+    use rstest::rstest;
+
+    async fn save(event_store: InMemoryEventStore<TemplateEvent>, template_id: &str, name: &str) {
+        SaveTemplateHandler::new(event_store)
+            .handle(SaveTemplate {
+                template_id: template_id.to_string(),
+                tenant_id: "ten1".to_string(),
+                user_id: "u1".to_string(),
+                name: name.to_string(),
+                tags: vec![],
+                description: None,
+                project: None,
+                default_duration_ms: 900_000,
+                saved_at: 1000,
+            })
+            .await
+            .unwrap();
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_rebuild_then_follow_the_feed() {
+        let (tx, _) = broadcast::channel::<StoredEvent<TemplateEvent>>(16);
+        let event_store = InMemoryEventStore::<TemplateEvent>::new_with_sender(tx.clone());
+        save(event_store.clone(), "tpl-1", "Standup").await;
+        let projection_store = InMemoryProjectionStore::<ListTemplatesState>::new();
+        let (tech_tx, mut tech_rx) = broadcast::channel(16);
+        let projector = ListTemplatesProjector::new(
+            "list_templates",
+            projection_store.clone(),
+            event_store.clone(),
+            tech_tx,
+        );
+        let running = tokio::spawn(projector.run(tx.subscribe()));
+
+        save(event_store.clone(), "tpl-1", "Daily").await;
+        save(event_store, "tpl-2", "Review").await;
+        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+        running.abort();
+
+        let state = projection_store.state().await.unwrap().unwrap();
+        assert_eq!(state.rows["tpl-1"].name, "Daily");
+        assert_eq!(state.rows["tpl-2"].name, "Review");
+        assert!(matches!(
+            tech_rx.try_recv(),
+            Ok(ProjectionTechnicalEvent::RebuildStarted { .. })
+        ));
+    }
+}
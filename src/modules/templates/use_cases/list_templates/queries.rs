@@ -0,0 +1,97 @@
+use crate::modules::templates::use_cases::list_templates::projection::{
+    ListTemplatesState, TemplateView,
+};
+use crate::shared::infrastructure::projection_store::ProjectionStore;
+
+#[derive(Clone)]
+pub struct ListTemplatesQueryHandler<TStore>
+where
+    TStore: ProjectionStore<ListTemplatesState> + Send + Sync + 'static,
+{
+    store: TStore,
+}
+
+impl<TStore> ListTemplatesQueryHandler<TStore>
+where
+    TStore: ProjectionStore<ListTemplatesState> + Send + Sync + 'static,
+{
+    pub fn new(store: TStore) -> Self {
+        Self { store }
+    }
+
+    /// The templates `user_id` saved in `tenant_id`, by name.
+    pub async fn list_for_user(
+        &self,
+        tenant_id: &str,
+        user_id: &str,
+    ) -> anyhow::Result<Vec<TemplateView>> {
+        let state = self.store.state().await?.unwrap_or_default();
+        let mut items: Vec<_> = state
+            .rows
+            .into_values()
+            .filter(|row| row.tenant_id == tenant_id && row.user_id == user_id)
+            .map(TemplateView::from)
+            .collect();
+        items.sort_by(|a, b| {
+            a.name
+                .cmp(&b.name)
+                .then_with(|| a.template_id.cmp(&b.template_id))
+        });
+        Ok(items)
+    }
+}
+
+#[cfg(test)]
+mod list_templates_query_handler_tests {
+    use super::*;
+    use crate::modules::templates::use_cases::list_templates::projection::TemplateRow;
+    use crate::shared::infrastructure::projection_store::in_memory::InMemoryProjectionStore;
+    use rstest::rstest;
+
+    fn row(template_id: &str, user_id: &str, name: &str) -> TemplateRow {
+        TemplateRow {
+            template_id: template_id.to_string(),
+            tenant_id: "ten1".to_string(),
+            user_id: user_id.to_string(),
+            name: name.to_string(),
+            tags: vec![],
+            description: None,
+            project: None,
+            default_duration_ms: 900_000,
+            last_event_id: None,
+        }
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_list_the_users_own_templates_by_name() {
+        let store = InMemoryProjectionStore::<ListTemplatesState>::new();
+        let mut state = ListTemplatesState::default();
+        for row in [
+            row("tpl-1", "u1", "Standup"),
+            row("tpl-2", "u2", "Review"),
+            row("tpl-3", "u1", "Planning"),
+        ] {
+            state.rows.insert(row.template_id.clone(), row);
+        }
+        store.save(state, 3).await.unwrap();
+        let handler = ListTemplatesQueryHandler::new(store);
+
+        let names: Vec<_> = handler
+            .list_for_user("ten1", "u1")
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|view| view.name)
+            .collect();
+
+        assert_eq!(names, vec!["Planning", "Standup"]);
+        assert!(
+            handler
+                .list_for_user("ten2", "u1")
+                .await
+                .unwrap()
+                .is_empty()
+        );
+    }
+}
@@ -0,0 +1,14 @@
+use crate::modules::time_entries::core::tag::Tag;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SaveTemplate {
+    pub template_id: String,
+    pub tenant_id: String,
+    pub user_id: String,
+    pub name: String,
+    pub tags: Vec<Tag>,
+    pub description: Option<String>,
+    pub project: Option<String>,
+    pub default_duration_ms: i64,
+    pub saved_at: i64,
+}
@@ -0,0 +1,137 @@
+use crate::modules::templates::core::events::TemplateEvent;
+use crate::modules::templates::core::events::v1::template_saved::TemplateSavedV1;
+use crate::modules::templates::core::evolve::evolve;
+use crate::modules::templates::core::state::TemplateState;
+use crate::modules::templates::use_cases::save_template::command::SaveTemplate;
+use crate::modules::templates::use_cases::save_template::decision::{DecideError, Decision};
+use crate::shared::core::decider::Decider;
+use std::convert::Infallible;
+
+const DAY_MS: i64 = 24 * 60 * 60 * 1000;
+
+/// Saves a new template or replaces one of the user's own.
+pub fn decide_save(state: &TemplateState, command: SaveTemplate) -> Decision {
+    if let TemplateState::Saved {
+        tenant_id, user_id, ..
+    } = state
+        && (*tenant_id != command.tenant_id || *user_id != command.user_id)
+    {
+        return Decision::Rejected {
+            reason: DecideError::NotOwner,
+        };
+    }
+    if command.name.trim().is_empty() {
+        return Decision::Rejected {
+            reason: DecideError::BlankName,
+        };
+    }
+    if !(1..=DAY_MS).contains(&command.default_duration_ms) {
+        return Decision::Rejected {
+            reason: DecideError::InvalidDuration,
+        };
+    }
+    Decision::Accepted {
+        events: vec![TemplateEvent::TemplateSavedV1(TemplateSavedV1 {
+            template_id: command.template_id,
+            tenant_id: command.tenant_id,
+            user_id: command.user_id,
+            name: command.name.trim().to_string(),
+            tags: command.tags,
+            description: command.description,
+            project: command.project,
+            default_duration_ms: command.default_duration_ms,
+            saved_at: command.saved_at,
+        })],
+        intents: vec![],
+    }
+}
+
+pub struct SaveTemplateDecider;
+
+impl Decider for SaveTemplateDecider {
+    type State = TemplateState;
+    type Command = SaveTemplate;
+    type Event = TemplateEvent;
+    type Intent = Infallible;
+    type Error = DecideError;
+
+    fn initial_state() -> TemplateState {
+        TemplateState::None
+    }
+
+    fn evolve(state: TemplateState, event: TemplateEvent) -> TemplateState {
+        evolve(state, event)
+    }
+
+    fn decide(state: &TemplateState, command: SaveTemplate) -> Decision {
+        decide_save(state, command)
+    }
+}
+
+#[cfg(test)]
+mod save_template_decide_tests {
+    use super::*;
+    use rstest::rstest;
+
+    fn command() -> SaveTemplate {
+        SaveTemplate {
+            template_id: "tpl-1".to_string(),
+            tenant_id: "ten1".to_string(),
+            user_id: "u1".to_string(),
+            name: " Standup ".to_string(),
+            tags: vec![],
+            description: None,
+            project: None,
+            default_duration_ms: 900_000,
+            saved_at: 1000,
+        }
+    }
+
+    fn saved_by(user_id: &str) -> TemplateState {
+        let Decision::Accepted { events, .. } = decide_save(
+            &TemplateState::None,
+            SaveTemplate {
+                user_id: user_id.to_string(),
+                ..command()
+            },
+        ) else {
+            panic!("expected Accepted");
+        };
+        events.into_iter().fold(TemplateState::None, evolve)
+    }
+
+    #[rstest]
+    #[case::new(TemplateState::None)]
+    #[case::own(saved_by("u1"))]
+    fn it_should_save_new_and_own_templates(#[case] state: TemplateState) {
+        let Decision::Accepted { events, .. } = decide_save(&state, command()) else {
+            panic!("expected Accepted");
+        };
+        let TemplateEvent::TemplateSavedV1(saved) = &events[0];
+        assert_eq!(saved.name, "Standup");
+    }
+
+    #[rstest]
+    #[case::blank_name(SaveTemplate { name: "  ".to_string(), ..command() }, DecideError::BlankName)]
+    #[case::no_duration(SaveTemplate { default_duration_ms: 0, ..command() }, DecideError::InvalidDuration)]
+    #[case::longer_than_a_day(SaveTemplate { default_duration_ms: DAY_MS + 1, ..command() }, DecideError::InvalidDuration)]
+    fn it_should_reject_invalid_templates(
+        #[case] command: SaveTemplate,
+        #[case] reason: DecideError,
+    ) {
+        assert_eq!(
+            decide_save(&TemplateState::None, command),
+            Decision::Rejected { reason }
+        );
+    }
+
+    #[rstest]
+    fn it_should_not_replace_another_users_template() {
+        assert_eq!(
+            decide_save(&saved_by("u2"), command()),
+            Decision::Rejected {
+                reason: DecideError::NotOwner
+            }
+        );
+    }
+}
@@ -0,0 +1,17 @@
+use crate::modules::templates::core::events::TemplateEvent;
+use crate::shared::core::decider;
+use std::convert::Infallible;
+
+#[derive(Debug, thiserror::Error, PartialEq, Eq)]
+pub enum DecideError {
+    #[error("template name must not be blank")]
+    BlankName,
+
+    #[error("default duration must be more than 0 and at most 24 hours")]
+    InvalidDuration,
+
+    #[error("template belongs to another user")]
+    NotOwner,
+}
+
+pub type Decision = decider::Decision<TemplateEvent, Infallible, DecideError>;
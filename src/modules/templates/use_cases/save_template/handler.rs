@@ -0,0 +1,114 @@
+use crate::modules::templates::core::events::TemplateEvent;
+use crate::modules::templates::core::state::{TemplateState, template_stream_id};
+use crate::modules::templates::use_cases::save_template::command::SaveTemplate;
+use crate::modules::templates::use_cases::save_template::decide::SaveTemplateDecider;
+use crate::modules::templates::use_cases::save_template::decision::DecideError;
+use crate::shared::application::event_sourced_handler::{
+    EventSourcedError, EventSourcedHandler, NoIntents,
+};
+use crate::shared::core::decider::Decider;
+use crate::shared::infrastructure::event_bus::SharedEventBus;
+use crate::shared::infrastructure::event_store::{EventStore, EventStoreError};
+use std::convert::Infallible;
+
+pub type ApplicationError = EventSourcedError<DecideError, Infallible>;
+
+#[derive(Debug, Clone)]
+pub struct SaveTemplateHandler<TEventStore>
+where
+    TEventStore: EventStore<TemplateEvent> + Clone + Send + Sync + 'static,
+{
+    event_store: TEventStore,
+    inner: EventSourcedHandler<SaveTemplateDecider, TEventStore, NoIntents>,
+}
+
+impl<TEventStore> SaveTemplateHandler<TEventStore>
+where
+    TEventStore: EventStore<TemplateEvent> + Clone + Send + Sync + 'static,
+{
+    pub fn new(event_store: TEventStore) -> Self {
+        Self {
+            event_store: event_store.clone(),
+            inner: EventSourcedHandler::new(event_store, NoIntents),
+        }
+    }
+
+    /// Announce the appended events on `event_bus`, for the projector to pick up.
+    pub fn with_event_bus(mut self, event_bus: SharedEventBus<TemplateEvent>) -> Self {
+        self.inner = self.inner.with_event_bus(event_bus);
+        self
+    }
+
+    pub async fn handle(&self, command: SaveTemplate) -> Result<(), ApplicationError> {
+        let stream_id = template_stream_id(&command.template_id);
+        self.inner.handle(&stream_id, command).await
+    }
+
+    /// The template as last saved, read from its stream rather than the list, so a template
+    /// can be applied right after saving it.
+    pub async fn template(&self, template_id: &str) -> Result<TemplateState, EventStoreError> {
+        let stream = self
+            .event_store
+            .load(&template_stream_id(template_id))
+            .await?;
+        Ok(stream.events.into_iter().fold(
+            SaveTemplateDecider::initial_state(),
+            SaveTemplateDecider::evolve,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod save_template_handler_tests {
+    use super::*;
+    use crate::shared::infrastructure::event_store::in_memory::InMemoryEventStore;
+    use rstest::rstest;
+
+    fn command(user_id: &str) -> SaveTemplate {
+        SaveTemplate {
+            template_id: "tpl-1".to_string(),
+            tenant_id: "ten1".to_string(),
+            user_id: user_id.to_string(),
+            name: "Standup".to_string(),
+            tags: vec![],
+            description: None,
+            project: None,
+            default_duration_ms: 900_000,
+            saved_at: 1000,
+        }
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_append_to_the_templates_stream_and_read_it_back() {
+        let event_store = InMemoryEventStore::<TemplateEvent>::new();
+        let handler = SaveTemplateHandler::new(event_store.clone());
+
+        handler.handle(command("u1")).await.unwrap();
+
+        assert_eq!(event_store.load("Template-tpl-1").await.unwrap().version, 1);
+        assert!(matches!(
+            handler.template("tpl-1").await.unwrap(),
+            TemplateState::Saved { name, .. } if name == "Standup"
+        ));
+        assert_eq!(
+            handler.template("tpl-2").await.unwrap(),
+            TemplateState::None
+        );
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_surface_rejections_and_store_failures() {
+        let event_store = InMemoryEventStore::<TemplateEvent>::new();
+        let handler = SaveTemplateHandler::new(event_store.clone());
+        handler.handle(command("u1")).await.unwrap();
+
+        assert!(matches!(
+            handler.handle(command("u2")).await,
+            Err(ApplicationError::Domain(DecideError::NotOwner))
+        ));
+        event_store.toggle_offline();
+        assert!(handler.template("tpl-1").await.is_err());
+    }
+}
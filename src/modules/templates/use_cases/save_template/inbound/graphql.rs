@@ -0,0 +1,157 @@
+use async_graphql::{Context, InputObject, Object, Result as GqlResult};
+use chrono::Utc;
+
+use crate::modules::templates::use_cases::save_template::command::SaveTemplate;
+use crate::modules::time_entries::core::tag::Tag;
+use crate::shared::infrastructure::request_context::RequestContext;
+use crate::shell::state::AppState;
+
+#[derive(InputObject)]
+pub struct SaveTemplateInput {
+    /// The template to overwrite; a new template is created when absent.
+    pub template_id: Option<String>,
+    pub name: String,
+    #[graphql(default)]
+    pub tags: Vec<String>,
+    pub description: Option<String>,
+    pub project: Option<String>,
+    pub default_duration_minutes: i64,
+}
+
+#[derive(Default)]
+pub struct SaveTemplateMutation;
+
+#[Object]
+impl SaveTemplateMutation {
+    /// Saves a named preset for the caller's recurring entries. Answers with its id.
+    async fn save_template(
+        &self,
+        context: &Context<'_>,
+        input: SaveTemplateInput,
+    ) -> GqlResult<String> {
+        let tags = Tag::parse_all(&input.tags)?;
+        let req_ctx = context
+            .data::<RequestContext>()
+            .map_err(|_| async_graphql::Error::new("Unauthorized"))?;
+        if !req_ctx.principal().can_register_for(&req_ctx.user_id) {
+            return Err(async_graphql::Error::new("Forbidden"));
+        }
+        let state = context.data_unchecked::<AppState>();
+        let template_id = input
+            .template_id
+            .unwrap_or_else(|| uuid::Uuid::now_v7().to_string());
+        let command = SaveTemplate {
+            template_id: template_id.clone(),
+            tenant_id: req_ctx.tenant_id.clone(),
+            user_id: req_ctx.user_id.clone(),
+            name: input.name,
+            tags,
+            description: input.description,
+            project: input.project,
+            default_duration_ms: input.default_duration_minutes.saturating_mul(60_000),
+            saved_at: Utc::now().timestamp_millis(),
+        };
+
+        state
+            .save_template_handler
+            .handle(command)
+            .await
+            .map_err(|e| async_graphql::Error::new(e.to_string()))?;
+
+        Ok(template_id)
+    }
+}
+
+#[cfg(test)]
+mod save_template_graphql_inbound_tests {
+    use async_graphql::{EmptySubscription, Schema};
+
+    use crate::modules::templates::core::state::TemplateState;
+    use crate::shared::auth::rbac::Role;
+    use crate::shared::infrastructure::request_context::RequestContext;
+    use crate::shell::graphql::{MutationRoot, QueryRoot};
+    use crate::shell::state::AppState;
+    use crate::tests::fixtures::tags::make_test_app_state;
+
+    fn make_schema_from_state(
+        state: AppState,
+    ) -> Schema<QueryRoot, MutationRoot, EmptySubscription> {
+        Schema::build(
+            QueryRoot::default(),
+            MutationRoot::default(),
+            EmptySubscription,
+        )
+        .data(state)
+        .finish()
+    }
+
+    fn req_ctx(user_id: &str, role: Role) -> RequestContext {
+        RequestContext {
+            user_id: user_id.to_string(),
+            tenant_id: "tenant-test".to_string(),
+            role,
+            scope: Default::default(),
+        }
+    }
+
+    const SAVE: &str = r#"mutation { saveTemplate(input: { templateId: "tpl-1", name: " Standup ", tags: ["Meeting"], project: "internal", defaultDurationMinutes: 15 }) }"#;
+
+    #[tokio::test]
+    async fn saves_the_template_for_the_caller() {
+        let state = make_test_app_state();
+        let schema = make_schema_from_state(state.clone());
+
+        let result = schema
+            .execute(async_graphql::Request::new(SAVE).data(req_ctx("u-1", Role::Employee)))
+            .await;
+
+        assert!(result.errors.is_empty(), "{:?}", result.errors);
+        assert_eq!(result.data.to_string(), r#"{saveTemplate: "tpl-1"}"#);
+        let TemplateState::Saved {
+            user_id,
+            name,
+            tags,
+            default_duration_ms,
+            ..
+        } = state.save_template_handler.template("tpl-1").await.unwrap()
+        else {
+            panic!("template was not saved");
+        };
+        assert_eq!(user_id, "u-1");
+        assert_eq!(name, "Standup");
+        assert_eq!(tags[0].as_str(), "meeting");
+        assert_eq!(default_duration_ms, 900_000);
+    }
+
+    #[tokio::test]
+    async fn refuses_to_overwrite_another_users_template() {
+        let schema = make_schema_from_state(make_test_app_state());
+        schema
+            .execute(async_graphql::Request::new(SAVE).data(req_ctx("u-1", Role::Employee)))
+            .await;
+
+        let result = schema
+            .execute(async_graphql::Request::new(SAVE).data(req_ctx("u-2", Role::Employee)))
+            .await;
+
+        assert_eq!(
+            result.errors[0].message,
+            "domain rejected: template belongs to another user"
+        );
+    }
+
+    #[tokio::test]
+    async fn returns_error_for_invalid_input() {
+        let schema = make_schema_from_state(make_test_app_state());
+        for query in [
+            r#"mutation { saveTemplate(input: { name: " ", defaultDurationMinutes: 15 }) }"#,
+            r#"mutation { saveTemplate(input: { name: "Standup", defaultDurationMinutes: 0 }) }"#,
+            r#"mutation { saveTemplate(input: { name: "Standup", tags: [""], defaultDurationMinutes: 15 }) }"#,
+        ] {
+            let result = schema
+                .execute(async_graphql::Request::new(query).data(req_ctx("u-1", Role::Employee)))
+                .await;
+            assert!(!result.errors.is_empty(), "{query}");
+        }
+    }
+}
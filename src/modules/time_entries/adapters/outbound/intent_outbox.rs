@@ -1,50 +1,122 @@
 use crate::modules::time_entries::core::intents::TimeEntryIntent;
-use crate::shared::infrastructure::intent_outbox::{DomainOutbox, OutboxError, OutboxRow};
+use crate::shared::application::event_sourced_handler::IntentDispatcher;
+use crate::shared::infrastructure::intent_outbox::{
+    DomainOutbox, IntentRegistration, IntentRegistry, OutboxError, OutboxRow,
+};
+use async_trait::async_trait;
+
+/// The outbox topic time entry intents are written to and the relay drains. Which broker
+/// topic they are published on is the relay's routing.
+pub const OUTBOX_TOPIC: &str = "time-entries.v1";
+
+/// Outbox shape of every time entry intent type.
+pub const INTENT_REGISTRY: IntentRegistry = IntentRegistry(&[
+    IntentRegistration {
+        event_type: TimeEntryIntent::TYPES[0],
+        event_version: 1,
+        topic: OUTBOX_TOPIC,
+    },
+    IntentRegistration {
+        event_type: TimeEntryIntent::TYPES[1],
+        event_version: 1,
+        topic: OUTBOX_TOPIC,
+    },
+]);
 
 /// Translate a list of domain intents into outbox rows and enqueue them.
 /// `starting_version` is the event store stream version before the append.
 /// `events_len` is the total number of events appended in this decision.
-/// The (events_len - intents.len()) offset ensures each intent maps to
-/// the correct event version when multiple events precede the intents.
+/// Every intent is keyed on the decision's last event version and numbered in order, so a
+/// decision may emit more intents than events and replaying it hits the same keys.
 pub async fn dispatch_intents(
     outbox: &impl DomainOutbox,
     stream_id: &str,
     starting_version: i64,
     events_len: usize,
-    topic: &str,
     intents: Vec<TimeEntryIntent>,
 ) -> Result<(), OutboxError> {
-    let intent_offset = events_len - intents.len();
-    for (i, intent) in intents.into_iter().enumerate() {
-        let stream_version = starting_version + (intent_offset + i) as i64 + 1;
-        match intent {
+    let stream_version = starting_version + events_len as i64;
+    for (intent_no, intent) in intents.into_iter().enumerate() {
+        let registration = INTENT_REGISTRY.resolve(intent.intent_type())?;
+        let (occurred_at, payload) = match intent {
             TimeEntryIntent::NotifyUser {
                 time_entry_id,
                 occurred_at,
-            } => {
-                outbox
-                    .enqueue(OutboxRow {
-                        topic: topic.to_string(),
-                        event_type: "TimeEntryTagsSet".to_string(),
-                        event_version: 1,
-                        stream_id: stream_id.to_string(),
-                        stream_version,
-                        occurred_at,
-                        payload: serde_json::json!({
-                            "time_entry_id": time_entry_id,
-                            "occurred_at": occurred_at
-                        }),
-                    })
-                    .await?;
-            }
-        }
+            } => (
+                occurred_at,
+                serde_json::json!({
+                    "time_entry_id": time_entry_id,
+                    "occurred_at": occurred_at
+                }),
+            ),
+            TimeEntryIntent::NotifyTimerAutoStopped {
+                time_entry_id,
+                reason,
+                occurred_at,
+            } => (
+                occurred_at,
+                serde_json::json!({
+                    "time_entry_id": time_entry_id,
+                    "reason": reason,
+                    "occurred_at": occurred_at
+                }),
+            ),
+        };
+        outbox
+            .enqueue(OutboxRow::pending(
+                registration,
+                stream_id,
+                stream_version,
+                intent_no as u32,
+                occurred_at,
+                payload,
+            ))
+            .await?;
     }
     Ok(())
 }
 
+/// Outbox-backed `IntentDispatcher` used by the time entry command handlers.
+#[derive(Debug, Clone)]
+pub struct TimeEntryIntentDispatcher<TOutbox> {
+    outbox: TOutbox,
+}
+
+impl<TOutbox> TimeEntryIntentDispatcher<TOutbox> {
+    pub fn new(outbox: TOutbox) -> Self {
+        Self { outbox }
+    }
+}
+
+#[async_trait]
+impl<TOutbox> IntentDispatcher<TimeEntryIntent> for TimeEntryIntentDispatcher<TOutbox>
+where
+    TOutbox: DomainOutbox + Send + Sync,
+{
+    type Error = OutboxError;
+
+    async fn dispatch(
+        &self,
+        stream_id: &str,
+        starting_version: i64,
+        events_len: usize,
+        intents: Vec<TimeEntryIntent>,
+    ) -> Result<(), OutboxError> {
+        dispatch_intents(
+            &self.outbox,
+            stream_id,
+            starting_version,
+            events_len,
+            intents,
+        )
+        .await
+    }
+}
+
 #[cfg(test)]
 mod dispatch_intents_tests {
     use super::*;
+    use crate::shared::infrastructure::intent_outbox::OutboxStatus;
     use crate::shared::infrastructure::intent_outbox::in_memory::InMemoryDomainOutbox;
     use rstest::rstest;
 
@@ -53,10 +125,10 @@ mod dispatch_intents_tests {
     async fn it_should_enqueue_single_intent_successfully() {
         let outbox = InMemoryDomainOutbox::new();
         let intents = vec![TimeEntryIntent::NotifyUser {
-            time_entry_id: "te-0001".to_string(),
+            time_entry_id: "te-0001".into(),
             occurred_at: 1_000,
         }];
-        dispatch_intents(&outbox, "stream-0001", 0, 1, "time-entries", intents)
+        dispatch_intents(&outbox, "stream-0001", 0, 1, intents)
             .await
             .unwrap();
     }
@@ -68,21 +140,27 @@ mod dispatch_intents_tests {
         // Pre-seed outbox at version 3 to prove the duplicate occurs at that exact version
         let outbox = InMemoryDomainOutbox::new();
         let pre_seed_row = OutboxRow {
-            topic: "time-entries".to_string(),
+            topic: OUTBOX_TOPIC.to_string(),
             event_type: "TimeEntryRegistered".to_string(),
             event_version: 1,
             stream_id: "stream-0001".to_string(),
             stream_version: 3,
+            intent_no: 0,
             occurred_at: 0,
             payload: serde_json::json!({}),
+            content_encoding: None,
+            status: OutboxStatus::Pending,
+            attempts: 0,
+            last_error: None,
+            published_at: None,
         };
         outbox.enqueue(pre_seed_row).await.unwrap();
 
         let intents = vec![TimeEntryIntent::NotifyUser {
-            time_entry_id: "te-0001".to_string(),
+            time_entry_id: "te-0001".into(),
             occurred_at: 2_000,
         }];
-        let result = dispatch_intents(&outbox, "stream-0001", 0, 3, "time-entries", intents).await;
+        let result = dispatch_intents(&outbox, "stream-0001", 0, 3, intents).await;
         assert!(
             matches!(
                 result,
@@ -99,7 +177,7 @@ mod dispatch_intents_tests {
     #[tokio::test]
     async fn it_should_return_ok_when_no_intents() {
         let outbox = InMemoryDomainOutbox::new();
-        dispatch_intents(&outbox, "stream-0001", 0, 0, "time-entries", vec![])
+        dispatch_intents(&outbox, "stream-0001", 0, 0, vec![])
             .await
             .unwrap();
     }
@@ -109,20 +187,79 @@ mod dispatch_intents_tests {
     async fn it_should_propagate_outbox_duplicate_error() {
         let outbox = InMemoryDomainOutbox::new();
         let intents = vec![TimeEntryIntent::NotifyUser {
-            time_entry_id: "te-0001".to_string(),
+            time_entry_id: "te-0001".into(),
             occurred_at: 1_000,
         }];
-        dispatch_intents(
-            &outbox,
-            "stream-0001",
-            0,
-            1,
-            "time-entries",
-            intents.clone(),
-        )
-        .await
-        .unwrap();
-        let result = dispatch_intents(&outbox, "stream-0001", 0, 1, "time-entries", intents).await;
+        dispatch_intents(&outbox, "stream-0001", 0, 1, intents.clone())
+            .await
+            .unwrap();
+        let result = dispatch_intents(&outbox, "stream-0001", 0, 1, intents).await;
         assert!(matches!(result, Err(OutboxError::Duplicate { .. })));
     }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_number_several_intents_of_one_decision() {
+        let outbox = InMemoryDomainOutbox::new();
+        let intents = vec![
+            TimeEntryIntent::NotifyUser {
+                time_entry_id: "te-0001".into(),
+                occurred_at: 1_000,
+            },
+            TimeEntryIntent::NotifyTimerAutoStopped {
+                time_entry_id: "te-0001".into(),
+                reason: "max_duration".to_string(),
+                occurred_at: 1_000,
+            },
+        ];
+        dispatch_intents(&outbox, "stream-0001", 4, 1, intents.clone())
+            .await
+            .unwrap();
+
+        let keys: Vec<_> = outbox
+            .rows()
+            .await
+            .iter()
+            .map(|row| (row.stream_version, row.intent_no))
+            .collect();
+        assert_eq!(keys, vec![(5, 0), (5, 1)]);
+        assert!(matches!(
+            dispatch_intents(&outbox, "stream-0001", 4, 1, intents).await,
+            Err(OutboxError::Duplicate {
+                stream_version: 5,
+                intent_no: 0,
+                ..
+            })
+        ));
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_resolve_topic_type_and_version_from_the_registry() {
+        let outbox = InMemoryDomainOutbox::new();
+        let intents = vec![TimeEntryIntent::NotifyTimerAutoStopped {
+            time_entry_id: "te-0001".into(),
+            reason: "max_duration".to_string(),
+            occurred_at: 1_000,
+        }];
+        dispatch_intents(&outbox, "stream-0001", 0, 1, intents)
+            .await
+            .unwrap();
+
+        let rows = outbox.rows().await;
+        assert_eq!(rows[0].topic, OUTBOX_TOPIC);
+        assert_eq!(rows[0].event_type, "TimerAutoStopped");
+        assert_eq!(rows[0].event_version, 1);
+        assert_eq!(rows[0].status, OutboxStatus::Pending);
+    }
+
+    #[rstest]
+    fn it_should_register_every_intent_type() {
+        for intent_type in TimeEntryIntent::TYPES {
+            assert!(
+                INTENT_REGISTRY.resolve(intent_type).is_ok(),
+                "{intent_type}"
+            );
+        }
+    }
 }
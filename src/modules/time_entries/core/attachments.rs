@@ -0,0 +1,86 @@
+use thiserror::Error;
+
+/// The largest file that can be attached to an entry.
+pub const MAX_ATTACHMENT_BYTES: u64 = 10 * 1024 * 1024;
+
+/// Receipts and screenshots: images and PDFs.
+pub const ALLOWED_CONTENT_TYPES: [&str; 5] = [
+    "application/pdf",
+    "image/gif",
+    "image/jpeg",
+    "image/png",
+    "image/webp",
+];
+
+#[derive(Debug, Clone, Copy, Error, PartialEq, Eq)]
+pub enum AttachmentError {
+    #[error("file name must not be empty")]
+    EmptyFileName,
+
+    #[error("attachments must be images or PDFs")]
+    UnsupportedContentType,
+
+    #[error("attachment is larger than {max} bytes")]
+    TooLarge { max: u64 },
+}
+
+/// Where the file of an attachment is stored. Ids are part of the key, never the file name,
+/// so keys are safe as paths and object names.
+pub fn object_key(time_entry_id: &str, attachment_id: &str) -> String {
+    format!("attachments/{time_entry_id}/{attachment_id}")
+}
+
+/// Checks what a client says about a file before it gets an upload URL, and again against
+/// the stored object before the attachment is added.
+pub fn ensure_valid_attachment(
+    file_name: &str,
+    content_type: &str,
+    size_bytes: u64,
+) -> Result<(), AttachmentError> {
+    if file_name.trim().is_empty() {
+        return Err(AttachmentError::EmptyFileName);
+    }
+    if !ALLOWED_CONTENT_TYPES.contains(&content_type) {
+        return Err(AttachmentError::UnsupportedContentType);
+    }
+    if size_bytes > MAX_ATTACHMENT_BYTES {
+        return Err(AttachmentError::TooLarge {
+            max: MAX_ATTACHMENT_BYTES,
+        });
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod attachments_tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case::receipt("receipt.pdf", "application/pdf", 1_024, Ok(()))]
+    #[case::largest("screen.png", "image/png", MAX_ATTACHMENT_BYTES, Ok(()))]
+    #[case::no_name(" ", "image/png", 1_024, Err(AttachmentError::EmptyFileName))]
+    #[case::executable(
+        "run.exe",
+        "application/octet-stream",
+        1_024,
+        Err(AttachmentError::UnsupportedContentType)
+    )]
+    #[case::too_large("scan.jpg", "image/jpeg", MAX_ATTACHMENT_BYTES + 1, Err(AttachmentError::TooLarge { max: MAX_ATTACHMENT_BYTES }))]
+    fn it_should_accept_only_small_images_and_pdfs(
+        #[case] file_name: &str,
+        #[case] content_type: &str,
+        #[case] size_bytes: u64,
+        #[case] expected: Result<(), AttachmentError>,
+    ) {
+        assert_eq!(
+            ensure_valid_attachment(file_name, content_type, size_bytes),
+            expected
+        );
+    }
+
+    #[rstest]
+    fn it_should_key_objects_by_entry_and_attachment() {
+        assert_eq!(object_key("te-1", "a-1"), "attachments/te-1/a-1");
+    }
+}
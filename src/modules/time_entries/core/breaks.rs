@@ -0,0 +1,122 @@
+use crate::modules::time_entries::core::time_interval::TimeInterval;
+use thiserror::Error;
+
+/// A pause inside a registered time entry, in epoch milliseconds. Its time does not count
+/// towards the entry's net duration.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct Break {
+    pub started_at: i64,
+    pub ended_at: i64,
+}
+
+impl Break {
+    pub fn duration_ms(&self) -> i64 {
+        self.ended_at - self.started_at
+    }
+
+    /// The milliseconds of the break between `from` and `until`.
+    pub fn millis_within(&self, from: i64, until: i64) -> i64 {
+        (self.ended_at.min(until) - self.started_at.max(from)).max(0)
+    }
+}
+
+#[derive(Debug, Clone, Copy, Error, PartialEq, Eq)]
+pub enum BreakError {
+    #[error("break must end after it starts")]
+    Empty,
+
+    #[error("break must lie within the time entry")]
+    OutsideEntry,
+
+    #[error("breaks must not overlap each other")]
+    Overlapping,
+}
+
+/// Checks that every break ends after it starts, lies within `interval` and overlaps none
+/// of the others. Breaks may touch, as intervals are half-open.
+pub fn ensure_valid_breaks(interval: &TimeInterval, breaks: &[Break]) -> Result<(), BreakError> {
+    let (start, end) = (interval.start().as_millis(), interval.end().as_millis());
+    let mut sorted = breaks.to_vec();
+    sorted.sort_by_key(|pause| pause.started_at);
+    for pause in &sorted {
+        if pause.ended_at <= pause.started_at {
+            return Err(BreakError::Empty);
+        }
+        if pause.started_at < start || pause.ended_at > end {
+            return Err(BreakError::OutsideEntry);
+        }
+    }
+    if sorted
+        .windows(2)
+        .any(|pair| pair[1].started_at < pair[0].ended_at)
+    {
+        return Err(BreakError::Overlapping);
+    }
+    Ok(())
+}
+
+/// The milliseconds between `from` and `until` that the entry from `started_at` to
+/// `ended_at` spends working, so outside its breaks.
+pub fn net_millis_within(
+    started_at: i64,
+    ended_at: i64,
+    breaks: &[Break],
+    from: i64,
+    until: i64,
+) -> i64 {
+    let gross = (ended_at.min(until) - started_at.max(from)).max(0);
+    let paused: i64 = breaks
+        .iter()
+        .map(|pause| pause.millis_within(from, until))
+        .sum();
+    (gross - paused).max(0)
+}
+
+#[cfg(test)]
+mod breaks_tests {
+    use super::*;
+    use rstest::rstest;
+
+    fn pause(started_at: i64, ended_at: i64) -> Break {
+        Break {
+            started_at,
+            ended_at,
+        }
+    }
+
+    #[rstest]
+    #[case::none(vec![], Ok(()))]
+    #[case::inside(vec![pause(200, 300), pause(500, 600)], Ok(()))]
+    #[case::touching(vec![pause(300, 400), pause(200, 300)], Ok(()))]
+    #[case::whole_entry(vec![pause(100, 1_000)], Ok(()))]
+    #[case::empty(vec![pause(300, 300)], Err(BreakError::Empty))]
+    #[case::before_the_start(vec![pause(50, 150)], Err(BreakError::OutsideEntry))]
+    #[case::after_the_end(vec![pause(900, 1_100)], Err(BreakError::OutsideEntry))]
+    #[case::overlapping(vec![pause(400, 600), pause(200, 401)], Err(BreakError::Overlapping))]
+    fn it_should_keep_breaks_inside_the_entry_and_apart(
+        #[case] breaks: Vec<Break>,
+        #[case] expected: Result<(), BreakError>,
+    ) {
+        let interval = TimeInterval::new(100, 1_000).unwrap();
+
+        assert_eq!(ensure_valid_breaks(&interval, &breaks), expected);
+    }
+
+    #[rstest]
+    #[case::whole_entry(0, 1_000, 700)]
+    #[case::clipped_break(0, 250, 100)]
+    #[case::before_the_breaks(0, 200, 100)]
+    #[case::outside_the_entry(1_000, 2_000, 0)]
+    fn it_should_leave_breaks_out_of_the_net_time(
+        #[case] from: i64,
+        #[case] until: i64,
+        #[case] expected: i64,
+    ) {
+        let breaks = [pause(200, 300), pause(500, 600)];
+
+        assert_eq!(
+            net_millis_within(100, 1_000, &breaks, from, until),
+            expected
+        );
+    }
+}
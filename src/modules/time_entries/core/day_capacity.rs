@@ -0,0 +1,255 @@
+use chrono::NaiveDate;
+use thiserror::Error;
+
+use crate::modules::time_entries::core::days_off::utc_date;
+
+const DAY_MS: i64 = 24 * 60 * 60 * 1000;
+
+/// What registering time that takes a user's day over its capacity does.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum CapacityPolicy {
+    #[default]
+    Allow,
+    /// Accept the entry but warn about the day.
+    Warn,
+    Reject,
+}
+
+impl std::str::FromStr for CapacityPolicy {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "allow" => Ok(Self::Allow),
+            "warn" => Ok(Self::Warn),
+            "reject" => Ok(Self::Reject),
+            other => Err(format!("unknown capacity policy: {other}")),
+        }
+    }
+}
+
+/// How much time a user may register on one UTC day. The limit is `max_day_ms`, or, with an
+/// `overtime_ms` allowance, the user's contracted time for the day plus that allowance when
+/// it is lower. Users without a contract are held to `max_day_ms` only.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DayCapacity {
+    pub policy: CapacityPolicy,
+    pub max_day_ms: i64,
+    pub overtime_ms: Option<i64>,
+}
+
+impl Default for DayCapacity {
+    /// Allows any day; when switched on, a day holds at most 24 hours.
+    fn default() -> Self {
+        Self {
+            policy: CapacityPolicy::Allow,
+            max_day_ms: DAY_MS,
+            overtime_ms: None,
+        }
+    }
+}
+
+impl DayCapacity {
+    pub fn limit_ms(&self, contracted_ms: Option<i64>) -> i64 {
+        match (self.overtime_ms, contracted_ms) {
+            (Some(overtime_ms), Some(contracted_ms)) => {
+                (contracted_ms + overtime_ms).min(self.max_day_ms)
+            }
+            _ => self.max_day_ms,
+        }
+    }
+}
+
+/// A user's other registered time on one day, and the time their contract has them work.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DayTotal {
+    pub date: NaiveDate,
+    pub registered_ms: i64,
+    pub contracted_ms: Option<i64>,
+}
+
+/// A day an entry takes over its capacity.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CapacityWarning {
+    pub date: NaiveDate,
+    pub total_ms: i64,
+    pub limit_ms: i64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum DayCapacityError {
+    #[error(
+        "time registered on {date} would total {total_ms} ms, more than the {limit_ms} ms allowed"
+    )]
+    OverCapacity {
+        date: NaiveDate,
+        total_ms: i64,
+        limit_ms: i64,
+    },
+}
+
+/// The milliseconds `[started_at, ended_at)` spends on each UTC day it touches, in order.
+pub fn ms_per_day(started_at: i64, ended_at: i64) -> Vec<(NaiveDate, i64)> {
+    let mut days = Vec::new();
+    let mut from = started_at;
+    while from < ended_at {
+        let until = (from.div_euclid(DAY_MS) + 1) * DAY_MS;
+        let until = until.min(ended_at);
+        days.push((utc_date(from), until - from));
+        from = until;
+    }
+    days
+}
+
+/// The days the entry's time, added to the user's other time, takes over capacity: warnings
+/// under `Warn`, the first day refused under `Reject`. `entry_days` is the entry's time per
+/// day, as `ms_per_day` splits it; days without a total count only the entry.
+pub fn check_capacity(
+    capacity: &DayCapacity,
+    entry_days: &[(NaiveDate, i64)],
+    totals: &[DayTotal],
+) -> Result<Vec<CapacityWarning>, DayCapacityError> {
+    if capacity.policy == CapacityPolicy::Allow {
+        return Ok(vec![]);
+    }
+    let mut warnings = Vec::new();
+    for (date, entry_ms) in entry_days {
+        let total = totals.iter().find(|total| total.date == *date);
+        let total_ms = entry_ms + total.map_or(0, |total| total.registered_ms);
+        let limit_ms = capacity.limit_ms(total.and_then(|total| total.contracted_ms));
+        if total_ms <= limit_ms {
+            continue;
+        }
+        if capacity.policy == CapacityPolicy::Reject {
+            return Err(DayCapacityError::OverCapacity {
+                date: *date,
+                total_ms,
+                limit_ms,
+            });
+        }
+        warnings.push(CapacityWarning {
+            date: *date,
+            total_ms,
+            limit_ms,
+        });
+    }
+    Ok(warnings)
+}
+
+#[cfg(test)]
+mod day_capacity_tests {
+    use super::*;
+    use rstest::rstest;
+
+    const HOUR: i64 = 60 * 60 * 1000;
+
+    fn date(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(1970, 1, day).unwrap()
+    }
+
+    fn capacity(policy: CapacityPolicy) -> DayCapacity {
+        DayCapacity {
+            policy,
+            max_day_ms: 10 * HOUR,
+            overtime_ms: None,
+        }
+    }
+
+    fn total(registered_ms: i64, contracted_ms: Option<i64>) -> DayTotal {
+        DayTotal {
+            date: date(1),
+            registered_ms,
+            contracted_ms,
+        }
+    }
+
+    #[rstest]
+    #[case::within_one_day(HOUR, 3 * HOUR, vec![(date(1), 2 * HOUR)])]
+    #[case::across_midnight(23 * HOUR, 26 * HOUR, vec![(date(1), HOUR), (date(2), 2 * HOUR)])]
+    #[case::ending_at_midnight(22 * HOUR, 24 * HOUR, vec![(date(1), 2 * HOUR)])]
+    fn it_should_split_an_interval_per_day(
+        #[case] started_at: i64,
+        #[case] ended_at: i64,
+        #[case] expected: Vec<(NaiveDate, i64)>,
+    ) {
+        assert_eq!(ms_per_day(started_at, ended_at), expected);
+    }
+
+    #[rstest]
+    #[case::no_overtime(None, Some(8 * HOUR), 10 * HOUR)]
+    #[case::no_contract(Some(HOUR), None, 10 * HOUR)]
+    #[case::contract_and_overtime(Some(HOUR), Some(8 * HOUR), 9 * HOUR)]
+    #[case::capped_by_the_maximum(Some(4 * HOUR), Some(8 * HOUR), 10 * HOUR)]
+    fn it_should_pick_the_limit(
+        #[case] overtime_ms: Option<i64>,
+        #[case] contracted_ms: Option<i64>,
+        #[case] expected: i64,
+    ) {
+        let capacity = DayCapacity {
+            overtime_ms,
+            ..capacity(CapacityPolicy::Warn)
+        };
+        assert_eq!(capacity.limit_ms(contracted_ms), expected);
+    }
+
+    #[rstest]
+    #[case::allow(CapacityPolicy::Allow, Ok(vec![]))]
+    #[case::warn(
+        CapacityPolicy::Warn,
+        Ok(vec![CapacityWarning { date: date(1), total_ms: 11 * HOUR, limit_ms: 10 * HOUR }])
+    )]
+    #[case::reject(
+        CapacityPolicy::Reject,
+        Err(DayCapacityError::OverCapacity { date: date(1), total_ms: 11 * HOUR, limit_ms: 10 * HOUR })
+    )]
+    fn it_should_apply_the_policy_over_capacity(
+        #[case] policy: CapacityPolicy,
+        #[case] expected: Result<Vec<CapacityWarning>, DayCapacityError>,
+    ) {
+        let result = check_capacity(
+            &capacity(policy),
+            &[(date(1), 3 * HOUR)],
+            &[total(8 * HOUR, None)],
+        );
+        assert_eq!(result, expected);
+    }
+
+    #[rstest]
+    fn it_should_accept_a_day_filled_exactly() {
+        let result = check_capacity(
+            &capacity(CapacityPolicy::Reject),
+            &[(date(1), 2 * HOUR), (date(2), 9 * HOUR)],
+            &[total(8 * HOUR, None)],
+        );
+        assert_eq!(result, Ok(vec![]));
+    }
+
+    #[rstest]
+    fn it_should_hold_users_with_a_contract_to_their_overtime() {
+        let capacity = DayCapacity {
+            overtime_ms: Some(HOUR),
+            ..capacity(CapacityPolicy::Reject)
+        };
+        let result = check_capacity(
+            &capacity,
+            &[(date(1), 2 * HOUR)],
+            &[total(7 * HOUR + 1, Some(8 * HOUR))],
+        );
+        assert!(matches!(
+            result,
+            Err(DayCapacityError::OverCapacity { limit_ms, .. }) if limit_ms == 9 * HOUR
+        ));
+    }
+
+    #[rstest]
+    #[case("allow", Ok(CapacityPolicy::Allow))]
+    #[case("warn", Ok(CapacityPolicy::Warn))]
+    #[case("reject", Ok(CapacityPolicy::Reject))]
+    #[case("block", Err("unknown capacity policy: block".to_string()))]
+    fn it_should_parse_policies(
+        #[case] value: &str,
+        #[case] expected: Result<CapacityPolicy, String>,
+    ) {
+        assert_eq!(value.parse::<CapacityPolicy>(), expected);
+    }
+}
@@ -0,0 +1,170 @@
+use chrono::{DateTime, NaiveDate};
+use thiserror::Error;
+
+use crate::modules::time_entries::core::state::TimeEntryState;
+use crate::shared::core::calendar::DayOff;
+
+/// What registering time on a holiday or approved absence does.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum AbsencePolicy {
+    #[default]
+    Allow,
+    /// Accept the entry but log a warning.
+    Warn,
+    Reject,
+}
+
+impl std::str::FromStr for AbsencePolicy {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "allow" => Ok(Self::Allow),
+            "warn" => Ok(Self::Warn),
+            "reject" => Ok(Self::Reject),
+            other => Err(format!("unknown absence policy: {other}")),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum DayOffError {
+    #[error("time entry falls on a day off ({date})")]
+    DayOff { date: NaiveDate },
+}
+
+/// The UTC days a registered entry covers, when the change registers it or moves its
+/// interval. Entries that stay drafts and changes that leave the interval alone are not
+/// checked.
+pub fn registered_days<'a>(
+    before: &TimeEntryState,
+    after: &'a TimeEntryState,
+) -> Option<(&'a str, NaiveDate, NaiveDate)> {
+    let TimeEntryState::Registered {
+        user_id, interval, ..
+    } = after
+    else {
+        return None;
+    };
+    if let TimeEntryState::Registered {
+        interval: previous, ..
+    } = before
+        && previous == interval
+    {
+        return None;
+    }
+    // The end is exclusive: an entry ending at midnight does not touch the next day.
+    Some((
+        user_id.as_str(),
+        utc_date(interval.start().as_millis()),
+        utc_date(interval.end().as_millis() - 1),
+    ))
+}
+
+/// `Ok(Some(day))` when the policy asks to warn about `day`.
+pub fn check_days_off(
+    policy: AbsencePolicy,
+    days_off: Vec<DayOff>,
+) -> Result<Option<DayOff>, DayOffError> {
+    let Some(day_off) = days_off.into_iter().next() else {
+        return Ok(None);
+    };
+    match policy {
+        AbsencePolicy::Allow => Ok(None),
+        AbsencePolicy::Warn => Ok(Some(day_off)),
+        AbsencePolicy::Reject => Err(DayOffError::DayOff { date: day_off.date }),
+    }
+}
+
+pub fn utc_date(timestamp_millis: i64) -> NaiveDate {
+    DateTime::from_timestamp_millis(timestamp_millis)
+        .unwrap_or_default()
+        .date_naive()
+}
+
+#[cfg(test)]
+mod days_off_tests {
+    use super::*;
+    use crate::modules::time_entries::core::time_interval::TimeInterval;
+    use crate::shared::core::calendar::DayOffKind;
+    use rstest::rstest;
+
+    const DAY: i64 = 86_400_000;
+
+    fn date(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(1970, 1, day).unwrap()
+    }
+
+    fn registered(started_at: i64, ended_at: i64) -> TimeEntryState {
+        TimeEntryState::Registered {
+            time_entry_id: "te-1".into(),
+            user_id: "u-1".into(),
+            interval: TimeInterval::new(started_at, ended_at).unwrap(),
+            tag_ids: vec![],
+            created_at: 0,
+            created_by: "u-1".into(),
+            hourly_rate: None,
+            breaks: vec![],
+        }
+    }
+
+    fn draft() -> TimeEntryState {
+        TimeEntryState::Draft {
+            time_entry_id: "te-1".into(),
+            user_id: "u-1".into(),
+            started_at: Some(0),
+            ended_at: None,
+            tag_ids: vec![],
+            created_at: 0,
+            created_by: "u-1".into(),
+            hourly_rate: None,
+        }
+    }
+
+    #[rstest]
+    #[case::registering(draft(), registered(DAY, 2 * DAY), Some(("u-1", date(2), date(2))))]
+    #[case::moving(registered(0, DAY), registered(DAY / 2, 3 * DAY / 2), Some(("u-1", date(1), date(2))))]
+    #[case::unchanged(registered(0, DAY), registered(0, DAY), None)]
+    #[case::still_draft(TimeEntryState::None, draft(), None)]
+    fn it_should_check_only_registered_interval_changes(
+        #[case] before: TimeEntryState,
+        #[case] after: TimeEntryState,
+        #[case] expected: Option<(&str, NaiveDate, NaiveDate)>,
+    ) {
+        assert_eq!(registered_days(&before, &after), expected);
+    }
+
+    fn holiday() -> DayOff {
+        DayOff {
+            date: date(1),
+            kind: DayOffKind::Holiday {
+                name: "New Year".to_string(),
+            },
+        }
+    }
+
+    #[rstest]
+    #[case::allow(AbsencePolicy::Allow, vec![holiday()], Ok(None))]
+    #[case::warn(AbsencePolicy::Warn, vec![holiday()], Ok(Some(holiday())))]
+    #[case::reject(AbsencePolicy::Reject, vec![holiday()], Err(DayOffError::DayOff { date: date(1) }))]
+    #[case::no_days_off(AbsencePolicy::Reject, vec![], Ok(None))]
+    fn it_should_apply_the_policy(
+        #[case] policy: AbsencePolicy,
+        #[case] days_off: Vec<DayOff>,
+        #[case] expected: Result<Option<DayOff>, DayOffError>,
+    ) {
+        assert_eq!(check_days_off(policy, days_off), expected);
+    }
+
+    #[rstest]
+    #[case("allow", Ok(AbsencePolicy::Allow))]
+    #[case("warn", Ok(AbsencePolicy::Warn))]
+    #[case("reject", Ok(AbsencePolicy::Reject))]
+    #[case("block", Err("unknown absence policy: block".to_string()))]
+    fn it_should_parse_policies(
+        #[case] value: &str,
+        #[case] expected: Result<AbsencePolicy, String>,
+    ) {
+        assert_eq!(value.parse::<AbsencePolicy>(), expected);
+    }
+}
@@ -1,13 +1,24 @@
+use crate::shared::core::domain_event::DomainEvent;
+use crate::shared::core::personal_data::PersonalData;
+
 pub mod v1 {
+    pub mod time_entry_approved;
+    pub mod time_entry_attachment_added;
+    pub mod time_entry_breaks_set;
+    pub mod time_entry_comment_added;
+    pub mod time_entry_compacted;
     pub mod time_entry_deleted;
     pub mod time_entry_end_set;
+    pub mod time_entry_hourly_rate_set;
     pub mod time_entry_initiated;
     pub mod time_entry_registered;
+    pub mod time_entry_rejected;
     pub mod time_entry_start_set;
     pub mod time_entry_tags_set;
+    pub mod timer_auto_stopped;
 }
 
-#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, PartialEq, Eq, DomainEvent)]
 #[serde(tag = "type")]
 pub enum TimeEntryEvent {
     TimeEntryInitiatedV1(v1::time_entry_initiated::TimeEntryInitiatedV1),
@@ -16,4 +27,287 @@ pub enum TimeEntryEvent {
     TimeEntryRegisteredV1(v1::time_entry_registered::TimeEntryRegisteredV1),
     TimeEntryDeletedV1(v1::time_entry_deleted::TimeEntryDeletedV1),
     TimeEntryTagsSetV1(v1::time_entry_tags_set::TimeEntryTagsSetV1),
+    TimeEntryApprovedV1(v1::time_entry_approved::TimeEntryApprovedV1),
+    TimeEntryRejectedV1(v1::time_entry_rejected::TimeEntryRejectedV1),
+    TimeEntryHourlyRateSetV1(v1::time_entry_hourly_rate_set::TimeEntryHourlyRateSetV1),
+    TimerAutoStoppedV1(v1::timer_auto_stopped::TimerAutoStoppedV1),
+    TimeEntryBreaksSetV1(v1::time_entry_breaks_set::TimeEntryBreaksSetV1),
+    TimeEntryCommentAddedV1(v1::time_entry_comment_added::TimeEntryCommentAddedV1),
+    TimeEntryAttachmentAddedV1(v1::time_entry_attachment_added::TimeEntryAttachmentAddedV1),
+    TimeEntryCompactedV1(v1::time_entry_compacted::TimeEntryCompactedV1),
+}
+
+impl TimeEntryEvent {
+    /// When the change described by the event happened, in epoch milliseconds.
+    pub fn occurred_at(&self) -> i64 {
+        match self {
+            TimeEntryEvent::TimeEntryInitiatedV1(e) => e.created_at,
+            TimeEntryEvent::TimeEntryStartSetV1(e) => e.updated_at,
+            TimeEntryEvent::TimeEntryEndSetV1(e) => e.updated_at,
+            TimeEntryEvent::TimeEntryRegisteredV1(e) => e.occurred_at,
+            TimeEntryEvent::TimeEntryDeletedV1(e) => e.deleted_at,
+            TimeEntryEvent::TimeEntryTagsSetV1(e) => e.updated_at,
+            TimeEntryEvent::TimeEntryApprovedV1(e) => e.approved_at,
+            TimeEntryEvent::TimeEntryRejectedV1(e) => e.rejected_at,
+            TimeEntryEvent::TimeEntryHourlyRateSetV1(e) => e.updated_at,
+            TimeEntryEvent::TimerAutoStoppedV1(e) => e.stopped_at,
+            TimeEntryEvent::TimeEntryBreaksSetV1(e) => e.updated_at,
+            TimeEntryEvent::TimeEntryCommentAddedV1(e) => e.added_at,
+            TimeEntryEvent::TimeEntryAttachmentAddedV1(e) => e.added_at,
+            TimeEntryEvent::TimeEntryCompactedV1(e) => e.compacted_at,
+        }
+    }
+}
+
+/// The owner's user id, actor ids (`*_by`), comment bodies and attachment file names. All of
+/// it belongs to the entry's owner, named by the event that starts the stream.
+impl PersonalData for TimeEntryEvent {
+    fn data_subject(&self) -> Option<String> {
+        match self {
+            TimeEntryEvent::TimeEntryInitiatedV1(e) => Some(e.user_id.to_string()),
+            TimeEntryEvent::TimeEntryCompactedV1(e) => Some(e.user_id.to_string()),
+            _ => None,
+        }
+    }
+
+    fn map_personal_data(self, f: &mut dyn FnMut(String) -> String) -> Self {
+        match self {
+            TimeEntryEvent::TimeEntryInitiatedV1(mut e) => {
+                e.user_id = f(e.user_id.into()).into();
+                e.created_by = f(e.created_by.into()).into();
+                TimeEntryEvent::TimeEntryInitiatedV1(e)
+            }
+            TimeEntryEvent::TimeEntryStartSetV1(mut e) => {
+                e.updated_by = f(e.updated_by.into()).into();
+                TimeEntryEvent::TimeEntryStartSetV1(e)
+            }
+            TimeEntryEvent::TimeEntryEndSetV1(mut e) => {
+                e.updated_by = f(e.updated_by.into()).into();
+                TimeEntryEvent::TimeEntryEndSetV1(e)
+            }
+            TimeEntryEvent::TimeEntryRegisteredV1(e) => TimeEntryEvent::TimeEntryRegisteredV1(e),
+            TimeEntryEvent::TimeEntryDeletedV1(mut e) => {
+                e.deleted_by = f(e.deleted_by.into()).into();
+                TimeEntryEvent::TimeEntryDeletedV1(e)
+            }
+            TimeEntryEvent::TimeEntryTagsSetV1(mut e) => {
+                e.updated_by = f(e.updated_by.into()).into();
+                TimeEntryEvent::TimeEntryTagsSetV1(e)
+            }
+            TimeEntryEvent::TimeEntryApprovedV1(mut e) => {
+                e.approved_by = f(e.approved_by.into()).into();
+                TimeEntryEvent::TimeEntryApprovedV1(e)
+            }
+            TimeEntryEvent::TimeEntryRejectedV1(mut e) => {
+                e.rejected_by = f(e.rejected_by.into()).into();
+                TimeEntryEvent::TimeEntryRejectedV1(e)
+            }
+            TimeEntryEvent::TimeEntryHourlyRateSetV1(mut e) => {
+                e.updated_by = f(e.updated_by.into()).into();
+                TimeEntryEvent::TimeEntryHourlyRateSetV1(e)
+            }
+            TimeEntryEvent::TimerAutoStoppedV1(e) => TimeEntryEvent::TimerAutoStoppedV1(e),
+            TimeEntryEvent::TimeEntryBreaksSetV1(mut e) => {
+                e.updated_by = f(e.updated_by.into()).into();
+                TimeEntryEvent::TimeEntryBreaksSetV1(e)
+            }
+            TimeEntryEvent::TimeEntryCommentAddedV1(mut e) => {
+                e.body = f(e.body);
+                e.added_by = f(e.added_by.into()).into();
+                TimeEntryEvent::TimeEntryCommentAddedV1(e)
+            }
+            TimeEntryEvent::TimeEntryAttachmentAddedV1(mut e) => {
+                e.file_name = f(e.file_name);
+                e.added_by = f(e.added_by.into()).into();
+                TimeEntryEvent::TimeEntryAttachmentAddedV1(e)
+            }
+            TimeEntryEvent::TimeEntryCompactedV1(mut e) => {
+                e.user_id = f(e.user_id.into()).into();
+                e.deleted_by = f(e.deleted_by.into()).into();
+                TimeEntryEvent::TimeEntryCompactedV1(e)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod time_entry_event_personal_data_tests {
+    use super::*;
+    use crate::modules::time_entries::core::breaks::Break;
+    use crate::modules::time_entries::core::events::v1::time_entry_approved::TimeEntryApprovedV1;
+    use crate::modules::time_entries::core::events::v1::time_entry_attachment_added::TimeEntryAttachmentAddedV1;
+    use crate::modules::time_entries::core::events::v1::time_entry_breaks_set::TimeEntryBreaksSetV1;
+    use crate::modules::time_entries::core::events::v1::time_entry_comment_added::TimeEntryCommentAddedV1;
+    use crate::modules::time_entries::core::events::v1::time_entry_compacted::TimeEntryCompactedV1;
+    use crate::modules::time_entries::core::events::v1::time_entry_deleted::TimeEntryDeletedV1;
+    use crate::modules::time_entries::core::events::v1::time_entry_hourly_rate_set::TimeEntryHourlyRateSetV1;
+    use crate::modules::time_entries::core::events::v1::time_entry_rejected::TimeEntryRejectedV1;
+    use crate::modules::time_entries::core::events::v1::time_entry_tags_set::TimeEntryTagsSetV1;
+    use crate::modules::time_entries::core::events::v1::timer_auto_stopped::TimerAutoStoppedV1;
+    use crate::modules::time_entries::core::tag::Tag;
+    use crate::tests::fixtures::events::time_entry_end_set_v1::make_time_entry_end_set_v1_event;
+    use crate::tests::fixtures::events::time_entry_initiated_v1::make_time_entry_initiated_v1_event;
+    use crate::tests::fixtures::events::time_entry_registered_v1::make_time_entry_registered_v1_event;
+    use crate::tests::fixtures::events::time_entry_start_set_v1::make_time_entry_start_set_v1_event;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case::initiated(
+        TimeEntryEvent::TimeEntryInitiatedV1(make_time_entry_initiated_v1_event()),
+        vec!["user-fixed-0001", "user-fixed-0001"]
+    )]
+    #[case::start_set(
+        TimeEntryEvent::TimeEntryStartSetV1(make_time_entry_start_set_v1_event()),
+        vec!["user-fixed-0001"]
+    )]
+    #[case::end_set(
+        TimeEntryEvent::TimeEntryEndSetV1(make_time_entry_end_set_v1_event()),
+        vec!["user-fixed-0001"]
+    )]
+    #[case::registered(
+        TimeEntryEvent::TimeEntryRegisteredV1(make_time_entry_registered_v1_event()),
+        vec![]
+    )]
+    #[case::deleted(
+        TimeEntryEvent::TimeEntryDeletedV1(TimeEntryDeletedV1 {
+            time_entry_id: "te-fixed-0001".into(),
+            deleted_at: 1_700_000_000_000,
+            deleted_by: "user-fixed-0001".into(),
+        }),
+        vec!["user-fixed-0001"]
+    )]
+    #[case::tags_set(
+        TimeEntryEvent::TimeEntryTagsSetV1(TimeEntryTagsSetV1 {
+            time_entry_id: "te-fixed-0001".into(),
+            tag_ids: vec![Tag::parse("tag-1").unwrap()],
+            updated_at: 1_700_000_000_000,
+            updated_by: "user-fixed-0001".into(),
+        }),
+        vec!["user-fixed-0001"]
+    )]
+    #[case::approved(
+        TimeEntryEvent::TimeEntryApprovedV1(TimeEntryApprovedV1 {
+            time_entry_id: "te-fixed-0001".into(),
+            approved_at: 1_700_000_000_000,
+            approved_by: "user-fixed-0001".into(),
+        }),
+        vec!["user-fixed-0001"]
+    )]
+    #[case::rejected(
+        TimeEntryEvent::TimeEntryRejectedV1(TimeEntryRejectedV1 {
+            time_entry_id: "te-fixed-0001".into(),
+            rejected_at: 1_700_000_000_000,
+            rejected_by: "user-fixed-0001".into(),
+            reason: Some("wrong project".to_string()),
+        }),
+        vec!["user-fixed-0001"]
+    )]
+    #[case::hourly_rate_set(
+        TimeEntryEvent::TimeEntryHourlyRateSetV1(TimeEntryHourlyRateSetV1 {
+            time_entry_id: "te-fixed-0001".into(),
+            hourly_rate_cents: 9_500,
+            currency: "EUR".to_string(),
+            updated_at: 1_700_000_000_000,
+            updated_by: "user-fixed-0001".into(),
+        }),
+        vec!["user-fixed-0001"]
+    )]
+    #[case::timer_auto_stopped(
+        TimeEntryEvent::TimerAutoStoppedV1(TimerAutoStoppedV1 {
+            time_entry_id: "te-fixed-0001".into(),
+            ended_at: 1_700_000_000_000,
+            reason: "exceeded 12h".to_string(),
+            stopped_at: 1_700_000_000_000,
+        }),
+        vec![]
+    )]
+    #[case::breaks_set(
+        TimeEntryEvent::TimeEntryBreaksSetV1(TimeEntryBreaksSetV1 {
+            time_entry_id: "te-fixed-0001".into(),
+            breaks: vec![Break {
+                started_at: 1_700_000_000_000,
+                ended_at: 1_700_000_900_000,
+            }],
+            updated_at: 1_700_000_000_000,
+            updated_by: "user-fixed-0001".into(),
+        }),
+        vec!["user-fixed-0001"]
+    )]
+    #[case::comment_added(
+        TimeEntryEvent::TimeEntryCommentAddedV1(TimeEntryCommentAddedV1 {
+            time_entry_id: "te-fixed-0001".into(),
+            comment_id: "comment-fixed-0001".to_string(),
+            body: "Please split this entry per project.".to_string(),
+            added_at: 1_700_000_000_000,
+            added_by: "user-fixed-0001".into(),
+        }),
+        vec!["Please split this entry per project.", "user-fixed-0001"]
+    )]
+    #[case::attachment_added(
+        TimeEntryEvent::TimeEntryAttachmentAddedV1(TimeEntryAttachmentAddedV1 {
+            time_entry_id: "te-fixed-0001".into(),
+            attachment_id: "attachment-fixed-0001".to_string(),
+            object_key: "attachments/te-fixed-0001/attachment-fixed-0001".to_string(),
+            file_name: "receipt.pdf".to_string(),
+            content_type: "application/pdf".to_string(),
+            size_bytes: 48_213,
+            added_at: 1_700_000_000_000,
+            added_by: "user-fixed-0001".into(),
+        }),
+        vec!["receipt.pdf", "user-fixed-0001"]
+    )]
+    #[case::compacted(
+        TimeEntryEvent::TimeEntryCompactedV1(TimeEntryCompactedV1 {
+            time_entry_id: "te-fixed-0001".into(),
+            user_id: "user-fixed-0001".into(),
+            deleted_at: 1_700_000_000_000,
+            deleted_by: "user-fixed-0001".into(),
+            compacted_at: 1_710_000_000_000,
+            compacted_version: 4,
+            archive_key: "compacted/TimeEntry-te-fixed-0001.jsonl".to_string(),
+        }),
+        vec!["user-fixed-0001", "user-fixed-0001"]
+    )]
+    fn it_should_expose_personal_data(#[case] event: TimeEntryEvent, #[case] expected: Vec<&str>) {
+        assert!(event.occurred_at() > 0);
+
+        assert_eq!(event.personal_data(), expected);
+
+        let masked = event
+            .clone()
+            .map_personal_data(&mut |_| "masked".to_string());
+
+        assert_eq!(masked.personal_data(), vec!["masked"; expected.len()]);
+        let mut originals = expected.iter();
+        assert_eq!(
+            masked.map_personal_data(&mut |_| originals.next().unwrap().to_string()),
+            event
+        );
+    }
+
+    #[rstest]
+    fn it_should_name_the_owner_in_the_events_that_start_a_stream() {
+        let initiated = TimeEntryEvent::TimeEntryInitiatedV1(make_time_entry_initiated_v1_event());
+        let start_set = TimeEntryEvent::TimeEntryStartSetV1(make_time_entry_start_set_v1_event());
+
+        assert_eq!(initiated.data_subject().as_deref(), Some("user-fixed-0001"));
+        assert_eq!(start_set.data_subject(), None);
+    }
+}
+
+#[cfg(test)]
+mod time_entry_event_schema_tests {
+    use super::*;
+    use crate::tests::golden::{assert_catalog, assert_event_goldens};
+    use rstest::rstest;
+
+    #[rstest]
+    fn every_version_should_match_its_golden() {
+        assert_event_goldens::<TimeEntryEvent>("time_entries");
+    }
+
+    #[rstest]
+    fn the_catalog_should_list_every_version() {
+        assert_catalog::<TimeEntryEvent>();
+    }
 }
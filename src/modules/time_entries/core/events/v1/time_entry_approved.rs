@@ -0,0 +1,8 @@
+use crate::shared::core::primitives::{TimeEntryId, UserId};
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, PartialEq, Eq)]
+pub struct TimeEntryApprovedV1 {
+    pub time_entry_id: TimeEntryId,
+    pub approved_at: i64,
+    pub approved_by: UserId,
+}
@@ -0,0 +1,15 @@
+use crate::shared::core::primitives::{TimeEntryId, UserId};
+
+/// Links a file uploaded to the attachment storage under `object_key` to the entry. The
+/// entry itself stays as it was.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, PartialEq, Eq)]
+pub struct TimeEntryAttachmentAddedV1 {
+    pub time_entry_id: TimeEntryId,
+    pub attachment_id: String,
+    pub object_key: String,
+    pub file_name: String,
+    pub content_type: String,
+    pub size_bytes: u64,
+    pub added_at: i64,
+    pub added_by: UserId,
+}
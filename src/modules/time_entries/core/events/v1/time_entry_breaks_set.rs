@@ -0,0 +1,11 @@
+use crate::modules::time_entries::core::breaks::Break;
+use crate::shared::core::primitives::{TimeEntryId, UserId};
+
+/// Replaces the breaks of the entry as a whole.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, PartialEq, Eq)]
+pub struct TimeEntryBreaksSetV1 {
+    pub time_entry_id: TimeEntryId,
+    pub breaks: Vec<Break>,
+    pub updated_at: i64,
+    pub updated_by: UserId,
+}
@@ -0,0 +1,12 @@
+use crate::shared::core::primitives::{TimeEntryId, UserId};
+
+/// A comment on the entry, for instance about why it was not approved. Comments leave the
+/// entry itself as it was.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, PartialEq, Eq)]
+pub struct TimeEntryCommentAddedV1 {
+    pub time_entry_id: TimeEntryId,
+    pub comment_id: String,
+    pub body: String,
+    pub added_at: i64,
+    pub added_by: UserId,
+}
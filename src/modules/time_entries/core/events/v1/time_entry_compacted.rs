@@ -0,0 +1,37 @@
+use crate::shared::core::primitives::{TimeEntryId, UserId};
+
+/// What is left of a deleted entry's stream once compaction moved its events to cold storage:
+/// the deletion it ended on, and where the events it replaced were archived.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, PartialEq, Eq)]
+pub struct TimeEntryCompactedV1 {
+    pub time_entry_id: TimeEntryId,
+    pub user_id: UserId,
+    pub deleted_at: i64,
+    pub deleted_by: UserId,
+    pub compacted_at: i64,
+    /// The stream's version before compaction: how many events were archived.
+    pub compacted_version: i64,
+    pub archive_key: String,
+}
+
+#[cfg(test)]
+mod time_entry_compacted_event_tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    fn it_serializes_and_deserializes_roundtrip() {
+        let event = TimeEntryCompactedV1 {
+            time_entry_id: "te-fixed-0001".into(),
+            user_id: "user-fixed-0001".into(),
+            deleted_at: 1_700_000_000_000,
+            deleted_by: "user-fixed-0001".into(),
+            compacted_at: 1_710_000_000_000,
+            compacted_version: 4,
+            archive_key: "compacted/TimeEntry-te-fixed-0001.jsonl".to_string(),
+        };
+        let json = serde_json::to_value(&event).unwrap();
+        let restored: TimeEntryCompactedV1 = serde_json::from_value(json).unwrap();
+        assert_eq!(restored, event);
+    }
+}
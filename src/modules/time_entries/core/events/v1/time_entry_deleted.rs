@@ -1,8 +1,10 @@
+use crate::shared::core::primitives::{TimeEntryId, UserId};
+
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize, PartialEq, Eq)]
 pub struct TimeEntryDeletedV1 {
-    pub time_entry_id: String,
+    pub time_entry_id: TimeEntryId,
     pub deleted_at: i64,
-    pub deleted_by: String,
+    pub deleted_by: UserId,
 }
 
 #[cfg(test)]
@@ -13,9 +15,9 @@ mod time_entry_deleted_event_tests {
     #[rstest]
     fn it_should_create_the_deleted_event() {
         let event = TimeEntryDeletedV1 {
-            time_entry_id: "te-fixed-0001".to_string(),
+            time_entry_id: "te-fixed-0001".into(),
             deleted_at: 1_700_000_500_000i64,
-            deleted_by: "user-fixed-0001".to_string(),
+            deleted_by: "user-fixed-0001".into(),
         };
         assert_eq!(event.time_entry_id, "te-fixed-0001");
         assert_eq!(event.deleted_at, 1_700_000_500_000i64);
@@ -25,9 +27,9 @@ mod time_entry_deleted_event_tests {
     #[rstest]
     fn it_serializes_and_deserializes_roundtrip() {
         let event = TimeEntryDeletedV1 {
-            time_entry_id: "te-fixed-0001".to_string(),
+            time_entry_id: "te-fixed-0001".into(),
             deleted_at: 1_700_000_500_000i64,
-            deleted_by: "user-fixed-0001".to_string(),
+            deleted_by: "user-fixed-0001".into(),
         };
         let json = serde_json::to_value(&event).unwrap();
         let restored: TimeEntryDeletedV1 = serde_json::from_value(json).unwrap();
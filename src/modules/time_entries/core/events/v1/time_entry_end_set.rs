@@ -1,9 +1,14 @@
+use crate::shared::core::primitives::{TimeEntryId, UserId};
+
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize, PartialEq, Eq)]
 pub struct TimeEntryEndSetV1 {
-    pub time_entry_id: String,
+    pub time_entry_id: TimeEntryId,
     pub ended_at: i64,
+    /// The end as supplied, when the tenant's rounding applied; `ended_at` holds it rounded.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub raw_ended_at: Option<i64>,
     pub updated_at: i64,
-    pub updated_by: String,
+    pub updated_by: UserId,
 }
 
 #[cfg(test)]
@@ -11,7 +16,6 @@ mod time_entry_end_set_event_tests {
     use super::*;
     use crate::tests::fixtures::events::time_entry_end_set_v1::make_time_entry_end_set_v1_event;
     use rstest::{fixture, rstest};
-    use std::fs;
 
     #[fixture]
     fn end_set_event() -> TimeEntryEndSetV1 {
@@ -28,9 +32,9 @@ mod time_entry_end_set_event_tests {
 
     #[fixture]
     fn golden_end_set_event_json() -> serde_json::Value {
-        let s = fs::read_to_string("./src/tests/fixtures/events/json/time_entry_end_set_v1.json")
-            .unwrap();
-        serde_json::from_str(&s).unwrap()
+        let s =
+            include_str!("../../../../../tests/fixtures/events/json/time_entry_end_set_v1.json");
+        serde_json::from_str(s).unwrap()
     }
 
     #[rstest]
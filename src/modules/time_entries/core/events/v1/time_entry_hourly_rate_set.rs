@@ -0,0 +1,10 @@
+use crate::shared::core::primitives::{TimeEntryId, UserId};
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, PartialEq, Eq)]
+pub struct TimeEntryHourlyRateSetV1 {
+    pub time_entry_id: TimeEntryId,
+    pub hourly_rate_cents: i64,
+    pub currency: String,
+    pub updated_at: i64,
+    pub updated_by: UserId,
+}
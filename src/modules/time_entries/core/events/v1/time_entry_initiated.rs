@@ -1,9 +1,11 @@
+use crate::shared::core::primitives::{TimeEntryId, UserId};
+
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize, PartialEq, Eq)]
 pub struct TimeEntryInitiatedV1 {
-    pub time_entry_id: String,
-    pub user_id: String,
+    pub time_entry_id: TimeEntryId,
+    pub user_id: UserId,
     pub created_at: i64,
-    pub created_by: String,
+    pub created_by: UserId,
 }
 
 #[cfg(test)]
@@ -11,7 +13,6 @@ mod time_entry_initiated_event_tests {
     use super::*;
     use crate::tests::fixtures::events::time_entry_initiated_v1::make_time_entry_initiated_v1_event;
     use rstest::{fixture, rstest};
-    use std::fs;
 
     #[fixture]
     fn initiated_event() -> TimeEntryInitiatedV1 {
@@ -28,9 +29,9 @@ mod time_entry_initiated_event_tests {
 
     #[fixture]
     fn golden_initiated_event_json() -> serde_json::Value {
-        let s = fs::read_to_string("./src/tests/fixtures/events/json/time_entry_initiated_v1.json")
-            .unwrap();
-        serde_json::from_str(&s).unwrap()
+        let s =
+            include_str!("../../../../../tests/fixtures/events/json/time_entry_initiated_v1.json");
+        serde_json::from_str(s).unwrap()
     }
 
     #[rstest]
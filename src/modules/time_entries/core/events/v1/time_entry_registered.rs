@@ -1,6 +1,8 @@
+use crate::shared::core::primitives::TimeEntryId;
+
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize, PartialEq, Eq)]
 pub struct TimeEntryRegisteredV1 {
-    pub time_entry_id: String,
+    pub time_entry_id: TimeEntryId,
     pub occurred_at: i64,
 }
 
@@ -9,7 +11,6 @@ mod time_entry_registered_event_tests {
     use super::*;
     use crate::tests::fixtures::events::time_entry_registered_v1::make_time_entry_registered_v1_event;
     use rstest::{fixture, rstest};
-    use std::fs;
 
     #[fixture]
     fn registered_event() -> TimeEntryRegisteredV1 {
@@ -24,9 +25,8 @@ mod time_entry_registered_event_tests {
 
     #[fixture]
     fn golden_registered_event_json() -> serde_json::Value {
-        let s = fs::read_to_string("./src/tests/fixtures/events/json/registered_event_v1.json")
-            .unwrap();
-        serde_json::from_str(&s).unwrap()
+        let s = include_str!("../../../../../tests/fixtures/events/json/registered_event_v1.json");
+        serde_json::from_str(s).unwrap()
     }
 
     #[rstest]
@@ -0,0 +1,10 @@
+use crate::shared::core::primitives::{TimeEntryId, UserId};
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, PartialEq, Eq)]
+pub struct TimeEntryRejectedV1 {
+    pub time_entry_id: TimeEntryId,
+    pub rejected_at: i64,
+    pub rejected_by: UserId,
+    /// Why the entry was sent back, for its owner.
+    pub reason: Option<String>,
+}
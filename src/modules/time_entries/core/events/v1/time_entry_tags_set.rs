@@ -1,7 +1,10 @@
+use crate::modules::time_entries::core::tag::Tag;
+use crate::shared::core::primitives::{TimeEntryId, UserId};
+
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize, PartialEq, Eq)]
 pub struct TimeEntryTagsSetV1 {
-    pub time_entry_id: String,
-    pub tag_ids: Vec<String>,
+    pub time_entry_id: TimeEntryId,
+    pub tag_ids: Vec<Tag>,
     pub updated_at: i64,
-    pub updated_by: String,
+    pub updated_by: UserId,
 }
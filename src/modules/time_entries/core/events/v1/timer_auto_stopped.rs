@@ -0,0 +1,13 @@
+use crate::shared::core::primitives::TimeEntryId;
+
+/// Actor recorded on read models for changes made by the auto-stop worker.
+pub const AUTO_STOP_ACTOR: &str = "system:timer-auto-stop";
+
+/// A timer that ran past the configured maximum was stopped at `ended_at` by the system.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, PartialEq, Eq)]
+pub struct TimerAutoStoppedV1 {
+    pub time_entry_id: TimeEntryId,
+    pub ended_at: i64,
+    pub reason: String,
+    pub stopped_at: i64,
+}
@@ -1,5 +1,7 @@
 use crate::modules::time_entries::core::events::TimeEntryEvent;
+use crate::modules::time_entries::core::hourly_rate::HourlyRate;
 use crate::modules::time_entries::core::state::TimeEntryState;
+use crate::modules::time_entries::core::time_interval::TimeInterval;
 
 pub fn evolve(state: TimeEntryState, event: TimeEntryEvent) -> TimeEntryState {
     match (state, event) {
@@ -11,6 +13,7 @@ pub fn evolve(state: TimeEntryState, event: TimeEntryEvent) -> TimeEntryState {
             tag_ids: vec![],
             created_at: e.created_at,
             created_by: e.created_by,
+            hourly_rate: None,
         },
         (
             TimeEntryState::Draft {
@@ -20,6 +23,7 @@ pub fn evolve(state: TimeEntryState, event: TimeEntryEvent) -> TimeEntryState {
                 tag_ids,
                 created_at,
                 created_by,
+                hourly_rate,
                 ..
             },
             TimeEntryEvent::TimeEntryStartSetV1(e),
@@ -31,6 +35,7 @@ pub fn evolve(state: TimeEntryState, event: TimeEntryEvent) -> TimeEntryState {
             tag_ids,
             created_at,
             created_by,
+            hourly_rate,
         },
         (
             TimeEntryState::Draft {
@@ -40,6 +45,7 @@ pub fn evolve(state: TimeEntryState, event: TimeEntryEvent) -> TimeEntryState {
                 tag_ids,
                 created_at,
                 created_by,
+                hourly_rate,
                 ..
             },
             TimeEntryEvent::TimeEntryEndSetV1(e),
@@ -51,6 +57,7 @@ pub fn evolve(state: TimeEntryState, event: TimeEntryEvent) -> TimeEntryState {
             tag_ids,
             created_at,
             created_by,
+            hourly_rate,
         },
         (
             TimeEntryState::Draft {
@@ -61,56 +68,62 @@ pub fn evolve(state: TimeEntryState, event: TimeEntryEvent) -> TimeEntryState {
                 tag_ids,
                 created_at,
                 created_by,
+                hourly_rate,
             },
             TimeEntryEvent::TimeEntryRegisteredV1(_),
         ) => TimeEntryState::Registered {
             time_entry_id,
             user_id,
-            started_at: started_at.unwrap_or(0),
-            ended_at: ended_at.unwrap_or(0),
+            interval: TimeInterval::recorded(started_at.unwrap_or(0), ended_at.unwrap_or(0)),
+            breaks: vec![],
             tag_ids,
             created_at,
             created_by,
+            hourly_rate,
         },
         (
             TimeEntryState::Registered {
                 time_entry_id,
                 user_id,
-                ended_at,
+                interval,
+                breaks,
                 tag_ids,
                 created_at,
                 created_by,
-                ..
+                hourly_rate,
             },
             TimeEntryEvent::TimeEntryStartSetV1(e),
         ) => TimeEntryState::Registered {
             time_entry_id,
             user_id,
-            started_at: e.started_at,
-            ended_at,
+            interval: TimeInterval::recorded(e.started_at, interval.end().as_millis()),
+            breaks,
             tag_ids,
             created_at,
             created_by,
+            hourly_rate,
         },
         (
             TimeEntryState::Registered {
                 time_entry_id,
                 user_id,
-                started_at,
+                interval,
+                breaks,
                 tag_ids,
                 created_at,
                 created_by,
-                ..
+                hourly_rate,
             },
             TimeEntryEvent::TimeEntryEndSetV1(e),
         ) => TimeEntryState::Registered {
             time_entry_id,
             user_id,
-            started_at,
-            ended_at: e.ended_at,
+            interval: TimeInterval::recorded(interval.start().as_millis(), e.ended_at),
+            breaks,
             tag_ids,
             created_at,
             created_by,
+            hourly_rate,
         },
         (
             TimeEntryState::Draft {
@@ -120,6 +133,7 @@ pub fn evolve(state: TimeEntryState, event: TimeEntryEvent) -> TimeEntryState {
                 ended_at,
                 created_at,
                 created_by,
+                hourly_rate,
                 ..
             },
             TimeEntryEvent::TimeEntryTagsSetV1(e),
@@ -131,26 +145,172 @@ pub fn evolve(state: TimeEntryState, event: TimeEntryEvent) -> TimeEntryState {
             tag_ids: e.tag_ids,
             created_at,
             created_by,
+            hourly_rate,
         },
         (
             TimeEntryState::Registered {
                 time_entry_id,
                 user_id,
-                started_at,
-                ended_at,
+                interval,
+                breaks,
                 created_at,
                 created_by,
+                hourly_rate,
                 ..
             },
             TimeEntryEvent::TimeEntryTagsSetV1(e),
         ) => TimeEntryState::Registered {
+            time_entry_id,
+            user_id,
+            interval,
+            breaks,
+            tag_ids: e.tag_ids,
+            created_at,
+            created_by,
+            hourly_rate,
+        },
+        (
+            TimeEntryState::Draft {
+                time_entry_id,
+                user_id,
+                started_at,
+                ended_at,
+                tag_ids,
+                created_at,
+                created_by,
+                ..
+            },
+            TimeEntryEvent::TimeEntryHourlyRateSetV1(e),
+        ) => TimeEntryState::Draft {
             time_entry_id,
             user_id,
             started_at,
             ended_at,
-            tag_ids: e.tag_ids,
+            tag_ids,
+            created_at,
+            created_by,
+            hourly_rate: Some(HourlyRate {
+                cents: e.hourly_rate_cents,
+                currency: e.currency,
+            }),
+        },
+        (
+            TimeEntryState::Registered {
+                time_entry_id,
+                user_id,
+                interval,
+                breaks,
+                tag_ids,
+                created_at,
+                created_by,
+                ..
+            },
+            TimeEntryEvent::TimeEntryHourlyRateSetV1(e),
+        ) => TimeEntryState::Registered {
+            time_entry_id,
+            user_id,
+            interval,
+            breaks,
+            tag_ids,
+            created_at,
+            created_by,
+            hourly_rate: Some(HourlyRate {
+                cents: e.hourly_rate_cents,
+                currency: e.currency,
+            }),
+        },
+        (
+            TimeEntryState::Draft {
+                time_entry_id,
+                user_id,
+                started_at: Some(started_at),
+                ended_at: None,
+                tag_ids,
+                created_at,
+                created_by,
+                hourly_rate,
+            },
+            TimeEntryEvent::TimerAutoStoppedV1(e),
+        ) => TimeEntryState::Draft {
+            time_entry_id,
+            user_id,
+            started_at: Some(started_at),
+            ended_at: Some(e.ended_at),
+            tag_ids,
             created_at,
             created_by,
+            hourly_rate,
+        },
+        (
+            TimeEntryState::Registered {
+                time_entry_id,
+                user_id,
+                interval,
+                breaks,
+                tag_ids,
+                created_at,
+                created_by,
+                hourly_rate,
+            },
+            TimeEntryEvent::TimeEntryApprovedV1(e),
+        ) => TimeEntryState::Approved {
+            time_entry_id,
+            user_id,
+            interval,
+            breaks,
+            tag_ids,
+            created_at,
+            created_by,
+            hourly_rate,
+            approved_at: e.approved_at,
+            approved_by: e.approved_by,
+        },
+        (
+            TimeEntryState::Registered {
+                time_entry_id,
+                user_id,
+                interval,
+                tag_ids,
+                created_at,
+                created_by,
+                hourly_rate,
+                ..
+            },
+            TimeEntryEvent::TimeEntryBreaksSetV1(e),
+        ) => TimeEntryState::Registered {
+            time_entry_id,
+            user_id,
+            interval,
+            breaks: e.breaks,
+            tag_ids,
+            created_at,
+            created_by,
+            hourly_rate,
+        },
+        (
+            TimeEntryState::Draft {
+                time_entry_id,
+                user_id,
+                ..
+            }
+            | TimeEntryState::Registered {
+                time_entry_id,
+                user_id,
+                ..
+            },
+            TimeEntryEvent::TimeEntryDeletedV1(e),
+        ) => TimeEntryState::Deleted {
+            time_entry_id,
+            user_id,
+            deleted_at: e.deleted_at,
+            deleted_by: e.deleted_by,
+        },
+        // The only event of a compacted stream.
+        (_, TimeEntryEvent::TimeEntryCompactedV1(e)) => TimeEntryState::Deleted {
+            time_entry_id: e.time_entry_id,
+            user_id: e.user_id,
+            deleted_at: e.deleted_at,
+            deleted_by: e.deleted_by,
         },
         (state, _) => state,
     }
@@ -159,53 +319,60 @@ pub fn evolve(state: TimeEntryState, event: TimeEntryEvent) -> TimeEntryState {
 #[cfg(test)]
 mod time_entry_evolve_tests {
     use super::*;
+    use crate::modules::time_entries::core::events::v1::time_entry_approved::TimeEntryApprovedV1;
+    use crate::modules::time_entries::core::events::v1::time_entry_compacted::TimeEntryCompactedV1;
     use crate::modules::time_entries::core::events::v1::time_entry_end_set::TimeEntryEndSetV1;
+    use crate::modules::time_entries::core::events::v1::time_entry_hourly_rate_set::TimeEntryHourlyRateSetV1;
     use crate::modules::time_entries::core::events::v1::time_entry_initiated::TimeEntryInitiatedV1;
     use crate::modules::time_entries::core::events::v1::time_entry_registered::TimeEntryRegisteredV1;
     use crate::modules::time_entries::core::events::v1::time_entry_start_set::TimeEntryStartSetV1;
     use crate::modules::time_entries::core::events::v1::time_entry_tags_set::TimeEntryTagsSetV1;
+    use crate::modules::time_entries::core::events::v1::timer_auto_stopped::TimerAutoStoppedV1;
+    use crate::modules::time_entries::core::tag::Tag;
     use rstest::rstest;
 
     fn make_initiated() -> TimeEntryInitiatedV1 {
         TimeEntryInitiatedV1 {
-            time_entry_id: "te-0001".to_string(),
-            user_id: "user-0001".to_string(),
+            time_entry_id: "te-0001".into(),
+            user_id: "user-0001".into(),
             created_at: 1_000,
-            created_by: "user-0001".to_string(),
+            created_by: "user-0001".into(),
         }
     }
 
     fn make_start_set(started_at: i64) -> TimeEntryStartSetV1 {
         TimeEntryStartSetV1 {
-            time_entry_id: "te-0001".to_string(),
+            time_entry_id: "te-0001".into(),
             started_at,
             updated_at: 1_000,
-            updated_by: "user-0001".to_string(),
+            updated_by: "user-0001".into(),
+            raw_started_at: None,
         }
     }
 
     fn make_end_set(ended_at: i64) -> TimeEntryEndSetV1 {
         TimeEntryEndSetV1 {
-            time_entry_id: "te-0001".to_string(),
+            time_entry_id: "te-0001".into(),
             ended_at,
             updated_at: 1_000,
-            updated_by: "user-0001".to_string(),
+            updated_by: "user-0001".into(),
+            raw_ended_at: None,
         }
     }
 
     fn make_registered() -> TimeEntryRegisteredV1 {
         TimeEntryRegisteredV1 {
-            time_entry_id: "te-0001".to_string(),
+            time_entry_id: "te-0001".into(),
             occurred_at: 1_000,
         }
     }
 
     fn make_tags_set(tag_ids: Vec<String>) -> TimeEntryTagsSetV1 {
         TimeEntryTagsSetV1 {
-            time_entry_id: "te-0001".to_string(),
-            tag_ids,
+            time_entry_id: "te-0001".into(),
+            tag_ids: Tag::parse_all(&tag_ids).unwrap(),
             updated_at: 1_000,
-            updated_by: "user-0001".to_string(),
+            updated_by: "user-0001".into(),
         }
     }
 
@@ -280,13 +447,14 @@ mod time_entry_evolve_tests {
     #[rstest]
     fn draft_plus_registered_becomes_registered() {
         let draft = TimeEntryState::Draft {
-            time_entry_id: "te-0001".to_string(),
-            user_id: "user-0001".to_string(),
+            time_entry_id: "te-0001".into(),
+            user_id: "user-0001".into(),
             started_at: Some(500),
             ended_at: Some(800),
-            tag_ids: vec!["tag-1".to_string()],
+            tag_ids: vec![Tag::parse("tag-1").unwrap()],
             created_at: 1_000,
-            created_by: "user-0001".to_string(),
+            created_by: "user-0001".into(),
+            hourly_rate: None,
         };
         let state = evolve(
             draft,
@@ -294,13 +462,9 @@ mod time_entry_evolve_tests {
         );
         match state {
             TimeEntryState::Registered {
-                started_at,
-                ended_at,
-                tag_ids,
-                ..
+                interval, tag_ids, ..
             } => {
-                assert_eq!(started_at, 500);
-                assert_eq!(ended_at, 800);
+                assert_eq!(interval, TimeInterval::new(500, 800).unwrap());
                 assert_eq!(tag_ids, vec!["tag-1".to_string()]);
             }
             _ => panic!("expected Registered"),
@@ -310,26 +474,22 @@ mod time_entry_evolve_tests {
     #[rstest]
     fn registered_plus_start_set_updates_started_at() {
         let registered = TimeEntryState::Registered {
-            time_entry_id: "te-0001".to_string(),
-            user_id: "user-0001".to_string(),
-            started_at: 500,
-            ended_at: 800,
+            time_entry_id: "te-0001".into(),
+            user_id: "user-0001".into(),
+            interval: TimeInterval::new(500, 800).unwrap(),
             tag_ids: vec![],
             created_at: 1_000,
-            created_by: "user-0001".to_string(),
+            created_by: "user-0001".into(),
+            hourly_rate: None,
+            breaks: vec![],
         };
         let state = evolve(
             registered,
             TimeEntryEvent::TimeEntryStartSetV1(make_start_set(600)),
         );
         match state {
-            TimeEntryState::Registered {
-                started_at,
-                ended_at,
-                ..
-            } => {
-                assert_eq!(started_at, 600);
-                assert_eq!(ended_at, 800);
+            TimeEntryState::Registered { interval, .. } => {
+                assert_eq!(interval, TimeInterval::new(600, 800).unwrap());
             }
             _ => panic!("expected Registered"),
         }
@@ -338,26 +498,22 @@ mod time_entry_evolve_tests {
     #[rstest]
     fn registered_plus_end_set_updates_ended_at() {
         let registered = TimeEntryState::Registered {
-            time_entry_id: "te-0001".to_string(),
-            user_id: "user-0001".to_string(),
-            started_at: 500,
-            ended_at: 800,
+            time_entry_id: "te-0001".into(),
+            user_id: "user-0001".into(),
+            interval: TimeInterval::new(500, 800).unwrap(),
             tag_ids: vec![],
             created_at: 1_000,
-            created_by: "user-0001".to_string(),
+            created_by: "user-0001".into(),
+            hourly_rate: None,
+            breaks: vec![],
         };
         let state = evolve(
             registered,
             TimeEntryEvent::TimeEntryEndSetV1(make_end_set(900)),
         );
         match state {
-            TimeEntryState::Registered {
-                started_at,
-                ended_at,
-                ..
-            } => {
-                assert_eq!(started_at, 500);
-                assert_eq!(ended_at, 900);
+            TimeEntryState::Registered { interval, .. } => {
+                assert_eq!(interval, TimeInterval::new(500, 900).unwrap());
             }
             _ => panic!("expected Registered"),
         }
@@ -387,13 +543,14 @@ mod time_entry_evolve_tests {
     #[rstest]
     fn registered_plus_tags_set_updates_tag_ids() {
         let registered = TimeEntryState::Registered {
-            time_entry_id: "te-0001".to_string(),
-            user_id: "user-0001".to_string(),
-            started_at: 500,
-            ended_at: 800,
+            time_entry_id: "te-0001".into(),
+            user_id: "user-0001".into(),
+            interval: TimeInterval::new(500, 800).unwrap(),
             tag_ids: vec![],
             created_at: 1_000,
-            created_by: "user-0001".to_string(),
+            created_by: "user-0001".into(),
+            hourly_rate: None,
+            breaks: vec![],
         };
         let state = evolve(
             registered,
@@ -407,6 +564,43 @@ mod time_entry_evolve_tests {
         }
     }
 
+    #[rstest]
+    fn registered_plus_approved_becomes_approved() {
+        let registered = TimeEntryState::Registered {
+            time_entry_id: "te-0001".into(),
+            user_id: "user-0001".into(),
+            interval: TimeInterval::new(500, 800).unwrap(),
+            tag_ids: vec![Tag::parse("tag-x").unwrap()],
+            created_at: 1_000,
+            created_by: "user-0001".into(),
+            hourly_rate: None,
+            breaks: vec![],
+        };
+        let state = evolve(
+            registered,
+            TimeEntryEvent::TimeEntryApprovedV1(TimeEntryApprovedV1 {
+                time_entry_id: "te-0001".into(),
+                approved_at: 2_000,
+                approved_by: "manager-0001".into(),
+            }),
+        );
+        match state {
+            TimeEntryState::Approved {
+                interval,
+                tag_ids,
+                approved_at,
+                approved_by,
+                ..
+            } => {
+                assert_eq!(interval, TimeInterval::new(500, 800).unwrap());
+                assert_eq!(tag_ids, vec!["tag-x".to_string()]);
+                assert_eq!(approved_at, 2_000);
+                assert_eq!(approved_by, "manager-0001");
+            }
+            _ => panic!("expected Approved"),
+        }
+    }
+
     #[rstest]
     fn fallback_none_plus_start_set_is_unchanged() {
         let state = evolve(
@@ -428,13 +622,14 @@ mod time_entry_evolve_tests {
     #[rstest]
     fn fallback_registered_plus_initiated_is_unchanged() {
         let registered = TimeEntryState::Registered {
-            time_entry_id: "te-0001".to_string(),
-            user_id: "user-0001".to_string(),
-            started_at: 500,
-            ended_at: 800,
+            time_entry_id: "te-0001".into(),
+            user_id: "user-0001".into(),
+            interval: TimeInterval::new(500, 800).unwrap(),
             tag_ids: vec![],
             created_at: 1_000,
-            created_by: "user-0001".to_string(),
+            created_by: "user-0001".into(),
+            hourly_rate: None,
+            breaks: vec![],
         };
         let expected = registered.clone();
         let state = evolve(
@@ -443,4 +638,99 @@ mod time_entry_evolve_tests {
         );
         assert_eq!(state, expected);
     }
+
+    fn make_draft(started_at: Option<i64>, ended_at: Option<i64>) -> TimeEntryState {
+        TimeEntryState::Draft {
+            time_entry_id: "te-0001".into(),
+            user_id: "user-0001".into(),
+            started_at,
+            ended_at,
+            tag_ids: vec![],
+            created_at: 1_000,
+            created_by: "user-0001".into(),
+            hourly_rate: None,
+        }
+    }
+
+    fn make_auto_stopped(ended_at: i64) -> TimeEntryEvent {
+        TimeEntryEvent::TimerAutoStoppedV1(TimerAutoStoppedV1 {
+            time_entry_id: "te-0001".into(),
+            ended_at,
+            reason: "exceeded 12h".to_string(),
+            stopped_at: 2_000,
+        })
+    }
+
+    #[rstest]
+    fn running_draft_plus_timer_auto_stopped_sets_ended_at() {
+        let state = evolve(make_draft(Some(500), None), make_auto_stopped(900));
+        assert_eq!(state, make_draft(Some(500), Some(900)));
+    }
+
+    #[rstest]
+    #[case::not_started(make_draft(None, None))]
+    #[case::already_stopped(make_draft(Some(500), Some(800)))]
+    fn timer_auto_stopped_leaves_stopped_timers_unchanged(#[case] draft: TimeEntryState) {
+        let state = evolve(draft.clone(), make_auto_stopped(900));
+        assert_eq!(state, draft);
+    }
+
+    #[rstest]
+    fn hourly_rate_set_prices_draft_and_registered_entries() {
+        let rate_set = || {
+            TimeEntryEvent::TimeEntryHourlyRateSetV1(TimeEntryHourlyRateSetV1 {
+                time_entry_id: "te-0001".into(),
+                hourly_rate_cents: 9_500,
+                currency: "EUR".to_string(),
+                updated_at: 2_000,
+                updated_by: "user-0001".into(),
+            })
+        };
+        let expected = Some(HourlyRate {
+            cents: 9_500,
+            currency: "EUR".to_string(),
+        });
+
+        let draft = evolve(make_draft(Some(500), Some(800)), rate_set());
+        let registered = evolve(
+            evolve(
+                make_draft(Some(500), Some(800)),
+                TimeEntryEvent::TimeEntryRegisteredV1(make_registered()),
+            ),
+            rate_set(),
+        );
+
+        assert!(
+            matches!(draft, TimeEntryState::Draft { hourly_rate, .. } if hourly_rate == expected)
+        );
+        assert!(
+            matches!(registered, TimeEntryState::Registered { hourly_rate, .. } if hourly_rate == expected)
+        );
+    }
+
+    #[rstest]
+    fn compacted_restores_the_deleted_state_from_nothing() {
+        let state = evolve(
+            TimeEntryState::None,
+            TimeEntryEvent::TimeEntryCompactedV1(TimeEntryCompactedV1 {
+                time_entry_id: "te-0001".into(),
+                user_id: "user-0001".into(),
+                deleted_at: 3_000,
+                deleted_by: "user-0002".into(),
+                compacted_at: 9_000,
+                compacted_version: 4,
+                archive_key: "compacted/TimeEntry-te-0001.jsonl".to_string(),
+            }),
+        );
+
+        assert_eq!(
+            state,
+            TimeEntryState::Deleted {
+                time_entry_id: "te-0001".into(),
+                user_id: "user-0001".into(),
+                deleted_at: 3_000,
+                deleted_by: "user-0002".into(),
+            }
+        );
+    }
 }
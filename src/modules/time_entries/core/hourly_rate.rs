@@ -0,0 +1,63 @@
+/// The price of one hour of a time entry, in minor units of an ISO 4217 currency.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct HourlyRate {
+    pub cents: i64,
+    pub currency: String,
+}
+
+const HOUR_MS: i128 = 3_600_000;
+
+impl HourlyRate {
+    /// The cost of `duration_ms` at this rate, rounded half away from zero to whole cents.
+    pub fn amount_cents(&self, duration_ms: i64) -> i64 {
+        let product = i128::from(self.cents) * i128::from(duration_ms);
+        let rounded = (product.abs() + HOUR_MS / 2) / HOUR_MS;
+        let signed = if product < 0 { -rounded } else { rounded };
+        i64::try_from(signed).unwrap_or(if signed < 0 { i64::MIN } else { i64::MAX })
+    }
+}
+
+/// Three uppercase ASCII letters, the shape of an ISO 4217 code.
+pub fn is_currency_code(value: &str) -> bool {
+    value.len() == 3 && value.bytes().all(|byte| byte.is_ascii_uppercase())
+}
+
+#[cfg(test)]
+mod hourly_rate_tests {
+    use super::*;
+    use rstest::rstest;
+
+    fn rate(cents: i64) -> HourlyRate {
+        HourlyRate {
+            cents,
+            currency: "EUR".to_string(),
+        }
+    }
+
+    #[rstest]
+    #[case::one_hour(10_000, 3_600_000, 10_000)]
+    #[case::half_hour(10_000, 1_800_000, 5_000)]
+    #[case::rounds_half_up(1, 1_800_000, 1)]
+    #[case::rounds_down(1, 1_799_999, 0)]
+    #[case::zero_duration(10_000, 0, 0)]
+    #[case::negative_duration(1, -1_800_000, -1)]
+    #[case::saturates(i64::MAX, i64::MAX, i64::MAX)]
+    #[case::saturates_negative(i64::MAX, i64::MIN, i64::MIN)]
+    fn it_should_compute_the_amount(
+        #[case] cents: i64,
+        #[case] duration_ms: i64,
+        #[case] expected: i64,
+    ) {
+        assert_eq!(rate(cents).amount_cents(duration_ms), expected);
+    }
+
+    #[rstest]
+    #[case::valid("EUR", true)]
+    #[case::lowercase("eur", false)]
+    #[case::too_short("EU", false)]
+    #[case::too_long("EURO", false)]
+    #[case::non_ascii("ÉU", false)]
+    fn it_should_recognise_currency_codes(#[case] value: &str, #[case] expected: bool) {
+        assert_eq!(is_currency_code(value), expected);
+    }
+}
@@ -1,9 +1,141 @@
+use crate::modules::time_entries::core::events::TimeEntryEvent;
+use crate::shared::core::primitives::TimeEntryId;
+
 /// Domain intents produced by the decider as part of an Accepted decision.
 /// The outbound intent_outbox adapter translates these into OutboxRows.
 #[derive(Clone)]
 pub enum TimeEntryIntent {
     NotifyUser {
-        time_entry_id: String,
+        time_entry_id: TimeEntryId,
         occurred_at: i64,
     },
+    /// Tell the user their timer was stopped for them, and why.
+    NotifyTimerAutoStopped {
+        time_entry_id: TimeEntryId,
+        reason: String,
+        occurred_at: i64,
+    },
+}
+
+impl TimeEntryIntent {
+    /// Every intent type, as written to the outbox `event_type` and keyed on by intent
+    /// handlers.
+    pub const TYPES: [&'static str; 2] = ["TimeEntryTagsSet", "TimerAutoStopped"];
+
+    pub fn intent_type(&self) -> &'static str {
+        match self {
+            TimeEntryIntent::NotifyUser { .. } => Self::TYPES[0],
+            TimeEntryIntent::NotifyTimerAutoStopped { .. } => Self::TYPES[1],
+        }
+    }
+
+    /// The intent type the deciders enqueue alongside `event`, given the event before it in
+    /// the same stream. Keep in step with the deciders: the outbox integrity check relies on it.
+    pub fn expected_for(
+        previous: Option<&TimeEntryEvent>,
+        event: &TimeEntryEvent,
+    ) -> Option<&'static str> {
+        match (previous, event) {
+            (_, TimeEntryEvent::TimeEntryTagsSetV1(_))
+            | (_, TimeEntryEvent::TimeEntryHourlyRateSetV1(_)) => Some(Self::TYPES[0]),
+            (
+                Some(TimeEntryEvent::TimerAutoStoppedV1(_)),
+                TimeEntryEvent::TimeEntryRegisteredV1(_),
+            ) => Some(Self::TYPES[1]),
+            (Some(_), TimeEntryEvent::TimeEntryRegisteredV1(_)) => Some(Self::TYPES[0]),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod time_entry_intent_tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(TimeEntryIntent::NotifyUser { time_entry_id: "te-1".into(), occurred_at: 0 }, "TimeEntryTagsSet")]
+    #[case(
+        TimeEntryIntent::NotifyTimerAutoStopped {
+            time_entry_id: "te-1".into(),
+            reason: "too long".to_string(),
+            occurred_at: 0,
+        },
+        "TimerAutoStopped"
+    )]
+    fn it_should_name_the_intent_type(#[case] intent: TimeEntryIntent, #[case] expected: &str) {
+        assert_eq!(intent.intent_type(), expected);
+        assert!(TimeEntryIntent::TYPES.contains(&expected));
+    }
+
+    fn event(kind: &str) -> TimeEntryEvent {
+        use crate::modules::time_entries::core::events::v1::{
+            time_entry_end_set::TimeEntryEndSetV1,
+            time_entry_hourly_rate_set::TimeEntryHourlyRateSetV1,
+            time_entry_initiated::TimeEntryInitiatedV1,
+            time_entry_registered::TimeEntryRegisteredV1, time_entry_tags_set::TimeEntryTagsSetV1,
+            timer_auto_stopped::TimerAutoStoppedV1,
+        };
+        let id = "te-1".to_string();
+        match kind {
+            "initiated" => TimeEntryEvent::TimeEntryInitiatedV1(TimeEntryInitiatedV1 {
+                time_entry_id: id.into(),
+                user_id: "u-1".into(),
+                created_at: 0,
+                created_by: "u-1".into(),
+            }),
+            "end_set" => TimeEntryEvent::TimeEntryEndSetV1(TimeEntryEndSetV1 {
+                time_entry_id: id.into(),
+                ended_at: 1,
+                updated_at: 0,
+                updated_by: "u-1".into(),
+                raw_ended_at: None,
+            }),
+            "registered" => TimeEntryEvent::TimeEntryRegisteredV1(TimeEntryRegisteredV1 {
+                time_entry_id: id.into(),
+                occurred_at: 0,
+            }),
+            "tags_set" => TimeEntryEvent::TimeEntryTagsSetV1(TimeEntryTagsSetV1 {
+                time_entry_id: id.into(),
+                tag_ids: vec![],
+                updated_at: 0,
+                updated_by: "u-1".into(),
+            }),
+            "rate_set" => TimeEntryEvent::TimeEntryHourlyRateSetV1(TimeEntryHourlyRateSetV1 {
+                time_entry_id: id.into(),
+                hourly_rate_cents: 1,
+                currency: "EUR".to_string(),
+                updated_at: 0,
+                updated_by: "u-1".into(),
+            }),
+            "auto_stopped" => TimeEntryEvent::TimerAutoStoppedV1(TimerAutoStoppedV1 {
+                time_entry_id: id.into(),
+                ended_at: 1,
+                reason: "too long".to_string(),
+                stopped_at: 0,
+            }),
+            _ => unreachable!("{kind}"),
+        }
+    }
+
+    #[rstest]
+    #[case::tags_set(None, "tags_set", Some("TimeEntryTagsSet"))]
+    #[case::rate_set(Some("initiated"), "rate_set", Some("TimeEntryTagsSet"))]
+    #[case::registered_by_a_set(Some("end_set"), "registered", Some("TimeEntryTagsSet"))]
+    #[case::registered_by_auto_stop(Some("auto_stopped"), "registered", Some("TimerAutoStopped"))]
+    #[case::initiated(None, "initiated", None)]
+    #[case::end_set(Some("initiated"), "end_set", None)]
+    #[case::auto_stopped(Some("end_set"), "auto_stopped", None)]
+    fn it_should_name_the_intent_an_event_carries(
+        #[case] previous: Option<&str>,
+        #[case] kind: &str,
+        #[case] expected: Option<&str>,
+    ) {
+        let previous = previous.map(event);
+
+        assert_eq!(
+            TimeEntryIntent::expected_for(previous.as_ref(), &event(kind)),
+            expected
+        );
+    }
 }
@@ -0,0 +1,243 @@
+// Payroll period locks.
+//
+// Once a period is closed for payroll, the entries inside it must not change. Locks live in
+// a single `PeriodLocks` stream: a lock either covers one user or, without a user, everyone
+// in the deployment. Command handlers fold the stream into `PeriodLocks` and refuse any
+// change that touches a locked interval, whether the entry is moved out of, into or within
+// the period. Unlocking appends a `PeriodUnlockedV1`; the lock history stays in the stream.
+
+use crate::modules::time_entries::core::state::TimeEntryState;
+use crate::modules::time_entries::core::time_interval::TimeInterval;
+use crate::modules::time_entries::core::user_time_entries::{Interval, claim_of};
+use crate::shared::core::domain_event::DomainEvent;
+use std::collections::BTreeMap;
+use thiserror::Error;
+
+pub const PERIOD_LOCKS_STREAM_ID: &str = "PeriodLocks";
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct PeriodLockedV1 {
+    pub lock_id: String,
+    /// `None` locks the period for every user.
+    pub user_id: Option<String>,
+    pub from: i64,
+    pub to: i64,
+    pub locked_at: i64,
+    pub locked_by: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct PeriodUnlockedV1 {
+    pub lock_id: String,
+    pub unlocked_at: i64,
+    pub unlocked_by: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize, DomainEvent)]
+#[serde(tag = "type")]
+pub enum PeriodLocksEvent {
+    PeriodLockedV1(PeriodLockedV1),
+    PeriodUnlockedV1(PeriodUnlockedV1),
+}
+
+/// A locked half-open period `[from, to)`.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct PeriodLock {
+    pub lock_id: String,
+    pub user_id: Option<String>,
+    pub from: i64,
+    pub to: i64,
+    pub locked_at: i64,
+    pub locked_by: String,
+}
+
+impl PeriodLock {
+    fn applies_to(&self, user_id: &str) -> bool {
+        self.user_id
+            .as_deref()
+            .is_none_or(|locked| locked == user_id)
+    }
+
+    fn covers(&self, interval: &Interval) -> bool {
+        interval.overlaps(&TimeInterval::recorded(self.from, self.to).into())
+    }
+}
+
+/// The active locks, keyed by lock id.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PeriodLocks {
+    pub locks: BTreeMap<String, PeriodLock>,
+}
+
+impl PeriodLocks {
+    /// The first active lock covering any part of `interval` for `user_id`.
+    pub fn lock_covering(&self, user_id: &str, interval: &Interval) -> Option<&PeriodLock> {
+        self.locks
+            .values()
+            .find(|lock| lock.applies_to(user_id) && lock.covers(interval))
+    }
+}
+
+pub fn evolve_period_locks(mut state: PeriodLocks, event: PeriodLocksEvent) -> PeriodLocks {
+    match event {
+        PeriodLocksEvent::PeriodLockedV1(e) => {
+            state.locks.insert(
+                e.lock_id.clone(),
+                PeriodLock {
+                    lock_id: e.lock_id,
+                    user_id: e.user_id,
+                    from: e.from,
+                    to: e.to,
+                    locked_at: e.locked_at,
+                    locked_by: e.locked_by,
+                },
+            );
+        }
+        PeriodLocksEvent::PeriodUnlockedV1(e) => {
+            state.locks.remove(&e.lock_id);
+        }
+    }
+    state
+}
+
+#[derive(Debug, Clone, Error, PartialEq, Eq)]
+pub enum PeriodLockError {
+    #[error("period lock {lock_id} already exists")]
+    AlreadyLocked { lock_id: String },
+
+    #[error("period lock {lock_id} not found")]
+    LockNotFound { lock_id: String },
+
+    #[error("time entry falls in period locked by {lock_id}")]
+    Locked { lock_id: String },
+}
+
+/// Refuses a change to a time entry when its interval before or after the change touches a
+/// locked period of its user.
+pub fn ensure_unlocked(
+    locks: &PeriodLocks,
+    before: &TimeEntryState,
+    after: &TimeEntryState,
+) -> Result<(), PeriodLockError> {
+    for (user_id, _, interval) in [claim_of(before), claim_of(after)].into_iter().flatten() {
+        if let Some(lock) = locks.lock_covering(user_id, &interval) {
+            return Err(PeriodLockError::Locked {
+                lock_id: lock.lock_id.clone(),
+            });
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod period_locks_tests {
+    use super::*;
+    use rstest::rstest;
+
+    fn locked(lock_id: &str, user_id: Option<&str>, from: i64, to: i64) -> PeriodLocksEvent {
+        PeriodLocksEvent::PeriodLockedV1(PeriodLockedV1 {
+            lock_id: lock_id.to_string(),
+            user_id: user_id.map(str::to_string),
+            from,
+            to,
+            locked_at: 0,
+            locked_by: "admin-1".to_string(),
+        })
+    }
+
+    fn fold(events: Vec<PeriodLocksEvent>) -> PeriodLocks {
+        events
+            .into_iter()
+            .fold(PeriodLocks::default(), evolve_period_locks)
+    }
+
+    fn registered(user_id: &str, started_at: i64, ended_at: i64) -> TimeEntryState {
+        TimeEntryState::Registered {
+            time_entry_id: "te-1".into(),
+            user_id: user_id.into(),
+            interval: TimeInterval::new(started_at, ended_at).unwrap(),
+            tag_ids: vec![],
+            created_at: 0,
+            created_by: user_id.into(),
+            hourly_rate: None,
+            breaks: vec![],
+        }
+    }
+
+    #[rstest]
+    fn it_should_track_active_locks() {
+        let state = fold(vec![
+            locked("lock-1", None, 100, 200),
+            locked("lock-2", Some("u-1"), 300, 400),
+            PeriodLocksEvent::PeriodUnlockedV1(PeriodUnlockedV1 {
+                lock_id: "lock-1".to_string(),
+                unlocked_at: 0,
+                unlocked_by: "admin-1".to_string(),
+            }),
+        ]);
+
+        assert_eq!(state.locks.keys().collect::<Vec<_>>(), vec!["lock-2"]);
+    }
+
+    #[rstest]
+    #[case::inside("u-1", 120, 150, Some("lock-1"))]
+    #[case::straddling_the_start("u-1", 50, 101, Some("lock-1"))]
+    #[case::ending_at_the_start("u-1", 50, 100, None)]
+    #[case::starting_at_the_end("u-1", 200, 250, None)]
+    #[case::user_lock("u-1", 350, 360, Some("lock-2"))]
+    #[case::other_users_lock("u-2", 350, 360, None)]
+    fn it_should_refuse_changes_touching_a_locked_period(
+        #[case] user_id: &str,
+        #[case] started_at: i64,
+        #[case] ended_at: i64,
+        #[case] expected_lock: Option<&str>,
+    ) {
+        let locks = fold(vec![
+            locked("lock-1", None, 100, 200),
+            locked("lock-2", Some("u-1"), 300, 400),
+        ]);
+        let entry = registered(user_id, started_at, ended_at);
+
+        let result = ensure_unlocked(&locks, &TimeEntryState::None, &entry);
+
+        assert_eq!(
+            result,
+            match expected_lock {
+                Some(lock_id) => Err(PeriodLockError::Locked {
+                    lock_id: lock_id.to_string(),
+                }),
+                None => Ok(()),
+            }
+        );
+    }
+
+    #[rstest]
+    fn it_should_refuse_moving_an_entry_out_of_a_locked_period() {
+        let locks = fold(vec![locked("lock-1", None, 100, 200)]);
+
+        let result = ensure_unlocked(
+            &locks,
+            &registered("u-1", 120, 150),
+            &registered("u-1", 500, 600),
+        );
+
+        assert!(matches!(result, Err(PeriodLockError::Locked { .. })));
+    }
+}
+
+#[cfg(test)]
+mod period_locks_event_schema_tests {
+    use super::*;
+    use crate::tests::golden::{assert_catalog, assert_event_goldens};
+    use rstest::rstest;
+
+    #[rstest]
+    fn every_version_should_match_its_golden() {
+        assert_event_goldens::<PeriodLocksEvent>("period_locks");
+    }
+
+    #[rstest]
+    fn the_catalog_should_list_every_version() {
+        assert_catalog::<PeriodLocksEvent>();
+    }
+}
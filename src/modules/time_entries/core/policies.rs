@@ -0,0 +1,268 @@
+// Domain policies a tenant configures for its time entries.
+//
+// Policies are limits a tenant chooses rather than invariants of the domain: how many tags an
+// entry may carry, the longest a finished entry may be, how far back entries stay open for
+// changes and how client-supplied times are rounded. Command handlers read them per tenant
+// from the policy store; tenants without their own use the deployment's defaults.
+
+use crate::modules::time_entries::core::state::TimeEntryState;
+use crate::modules::time_entries::core::tag::Tag;
+use crate::modules::time_entries::core::user_time_entries::claim_of;
+use thiserror::Error;
+
+const MINUTE_MS: i64 = 60 * 1000;
+const DAY_MS: i64 = 24 * 60 * MINUTE_MS;
+
+pub const DEFAULT_MAX_ENTRY_DURATION_MS: i64 = 24 * 60 * MINUTE_MS;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum RoundingMode {
+    Nearest,
+    Up,
+    Down,
+}
+
+/// The increments organizations bill in, and so the ones starts and ends can be rounded to.
+pub const ROUNDING_INCREMENTS: [u32; 3] = [5, 15, 30];
+
+/// Rounds instants to whole multiples of `increment_minutes` since the epoch, so to the
+/// quarter hour in UTC and in every time zone offset by whole quarters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct Rounding {
+    pub increment_minutes: u32,
+    pub mode: RoundingMode,
+}
+
+impl Rounding {
+    /// Halfway rounds up.
+    pub fn round(&self, at: i64) -> i64 {
+        let increment = i64::from(self.increment_minutes.max(1)) * MINUTE_MS;
+        let down = at.div_euclid(increment) * increment;
+        match self.mode {
+            RoundingMode::Down => down,
+            RoundingMode::Up if down == at => at,
+            RoundingMode::Up => down + increment,
+            RoundingMode::Nearest if at - down < increment - (at - down) => down,
+            RoundingMode::Nearest => down + increment,
+        }
+    }
+}
+
+/// `at` rounded per `rounding`, and `at` as supplied when a rounding applied.
+pub fn round_instant(rounding: Option<Rounding>, at: i64) -> (i64, Option<i64>) {
+    match rounding {
+        Some(rounding) => (rounding.round(at), Some(at)),
+        None => (at, None),
+    }
+}
+
+/// A command carrying client-supplied instants its decider rounds. Handlers hand it the
+/// tenant's rounding before deciding.
+pub trait RoundedCommand {
+    fn set_rounding(&mut self, _rounding: Rounding) {}
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct Policies {
+    /// The most tags an entry may carry; `None` for no limit.
+    pub max_tags: Option<usize>,
+    /// The longest a finished entry may be where the `max_entry_duration` rule is on.
+    pub max_entry_duration_ms: i64,
+    /// Entries that ended more than this many days ago can no longer be changed, and no
+    /// entries can be registered that far back; `None` keeps every entry open.
+    pub lock_after_days: Option<u32>,
+    /// How client-supplied starts and ends are rounded; `None` keeps them as given.
+    pub rounding: Option<Rounding>,
+}
+
+impl Default for Policies {
+    /// No tag limit, a 24 hour maximum duration, no lock and no rounding.
+    fn default() -> Self {
+        Self {
+            max_tags: None,
+            max_entry_duration_ms: DEFAULT_MAX_ENTRY_DURATION_MS,
+            lock_after_days: None,
+            rounding: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Error, PartialEq, Eq)]
+pub enum PolicyError {
+    #[error("time entry has {count} tags, more than the maximum of {max}")]
+    TooManyTags { count: usize, max: usize },
+
+    #[error("time entry is more than {lock_after_days} days old and can no longer be changed")]
+    Closed { lock_after_days: u32 },
+}
+
+fn tag_ids(state: &TimeEntryState) -> &[Tag] {
+    match state {
+        TimeEntryState::None | TimeEntryState::Deleted { .. } => &[],
+        TimeEntryState::Draft { tag_ids, .. }
+        | TimeEntryState::Registered { tag_ids, .. }
+        | TimeEntryState::Approved { tag_ids, .. } => tag_ids,
+    }
+}
+
+/// Refuses a change to a time entry that breaks `policies` at `now`. The tag limit applies
+/// only when the tags change, so entries tagged before it was lowered can still be moved;
+/// the lock applies to the entry's interval before and after the change.
+pub fn ensure_within_policies(
+    policies: &Policies,
+    before: &TimeEntryState,
+    after: &TimeEntryState,
+    now: i64,
+) -> Result<(), PolicyError> {
+    if let Some(max) = policies.max_tags {
+        let count = tag_ids(after).len();
+        if count > max && tag_ids(after) != tag_ids(before) {
+            return Err(PolicyError::TooManyTags { count, max });
+        }
+    }
+    if let Some(lock_after_days) = policies.lock_after_days {
+        let cutoff = now - i64::from(lock_after_days) * DAY_MS;
+        for (_, _, interval) in [claim_of(before), claim_of(after)].into_iter().flatten() {
+            if interval
+                .ended_at
+                .or(interval.started_at)
+                .is_some_and(|latest| latest < cutoff)
+            {
+                return Err(PolicyError::Closed { lock_after_days });
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod policies_tests {
+    use super::*;
+    use crate::modules::time_entries::core::time_interval::TimeInterval;
+    use rstest::rstest;
+
+    const NOW: i64 = 100 * DAY_MS;
+
+    fn registered(started_at: i64, ended_at: i64, tags: &[&str]) -> TimeEntryState {
+        TimeEntryState::Registered {
+            time_entry_id: "te-1".into(),
+            user_id: "u-1".into(),
+            interval: TimeInterval::recorded(started_at, ended_at),
+            tag_ids: tags.iter().map(|tag| Tag::parse(tag).unwrap()).collect(),
+            created_at: 0,
+            created_by: "u-1".into(),
+            hourly_rate: None,
+            breaks: vec![],
+        }
+    }
+
+    #[rstest]
+    #[case::nearest_down(RoundingMode::Nearest, 7 * MINUTE_MS, 0)]
+    #[case::nearest_halfway(RoundingMode::Nearest, 7 * MINUTE_MS + 30_000, 15 * MINUTE_MS)]
+    #[case::up(RoundingMode::Up, MINUTE_MS, 15 * MINUTE_MS)]
+    #[case::up_exact(RoundingMode::Up, 15 * MINUTE_MS, 15 * MINUTE_MS)]
+    #[case::down(RoundingMode::Down, 29 * MINUTE_MS, 15 * MINUTE_MS)]
+    #[case::down_before_epoch(RoundingMode::Down, -MINUTE_MS, -15 * MINUTE_MS)]
+    fn it_should_round_to_the_increment(
+        #[case] mode: RoundingMode,
+        #[case] at: i64,
+        #[case] expected: i64,
+    ) {
+        let rounding = Rounding {
+            increment_minutes: 15,
+            mode,
+        };
+        assert_eq!(rounding.round(at), expected);
+    }
+
+    #[rstest]
+    #[case::on_the_boundary(5, RoundingMode::Up, 10 * MINUTE_MS, 10 * MINUTE_MS)]
+    #[case::just_past_the_boundary(5, RoundingMode::Up, 10 * MINUTE_MS + 1, 15 * MINUTE_MS)]
+    #[case::just_before_the_boundary(5, RoundingMode::Down, 10 * MINUTE_MS - 1, 5 * MINUTE_MS)]
+    #[case::just_below_halfway(15, RoundingMode::Nearest, 7 * MINUTE_MS + 29_999, 0)]
+    #[case::halfway(30, RoundingMode::Nearest, 15 * MINUTE_MS, 30 * MINUTE_MS)]
+    #[case::up_to_midnight(30, RoundingMode::Up, DAY_MS - 1, DAY_MS)]
+    #[case::nearest_to_midnight(15, RoundingMode::Nearest, DAY_MS - 7 * MINUTE_MS, DAY_MS)]
+    fn it_should_round_on_the_boundaries(
+        #[case] increment_minutes: u32,
+        #[case] mode: RoundingMode,
+        #[case] at: i64,
+        #[case] expected: i64,
+    ) {
+        let rounding = Rounding {
+            increment_minutes,
+            mode,
+        };
+        assert_eq!(rounding.round(at), expected);
+    }
+
+    #[rstest]
+    fn it_should_keep_the_instant_as_given_only_when_rounding() {
+        let rounding = Rounding {
+            increment_minutes: 15,
+            mode: RoundingMode::Nearest,
+        };
+
+        assert_eq!(round_instant(None, MINUTE_MS), (MINUTE_MS, None));
+        assert_eq!(
+            round_instant(Some(rounding), 15 * MINUTE_MS),
+            (15 * MINUTE_MS, Some(15 * MINUTE_MS))
+        );
+    }
+
+    #[rstest]
+    #[case::within(&["a", "b"], Ok(()))]
+    #[case::over(&["a", "b", "c"], Err(PolicyError::TooManyTags { count: 3, max: 2 }))]
+    fn it_should_limit_the_tags_an_entry_gets(
+        #[case] tags: &[&str],
+        #[case] expected: Result<(), PolicyError>,
+    ) {
+        let policies = Policies {
+            max_tags: Some(2),
+            ..Policies::default()
+        };
+        let before = registered(NOW - 1_000, NOW, &[]);
+        let after = registered(NOW - 1_000, NOW, tags);
+
+        assert_eq!(
+            ensure_within_policies(&policies, &before, &after, NOW),
+            expected
+        );
+    }
+
+    #[rstest]
+    fn it_should_let_entries_over_the_tag_limit_move() {
+        let policies = Policies {
+            max_tags: Some(1),
+            ..Policies::default()
+        };
+        let before = registered(NOW - 2_000, NOW, &["a", "b"]);
+        let after = registered(NOW - 1_000, NOW, &["a", "b"]);
+
+        assert_eq!(
+            ensure_within_policies(&policies, &before, &after, NOW),
+            Ok(())
+        );
+    }
+
+    #[rstest]
+    #[case::recent(registered(NOW - 8 * DAY_MS, NOW - 6 * DAY_MS, &[]), registered(NOW - 8 * DAY_MS, NOW - 5 * DAY_MS, &[]), true)]
+    #[case::moved_back(registered(NOW - 2 * DAY_MS, NOW - DAY_MS, &[]), registered(NOW - 9 * DAY_MS, NOW - 8 * DAY_MS, &[]), false)]
+    #[case::closed(registered(NOW - 9 * DAY_MS, NOW - 8 * DAY_MS, &[]), registered(NOW - 2 * DAY_MS, NOW - DAY_MS, &[]), false)]
+    #[case::new(TimeEntryState::None, registered(NOW - 9 * DAY_MS, NOW - 8 * DAY_MS, &[]), false)]
+    fn it_should_close_entries_after_the_lock_period(
+        #[case] before: TimeEntryState,
+        #[case] after: TimeEntryState,
+        #[case] allowed: bool,
+    ) {
+        let policies = Policies {
+            lock_after_days: Some(7),
+            ..Policies::default()
+        };
+
+        assert_eq!(
+            ensure_within_policies(&policies, &before, &after, NOW).is_ok(),
+            allowed
+        );
+    }
+}
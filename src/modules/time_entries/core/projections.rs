@@ -1,4 +1,8 @@
+use crate::modules::time_entries::core::breaks::Break;
 use crate::modules::time_entries::core::events::TimeEntryEvent;
+use crate::modules::time_entries::core::events::v1::timer_auto_stopped::AUTO_STOP_ACTOR;
+use crate::modules::time_entries::core::hourly_rate::HourlyRate;
+use crate::modules::time_entries::core::tag::Tag;
 use crate::modules::time_entries::use_cases::list_time_entries::projection::{
     TimeEntryRow, TimeEntryStatus,
 };
@@ -30,72 +34,164 @@ pub enum Mutation {
     },
     SetTags {
         time_entry_id: String,
-        tag_ids: Vec<String>,
+        tag_ids: Vec<Tag>,
+        updated_at: i64,
+        updated_by: String,
+        last_event_id: String,
+    },
+    SetApproved {
+        time_entry_id: String,
+        approved_at: i64,
+        approved_by: String,
+        last_event_id: String,
+    },
+    SetRejected {
+        time_entry_id: String,
+        rejected_at: i64,
+        rejected_by: String,
+        last_event_id: String,
+    },
+    SetHourlyRate {
+        time_entry_id: String,
+        hourly_rate: HourlyRate,
+        updated_at: i64,
+        updated_by: String,
+        last_event_id: String,
+    },
+    SetBreaks {
+        time_entry_id: String,
+        breaks: Vec<Break>,
         updated_at: i64,
         updated_by: String,
         last_event_id: String,
     },
 }
 
+impl Mutation {
+    /// The entry whose row the mutation writes.
+    pub fn time_entry_id(&self) -> &str {
+        match self {
+            Mutation::Upsert(row) => &row.time_entry_id,
+            Mutation::SetStartedAt { time_entry_id, .. }
+            | Mutation::SetEndedAt { time_entry_id, .. }
+            | Mutation::SetRegistered { time_entry_id, .. }
+            | Mutation::SetDeleted { time_entry_id, .. }
+            | Mutation::SetTags { time_entry_id, .. }
+            | Mutation::SetApproved { time_entry_id, .. }
+            | Mutation::SetRejected { time_entry_id, .. }
+            | Mutation::SetHourlyRate { time_entry_id, .. }
+            | Mutation::SetBreaks { time_entry_id, .. } => time_entry_id,
+        }
+    }
+}
+
 pub fn apply(stream_id: &str, version: i64, event: &TimeEntryEvent) -> Vec<Mutation> {
     let last_event_id = format!("{stream_id}:{version}");
     match event {
         TimeEntryEvent::TimeEntryInitiatedV1(e) => vec![Mutation::Upsert(TimeEntryRow {
-            time_entry_id: e.time_entry_id.clone(),
-            user_id: e.user_id.clone(),
+            time_entry_id: e.time_entry_id.to_string(),
+            user_id: e.user_id.to_string(),
             started_at: None,
             ended_at: None,
             tag_ids: vec![],
             status: TimeEntryStatus::Draft,
             created_at: e.created_at,
-            created_by: e.created_by.clone(),
+            created_by: e.created_by.to_string(),
             updated_at: e.created_at,
-            updated_by: e.created_by.clone(),
+            updated_by: e.created_by.to_string(),
             deleted_at: None,
+            hourly_rate: None,
+            breaks: vec![],
             last_event_id: Some(last_event_id),
         })],
         TimeEntryEvent::TimeEntryStartSetV1(e) => vec![Mutation::SetStartedAt {
-            time_entry_id: e.time_entry_id.clone(),
+            time_entry_id: e.time_entry_id.to_string(),
             started_at: e.started_at,
             updated_at: e.updated_at,
-            updated_by: e.updated_by.clone(),
+            updated_by: e.updated_by.to_string(),
             last_event_id,
         }],
         TimeEntryEvent::TimeEntryEndSetV1(e) => vec![Mutation::SetEndedAt {
-            time_entry_id: e.time_entry_id.clone(),
+            time_entry_id: e.time_entry_id.to_string(),
             ended_at: e.ended_at,
             updated_at: e.updated_at,
-            updated_by: e.updated_by.clone(),
+            updated_by: e.updated_by.to_string(),
             last_event_id,
         }],
         TimeEntryEvent::TimeEntryRegisteredV1(e) => vec![Mutation::SetRegistered {
-            time_entry_id: e.time_entry_id.clone(),
+            time_entry_id: e.time_entry_id.to_string(),
             last_event_id,
         }],
         TimeEntryEvent::TimeEntryDeletedV1(e) => vec![Mutation::SetDeleted {
-            time_entry_id: e.time_entry_id.clone(),
+            time_entry_id: e.time_entry_id.to_string(),
             deleted_at: e.deleted_at,
             last_event_id,
         }],
         TimeEntryEvent::TimeEntryTagsSetV1(e) => vec![Mutation::SetTags {
-            time_entry_id: e.time_entry_id.clone(),
+            time_entry_id: e.time_entry_id.to_string(),
             tag_ids: e.tag_ids.clone(),
             updated_at: e.updated_at,
-            updated_by: e.updated_by.clone(),
+            updated_by: e.updated_by.to_string(),
+            last_event_id,
+        }],
+        TimeEntryEvent::TimeEntryApprovedV1(e) => vec![Mutation::SetApproved {
+            time_entry_id: e.time_entry_id.to_string(),
+            approved_at: e.approved_at,
+            approved_by: e.approved_by.to_string(),
+            last_event_id,
+        }],
+        TimeEntryEvent::TimeEntryRejectedV1(e) => vec![Mutation::SetRejected {
+            time_entry_id: e.time_entry_id.to_string(),
+            rejected_at: e.rejected_at,
+            rejected_by: e.rejected_by.to_string(),
+            last_event_id,
+        }],
+        TimeEntryEvent::TimerAutoStoppedV1(e) => vec![Mutation::SetEndedAt {
+            time_entry_id: e.time_entry_id.to_string(),
+            ended_at: e.ended_at,
+            updated_at: e.stopped_at,
+            updated_by: AUTO_STOP_ACTOR.to_string(),
             last_event_id,
         }],
+        TimeEntryEvent::TimeEntryHourlyRateSetV1(e) => vec![Mutation::SetHourlyRate {
+            time_entry_id: e.time_entry_id.to_string(),
+            hourly_rate: HourlyRate {
+                cents: e.hourly_rate_cents,
+                currency: e.currency.clone(),
+            },
+            updated_at: e.updated_at,
+            updated_by: e.updated_by.to_string(),
+            last_event_id,
+        }],
+        TimeEntryEvent::TimeEntryBreaksSetV1(e) => vec![Mutation::SetBreaks {
+            time_entry_id: e.time_entry_id.to_string(),
+            breaks: e.breaks.clone(),
+            updated_at: e.updated_at,
+            updated_by: e.updated_by.to_string(),
+            last_event_id,
+        }],
+        // Comments and attachments have their own projections and leave the entry row as
+        // it was.
+        TimeEntryEvent::TimeEntryCommentAddedV1(_)
+        | TimeEntryEvent::TimeEntryAttachmentAddedV1(_) => vec![],
+        // Compaction rewrites a deleted entry's stream in place; the row is already deleted.
+        TimeEntryEvent::TimeEntryCompactedV1(_) => vec![],
     }
 }
 
 #[cfg(test)]
 mod time_entry_projector_apply_tests {
     use super::*;
+    use crate::modules::time_entries::core::events::v1::time_entry_approved::TimeEntryApprovedV1;
     use crate::modules::time_entries::core::events::v1::time_entry_deleted::TimeEntryDeletedV1;
     use crate::modules::time_entries::core::events::v1::time_entry_end_set::TimeEntryEndSetV1;
+    use crate::modules::time_entries::core::events::v1::time_entry_hourly_rate_set::TimeEntryHourlyRateSetV1;
     use crate::modules::time_entries::core::events::v1::time_entry_initiated::TimeEntryInitiatedV1;
     use crate::modules::time_entries::core::events::v1::time_entry_registered::TimeEntryRegisteredV1;
+    use crate::modules::time_entries::core::events::v1::time_entry_rejected::TimeEntryRejectedV1;
     use crate::modules::time_entries::core::events::v1::time_entry_start_set::TimeEntryStartSetV1;
     use crate::modules::time_entries::core::events::v1::time_entry_tags_set::TimeEntryTagsSetV1;
+    use crate::modules::time_entries::core::events::v1::timer_auto_stopped::TimerAutoStoppedV1;
     use rstest::rstest;
 
     const STREAM_ID: &str = "TimeEntry-te-0001";
@@ -103,10 +199,10 @@ mod time_entry_projector_apply_tests {
     #[rstest]
     fn it_should_apply_initiated_event() {
         let event = TimeEntryEvent::TimeEntryInitiatedV1(TimeEntryInitiatedV1 {
-            time_entry_id: "te-0001".to_string(),
-            user_id: "user-0001".to_string(),
+            time_entry_id: "te-0001".into(),
+            user_id: "user-0001".into(),
             created_at: 1_000,
-            created_by: "user-0001".to_string(),
+            created_by: "user-0001".into(),
         });
         let mutations = apply(STREAM_ID, 1, &event);
         assert_eq!(mutations.len(), 1);
@@ -116,10 +212,11 @@ mod time_entry_projector_apply_tests {
     #[rstest]
     fn it_should_apply_start_set_event() {
         let event = TimeEntryEvent::TimeEntryStartSetV1(TimeEntryStartSetV1 {
-            time_entry_id: "te-0001".to_string(),
+            time_entry_id: "te-0001".into(),
             started_at: 500,
             updated_at: 1_000,
-            updated_by: "user-0001".to_string(),
+            updated_by: "user-0001".into(),
+            raw_started_at: None,
         });
         let mutations = apply(STREAM_ID, 2, &event);
         assert_eq!(mutations.len(), 1);
@@ -129,10 +226,11 @@ mod time_entry_projector_apply_tests {
     #[rstest]
     fn it_should_apply_end_set_event() {
         let event = TimeEntryEvent::TimeEntryEndSetV1(TimeEntryEndSetV1 {
-            time_entry_id: "te-0001".to_string(),
+            time_entry_id: "te-0001".into(),
             ended_at: 800,
             updated_at: 1_000,
-            updated_by: "user-0001".to_string(),
+            updated_by: "user-0001".into(),
+            raw_ended_at: None,
         });
         let mutations = apply(STREAM_ID, 3, &event);
         assert_eq!(mutations.len(), 1);
@@ -142,7 +240,7 @@ mod time_entry_projector_apply_tests {
     #[rstest]
     fn it_should_apply_registered_event() {
         let event = TimeEntryEvent::TimeEntryRegisteredV1(TimeEntryRegisteredV1 {
-            time_entry_id: "te-0001".to_string(),
+            time_entry_id: "te-0001".into(),
             occurred_at: 1_000,
         });
         let mutations = apply(STREAM_ID, 4, &event);
@@ -153,9 +251,9 @@ mod time_entry_projector_apply_tests {
     #[rstest]
     fn it_should_apply_deleted_event() {
         let event = TimeEntryEvent::TimeEntryDeletedV1(TimeEntryDeletedV1 {
-            time_entry_id: "te-0001".to_string(),
+            time_entry_id: "te-0001".into(),
             deleted_at: 2_000,
-            deleted_by: "user-0001".to_string(),
+            deleted_by: "user-0001".into(),
         });
         let mutations = apply(STREAM_ID, 5, &event);
         assert_eq!(mutations.len(), 1);
@@ -165,13 +263,71 @@ mod time_entry_projector_apply_tests {
     #[rstest]
     fn it_should_apply_tags_set_event() {
         let event = TimeEntryEvent::TimeEntryTagsSetV1(TimeEntryTagsSetV1 {
-            time_entry_id: "te-0001".to_string(),
-            tag_ids: vec!["tag-1".to_string(), "tag-2".to_string()],
+            time_entry_id: "te-0001".into(),
+            tag_ids: vec![Tag::parse("tag-1").unwrap(), Tag::parse("tag-2").unwrap()],
             updated_at: 1_000,
-            updated_by: "user-0001".to_string(),
+            updated_by: "user-0001".into(),
         });
         let mutations = apply(STREAM_ID, 6, &event);
         assert_eq!(mutations.len(), 1);
         assert!(matches!(&mutations[0], Mutation::SetTags { .. }));
     }
+
+    #[rstest]
+    fn it_should_apply_approved_event() {
+        let event = TimeEntryEvent::TimeEntryApprovedV1(TimeEntryApprovedV1 {
+            time_entry_id: "te-0001".into(),
+            approved_at: 2_000,
+            approved_by: "manager-0001".into(),
+        });
+        let mutations = apply(STREAM_ID, 7, &event);
+        assert_eq!(mutations.len(), 1);
+        assert!(matches!(&mutations[0], Mutation::SetApproved { .. }));
+    }
+
+    #[rstest]
+    fn it_should_apply_rejected_event() {
+        let event = TimeEntryEvent::TimeEntryRejectedV1(TimeEntryRejectedV1 {
+            time_entry_id: "te-0001".into(),
+            rejected_at: 2_000,
+            rejected_by: "manager-0001".into(),
+            reason: None,
+        });
+        let mutations = apply(STREAM_ID, 7, &event);
+        assert_eq!(mutations.len(), 1);
+        assert!(matches!(&mutations[0], Mutation::SetRejected { .. }));
+    }
+
+    #[rstest]
+    fn it_should_apply_timer_auto_stopped_as_an_end_set_by_the_system() {
+        let event = TimeEntryEvent::TimerAutoStoppedV1(TimerAutoStoppedV1 {
+            time_entry_id: "te-0001".into(),
+            ended_at: 43_200_000,
+            reason: "exceeded 12h".to_string(),
+            stopped_at: 50_000_000,
+        });
+        let mutations = apply(STREAM_ID, 9, &event);
+        assert!(matches!(
+            &mutations[..],
+            [Mutation::SetEndedAt { ended_at: 43_200_000, updated_at: 50_000_000, updated_by, .. }]
+                if updated_by == AUTO_STOP_ACTOR
+        ));
+    }
+
+    #[rstest]
+    fn it_should_apply_hourly_rate_set_event() {
+        let event = TimeEntryEvent::TimeEntryHourlyRateSetV1(TimeEntryHourlyRateSetV1 {
+            time_entry_id: "te-0001".into(),
+            hourly_rate_cents: 9_500,
+            currency: "EUR".to_string(),
+            updated_at: 2_000,
+            updated_by: "user-0001".into(),
+        });
+        let mutations = apply(STREAM_ID, 8, &event);
+        assert_eq!(mutations.len(), 1);
+        assert!(matches!(
+            &mutations[0],
+            Mutation::SetHourlyRate { hourly_rate, .. } if hourly_rate.cents == 9_500
+        ));
+    }
 }
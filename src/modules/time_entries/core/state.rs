@@ -1,23 +1,51 @@
+use crate::modules::time_entries::core::breaks::Break;
+use crate::modules::time_entries::core::hourly_rate::HourlyRate;
+use crate::modules::time_entries::core::tag::Tag;
+use crate::modules::time_entries::core::time_interval::TimeInterval;
+use crate::shared::core::primitives::{TimeEntryId, UserId};
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum TimeEntryState {
     None,
     Draft {
-        time_entry_id: String,
-        user_id: String,
+        time_entry_id: TimeEntryId,
+        user_id: UserId,
         started_at: Option<i64>,
         ended_at: Option<i64>,
-        tag_ids: Vec<String>,
+        tag_ids: Vec<Tag>,
         created_at: i64,
-        created_by: String,
+        created_by: UserId,
+        hourly_rate: Option<HourlyRate>,
     },
     Registered {
-        time_entry_id: String,
-        user_id: String,
-        started_at: i64,
-        ended_at: i64,
-        tag_ids: Vec<String>,
+        time_entry_id: TimeEntryId,
+        user_id: UserId,
+        interval: TimeInterval,
+        breaks: Vec<Break>,
+        tag_ids: Vec<Tag>,
+        created_at: i64,
+        created_by: UserId,
+        hourly_rate: Option<HourlyRate>,
+    },
+    /// Signed off by a manager; the entry can no longer be changed.
+    Approved {
+        time_entry_id: TimeEntryId,
+        user_id: UserId,
+        interval: TimeInterval,
+        breaks: Vec<Break>,
+        tag_ids: Vec<Tag>,
         created_at: i64,
-        created_by: String,
+        created_by: UserId,
+        hourly_rate: Option<HourlyRate>,
+        approved_at: i64,
+        approved_by: UserId,
+    },
+    /// Soft-deleted; kept in the log, but no longer changed.
+    Deleted {
+        time_entry_id: TimeEntryId,
+        user_id: UserId,
+        deleted_at: i64,
+        deleted_by: UserId,
     },
 }
 
@@ -29,13 +57,14 @@ mod time_entry_state_tests {
     #[rstest]
     fn it_should_create_the_draft_state() {
         let state = TimeEntryState::Draft {
-            time_entry_id: "te-fixed-0001".to_string(),
-            user_id: "user-fixed-0001".to_string(),
+            time_entry_id: "te-fixed-0001".into(),
+            user_id: "user-fixed-0001".into(),
             started_at: None,
             ended_at: None,
             tag_ids: vec![],
             created_at: 1_700_000_000_000i64,
-            created_by: "user-fixed-0001".to_string(),
+            created_by: "user-fixed-0001".into(),
+            hourly_rate: None,
         };
         match state {
             TimeEntryState::Draft {
@@ -57,26 +86,28 @@ mod time_entry_state_tests {
     #[rstest]
     fn it_should_create_the_registered_state() {
         let state = TimeEntryState::Registered {
-            time_entry_id: "te-fixed-0001".to_string(),
-            user_id: "user-fixed-0001".to_string(),
-            started_at: 1_700_000_000_000i64,
-            ended_at: 1_700_000_360_000i64,
+            time_entry_id: "te-fixed-0001".into(),
+            user_id: "user-fixed-0001".into(),
+            interval: TimeInterval::new(1_700_000_000_000i64, 1_700_000_360_000i64).unwrap(),
             tag_ids: vec![],
             created_at: 1_700_000_000_000i64,
-            created_by: "user-fixed-0001".to_string(),
+            created_by: "user-fixed-0001".into(),
+            hourly_rate: None,
+            breaks: vec![],
         };
         match state {
             TimeEntryState::Registered {
                 time_entry_id,
                 user_id,
-                started_at,
-                ended_at,
+                interval,
                 ..
             } => {
                 assert_eq!(time_entry_id, "te-fixed-0001");
                 assert_eq!(user_id, "user-fixed-0001");
-                assert_eq!(started_at, 1_700_000_000_000i64);
-                assert_eq!(ended_at, 1_700_000_360_000i64);
+                assert_eq!(
+                    interval,
+                    TimeInterval::new(1_700_000_000_000i64, 1_700_000_360_000i64).unwrap()
+                );
             }
             _ => panic!("expected Registered state"),
         }
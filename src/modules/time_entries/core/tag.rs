@@ -0,0 +1,176 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+use thiserror::Error;
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum TagError {
+    #[error("tag must not be blank")]
+    Blank,
+
+    #[error("tag must be at most {max} characters")]
+    TooLong { max: usize },
+
+    #[error("tag must not contain control characters")]
+    ControlCharacter,
+}
+
+/// A tag in canonical form: trimmed, inner whitespace collapsed to one space, full-width
+/// forms folded to ASCII and lowercased, so `" Dev "`, `"DEV"` and `"ｄｅｖ"` are one tag.
+/// Canonical composition (NFC) is not applied; the crate carries no normalization tables.
+///
+/// Deserializing trusts the value: events only ever record parsed tags.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Tag(String);
+
+impl Tag {
+    pub const MAX_CHARS: usize = 64;
+
+    pub fn parse(value: &str) -> Result<Self, TagError> {
+        let mut canonical = String::with_capacity(value.len());
+        for word in value.split_whitespace() {
+            if !canonical.is_empty() {
+                canonical.push(' ');
+            }
+            for c in word.chars() {
+                if c.is_control() {
+                    return Err(TagError::ControlCharacter);
+                }
+                canonical.extend(fold_width(c).to_lowercase());
+            }
+        }
+        if canonical.is_empty() {
+            return Err(TagError::Blank);
+        }
+        if canonical.chars().count() > Self::MAX_CHARS {
+            return Err(TagError::TooLong {
+                max: Self::MAX_CHARS,
+            });
+        }
+        Ok(Self(canonical))
+    }
+
+    /// Parses every value, dropping tags that become duplicates once canonical.
+    pub fn parse_all<S: AsRef<str>>(values: &[S]) -> Result<Vec<Self>, TagError> {
+        let mut tags: Vec<Self> = Vec::with_capacity(values.len());
+        for value in values {
+            let tag = Self::parse(value.as_ref())?;
+            if !tags.contains(&tag) {
+                tags.push(tag);
+            }
+        }
+        Ok(tags)
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+/// Maps the full-width ASCII block (U+FF01..U+FF5E) onto ASCII.
+fn fold_width(c: char) -> char {
+    match c {
+        '\u{FF01}'..='\u{FF5E}' => char::from_u32(c as u32 - 0xFEE0).unwrap_or(c),
+        _ => c,
+    }
+}
+
+impl FromStr for Tag {
+    type Err = TagError;
+
+    fn from_str(value: &str) -> Result<Self, TagError> {
+        Self::parse(value)
+    }
+}
+
+impl fmt::Display for Tag {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl AsRef<str> for Tag {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl From<Tag> for String {
+    fn from(tag: Tag) -> Self {
+        tag.0
+    }
+}
+
+impl PartialEq<str> for Tag {
+    fn eq(&self, other: &str) -> bool {
+        self.0 == other
+    }
+}
+
+impl PartialEq<&str> for Tag {
+    fn eq(&self, other: &&str) -> bool {
+        self.0 == *other
+    }
+}
+
+impl PartialEq<String> for Tag {
+    fn eq(&self, other: &String) -> bool {
+        &self.0 == other
+    }
+}
+
+#[cfg(test)]
+mod tag_tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case::already_canonical("dev", "dev")]
+    #[case::trimmed("  dev\t", "dev")]
+    #[case::case_folded("Client-X", "client-x")]
+    #[case::collapsed_whitespace("deep \u{00A0}  work", "deep work")]
+    #[case::full_width("ＤＥＶ", "dev")]
+    #[case::non_ascii("ÉTÉ", "été")]
+    fn it_should_parse_to_the_canonical_form(#[case] raw: &str, #[case] canonical: &str) {
+        assert_eq!(Tag::parse(raw).unwrap(), canonical);
+    }
+
+    #[rstest]
+    #[case::empty("", TagError::Blank)]
+    #[case::whitespace(" \n ", TagError::Blank)]
+    #[case::control("dev\u{7}", TagError::ControlCharacter)]
+    #[case::too_long(&"x".repeat(Tag::MAX_CHARS + 1), TagError::TooLong { max: Tag::MAX_CHARS })]
+    fn it_should_reject_invalid_tags(#[case] raw: &str, #[case] error: TagError) {
+        assert_eq!(Tag::parse(raw), Err(error));
+    }
+
+    #[rstest]
+    fn it_should_count_characters_not_bytes() {
+        assert!(Tag::parse(&"é".repeat(Tag::MAX_CHARS)).is_ok());
+    }
+
+    #[rstest]
+    fn it_should_drop_duplicates_when_parsing_a_list() {
+        let tags = Tag::parse_all(&["Dev", "ops", " dev "]).unwrap();
+
+        assert_eq!(
+            tags,
+            vec![Tag::parse("dev").unwrap(), Tag::parse("ops").unwrap()]
+        );
+        assert_eq!(Tag::parse_all(&["dev", ""]), Err(TagError::Blank));
+    }
+
+    #[rstest]
+    fn it_should_round_trip_as_a_plain_string() {
+        let tag: Tag = "Dev".parse().unwrap();
+
+        assert_eq!(serde_json::to_string(&tag).unwrap(), r#""dev""#);
+        assert_eq!(serde_json::from_str::<Tag>(r#""dev""#).unwrap(), tag);
+        assert_eq!(tag.to_string(), "dev");
+        assert_eq!(tag.as_ref(), tag.as_str());
+        assert!(tag == *"dev");
+        assert_eq!(tag, "dev".to_string());
+        assert_eq!(String::from(tag), "dev");
+    }
+}
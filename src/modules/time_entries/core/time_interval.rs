@@ -0,0 +1,140 @@
+use chrono::TimeDelta;
+use thiserror::Error;
+
+use crate::shared::core::primitives::Timestamp;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Error)]
+#[error("interval must end after it starts")]
+pub struct InvalidInterval;
+
+/// A closed stretch of time, half-open `[start, end)` with `end > start`, so an entry ending
+/// when the next one starts does not overlap it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TimeInterval {
+    start: Timestamp,
+    end: Timestamp,
+}
+
+impl TimeInterval {
+    pub fn new(
+        start: impl Into<Timestamp>,
+        end: impl Into<Timestamp>,
+    ) -> Result<Self, InvalidInterval> {
+        let (start, end) = (start.into(), end.into());
+        if end <= start {
+            return Err(InvalidInterval);
+        }
+        Ok(Self { start, end })
+    }
+
+    /// For folding recorded events: the decider validated the interval before it was
+    /// appended, and replay must not fail on history.
+    pub fn recorded(start: i64, end: i64) -> Self {
+        Self {
+            start: start.into(),
+            end: end.into(),
+        }
+    }
+
+    pub fn start(&self) -> Timestamp {
+        self.start
+    }
+
+    pub fn end(&self) -> Timestamp {
+        self.end
+    }
+
+    pub fn with_start(self, start: impl Into<Timestamp>) -> Result<Self, InvalidInterval> {
+        Self::new(start, self.end)
+    }
+
+    pub fn with_end(self, end: impl Into<Timestamp>) -> Result<Self, InvalidInterval> {
+        Self::new(self.start, end)
+    }
+
+    pub fn duration(&self) -> TimeDelta {
+        TimeDelta::milliseconds(self.end.as_millis() - self.start.as_millis())
+    }
+
+    pub fn overlaps(&self, other: &TimeInterval) -> bool {
+        self.start < other.end && other.start < self.end
+    }
+
+    pub fn contains(&self, at: impl Into<Timestamp>) -> bool {
+        let at = at.into();
+        self.start <= at && at < self.end
+    }
+}
+
+#[cfg(test)]
+mod time_interval_tests {
+    use super::*;
+    use rstest::rstest;
+
+    fn interval(start: i64, end: i64) -> TimeInterval {
+        TimeInterval::new(start, end).unwrap()
+    }
+
+    #[rstest]
+    #[case::empty(100, 100)]
+    #[case::reversed(200, 100)]
+    fn it_should_refuse_intervals_that_do_not_end_after_they_start(
+        #[case] start: i64,
+        #[case] end: i64,
+    ) {
+        assert_eq!(TimeInterval::new(start, end), Err(InvalidInterval));
+    }
+
+    #[rstest]
+    fn it_should_expose_its_bounds_and_duration() {
+        let interval = interval(1_000, 61_000);
+
+        assert_eq!(interval.start(), Timestamp::from_millis(1_000));
+        assert_eq!(interval.end(), Timestamp::from_millis(61_000));
+        assert_eq!(interval.duration(), TimeDelta::minutes(1));
+    }
+
+    #[rstest]
+    #[case::moved_start(interval(100, 200).with_start(150), Ok(interval(150, 200)))]
+    #[case::start_past_end(interval(100, 200).with_start(200), Err(InvalidInterval))]
+    #[case::moved_end(interval(100, 200).with_end(300), Ok(interval(100, 300)))]
+    #[case::end_before_start(interval(100, 200).with_end(50), Err(InvalidInterval))]
+    fn it_should_revalidate_when_a_bound_moves(
+        #[case] moved: Result<TimeInterval, InvalidInterval>,
+        #[case] expected: Result<TimeInterval, InvalidInterval>,
+    ) {
+        assert_eq!(moved, expected);
+    }
+
+    #[rstest]
+    #[case::inside(interval(120, 180), true)]
+    #[case::around(interval(50, 250), true)]
+    #[case::straddles_start(interval(50, 150), true)]
+    #[case::ends_at_start(interval(50, 100), false)]
+    #[case::starts_at_end(interval(200, 250), false)]
+    fn it_should_overlap_half_open(#[case] other: TimeInterval, #[case] expected: bool) {
+        let interval = interval(100, 200);
+
+        assert_eq!(interval.overlaps(&other), expected);
+        assert_eq!(other.overlaps(&interval), expected);
+    }
+
+    #[rstest]
+    #[case::start(100, true)]
+    #[case::inside(150, true)]
+    #[case::end(200, false)]
+    #[case::before(99, false)]
+    fn it_should_contain_its_start_but_not_its_end(#[case] at: i64, #[case] expected: bool) {
+        assert_eq!(interval(100, 200).contains(at), expected);
+    }
+
+    #[rstest]
+    fn it_should_trust_recorded_bounds() {
+        let recorded = TimeInterval::recorded(200, 100);
+
+        assert_eq!(
+            (recorded.start().as_millis(), recorded.end().as_millis()),
+            (200, 100)
+        );
+    }
+}
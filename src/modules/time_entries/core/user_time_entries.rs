@@ -0,0 +1,526 @@
+// Per-user aggregate kept alongside the per-entry `TimeEntry-{id}` streams.
+//
+// A single time entry stream cannot see its siblings, so invariants that span a user's
+// entries live here: entries may not overlap, and at most one timer (an entry with a start
+// but no end) may run at a time. The stream only records the interval each entry claims;
+// the entry streams remain the source of truth for everything else.
+//
+// Rules rolled out per tenant arrive as `ClaimRules` on the claim: overlap rejection can be
+// switched off, and a maximum duration for finished entries switched on.
+
+use crate::modules::time_entries::core::state::TimeEntryState;
+use crate::modules::time_entries::core::time_interval::TimeInterval;
+use crate::shared::core::decider::{Decider, Decision};
+use crate::shared::core::domain_event::DomainEvent;
+use std::collections::BTreeMap;
+use std::convert::Infallible;
+use thiserror::Error;
+
+pub const USER_STREAM_PREFIX: &str = "UserTimeEntries-";
+
+pub fn user_stream_id(user_id: &str) -> String {
+    format!("{USER_STREAM_PREFIX}{user_id}")
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct Interval {
+    pub started_at: Option<i64>,
+    pub ended_at: Option<i64>,
+}
+
+impl Interval {
+    fn is_running(&self) -> bool {
+        self.started_at.is_some() && self.ended_at.is_none()
+    }
+
+    /// Half-open `[start, end)`; a running timer extends indefinitely. Entries without a
+    /// start claim no time yet.
+    pub fn overlaps(&self, other: &Interval) -> bool {
+        match (self.claimed(), other.claimed()) {
+            (Some(claimed), Some(other)) => claimed.overlaps(&other),
+            _ => false,
+        }
+    }
+
+    fn claimed(&self) -> Option<TimeInterval> {
+        self.started_at
+            .map(|start| TimeInterval::recorded(start, self.ended_at.unwrap_or(i64::MAX)))
+    }
+}
+
+impl From<TimeInterval> for Interval {
+    fn from(interval: TimeInterval) -> Self {
+        Self {
+            started_at: Some(interval.start().as_millis()),
+            ended_at: Some(interval.end().as_millis()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct IntervalClaimedV1 {
+    pub time_entry_id: String,
+    pub interval: Interval,
+    pub occurred_at: i64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct IntervalReleasedV1 {
+    pub time_entry_id: String,
+    pub occurred_at: i64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize, DomainEvent)]
+#[serde(tag = "type")]
+pub enum UserTimeEntriesEvent {
+    IntervalClaimedV1(IntervalClaimedV1),
+    IntervalReleasedV1(IntervalReleasedV1),
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct UserTimeEntriesState {
+    pub intervals: BTreeMap<String, Interval>,
+}
+
+pub fn evolve_user_time_entries(
+    mut state: UserTimeEntriesState,
+    event: UserTimeEntriesEvent,
+) -> UserTimeEntriesState {
+    match event {
+        UserTimeEntriesEvent::IntervalClaimedV1(e) => {
+            state.intervals.insert(e.time_entry_id, e.interval);
+        }
+        UserTimeEntriesEvent::IntervalReleasedV1(e) => {
+            state.intervals.remove(&e.time_entry_id);
+        }
+    }
+    state
+}
+
+#[derive(Debug, Clone, Error, PartialEq, Eq)]
+pub enum UserTimeEntriesError {
+    #[error("interval overlaps time entry {time_entry_id}")]
+    Overlaps { time_entry_id: String },
+
+    #[error("a timer is already running for time entry {time_entry_id}")]
+    TimerAlreadyRunning { time_entry_id: String },
+
+    #[error("interval is longer than the maximum of {max_duration_ms} ms")]
+    TooLong { max_duration_ms: i64 },
+}
+
+/// The rules a claim is held to beyond "one running timer", which always applies.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ClaimRules {
+    pub reject_overlaps: bool,
+    /// The longest a finished entry may be; running timers are left to the auto-stop.
+    pub max_duration_ms: Option<i64>,
+}
+
+impl Default for ClaimRules {
+    fn default() -> Self {
+        Self {
+            reject_overlaps: true,
+            max_duration_ms: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClaimInterval {
+    pub time_entry_id: String,
+    pub interval: Interval,
+    pub occurred_at: i64,
+    pub rules: ClaimRules,
+}
+
+pub type UserTimeEntriesDecision = Decision<UserTimeEntriesEvent, Infallible, UserTimeEntriesError>;
+
+/// Decides whether the entry may claim the interval under the command's rules. Re-claiming
+/// the interval already on record is accepted without events, so retries after a partial
+/// failure are harmless, and so are entries recorded before a rule was switched on.
+pub fn decide_claim(
+    state: &UserTimeEntriesState,
+    command: ClaimInterval,
+) -> UserTimeEntriesDecision {
+    if state.intervals.get(&command.time_entry_id) == Some(&command.interval) {
+        return Decision::Accepted {
+            events: vec![],
+            intents: vec![],
+        };
+    }
+    if let (Some(max_duration_ms), Some(started_at), Some(ended_at)) = (
+        command.rules.max_duration_ms,
+        command.interval.started_at,
+        command.interval.ended_at,
+    ) && ended_at - started_at > max_duration_ms
+    {
+        return Decision::Rejected {
+            reason: UserTimeEntriesError::TooLong { max_duration_ms },
+        };
+    }
+    for (other_id, other) in state
+        .intervals
+        .iter()
+        .filter(|(id, _)| **id != command.time_entry_id)
+    {
+        if command.interval.is_running() && other.is_running() {
+            return Decision::Rejected {
+                reason: UserTimeEntriesError::TimerAlreadyRunning {
+                    time_entry_id: other_id.clone(),
+                },
+            };
+        }
+        if command.rules.reject_overlaps && command.interval.overlaps(other) {
+            return Decision::Rejected {
+                reason: UserTimeEntriesError::Overlaps {
+                    time_entry_id: other_id.clone(),
+                },
+            };
+        }
+    }
+    Decision::Accepted {
+        events: vec![UserTimeEntriesEvent::IntervalClaimedV1(IntervalClaimedV1 {
+            time_entry_id: command.time_entry_id,
+            interval: command.interval,
+            occurred_at: command.occurred_at,
+        })],
+        intents: vec![],
+    }
+}
+
+pub struct ClaimIntervalDecider;
+
+impl Decider for ClaimIntervalDecider {
+    type State = UserTimeEntriesState;
+    type Command = ClaimInterval;
+    type Event = UserTimeEntriesEvent;
+    type Intent = Infallible;
+    type Error = UserTimeEntriesError;
+
+    fn initial_state() -> UserTimeEntriesState {
+        UserTimeEntriesState::default()
+    }
+
+    fn evolve(state: UserTimeEntriesState, event: UserTimeEntriesEvent) -> UserTimeEntriesState {
+        evolve_user_time_entries(state, event)
+    }
+
+    fn decide(state: &UserTimeEntriesState, command: ClaimInterval) -> UserTimeEntriesDecision {
+        decide_claim(state, command)
+    }
+}
+
+/// The user, entry id and interval a time entry claims, if it exists.
+pub fn claim_of(state: &TimeEntryState) -> Option<(&str, &str, Interval)> {
+    match state {
+        TimeEntryState::None | TimeEntryState::Deleted { .. } => None,
+        TimeEntryState::Draft {
+            time_entry_id,
+            user_id,
+            started_at,
+            ended_at,
+            ..
+        } => Some((
+            user_id.as_str(),
+            time_entry_id.as_str(),
+            Interval {
+                started_at: *started_at,
+                ended_at: *ended_at,
+            },
+        )),
+        TimeEntryState::Registered {
+            time_entry_id,
+            user_id,
+            interval,
+            ..
+        }
+        | TimeEntryState::Approved {
+            time_entry_id,
+            user_id,
+            interval,
+            ..
+        } => Some((
+            user_id.as_str(),
+            time_entry_id.as_str(),
+            Interval::from(*interval),
+        )),
+    }
+}
+
+#[cfg(test)]
+mod user_time_entries_tests {
+    use super::*;
+    use rstest::rstest;
+
+    fn interval(started_at: Option<i64>, ended_at: Option<i64>) -> Interval {
+        Interval {
+            started_at,
+            ended_at,
+        }
+    }
+
+    fn claimed_at(
+        time_entry_id: &str,
+        interval: Interval,
+        occurred_at: i64,
+    ) -> UserTimeEntriesEvent {
+        UserTimeEntriesEvent::IntervalClaimedV1(IntervalClaimedV1 {
+            time_entry_id: time_entry_id.to_string(),
+            interval,
+            occurred_at,
+        })
+    }
+
+    fn claimed(time_entry_id: &str, interval: Interval) -> UserTimeEntriesEvent {
+        claimed_at(time_entry_id, interval, 0)
+    }
+
+    fn claim(time_entry_id: &str, interval: Interval, occurred_at: i64) -> ClaimInterval {
+        ClaimInterval {
+            time_entry_id: time_entry_id.to_string(),
+            interval,
+            occurred_at,
+            rules: ClaimRules::default(),
+        }
+    }
+
+    fn accepted(events: Vec<UserTimeEntriesEvent>) -> UserTimeEntriesDecision {
+        Decision::Accepted {
+            events,
+            intents: vec![],
+        }
+    }
+
+    fn state_with(entries: &[(&str, Interval)]) -> UserTimeEntriesState {
+        entries
+            .iter()
+            .fold(UserTimeEntriesState::default(), |state, (id, interval)| {
+                evolve_user_time_entries(state, claimed(id, *interval))
+            })
+    }
+
+    #[test]
+    fn it_should_name_the_user_stream() {
+        assert_eq!(user_stream_id("u-1"), "UserTimeEntries-u-1");
+    }
+
+    #[test]
+    fn it_should_evolve_claims_and_releases() {
+        let state = state_with(&[
+            ("te-1", interval(Some(1), None)),
+            ("te-1", interval(Some(1), Some(5))),
+            ("te-2", interval(Some(5), Some(9))),
+        ]);
+        assert_eq!(state.intervals["te-1"], interval(Some(1), Some(5)));
+
+        let state = evolve_user_time_entries(
+            state,
+            UserTimeEntriesEvent::IntervalReleasedV1(IntervalReleasedV1 {
+                time_entry_id: "te-2".to_string(),
+                occurred_at: 0,
+            }),
+        );
+        assert_eq!(state.intervals.keys().collect::<Vec<_>>(), vec!["te-1"]);
+    }
+
+    #[rstest]
+    #[case::adjacent_before(interval(Some(0), Some(10)))]
+    #[case::adjacent_after(interval(Some(20), Some(30)))]
+    #[case::no_start_yet(interval(None, Some(15)))]
+    #[case::running_after(interval(Some(20), None))]
+    fn it_should_accept_intervals_that_do_not_overlap(#[case] candidate: Interval) {
+        let state = state_with(&[("te-1", interval(Some(10), Some(20)))]);
+        assert_eq!(
+            decide_claim(&state, claim("te-2", candidate, 7)),
+            accepted(vec![claimed_at("te-2", candidate, 7)])
+        );
+    }
+
+    #[rstest]
+    #[case::inside(interval(Some(12), Some(18)))]
+    #[case::straddling_start(interval(Some(5), Some(11)))]
+    #[case::straddling_end(interval(Some(19), Some(25)))]
+    #[case::running_before(interval(Some(5), None))]
+    fn it_should_reject_overlapping_intervals(#[case] candidate: Interval) {
+        let state = state_with(&[("te-1", interval(Some(10), Some(20)))]);
+        assert_eq!(
+            decide_claim(&state, claim("te-2", candidate, 0)),
+            Decision::Rejected {
+                reason: UserTimeEntriesError::Overlaps {
+                    time_entry_id: "te-1".to_string()
+                }
+            }
+        );
+    }
+
+    #[rstest]
+    fn it_should_accept_overlaps_when_the_rule_is_off() {
+        let state = state_with(&[("te-1", interval(Some(10), Some(20)))]);
+        let command = ClaimInterval {
+            rules: ClaimRules {
+                reject_overlaps: false,
+                ..ClaimRules::default()
+            },
+            ..claim("te-2", interval(Some(12), Some(18)), 0)
+        };
+
+        assert_eq!(
+            decide_claim(&state, command),
+            accepted(vec![claimed("te-2", interval(Some(12), Some(18)))])
+        );
+    }
+
+    #[rstest]
+    #[case::within(interval(Some(0), Some(10)), true)]
+    #[case::too_long(interval(Some(0), Some(11)), false)]
+    #[case::running(interval(Some(0), None), true)]
+    fn it_should_hold_finished_entries_to_the_max_duration(
+        #[case] candidate: Interval,
+        #[case] is_accepted: bool,
+    ) {
+        let command = ClaimInterval {
+            rules: ClaimRules {
+                max_duration_ms: Some(10),
+                ..ClaimRules::default()
+            },
+            ..claim("te-1", candidate, 0)
+        };
+
+        let decision = decide_claim(&UserTimeEntriesState::default(), command);
+
+        match is_accepted {
+            true => assert_eq!(decision, accepted(vec![claimed("te-1", candidate)])),
+            false => assert_eq!(
+                decision,
+                Decision::Rejected {
+                    reason: UserTimeEntriesError::TooLong {
+                        max_duration_ms: 10
+                    }
+                }
+            ),
+        }
+    }
+
+    #[test]
+    fn it_should_keep_entries_recorded_before_the_max_duration_applied() {
+        let state = state_with(&[("te-1", interval(Some(0), Some(100)))]);
+        let command = ClaimInterval {
+            rules: ClaimRules {
+                max_duration_ms: Some(10),
+                ..ClaimRules::default()
+            },
+            ..claim("te-1", interval(Some(0), Some(100)), 0)
+        };
+
+        assert_eq!(decide_claim(&state, command), accepted(vec![]));
+    }
+
+    #[test]
+    fn it_should_allow_only_one_running_timer() {
+        let state = state_with(&[("te-1", interval(Some(10), None))]);
+        let Decision::Rejected { reason: error } =
+            decide_claim(&state, claim("te-2", interval(Some(30), None), 0))
+        else {
+            panic!("expected a rejection");
+        };
+        assert_eq!(
+            error,
+            UserTimeEntriesError::TimerAlreadyRunning {
+                time_entry_id: "te-1".to_string()
+            }
+        );
+        assert_eq!(
+            error.to_string(),
+            "a timer is already running for time entry te-1"
+        );
+    }
+
+    #[test]
+    fn it_should_let_an_entry_move_its_own_interval_without_new_claims_for_repeats() {
+        let state = state_with(&[("te-1", interval(Some(10), Some(20)))]);
+        assert_eq!(
+            decide_claim(&state, claim("te-1", interval(Some(10), Some(20)), 0)),
+            accepted(vec![])
+        );
+        assert_eq!(
+            decide_claim(&state, claim("te-1", interval(Some(15), Some(25)), 0)),
+            accepted(vec![claimed("te-1", interval(Some(15), Some(25)))])
+        );
+    }
+
+    #[test]
+    fn it_should_decide_claims_against_folded_user_streams() {
+        let state = ClaimIntervalDecider::fold(vec![claimed("te-1", interval(Some(10), None))]);
+        assert_eq!(
+            ClaimIntervalDecider::decide(&state, claim("te-2", interval(Some(5), Some(8)), 0)),
+            accepted(vec![claimed("te-2", interval(Some(5), Some(8)))])
+        );
+    }
+
+    #[test]
+    fn it_should_read_the_claim_of_a_time_entry() {
+        assert_eq!(claim_of(&TimeEntryState::None), None);
+        let draft = TimeEntryState::Draft {
+            time_entry_id: "te-1".into(),
+            user_id: "u-1".into(),
+            started_at: Some(1),
+            ended_at: None,
+            tag_ids: vec![],
+            created_at: 0,
+            created_by: "u-1".into(),
+            hourly_rate: None,
+        };
+        assert_eq!(
+            claim_of(&draft),
+            Some(("u-1", "te-1", interval(Some(1), None)))
+        );
+        let registered = TimeEntryState::Registered {
+            time_entry_id: "te-1".into(),
+            user_id: "u-1".into(),
+            interval: TimeInterval::new(1, 2).unwrap(),
+            tag_ids: vec![],
+            created_at: 0,
+            created_by: "u-1".into(),
+            hourly_rate: None,
+            breaks: vec![],
+        };
+        assert_eq!(
+            claim_of(&registered),
+            Some(("u-1", "te-1", interval(Some(1), Some(2))))
+        );
+        let approved = TimeEntryState::Approved {
+            time_entry_id: "te-1".into(),
+            user_id: "u-1".into(),
+            interval: TimeInterval::new(1, 2).unwrap(),
+            tag_ids: vec![],
+            created_at: 0,
+            created_by: "u-1".into(),
+            hourly_rate: None,
+            approved_at: 3,
+            approved_by: "m-1".into(),
+            breaks: vec![],
+        };
+        assert_eq!(
+            claim_of(&approved),
+            Some(("u-1", "te-1", interval(Some(1), Some(2))))
+        );
+    }
+}
+
+#[cfg(test)]
+mod user_time_entries_event_schema_tests {
+    use super::*;
+    use crate::tests::golden::{assert_catalog, assert_event_goldens};
+    use rstest::rstest;
+
+    #[rstest]
+    fn every_version_should_match_its_golden() {
+        assert_event_goldens::<UserTimeEntriesEvent>("user_time_entries");
+    }
+
+    #[rstest]
+    fn the_catalog_should_list_every_version() {
+        assert_catalog::<UserTimeEntriesEvent>();
+    }
+}
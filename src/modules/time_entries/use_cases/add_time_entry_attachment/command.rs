@@ -0,0 +1,16 @@
+use crate::shared::auth::rbac::Principal;
+use crate::shared::core::primitives::TimeEntryId;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AddTimeEntryAttachment {
+    pub time_entry_id: TimeEntryId,
+    /// The id the upload URL was issued for.
+    pub attachment_id: String,
+    pub author: Principal,
+    pub file_name: String,
+    pub content_type: String,
+    /// The size of the uploaded file as the storage reports it; `None` when nothing was
+    /// uploaded.
+    pub size_bytes: Option<u64>,
+    pub added_at: i64,
+}
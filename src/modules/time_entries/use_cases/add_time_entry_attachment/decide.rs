@@ -0,0 +1,163 @@
+use crate::modules::time_entries::core::attachments::{ensure_valid_attachment, object_key};
+use crate::modules::time_entries::core::events::TimeEntryEvent;
+use crate::modules::time_entries::core::events::v1::time_entry_attachment_added::TimeEntryAttachmentAddedV1;
+use crate::modules::time_entries::core::evolve::evolve;
+use crate::modules::time_entries::core::intents::TimeEntryIntent;
+use crate::modules::time_entries::core::state::TimeEntryState;
+use crate::modules::time_entries::use_cases::add_time_entry_attachment::command::AddTimeEntryAttachment;
+use crate::modules::time_entries::use_cases::add_time_entry_attachment::decision::{
+    DecideError, Decision,
+};
+use crate::shared::core::decider::Decider;
+
+/// Whoever may register time for the entry's owner can attach files to it in any state, once
+/// the file is uploaded. The stored file, not what the client claimed, is held to the size
+/// limit.
+pub fn decide_add_time_entry_attachment(
+    state: &TimeEntryState,
+    command: AddTimeEntryAttachment,
+) -> Decision {
+    let rejected = |reason| Decision::Rejected { reason };
+    let user_id = match state {
+        TimeEntryState::None | TimeEntryState::Deleted { .. } => {
+            return rejected(DecideError::NotFound);
+        }
+        TimeEntryState::Draft { user_id, .. }
+        | TimeEntryState::Registered { user_id, .. }
+        | TimeEntryState::Approved { user_id, .. } => user_id,
+    };
+    // Authorization comes first so callers without rights learn nothing about the entry.
+    if !command.author.can_register_for(user_id.as_str()) {
+        return rejected(DecideError::Forbidden);
+    }
+    let Some(size_bytes) = command.size_bytes else {
+        return rejected(DecideError::NotUploaded);
+    };
+    let file_name = command.file_name.trim();
+    if let Err(error) = ensure_valid_attachment(file_name, &command.content_type, size_bytes) {
+        return rejected(error.into());
+    }
+
+    Decision::Accepted {
+        events: vec![TimeEntryEvent::TimeEntryAttachmentAddedV1(
+            TimeEntryAttachmentAddedV1 {
+                object_key: object_key(command.time_entry_id.as_str(), &command.attachment_id),
+                time_entry_id: command.time_entry_id,
+                attachment_id: command.attachment_id,
+                file_name: file_name.to_string(),
+                content_type: command.content_type,
+                size_bytes,
+                added_at: command.added_at,
+                added_by: command.author.user_id.into(),
+            },
+        )],
+        intents: vec![],
+    }
+}
+
+pub struct AddTimeEntryAttachmentDecider;
+
+impl Decider for AddTimeEntryAttachmentDecider {
+    type State = TimeEntryState;
+    type Command = AddTimeEntryAttachment;
+    type Event = TimeEntryEvent;
+    type Intent = TimeEntryIntent;
+    type Error = DecideError;
+
+    fn initial_state() -> TimeEntryState {
+        TimeEntryState::None
+    }
+
+    fn evolve(state: TimeEntryState, event: TimeEntryEvent) -> TimeEntryState {
+        evolve(state, event)
+    }
+
+    fn decide(state: &TimeEntryState, command: AddTimeEntryAttachment) -> Decision {
+        decide_add_time_entry_attachment(state, command)
+    }
+}
+
+#[cfg(test)]
+mod decide_add_time_entry_attachment_tests {
+    use super::*;
+    use crate::modules::time_entries::core::attachments::{AttachmentError, MAX_ATTACHMENT_BYTES};
+    use crate::shared::auth::rbac::{Principal, Role};
+    use crate::tests::fixtures::commands::add_time_entry_attachment::AddTimeEntryAttachmentBuilder;
+    use rstest::rstest;
+
+    fn draft() -> TimeEntryState {
+        TimeEntryState::Draft {
+            time_entry_id: "te-fixed-0001".into(),
+            user_id: "user-fixed-0001".into(),
+            started_at: Some(1_000),
+            ended_at: None,
+            tag_ids: vec![],
+            created_at: 0,
+            created_by: "user-fixed-0001".into(),
+            hourly_rate: None,
+        }
+    }
+
+    #[rstest]
+    fn it_should_link_the_uploaded_file() {
+        let command = AddTimeEntryAttachmentBuilder::new()
+            .file_name(" receipt.pdf ")
+            .build();
+
+        match decide_add_time_entry_attachment(&draft(), command) {
+            Decision::Accepted { events, intents } => {
+                assert_eq!(
+                    events,
+                    vec![TimeEntryEvent::TimeEntryAttachmentAddedV1(
+                        TimeEntryAttachmentAddedV1 {
+                            time_entry_id: "te-fixed-0001".into(),
+                            attachment_id: "attachment-fixed-0001".to_string(),
+                            object_key: "attachments/te-fixed-0001/attachment-fixed-0001"
+                                .to_string(),
+                            file_name: "receipt.pdf".to_string(),
+                            content_type: "application/pdf".to_string(),
+                            size_bytes: 48_213,
+                            added_at: 1_700_000_000_000,
+                            added_by: "user-fixed-0001".into(),
+                        }
+                    )]
+                );
+                assert!(intents.is_empty());
+            }
+            Decision::Rejected { .. } => panic!("expected Accepted"),
+        }
+    }
+
+    #[rstest]
+    #[case::missing(
+        TimeEntryState::None,
+        AddTimeEntryAttachmentBuilder::new(),
+        DecideError::NotFound
+    )]
+    #[case::other_employee(
+        draft(),
+        AddTimeEntryAttachmentBuilder::new().author(Principal::new("user-fixed-0002", Role::Employee)),
+        DecideError::Forbidden
+    )]
+    #[case::not_uploaded(draft(), AddTimeEntryAttachmentBuilder::new().size_bytes(None), DecideError::NotUploaded)]
+    #[case::too_large(
+        draft(),
+        AddTimeEntryAttachmentBuilder::new().size_bytes(Some(MAX_ATTACHMENT_BYTES + 1)),
+        DecideError::Invalid(AttachmentError::TooLarge { max: MAX_ATTACHMENT_BYTES })
+    )]
+    #[case::unsupported(
+        draft(),
+        AddTimeEntryAttachmentBuilder::new().content_type("text/html"),
+        DecideError::Invalid(AttachmentError::UnsupportedContentType)
+    )]
+    fn it_should_reject_attachments_that_cannot_be_added(
+        #[case] state: TimeEntryState,
+        #[case] command: AddTimeEntryAttachmentBuilder,
+        #[case] expected: DecideError,
+    ) {
+        match decide_add_time_entry_attachment(&state, command.build()) {
+            Decision::Rejected { reason } => assert_eq!(reason, expected),
+            Decision::Accepted { .. } => panic!("expected Rejected"),
+        }
+    }
+}
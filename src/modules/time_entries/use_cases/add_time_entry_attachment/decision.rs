@@ -0,0 +1,22 @@
+use crate::modules::time_entries::core::attachments::AttachmentError;
+use crate::modules::time_entries::core::events::TimeEntryEvent;
+use crate::modules::time_entries::core::intents::TimeEntryIntent;
+use crate::shared::core::decider;
+use thiserror::Error;
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum DecideError {
+    #[error("time entry not found")]
+    NotFound,
+
+    #[error("author may not attach files to this time entry")]
+    Forbidden,
+
+    #[error("no file was uploaded for this attachment")]
+    NotUploaded,
+
+    #[error(transparent)]
+    Invalid(#[from] AttachmentError),
+}
+
+pub type Decision = decider::Decision<TimeEntryEvent, TimeEntryIntent, DecideError>;
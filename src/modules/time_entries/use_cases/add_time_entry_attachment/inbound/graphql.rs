@@ -0,0 +1,321 @@
+use async_graphql::{Context, ID, Object, Result as GqlResult, SimpleObject};
+use chrono::Utc;
+use uuid::{Uuid, Version};
+
+use crate::modules::time_entries::core::attachments::{ensure_valid_attachment, object_key};
+use crate::modules::time_entries::use_cases::add_time_entry_attachment::command::AddTimeEntryAttachment;
+use crate::shared::core::primitives::TimeEntryId;
+use crate::shared::infrastructure::attachment_storage::AttachmentStorage;
+use crate::shared::infrastructure::request_context::RequestContext;
+use crate::shell::state::AppState;
+
+#[derive(SimpleObject, Clone)]
+#[graphql(name = "AttachmentUpload")]
+pub struct GqlAttachmentUpload {
+    /// Pass to `addTimeEntryAttachment` once the upload is done.
+    pub attachment_id: ID,
+    /// `PUT` the file here, with the `Content-Type` it was requested for.
+    pub upload_url: String,
+    pub expires_at: i64,
+}
+
+#[cfg(test)]
+mod add_time_entry_attachment_graphql_inbound_tests {
+    use async_graphql::{EmptySubscription, Schema};
+
+    use crate::modules::time_entries::core::events::TimeEntryEvent;
+    use crate::modules::time_entries::use_cases::set_started_at::command::SetStartedAt;
+    use crate::shared::auth::rbac::Scope;
+    use crate::shared::core::stream_naming::{DefaultStreamNaming, StreamNaming};
+    use crate::shared::infrastructure::event_store::EventStore;
+    use crate::shared::infrastructure::request_context::RequestContext;
+    use crate::shell::graphql::{MutationRoot, QueryRoot};
+    use crate::shell::state::AppState;
+    use crate::tests::fixtures::tags::make_test_app_state;
+
+    fn make_schema_from_state(
+        state: AppState,
+    ) -> Schema<QueryRoot, MutationRoot, EmptySubscription> {
+        Schema::build(
+            QueryRoot::default(),
+            MutationRoot::default(),
+            EmptySubscription,
+        )
+        .data(state)
+        .finish()
+    }
+
+    fn req_ctx() -> RequestContext {
+        RequestContext {
+            user_id: "u-1".to_string(),
+            tenant_id: "tenant-test".to_string(),
+            role: Default::default(),
+            scope: Default::default(),
+        }
+    }
+
+    /// A draft entry of `u-1`.
+    async fn started(state: &AppState) -> String {
+        let te_id = uuid::Uuid::now_v7().to_string();
+        state
+            .set_started_at_handler
+            .handle(
+                &DefaultStreamNaming.time_entry(None, &te_id),
+                SetStartedAt {
+                    time_entry_id: te_id.clone().into(),
+                    user_id: "u-1".into(),
+                    started_at: 1_000,
+                    updated_at: 1_000,
+                    updated_by: "u-1".into(),
+                    rounding: None,
+                },
+            )
+            .await
+            .unwrap();
+        te_id
+    }
+
+    fn request_upload(time_entry_id: &str, content_type: &str) -> String {
+        format!(
+            r#"mutation {{ requestAttachmentUpload(timeEntryId: "{time_entry_id}", fileName: "receipt.pdf", contentType: "{content_type}", sizeBytes: 2048) {{ attachmentId uploadUrl expiresAt }} }}"#
+        )
+    }
+
+    fn add(time_entry_id: &str, attachment_id: &str) -> String {
+        format!(
+            r#"mutation {{ addTimeEntryAttachment(timeEntryId: "{time_entry_id}", attachmentId: "{attachment_id}", fileName: "receipt.pdf", contentType: "application/pdf") }}"#
+        )
+    }
+
+    #[tokio::test]
+    async fn links_an_uploaded_file_to_the_entry() {
+        let state = make_test_app_state();
+        let te_id = started(&state).await;
+        let schema = make_schema_from_state(state.clone());
+
+        let requested = schema
+            .execute(
+                async_graphql::Request::new(request_upload(&te_id, "application/pdf"))
+                    .data(req_ctx()),
+            )
+            .await;
+        assert!(requested.errors.is_empty(), "{:?}", requested.errors);
+        let data = requested.data.into_json().unwrap();
+        let attachment_id = data["requestAttachmentUpload"]["attachmentId"]
+            .as_str()
+            .unwrap()
+            .to_string();
+        let key = format!("attachments/{te_id}/{attachment_id}");
+        assert!(
+            data["requestAttachmentUpload"]["uploadUrl"]
+                .as_str()
+                .unwrap()
+                .starts_with(&format!("memory:{key}?method=PUT"))
+        );
+        state.attachment_storage.put_object(&key, 2_048).await;
+
+        let added = schema
+            .execute(async_graphql::Request::new(add(&te_id, &attachment_id)).data(req_ctx()))
+            .await;
+
+        assert!(added.errors.is_empty(), "{:?}", added.errors);
+        let stream = state
+            .event_store
+            .load(&DefaultStreamNaming.time_entry(None, &te_id))
+            .await
+            .unwrap();
+        match stream.events.last() {
+            Some(TimeEntryEvent::TimeEntryAttachmentAddedV1(e)) => {
+                assert_eq!(e.object_key, key);
+                assert_eq!(e.size_bytes, 2_048);
+            }
+            other => panic!("expected an attachment, got {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn refuses_uploads_of_unsupported_files() {
+        let te_id = uuid::Uuid::now_v7().to_string();
+        let schema = make_schema_from_state(make_test_app_state());
+
+        let result = schema
+            .execute(
+                async_graphql::Request::new(request_upload(&te_id, "text/html")).data(req_ctx()),
+            )
+            .await;
+
+        assert_eq!(
+            result.errors[0].message,
+            "attachments must be images or PDFs"
+        );
+    }
+
+    #[tokio::test]
+    async fn refuses_attachments_that_were_not_uploaded() {
+        let state = make_test_app_state();
+        let te_id = started(&state).await;
+        let schema = make_schema_from_state(state);
+        let attachment_id = uuid::Uuid::now_v7().to_string();
+
+        let result = schema
+            .execute(async_graphql::Request::new(add(&te_id, &attachment_id)).data(req_ctx()))
+            .await;
+
+        assert_eq!(
+            result.errors[0].message,
+            "domain rejected: no file was uploaded for this attachment"
+        );
+    }
+
+    #[tokio::test]
+    async fn refuses_attachment_ids_it_did_not_issue() {
+        let te_id = uuid::Uuid::now_v7().to_string();
+        let schema = make_schema_from_state(make_test_app_state());
+
+        let result = schema
+            .execute(async_graphql::Request::new(add(&te_id, "../../etc/passwd")).data(req_ctx()))
+            .await;
+
+        assert_eq!(
+            result.errors[0].message,
+            "attachment_id must be a valid UUID v7"
+        );
+    }
+
+    #[tokio::test]
+    async fn returns_forbidden_for_read_only_api_keys() {
+        let te_id = uuid::Uuid::now_v7().to_string();
+        let schema = make_schema_from_state(make_test_app_state());
+
+        let result = schema
+            .execute(
+                async_graphql::Request::new(request_upload(&te_id, "application/pdf")).data(
+                    RequestContext {
+                        scope: Scope::ReadOnly,
+                        ..req_ctx()
+                    },
+                ),
+            )
+            .await;
+
+        assert_eq!(result.errors[0].message, "Forbidden");
+    }
+
+    #[tokio::test]
+    async fn returns_error_when_the_storage_is_offline() {
+        let state = make_test_app_state();
+        state.attachment_storage.toggle_offline();
+        let te_id = uuid::Uuid::now_v7().to_string();
+        let schema = make_schema_from_state(state);
+
+        let result = schema
+            .execute(
+                async_graphql::Request::new(request_upload(&te_id, "application/pdf"))
+                    .data(req_ctx()),
+            )
+            .await;
+
+        assert_eq!(
+            result.errors[0].message,
+            "backend error: Attachment storage offline"
+        );
+    }
+}
+
+#[derive(Default)]
+pub struct AddTimeEntryAttachmentMutation;
+
+#[Object]
+impl AddTimeEntryAttachmentMutation {
+    /// Starts attaching a receipt or screenshot: returns the id of the attachment and a URL to
+    /// upload the file to. Images and PDFs up to 10 MiB.
+    async fn request_attachment_upload(
+        &self,
+        context: &Context<'_>,
+        time_entry_id: String,
+        file_name: String,
+        content_type: String,
+        size_bytes: i64,
+    ) -> GqlResult<GqlAttachmentUpload> {
+        let time_entry_id = TimeEntryId::parse_v7(&time_entry_id)
+            .map_err(|_| async_graphql::Error::new("time_entry_id must be a valid UUID v7"))?;
+
+        let req_ctx = context
+            .data::<RequestContext>()
+            .map_err(|_| async_graphql::Error::new("Unauthorized"))?;
+        if !req_ctx.principal().can_write() {
+            return Err(async_graphql::Error::new("Forbidden"));
+        }
+        ensure_valid_attachment(&file_name, &content_type, size_bytes.max(0) as u64)?;
+        let state = context.data_unchecked::<AppState>();
+
+        let attachment_id = Uuid::now_v7().to_string();
+        let upload = state
+            .attachment_storage
+            .upload_url(
+                &object_key(time_entry_id.as_str(), &attachment_id),
+                &content_type,
+            )
+            .await?;
+
+        Ok(GqlAttachmentUpload {
+            attachment_id: ID(attachment_id),
+            upload_url: upload.url,
+            expires_at: upload.expires_at,
+        })
+    }
+
+    /// Links the file uploaded for `attachmentId` to the entry. The owner of the entry,
+    /// managers and admins can attach files. Returns the attachment id.
+    async fn add_time_entry_attachment(
+        &self,
+        context: &Context<'_>,
+        time_entry_id: String,
+        attachment_id: String,
+        file_name: String,
+        content_type: String,
+    ) -> GqlResult<ID> {
+        let time_entry_id = TimeEntryId::parse_v7(&time_entry_id)
+            .map_err(|_| async_graphql::Error::new("time_entry_id must be a valid UUID v7"))?;
+        // Only ids `requestAttachmentUpload` issued, which keeps object keys well-formed.
+        let attachment_id = Uuid::parse_str(&attachment_id)
+            .ok()
+            .filter(|id| id.get_version() == Some(Version::SortRand))
+            .ok_or_else(|| async_graphql::Error::new("attachment_id must be a valid UUID v7"))?
+            .to_string();
+
+        let req_ctx = context
+            .data::<RequestContext>()
+            .map_err(|_| async_graphql::Error::new("Unauthorized"))?;
+        let author = req_ctx.principal();
+        if !author.can_write() {
+            return Err(async_graphql::Error::new("Forbidden"));
+        }
+        let state = context.data_unchecked::<AppState>();
+        let stream_id = state
+            .stream_naming
+            .time_entry(Some(&req_ctx.tenant_id), &time_entry_id);
+
+        let size_bytes = state
+            .attachment_storage
+            .object_size(&object_key(time_entry_id.as_str(), &attachment_id))
+            .await?;
+        let command = AddTimeEntryAttachment {
+            time_entry_id,
+            attachment_id: attachment_id.clone(),
+            author,
+            file_name,
+            content_type,
+            size_bytes,
+            added_at: Utc::now().timestamp_millis(),
+        };
+
+        state
+            .add_time_entry_attachment_handler
+            .handle(&stream_id, command)
+            .await
+            .map_err(|e| async_graphql::Error::new(e.to_string()))?;
+
+        Ok(ID(attachment_id))
+    }
+}
@@ -0,0 +1,11 @@
+use crate::shared::auth::rbac::Principal;
+use crate::shared::core::primitives::TimeEntryId;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AddTimeEntryComment {
+    pub time_entry_id: TimeEntryId,
+    pub comment_id: String,
+    pub author: Principal,
+    pub body: String,
+    pub added_at: i64,
+}
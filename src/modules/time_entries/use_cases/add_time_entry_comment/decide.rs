@@ -0,0 +1,198 @@
+use crate::modules::time_entries::core::events::TimeEntryEvent;
+use crate::modules::time_entries::core::events::v1::time_entry_comment_added::TimeEntryCommentAddedV1;
+use crate::modules::time_entries::core::evolve::evolve;
+use crate::modules::time_entries::core::intents::TimeEntryIntent;
+use crate::modules::time_entries::core::state::TimeEntryState;
+use crate::modules::time_entries::use_cases::add_time_entry_comment::command::AddTimeEntryComment;
+use crate::modules::time_entries::use_cases::add_time_entry_comment::decision::{
+    DecideError, Decision,
+};
+use crate::shared::core::decider::Decider;
+
+/// The longest comment, in characters.
+pub const MAX_COMMENT_LENGTH: usize = 2_000;
+
+/// The entry's owner and whoever may register time for them, so managers and admins, can
+/// comment on it in any state. Surrounding whitespace is dropped from the body.
+pub fn decide_add_time_entry_comment(
+    state: &TimeEntryState,
+    command: AddTimeEntryComment,
+) -> Decision {
+    let rejected = |reason| Decision::Rejected { reason };
+    let user_id = match state {
+        TimeEntryState::None | TimeEntryState::Deleted { .. } => {
+            return rejected(DecideError::NotFound);
+        }
+        TimeEntryState::Draft { user_id, .. }
+        | TimeEntryState::Registered { user_id, .. }
+        | TimeEntryState::Approved { user_id, .. } => user_id,
+    };
+    // Authorization comes first so callers without rights learn nothing about the entry.
+    if !command.author.can_register_for(user_id.as_str()) {
+        return rejected(DecideError::Forbidden);
+    }
+
+    let body = command.body.trim();
+    if body.is_empty() {
+        return rejected(DecideError::Empty);
+    }
+    if body.chars().count() > MAX_COMMENT_LENGTH {
+        return rejected(DecideError::TooLong {
+            max: MAX_COMMENT_LENGTH,
+        });
+    }
+
+    Decision::Accepted {
+        events: vec![TimeEntryEvent::TimeEntryCommentAddedV1(
+            TimeEntryCommentAddedV1 {
+                time_entry_id: command.time_entry_id,
+                comment_id: command.comment_id,
+                body: body.to_string(),
+                added_at: command.added_at,
+                added_by: command.author.user_id.into(),
+            },
+        )],
+        intents: vec![],
+    }
+}
+
+pub struct AddTimeEntryCommentDecider;
+
+impl Decider for AddTimeEntryCommentDecider {
+    type State = TimeEntryState;
+    type Command = AddTimeEntryComment;
+    type Event = TimeEntryEvent;
+    type Intent = TimeEntryIntent;
+    type Error = DecideError;
+
+    fn initial_state() -> TimeEntryState {
+        TimeEntryState::None
+    }
+
+    fn evolve(state: TimeEntryState, event: TimeEntryEvent) -> TimeEntryState {
+        evolve(state, event)
+    }
+
+    fn decide(state: &TimeEntryState, command: AddTimeEntryComment) -> Decision {
+        decide_add_time_entry_comment(state, command)
+    }
+}
+
+#[cfg(test)]
+mod decide_add_time_entry_comment_tests {
+    use super::*;
+    use crate::modules::time_entries::core::time_interval::TimeInterval;
+    use crate::shared::auth::rbac::{Principal, Role, Scope};
+    use crate::tests::fixtures::commands::add_time_entry_comment::AddTimeEntryCommentBuilder;
+    use rstest::{fixture, rstest};
+
+    #[fixture]
+    fn command() -> AddTimeEntryComment {
+        AddTimeEntryCommentBuilder::new().build()
+    }
+
+    fn draft() -> TimeEntryState {
+        TimeEntryState::Draft {
+            time_entry_id: "te-fixed-0001".into(),
+            user_id: "user-fixed-0001".into(),
+            started_at: Some(1_000),
+            ended_at: None,
+            tag_ids: vec![],
+            created_at: 0,
+            created_by: "user-fixed-0001".into(),
+            hourly_rate: None,
+        }
+    }
+
+    fn approved() -> TimeEntryState {
+        TimeEntryState::Approved {
+            time_entry_id: "te-fixed-0001".into(),
+            user_id: "user-fixed-0001".into(),
+            interval: TimeInterval::new(1_000, 2_000).unwrap(),
+            tag_ids: vec![],
+            created_at: 0,
+            created_by: "user-fixed-0001".into(),
+            hourly_rate: None,
+            approved_at: 3_000,
+            approved_by: "manager-fixed-0001".into(),
+            breaks: vec![],
+        }
+    }
+
+    #[rstest]
+    #[case::draft(draft())]
+    #[case::approved(approved())]
+    fn it_should_add_a_trimmed_comment(
+        #[case] state: TimeEntryState,
+        #[values(
+            Principal::new("manager-fixed-0001", Role::Manager),
+            Principal::new("user-fixed-0001", Role::Employee)
+        )]
+        author: Principal,
+    ) {
+        let command = AddTimeEntryCommentBuilder::new()
+            .author(author.clone())
+            .body("  Please split this entry per project.\n")
+            .build();
+
+        match decide_add_time_entry_comment(&state, command) {
+            Decision::Accepted { events, intents } => {
+                assert_eq!(
+                    events,
+                    vec![TimeEntryEvent::TimeEntryCommentAddedV1(
+                        TimeEntryCommentAddedV1 {
+                            time_entry_id: "te-fixed-0001".into(),
+                            comment_id: "comment-fixed-0001".to_string(),
+                            body: "Please split this entry per project.".to_string(),
+                            added_at: 1_700_000_000_000,
+                            added_by: author.user_id.into(),
+                        }
+                    )]
+                );
+                assert!(intents.is_empty());
+            }
+            Decision::Rejected { .. } => panic!("expected Accepted"),
+        }
+    }
+
+    #[rstest]
+    fn it_should_reject_a_missing_entry(command: AddTimeEntryComment) {
+        assert!(matches!(
+            decide_add_time_entry_comment(&TimeEntryState::None, command),
+            Decision::Rejected {
+                reason: DecideError::NotFound
+            }
+        ));
+    }
+
+    #[rstest]
+    #[case::other_employee(Principal::new("user-fixed-0002", Role::Employee))]
+    #[case::read_only_manager(
+        Principal::new("manager-fixed-0001", Role::Manager).with_scope(Scope::ReadOnly)
+    )]
+    fn it_should_forbid_authors_without_rights(#[case] author: Principal) {
+        let command = AddTimeEntryCommentBuilder::new()
+            .author(author)
+            .body("")
+            .build();
+
+        assert!(matches!(
+            decide_add_time_entry_comment(&approved(), command),
+            Decision::Rejected {
+                reason: DecideError::Forbidden
+            }
+        ));
+    }
+
+    #[rstest]
+    #[case::empty(" \n ".to_string(), DecideError::Empty)]
+    #[case::too_long("x".repeat(MAX_COMMENT_LENGTH + 1), DecideError::TooLong { max: MAX_COMMENT_LENGTH })]
+    fn it_should_reject_invalid_bodies(#[case] body: String, #[case] expected: DecideError) {
+        let command = AddTimeEntryCommentBuilder::new().body(body).build();
+
+        match decide_add_time_entry_comment(&draft(), command) {
+            Decision::Rejected { reason } => assert_eq!(reason, expected),
+            Decision::Accepted { .. } => panic!("expected Rejected"),
+        }
+    }
+}
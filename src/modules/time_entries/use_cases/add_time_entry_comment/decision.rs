@@ -0,0 +1,21 @@
+use crate::modules::time_entries::core::events::TimeEntryEvent;
+use crate::modules::time_entries::core::intents::TimeEntryIntent;
+use crate::shared::core::decider;
+use thiserror::Error;
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum DecideError {
+    #[error("time entry not found")]
+    NotFound,
+
+    #[error("author may not comment on this time entry")]
+    Forbidden,
+
+    #[error("comment must not be empty")]
+    Empty,
+
+    #[error("comment is longer than {max} characters")]
+    TooLong { max: usize },
+}
+
+pub type Decision = decider::Decision<TimeEntryEvent, TimeEntryIntent, DecideError>;
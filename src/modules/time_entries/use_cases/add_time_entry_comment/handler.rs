@@ -0,0 +1,165 @@
+use crate::modules::time_entries::adapters::outbound::intent_outbox::TimeEntryIntentDispatcher;
+use crate::modules::time_entries::core::events::TimeEntryEvent;
+use crate::modules::time_entries::use_cases::add_time_entry_comment::command::AddTimeEntryComment;
+use crate::modules::time_entries::use_cases::add_time_entry_comment::decide::AddTimeEntryCommentDecider;
+use crate::modules::time_entries::use_cases::add_time_entry_comment::decision::DecideError;
+use crate::shared::application::command_bus::CommandHandler;
+use crate::shared::application::event_sourced_handler::{EventSourcedError, EventSourcedHandler};
+use crate::shared::infrastructure::event_bus::SharedEventBus;
+use crate::shared::infrastructure::event_store::EventStore;
+use crate::shared::infrastructure::intent_outbox::{DomainOutbox, OutboxError};
+use async_trait::async_trait;
+
+pub type ApplicationError = EventSourcedError<DecideError, OutboxError>;
+
+#[derive(Debug, Clone)]
+pub struct AddTimeEntryCommentHandler<TEventStore, TOutbox>
+where
+    TEventStore: EventStore<TimeEntryEvent> + Send + Sync + 'static,
+    TOutbox: DomainOutbox + Send + Sync + 'static,
+{
+    inner: EventSourcedHandler<
+        AddTimeEntryCommentDecider,
+        TEventStore,
+        TimeEntryIntentDispatcher<TOutbox>,
+    >,
+}
+
+impl<TEventStore, TOutbox> AddTimeEntryCommentHandler<TEventStore, TOutbox>
+where
+    TEventStore: EventStore<TimeEntryEvent> + Send + Sync + 'static,
+    TOutbox: DomainOutbox + Send + Sync + 'static,
+{
+    pub fn new(event_store: TEventStore, outbox: TOutbox) -> Self {
+        Self {
+            inner: EventSourcedHandler::new(event_store, TimeEntryIntentDispatcher::new(outbox)),
+        }
+    }
+
+    /// Announce the appended events on `event_bus`, for the projectors to pick up.
+    pub fn with_event_bus(mut self, event_bus: SharedEventBus<TimeEntryEvent>) -> Self {
+        self.inner = self.inner.with_event_bus(event_bus);
+        self
+    }
+
+    pub async fn handle(
+        &self,
+        stream_id: &str,
+        command: AddTimeEntryComment,
+    ) -> Result<(), ApplicationError> {
+        self.inner.handle(stream_id, command).await
+    }
+}
+
+#[async_trait]
+impl<TEventStore, TOutbox> CommandHandler<AddTimeEntryComment>
+    for AddTimeEntryCommentHandler<TEventStore, TOutbox>
+where
+    TEventStore: EventStore<TimeEntryEvent> + Send + Sync + 'static,
+    TOutbox: DomainOutbox + Send + Sync + 'static,
+{
+    type Error = ApplicationError;
+
+    async fn handle(
+        &self,
+        stream_id: &str,
+        command: AddTimeEntryComment,
+    ) -> Result<(), ApplicationError> {
+        AddTimeEntryCommentHandler::handle(self, stream_id, command).await
+    }
+}
+
+#[cfg(test)]
+mod add_time_entry_comment_handler_tests {
+    use super::*;
+    use crate::modules::time_entries::use_cases::set_started_at::handler::SetStartedAtHandler;
+    use crate::shared::application::command_bus::{CommandBus, CommandEnvelope};
+    use crate::shared::infrastructure::event_store::EventStoreError;
+    use crate::shared::infrastructure::event_store::in_memory::InMemoryEventStore;
+    use crate::shared::infrastructure::intent_outbox::in_memory::InMemoryDomainOutbox;
+    use crate::tests::fixtures::commands::add_time_entry_comment::AddTimeEntryCommentBuilder;
+    use crate::tests::fixtures::commands::set_started_at::SetStartedAtBuilder;
+    use rstest::{fixture, rstest};
+
+    const STREAM_ID: &str = "TimeEntry-te-fixed-0001";
+
+    type BeforeEachReturn = (InMemoryEventStore<TimeEntryEvent>, InMemoryDomainOutbox);
+
+    #[fixture]
+    fn before_each() -> BeforeEachReturn {
+        (
+            InMemoryEventStore::<TimeEntryEvent>::new(),
+            InMemoryDomainOutbox::new(),
+        )
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn handle_add_time_entry_comment_appends_every_comment(before_each: BeforeEachReturn) {
+        let (event_store, outbox) = before_each;
+        SetStartedAtHandler::new(event_store.clone(), outbox.clone())
+            .handle(STREAM_ID, SetStartedAtBuilder::new().build())
+            .await
+            .unwrap();
+        let bus = CommandBus::new(AddTimeEntryCommentHandler::new(event_store.clone(), outbox));
+
+        for comment_id in ["comment-1", "comment-2"] {
+            bus.dispatch(CommandEnvelope::new(
+                STREAM_ID,
+                AddTimeEntryCommentBuilder::new()
+                    .comment_id(comment_id)
+                    .build(),
+            ))
+            .await
+            .expect("comment failed");
+        }
+
+        let stream = event_store.load(STREAM_ID).await.unwrap();
+        let comments: Vec<&str> = stream
+            .events
+            .iter()
+            .filter_map(|event| match event {
+                TimeEntryEvent::TimeEntryCommentAddedV1(e) => Some(e.comment_id.as_str()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(comments, vec!["comment-1", "comment-2"]);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn handle_add_time_entry_comment_rejects_a_missing_entry(before_each: BeforeEachReturn) {
+        let (event_store, outbox) = before_each;
+        let handler = AddTimeEntryCommentHandler::new(event_store, outbox);
+
+        let result = handler
+            .handle(STREAM_ID, AddTimeEntryCommentBuilder::new().build())
+            .await;
+
+        assert!(matches!(
+            result,
+            Err(ApplicationError::Domain(DecideError::NotFound))
+        ));
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn handle_add_time_entry_comment_fails_if_event_store_is_offline(
+        before_each: BeforeEachReturn,
+    ) {
+        let (event_store, outbox) = before_each;
+        event_store.toggle_offline();
+        let handler = AddTimeEntryCommentHandler::new(event_store, outbox);
+
+        let result = handler
+            .handle(STREAM_ID, AddTimeEntryCommentBuilder::new().build())
+            .await;
+
+        assert!(matches!(
+            result,
+            Err(ApplicationError::VersionConflict(EventStoreError::Backend(
+                _
+            )))
+        ));
+    }
+}
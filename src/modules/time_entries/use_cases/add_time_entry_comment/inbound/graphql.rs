@@ -0,0 +1,203 @@
+use async_graphql::{Context, ID, Object, Result as GqlResult};
+use chrono::Utc;
+use uuid::Uuid;
+
+use crate::modules::time_entries::use_cases::add_time_entry_comment::command::AddTimeEntryComment;
+use crate::shared::core::primitives::TimeEntryId;
+use crate::shared::infrastructure::request_context::RequestContext;
+use crate::shell::state::AppState;
+
+#[cfg(test)]
+mod add_time_entry_comment_graphql_inbound_tests {
+    use async_graphql::{EmptySubscription, Schema};
+
+    use crate::modules::time_entries::core::events::TimeEntryEvent;
+    use crate::modules::time_entries::use_cases::set_started_at::command::SetStartedAt;
+    use crate::shared::auth::rbac::{Role, Scope};
+    use crate::shared::core::stream_naming::{DefaultStreamNaming, StreamNaming};
+    use crate::shared::infrastructure::event_store::EventStore;
+    use crate::shared::infrastructure::request_context::RequestContext;
+    use crate::shell::graphql::{MutationRoot, QueryRoot};
+    use crate::shell::state::AppState;
+    use crate::tests::fixtures::tags::make_test_app_state;
+
+    fn make_schema_from_state(
+        state: AppState,
+    ) -> Schema<QueryRoot, MutationRoot, EmptySubscription> {
+        Schema::build(
+            QueryRoot::default(),
+            MutationRoot::default(),
+            EmptySubscription,
+        )
+        .data(state)
+        .finish()
+    }
+
+    fn manager_ctx() -> RequestContext {
+        RequestContext {
+            user_id: "manager-1".to_string(),
+            tenant_id: "tenant-test".to_string(),
+            role: Role::Manager,
+            scope: Default::default(),
+        }
+    }
+
+    /// A draft entry of `u-1`.
+    async fn started(state: &AppState) -> String {
+        let te_id = uuid::Uuid::now_v7().to_string();
+        state
+            .set_started_at_handler
+            .handle(
+                &DefaultStreamNaming.time_entry(None, &te_id),
+                SetStartedAt {
+                    time_entry_id: te_id.clone().into(),
+                    user_id: "u-1".into(),
+                    started_at: 1_000,
+                    updated_at: 1_000,
+                    updated_by: "u-1".into(),
+                    rounding: None,
+                },
+            )
+            .await
+            .unwrap();
+        te_id
+    }
+
+    fn mutation(time_entry_id: &str, body: &str) -> String {
+        format!(
+            r#"mutation {{ addTimeEntryComment(timeEntryId: "{time_entry_id}", body: "{body}") }}"#
+        )
+    }
+
+    #[tokio::test]
+    async fn returns_the_id_of_the_new_comment() {
+        let state = make_test_app_state();
+        let te_id = started(&state).await;
+        let schema = make_schema_from_state(state.clone());
+
+        let result = schema
+            .execute(
+                async_graphql::Request::new(mutation(&te_id, "Please split this entry."))
+                    .data(manager_ctx()),
+            )
+            .await;
+
+        assert!(result.errors.is_empty(), "{:?}", result.errors);
+        let data = result.data.into_json().unwrap();
+        let stream = state
+            .event_store
+            .load(&DefaultStreamNaming.time_entry(None, &te_id))
+            .await
+            .unwrap();
+        match stream.events.last() {
+            Some(TimeEntryEvent::TimeEntryCommentAddedV1(e)) => {
+                assert_eq!(data["addTimeEntryComment"], e.comment_id.as_str());
+                assert_eq!(e.body, "Please split this entry.");
+                assert_eq!(e.added_by, "manager-1");
+            }
+            other => panic!("expected a comment, got {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn returns_error_on_empty_comments() {
+        let state = make_test_app_state();
+        let te_id = started(&state).await;
+        let schema = make_schema_from_state(state);
+
+        let result = schema
+            .execute(async_graphql::Request::new(mutation(&te_id, "  ")).data(manager_ctx()))
+            .await;
+
+        assert_eq!(
+            result.errors[0].message,
+            "domain rejected: comment must not be empty"
+        );
+    }
+
+    #[tokio::test]
+    async fn returns_error_for_other_employees() {
+        let state = make_test_app_state();
+        let te_id = started(&state).await;
+        let schema = make_schema_from_state(state);
+
+        let result = schema
+            .execute(
+                async_graphql::Request::new(mutation(&te_id, "Hi")).data(RequestContext {
+                    user_id: "u-2".to_string(),
+                    role: Role::Employee,
+                    ..manager_ctx()
+                }),
+            )
+            .await;
+
+        assert_eq!(
+            result.errors[0].message,
+            "domain rejected: author may not comment on this time entry"
+        );
+    }
+
+    #[tokio::test]
+    async fn returns_forbidden_for_read_only_api_keys() {
+        let te_id = uuid::Uuid::now_v7().to_string();
+        let schema = make_schema_from_state(make_test_app_state());
+
+        let result = schema
+            .execute(
+                async_graphql::Request::new(mutation(&te_id, "Hi")).data(RequestContext {
+                    scope: Scope::ReadOnly,
+                    ..manager_ctx()
+                }),
+            )
+            .await;
+
+        assert_eq!(result.errors[0].message, "Forbidden");
+    }
+}
+
+#[derive(Default)]
+pub struct AddTimeEntryCommentMutation;
+
+#[Object]
+impl AddTimeEntryCommentMutation {
+    /// Comments on a time entry, for instance to discuss why it was not approved. The owner
+    /// of the entry, managers and admins can comment. Returns the id of the new comment.
+    async fn add_time_entry_comment(
+        &self,
+        context: &Context<'_>,
+        time_entry_id: String,
+        body: String,
+    ) -> GqlResult<ID> {
+        let time_entry_id = TimeEntryId::parse_v7(&time_entry_id)
+            .map_err(|_| async_graphql::Error::new("time_entry_id must be a valid UUID v7"))?;
+
+        let req_ctx = context
+            .data::<RequestContext>()
+            .map_err(|_| async_graphql::Error::new("Unauthorized"))?;
+        let author = req_ctx.principal();
+        if !author.can_write() {
+            return Err(async_graphql::Error::new("Forbidden"));
+        }
+        let state = context.data_unchecked::<AppState>();
+        let stream_id = state
+            .stream_naming
+            .time_entry(Some(&req_ctx.tenant_id), &time_entry_id);
+
+        let comment_id = Uuid::now_v7().to_string();
+        let command = AddTimeEntryComment {
+            time_entry_id,
+            comment_id: comment_id.clone(),
+            author,
+            body,
+            added_at: Utc::now().timestamp_millis(),
+        };
+
+        state
+            .add_time_entry_comment_handler
+            .handle(&stream_id, command)
+            .await
+            .map_err(|e| async_graphql::Error::new(e.to_string()))?;
+
+        Ok(ID(comment_id))
+    }
+}
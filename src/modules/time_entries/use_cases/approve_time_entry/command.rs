@@ -0,0 +1,9 @@
+use crate::shared::auth::rbac::Principal;
+use crate::shared::core::primitives::TimeEntryId;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ApproveTimeEntry {
+    pub time_entry_id: TimeEntryId,
+    pub approver: Principal,
+    pub approved_at: i64,
+}
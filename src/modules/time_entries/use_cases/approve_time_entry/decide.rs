@@ -0,0 +1,163 @@
+use crate::modules::time_entries::core::events::TimeEntryEvent;
+use crate::modules::time_entries::core::events::v1::time_entry_approved::TimeEntryApprovedV1;
+use crate::modules::time_entries::core::evolve::evolve;
+use crate::modules::time_entries::core::intents::TimeEntryIntent;
+use crate::modules::time_entries::core::state::TimeEntryState;
+use crate::modules::time_entries::use_cases::approve_time_entry::command::ApproveTimeEntry;
+use crate::modules::time_entries::use_cases::approve_time_entry::decision::{
+    DecideError, Decision,
+};
+use crate::shared::core::decider::Decider;
+
+pub fn decide_approve_time_entry(state: &TimeEntryState, command: ApproveTimeEntry) -> Decision {
+    let rejected = |reason| Decision::Rejected { reason };
+    let user_id = match state {
+        TimeEntryState::None | TimeEntryState::Deleted { .. } => {
+            return rejected(DecideError::NotFound);
+        }
+        TimeEntryState::Draft { user_id, .. }
+        | TimeEntryState::Registered { user_id, .. }
+        | TimeEntryState::Approved { user_id, .. } => user_id,
+    };
+    // Authorization comes first so callers without rights learn nothing about the entry.
+    if !command.approver.can_approve(user_id.as_str()) {
+        return rejected(DecideError::Forbidden);
+    }
+
+    match state {
+        TimeEntryState::Registered { .. } => Decision::Accepted {
+            events: vec![TimeEntryEvent::TimeEntryApprovedV1(TimeEntryApprovedV1 {
+                time_entry_id: command.time_entry_id,
+                approved_at: command.approved_at,
+                approved_by: command.approver.user_id.into(),
+            })],
+            intents: vec![],
+        },
+        TimeEntryState::Approved { .. } => rejected(DecideError::AlreadyApproved),
+        _ => rejected(DecideError::NotRegistered),
+    }
+}
+
+pub struct ApproveTimeEntryDecider;
+
+impl Decider for ApproveTimeEntryDecider {
+    type State = TimeEntryState;
+    type Command = ApproveTimeEntry;
+    type Event = TimeEntryEvent;
+    type Intent = TimeEntryIntent;
+    type Error = DecideError;
+
+    fn initial_state() -> TimeEntryState {
+        TimeEntryState::None
+    }
+
+    fn evolve(state: TimeEntryState, event: TimeEntryEvent) -> TimeEntryState {
+        evolve(state, event)
+    }
+
+    fn decide(state: &TimeEntryState, command: ApproveTimeEntry) -> Decision {
+        decide_approve_time_entry(state, command)
+    }
+}
+
+#[cfg(test)]
+mod decide_approve_time_entry_tests {
+    use super::*;
+    use crate::modules::time_entries::core::time_interval::TimeInterval;
+    use crate::shared::auth::rbac::{Principal, Role, Scope};
+    use crate::tests::fixtures::commands::approve_time_entry::ApproveTimeEntryBuilder;
+    use rstest::{fixture, rstest};
+
+    #[fixture]
+    fn command() -> ApproveTimeEntry {
+        ApproveTimeEntryBuilder::new().build()
+    }
+
+    fn draft() -> TimeEntryState {
+        TimeEntryState::Draft {
+            time_entry_id: "te-fixed-0001".into(),
+            user_id: "user-fixed-0001".into(),
+            started_at: Some(1_000),
+            ended_at: None,
+            tag_ids: vec![],
+            created_at: 0,
+            created_by: "user-fixed-0001".into(),
+            hourly_rate: None,
+        }
+    }
+
+    fn registered() -> TimeEntryState {
+        TimeEntryState::Registered {
+            time_entry_id: "te-fixed-0001".into(),
+            user_id: "user-fixed-0001".into(),
+            interval: TimeInterval::new(1_000, 2_000).unwrap(),
+            tag_ids: vec![],
+            created_at: 0,
+            created_by: "user-fixed-0001".into(),
+            hourly_rate: None,
+            breaks: vec![],
+        }
+    }
+
+    fn approved() -> TimeEntryState {
+        TimeEntryState::Approved {
+            time_entry_id: "te-fixed-0001".into(),
+            user_id: "user-fixed-0001".into(),
+            interval: TimeInterval::new(1_000, 2_000).unwrap(),
+            tag_ids: vec![],
+            created_at: 0,
+            created_by: "user-fixed-0001".into(),
+            hourly_rate: None,
+            approved_at: 3_000,
+            approved_by: "manager-fixed-0001".into(),
+            breaks: vec![],
+        }
+    }
+
+    #[rstest]
+    fn it_should_approve_a_registered_entry(command: ApproveTimeEntry) {
+        match decide_approve_time_entry(&registered(), command) {
+            Decision::Accepted { events, intents } => {
+                assert_eq!(
+                    events,
+                    vec![TimeEntryEvent::TimeEntryApprovedV1(TimeEntryApprovedV1 {
+                        time_entry_id: "te-fixed-0001".into(),
+                        approved_at: 1_700_000_000_000,
+                        approved_by: "manager-fixed-0001".into(),
+                    })]
+                );
+                assert!(intents.is_empty());
+            }
+            Decision::Rejected { .. } => panic!("expected Accepted"),
+        }
+    }
+
+    #[rstest]
+    #[case::missing(TimeEntryState::None, DecideError::NotFound)]
+    #[case::draft(draft(), DecideError::NotRegistered)]
+    #[case::approved(approved(), DecideError::AlreadyApproved)]
+    fn it_should_reject_entries_that_cannot_be_approved(
+        command: ApproveTimeEntry,
+        #[case] state: TimeEntryState,
+        #[case] expected: DecideError,
+    ) {
+        match decide_approve_time_entry(&state, command) {
+            Decision::Rejected { reason } => assert_eq!(reason, expected),
+            Decision::Accepted { .. } => panic!("expected Rejected"),
+        }
+    }
+
+    #[rstest]
+    #[case::own_entry(Principal::new("user-fixed-0001", Role::Manager))]
+    #[case::employee(Principal::new("employee-0001", Role::Employee))]
+    #[case::read_only(Principal::new("manager-fixed-0001", Role::Manager).with_scope(Scope::ReadOnly))]
+    fn it_should_reject_approvers_without_rights(#[case] approver: Principal) {
+        let command = ApproveTimeEntryBuilder::new().approver(approver).build();
+        for state in [draft(), registered(), approved()] {
+            match decide_approve_time_entry(&state, command.clone()) {
+                Decision::Rejected { reason } => assert_eq!(reason, DecideError::Forbidden),
+                Decision::Accepted { .. } => panic!("expected Rejected"),
+            }
+        }
+    }
+}
@@ -0,0 +1,21 @@
+use crate::modules::time_entries::core::events::TimeEntryEvent;
+use crate::modules::time_entries::core::intents::TimeEntryIntent;
+use crate::shared::core::decider;
+use thiserror::Error;
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum DecideError {
+    #[error("time entry not found")]
+    NotFound,
+
+    #[error("approver may not approve this time entry")]
+    Forbidden,
+
+    #[error("time entry is not registered yet")]
+    NotRegistered,
+
+    #[error("time entry is already approved")]
+    AlreadyApproved,
+}
+
+pub type Decision = decider::Decision<TimeEntryEvent, TimeEntryIntent, DecideError>;
@@ -0,0 +1,181 @@
+use crate::modules::time_entries::adapters::outbound::intent_outbox::TimeEntryIntentDispatcher;
+use crate::modules::time_entries::core::events::TimeEntryEvent;
+use crate::modules::time_entries::use_cases::approve_time_entry::command::ApproveTimeEntry;
+use crate::modules::time_entries::use_cases::approve_time_entry::decide::ApproveTimeEntryDecider;
+use crate::modules::time_entries::use_cases::approve_time_entry::decision::DecideError;
+use crate::shared::application::command_bus::CommandHandler;
+use crate::shared::application::event_sourced_handler::{EventSourcedError, EventSourcedHandler};
+use crate::shared::infrastructure::event_bus::SharedEventBus;
+use crate::shared::infrastructure::event_store::EventStore;
+use crate::shared::infrastructure::intent_outbox::{DomainOutbox, OutboxError};
+use async_trait::async_trait;
+
+pub type ApplicationError = EventSourcedError<DecideError, OutboxError>;
+
+#[derive(Debug, Clone)]
+pub struct ApproveTimeEntryHandler<TEventStore, TOutbox>
+where
+    TEventStore: EventStore<TimeEntryEvent> + Send + Sync + 'static,
+    TOutbox: DomainOutbox + Send + Sync + 'static,
+{
+    inner: EventSourcedHandler<
+        ApproveTimeEntryDecider,
+        TEventStore,
+        TimeEntryIntentDispatcher<TOutbox>,
+    >,
+}
+
+impl<TEventStore, TOutbox> ApproveTimeEntryHandler<TEventStore, TOutbox>
+where
+    TEventStore: EventStore<TimeEntryEvent> + Send + Sync + 'static,
+    TOutbox: DomainOutbox + Send + Sync + 'static,
+{
+    pub fn new(event_store: TEventStore, outbox: TOutbox) -> Self {
+        Self {
+            inner: EventSourcedHandler::new(event_store, TimeEntryIntentDispatcher::new(outbox)),
+        }
+    }
+
+    /// Announce the appended events on `event_bus`, for the projectors to pick up.
+    pub fn with_event_bus(mut self, event_bus: SharedEventBus<TimeEntryEvent>) -> Self {
+        self.inner = self.inner.with_event_bus(event_bus);
+        self
+    }
+
+    pub async fn handle(
+        &self,
+        stream_id: &str,
+        command: ApproveTimeEntry,
+    ) -> Result<(), ApplicationError> {
+        self.inner.handle(stream_id, command).await
+    }
+}
+
+#[async_trait]
+impl<TEventStore, TOutbox> CommandHandler<ApproveTimeEntry>
+    for ApproveTimeEntryHandler<TEventStore, TOutbox>
+where
+    TEventStore: EventStore<TimeEntryEvent> + Send + Sync + 'static,
+    TOutbox: DomainOutbox + Send + Sync + 'static,
+{
+    type Error = ApplicationError;
+
+    async fn handle(
+        &self,
+        stream_id: &str,
+        command: ApproveTimeEntry,
+    ) -> Result<(), ApplicationError> {
+        ApproveTimeEntryHandler::handle(self, stream_id, command).await
+    }
+}
+
+#[cfg(test)]
+mod approve_time_entry_handler_tests {
+    use super::*;
+    use crate::modules::time_entries::use_cases::set_ended_at::handler::SetEndedAtHandler;
+    use crate::modules::time_entries::use_cases::set_started_at::handler::SetStartedAtHandler;
+    use crate::shared::application::command_bus::{CommandBus, CommandBusError, CommandEnvelope};
+    use crate::shared::infrastructure::event_store::EventStoreError;
+    use crate::shared::infrastructure::event_store::in_memory::InMemoryEventStore;
+    use crate::shared::infrastructure::intent_outbox::in_memory::InMemoryDomainOutbox;
+    use crate::tests::fixtures::commands::approve_time_entry::ApproveTimeEntryBuilder;
+    use crate::tests::fixtures::commands::set_ended_at::SetEndedAtBuilder;
+    use crate::tests::fixtures::commands::set_started_at::SetStartedAtBuilder;
+    use rstest::{fixture, rstest};
+
+    const STREAM_ID: &str = "TimeEntry-te-fixed-0001";
+
+    type BeforeEachReturn = (InMemoryEventStore<TimeEntryEvent>, InMemoryDomainOutbox);
+
+    #[fixture]
+    fn before_each() -> BeforeEachReturn {
+        (
+            InMemoryEventStore::<TimeEntryEvent>::new(),
+            InMemoryDomainOutbox::new(),
+        )
+    }
+
+    async fn register(
+        event_store: &InMemoryEventStore<TimeEntryEvent>,
+        outbox: &InMemoryDomainOutbox,
+    ) {
+        SetStartedAtHandler::new(event_store.clone(), outbox.clone())
+            .handle(STREAM_ID, SetStartedAtBuilder::new().build())
+            .await
+            .unwrap();
+        SetEndedAtHandler::new(event_store.clone(), outbox.clone())
+            .handle(STREAM_ID, SetEndedAtBuilder::new().build())
+            .await
+            .unwrap();
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn handle_approve_time_entry_appends_approved_once(before_each: BeforeEachReturn) {
+        let (event_store, outbox) = before_each;
+        register(&event_store, &outbox).await;
+        let bus = CommandBus::new(ApproveTimeEntryHandler::new(event_store.clone(), outbox));
+
+        bus.dispatch(CommandEnvelope::new(
+            STREAM_ID,
+            ApproveTimeEntryBuilder::new().build(),
+        ))
+        .await
+        .expect("approve failed");
+        let again = bus
+            .dispatch(CommandEnvelope::new(
+                STREAM_ID,
+                ApproveTimeEntryBuilder::new().build(),
+            ))
+            .await;
+
+        assert!(matches!(
+            again,
+            Err(CommandBusError::Handler(ApplicationError::Domain(
+                DecideError::AlreadyApproved
+            )))
+        ));
+        let stream = event_store.load(STREAM_ID).await.unwrap();
+        assert!(matches!(
+            stream.events.last(),
+            Some(TimeEntryEvent::TimeEntryApprovedV1(_))
+        ));
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn handle_approve_time_entry_rejects_a_missing_entry(before_each: BeforeEachReturn) {
+        let (event_store, outbox) = before_each;
+        let handler = ApproveTimeEntryHandler::new(event_store, outbox);
+
+        let result = handler
+            .handle(STREAM_ID, ApproveTimeEntryBuilder::new().build())
+            .await;
+
+        assert!(matches!(
+            result,
+            Err(ApplicationError::Domain(DecideError::NotFound))
+        ));
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn handle_approve_time_entry_fails_if_event_store_is_offline(
+        before_each: BeforeEachReturn,
+    ) {
+        let (event_store, outbox) = before_each;
+        event_store.toggle_offline();
+        let handler = ApproveTimeEntryHandler::new(event_store, outbox);
+
+        let result = handler
+            .handle(STREAM_ID, ApproveTimeEntryBuilder::new().build())
+            .await;
+
+        assert!(matches!(
+            result,
+            Err(ApplicationError::VersionConflict(EventStoreError::Backend(
+                _
+            )))
+        ));
+    }
+}
@@ -0,0 +1,307 @@
+use async_graphql::{Context, Enum, ID, Object, Result as GqlResult, SimpleObject};
+use chrono::Utc;
+use uuid::{Uuid, Version};
+
+use crate::modules::time_entries::use_cases::approve_time_entry::command::ApproveTimeEntry;
+use crate::modules::time_entries::use_cases::approve_time_entry::decision::DecideError;
+use crate::modules::time_entries::use_cases::approve_time_entry::handler::ApplicationError;
+use crate::shared::infrastructure::request_context::RequestContext;
+use crate::shell::state::AppState;
+
+#[derive(Debug, Enum, Copy, Clone, Eq, PartialEq)]
+pub enum GqlApprovalStatus {
+    Approved,
+    AlreadyApproved,
+    NotFound,
+    Forbidden,
+    NotRegistered,
+    /// The entry could not be processed, e.g. the event store was unavailable; safe to retry.
+    Failed,
+}
+
+#[derive(SimpleObject, Clone)]
+#[graphql(name = "ApprovalResult")]
+pub struct GqlApprovalResult {
+    pub id: ID,
+    pub status: GqlApprovalStatus,
+    pub message: Option<String>,
+}
+
+impl GqlApprovalResult {
+    fn new(id: ID, status: GqlApprovalStatus) -> Self {
+        Self {
+            id,
+            status,
+            message: None,
+        }
+    }
+}
+
+impl From<DecideError> for GqlApprovalStatus {
+    fn from(reason: DecideError) -> Self {
+        match reason {
+            DecideError::NotFound => GqlApprovalStatus::NotFound,
+            DecideError::Forbidden => GqlApprovalStatus::Forbidden,
+            DecideError::NotRegistered => GqlApprovalStatus::NotRegistered,
+            DecideError::AlreadyApproved => GqlApprovalStatus::AlreadyApproved,
+        }
+    }
+}
+
+#[cfg(test)]
+mod approve_time_entries_graphql_inbound_tests {
+    use async_graphql::{EmptySubscription, Schema};
+
+    use crate::modules::time_entries::use_cases::set_ended_at::command::SetEndedAt;
+    use crate::modules::time_entries::use_cases::set_started_at::command::SetStartedAt;
+    use crate::shared::auth::rbac::{Role, Scope};
+    use crate::shared::core::stream_naming::{DefaultStreamNaming, StreamNaming};
+    use crate::shared::infrastructure::request_context::RequestContext;
+    use crate::shell::graphql::{MutationRoot, QueryRoot};
+    use crate::shell::state::AppState;
+    use crate::tests::fixtures::tags::{make_test_app_state, make_test_app_state_with_store};
+
+    fn make_schema_from_state(
+        state: AppState,
+    ) -> Schema<QueryRoot, MutationRoot, EmptySubscription> {
+        Schema::build(
+            QueryRoot::default(),
+            MutationRoot::default(),
+            EmptySubscription,
+        )
+        .data(state)
+        .finish()
+    }
+
+    fn manager_ctx() -> RequestContext {
+        RequestContext {
+            user_id: "manager-1".to_string(),
+            tenant_id: "tenant-test".to_string(),
+            role: Role::Manager,
+            scope: Default::default(),
+        }
+    }
+
+    fn valid_v7_id() -> String {
+        uuid::Uuid::now_v7().to_string()
+    }
+
+    async fn set_interval(
+        state: &AppState,
+        user_id: &str,
+        started_at: i64,
+        ended_at: Option<i64>,
+    ) -> String {
+        let te_id = valid_v7_id();
+        let stream_id = DefaultStreamNaming.time_entry(None, &te_id);
+        state
+            .set_started_at_handler
+            .handle(
+                &stream_id,
+                SetStartedAt {
+                    time_entry_id: te_id.clone().into(),
+                    user_id: user_id.into(),
+                    started_at,
+                    updated_at: started_at,
+                    updated_by: user_id.into(),
+                    rounding: None,
+                },
+            )
+            .await
+            .unwrap();
+        if let Some(ended_at) = ended_at {
+            state
+                .set_ended_at_handler
+                .handle(
+                    &stream_id,
+                    SetEndedAt {
+                        time_entry_id: te_id.clone().into(),
+                        user_id: user_id.into(),
+                        ended_at,
+                        updated_at: ended_at,
+                        updated_by: user_id.into(),
+                        rounding: None,
+                    },
+                )
+                .await
+                .unwrap();
+        }
+        te_id
+    }
+
+    fn approve(ids: &[&str]) -> String {
+        let ids = ids
+            .iter()
+            .map(|id| format!(r#""{id}""#))
+            .collect::<Vec<_>>()
+            .join(", ");
+        format!("mutation {{ approveTimeEntries(ids: [{ids}]) {{ id status message }} }}")
+    }
+
+    #[tokio::test]
+    async fn reports_the_outcome_of_each_entry() {
+        let state = make_test_app_state();
+        let registered = set_interval(&state, "u-1", 1_000, Some(2_000)).await;
+        let draft = set_interval(&state, "u-1", 3_000, None).await;
+        let own = set_interval(&state, "manager-1", 5_000, Some(6_000)).await;
+        let missing = valid_v7_id();
+        let schema = make_schema_from_state(state);
+
+        let result = schema
+            .execute(
+                async_graphql::Request::new(approve(&[
+                    &registered,
+                    &registered,
+                    &draft,
+                    &own,
+                    &missing,
+                    "not-a-uuid",
+                ]))
+                .data(manager_ctx()),
+            )
+            .await;
+
+        assert!(result.errors.is_empty(), "{:?}", result.errors);
+        let data = result.data.into_json().unwrap();
+        let statuses: Vec<&str> = data["approveTimeEntries"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|item| item["status"].as_str().unwrap())
+            .collect();
+        assert_eq!(
+            statuses,
+            vec![
+                "APPROVED",
+                "ALREADY_APPROVED",
+                "NOT_REGISTERED",
+                "FORBIDDEN",
+                "NOT_FOUND",
+                "NOT_FOUND",
+            ]
+        );
+        assert_eq!(data["approveTimeEntries"][0]["id"], registered.as_str());
+    }
+
+    #[tokio::test]
+    async fn reports_every_entry_as_forbidden_for_employees() {
+        let state = make_test_app_state();
+        let registered = set_interval(&state, "u-1", 1_000, Some(2_000)).await;
+        let schema = make_schema_from_state(state);
+
+        let result = schema
+            .execute(
+                async_graphql::Request::new(approve(&[&registered])).data(RequestContext {
+                    user_id: "u-2".to_string(),
+                    role: Role::Employee,
+                    ..manager_ctx()
+                }),
+            )
+            .await;
+
+        let data = result.data.into_json().unwrap();
+        assert_eq!(data["approveTimeEntries"][0]["status"], "FORBIDDEN");
+    }
+
+    #[tokio::test]
+    async fn reports_every_entry_as_forbidden_for_read_only_api_keys() {
+        let state = make_test_app_state();
+        let registered = set_interval(&state, "u-1", 1_000, Some(2_000)).await;
+        let schema = make_schema_from_state(state);
+
+        let result = schema
+            .execute(
+                async_graphql::Request::new(approve(&[&registered])).data(RequestContext {
+                    scope: Scope::ReadOnly,
+                    ..manager_ctx()
+                }),
+            )
+            .await;
+
+        let data = result.data.into_json().unwrap();
+        assert_eq!(data["approveTimeEntries"][0]["status"], "FORBIDDEN");
+    }
+
+    #[tokio::test]
+    async fn reports_failed_entries_when_event_store_offline() {
+        let (state, event_store) = make_test_app_state_with_store();
+        let registered = set_interval(&state, "u-1", 1_000, Some(2_000)).await;
+        event_store.toggle_offline();
+        let schema = make_schema_from_state(state);
+
+        let result = schema
+            .execute(async_graphql::Request::new(approve(&[&registered])).data(manager_ctx()))
+            .await;
+
+        assert!(result.errors.is_empty());
+        let data = result.data.into_json().unwrap();
+        assert_eq!(data["approveTimeEntries"][0]["status"], "FAILED");
+        assert!(data["approveTimeEntries"][0]["message"].is_string());
+    }
+
+    #[tokio::test]
+    async fn returns_unauthorized_without_request_context() {
+        let schema = make_schema_from_state(make_test_app_state());
+
+        let result = schema
+            .execute(async_graphql::Request::new(approve(&[&valid_v7_id()])))
+            .await;
+
+        assert_eq!(result.errors[0].message, "Unauthorized");
+    }
+}
+
+#[derive(Default)]
+pub struct ApproveTimeEntriesMutation;
+
+#[Object]
+impl ApproveTimeEntriesMutation {
+    /// Approves each entry independently and reports a result per id, in request order.
+    async fn approve_time_entries(
+        &self,
+        context: &Context<'_>,
+        ids: Vec<ID>,
+    ) -> GqlResult<Vec<GqlApprovalResult>> {
+        let req_ctx = context
+            .data::<RequestContext>()
+            .map_err(|_| async_graphql::Error::new("Unauthorized"))?;
+        let state = context.data_unchecked::<AppState>();
+        let approver = req_ctx.principal();
+
+        let mut results = Vec::with_capacity(ids.len());
+        for id in ids {
+            let is_valid_v7 = Uuid::parse_str(&id)
+                .ok()
+                .filter(|u| u.get_version() == Some(Version::SortRand))
+                .is_some();
+            if !is_valid_v7 {
+                results.push(GqlApprovalResult::new(id, GqlApprovalStatus::NotFound));
+                continue;
+            }
+
+            let stream_id = state
+                .stream_naming
+                .time_entry(Some(&req_ctx.tenant_id), &id.as_str());
+            let command = ApproveTimeEntry {
+                time_entry_id: id.0.clone().into(),
+                approver: approver.clone(),
+                approved_at: Utc::now().timestamp_millis(),
+            };
+            let result = match state
+                .approve_time_entry_handler
+                .handle(&stream_id, command)
+                .await
+            {
+                Ok(()) => GqlApprovalResult::new(id, GqlApprovalStatus::Approved),
+                Err(ApplicationError::Domain(reason)) => GqlApprovalResult::new(id, reason.into()),
+                Err(error) => GqlApprovalResult {
+                    id,
+                    status: GqlApprovalStatus::Failed,
+                    message: Some(error.to_string()),
+                },
+            };
+            results.push(result);
+        }
+        Ok(results)
+    }
+}
@@ -0,0 +1,468 @@
+// Moves finished time entries out of the list projection into cold storage.
+//
+// Rows whose `ended_at` is older than the retention period are appended as JSONL to one
+// object per user, then removed from the projection. Cold storage is written first, so a
+// failed projection save leaves the rows in place and the next run archives them again;
+// restore takes the latest archived copy of an entry.
+//
+// The projector keeps applying events meanwhile, so the rows are removed with a save that
+// only lands while the projection's checkpoint is the one read; a row the projector changed
+// since stays hot until the next run. Each archived entry leaves a marker with the version
+// it was archived at, which replays honour: a rebuild does not bring it back, and an event
+// past that version makes it hot again.
+
+use crate::modules::time_entries::use_cases::list_time_entries::projection::{
+    ListTimeEntriesState, TimeEntryRow,
+};
+use crate::modules::time_entries::use_cases::list_time_entries::queries::ListTimeEntriesCache;
+use crate::shared::core::primitives::last_event_version;
+use crate::shared::infrastructure::cold_storage::ColdStorage;
+use crate::shared::infrastructure::projection_store::ProjectionStore;
+use std::collections::BTreeMap;
+
+/// Conditional saves tried before a run gives up on a projection that keeps moving.
+const SAVE_ATTEMPTS: usize = 3;
+
+pub fn archive_key(user_id: &str) -> String {
+    format!("time_entries/{user_id}.jsonl")
+}
+
+pub struct TimeEntryArchiver<TStore, TColdStorage>
+where
+    TStore: ProjectionStore<ListTimeEntriesState> + Send + Sync + 'static,
+    TColdStorage: ColdStorage + 'static,
+{
+    store: TStore,
+    cold_storage: TColdStorage,
+    retention_ms: i64,
+    query_cache: Option<ListTimeEntriesCache>,
+}
+
+impl<TStore, TColdStorage> TimeEntryArchiver<TStore, TColdStorage>
+where
+    TStore: ProjectionStore<ListTimeEntriesState> + Send + Sync + 'static,
+    TColdStorage: ColdStorage + 'static,
+{
+    pub fn new(store: TStore, cold_storage: TColdStorage, retention_ms: i64) -> Self {
+        Self {
+            store,
+            cold_storage,
+            retention_ms,
+            query_cache: None,
+        }
+    }
+
+    pub fn with_query_cache(mut self, query_cache: ListTimeEntriesCache) -> Self {
+        self.query_cache = Some(query_cache);
+        self
+    }
+
+    /// Archives every entry that ended before `now - retention`. Returns how many were moved.
+    pub async fn archive(&self, now: i64) -> anyhow::Result<u64> {
+        let cutoff = now - self.retention_ms;
+        let (mut checkpoint, mut state) = self.snapshot().await?;
+
+        let mut by_user: BTreeMap<String, Vec<TimeEntryRow>> = BTreeMap::new();
+        for row in state.rows.values() {
+            if row.ended_at.is_some_and(|ended_at| ended_at < cutoff) {
+                by_user
+                    .entry(row.user_id.clone())
+                    .or_default()
+                    .push(row.clone());
+            }
+        }
+        if by_user.is_empty() {
+            return Ok(0);
+        }
+
+        for (user_id, rows) in &mut by_user {
+            rows.sort_by(|a, b| a.time_entry_id.cmp(&b.time_entry_id));
+            let lines = rows
+                .iter()
+                .map(|row| serde_json::to_string(row).expect("time entry rows serialize to JSON"))
+                .collect();
+            self.cold_storage
+                .append_lines(&archive_key(user_id), lines)
+                .await?;
+        }
+
+        for _ in 0..SAVE_ATTEMPTS {
+            let mut archived = 0;
+            for row in by_user.values().flatten() {
+                if state.rows.get(&row.time_entry_id) != Some(row) {
+                    continue;
+                }
+                state.rows.remove(&row.time_entry_id);
+                let version = last_event_version(row.last_event_id.as_deref()).unwrap_or(0);
+                state.archived.insert(row.time_entry_id.clone(), version);
+                archived += 1;
+            }
+            if self.store.save_if_unchanged(state, checkpoint).await? {
+                self.invalidate(by_user.keys()).await;
+                return Ok(archived);
+            }
+            (checkpoint, state) = self.snapshot().await?;
+        }
+        anyhow::bail!("the list projection kept changing while entries were archived")
+    }
+
+    /// Moves an archived entry back into the projection. Returns `false` when it is not
+    /// archived from this projection.
+    pub async fn restore(&self, user_id: &str, time_entry_id: &str) -> anyhow::Result<bool> {
+        let (mut checkpoint, mut state) = self.snapshot().await?;
+        if !state.archived.contains_key(time_entry_id) {
+            return Ok(false);
+        }
+
+        let key = archive_key(user_id);
+        let mut restored = None;
+        let mut remaining = Vec::new();
+        for line in self.cold_storage.read_lines(&key).await? {
+            let row: TimeEntryRow = serde_json::from_str(&line)?;
+            if row.time_entry_id == time_entry_id {
+                restored = Some(row);
+            } else {
+                remaining.push(line);
+            }
+        }
+        let Some(row) = restored else {
+            return Ok(false);
+        };
+
+        for _ in 0..SAVE_ATTEMPTS {
+            state.archived.remove(time_entry_id);
+            state.upsert(row.clone());
+            if self.store.save_if_unchanged(state, checkpoint).await? {
+                self.cold_storage.write_lines(&key, remaining).await?;
+                self.invalidate([&user_id.to_string()]).await;
+                return Ok(true);
+            }
+            (checkpoint, state) = self.snapshot().await?;
+        }
+        anyhow::bail!("the list projection kept changing while {time_entry_id} was restored")
+    }
+
+    /// The projection with the checkpoint it was read at. The checkpoint is read first, so a
+    /// projector save in between fails the conditional save that follows.
+    async fn snapshot(&self) -> anyhow::Result<(u64, ListTimeEntriesState)> {
+        let checkpoint = self.store.checkpoint().await?;
+        let state = self.store.state().await?.unwrap_or_default();
+        Ok((checkpoint, state))
+    }
+
+    async fn invalidate<'a>(&self, user_ids: impl IntoIterator<Item = &'a String>) {
+        if let Some(query_cache) = &self.query_cache {
+            for user_id in user_ids {
+                query_cache.invalidate(user_id).await;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod time_entry_archiver_tests {
+    use super::*;
+    use crate::modules::time_entries::use_cases::list_time_entries::projection::TimeEntryStatus;
+    use crate::modules::time_entries::use_cases::list_time_entries::queries::Page;
+    use crate::shared::infrastructure::cold_storage::in_memory::InMemoryColdStorage;
+    use crate::shared::infrastructure::projection_store::in_memory::InMemoryProjectionStore;
+    use crate::shared::infrastructure::query_cache::QueryCache;
+    use crate::shared::infrastructure::query_cache::in_memory::InMemoryQueryCache;
+    use rstest::rstest;
+    use std::sync::Arc;
+
+    const RETENTION_MS: i64 = 1_000;
+
+    fn make_row(user_id: &str, te_id: &str, ended_at: Option<i64>) -> TimeEntryRow {
+        TimeEntryRow {
+            time_entry_id: te_id.to_string(),
+            user_id: user_id.to_string(),
+            started_at: ended_at.map(|e| e - 100),
+            ended_at,
+            tag_ids: vec![],
+            status: if ended_at.is_some() {
+                TimeEntryStatus::Registered
+            } else {
+                TimeEntryStatus::Draft
+            },
+            created_at: 0,
+            created_by: user_id.to_string(),
+            updated_at: 0,
+            updated_by: user_id.to_string(),
+            deleted_at: None,
+            hourly_rate: None,
+            last_event_id: Some(format!("TimeEntry-{te_id}:2")),
+            breaks: vec![],
+        }
+    }
+
+    async fn store_with_rows(
+        rows: Vec<TimeEntryRow>,
+    ) -> InMemoryProjectionStore<ListTimeEntriesState> {
+        let store = InMemoryProjectionStore::<ListTimeEntriesState>::new();
+        let mut state = ListTimeEntriesState::default();
+        for row in rows {
+            state.rows.insert(row.time_entry_id.clone(), row);
+        }
+        store.save(state, 7).await.unwrap();
+        store
+    }
+
+    async fn row_ids(store: &InMemoryProjectionStore<ListTimeEntriesState>) -> Vec<String> {
+        let mut ids: Vec<_> = store
+            .state()
+            .await
+            .unwrap()
+            .unwrap()
+            .rows
+            .into_keys()
+            .collect();
+        ids.sort();
+        ids
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_move_entries_past_retention_to_cold_storage() {
+        let store = store_with_rows(vec![
+            make_row("u1", "te-old", Some(100)),
+            make_row("u1", "te-recent", Some(4_500)),
+            make_row("u1", "te-draft", None),
+            make_row("u2", "te-old-2", Some(200)),
+        ])
+        .await;
+        let cold_storage = InMemoryColdStorage::new();
+        let archiver = TimeEntryArchiver::new(store.clone(), cold_storage.clone(), RETENTION_MS);
+
+        let archived = archiver.archive(5_000).await.unwrap();
+
+        assert_eq!(archived, 2);
+        assert_eq!(row_ids(&store).await, vec!["te-draft", "te-recent"]);
+        assert_eq!(store.checkpoint().await.unwrap(), 7);
+        assert_eq!(
+            cold_storage.keys().await,
+            vec!["time_entries/u1.jsonl", "time_entries/u2.jsonl"]
+        );
+        let lines = cold_storage
+            .read_lines("time_entries/u1.jsonl")
+            .await
+            .unwrap();
+        let archived_row: TimeEntryRow = serde_json::from_str(&lines[0]).unwrap();
+        assert_eq!(archived_row, make_row("u1", "te-old", Some(100)));
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_do_nothing_when_no_entry_is_past_retention() {
+        let store = store_with_rows(vec![make_row("u1", "te-recent", Some(4_500))]).await;
+        let cold_storage = InMemoryColdStorage::new();
+        let archiver = TimeEntryArchiver::new(store.clone(), cold_storage.clone(), RETENTION_MS);
+
+        assert_eq!(archiver.archive(5_000).await.unwrap(), 0);
+        assert_eq!(row_ids(&store).await, vec!["te-recent"]);
+        assert!(cold_storage.keys().await.is_empty());
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_keep_rows_when_cold_storage_is_offline() {
+        let store = store_with_rows(vec![make_row("u1", "te-old", Some(100))]).await;
+        let cold_storage = InMemoryColdStorage::new();
+        cold_storage.toggle_offline();
+        let archiver = TimeEntryArchiver::new(store.clone(), cold_storage, RETENTION_MS);
+
+        assert!(archiver.archive(5_000).await.is_err());
+        assert_eq!(row_ids(&store).await, vec!["te-old"]);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_propagate_projection_store_errors() {
+        let mut store = InMemoryProjectionStore::<ListTimeEntriesState>::new();
+        store.toggle_offline();
+        let archiver = TimeEntryArchiver::new(store, InMemoryColdStorage::new(), RETENTION_MS);
+
+        assert!(archiver.archive(5_000).await.is_err());
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_keep_the_archived_copy_when_restore_cannot_save() {
+        let store = store_with_rows(vec![make_row("u1", "te-old", Some(100))]).await;
+        let cold_storage = InMemoryColdStorage::new();
+        let archiver = TimeEntryArchiver::new(store.clone(), cold_storage.clone(), RETENTION_MS);
+        archiver.archive(5_000).await.unwrap();
+        store.set_fail_next_save();
+
+        assert!(archiver.restore("u1", "te-old").await.is_err());
+
+        assert!(row_ids(&store).await.is_empty());
+        assert_eq!(
+            cold_storage
+                .read_lines("time_entries/u1.jsonl")
+                .await
+                .unwrap()
+                .len(),
+            1
+        );
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_restore_an_archived_entry() {
+        let store = store_with_rows(vec![
+            make_row("u1", "te-old", Some(100)),
+            make_row("u1", "te-older", Some(50)),
+        ])
+        .await;
+        let cold_storage = InMemoryColdStorage::new();
+        let archiver = TimeEntryArchiver::new(store.clone(), cold_storage.clone(), RETENTION_MS);
+        archiver.archive(5_000).await.unwrap();
+
+        assert!(archiver.restore("u1", "te-old").await.unwrap());
+
+        assert_eq!(row_ids(&store).await, vec!["te-old"]);
+        assert_eq!(
+            store.state().await.unwrap().unwrap().rows["te-old"],
+            make_row("u1", "te-old", Some(100))
+        );
+        let lines = cold_storage
+            .read_lines("time_entries/u1.jsonl")
+            .await
+            .unwrap();
+        assert_eq!(lines.len(), 1);
+        assert!(lines[0].contains("te-older"));
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_report_entries_that_are_not_archived() {
+        let store = store_with_rows(vec![]).await;
+        let archiver = TimeEntryArchiver::new(store, InMemoryColdStorage::new(), RETENTION_MS);
+
+        assert!(!archiver.restore("u1", "te-unknown").await.unwrap());
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_fail_restore_on_corrupt_archive_lines() {
+        let store = store_with_rows(vec![make_row("u1", "te-old", Some(100))]).await;
+        let cold_storage = InMemoryColdStorage::new();
+        let archiver = TimeEntryArchiver::new(store, cold_storage.clone(), RETENTION_MS);
+        archiver.archive(5_000).await.unwrap();
+        cold_storage
+            .append_lines(&archive_key("u1"), vec!["not json".to_string()])
+            .await
+            .unwrap();
+
+        assert!(archiver.restore("u1", "te-old").await.is_err());
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_leave_a_marker_with_the_archived_version() {
+        let store = store_with_rows(vec![make_row("u1", "te-old", Some(100))]).await;
+        let archiver =
+            TimeEntryArchiver::new(store.clone(), InMemoryColdStorage::new(), RETENTION_MS);
+
+        archiver.archive(5_000).await.unwrap();
+        let state = store.state().await.unwrap().unwrap();
+        assert_eq!(state.archived.get("te-old"), Some(&2));
+
+        archiver.restore("u1", "te-old").await.unwrap();
+        let state = store.state().await.unwrap().unwrap();
+        assert!(state.archived.is_empty());
+    }
+
+    /// Lets the projector apply an event between the archiver's read and its save, once.
+    #[derive(Clone)]
+    struct RacingProjector {
+        inner: InMemoryProjectionStore<ListTimeEntriesState>,
+        raced: Arc<std::sync::atomic::AtomicBool>,
+    }
+
+    #[async_trait::async_trait]
+    impl ProjectionStore<ListTimeEntriesState> for RacingProjector {
+        async fn state(&self) -> anyhow::Result<Option<ListTimeEntriesState>> {
+            self.inner.state().await
+        }
+
+        async fn checkpoint(&self) -> anyhow::Result<u64> {
+            self.inner.checkpoint().await
+        }
+
+        async fn schema_version(&self) -> anyhow::Result<Option<u32>> {
+            self.inner.schema_version().await
+        }
+
+        async fn save(&self, state: ListTimeEntriesState, checkpoint: u64) -> anyhow::Result<()> {
+            self.inner.save(state, checkpoint).await
+        }
+
+        async fn save_if_unchanged(
+            &self,
+            state: ListTimeEntriesState,
+            checkpoint: u64,
+        ) -> anyhow::Result<bool> {
+            if !self.raced.swap(true, std::sync::atomic::Ordering::SeqCst) {
+                let mut applied = self.inner.state().await?.unwrap_or_default();
+                let row = applied.rows.get_mut("te-edited").unwrap();
+                row.tag_ids = vec!["late".parse().unwrap()];
+                row.last_event_id = Some("TimeEntry-te-edited:3".to_string());
+                self.inner.save(applied, checkpoint + 1).await?;
+            }
+            self.inner.save_if_unchanged(state, checkpoint).await
+        }
+
+        async fn save_schema_version(&self, version: u32) -> anyhow::Result<()> {
+            self.inner.save_schema_version(version).await
+        }
+
+        async fn clear(&self) -> anyhow::Result<()> {
+            self.inner.clear().await
+        }
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_not_overwrite_events_the_projector_applied_meanwhile() {
+        let inner = store_with_rows(vec![
+            make_row("u1", "te-old", Some(100)),
+            make_row("u1", "te-edited", Some(100)),
+        ])
+        .await;
+        let store = RacingProjector {
+            inner: inner.clone(),
+            raced: Arc::default(),
+        };
+        let archiver = TimeEntryArchiver::new(store, InMemoryColdStorage::new(), RETENTION_MS);
+
+        assert_eq!(archiver.archive(5_000).await.unwrap(), 1);
+
+        let state = inner.state().await.unwrap().unwrap();
+        assert_eq!(inner.checkpoint().await.unwrap(), 8);
+        assert_eq!(state.rows.keys().collect::<Vec<_>>(), vec!["te-edited"]);
+        assert_eq!(state.rows["te-edited"].tag_ids.len(), 1);
+        assert_eq!(state.archived.keys().collect::<Vec<_>>(), vec!["te-old"]);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_invalidate_cached_queries_for_affected_users() {
+        let store = store_with_rows(vec![make_row("u1", "te-old", Some(100))]).await;
+        let cache = InMemoryQueryCache::new();
+        let archiver = TimeEntryArchiver::new(store, InMemoryColdStorage::new(), RETENTION_MS)
+            .with_query_cache(Arc::new(cache.clone()));
+        cache.put("u1", "page", 0, Page::default()).await;
+        cache.put("u2", "page", 0, Page::default()).await;
+
+        archiver.archive(5_000).await.unwrap();
+        assert_eq!(cache.get("u1", "page").await, None);
+        assert_eq!(cache.get("u2", "page").await, Some(Page::default()));
+
+        let generation = cache.generation("u1").await;
+        cache.put("u1", "page", generation, Page::default()).await;
+        archiver.restore("u1", "te-old").await.unwrap();
+        assert_eq!(cache.get("u1", "page").await, None);
+    }
+}
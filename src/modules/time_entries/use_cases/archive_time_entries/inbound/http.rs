@@ -0,0 +1,226 @@
+use axum::{
+    Json,
+    extract::{Path, State, rejection::JsonRejection},
+    http::StatusCode,
+    response::IntoResponse,
+};
+use chrono::Utc;
+
+use crate::modules::time_entries::use_cases::archive_time_entries::archiver::TimeEntryArchiver;
+use crate::modules::time_entries::use_cases::archive_time_entries::job::{
+    ArchivePayload, JOB_KIND,
+};
+use crate::shared::infrastructure::job_store::Job;
+use crate::shared::infrastructure::request_context::RequestContext;
+use crate::shell::jobs;
+use crate::shell::state::AppState;
+
+/// POST /admin/time-entries/archive — starts a job moving entries that ended more than
+/// `retention_days` ago to cold storage; poll the `Location` it answers with. Admins only.
+pub async fn handle(
+    State(state): State<AppState>,
+    request_ctx: RequestContext,
+    body: Result<Json<ArchivePayload>, JsonRejection>,
+) -> impl IntoResponse {
+    if !request_ctx.principal().can_administer() {
+        return StatusCode::FORBIDDEN.into_response();
+    }
+    let Ok(Json(payload)) = body else {
+        return StatusCode::UNPROCESSABLE_ENTITY.into_response();
+    };
+    let payload = serde_json::to_value(payload).expect("archive payloads serialize to JSON");
+    let job = Job::queued(JOB_KIND, payload, Utc::now().timestamp_millis());
+    jobs::accept(&state, job).await
+}
+
+/// POST /admin/users/{user_id}/time-entries/{time_entry_id}/restore — moves an archived entry
+/// back into the list. `204 No Content` once restored, `404` when the entry is not archived.
+/// Admins only.
+pub async fn handle_restore(
+    State(state): State<AppState>,
+    request_ctx: RequestContext,
+    Path((user_id, time_entry_id)): Path<(String, String)>,
+) -> impl IntoResponse {
+    if !request_ctx.principal().can_administer() {
+        return StatusCode::FORBIDDEN;
+    }
+    let handler = &state.list_time_entries_handler;
+    // Only the partition the entry was archived from holds its marker.
+    for (_, partition_store) in handler.store().partitions() {
+        let mut archiver = TimeEntryArchiver::new(partition_store, state.cold_storage.clone(), 0);
+        if let Some(query_cache) = handler.cache() {
+            archiver = archiver.with_query_cache(query_cache.clone());
+        }
+        match archiver.restore(&user_id, &time_entry_id).await {
+            Ok(true) => {
+                tracing::info!(user_id, time_entry_id, "archived time entry restored");
+                return StatusCode::NO_CONTENT;
+            }
+            Ok(false) => {}
+            Err(_) => return StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+    StatusCode::NOT_FOUND
+}
+
+#[cfg(test)]
+mod archive_time_entries_http_inbound_tests {
+    use super::*;
+    use crate::modules::time_entries::use_cases::list_time_entries::projection::{
+        ListTimeEntriesState, TimeEntryRow, TimeEntryStatus,
+    };
+    use crate::shared::infrastructure::job_store::{JobStatus, JobStore};
+    use crate::shared::infrastructure::projection_store::ProjectionStore;
+    use crate::tests::fixtures::tags::make_test_app_state;
+    use axum::{
+        Router,
+        body::Body,
+        http::{Request, header},
+        routing::post,
+    };
+    use rstest::rstest;
+    use tower::ServiceExt;
+
+    fn request(role: &str, body: &str) -> Request<Body> {
+        Request::post("/admin/time-entries/archive")
+            .header("x-user-id", "admin-1")
+            .header("x-tenant-id", "tenant-test")
+            .header("x-user-role", role)
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(Body::from(body.to_string()))
+            .unwrap()
+    }
+
+    fn app(state: AppState) -> Router {
+        Router::new()
+            .route("/admin/time-entries/archive", post(handle))
+            .with_state(state)
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_queue_an_archive_job() {
+        let state = make_test_app_state();
+
+        let response = app(state.clone())
+            .oneshot(request("admin", r#"{"retention_days":90}"#))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::ACCEPTED);
+        let location = response.headers()[header::LOCATION].to_str().unwrap();
+        let job_id = location.trim_start_matches("/admin/jobs/");
+        let job = state.job_store.get(job_id).await.unwrap().unwrap();
+        assert_eq!(job.kind, JOB_KIND);
+        assert_eq!(job.status, JobStatus::Queued);
+        assert_eq!(job.payload, serde_json::json!({ "retention_days": 90 }));
+    }
+
+    #[rstest]
+    #[case::employee("employee", r#"{"retention_days":90}"#, StatusCode::FORBIDDEN)]
+    #[case::missing_retention("admin", "{}", StatusCode::UNPROCESSABLE_ENTITY)]
+    #[tokio::test]
+    async fn it_should_reject_requests_it_cannot_queue(
+        #[case] role: &str,
+        #[case] body: &str,
+        #[case] expected: StatusCode,
+    ) {
+        let response = app(make_test_app_state())
+            .oneshot(request(role, body))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), expected);
+    }
+
+    fn restore_request(role: &str, time_entry_id: &str) -> Request<Body> {
+        Request::post(format!(
+            "/admin/users/u1/time-entries/{time_entry_id}/restore"
+        ))
+        .header("x-user-id", "admin-1")
+        .header("x-tenant-id", "tenant-test")
+        .header("x-user-role", role)
+        .body(Body::empty())
+        .unwrap()
+    }
+
+    fn restore_app(state: AppState) -> Router {
+        Router::new()
+            .route(
+                "/admin/users/{user_id}/time-entries/{time_entry_id}/restore",
+                post(handle_restore),
+            )
+            .with_state(state)
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_restore_an_archived_entry_once() {
+        let state = make_test_app_state();
+        let partitions = state.list_time_entries_handler.store().partitions();
+        let (_, partition_store) = &partitions[0];
+        let mut projection = ListTimeEntriesState::default();
+        projection.rows.insert("te-old".to_string(), archived_row());
+        partition_store.save(projection, 1).await.unwrap();
+        TimeEntryArchiver::new(partition_store.clone(), state.cold_storage.clone(), 0)
+            .archive(i64::MAX)
+            .await
+            .unwrap();
+
+        let restored = restore_app(state.clone())
+            .oneshot(restore_request("admin", "te-old"))
+            .await
+            .unwrap();
+        let repeated = restore_app(state.clone())
+            .oneshot(restore_request("admin", "te-old"))
+            .await
+            .unwrap();
+
+        assert_eq!(restored.status(), StatusCode::NO_CONTENT);
+        assert_eq!(repeated.status(), StatusCode::NOT_FOUND);
+        let projection = partition_store.state().await.unwrap().unwrap();
+        assert_eq!(projection.rows["te-old"], archived_row());
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_reserve_restores_for_admins() {
+        let response = restore_app(make_test_app_state())
+            .oneshot(restore_request("employee", "te-old"))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    }
+
+    fn archived_row() -> TimeEntryRow {
+        TimeEntryRow {
+            time_entry_id: "te-old".to_string(),
+            user_id: "u1".to_string(),
+            started_at: Some(1),
+            ended_at: Some(2),
+            tag_ids: vec![],
+            status: TimeEntryStatus::Registered,
+            created_at: 0,
+            created_by: "u1".to_string(),
+            updated_at: 0,
+            updated_by: "u1".to_string(),
+            deleted_at: None,
+            hourly_rate: None,
+            last_event_id: Some("TimeEntry-te-old:4".to_string()),
+            breaks: vec![],
+        }
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_return_500_when_the_job_store_is_offline() {
+        let state = make_test_app_state();
+        state.job_store.toggle_offline();
+
+        let response = app(state)
+            .oneshot(request("admin", r#"{"retention_days":90}"#))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+    }
+}
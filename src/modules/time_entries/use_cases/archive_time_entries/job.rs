@@ -0,0 +1,189 @@
+// Archives finished time entries as a job, partition by partition. Each partition store is
+// archived on its own, since only a single partition can be saved at a time; progress counts
+// the partitions done. A retried job archives whatever its failed run left behind.
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use serde_json::{Value as Json, json};
+
+use crate::modules::time_entries::use_cases::archive_time_entries::archiver::TimeEntryArchiver;
+use crate::modules::time_entries::use_cases::list_time_entries::projection::ListTimeEntriesState;
+use crate::modules::time_entries::use_cases::list_time_entries::queries::ListTimeEntriesCache;
+use crate::shared::application::jobs::{JobContext, JobHandler};
+use crate::shared::infrastructure::cold_storage::ColdStorage;
+use crate::shared::infrastructure::job_store::Job;
+use crate::shared::infrastructure::projection_store::ProjectionStore;
+
+pub const JOB_KIND: &str = "time_entries.archive";
+
+const DAY_MS: i64 = 24 * 60 * 60 * 1000;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchivePayload {
+    /// Entries that ended more than this many days ago are archived.
+    pub retention_days: u32,
+}
+
+pub struct ArchiveTimeEntriesJob<TStore, TColdStorage> {
+    partitions: Vec<TStore>,
+    cold_storage: TColdStorage,
+    query_cache: Option<ListTimeEntriesCache>,
+}
+
+impl<TStore, TColdStorage> ArchiveTimeEntriesJob<TStore, TColdStorage>
+where
+    TStore: ProjectionStore<ListTimeEntriesState> + Clone + Send + Sync + 'static,
+    TColdStorage: ColdStorage + Clone + 'static,
+{
+    pub fn new(partitions: Vec<TStore>, cold_storage: TColdStorage) -> Self {
+        Self {
+            partitions,
+            cold_storage,
+            query_cache: None,
+        }
+    }
+
+    pub fn with_query_cache(mut self, query_cache: ListTimeEntriesCache) -> Self {
+        self.query_cache = Some(query_cache);
+        self
+    }
+}
+
+#[async_trait]
+impl<TStore, TColdStorage> JobHandler for ArchiveTimeEntriesJob<TStore, TColdStorage>
+where
+    TStore: ProjectionStore<ListTimeEntriesState> + Clone + Send + Sync + 'static,
+    TColdStorage: ColdStorage + Clone + 'static,
+{
+    async fn run(&self, job: &Job, ctx: &JobContext<'_>) -> anyhow::Result<Json> {
+        let payload: ArchivePayload = serde_json::from_value(job.payload.clone())?;
+        let retention_ms = i64::from(payload.retention_days) * DAY_MS;
+        let now = chrono::Utc::now().timestamp_millis();
+        let total = self.partitions.len() as u64;
+        let mut archived = 0;
+        for (done, store) in self.partitions.iter().enumerate() {
+            ctx.report_progress(done as u64, Some(total)).await?;
+            let mut archiver =
+                TimeEntryArchiver::new(store.clone(), self.cold_storage.clone(), retention_ms);
+            if let Some(query_cache) = &self.query_cache {
+                archiver = archiver.with_query_cache(query_cache.clone());
+            }
+            archived += archiver.archive(now).await?;
+        }
+        ctx.report_progress(total, Some(total)).await?;
+        Ok(json!({ "archived": archived }))
+    }
+}
+
+#[cfg(test)]
+mod archive_job_tests {
+    use super::*;
+    use crate::modules::time_entries::use_cases::list_time_entries::projection::{
+        TimeEntryRow, TimeEntryStatus,
+    };
+    use crate::shared::application::jobs::{JobRunner, RetryPolicy};
+    use crate::shared::infrastructure::cold_storage::in_memory::InMemoryColdStorage;
+    use crate::shared::infrastructure::job_store::in_memory::InMemoryJobStore;
+    use crate::shared::infrastructure::job_store::{JobStatus, JobStore};
+    use crate::shared::infrastructure::projection_store::in_memory::InMemoryProjectionStore;
+    use rstest::rstest;
+    use std::sync::Arc;
+
+    fn row(time_entry_id: &str, ended_at: i64) -> TimeEntryRow {
+        TimeEntryRow {
+            time_entry_id: time_entry_id.to_string(),
+            user_id: "u1".to_string(),
+            started_at: Some(ended_at - 1),
+            ended_at: Some(ended_at),
+            tag_ids: vec![],
+            status: TimeEntryStatus::Registered,
+            created_at: 0,
+            created_by: "u1".to_string(),
+            updated_at: 0,
+            updated_by: "u1".to_string(),
+            deleted_at: None,
+            hourly_rate: None,
+            last_event_id: None,
+            breaks: vec![],
+        }
+    }
+
+    async fn partition(rows: Vec<TimeEntryRow>) -> InMemoryProjectionStore<ListTimeEntriesState> {
+        let store = InMemoryProjectionStore::<ListTimeEntriesState>::new();
+        let mut state = ListTimeEntriesState::default();
+        for row in rows {
+            state.rows.insert(row.time_entry_id.clone(), row);
+        }
+        store.save(state, 1).await.unwrap();
+        store
+    }
+
+    async fn run(
+        partitions: Vec<InMemoryProjectionStore<ListTimeEntriesState>>,
+        cold_storage: &InMemoryColdStorage,
+        payload: Json,
+    ) -> Job {
+        let job_store = InMemoryJobStore::new();
+        let job = Job::queued(JOB_KIND, payload, 0);
+        job_store.enqueue(job.clone()).await.unwrap();
+        JobRunner::new(job_store.clone())
+            .with_handler(
+                JOB_KIND,
+                Arc::new(ArchiveTimeEntriesJob::new(partitions, cold_storage.clone())),
+            )
+            .with_retry_policy(RetryPolicy {
+                max_attempts: 1,
+                ..RetryPolicy::default()
+            })
+            .run_due()
+            .await
+            .unwrap();
+        job_store.get(&job.job_id).await.unwrap().unwrap()
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_archive_old_entries_of_every_partition() {
+        let now = chrono::Utc::now().timestamp_millis();
+        let first = partition(vec![row("te-old-1", 1), row("te-new", now)]).await;
+        let second = partition(vec![row("te-old-2", 2)]).await;
+        let cold_storage = InMemoryColdStorage::new();
+
+        let job = run(
+            vec![first.clone(), second.clone()],
+            &cold_storage,
+            json!({ "retention_days": 30 }),
+        )
+        .await;
+
+        assert_eq!(job.status, JobStatus::Succeeded);
+        assert_eq!(job.result, Some(json!({ "archived": 2 })));
+        let kept: Vec<String> = first
+            .state()
+            .await
+            .unwrap()
+            .unwrap()
+            .rows
+            .into_keys()
+            .collect();
+        assert_eq!(kept, vec!["te-new"]);
+        assert!(second.state().await.unwrap().unwrap().rows.is_empty());
+        assert_eq!(
+            cold_storage
+                .read_lines("time_entries/u1.jsonl")
+                .await
+                .unwrap()
+                .len(),
+            2
+        );
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_fail_on_an_invalid_payload() {
+        let job = run(vec![], &InMemoryColdStorage::new(), json!({})).await;
+
+        assert_eq!(job.status, JobStatus::Failed);
+        assert!(job.last_error.unwrap().contains("retention_days"));
+    }
+}
@@ -0,0 +1,20 @@
+use crate::modules::time_entries::core::policies::RoundedCommand;
+use crate::shared::application::server_time::StampedCommand;
+use crate::shared::core::primitives::{TimeEntryId, Timestamp};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AutoStopTimer {
+    pub time_entry_id: TimeEntryId,
+    /// Longest a timer may run; the entry is ended this long after its start.
+    pub max_duration_ms: i64,
+    pub reason: String,
+    pub stopped_at: i64,
+}
+
+/// `stopped_at` is the stopper's own reading, the instant it judged the timer overdue
+/// against, so the handler leaves it alone.
+impl StampedCommand for AutoStopTimer {
+    fn stamp(&mut self, _now: Timestamp) {}
+}
+
+impl RoundedCommand for AutoStopTimer {}
@@ -0,0 +1,158 @@
+use chrono::TimeDelta;
+
+use crate::modules::time_entries::core::events::TimeEntryEvent;
+use crate::modules::time_entries::core::events::v1::time_entry_registered::TimeEntryRegisteredV1;
+use crate::modules::time_entries::core::events::v1::timer_auto_stopped::TimerAutoStoppedV1;
+use crate::modules::time_entries::core::evolve::evolve;
+use crate::modules::time_entries::core::intents::TimeEntryIntent;
+use crate::modules::time_entries::core::state::TimeEntryState;
+use crate::modules::time_entries::core::time_interval::TimeInterval;
+use crate::modules::time_entries::use_cases::auto_stop_timers::command::AutoStopTimer;
+use crate::modules::time_entries::use_cases::auto_stop_timers::decision::{DecideError, Decision};
+use crate::shared::core::decider::Decider;
+
+/// Ends a timer that ran longer than `max_duration_ms` at its start plus that maximum, and
+/// registers the entry like a manual stop would.
+pub fn decide_auto_stop_timer(state: &TimeEntryState, command: AutoStopTimer) -> Decision {
+    let TimeEntryState::Draft {
+        started_at: Some(started_at),
+        ended_at: None,
+        ..
+    } = state
+    else {
+        return Decision::Rejected {
+            reason: DecideError::NotRunning,
+        };
+    };
+    let max_duration = TimeDelta::milliseconds(command.max_duration_ms);
+    let ran_past_limit = TimeInterval::new(*started_at, command.stopped_at)
+        .is_ok_and(|ran| ran.duration() >= max_duration);
+    if !ran_past_limit {
+        return Decision::Rejected {
+            reason: DecideError::WithinLimit,
+        };
+    }
+
+    let auto_stopped = TimeEntryEvent::TimerAutoStoppedV1(TimerAutoStoppedV1 {
+        time_entry_id: command.time_entry_id.clone(),
+        ended_at: started_at + command.max_duration_ms,
+        reason: command.reason.clone(),
+        stopped_at: command.stopped_at,
+    });
+    let registered = TimeEntryEvent::TimeEntryRegisteredV1(TimeEntryRegisteredV1 {
+        time_entry_id: command.time_entry_id.clone(),
+        occurred_at: command.stopped_at,
+    });
+    Decision::Accepted {
+        events: vec![auto_stopped, registered],
+        intents: vec![TimeEntryIntent::NotifyTimerAutoStopped {
+            time_entry_id: command.time_entry_id,
+            reason: command.reason,
+            occurred_at: command.stopped_at,
+        }],
+    }
+}
+
+pub struct AutoStopTimerDecider;
+
+impl Decider for AutoStopTimerDecider {
+    type State = TimeEntryState;
+    type Command = AutoStopTimer;
+    type Event = TimeEntryEvent;
+    type Intent = TimeEntryIntent;
+    type Error = DecideError;
+
+    fn initial_state() -> TimeEntryState {
+        TimeEntryState::None
+    }
+
+    fn evolve(state: TimeEntryState, event: TimeEntryEvent) -> TimeEntryState {
+        evolve(state, event)
+    }
+
+    fn decide(state: &TimeEntryState, command: AutoStopTimer) -> Decision {
+        decide_auto_stop_timer(state, command)
+    }
+}
+
+#[cfg(test)]
+mod decide_auto_stop_timer_tests {
+    use super::*;
+    use rstest::{fixture, rstest};
+
+    const HOUR: i64 = 3_600_000;
+
+    #[fixture]
+    fn command() -> AutoStopTimer {
+        AutoStopTimer {
+            time_entry_id: "te-0001".into(),
+            max_duration_ms: 12 * HOUR,
+            reason: "timer ran longer than 12 hours".to_string(),
+            stopped_at: 13 * HOUR,
+        }
+    }
+
+    fn draft(started_at: Option<i64>, ended_at: Option<i64>) -> TimeEntryState {
+        TimeEntryState::Draft {
+            time_entry_id: "te-0001".into(),
+            user_id: "user-0001".into(),
+            started_at,
+            ended_at,
+            tag_ids: vec![],
+            created_at: 0,
+            created_by: "user-0001".into(),
+            hourly_rate: None,
+        }
+    }
+
+    #[rstest]
+    fn it_should_end_the_timer_at_the_maximum_and_register_it(command: AutoStopTimer) {
+        match decide_auto_stop_timer(&draft(Some(0), None), command) {
+            Decision::Accepted { events, intents } => {
+                assert!(matches!(
+                    &events[..],
+                    [
+                        TimeEntryEvent::TimerAutoStoppedV1(stopped),
+                        TimeEntryEvent::TimeEntryRegisteredV1(_),
+                    ] if stopped.ended_at == 12 * HOUR && stopped.stopped_at == 13 * HOUR
+                ));
+                assert!(matches!(
+                    &intents[..],
+                    [TimeEntryIntent::NotifyTimerAutoStopped { reason, .. }]
+                        if reason == "timer ran longer than 12 hours"
+                ));
+            }
+            Decision::Rejected { .. } => panic!("expected Accepted"),
+        }
+    }
+
+    #[rstest]
+    fn it_should_leave_timers_within_the_limit_running(command: AutoStopTimer) {
+        match decide_auto_stop_timer(&draft(Some(2 * HOUR), None), command) {
+            Decision::Rejected { reason } => assert_eq!(reason, DecideError::WithinLimit),
+            Decision::Accepted { .. } => panic!("expected Rejected"),
+        }
+    }
+
+    #[rstest]
+    #[case::none(TimeEntryState::None)]
+    #[case::not_started(draft(None, None))]
+    #[case::stopped(draft(Some(0), Some(HOUR)))]
+    #[case::registered(TimeEntryState::Registered {
+        time_entry_id: "te-0001".into(),
+        user_id: "user-0001".into(),
+        interval: TimeInterval::new(0, HOUR).unwrap(),
+        tag_ids: vec![],
+        created_at: 0,
+        created_by: "user-0001".into(),
+        hourly_rate: None, breaks: vec![], })]
+    fn it_should_reject_entries_without_a_running_timer(
+        command: AutoStopTimer,
+        #[case] state: TimeEntryState,
+    ) {
+        match decide_auto_stop_timer(&state, command) {
+            Decision::Rejected { reason } => assert_eq!(reason, DecideError::NotRunning),
+            Decision::Accepted { .. } => panic!("expected Rejected"),
+        }
+    }
+}
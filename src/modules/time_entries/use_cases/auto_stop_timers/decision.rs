@@ -0,0 +1,40 @@
+use crate::modules::time_entries::core::day_capacity::DayCapacityError;
+use crate::modules::time_entries::core::days_off::DayOffError;
+use crate::modules::time_entries::core::events::TimeEntryEvent;
+use crate::modules::time_entries::core::intents::TimeEntryIntent;
+use crate::modules::time_entries::core::period_locks::PeriodLockError;
+use crate::modules::time_entries::core::policies::PolicyError;
+use crate::modules::time_entries::core::user_time_entries::UserTimeEntriesError;
+use crate::shared::application::server_time::ClockSkewError;
+use crate::shared::core::decider;
+use thiserror::Error;
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum DecideError {
+    #[error("time entry has no running timer")]
+    NotRunning,
+
+    #[error("timer has not exceeded the maximum duration")]
+    WithinLimit,
+
+    #[error(transparent)]
+    UserTimeEntries(#[from] UserTimeEntriesError),
+
+    #[error(transparent)]
+    PeriodLocked(#[from] PeriodLockError),
+
+    #[error(transparent)]
+    Policy(#[from] PolicyError),
+
+    /// Required by `UserShardedHandler`; never produced, as auto-stop runs without a calendar.
+    #[error(transparent)]
+    DayOff(#[from] DayOffError),
+
+    #[error(transparent)]
+    OverCapacity(#[from] DayCapacityError),
+
+    #[error(transparent)]
+    ClockSkew(#[from] ClockSkewError),
+}
+
+pub type Decision = decider::Decision<TimeEntryEvent, TimeEntryIntent, DecideError>;
@@ -0,0 +1,63 @@
+use crate::modules::time_entries::adapters::outbound::intent_outbox::TimeEntryIntentDispatcher;
+use crate::modules::time_entries::core::events::TimeEntryEvent;
+use crate::modules::time_entries::use_cases::auto_stop_timers::command::AutoStopTimer;
+use crate::modules::time_entries::use_cases::auto_stop_timers::decide::AutoStopTimerDecider;
+use crate::modules::time_entries::use_cases::auto_stop_timers::decision::DecideError;
+use crate::modules::time_entries::use_cases::user_time_entries::sharded_handler::{
+    PeriodLockStreams, UserShardedHandler, UserStreams,
+};
+use crate::shared::application::event_sourced_handler::EventSourcedError;
+use crate::shared::infrastructure::event_bus::SharedEventBus;
+use crate::shared::infrastructure::event_store::EventStore;
+use crate::shared::infrastructure::intent_outbox::{DomainOutbox, OutboxError};
+
+pub type ApplicationError = EventSourcedError<DecideError, OutboxError>;
+
+#[derive(Debug, Clone)]
+pub struct AutoStopTimerHandler<TEventStore, TOutbox>
+where
+    TEventStore: EventStore<TimeEntryEvent> + Send + Sync + 'static,
+    TOutbox: DomainOutbox + Send + Sync + 'static,
+{
+    inner:
+        UserShardedHandler<AutoStopTimerDecider, TEventStore, TimeEntryIntentDispatcher<TOutbox>>,
+}
+
+impl<TEventStore, TOutbox> AutoStopTimerHandler<TEventStore, TOutbox>
+where
+    TEventStore: EventStore<TimeEntryEvent> + Send + Sync + 'static,
+    TOutbox: DomainOutbox + Send + Sync + 'static,
+{
+    pub fn new(event_store: TEventStore, outbox: TOutbox) -> Self {
+        Self {
+            inner: UserShardedHandler::new(event_store, TimeEntryIntentDispatcher::new(outbox)),
+        }
+    }
+
+    /// Replace the running claim on the user's `UserTimeEntries-{user_id}` stream with the
+    /// stopped interval.
+    pub fn with_user_streams(mut self, user_streams: UserStreams) -> Self {
+        self.inner = self.inner.with_user_streams(user_streams);
+        self
+    }
+
+    /// Leave timers inside a locked payroll period running.
+    pub fn with_period_locks(mut self, period_locks: PeriodLockStreams) -> Self {
+        self.inner = self.inner.with_period_locks(period_locks);
+        self
+    }
+
+    /// Announce the appended events on `event_bus`, for the projectors to pick up.
+    pub fn with_event_bus(mut self, event_bus: SharedEventBus<TimeEntryEvent>) -> Self {
+        self.inner = self.inner.with_event_bus(event_bus);
+        self
+    }
+
+    pub async fn handle(
+        &self,
+        stream_id: &str,
+        command: AutoStopTimer,
+    ) -> Result<(), ApplicationError> {
+        self.inner.handle(stream_id, command).await
+    }
+}
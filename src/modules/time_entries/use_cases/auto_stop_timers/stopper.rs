@@ -0,0 +1,294 @@
+// Stops timers that were left running.
+//
+// Running timers are found in the list time entries read model: rows with a start but no
+// end. The read model may lag, so each candidate is re-checked by the decider against its
+// stream; timers stopped or deleted in the meantime are skipped. Stopping a timer ends it at
+// its start plus the maximum, registers it and notifies the user through the outbox. Each
+// timer is stopped on the stream its row was projected from, whatever the stream naming.
+
+use crate::modules::time_entries::core::events::TimeEntryEvent;
+use crate::modules::time_entries::use_cases::auto_stop_timers::command::AutoStopTimer;
+use crate::modules::time_entries::use_cases::auto_stop_timers::decision::DecideError;
+use crate::modules::time_entries::use_cases::auto_stop_timers::handler::{
+    ApplicationError, AutoStopTimerHandler,
+};
+use crate::modules::time_entries::use_cases::list_time_entries::projection::ListTimeEntriesState;
+use crate::shared::core::primitives::last_event_stream;
+use crate::shared::core::stream_naming::{DefaultStreamNaming, StreamNaming};
+use crate::shared::infrastructure::event_store::EventStore;
+use crate::shared::infrastructure::intent_outbox::DomainOutbox;
+use crate::shared::infrastructure::projection_store::ProjectionStore;
+use std::sync::Arc;
+
+pub const DEFAULT_MAX_DURATION_MS: i64 = 12 * 60 * 60 * 1000;
+
+pub struct TimerAutoStopper<TStore, TEventStore, TOutbox>
+where
+    TStore: ProjectionStore<ListTimeEntriesState> + Send + Sync + 'static,
+    TEventStore: EventStore<TimeEntryEvent> + Send + Sync + 'static,
+    TOutbox: DomainOutbox + Send + Sync + 'static,
+{
+    store: TStore,
+    handler: AutoStopTimerHandler<TEventStore, TOutbox>,
+    max_duration_ms: i64,
+    stream_naming: Arc<dyn StreamNaming>,
+}
+
+impl<TStore, TEventStore, TOutbox> TimerAutoStopper<TStore, TEventStore, TOutbox>
+where
+    TStore: ProjectionStore<ListTimeEntriesState> + Send + Sync + 'static,
+    TEventStore: EventStore<TimeEntryEvent> + Send + Sync + 'static,
+    TOutbox: DomainOutbox + Send + Sync + 'static,
+{
+    pub fn new(store: TStore, handler: AutoStopTimerHandler<TEventStore, TOutbox>) -> Self {
+        Self {
+            store,
+            handler,
+            max_duration_ms: DEFAULT_MAX_DURATION_MS,
+            stream_naming: Arc::new(DefaultStreamNaming),
+        }
+    }
+
+    /// Names the streams of rows that do not record the stream they were projected from.
+    pub fn with_stream_naming(mut self, stream_naming: Arc<dyn StreamNaming>) -> Self {
+        self.stream_naming = stream_naming;
+        self
+    }
+
+    pub fn with_max_duration_ms(mut self, max_duration_ms: i64) -> Self {
+        self.max_duration_ms = max_duration_ms;
+        self
+    }
+
+    /// Stops every timer that started at least the maximum duration before `now`. Returns
+    /// how many were stopped. A timer that fails to stop is logged and retried on the next
+    /// run; it does not hold up the others.
+    pub async fn stop_overdue(&self, now: i64) -> anyhow::Result<u64> {
+        let state = self.store.state().await?.unwrap_or_default();
+        let cutoff = now - self.max_duration_ms;
+        let mut overdue: Vec<(String, String)> = state
+            .rows
+            .values()
+            .filter(|row| row.ended_at.is_none() && row.deleted_at.is_none())
+            .filter(|row| {
+                row.started_at
+                    .is_some_and(|started_at| started_at <= cutoff)
+            })
+            .map(|row| {
+                let stream_id = last_event_stream(row.last_event_id.as_deref())
+                    .map(str::to_string)
+                    .unwrap_or_else(|| self.stream_naming.time_entry(None, &row.time_entry_id));
+                (row.time_entry_id.clone(), stream_id)
+            })
+            .collect();
+        overdue.sort();
+
+        let mut stopped = 0;
+        for (time_entry_id, stream_id) in overdue {
+            let command = AutoStopTimer {
+                time_entry_id: time_entry_id.clone().into(),
+                max_duration_ms: self.max_duration_ms,
+                reason: self.reason(),
+                stopped_at: now,
+            };
+            match self.handler.handle(&stream_id, command).await {
+                Ok(()) => stopped += 1,
+                Err(ApplicationError::Domain(
+                    DecideError::NotRunning | DecideError::WithinLimit,
+                )) => {}
+                Err(reason) => {
+                    tracing::warn!(%reason, %time_entry_id, "failed to auto-stop timer");
+                }
+            }
+        }
+        Ok(stopped)
+    }
+
+    fn reason(&self) -> String {
+        format!(
+            "timer ran longer than the maximum of {} minutes",
+            self.max_duration_ms / 60_000
+        )
+    }
+}
+
+#[cfg(test)]
+mod timer_auto_stopper_tests {
+    use super::*;
+    use crate::modules::time_entries::core::events::v1::time_entry_initiated::TimeEntryInitiatedV1;
+    use crate::modules::time_entries::core::events::v1::time_entry_start_set::TimeEntryStartSetV1;
+    use crate::modules::time_entries::core::period_locks::PeriodLocksEvent;
+    use crate::modules::time_entries::core::user_time_entries::{
+        UserTimeEntriesEvent, user_stream_id,
+    };
+    use crate::modules::time_entries::use_cases::list_time_entries::projection::{
+        TimeEntryRow, TimeEntryStatus,
+    };
+    use crate::shared::infrastructure::event_store::in_memory::InMemoryEventStore;
+    use crate::shared::infrastructure::intent_outbox::in_memory::InMemoryDomainOutbox;
+    use crate::shared::infrastructure::projection_store::in_memory::InMemoryProjectionStore;
+    use rstest::rstest;
+    use std::sync::Arc;
+
+    const HOUR: i64 = 3_600_000;
+
+    fn row(time_entry_id: &str, started_at: i64, ended_at: Option<i64>) -> TimeEntryRow {
+        TimeEntryRow {
+            time_entry_id: time_entry_id.to_string(),
+            user_id: "u-1".to_string(),
+            started_at: Some(started_at),
+            ended_at,
+            tag_ids: vec![],
+            status: TimeEntryStatus::Draft,
+            created_at: 0,
+            created_by: "u-1".to_string(),
+            updated_at: 0,
+            updated_by: "u-1".to_string(),
+            deleted_at: None,
+            hourly_rate: None,
+            last_event_id: None,
+            breaks: vec![],
+        }
+    }
+
+    async fn store_with_rows(
+        rows: Vec<TimeEntryRow>,
+    ) -> InMemoryProjectionStore<ListTimeEntriesState> {
+        let store = InMemoryProjectionStore::<ListTimeEntriesState>::new();
+        let mut state = ListTimeEntriesState::default();
+        for row in rows {
+            state.rows.insert(row.time_entry_id.clone(), row);
+        }
+        store.save(state, 1).await.unwrap();
+        store
+    }
+
+    async fn start_timer(
+        event_store: &InMemoryEventStore<TimeEntryEvent>,
+        time_entry_id: &str,
+        started_at: i64,
+    ) {
+        event_store
+            .append(
+                &format!("TimeEntry-{time_entry_id}"),
+                0,
+                &[
+                    TimeEntryEvent::TimeEntryInitiatedV1(TimeEntryInitiatedV1 {
+                        time_entry_id: time_entry_id.into(),
+                        user_id: "u-1".into(),
+                        created_at: started_at,
+                        created_by: "u-1".into(),
+                    }),
+                    TimeEntryEvent::TimeEntryStartSetV1(TimeEntryStartSetV1 {
+                        time_entry_id: time_entry_id.into(),
+                        started_at,
+                        updated_at: started_at,
+                        updated_by: "u-1".into(),
+                        raw_started_at: None,
+                    }),
+                ],
+            )
+            .await
+            .unwrap();
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_stop_only_timers_running_past_the_maximum() {
+        let event_store = InMemoryEventStore::<TimeEntryEvent>::new();
+        let outbox = InMemoryDomainOutbox::new();
+        let user_streams = Arc::new(InMemoryEventStore::<UserTimeEntriesEvent>::new());
+        start_timer(&event_store, "te-overdue", 0).await;
+        start_timer(&event_store, "te-recent", 10 * HOUR).await;
+        let mut deleted = row("te-deleted", 0, None);
+        deleted.deleted_at = Some(HOUR);
+        let store = store_with_rows(vec![
+            row("te-overdue", 0, None),
+            row("te-recent", 10 * HOUR, None),
+            row("te-stopped", 0, Some(HOUR)),
+            // Stale: the read model still shows a timer the stream no longer has.
+            row("te-missing", 0, None),
+            deleted,
+        ])
+        .await;
+        let handler = AutoStopTimerHandler::new(event_store.clone(), outbox.clone())
+            .with_user_streams(user_streams.clone());
+        let stopper = TimerAutoStopper::new(store, handler).with_max_duration_ms(8 * HOUR);
+
+        let stopped = stopper.stop_overdue(12 * HOUR).await.unwrap();
+
+        assert_eq!(stopped, 1);
+        let stream = event_store.load("TimeEntry-te-overdue").await.unwrap();
+        assert!(matches!(
+            &stream.events[2..],
+            [
+                TimeEntryEvent::TimerAutoStoppedV1(e),
+                TimeEntryEvent::TimeEntryRegisteredV1(_),
+            ] if e.ended_at == 8 * HOUR && e.reason == "timer ran longer than the maximum of 480 minutes"
+        ));
+        assert_eq!(
+            event_store
+                .load("TimeEntry-te-recent")
+                .await
+                .unwrap()
+                .events
+                .len(),
+            2
+        );
+        let rows = outbox.rows().await;
+        assert!(matches!(
+            &rows[..],
+            [row] if row.event_type == "TimerAutoStopped" && row.payload["time_entry_id"] == "te-overdue"
+        ));
+        let claims = user_streams.load(&user_stream_id("u-1")).await.unwrap();
+        assert!(matches!(
+            claims.events.last(),
+            Some(UserTimeEntriesEvent::IntervalClaimedV1(claim))
+                if claim.interval.ended_at == Some(8 * HOUR)
+        ));
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_keep_going_when_a_timer_fails_to_stop() {
+        let event_store = InMemoryEventStore::<TimeEntryEvent>::new();
+        start_timer(&event_store, "te-1", 0).await;
+        let period_locks = InMemoryEventStore::<PeriodLocksEvent>::new();
+        period_locks.toggle_offline();
+        let handler = AutoStopTimerHandler::new(event_store.clone(), InMemoryDomainOutbox::new())
+            .with_period_locks(Arc::new(period_locks));
+        let stopper =
+            TimerAutoStopper::new(store_with_rows(vec![row("te-1", 0, None)]).await, handler);
+
+        let stopped = stopper.stop_overdue(DEFAULT_MAX_DURATION_MS).await.unwrap();
+
+        assert_eq!(stopped, 0);
+        assert_eq!(
+            event_store
+                .load("TimeEntry-te-1")
+                .await
+                .unwrap()
+                .events
+                .len(),
+            2
+        );
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_fail_when_the_read_model_is_offline() {
+        let mut store = InMemoryProjectionStore::<ListTimeEntriesState>::new();
+        store.toggle_offline();
+        let handler = AutoStopTimerHandler::new(
+            InMemoryEventStore::<TimeEntryEvent>::new(),
+            InMemoryDomainOutbox::new(),
+        );
+
+        assert!(
+            TimerAutoStopper::new(store, handler)
+                .stop_overdue(0)
+                .await
+                .is_err()
+        );
+    }
+}
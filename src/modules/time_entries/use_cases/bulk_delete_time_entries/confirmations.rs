@@ -0,0 +1,127 @@
+// Bulk deletes waiting for their confirmation.
+//
+// Deleting by filter takes two steps, so a mistyped filter never deletes anything on its own:
+// the first answers how many entries the filter matches with a token, and only confirming
+// that token queues the job. A token confirms once, only by the admin who asked for it and
+// only for a few minutes. Pending deletes live in the process; a restart forgets them and
+// the admin asks again.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use crate::modules::time_entries::use_cases::bulk_delete_time_entries::job::BulkDeletePayload;
+
+/// How long a confirmation token can be confirmed.
+pub const CONFIRMATION_TTL_MS: i64 = 5 * 60 * 1000;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PendingBulkDelete {
+    pub payload: BulkDeletePayload,
+    /// Entries the filter matched when the token was issued.
+    pub matched: usize,
+    pub expires_at: i64,
+}
+
+/// Pending bulk deletes by confirmation token; clones share them.
+#[derive(Debug, Clone, Default)]
+pub struct BulkDeleteConfirmations {
+    pending: Arc<Mutex<HashMap<String, PendingBulkDelete>>>,
+}
+
+impl BulkDeleteConfirmations {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Holds `payload` until its token is confirmed or expires. Returns the token and when
+    /// it expires.
+    pub fn issue(&self, payload: BulkDeletePayload, matched: usize, now: i64) -> (String, i64) {
+        let token = uuid::Uuid::now_v7().simple().to_string();
+        let expires_at = now + CONFIRMATION_TTL_MS;
+        let mut pending = self.pending.lock().unwrap();
+        pending.retain(|_, pending| pending.expires_at > now);
+        pending.insert(
+            token.clone(),
+            PendingBulkDelete {
+                payload,
+                matched,
+                expires_at,
+            },
+        );
+        (token, expires_at)
+    }
+
+    /// Takes the bulk delete `token` stands for, if `user_id` of `tenant_id` asked for it and
+    /// it has not expired. A taken token cannot be confirmed again.
+    pub fn confirm(
+        &self,
+        token: &str,
+        tenant_id: &str,
+        user_id: &str,
+        now: i64,
+    ) -> Option<PendingBulkDelete> {
+        let mut pending = self.pending.lock().unwrap();
+        let issued = pending.get(token)?;
+        if issued.payload.tenant_id != tenant_id || issued.payload.requested_by != user_id {
+            return None;
+        }
+        pending
+            .remove(token)
+            .filter(|pending| pending.expires_at > now)
+    }
+}
+
+#[cfg(test)]
+mod bulk_delete_confirmations_tests {
+    use super::*;
+    use crate::modules::time_entries::use_cases::bulk_delete_time_entries::job::BulkDeleteFilter;
+    use rstest::rstest;
+
+    fn payload() -> BulkDeletePayload {
+        BulkDeletePayload {
+            filter: BulkDeleteFilter {
+                user_id: Some("u-1".to_string()),
+                ..Default::default()
+            },
+            tenant_id: "t-1".to_string(),
+            requested_by: "admin-1".to_string(),
+        }
+    }
+
+    #[rstest]
+    fn it_should_confirm_a_token_once() {
+        let confirmations = BulkDeleteConfirmations::new();
+        let (token, expires_at) = confirmations.issue(payload(), 3, 1_000);
+
+        let confirmed = confirmations.confirm(&token, "t-1", "admin-1", 2_000);
+
+        assert_eq!(
+            confirmed,
+            Some(PendingBulkDelete {
+                payload: payload(),
+                matched: 3,
+                expires_at,
+            })
+        );
+        assert_eq!(confirmations.confirm(&token, "t-1", "admin-1", 2_000), None);
+    }
+
+    #[rstest]
+    #[case::other_tenant("t-2", "admin-1", 2_000)]
+    #[case::other_admin("t-1", "admin-2", 2_000)]
+    #[case::expired("t-1", "admin-1", 1_000 + CONFIRMATION_TTL_MS)]
+    fn it_should_refuse_tokens_of_others_or_expired_ones(
+        #[case] tenant_id: &str,
+        #[case] user_id: &str,
+        #[case] now: i64,
+    ) {
+        let confirmations = BulkDeleteConfirmations::new();
+        let (token, _) = confirmations.issue(payload(), 3, 1_000);
+
+        assert_eq!(confirmations.confirm(&token, tenant_id, user_id, now), None);
+        assert_eq!(
+            confirmations.confirm("unknown", "t-1", "admin-1", 2_000),
+            None
+        );
+    }
+}
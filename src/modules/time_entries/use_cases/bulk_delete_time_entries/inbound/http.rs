@@ -0,0 +1,290 @@
+use axum::{
+    Json,
+    extract::{State, rejection::JsonRejection},
+    http::StatusCode,
+    response::IntoResponse,
+};
+use chrono::Utc;
+use serde::Deserialize;
+use serde_json::json;
+
+use crate::modules::time_entries::core::tag::Tag;
+use crate::modules::time_entries::use_cases::bulk_delete_time_entries::job::{
+    BulkDeleteFilter, BulkDeletePayload, JOB_KIND, matching_ids,
+};
+use crate::shared::infrastructure::job_store::Job;
+use crate::shared::infrastructure::request_context::RequestContext;
+use crate::shell::jobs;
+use crate::shell::state::AppState;
+
+#[derive(Debug, Deserialize)]
+pub struct BulkDeleteBody {
+    pub user_id: Option<String>,
+    pub from: Option<i64>,
+    pub to: Option<i64>,
+    pub tag: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ConfirmBody {
+    pub confirmation_token: String,
+}
+
+/// POST /admin/time-entries/bulk-delete — how many entries `{user_id, from, to, tag}`
+/// matches, with the token confirming their deletion; nothing is deleted yet. At least one
+/// criterion is required. Admins only.
+pub async fn handle_request(
+    State(state): State<AppState>,
+    request_ctx: RequestContext,
+    body: Result<Json<BulkDeleteBody>, JsonRejection>,
+) -> impl IntoResponse {
+    if !request_ctx.principal().can_administer() {
+        return StatusCode::FORBIDDEN.into_response();
+    }
+    let Ok(Json(body)) = body else {
+        return StatusCode::UNPROCESSABLE_ENTITY.into_response();
+    };
+    let Ok(tag) = body.tag.as_deref().map(Tag::parse).transpose() else {
+        return (StatusCode::UNPROCESSABLE_ENTITY, "tag is invalid").into_response();
+    };
+    let filter = BulkDeleteFilter {
+        user_id: body.user_id,
+        from: body.from,
+        to: body.to,
+        tag,
+    };
+    if filter.is_empty() {
+        return (
+            StatusCode::UNPROCESSABLE_ENTITY,
+            "a bulk delete needs at least one of user_id, from, to or tag",
+        )
+            .into_response();
+    }
+    if let (Some(from), Some(to)) = (filter.from, filter.to)
+        && to <= from
+    {
+        return (StatusCode::UNPROCESSABLE_ENTITY, "to must be after from").into_response();
+    }
+    let Ok(matched) = matching_ids(&state, &filter).await else {
+        return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+    };
+    let payload = BulkDeletePayload {
+        filter,
+        tenant_id: request_ctx.tenant_id,
+        requested_by: request_ctx.user_id,
+    };
+    let (confirmation_token, expires_at) = state.bulk_delete_confirmations.issue(
+        payload,
+        matched.len(),
+        Utc::now().timestamp_millis(),
+    );
+    Json(json!({
+        "matched": matched.len(),
+        "confirmation_token": confirmation_token,
+        "expires_at": expires_at,
+    }))
+    .into_response()
+}
+
+/// POST /admin/time-entries/bulk-delete/confirm — queues the job deleting what the
+/// `confirmation_token` was issued for; poll the `Location` it answers with. A token is
+/// confirmed once, by the admin it was issued to, before it expires. Admins only.
+pub async fn handle_confirm(
+    State(state): State<AppState>,
+    request_ctx: RequestContext,
+    body: Result<Json<ConfirmBody>, JsonRejection>,
+) -> impl IntoResponse {
+    if !request_ctx.principal().can_administer() {
+        return StatusCode::FORBIDDEN.into_response();
+    }
+    let Ok(Json(body)) = body else {
+        return StatusCode::UNPROCESSABLE_ENTITY.into_response();
+    };
+    let now = Utc::now().timestamp_millis();
+    let Some(pending) = state.bulk_delete_confirmations.confirm(
+        &body.confirmation_token,
+        &request_ctx.tenant_id,
+        &request_ctx.user_id,
+        now,
+    ) else {
+        return (
+            StatusCode::NOT_FOUND,
+            "confirmation token is unknown, expired or already confirmed",
+        )
+            .into_response();
+    };
+    let payload =
+        serde_json::to_value(pending.payload).expect("bulk delete payloads serialize to JSON");
+    jobs::accept(&state, Job::queued(JOB_KIND, payload, now)).await
+}
+
+#[cfg(test)]
+mod bulk_delete_time_entries_http_inbound_tests {
+    use super::*;
+    use crate::shared::infrastructure::job_store::{JobQuery, JobStatus, JobStore};
+    use crate::tests::fixtures::tags::make_test_app_state;
+    use axum::{
+        Router,
+        body::Body,
+        http::{Request, header},
+        routing::post,
+    };
+    use http_body_util::BodyExt;
+    use rstest::rstest;
+    use serde_json::Value;
+    use tower::ServiceExt;
+
+    fn app(state: AppState) -> Router {
+        Router::new()
+            .route("/admin/time-entries/bulk-delete", post(handle_request))
+            .route(
+                "/admin/time-entries/bulk-delete/confirm",
+                post(handle_confirm),
+            )
+            .with_state(state)
+    }
+
+    async fn send(
+        state: &AppState,
+        uri: &str,
+        user_id: &str,
+        role: &str,
+        body: &str,
+    ) -> axum::response::Response {
+        app(state.clone())
+            .oneshot(
+                Request::post(uri)
+                    .header("x-user-id", user_id)
+                    .header("x-tenant-id", "tenant-test")
+                    .header("x-user-role", role)
+                    .header(header::CONTENT_TYPE, "application/json")
+                    .body(Body::from(body.to_string()))
+                    .unwrap(),
+            )
+            .await
+            .unwrap()
+    }
+
+    async fn json_body(response: axum::response::Response) -> Value {
+        let bytes = response.into_body().collect().await.unwrap().to_bytes();
+        serde_json::from_slice(&bytes).unwrap()
+    }
+
+    async fn request_token(state: &AppState) -> String {
+        let response = send(
+            state,
+            "/admin/time-entries/bulk-delete",
+            "admin-1",
+            "admin",
+            r#"{"user_id":"u-1","tag":" Dev "}"#,
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = json_body(response).await;
+        assert_eq!(body["matched"], 0);
+        body["confirmation_token"].as_str().unwrap().to_string()
+    }
+
+    fn confirm_body(token: &str) -> String {
+        json!({ "confirmation_token": token }).to_string()
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_queue_the_job_only_once_confirmed() {
+        let state = make_test_app_state();
+        let token = request_token(&state).await;
+        let queued = JobQuery {
+            kind: None,
+            status: None,
+            limit: 10,
+        };
+        assert!(state.job_store.list(&queued).await.unwrap().is_empty());
+
+        let response = send(
+            &state,
+            "/admin/time-entries/bulk-delete/confirm",
+            "admin-1",
+            "admin",
+            &confirm_body(&token),
+        )
+        .await;
+
+        assert_eq!(response.status(), StatusCode::ACCEPTED);
+        let location = response.headers()[header::LOCATION].to_str().unwrap();
+        let job_id = location.trim_start_matches("/admin/jobs/");
+        let job = state.job_store.get(job_id).await.unwrap().unwrap();
+        assert_eq!(job.kind, JOB_KIND);
+        assert_eq!(job.status, JobStatus::Queued);
+        assert_eq!(job.payload["filter"]["tag"], "dev");
+        assert_eq!(job.payload["requested_by"], "admin-1");
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_refuse_confirming_twice_or_by_another_admin() {
+        let state = make_test_app_state();
+        let token = request_token(&state).await;
+
+        let other_admin = send(
+            &state,
+            "/admin/time-entries/bulk-delete/confirm",
+            "admin-2",
+            "admin",
+            &confirm_body(&token),
+        )
+        .await;
+        let first = send(
+            &state,
+            "/admin/time-entries/bulk-delete/confirm",
+            "admin-1",
+            "admin",
+            &confirm_body(&token),
+        )
+        .await;
+        let second = send(
+            &state,
+            "/admin/time-entries/bulk-delete/confirm",
+            "admin-1",
+            "admin",
+            &confirm_body(&token),
+        )
+        .await;
+
+        assert_eq!(other_admin.status(), StatusCode::NOT_FOUND);
+        assert_eq!(first.status(), StatusCode::ACCEPTED);
+        assert_eq!(second.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[rstest]
+    #[case::empty_filter("{}")]
+    #[case::backwards_range(r#"{"from":2000,"to":1000}"#)]
+    #[case::blank_tag(r#"{"tag":" "}"#)]
+    #[case::malformed("not json")]
+    #[tokio::test]
+    async fn it_should_reject_invalid_filters(#[case] body: &str) {
+        let response = send(
+            &make_test_app_state(),
+            "/admin/time-entries/bulk-delete",
+            "admin-1",
+            "admin",
+            body,
+        )
+        .await;
+
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    #[rstest]
+    #[case::request("/admin/time-entries/bulk-delete", r#"{"user_id":"u-1"}"#)]
+    #[case::confirm(
+        "/admin/time-entries/bulk-delete/confirm",
+        r#"{"confirmation_token":"t"}"#
+    )]
+    #[tokio::test]
+    async fn it_should_forbid_non_admins(#[case] uri: &str, #[case] body: &str) {
+        let response = send(&make_test_app_state(), uri, "u-1", "employee", body).await;
+
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    }
+}
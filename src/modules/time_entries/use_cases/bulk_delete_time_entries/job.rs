@@ -0,0 +1,295 @@
+// Soft-deletes every time entry matching a filter, as a job. The entries are picked from the
+// list projection when the job runs and deleted one by one through the delete handler, so
+// each deletion is decided, released and notified as if its owner deleted it. Entries the
+// decider refuses, approved ones or ones in a locked period, are skipped rather than failing
+// the job; a retried job skips what its failed run already deleted.
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use serde_json::{Value as Json, json};
+
+use crate::modules::time_entries::core::tag::Tag;
+use crate::modules::time_entries::use_cases::delete_time_entry::command::DeleteTimeEntry;
+use crate::modules::time_entries::use_cases::delete_time_entry::handler::ApplicationError;
+use crate::modules::time_entries::use_cases::list_time_entries::projection::TimeEntryRow;
+use crate::shared::application::jobs::{JobContext, JobHandler};
+use crate::shared::infrastructure::job_store::Job;
+use crate::shared::infrastructure::projection_store::ProjectionStore;
+use crate::shell::state::AppState;
+
+pub const JOB_KIND: &str = "time_entries.bulk_delete";
+
+/// Which entries a bulk delete removes; every criterion given must match.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BulkDeleteFilter {
+    pub user_id: Option<String>,
+    /// Entries started at or after this instant.
+    pub from: Option<i64>,
+    /// Entries started before this instant.
+    pub to: Option<i64>,
+    pub tag: Option<Tag>,
+}
+
+impl BulkDeleteFilter {
+    /// Without any criterion the filter matches every entry, which is never meant.
+    pub fn is_empty(&self) -> bool {
+        self.user_id.is_none() && self.from.is_none() && self.to.is_none() && self.tag.is_none()
+    }
+
+    /// Entries not started yet only match filters without a range.
+    pub fn matches(&self, row: &TimeEntryRow) -> bool {
+        let in_range = match (row.started_at, self.from, self.to) {
+            (_, None, None) => true,
+            (None, _, _) => false,
+            (Some(started_at), from, to) => {
+                from.is_none_or(|from| started_at >= from) && to.is_none_or(|to| started_at < to)
+            }
+        };
+        row.deleted_at.is_none()
+            && in_range
+            && self
+                .user_id
+                .as_ref()
+                .is_none_or(|user_id| *user_id == row.user_id)
+            && self
+                .tag
+                .as_ref()
+                .is_none_or(|tag| row.tag_ids.contains(tag))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BulkDeletePayload {
+    pub filter: BulkDeleteFilter,
+    pub tenant_id: String,
+    /// The admin who confirmed the bulk delete, recorded as the deleter of every entry.
+    pub requested_by: String,
+}
+
+/// The ids of the entries in the list projection `filter` matches, in id order.
+pub async fn matching_ids(
+    state: &AppState,
+    filter: &BulkDeleteFilter,
+) -> anyhow::Result<Vec<String>> {
+    let mut ids: Vec<String> = state
+        .list_time_entries_handler
+        .store()
+        .state()
+        .await?
+        .unwrap_or_default()
+        .rows
+        .into_values()
+        .filter(|row| filter.matches(row))
+        .map(|row| row.time_entry_id)
+        .collect();
+    ids.sort();
+    Ok(ids)
+}
+
+#[derive(Clone)]
+pub struct BulkDeleteTimeEntriesJob {
+    state: AppState,
+}
+
+impl BulkDeleteTimeEntriesJob {
+    pub fn new(state: AppState) -> Self {
+        Self { state }
+    }
+}
+
+#[async_trait]
+impl JobHandler for BulkDeleteTimeEntriesJob {
+    async fn run(&self, job: &Job, ctx: &JobContext<'_>) -> anyhow::Result<Json> {
+        let payload: BulkDeletePayload = serde_json::from_value(job.payload.clone())?;
+        let ids = matching_ids(&self.state, &payload.filter).await?;
+        let total = ids.len() as u64;
+        let (mut deleted, mut skipped) = (0, 0);
+        for (done, time_entry_id) in ids.into_iter().enumerate() {
+            ctx.report_progress(done as u64, Some(total)).await?;
+            let stream_id = self
+                .state
+                .stream_naming
+                .time_entry(Some(&payload.tenant_id), &time_entry_id);
+            let command =
+                DeleteTimeEntry::new(time_entry_id.into(), payload.requested_by.clone().into());
+            match self
+                .state
+                .delete_time_entry_handler
+                .handle_at_version(&payload.tenant_id, &stream_id, None, command)
+                .await
+            {
+                Ok(()) => deleted += 1,
+                Err(ApplicationError::Domain(_)) => skipped += 1,
+                Err(e) => return Err(e.into()),
+            }
+        }
+        ctx.report_progress(total, Some(total)).await?;
+        Ok(json!({ "matched": total, "deleted": deleted, "skipped": skipped }))
+    }
+}
+
+#[cfg(test)]
+mod bulk_delete_job_tests {
+    use super::*;
+    use crate::modules::time_entries::core::events::TimeEntryEvent;
+    use crate::modules::time_entries::use_cases::list_time_entries::projection::{
+        ListTimeEntriesState, TimeEntryStatus,
+    };
+    use crate::modules::time_entries::use_cases::set_started_at::command::SetStartedAt;
+    use crate::shared::application::jobs::{JobRunner, RetryPolicy};
+    use crate::shared::infrastructure::event_store::EventStore;
+    use crate::shared::infrastructure::event_store::in_memory::InMemoryEventStore;
+    use crate::shared::infrastructure::job_store::in_memory::InMemoryJobStore;
+    use crate::shared::infrastructure::job_store::{JobStatus, JobStore};
+    use crate::shared::infrastructure::projection_store::in_memory::InMemoryProjectionStore;
+    use crate::tests::fixtures::tags::make_test_app_state_with;
+    use rstest::rstest;
+    use std::sync::Arc;
+
+    fn row(time_entry_id: &str, user_id: &str, started_at: Option<i64>) -> TimeEntryRow {
+        TimeEntryRow {
+            time_entry_id: time_entry_id.to_string(),
+            user_id: user_id.to_string(),
+            started_at,
+            ended_at: None,
+            tag_ids: vec![],
+            status: TimeEntryStatus::Draft,
+            created_at: 0,
+            created_by: user_id.to_string(),
+            updated_at: 0,
+            updated_by: user_id.to_string(),
+            deleted_at: None,
+            hourly_rate: None,
+            last_event_id: None,
+            breaks: vec![],
+        }
+    }
+
+    #[rstest]
+    #[case::user(BulkDeleteFilter { user_id: Some("u-1".into()), ..Default::default() }, true)]
+    #[case::other_user(BulkDeleteFilter { user_id: Some("u-2".into()), ..Default::default() }, false)]
+    #[case::in_range(BulkDeleteFilter { from: Some(1_000), to: Some(2_000), ..Default::default() }, true)]
+    #[case::range_end_exclusive(BulkDeleteFilter { to: Some(1_000), ..Default::default() }, false)]
+    #[case::tag(BulkDeleteFilter { tag: Some(Tag::parse("dev").unwrap()), ..Default::default() }, true)]
+    #[case::other_tag(BulkDeleteFilter { tag: Some(Tag::parse("ops").unwrap()), ..Default::default() }, false)]
+    fn it_should_match_rows_meeting_every_criterion(
+        #[case] filter: BulkDeleteFilter,
+        #[case] expected: bool,
+    ) {
+        let mut row = row("te-1", "u-1", Some(1_000));
+        row.tag_ids = vec![Tag::parse("dev").unwrap()];
+
+        assert_eq!(filter.matches(&row), expected);
+    }
+
+    #[rstest]
+    fn it_should_not_match_deleted_or_unstarted_rows_by_range() {
+        let filter = BulkDeleteFilter {
+            from: Some(0),
+            ..Default::default()
+        };
+        let mut deleted = row("te-1", "u-1", Some(1_000));
+        deleted.deleted_at = Some(2_000);
+
+        assert!(!filter.matches(&deleted));
+        assert!(!filter.matches(&row("te-2", "u-1", None)));
+        assert!(BulkDeleteFilter::default().is_empty());
+    }
+
+    async fn start_entry(state: &AppState, time_entry_id: &str, user_id: &str) {
+        state
+            .set_started_at_handler
+            .handle(
+                &format!("TimeEntry-{time_entry_id}"),
+                SetStartedAt {
+                    time_entry_id: time_entry_id.into(),
+                    user_id: user_id.into(),
+                    started_at: 1_000,
+                    updated_at: 1_000,
+                    updated_by: user_id.into(),
+                    rounding: None,
+                },
+            )
+            .await
+            .unwrap();
+    }
+
+    async fn run(state: &AppState, payload: Json) -> Job {
+        let job_store = InMemoryJobStore::new();
+        let job = Job::queued(JOB_KIND, payload, 0);
+        job_store.enqueue(job.clone()).await.unwrap();
+        JobRunner::new(job_store.clone())
+            .with_handler(
+                JOB_KIND,
+                Arc::new(BulkDeleteTimeEntriesJob::new(state.clone())),
+            )
+            .with_retry_policy(RetryPolicy {
+                max_attempts: 1,
+                ..RetryPolicy::default()
+            })
+            .run_due()
+            .await
+            .unwrap();
+        job_store.get(&job.job_id).await.unwrap().unwrap()
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_delete_the_matching_entries_and_skip_unknown_ones() {
+        let event_store = InMemoryEventStore::<TimeEntryEvent>::new();
+        let projection_store = InMemoryProjectionStore::<ListTimeEntriesState>::new();
+        let state = make_test_app_state_with(event_store.clone(), projection_store.clone());
+        start_entry(&state, "te-1", "u-1").await;
+        start_entry(&state, "te-2", "u-2").await;
+        let mut list = ListTimeEntriesState::default();
+        // `te-3` is in the projection only, so the decider finds no entry to delete.
+        for row in [
+            row("te-1", "u-1", Some(1_000)),
+            row("te-2", "u-2", Some(1_000)),
+            row("te-3", "u-1", Some(1_000)),
+        ] {
+            list.rows.insert(row.time_entry_id.clone(), row);
+        }
+        projection_store.save(list, 1).await.unwrap();
+        let payload = BulkDeletePayload {
+            filter: BulkDeleteFilter {
+                user_id: Some("u-1".into()),
+                ..Default::default()
+            },
+            tenant_id: "tenant-test".to_string(),
+            requested_by: "admin-1".to_string(),
+        };
+
+        let job = run(&state, serde_json::to_value(payload).unwrap()).await;
+
+        assert_eq!(job.status, JobStatus::Succeeded);
+        assert_eq!(
+            job.result,
+            Some(json!({ "matched": 2, "deleted": 1, "skipped": 1 }))
+        );
+        let deleted = event_store.load("TimeEntry-te-1").await.unwrap().events;
+        assert!(matches!(
+            deleted.last(),
+            Some(TimeEntryEvent::TimeEntryDeletedV1(e)) if e.deleted_by.as_str() == "admin-1"
+        ));
+        let kept = event_store.load("TimeEntry-te-2").await.unwrap().events;
+        assert!(!matches!(
+            kept.last(),
+            Some(TimeEntryEvent::TimeEntryDeletedV1(_))
+        ));
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_fail_on_an_invalid_payload() {
+        let state = make_test_app_state_with(
+            InMemoryEventStore::<TimeEntryEvent>::new(),
+            InMemoryProjectionStore::<ListTimeEntriesState>::new(),
+        );
+
+        let job = run(&state, json!({})).await;
+
+        assert_eq!(job.status, JobStatus::Failed);
+        assert!(job.last_error.unwrap().contains("filter"));
+    }
+}
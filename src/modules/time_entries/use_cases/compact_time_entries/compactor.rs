@@ -0,0 +1,352 @@
+// Compacts the streams of time entries deleted longer ago than the retention.
+//
+// Deletion is the only terminal state of an entry: archiving moves list rows to cold storage
+// but leaves their streams as they are. A compacted stream keeps a single
+// `TimeEntryCompactedV1` tombstone, which folds back into the deleted state, and its events go
+// to cold storage first under `compacted/{stream_id}.jsonl`, as the event archiver writes
+// them. A stream is only rewritten once its archive copy reads back whole, once every
+// projection has processed its deletion, and while it is still at the version it was read at;
+// a stream failing a check is reported as skipped and tried again on the next run.
+
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use crate::modules::time_entries::core::events::TimeEntryEvent;
+use crate::modules::time_entries::core::events::v1::time_entry_compacted::TimeEntryCompactedV1;
+use crate::modules::time_entries::core::evolve::evolve;
+use crate::modules::time_entries::core::state::TimeEntryState;
+use crate::shared::infrastructure::cold_storage::ColdStorage;
+use crate::shared::infrastructure::event_archiver::ArchivedEvent;
+use crate::shared::infrastructure::event_store::{
+    EventLog, EventStoreError, SharedEventLog, StoredEvent,
+};
+
+/// What a compaction did, or would do on a dry run.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct CompactionReport {
+    pub dry_run: bool,
+    /// The streams compacted, in id order.
+    pub stream_ids: Vec<String>,
+    /// Events removed from hot storage, not counting the tombstones written.
+    pub events_removed: usize,
+    /// Streams due for compaction that a projection has yet to process, or that changed while
+    /// they were compacted.
+    pub skipped: Vec<String>,
+}
+
+pub struct TimeEntryCompactor<TColdStorage> {
+    event_store: SharedEventLog<TimeEntryEvent>,
+    cold_storage: TColdStorage,
+    retention_ms: i64,
+}
+
+impl<TColdStorage: ColdStorage> TimeEntryCompactor<TColdStorage> {
+    pub fn new(
+        event_store: impl EventLog<TimeEntryEvent> + 'static,
+        cold_storage: TColdStorage,
+        retention_ms: i64,
+    ) -> Self {
+        Self {
+            event_store: Arc::new(event_store),
+            cold_storage,
+            retention_ms,
+        }
+    }
+
+    pub fn archive_key(stream_id: &str) -> String {
+        format!("compacted/{stream_id}.jsonl")
+    }
+
+    /// Compacts every stream deleted before `now` minus the retention whose events all lie
+    /// below `projected_below`, the lowest checkpoint of the projections reading them. A dry
+    /// run writes nothing and reports what would be compacted.
+    pub async fn compact(
+        &self,
+        now: i64,
+        projected_below: u64,
+        dry_run: bool,
+    ) -> anyhow::Result<CompactionReport> {
+        let mut streams: BTreeMap<String, Vec<StoredEvent<TimeEntryEvent>>> = BTreeMap::new();
+        for stored in self.event_store.load_all_from(0).await? {
+            streams
+                .entry(stored.stream_id.clone())
+                .or_default()
+                .push(stored);
+        }
+
+        let mut report = CompactionReport {
+            dry_run,
+            ..CompactionReport::default()
+        };
+        for (stream_id, stored) in streams {
+            // A single event has nothing to compact, and a compacted stream is that tombstone.
+            let Some(last) = stored.last().filter(|_| stored.len() > 1) else {
+                continue;
+            };
+            let state = stored.iter().fold(TimeEntryState::None, |state, stored| {
+                evolve(state, stored.event.clone())
+            });
+            let TimeEntryState::Deleted {
+                time_entry_id,
+                user_id,
+                deleted_at,
+                deleted_by,
+            } = state
+            else {
+                continue;
+            };
+            if deleted_at > now - self.retention_ms {
+                continue;
+            }
+            if last.global_position >= projected_below {
+                report.skipped.push(stream_id);
+                continue;
+            }
+            let (version, removed) = (last.stream_version, stored.len() - 1);
+            if dry_run {
+                report.events_removed += removed;
+                report.stream_ids.push(stream_id);
+                continue;
+            }
+
+            let archive_key = Self::archive_key(&stream_id);
+            let lines: Vec<String> = stored
+                .into_iter()
+                .map(|stored| {
+                    serde_json::to_string(&ArchivedEvent::from(stored))
+                        .expect("events serialize to JSON")
+                })
+                .collect();
+            self.cold_storage
+                .write_lines(&archive_key, lines.clone())
+                .await?;
+            if self.cold_storage.read_lines(&archive_key).await? != lines {
+                anyhow::bail!("the archive copy of {stream_id} did not read back whole");
+            }
+
+            let tombstone = TimeEntryEvent::TimeEntryCompactedV1(TimeEntryCompactedV1 {
+                time_entry_id,
+                user_id,
+                deleted_at,
+                deleted_by,
+                compacted_at: now,
+                compacted_version: version,
+                archive_key,
+            });
+            match self
+                .event_store
+                .compact(&stream_id, version, tombstone)
+                .await
+            {
+                Ok(_) => {
+                    report.events_removed += removed;
+                    report.stream_ids.push(stream_id);
+                }
+                Err(EventStoreError::VersionMismatch { .. }) => report.skipped.push(stream_id),
+                Err(e) => return Err(e.into()),
+            }
+        }
+        Ok(report)
+    }
+}
+
+#[cfg(test)]
+mod time_entry_compactor_tests {
+    use super::*;
+    use crate::modules::time_entries::core::events::v1::time_entry_deleted::TimeEntryDeletedV1;
+    use crate::modules::time_entries::core::events::v1::time_entry_initiated::TimeEntryInitiatedV1;
+    use crate::modules::time_entries::core::events::v1::time_entry_start_set::TimeEntryStartSetV1;
+    use crate::shared::infrastructure::cold_storage::in_memory::InMemoryColdStorage;
+    use crate::shared::infrastructure::event_store::EventStore;
+    use crate::shared::infrastructure::event_store::in_memory::InMemoryEventStore;
+    use rstest::rstest;
+
+    const DAY_MS: i64 = 24 * 60 * 60 * 1000;
+    const NOW: i64 = 100 * DAY_MS;
+
+    fn history(time_entry_id: &str, deleted_at: Option<i64>) -> Vec<TimeEntryEvent> {
+        let mut events = vec![
+            TimeEntryEvent::TimeEntryInitiatedV1(TimeEntryInitiatedV1 {
+                time_entry_id: time_entry_id.into(),
+                user_id: "u1".into(),
+                created_at: 1,
+                created_by: "u1".into(),
+            }),
+            TimeEntryEvent::TimeEntryStartSetV1(TimeEntryStartSetV1 {
+                time_entry_id: time_entry_id.into(),
+                started_at: 2,
+                raw_started_at: None,
+                updated_at: 2,
+                updated_by: "u1".into(),
+            }),
+        ];
+        if let Some(deleted_at) = deleted_at {
+            events.push(TimeEntryEvent::TimeEntryDeletedV1(TimeEntryDeletedV1 {
+                time_entry_id: time_entry_id.into(),
+                deleted_at,
+                deleted_by: "admin-1".into(),
+            }));
+        }
+        events
+    }
+
+    /// An entry deleted long ago, one deleted yesterday and one still live.
+    async fn event_store() -> InMemoryEventStore<TimeEntryEvent> {
+        let event_store = InMemoryEventStore::new();
+        for (time_entry_id, deleted_at) in [
+            ("te-old", Some(DAY_MS)),
+            ("te-recent", Some(NOW - DAY_MS)),
+            ("te-live", None),
+        ] {
+            event_store
+                .append(
+                    &format!("TimeEntry-{time_entry_id}"),
+                    0,
+                    &history(time_entry_id, deleted_at),
+                )
+                .await
+                .unwrap();
+        }
+        event_store
+    }
+
+    fn compactor(
+        event_store: &InMemoryEventStore<TimeEntryEvent>,
+        cold_storage: &InMemoryColdStorage,
+    ) -> TimeEntryCompactor<InMemoryColdStorage> {
+        TimeEntryCompactor::new(event_store.clone(), cold_storage.clone(), 30 * DAY_MS)
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_compact_streams_deleted_beyond_retention_to_a_tombstone() {
+        let event_store = event_store().await;
+        let cold_storage = InMemoryColdStorage::new();
+
+        let report = compactor(&event_store, &cold_storage)
+            .compact(NOW, u64::MAX, false)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            report,
+            CompactionReport {
+                dry_run: false,
+                stream_ids: vec!["TimeEntry-te-old".to_string()],
+                events_removed: 2,
+                skipped: vec![],
+            }
+        );
+        let stream = event_store.load("TimeEntry-te-old").await.unwrap();
+        assert_eq!(stream.version, 1);
+        assert!(matches!(
+            &stream.events[0],
+            TimeEntryEvent::TimeEntryCompactedV1(e)
+                if e.compacted_version == 3 && e.archive_key == "compacted/TimeEntry-te-old.jsonl"
+        ));
+        let archived = cold_storage
+            .read_lines("compacted/TimeEntry-te-old.jsonl")
+            .await
+            .unwrap();
+        assert_eq!(archived.len(), 3);
+        assert_eq!(
+            event_store
+                .load("TimeEntry-te-recent")
+                .await
+                .unwrap()
+                .version,
+            3
+        );
+        assert_eq!(
+            event_store.load("TimeEntry-te-live").await.unwrap().version,
+            2
+        );
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_only_report_on_a_dry_run() {
+        let event_store = event_store().await;
+        let cold_storage = InMemoryColdStorage::new();
+
+        let report = compactor(&event_store, &cold_storage)
+            .compact(NOW, u64::MAX, true)
+            .await
+            .unwrap();
+
+        assert!(report.dry_run);
+        assert_eq!(report.stream_ids, vec!["TimeEntry-te-old"]);
+        assert_eq!(report.events_removed, 2);
+        assert_eq!(
+            event_store.load("TimeEntry-te-old").await.unwrap().version,
+            3
+        );
+        assert!(cold_storage.keys().await.is_empty());
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_skip_streams_whose_deletion_is_not_projected_yet() {
+        let event_store = event_store().await;
+        let cold_storage = InMemoryColdStorage::new();
+
+        // te-old's deletion is at global position 2.
+        let report = compactor(&event_store, &cold_storage)
+            .compact(NOW, 2, false)
+            .await
+            .unwrap();
+
+        assert_eq!(report.stream_ids, Vec::<String>::new());
+        assert_eq!(report.skipped, vec!["TimeEntry-te-old"]);
+        assert_eq!(
+            event_store.load("TimeEntry-te-old").await.unwrap().version,
+            3
+        );
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_leave_compacted_streams_alone_and_keep_them_deleted() {
+        let event_store = event_store().await;
+        let cold_storage = InMemoryColdStorage::new();
+        let compactor = compactor(&event_store, &cold_storage);
+        compactor.compact(NOW, u64::MAX, false).await.unwrap();
+
+        let again = compactor
+            .compact(NOW + DAY_MS, u64::MAX, false)
+            .await
+            .unwrap();
+
+        assert!(again.stream_ids.is_empty());
+        let state = event_store
+            .load("TimeEntry-te-old")
+            .await
+            .unwrap()
+            .events
+            .into_iter()
+            .fold(TimeEntryState::None, evolve);
+        assert!(matches!(
+            state,
+            TimeEntryState::Deleted { deleted_at, .. } if deleted_at == DAY_MS
+        ));
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_not_compact_when_the_archive_copy_cannot_be_written() {
+        let event_store = event_store().await;
+        let cold_storage = InMemoryColdStorage::new();
+        cold_storage.toggle_offline();
+
+        let result = compactor(&event_store, &cold_storage)
+            .compact(NOW, u64::MAX, false)
+            .await;
+
+        assert!(result.is_err());
+        assert_eq!(
+            event_store.load("TimeEntry-te-old").await.unwrap().version,
+            3
+        );
+    }
+}
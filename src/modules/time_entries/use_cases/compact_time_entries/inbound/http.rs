@@ -0,0 +1,105 @@
+use axum::{
+    Json,
+    extract::{State, rejection::JsonRejection},
+    http::StatusCode,
+    response::IntoResponse,
+};
+use chrono::Utc;
+
+use crate::modules::time_entries::use_cases::compact_time_entries::job::{
+    CompactPayload, JOB_KIND,
+};
+use crate::shared::infrastructure::job_store::Job;
+use crate::shared::infrastructure::request_context::RequestContext;
+use crate::shell::jobs;
+use crate::shell::state::AppState;
+
+/// POST /admin/time-entries/compact — starts a job shrinking the streams of entries deleted
+/// more than `retention_days` ago to a tombstone, their events kept in cold storage. With
+/// `dry_run` the job only reports what it would compact. Admins only.
+pub async fn handle(
+    State(state): State<AppState>,
+    request_ctx: RequestContext,
+    body: Result<Json<CompactPayload>, JsonRejection>,
+) -> impl IntoResponse {
+    if !request_ctx.principal().can_administer() {
+        return StatusCode::FORBIDDEN.into_response();
+    }
+    let Ok(Json(payload)) = body else {
+        return StatusCode::UNPROCESSABLE_ENTITY.into_response();
+    };
+    let payload = serde_json::to_value(payload).expect("compact payloads serialize to JSON");
+    let job = Job::queued(JOB_KIND, payload, Utc::now().timestamp_millis());
+    jobs::accept(&state, job).await
+}
+
+#[cfg(test)]
+mod compact_time_entries_http_inbound_tests {
+    use super::*;
+    use crate::shared::infrastructure::job_store::{JobStatus, JobStore};
+    use crate::tests::fixtures::tags::make_test_app_state;
+    use axum::{
+        Router,
+        body::Body,
+        http::{Request, header},
+        routing::post,
+    };
+    use rstest::rstest;
+    use tower::ServiceExt;
+
+    fn request(role: &str, body: &str) -> Request<Body> {
+        Request::post("/admin/time-entries/compact")
+            .header("x-user-id", "admin-1")
+            .header("x-tenant-id", "tenant-test")
+            .header("x-user-role", role)
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(Body::from(body.to_string()))
+            .unwrap()
+    }
+
+    fn app(state: AppState) -> Router {
+        Router::new()
+            .route("/admin/time-entries/compact", post(handle))
+            .with_state(state)
+    }
+
+    #[rstest]
+    #[case::dry_run(r#"{"retention_days":365,"dry_run":true}"#, true)]
+    #[case::compacting(r#"{"retention_days":365}"#, false)]
+    #[tokio::test]
+    async fn it_should_queue_a_compaction_job(#[case] body: &str, #[case] dry_run: bool) {
+        let state = make_test_app_state();
+
+        let response = app(state.clone())
+            .oneshot(request("admin", body))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::ACCEPTED);
+        let location = response.headers()[header::LOCATION].to_str().unwrap();
+        let job_id = location.trim_start_matches("/admin/jobs/");
+        let job = state.job_store.get(job_id).await.unwrap().unwrap();
+        assert_eq!(job.kind, JOB_KIND);
+        assert_eq!(job.status, JobStatus::Queued);
+        assert_eq!(
+            job.payload,
+            serde_json::json!({ "retention_days": 365, "dry_run": dry_run })
+        );
+    }
+
+    #[rstest]
+    #[case::employee("employee", r#"{"retention_days":365}"#, StatusCode::FORBIDDEN)]
+    #[case::missing_retention("admin", r#"{"dry_run":true}"#, StatusCode::UNPROCESSABLE_ENTITY)]
+    #[tokio::test]
+    async fn it_should_reject_requests_it_cannot_queue(
+        #[case] role: &str,
+        #[case] body: &str,
+        #[case] expected: StatusCode,
+    ) {
+        let response = app(make_test_app_state())
+            .oneshot(request(role, body))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), expected);
+    }
+}
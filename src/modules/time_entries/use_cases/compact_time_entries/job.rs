@@ -0,0 +1,211 @@
+// Compacts the streams of long-deleted time entries as a job; a dry run leaves the event store
+// as it is and answers what a real run would compact. Compaction waits for the slowest
+// projection reading time entry events, so no projection misses a deletion it replaces.
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use serde_json::Value as Json;
+
+use crate::modules::time_entries::use_cases::compact_time_entries::compactor::TimeEntryCompactor;
+use crate::shared::application::jobs::{JobContext, JobHandler};
+use crate::shared::infrastructure::job_store::Job;
+use crate::shared::infrastructure::projection_store::ProjectionStore;
+use crate::shell::state::AppState;
+
+pub const JOB_KIND: &str = "time_entries.compact";
+
+const DAY_MS: i64 = 24 * 60 * 60 * 1000;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CompactPayload {
+    /// Entries deleted more than this many days ago are compacted.
+    pub retention_days: u32,
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+#[derive(Clone)]
+pub struct CompactTimeEntriesJob {
+    state: AppState,
+}
+
+impl CompactTimeEntriesJob {
+    pub fn new(state: AppState) -> Self {
+        Self { state }
+    }
+
+    /// The lowest checkpoint of the projections that act on deletions.
+    async fn projected_below(&self) -> anyhow::Result<u64> {
+        let list = self.state.list_time_entries_handler.store().checkpoint();
+        let comments = self.state.time_entry_comments_handler.store().checkpoint();
+        let attachments = self
+            .state
+            .time_entry_attachments_handler
+            .store()
+            .checkpoint();
+        Ok(list.await?.min(comments.await?).min(attachments.await?))
+    }
+}
+
+#[async_trait]
+impl JobHandler for CompactTimeEntriesJob {
+    async fn run(&self, job: &Job, _ctx: &JobContext<'_>) -> anyhow::Result<Json> {
+        let payload: CompactPayload = serde_json::from_value(job.payload.clone())?;
+        let compactor = TimeEntryCompactor::new(
+            self.state.event_store.clone(),
+            self.state.cold_storage.clone(),
+            i64::from(payload.retention_days) * DAY_MS,
+        );
+        let report = compactor
+            .compact(
+                chrono::Utc::now().timestamp_millis(),
+                self.projected_below().await?,
+                payload.dry_run,
+            )
+            .await?;
+        Ok(serde_json::to_value(report)?)
+    }
+}
+
+#[cfg(test)]
+mod compact_job_tests {
+    use super::*;
+    use crate::modules::time_entries::core::events::TimeEntryEvent;
+    use crate::modules::time_entries::core::events::v1::time_entry_deleted::TimeEntryDeletedV1;
+    use crate::modules::time_entries::core::events::v1::time_entry_initiated::TimeEntryInitiatedV1;
+    use crate::modules::time_entries::use_cases::list_time_entries::projection::ListTimeEntriesState;
+    use crate::modules::time_entries::use_cases::time_entry_attachments::projection::TimeEntryAttachmentsState;
+    use crate::modules::time_entries::use_cases::time_entry_comments::projection::TimeEntryCommentsState;
+    use crate::shared::application::jobs::{JobRunner, RetryPolicy};
+    use crate::shared::infrastructure::event_store::EventStore;
+    use crate::shared::infrastructure::job_store::in_memory::InMemoryJobStore;
+    use crate::shared::infrastructure::job_store::{JobStatus, JobStore};
+    use crate::tests::fixtures::tags::make_test_app_state;
+    use rstest::rstest;
+    use serde_json::json;
+    use std::sync::Arc;
+
+    /// An app whose only entry was deleted at the epoch, with every projection caught up to
+    /// `checkpoint`.
+    async fn deleted_entry(checkpoint: u64) -> AppState {
+        let state = make_test_app_state();
+        state
+            .event_store
+            .append(
+                "TimeEntry-te-1",
+                0,
+                &[
+                    TimeEntryEvent::TimeEntryInitiatedV1(TimeEntryInitiatedV1 {
+                        time_entry_id: "te-1".into(),
+                        user_id: "u1".into(),
+                        created_at: 0,
+                        created_by: "u1".into(),
+                    }),
+                    TimeEntryEvent::TimeEntryDeletedV1(TimeEntryDeletedV1 {
+                        time_entry_id: "te-1".into(),
+                        deleted_at: 0,
+                        deleted_by: "u1".into(),
+                    }),
+                ],
+            )
+            .await
+            .unwrap();
+        for (_, partition) in state.list_time_entries_handler.store().partitions() {
+            partition
+                .save(ListTimeEntriesState::default(), checkpoint)
+                .await
+                .unwrap();
+        }
+        state
+            .time_entry_comments_handler
+            .store()
+            .save(TimeEntryCommentsState::default(), checkpoint)
+            .await
+            .unwrap();
+        state
+            .time_entry_attachments_handler
+            .store()
+            .save(TimeEntryAttachmentsState::default(), checkpoint)
+            .await
+            .unwrap();
+        state
+    }
+
+    async fn run(state: &AppState, payload: Json) -> Job {
+        let job_store = InMemoryJobStore::new();
+        let job = Job::queued(JOB_KIND, payload, 0);
+        job_store.enqueue(job.clone()).await.unwrap();
+        JobRunner::new(job_store.clone())
+            .with_handler(
+                JOB_KIND,
+                Arc::new(CompactTimeEntriesJob::new(state.clone())),
+            )
+            .with_retry_policy(RetryPolicy {
+                max_attempts: 1,
+                ..RetryPolicy::default()
+            })
+            .run_due()
+            .await
+            .unwrap();
+        job_store.get(&job.job_id).await.unwrap().unwrap()
+    }
+
+    #[rstest]
+    #[case::dry_run(true, 2)]
+    #[case::compacting(false, 1)]
+    #[tokio::test]
+    async fn it_should_report_the_streams_it_compacts(
+        #[case] dry_run: bool,
+        #[case] version_after: i64,
+    ) {
+        let state = deleted_entry(2).await;
+
+        let job = run(&state, json!({ "retention_days": 30, "dry_run": dry_run })).await;
+
+        assert_eq!(job.status, JobStatus::Succeeded);
+        assert_eq!(
+            job.result,
+            Some(json!({
+                "dry_run": dry_run,
+                "stream_ids": ["TimeEntry-te-1"],
+                "events_removed": 1,
+                "skipped": [],
+            }))
+        );
+        assert_eq!(
+            state
+                .event_store
+                .load("TimeEntry-te-1")
+                .await
+                .unwrap()
+                .version,
+            version_after
+        );
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_wait_for_the_slowest_projection() {
+        let state = deleted_entry(2).await;
+        state
+            .time_entry_comments_handler
+            .store()
+            .save(TimeEntryCommentsState::default(), 1)
+            .await
+            .unwrap();
+
+        let job = run(&state, json!({ "retention_days": 30 })).await;
+
+        assert_eq!(job.status, JobStatus::Succeeded);
+        assert_eq!(job.result.unwrap()["skipped"], json!(["TimeEntry-te-1"]));
+        assert_eq!(
+            state
+                .event_store
+                .load("TimeEntry-te-1")
+                .await
+                .unwrap()
+                .version,
+            2
+        );
+    }
+}
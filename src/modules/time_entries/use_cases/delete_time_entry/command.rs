@@ -0,0 +1,30 @@
+use crate::modules::time_entries::core::policies::RoundedCommand;
+use crate::shared::application::server_time::StampedCommand;
+use crate::shared::core::primitives::{TimeEntryId, Timestamp, UserId};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeleteTimeEntry {
+    pub time_entry_id: TimeEntryId,
+    /// Stamped by the handler from its clock.
+    pub deleted_at: i64,
+    pub deleted_by: UserId,
+}
+
+impl DeleteTimeEntry {
+    /// `user_id` deletes the entry; the handler stamps `deleted_at`.
+    pub fn new(time_entry_id: TimeEntryId, user_id: UserId) -> Self {
+        Self {
+            time_entry_id,
+            deleted_at: 0,
+            deleted_by: user_id,
+        }
+    }
+}
+
+impl StampedCommand for DeleteTimeEntry {
+    fn stamp(&mut self, now: Timestamp) {
+        self.deleted_at = now.as_millis();
+    }
+}
+
+impl RoundedCommand for DeleteTimeEntry {}
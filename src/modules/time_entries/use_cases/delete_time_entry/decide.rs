@@ -0,0 +1,143 @@
+use crate::modules::time_entries::core::events::TimeEntryEvent;
+use crate::modules::time_entries::core::events::v1::time_entry_deleted::TimeEntryDeletedV1;
+use crate::modules::time_entries::core::evolve::evolve;
+use crate::modules::time_entries::core::intents::TimeEntryIntent;
+use crate::modules::time_entries::core::state::TimeEntryState;
+use crate::modules::time_entries::use_cases::delete_time_entry::command::DeleteTimeEntry;
+use crate::modules::time_entries::use_cases::delete_time_entry::decision::{DecideError, Decision};
+use crate::shared::core::decider::Decider;
+
+/// Drafts and registered entries can be deleted; approved entries are signed off and stay.
+pub fn decide_delete_time_entry(state: &TimeEntryState, command: DeleteTimeEntry) -> Decision {
+    match state {
+        TimeEntryState::None => Decision::Rejected {
+            reason: DecideError::NotFound,
+        },
+        TimeEntryState::Draft { .. } | TimeEntryState::Registered { .. } => Decision::Accepted {
+            events: vec![TimeEntryEvent::TimeEntryDeletedV1(TimeEntryDeletedV1 {
+                time_entry_id: command.time_entry_id.clone(),
+                deleted_at: command.deleted_at,
+                deleted_by: command.deleted_by,
+            })],
+            intents: vec![TimeEntryIntent::NotifyUser {
+                time_entry_id: command.time_entry_id,
+                occurred_at: command.deleted_at,
+            }],
+        },
+        TimeEntryState::Approved { .. } => Decision::Rejected {
+            reason: DecideError::Approved,
+        },
+        TimeEntryState::Deleted { .. } => Decision::Rejected {
+            reason: DecideError::Deleted,
+        },
+    }
+}
+
+pub struct DeleteTimeEntryDecider;
+
+impl Decider for DeleteTimeEntryDecider {
+    type State = TimeEntryState;
+    type Command = DeleteTimeEntry;
+    type Event = TimeEntryEvent;
+    type Intent = TimeEntryIntent;
+    type Error = DecideError;
+
+    fn initial_state() -> TimeEntryState {
+        TimeEntryState::None
+    }
+
+    fn evolve(state: TimeEntryState, event: TimeEntryEvent) -> TimeEntryState {
+        evolve(state, event)
+    }
+
+    fn decide(state: &TimeEntryState, command: DeleteTimeEntry) -> Decision {
+        decide_delete_time_entry(state, command)
+    }
+}
+
+#[cfg(test)]
+mod decide_delete_time_entry_tests {
+    use super::*;
+    use crate::modules::time_entries::core::time_interval::TimeInterval;
+    use rstest::{fixture, rstest};
+
+    #[fixture]
+    fn command() -> DeleteTimeEntry {
+        DeleteTimeEntry {
+            time_entry_id: "te-fixed-0001".into(),
+            deleted_at: 1_700_000_500_000,
+            deleted_by: "user-fixed-0001".into(),
+        }
+    }
+
+    fn registered() -> TimeEntryState {
+        TimeEntryState::Registered {
+            time_entry_id: "te-fixed-0001".into(),
+            user_id: "user-fixed-0001".into(),
+            interval: TimeInterval::new(1_000, 2_000).unwrap(),
+            breaks: vec![],
+            tag_ids: vec![],
+            created_at: 0,
+            created_by: "user-fixed-0001".into(),
+            hourly_rate: None,
+        }
+    }
+
+    #[rstest]
+    fn it_should_delete_a_registered_entry(command: DeleteTimeEntry) {
+        match decide_delete_time_entry(&registered(), command) {
+            Decision::Accepted { events, intents } => {
+                assert_eq!(
+                    events,
+                    vec![TimeEntryEvent::TimeEntryDeletedV1(TimeEntryDeletedV1 {
+                        time_entry_id: "te-fixed-0001".into(),
+                        deleted_at: 1_700_000_500_000,
+                        deleted_by: "user-fixed-0001".into(),
+                    })]
+                );
+                assert!(matches!(&intents[..], [TimeEntryIntent::NotifyUser { .. }]));
+                let state = events.into_iter().fold(registered(), evolve);
+                assert!(matches!(state, TimeEntryState::Deleted { .. }));
+            }
+            Decision::Rejected { reason } => panic!("expected accepted, got {reason:?}"),
+        }
+    }
+
+    fn approved() -> TimeEntryState {
+        TimeEntryState::Approved {
+            time_entry_id: "te-fixed-0001".into(),
+            user_id: "user-fixed-0001".into(),
+            interval: TimeInterval::new(1_000, 2_000).unwrap(),
+            breaks: vec![],
+            tag_ids: vec![],
+            created_at: 0,
+            created_by: "user-fixed-0001".into(),
+            hourly_rate: None,
+            approved_at: 3_000,
+            approved_by: "manager-1".into(),
+        }
+    }
+
+    #[rstest]
+    #[case::missing(TimeEntryState::None, DecideError::NotFound)]
+    #[case::approved(approved(), DecideError::Approved)]
+    #[case::deleted(
+        TimeEntryState::Deleted {
+            time_entry_id: "te-fixed-0001".into(),
+            user_id: "user-fixed-0001".into(),
+            deleted_at: 1,
+            deleted_by: "user-fixed-0001".into(),
+        },
+        DecideError::Deleted
+    )]
+    fn it_should_reject_entries_that_cannot_be_deleted(
+        command: DeleteTimeEntry,
+        #[case] state: TimeEntryState,
+        #[case] expected: DecideError,
+    ) {
+        assert!(matches!(
+            decide_delete_time_entry(&state, command),
+            Decision::Rejected { reason } if reason == expected
+        ));
+    }
+}
@@ -0,0 +1,215 @@
+use crate::modules::time_entries::adapters::outbound::intent_outbox::TimeEntryIntentDispatcher;
+use crate::modules::time_entries::core::events::TimeEntryEvent;
+use crate::modules::time_entries::core::policies::Policies;
+use crate::modules::time_entries::use_cases::delete_time_entry::command::DeleteTimeEntry;
+use crate::modules::time_entries::use_cases::delete_time_entry::decide::DeleteTimeEntryDecider;
+use crate::modules::time_entries::use_cases::delete_time_entry::decision::DecideError;
+use crate::modules::time_entries::use_cases::user_time_entries::sharded_handler::{
+    PeriodLockStreams, UserShardedHandler, UserStreams,
+};
+use crate::shared::application::command_bus::CommandHandler;
+use crate::shared::application::event_sourced_handler::EventSourcedError;
+use crate::shared::infrastructure::clock::SharedClock;
+use crate::shared::infrastructure::event_bus::SharedEventBus;
+use crate::shared::infrastructure::event_store::EventStore;
+use crate::shared::infrastructure::intent_outbox::{DomainOutbox, OutboxError};
+use crate::shared::infrastructure::policy_store::SharedPolicyStore;
+use async_trait::async_trait;
+
+pub type ApplicationError = EventSourcedError<DecideError, OutboxError>;
+
+#[derive(Debug, Clone)]
+pub struct DeleteTimeEntryHandler<TEventStore, TOutbox>
+where
+    TEventStore: EventStore<TimeEntryEvent> + Send + Sync + 'static,
+    TOutbox: DomainOutbox + Send + Sync + 'static,
+{
+    inner:
+        UserShardedHandler<DeleteTimeEntryDecider, TEventStore, TimeEntryIntentDispatcher<TOutbox>>,
+}
+
+impl<TEventStore, TOutbox> DeleteTimeEntryHandler<TEventStore, TOutbox>
+where
+    TEventStore: EventStore<TimeEntryEvent> + Send + Sync + 'static,
+    TOutbox: DomainOutbox + Send + Sync + 'static,
+{
+    pub fn new(event_store: TEventStore, outbox: TOutbox) -> Self {
+        Self {
+            inner: UserShardedHandler::new(event_store, TimeEntryIntentDispatcher::new(outbox)),
+        }
+    }
+
+    /// Release the deleted entry's interval on the per-user `UserTimeEntries-{user_id}`
+    /// streams, so its time can be registered again.
+    pub fn with_user_streams(mut self, user_streams: UserStreams) -> Self {
+        self.inner = self.inner.with_user_streams(user_streams);
+        self
+    }
+
+    /// Refuse deleting entries inside a locked payroll period.
+    pub fn with_period_locks(mut self, period_locks: PeriodLockStreams) -> Self {
+        self.inner = self.inner.with_period_locks(period_locks);
+        self
+    }
+
+    /// Hold each tenant to the policies in `store`, or to `defaults` where it has none.
+    pub fn with_policies(mut self, store: SharedPolicyStore, defaults: Policies) -> Self {
+        self.inner = self.inner.with_policies(store, defaults);
+        self
+    }
+
+    /// Read the time commands are recorded at from `clock` instead of the system clock.
+    pub fn with_clock(mut self, clock: SharedClock) -> Self {
+        self.inner = self.inner.with_clock(clock);
+        self
+    }
+
+    /// Announce the appended events on `event_bus`, for the projectors to pick up.
+    pub fn with_event_bus(mut self, event_bus: SharedEventBus<TimeEntryEvent>) -> Self {
+        self.inner = self.inner.with_event_bus(event_bus);
+        self
+    }
+
+    pub async fn handle(
+        &self,
+        stream_id: &str,
+        command: DeleteTimeEntry,
+    ) -> Result<(), ApplicationError> {
+        self.inner.handle(stream_id, command).await
+    }
+
+    /// Deletes the entry under `tenant_id`'s policies, only if its stream is still at
+    /// `expected_version` when one is given.
+    pub async fn handle_at_version(
+        &self,
+        tenant_id: &str,
+        stream_id: &str,
+        expected_version: Option<i64>,
+        command: DeleteTimeEntry,
+    ) -> Result<(), ApplicationError> {
+        self.inner
+            .handle_at_version(Some(tenant_id), stream_id, expected_version, command)
+            .await
+    }
+}
+
+#[async_trait]
+impl<TEventStore, TOutbox> CommandHandler<DeleteTimeEntry>
+    for DeleteTimeEntryHandler<TEventStore, TOutbox>
+where
+    TEventStore: EventStore<TimeEntryEvent> + Send + Sync + 'static,
+    TOutbox: DomainOutbox + Send + Sync + 'static,
+{
+    type Error = ApplicationError;
+
+    async fn handle(
+        &self,
+        stream_id: &str,
+        command: DeleteTimeEntry,
+    ) -> Result<(), ApplicationError> {
+        DeleteTimeEntryHandler::handle(self, stream_id, command).await
+    }
+}
+
+#[cfg(test)]
+mod delete_time_entry_handler_tests {
+    use crate::modules::time_entries::core::events::TimeEntryEvent;
+    use crate::modules::time_entries::core::user_time_entries::{
+        UserTimeEntriesEvent, UserTimeEntriesState, evolve_user_time_entries, user_stream_id,
+    };
+    use crate::modules::time_entries::use_cases::delete_time_entry::command::DeleteTimeEntry;
+    use crate::modules::time_entries::use_cases::delete_time_entry::decision::DecideError;
+    use crate::modules::time_entries::use_cases::delete_time_entry::handler::{
+        ApplicationError, DeleteTimeEntryHandler,
+    };
+    use crate::modules::time_entries::use_cases::set_ended_at::handler::SetEndedAtHandler;
+    use crate::modules::time_entries::use_cases::set_started_at::handler::SetStartedAtHandler;
+    use crate::shared::infrastructure::event_store::in_memory::InMemoryEventStore;
+    use crate::shared::infrastructure::event_store::{EventStore, EventStoreError};
+    use crate::shared::infrastructure::intent_outbox::in_memory::InMemoryDomainOutbox;
+    use crate::tests::fixtures::commands::set_ended_at::SetEndedAtBuilder;
+    use crate::tests::fixtures::commands::set_started_at::SetStartedAtBuilder;
+    use rstest::rstest;
+    use std::sync::Arc;
+
+    const STREAM_ID: &str = "TimeEntry-te-fixed-0001";
+
+    fn delete() -> DeleteTimeEntry {
+        DeleteTimeEntry::new("te-fixed-0001".into(), "user-fixed-0001".into())
+    }
+
+    /// A registered entry, with its interval claimed on the user stream.
+    async fn registered(
+        event_store: &InMemoryEventStore<TimeEntryEvent>,
+        user_streams: &InMemoryEventStore<UserTimeEntriesEvent>,
+    ) {
+        let outbox = InMemoryDomainOutbox::new();
+        SetStartedAtHandler::new(event_store.clone(), outbox.clone())
+            .with_user_streams(Arc::new(user_streams.clone()))
+            .handle(STREAM_ID, SetStartedAtBuilder::new().build())
+            .await
+            .unwrap();
+        SetEndedAtHandler::new(event_store.clone(), outbox)
+            .with_user_streams(Arc::new(user_streams.clone()))
+            .handle(STREAM_ID, SetEndedAtBuilder::new().build())
+            .await
+            .unwrap();
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_delete_the_entry_and_release_its_claim() {
+        let event_store = InMemoryEventStore::<TimeEntryEvent>::new();
+        let user_streams = InMemoryEventStore::<UserTimeEntriesEvent>::new();
+        registered(&event_store, &user_streams).await;
+        let handler = DeleteTimeEntryHandler::new(event_store.clone(), InMemoryDomainOutbox::new())
+            .with_user_streams(Arc::new(user_streams.clone()));
+
+        handler.handle(STREAM_ID, delete()).await.unwrap();
+
+        let stream = event_store.load(STREAM_ID).await.unwrap();
+        assert!(matches!(
+            stream.events.last(),
+            Some(TimeEntryEvent::TimeEntryDeletedV1(_))
+        ));
+        let user_state = user_streams
+            .load(&user_stream_id("user-fixed-0001"))
+            .await
+            .unwrap()
+            .events
+            .into_iter()
+            .fold(UserTimeEntriesState::default(), evolve_user_time_entries);
+        assert!(user_state.intervals.is_empty());
+        assert!(matches!(
+            handler.handle(STREAM_ID, delete()).await,
+            Err(ApplicationError::Domain(DecideError::Deleted))
+        ));
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_refuse_to_delete_a_stream_that_moved_on() {
+        let event_store = InMemoryEventStore::<TimeEntryEvent>::new();
+        let user_streams = InMemoryEventStore::<UserTimeEntriesEvent>::new();
+        registered(&event_store, &user_streams).await;
+        let version = event_store.load(STREAM_ID).await.unwrap().version;
+        let handler = DeleteTimeEntryHandler::new(event_store.clone(), InMemoryDomainOutbox::new());
+
+        let result = handler
+            .handle_at_version("tenant-1", STREAM_ID, Some(version - 1), delete())
+            .await;
+
+        assert!(matches!(
+            result,
+            Err(ApplicationError::VersionConflict(EventStoreError::VersionMismatch {
+                expected,
+                actual,
+            })) if expected == version - 1 && actual == version
+        ));
+        assert_eq!(event_store.load(STREAM_ID).await.unwrap().version, version);
+        handler
+            .handle_at_version("tenant-1", STREAM_ID, Some(version), delete())
+            .await
+            .unwrap();
+    }
+}
@@ -0,0 +1,193 @@
+use axum::{
+    extract::{Path, State},
+    http::{HeaderMap, StatusCode},
+    response::IntoResponse,
+};
+
+use crate::modules::time_entries::use_cases::delete_time_entry::command::DeleteTimeEntry;
+use crate::modules::time_entries::use_cases::delete_time_entry::decision::DecideError;
+use crate::modules::time_entries::use_cases::delete_time_entry::handler::ApplicationError;
+use crate::shared::core::primitives::TimeEntryId;
+use crate::shared::infrastructure::event_store::EventStoreError;
+use crate::shared::infrastructure::request_context::RequestContext;
+use crate::shell::http::etag::if_match;
+use crate::shell::state::AppState;
+
+/// DELETE /time-entries/{id} — soft-deletes an entry: it stays in the log but is no longer
+/// found or changed. With `If-Match`, only while the entry is still at that version (412
+/// otherwise).
+pub async fn handle_delete(
+    State(state): State<AppState>,
+    request_ctx: RequestContext,
+    Path(time_entry_id): Path<String>,
+    headers: HeaderMap,
+) -> impl IntoResponse {
+    if !request_ctx
+        .principal()
+        .can_register_for(&request_ctx.user_id)
+    {
+        return StatusCode::FORBIDDEN.into_response();
+    }
+    let Ok(time_entry_id) = TimeEntryId::parse_v7(&time_entry_id) else {
+        return StatusCode::UNPROCESSABLE_ENTITY.into_response();
+    };
+    let Ok(expected_version) = if_match(&headers) else {
+        return StatusCode::PRECONDITION_FAILED.into_response();
+    };
+    // Entries of users the caller may not register for are not theirs to find.
+    if let Ok(Some((view, _))) = state
+        .list_time_entries_handler
+        .get(time_entry_id.as_str())
+        .await
+        && !request_ctx.principal().can_register_for(&view.user_id)
+    {
+        return (StatusCode::NOT_FOUND, "time entry not found").into_response();
+    }
+
+    let stream_id = state
+        .stream_naming
+        .time_entry(Some(&request_ctx.tenant_id), &time_entry_id);
+    let command = DeleteTimeEntry::new(time_entry_id, request_ctx.user_id.into());
+
+    match state
+        .delete_time_entry_handler
+        .handle_at_version(
+            &request_ctx.tenant_id,
+            &stream_id,
+            expected_version,
+            command,
+        )
+        .await
+    {
+        Ok(()) => StatusCode::NO_CONTENT.into_response(),
+        Err(ApplicationError::VersionConflict(EventStoreError::VersionMismatch { .. }))
+            if expected_version.is_some() =>
+        {
+            StatusCode::PRECONDITION_FAILED.into_response()
+        }
+        Err(ApplicationError::Domain(DecideError::NotFound | DecideError::Deleted)) => {
+            (StatusCode::NOT_FOUND, "time entry not found").into_response()
+        }
+        Err(ApplicationError::Domain(DecideError::ClockSkew(_))) => {
+            StatusCode::UNPROCESSABLE_ENTITY.into_response()
+        }
+        Err(ApplicationError::Domain(_)) => StatusCode::CONFLICT.into_response(),
+        Err(_) => StatusCode::INTERNAL_SERVER_ERROR.into_response(),
+    }
+}
+
+#[cfg(test)]
+mod delete_time_entry_http_inbound_tests {
+    use axum::{
+        Router,
+        body::Body,
+        http::{Request, StatusCode},
+        routing::delete,
+    };
+    use rstest::rstest;
+    use tower::ServiceExt;
+
+    use super::handle_delete;
+    use crate::modules::time_entries::use_cases::list_time_entries::projection::{
+        ListTimeEntriesState, TimeEntryRow, TimeEntryStatus,
+    };
+    use crate::modules::time_entries::use_cases::set_started_at::command::SetStartedAt;
+    use crate::shared::infrastructure::event_store::EventStore;
+    use crate::shared::infrastructure::event_store::in_memory::InMemoryEventStore;
+    use crate::shared::infrastructure::projection_store::ProjectionStore;
+    use crate::shared::infrastructure::projection_store::in_memory::InMemoryProjectionStore;
+    use crate::shell::state::AppState;
+    use crate::tests::fixtures::tags::{make_test_app_state, make_test_app_state_with};
+
+    /// A draft entry of `u-1`, and its stream version.
+    async fn drafted(state: &AppState, te_id: &str) -> i64 {
+        let stream_id = state.stream_naming.time_entry(Some("tenant-test"), &te_id);
+        state
+            .set_started_at_handler
+            .handle_for_tenant(
+                "tenant-test",
+                &stream_id,
+                SetStartedAt::new(te_id.into(), "u-1".into(), 1_000),
+            )
+            .await
+            .unwrap();
+        state.event_store.load(&stream_id).await.unwrap().version
+    }
+
+    async fn send(state: AppState, te_id: &str, if_match: Option<String>) -> StatusCode {
+        let mut request = Request::builder()
+            .method("DELETE")
+            .uri(format!("/time-entries/{te_id}"))
+            .header("x-user-id", "u-1")
+            .header("x-tenant-id", "tenant-test");
+        if let Some(if_match) = if_match {
+            request = request.header("if-match", if_match);
+        }
+        Router::new()
+            .route("/time-entries/{id}", delete(handle_delete))
+            .with_state(state)
+            .oneshot(request.body(Body::empty()).unwrap())
+            .await
+            .unwrap()
+            .status()
+    }
+
+    #[tokio::test]
+    async fn delete_returns_204_and_then_404() {
+        let te_id = uuid::Uuid::now_v7().to_string();
+        let state = make_test_app_state();
+        let version = drafted(&state, &te_id).await;
+
+        assert_eq!(
+            send(state.clone(), &te_id, Some(format!("\"{version}\""))).await,
+            StatusCode::NO_CONTENT
+        );
+        assert_eq!(send(state, &te_id, None).await, StatusCode::NOT_FOUND);
+    }
+
+    #[rstest]
+    #[case::stale(Some("\"1\"".to_string()), StatusCode::PRECONDITION_FAILED)]
+    #[case::unquoted(Some("2".to_string()), StatusCode::PRECONDITION_FAILED)]
+    #[case::any(Some("*".to_string()), StatusCode::NO_CONTENT)]
+    #[tokio::test]
+    async fn delete_honours_if_match(
+        #[case] if_match: Option<String>,
+        #[case] expected: StatusCode,
+    ) {
+        let te_id = uuid::Uuid::now_v7().to_string();
+        let state = make_test_app_state();
+        drafted(&state, &te_id).await;
+
+        assert_eq!(send(state, &te_id, if_match).await, expected);
+    }
+
+    #[tokio::test]
+    async fn delete_returns_404_for_entries_of_other_users() {
+        let te_id = uuid::Uuid::now_v7().to_string();
+        let projection_store = InMemoryProjectionStore::<ListTimeEntriesState>::new();
+        let mut projection = ListTimeEntriesState::default();
+        projection.rows.insert(
+            te_id.clone(),
+            TimeEntryRow {
+                time_entry_id: te_id.clone(),
+                user_id: "u-2".to_string(),
+                started_at: Some(1_000),
+                ended_at: None,
+                tag_ids: vec![],
+                status: TimeEntryStatus::Draft,
+                created_at: 0,
+                created_by: "u-2".to_string(),
+                updated_at: 0,
+                updated_by: "u-2".to_string(),
+                deleted_at: None,
+                hourly_rate: None,
+                last_event_id: None,
+                breaks: vec![],
+            },
+        );
+        projection_store.save(projection, 1).await.unwrap();
+        let state = make_test_app_state_with(InMemoryEventStore::new(), projection_store);
+
+        assert_eq!(send(state, &te_id, None).await, StatusCode::NOT_FOUND);
+    }
+}
@@ -0,0 +1,441 @@
+// Erases a forgotten user's personal data from the time entry read models.
+//
+// Every personal data field of an entry is encrypted under its owner's key, so once the key
+// is gone a rebuild projects the owner's entries with `FORGOTTEN` in place of the user id,
+// the actors, comment bodies and attachment file names. This writes the same into the read
+// models as they stand, rather than waiting for the next rebuild. Day totals of the owner
+// move to the `FORGOTTEN` user, as a rebuild would add them up.
+//
+// The owned entries are found before anything is written, and the list rows, which name the
+// owner, are written last, so a run that fails part way finds them again when repeated.
+// Writes race the projectors like the archiver's do: each store is saved only while its
+// checkpoint is the one read, and read again when it moved on.
+//
+// Archived rows in cold storage are not covered; they keep the ids until restored.
+
+use crate::modules::time_entries::use_cases::list_time_entries::projection::ListTimeEntriesState;
+use crate::modules::time_entries::use_cases::list_time_entries::queries::ListTimeEntriesCache;
+use crate::modules::time_entries::use_cases::time_entry_attachments::projection::TimeEntryAttachmentsState;
+use crate::modules::time_entries::use_cases::time_entry_comments::projection::TimeEntryCommentsState;
+use crate::modules::time_entries::use_cases::user_stats::projection::UserStatsState;
+use crate::shared::application::forget_user::ReadModelEraser;
+use crate::shared::infrastructure::event_store::crypto_shredding::FORGOTTEN;
+use crate::shared::infrastructure::projection_store::ProjectionStore;
+use async_trait::async_trait;
+use std::collections::HashSet;
+
+/// Conditional saves tried before a run gives up on a projection that keeps moving.
+const SAVE_ATTEMPTS: usize = 3;
+
+pub struct TimeEntriesEraser<TList, TStats, TComments, TAttachments> {
+    lists: Vec<TList>,
+    stats: TStats,
+    comments: TComments,
+    attachments: TAttachments,
+    query_cache: Option<ListTimeEntriesCache>,
+}
+
+impl<TList, TStats, TComments, TAttachments>
+    TimeEntriesEraser<TList, TStats, TComments, TAttachments>
+where
+    TList: ProjectionStore<ListTimeEntriesState>,
+    TStats: ProjectionStore<UserStatsState>,
+    TComments: ProjectionStore<TimeEntryCommentsState>,
+    TAttachments: ProjectionStore<TimeEntryAttachmentsState>,
+{
+    /// `lists` are the stores the list projection is written to, one per partition.
+    pub fn new(
+        lists: Vec<TList>,
+        stats: TStats,
+        comments: TComments,
+        attachments: TAttachments,
+    ) -> Self {
+        Self {
+            lists,
+            stats,
+            comments,
+            attachments,
+            query_cache: None,
+        }
+    }
+
+    pub fn with_query_cache(mut self, query_cache: ListTimeEntriesCache) -> Self {
+        self.query_cache = Some(query_cache);
+        self
+    }
+
+    /// The ids of the entries `user_id` owns, by the list rows and the stats.
+    async fn owned_entries(&self, user_id: &str) -> anyhow::Result<HashSet<String>> {
+        let mut owned = HashSet::new();
+        for list in &self.lists {
+            let state = list.state().await?.unwrap_or_default();
+            owned.extend(
+                state
+                    .rows
+                    .into_values()
+                    .filter(|row| row.user_id == user_id)
+                    .map(|row| row.time_entry_id),
+            );
+        }
+        let stats = self.stats.state().await?.unwrap_or_default();
+        owned.extend(
+            stats
+                .entries
+                .into_iter()
+                .filter(|(_, entry)| entry.user_id == user_id)
+                .map(|(time_entry_id, _)| time_entry_id),
+        );
+        Ok(owned)
+    }
+}
+
+#[async_trait]
+impl<TList, TStats, TComments, TAttachments> ReadModelEraser
+    for TimeEntriesEraser<TList, TStats, TComments, TAttachments>
+where
+    TList: ProjectionStore<ListTimeEntriesState>,
+    TStats: ProjectionStore<UserStatsState>,
+    TComments: ProjectionStore<TimeEntryCommentsState>,
+    TAttachments: ProjectionStore<TimeEntryAttachmentsState>,
+{
+    async fn erase(&self, user_id: &str) -> anyhow::Result<()> {
+        let owned = self.owned_entries(user_id).await?;
+
+        scrub(&self.comments, |state: &mut TimeEntryCommentsState| {
+            let mut changed = false;
+            for id in &owned {
+                for comment in state.comments.get_mut(id).into_iter().flatten() {
+                    changed |= forget(&mut comment.body) | forget(&mut comment.added_by);
+                }
+            }
+            changed
+        })
+        .await?;
+        scrub(
+            &self.attachments,
+            |state: &mut TimeEntryAttachmentsState| {
+                let mut changed = false;
+                for id in &owned {
+                    for attachment in state.attachments.get_mut(id).into_iter().flatten() {
+                        changed |=
+                            forget(&mut attachment.file_name) | forget(&mut attachment.added_by);
+                    }
+                }
+                changed
+            },
+        )
+        .await?;
+        scrub(&self.stats, |state: &mut UserStatsState| {
+            let mut changed = false;
+            for entry in state.entries.values_mut() {
+                if entry.user_id == user_id {
+                    changed |= forget(&mut entry.user_id);
+                }
+            }
+            if let Some(days) = state.days.remove(user_id) {
+                let forgotten = state.days.entry(FORGOTTEN.to_string()).or_default();
+                for (date, totals) in days {
+                    let day = forgotten.entry(date).or_default();
+                    day.millis += totals.millis;
+                    for (tag, millis) in totals.tag_millis {
+                        *day.tag_millis.entry(tag).or_default() += millis;
+                    }
+                }
+                changed = true;
+            }
+            changed
+        })
+        .await?;
+        for list in &self.lists {
+            scrub(list, |state: &mut ListTimeEntriesState| {
+                let mut changed = false;
+                for id in &owned {
+                    if let Some(row) = state.rows.get_mut(id) {
+                        changed |= forget(&mut row.user_id)
+                            | forget(&mut row.created_by)
+                            | forget(&mut row.updated_by);
+                    }
+                }
+                changed
+            })
+            .await?;
+        }
+
+        if let Some(query_cache) = &self.query_cache {
+            query_cache.invalidate_all().await;
+        }
+        Ok(())
+    }
+}
+
+/// Replaces `value` with `FORGOTTEN`. Returns whether it changed.
+fn forget(value: &mut String) -> bool {
+    if value == FORGOTTEN {
+        return false;
+    }
+    *value = FORGOTTEN.to_string();
+    true
+}
+
+/// Saves `store`'s state as `erase` leaves it, unless `erase` changed nothing. The state is
+/// read again whenever a projector saved in between.
+async fn scrub<P, TStore>(
+    store: &TStore,
+    mut erase: impl FnMut(&mut P) -> bool,
+) -> anyhow::Result<()>
+where
+    P: Clone + Send + Sync + 'static,
+    TStore: ProjectionStore<P>,
+{
+    for _ in 0..SAVE_ATTEMPTS {
+        let checkpoint = store.checkpoint().await?;
+        let Some(mut state) = store.state().await? else {
+            return Ok(());
+        };
+        if !erase(&mut state) || store.save_if_unchanged(state, checkpoint).await? {
+            return Ok(());
+        }
+    }
+    anyhow::bail!("a time entry read model kept changing while personal data was erased")
+}
+
+#[cfg(test)]
+mod time_entries_eraser_tests {
+    use super::*;
+    use crate::modules::time_entries::core::tag::Tag;
+    use crate::modules::time_entries::use_cases::list_time_entries::projection::{
+        TimeEntryRow, TimeEntryStatus,
+    };
+    use crate::modules::time_entries::use_cases::time_entry_attachments::projection::AttachmentRow;
+    use crate::modules::time_entries::use_cases::time_entry_comments::projection::CommentRow;
+    use crate::modules::time_entries::use_cases::user_stats::projection::{DayTotals, StatsEntry};
+    use crate::shared::infrastructure::projection_store::in_memory::InMemoryProjectionStore;
+    use chrono::NaiveDate;
+    use rstest::rstest;
+    use std::collections::{BTreeMap, HashMap};
+
+    type Eraser = TimeEntriesEraser<
+        InMemoryProjectionStore<ListTimeEntriesState>,
+        InMemoryProjectionStore<UserStatsState>,
+        InMemoryProjectionStore<TimeEntryCommentsState>,
+        InMemoryProjectionStore<TimeEntryAttachmentsState>,
+    >;
+
+    struct Stores {
+        lists: Vec<InMemoryProjectionStore<ListTimeEntriesState>>,
+        stats: InMemoryProjectionStore<UserStatsState>,
+        comments: InMemoryProjectionStore<TimeEntryCommentsState>,
+        attachments: InMemoryProjectionStore<TimeEntryAttachmentsState>,
+    }
+
+    fn row(user_id: &str, te_id: &str, updated_by: &str) -> TimeEntryRow {
+        TimeEntryRow {
+            time_entry_id: te_id.to_string(),
+            user_id: user_id.to_string(),
+            started_at: Some(0),
+            ended_at: Some(3_600_000),
+            tag_ids: vec![],
+            status: TimeEntryStatus::Registered,
+            created_at: 0,
+            created_by: user_id.to_string(),
+            updated_at: 0,
+            updated_by: updated_by.to_string(),
+            deleted_at: None,
+            hourly_rate: None,
+            breaks: vec![],
+            last_event_id: Some(format!("TimeEntry-{te_id}:2")),
+        }
+    }
+
+    fn comment(te_id: &str, added_by: &str) -> CommentRow {
+        CommentRow {
+            comment_id: format!("c-{te_id}"),
+            time_entry_id: te_id.to_string(),
+            body: format!("note by {added_by}"),
+            added_at: 0,
+            added_by: added_by.to_string(),
+        }
+    }
+
+    fn attachment(te_id: &str, added_by: &str) -> AttachmentRow {
+        AttachmentRow {
+            attachment_id: format!("a-{te_id}"),
+            time_entry_id: te_id.to_string(),
+            object_key: format!("attachments/{te_id}/a-{te_id}"),
+            file_name: format!("{added_by}.pdf"),
+            content_type: "application/pdf".to_string(),
+            size_bytes: 1,
+            added_at: 0,
+            added_by: added_by.to_string(),
+        }
+    }
+
+    fn day(millis: i64) -> BTreeMap<NaiveDate, DayTotals> {
+        BTreeMap::from([(
+            NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
+            DayTotals {
+                millis,
+                tag_millis: BTreeMap::from([(Tag::parse("t-1").unwrap(), millis)]),
+            },
+        )])
+    }
+
+    /// `u-1` owns te-1 in the first partition and te-2 in the second, with a comment and an
+    /// attachment by their manager on te-1; `u-2` owns te-3, which `u-1` approved.
+    async fn stores() -> Stores {
+        let lists = vec![
+            InMemoryProjectionStore::new(),
+            InMemoryProjectionStore::new(),
+        ];
+        for (list, rows) in lists.iter().zip([
+            vec![row("u-1", "te-1", "manager-1"), row("u-2", "te-3", "u-1")],
+            vec![row("u-1", "te-2", "u-1")],
+        ]) {
+            let mut state = ListTimeEntriesState::default();
+            for row in rows {
+                state.rows.insert(row.time_entry_id.clone(), row);
+            }
+            list.save(state, 7).await.unwrap();
+        }
+        let stats = InMemoryProjectionStore::new();
+        stats
+            .save(
+                UserStatsState {
+                    entries: HashMap::from([
+                        ("te-1".to_string(), stats_entry("u-1")),
+                        ("te-3".to_string(), stats_entry("u-2")),
+                    ]),
+                    days: HashMap::from([
+                        ("u-1".to_string(), day(3_600_000)),
+                        ("u-2".to_string(), day(60_000)),
+                    ]),
+                },
+                7,
+            )
+            .await
+            .unwrap();
+        let comments = InMemoryProjectionStore::new();
+        comments
+            .save(
+                TimeEntryCommentsState {
+                    comments: HashMap::from([
+                        ("te-1".to_string(), vec![comment("te-1", "manager-1")]),
+                        ("te-3".to_string(), vec![comment("te-3", "u-1")]),
+                    ]),
+                },
+                7,
+            )
+            .await
+            .unwrap();
+        let attachments = InMemoryProjectionStore::new();
+        attachments
+            .save(
+                TimeEntryAttachmentsState {
+                    attachments: HashMap::from([(
+                        "te-1".to_string(),
+                        vec![attachment("te-1", "manager-1")],
+                    )]),
+                },
+                7,
+            )
+            .await
+            .unwrap();
+        Stores {
+            lists,
+            stats,
+            comments,
+            attachments,
+        }
+    }
+
+    fn stats_entry(user_id: &str) -> StatsEntry {
+        StatsEntry {
+            user_id: user_id.to_string(),
+            ..StatsEntry::default()
+        }
+    }
+
+    fn eraser(stores: &Stores) -> Eraser {
+        TimeEntriesEraser::new(
+            stores.lists.clone(),
+            stores.stats.clone(),
+            stores.comments.clone(),
+            stores.attachments.clone(),
+        )
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_erase_what_a_rebuild_would_project_as_forgotten() {
+        let stores = stores().await;
+
+        eraser(&stores).erase("u-1").await.unwrap();
+
+        let first = stores.lists[0].state().await.unwrap().unwrap();
+        let second = stores.lists[1].state().await.unwrap().unwrap();
+        for row in [&first.rows["te-1"], &second.rows["te-2"]] {
+            assert_eq!(
+                (&*row.user_id, &*row.created_by, &*row.updated_by),
+                (FORGOTTEN, FORGOTTEN, FORGOTTEN)
+            );
+        }
+        assert_eq!(first.rows["te-3"], row("u-2", "te-3", "u-1"));
+        let comments = stores.comments.state().await.unwrap().unwrap().comments;
+        assert_eq!(
+            (&*comments["te-1"][0].body, &*comments["te-1"][0].added_by),
+            (FORGOTTEN, FORGOTTEN)
+        );
+        assert_eq!(comments["te-3"][0], comment("te-3", "u-1"));
+        let attachments = stores
+            .attachments
+            .state()
+            .await
+            .unwrap()
+            .unwrap()
+            .attachments;
+        assert_eq!(
+            (
+                &*attachments["te-1"][0].file_name,
+                &*attachments["te-1"][0].added_by
+            ),
+            (FORGOTTEN, FORGOTTEN)
+        );
+        assert_eq!(attachments["te-1"][0].object_key, "attachments/te-1/a-te-1");
+        let stats = stores.stats.state().await.unwrap().unwrap();
+        assert_eq!(stats.entries["te-1"].user_id, FORGOTTEN);
+        assert_eq!(stats.entries["te-3"].user_id, "u-2");
+        assert_eq!(stats.days.get("u-1"), None);
+        assert_eq!(stats.days[FORGOTTEN], day(3_600_000));
+        assert_eq!(stats.days["u-2"], day(60_000));
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_keep_the_checkpoints_and_change_nothing_when_repeated() {
+        let stores = stores().await;
+        let eraser = eraser(&stores);
+        eraser.erase("u-1").await.unwrap();
+        let erased = stores.lists[0].state().await.unwrap().unwrap().rows;
+
+        eraser.erase("u-1").await.unwrap();
+
+        assert_eq!(stores.lists[0].state().await.unwrap().unwrap().rows, erased);
+        assert_eq!(stores.lists[0].checkpoint().await.unwrap(), 7);
+        assert_eq!(stores.stats.checkpoint().await.unwrap(), 7);
+        let stats = stores.stats.state().await.unwrap().unwrap();
+        assert_eq!(stats.days[FORGOTTEN], day(3_600_000));
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_leave_empty_read_models_alone() {
+        let eraser: Eraser = TimeEntriesEraser::new(
+            vec![InMemoryProjectionStore::new()],
+            InMemoryProjectionStore::new(),
+            InMemoryProjectionStore::new(),
+            InMemoryProjectionStore::new(),
+        );
+
+        eraser.erase("u-1").await.unwrap();
+    }
+}
@@ -0,0 +1,418 @@
+// Dumps the list_time_entries read model for the data warehouse.
+//
+// Rows are grouped by the UTC month of `started_at` and written as one Parquet file per month
+// (`{prefix}/month=YYYY-MM/time_entries.parquet`), replacing the previous export for that
+// month. Drafts without a start time have no month yet and are left out. Timestamps are UTC
+// milliseconds, tags a list of strings and breaks a list of `{started_at, ended_at}`.
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use arrow_array::builder::{
+    ListBuilder, StringBuilder, StructBuilder, TimestampMillisecondBuilder,
+};
+use arrow_array::{ArrayRef, Int64Array, RecordBatch, StringArray, TimestampMillisecondArray};
+use arrow_schema::{DataType, Field, Fields, Schema, SchemaRef, TimeUnit};
+use chrono::DateTime;
+use parquet::arrow::ArrowWriter;
+use serde::Serialize;
+
+use crate::modules::time_entries::use_cases::list_time_entries::projection::{
+    ListTimeEntriesState, TimeEntryRow, TimeEntryStatus,
+};
+use crate::shared::infrastructure::cold_storage::ColdStorage;
+use crate::shared::infrastructure::projection_store::ProjectionStore;
+
+const UTC: &str = "UTC";
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ExportedPartition {
+    pub key: String,
+    pub rows: u64,
+}
+
+pub struct TimeEntryExporter<TStore, TColdStorage>
+where
+    TStore: ProjectionStore<ListTimeEntriesState> + Send + Sync + 'static,
+    TColdStorage: ColdStorage + 'static,
+{
+    store: TStore,
+    cold_storage: TColdStorage,
+    prefix: String,
+}
+
+impl<TStore, TColdStorage> TimeEntryExporter<TStore, TColdStorage>
+where
+    TStore: ProjectionStore<ListTimeEntriesState> + Send + Sync + 'static,
+    TColdStorage: ColdStorage + 'static,
+{
+    pub fn new(store: TStore, cold_storage: TColdStorage, prefix: impl Into<String>) -> Self {
+        Self {
+            store,
+            cold_storage,
+            prefix: prefix.into(),
+        }
+    }
+
+    pub async fn export(&self) -> anyhow::Result<Vec<ExportedPartition>> {
+        let state = self.store.state().await?.unwrap_or_default();
+        let mut by_month: BTreeMap<String, Vec<TimeEntryRow>> = BTreeMap::new();
+        for row in state.rows.into_values() {
+            if let Some(month) = row.started_at.and_then(month_of) {
+                by_month.entry(month).or_default().push(row);
+            }
+        }
+
+        let mut partitions = Vec::with_capacity(by_month.len());
+        for (month, mut rows) in by_month {
+            rows.sort_by(|a, b| {
+                (a.started_at, &a.time_entry_id).cmp(&(b.started_at, &b.time_entry_id))
+            });
+            let key = format!("{}/month={month}/time_entries.parquet", self.prefix);
+            self.cold_storage
+                .write_object(&key, to_parquet(&rows)?)
+                .await?;
+            partitions.push(ExportedPartition {
+                key,
+                rows: rows.len() as u64,
+            });
+        }
+        Ok(partitions)
+    }
+}
+
+fn month_of(timestamp_ms: i64) -> Option<String> {
+    DateTime::from_timestamp_millis(timestamp_ms).map(|at| at.format("%Y-%m").to_string())
+}
+
+fn timestamp() -> DataType {
+    DataType::Timestamp(TimeUnit::Millisecond, Some(UTC.into()))
+}
+
+fn break_fields() -> Fields {
+    Fields::from(vec![
+        Field::new("started_at", timestamp(), false),
+        Field::new("ended_at", timestamp(), false),
+    ])
+}
+
+/// The columns of an exported partition, in file order.
+pub fn schema() -> SchemaRef {
+    let tag = Field::new("item", DataType::Utf8, false);
+    let break_ = Field::new("item", DataType::Struct(break_fields()), false);
+    Arc::new(Schema::new(vec![
+        Field::new("time_entry_id", DataType::Utf8, false),
+        Field::new("user_id", DataType::Utf8, false),
+        Field::new("status", DataType::Utf8, false),
+        Field::new("started_at", timestamp(), false),
+        Field::new("ended_at", timestamp(), true),
+        Field::new("tag_ids", DataType::List(Arc::new(tag)), false),
+        Field::new("breaks", DataType::List(Arc::new(break_)), false),
+        Field::new("hourly_rate_cents", DataType::Int64, true),
+        Field::new("hourly_rate_currency", DataType::Utf8, true),
+        Field::new("created_at", timestamp(), false),
+        Field::new("created_by", DataType::Utf8, false),
+        Field::new("updated_at", timestamp(), false),
+        Field::new("updated_by", DataType::Utf8, false),
+        Field::new("deleted_at", timestamp(), true),
+    ]))
+}
+
+fn to_parquet(rows: &[TimeEntryRow]) -> anyhow::Result<Vec<u8>> {
+    let batch = record_batch(rows)?;
+    let mut bytes = Vec::new();
+    let mut writer = ArrowWriter::try_new(&mut bytes, batch.schema(), None)?;
+    writer.write(&batch)?;
+    writer.close()?;
+    Ok(bytes)
+}
+
+fn record_batch(rows: &[TimeEntryRow]) -> anyhow::Result<RecordBatch> {
+    let strings = |value: fn(&TimeEntryRow) -> Option<&str>| -> ArrayRef {
+        Arc::new(rows.iter().map(value).collect::<StringArray>())
+    };
+    let timestamps = |value: fn(&TimeEntryRow) -> Option<i64>| -> ArrayRef {
+        Arc::new(
+            rows.iter()
+                .map(value)
+                .collect::<TimestampMillisecondArray>()
+                .with_timezone(UTC),
+        )
+    };
+
+    let mut tags = ListBuilder::new(StringBuilder::new()).with_field(Field::new(
+        "item",
+        DataType::Utf8,
+        false,
+    ));
+    let mut breaks = ListBuilder::new(StructBuilder::from_fields(break_fields(), 0))
+        .with_field(Field::new("item", DataType::Struct(break_fields()), false));
+    for row in rows {
+        for tag in &row.tag_ids {
+            tags.values().append_value(tag.as_str());
+        }
+        tags.append(true);
+        for break_ in &row.breaks {
+            let values = breaks.values();
+            for (field, at) in [(0, break_.started_at), (1, break_.ended_at)] {
+                values
+                    .field_builder::<TimestampMillisecondBuilder>(field)
+                    .expect("break fields are timestamps")
+                    .append_value(at);
+            }
+            values.append(true);
+        }
+        breaks.append(true);
+    }
+
+    let columns: Vec<ArrayRef> = vec![
+        strings(|row| Some(&row.time_entry_id)),
+        strings(|row| Some(&row.user_id)),
+        strings(|row| Some(status_name(&row.status))),
+        timestamps(|row| row.started_at),
+        timestamps(|row| row.ended_at),
+        Arc::new(tags.finish()),
+        Arc::new(breaks.finish()),
+        Arc::new(
+            rows.iter()
+                .map(|row| row.hourly_rate.as_ref().map(|rate| rate.cents))
+                .collect::<Int64Array>(),
+        ),
+        strings(|row| row.hourly_rate.as_ref().map(|rate| rate.currency.as_str())),
+        timestamps(|row| Some(row.created_at)),
+        strings(|row| Some(&row.created_by)),
+        timestamps(|row| Some(row.updated_at)),
+        strings(|row| Some(&row.updated_by)),
+        timestamps(|row| row.deleted_at),
+    ];
+    Ok(RecordBatch::try_new(schema(), columns)?)
+}
+
+fn status_name(status: &TimeEntryStatus) -> &'static str {
+    match status {
+        TimeEntryStatus::Draft => "draft",
+        TimeEntryStatus::Registered => "registered",
+        TimeEntryStatus::Approved => "approved",
+        TimeEntryStatus::Rejected => "rejected",
+    }
+}
+
+#[cfg(test)]
+mod time_entry_exporter_tests {
+    use super::*;
+    use crate::modules::time_entries::core::breaks::Break;
+    use crate::modules::time_entries::core::hourly_rate::HourlyRate;
+    use crate::modules::time_entries::core::tag::Tag;
+    use crate::shared::infrastructure::cold_storage::in_memory::InMemoryColdStorage;
+    use crate::shared::infrastructure::projection_store::in_memory::InMemoryProjectionStore;
+    use arrow_array::Array;
+    use arrow_array::cast::AsArray;
+    use arrow_array::types::{Int64Type, TimestampMillisecondType};
+    use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+    use rstest::rstest;
+
+    // 2026-01-31T23:00:00Z and 2026-02-01T00:00:00Z
+    const END_OF_JANUARY: i64 = 1_769_900_400_000;
+    const START_OF_FEBRUARY: i64 = 1_769_904_000_000;
+
+    fn make_row(te_id: &str, started_at: Option<i64>) -> TimeEntryRow {
+        TimeEntryRow {
+            time_entry_id: te_id.to_string(),
+            user_id: "u1".to_string(),
+            started_at,
+            ended_at: started_at.map(|s| s + 1_000),
+            tag_ids: vec![],
+            status: TimeEntryStatus::Registered,
+            created_at: 0,
+            created_by: "u1".to_string(),
+            updated_at: 0,
+            updated_by: "u1".to_string(),
+            deleted_at: None,
+            hourly_rate: None,
+            last_event_id: None,
+            breaks: vec![],
+        }
+    }
+
+    async fn store_with_rows(
+        rows: Vec<TimeEntryRow>,
+    ) -> InMemoryProjectionStore<ListTimeEntriesState> {
+        let store = InMemoryProjectionStore::<ListTimeEntriesState>::new();
+        let mut state = ListTimeEntriesState::default();
+        for row in rows {
+            state.rows.insert(row.time_entry_id.clone(), row);
+        }
+        store.save(state, 1).await.unwrap();
+        store
+    }
+
+    async fn read_parquet(cold_storage: &InMemoryColdStorage, key: &str) -> RecordBatch {
+        let bytes = cold_storage.read_object(key).await.unwrap().unwrap();
+        let mut reader = ParquetRecordBatchReaderBuilder::try_new(bytes::Bytes::from(bytes))
+            .unwrap()
+            .build()
+            .unwrap();
+        let batch = reader.next().unwrap().unwrap();
+        assert!(reader.next().is_none());
+        batch
+    }
+
+    fn strings(batch: &RecordBatch, column: &str) -> Vec<Option<String>> {
+        batch
+            .column_by_name(column)
+            .unwrap()
+            .as_string::<i32>()
+            .iter()
+            .map(|value| value.map(str::to_string))
+            .collect()
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_partition_rows_by_month_of_start() {
+        let store = store_with_rows(vec![
+            make_row("te-jan-2", Some(END_OF_JANUARY)),
+            make_row("te-jan-1", Some(END_OF_JANUARY - 1_000)),
+            make_row("te-feb", Some(START_OF_FEBRUARY)),
+            make_row("te-draft", None),
+        ])
+        .await;
+        let cold_storage = InMemoryColdStorage::new();
+        let exporter = TimeEntryExporter::new(store, cold_storage.clone(), "exports");
+
+        let partitions = exporter.export().await.unwrap();
+
+        assert_eq!(
+            partitions,
+            vec![
+                ExportedPartition {
+                    key: "exports/month=2026-01/time_entries.parquet".to_string(),
+                    rows: 2,
+                },
+                ExportedPartition {
+                    key: "exports/month=2026-02/time_entries.parquet".to_string(),
+                    rows: 1,
+                },
+            ]
+        );
+        let january =
+            read_parquet(&cold_storage, "exports/month=2026-01/time_entries.parquet").await;
+        assert_eq!(january.schema(), schema());
+        assert_eq!(
+            strings(&january, "time_entry_id"),
+            vec![Some("te-jan-1".to_string()), Some("te-jan-2".to_string())]
+        );
+        let started_at: Vec<_> = january
+            .column_by_name("started_at")
+            .unwrap()
+            .as_primitive::<TimestampMillisecondType>()
+            .values()
+            .to_vec();
+        assert_eq!(started_at, vec![END_OF_JANUARY - 1_000, END_OF_JANUARY]);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_write_every_column_of_a_row() {
+        let mut row = make_row("te-1", Some(START_OF_FEBRUARY));
+        row.status = TimeEntryStatus::Approved;
+        row.tag_ids = vec![Tag::parse("billable").unwrap(), Tag::parse("ops").unwrap()];
+        row.breaks = vec![Break {
+            started_at: START_OF_FEBRUARY + 100,
+            ended_at: START_OF_FEBRUARY + 200,
+        }];
+        row.hourly_rate = Some(HourlyRate {
+            cents: 9_500,
+            currency: "EUR".to_string(),
+        });
+        row.deleted_at = Some(START_OF_FEBRUARY + 5_000);
+        let cold_storage = InMemoryColdStorage::new();
+
+        TimeEntryExporter::new(store_with_rows(vec![row]).await, cold_storage.clone(), "x")
+            .export()
+            .await
+            .unwrap();
+
+        let batch = read_parquet(&cold_storage, "x/month=2026-02/time_entries.parquet").await;
+        assert_eq!(
+            strings(&batch, "status"),
+            vec![Some("approved".to_string())]
+        );
+        assert_eq!(
+            strings(&batch, "hourly_rate_currency"),
+            vec![Some("EUR".to_string())]
+        );
+        let cents = batch.column_by_name("hourly_rate_cents").unwrap();
+        assert_eq!(cents.as_primitive::<Int64Type>().value(0), 9_500);
+        let tags = batch.column_by_name("tag_ids").unwrap().as_list::<i32>();
+        let tags: Vec<_> = tags
+            .value(0)
+            .as_string::<i32>()
+            .iter()
+            .flatten()
+            .map(str::to_string)
+            .collect();
+        assert_eq!(tags, vec!["billable", "ops"]);
+        let breaks = batch.column_by_name("breaks").unwrap().as_list::<i32>();
+        let break_ = breaks.value(0);
+        let break_ = break_.as_struct();
+        assert_eq!(break_.len(), 1);
+        let ended_at = break_.column_by_name("ended_at").unwrap();
+        assert_eq!(
+            ended_at.as_primitive::<TimestampMillisecondType>().value(0),
+            START_OF_FEBRUARY + 200
+        );
+        let deleted_at = batch.column_by_name("deleted_at").unwrap();
+        assert_eq!(
+            deleted_at
+                .as_primitive::<TimestampMillisecondType>()
+                .value(0),
+            START_OF_FEBRUARY + 5_000
+        );
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_replace_previous_exports_of_a_month() {
+        let cold_storage = InMemoryColdStorage::new();
+        cold_storage
+            .write_object(
+                "exports/month=2026-02/time_entries.parquet",
+                b"stale".to_vec(),
+            )
+            .await
+            .unwrap();
+        let store = store_with_rows(vec![make_row("te-feb", Some(START_OF_FEBRUARY))]).await;
+
+        TimeEntryExporter::new(store, cold_storage.clone(), "exports")
+            .export()
+            .await
+            .unwrap();
+
+        let batch = read_parquet(&cold_storage, "exports/month=2026-02/time_entries.parquet").await;
+        assert_eq!(
+            strings(&batch, "time_entry_id"),
+            vec![Some("te-feb".to_string())]
+        );
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_propagate_store_and_storage_errors() {
+        let mut offline_store = InMemoryProjectionStore::<ListTimeEntriesState>::new();
+        offline_store.toggle_offline();
+        let exporter = TimeEntryExporter::new(offline_store, InMemoryColdStorage::new(), "x");
+        assert!(exporter.export().await.is_err());
+
+        let cold_storage = InMemoryColdStorage::new();
+        cold_storage.toggle_offline();
+        let store = store_with_rows(vec![make_row("te-feb", Some(START_OF_FEBRUARY))]).await;
+        let exporter = TimeEntryExporter::new(store, cold_storage, "x");
+        assert!(exporter.export().await.is_err());
+    }
+
+    #[rstest]
+    fn it_should_skip_timestamps_outside_the_calendar_range() {
+        assert_eq!(month_of(i64::MAX), None);
+    }
+}
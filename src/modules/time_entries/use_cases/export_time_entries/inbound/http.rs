@@ -0,0 +1,77 @@
+use axum::{extract::State, http::StatusCode, response::IntoResponse};
+use chrono::Utc;
+use serde_json::json;
+
+use crate::modules::time_entries::use_cases::export_time_entries::job::JOB_KIND;
+use crate::shared::infrastructure::job_store::Job;
+use crate::shared::infrastructure::request_context::RequestContext;
+use crate::shell::jobs;
+use crate::shell::state::AppState;
+
+/// POST /admin/time-entries/export — starts a job writing the time entries to cold storage as
+/// one Parquet file per month; poll the `Location` it answers with. Admins only.
+pub async fn handle(
+    State(state): State<AppState>,
+    request_ctx: RequestContext,
+) -> impl IntoResponse {
+    if !request_ctx.principal().can_administer() {
+        return StatusCode::FORBIDDEN.into_response();
+    }
+    let job = Job::queued(JOB_KIND, json!({}), Utc::now().timestamp_millis());
+    jobs::accept(&state, job).await
+}
+
+#[cfg(test)]
+mod export_time_entries_http_inbound_tests {
+    use super::*;
+    use crate::shared::infrastructure::job_store::{JobStatus, JobStore};
+    use crate::tests::fixtures::tags::make_test_app_state;
+    use axum::{
+        Router,
+        body::Body,
+        http::{Request, header},
+        routing::post,
+    };
+    use rstest::rstest;
+    use tower::ServiceExt;
+
+    fn request(role: &str) -> Request<Body> {
+        Request::post("/admin/time-entries/export")
+            .header("x-user-id", "admin-1")
+            .header("x-tenant-id", "tenant-test")
+            .header("x-user-role", role)
+            .body(Body::empty())
+            .unwrap()
+    }
+
+    fn app(state: AppState) -> Router {
+        Router::new()
+            .route("/admin/time-entries/export", post(handle))
+            .with_state(state)
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_queue_an_export_job() {
+        let state = make_test_app_state();
+
+        let response = app(state.clone()).oneshot(request("admin")).await.unwrap();
+
+        assert_eq!(response.status(), StatusCode::ACCEPTED);
+        let location = response.headers()[header::LOCATION].to_str().unwrap();
+        let job_id = location.trim_start_matches("/admin/jobs/");
+        let job = state.job_store.get(job_id).await.unwrap().unwrap();
+        assert_eq!(job.kind, JOB_KIND);
+        assert_eq!(job.status, JobStatus::Queued);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_be_reserved_for_admins() {
+        let response = app(make_test_app_state())
+            .oneshot(request("employee"))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    }
+}
@@ -0,0 +1,136 @@
+// Exports the list_time_entries read model to cold storage as a job, so the warehouse load
+// does not hold up the request that asked for it. A retried job exports every month again.
+
+use async_trait::async_trait;
+use serde_json::{Value as Json, json};
+
+use crate::modules::time_entries::use_cases::export_time_entries::exporter::TimeEntryExporter;
+use crate::modules::time_entries::use_cases::list_time_entries::projection::ListTimeEntriesState;
+use crate::shared::application::jobs::{JobContext, JobHandler};
+use crate::shared::infrastructure::cold_storage::ColdStorage;
+use crate::shared::infrastructure::job_store::Job;
+use crate::shared::infrastructure::projection_store::ProjectionStore;
+
+pub const JOB_KIND: &str = "time_entries.export";
+
+/// Where exports go when no prefix is configured.
+pub const DEFAULT_EXPORT_PREFIX: &str = "exports/time_entries";
+
+pub struct ExportTimeEntriesJob<TStore, TColdStorage>
+where
+    TStore: ProjectionStore<ListTimeEntriesState> + Send + Sync + 'static,
+    TColdStorage: ColdStorage + 'static,
+{
+    exporter: TimeEntryExporter<TStore, TColdStorage>,
+}
+
+impl<TStore, TColdStorage> ExportTimeEntriesJob<TStore, TColdStorage>
+where
+    TStore: ProjectionStore<ListTimeEntriesState> + Send + Sync + 'static,
+    TColdStorage: ColdStorage + 'static,
+{
+    pub fn new(exporter: TimeEntryExporter<TStore, TColdStorage>) -> Self {
+        Self { exporter }
+    }
+}
+
+#[async_trait]
+impl<TStore, TColdStorage> JobHandler for ExportTimeEntriesJob<TStore, TColdStorage>
+where
+    TStore: ProjectionStore<ListTimeEntriesState> + Send + Sync + 'static,
+    TColdStorage: ColdStorage + 'static,
+{
+    async fn run(&self, _job: &Job, ctx: &JobContext<'_>) -> anyhow::Result<Json> {
+        let partitions = self.exporter.export().await?;
+        let total = partitions.len() as u64;
+        ctx.report_progress(total, Some(total)).await?;
+        Ok(json!({ "partitions": partitions }))
+    }
+}
+
+#[cfg(test)]
+mod export_job_tests {
+    use super::*;
+    use crate::modules::time_entries::use_cases::list_time_entries::projection::{
+        TimeEntryRow, TimeEntryStatus,
+    };
+    use crate::shared::application::jobs::{JobRunner, RetryPolicy};
+    use crate::shared::infrastructure::cold_storage::in_memory::InMemoryColdStorage;
+    use crate::shared::infrastructure::job_store::in_memory::InMemoryJobStore;
+    use crate::shared::infrastructure::job_store::{JobStatus, JobStore};
+    use crate::shared::infrastructure::projection_store::in_memory::InMemoryProjectionStore;
+    use rstest::rstest;
+    use std::sync::Arc;
+
+    // 2026-02-01T00:00:00Z
+    const START_OF_FEBRUARY: i64 = 1_769_904_000_000;
+
+    fn row(time_entry_id: &str) -> TimeEntryRow {
+        TimeEntryRow {
+            time_entry_id: time_entry_id.to_string(),
+            user_id: "u1".to_string(),
+            started_at: Some(START_OF_FEBRUARY),
+            ended_at: None,
+            tag_ids: vec![],
+            status: TimeEntryStatus::Draft,
+            created_at: 0,
+            created_by: "u1".to_string(),
+            updated_at: 0,
+            updated_by: "u1".to_string(),
+            deleted_at: None,
+            hourly_rate: None,
+            last_event_id: None,
+            breaks: vec![],
+        }
+    }
+
+    async fn run(store: InMemoryProjectionStore<ListTimeEntriesState>) -> Job {
+        let job_store = InMemoryJobStore::new();
+        let job = Job::queued(JOB_KIND, json!({}), 0);
+        job_store.enqueue(job.clone()).await.unwrap();
+        let exporter = TimeEntryExporter::new(store, InMemoryColdStorage::new(), "exports");
+        JobRunner::new(job_store.clone())
+            .with_handler(JOB_KIND, Arc::new(ExportTimeEntriesJob::new(exporter)))
+            .with_retry_policy(RetryPolicy {
+                max_attempts: 1,
+                ..RetryPolicy::default()
+            })
+            .run_due()
+            .await
+            .unwrap();
+        job_store.get(&job.job_id).await.unwrap().unwrap()
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_report_the_exported_partitions() {
+        let store = InMemoryProjectionStore::<ListTimeEntriesState>::new();
+        let mut state = ListTimeEntriesState::default();
+        state.rows.insert("te-1".to_string(), row("te-1"));
+        store.save(state, 1).await.unwrap();
+
+        let job = run(store).await;
+
+        assert_eq!(job.status, JobStatus::Succeeded);
+        assert_eq!(
+            job.result,
+            Some(json!({
+                "partitions": [{
+                    "key": "exports/month=2026-02/time_entries.parquet",
+                    "rows": 1,
+                }],
+            }))
+        );
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_fail_when_the_read_model_is_unavailable() {
+        let mut store = InMemoryProjectionStore::<ListTimeEntriesState>::new();
+        store.toggle_offline();
+
+        let job = run(store).await;
+
+        assert_eq!(job.status, JobStatus::Failed);
+    }
+}
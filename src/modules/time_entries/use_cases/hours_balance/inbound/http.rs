@@ -0,0 +1,161 @@
+use axum::{
+    Json,
+    extract::{Query, State},
+    http::StatusCode,
+    response::IntoResponse,
+};
+use chrono::NaiveDate;
+use serde::Deserialize;
+
+use crate::shared::infrastructure::request_context::RequestContext;
+use crate::shell::state::AppState;
+
+/// Longest range a single balance may span.
+const MAX_DAYS: i64 = 366;
+
+#[derive(Deserialize)]
+pub struct HoursBalanceParams {
+    /// Inclusive, `YYYY-MM-DD`.
+    pub from: NaiveDate,
+    /// Inclusive, `YYYY-MM-DD`.
+    pub to: NaiveDate,
+    /// Whose balance to compute; defaults to the caller. Others require `can_view_user`.
+    pub user_id: Option<String>,
+}
+
+/// GET /hours-balance — expected versus registered minutes over a date range.
+pub async fn handle(
+    State(state): State<AppState>,
+    request_ctx: RequestContext,
+    Query(params): Query<HoursBalanceParams>,
+) -> impl IntoResponse {
+    let user_id = params.user_id.unwrap_or(request_ctx.user_id.clone());
+    if !request_ctx.principal().can_view_user(&user_id) {
+        return StatusCode::FORBIDDEN.into_response();
+    }
+    let days = (params.to - params.from).num_days();
+    if !(0..MAX_DAYS).contains(&days) {
+        return StatusCode::UNPROCESSABLE_ENTITY.into_response();
+    }
+    match state
+        .hours_balance_handler
+        .balance(&user_id, params.from, params.to)
+        .await
+    {
+        Ok(balance) => Json(balance).into_response(),
+        Err(_) => StatusCode::INTERNAL_SERVER_ERROR.into_response(),
+    }
+}
+
+#[cfg(test)]
+mod hours_balance_http_inbound_tests {
+    use axum::{
+        Router,
+        body::Body,
+        http::{Request, StatusCode},
+        routing::get,
+    };
+    use http_body_util::BodyExt;
+    use rstest::rstest;
+    use std::sync::Arc;
+    use tower::ServiceExt;
+
+    use super::handle;
+    use crate::modules::time_entries::use_cases::hours_balance::queries::HoursBalanceQueryHandler;
+    use crate::shared::infrastructure::calendar::static_config::StaticCalendar;
+    use crate::shared::infrastructure::projection_store::in_memory::InMemoryProjectionStore;
+    use crate::shared::infrastructure::projection_store::partitioned::PartitionedProjectionStore;
+    use crate::shell::state::AppState;
+    use crate::tests::fixtures::tags::make_test_app_state;
+
+    async fn get_balance(
+        state: AppState,
+        query: &str,
+        role: &str,
+    ) -> (StatusCode, serde_json::Value) {
+        let response = Router::new()
+            .route("/hours-balance", get(handle))
+            .with_state(state)
+            .oneshot(
+                Request::get(format!("/hours-balance?{query}"))
+                    .header("x-user-id", "u-1")
+                    .header("x-tenant-id", "tenant-test")
+                    .header("x-user-role", role)
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        let status = response.status();
+        let bytes = response.into_body().collect().await.unwrap().to_bytes();
+        (
+            status,
+            serde_json::from_slice(&bytes).unwrap_or(serde_json::Value::Null),
+        )
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_return_the_callers_balance() {
+        let (status, body) = get_balance(
+            make_test_app_state(),
+            "from=2026-12-21&to=2026-12-25",
+            "employee",
+        )
+        .await;
+
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["user_id"], "u-1");
+        assert_eq!(body["expected_minutes"], 5 * 8 * 60);
+        assert_eq!(body["actual_minutes"], 0);
+    }
+
+    #[rstest]
+    #[case::other_user(
+        "from=2026-12-21&to=2026-12-25&user_id=u-2",
+        "employee",
+        StatusCode::FORBIDDEN
+    )]
+    #[case::other_user_as_manager(
+        "from=2026-12-21&to=2026-12-25&user_id=u-2",
+        "manager",
+        StatusCode::OK
+    )]
+    #[case::reversed(
+        "from=2026-12-25&to=2026-12-21",
+        "employee",
+        StatusCode::UNPROCESSABLE_ENTITY
+    )]
+    #[case::too_long(
+        "from=2026-01-01&to=2027-01-02",
+        "employee",
+        StatusCode::UNPROCESSABLE_ENTITY
+    )]
+    #[case::full_year("from=2026-01-01&to=2026-12-31", "employee", StatusCode::OK)]
+    #[case::invalid_date("from=2026-13-01&to=2026-12-31", "employee", StatusCode::BAD_REQUEST)]
+    #[tokio::test]
+    async fn it_should_validate_access_and_range(
+        #[case] query: &str,
+        #[case] role: &str,
+        #[case] expected: StatusCode,
+    ) {
+        let (status, _) = get_balance(make_test_app_state(), query, role).await;
+        assert_eq!(status, expected);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_return_500_when_the_calendar_is_offline() {
+        let calendar = StaticCalendar::default();
+        calendar.toggle_offline();
+        let mut state = make_test_app_state();
+        state.hours_balance_handler = HoursBalanceQueryHandler::new(
+            PartitionedProjectionStore::single(InMemoryProjectionStore::new()),
+            Arc::new(calendar),
+        );
+
+        let (status, _) = get_balance(state, "from=2026-12-21&to=2026-12-25", "employee").await;
+
+        assert_eq!(status, StatusCode::INTERNAL_SERVER_ERROR);
+    }
+}
@@ -0,0 +1,332 @@
+use chrono::{Datelike, NaiveDate, Weekday};
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::modules::time_entries::use_cases::list_time_entries::projection::{
+    ListTimeEntriesState, TimeEntryRow, TimeEntryStatus,
+};
+use crate::modules::time_entries::use_cases::user_time_entries::sharded_handler::SharedCalendar;
+use crate::shared::infrastructure::calendar::DayOff;
+use crate::shared::infrastructure::projection_store::ProjectionStore;
+
+pub const DEFAULT_DAILY_MINUTES: i64 = 8 * 60;
+
+const MINUTE: i64 = 60_000;
+const DAY: i64 = 24 * 60 * MINUTE;
+
+/// Expected versus registered time of one user over a date range.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct HoursBalance {
+    pub user_id: String,
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+    /// Working days (Monday to Friday) that are not a holiday or absence, times the daily
+    /// hours.
+    pub expected_minutes: i64,
+    /// Registered and approved entries, clipped to the range.
+    pub actual_minutes: i64,
+    /// The cost of the registered time per currency, for entries that have a rate.
+    pub amounts_cents: BTreeMap<String, i64>,
+    pub days_off: Vec<DayOff>,
+}
+
+/// Computes hours balances from the list time entries read model and the calendar.
+#[derive(Clone)]
+pub struct HoursBalanceQueryHandler<TStore>
+where
+    TStore: ProjectionStore<ListTimeEntriesState> + Send + Sync + 'static,
+{
+    store: TStore,
+    calendar: SharedCalendar,
+    daily_minutes: i64,
+}
+
+impl<TStore> HoursBalanceQueryHandler<TStore>
+where
+    TStore: ProjectionStore<ListTimeEntriesState> + Send + Sync + 'static,
+{
+    pub fn new(store: TStore, calendar: SharedCalendar) -> Self {
+        Self {
+            store,
+            calendar,
+            daily_minutes: DEFAULT_DAILY_MINUTES,
+        }
+    }
+
+    pub fn with_daily_minutes(mut self, daily_minutes: i64) -> Self {
+        self.daily_minutes = daily_minutes;
+        self
+    }
+
+    /// `from` and `to` are inclusive UTC dates.
+    pub async fn balance(
+        &self,
+        user_id: &str,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> anyhow::Result<HoursBalance> {
+        let days_off = self.calendar.days_off(user_id, from, to).await?;
+        let state = self.store.state().await?.unwrap_or_default();
+        let working_days = working_days(from, to, &days_off).len() as i64;
+
+        Ok(HoursBalance {
+            user_id: user_id.to_string(),
+            from,
+            to,
+            expected_minutes: working_days * self.daily_minutes,
+            actual_minutes: registered_minutes(&state, user_id, from, to),
+            amounts_cents: registered_amounts(&state, user_id, from, to),
+            days_off,
+        })
+    }
+}
+
+/// Monday to Friday between `from` and `to` (inclusive), except `days_off`.
+pub fn working_days(from: NaiveDate, to: NaiveDate, days_off: &[DayOff]) -> Vec<NaiveDate> {
+    let off: BTreeSet<NaiveDate> = days_off.iter().map(|day_off| day_off.date).collect();
+    from.iter_days()
+        .take_while(|date| *date <= to)
+        .filter(|date| !matches!(date.weekday(), Weekday::Sat | Weekday::Sun))
+        .filter(|date| !off.contains(date))
+        .collect()
+}
+
+/// Whole minutes of the user's registered and approved entries between `from` and `to`
+/// (inclusive UTC dates), clipping entries that cross the range boundaries.
+pub fn registered_minutes(
+    state: &ListTimeEntriesState,
+    user_id: &str,
+    from: NaiveDate,
+    to: NaiveDate,
+) -> i64 {
+    let millis: i64 = clipped_rows(state, user_id, from, to)
+        .map(|(_, millis)| millis)
+        .sum();
+    millis / MINUTE
+}
+
+/// The cost of the user's registered and approved entries between `from` and `to`
+/// (inclusive UTC dates) per currency, pricing only the part inside the range. Entries
+/// without a rate are left out.
+pub fn registered_amounts(
+    state: &ListTimeEntriesState,
+    user_id: &str,
+    from: NaiveDate,
+    to: NaiveDate,
+) -> BTreeMap<String, i64> {
+    let mut amounts = BTreeMap::new();
+    for (row, millis) in clipped_rows(state, user_id, from, to) {
+        if let Some(rate) = &row.hourly_rate {
+            *amounts.entry(rate.currency.clone()).or_default() += rate.amount_cents(millis);
+        }
+    }
+    amounts
+}
+
+/// The user's registered and approved entries between `from` and `to` (inclusive UTC dates),
+/// each with the milliseconds it works inside the range, so leaving out its breaks.
+pub fn clipped_rows<'a>(
+    state: &'a ListTimeEntriesState,
+    user_id: &'a str,
+    from: NaiveDate,
+    to: NaiveDate,
+) -> impl Iterator<Item = (&'a TimeEntryRow, i64)> {
+    let range_start = start_of(from);
+    let range_end = start_of(to) + DAY;
+    state
+        .rows
+        .values()
+        .filter(move |row| row.user_id == user_id && row.deleted_at.is_none())
+        .filter(|row| row.status != TimeEntryStatus::Draft)
+        .filter_map(move |row| Some((row, row.net_millis_within(range_start, range_end)?)))
+}
+
+fn start_of(date: NaiveDate) -> i64 {
+    date.and_hms_opt(0, 0, 0)
+        .unwrap_or_default()
+        .and_utc()
+        .timestamp_millis()
+}
+
+#[cfg(test)]
+mod hours_balance_query_handler_tests {
+    use super::*;
+    use crate::modules::time_entries::core::hourly_rate::HourlyRate;
+    use crate::shared::infrastructure::calendar::DayOffKind;
+    use crate::shared::infrastructure::calendar::static_config::StaticCalendar;
+    use crate::shared::infrastructure::projection_store::in_memory::InMemoryProjectionStore;
+    use rstest::rstest;
+    use std::sync::Arc;
+
+    fn date(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 12, day).unwrap()
+    }
+
+    fn row(
+        time_entry_id: &str,
+        user_id: &str,
+        interval: (i64, i64),
+        status: TimeEntryStatus,
+    ) -> TimeEntryRow {
+        TimeEntryRow {
+            time_entry_id: time_entry_id.to_string(),
+            user_id: user_id.to_string(),
+            started_at: Some(interval.0),
+            ended_at: Some(interval.1),
+            tag_ids: vec![],
+            status,
+            created_at: 0,
+            created_by: user_id.to_string(),
+            updated_at: 0,
+            updated_by: user_id.to_string(),
+            deleted_at: None,
+            hourly_rate: None,
+            last_event_id: None,
+            breaks: vec![],
+        }
+    }
+
+    async fn store_with_rows(
+        rows: Vec<TimeEntryRow>,
+    ) -> InMemoryProjectionStore<ListTimeEntriesState> {
+        let store = InMemoryProjectionStore::<ListTimeEntriesState>::new();
+        let mut state = ListTimeEntriesState::default();
+        for row in rows {
+            state.rows.insert(row.time_entry_id.clone(), row);
+        }
+        store.save(state, 1).await.unwrap();
+        store
+    }
+
+    fn calendar() -> StaticCalendar {
+        StaticCalendar::from_json(
+            r#"{
+                "holidays": [{ "date": "2026-12-25", "name": "Christmas" }],
+                "absences": [{ "user_id": "u-1", "date": "2026-12-24", "reason": "vacation" }]
+            }"#,
+        )
+        .unwrap()
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_compare_expected_and_registered_minutes() {
+        let monday = start_of(date(21));
+        let hour = 60 * MINUTE;
+        let mut deleted = row(
+            "te-5",
+            "u-1",
+            (monday, monday + hour),
+            TimeEntryStatus::Registered,
+        );
+        deleted.deleted_at = Some(1);
+        let mut running = row("te-6", "u-1", (monday, 0), TimeEntryStatus::Draft);
+        running.ended_at = None;
+        let mut in_euros = row(
+            "te-1",
+            "u-1",
+            (monday + 9 * hour, monday + 17 * hour),
+            TimeEntryStatus::Registered,
+        );
+        in_euros.hourly_rate = Some(HourlyRate {
+            cents: 10_000,
+            currency: "EUR".to_string(),
+        });
+        let mut in_dollars = row(
+            "te-2",
+            "u-1",
+            (monday - hour, monday + hour),
+            TimeEntryStatus::Approved,
+        );
+        in_dollars.hourly_rate = Some(HourlyRate {
+            cents: 5_000,
+            currency: "USD".to_string(),
+        });
+        let store = store_with_rows(vec![
+            in_euros,
+            in_dollars,
+            row(
+                "te-3",
+                "u-1",
+                (monday, monday + hour),
+                TimeEntryStatus::Draft,
+            ),
+            row(
+                "te-4",
+                "u-2",
+                (monday, monday + hour),
+                TimeEntryStatus::Registered,
+            ),
+            deleted,
+            running,
+        ])
+        .await;
+        let handler = HoursBalanceQueryHandler::new(store, Arc::new(calendar()));
+
+        let balance = handler.balance("u-1", date(21), date(27)).await.unwrap();
+
+        assert_eq!(
+            balance,
+            HoursBalance {
+                user_id: "u-1".to_string(),
+                from: date(21),
+                to: date(27),
+                expected_minutes: 3 * 8 * 60,
+                actual_minutes: 9 * 60,
+                amounts_cents: BTreeMap::from([
+                    ("EUR".to_string(), 80_000),
+                    ("USD".to_string(), 5_000),
+                ]),
+                days_off: vec![
+                    DayOff {
+                        date: date(24),
+                        kind: DayOffKind::Absence {
+                            reason: "vacation".to_string()
+                        },
+                    },
+                    DayOff {
+                        date: date(25),
+                        kind: DayOffKind::Holiday {
+                            name: "Christmas".to_string()
+                        },
+                    },
+                ],
+            }
+        );
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_use_the_configured_daily_hours_on_an_empty_store() {
+        let handler = HoursBalanceQueryHandler::new(
+            InMemoryProjectionStore::<ListTimeEntriesState>::new(),
+            Arc::new(StaticCalendar::default()),
+        )
+        .with_daily_minutes(6 * 60);
+
+        let balance = handler.balance("u-1", date(21), date(21)).await.unwrap();
+
+        assert_eq!(balance.expected_minutes, 6 * 60);
+        assert_eq!(balance.actual_minutes, 0);
+    }
+
+    #[rstest]
+    #[case::calendar(true, false)]
+    #[case::store(false, true)]
+    #[tokio::test]
+    async fn it_should_fail_when_a_source_is_offline(
+        #[case] calendar_offline: bool,
+        #[case] store_offline: bool,
+    ) {
+        let calendar = StaticCalendar::default();
+        if calendar_offline {
+            calendar.toggle_offline();
+        }
+        let mut store = InMemoryProjectionStore::<ListTimeEntriesState>::new();
+        if store_offline {
+            store.toggle_offline();
+        }
+        let handler = HoursBalanceQueryHandler::new(store, Arc::new(calendar));
+
+        assert!(handler.balance("u-1", date(21), date(27)).await.is_err());
+    }
+}
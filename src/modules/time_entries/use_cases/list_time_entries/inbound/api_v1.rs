@@ -0,0 +1,168 @@
+// Version 1 of a time entry as API clients see it.
+//
+// The HTTP list and get responses, the sync changes and the SSE patches serialize
+// `TimeEntryV1`, and GraphQL builds its `TimeEntry` from it, so none of them expose
+// `TimeEntryView`, `TimeEntryRow` or the core types they carry. Those follow the events and
+// may change as event versions do; this module translates them to a shape that does not.
+//
+// The JSON of `TimeEntryV1` is pinned by `src/tests/fixtures/api/v1/time_entry.json`. A field
+// may be added here without breaking clients, but renaming, retyping or removing one is a
+// `TimeEntryV2` served next to this one, never an edit of this struct or its golden.
+
+use serde::{Deserialize, Serialize};
+
+use crate::modules::time_entries::core::breaks::Break;
+use crate::modules::time_entries::use_cases::list_time_entries::projection::{
+    TimeEntryStatus, TimeEntryView,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TimeEntryStatusV1 {
+    Draft,
+    Registered,
+    Approved,
+    Rejected,
+}
+
+impl From<TimeEntryStatus> for TimeEntryStatusV1 {
+    fn from(status: TimeEntryStatus) -> Self {
+        match status {
+            TimeEntryStatus::Draft => Self::Draft,
+            TimeEntryStatus::Registered => Self::Registered,
+            TimeEntryStatus::Approved => Self::Approved,
+            TimeEntryStatus::Rejected => Self::Rejected,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BreakV1 {
+    pub started_at: i64,
+    pub ended_at: i64,
+}
+
+impl From<Break> for BreakV1 {
+    fn from(pause: Break) -> Self {
+        Self {
+            started_at: pause.started_at,
+            ended_at: pause.ended_at,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TimeEntryV1 {
+    pub time_entry_id: String,
+    pub user_id: String,
+    pub started_at: Option<i64>,
+    pub ended_at: Option<i64>,
+    /// The entry's tags, normalized.
+    pub tag_ids: Vec<String>,
+    pub status: TimeEntryStatusV1,
+    pub created_at: i64,
+    pub created_by: String,
+    pub updated_at: i64,
+    pub updated_by: String,
+    pub deleted_at: Option<i64>,
+    pub hourly_rate_cents: Option<i64>,
+    pub currency: Option<String>,
+    pub breaks: Vec<BreakV1>,
+    pub net_duration_ms: Option<i64>,
+    pub amount_cents: Option<i64>,
+}
+
+impl From<TimeEntryView> for TimeEntryV1 {
+    fn from(view: TimeEntryView) -> Self {
+        Self {
+            time_entry_id: view.time_entry_id,
+            user_id: view.user_id,
+            started_at: view.started_at,
+            ended_at: view.ended_at,
+            tag_ids: view
+                .tag_ids
+                .iter()
+                .map(|tag| tag.as_str().to_string())
+                .collect(),
+            status: view.status.into(),
+            created_at: view.created_at,
+            created_by: view.created_by,
+            updated_at: view.updated_at,
+            updated_by: view.updated_by,
+            deleted_at: view.deleted_at,
+            hourly_rate_cents: view.hourly_rate_cents,
+            currency: view.currency,
+            breaks: view.breaks.into_iter().map(BreakV1::from).collect(),
+            net_duration_ms: view.net_duration_ms,
+            amount_cents: view.amount_cents,
+        }
+    }
+}
+
+#[cfg(test)]
+mod time_entry_api_v1_tests {
+    use super::*;
+    use crate::modules::time_entries::core::hourly_rate::HourlyRate;
+    use crate::modules::time_entries::core::tag::Tag;
+    use crate::modules::time_entries::use_cases::list_time_entries::projection::TimeEntryRow;
+    use rstest::rstest;
+    use serde_json::Value;
+
+    const GOLDEN: &str = "./src/tests/fixtures/api/v1/time_entry.json";
+
+    fn golden() -> Value {
+        let raw = std::fs::read_to_string(GOLDEN).expect("golden missing");
+        serde_json::from_str(&raw).expect("golden is not JSON")
+    }
+
+    /// An entry with every optional field set, as the list projection stores it.
+    fn row() -> TimeEntryRow {
+        TimeEntryRow {
+            time_entry_id: "te-fixed-0001".to_string(),
+            user_id: "user-fixed-0001".to_string(),
+            started_at: Some(1_700_000_000_000),
+            ended_at: Some(1_700_003_600_000),
+            tag_ids: vec![Tag::parse("Deep Work").unwrap()],
+            status: TimeEntryStatus::Registered,
+            created_at: 1_700_000_000_000,
+            created_by: "user-fixed-0001".to_string(),
+            updated_at: 1_700_003_600_000,
+            updated_by: "user-fixed-0001".to_string(),
+            deleted_at: None,
+            hourly_rate: Some(HourlyRate {
+                cents: 6_000,
+                currency: "EUR".to_string(),
+            }),
+            breaks: vec![Break {
+                started_at: 1_700_001_800_000,
+                ended_at: 1_700_002_400_000,
+            }],
+            last_event_id: Some("TimeEntry-te-fixed-0001:4".to_string()),
+        }
+    }
+
+    #[rstest]
+    fn it_should_serialize_exactly_as_the_golden() {
+        let dto = TimeEntryV1::from(TimeEntryView::from(row()));
+
+        assert_eq!(serde_json::to_value(&dto).unwrap(), golden());
+    }
+
+    #[rstest]
+    fn the_golden_should_deserialize_back_to_the_same_entry() {
+        let dto: TimeEntryV1 = serde_json::from_value(golden()).unwrap();
+
+        assert_eq!(dto, TimeEntryV1::from(TimeEntryView::from(row())));
+    }
+
+    #[rstest]
+    #[case(TimeEntryStatus::Draft, "draft")]
+    #[case(TimeEntryStatus::Registered, "registered")]
+    #[case(TimeEntryStatus::Approved, "approved")]
+    fn it_should_name_every_status(#[case] status: TimeEntryStatus, #[case] expected: &str) {
+        assert_eq!(
+            serde_json::to_value(TimeEntryStatusV1::from(status)).unwrap(),
+            Value::String(expected.to_string())
+        );
+    }
+}
@@ -1,8 +1,22 @@
-use async_graphql::{Context, Enum, Object, Result as GqlResult};
+use async_graphql::futures_util::{Stream, StreamExt};
+use async_graphql::{
+    ComplexObject, Context, Enum, InputObject, Object, Result as GqlResult, SimpleObject,
+    Subscription,
+};
+use chrono::NaiveDate;
+use chrono_tz::Tz;
 
-use crate::modules::time_entries::use_cases::list_time_entries::projection::{
-    TimeEntryStatus, TimeEntryView,
+use crate::modules::time_entries::core::tag::Tag;
+use crate::modules::time_entries::use_cases::list_time_entries::inbound::api_v1::{
+    BreakV1, TimeEntryStatusV1, TimeEntryV1,
+};
+use crate::modules::time_entries::use_cases::list_time_entries::projection::TimeEntryView;
+use crate::modules::time_entries::use_cases::list_time_entries::queries::{
+    DayEntries, Page, SimilarEntry, SimilarityReason, TagTotal,
 };
+use crate::modules::time_entries::use_cases::list_time_entries::updates::TimeEntryUpdate;
+use crate::modules::time_entries::use_cases::time_entry_attachments::inbound::graphql::GqlTimeEntryAttachment;
+use crate::modules::time_entries::use_cases::time_entry_comments::inbound::graphql::GqlTimeEntryComment;
 use crate::shared::infrastructure::request_context::RequestContext;
 use crate::shell::state::AppState;
 
@@ -10,18 +24,45 @@ use crate::shell::state::AppState;
 pub enum GqlTimeEntryStatus {
     Draft,
     Registered,
+    Approved,
+    Rejected,
 }
 
-impl From<TimeEntryStatus> for GqlTimeEntryStatus {
-    fn from(s: TimeEntryStatus) -> Self {
+impl From<TimeEntryStatusV1> for GqlTimeEntryStatus {
+    fn from(s: TimeEntryStatusV1) -> Self {
         match s {
-            TimeEntryStatus::Draft => GqlTimeEntryStatus::Draft,
-            TimeEntryStatus::Registered => GqlTimeEntryStatus::Registered,
+            TimeEntryStatusV1::Draft => GqlTimeEntryStatus::Draft,
+            TimeEntryStatusV1::Registered => GqlTimeEntryStatus::Registered,
+            TimeEntryStatusV1::Approved => GqlTimeEntryStatus::Approved,
+            TimeEntryStatusV1::Rejected => GqlTimeEntryStatus::Rejected,
         }
     }
 }
 
-#[derive(async_graphql::SimpleObject, Clone)]
+#[derive(SimpleObject, Clone)]
+pub struct GqlUser {
+    pub id: String,
+    pub display_name: Option<String>,
+}
+
+#[derive(SimpleObject, Clone)]
+#[graphql(name = "Break")]
+pub struct GqlBreak {
+    pub started_at: i64,
+    pub ended_at: i64,
+}
+
+impl From<BreakV1> for GqlBreak {
+    fn from(pause: BreakV1) -> Self {
+        Self {
+            started_at: pause.started_at,
+            ended_at: pause.ended_at,
+        }
+    }
+}
+
+#[derive(SimpleObject, Clone)]
+#[graphql(complex)]
 pub struct GqlTimeEntry {
     pub time_entry_id: String,
     pub user_id: String,
@@ -33,10 +74,24 @@ pub struct GqlTimeEntry {
     pub updated_at: i64,
     pub updated_by: String,
     pub deleted_at: Option<i64>,
+    pub hourly_rate_cents: Option<i64>,
+    pub currency: Option<String>,
+    pub breaks: Vec<GqlBreak>,
+    /// Milliseconds worked, leaving out the breaks; null until the entry has both ends.
+    pub net_duration_ms: Option<i64>,
+    /// The cost of the net duration.
+    pub amount_cents: Option<i64>,
 }
 
+/// Goes through `TimeEntryV1`, so GraphQL and HTTP clients see the same translation.
 impl From<TimeEntryView> for GqlTimeEntry {
     fn from(v: TimeEntryView) -> Self {
+        TimeEntryV1::from(v).into()
+    }
+}
+
+impl From<TimeEntryV1> for GqlTimeEntry {
+    fn from(v: TimeEntryV1) -> Self {
         Self {
             time_entry_id: v.time_entry_id,
             user_id: v.user_id,
@@ -48,10 +103,206 @@ impl From<TimeEntryView> for GqlTimeEntry {
             updated_at: v.updated_at,
             updated_by: v.updated_by,
             deleted_at: v.deleted_at,
+            hourly_rate_cents: v.hourly_rate_cents,
+            currency: v.currency,
+            breaks: v.breaks.into_iter().map(GqlBreak::from).collect(),
+            net_duration_ms: v.net_duration_ms,
+            amount_cents: v.amount_cents,
         }
     }
 }
 
+#[ComplexObject]
+impl GqlTimeEntry {
+    /// Resolved through the batching loader so a page of entries costs one directory call.
+    async fn user(&self, context: &Context<'_>) -> GqlResult<GqlUser> {
+        let state = context.data_unchecked::<AppState>();
+        let display_name = state.user_display_name_loader.load(&self.user_id).await?;
+        Ok(GqlUser {
+            id: self.user_id.clone(),
+            display_name,
+        })
+    }
+
+    /// The discussion on the entry, oldest first.
+    async fn comments(
+        &self,
+        context: &Context<'_>,
+        offset: Option<i64>,
+        limit: Option<i64>,
+    ) -> GqlResult<Vec<GqlTimeEntryComment>> {
+        let state = context.data_unchecked::<AppState>();
+        let comments = state
+            .time_entry_comments_handler
+            .comments(
+                &self.time_entry_id,
+                offset.unwrap_or(0).max(0) as u64,
+                limit.unwrap_or(20).max(0) as u64,
+            )
+            .await?;
+        Ok(comments.into_iter().map(Into::into).collect())
+    }
+
+    /// Receipts and screenshots linked to the entry, in the order they were added.
+    async fn attachments(&self, context: &Context<'_>) -> GqlResult<Vec<GqlTimeEntryAttachment>> {
+        let state = context.data_unchecked::<AppState>();
+        let attachments = state
+            .time_entry_attachments_handler
+            .attachments(&self.time_entry_id)
+            .await?;
+        Ok(attachments.into_iter().map(Into::into).collect())
+    }
+}
+
+/// Longest range a single time-by-tag breakdown may span.
+const MAX_DAYS: i64 = 366;
+
+#[derive(SimpleObject, Clone)]
+pub struct GqlTagTotal {
+    /// Null for entries without tags.
+    pub tag_id: Option<String>,
+    pub minutes: i64,
+}
+
+impl From<TagTotal> for GqlTagTotal {
+    fn from(total: TagTotal) -> Self {
+        Self {
+            tag_id: total.tag_id,
+            minutes: total.minutes,
+        }
+    }
+}
+
+#[derive(Debug, Enum, Copy, Clone, Eq, PartialEq)]
+pub enum GqlSimilarityReason {
+    Overlapping,
+    SameTagsSameDay,
+}
+
+impl From<SimilarityReason> for GqlSimilarityReason {
+    fn from(reason: SimilarityReason) -> Self {
+        match reason {
+            SimilarityReason::Overlapping => GqlSimilarityReason::Overlapping,
+            SimilarityReason::SameTagsSameDay => GqlSimilarityReason::SameTagsSameDay,
+        }
+    }
+}
+
+#[derive(SimpleObject, Clone)]
+pub struct GqlSimilarEntry {
+    pub time_entry: GqlTimeEntry,
+    pub reason: GqlSimilarityReason,
+}
+
+impl From<SimilarEntry> for GqlSimilarEntry {
+    fn from(entry: SimilarEntry) -> Self {
+        Self {
+            time_entry: entry.time_entry.into(),
+            reason: entry.reason.into(),
+        }
+    }
+}
+
+#[derive(SimpleObject, Clone)]
+#[graphql(name = "TimeEntryDay")]
+pub struct GqlTimeEntryDay {
+    /// `YYYY-MM-DD` in the requested time zone.
+    pub date: String,
+    pub total_ms: i64,
+    pub entries: Vec<GqlTimeEntry>,
+}
+
+impl From<DayEntries> for GqlTimeEntryDay {
+    fn from(day: DayEntries) -> Self {
+        Self {
+            date: day.date.to_string(),
+            total_ms: day.total_ms,
+            entries: day.entries.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+#[derive(SimpleObject, Clone)]
+#[graphql(name = "TimeEntryPage")]
+pub struct GqlTimeEntryPage {
+    pub items: Vec<GqlTimeEntry>,
+    /// Entries across every page.
+    pub total: i64,
+    /// The offset of the next page; null on the last page.
+    pub next_cursor: Option<i64>,
+}
+
+impl From<Page<TimeEntryView>> for GqlTimeEntryPage {
+    fn from(page: Page<TimeEntryView>) -> Self {
+        Self {
+            items: page.items.into_iter().map(Into::into).collect(),
+            total: page.total as i64,
+            next_cursor: page.next_cursor.map(|cursor| cursor as i64),
+        }
+    }
+}
+
+/// Which of a user's entries to list, and which page of them.
+#[derive(InputObject)]
+pub struct TimeEntryFilterInput {
+    /// Whose entries to list; defaults to the caller.
+    pub user_id: Option<String>,
+    #[graphql(default = 0, validator(minimum = 0))]
+    pub offset: i64,
+    #[graphql(default = 20, validator(minimum = 1, maximum = 100))]
+    pub limit: i64,
+    /// Newest start first.
+    #[graphql(default = true)]
+    pub sort_desc: bool,
+}
+
+/// The filter of a list query: `filter`, or the arguments it replaces for clients that
+/// still send them. Mixing the two is rejected rather than guessing which one wins.
+fn list_filter(
+    filter: Option<TimeEntryFilterInput>,
+    offset: Option<i64>,
+    limit: Option<i64>,
+    sort_desc: Option<bool>,
+    user_id: Option<String>,
+) -> GqlResult<TimeEntryFilterInput> {
+    let legacy = offset.is_some() || limit.is_some() || sort_desc.is_some() || user_id.is_some();
+    match filter {
+        Some(_) if legacy => Err(async_graphql::Error::new(
+            "pass either filter or offset, limit, sortDesc and userId",
+        )),
+        Some(filter) => Ok(filter),
+        None => Ok(TimeEntryFilterInput {
+            user_id,
+            offset: offset.unwrap_or(0).max(0),
+            limit: limit.unwrap_or(20).max(0),
+            sort_desc: sort_desc.unwrap_or(true),
+        }),
+    }
+}
+
+async fn list_page(
+    context: &Context<'_>,
+    filter: TimeEntryFilterInput,
+) -> GqlResult<Page<TimeEntryView>> {
+    let req_ctx = context
+        .data::<RequestContext>()
+        .map_err(|_| async_graphql::Error::new("Unauthorized"))?;
+    let user_id = filter.user_id.unwrap_or(req_ctx.user_id.clone());
+    if !req_ctx.principal().can_view_user(&user_id) {
+        return Err(async_graphql::Error::new("Forbidden"));
+    }
+    let state = context.data_unchecked::<AppState>();
+    Ok(state
+        .list_time_entries_handler
+        .list_by_user_id(
+            &user_id,
+            filter.offset as u64,
+            filter.limit as u64,
+            filter.sort_desc,
+        )
+        .await?)
+}
+
 #[derive(Default)]
 pub struct TimeEntryQueries;
 
@@ -60,24 +311,164 @@ impl TimeEntryQueries {
     async fn list_time_entries(
         &self,
         context: &Context<'_>,
-        offset: Option<i64>,
-        limit: Option<i64>,
-        sort_desc: Option<bool>,
+        filter: Option<TimeEntryFilterInput>,
+        #[graphql(deprecation = "Use `filter`.")] offset: Option<i64>,
+        #[graphql(deprecation = "Use `filter`.")] limit: Option<i64>,
+        #[graphql(deprecation = "Use `filter`.")] sort_desc: Option<bool>,
+        #[graphql(deprecation = "Use `filter`.")] user_id: Option<String>,
     ) -> GqlResult<Vec<GqlTimeEntry>> {
+        let filter = list_filter(filter, offset, limit, sort_desc, user_id)?;
+        let page = list_page(context, filter).await?;
+        Ok(page.items.into_iter().map(Into::into).collect())
+    }
+
+    /// `listTimeEntries` with the total across pages and the offset of the next page, so a
+    /// pager knows how many entries there are without another query.
+    async fn time_entry_page(
+        &self,
+        context: &Context<'_>,
+        filter: Option<TimeEntryFilterInput>,
+        #[graphql(deprecation = "Use `filter`.")] offset: Option<i64>,
+        #[graphql(deprecation = "Use `filter`.")] limit: Option<i64>,
+        #[graphql(deprecation = "Use `filter`.")] sort_desc: Option<bool>,
+        #[graphql(deprecation = "Use `filter`.")] user_id: Option<String>,
+    ) -> GqlResult<GqlTimeEntryPage> {
+        let filter = list_filter(filter, offset, limit, sort_desc, user_id)?;
+        Ok(list_page(context, filter).await?.into())
+    }
+
+    /// Registered minutes per tag between `from` and `to` (`YYYY-MM-DD`, inclusive), largest
+    /// first. `userId` defaults to the caller.
+    async fn time_by_tag(
+        &self,
+        context: &Context<'_>,
+        user_id: Option<String>,
+        from: String,
+        to: String,
+    ) -> GqlResult<Vec<GqlTagTotal>> {
         let req_ctx = context
             .data::<RequestContext>()
             .map_err(|_| async_graphql::Error::new("Unauthorized"))?;
+        let user_id = user_id.unwrap_or(req_ctx.user_id.clone());
+        if !req_ctx.principal().can_view_user(&user_id) {
+            return Err(async_graphql::Error::new("Forbidden"));
+        }
+        let (Ok(from), Ok(to)) = (from.parse::<NaiveDate>(), to.parse::<NaiveDate>()) else {
+            return Err(async_graphql::Error::new("from and to must be YYYY-MM-DD"));
+        };
+        if !(0..MAX_DAYS).contains(&(to - from).num_days()) {
+            return Err(async_graphql::Error::new(
+                "to must not be before from, and the range at most 366 days",
+            ));
+        }
         let state = context.data_unchecked::<AppState>();
-        let list: Vec<TimeEntryView> = state
+        let totals = state
             .list_time_entries_handler
-            .list_by_user_id(
-                &req_ctx.user_id,
-                offset.unwrap_or(0).max(0) as u64,
-                limit.unwrap_or(20).max(0) as u64,
-                sort_desc.unwrap_or(true),
-            )
+            .time_by_tag(&user_id, from, to)
+            .await?;
+        Ok(totals.into_iter().map(Into::into).collect())
+    }
+
+    /// The entries of each day from `from` to `to` (`YYYY-MM-DD`, inclusive) in `timeZone`
+    /// (an IANA name, default `UTC`), empty days included, so a calendar fetches a range in
+    /// one round trip. An entry crossing midnight is listed on both days, its time split
+    /// between them. `userId` defaults to the caller.
+    async fn time_entries_by_day(
+        &self,
+        context: &Context<'_>,
+        user_id: Option<String>,
+        from: String,
+        to: String,
+        time_zone: Option<String>,
+    ) -> GqlResult<Vec<GqlTimeEntryDay>> {
+        let req_ctx = context
+            .data::<RequestContext>()
+            .map_err(|_| async_graphql::Error::new("Unauthorized"))?;
+        let user_id = user_id.unwrap_or(req_ctx.user_id.clone());
+        if !req_ctx.principal().can_view_user(&user_id) {
+            return Err(async_graphql::Error::new("Forbidden"));
+        }
+        let (Ok(from), Ok(to)) = (from.parse::<NaiveDate>(), to.parse::<NaiveDate>()) else {
+            return Err(async_graphql::Error::new("from and to must be YYYY-MM-DD"));
+        };
+        if !(0..MAX_DAYS).contains(&(to - from).num_days()) {
+            return Err(async_graphql::Error::new(
+                "to must not be before from, and the range at most 366 days",
+            ));
+        }
+        let time_zone = time_zone.unwrap_or_else(|| "UTC".to_string());
+        let Ok(time_zone) = time_zone.parse::<Tz>() else {
+            return Err(async_graphql::Error::new(format!(
+                "Unknown time zone {time_zone}"
+            )));
+        };
+        let state = context.data_unchecked::<AppState>();
+        let days = state
+            .list_time_entries_handler
+            .entries_by_day(&user_id, from, to, time_zone)
+            .await?;
+        Ok(days.into_iter().map(Into::into).collect())
+    }
+
+    /// Existing entries that likely duplicate one from `start` to `end` (epoch millis) with
+    /// `tagIds`, so clients can warn before submitting. `userId` defaults to the caller.
+    async fn find_similar_entries(
+        &self,
+        context: &Context<'_>,
+        user_id: Option<String>,
+        start: i64,
+        end: i64,
+        tag_ids: Option<Vec<String>>,
+    ) -> GqlResult<Vec<GqlSimilarEntry>> {
+        let req_ctx = context
+            .data::<RequestContext>()
+            .map_err(|_| async_graphql::Error::new("Unauthorized"))?;
+        let user_id = user_id.unwrap_or(req_ctx.user_id.clone());
+        if !req_ctx.principal().can_view_user(&user_id) {
+            return Err(async_graphql::Error::new("Forbidden"));
+        }
+        if end <= start {
+            return Err(async_graphql::Error::new("end must be after start"));
+        }
+        let tag_ids = Tag::parse_all(&tag_ids.unwrap_or_default())?;
+        let state = context.data_unchecked::<AppState>();
+        let similar = state
+            .list_time_entries_handler
+            .find_similar_entries(&user_id, start, end, &tag_ids)
             .await?;
-        Ok(list.into_iter().map(Into::into).collect())
+        Ok(similar.into_iter().map(Into::into).collect())
+    }
+}
+
+#[derive(Default)]
+pub struct TimeEntrySubscriptions;
+
+#[Subscription]
+impl TimeEntrySubscriptions {
+    /// Every entry of `userId` (default: the caller) as the list reads it after each change,
+    /// from now on. An error item means updates were dropped; refetch the list.
+    async fn time_entry_updated(
+        &self,
+        context: &Context<'_>,
+        user_id: Option<String>,
+    ) -> async_graphql::Result<impl Stream<Item = GqlResult<GqlTimeEntry>> + use<>> {
+        let req_ctx = context
+            .data::<RequestContext>()
+            .map_err(|_| async_graphql::Error::new("Unauthorized"))?;
+        let user_id = user_id.unwrap_or(req_ctx.user_id.clone());
+        if !req_ctx.principal().can_view_user(&user_id) {
+            return Err(async_graphql::Error::new("Forbidden"));
+        }
+        let state = context.data_unchecked::<AppState>();
+        Ok(state
+            .time_entry_updates
+            .subscribe(&user_id)
+            .map(|update| match update {
+                TimeEntryUpdate::Upserted(row) => Ok((*row).into()),
+                TimeEntryUpdate::Missed(missed) => Err(async_graphql::Error::new(format!(
+                    "missed {missed} updates; refetch the list"
+                ))),
+            }))
     }
 }
 
@@ -87,8 +478,21 @@ mod list_time_entries_graphql_tests {
 
     use super::*;
     use rstest::rstest;
+    use std::sync::Arc;
+    use std::time::Duration;
 
+    use crate::modules::time_entries::use_cases::list_time_entries::projection::{
+        ListTimeEntriesState, TimeEntryRow, TimeEntryStatus,
+    };
+    use crate::modules::time_entries::use_cases::list_time_entries::queries::ListTimeEntriesQueryHandler;
+    use crate::modules::time_entries::use_cases::list_time_entries::updates::DEFAULT_CAPACITY;
+    use crate::shared::infrastructure::projection_store::ProjectionStore;
+    use crate::shared::infrastructure::projection_store::in_memory::InMemoryProjectionStore;
+    use crate::shared::infrastructure::projection_store::partitioned::PartitionedProjectionStore;
     use crate::shared::infrastructure::request_context::RequestContext;
+    use crate::shared::infrastructure::user_directory::SharedUserDirectory;
+    use crate::shared::infrastructure::user_directory::in_memory::InMemoryUserDirectory;
+    use crate::shared::infrastructure::user_directory::loader::UserDisplayNameLoader;
     use crate::shell::graphql::{MutationRoot, QueryRoot};
     use crate::tests::fixtures::tags::make_test_app_state;
 
@@ -108,6 +512,8 @@ mod list_time_entries_graphql_tests {
         RequestContext {
             user_id: "u-1".to_string(),
             tenant_id: "tenant-test".to_string(),
+            role: Default::default(),
+            scope: Default::default(),
         }
     }
 
@@ -139,18 +545,341 @@ mod list_time_entries_graphql_tests {
         assert_eq!(result.data.to_string(), "{listTimeEntries: []}");
     }
 
+    #[tokio::test]
+    async fn resolver_forbids_listing_another_users_entries_for_employees() {
+        let schema = make_schema_from_state(make_test_app_state());
+        let result = schema
+            .execute(
+                async_graphql::Request::new(
+                    r#"{ listTimeEntries(userId: "u-2") { timeEntryId } }"#,
+                )
+                .data(req_ctx()),
+            )
+            .await;
+        assert_eq!(result.errors[0].message, "Forbidden");
+    }
+
+    async fn make_seeded_state() -> AppState {
+        let mut state = make_test_app_state();
+        let store = InMemoryProjectionStore::<ListTimeEntriesState>::new();
+        let mut projection = ListTimeEntriesState::default();
+        for te_id in ["te-1", "te-2"] {
+            projection.rows.insert(
+                te_id.to_string(),
+                TimeEntryRow {
+                    time_entry_id: te_id.to_string(),
+                    user_id: "u-1".to_string(),
+                    started_at: Some(1_000),
+                    ended_at: Some(2_000),
+                    tag_ids: vec![],
+                    status: TimeEntryStatus::Registered,
+                    created_at: 0,
+                    created_by: "u-1".to_string(),
+                    updated_at: 0,
+                    updated_by: "u-1".to_string(),
+                    deleted_at: None,
+                    hourly_rate: None,
+                    last_event_id: None,
+                    breaks: vec![],
+                },
+            );
+        }
+        store.save(projection, 1).await.unwrap();
+        state.list_time_entries_handler =
+            ListTimeEntriesQueryHandler::new(PartitionedProjectionStore::single(store));
+        state
+    }
+
+    #[rstest]
+    #[case::first_page(
+        0,
+        1,
+        "{timeEntryPage: {items: [{status: REGISTERED}], total: 2, nextCursor: 1}}"
+    )]
+    #[case::last_page(
+        1,
+        1,
+        "{timeEntryPage: {items: [{status: REGISTERED}], total: 2, nextCursor: null}}"
+    )]
+    #[tokio::test]
+    async fn resolver_pages_with_the_total_and_next_cursor(
+        #[case] offset: i64,
+        #[case] limit: i64,
+        #[case] expected: &str,
+    ) {
+        let schema = make_schema_from_state(make_seeded_state().await);
+        let result = schema
+            .execute(
+                async_graphql::Request::new(format!(
+                    "{{ timeEntryPage(filter: {{ offset: {offset}, limit: {limit}, sortDesc: false }}) {{ items {{ status }} total nextCursor }} }}"
+                ))
+                .data(req_ctx()),
+            )
+            .await;
+        assert!(result.errors.is_empty(), "{:?}", result.errors);
+        assert_eq!(result.data.to_string(), expected);
+    }
+
+    #[rstest]
+    #[case::filter_defaults(r#"{ listTimeEntries(filter: {}) { timeEntryId } }"#, None)]
+    #[case::deprecated_arguments(r#"{ listTimeEntries(limit: 1) { timeEntryId } }"#, None)]
+    #[case::filter_below_minimum(
+        r#"{ listTimeEntries(filter: { limit: 0 }) { timeEntryId } }"#,
+        Some("Failed to parse")
+    )]
+    #[case::filter_above_maximum(
+        r#"{ listTimeEntries(filter: { limit: 101 }) { timeEntryId } }"#,
+        Some("Failed to parse")
+    )]
+    #[case::filter_and_arguments(
+        r#"{ listTimeEntries(filter: { limit: 1 }, offset: 1) { timeEntryId } }"#,
+        Some("pass either filter")
+    )]
+    #[case::filter_for_another_user(
+        r#"{ listTimeEntries(filter: { userId: "u-2" }) { timeEntryId } }"#,
+        Some("Forbidden")
+    )]
+    #[tokio::test]
+    async fn resolver_validates_the_list_filter(#[case] query: &str, #[case] error: Option<&str>) {
+        let schema = make_schema_from_state(make_seeded_state().await);
+        let result = schema
+            .execute(async_graphql::Request::new(query).data(req_ctx()))
+            .await;
+        match error {
+            None => assert!(result.errors.is_empty(), "{:?}", result.errors),
+            Some(error) => assert!(
+                result.errors[0].message.contains(error),
+                "{}",
+                result.errors[0].message
+            ),
+        }
+    }
+
+    #[tokio::test]
+    async fn resolver_resolves_user_display_names_in_one_batch() {
+        let mut state = make_seeded_state().await;
+        let directory = InMemoryUserDirectory::new();
+        directory.insert("u-1", "Teddy Test").await;
+        state.user_display_name_loader =
+            UserDisplayNameLoader::new(Arc::new(directory.clone()) as SharedUserDirectory);
+        let schema = make_schema_from_state(state);
+        let result = schema
+            .execute(
+                async_graphql::Request::new(
+                    r#"{ listTimeEntries { timeEntryId user { id displayName } } }"#,
+                )
+                .data(req_ctx()),
+            )
+            .await;
+        assert!(result.errors.is_empty());
+        let json = result.data.into_json().unwrap();
+        let entries = json["listTimeEntries"].as_array().unwrap();
+        assert_eq!(entries.len(), 2);
+        for entry in entries {
+            assert_eq!(entry["user"]["id"], "u-1");
+            assert_eq!(entry["user"]["displayName"], "Teddy Test");
+        }
+        assert_eq!(directory.batch_calls(), 1);
+    }
+
+    #[tokio::test]
+    async fn resolver_totals_time_by_tag() {
+        let mut state = make_test_app_state();
+        let store = InMemoryProjectionStore::<ListTimeEntriesState>::new();
+        let mut projection = ListTimeEntriesState::default();
+        projection.rows.insert(
+            "te-1".to_string(),
+            TimeEntryRow {
+                time_entry_id: "te-1".to_string(),
+                user_id: "u-1".to_string(),
+                started_at: Some(0),
+                ended_at: Some(3_600_000),
+                tag_ids: vec![Tag::parse("dev").unwrap()],
+                status: TimeEntryStatus::Registered,
+                created_at: 0,
+                created_by: "u-1".to_string(),
+                updated_at: 0,
+                updated_by: "u-1".to_string(),
+                deleted_at: None,
+                hourly_rate: None,
+                last_event_id: None,
+                breaks: vec![],
+            },
+        );
+        store.save(projection, 1).await.unwrap();
+        state.list_time_entries_handler =
+            ListTimeEntriesQueryHandler::new(PartitionedProjectionStore::single(store));
+        let schema = make_schema_from_state(state);
+        let result = schema
+            .execute(
+                async_graphql::Request::new(
+                    r#"{ timeByTag(from: "1970-01-01", to: "1970-01-01") { tagId minutes } }"#,
+                )
+                .data(req_ctx()),
+            )
+            .await;
+        assert!(result.errors.is_empty(), "{:?}", result.errors);
+        assert_eq!(
+            result.data.to_string(),
+            "{timeByTag: [{tagId: \"dev\", minutes: 60}]}"
+        );
+    }
+
+    #[tokio::test]
+    async fn resolver_rejects_forbidden_or_invalid_time_by_tag_requests() {
+        let schema = make_schema_from_state(make_test_app_state());
+        for (query, message) in [
+            (
+                r#"{ timeByTag(userId: "u-2", from: "2026-12-21", to: "2026-12-27") { minutes } }"#,
+                "Forbidden",
+            ),
+            (
+                r#"{ timeByTag(from: "21-12-2026", to: "2026-12-27") { minutes } }"#,
+                "from and to must be YYYY-MM-DD",
+            ),
+            (
+                r#"{ timeByTag(from: "2026-12-27", to: "2026-12-21") { minutes } }"#,
+                "to must not be before from, and the range at most 366 days",
+            ),
+        ] {
+            let result = schema
+                .execute(async_graphql::Request::new(query).data(req_ctx()))
+                .await;
+            assert_eq!(result.errors[0].message, message);
+        }
+    }
+
+    #[tokio::test]
+    async fn resolver_groups_entries_by_day() {
+        let schema = make_schema_from_state(make_seeded_state().await);
+        let result = schema
+            .execute(
+                async_graphql::Request::new(
+                    r#"{ timeEntriesByDay(from: "1970-01-01", to: "1970-01-02", timeZone: "Europe/Amsterdam") { date totalMs entries { timeEntryId } } }"#,
+                )
+                .data(req_ctx()),
+            )
+            .await;
+        assert!(result.errors.is_empty(), "{:?}", result.errors);
+        assert_eq!(
+            result.data.to_string(),
+            "{timeEntriesByDay: [{date: \"1970-01-01\", totalMs: 2000, entries: [{timeEntryId: \"te-1\"}, {timeEntryId: \"te-2\"}]}, {date: \"1970-01-02\", totalMs: 0, entries: []}]}"
+        );
+    }
+
+    #[tokio::test]
+    async fn resolver_rejects_forbidden_or_invalid_day_view_requests() {
+        let schema = make_schema_from_state(make_test_app_state());
+        for (query, message) in [
+            (
+                r#"{ timeEntriesByDay(userId: "u-2", from: "2026-12-21", to: "2026-12-27") { date } }"#,
+                "Forbidden",
+            ),
+            (
+                r#"{ timeEntriesByDay(from: "2026-12-27", to: "2026-12-21") { date } }"#,
+                "to must not be before from, and the range at most 366 days",
+            ),
+            (
+                r#"{ timeEntriesByDay(from: "2026-12-21", to: "2026-12-27", timeZone: "Mars/Olympus") { date } }"#,
+                "Unknown time zone Mars/Olympus",
+            ),
+        ] {
+            let result = schema
+                .execute(async_graphql::Request::new(query).data(req_ctx()))
+                .await;
+            assert_eq!(result.errors[0].message, message);
+        }
+    }
+
+    #[tokio::test]
+    async fn resolver_finds_similar_entries() {
+        let schema = make_schema_from_state(make_seeded_state().await);
+        let result = schema
+            .execute(
+                async_graphql::Request::new(
+                    r#"{ findSimilarEntries(start: 1500, end: 2500) { reason timeEntry { timeEntryId } } }"#,
+                )
+                .data(req_ctx()),
+            )
+            .await;
+        assert!(result.errors.is_empty(), "{:?}", result.errors);
+        let json = result.data.into_json().unwrap();
+        let entries = json["findSimilarEntries"].as_array().unwrap();
+        assert_eq!(entries.len(), 2);
+        assert!(entries.iter().all(|entry| entry["reason"] == "OVERLAPPING"));
+    }
+
+    #[tokio::test]
+    async fn resolver_rejects_forbidden_or_invalid_similar_entry_requests() {
+        let schema = make_schema_from_state(make_test_app_state());
+        for (query, message) in [
+            (
+                r#"{ findSimilarEntries(userId: "u-2", start: 0, end: 1) { reason } }"#,
+                "Forbidden",
+            ),
+            (
+                r#"{ findSimilarEntries(start: 1, end: 1) { reason } }"#,
+                "end must be after start",
+            ),
+            (
+                r#"{ findSimilarEntries(start: 0, end: 1, tagIds: [" "]) { reason } }"#,
+                "tag must not be blank",
+            ),
+        ] {
+            let result = schema
+                .execute(async_graphql::Request::new(query).data(req_ctx()))
+                .await;
+            assert_eq!(result.errors[0].message, message);
+        }
+    }
+
+    #[rstest]
+    fn it_should_convert_similarity_reasons_to_gql() {
+        assert_eq!(
+            GqlSimilarityReason::from(SimilarityReason::Overlapping),
+            GqlSimilarityReason::Overlapping
+        );
+        assert_eq!(
+            GqlSimilarityReason::from(SimilarityReason::SameTagsSameDay),
+            GqlSimilarityReason::SameTagsSameDay
+        );
+    }
+
+    #[tokio::test]
+    async fn resolver_returns_error_when_user_directory_offline() {
+        let mut state = make_seeded_state().await;
+        let directory = InMemoryUserDirectory::new();
+        directory.toggle_offline();
+        state.user_display_name_loader =
+            UserDisplayNameLoader::new(Arc::new(directory) as SharedUserDirectory);
+        let schema = make_schema_from_state(state);
+        let result = schema
+            .execute(
+                async_graphql::Request::new(r#"{ listTimeEntries { user { displayName } } }"#)
+                    .data(req_ctx()),
+            )
+            .await;
+        assert!(!result.errors.is_empty());
+    }
+
     #[rstest]
     fn it_should_convert_draft_status_to_gql() {
-        let gql: GqlTimeEntryStatus = TimeEntryStatus::Draft.into();
+        let gql: GqlTimeEntryStatus = TimeEntryStatusV1::from(TimeEntryStatus::Draft).into();
         assert_eq!(gql, GqlTimeEntryStatus::Draft);
     }
 
     #[rstest]
     fn it_should_convert_registered_status_to_gql() {
-        let gql: GqlTimeEntryStatus = TimeEntryStatus::Registered.into();
+        let gql: GqlTimeEntryStatus = TimeEntryStatusV1::from(TimeEntryStatus::Registered).into();
         assert_eq!(gql, GqlTimeEntryStatus::Registered);
     }
 
+    #[rstest]
+    fn it_should_convert_approved_status_to_gql() {
+        let gql: GqlTimeEntryStatus = TimeEntryStatusV1::from(TimeEntryStatus::Approved).into();
+        assert_eq!(gql, GqlTimeEntryStatus::Approved);
+    }
+
     #[rstest]
     fn it_should_convert_time_entry_view_to_gql() {
         let view = TimeEntryView {
@@ -165,11 +894,91 @@ mod list_time_entries_graphql_tests {
             updated_at: 0,
             updated_by: "user-0001".to_string(),
             deleted_at: None,
+            hourly_rate_cents: Some(9_000),
+            currency: Some("EUR".to_string()),
+            amount_cents: Some(3),
+            breaks: vec![],
+            net_duration_ms: None,
         };
         let gql = GqlTimeEntry::from(view);
         assert_eq!(gql.time_entry_id, "te-0001");
         assert_eq!(gql.started_at, Some(1_000));
         assert_eq!(gql.ended_at, Some(2_000));
         assert_eq!(gql.status, GqlTimeEntryStatus::Registered);
+        assert_eq!(gql.currency.as_deref(), Some("EUR"));
+        assert_eq!(gql.amount_cents, Some(3));
+    }
+
+    #[tokio::test]
+    async fn subscription_streams_the_users_rows_and_reports_dropped_updates() {
+        let state = make_test_app_state();
+        let updates = state.time_entry_updates.clone();
+        let schema = crate::shell::graphql::schema(state);
+        let mut stream = schema.execute_stream(
+            async_graphql::Request::new(
+                r#"subscription { timeEntryUpdated { timeEntryId status } }"#,
+            )
+            .data(req_ctx()),
+        );
+        // Polled once so the resolver subscribes before anything is published.
+        let idle = tokio::time::timeout(Duration::from_millis(20), stream.next()).await;
+        assert!(idle.is_err());
+
+        let mut row = make_seeded_view("te-1");
+        updates.publish(row.clone());
+        row.user_id = "u-2".to_string();
+        updates.publish(row);
+
+        let first = stream.next().await.unwrap();
+        assert!(first.errors.is_empty(), "{:?}", first.errors);
+        assert_eq!(
+            first.data.to_string(),
+            "{timeEntryUpdated: {timeEntryId: \"te-1\", status: REGISTERED}}"
+        );
+
+        // The other user's row is still buffered, so this overflows the buffer by one.
+        for _ in 0..DEFAULT_CAPACITY {
+            updates.publish(make_seeded_view("te-1"));
+        }
+        let lagged = stream.next().await.unwrap();
+        assert_eq!(
+            lagged.errors[0].message,
+            "missed 1 updates; refetch the list"
+        );
+    }
+
+    #[tokio::test]
+    async fn subscription_forbids_other_users_rows_for_employees() {
+        let schema = crate::shell::graphql::schema(make_test_app_state());
+        let mut stream = schema.execute_stream(
+            async_graphql::Request::new(
+                r#"subscription { timeEntryUpdated(userId: "u-2") { timeEntryId } }"#,
+            )
+            .data(req_ctx()),
+        );
+
+        let response = stream.next().await.unwrap();
+        assert_eq!(response.errors[0].message, "Forbidden");
+    }
+
+    fn make_seeded_view(time_entry_id: &str) -> TimeEntryView {
+        TimeEntryView {
+            time_entry_id: time_entry_id.to_string(),
+            user_id: "u-1".to_string(),
+            started_at: Some(1_000),
+            ended_at: Some(2_000),
+            tag_ids: vec![],
+            status: TimeEntryStatus::Registered,
+            created_at: 0,
+            created_by: "u-1".to_string(),
+            updated_at: 0,
+            updated_by: "u-1".to_string(),
+            deleted_at: None,
+            hourly_rate_cents: None,
+            currency: None,
+            amount_cents: None,
+            breaks: vec![],
+            net_duration_ms: None,
+        }
     }
 }
@@ -1,12 +1,17 @@
 use axum::{
     Json,
-    extract::{Query, State},
-    http::StatusCode,
+    extract::{Path, Query, State},
+    http::{StatusCode, header},
     response::IntoResponse,
 };
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
+use crate::modules::time_entries::use_cases::list_time_entries::inbound::api_v1::TimeEntryV1;
+use crate::modules::time_entries::use_cases::list_time_entries::rebuild_job;
+use crate::shared::infrastructure::job_store::Job;
 use crate::shared::infrastructure::request_context::RequestContext;
+use crate::shell::http::etag::etag;
+use crate::shell::jobs;
 use crate::shell::state::AppState;
 
 #[derive(Deserialize)]
@@ -14,6 +19,20 @@ pub struct ListTimeEntriesParams {
     pub offset: Option<u64>,
     pub limit: Option<u64>,
     pub sort_desc: Option<bool>,
+    /// Whose entries to list; defaults to the caller. Others require `can_view_user`.
+    pub user_id: Option<String>,
+}
+
+/// One page of entries, with what a pager needs to render the rest.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ListTimeEntriesPage {
+    pub items: Vec<TimeEntryV1>,
+    pub total: u64,
+    pub offset: u64,
+    pub limit: u64,
+    pub has_more: bool,
+    /// The offset of the next page; `None` on the last page.
+    pub next_cursor: Option<u64>,
 }
 
 pub async fn handle(
@@ -21,44 +40,118 @@ pub async fn handle(
     request_ctx: RequestContext,
     Query(params): Query<ListTimeEntriesParams>,
 ) -> impl IntoResponse {
-    match state
+    let user_id = params.user_id.unwrap_or(request_ctx.user_id.clone());
+    if !request_ctx.principal().can_view_user(&user_id) {
+        return StatusCode::FORBIDDEN.into_response();
+    }
+    let offset = params.offset.unwrap_or(0);
+    let limit = params.limit.unwrap_or(20);
+    let page = state
         .list_time_entries_handler
-        .list_by_user_id(
-            &request_ctx.user_id,
-            params.offset.unwrap_or(0),
-            params.limit.unwrap_or(20),
-            params.sort_desc.unwrap_or(true),
-        )
-        .await
-    {
-        Ok(entries) => Json(entries).into_response(),
+        .list_by_user_id(&user_id, offset, limit, params.sort_desc.unwrap_or(true))
+        .await;
+    match page {
+        Ok(page) => Json(ListTimeEntriesPage {
+            items: page.items.into_iter().map(TimeEntryV1::from).collect(),
+            total: page.total,
+            offset,
+            limit,
+            has_more: page.next_cursor.is_some(),
+            next_cursor: page.next_cursor,
+        })
+        .into_response(),
+        Err(_) => StatusCode::INTERNAL_SERVER_ERROR.into_response(),
+    }
+}
+
+/// GET /time-entries/{id} — the entry as lists show it, tagged with the version of its
+/// stream the view reflects for `If-Match` on PUT and DELETE. Deleted entries and entries the
+/// caller may not see are not found.
+pub async fn handle_get(
+    State(state): State<AppState>,
+    request_ctx: RequestContext,
+    Path(time_entry_id): Path<String>,
+) -> impl IntoResponse {
+    match state.list_time_entries_handler.get(&time_entry_id).await {
+        Ok(Some((view, version)))
+            if view.deleted_at.is_none()
+                && request_ctx.principal().can_view_user(&view.user_id) =>
+        {
+            let mut response = Json(TimeEntryV1::from(view)).into_response();
+            if let Some(version) = version {
+                response.headers_mut().insert(header::ETAG, etag(version));
+            }
+            response
+        }
+        Ok(_) => (StatusCode::NOT_FOUND, "time entry not found").into_response(),
+        Err(_) => StatusCode::INTERNAL_SERVER_ERROR.into_response(),
+    }
+}
+
+/// GET /admin/projections/list-time-entries — checkpoint and schema version per projector
+/// partition. Admins only.
+pub async fn handle_partitions(
+    State(state): State<AppState>,
+    request_ctx: RequestContext,
+) -> impl IntoResponse {
+    if !request_ctx.principal().can_administer() {
+        return StatusCode::FORBIDDEN.into_response();
+    }
+    match state.list_time_entries_handler.store().status().await {
+        Ok(partitions) => Json(partitions).into_response(),
         Err(_) => StatusCode::INTERNAL_SERVER_ERROR.into_response(),
     }
 }
 
+/// POST /admin/projections/list-time-entries/rebuild — starts a job rebuilding every
+/// partition from the event log; poll the `Location` it answers with. Admins only.
+pub async fn handle_rebuild(
+    State(state): State<AppState>,
+    request_ctx: RequestContext,
+) -> impl IntoResponse {
+    if !request_ctx.principal().can_administer() {
+        return StatusCode::FORBIDDEN.into_response();
+    }
+    let job = Job::queued(
+        rebuild_job::JOB_KIND,
+        serde_json::json!({}),
+        chrono::Utc::now().timestamp_millis(),
+    );
+    jobs::accept(&state, job).await
+}
+
 #[cfg(test)]
 mod list_time_entries_http_inbound_tests {
     use axum::{
         Router,
         body::Body,
         http::{Request, StatusCode},
-        routing::get,
+        routing::{get, post},
     };
     use http_body_util::BodyExt;
+    use rstest::rstest;
     use tower::ServiceExt;
 
-    use super::handle;
-    use crate::modules::time_entries::use_cases::list_time_entries::projection::ListTimeEntriesState;
+    use super::{handle, handle_get, handle_partitions, handle_rebuild};
+    use crate::modules::time_entries::use_cases::list_time_entries::projection::{
+        ListTimeEntriesState, TimeEntryRow, TimeEntryStatus,
+    };
     use crate::modules::time_entries::use_cases::list_time_entries::queries::ListTimeEntriesQueryHandler;
+    use crate::modules::time_entries::use_cases::list_time_entries::rebuild_job;
+    use crate::shared::infrastructure::event_store::in_memory::InMemoryEventStore;
+    use crate::shared::infrastructure::job_store::JobStore;
+    use crate::shared::infrastructure::projection_store::ProjectionStore;
     use crate::shared::infrastructure::projection_store::in_memory::InMemoryProjectionStore;
+    use crate::shared::infrastructure::projection_store::partitioned::PartitionedProjectionStore;
     use crate::shell::state::AppState;
-    use crate::tests::fixtures::tags::make_test_app_state;
+    use crate::tests::fixtures::tags::{make_test_app_state, make_test_app_state_with};
 
     fn make_failing_queries_state() -> AppState {
         let mut state = make_test_app_state();
         let mut projection_store = InMemoryProjectionStore::<ListTimeEntriesState>::new();
         projection_store.toggle_offline();
-        state.list_time_entries_handler = ListTimeEntriesQueryHandler::new(projection_store);
+        state.list_time_entries_handler =
+            ListTimeEntriesQueryHandler::new(PartitionedProjectionStore::single(projection_store));
         state
     }
 
@@ -69,9 +162,27 @@ mod list_time_entries_http_inbound_tests {
     fn app(state: AppState) -> Router {
         Router::new()
             .route("/list-time-entries", get(handle))
+            .route("/time-entries/{id}", get(handle_get))
+            .route(
+                "/admin/projections/list-time-entries",
+                get(handle_partitions),
+            )
+            .route(
+                "/admin/projections/list-time-entries/rebuild",
+                post(handle_rebuild),
+            )
             .with_state(state)
     }
 
+    fn partitions_request(role: &str) -> Request<Body> {
+        Request::get("/admin/projections/list-time-entries")
+            .header("x-user-id", "u-admin")
+            .header("x-tenant-id", "tenant-test")
+            .header("x-user-role", role)
+            .body(Body::empty())
+            .unwrap()
+    }
+
     #[tokio::test]
     async fn it_should_return_200_with_empty_list_when_no_entries_exist() {
         let response = app(make_test_state())
@@ -88,7 +199,17 @@ mod list_time_entries_http_inbound_tests {
         assert_eq!(response.status(), StatusCode::OK);
         let bytes = response.into_body().collect().await.unwrap().to_bytes();
         let json: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
-        assert_eq!(json, serde_json::json!([]));
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "items": [],
+                "total": 0,
+                "offset": 0,
+                "limit": 20,
+                "has_more": false,
+                "next_cursor": null
+            })
+        );
     }
 
     #[tokio::test]
@@ -106,6 +227,63 @@ mod list_time_entries_http_inbound_tests {
         assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
     }
 
+    #[rstest]
+    #[case::first_page(0, 2, 2, Some(2))]
+    #[case::last_page(2, 2, 1, None)]
+    #[case::past_the_end(5, 2, 0, None)]
+    #[tokio::test]
+    async fn it_should_describe_the_page_for_pagers(
+        #[case] offset: u64,
+        #[case] limit: u64,
+        #[case] items: usize,
+        #[case] next_cursor: Option<u64>,
+    ) {
+        let projection_store = InMemoryProjectionStore::<ListTimeEntriesState>::new();
+        let mut projection = ListTimeEntriesState::default();
+        for (index, time_entry_id) in ["te-1", "te-2", "te-3"].into_iter().enumerate() {
+            let row = TimeEntryRow {
+                time_entry_id: time_entry_id.to_string(),
+                user_id: "u-1".to_string(),
+                started_at: Some(index as i64 * 1_000),
+                ended_at: None,
+                tag_ids: vec![],
+                status: TimeEntryStatus::Draft,
+                created_at: 0,
+                created_by: "u-1".to_string(),
+                updated_at: 0,
+                updated_by: "u-1".to_string(),
+                deleted_at: None,
+                hourly_rate: None,
+                last_event_id: None,
+                breaks: vec![],
+            };
+            projection.rows.insert(row.time_entry_id.clone(), row);
+        }
+        projection_store.save(projection, 3).await.unwrap();
+        let state = make_test_app_state_with(InMemoryEventStore::new(), projection_store);
+
+        let response = app(state)
+            .oneshot(
+                Request::get(format!("/list-time-entries?offset={offset}&limit={limit}"))
+                    .header("x-user-id", "u-1")
+                    .header("x-tenant-id", "tenant-test")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let bytes = response.into_body().collect().await.unwrap().to_bytes();
+        let json: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+        assert_eq!(json["items"].as_array().unwrap().len(), items);
+        assert_eq!(json["total"], 3);
+        assert_eq!(json["offset"], offset);
+        assert_eq!(json["limit"], limit);
+        assert_eq!(json["has_more"], next_cursor.is_some());
+        assert_eq!(json["next_cursor"], serde_json::json!(next_cursor));
+    }
+
     #[tokio::test]
     async fn it_should_return_500_when_queries_fail() {
         let response = app(make_failing_queries_state())
@@ -121,4 +299,173 @@ mod list_time_entries_http_inbound_tests {
 
         assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
     }
+
+    #[rstest]
+    #[case::employee_self(None, Some("u-1"), StatusCode::OK)]
+    #[case::employee_other(None, Some("u-2"), StatusCode::FORBIDDEN)]
+    #[case::manager_other(Some("manager"), Some("u-2"), StatusCode::OK)]
+    #[case::admin_other(Some("admin"), Some("u-2"), StatusCode::OK)]
+    #[case::defaults_to_caller(None, None, StatusCode::OK)]
+    #[tokio::test]
+    async fn it_should_enforce_can_view_user(
+        #[case] role: Option<&str>,
+        #[case] user_id: Option<&str>,
+        #[case] expected: StatusCode,
+    ) {
+        let uri = match user_id {
+            Some(user_id) => format!("/list-time-entries?user_id={user_id}"),
+            None => "/list-time-entries".to_string(),
+        };
+        let mut request = Request::get(uri)
+            .header("x-user-id", "u-1")
+            .header("x-tenant-id", "tenant-test");
+        if let Some(role) = role {
+            request = request.header("x-user-role", role);
+        }
+        let response = app(make_test_state())
+            .oneshot(request.body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), expected);
+    }
+
+    async fn get_entry(
+        deleted_at: Option<i64>,
+        caller: &str,
+        role: Option<&str>,
+    ) -> axum::response::Response {
+        let projection_store = InMemoryProjectionStore::<ListTimeEntriesState>::new();
+        let mut projection = ListTimeEntriesState::default();
+        let row = TimeEntryRow {
+            time_entry_id: "te-1".to_string(),
+            user_id: "u-1".to_string(),
+            started_at: Some(1_000),
+            ended_at: Some(2_000),
+            tag_ids: vec![],
+            status: TimeEntryStatus::Registered,
+            created_at: 0,
+            created_by: "u-1".to_string(),
+            updated_at: 0,
+            updated_by: "u-1".to_string(),
+            deleted_at,
+            hourly_rate: None,
+            last_event_id: Some("TimeEntry-te-1:4".to_string()),
+            breaks: vec![],
+        };
+        projection.rows.insert(row.time_entry_id.clone(), row);
+        projection_store.save(projection, 4).await.unwrap();
+        let state = make_test_app_state_with(InMemoryEventStore::new(), projection_store);
+        let mut request = Request::get("/time-entries/te-1")
+            .header("x-user-id", caller)
+            .header("x-tenant-id", "tenant-test");
+        if let Some(role) = role {
+            request = request.header("x-user-role", role);
+        }
+
+        app(state)
+            .oneshot(request.body(Body::empty()).unwrap())
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn it_should_get_an_entry_tagged_with_its_stream_version() {
+        let response = get_entry(None, "u-1", None).await;
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()["etag"], "\"4\"");
+        let bytes = response.into_body().collect().await.unwrap().to_bytes();
+        let json: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+        assert_eq!(json["time_entry_id"], "te-1");
+        assert_eq!(json["ended_at"], 2_000);
+    }
+
+    #[rstest]
+    #[case::deleted(Some(3_000), "u-1", None, StatusCode::NOT_FOUND)]
+    #[case::other_employee(None, "u-2", None, StatusCode::NOT_FOUND)]
+    #[case::manager(None, "u-2", Some("manager"), StatusCode::OK)]
+    #[tokio::test]
+    async fn it_should_hide_deleted_entries_and_entries_the_caller_may_not_see(
+        #[case] deleted_at: Option<i64>,
+        #[case] caller: &str,
+        #[case] role: Option<&str>,
+        #[case] expected: StatusCode,
+    ) {
+        assert_eq!(get_entry(deleted_at, caller, role).await.status(), expected);
+    }
+
+    #[rstest]
+    #[case::admin("admin", StatusCode::OK)]
+    #[case::manager("manager", StatusCode::FORBIDDEN)]
+    #[tokio::test]
+    async fn it_should_show_partition_status_to_admins_only(
+        #[case] role: &str,
+        #[case] expected: StatusCode,
+    ) {
+        let response = app(make_test_state())
+            .oneshot(partitions_request(role))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), expected);
+        if expected == StatusCode::OK {
+            let bytes = response.into_body().collect().await.unwrap().to_bytes();
+            let json: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+            assert_eq!(
+                json,
+                serde_json::json!([{
+                    "partition": { "index": 0, "count": 1 },
+                    "checkpoint": 0,
+                    "schema_version": null,
+                }])
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn it_should_return_500_when_partition_status_fails() {
+        let response = app(make_failing_queries_state())
+            .oneshot(partitions_request("admin"))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+    }
+
+    #[rstest]
+    #[case::admin("admin", StatusCode::ACCEPTED)]
+    #[case::manager("manager", StatusCode::FORBIDDEN)]
+    #[tokio::test]
+    async fn it_should_queue_a_rebuild_job_for_admins(
+        #[case] role: &str,
+        #[case] expected: StatusCode,
+    ) {
+        let state = make_test_state();
+
+        let response = app(state.clone())
+            .oneshot(
+                Request::post("/admin/projections/list-time-entries/rebuild")
+                    .header("x-user-id", "u-admin")
+                    .header("x-tenant-id", "tenant-test")
+                    .header("x-user-role", role)
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), expected);
+        let bytes = response.into_body().collect().await.unwrap().to_bytes();
+        if expected == StatusCode::ACCEPTED {
+            let json: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+            let job = state
+                .job_store
+                .get(json["job_id"].as_str().unwrap())
+                .await
+                .unwrap()
+                .unwrap();
+            assert_eq!(job.kind, rebuild_job::JOB_KIND);
+        }
+    }
 }
@@ -0,0 +1,167 @@
+use async_graphql::futures_util::StreamExt;
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::{
+        IntoResponse,
+        sse::{Event, KeepAlive, Sse},
+    },
+};
+use serde_json::{Value, json};
+use std::convert::Infallible;
+
+use crate::modules::time_entries::use_cases::list_time_entries::inbound::api_v1::TimeEntryV1;
+use crate::modules::time_entries::use_cases::list_time_entries::projection::TimeEntryView;
+use crate::modules::time_entries::use_cases::list_time_entries::updates::TimeEntryUpdate;
+use crate::shared::infrastructure::request_context::RequestContext;
+use crate::shell::state::AppState;
+
+/// GET /users/{user_id}/time-entries/stream — server-sent events for every row of
+/// `user_id`'s list the projector writes from now on. Viewing others follows the
+/// list-time-entries rules.
+///
+/// Each `patch` event is a JSON Patch against an object of the user's entries keyed by
+/// `time_entry_id`. A `resync` event means updates were dropped and the list should be
+/// refetched.
+pub async fn handle(
+    State(state): State<AppState>,
+    request_ctx: RequestContext,
+    Path(user_id): Path<String>,
+) -> impl IntoResponse {
+    if !request_ctx.principal().can_view_user(&user_id) {
+        return StatusCode::FORBIDDEN.into_response();
+    }
+    let events = state
+        .time_entry_updates
+        .subscribe(&user_id)
+        .map(|update| Ok::<_, Infallible>(event_of(update)));
+    Sse::new(events)
+        .keep_alive(KeepAlive::default())
+        .into_response()
+}
+
+fn event_of(update: TimeEntryUpdate) -> Event {
+    match update {
+        TimeEntryUpdate::Upserted(row) => Event::default()
+            .event("patch")
+            .data(patch_of(&row).to_string()),
+        TimeEntryUpdate::Missed(missed) => Event::default()
+            .event("resync")
+            .data(json!({ "missed": missed }).to_string()),
+    }
+}
+
+/// `add` replaces an existing member, so the same patch serves new and changed rows.
+/// Deleted rows are kept with their `deleted_at` set.
+fn patch_of(row: &TimeEntryView) -> Value {
+    // JSON Pointer escaping (RFC 6901); UUIDs never need it, but ids are not validated here.
+    let key = row.time_entry_id.replace('~', "~0").replace('/', "~1");
+    let value = TimeEntryV1::from(row.clone());
+    json!([{ "op": "add", "path": format!("/{key}"), "value": value }])
+}
+
+#[cfg(test)]
+mod list_time_entries_sse_inbound_tests {
+    use super::*;
+    use crate::modules::time_entries::use_cases::list_time_entries::projection::TimeEntryStatus;
+    use crate::tests::fixtures::tags::make_test_app_state;
+    use axum::{Router, body::Body, http::Request, routing::get};
+    use rstest::rstest;
+    use tower::ServiceExt;
+
+    fn app(state: AppState) -> Router {
+        Router::new()
+            .route("/users/{user_id}/time-entries/stream", get(handle))
+            .with_state(state)
+    }
+
+    fn view(time_entry_id: &str, user_id: &str) -> TimeEntryView {
+        TimeEntryView {
+            time_entry_id: time_entry_id.to_string(),
+            user_id: user_id.to_string(),
+            started_at: Some(1_000),
+            ended_at: Some(61_000),
+            tag_ids: vec![],
+            status: TimeEntryStatus::Registered,
+            created_at: 0,
+            created_by: user_id.to_string(),
+            updated_at: 0,
+            updated_by: user_id.to_string(),
+            deleted_at: None,
+            hourly_rate_cents: None,
+            currency: None,
+            amount_cents: None,
+            breaks: vec![],
+            net_duration_ms: None,
+        }
+    }
+
+    fn request(path: &str, user_id: &str, role: &str) -> Request<Body> {
+        Request::get(path)
+            .header("x-user-id", user_id)
+            .header("x-tenant-id", "tenant-test")
+            .header("x-user-role", role)
+            .body(Body::empty())
+            .unwrap()
+    }
+
+    #[rstest]
+    #[case::self_("u-1", "employee")]
+    #[case::manager("m-1", "manager")]
+    #[tokio::test]
+    async fn it_should_stream_patches_for_the_users_rows(#[case] caller: &str, #[case] role: &str) {
+        let state = make_test_app_state();
+        let updates = state.time_entry_updates.clone();
+
+        let response = app(state)
+            .oneshot(request("/users/u-1/time-entries/stream", caller, role))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()["content-type"], "text/event-stream");
+
+        updates.publish(view("te-other", "u-2"));
+        updates.publish(view("te-1", "u-1"));
+        let mut body = response.into_body().into_data_stream();
+        let frame = body.next().await.unwrap().unwrap();
+
+        let frame = String::from_utf8(frame.to_vec()).unwrap();
+        let data = frame
+            .lines()
+            .find_map(|line| line.strip_prefix("data: "))
+            .unwrap();
+        assert!(frame.starts_with("event: patch\n"), "{frame}");
+        assert_eq!(
+            serde_json::from_str::<Value>(data).unwrap(),
+            json!([{ "op": "add", "path": "/te-1", "value": view("te-1", "u-1") }])
+        );
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_forbid_streaming_other_users_entries_to_employees() {
+        let response = app(make_test_app_state())
+            .oneshot(request("/users/u-2/time-entries/stream", "u-1", "employee"))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    }
+
+    #[rstest]
+    #[case::patch(
+        TimeEntryUpdate::Upserted(Box::new(view("a/b~c", "u-1"))),
+        "patch",
+        json!([{ "op": "add", "path": "/a~1b~0c", "value": view("a/b~c", "u-1") }])
+    )]
+    #[case::resync(TimeEntryUpdate::Missed(3), "resync", json!({ "missed": 3 }))]
+    fn it_should_encode_updates_as_events(
+        #[case] update: TimeEntryUpdate,
+        #[case] name: &str,
+        #[case] data: Value,
+    ) {
+        let expected = Event::default().event(name).data(data.to_string());
+
+        assert_eq!(format!("{:?}", event_of(update)), format!("{expected:?}"));
+    }
+}
@@ -1,3 +1,10 @@
+use crate::modules::time_entries::core::breaks::{Break, net_millis_within};
+use crate::modules::time_entries::core::hourly_rate::HourlyRate;
+use crate::modules::time_entries::core::tag::Tag;
+use crate::shared::core::primitives::last_event_version;
+use crate::shared::infrastructure::capacity::{EvictRows, evict_oldest};
+use crate::shared::infrastructure::projection_store::partitioned::MergeProjection;
+
 pub const SCHEMA_VERSION: u32 = 1;
 
 #[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
@@ -5,11 +12,54 @@ pub const SCHEMA_VERSION: u32 = 1;
 pub enum TimeEntryStatus {
     Draft,
     Registered,
+    Approved,
+    /// Sent back by a manager; still registered and open to changes until approved.
+    Rejected,
 }
 
 #[derive(Clone, Default)]
 pub struct ListTimeEntriesState {
     pub rows: std::collections::HashMap<String, TimeEntryRow>,
+    /// Entries moved to cold storage, with the stream version they were archived at. Replays
+    /// skip their events up to that version, so a rebuild does not bring them back.
+    pub archived: std::collections::HashMap<String, i64>,
+}
+
+impl ListTimeEntriesState {
+    /// Writes `row` unless the stored row already reflects the event that produced it.
+    /// Returns whether the row was written.
+    pub fn upsert(&mut self, row: TimeEntryRow) -> bool {
+        let already_applied = match last_event_version(row.last_event_id.as_deref()) {
+            Some(version) => self
+                .rows
+                .get(&row.time_entry_id)
+                .is_some_and(|existing| existing.has_applied(version)),
+            None => false,
+        };
+        if already_applied {
+            return false;
+        }
+        self.rows.insert(row.time_entry_id.clone(), row);
+        true
+    }
+}
+
+/// Partitions own disjoint streams, so their rows never collide.
+impl MergeProjection for ListTimeEntriesState {
+    fn merge(&mut self, partition: Self) {
+        self.rows.extend(partition.rows);
+        self.archived.extend(partition.archived);
+    }
+}
+
+impl EvictRows for ListTimeEntriesState {
+    fn row_count(&self) -> usize {
+        self.rows.len()
+    }
+
+    fn evict_rows(&mut self, max_rows: usize) -> usize {
+        evict_oldest(&mut self.rows, max_rows, |row| row.updated_at)
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
@@ -18,33 +68,95 @@ pub struct TimeEntryRow {
     pub user_id: String,
     pub started_at: Option<i64>,
     pub ended_at: Option<i64>,
-    pub tag_ids: Vec<String>,
+    pub tag_ids: Vec<Tag>,
     pub status: TimeEntryStatus,
     pub created_at: i64,
     pub created_by: String,
     pub updated_at: i64,
     pub updated_by: String,
     pub deleted_at: Option<i64>,
+    /// Absent in rows persisted before rates existed.
+    #[serde(default)]
+    pub hourly_rate: Option<HourlyRate>,
+    /// Absent in rows persisted before breaks existed.
+    #[serde(default)]
+    pub breaks: Vec<Break>,
     pub last_event_id: Option<String>,
 }
 
+impl TimeEntryRow {
+    /// Whether the event at `stream_version` is already reflected in this row.
+    /// `last_event_id` has the form `{stream_id}:{stream_version}`.
+    pub fn has_applied(&self, stream_version: i64) -> bool {
+        last_event_version(self.last_event_id.as_deref())
+            .is_some_and(|applied| applied >= stream_version)
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 pub struct TimeEntryView {
     pub time_entry_id: String,
     pub user_id: String,
     pub started_at: Option<i64>,
     pub ended_at: Option<i64>,
-    pub tag_ids: Vec<String>,
+    pub tag_ids: Vec<Tag>,
     pub status: TimeEntryStatus,
     pub created_at: i64,
     pub created_by: String,
     pub updated_at: i64,
     pub updated_by: String,
     pub deleted_at: Option<i64>,
+    pub hourly_rate_cents: Option<i64>,
+    pub currency: Option<String>,
+    pub breaks: Vec<Break>,
+    /// The time worked, so between the ends and outside the breaks; present once the entry
+    /// has both ends.
+    pub net_duration_ms: Option<i64>,
+    /// The cost of the net duration at the entry's rate; present once it has a rate and both
+    /// ends.
+    pub amount_cents: Option<i64>,
+}
+
+impl TimeEntryRow {
+    /// The time worked, once the entry has both ends.
+    pub fn net_duration_ms(&self) -> Option<i64> {
+        let (started_at, ended_at) = (self.started_at?, self.ended_at?);
+        Some(net_millis_within(
+            started_at,
+            ended_at,
+            &self.breaks,
+            started_at,
+            ended_at,
+        ))
+    }
+
+    /// The milliseconds the entry works between `from` and `until`, once it has both ends.
+    pub fn net_millis_within(&self, from: i64, until: i64) -> Option<i64> {
+        Some(net_millis_within(
+            self.started_at?,
+            self.ended_at?,
+            &self.breaks,
+            from,
+            until,
+        ))
+    }
+
+    /// The cost of the time worked at the entry's rate, once it has both a rate and an
+    /// interval.
+    pub fn amount_cents(&self) -> Option<i64> {
+        let rate = self.hourly_rate.as_ref()?;
+        Some(rate.amount_cents(self.net_duration_ms()?))
+    }
 }
 
 impl From<TimeEntryRow> for TimeEntryView {
     fn from(row: TimeEntryRow) -> Self {
+        let amount_cents = row.amount_cents();
+        let net_duration_ms = row.net_duration_ms();
+        let (hourly_rate_cents, currency) = row
+            .hourly_rate
+            .map(|rate| (rate.cents, rate.currency))
+            .unzip();
         Self {
             time_entry_id: row.time_entry_id,
             user_id: row.user_id,
@@ -57,6 +169,11 @@ impl From<TimeEntryRow> for TimeEntryView {
             updated_at: row.updated_at,
             updated_by: row.updated_by,
             deleted_at: row.deleted_at,
+            hourly_rate_cents,
+            currency,
+            breaks: row.breaks,
+            net_duration_ms,
+            amount_cents,
         }
     }
 }
@@ -80,7 +197,9 @@ mod time_entry_projector_model_tests {
             updated_at: 1_700_000_000_000i64,
             updated_by: "user-fixed-0001".to_string(),
             deleted_at: None,
+            hourly_rate: None,
             last_event_id: None,
+            breaks: vec![],
         };
         assert_eq!(row.time_entry_id, "te-fixed-0001");
         assert_eq!(row.user_id, "user-fixed-0001");
@@ -97,14 +216,16 @@ mod time_entry_projector_model_tests {
             user_id: "user-fixed-0001".to_string(),
             started_at: Some(1_700_000_000_000i64),
             ended_at: Some(1_700_000_360_000i64),
-            tag_ids: vec!["tag-1".to_string()],
+            tag_ids: vec![Tag::parse("tag-1").unwrap()],
             status: TimeEntryStatus::Registered,
             created_at: 1_700_000_000_000i64,
             created_by: "user-fixed-0001".to_string(),
             updated_at: 1_700_000_000_000i64,
             updated_by: "user-fixed-0001".to_string(),
             deleted_at: None,
+            hourly_rate: None,
             last_event_id: None,
+            breaks: vec![],
         };
         assert_eq!(row.status, TimeEntryStatus::Registered);
         assert_eq!(row.started_at, Some(1_700_000_000_000i64));
@@ -119,14 +240,16 @@ mod time_entry_projector_model_tests {
             user_id: "user-fixed-0001".to_string(),
             started_at: Some(1_700_000_000_000i64),
             ended_at: Some(1_700_000_360_000i64),
-            tag_ids: vec!["tag-1".to_string()],
+            tag_ids: vec![Tag::parse("tag-1").unwrap()],
             status: TimeEntryStatus::Registered,
             created_at: 1_700_000_000_000i64,
             created_by: "user-fixed-0001".to_string(),
             updated_at: 1_700_000_000_000i64,
             updated_by: "user-fixed-0001".to_string(),
             deleted_at: None,
+            hourly_rate: None,
             last_event_id: Some("stream:1".to_string()),
+            breaks: vec![],
         };
         let view = TimeEntryView::from(row.clone());
         assert_eq!(view.time_entry_id, row.time_entry_id);
@@ -149,4 +272,158 @@ mod time_entry_projector_model_tests {
             serde_json::json!("registered")
         );
     }
+
+    #[rstest]
+    #[case::no_event_applied(None, 1, false)]
+    #[case::older_event(Some("TimeEntry-te-1:2"), 3, false)]
+    #[case::same_event(Some("TimeEntry-te-1:3"), 3, true)]
+    #[case::newer_event(Some("TimeEntry-te-1:4"), 3, true)]
+    #[case::malformed_id(Some("TimeEntry-te-1"), 1, false)]
+    fn it_should_detect_already_applied_events(
+        #[case] last_event_id: Option<&str>,
+        #[case] stream_version: i64,
+        #[case] expected: bool,
+    ) {
+        let row = TimeEntryRow {
+            time_entry_id: "te-1".to_string(),
+            user_id: "user-fixed-0001".to_string(),
+            started_at: None,
+            ended_at: None,
+            tag_ids: vec![],
+            status: TimeEntryStatus::Draft,
+            created_at: 0,
+            created_by: "user-fixed-0001".to_string(),
+            updated_at: 0,
+            updated_by: "user-fixed-0001".to_string(),
+            deleted_at: None,
+            hourly_rate: None,
+            last_event_id: last_event_id.map(str::to_string),
+            breaks: vec![],
+        };
+        assert_eq!(row.has_applied(stream_version), expected);
+    }
+
+    fn row_at(last_event_id: Option<&str>, started_at: Option<i64>) -> TimeEntryRow {
+        TimeEntryRow {
+            time_entry_id: "te-1".to_string(),
+            user_id: "user-fixed-0001".to_string(),
+            started_at,
+            ended_at: None,
+            tag_ids: vec![],
+            status: TimeEntryStatus::Draft,
+            created_at: 0,
+            created_by: "user-fixed-0001".to_string(),
+            updated_at: 0,
+            updated_by: "user-fixed-0001".to_string(),
+            deleted_at: None,
+            hourly_rate: None,
+            last_event_id: last_event_id.map(str::to_string),
+            breaks: vec![],
+        }
+    }
+
+    #[rstest]
+    #[case::new_row(None, Some("TimeEntry-te-1:1"), true)]
+    #[case::replayed_event(Some("TimeEntry-te-1:2"), Some("TimeEntry-te-1:1"), false)]
+    #[case::same_event(Some("TimeEntry-te-1:2"), Some("TimeEntry-te-1:2"), false)]
+    #[case::newer_event(Some("TimeEntry-te-1:2"), Some("TimeEntry-te-1:3"), true)]
+    #[case::unversioned_incoming(Some("TimeEntry-te-1:2"), None, true)]
+    fn it_should_upsert_only_when_the_event_is_not_yet_applied(
+        #[case] existing: Option<&str>,
+        #[case] incoming: Option<&str>,
+        #[case] written: bool,
+    ) {
+        let mut state = ListTimeEntriesState::default();
+        if let Some(existing) = existing {
+            state
+                .rows
+                .insert("te-1".to_string(), row_at(Some(existing), Some(1_000)));
+        }
+
+        assert_eq!(state.upsert(row_at(incoming, None)), written);
+
+        let expected_started_at = if written { None } else { Some(1_000) };
+        assert_eq!(state.rows["te-1"].started_at, expected_started_at);
+    }
+
+    #[rstest]
+    fn it_should_merge_partition_rows() {
+        let row = |time_entry_id: &str| TimeEntryRow {
+            time_entry_id: time_entry_id.to_string(),
+            user_id: "user-fixed-0001".to_string(),
+            started_at: None,
+            ended_at: None,
+            tag_ids: vec![],
+            status: TimeEntryStatus::Draft,
+            created_at: 0,
+            created_by: "user-fixed-0001".to_string(),
+            updated_at: 0,
+            updated_by: "user-fixed-0001".to_string(),
+            deleted_at: None,
+            hourly_rate: None,
+            last_event_id: None,
+            breaks: vec![],
+        };
+        let mut state = ListTimeEntriesState::default();
+        state.upsert(row("te-1"));
+        let mut partition = ListTimeEntriesState::default();
+        partition.upsert(row("te-2"));
+
+        state.merge(partition);
+
+        assert_eq!(state.rows.len(), 2);
+        assert!(state.rows.contains_key("te-2"));
+    }
+
+    #[rstest]
+    #[case::priced(Some(7_200_000), Some(9_000), Some(18_000))]
+    #[case::running(None, Some(9_000), None)]
+    #[case::unpriced(Some(7_200_000), None, None)]
+    fn it_should_price_the_view(
+        #[case] ended_at: Option<i64>,
+        #[case] hourly_rate_cents: Option<i64>,
+        #[case] amount_cents: Option<i64>,
+    ) {
+        let row = TimeEntryRow {
+            started_at: Some(0),
+            ended_at,
+            hourly_rate: hourly_rate_cents.map(|cents| HourlyRate {
+                cents,
+                currency: "EUR".to_string(),
+            }),
+            ..row_at(None, None)
+        };
+
+        let view = TimeEntryView::from(row);
+
+        assert_eq!(view.hourly_rate_cents, hourly_rate_cents);
+        assert_eq!(view.amount_cents, amount_cents);
+        assert_eq!(view.currency.is_some(), hourly_rate_cents.is_some());
+    }
+
+    #[rstest]
+    fn it_should_read_rows_persisted_before_rates_existed() {
+        let mut json = serde_json::to_value(row_at(None, None)).unwrap();
+        json.as_object_mut().unwrap().remove("hourly_rate");
+
+        let row: TimeEntryRow = serde_json::from_value(json).unwrap();
+
+        assert_eq!(row.hourly_rate, None);
+    }
+
+    #[rstest]
+    fn it_should_evict_the_least_recently_updated_rows() {
+        let mut state = ListTimeEntriesState::default();
+        for (time_entry_id, updated_at) in [("te-1", 3_000), ("te-2", 1_000), ("te-3", 2_000)] {
+            let mut row = row_at(None, None);
+            row.time_entry_id = time_entry_id.to_string();
+            row.updated_at = updated_at;
+            state.rows.insert(time_entry_id.to_string(), row);
+        }
+
+        assert_eq!(state.evict_rows(2), 1);
+
+        assert!(!state.rows.contains_key("te-2"));
+        assert_eq!(state.row_count(), 2);
+    }
 }
@@ -1,11 +1,15 @@
 use crate::modules::time_entries::core::events::TimeEntryEvent;
 use crate::modules::time_entries::core::projections::{Mutation, apply};
 use crate::modules::time_entries::use_cases::list_time_entries::projection::{
-    ListTimeEntriesState, SCHEMA_VERSION,
+    ListTimeEntriesState, SCHEMA_VERSION, TimeEntryRow, TimeEntryStatus,
 };
-use crate::shared::infrastructure::event_store::StoredEvent;
-use crate::shared::infrastructure::event_store::in_memory::InMemoryEventStore;
+use crate::modules::time_entries::use_cases::list_time_entries::queries::ListTimeEntriesCache;
+use crate::modules::time_entries::use_cases::list_time_entries::updates::TimeEntryUpdates;
+use crate::shared::core::partitioning::Partition;
+use crate::shared::infrastructure::control_store::PauseSwitch;
+use crate::shared::infrastructure::event_store::{EventLog, SharedEventLog, StoredEvent};
 use crate::shared::infrastructure::projection_store::ProjectionStore;
+use std::sync::Arc;
 use tokio::sync::broadcast;
 
 #[derive(Debug, Clone)]
@@ -31,6 +35,26 @@ pub enum ProjectionTechnicalEvent {
         reason: String,
         timestamp: i64,
     },
+    GapDetected {
+        projection_name: String,
+        checkpoint: u64,
+        received: u64,
+    },
+    CaughtUp {
+        projection_name: String,
+        from_checkpoint: u64,
+        events_replayed: u64,
+    },
+    CatchUpFailed {
+        projection_name: String,
+        reason: String,
+        timestamp: i64,
+    },
+}
+
+/// The name a partition's projector runs under, also used for its lease and control.
+pub fn partition_projector_name(partition: Partition) -> String {
+    format!("list_time_entries:{}", partition.index)
 }
 
 pub struct ListTimeEntriesProjector<TStore>
@@ -39,8 +63,12 @@ where
 {
     pub name: String,
     pub store: TStore,
-    pub event_store: InMemoryEventStore<TimeEntryEvent>,
+    pub event_store: SharedEventLog<TimeEntryEvent>,
     pub technical_tx: broadcast::Sender<ProjectionTechnicalEvent>,
+    pub query_cache: Option<ListTimeEntriesCache>,
+    pub updates: Option<TimeEntryUpdates>,
+    pub partition: Option<Partition>,
+    pub pause: Option<PauseSwitch>,
 }
 
 impl<TStore> ListTimeEntriesProjector<TStore>
@@ -50,39 +78,88 @@ where
     pub fn new(
         name: impl Into<String>,
         store: TStore,
-        event_store: InMemoryEventStore<TimeEntryEvent>,
+        event_store: impl EventLog<TimeEntryEvent> + 'static,
         technical_tx: broadcast::Sender<ProjectionTechnicalEvent>,
     ) -> Self {
         Self {
             name: name.into(),
             store,
-            event_store,
+            event_store: Arc::new(event_store),
             technical_tx,
+            query_cache: None,
+            updates: None,
+            partition: None,
+            pause: None,
         }
     }
 
+    /// Projects only the streams `partition` owns into `store`. Events of other streams still
+    /// advance the checkpoint, so each partition keeps its own complete watermark.
+    pub fn with_partition(mut self, partition: Partition) -> Self {
+        self.partition = Some(partition);
+        self
+    }
+
+    /// Drops cached query pages for every user whose rows this projector writes.
+    pub fn with_query_cache(mut self, query_cache: ListTimeEntriesCache) -> Self {
+        self.query_cache = Some(query_cache);
+        self
+    }
+
+    /// Publishes every row this projector writes, as the list reads it, to live subscribers.
+    pub fn with_updates(mut self, updates: TimeEntryUpdates) -> Self {
+        self.updates = Some(updates);
+        self
+    }
+
+    /// Stops applying events while `pause` is set; on resume the projector catches up from its
+    /// checkpoint.
+    pub fn with_pause_switch(mut self, pause: PauseSwitch) -> Self {
+        self.pause = Some(pause);
+        self
+    }
+
     pub async fn run(self, mut receiver: broadcast::Receiver<StoredEvent<TimeEntryEvent>>) {
         let stored_schema = self.store.schema_version().await.unwrap_or(None);
-        if stored_schema != Some(SCHEMA_VERSION)
-            && let Err(reason) = self.rebuild().await
-        {
-            let _ = self
-                .technical_tx
-                .send(ProjectionTechnicalEvent::RebuildFailed {
-                    projection_name: self.name.clone(),
-                    reason: reason.to_string(),
-                    timestamp: chrono::Utc::now().timestamp_millis(),
-                });
-            return;
+        if stored_schema != Some(SCHEMA_VERSION) {
+            if let Err(reason) = self.rebuild().await {
+                let _ = self
+                    .technical_tx
+                    .send(ProjectionTechnicalEvent::RebuildFailed {
+                        projection_name: self.name.clone(),
+                        reason: reason.to_string(),
+                        timestamp: chrono::Utc::now().timestamp_millis(),
+                    });
+                return;
+            }
+        } else if self.hold_while_paused(&mut receiver).await.is_none() {
+            // Events appended while the projector was down never reach this receiver.
+            let _ = self.catch_up().await;
         }
 
         loop {
             match receiver.recv().await {
                 Ok(stored_event) => {
+                    match self.hold_while_paused(&mut receiver).await {
+                        Some(true) => continue,
+                        Some(false) => break,
+                        None => {}
+                    }
                     let checkpoint = self.store.checkpoint().await.unwrap_or(0);
                     if stored_event.global_position < checkpoint {
                         continue;
                     }
+                    if stored_event.global_position > checkpoint {
+                        let _ = self
+                            .technical_tx
+                            .send(ProjectionTechnicalEvent::GapDetected {
+                                projection_name: self.name.clone(),
+                                checkpoint,
+                                received: stored_event.global_position,
+                            });
+                        let _ = self.catch_up().await;
+                        continue;
+                    }
                     let start = std::time::Instant::now();
                     if self.apply_stored_event(&stored_event).await.is_err() {
                         continue;
@@ -96,6 +173,11 @@ where
                         });
                 }
                 Err(broadcast::error::RecvError::Lagged(_)) => {
+                    match self.hold_while_paused(&mut receiver).await {
+                        Some(true) => continue,
+                        Some(false) => break,
+                        None => {}
+                    }
                     if let Err(reason) = self.rebuild().await {
                         let _ = self
                             .technical_tx
@@ -112,7 +194,81 @@ where
         }
     }
 
-    async fn rebuild(&self) -> anyhow::Result<()> {
+    /// `None` when not paused. Otherwise waits for the resume and catches up, returning
+    /// `Some(false)` if the channel closed meanwhile.
+    async fn hold_while_paused(
+        &self,
+        receiver: &mut broadcast::Receiver<StoredEvent<TimeEntryEvent>>,
+    ) -> Option<bool> {
+        let pause = self.pause.as_ref()?;
+        if !pause.is_paused().await {
+            return None;
+        }
+        if !pause.wait_discarding(receiver).await {
+            return Some(false);
+        }
+        let _ = self.catch_up().await;
+        Some(true)
+    }
+
+    /// Re-applies every stored event from the persisted checkpoint onwards.
+    /// Safe after a crash mid-batch: rows skip events their `last_event_id` already covers.
+    async fn catch_up(&self) -> anyhow::Result<()> {
+        let result = self.replay_from_checkpoint().await;
+        if let Err(reason) = &result {
+            let _ = self
+                .technical_tx
+                .send(ProjectionTechnicalEvent::CatchUpFailed {
+                    projection_name: self.name.clone(),
+                    reason: reason.to_string(),
+                    timestamp: chrono::Utc::now().timestamp_millis(),
+                });
+        }
+        result
+    }
+
+    async fn replay_from_checkpoint(&self) -> anyhow::Result<()> {
+        let from_checkpoint = self.store.checkpoint().await?;
+        let missed = self.event_store.load_all_from(from_checkpoint).await?;
+        if missed.is_empty() {
+            return Ok(());
+        }
+        for stored_event in &missed {
+            let start = std::time::Instant::now();
+            self.apply_stored_event(stored_event).await?;
+            let _ = self
+                .technical_tx
+                .send(ProjectionTechnicalEvent::EventApplied {
+                    projection_name: self.name.clone(),
+                    checkpoint: stored_event.global_position + 1,
+                    duration_ms: start.elapsed().as_millis() as u64,
+                });
+        }
+        let _ = self.technical_tx.send(ProjectionTechnicalEvent::CaughtUp {
+            projection_name: self.name.clone(),
+            from_checkpoint,
+            events_replayed: missed.len() as u64,
+        });
+        Ok(())
+    }
+
+    /// Applies stored events from the persisted checkpoint up to, not including, `until`,
+    /// without emitting technical events. Returns the checkpoint reached, which falls short
+    /// of `until` when the event store has fewer events.
+    pub async fn replay_until(&self, until: u64) -> anyhow::Result<u64> {
+        let mut checkpoint = self.store.checkpoint().await?;
+        for stored_event in self.event_store.load_all_from(checkpoint).await? {
+            if stored_event.global_position >= until {
+                break;
+            }
+            self.apply_stored_event(&stored_event).await?;
+            checkpoint = stored_event.global_position + 1;
+        }
+        Ok(checkpoint)
+    }
+
+    /// Clears the store and replays the whole event log into it under the current schema.
+    pub async fn rebuild(&self) -> anyhow::Result<()> {
         let start = std::time::Instant::now();
         let _ = self
             .technical_tx
@@ -121,7 +277,24 @@ where
                 schema_version: SCHEMA_VERSION,
                 timestamp: chrono::Utc::now().timestamp_millis(),
             });
+        // Archived entries stay archived: their markers outlive the rows.
+        let archived = self
+            .store
+            .state()
+            .await?
+            .map(|state| state.archived)
+            .unwrap_or_default();
         self.store.clear().await?;
+        if !archived.is_empty() {
+            let state = ListTimeEntriesState {
+                archived,
+                ..ListTimeEntriesState::default()
+            };
+            self.store.save(state, 0).await?;
+        }
+        if let Some(query_cache) = &self.query_cache {
+            query_cache.invalidate_all().await;
+        }
         let all_events = self.event_store.load_all_from(0).await?;
         let events_replayed = all_events.len() as u64;
         for stored_event in all_events {
@@ -144,14 +317,52 @@ where
         stored_event: &StoredEvent<TimeEntryEvent>,
     ) -> anyhow::Result<()> {
         let mut state = self.store.state().await?.unwrap_or_default();
-        for mutation in apply(
-            &stored_event.stream_id,
-            stored_event.stream_version,
-            &stored_event.event,
-        ) {
+        let version = stored_event.stream_version;
+        let mut touched = std::collections::BTreeSet::new();
+        let owned = self
+            .partition
+            .is_none_or(|partition| partition.owns(&stored_event.stream_id));
+        let mutations = if owned {
+            apply(
+                &stored_event.stream_id,
+                stored_event.stream_version,
+                &stored_event.event,
+            )
+        } else {
+            Vec::new()
+        };
+        let archived_at = mutations
+            .first()
+            .and_then(|mutation| state.archived.get(mutation.time_entry_id()).copied());
+        let mutations: Vec<(i64, Mutation)> = match archived_at {
+            None => mutations
+                .into_iter()
+                .map(|mutation| (version, mutation))
+                .collect(),
+            Some(archived_at) if version <= archived_at => Vec::new(),
+            // The entry changed after it was archived: it is hot again, rebuilt from its stream.
+            Some(_) => {
+                state.archived.remove(mutations[0].time_entry_id());
+                let stream = self.event_store.load(&stored_event.stream_id).await?;
+                let mut revived = Vec::new();
+                for (index, event) in stream.events.iter().enumerate() {
+                    let version = index as i64 + 1;
+                    revived.extend(
+                        apply(&stored_event.stream_id, version, event)
+                            .into_iter()
+                            .map(|mutation| (version, mutation)),
+                    );
+                }
+                revived
+            }
+        };
+        for (version, mutation) in mutations {
             match mutation {
                 Mutation::Upsert(row) => {
-                    state.rows.insert(row.time_entry_id.clone(), row);
+                    let time_entry_id = row.time_entry_id.clone();
+                    if state.upsert(row) {
+                        touched.insert(time_entry_id);
+                    }
                 }
                 Mutation::SetStartedAt {
                     time_entry_id,
@@ -160,11 +371,16 @@ where
                     updated_by,
                     last_event_id,
                 } => {
-                    if let Some(row) = state.rows.get_mut(&time_entry_id) {
+                    if let Some(row) = state
+                        .rows
+                        .get_mut(&time_entry_id)
+                        .filter(|row| !row.has_applied(version))
+                    {
                         row.started_at = Some(started_at);
                         row.updated_at = updated_at;
                         row.updated_by = updated_by;
                         row.last_event_id = Some(last_event_id);
+                        touched.insert(time_entry_id);
                     }
                 }
                 Mutation::SetEndedAt {
@@ -174,21 +390,30 @@ where
                     updated_by,
                     last_event_id,
                 } => {
-                    if let Some(row) = state.rows.get_mut(&time_entry_id) {
+                    if let Some(row) = state
+                        .rows
+                        .get_mut(&time_entry_id)
+                        .filter(|row| !row.has_applied(version))
+                    {
                         row.ended_at = Some(ended_at);
                         row.updated_at = updated_at;
                         row.updated_by = updated_by;
                         row.last_event_id = Some(last_event_id);
+                        touched.insert(time_entry_id);
                     }
                 }
                 Mutation::SetRegistered {
                     time_entry_id,
                     last_event_id,
                 } => {
-                    if let Some(row) = state.rows.get_mut(&time_entry_id) {
-                        row.status =
-                            crate::modules::time_entries::use_cases::list_time_entries::projection::TimeEntryStatus::Registered;
+                    if let Some(row) = state
+                        .rows
+                        .get_mut(&time_entry_id)
+                        .filter(|row| !row.has_applied(version))
+                    {
+                        row.status = TimeEntryStatus::Registered;
                         row.last_event_id = Some(last_event_id);
+                        touched.insert(time_entry_id);
                     }
                 }
                 Mutation::SetDeleted {
@@ -196,9 +421,14 @@ where
                     deleted_at,
                     last_event_id,
                 } => {
-                    if let Some(row) = state.rows.get_mut(&time_entry_id) {
+                    if let Some(row) = state
+                        .rows
+                        .get_mut(&time_entry_id)
+                        .filter(|row| !row.has_applied(version))
+                    {
                         row.deleted_at = Some(deleted_at);
                         row.last_event_id = Some(last_event_id);
+                        touched.insert(time_entry_id);
                     }
                 }
                 Mutation::SetTags {
@@ -208,18 +438,115 @@ where
                     updated_by,
                     last_event_id,
                 } => {
-                    if let Some(row) = state.rows.get_mut(&time_entry_id) {
+                    if let Some(row) = state
+                        .rows
+                        .get_mut(&time_entry_id)
+                        .filter(|row| !row.has_applied(version))
+                    {
                         row.tag_ids = tag_ids;
                         row.updated_at = updated_at;
                         row.updated_by = updated_by;
                         row.last_event_id = Some(last_event_id);
+                        touched.insert(time_entry_id);
+                    }
+                }
+                Mutation::SetApproved {
+                    time_entry_id,
+                    approved_at,
+                    approved_by,
+                    last_event_id,
+                } => {
+                    if let Some(row) = state
+                        .rows
+                        .get_mut(&time_entry_id)
+                        .filter(|row| !row.has_applied(version))
+                    {
+                        row.status = TimeEntryStatus::Approved;
+                        row.updated_at = approved_at;
+                        row.updated_by = approved_by;
+                        row.last_event_id = Some(last_event_id);
+                        touched.insert(time_entry_id);
+                    }
+                }
+                Mutation::SetRejected {
+                    time_entry_id,
+                    rejected_at,
+                    rejected_by,
+                    last_event_id,
+                } => {
+                    if let Some(row) = state
+                        .rows
+                        .get_mut(&time_entry_id)
+                        .filter(|row| !row.has_applied(version))
+                    {
+                        row.status = TimeEntryStatus::Rejected;
+                        row.updated_at = rejected_at;
+                        row.updated_by = rejected_by;
+                        row.last_event_id = Some(last_event_id);
+                        touched.insert(time_entry_id);
+                    }
+                }
+                Mutation::SetHourlyRate {
+                    time_entry_id,
+                    hourly_rate,
+                    updated_at,
+                    updated_by,
+                    last_event_id,
+                } => {
+                    if let Some(row) = state
+                        .rows
+                        .get_mut(&time_entry_id)
+                        .filter(|row| !row.has_applied(version))
+                    {
+                        row.hourly_rate = Some(hourly_rate);
+                        row.updated_at = updated_at;
+                        row.updated_by = updated_by;
+                        row.last_event_id = Some(last_event_id);
+                        touched.insert(time_entry_id);
+                    }
+                }
+                Mutation::SetBreaks {
+                    time_entry_id,
+                    breaks,
+                    updated_at,
+                    updated_by,
+                    last_event_id,
+                } => {
+                    if let Some(row) = state
+                        .rows
+                        .get_mut(&time_entry_id)
+                        .filter(|row| !row.has_applied(version))
+                    {
+                        row.breaks = breaks;
+                        row.updated_at = updated_at;
+                        row.updated_by = updated_by;
+                        row.last_event_id = Some(last_event_id);
+                        touched.insert(time_entry_id);
                     }
                 }
             }
         }
+        let touched_rows: Vec<TimeEntryRow> = touched
+            .iter()
+            .filter_map(|time_entry_id| state.rows.get(time_entry_id).cloned())
+            .collect();
         self.store
             .save(state, stored_event.global_position + 1)
             .await?;
+        if let Some(updates) = &self.updates {
+            for row in &touched_rows {
+                updates.publish(row.clone().into());
+            }
+        }
+        if let Some(query_cache) = &self.query_cache {
+            let touched_users: std::collections::BTreeSet<&str> = touched_rows
+                .iter()
+                .map(|row| row.user_id.as_str())
+                .collect();
+            for user_id in touched_users {
+                query_cache.invalidate(user_id).await;
+            }
+        }
         Ok(())
     }
 }
@@ -227,20 +554,41 @@ where
 #[cfg(test)]
 mod list_time_entries_projector_tests {
     use super::*;
+    use crate::modules::time_entries::core::events::v1::time_entry_approved::TimeEntryApprovedV1;
     use crate::modules::time_entries::core::events::v1::time_entry_deleted::TimeEntryDeletedV1;
     use crate::modules::time_entries::core::events::v1::time_entry_end_set::TimeEntryEndSetV1;
+    use crate::modules::time_entries::core::events::v1::time_entry_hourly_rate_set::TimeEntryHourlyRateSetV1;
     use crate::modules::time_entries::core::events::v1::time_entry_initiated::TimeEntryInitiatedV1;
     use crate::modules::time_entries::core::events::v1::time_entry_registered::TimeEntryRegisteredV1;
+    use crate::modules::time_entries::core::events::v1::time_entry_rejected::TimeEntryRejectedV1;
     use crate::modules::time_entries::core::events::v1::time_entry_start_set::TimeEntryStartSetV1;
     use crate::modules::time_entries::core::events::v1::time_entry_tags_set::TimeEntryTagsSetV1;
+    use crate::modules::time_entries::core::tag::Tag;
+    use crate::modules::time_entries::use_cases::list_time_entries::projection::{
+        TimeEntryRow, TimeEntryStatus,
+    };
+    use crate::modules::time_entries::use_cases::list_time_entries::queries::Page;
+    use crate::modules::time_entries::use_cases::list_time_entries::updates::TimeEntryUpdate;
     use crate::modules::time_entries::use_cases::set_ended_at::handler::SetEndedAtHandler;
     use crate::modules::time_entries::use_cases::set_started_at::handler::SetStartedAtHandler;
+    use crate::shared::core::primitives::last_event_version;
+    use crate::shared::infrastructure::control_store::in_memory::InMemoryControlStore;
+    use crate::shared::infrastructure::control_store::{
+        ControlStore, WorkerControl, projector_worker,
+    };
+    use crate::shared::infrastructure::event_bus::in_memory::InMemoryEventBus;
     use crate::shared::infrastructure::event_store::EventStore;
+    use crate::shared::infrastructure::event_store::in_memory::InMemoryEventStore;
     use crate::shared::infrastructure::intent_outbox::in_memory::InMemoryDomainOutbox;
     use crate::shared::infrastructure::projection_store::in_memory::InMemoryProjectionStore;
+    use crate::shared::infrastructure::query_cache::QueryCache;
+    use crate::shared::infrastructure::query_cache::in_memory::InMemoryQueryCache;
     use crate::tests::fixtures::commands::set_ended_at::SetEndedAtBuilder;
     use crate::tests::fixtures::commands::set_started_at::SetStartedAtBuilder;
+    use crate::tests::fixtures::projections::CrashingProjectionStore;
+    use async_graphql::futures_util::StreamExt;
     use rstest::rstest;
+    use std::sync::Arc;
 
     async fn initiate_and_register(
         event_store: InMemoryEventStore<TimeEntryEvent>,
@@ -248,7 +596,7 @@ mod list_time_entries_projector_tests {
         stream_id: &str,
     ) {
         let outbox = InMemoryDomainOutbox::new();
-        SetStartedAtHandler::new("t", event_store.clone(), outbox.clone())
+        SetStartedAtHandler::new(event_store.clone(), outbox.clone())
             .handle(
                 stream_id,
                 SetStartedAtBuilder::new()
@@ -257,7 +605,7 @@ mod list_time_entries_projector_tests {
             )
             .await
             .unwrap();
-        SetEndedAtHandler::new("t", event_store, outbox)
+        SetEndedAtHandler::new(event_store, outbox)
             .handle(
                 stream_id,
                 SetEndedAtBuilder::new()
@@ -345,7 +693,7 @@ mod list_time_entries_projector_tests {
         tokio::spawn(projector.run(receiver));
 
         let outbox = InMemoryDomainOutbox::new();
-        SetStartedAtHandler::new("t", event_store, outbox)
+        SetStartedAtHandler::new(event_store, outbox)
             .handle(
                 "TimeEntry-1",
                 SetStartedAtBuilder::new()
@@ -369,6 +717,97 @@ mod list_time_entries_projector_tests {
         assert!(got_applied);
     }
 
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_apply_events_the_handler_publishes_on_the_event_bus() {
+        // The store announces nothing: the row can only come from the bus
+        let event_store = InMemoryEventStore::<TimeEntryEvent>::new();
+        let event_bus = InMemoryEventBus::<TimeEntryEvent>::new(16);
+
+        let projection_store = InMemoryProjectionStore::<ListTimeEntriesState>::new();
+        projection_store
+            .save_schema_version(SCHEMA_VERSION)
+            .await
+            .unwrap();
+
+        let (tech_tx, _) = broadcast::channel(16);
+        let projector = ListTimeEntriesProjector::new(
+            "p",
+            projection_store.clone(),
+            event_store.clone(),
+            tech_tx,
+        );
+        tokio::spawn(projector.run(event_bus.subscribe()));
+
+        SetStartedAtHandler::new(event_store, InMemoryDomainOutbox::new())
+            .with_event_bus(Arc::new(event_bus))
+            .handle(
+                "TimeEntry-1",
+                SetStartedAtBuilder::new()
+                    .time_entry_id("te-1".to_string())
+                    .build(),
+            )
+            .await
+            .unwrap();
+
+        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+
+        let state = projection_store.state().await.unwrap().unwrap();
+        assert_eq!(state.rows.len(), 1);
+        assert!(state.rows.contains_key("te-1"));
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_hold_events_while_paused_and_catch_up_on_resume() {
+        let (tx, _) = broadcast::channel::<StoredEvent<TimeEntryEvent>>(4);
+        let event_store = InMemoryEventStore::<TimeEntryEvent>::new_with_sender(tx.clone());
+        let projection_store = InMemoryProjectionStore::<ListTimeEntriesState>::new();
+        projection_store
+            .save_schema_version(SCHEMA_VERSION)
+            .await
+            .unwrap();
+        let controls = InMemoryControlStore::new();
+        let pause = |paused| WorkerControl {
+            worker: projector_worker("p"),
+            paused,
+            updated_at: 0,
+            updated_by: "admin-1".to_string(),
+        };
+        controls.put(pause(true)).await.unwrap();
+        let (tech_tx, _) = broadcast::channel(64);
+        let projector = ListTimeEntriesProjector::new(
+            "p",
+            projection_store.clone(),
+            event_store.clone(),
+            tech_tx,
+        )
+        .with_pause_switch(
+            PauseSwitch::new(Arc::new(controls.clone()), projector_worker("p"))
+                .with_poll(std::time::Duration::from_millis(5)),
+        );
+        tokio::spawn(projector.run(tx.subscribe()));
+
+        // More events than the channel holds: a paused projector must not lag into a rebuild
+        for n in 0..4 {
+            initiate_and_register(
+                event_store.clone(),
+                &format!("te-{n}"),
+                &format!("TimeEntry-{n}"),
+            )
+            .await;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(30)).await;
+        let state = projection_store.state().await.unwrap().unwrap_or_default();
+        assert!(state.rows.is_empty());
+
+        controls.put(pause(false)).await.unwrap();
+        tokio::time::sleep(std::time::Duration::from_millis(30)).await;
+
+        let state = projection_store.state().await.unwrap().unwrap();
+        assert_eq!(state.rows.len(), 4);
+    }
+
     #[rstest]
     #[tokio::test]
     async fn it_should_apply_all_mutation_types() {
@@ -394,37 +833,57 @@ mod list_time_entries_projector_tests {
         // Append events covering all mutation types
         let events = vec![
             TimeEntryEvent::TimeEntryInitiatedV1(TimeEntryInitiatedV1 {
-                time_entry_id: "te-mut".to_string(),
-                user_id: "user-0001".to_string(),
+                time_entry_id: "te-mut".into(),
+                user_id: "user-0001".into(),
                 created_at: 1_000,
-                created_by: "user-0001".to_string(),
+                created_by: "user-0001".into(),
             }),
             TimeEntryEvent::TimeEntryStartSetV1(TimeEntryStartSetV1 {
-                time_entry_id: "te-mut".to_string(),
+                time_entry_id: "te-mut".into(),
                 started_at: 500,
                 updated_at: 1_000,
-                updated_by: "user-0001".to_string(),
+                updated_by: "user-0001".into(),
+                raw_started_at: None,
             }),
             TimeEntryEvent::TimeEntryEndSetV1(TimeEntryEndSetV1 {
-                time_entry_id: "te-mut".to_string(),
+                time_entry_id: "te-mut".into(),
                 ended_at: 800,
                 updated_at: 1_000,
-                updated_by: "user-0001".to_string(),
+                updated_by: "user-0001".into(),
+                raw_ended_at: None,
             }),
             TimeEntryEvent::TimeEntryRegisteredV1(TimeEntryRegisteredV1 {
-                time_entry_id: "te-mut".to_string(),
+                time_entry_id: "te-mut".into(),
                 occurred_at: 1_000,
             }),
             TimeEntryEvent::TimeEntryTagsSetV1(TimeEntryTagsSetV1 {
-                time_entry_id: "te-mut".to_string(),
-                tag_ids: vec!["tag-1".to_string()],
+                time_entry_id: "te-mut".into(),
+                tag_ids: vec![Tag::parse("tag-1").unwrap()],
                 updated_at: 1_500,
-                updated_by: "user-0001".to_string(),
+                updated_by: "user-0001".into(),
+            }),
+            TimeEntryEvent::TimeEntryHourlyRateSetV1(TimeEntryHourlyRateSetV1 {
+                time_entry_id: "te-mut".into(),
+                hourly_rate_cents: 9_500,
+                currency: "EUR".to_string(),
+                updated_at: 1_600,
+                updated_by: "user-0001".into(),
+            }),
+            TimeEntryEvent::TimeEntryRejectedV1(TimeEntryRejectedV1 {
+                time_entry_id: "te-mut".into(),
+                rejected_at: 1_700,
+                rejected_by: "manager-0001".into(),
+                reason: Some("wrong project".to_string()),
+            }),
+            TimeEntryEvent::TimeEntryApprovedV1(TimeEntryApprovedV1 {
+                time_entry_id: "te-mut".into(),
+                approved_at: 1_800,
+                approved_by: "manager-0001".into(),
             }),
             TimeEntryEvent::TimeEntryDeletedV1(TimeEntryDeletedV1 {
-                time_entry_id: "te-mut".to_string(),
+                time_entry_id: "te-mut".into(),
                 deleted_at: 2_000,
-                deleted_by: "user-0001".to_string(),
+                deleted_by: "user-0001".into(),
             }),
         ];
         event_store
@@ -439,8 +898,10 @@ mod list_time_entries_projector_tests {
         use crate::modules::time_entries::use_cases::list_time_entries::projection::TimeEntryStatus;
         assert_eq!(row.started_at, Some(500));
         assert_eq!(row.ended_at, Some(800));
-        assert_eq!(row.status, TimeEntryStatus::Registered);
+        assert_eq!(row.status, TimeEntryStatus::Approved);
+        assert_eq!(row.updated_by, "manager-0001");
         assert_eq!(row.tag_ids, vec!["tag-1".to_string()]);
+        assert_eq!(row.hourly_rate.as_ref().map(|rate| rate.cents), Some(9_500));
         assert_eq!(row.deleted_at, Some(2_000));
     }
 
@@ -466,35 +927,49 @@ mod list_time_entries_projector_tests {
         let receiver = tx.subscribe();
         tokio::spawn(projector.run(receiver));
 
-        // Send SetStartedAt, SetEndedAt, SetRegistered, SetTags, and SetDeleted without a
+        // Send SetStartedAt, SetEndedAt, SetRegistered, SetTags, SetHourlyRate, SetApproved and SetDeleted without a
         // preceding Initiated event — these should all be silently skipped (row not found)
         let events = vec![
             TimeEntryEvent::TimeEntryStartSetV1(TimeEntryStartSetV1 {
-                time_entry_id: "te-orphan".to_string(),
+                time_entry_id: "te-orphan".into(),
                 started_at: 1_000,
                 updated_at: 2_000,
-                updated_by: "u1".to_string(),
+                updated_by: "u1".into(),
+                raw_started_at: None,
             }),
             TimeEntryEvent::TimeEntryEndSetV1(TimeEntryEndSetV1 {
-                time_entry_id: "te-orphan".to_string(),
+                time_entry_id: "te-orphan".into(),
                 ended_at: 3_000,
                 updated_at: 2_000,
-                updated_by: "u1".to_string(),
+                updated_by: "u1".into(),
+                raw_ended_at: None,
             }),
             TimeEntryEvent::TimeEntryRegisteredV1(TimeEntryRegisteredV1 {
-                time_entry_id: "te-orphan".to_string(),
+                time_entry_id: "te-orphan".into(),
                 occurred_at: 2_000,
             }),
             TimeEntryEvent::TimeEntryTagsSetV1(TimeEntryTagsSetV1 {
-                time_entry_id: "te-orphan".to_string(),
-                tag_ids: vec!["tag-1".to_string()],
+                time_entry_id: "te-orphan".into(),
+                tag_ids: vec![Tag::parse("tag-1").unwrap()],
+                updated_at: 2_000,
+                updated_by: "u1".into(),
+            }),
+            TimeEntryEvent::TimeEntryHourlyRateSetV1(TimeEntryHourlyRateSetV1 {
+                time_entry_id: "te-orphan".into(),
+                hourly_rate_cents: 9_500,
+                currency: "EUR".to_string(),
                 updated_at: 2_000,
-                updated_by: "u1".to_string(),
+                updated_by: "u1".into(),
+            }),
+            TimeEntryEvent::TimeEntryApprovedV1(TimeEntryApprovedV1 {
+                time_entry_id: "te-orphan".into(),
+                approved_at: 3_000,
+                approved_by: "u2".into(),
             }),
             TimeEntryEvent::TimeEntryDeletedV1(TimeEntryDeletedV1 {
-                time_entry_id: "te-orphan".to_string(),
+                time_entry_id: "te-orphan".into(),
                 deleted_at: 4_000,
-                deleted_by: "u1".to_string(),
+                deleted_by: "u1".into(),
             }),
         ];
         event_store
@@ -537,7 +1012,7 @@ mod list_time_entries_projector_tests {
         tokio::spawn(projector.run(receiver));
 
         let outbox = InMemoryDomainOutbox::new();
-        SetStartedAtHandler::new("t", event_store, outbox)
+        SetStartedAtHandler::new(event_store, outbox)
             .handle(
                 "TimeEntry-skip",
                 SetStartedAtBuilder::new()
@@ -582,7 +1057,7 @@ mod list_time_entries_projector_tests {
         projection_store.toggle_offline();
 
         let outbox = InMemoryDomainOutbox::new();
-        SetStartedAtHandler::new("t", event_store, outbox)
+        SetStartedAtHandler::new(event_store, outbox)
             .handle(
                 "TimeEntry-fail",
                 SetStartedAtBuilder::new()
@@ -745,4 +1220,364 @@ mod list_time_entries_projector_tests {
         projector.run(receiver).await;
         // If we reach here, the projector exited cleanly on Closed
     }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_keep_archived_entries_out_until_they_change() {
+        let event_store = InMemoryEventStore::<TimeEntryEvent>::new();
+        initiate_and_register(event_store.clone(), "te-abc", "TimeEntry-abc").await;
+        let projection_store = InMemoryProjectionStore::<ListTimeEntriesState>::new();
+        let (tech_tx, _) = broadcast::channel(64);
+        let projector = ListTimeEntriesProjector::new(
+            "p",
+            projection_store.clone(),
+            event_store.clone(),
+            tech_tx,
+        );
+        projector.rebuild().await.unwrap();
+        let checkpoint = projection_store.checkpoint().await.unwrap();
+        let mut state = projection_store.state().await.unwrap().unwrap();
+        let archived = state.rows.remove("te-abc").unwrap();
+        let archived_at = last_event_version(archived.last_event_id.as_deref()).unwrap();
+        state.archived.insert("te-abc".to_string(), archived_at);
+        projection_store.save(state, checkpoint).await.unwrap();
+
+        projector.rebuild().await.unwrap();
+        let state = projection_store.state().await.unwrap().unwrap();
+        assert!(state.rows.is_empty());
+        assert_eq!(state.archived["te-abc"], archived_at);
+
+        event_store
+            .append(
+                "TimeEntry-abc",
+                archived_at,
+                &[TimeEntryEvent::TimeEntryTagsSetV1(TimeEntryTagsSetV1 {
+                    time_entry_id: "te-abc".into(),
+                    tag_ids: vec![Tag::parse("late").unwrap()],
+                    updated_at: 9_000,
+                    updated_by: "user-0001".into(),
+                })],
+            )
+            .await
+            .unwrap();
+        projector.replay_until(u64::MAX).await.unwrap();
+
+        let state = projection_store.state().await.unwrap().unwrap();
+        assert!(state.archived.is_empty());
+        let revived = &state.rows["te-abc"];
+        assert_eq!(revived.tag_ids, vec![Tag::parse("late").unwrap()]);
+        assert_eq!(revived.ended_at, archived.ended_at);
+    }
+
+    async fn rebuild_rows(
+        event_store: InMemoryEventStore<TimeEntryEvent>,
+    ) -> std::collections::HashMap<String, TimeEntryRow> {
+        let projection_store = InMemoryProjectionStore::<ListTimeEntriesState>::new();
+        let (closed_tx, receiver) = broadcast::channel::<StoredEvent<TimeEntryEvent>>(1);
+        drop(closed_tx);
+        let (tech_tx, _) = broadcast::channel(64);
+        ListTimeEntriesProjector::new("clean", projection_store.clone(), event_store, tech_tx)
+            .run(receiver)
+            .await;
+        projection_store.state().await.unwrap().unwrap().rows
+    }
+
+    fn drain(
+        tech_rx: &mut broadcast::Receiver<ProjectionTechnicalEvent>,
+    ) -> Vec<ProjectionTechnicalEvent> {
+        let mut events = Vec::new();
+        while let Ok(event) = tech_rx.try_recv() {
+            events.push(event);
+        }
+        events
+    }
+
+    #[rstest]
+    #[case::torn_on_first_event(0)]
+    #[case::torn_mid_stream(3)]
+    #[case::torn_on_second_stream(5)]
+    #[tokio::test]
+    async fn it_should_resume_after_a_crash_mid_catch_up(#[case] saves_before_crash: u64) {
+        let event_store = InMemoryEventStore::<TimeEntryEvent>::new();
+        initiate_and_register(event_store.clone(), "te-crash1", "TimeEntry-crash1").await;
+        initiate_and_register(event_store.clone(), "te-crash2", "TimeEntry-crash2").await;
+
+        let projection_store = InMemoryProjectionStore::<ListTimeEntriesState>::new();
+        let crashing_store =
+            CrashingProjectionStore::new(projection_store.clone(), saves_before_crash);
+        crashing_store
+            .save_schema_version(SCHEMA_VERSION)
+            .await
+            .unwrap();
+
+        let (closed_tx, receiver) = broadcast::channel::<StoredEvent<TimeEntryEvent>>(1);
+        drop(closed_tx);
+        let (tech_tx, mut tech_rx) = broadcast::channel(64);
+        ListTimeEntriesProjector::new("p", crashing_store.clone(), event_store.clone(), tech_tx)
+            .run(receiver)
+            .await;
+        assert!(crashing_store.has_crashed());
+        assert!(
+            drain(&mut tech_rx)
+                .iter()
+                .any(|event| matches!(event, ProjectionTechnicalEvent::CatchUpFailed { .. }))
+        );
+        assert_eq!(
+            projection_store.checkpoint().await.unwrap(),
+            saves_before_crash
+        );
+
+        let (closed_tx, receiver) = broadcast::channel::<StoredEvent<TimeEntryEvent>>(1);
+        drop(closed_tx);
+        let (tech_tx, mut tech_rx) = broadcast::channel(64);
+        ListTimeEntriesProjector::new("p", projection_store.clone(), event_store.clone(), tech_tx)
+            .run(receiver)
+            .await;
+
+        assert!(drain(&mut tech_rx).iter().any(|event| matches!(
+            event,
+            ProjectionTechnicalEvent::CaughtUp { from_checkpoint, events_replayed, .. }
+                if *from_checkpoint == saves_before_crash
+                    && *events_replayed == 8 - saves_before_crash
+        )));
+        assert_eq!(projection_store.checkpoint().await.unwrap(), 8);
+        let resumed = projection_store.state().await.unwrap().unwrap().rows;
+        assert_eq!(resumed, rebuild_rows(event_store).await);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_resume_after_a_crash_mid_rebuild() {
+        let event_store = InMemoryEventStore::<TimeEntryEvent>::new();
+        initiate_and_register(event_store.clone(), "te-crash1", "TimeEntry-crash1").await;
+
+        let projection_store = InMemoryProjectionStore::<ListTimeEntriesState>::new();
+        let crashing_store = CrashingProjectionStore::new(projection_store.clone(), 2);
+
+        let (closed_tx, receiver) = broadcast::channel::<StoredEvent<TimeEntryEvent>>(1);
+        drop(closed_tx);
+        let (tech_tx, mut tech_rx) = broadcast::channel(64);
+        ListTimeEntriesProjector::new("p", crashing_store.clone(), event_store.clone(), tech_tx)
+            .run(receiver)
+            .await;
+        assert!(
+            drain(&mut tech_rx)
+                .iter()
+                .any(|event| matches!(event, ProjectionTechnicalEvent::RebuildFailed { .. }))
+        );
+        assert!(crashing_store.clear().await.is_err());
+        assert!(crashing_store.schema_version().await.is_err());
+        assert!(
+            crashing_store
+                .save_schema_version(SCHEMA_VERSION)
+                .await
+                .is_err()
+        );
+
+        let (closed_tx, receiver) = broadcast::channel::<StoredEvent<TimeEntryEvent>>(1);
+        drop(closed_tx);
+        let (tech_tx, _) = broadcast::channel(64);
+        ListTimeEntriesProjector::new("p", projection_store.clone(), event_store.clone(), tech_tx)
+            .run(receiver)
+            .await;
+
+        let resumed = projection_store.state().await.unwrap().unwrap().rows;
+        assert_eq!(resumed, rebuild_rows(event_store).await);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_catch_up_when_a_gap_is_detected() {
+        let event_store = InMemoryEventStore::<TimeEntryEvent>::new();
+        let projection_store = InMemoryProjectionStore::<ListTimeEntriesState>::new();
+        projection_store
+            .save_schema_version(SCHEMA_VERSION)
+            .await
+            .unwrap();
+
+        let (tx, receiver) = broadcast::channel::<StoredEvent<TimeEntryEvent>>(16);
+        let (tech_tx, mut tech_rx) = broadcast::channel(64);
+        let projector = ListTimeEntriesProjector::new(
+            "p",
+            projection_store.clone(),
+            event_store.clone(),
+            tech_tx,
+        );
+        let running = tokio::spawn(projector.run(receiver));
+        tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+
+        // Events are stored but only the last one is ever broadcast.
+        initiate_and_register(event_store.clone(), "te-gap", "TimeEntry-gap").await;
+        let last = event_store.load_all_from(0).await.unwrap().pop().unwrap();
+        tx.send(last).unwrap();
+        drop(tx);
+        running.await.unwrap();
+
+        let technical_events = drain(&mut tech_rx);
+        assert!(technical_events.iter().any(|event| matches!(
+            event,
+            ProjectionTechnicalEvent::GapDetected {
+                checkpoint: 0,
+                received: 3,
+                ..
+            }
+        )));
+        assert!(technical_events.iter().any(|event| matches!(
+            event,
+            ProjectionTechnicalEvent::EventApplied { checkpoint: 4, .. }
+        )));
+        let state = projection_store.state().await.unwrap().unwrap();
+        assert_eq!(state.rows["te-gap"].status, TimeEntryStatus::Registered);
+        assert_eq!(projection_store.checkpoint().await.unwrap(), 4);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_not_reapply_events_already_reflected_in_rows() {
+        let event_store = InMemoryEventStore::<TimeEntryEvent>::new();
+        initiate_and_register(event_store.clone(), "te-twice", "TimeEntry-twice").await;
+        let projection_store = InMemoryProjectionStore::<ListTimeEntriesState>::new();
+        let (tech_tx, _) = broadcast::channel(64);
+        let projector = ListTimeEntriesProjector::new(
+            "p",
+            projection_store.clone(),
+            event_store.clone(),
+            tech_tx,
+        );
+        let stored_events = event_store.load_all_from(0).await.unwrap();
+        for stored_event in &stored_events {
+            projector.apply_stored_event(stored_event).await.unwrap();
+        }
+        let once = projection_store.state().await.unwrap().unwrap().rows;
+
+        for stored_event in &stored_events {
+            projector.apply_stored_event(stored_event).await.unwrap();
+        }
+
+        let twice = projection_store.state().await.unwrap().unwrap().rows;
+        assert_eq!(once, twice);
+        assert_eq!(
+            twice["te-twice"].last_event_id.as_deref(),
+            Some("TimeEntry-twice:4")
+        );
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_publish_the_rows_it_writes_once() {
+        let event_store = InMemoryEventStore::<TimeEntryEvent>::new();
+        initiate_and_register(event_store.clone(), "te-live", "TimeEntry-live").await;
+        let updates = TimeEntryUpdates::default();
+        let mut stream = Box::pin(updates.subscribe("user-fixed-0001"));
+        let (tech_tx, _) = broadcast::channel(64);
+        let projector = ListTimeEntriesProjector::new(
+            "p",
+            InMemoryProjectionStore::<ListTimeEntriesState>::new(),
+            event_store.clone(),
+            tech_tx,
+        )
+        .with_updates(updates.clone());
+
+        let stored_events = event_store.load_all_from(0).await.unwrap();
+        for stored_event in stored_events.iter().chain(&stored_events) {
+            projector.apply_stored_event(stored_event).await.unwrap();
+        }
+        drop((projector, updates));
+
+        let published: Vec<TimeEntryUpdate> = stream.by_ref().collect().await;
+        assert_eq!(published.len(), stored_events.len());
+        let Some(TimeEntryUpdate::Upserted(last)) = published.last() else {
+            panic!("expected an upserted row, got {published:?}");
+        };
+        assert_eq!(last.time_entry_id, "te-live");
+        assert_eq!(last.status, TimeEntryStatus::Registered);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_invalidate_cached_queries_for_users_whose_rows_change() {
+        let event_store = InMemoryEventStore::<TimeEntryEvent>::new();
+        initiate_and_register(event_store.clone(), "te-cached", "TimeEntry-cached").await;
+        let cache = InMemoryQueryCache::new();
+        let (tech_tx, _) = broadcast::channel(64);
+        let projector = ListTimeEntriesProjector::new(
+            "p",
+            InMemoryProjectionStore::<ListTimeEntriesState>::new(),
+            event_store.clone(),
+            tech_tx,
+        )
+        .with_query_cache(Arc::new(cache.clone()));
+        cache
+            .put("user-fixed-0001", "page", 0, Page::default())
+            .await;
+        cache.put("someone-else", "page", 0, Page::default()).await;
+
+        let stored_events = event_store.load_all_from(0).await.unwrap();
+        for stored_event in &stored_events {
+            projector.apply_stored_event(stored_event).await.unwrap();
+        }
+        assert_eq!(cache.get("user-fixed-0001", "page").await, None);
+        assert_eq!(
+            cache.get("someone-else", "page").await,
+            Some(Page::default())
+        );
+
+        // Replayed events change nothing, so cached pages stay valid.
+        let generation = cache.generation("user-fixed-0001").await;
+        cache
+            .put("user-fixed-0001", "page", generation, Page::default())
+            .await;
+        for stored_event in &stored_events {
+            projector.apply_stored_event(stored_event).await.unwrap();
+        }
+        assert_eq!(
+            cache.get("user-fixed-0001", "page").await,
+            Some(Page::default())
+        );
+
+        projector.rebuild().await.unwrap();
+        assert_eq!(cache.get("someone-else", "page").await, None);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_only_project_owned_streams_while_advancing_every_partition() {
+        let event_store = InMemoryEventStore::<TimeEntryEvent>::new();
+        initiate_and_register(event_store.clone(), "te-1", "TimeEntry-te-1").await;
+        initiate_and_register(event_store.clone(), "te-2", "TimeEntry-te-2").await;
+
+        let mut stores = Vec::new();
+        for partition in Partition::all(2) {
+            let store = InMemoryProjectionStore::<ListTimeEntriesState>::new();
+            let (tech_tx, _) = broadcast::channel(16);
+            let (closed_tx, receiver) = broadcast::channel::<StoredEvent<TimeEntryEvent>>(16);
+            drop(closed_tx);
+            ListTimeEntriesProjector::new("p", store.clone(), event_store.clone(), tech_tx)
+                .with_partition(partition)
+                .run(receiver)
+                .await;
+            stores.push(store);
+        }
+
+        let rows = |store: &InMemoryProjectionStore<ListTimeEntriesState>| {
+            let store = store.clone();
+            async move {
+                let mut ids: Vec<_> = store
+                    .state()
+                    .await
+                    .unwrap()
+                    .unwrap()
+                    .rows
+                    .into_keys()
+                    .collect();
+                ids.sort();
+                ids
+            }
+        };
+        assert_eq!(rows(&stores[0]).await, vec!["te-1"]);
+        assert_eq!(rows(&stores[1]).await, vec!["te-2"]);
+        for store in &stores {
+            assert_eq!(store.checkpoint().await.unwrap(), 8);
+        }
+    }
 }
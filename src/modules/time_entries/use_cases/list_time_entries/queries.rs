@@ -1,7 +1,74 @@
+use crate::modules::time_entries::core::tag::Tag;
+use crate::modules::time_entries::use_cases::hours_balance::queries::clipped_rows;
 use crate::modules::time_entries::use_cases::list_time_entries::projection::{
     ListTimeEntriesState, TimeEntryView,
 };
+use crate::shared::core::primitives::last_event_version;
 use crate::shared::infrastructure::projection_store::ProjectionStore;
+use crate::shared::infrastructure::query_cache::{QueryCache, QueryCacheMetrics};
+use chrono::{DateTime, NaiveDate, TimeZone};
+use chrono_tz::Tz;
+use std::collections::{BTreeMap, BTreeSet};
+use std::sync::Arc;
+
+const MINUTE: i64 = 60_000;
+
+/// Registered time carrying one tag, or no tag at all when `tag_id` is `None`.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct TagTotal {
+    pub tag_id: Option<String>,
+    pub minutes: i64,
+}
+
+/// Why an existing entry looks like a duplicate of the one about to be submitted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+pub enum SimilarityReason {
+    /// The intervals overlap; a running timer overlaps everything after its start.
+    Overlapping,
+    /// Starts on the same UTC day with exactly the same tags.
+    SameTagsSameDay,
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+pub struct SimilarEntry {
+    pub time_entry: TimeEntryView,
+    pub reason: SimilarityReason,
+}
+
+/// One local day of a user's entries.
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+pub struct DayEntries {
+    pub date: NaiveDate,
+    /// Time the entries work inside this day, leaving out their breaks; running timers count
+    /// nothing yet.
+    pub total_ms: i64,
+    /// Ordered by start.
+    pub entries: Vec<TimeEntryView>,
+}
+
+/// One page of results, with how many there are in all.
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    /// Results across every page.
+    pub total: u64,
+    /// The offset the next page starts at; `None` on the last page.
+    pub next_cursor: Option<u64>,
+}
+
+impl<T> Default for Page<T> {
+    fn default() -> Self {
+        Self {
+            items: Vec::new(),
+            total: 0,
+            next_cursor: None,
+        }
+    }
+}
+
+/// Cache shared between the query handler (read-through) and the projector (invalidation),
+/// partitioned by user id.
+pub type ListTimeEntriesCache = Arc<dyn QueryCache<Page<TimeEntryView>>>;
 
 #[derive(Clone)]
 pub struct ListTimeEntriesQueryHandler<TStore>
@@ -9,6 +76,8 @@ where
     TStore: ProjectionStore<ListTimeEntriesState> + Send + Sync + 'static,
 {
     store: TStore,
+    cache: Option<ListTimeEntriesCache>,
+    cache_metrics: QueryCacheMetrics,
 }
 
 impl<TStore> ListTimeEntriesQueryHandler<TStore>
@@ -16,7 +85,30 @@ where
     TStore: ProjectionStore<ListTimeEntriesState> + Send + Sync + 'static,
 {
     pub fn new(store: TStore) -> Self {
-        Self { store }
+        Self {
+            store,
+            cache: None,
+            cache_metrics: QueryCacheMetrics::new(),
+        }
+    }
+
+    /// Serves repeated queries from `cache`. Register the same cache on the projector so
+    /// pages are dropped when rows for a user change.
+    pub fn with_cache(mut self, cache: ListTimeEntriesCache) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    pub fn cache_metrics(&self) -> &QueryCacheMetrics {
+        &self.cache_metrics
+    }
+
+    pub fn store(&self) -> &TStore {
+        &self.store
+    }
+
+    pub fn cache(&self) -> Option<&ListTimeEntriesCache> {
+        self.cache.as_ref()
     }
 }
 
@@ -30,7 +122,208 @@ where
         offset: u64,
         limit: u64,
         sort_desc: bool,
+    ) -> anyhow::Result<Page<TimeEntryView>> {
+        let Some(cache) = &self.cache else {
+            return self
+                .load_by_user_id(user_id, offset, limit, sort_desc)
+                .await;
+        };
+        let key = format!("offset={offset}&limit={limit}&sort_desc={sort_desc}");
+        if let Some(page) = cache.get(user_id, &key).await {
+            self.cache_metrics.record_hit();
+            return Ok(page);
+        }
+        self.cache_metrics.record_miss();
+        // Taken before loading, so a page read before a projector write is not cached after it.
+        let generation = cache.generation(user_id).await;
+        let page = self
+            .load_by_user_id(user_id, offset, limit, sort_desc)
+            .await?;
+        cache.put(user_id, &key, generation, page.clone()).await;
+        Ok(page)
+    }
+
+    /// Registered and approved minutes per tag between `from` and `to` (inclusive UTC dates),
+    /// largest first. An entry with several tags counts in full towards each of them, so the
+    /// totals may add up to more than the registered time.
+    pub async fn time_by_tag(
+        &self,
+        user_id: &str,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> anyhow::Result<Vec<TagTotal>> {
+        let state = self.store.state().await?.unwrap_or_default();
+        let mut millis: BTreeMap<Option<String>, i64> = BTreeMap::new();
+        for (row, row_millis) in clipped_rows(&state, user_id, from, to) {
+            if row.tag_ids.is_empty() {
+                *millis.entry(None).or_default() += row_millis;
+            }
+            for tag_id in &row.tag_ids {
+                *millis.entry(Some(tag_id.to_string())).or_default() += row_millis;
+            }
+        }
+        let mut totals: Vec<TagTotal> = millis
+            .into_iter()
+            .map(|(tag_id, millis)| TagTotal {
+                tag_id,
+                minutes: millis / MINUTE,
+            })
+            .filter(|total| total.minutes > 0)
+            .collect();
+        totals.sort_by_key(|total| std::cmp::Reverse(total.minutes));
+        Ok(totals)
+    }
+
+    /// Entries of `user_id` that likely duplicate one from `start` to `end` (epoch millis)
+    /// carrying `tag_ids`, ordered by start. Entries without a start and deleted entries are
+    /// never reported. Time entries carry tags rather than descriptions, so matching tags
+    /// stand in for a matching description.
+    pub async fn find_similar_entries(
+        &self,
+        user_id: &str,
+        start: i64,
+        end: i64,
+        tag_ids: &[Tag],
+    ) -> anyhow::Result<Vec<SimilarEntry>> {
+        let state = self.store.state().await?.unwrap_or_default();
+        let day = DateTime::from_timestamp_millis(start).map(|at| at.date_naive());
+        let tags: BTreeSet<&Tag> = tag_ids.iter().collect();
+        let mut similar: Vec<SimilarEntry> = state
+            .rows
+            .values()
+            .filter(|row| row.user_id == user_id && row.deleted_at.is_none())
+            .filter_map(|row| {
+                let started_at = row.started_at?;
+                let reason = if started_at < end && row.ended_at.unwrap_or(i64::MAX) > start {
+                    SimilarityReason::Overlapping
+                } else if !tags.is_empty()
+                    && day.is_some()
+                    && DateTime::from_timestamp_millis(started_at).map(|at| at.date_naive()) == day
+                    && row.tag_ids.iter().collect::<BTreeSet<_>>() == tags
+                {
+                    SimilarityReason::SameTagsSameDay
+                } else {
+                    return None;
+                };
+                Some(SimilarEntry {
+                    time_entry: TimeEntryView::from(row.clone()),
+                    reason,
+                })
+            })
+            .collect();
+        similar.sort_by_key(|entry| entry.time_entry.started_at);
+        Ok(similar)
+    }
+
+    /// One bucket per day from `from` to `to` (inclusive dates in `time_zone`), empty days
+    /// included. An entry is listed under every day its interval touches, so one crossing
+    /// midnight shows up on both days with its time split between them. Deleted entries and
+    /// entries without a start are left out.
+    pub async fn entries_by_day(
+        &self,
+        user_id: &str,
+        from: NaiveDate,
+        to: NaiveDate,
+        time_zone: Tz,
+    ) -> anyhow::Result<Vec<DayEntries>> {
+        let state = self.store.state().await?.unwrap_or_default();
+        let mut days: Vec<DayEntries> = from
+            .iter_days()
+            .take_while(|date| *date <= to)
+            .map(|date| DayEntries {
+                date,
+                total_ms: 0,
+                entries: Vec::new(),
+            })
+            .collect();
+        let mut rows: Vec<_> = state
+            .rows
+            .values()
+            .filter(|row| row.user_id == user_id && row.deleted_at.is_none())
+            .filter(|row| row.started_at.is_some())
+            .collect();
+        rows.sort_by_key(|row| (row.started_at, row.time_entry_id.as_str()));
+        let local_date = |at: i64| {
+            DateTime::from_timestamp_millis(at)
+                .map(|at| at.with_timezone(&time_zone).date_naive())
+                .unwrap_or(from)
+        };
+        for row in rows {
+            let started_at = row.started_at.unwrap_or_default();
+            let ended_at = row.ended_at.unwrap_or(started_at).max(started_at);
+            let first = local_date(started_at);
+            let last = local_date((ended_at - 1).max(started_at));
+            let skip = (first - from).num_days().max(0) as usize;
+            let take = ((last - from).num_days() + 1).max(0) as usize;
+            for day in days.iter_mut().take(take).skip(skip) {
+                let day_start = local_midnight(time_zone, day.date);
+                let day_end = local_midnight(time_zone, day.date + chrono::Days::new(1));
+                day.total_ms += row.net_millis_within(day_start, day_end).unwrap_or(0);
+                day.entries.push(TimeEntryView::from(row.clone()));
+            }
+        }
+        Ok(days)
+    }
+
+    /// The current views of those of `time_entry_ids` that belong to `user_id`, by id.
+    pub async fn list_by_ids(
+        &self,
+        user_id: &str,
+        time_entry_ids: &BTreeSet<String>,
     ) -> anyhow::Result<Vec<TimeEntryView>> {
+        let state = self.store.state().await?.unwrap_or_default();
+        let mut items: Vec<TimeEntryView> = time_entry_ids
+            .iter()
+            .filter_map(|time_entry_id| state.rows.get(time_entry_id))
+            .filter(|row| row.user_id == user_id)
+            .cloned()
+            .map(TimeEntryView::from)
+            .collect();
+        items.sort_by(|a, b| a.time_entry_id.cmp(&b.time_entry_id));
+        Ok(items)
+    }
+
+    /// The entry with the version of its stream the view reflects, `None` for rows projected
+    /// before versions were recorded. Deleted entries are returned too; callers decide.
+    pub async fn get(
+        &self,
+        time_entry_id: &str,
+    ) -> anyhow::Result<Option<(TimeEntryView, Option<i64>)>> {
+        let state = self.store.state().await?.unwrap_or_default();
+        Ok(state.rows.get(time_entry_id).cloned().map(|row| {
+            let version = last_event_version(row.last_event_id.as_deref());
+            (TimeEntryView::from(row), version)
+        }))
+    }
+
+    /// The entry once its row has caught up with `stream_version` of its stream; `None`
+    /// while the projection lags behind, or when the entry is not the user's.
+    pub async fn get_at_version(
+        &self,
+        user_id: &str,
+        time_entry_id: &str,
+        stream_version: i64,
+    ) -> anyhow::Result<Option<TimeEntryView>> {
+        let state = self.store.state().await?.unwrap_or_default();
+        Ok(state
+            .rows
+            .get(time_entry_id)
+            .filter(|row| row.user_id == user_id)
+            .filter(|row| {
+                last_event_version(row.last_event_id.as_deref())
+                    .is_some_and(|version| version >= stream_version)
+            })
+            .cloned()
+            .map(TimeEntryView::from))
+    }
+
+    async fn load_by_user_id(
+        &self,
+        user_id: &str,
+        offset: u64,
+        limit: u64,
+        sort_desc: bool,
+    ) -> anyhow::Result<Page<TimeEntryView>> {
         let state = self.store.state().await?.unwrap_or_default();
         let mut items: Vec<_> = state
             .rows
@@ -44,19 +337,34 @@ where
             items.reverse();
         }
 
-        let start = offset as usize;
+        let total = items.len() as u64;
+        let start = (offset as usize).min(items.len());
         let end = start.saturating_add(limit as usize).min(items.len());
-        if start >= items.len() {
-            return Ok(Vec::new());
-        }
-        Ok(items[start..end]
-            .iter()
-            .cloned()
-            .map(TimeEntryView::from)
-            .collect())
+        Ok(Page {
+            items: items[start..end]
+                .iter()
+                .cloned()
+                .map(TimeEntryView::from)
+                .collect(),
+            total,
+            next_cursor: (end < items.len()).then_some(end as u64),
+        })
     }
 }
 
+/// Epoch millis at which `date` starts in `time_zone`: midnight, or the first whole hour after
+/// it when a DST change skips midnight.
+fn local_midnight(time_zone: Tz, date: NaiveDate) -> i64 {
+    (0..24)
+        .find_map(|hour| {
+            time_zone
+                .from_local_datetime(&date.and_hms_opt(hour, 0, 0)?)
+                .earliest()
+        })
+        .map(|start| start.timestamp_millis())
+        .unwrap_or_default()
+}
+
 #[cfg(test)]
 mod list_time_entries_query_handler_tests {
     use super::*;
@@ -64,6 +372,7 @@ mod list_time_entries_query_handler_tests {
         TimeEntryRow, TimeEntryStatus,
     };
     use crate::shared::infrastructure::projection_store::in_memory::InMemoryProjectionStore;
+    use crate::shared::infrastructure::query_cache::in_memory::InMemoryQueryCache;
     use rstest::rstest;
 
     fn make_row(user_id: &str, te_id: &str, started_at: Option<i64>) -> TimeEntryRow {
@@ -83,7 +392,9 @@ mod list_time_entries_query_handler_tests {
             updated_at: 0,
             updated_by: "sys".to_string(),
             deleted_at: None,
+            hourly_rate: None,
             last_event_id: None,
+            breaks: vec![],
         }
     }
 
@@ -104,7 +415,11 @@ mod list_time_entries_query_handler_tests {
     async fn it_should_return_empty_list_when_no_entries() {
         let store = InMemoryProjectionStore::<ListTimeEntriesState>::new();
         let handler = ListTimeEntriesQueryHandler::new(store);
-        let result = handler.list_by_user_id("u1", 0, 10, true).await.unwrap();
+        let result = handler
+            .list_by_user_id("u1", 0, 10, true)
+            .await
+            .unwrap()
+            .items;
         assert!(result.is_empty());
     }
 
@@ -117,7 +432,11 @@ mod list_time_entries_query_handler_tests {
         ];
         let store = store_with_rows(rows).await;
         let handler = ListTimeEntriesQueryHandler::new(store);
-        let result = handler.list_by_user_id("u1", 0, 10, false).await.unwrap();
+        let result = handler
+            .list_by_user_id("u1", 0, 10, false)
+            .await
+            .unwrap()
+            .items;
         assert_eq!(result.len(), 1);
         assert_eq!(result[0].time_entry_id, "te1");
     }
@@ -132,7 +451,11 @@ mod list_time_entries_query_handler_tests {
         ];
         let store = store_with_rows(rows).await;
         let handler = ListTimeEntriesQueryHandler::new(store);
-        let result = handler.list_by_user_id("u1", 0, 10, true).await.unwrap();
+        let result = handler
+            .list_by_user_id("u1", 0, 10, true)
+            .await
+            .unwrap()
+            .items;
         assert_eq!(result[0].started_at, Some(3000));
         assert_eq!(result[1].started_at, Some(2000));
         assert_eq!(result[2].started_at, Some(1000));
@@ -147,7 +470,11 @@ mod list_time_entries_query_handler_tests {
         ];
         let store = store_with_rows(rows).await;
         let handler = ListTimeEntriesQueryHandler::new(store);
-        let result = handler.list_by_user_id("u1", 0, 10, false).await.unwrap();
+        let result = handler
+            .list_by_user_id("u1", 0, 10, false)
+            .await
+            .unwrap()
+            .items;
         assert_eq!(result[0].started_at, Some(1000));
         assert_eq!(result[1].started_at, Some(3000));
     }
@@ -161,7 +488,11 @@ mod list_time_entries_query_handler_tests {
         ];
         let store = store_with_rows(rows).await;
         let handler = ListTimeEntriesQueryHandler::new(store);
-        let result = handler.list_by_user_id("u1", 0, 10, false).await.unwrap();
+        let result = handler
+            .list_by_user_id("u1", 0, 10, false)
+            .await
+            .unwrap()
+            .items;
         assert_eq!(result.len(), 2);
         // Draft (no started_at → unwrap_or(0)) sorts before registered in ascending
         assert_eq!(result[0].time_entry_id, "te-draft");
@@ -178,7 +509,11 @@ mod list_time_entries_query_handler_tests {
         ];
         let store = store_with_rows(rows).await;
         let handler = ListTimeEntriesQueryHandler::new(store);
-        let result = handler.list_by_user_id("u1", 1, 1, false).await.unwrap();
+        let result = handler
+            .list_by_user_id("u1", 1, 1, false)
+            .await
+            .unwrap()
+            .items;
         assert_eq!(result.len(), 1);
         assert_eq!(result[0].started_at, Some(2000));
     }
@@ -189,7 +524,11 @@ mod list_time_entries_query_handler_tests {
         let rows = vec![make_row("u1", "te1", Some(1000))];
         let store = store_with_rows(rows).await;
         let handler = ListTimeEntriesQueryHandler::new(store);
-        let result = handler.list_by_user_id("u1", 10, 5, false).await.unwrap();
+        let result = handler
+            .list_by_user_id("u1", 10, 5, false)
+            .await
+            .unwrap()
+            .items;
         assert!(result.is_empty());
     }
 
@@ -202,4 +541,373 @@ mod list_time_entries_query_handler_tests {
         let result = handler.list_by_user_id("u1", 0, 10, false).await;
         assert!(result.is_err());
     }
+
+    fn at(datetime: &str) -> i64 {
+        DateTime::parse_from_rfc3339(datetime)
+            .unwrap()
+            .timestamp_millis()
+    }
+
+    fn entry(te_id: &str, from: &str, to: Option<&str>) -> TimeEntryRow {
+        TimeEntryRow {
+            ended_at: to.map(at),
+            ..make_row("u1", te_id, Some(at(from)))
+        }
+    }
+
+    #[rstest]
+    #[case::utc(chrono_tz::UTC, &[("2026-12-01", 1_800_000, 1), ("2026-12-02", 0, 0)])]
+    #[case::amsterdam(
+        chrono_tz::Europe::Amsterdam,
+        &[("2026-12-01", 0, 0), ("2026-12-02", 1_800_000, 1)]
+    )]
+    #[tokio::test]
+    async fn it_should_bucket_entries_by_local_date(
+        #[case] time_zone: Tz,
+        #[case] expected: &[(&str, i64, usize)],
+    ) {
+        let rows = vec![entry(
+            "te1",
+            "2026-12-01T23:15:00Z",
+            Some("2026-12-01T23:45:00Z"),
+        )];
+        let handler = ListTimeEntriesQueryHandler::new(store_with_rows(rows).await);
+
+        let days = handler
+            .entries_by_day(
+                "u1",
+                "2026-12-01".parse().unwrap(),
+                "2026-12-02".parse().unwrap(),
+                time_zone,
+            )
+            .await
+            .unwrap();
+
+        let buckets: Vec<(String, i64, usize)> = days
+            .iter()
+            .map(|day| (day.date.to_string(), day.total_ms, day.entries.len()))
+            .collect();
+        let expected: Vec<(String, i64, usize)> = expected
+            .iter()
+            .map(|(date, total_ms, count)| (date.to_string(), *total_ms, *count))
+            .collect();
+        assert_eq!(buckets, expected);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_split_entries_across_midnight_and_skip_deleted_and_others() {
+        let mut deleted = entry("te-deleted", "2026-12-02T09:00:00Z", None);
+        deleted.deleted_at = Some(0);
+        let rows = vec![
+            entry(
+                "te-late",
+                "2026-12-02T10:00:00Z",
+                Some("2026-12-02T11:00:00Z"),
+            ),
+            entry(
+                "te-night",
+                "2026-12-01T23:00:00Z",
+                Some("2026-12-02T01:00:00Z"),
+            ),
+            entry("te-running", "2026-12-03T08:00:00Z", None),
+            make_row("u1", "te-draft", None),
+            make_row("u2", "te-other", Some(at("2026-12-02T12:00:00Z"))),
+            deleted,
+        ];
+        let handler = ListTimeEntriesQueryHandler::new(store_with_rows(rows).await);
+
+        let days = handler
+            .entries_by_day(
+                "u1",
+                "2026-12-01".parse().unwrap(),
+                "2026-12-03".parse().unwrap(),
+                chrono_tz::UTC,
+            )
+            .await
+            .unwrap();
+
+        let ids = |index: usize| -> Vec<&str> {
+            days[index]
+                .entries
+                .iter()
+                .map(|view| view.time_entry_id.as_str())
+                .collect()
+        };
+        assert_eq!(ids(0), vec!["te-night"]);
+        assert_eq!(days[0].total_ms, 3_600_000);
+        assert_eq!(ids(1), vec!["te-night", "te-late"]);
+        assert_eq!(days[1].total_ms, 7_200_000);
+        assert_eq!(ids(2), vec!["te-running"]);
+        assert_eq!(days[2].total_ms, 0);
+    }
+
+    #[rstest]
+    #[case::first_page(0, 2, &["te1", "te2"], Some(2))]
+    #[case::last_page(2, 2, &["te3"], None)]
+    #[case::past_the_end(5, 2, &[], None)]
+    #[tokio::test]
+    async fn it_should_page_with_the_total_and_next_cursor(
+        #[case] offset: u64,
+        #[case] limit: u64,
+        #[case] expected: &[&str],
+        #[case] next_cursor: Option<u64>,
+    ) {
+        let rows = vec![
+            make_row("u1", "te1", Some(1000)),
+            make_row("u1", "te2", None),
+            make_row("u1", "te3", Some(3000)),
+            make_row("u2", "te4", Some(4000)),
+        ];
+        let handler = ListTimeEntriesQueryHandler::new(store_with_rows(rows).await);
+
+        let page = handler
+            .list_by_user_id("u1", offset, limit, false)
+            .await
+            .unwrap();
+
+        let mut ids: Vec<&str> = page
+            .items
+            .iter()
+            .map(|v| v.time_entry_id.as_str())
+            .collect();
+        ids.sort();
+        assert_eq!(ids, expected);
+        assert_eq!(page.total, 3);
+        assert_eq!(page.next_cursor, next_cursor);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_list_the_requested_entries_of_a_user() {
+        let rows = vec![
+            make_row("u1", "te1", Some(1000)),
+            make_row("u1", "te2", Some(2000)),
+            make_row("u2", "te3", Some(3000)),
+        ];
+        let handler = ListTimeEntriesQueryHandler::new(store_with_rows(rows).await);
+        let ids = ["te3", "te2", "te1", "te9"].map(str::to_string).into();
+
+        let result = handler.list_by_ids("u1", &ids).await.unwrap();
+
+        let ids: Vec<&str> = result.iter().map(|v| v.time_entry_id.as_str()).collect();
+        assert_eq!(ids, vec!["te1", "te2"]);
+    }
+
+    #[rstest]
+    #[case::caught_up("u1", Some("TimeEntry-te1:3"), 3, true)]
+    #[case::ahead("u1", Some("TimeEntry-te1:4"), 3, true)]
+    #[case::lagging("u1", Some("TimeEntry-te1:2"), 3, false)]
+    #[case::never_projected("u1", None, 1, false)]
+    #[case::other_user("u2", Some("TimeEntry-te1:3"), 3, false)]
+    #[tokio::test]
+    async fn it_should_get_an_entry_once_it_caught_up_with_a_version(
+        #[case] user_id: &str,
+        #[case] last_event_id: Option<&str>,
+        #[case] stream_version: i64,
+        #[case] found: bool,
+    ) {
+        let mut row = make_row("u1", "te1", Some(1000));
+        row.last_event_id = last_event_id.map(str::to_string);
+        let handler = ListTimeEntriesQueryHandler::new(store_with_rows(vec![row]).await);
+
+        let view = handler
+            .get_at_version(user_id, "te1", stream_version)
+            .await
+            .unwrap();
+
+        assert_eq!(view.is_some(), found);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_get_an_entry_with_the_version_it_reflects() {
+        let mut row = make_row("u1", "te1", Some(1000));
+        row.last_event_id = Some("TimeEntry-te1:5".to_string());
+        let handler = ListTimeEntriesQueryHandler::new(store_with_rows(vec![row]).await);
+
+        let (view, version) = handler.get("te1").await.unwrap().unwrap();
+
+        assert_eq!(view.time_entry_id, "te1");
+        assert_eq!(version, Some(5));
+        assert!(handler.get("te9").await.unwrap().is_none());
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_propagate_store_errors_when_listing_by_ids() {
+        let mut store = InMemoryProjectionStore::<ListTimeEntriesState>::new();
+        store.toggle_offline();
+        let handler = ListTimeEntriesQueryHandler::new(store);
+        assert!(handler.list_by_ids("u1", &BTreeSet::new()).await.is_err());
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_serve_repeated_queries_from_the_cache() {
+        let store = store_with_rows(vec![make_row("u1", "te1", Some(1000))]).await;
+        let cache = InMemoryQueryCache::<Page<TimeEntryView>>::new();
+        let handler = ListTimeEntriesQueryHandler::new(store.clone()).with_cache(Arc::new(cache));
+
+        let first = handler.list_by_user_id("u1", 0, 10, false).await.unwrap();
+        store
+            .save(ListTimeEntriesState::default(), 2)
+            .await
+            .unwrap();
+        let second = handler.list_by_user_id("u1", 0, 10, false).await.unwrap();
+        let other_page = handler.list_by_user_id("u1", 1, 10, false).await.unwrap();
+
+        assert_eq!(first, second);
+        assert!(other_page.items.is_empty());
+        assert_eq!(handler.cache_metrics().hits(), 1);
+        assert_eq!(handler.cache_metrics().misses(), 2);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_not_cache_store_errors() {
+        let mut store = InMemoryProjectionStore::<ListTimeEntriesState>::new();
+        store.toggle_offline();
+        let cache = InMemoryQueryCache::<Page<TimeEntryView>>::new();
+        let handler = ListTimeEntriesQueryHandler::new(store).with_cache(Arc::new(cache.clone()));
+
+        assert!(handler.list_by_user_id("u1", 0, 10, false).await.is_err());
+        assert!(cache.is_empty().await);
+        assert_eq!(handler.cache_metrics().misses(), 1);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_total_registered_minutes_per_tag() {
+        let hour = 60 * MINUTE;
+        let tagged = |te_id: &str, hours: i64, tag_ids: &[&str]| TimeEntryRow {
+            ended_at: Some(hours * hour),
+            tag_ids: tag_ids
+                .iter()
+                .map(|tag_id| Tag::parse(tag_id).unwrap())
+                .collect(),
+            ..make_row("u1", te_id, Some(0))
+        };
+        let draft = TimeEntryRow {
+            status: TimeEntryStatus::Draft,
+            ..tagged("te5", 9, &["dev"])
+        };
+        let instant = tagged("te6", 0, &["ops"]);
+        let store = store_with_rows(vec![
+            tagged("te1", 3, &["dev"]),
+            tagged("te2", 2, &["dev", "ops"]),
+            tagged("te3", 1, &[]),
+            make_row("u2", "te4", Some(0)),
+            draft,
+            instant,
+        ])
+        .await;
+        let handler = ListTimeEntriesQueryHandler::new(store);
+        let day = NaiveDate::from_ymd_opt(1970, 1, 1).unwrap();
+
+        let totals = handler.time_by_tag("u1", day, day).await.unwrap();
+
+        assert_eq!(
+            totals,
+            vec![
+                TagTotal {
+                    tag_id: Some("dev".to_string()),
+                    minutes: 300,
+                },
+                TagTotal {
+                    tag_id: Some("ops".to_string()),
+                    minutes: 120,
+                },
+                TagTotal {
+                    tag_id: None,
+                    minutes: 60,
+                },
+            ]
+        );
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_propagate_store_errors_when_totalling_tags() {
+        let mut store = InMemoryProjectionStore::<ListTimeEntriesState>::new();
+        store.toggle_offline();
+        let handler = ListTimeEntriesQueryHandler::new(store);
+        let day = NaiveDate::from_ymd_opt(1970, 1, 1).unwrap();
+
+        assert!(handler.time_by_tag("u1", day, day).await.is_err());
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_find_overlapping_entries_and_same_tags_on_the_same_day() {
+        let hour = 60 * MINUTE;
+        let entry = |te_id: &str, start: i64, end: Option<i64>, tag_ids: &[&str]| TimeEntryRow {
+            ended_at: end,
+            tag_ids: tag_ids
+                .iter()
+                .map(|tag_id| Tag::parse(tag_id).unwrap())
+                .collect(),
+            ..make_row("u1", te_id, Some(start))
+        };
+        let deleted = TimeEntryRow {
+            deleted_at: Some(0),
+            ..entry("te-deleted", 9 * hour, Some(11 * hour), &[])
+        };
+        let store = store_with_rows(vec![
+            entry("te-overlap", 9 * hour, Some(11 * hour), &["ops"]),
+            entry("te-running", 8 * hour, None, &[]),
+            entry("te-same-tags", hour, Some(2 * hour), &["ops", "dev"]),
+            entry("te-other-tags", 2 * hour, Some(3 * hour), &["dev"]),
+            entry("te-next-day", 25 * hour, Some(26 * hour), &["dev", "ops"]),
+            entry("te-adjacent", 12 * hour, Some(13 * hour), &[]),
+            make_row("u1", "te-draft", None),
+            make_row("u2", "te-other-user", Some(10 * hour)),
+            deleted,
+        ])
+        .await;
+        let handler = ListTimeEntriesQueryHandler::new(store);
+        let tag_ids = vec![Tag::parse("dev").unwrap(), Tag::parse("ops").unwrap()];
+
+        let similar = handler
+            .find_similar_entries("u1", 10 * hour, 12 * hour, &tag_ids)
+            .await
+            .unwrap();
+
+        let found: Vec<(&str, SimilarityReason)> = similar
+            .iter()
+            .map(|entry| (entry.time_entry.time_entry_id.as_str(), entry.reason))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                ("te-same-tags", SimilarityReason::SameTagsSameDay),
+                ("te-running", SimilarityReason::Overlapping),
+                ("te-overlap", SimilarityReason::Overlapping),
+            ]
+        );
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_not_match_untagged_entries_by_day_alone() {
+        let store = store_with_rows(vec![make_row("u1", "te1", Some(1000))]).await;
+        let handler = ListTimeEntriesQueryHandler::new(store);
+
+        let similar = handler
+            .find_similar_entries("u1", 5000, 6000, &[])
+            .await
+            .unwrap();
+
+        assert!(similar.is_empty());
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_propagate_store_errors_when_finding_similar_entries() {
+        let mut store = InMemoryProjectionStore::<ListTimeEntriesState>::new();
+        store.toggle_offline();
+        let handler = ListTimeEntriesQueryHandler::new(store);
+
+        assert!(handler.find_similar_entries("u1", 0, 1, &[]).await.is_err());
+    }
 }
@@ -0,0 +1,126 @@
+// Rebuilds the list time entries projection as a job, one partition after the other, so an
+// operator can recover a corrupted read model without a redeploy. Progress counts the
+// partitions rebuilt; a retried job rebuilds all of them again.
+
+use async_trait::async_trait;
+use serde_json::{Value as Json, json};
+
+use crate::modules::time_entries::use_cases::list_time_entries::projection::ListTimeEntriesState;
+use crate::modules::time_entries::use_cases::list_time_entries::projector::ListTimeEntriesProjector;
+use crate::shared::application::jobs::{JobContext, JobHandler};
+use crate::shared::infrastructure::job_store::Job;
+use crate::shared::infrastructure::projection_store::ProjectionStore;
+
+pub const JOB_KIND: &str = "list_time_entries.rebuild";
+
+pub struct RebuildListTimeEntriesJob<TStore>
+where
+    TStore: ProjectionStore<ListTimeEntriesState> + Send + Sync + 'static,
+{
+    projectors: Vec<ListTimeEntriesProjector<TStore>>,
+}
+
+impl<TStore> RebuildListTimeEntriesJob<TStore>
+where
+    TStore: ProjectionStore<ListTimeEntriesState> + Send + Sync + 'static,
+{
+    /// One projector per partition, each writing to its own partition's store.
+    pub fn new(projectors: Vec<ListTimeEntriesProjector<TStore>>) -> Self {
+        Self { projectors }
+    }
+}
+
+#[async_trait]
+impl<TStore> JobHandler for RebuildListTimeEntriesJob<TStore>
+where
+    TStore: ProjectionStore<ListTimeEntriesState> + Send + Sync + 'static,
+{
+    async fn run(&self, _job: &Job, ctx: &JobContext<'_>) -> anyhow::Result<Json> {
+        let total = self.projectors.len() as u64;
+        for (rebuilt, projector) in self.projectors.iter().enumerate() {
+            ctx.report_progress(rebuilt as u64, Some(total)).await?;
+            projector.rebuild().await?;
+        }
+        ctx.report_progress(total, Some(total)).await?;
+        Ok(json!({ "partitions": total }))
+    }
+}
+
+#[cfg(test)]
+mod rebuild_job_tests {
+    use super::*;
+    use crate::modules::time_entries::core::events::TimeEntryEvent;
+    use crate::modules::time_entries::use_cases::set_started_at::handler::SetStartedAtHandler;
+    use crate::shared::application::jobs::JobRunner;
+    use crate::shared::core::partitioning::Partition;
+    use crate::shared::infrastructure::event_store::in_memory::InMemoryEventStore;
+    use crate::shared::infrastructure::intent_outbox::in_memory::InMemoryDomainOutbox;
+    use crate::shared::infrastructure::job_store::in_memory::InMemoryJobStore;
+    use crate::shared::infrastructure::job_store::{JobProgress, JobStatus, JobStore};
+    use crate::shared::infrastructure::projection_store::in_memory::InMemoryProjectionStore;
+    use crate::tests::fixtures::commands::set_started_at::SetStartedAtBuilder;
+    use rstest::rstest;
+    use std::sync::Arc;
+    use tokio::sync::broadcast;
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_rebuild_every_partition_and_report_progress() {
+        let event_store = InMemoryEventStore::<TimeEntryEvent>::new();
+        let handler = SetStartedAtHandler::new(event_store.clone(), InMemoryDomainOutbox::new());
+        for time_entry_id in ["te-1", "te-2", "te-3", "te-4"] {
+            let command = SetStartedAtBuilder::new()
+                .time_entry_id(time_entry_id.to_string())
+                .build();
+            handler
+                .handle(&format!("TimeEntry-{time_entry_id}"), command)
+                .await
+                .unwrap();
+        }
+        let (tech_tx, _) = broadcast::channel(64);
+        let stores: Vec<_> = (0..2)
+            .map(|_| InMemoryProjectionStore::<ListTimeEntriesState>::new())
+            .collect();
+        let projectors = stores
+            .iter()
+            .enumerate()
+            .map(|(index, store)| {
+                ListTimeEntriesProjector::new(
+                    format!("p:{index}"),
+                    store.clone(),
+                    event_store.clone(),
+                    tech_tx.clone(),
+                )
+                .with_partition(Partition { index, count: 2 })
+            })
+            .collect();
+        let job_store = InMemoryJobStore::new();
+        let job = Job::queued(JOB_KIND, json!({}), 0);
+        job_store.enqueue(job.clone()).await.unwrap();
+
+        JobRunner::new(job_store.clone())
+            .with_handler(
+                JOB_KIND,
+                Arc::new(RebuildListTimeEntriesJob::new(projectors)),
+            )
+            .run_due()
+            .await
+            .unwrap();
+
+        let job = job_store.get(&job.job_id).await.unwrap().unwrap();
+        assert_eq!(job.status, JobStatus::Succeeded);
+        assert_eq!(job.result, Some(json!({ "partitions": 2 })));
+        assert_eq!(
+            job.progress,
+            Some(JobProgress {
+                done: 2,
+                total: Some(2)
+            })
+        );
+        let mut rows = 0;
+        for store in &stores {
+            rows += store.state().await.unwrap().unwrap_or_default().rows.len();
+        }
+        assert_eq!(rows, 4);
+    }
+}
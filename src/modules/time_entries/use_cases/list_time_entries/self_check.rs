@@ -0,0 +1,389 @@
+// Startup self-check for the list projection: a bounded comparison of the persisted read model
+// against the event log, run before the projectors start.
+//
+// Every store's checkpoint must lie within the log. A sample of each store's rows is then
+// checked against its own stream: a row must not reflect a version its stream never reached,
+// and folding the stream up to the version the row reflects must give back the same row.
+// Only the sampled streams are read, so the cost is bounded by the sample size, not the log.
+// Stores under another schema version are skipped; their projector rebuilds them anyway.
+
+use crate::modules::time_entries::core::events::TimeEntryEvent;
+use crate::modules::time_entries::use_cases::list_time_entries::projection::{
+    ListTimeEntriesState, SCHEMA_VERSION, TimeEntryRow,
+};
+use crate::modules::time_entries::use_cases::list_time_entries::projector::ListTimeEntriesProjector;
+use crate::shared::core::primitives::last_event_version;
+use crate::shared::infrastructure::event_store::in_memory::InMemoryEventStore;
+use crate::shared::infrastructure::event_store::{EventLog, EventStore, SharedEventLog};
+use crate::shared::infrastructure::projection_store::ProjectionStore;
+use crate::shared::infrastructure::projection_store::in_memory::InMemoryProjectionStore;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use tokio::sync::broadcast;
+
+const DEFAULT_SAMPLE_SIZE: usize = 100;
+
+/// What to do with the self-check at boot.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SelfCheckMode {
+    #[default]
+    Off,
+    /// Log inconsistencies and start anyway.
+    Warn,
+    /// Refuse to start on any inconsistency.
+    Strict,
+}
+
+impl std::str::FromStr for SelfCheckMode {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "off" => Ok(Self::Off),
+            "warn" => Ok(Self::Warn),
+            "strict" => Ok(Self::Strict),
+            other => Err(format!("unknown self-check mode: {other}")),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Inconsistency {
+    /// The store claims to reflect events the log does not have.
+    CheckpointAhead {
+        store: String,
+        checkpoint: u64,
+        head: u64,
+    },
+    /// The row reflects a version its stream never reached.
+    RowAhead {
+        store: String,
+        time_entry_id: String,
+        row_version: i64,
+        stream_version: i64,
+    },
+    /// Folding the stream up to the row's version gives another row, or none.
+    RowDiffers {
+        store: String,
+        live: Box<TimeEntryRow>,
+        folded: Option<Box<TimeEntryRow>>,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct SelfCheckReport {
+    /// The event log's head when the check ran.
+    pub head: u64,
+    pub stores_checked: usize,
+    pub rows_sampled: usize,
+    pub inconsistencies: Vec<Inconsistency>,
+}
+
+impl SelfCheckReport {
+    pub fn is_clean(&self) -> bool {
+        self.inconsistencies.is_empty()
+    }
+}
+
+pub struct SelfCheck {
+    pub event_store: SharedEventLog<TimeEntryEvent>,
+    pub sample_size: usize,
+}
+
+impl SelfCheck {
+    pub fn new(event_store: impl EventLog<TimeEntryEvent> + 'static) -> Self {
+        Self {
+            event_store: Arc::new(event_store),
+            sample_size: DEFAULT_SAMPLE_SIZE,
+        }
+    }
+
+    /// Rows checked per store at most, spread evenly over its time entry ids.
+    pub fn with_sample_size(mut self, sample_size: usize) -> Self {
+        self.sample_size = sample_size;
+        self
+    }
+
+    /// Checks each named store against the event log.
+    pub async fn run<TStore>(&self, stores: &[(String, TStore)]) -> anyhow::Result<SelfCheckReport>
+    where
+        TStore: ProjectionStore<ListTimeEntriesState>,
+    {
+        let head = self.event_store.head().await?;
+        let mut report = SelfCheckReport {
+            head,
+            stores_checked: 0,
+            rows_sampled: 0,
+            inconsistencies: Vec::new(),
+        };
+        for (name, store) in stores {
+            if store.schema_version().await? != Some(SCHEMA_VERSION) {
+                continue;
+            }
+            report.stores_checked += 1;
+            let checkpoint = store.checkpoint().await?;
+            if checkpoint > head {
+                report.inconsistencies.push(Inconsistency::CheckpointAhead {
+                    store: name.clone(),
+                    checkpoint,
+                    head,
+                });
+            }
+            let state = store.state().await?.unwrap_or_default();
+            let sample = self.sample(state);
+            report.rows_sampled += sample.len();
+            report
+                .inconsistencies
+                .extend(self.check_rows(name, sample).await?);
+        }
+        Ok(report)
+    }
+
+    fn sample(&self, state: ListTimeEntriesState) -> Vec<TimeEntryRow> {
+        let rows: BTreeMap<String, TimeEntryRow> = state.rows.into_iter().collect();
+        let step = rows.len().div_ceil(self.sample_size.max(1)).max(1);
+        rows.into_values().step_by(step).collect()
+    }
+
+    async fn check_rows(
+        &self,
+        store: &str,
+        rows: Vec<TimeEntryRow>,
+    ) -> anyhow::Result<Vec<Inconsistency>> {
+        let mut inconsistencies = Vec::new();
+        let scratch_log = InMemoryEventStore::<TimeEntryEvent>::new();
+        let mut folded_rows = Vec::new();
+        for row in rows {
+            let Some((stream_id, row_version)) = row
+                .last_event_id
+                .as_deref()
+                .and_then(|id| Some((id.rsplit_once(':')?.0, last_event_version(Some(id))?)))
+            else {
+                continue;
+            };
+            let stream = self
+                .event_store
+                .load_paged(stream_id, 0, row_version as usize)
+                .await?;
+            if stream.version < row_version {
+                inconsistencies.push(Inconsistency::RowAhead {
+                    store: store.to_string(),
+                    time_entry_id: row.time_entry_id,
+                    row_version,
+                    stream_version: stream.version,
+                });
+                continue;
+            }
+            scratch_log.append(stream_id, 0, &stream.events).await?;
+            folded_rows.push(row);
+        }
+
+        let scratch = InMemoryProjectionStore::<ListTimeEntriesState>::new();
+        let (technical_tx, _) = broadcast::channel(1);
+        ListTimeEntriesProjector::new("self_check", scratch.clone(), scratch_log, technical_tx)
+            .replay_until(u64::MAX)
+            .await?;
+        let mut folded = scratch.state().await?.unwrap_or_default();
+        for live in folded_rows {
+            let expected = folded.rows.remove(&live.time_entry_id);
+            if expected.as_ref() != Some(&live) {
+                inconsistencies.push(Inconsistency::RowDiffers {
+                    store: store.to_string(),
+                    live: Box::new(live),
+                    folded: expected.map(Box::new),
+                });
+            }
+        }
+        Ok(inconsistencies)
+    }
+}
+
+#[cfg(test)]
+mod self_check_tests {
+    use super::*;
+    use crate::modules::time_entries::use_cases::set_ended_at::handler::SetEndedAtHandler;
+    use crate::modules::time_entries::use_cases::set_started_at::handler::SetStartedAtHandler;
+    use crate::shared::infrastructure::event_store::StoredEvent;
+    use crate::shared::infrastructure::intent_outbox::in_memory::InMemoryDomainOutbox;
+    use crate::tests::fixtures::commands::set_ended_at::SetEndedAtBuilder;
+    use crate::tests::fixtures::commands::set_started_at::SetStartedAtBuilder;
+    use rstest::rstest;
+
+    async fn register(event_store: &InMemoryEventStore<TimeEntryEvent>, time_entry_id: &str) {
+        let outbox = InMemoryDomainOutbox::new();
+        let stream_id = format!("TimeEntry-{time_entry_id}");
+        SetStartedAtHandler::new(event_store.clone(), outbox.clone())
+            .handle(
+                &stream_id,
+                SetStartedAtBuilder::new()
+                    .time_entry_id(time_entry_id.to_string())
+                    .build(),
+            )
+            .await
+            .unwrap();
+        SetEndedAtHandler::new(event_store.clone(), outbox)
+            .handle(
+                &stream_id,
+                SetEndedAtBuilder::new()
+                    .time_entry_id(time_entry_id.to_string())
+                    .build(),
+            )
+            .await
+            .unwrap();
+    }
+
+    /// A live store built by a full rebuild, the way a projector starts.
+    async fn project(
+        event_store: &InMemoryEventStore<TimeEntryEvent>,
+    ) -> InMemoryProjectionStore<ListTimeEntriesState> {
+        let store = InMemoryProjectionStore::new();
+        let (tech_tx, _) = broadcast::channel(16);
+        let (closed_tx, receiver) = broadcast::channel::<StoredEvent<TimeEntryEvent>>(1);
+        drop(closed_tx);
+        ListTimeEntriesProjector::new("live", store.clone(), event_store.clone(), tech_tx)
+            .run(receiver)
+            .await;
+        store
+    }
+
+    fn named(
+        store: InMemoryProjectionStore<ListTimeEntriesState>,
+    ) -> Vec<(String, InMemoryProjectionStore<ListTimeEntriesState>)> {
+        vec![("list_time_entries:0".to_string(), store)]
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_pass_a_store_that_matches_the_log() {
+        let event_store = InMemoryEventStore::<TimeEntryEvent>::new();
+        register(&event_store, "te-1").await;
+        register(&event_store, "te-2").await;
+        let live = project(&event_store).await;
+
+        let report = SelfCheck::new(event_store).run(&named(live)).await.unwrap();
+
+        assert!(report.is_clean(), "{report:?}");
+        assert_eq!(report.head, 8);
+        assert_eq!(report.stores_checked, 1);
+        assert_eq!(report.rows_sampled, 2);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_pass_a_store_that_lags_behind_the_log() {
+        let event_store = InMemoryEventStore::<TimeEntryEvent>::new();
+        register(&event_store, "te-1").await;
+        let live = project(&event_store).await;
+        register(&event_store, "te-2").await;
+        register(&event_store, "te-1").await;
+
+        let report = SelfCheck::new(event_store).run(&named(live)).await.unwrap();
+
+        assert!(report.is_clean(), "{report:?}");
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_report_rows_and_checkpoints_the_log_cannot_explain() {
+        let event_store = InMemoryEventStore::<TimeEntryEvent>::new();
+        register(&event_store, "te-1").await;
+        register(&event_store, "te-2").await;
+        let live = project(&event_store).await;
+        let mut state = live.state().await.unwrap().unwrap();
+        let folded = state.rows["te-1"].clone();
+        let mut tampered = folded.clone();
+        tampered.user_id = "someone-else".to_string();
+        state.rows.insert("te-1".to_string(), tampered.clone());
+        let ahead = state.rows.get_mut("te-2").unwrap();
+        ahead.last_event_id = Some("TimeEntry-te-2:9".to_string());
+        live.save(state, 12).await.unwrap();
+
+        let report = SelfCheck::new(event_store).run(&named(live)).await.unwrap();
+
+        let store = "list_time_entries:0".to_string();
+        assert_eq!(
+            report.inconsistencies,
+            vec![
+                Inconsistency::CheckpointAhead {
+                    store: store.clone(),
+                    checkpoint: 12,
+                    head: 8,
+                },
+                Inconsistency::RowAhead {
+                    store: store.clone(),
+                    time_entry_id: "te-2".to_string(),
+                    row_version: 9,
+                    stream_version: 4,
+                },
+                Inconsistency::RowDiffers {
+                    store,
+                    live: Box::new(tampered),
+                    folded: Some(Box::new(folded)),
+                },
+            ]
+        );
+    }
+
+    #[rstest]
+    #[case::fewer_rows_than_the_sample(10, 4)]
+    #[case::every_other_row(2, 2)]
+    #[case::one_row(1, 1)]
+    #[tokio::test]
+    async fn it_should_sample_at_most_the_sample_size(
+        #[case] sample_size: usize,
+        #[case] expected: usize,
+    ) {
+        let event_store = InMemoryEventStore::<TimeEntryEvent>::new();
+        for index in 0..4 {
+            register(&event_store, &format!("te-{index}")).await;
+        }
+        let live = project(&event_store).await;
+
+        let report = SelfCheck::new(event_store)
+            .with_sample_size(sample_size)
+            .run(&named(live))
+            .await
+            .unwrap();
+
+        assert!(report.is_clean(), "{report:?}");
+        assert_eq!(report.rows_sampled, expected);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_skip_stores_under_another_schema() {
+        let live = InMemoryProjectionStore::<ListTimeEntriesState>::new();
+        live.save(ListTimeEntriesState::default(), 5).await.unwrap();
+
+        let report = SelfCheck::new(InMemoryEventStore::new())
+            .run(&named(live))
+            .await
+            .unwrap();
+
+        assert!(report.is_clean(), "{report:?}");
+        assert_eq!(report.stores_checked, 0);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_fail_when_the_event_store_is_offline() {
+        let event_store = InMemoryEventStore::<TimeEntryEvent>::new();
+        event_store.toggle_offline();
+
+        let result = SelfCheck::new(event_store)
+            .run(&named(InMemoryProjectionStore::new()))
+            .await;
+
+        assert!(result.is_err());
+    }
+
+    #[rstest]
+    #[case("off", SelfCheckMode::Off)]
+    #[case("warn", SelfCheckMode::Warn)]
+    #[case("strict", SelfCheckMode::Strict)]
+    fn it_should_parse_modes(#[case] value: &str, #[case] expected: SelfCheckMode) {
+        assert_eq!(value.parse::<SelfCheckMode>(), Ok(expected));
+        assert!("loud".parse::<SelfCheckMode>().is_err());
+    }
+}
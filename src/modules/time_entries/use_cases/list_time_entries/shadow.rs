@@ -0,0 +1,337 @@
+// Shadow mode for the list projection: a second projector replays the same event feed into a
+// scratch store, up to the live read model's checkpoint, and the two sets of rows are diffed.
+// Run it after changing `apply` and before letting a schema bump rebuild the live model: any
+// divergence is a row the new code would write differently.
+//
+// Every comparison replays from the first event, so this is a debugging aid, not something
+// to run at a high frequency against a large store.
+
+use crate::modules::time_entries::core::events::TimeEntryEvent;
+use crate::modules::time_entries::use_cases::list_time_entries::projection::{
+    ListTimeEntriesState, TimeEntryRow,
+};
+use crate::modules::time_entries::use_cases::list_time_entries::projector::ListTimeEntriesProjector;
+use crate::shared::core::partitioning::Partition;
+use crate::shared::infrastructure::event_store::{EventLog, SharedEventLog};
+use crate::shared::infrastructure::projection_store::ProjectionStore;
+use crate::shared::infrastructure::projection_store::in_memory::InMemoryProjectionStore;
+use std::collections::BTreeSet;
+use std::sync::Arc;
+use tokio::sync::broadcast;
+
+/// Reads of a moving live store are retried this often before giving up.
+const SNAPSHOT_ATTEMPTS: usize = 3;
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Divergence {
+    /// The live read model has a row the replay does not produce.
+    Unexpected { live: TimeEntryRow },
+    /// The replay produces a row the live read model lacks.
+    Missing { shadow: TimeEntryRow },
+    Differs {
+        live: Box<TimeEntryRow>,
+        shadow: Box<TimeEntryRow>,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct ShadowReport {
+    /// The live checkpoint both sides were compared at.
+    pub checkpoint: u64,
+    pub rows_compared: usize,
+    /// Ordered by time entry id.
+    pub divergences: Vec<Divergence>,
+}
+
+impl ShadowReport {
+    pub fn is_clean(&self) -> bool {
+        self.divergences.is_empty()
+    }
+}
+
+pub struct ShadowProjector {
+    pub name: String,
+    pub event_store: SharedEventLog<TimeEntryEvent>,
+    pub partition: Option<Partition>,
+}
+
+impl ShadowProjector {
+    pub fn new(
+        name: impl Into<String>,
+        event_store: impl EventLog<TimeEntryEvent> + 'static,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            event_store: Arc::new(event_store),
+            partition: None,
+        }
+    }
+
+    /// Replays only the streams `partition` owns, to compare against that partition's store.
+    pub fn with_partition(mut self, partition: Partition) -> Self {
+        self.partition = Some(partition);
+        self
+    }
+
+    /// Replays the feed up to `live`'s checkpoint into a scratch store and diffs the rows.
+    pub async fn compare<TStore>(&self, live: &TStore) -> anyhow::Result<ShadowReport>
+    where
+        TStore: ProjectionStore<ListTimeEntriesState>,
+    {
+        let (checkpoint, live_state) = snapshot(live).await?;
+        let scratch = InMemoryProjectionStore::<ListTimeEntriesState>::new();
+        // Archived rows are left out of the replay as they are of the live rows.
+        let archived = ListTimeEntriesState {
+            archived: live_state.archived.clone(),
+            ..ListTimeEntriesState::default()
+        };
+        scratch.save(archived, 0).await?;
+        let (technical_tx, _) = broadcast::channel(1);
+        let mut projector = ListTimeEntriesProjector::new(
+            format!("{}:shadow", self.name),
+            scratch.clone(),
+            self.event_store.clone(),
+            technical_tx,
+        );
+        if let Some(partition) = self.partition {
+            projector = projector.with_partition(partition);
+        }
+        let replayed = projector.replay_until(checkpoint).await?;
+        if replayed < checkpoint {
+            anyhow::bail!(
+                "live checkpoint {checkpoint} is ahead of the event store ({replayed} events)"
+            );
+        }
+        let shadow_state = scratch.state().await?.unwrap_or_default();
+        Ok(diff(checkpoint, live_state, shadow_state))
+    }
+}
+
+/// The live state with the checkpoint it reflects, read twice over when a projector moved it
+/// in between.
+async fn snapshot<TStore>(live: &TStore) -> anyhow::Result<(u64, ListTimeEntriesState)>
+where
+    TStore: ProjectionStore<ListTimeEntriesState>,
+{
+    for _ in 0..SNAPSHOT_ATTEMPTS {
+        let before = live.checkpoint().await?;
+        let state = live.state().await?.unwrap_or_default();
+        if live.checkpoint().await? == before {
+            return Ok((before, state));
+        }
+    }
+    anyhow::bail!("live read model kept moving while it was read")
+}
+
+fn diff(
+    checkpoint: u64,
+    mut live: ListTimeEntriesState,
+    mut shadow: ListTimeEntriesState,
+) -> ShadowReport {
+    let ids: BTreeSet<String> = live
+        .rows
+        .keys()
+        .chain(shadow.rows.keys())
+        .cloned()
+        .collect();
+    let divergences = ids
+        .iter()
+        .filter_map(|time_entry_id| {
+            match (
+                live.rows.remove(time_entry_id),
+                shadow.rows.remove(time_entry_id),
+            ) {
+                (Some(live), None) => Some(Divergence::Unexpected { live }),
+                (None, Some(shadow)) => Some(Divergence::Missing { shadow }),
+                (Some(live), Some(shadow)) if live != shadow => Some(Divergence::Differs {
+                    live: Box::new(live),
+                    shadow: Box::new(shadow),
+                }),
+                _ => None,
+            }
+        })
+        .collect();
+    ShadowReport {
+        checkpoint,
+        rows_compared: ids.len(),
+        divergences,
+    }
+}
+
+#[cfg(test)]
+mod shadow_tests {
+    use super::*;
+    use crate::modules::time_entries::use_cases::set_ended_at::handler::SetEndedAtHandler;
+    use crate::modules::time_entries::use_cases::set_started_at::handler::SetStartedAtHandler;
+    use crate::shared::infrastructure::event_store::StoredEvent;
+    use crate::shared::infrastructure::event_store::in_memory::InMemoryEventStore;
+    use crate::shared::infrastructure::intent_outbox::in_memory::InMemoryDomainOutbox;
+    use crate::shared::infrastructure::projection_store::partitioned::PartitionedProjectionStore;
+    use crate::tests::fixtures::commands::set_ended_at::SetEndedAtBuilder;
+    use crate::tests::fixtures::commands::set_started_at::SetStartedAtBuilder;
+    use rstest::rstest;
+
+    async fn register(event_store: &InMemoryEventStore<TimeEntryEvent>, time_entry_id: &str) {
+        let outbox = InMemoryDomainOutbox::new();
+        let stream_id = format!("TimeEntry-{time_entry_id}");
+        SetStartedAtHandler::new(event_store.clone(), outbox.clone())
+            .handle(
+                &stream_id,
+                SetStartedAtBuilder::new()
+                    .time_entry_id(time_entry_id.to_string())
+                    .build(),
+            )
+            .await
+            .unwrap();
+        SetEndedAtHandler::new(event_store.clone(), outbox)
+            .handle(
+                &stream_id,
+                SetEndedAtBuilder::new()
+                    .time_entry_id(time_entry_id.to_string())
+                    .build(),
+            )
+            .await
+            .unwrap();
+    }
+
+    /// Builds the live read model the way a projector does at startup: a full rebuild.
+    async fn project(
+        event_store: &InMemoryEventStore<TimeEntryEvent>,
+        store: InMemoryProjectionStore<ListTimeEntriesState>,
+        partition: Option<Partition>,
+    ) {
+        let (tech_tx, _) = broadcast::channel(16);
+        let (closed_tx, receiver) = broadcast::channel::<StoredEvent<TimeEntryEvent>>(1);
+        drop(closed_tx);
+        let mut projector =
+            ListTimeEntriesProjector::new("live", store, event_store.clone(), tech_tx);
+        if let Some(partition) = partition {
+            projector = projector.with_partition(partition);
+        }
+        projector.run(receiver).await;
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_report_no_divergences_when_replay_matches_the_live_rows() {
+        let event_store = InMemoryEventStore::<TimeEntryEvent>::new();
+        register(&event_store, "te-1").await;
+        register(&event_store, "te-2").await;
+        let live = InMemoryProjectionStore::new();
+        project(&event_store, live.clone(), None).await;
+
+        let report = ShadowProjector::new("list_time_entries", event_store)
+            .compare(&live)
+            .await
+            .unwrap();
+
+        assert!(report.is_clean(), "{report:?}");
+        assert_eq!(report.checkpoint, 8);
+        assert_eq!(report.rows_compared, 2);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_report_each_kind_of_divergence() {
+        let event_store = InMemoryEventStore::<TimeEntryEvent>::new();
+        register(&event_store, "te-1").await;
+        register(&event_store, "te-2").await;
+        let live = InMemoryProjectionStore::new();
+        project(&event_store, live.clone(), None).await;
+        let mut state = live.state().await.unwrap().unwrap();
+        let checkpoint = live.checkpoint().await.unwrap();
+        let missing = state.rows.remove("te-1").unwrap();
+        let replayed = state.rows["te-2"].clone();
+        let mut changed = replayed.clone();
+        changed.started_at = Some(-1);
+        state.rows.insert("te-2".to_string(), changed.clone());
+        let mut unexpected = replayed.clone();
+        unexpected.time_entry_id = "te-3".to_string();
+        state.rows.insert("te-3".to_string(), unexpected.clone());
+        live.save(state, checkpoint).await.unwrap();
+
+        let report = ShadowProjector::new("list_time_entries", event_store)
+            .compare(&live)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            report.divergences,
+            vec![
+                Divergence::Missing { shadow: missing },
+                Divergence::Differs {
+                    live: Box::new(changed),
+                    shadow: Box::new(replayed),
+                },
+                Divergence::Unexpected { live: unexpected },
+            ]
+        );
+        assert_eq!(report.rows_compared, 3);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_only_replay_up_to_the_live_checkpoint() {
+        let event_store = InMemoryEventStore::<TimeEntryEvent>::new();
+        register(&event_store, "te-1").await;
+        let live = InMemoryProjectionStore::new();
+        project(&event_store, live.clone(), None).await;
+        register(&event_store, "te-2").await;
+
+        let report = ShadowProjector::new("list_time_entries", event_store)
+            .compare(&live)
+            .await
+            .unwrap();
+
+        assert!(report.is_clean(), "{report:?}");
+        assert_eq!(report.checkpoint, 4);
+        assert_eq!(report.rows_compared, 1);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_compare_a_partition_against_its_own_store() {
+        let event_store = InMemoryEventStore::<TimeEntryEvent>::new();
+        for index in 0..6 {
+            register(&event_store, &format!("te-{index}")).await;
+        }
+        let live = PartitionedProjectionStore::new(2, |_| InMemoryProjectionStore::new());
+
+        for (partition, store) in live.partitions() {
+            project(&event_store, store.clone(), Some(partition)).await;
+            let report = ShadowProjector::new("list_time_entries", event_store.clone())
+                .with_partition(partition)
+                .compare(&store)
+                .await
+                .unwrap();
+            assert!(report.is_clean(), "{report:?}");
+        }
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_fail_when_the_live_checkpoint_is_ahead_of_the_event_store() {
+        let live = InMemoryProjectionStore::<ListTimeEntriesState>::new();
+        live.save(ListTimeEntriesState::default(), 5).await.unwrap();
+
+        let result = ShadowProjector::new("list_time_entries", InMemoryEventStore::new())
+            .compare(&live)
+            .await;
+
+        assert!(result.is_err());
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_fail_when_the_live_store_is_offline() {
+        let mut live = InMemoryProjectionStore::<ListTimeEntriesState>::new();
+        live.toggle_offline();
+
+        let result = ShadowProjector::new("list_time_entries", InMemoryEventStore::new())
+            .compare(&live)
+            .await;
+
+        assert!(result.is_err());
+    }
+}
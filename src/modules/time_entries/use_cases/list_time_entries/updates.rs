@@ -0,0 +1,139 @@
+use async_graphql::futures_util::stream::{self, Stream};
+use tokio::sync::broadcast;
+
+use crate::modules::time_entries::use_cases::list_time_entries::projection::TimeEntryView;
+
+/// Updates buffered per subscriber before the slowest one starts missing them.
+pub const DEFAULT_CAPACITY: usize = 1024;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TimeEntryUpdate {
+    /// The row as the list now reads it.
+    Upserted(Box<TimeEntryView>),
+    /// The subscriber fell behind and this many updates were dropped; refetch the list.
+    Missed(u64),
+}
+
+/// Rows the list projector wrote, fanned out to live subscribers: the SSE stream and the
+/// GraphQL subscription both read from here.
+#[derive(Clone)]
+pub struct TimeEntryUpdates {
+    sender: broadcast::Sender<TimeEntryView>,
+}
+
+impl TimeEntryUpdates {
+    pub fn new(capacity: usize) -> Self {
+        let (sender, _) = broadcast::channel(capacity);
+        Self { sender }
+    }
+
+    /// Publishes `row` to current subscribers; a no-op when nobody listens.
+    pub fn publish(&self, row: TimeEntryView) {
+        let _ = self.sender.send(row);
+    }
+
+    /// Updates to `user_id`'s rows, from now on. Ends when every publisher is gone.
+    pub fn subscribe(&self, user_id: &str) -> impl Stream<Item = TimeEntryUpdate> + Send + use<> {
+        let receiver = self.sender.subscribe();
+        stream::unfold(
+            (receiver, user_id.to_string()),
+            |(mut receiver, user_id)| async move {
+                loop {
+                    let update = match receiver.recv().await {
+                        Ok(row) if row.user_id == user_id => {
+                            TimeEntryUpdate::Upserted(Box::new(row))
+                        }
+                        Ok(_) => continue,
+                        Err(broadcast::error::RecvError::Lagged(missed)) => {
+                            TimeEntryUpdate::Missed(missed)
+                        }
+                        Err(broadcast::error::RecvError::Closed) => return None,
+                    };
+                    return Some((update, (receiver, user_id)));
+                }
+            },
+        )
+    }
+}
+
+impl Default for TimeEntryUpdates {
+    fn default() -> Self {
+        Self::new(DEFAULT_CAPACITY)
+    }
+}
+
+#[cfg(test)]
+mod time_entry_updates_tests {
+    use super::*;
+    use crate::modules::time_entries::use_cases::list_time_entries::projection::TimeEntryStatus;
+    use async_graphql::futures_util::StreamExt;
+    use rstest::rstest;
+
+    fn view(time_entry_id: &str, user_id: &str) -> TimeEntryView {
+        TimeEntryView {
+            time_entry_id: time_entry_id.to_string(),
+            user_id: user_id.to_string(),
+            started_at: Some(1_000),
+            ended_at: None,
+            tag_ids: vec![],
+            status: TimeEntryStatus::Draft,
+            created_at: 0,
+            created_by: user_id.to_string(),
+            updated_at: 0,
+            updated_by: user_id.to_string(),
+            deleted_at: None,
+            hourly_rate_cents: None,
+            currency: None,
+            amount_cents: None,
+            breaks: vec![],
+            net_duration_ms: None,
+        }
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_stream_only_the_subscribers_rows() {
+        let updates = TimeEntryUpdates::default();
+        let mut stream = Box::pin(updates.subscribe("user-1"));
+
+        updates.publish(view("te-1", "user-2"));
+        updates.publish(view("te-2", "user-1"));
+
+        assert_eq!(
+            stream.next().await,
+            Some(TimeEntryUpdate::Upserted(Box::new(view("te-2", "user-1"))))
+        );
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_report_dropped_updates_to_lagging_subscribers() {
+        let updates = TimeEntryUpdates::new(2);
+        let mut stream = Box::pin(updates.subscribe("user-1"));
+
+        for id in ["te-1", "te-2", "te-3"] {
+            updates.publish(view(id, "user-1"));
+        }
+
+        assert_eq!(stream.next().await, Some(TimeEntryUpdate::Missed(1)));
+        assert_eq!(
+            stream.next().await,
+            Some(TimeEntryUpdate::Upserted(Box::new(view("te-2", "user-1"))))
+        );
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_end_when_the_publisher_is_gone() {
+        let updates = TimeEntryUpdates::default();
+        let stream = updates.subscribe("user-1");
+        drop(updates);
+
+        assert_eq!(stream.collect::<Vec<_>>().await, vec![]);
+    }
+
+    #[rstest]
+    fn it_should_ignore_publishing_without_subscribers() {
+        TimeEntryUpdates::default().publish(view("te-1", "user-1"));
+    }
+}
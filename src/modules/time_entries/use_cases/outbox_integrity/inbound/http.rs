@@ -0,0 +1,320 @@
+use axum::{Json, extract::State, http::StatusCode, response::IntoResponse};
+use std::collections::HashSet;
+
+use crate::modules::time_entries::core::events::TimeEntryEvent;
+use crate::modules::time_entries::core::intents::TimeEntryIntent;
+use crate::shared::application::outbox_integrity::check_outbox_integrity;
+use crate::shared::infrastructure::request_context::RequestContext;
+use crate::shell::state::AppState;
+
+/// GET /admin/outbox/integrity — cross-checks every time entry event against the outbox:
+/// `missing_rows` are intents that were decided but never enqueued, `orphan_rows` are rows no
+/// event accounts for. Reads the whole event log, so it is meant for after an incident rather
+/// than for polling. Rows of compacted streams are left out: the events they were enqueued
+/// for are in cold storage.
+pub async fn handle(
+    State(state): State<AppState>,
+    request_ctx: RequestContext,
+) -> impl IntoResponse {
+    if !request_ctx.principal().can_administer() {
+        return StatusCode::FORBIDDEN.into_response();
+    }
+    let Ok(events) = state.event_store.load_all_from(0).await else {
+        return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+    };
+    let compacted: HashSet<&str> = events
+        .iter()
+        .filter(|stored| matches!(stored.event, TimeEntryEvent::TimeEntryCompactedV1(_)))
+        .map(|stored| stored.stream_id.as_str())
+        .collect();
+    let rows: Vec<_> = state
+        .outbox
+        .rows()
+        .await
+        .into_iter()
+        .filter(|row| !compacted.contains(row.stream_id.as_str()))
+        .collect();
+    Json(check_outbox_integrity(
+        &events,
+        &rows,
+        TimeEntryIntent::expected_for,
+    ))
+    .into_response()
+}
+
+#[cfg(test)]
+mod outbox_integrity_http_inbound_tests {
+    use super::*;
+    use crate::modules::time_entries::core::events::v1::time_entry_compacted::TimeEntryCompactedV1;
+    use crate::modules::time_entries::core::events::v1::time_entry_tags_set::TimeEntryTagsSetV1;
+    use crate::modules::time_entries::core::tag::Tag;
+    use crate::modules::time_entries::use_cases::auto_stop_timers::command::AutoStopTimer;
+    use crate::modules::time_entries::use_cases::auto_stop_timers::handler::AutoStopTimerHandler;
+    use crate::modules::time_entries::use_cases::set_ended_at::command::SetEndedAt;
+    use crate::modules::time_entries::use_cases::set_hourly_rate::command::SetHourlyRate;
+    use crate::modules::time_entries::use_cases::set_started_at::command::SetStartedAt;
+    use crate::modules::time_entries::use_cases::set_time_entry_tags::command::SetTimeEntryTags;
+    use crate::shared::infrastructure::event_store::EventStore;
+    use crate::shared::infrastructure::intent_outbox::{DomainOutbox, OutboxRow, OutboxStatus};
+    use crate::tests::fixtures::tags::{make_test_app_state, make_test_app_state_with_store};
+    use axum::{Router, body::Body, http::Request, routing::get};
+    use http_body_util::BodyExt;
+    use rstest::rstest;
+    use serde_json::{Value, json};
+    use tower::ServiceExt;
+
+    async fn check(state: AppState, role: &str) -> (StatusCode, Value) {
+        let response = Router::new()
+            .route("/admin/outbox/integrity", get(handle))
+            .with_state(state)
+            .oneshot(
+                Request::get("/admin/outbox/integrity")
+                    .header("x-user-id", "admin-1")
+                    .header("x-tenant-id", "tenant-test")
+                    .header("x-user-role", role)
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        let status = response.status();
+        let bytes = response.into_body().collect().await.unwrap().to_bytes();
+        (
+            status,
+            serde_json::from_slice(&bytes).unwrap_or(Value::Null),
+        )
+    }
+
+    /// Runs every use case that enqueues an intent, through the real handlers.
+    async fn make_history() -> AppState {
+        let state = make_test_app_state();
+        let (registered, timer) = ("TimeEntry-te-1", "TimeEntry-te-2");
+        state
+            .set_started_at_handler
+            .handle(
+                registered,
+                SetStartedAt {
+                    time_entry_id: "te-1".into(),
+                    user_id: "u-1".into(),
+                    started_at: 1_000,
+                    updated_at: 1_000,
+                    updated_by: "u-1".into(),
+                    rounding: None,
+                },
+            )
+            .await
+            .unwrap();
+        state
+            .set_ended_at_handler
+            .handle(
+                registered,
+                SetEndedAt {
+                    time_entry_id: "te-1".into(),
+                    user_id: "u-1".into(),
+                    ended_at: 61_000,
+                    updated_at: 2_000,
+                    updated_by: "u-1".into(),
+                    rounding: None,
+                },
+            )
+            .await
+            .unwrap();
+        state
+            .set_time_entry_tags_handler
+            .handle(
+                registered,
+                SetTimeEntryTags {
+                    time_entry_id: "te-1".into(),
+                    user_id: "u-1".into(),
+                    tag_ids: vec![Tag::parse("dev").unwrap()],
+                    updated_at: 3_000,
+                    updated_by: "u-1".into(),
+                },
+            )
+            .await
+            .unwrap();
+        state
+            .set_hourly_rate_handler
+            .handle(
+                registered,
+                SetHourlyRate {
+                    time_entry_id: "te-1".into(),
+                    user_id: "u-1".into(),
+                    hourly_rate_cents: 5_000,
+                    currency: "EUR".to_string(),
+                    updated_at: 4_000,
+                    updated_by: "u-1".into(),
+                },
+            )
+            .await
+            .unwrap();
+        state
+            .set_started_at_handler
+            .handle(
+                timer,
+                SetStartedAt {
+                    time_entry_id: "te-2".into(),
+                    user_id: "u-1".into(),
+                    started_at: 100_000,
+                    updated_at: 100_000,
+                    updated_by: "u-1".into(),
+                    rounding: None,
+                },
+            )
+            .await
+            .unwrap();
+        AutoStopTimerHandler::new(state.event_store.clone(), state.outbox.clone())
+            .handle(
+                timer,
+                AutoStopTimer {
+                    time_entry_id: "te-2".into(),
+                    max_duration_ms: 60_000,
+                    reason: "too long".to_string(),
+                    stopped_at: 200_000,
+                },
+            )
+            .await
+            .unwrap();
+        state
+    }
+
+    fn row(stream_id: &str, stream_version: i64, event_type: &str) -> OutboxRow {
+        OutboxRow {
+            topic: "time-entries".to_string(),
+            event_type: event_type.to_string(),
+            event_version: 1,
+            stream_id: stream_id.to_string(),
+            stream_version,
+            intent_no: 0,
+            occurred_at: 0,
+            payload: Value::Null,
+            content_encoding: None,
+            status: OutboxStatus::Pending,
+            attempts: 0,
+            last_error: None,
+            published_at: None,
+        }
+    }
+
+    /// Guards `TimeEntryIntent::expected_for` against drifting from the deciders.
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_find_an_intent_for_every_row_the_handlers_enqueue() {
+        let state = make_history().await;
+        let rows = state.outbox.rows().await;
+
+        let (status, body) = check(state, "admin").await;
+
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["missing_rows"], json!([]), "{body}");
+        assert_eq!(body["orphan_rows"], json!([]), "{body}");
+        assert_eq!(body["rows_checked"], 4);
+        assert!(rows.iter().any(|row| row.event_type == "TimerAutoStopped"));
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_report_dropped_intents_and_stray_rows() {
+        let state = make_history().await;
+        let version = state
+            .event_store
+            .load("TimeEntry-te-1")
+            .await
+            .unwrap()
+            .version;
+        // Appended around the handlers, as a crash between append and enqueue would leave it.
+        state
+            .event_store
+            .append(
+                "TimeEntry-te-1",
+                version,
+                &[TimeEntryEvent::TimeEntryTagsSetV1(TimeEntryTagsSetV1 {
+                    time_entry_id: "te-1".into(),
+                    tag_ids: vec![],
+                    updated_at: 5_000,
+                    updated_by: "u-1".into(),
+                })],
+            )
+            .await
+            .unwrap();
+        state
+            .outbox
+            .enqueue(row("TimeEntry-te-9", 1, "TimeEntryTagsSet"))
+            .await
+            .unwrap();
+
+        let (_, body) = check(state, "admin").await;
+
+        assert_eq!(
+            body["missing_rows"],
+            json!([{
+                "stream_id": "TimeEntry-te-1",
+                "stream_version": version + 1,
+                "expected_type": "TimeEntryTagsSet",
+            }])
+        );
+        assert_eq!(
+            body["orphan_rows"],
+            json!([{
+                "stream_id": "TimeEntry-te-9",
+                "stream_version": 1,
+                "event_type": "TimeEntryTagsSet",
+                "expected_type": null,
+            }])
+        );
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_leave_out_the_rows_of_compacted_streams() {
+        let state = make_history().await;
+        let version = state
+            .event_store
+            .load("TimeEntry-te-1")
+            .await
+            .unwrap()
+            .version;
+        state
+            .event_store
+            .compact(
+                "TimeEntry-te-1",
+                version,
+                TimeEntryEvent::TimeEntryCompactedV1(TimeEntryCompactedV1 {
+                    time_entry_id: "te-1".into(),
+                    user_id: "u-1".into(),
+                    deleted_at: 5_000,
+                    deleted_by: "u-1".into(),
+                    compacted_at: 6_000,
+                    compacted_version: version,
+                    archive_key: "compacted/TimeEntry-te-1.jsonl".to_string(),
+                }),
+            )
+            .await
+            .unwrap();
+
+        let (_, body) = check(state, "admin").await;
+
+        assert_eq!(body["orphan_rows"], json!([]), "{body}");
+        assert_eq!(body["missing_rows"], json!([]), "{body}");
+        assert_eq!(body["rows_checked"], 1);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_forbid_non_admins() {
+        let (status, _) = check(make_test_app_state(), "manager").await;
+
+        assert_eq!(status, StatusCode::FORBIDDEN);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_fail_while_the_event_store_is_offline() {
+        let (state, event_store) = make_test_app_state_with_store();
+        event_store.toggle_offline();
+
+        let (status, _) = check(state, "admin").await;
+
+        assert_eq!(status, StatusCode::INTERNAL_SERVER_ERROR);
+    }
+}
@@ -0,0 +1,18 @@
+use crate::modules::time_entries::core::time_interval::TimeInterval;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LockPeriod {
+    pub lock_id: String,
+    /// `None` locks the period for every user.
+    pub user_id: Option<String>,
+    pub period: TimeInterval,
+    pub locked_at: i64,
+    pub locked_by: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnlockPeriod {
+    pub lock_id: String,
+    pub unlocked_at: i64,
+    pub unlocked_by: String,
+}
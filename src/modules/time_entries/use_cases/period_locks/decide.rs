@@ -0,0 +1,175 @@
+use crate::modules::time_entries::core::period_locks::{
+    PeriodLockError, PeriodLockedV1, PeriodLocks, PeriodLocksEvent, PeriodUnlockedV1,
+    evolve_period_locks,
+};
+use crate::modules::time_entries::use_cases::period_locks::command::{LockPeriod, UnlockPeriod};
+use crate::shared::core::decider::{Decider, Decision};
+use std::convert::Infallible;
+
+type PeriodLocksDecision = Decision<PeriodLocksEvent, Infallible, PeriodLockError>;
+
+fn rejected(reason: PeriodLockError) -> PeriodLocksDecision {
+    Decision::Rejected { reason }
+}
+
+fn accepted(event: PeriodLocksEvent) -> PeriodLocksDecision {
+    Decision::Accepted {
+        events: vec![event],
+        intents: vec![],
+    }
+}
+
+pub fn decide_lock_period(state: &PeriodLocks, command: LockPeriod) -> PeriodLocksDecision {
+    if state.locks.contains_key(&command.lock_id) {
+        return rejected(PeriodLockError::AlreadyLocked {
+            lock_id: command.lock_id,
+        });
+    }
+    accepted(PeriodLocksEvent::PeriodLockedV1(PeriodLockedV1 {
+        lock_id: command.lock_id,
+        user_id: command.user_id,
+        from: command.period.start().as_millis(),
+        to: command.period.end().as_millis(),
+        locked_at: command.locked_at,
+        locked_by: command.locked_by,
+    }))
+}
+
+pub fn decide_unlock_period(state: &PeriodLocks, command: UnlockPeriod) -> PeriodLocksDecision {
+    if !state.locks.contains_key(&command.lock_id) {
+        return rejected(PeriodLockError::LockNotFound {
+            lock_id: command.lock_id,
+        });
+    }
+    accepted(PeriodLocksEvent::PeriodUnlockedV1(PeriodUnlockedV1 {
+        lock_id: command.lock_id,
+        unlocked_at: command.unlocked_at,
+        unlocked_by: command.unlocked_by,
+    }))
+}
+
+pub struct LockPeriodDecider;
+
+impl Decider for LockPeriodDecider {
+    type State = PeriodLocks;
+    type Command = LockPeriod;
+    type Event = PeriodLocksEvent;
+    type Intent = Infallible;
+    type Error = PeriodLockError;
+
+    fn initial_state() -> PeriodLocks {
+        PeriodLocks::default()
+    }
+
+    fn evolve(state: PeriodLocks, event: PeriodLocksEvent) -> PeriodLocks {
+        evolve_period_locks(state, event)
+    }
+
+    fn decide(state: &PeriodLocks, command: LockPeriod) -> PeriodLocksDecision {
+        decide_lock_period(state, command)
+    }
+}
+
+pub struct UnlockPeriodDecider;
+
+impl Decider for UnlockPeriodDecider {
+    type State = PeriodLocks;
+    type Command = UnlockPeriod;
+    type Event = PeriodLocksEvent;
+    type Intent = Infallible;
+    type Error = PeriodLockError;
+
+    fn initial_state() -> PeriodLocks {
+        PeriodLocks::default()
+    }
+
+    fn evolve(state: PeriodLocks, event: PeriodLocksEvent) -> PeriodLocks {
+        evolve_period_locks(state, event)
+    }
+
+    fn decide(state: &PeriodLocks, command: UnlockPeriod) -> PeriodLocksDecision {
+        decide_unlock_period(state, command)
+    }
+}
+
+#[cfg(test)]
+mod decide_period_locks_tests {
+    use super::*;
+    use crate::modules::time_entries::core::time_interval::TimeInterval;
+    use rstest::{fixture, rstest};
+
+    #[fixture]
+    fn lock() -> LockPeriod {
+        LockPeriod {
+            lock_id: "lock-1".to_string(),
+            user_id: None,
+            period: TimeInterval::new(100, 200).unwrap(),
+            locked_at: 1_000,
+            locked_by: "admin-1".to_string(),
+        }
+    }
+
+    fn unlock(lock_id: &str) -> UnlockPeriod {
+        UnlockPeriod {
+            lock_id: lock_id.to_string(),
+            unlocked_at: 2_000,
+            unlocked_by: "admin-1".to_string(),
+        }
+    }
+
+    fn rejection(decision: PeriodLocksDecision) -> PeriodLockError {
+        match decision {
+            Decision::Rejected { reason } => reason,
+            Decision::Accepted { .. } => panic!("expected Rejected"),
+        }
+    }
+
+    #[rstest]
+    fn it_should_lock_and_unlock_a_period(lock: LockPeriod) {
+        let Decision::Accepted { events, .. } =
+            LockPeriodDecider::decide(&PeriodLocks::default(), lock)
+        else {
+            panic!("expected Accepted");
+        };
+        let state = LockPeriodDecider::fold(events);
+        assert!(state.locks.contains_key("lock-1"));
+
+        let Decision::Accepted { events, .. } =
+            UnlockPeriodDecider::decide(&state, unlock("lock-1"))
+        else {
+            panic!("expected Accepted");
+        };
+        let state = events.into_iter().fold(state, UnlockPeriodDecider::evolve);
+        assert_eq!(state, UnlockPeriodDecider::initial_state());
+    }
+
+    #[rstest]
+    fn it_should_reject_a_duplicate_lock_id(lock: LockPeriod) {
+        let Decision::Accepted { events, .. } =
+            decide_lock_period(&PeriodLocks::default(), lock.clone())
+        else {
+            panic!("expected Accepted");
+        };
+        let state = LockPeriodDecider::fold(events);
+
+        assert_eq!(
+            rejection(decide_lock_period(&state, lock)),
+            PeriodLockError::AlreadyLocked {
+                lock_id: "lock-1".to_string()
+            }
+        );
+    }
+
+    #[rstest]
+    fn it_should_reject_unlocking_an_unknown_lock() {
+        assert_eq!(
+            rejection(decide_unlock_period(
+                &PeriodLocks::default(),
+                unlock("lock-9")
+            )),
+            PeriodLockError::LockNotFound {
+                lock_id: "lock-9".to_string()
+            }
+        );
+    }
+}
@@ -0,0 +1,124 @@
+use crate::modules::time_entries::core::period_locks::{
+    PERIOD_LOCKS_STREAM_ID, PeriodLockError, PeriodLocks, PeriodLocksEvent, evolve_period_locks,
+};
+use crate::modules::time_entries::use_cases::period_locks::command::{LockPeriod, UnlockPeriod};
+use crate::modules::time_entries::use_cases::period_locks::decide::{
+    LockPeriodDecider, UnlockPeriodDecider,
+};
+use crate::shared::application::event_sourced_handler::{
+    EventSourcedError, EventSourcedHandler, NoIntents,
+};
+use crate::shared::infrastructure::event_store::{EventStore, EventStoreError};
+use std::convert::Infallible;
+
+pub type ApplicationError = EventSourcedError<PeriodLockError, Infallible>;
+
+/// The active period locks, folded from the `PeriodLocks` stream.
+pub async fn load_period_locks<TEventStore>(
+    event_store: &TEventStore,
+) -> Result<PeriodLocks, EventStoreError>
+where
+    TEventStore: EventStore<PeriodLocksEvent> + ?Sized,
+{
+    let stream = event_store.load(PERIOD_LOCKS_STREAM_ID).await?;
+    Ok(stream
+        .events
+        .into_iter()
+        .fold(PeriodLocks::default(), evolve_period_locks))
+}
+
+#[derive(Debug, Clone)]
+pub struct PeriodLocksHandler<TEventStore>
+where
+    TEventStore: EventStore<PeriodLocksEvent> + Clone + Send + Sync + 'static,
+{
+    event_store: TEventStore,
+    lock: EventSourcedHandler<LockPeriodDecider, TEventStore, NoIntents>,
+    unlock: EventSourcedHandler<UnlockPeriodDecider, TEventStore, NoIntents>,
+}
+
+impl<TEventStore> PeriodLocksHandler<TEventStore>
+where
+    TEventStore: EventStore<PeriodLocksEvent> + Clone + Send + Sync + 'static,
+{
+    pub fn new(event_store: TEventStore) -> Self {
+        Self {
+            lock: EventSourcedHandler::new(event_store.clone(), NoIntents),
+            unlock: EventSourcedHandler::new(event_store.clone(), NoIntents),
+            event_store,
+        }
+    }
+
+    pub async fn lock(&self, command: LockPeriod) -> Result<(), ApplicationError> {
+        self.lock.handle(PERIOD_LOCKS_STREAM_ID, command).await
+    }
+
+    pub async fn unlock(&self, command: UnlockPeriod) -> Result<(), ApplicationError> {
+        self.unlock.handle(PERIOD_LOCKS_STREAM_ID, command).await
+    }
+
+    pub async fn locks(&self) -> Result<PeriodLocks, EventStoreError> {
+        load_period_locks(&self.event_store).await
+    }
+}
+
+#[cfg(test)]
+mod period_locks_handler_tests {
+    use super::*;
+    use crate::modules::time_entries::core::time_interval::TimeInterval;
+    use crate::shared::infrastructure::event_store::in_memory::InMemoryEventStore;
+    use rstest::rstest;
+
+    fn lock(lock_id: &str) -> LockPeriod {
+        LockPeriod {
+            lock_id: lock_id.to_string(),
+            user_id: Some("u-1".to_string()),
+            period: TimeInterval::new(100, 200).unwrap(),
+            locked_at: 1_000,
+            locked_by: "admin-1".to_string(),
+        }
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_lock_list_and_unlock_periods() {
+        let handler = PeriodLocksHandler::new(InMemoryEventStore::<PeriodLocksEvent>::new());
+
+        handler.lock(lock("lock-1")).await.unwrap();
+        handler.lock(lock("lock-2")).await.unwrap();
+        handler
+            .unlock(UnlockPeriod {
+                lock_id: "lock-1".to_string(),
+                unlocked_at: 2_000,
+                unlocked_by: "admin-1".to_string(),
+            })
+            .await
+            .unwrap();
+
+        let locks = handler.locks().await.unwrap();
+        assert_eq!(locks.locks.keys().collect::<Vec<_>>(), vec!["lock-2"]);
+        assert_eq!(locks.locks["lock-2"].user_id.as_deref(), Some("u-1"));
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_surface_rejections_and_store_failures() {
+        let event_store = InMemoryEventStore::<PeriodLocksEvent>::new();
+        let handler = PeriodLocksHandler::new(event_store.clone());
+        handler.lock(lock("lock-1")).await.unwrap();
+
+        assert!(matches!(
+            handler.lock(lock("lock-1")).await,
+            Err(ApplicationError::Domain(
+                PeriodLockError::AlreadyLocked { .. }
+            ))
+        ));
+
+        event_store.toggle_offline();
+        assert!(handler.locks().await.is_err());
+        assert!(matches!(
+            handler.lock(lock("lock-2")).await,
+            Err(ApplicationError::VersionConflict(_))
+        ));
+    }
+}
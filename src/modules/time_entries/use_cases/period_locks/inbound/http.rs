@@ -0,0 +1,317 @@
+use axum::{
+    Json,
+    extract::{Path, State, rejection::JsonRejection},
+    http::StatusCode,
+    response::IntoResponse,
+};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::modules::time_entries::core::period_locks::PeriodLock;
+use crate::modules::time_entries::core::time_interval::TimeInterval;
+use crate::modules::time_entries::use_cases::period_locks::command::{LockPeriod, UnlockPeriod};
+use crate::modules::time_entries::use_cases::period_locks::handler::ApplicationError;
+use crate::shared::infrastructure::request_context::RequestContext;
+use crate::shell::state::AppState;
+
+#[derive(Deserialize)]
+pub struct LockPeriodBody {
+    pub lock_id: Option<String>,
+    /// Omit to lock the period for every user.
+    pub user_id: Option<String>,
+    pub from: i64,
+    pub to: i64,
+}
+
+#[derive(Serialize)]
+pub struct LockPeriodResponse {
+    pub lock_id: String,
+}
+
+/// POST /period-locks — locks `[from, to)` for one user (managers and admins) or for
+/// everyone (admins only).
+pub async fn handle_lock(
+    State(state): State<AppState>,
+    request_ctx: RequestContext,
+    body: Result<Json<LockPeriodBody>, JsonRejection>,
+) -> impl IntoResponse {
+    let Json(body) = match body {
+        Ok(b) => b,
+        Err(_) => return StatusCode::UNPROCESSABLE_ENTITY.into_response(),
+    };
+    let principal = request_ctx.principal();
+    let allowed = match &body.user_id {
+        Some(user_id) => principal.can_approve(user_id),
+        None => principal.can_administer(),
+    };
+    if !allowed {
+        return StatusCode::FORBIDDEN.into_response();
+    }
+
+    let Ok(period) = TimeInterval::new(body.from, body.to) else {
+        return StatusCode::UNPROCESSABLE_ENTITY.into_response();
+    };
+    let lock_id = body.lock_id.unwrap_or_else(|| Uuid::now_v7().to_string());
+    let command = LockPeriod {
+        lock_id: lock_id.clone(),
+        user_id: body.user_id,
+        period,
+        locked_at: Utc::now().timestamp_millis(),
+        locked_by: request_ctx.user_id,
+    };
+
+    match state.period_locks_handler.lock(command).await {
+        Ok(()) => (StatusCode::CREATED, Json(LockPeriodResponse { lock_id })).into_response(),
+        Err(ApplicationError::Domain(_)) => StatusCode::CONFLICT.into_response(),
+        Err(_) => StatusCode::INTERNAL_SERVER_ERROR.into_response(),
+    }
+}
+
+/// DELETE /period-locks/{lock_id} — reopens a locked period. Admins only.
+pub async fn handle_unlock(
+    State(state): State<AppState>,
+    request_ctx: RequestContext,
+    Path(lock_id): Path<String>,
+) -> impl IntoResponse {
+    if !request_ctx.principal().can_administer() {
+        return StatusCode::FORBIDDEN.into_response();
+    }
+    let command = UnlockPeriod {
+        lock_id,
+        unlocked_at: Utc::now().timestamp_millis(),
+        unlocked_by: request_ctx.user_id,
+    };
+
+    match state.period_locks_handler.unlock(command).await {
+        Ok(()) => StatusCode::NO_CONTENT.into_response(),
+        Err(ApplicationError::Domain(_)) => StatusCode::NOT_FOUND.into_response(),
+        Err(_) => StatusCode::INTERNAL_SERVER_ERROR.into_response(),
+    }
+}
+
+/// GET /period-locks — the active locks the caller may see: everyone's locks and those of
+/// users they can view.
+pub async fn handle_list(
+    State(state): State<AppState>,
+    request_ctx: RequestContext,
+) -> impl IntoResponse {
+    let principal = request_ctx.principal();
+    if !principal.can_view_user(&request_ctx.user_id) {
+        return StatusCode::FORBIDDEN.into_response();
+    }
+    match state.period_locks_handler.locks().await {
+        Ok(locks) => {
+            let visible: Vec<PeriodLock> = locks
+                .locks
+                .into_values()
+                .filter(|lock| {
+                    lock.user_id
+                        .as_deref()
+                        .is_none_or(|user_id| principal.can_view_user(user_id))
+                })
+                .collect();
+            Json(visible).into_response()
+        }
+        Err(_) => StatusCode::INTERNAL_SERVER_ERROR.into_response(),
+    }
+}
+
+#[cfg(test)]
+mod period_locks_http_inbound_tests {
+    use axum::{
+        Router,
+        body::Body,
+        http::{Request, StatusCode},
+        routing::{delete, get, post},
+    };
+    use http_body_util::BodyExt;
+    use rstest::rstest;
+    use tower::ServiceExt;
+
+    use super::{handle_list, handle_lock, handle_unlock};
+    use crate::shared::auth::rbac::ApiKeyScope;
+    use crate::shared::infrastructure::api_key_store::ApiKey;
+    use crate::shell::state::AppState;
+    use crate::tests::fixtures::tags::make_test_app_state;
+
+    fn app(state: AppState) -> Router {
+        Router::new()
+            .route("/period-locks", post(handle_lock))
+            .route("/period-locks", get(handle_list))
+            .route("/period-locks/{lock_id}", delete(handle_unlock))
+            .with_state(state)
+    }
+
+    fn request(method: &str, uri: &str, user_id: &str, role: &str, body: &str) -> Request<Body> {
+        Request::builder()
+            .method(method)
+            .uri(uri)
+            .header("content-type", "application/json")
+            .header("x-user-id", user_id)
+            .header("x-tenant-id", "tenant-test")
+            .header("x-user-role", role)
+            .body(Body::from(body.to_string()))
+            .unwrap()
+    }
+
+    async fn send(state: &AppState, request: Request<Body>) -> (StatusCode, serde_json::Value) {
+        let response = app(state.clone()).oneshot(request).await.unwrap();
+        let status = response.status();
+        let bytes = response.into_body().collect().await.unwrap().to_bytes();
+        (
+            status,
+            serde_json::from_slice(&bytes).unwrap_or(serde_json::Value::Null),
+        )
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_lock_list_and_unlock_periods() {
+        let state = make_test_app_state();
+        let (status, body) = send(
+            &state,
+            request(
+                "POST",
+                "/period-locks",
+                "admin-1",
+                "admin",
+                r#"{"from":100,"to":200}"#,
+            ),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CREATED);
+        let lock_id = body["lock_id"].as_str().unwrap().to_string();
+        let (status, _) = send(
+            &state,
+            request(
+                "POST",
+                "/period-locks",
+                "manager-1",
+                "manager",
+                r#"{"lock_id":"lock-u1","user_id":"u-1","from":300,"to":400}"#,
+            ),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CREATED);
+
+        let (status, body) = send(
+            &state,
+            request("GET", "/period-locks", "u-2", "employee", ""),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body.as_array().unwrap().len(), 1);
+        let (_, body) = send(
+            &state,
+            request("GET", "/period-locks", "u-1", "employee", ""),
+        )
+        .await;
+        assert_eq!(body.as_array().unwrap().len(), 2);
+
+        let (status, _) = send(
+            &state,
+            request(
+                "DELETE",
+                &format!("/period-locks/{lock_id}"),
+                "admin-1",
+                "admin",
+                "",
+            ),
+        )
+        .await;
+        assert_eq!(status, StatusCode::NO_CONTENT);
+        let (_, body) = send(
+            &state,
+            request("GET", "/period-locks", "admin-1", "admin", ""),
+        )
+        .await;
+        assert_eq!(body[0]["lock_id"], "lock-u1");
+    }
+
+    #[rstest]
+    #[case::tenant_lock_by_manager("manager-1", "manager", r#"{"from":100,"to":200}"#)]
+    #[case::own_lock_by_manager(
+        "manager-1",
+        "manager",
+        r#"{"user_id":"manager-1","from":100,"to":200}"#
+    )]
+    #[case::employee("u-1", "employee", r#"{"user_id":"u-2","from":100,"to":200}"#)]
+    #[tokio::test]
+    async fn it_should_forbid_locks_outside_the_callers_rights(
+        #[case] user_id: &str,
+        #[case] role: &str,
+        #[case] body: &str,
+    ) {
+        let state = make_test_app_state();
+        let (status, _) = send(
+            &state,
+            request("POST", "/period-locks", user_id, role, body),
+        )
+        .await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_map_rejections_to_status_codes() {
+        let state = make_test_app_state();
+        let admin =
+            |method: &str, uri: &str, body: &str| request(method, uri, "admin-1", "admin", body);
+
+        let (status, _) = send(&state, admin("POST", "/period-locks", "not json")).await;
+        assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+        let (status, _) = send(
+            &state,
+            admin("POST", "/period-locks", r#"{"from":200,"to":100}"#),
+        )
+        .await;
+        assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+        let lock = r#"{"lock_id":"lock-1","from":100,"to":200}"#;
+        send(&state, admin("POST", "/period-locks", lock)).await;
+        let (status, _) = send(&state, admin("POST", "/period-locks", lock)).await;
+        assert_eq!(status, StatusCode::CONFLICT);
+        let (status, _) = send(&state, admin("DELETE", "/period-locks/lock-9", "")).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        let (status, _) = send(
+            &state,
+            request("DELETE", "/period-locks/lock-1", "manager-1", "manager", ""),
+        )
+        .await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_return_500_when_the_lock_store_is_offline() {
+        let state = make_test_app_state();
+        state.period_lock_store.toggle_offline();
+        let admin =
+            |method: &str, uri: &str, body: &str| request(method, uri, "admin-1", "admin", body);
+
+        let (status, _) = send(
+            &state,
+            admin("POST", "/period-locks", r#"{"from":100,"to":200}"#),
+        )
+        .await;
+        assert_eq!(status, StatusCode::INTERNAL_SERVER_ERROR);
+        let (status, _) = send(&state, admin("DELETE", "/period-locks/lock-1", "")).await;
+        assert_eq!(status, StatusCode::INTERNAL_SERVER_ERROR);
+        let (status, _) = send(&state, admin("GET", "/period-locks", "")).await;
+        assert_eq!(status, StatusCode::INTERNAL_SERVER_ERROR);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_forbid_listing_for_register_only_keys() {
+        let state = make_test_app_state();
+        let mut key_request = request("GET", "/period-locks", "u-1", "employee", "");
+        key_request.extensions_mut().insert(ApiKey {
+            user_id: "ci-bot".to_string(),
+            tenant_id: "tenant-test".to_string(),
+            scope: ApiKeyScope::RegisterOnly,
+        });
+        let (status, _) = send(&state, key_request).await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+    }
+}
@@ -0,0 +1,389 @@
+// Registers a finished entry in one mutation: sets its start and its end, which is what
+// registers it, and answers with what clients would otherwise query for right after.
+//
+// The entry is read back from the list projection, which catches up with the event store
+// asynchronously. It is only returned once its row covers the writes of this mutation, so
+// clients never get a stale snapshot; `timeEntry` is null until then and clients fall back
+// to querying it.
+
+use async_graphql::{Context, InputObject, Object, Result as GqlResult, SimpleObject};
+
+use crate::modules::time_entries::core::day_capacity::CapacityWarning;
+use crate::modules::time_entries::use_cases::list_time_entries::inbound::graphql::{
+    GqlSimilarEntry, GqlTimeEntry,
+};
+use crate::modules::time_entries::use_cases::set_ended_at::command::SetEndedAt;
+use crate::modules::time_entries::use_cases::set_started_at::command::SetStartedAt;
+use crate::shared::core::primitives::TimeEntryId;
+use crate::shared::infrastructure::event_store::EventStore;
+use crate::shared::infrastructure::request_context::RequestContext;
+use crate::shell::state::AppState;
+
+#[derive(InputObject)]
+pub struct RegisterTimeEntryInput {
+    pub time_entry_id: String,
+    pub started_at: i64,
+    pub ended_at: i64,
+    /// Echoed in the payload, for clients matching responses to queued mutations.
+    pub client_mutation_id: Option<String>,
+}
+
+#[derive(SimpleObject)]
+#[graphql(name = "RegisterTimeEntryPayload")]
+pub struct GqlRegisterTimeEntryPayload {
+    pub time_entry_id: String,
+    /// The entry as lists show it; null while the lists have not caught up with this
+    /// registration yet.
+    pub time_entry: Option<GqlTimeEntry>,
+    /// Other entries of the user this one likely duplicates, found before registering.
+    pub warnings: Vec<GqlSimilarEntry>,
+    /// Days this entry takes over what the user may register on one day, when the server
+    /// warns about them instead of refusing the entry.
+    pub capacity_warnings: Vec<GqlCapacityWarning>,
+    pub client_mutation_id: Option<String>,
+}
+
+#[derive(SimpleObject)]
+#[graphql(name = "CapacityWarning")]
+pub struct GqlCapacityWarning {
+    /// The UTC day, as YYYY-MM-DD.
+    pub date: String,
+    /// The user's time registered on the day, this entry included.
+    pub total_ms: i64,
+    pub limit_ms: i64,
+}
+
+impl From<CapacityWarning> for GqlCapacityWarning {
+    fn from(warning: CapacityWarning) -> Self {
+        Self {
+            date: warning.date.to_string(),
+            total_ms: warning.total_ms,
+            limit_ms: warning.limit_ms,
+        }
+    }
+}
+
+#[cfg(test)]
+mod register_time_entry_graphql_inbound_tests {
+    use async_graphql::{EmptySubscription, Schema};
+    use chrono::NaiveDate;
+    use std::sync::Arc;
+
+    use crate::modules::time_entries::core::day_capacity::{CapacityPolicy, DayCapacity, DayTotal};
+
+    use crate::modules::time_entries::use_cases::list_time_entries::projection::{
+        ListTimeEntriesState, TimeEntryRow, TimeEntryStatus,
+    };
+    use crate::modules::time_entries::use_cases::list_time_entries::queries::ListTimeEntriesQueryHandler;
+    use crate::modules::time_entries::use_cases::user_time_entries::day_totals::{
+        DayTotalsError, DayTotalsPort,
+    };
+    use crate::shared::auth::rbac::Scope;
+    use crate::shared::core::stream_naming::{DefaultStreamNaming, StreamNaming};
+    use crate::shared::infrastructure::event_store::EventStore;
+    use crate::shared::infrastructure::projection_store::ProjectionStore;
+    use crate::shared::infrastructure::projection_store::in_memory::InMemoryProjectionStore;
+    use crate::shared::infrastructure::projection_store::partitioned::PartitionedProjectionStore;
+    use crate::shared::infrastructure::request_context::RequestContext;
+    use crate::shell::graphql::{MutationRoot, QueryRoot};
+    use crate::shell::state::AppState;
+    use crate::tests::fixtures::tags::make_test_app_state;
+
+    fn make_schema_from_state(
+        state: AppState,
+    ) -> Schema<QueryRoot, MutationRoot, EmptySubscription> {
+        Schema::build(
+            QueryRoot::default(),
+            MutationRoot::default(),
+            EmptySubscription,
+        )
+        .data(state)
+        .finish()
+    }
+
+    fn req_ctx() -> RequestContext {
+        RequestContext {
+            user_id: "u-1".to_string(),
+            tenant_id: "tenant-test".to_string(),
+            role: Default::default(),
+            scope: Default::default(),
+        }
+    }
+
+    fn row(
+        time_entry_id: &str,
+        started_at: i64,
+        ended_at: i64,
+        last_event_id: &str,
+    ) -> TimeEntryRow {
+        TimeEntryRow {
+            time_entry_id: time_entry_id.to_string(),
+            user_id: "u-1".to_string(),
+            started_at: Some(started_at),
+            ended_at: Some(ended_at),
+            tag_ids: vec![],
+            status: TimeEntryStatus::Registered,
+            created_at: 0,
+            created_by: "u-1".to_string(),
+            updated_at: 0,
+            updated_by: "u-1".to_string(),
+            deleted_at: None,
+            hourly_rate: None,
+            last_event_id: Some(last_event_id.to_string()),
+            breaks: vec![],
+        }
+    }
+
+    async fn state_with_rows(rows: Vec<TimeEntryRow>) -> AppState {
+        let mut state = make_test_app_state();
+        let store = InMemoryProjectionStore::<ListTimeEntriesState>::new();
+        let mut projection = ListTimeEntriesState::default();
+        for row in rows {
+            projection.rows.insert(row.time_entry_id.clone(), row);
+        }
+        store.save(projection, 1).await.unwrap();
+        state.list_time_entries_handler =
+            ListTimeEntriesQueryHandler::new(PartitionedProjectionStore::single(store));
+        state
+    }
+
+    fn register(te_id: &str, started_at: i64, ended_at: i64) -> String {
+        format!(
+            r#"mutation {{ registerTimeEntry(input: {{ timeEntryId: "{te_id}", startedAt: {started_at}, endedAt: {ended_at}, clientMutationId: "m-1" }}) {{ timeEntryId timeEntry {{ startedAt endedAt }} warnings {{ reason timeEntry {{ timeEntryId }} }} clientMutationId }} }}"#
+        )
+    }
+
+    #[tokio::test]
+    async fn registers_the_entry_and_echoes_the_client_mutation_id() {
+        let te_id = uuid::Uuid::now_v7().to_string();
+        let state = make_test_app_state();
+        let schema = make_schema_from_state(state.clone());
+
+        let result = schema
+            .execute(async_graphql::Request::new(register(&te_id, 1_000, 2_000)).data(req_ctx()))
+            .await;
+
+        assert!(result.errors.is_empty(), "{:?}", result.errors);
+        assert_eq!(
+            result.data.to_string(),
+            format!(
+                r#"{{registerTimeEntry: {{timeEntryId: "{te_id}", timeEntry: null, warnings: [], clientMutationId: "m-1"}}}}"#
+            )
+        );
+        let stream_id = DefaultStreamNaming.time_entry(None, &te_id);
+        assert!(state.event_store.load(&stream_id).await.unwrap().version > 0);
+    }
+
+    #[tokio::test]
+    async fn returns_the_entry_once_the_lists_caught_up() {
+        let te_id = uuid::Uuid::now_v7().to_string();
+        let stream_id = DefaultStreamNaming.time_entry(None, &te_id);
+        let state =
+            state_with_rows(vec![row(&te_id, 1_000, 2_000, &format!("{stream_id}:99"))]).await;
+
+        let result = make_schema_from_state(state)
+            .execute(async_graphql::Request::new(register(&te_id, 1_000, 2_000)).data(req_ctx()))
+            .await;
+
+        assert!(result.errors.is_empty(), "{:?}", result.errors);
+        let json = result.data.into_json().unwrap();
+        assert_eq!(
+            json["registerTimeEntry"]["timeEntry"],
+            serde_json::json!({ "startedAt": 1_000, "endedAt": 2_000 })
+        );
+        assert_eq!(json["registerTimeEntry"]["warnings"], serde_json::json!([]));
+    }
+
+    #[tokio::test]
+    async fn warns_about_overlapping_entries() {
+        let te_id = uuid::Uuid::now_v7().to_string();
+        let state =
+            state_with_rows(vec![row("te-other", 1_500, 3_000, "TimeEntry-te-other:4")]).await;
+
+        let result = make_schema_from_state(state)
+            .execute(async_graphql::Request::new(register(&te_id, 1_000, 2_000)).data(req_ctx()))
+            .await;
+
+        assert!(result.errors.is_empty(), "{:?}", result.errors);
+        let json = result.data.into_json().unwrap();
+        assert_eq!(
+            json["registerTimeEntry"]["warnings"],
+            serde_json::json!([{ "reason": "OVERLAPPING", "timeEntry": { "timeEntryId": "te-other" } }])
+        );
+    }
+
+    /// A user whose first of January already holds a full day.
+    struct FullDay;
+
+    #[async_trait::async_trait]
+    impl DayTotalsPort for FullDay {
+        async fn day_totals(
+            &self,
+            _user_id: &str,
+            from: NaiveDate,
+            _to: NaiveDate,
+            _time_entry_id: &str,
+        ) -> Result<Vec<DayTotal>, DayTotalsError> {
+            Ok(vec![DayTotal {
+                date: from,
+                registered_ms: 24 * 60 * 60 * 1000,
+                contracted_ms: None,
+            }])
+        }
+    }
+
+    #[tokio::test]
+    async fn warns_about_days_over_capacity() {
+        let te_id = uuid::Uuid::now_v7().to_string();
+        let mut state = make_test_app_state();
+        let capacity = DayCapacity {
+            policy: CapacityPolicy::Warn,
+            ..DayCapacity::default()
+        };
+        state.set_ended_at_handler = state
+            .set_ended_at_handler
+            .with_day_capacity(Arc::new(FullDay), capacity);
+
+        let result = make_schema_from_state(state)
+            .execute(
+                async_graphql::Request::new(format!(
+                    r#"mutation {{ registerTimeEntry(input: {{ timeEntryId: "{te_id}", startedAt: 1000, endedAt: 2000 }}) {{ capacityWarnings {{ date totalMs limitMs }} }} }}"#
+                ))
+                .data(req_ctx()),
+            )
+            .await;
+
+        assert!(result.errors.is_empty(), "{:?}", result.errors);
+        assert_eq!(
+            result.data.into_json().unwrap()["registerTimeEntry"]["capacityWarnings"],
+            serde_json::json!([{ "date": "1970-01-01", "totalMs": 86_401_000, "limitMs": 86_400_000 }])
+        );
+    }
+
+    #[tokio::test]
+    async fn rejects_invalid_input_without_registering() {
+        let te_id = uuid::Uuid::now_v7().to_string();
+        let schema = make_schema_from_state(make_test_app_state());
+
+        let not_v7 = schema
+            .execute(
+                async_graphql::Request::new(register("not-a-uuid", 1_000, 2_000)).data(req_ctx()),
+            )
+            .await;
+        let backwards = schema
+            .execute(async_graphql::Request::new(register(&te_id, 2_000, 1_000)).data(req_ctx()))
+            .await;
+
+        assert_eq!(
+            not_v7.errors[0].message,
+            "time_entry_id must be a valid UUID v7"
+        );
+        assert_eq!(
+            backwards.errors[0].message,
+            "endedAt must be after startedAt"
+        );
+    }
+
+    #[tokio::test]
+    async fn returns_forbidden_for_read_only_api_keys() {
+        let te_id = uuid::Uuid::now_v7().to_string();
+        let result = make_schema_from_state(make_test_app_state())
+            .execute(
+                async_graphql::Request::new(register(&te_id, 1_000, 2_000)).data(RequestContext {
+                    scope: Scope::ReadOnly,
+                    ..req_ctx()
+                }),
+            )
+            .await;
+
+        assert_eq!(result.errors[0].message, "Forbidden");
+    }
+}
+
+#[derive(Default)]
+pub struct RegisterTimeEntryMutation;
+
+#[Object]
+impl RegisterTimeEntryMutation {
+    /// Starts and ends a time entry in one call, registering it. Answers with the entry as
+    /// lists show it, once they caught up, with the entries it likely duplicates and with the
+    /// days it takes over capacity.
+    async fn register_time_entry(
+        &self,
+        context: &Context<'_>,
+        input: RegisterTimeEntryInput,
+    ) -> GqlResult<GqlRegisterTimeEntryPayload> {
+        let time_entry_id = TimeEntryId::parse_v7(&input.time_entry_id)
+            .map_err(|_| async_graphql::Error::new("time_entry_id must be a valid UUID v7"))?;
+        if input.ended_at <= input.started_at {
+            return Err(async_graphql::Error::new("endedAt must be after startedAt"));
+        }
+
+        let req_ctx = context
+            .data::<RequestContext>()
+            .map_err(|_| async_graphql::Error::new("Unauthorized"))?;
+        if !req_ctx.principal().can_register_for(&req_ctx.user_id) {
+            return Err(async_graphql::Error::new("Forbidden"));
+        }
+        let state = context.data_unchecked::<AppState>();
+        let stream_id = state
+            .stream_naming
+            .time_entry(Some(&req_ctx.tenant_id), &time_entry_id);
+        let queries = &state.list_time_entries_handler;
+
+        let warnings = queries
+            .find_similar_entries(&req_ctx.user_id, input.started_at, input.ended_at, &[])
+            .await?
+            .into_iter()
+            .filter(|similar| similar.time_entry.time_entry_id != input.time_entry_id)
+            .map(Into::into)
+            .collect();
+
+        let mut capacity_warnings = state
+            .set_started_at_handler
+            .handle_for_tenant_with_warnings(
+                &req_ctx.tenant_id,
+                &stream_id,
+                SetStartedAt::new(
+                    time_entry_id.clone(),
+                    req_ctx.user_id.clone().into(),
+                    input.started_at,
+                ),
+            )
+            .await
+            .map_err(|e| async_graphql::Error::new(e.to_string()))?;
+        capacity_warnings.extend(
+            state
+                .set_ended_at_handler
+                .handle_for_tenant_with_warnings(
+                    &req_ctx.tenant_id,
+                    &stream_id,
+                    SetEndedAt::new(
+                        time_entry_id,
+                        req_ctx.user_id.clone().into(),
+                        input.ended_at,
+                    ),
+                )
+                .await
+                .map_err(|e| async_graphql::Error::new(e.to_string()))?,
+        );
+
+        let version = state
+            .event_store
+            .load(&stream_id)
+            .await
+            .map_err(|e| async_graphql::Error::new(e.to_string()))?
+            .version;
+        let time_entry = queries
+            .get_at_version(&req_ctx.user_id, &input.time_entry_id, version)
+            .await?;
+
+        Ok(GqlRegisterTimeEntryPayload {
+            time_entry_id: input.time_entry_id,
+            time_entry: time_entry.map(Into::into),
+            warnings,
+            capacity_warnings: capacity_warnings.into_iter().map(Into::into).collect(),
+            client_mutation_id: input.client_mutation_id,
+        })
+    }
+}
@@ -0,0 +1,10 @@
+use crate::shared::auth::rbac::Principal;
+use crate::shared::core::primitives::TimeEntryId;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RejectTimeEntry {
+    pub time_entry_id: TimeEntryId,
+    pub reviewer: Principal,
+    pub rejected_at: i64,
+    pub reason: Option<String>,
+}
@@ -0,0 +1,177 @@
+use crate::modules::time_entries::core::events::TimeEntryEvent;
+use crate::modules::time_entries::core::events::v1::time_entry_rejected::TimeEntryRejectedV1;
+use crate::modules::time_entries::core::evolve::evolve;
+use crate::modules::time_entries::core::intents::TimeEntryIntent;
+use crate::modules::time_entries::core::state::TimeEntryState;
+use crate::modules::time_entries::use_cases::reject_time_entry::command::RejectTimeEntry;
+use crate::modules::time_entries::use_cases::reject_time_entry::decision::{DecideError, Decision};
+use crate::shared::core::decider::Decider;
+
+/// Sends a registered entry back to its owner. It stays registered, so the owner can correct
+/// it and a manager can approve it later; an approved entry can no longer be rejected.
+pub fn decide_reject_time_entry(state: &TimeEntryState, command: RejectTimeEntry) -> Decision {
+    let rejected = |reason| Decision::Rejected { reason };
+    let user_id = match state {
+        TimeEntryState::None | TimeEntryState::Deleted { .. } => {
+            return rejected(DecideError::NotFound);
+        }
+        TimeEntryState::Draft { user_id, .. }
+        | TimeEntryState::Registered { user_id, .. }
+        | TimeEntryState::Approved { user_id, .. } => user_id,
+    };
+    // Rejecting takes the same rights as approving, checked first for the same reason.
+    if !command.reviewer.can_approve(user_id.as_str()) {
+        return rejected(DecideError::Forbidden);
+    }
+
+    match state {
+        TimeEntryState::Registered { .. } => Decision::Accepted {
+            events: vec![TimeEntryEvent::TimeEntryRejectedV1(TimeEntryRejectedV1 {
+                time_entry_id: command.time_entry_id,
+                rejected_at: command.rejected_at,
+                rejected_by: command.reviewer.user_id.into(),
+                reason: command.reason,
+            })],
+            intents: vec![],
+        },
+        TimeEntryState::Approved { .. } => rejected(DecideError::AlreadyApproved),
+        _ => rejected(DecideError::NotRegistered),
+    }
+}
+
+pub struct RejectTimeEntryDecider;
+
+impl Decider for RejectTimeEntryDecider {
+    type State = TimeEntryState;
+    type Command = RejectTimeEntry;
+    type Event = TimeEntryEvent;
+    type Intent = TimeEntryIntent;
+    type Error = DecideError;
+
+    fn initial_state() -> TimeEntryState {
+        TimeEntryState::None
+    }
+
+    fn evolve(state: TimeEntryState, event: TimeEntryEvent) -> TimeEntryState {
+        evolve(state, event)
+    }
+
+    fn decide(state: &TimeEntryState, command: RejectTimeEntry) -> Decision {
+        decide_reject_time_entry(state, command)
+    }
+}
+
+#[cfg(test)]
+mod decide_reject_time_entry_tests {
+    use super::*;
+    use crate::modules::time_entries::core::time_interval::TimeInterval;
+    use crate::shared::auth::rbac::{Principal, Role, Scope};
+    use crate::tests::fixtures::commands::reject_time_entry::RejectTimeEntryBuilder;
+    use rstest::{fixture, rstest};
+
+    #[fixture]
+    fn command() -> RejectTimeEntry {
+        RejectTimeEntryBuilder::new().build()
+    }
+
+    fn draft() -> TimeEntryState {
+        TimeEntryState::Draft {
+            time_entry_id: "te-fixed-0001".into(),
+            user_id: "user-fixed-0001".into(),
+            started_at: Some(1_000),
+            ended_at: None,
+            tag_ids: vec![],
+            created_at: 0,
+            created_by: "user-fixed-0001".into(),
+            hourly_rate: None,
+        }
+    }
+
+    fn registered() -> TimeEntryState {
+        TimeEntryState::Registered {
+            time_entry_id: "te-fixed-0001".into(),
+            user_id: "user-fixed-0001".into(),
+            interval: TimeInterval::new(1_000, 2_000).unwrap(),
+            tag_ids: vec![],
+            created_at: 0,
+            created_by: "user-fixed-0001".into(),
+            hourly_rate: None,
+            breaks: vec![],
+        }
+    }
+
+    fn approved() -> TimeEntryState {
+        TimeEntryState::Approved {
+            time_entry_id: "te-fixed-0001".into(),
+            user_id: "user-fixed-0001".into(),
+            interval: TimeInterval::new(1_000, 2_000).unwrap(),
+            tag_ids: vec![],
+            created_at: 0,
+            created_by: "user-fixed-0001".into(),
+            hourly_rate: None,
+            approved_at: 3_000,
+            approved_by: "manager-fixed-0001".into(),
+            breaks: vec![],
+        }
+    }
+
+    #[rstest]
+    fn it_should_reject_a_registered_entry(command: RejectTimeEntry) {
+        match decide_reject_time_entry(&registered(), command) {
+            Decision::Accepted { events, intents } => {
+                assert_eq!(
+                    events,
+                    vec![TimeEntryEvent::TimeEntryRejectedV1(TimeEntryRejectedV1 {
+                        time_entry_id: "te-fixed-0001".into(),
+                        rejected_at: 1_700_000_000_000,
+                        rejected_by: "manager-fixed-0001".into(),
+                        reason: Some("Booked on the wrong project".to_string()),
+                    })]
+                );
+                assert!(intents.is_empty());
+            }
+            Decision::Rejected { .. } => panic!("expected Accepted"),
+        }
+    }
+
+    #[rstest]
+    fn it_should_keep_a_rejected_entry_registered(command: RejectTimeEntry) {
+        let Decision::Accepted { events, .. } = decide_reject_time_entry(&registered(), command)
+        else {
+            panic!("expected Accepted");
+        };
+
+        let state = events.into_iter().fold(registered(), evolve);
+
+        assert_eq!(state, registered());
+    }
+
+    #[rstest]
+    #[case::missing(TimeEntryState::None, DecideError::NotFound)]
+    #[case::draft(draft(), DecideError::NotRegistered)]
+    #[case::approved(approved(), DecideError::AlreadyApproved)]
+    fn it_should_refuse_entries_that_cannot_be_rejected(
+        command: RejectTimeEntry,
+        #[case] state: TimeEntryState,
+        #[case] expected: DecideError,
+    ) {
+        match decide_reject_time_entry(&state, command) {
+            Decision::Rejected { reason } => assert_eq!(reason, expected),
+            Decision::Accepted { .. } => panic!("expected Rejected"),
+        }
+    }
+
+    #[rstest]
+    #[case::own_entry(Principal::new("user-fixed-0001", Role::Manager))]
+    #[case::employee(Principal::new("employee-0001", Role::Employee))]
+    #[case::read_only(Principal::new("manager-fixed-0001", Role::Manager).with_scope(Scope::ReadOnly))]
+    fn it_should_refuse_reviewers_without_rights(#[case] reviewer: Principal) {
+        let command = RejectTimeEntryBuilder::new().reviewer(reviewer).build();
+        for state in [draft(), registered(), approved()] {
+            match decide_reject_time_entry(&state, command.clone()) {
+                Decision::Rejected { reason } => assert_eq!(reason, DecideError::Forbidden),
+                Decision::Accepted { .. } => panic!("expected Rejected"),
+            }
+        }
+    }
+}
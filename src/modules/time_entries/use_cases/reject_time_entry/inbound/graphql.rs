@@ -0,0 +1,343 @@
+use async_graphql::{Context, Enum, ID, Object, Result as GqlResult, SimpleObject};
+use chrono::Utc;
+use uuid::{Uuid, Version};
+
+use crate::modules::time_entries::use_cases::reject_time_entry::command::RejectTimeEntry;
+use crate::modules::time_entries::use_cases::reject_time_entry::decision::DecideError;
+use crate::modules::time_entries::use_cases::reject_time_entry::handler::ApplicationError;
+use crate::shared::infrastructure::request_context::RequestContext;
+use crate::shell::state::AppState;
+
+#[derive(Debug, Enum, Copy, Clone, Eq, PartialEq)]
+pub enum GqlRejectionStatus {
+    Rejected,
+    AlreadyApproved,
+    NotFound,
+    Forbidden,
+    NotRegistered,
+    /// The entry could not be processed, e.g. the event store was unavailable; safe to retry.
+    Failed,
+}
+
+#[derive(SimpleObject, Clone)]
+#[graphql(name = "RejectionResult")]
+pub struct GqlRejectionResult {
+    pub id: ID,
+    pub status: GqlRejectionStatus,
+    pub message: Option<String>,
+}
+
+impl GqlRejectionResult {
+    fn new(id: ID, status: GqlRejectionStatus) -> Self {
+        Self {
+            id,
+            status,
+            message: None,
+        }
+    }
+}
+
+impl From<DecideError> for GqlRejectionStatus {
+    fn from(reason: DecideError) -> Self {
+        match reason {
+            DecideError::NotFound => GqlRejectionStatus::NotFound,
+            DecideError::Forbidden => GqlRejectionStatus::Forbidden,
+            DecideError::NotRegistered => GqlRejectionStatus::NotRegistered,
+            DecideError::AlreadyApproved => GqlRejectionStatus::AlreadyApproved,
+        }
+    }
+}
+
+#[cfg(test)]
+mod reject_time_entries_graphql_inbound_tests {
+    use async_graphql::{EmptySubscription, Schema};
+
+    use crate::modules::time_entries::core::events::TimeEntryEvent;
+    use crate::modules::time_entries::use_cases::set_ended_at::command::SetEndedAt;
+    use crate::modules::time_entries::use_cases::set_started_at::command::SetStartedAt;
+    use crate::shared::auth::rbac::Role;
+    use crate::shared::core::stream_naming::{DefaultStreamNaming, StreamNaming};
+    use crate::shared::infrastructure::event_store::EventStore;
+    use crate::shared::infrastructure::request_context::RequestContext;
+    use crate::shell::graphql::{MutationRoot, QueryRoot};
+    use crate::shell::state::AppState;
+    use crate::tests::fixtures::tags::{make_test_app_state, make_test_app_state_with_store};
+
+    fn make_schema_from_state(
+        state: AppState,
+    ) -> Schema<QueryRoot, MutationRoot, EmptySubscription> {
+        Schema::build(
+            QueryRoot::default(),
+            MutationRoot::default(),
+            EmptySubscription,
+        )
+        .data(state)
+        .finish()
+    }
+
+    fn manager_ctx() -> RequestContext {
+        RequestContext {
+            user_id: "manager-1".to_string(),
+            tenant_id: "tenant-test".to_string(),
+            role: Role::Manager,
+            scope: Default::default(),
+        }
+    }
+
+    fn valid_v7_id() -> String {
+        uuid::Uuid::now_v7().to_string()
+    }
+
+    async fn set_interval(
+        state: &AppState,
+        user_id: &str,
+        started_at: i64,
+        ended_at: Option<i64>,
+    ) -> String {
+        let te_id = valid_v7_id();
+        let stream_id = DefaultStreamNaming.time_entry(None, &te_id);
+        state
+            .set_started_at_handler
+            .handle(
+                &stream_id,
+                SetStartedAt {
+                    time_entry_id: te_id.clone().into(),
+                    user_id: user_id.into(),
+                    started_at,
+                    updated_at: started_at,
+                    updated_by: user_id.into(),
+                    rounding: None,
+                },
+            )
+            .await
+            .unwrap();
+        if let Some(ended_at) = ended_at {
+            state
+                .set_ended_at_handler
+                .handle(
+                    &stream_id,
+                    SetEndedAt {
+                        time_entry_id: te_id.clone().into(),
+                        user_id: user_id.into(),
+                        ended_at,
+                        updated_at: ended_at,
+                        updated_by: user_id.into(),
+                        rounding: None,
+                    },
+                )
+                .await
+                .unwrap();
+        }
+        te_id
+    }
+
+    fn ids(ids: &[&str]) -> String {
+        ids.iter()
+            .map(|id| format!(r#""{id}""#))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    fn reject(te_ids: &[&str], reason: &str) -> String {
+        format!(
+            r#"mutation {{ rejectTimeEntries(ids: [{}], reason: "{reason}") {{ id status message }} }}"#,
+            ids(te_ids)
+        )
+    }
+
+    fn statuses(data: &serde_json::Value, field: &str) -> Vec<String> {
+        data[field]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|item| item["status"].as_str().unwrap().to_string())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn reports_the_outcome_of_each_entry() {
+        let state = make_test_app_state();
+        let registered = set_interval(&state, "u-1", 1_000, Some(2_000)).await;
+        let approved = set_interval(&state, "u-1", 2_500, Some(2_800)).await;
+        let draft = set_interval(&state, "u-1", 3_000, None).await;
+        let own = set_interval(&state, "manager-1", 5_000, Some(6_000)).await;
+        let missing = valid_v7_id();
+        let schema = make_schema_from_state(state);
+        let approval = format!(
+            "mutation {{ approveTimeEntries(ids: [{}]) {{ status }} }}",
+            ids(&[&approved])
+        );
+        schema
+            .execute(async_graphql::Request::new(approval).data(manager_ctx()))
+            .await;
+
+        let result = schema
+            .execute(
+                async_graphql::Request::new(reject(
+                    &[&registered, &approved, &draft, &own, &missing, "not-a-uuid"],
+                    "Booked on the wrong project",
+                ))
+                .data(manager_ctx()),
+            )
+            .await;
+
+        assert!(result.errors.is_empty(), "{:?}", result.errors);
+        let data = result.data.into_json().unwrap();
+        assert_eq!(
+            statuses(&data, "rejectTimeEntries"),
+            vec![
+                "REJECTED",
+                "ALREADY_APPROVED",
+                "NOT_REGISTERED",
+                "FORBIDDEN",
+                "NOT_FOUND",
+                "NOT_FOUND",
+            ]
+        );
+        assert_eq!(data["rejectTimeEntries"][0]["id"], registered.as_str());
+    }
+
+    #[tokio::test]
+    async fn records_the_reason_and_drops_a_blank_one() {
+        let (state, event_store) = make_test_app_state_with_store();
+        let with_reason = set_interval(&state, "u-1", 1_000, Some(2_000)).await;
+        let blank = set_interval(&state, "u-1", 3_000, Some(4_000)).await;
+        let schema = make_schema_from_state(state);
+
+        for (te_id, reason) in [
+            (&with_reason, " Booked on the wrong project "),
+            (&blank, "  "),
+        ] {
+            schema
+                .execute(async_graphql::Request::new(reject(&[te_id], reason)).data(manager_ctx()))
+                .await;
+        }
+
+        let mut reasons = Vec::new();
+        for te_id in [&with_reason, &blank] {
+            let stream = event_store
+                .load(&DefaultStreamNaming.time_entry(Some("tenant-test"), te_id))
+                .await
+                .unwrap();
+            match stream.events.last() {
+                Some(TimeEntryEvent::TimeEntryRejectedV1(event)) => {
+                    reasons.push(event.reason.clone())
+                }
+                other => panic!("expected TimeEntryRejectedV1, got {other:?}"),
+            }
+        }
+        assert_eq!(
+            reasons,
+            vec![Some("Booked on the wrong project".to_string()), None]
+        );
+    }
+
+    #[tokio::test]
+    async fn reports_every_entry_as_forbidden_for_employees() {
+        let state = make_test_app_state();
+        let registered = set_interval(&state, "u-1", 1_000, Some(2_000)).await;
+        let schema = make_schema_from_state(state);
+
+        let result = schema
+            .execute(
+                async_graphql::Request::new(reject(&[&registered], "no")).data(RequestContext {
+                    user_id: "u-2".to_string(),
+                    role: Role::Employee,
+                    ..manager_ctx()
+                }),
+            )
+            .await;
+
+        let data = result.data.into_json().unwrap();
+        assert_eq!(statuses(&data, "rejectTimeEntries"), vec!["FORBIDDEN"]);
+    }
+
+    #[tokio::test]
+    async fn reports_failed_entries_when_event_store_offline() {
+        let (state, event_store) = make_test_app_state_with_store();
+        let registered = set_interval(&state, "u-1", 1_000, Some(2_000)).await;
+        event_store.toggle_offline();
+        let schema = make_schema_from_state(state);
+
+        let result = schema
+            .execute(async_graphql::Request::new(reject(&[&registered], "no")).data(manager_ctx()))
+            .await;
+
+        assert!(result.errors.is_empty());
+        let data = result.data.into_json().unwrap();
+        assert_eq!(statuses(&data, "rejectTimeEntries"), vec!["FAILED"]);
+        assert!(data["rejectTimeEntries"][0]["message"].is_string());
+    }
+
+    #[tokio::test]
+    async fn returns_unauthorized_without_request_context() {
+        let schema = make_schema_from_state(make_test_app_state());
+
+        let result = schema
+            .execute(async_graphql::Request::new(reject(&[&valid_v7_id()], "no")))
+            .await;
+
+        assert_eq!(result.errors[0].message, "Unauthorized");
+    }
+}
+
+#[derive(Default)]
+pub struct RejectTimeEntriesMutation;
+
+#[Object]
+impl RejectTimeEntriesMutation {
+    /// Sends each entry back to its owner independently and reports a result per id, in
+    /// request order. `reason` is recorded on every rejected entry.
+    async fn reject_time_entries(
+        &self,
+        context: &Context<'_>,
+        ids: Vec<ID>,
+        reason: Option<String>,
+    ) -> GqlResult<Vec<GqlRejectionResult>> {
+        let req_ctx = context
+            .data::<RequestContext>()
+            .map_err(|_| async_graphql::Error::new("Unauthorized"))?;
+        let state = context.data_unchecked::<AppState>();
+        let reviewer = req_ctx.principal();
+        let reason = reason
+            .map(|reason| reason.trim().to_string())
+            .filter(|reason| !reason.is_empty());
+
+        let mut results = Vec::with_capacity(ids.len());
+        for id in ids {
+            let is_valid_v7 = Uuid::parse_str(&id)
+                .ok()
+                .filter(|u| u.get_version() == Some(Version::SortRand))
+                .is_some();
+            if !is_valid_v7 {
+                results.push(GqlRejectionResult::new(id, GqlRejectionStatus::NotFound));
+                continue;
+            }
+
+            let stream_id = state
+                .stream_naming
+                .time_entry(Some(&req_ctx.tenant_id), &id.as_str());
+            let command = RejectTimeEntry {
+                time_entry_id: id.0.clone().into(),
+                reviewer: reviewer.clone(),
+                rejected_at: Utc::now().timestamp_millis(),
+                reason: reason.clone(),
+            };
+            let result = match state
+                .reject_time_entry_handler
+                .handle(&stream_id, command)
+                .await
+            {
+                Ok(()) => GqlRejectionResult::new(id, GqlRejectionStatus::Rejected),
+                Err(ApplicationError::Domain(reason)) => GqlRejectionResult::new(id, reason.into()),
+                Err(error) => GqlRejectionResult {
+                    id,
+                    status: GqlRejectionStatus::Failed,
+                    message: Some(error.to_string()),
+                },
+            };
+            results.push(result);
+        }
+        Ok(results)
+    }
+}
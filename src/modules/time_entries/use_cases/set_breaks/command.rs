@@ -0,0 +1,36 @@
+use crate::modules::time_entries::core::breaks::Break;
+use crate::modules::time_entries::core::policies::RoundedCommand;
+use crate::shared::application::server_time::StampedCommand;
+use crate::shared::core::primitives::{TimeEntryId, Timestamp, UserId};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SetBreaks {
+    pub time_entry_id: TimeEntryId,
+    pub user_id: UserId,
+    /// Replaces the breaks the entry had; empty to clear them.
+    pub breaks: Vec<Break>,
+    /// Stamped by the handler from its clock.
+    pub updated_at: i64,
+    pub updated_by: UserId,
+}
+
+impl SetBreaks {
+    /// `user_id` sets the breaks of their own entry; the handler stamps `updated_at`.
+    pub fn new(time_entry_id: TimeEntryId, user_id: UserId, breaks: Vec<Break>) -> Self {
+        Self {
+            time_entry_id,
+            updated_by: user_id.clone(),
+            user_id,
+            breaks,
+            updated_at: 0,
+        }
+    }
+}
+
+impl StampedCommand for SetBreaks {
+    fn stamp(&mut self, now: Timestamp) {
+        self.updated_at = now.as_millis();
+    }
+}
+
+impl RoundedCommand for SetBreaks {}
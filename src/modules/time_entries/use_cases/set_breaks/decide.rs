@@ -0,0 +1,171 @@
+use crate::modules::time_entries::core::breaks::ensure_valid_breaks;
+use crate::modules::time_entries::core::events::TimeEntryEvent;
+use crate::modules::time_entries::core::events::v1::time_entry_breaks_set::TimeEntryBreaksSetV1;
+use crate::modules::time_entries::core::evolve::evolve;
+use crate::modules::time_entries::core::intents::TimeEntryIntent;
+use crate::modules::time_entries::core::state::TimeEntryState;
+use crate::modules::time_entries::use_cases::set_breaks::command::SetBreaks;
+use crate::modules::time_entries::use_cases::set_breaks::decision::{DecideError, Decision};
+use crate::shared::core::decider::Decider;
+
+pub fn decide_set_breaks(state: &TimeEntryState, command: SetBreaks) -> Decision {
+    match state {
+        TimeEntryState::None | TimeEntryState::Draft { .. } => Decision::Rejected {
+            reason: DecideError::NotRegistered,
+        },
+        TimeEntryState::Registered { interval, .. } => {
+            if let Err(reason) = ensure_valid_breaks(interval, &command.breaks) {
+                return Decision::Rejected {
+                    reason: reason.into(),
+                };
+            }
+            let mut breaks = command.breaks;
+            breaks.sort_by_key(|pause| pause.started_at);
+            Decision::Accepted {
+                events: vec![TimeEntryEvent::TimeEntryBreaksSetV1(TimeEntryBreaksSetV1 {
+                    time_entry_id: command.time_entry_id,
+                    breaks,
+                    updated_at: command.updated_at,
+                    updated_by: command.updated_by,
+                })],
+                intents: vec![],
+            }
+        }
+        TimeEntryState::Approved { .. } => Decision::Rejected {
+            reason: DecideError::Approved,
+        },
+        TimeEntryState::Deleted { .. } => Decision::Rejected {
+            reason: DecideError::Deleted,
+        },
+    }
+}
+
+pub struct SetBreaksDecider;
+
+impl Decider for SetBreaksDecider {
+    type State = TimeEntryState;
+    type Command = SetBreaks;
+    type Event = TimeEntryEvent;
+    type Intent = TimeEntryIntent;
+    type Error = DecideError;
+
+    fn initial_state() -> TimeEntryState {
+        TimeEntryState::None
+    }
+
+    fn evolve(state: TimeEntryState, event: TimeEntryEvent) -> TimeEntryState {
+        evolve(state, event)
+    }
+
+    fn decide(state: &TimeEntryState, command: SetBreaks) -> Decision {
+        decide_set_breaks(state, command)
+    }
+}
+
+#[cfg(test)]
+mod decide_set_breaks_tests {
+    use super::*;
+    use crate::modules::time_entries::core::breaks::{Break, BreakError};
+    use crate::modules::time_entries::core::time_interval::TimeInterval;
+    use crate::tests::fixtures::commands::set_breaks::SetBreaksBuilder;
+    use rstest::rstest;
+
+    fn pause(started_at: i64, ended_at: i64) -> Break {
+        Break {
+            started_at,
+            ended_at,
+        }
+    }
+
+    fn registered(command: &SetBreaks) -> TimeEntryState {
+        TimeEntryState::Registered {
+            time_entry_id: command.time_entry_id.clone(),
+            user_id: command.user_id.clone(),
+            interval: TimeInterval::new(1_000, 10_000).unwrap(),
+            breaks: vec![pause(2_000, 3_000)],
+            tag_ids: vec![],
+            created_at: 0,
+            created_by: command.updated_by.clone(),
+            hourly_rate: None,
+        }
+    }
+
+    #[rstest]
+    fn it_should_replace_the_breaks_in_order() {
+        let command = SetBreaksBuilder::new()
+            .breaks(vec![pause(6_000, 7_000), pause(4_000, 5_000)])
+            .build();
+
+        match decide_set_breaks(&registered(&command), command) {
+            Decision::Accepted { events, intents } => {
+                assert!(intents.is_empty());
+                match &events[..] {
+                    [TimeEntryEvent::TimeEntryBreaksSetV1(event)] => {
+                        assert_eq!(event.breaks, vec![pause(4_000, 5_000), pause(6_000, 7_000)])
+                    }
+                    other => panic!("expected TimeEntryBreaksSetV1, got {other:?}"),
+                }
+            }
+            Decision::Rejected { reason } => panic!("expected Accepted, got {reason}"),
+        }
+    }
+
+    #[rstest]
+    #[case::outside(vec![pause(500, 1_500)], BreakError::OutsideEntry)]
+    #[case::overlapping(vec![pause(4_000, 6_000), pause(5_000, 7_000)], BreakError::Overlapping)]
+    #[case::empty(vec![pause(4_000, 4_000)], BreakError::Empty)]
+    fn it_should_reject_invalid_breaks(#[case] breaks: Vec<Break>, #[case] expected: BreakError) {
+        let command = SetBreaksBuilder::new().breaks(breaks).build();
+
+        match decide_set_breaks(&registered(&command), command) {
+            Decision::Rejected { reason } => {
+                assert_eq!(reason, DecideError::InvalidBreaks(expected))
+            }
+            Decision::Accepted { .. } => panic!("expected Rejected"),
+        }
+    }
+
+    #[rstest]
+    fn it_should_reject_breaks_on_entries_without_both_ends() {
+        let command = SetBreaksBuilder::new().build();
+        let draft = TimeEntryState::Draft {
+            time_entry_id: command.time_entry_id.clone(),
+            user_id: command.user_id.clone(),
+            started_at: Some(1_000),
+            ended_at: None,
+            tag_ids: vec![],
+            created_at: 0,
+            created_by: command.updated_by.clone(),
+            hourly_rate: None,
+        };
+
+        for state in [TimeEntryState::None, draft] {
+            match decide_set_breaks(&state, command.clone()) {
+                Decision::Rejected { reason } => assert_eq!(reason, DecideError::NotRegistered),
+                Decision::Accepted { .. } => panic!("expected Rejected"),
+            }
+        }
+    }
+
+    #[rstest]
+    fn it_should_reject_changes_to_an_approved_entry() {
+        let command = SetBreaksBuilder::new().build();
+        let state = TimeEntryState::Approved {
+            time_entry_id: command.time_entry_id.clone(),
+            user_id: command.user_id.clone(),
+            interval: TimeInterval::new(1_000, 10_000).unwrap(),
+            breaks: vec![],
+            tag_ids: vec![],
+            created_at: 0,
+            created_by: command.updated_by.clone(),
+            hourly_rate: None,
+            approved_at: 11_000,
+            approved_by: "manager-0001".into(),
+        };
+
+        match decide_set_breaks(&state, command) {
+            Decision::Rejected { reason } => assert_eq!(reason, DecideError::Approved),
+            Decision::Accepted { .. } => panic!("expected Rejected"),
+        }
+    }
+}
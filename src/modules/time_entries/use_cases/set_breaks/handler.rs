@@ -0,0 +1,255 @@
+use crate::modules::time_entries::adapters::outbound::intent_outbox::TimeEntryIntentDispatcher;
+use crate::modules::time_entries::core::events::TimeEntryEvent;
+use crate::modules::time_entries::core::policies::Policies;
+use crate::modules::time_entries::use_cases::set_breaks::command::SetBreaks;
+use crate::modules::time_entries::use_cases::set_breaks::decide::SetBreaksDecider;
+use crate::modules::time_entries::use_cases::set_breaks::decision::DecideError;
+use crate::modules::time_entries::use_cases::user_time_entries::sharded_handler::{
+    PeriodLockStreams, UserShardedHandler,
+};
+use crate::shared::application::command_bus::CommandHandler;
+use crate::shared::application::event_sourced_handler::EventSourcedError;
+use crate::shared::infrastructure::clock::SharedClock;
+use crate::shared::infrastructure::event_bus::SharedEventBus;
+use crate::shared::infrastructure::event_store::EventStore;
+use crate::shared::infrastructure::intent_outbox::{DomainOutbox, OutboxError};
+use crate::shared::infrastructure::policy_store::SharedPolicyStore;
+use async_trait::async_trait;
+
+pub type ApplicationError = EventSourcedError<DecideError, OutboxError>;
+
+#[derive(Debug, Clone)]
+pub struct SetBreaksHandler<TEventStore, TOutbox>
+where
+    TEventStore: EventStore<TimeEntryEvent> + Send + Sync + 'static,
+    TOutbox: DomainOutbox + Send + Sync + 'static,
+{
+    inner: UserShardedHandler<SetBreaksDecider, TEventStore, TimeEntryIntentDispatcher<TOutbox>>,
+}
+
+impl<TEventStore, TOutbox> SetBreaksHandler<TEventStore, TOutbox>
+where
+    TEventStore: EventStore<TimeEntryEvent> + Send + Sync + 'static,
+    TOutbox: DomainOutbox + Send + Sync + 'static,
+{
+    pub fn new(event_store: TEventStore, outbox: TOutbox) -> Self {
+        Self {
+            inner: UserShardedHandler::new(event_store, TimeEntryIntentDispatcher::new(outbox)),
+        }
+    }
+
+    /// Refuse changes to entries inside a locked payroll period.
+    pub fn with_period_locks(mut self, period_locks: PeriodLockStreams) -> Self {
+        self.inner = self.inner.with_period_locks(period_locks);
+        self
+    }
+
+    /// Hold each tenant to the policies in `store`, or to `defaults` where it has none.
+    pub fn with_policies(mut self, store: SharedPolicyStore, defaults: Policies) -> Self {
+        self.inner = self.inner.with_policies(store, defaults);
+        self
+    }
+
+    /// Read the time commands are recorded at from `clock` instead of the system clock.
+    pub fn with_clock(mut self, clock: SharedClock) -> Self {
+        self.inner = self.inner.with_clock(clock);
+        self
+    }
+
+    /// Announce the appended events on `event_bus`, for the projectors to pick up.
+    pub fn with_event_bus(mut self, event_bus: SharedEventBus<TimeEntryEvent>) -> Self {
+        self.inner = self.inner.with_event_bus(event_bus);
+        self
+    }
+
+    pub async fn handle(
+        &self,
+        stream_id: &str,
+        command: SetBreaks,
+    ) -> Result<(), ApplicationError> {
+        self.inner.handle(stream_id, command).await
+    }
+
+    /// Handles the command under the policies of `tenant_id`.
+    pub async fn handle_for_tenant(
+        &self,
+        tenant_id: &str,
+        stream_id: &str,
+        command: SetBreaks,
+    ) -> Result<(), ApplicationError> {
+        self.inner
+            .handle_for_tenant(Some(tenant_id), stream_id, command)
+            .await
+    }
+}
+
+#[async_trait]
+impl<TEventStore, TOutbox> CommandHandler<SetBreaks> for SetBreaksHandler<TEventStore, TOutbox>
+where
+    TEventStore: EventStore<TimeEntryEvent> + Send + Sync + 'static,
+    TOutbox: DomainOutbox + Send + Sync + 'static,
+{
+    type Error = ApplicationError;
+
+    async fn handle(&self, stream_id: &str, command: SetBreaks) -> Result<(), ApplicationError> {
+        SetBreaksHandler::handle(self, stream_id, command).await
+    }
+
+    async fn handle_for_tenant(
+        &self,
+        tenant_id: &str,
+        stream_id: &str,
+        command: SetBreaks,
+    ) -> Result<(), ApplicationError> {
+        SetBreaksHandler::handle_for_tenant(self, tenant_id, stream_id, command).await
+    }
+}
+
+#[cfg(test)]
+mod set_breaks_handler_tests {
+    use crate::modules::time_entries::core::breaks::{Break, BreakError};
+    use crate::modules::time_entries::core::events::TimeEntryEvent;
+    use crate::modules::time_entries::core::evolve::evolve;
+    use crate::modules::time_entries::core::state::TimeEntryState;
+    use crate::modules::time_entries::use_cases::set_breaks::decision::DecideError;
+    use crate::modules::time_entries::use_cases::set_breaks::handler::{
+        ApplicationError, SetBreaksHandler,
+    };
+    use crate::modules::time_entries::use_cases::set_ended_at::handler::SetEndedAtHandler;
+    use crate::modules::time_entries::use_cases::set_started_at::handler::SetStartedAtHandler;
+    use crate::shared::application::command_bus::{CommandBus, CommandEnvelope};
+    use crate::shared::infrastructure::clock::FixedClock;
+    use crate::shared::infrastructure::event_store::in_memory::InMemoryEventStore;
+    use crate::shared::infrastructure::event_store::{EventStore, EventStoreError};
+    use crate::shared::infrastructure::intent_outbox::in_memory::InMemoryDomainOutbox;
+    use crate::tests::fixtures::commands::set_breaks::SetBreaksBuilder;
+    use crate::tests::fixtures::commands::set_ended_at::SetEndedAtBuilder;
+    use crate::tests::fixtures::commands::set_started_at::SetStartedAtBuilder;
+    use rstest::{fixture, rstest};
+    use std::sync::Arc;
+
+    type BeforeEachReturn = (
+        &'static str,
+        InMemoryEventStore<TimeEntryEvent>,
+        InMemoryDomainOutbox,
+    );
+
+    const NOW: i64 = 1_700_000_400_000;
+
+    #[fixture]
+    fn before_each() -> BeforeEachReturn {
+        (
+            "TimeEntry-te-fixed-0001",
+            InMemoryEventStore::<TimeEntryEvent>::new(),
+            InMemoryDomainOutbox::new(),
+        )
+    }
+
+    async fn register(
+        stream_id: &str,
+        event_store: &InMemoryEventStore<TimeEntryEvent>,
+        outbox: &InMemoryDomainOutbox,
+    ) {
+        let clock = Arc::new(FixedClock::at(NOW));
+        SetStartedAtHandler::new(event_store.clone(), outbox.clone())
+            .with_clock(clock.clone())
+            .handle(stream_id, SetStartedAtBuilder::new().build())
+            .await
+            .unwrap();
+        SetEndedAtHandler::new(event_store.clone(), outbox.clone())
+            .with_clock(clock)
+            .handle(stream_id, SetEndedAtBuilder::new().build())
+            .await
+            .unwrap();
+    }
+
+    fn handler(
+        event_store: &InMemoryEventStore<TimeEntryEvent>,
+        outbox: InMemoryDomainOutbox,
+    ) -> SetBreaksHandler<InMemoryEventStore<TimeEntryEvent>, InMemoryDomainOutbox> {
+        SetBreaksHandler::new(event_store.clone(), outbox).with_clock(Arc::new(FixedClock::at(NOW)))
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_record_the_breaks_of_a_registered_entry(before_each: BeforeEachReturn) {
+        let (stream_id, event_store, outbox) = before_each;
+        register(stream_id, &event_store, &outbox).await;
+
+        handler(&event_store, outbox)
+            .handle(stream_id, SetBreaksBuilder::new().build())
+            .await
+            .unwrap();
+
+        let stream = event_store.load(stream_id).await.unwrap();
+        let state = stream.events.into_iter().fold(TimeEntryState::None, evolve);
+        assert!(matches!(
+            state,
+            TimeEntryState::Registered { breaks, .. } if breaks == vec![Break {
+                started_at: 1_700_000_120_000,
+                ended_at: 1_700_000_180_000,
+            }]
+        ));
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_refuse_breaks_outside_the_entry(before_each: BeforeEachReturn) {
+        let (stream_id, event_store, outbox) = before_each;
+        register(stream_id, &event_store, &outbox).await;
+
+        let result = handler(&event_store, outbox)
+            .handle(
+                stream_id,
+                SetBreaksBuilder::new()
+                    .breaks(vec![Break {
+                        started_at: 1_700_000_300_000,
+                        ended_at: 1_700_000_400_000,
+                    }])
+                    .build(),
+            )
+            .await;
+
+        assert!(matches!(
+            result,
+            Err(ApplicationError::Domain(DecideError::InvalidBreaks(
+                BreakError::OutsideEntry
+            )))
+        ));
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_fail_if_event_store_is_offline(before_each: BeforeEachReturn) {
+        let (stream_id, event_store, outbox) = before_each;
+        event_store.toggle_offline();
+
+        let result = handler(&event_store, outbox)
+            .handle(stream_id, SetBreaksBuilder::new().build())
+            .await;
+
+        assert!(matches!(
+            result,
+            Err(ApplicationError::VersionConflict(EventStoreError::Backend(
+                _
+            )))
+        ));
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_dispatch_through_the_command_bus(before_each: BeforeEachReturn) {
+        let (stream_id, event_store, outbox) = before_each;
+        register(stream_id, &event_store, &outbox).await;
+        let bus = CommandBus::new(handler(&event_store, outbox));
+
+        bus.dispatch(CommandEnvelope::new(
+            stream_id,
+            SetBreaksBuilder::new().build(),
+        ))
+        .await
+        .unwrap();
+
+        assert_eq!(event_store.load(stream_id).await.unwrap().events.len(), 5);
+    }
+}
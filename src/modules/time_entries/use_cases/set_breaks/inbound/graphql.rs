@@ -0,0 +1,169 @@
+use async_graphql::{Context, InputObject, Object, Result as GqlResult};
+
+use crate::modules::time_entries::core::breaks::Break;
+use crate::modules::time_entries::use_cases::set_breaks::command::SetBreaks;
+use crate::shared::core::primitives::TimeEntryId;
+use crate::shared::infrastructure::request_context::RequestContext;
+use crate::shell::state::AppState;
+
+/// A pause inside a time entry, in epoch milliseconds.
+#[derive(InputObject)]
+pub struct BreakInput {
+    pub started_at: i64,
+    pub ended_at: i64,
+}
+
+#[cfg(test)]
+mod set_breaks_graphql_inbound_tests {
+    use async_graphql::{EmptySubscription, Schema};
+
+    use crate::shared::auth::rbac::Scope;
+    use crate::shared::infrastructure::request_context::RequestContext;
+    use crate::shell::graphql::{MutationRoot, QueryRoot};
+    use crate::shell::state::AppState;
+    use crate::tests::fixtures::tags::make_test_app_state;
+
+    fn make_schema_from_state(
+        state: AppState,
+    ) -> Schema<QueryRoot, MutationRoot, EmptySubscription> {
+        Schema::build(
+            QueryRoot::default(),
+            MutationRoot::default(),
+            EmptySubscription,
+        )
+        .data(state)
+        .finish()
+    }
+
+    fn req_ctx() -> RequestContext {
+        RequestContext {
+            user_id: "u-1".to_string(),
+            tenant_id: "tenant-test".to_string(),
+            role: Default::default(),
+            scope: Default::default(),
+        }
+    }
+
+    const HOUR: i64 = 3_600_000;
+
+    /// A registered entry from 2 hours ago to 1 hour ago, with its id and start.
+    async fn registered(
+        schema: &Schema<QueryRoot, MutationRoot, EmptySubscription>,
+    ) -> (String, i64) {
+        let te_id = uuid::Uuid::now_v7().to_string();
+        let started_at = chrono::Utc::now().timestamp_millis() - 2 * HOUR;
+        let mutation = format!(
+            r#"mutation {{ setStartedAt(timeEntryId: "{te_id}", startedAt: {started_at}) setEndedAt(timeEntryId: "{te_id}", endedAt: {}) }}"#,
+            started_at + HOUR
+        );
+        let result = schema
+            .execute(async_graphql::Request::new(mutation).data(req_ctx()))
+            .await;
+        assert!(result.errors.is_empty(), "{:?}", result.errors);
+        (te_id, started_at)
+    }
+
+    fn mutation(time_entry_id: &str, started_at: i64, ended_at: i64) -> String {
+        format!(
+            r#"mutation {{ setBreaks(timeEntryId: "{time_entry_id}", breaks: [{{ startedAt: {started_at}, endedAt: {ended_at} }}]) }}"#
+        )
+    }
+
+    #[tokio::test]
+    async fn returns_true_on_valid_input() {
+        let schema = make_schema_from_state(make_test_app_state());
+        let (te_id, started_at) = registered(&schema).await;
+
+        let result = schema
+            .execute(
+                async_graphql::Request::new(mutation(
+                    &te_id,
+                    started_at + HOUR / 4,
+                    started_at + HOUR / 2,
+                ))
+                .data(req_ctx()),
+            )
+            .await;
+
+        assert!(result.errors.is_empty(), "{:?}", result.errors);
+        assert_eq!(result.data.to_string(), "{setBreaks: true}");
+    }
+
+    #[tokio::test]
+    async fn returns_error_on_breaks_outside_the_entry() {
+        let schema = make_schema_from_state(make_test_app_state());
+        let (te_id, started_at) = registered(&schema).await;
+
+        let result = schema
+            .execute(
+                async_graphql::Request::new(mutation(&te_id, started_at - HOUR, started_at))
+                    .data(req_ctx()),
+            )
+            .await;
+
+        assert_eq!(
+            result.errors[0].message,
+            "domain rejected: break must lie within the time entry"
+        );
+    }
+
+    #[tokio::test]
+    async fn returns_forbidden_for_read_only_api_keys() {
+        let te_id = uuid::Uuid::now_v7().to_string();
+        let schema = make_schema_from_state(make_test_app_state());
+        let result = schema
+            .execute(
+                async_graphql::Request::new(mutation(&te_id, 0, 1)).data(RequestContext {
+                    scope: Scope::ReadOnly,
+                    ..req_ctx()
+                }),
+            )
+            .await;
+        assert_eq!(result.errors[0].message, "Forbidden");
+    }
+}
+
+#[derive(Default)]
+pub struct SetBreaksMutation;
+
+#[Object]
+impl SetBreaksMutation {
+    /// Replaces the breaks of a registered time entry; pass none to clear them.
+    async fn set_breaks(
+        &self,
+        context: &Context<'_>,
+        time_entry_id: String,
+        breaks: Vec<BreakInput>,
+    ) -> GqlResult<bool> {
+        let time_entry_id = TimeEntryId::parse_v7(&time_entry_id)
+            .map_err(|_| async_graphql::Error::new("time_entry_id must be a valid UUID v7"))?;
+
+        let req_ctx = context
+            .data::<RequestContext>()
+            .map_err(|_| async_graphql::Error::new("Unauthorized"))?;
+        if !req_ctx.principal().can_register_for(&req_ctx.user_id) {
+            return Err(async_graphql::Error::new("Forbidden"));
+        }
+        let state = context.data_unchecked::<AppState>();
+        let stream_id = state
+            .stream_naming
+            .time_entry(Some(&req_ctx.tenant_id), &time_entry_id);
+
+        let breaks = breaks
+            .into_iter()
+            .map(|pause| Break {
+                started_at: pause.started_at,
+                ended_at: pause.ended_at,
+            })
+            .collect();
+        let command = SetBreaks::new(time_entry_id, req_ctx.user_id.clone().into(), breaks);
+
+        state
+            .set_breaks_handler
+            .handle_for_tenant(&req_ctx.tenant_id, &stream_id, command)
+            .await
+            .map_err(|e| async_graphql::Error::new(e.to_string()))?;
+
+        Ok(true)
+    }
+}
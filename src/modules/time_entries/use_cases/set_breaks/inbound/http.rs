@@ -0,0 +1,188 @@
+use axum::{
+    Json,
+    extract::{Path, State, rejection::JsonRejection},
+    http::StatusCode,
+    response::IntoResponse,
+};
+use serde::Deserialize;
+
+use crate::modules::time_entries::core::breaks::Break;
+use crate::modules::time_entries::use_cases::set_breaks::command::SetBreaks;
+use crate::modules::time_entries::use_cases::set_breaks::decision::DecideError;
+use crate::modules::time_entries::use_cases::set_breaks::handler::ApplicationError;
+use crate::shared::application::command_bus::{CommandBusError, CommandEnvelope};
+use crate::shared::core::primitives::TimeEntryId;
+use crate::shared::infrastructure::request_context::{IdempotencyKey, RequestContext};
+use crate::shell::state::AppState;
+
+#[derive(Deserialize)]
+pub struct SetBreaksBody {
+    pub breaks: Vec<Break>,
+}
+
+/// PUT /time-entries/{id}/breaks — replaces the breaks of a registered time entry
+pub async fn handle_put(
+    State(state): State<AppState>,
+    request_ctx: RequestContext,
+    idempotency_key: IdempotencyKey,
+    Path(time_entry_id): Path<String>,
+    body: Result<Json<SetBreaksBody>, JsonRejection>,
+) -> impl IntoResponse {
+    if !request_ctx
+        .principal()
+        .can_register_for(&request_ctx.user_id)
+    {
+        return StatusCode::FORBIDDEN.into_response();
+    }
+    let Ok(time_entry_id) = TimeEntryId::parse_v7(&time_entry_id) else {
+        return StatusCode::UNPROCESSABLE_ENTITY.into_response();
+    };
+
+    let Json(body) = match body {
+        Ok(b) => b,
+        Err(_) => return StatusCode::UNPROCESSABLE_ENTITY.into_response(),
+    };
+
+    let stream_id = state
+        .stream_naming
+        .time_entry(Some(&request_ctx.tenant_id), &time_entry_id);
+
+    let command = SetBreaks::new(
+        time_entry_id,
+        request_ctx.user_id.clone().into(),
+        body.breaks,
+    );
+
+    let mut envelope = CommandEnvelope::new(stream_id, command)
+        .with_user_id(request_ctx.user_id)
+        .with_tenant_id(request_ctx.tenant_id);
+    envelope.idempotency_key = idempotency_key.0;
+
+    match state
+        .command_pipeline
+        .dispatch("set_breaks", state.set_breaks_handler.clone(), envelope)
+        .await
+    {
+        Ok(()) => StatusCode::OK.into_response(),
+        Err(CommandBusError::Unauthorized) => StatusCode::UNAUTHORIZED.into_response(),
+        Err(CommandBusError::Handler(ApplicationError::Domain(DecideError::InvalidBreaks(_)))) => {
+            StatusCode::UNPROCESSABLE_ENTITY.into_response()
+        }
+        Err(CommandBusError::Handler(ApplicationError::Domain(_))) => {
+            StatusCode::CONFLICT.into_response()
+        }
+        Err(_) => StatusCode::INTERNAL_SERVER_ERROR.into_response(),
+    }
+}
+
+#[cfg(test)]
+mod set_breaks_http_inbound_tests {
+    use axum::{
+        Router,
+        body::Body,
+        http::{Request, StatusCode},
+        routing::put,
+    };
+    use rstest::rstest;
+    use tower::ServiceExt;
+
+    use super::handle_put;
+    use crate::modules::time_entries::use_cases::set_ended_at::inbound::http::handle_put as handle_put_end;
+    use crate::modules::time_entries::use_cases::set_started_at::inbound::http::handle_put as handle_put_start;
+    use crate::shell::state::AppState;
+    use crate::tests::fixtures::tags::{make_test_app_state, make_test_app_state_with_store};
+
+    fn app(state: AppState) -> Router {
+        Router::new()
+            .route("/time-entries/{id}/start", put(handle_put_start))
+            .route("/time-entries/{id}/end", put(handle_put_end))
+            .route("/time-entries/{id}/breaks", put(handle_put))
+            .with_state(state)
+    }
+
+    async fn send(state: &AppState, te_id: &str, path: &str, body: &str) -> StatusCode {
+        app(state.clone())
+            .oneshot(
+                Request::builder()
+                    .method("PUT")
+                    .uri(format!("/time-entries/{te_id}/{path}"))
+                    .header("content-type", "application/json")
+                    .header("x-user-id", "u-1")
+                    .header("x-tenant-id", "tenant-test")
+                    .body(Body::from(body.to_string()))
+                    .unwrap(),
+            )
+            .await
+            .unwrap()
+            .status()
+    }
+
+    /// A registered entry from 1 to 2 hours ago.
+    async fn registered(state: &AppState) -> String {
+        let te_id = uuid::Uuid::now_v7().to_string();
+        let now = chrono::Utc::now().timestamp_millis();
+        let start = format!(r#"{{"started_at":{}}}"#, now - 2 * 3_600_000);
+        let end = format!(r#"{{"ended_at":{}}}"#, now - 3_600_000);
+        assert_eq!(send(state, &te_id, "start", &start).await, StatusCode::OK);
+        assert_eq!(send(state, &te_id, "end", &end).await, StatusCode::OK);
+        te_id
+    }
+
+    fn breaks(from_hours_ago: f64, to_hours_ago: f64) -> String {
+        let now = chrono::Utc::now().timestamp_millis();
+        let at = |hours_ago: f64| now - (hours_ago * 3_600_000.0) as i64;
+        format!(
+            r#"{{"breaks":[{{"started_at":{},"ended_at":{}}}]}}"#,
+            at(from_hours_ago),
+            at(to_hours_ago)
+        )
+    }
+
+    #[tokio::test]
+    async fn put_returns_200_for_breaks_inside_the_entry() {
+        let state = make_test_app_state();
+        let te_id = registered(&state).await;
+
+        assert_eq!(
+            send(&state, &te_id, "breaks", &breaks(1.75, 1.5)).await,
+            StatusCode::OK
+        );
+    }
+
+    #[tokio::test]
+    async fn put_returns_409_for_entries_without_both_ends() {
+        let te_id = uuid::Uuid::now_v7().to_string();
+
+        assert_eq!(
+            send(&make_test_app_state(), &te_id, "breaks", r#"{"breaks":[]}"#).await,
+            StatusCode::CONFLICT
+        );
+    }
+
+    #[rstest]
+    #[case::outside(breaks(2.5, 1.5))]
+    #[case::empty(breaks(1.5, 1.5))]
+    #[case::invalid_json("not-json".to_string())]
+    #[tokio::test]
+    async fn put_returns_422_on_invalid_breaks(#[case] body: String) {
+        let state = make_test_app_state();
+        let te_id = registered(&state).await;
+
+        assert_eq!(
+            send(&state, &te_id, "breaks", &body).await,
+            StatusCode::UNPROCESSABLE_ENTITY
+        );
+    }
+
+    #[tokio::test]
+    async fn put_returns_500_when_event_store_offline() {
+        let (state, event_store) = make_test_app_state_with_store();
+        let te_id = registered(&state).await;
+        event_store.toggle_offline();
+
+        assert_eq!(
+            send(&state, &te_id, "breaks", &breaks(1.75, 1.5)).await,
+            StatusCode::INTERNAL_SERVER_ERROR
+        );
+    }
+}
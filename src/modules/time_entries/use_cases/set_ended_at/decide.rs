@@ -1,16 +1,23 @@
+use crate::modules::time_entries::core::breaks::ensure_valid_breaks;
 use crate::modules::time_entries::core::events::TimeEntryEvent;
 use crate::modules::time_entries::core::events::v1::time_entry_end_set::TimeEntryEndSetV1;
 use crate::modules::time_entries::core::events::v1::time_entry_initiated::TimeEntryInitiatedV1;
 use crate::modules::time_entries::core::events::v1::time_entry_registered::TimeEntryRegisteredV1;
+use crate::modules::time_entries::core::evolve::evolve;
 use crate::modules::time_entries::core::intents::TimeEntryIntent;
+use crate::modules::time_entries::core::policies::round_instant;
 use crate::modules::time_entries::core::state::TimeEntryState;
+use crate::modules::time_entries::core::time_interval::TimeInterval;
 use crate::modules::time_entries::use_cases::set_ended_at::command::SetEndedAt;
 use crate::modules::time_entries::use_cases::set_ended_at::decision::{DecideError, Decision};
+use crate::shared::core::decider::Decider;
 
 pub fn decide_set_ended_at(state: &TimeEntryState, command: SetEndedAt) -> Decision {
+    let (ended_at, raw_ended_at) = round_instant(command.rounding, command.ended_at);
     let end_set_event = TimeEntryEvent::TimeEntryEndSetV1(TimeEntryEndSetV1 {
         time_entry_id: command.time_entry_id.clone(),
-        ended_at: command.ended_at,
+        ended_at,
+        raw_ended_at,
         updated_at: command.updated_at,
         updated_by: command.updated_by.clone(),
     });
@@ -38,7 +45,7 @@ pub fn decide_set_ended_at(state: &TimeEntryState, command: SetEndedAt) -> Decis
             started_at: Some(s),
             ..
         } => {
-            if command.ended_at <= *s {
+            if TimeInterval::new(*s, ended_at).is_err() {
                 return Decision::Rejected {
                     reason: DecideError::InvalidInterval,
                 };
@@ -55,23 +62,59 @@ pub fn decide_set_ended_at(state: &TimeEntryState, command: SetEndedAt) -> Decis
                 }],
             }
         }
-        TimeEntryState::Registered { started_at, .. } => {
-            if command.ended_at <= *started_at {
+        TimeEntryState::Registered {
+            interval, breaks, ..
+        } => {
+            let Ok(moved) = interval.with_end(ended_at) else {
                 return Decision::Rejected {
                     reason: DecideError::InvalidInterval,
                 };
+            };
+            if let Err(reason) = ensure_valid_breaks(&moved, breaks) {
+                return Decision::Rejected {
+                    reason: reason.into(),
+                };
             }
             Decision::Accepted {
                 events: vec![end_set_event],
                 intents: vec![],
             }
         }
+        TimeEntryState::Approved { .. } => Decision::Rejected {
+            reason: DecideError::Approved,
+        },
+        TimeEntryState::Deleted { .. } => Decision::Rejected {
+            reason: DecideError::Deleted,
+        },
+    }
+}
+
+pub struct SetEndedAtDecider;
+
+impl Decider for SetEndedAtDecider {
+    type State = TimeEntryState;
+    type Command = SetEndedAt;
+    type Event = TimeEntryEvent;
+    type Intent = TimeEntryIntent;
+    type Error = DecideError;
+
+    fn initial_state() -> TimeEntryState {
+        TimeEntryState::None
+    }
+
+    fn evolve(state: TimeEntryState, event: TimeEntryEvent) -> TimeEntryState {
+        evolve(state, event)
+    }
+
+    fn decide(state: &TimeEntryState, command: SetEndedAt) -> Decision {
+        decide_set_ended_at(state, command)
     }
 }
 
 #[cfg(test)]
 mod decide_set_ended_at_tests {
     use super::*;
+    use crate::modules::time_entries::core::policies::{Rounding, RoundingMode};
     use crate::tests::fixtures::commands::set_ended_at::SetEndedAtBuilder;
     use rstest::{fixture, rstest};
 
@@ -107,6 +150,7 @@ mod decide_set_ended_at_tests {
             tag_ids: vec![],
             created_at: 0,
             created_by: command.updated_by.clone(),
+            hourly_rate: None,
         };
         let decision = decide_set_ended_at(&state, command);
         match decision {
@@ -130,6 +174,7 @@ mod decide_set_ended_at_tests {
             tag_ids: vec![],
             created_at: 0,
             created_by: command.updated_by.clone(),
+            hourly_rate: None,
         };
         let decision = decide_set_ended_at(&state, command);
         match decision {
@@ -158,6 +203,7 @@ mod decide_set_ended_at_tests {
             tag_ids: vec![],
             created_at: 0,
             created_by: command.updated_by.clone(),
+            hourly_rate: None,
         };
         let decision = decide_set_ended_at(&state, command);
         assert!(matches!(
@@ -173,11 +219,12 @@ mod decide_set_ended_at_tests {
         let state = TimeEntryState::Registered {
             time_entry_id: command.time_entry_id.clone(),
             user_id: command.user_id.clone(),
-            started_at: command.ended_at - 100_000,
-            ended_at: command.ended_at,
+            interval: TimeInterval::new(command.ended_at - 100_000, command.ended_at).unwrap(),
             tag_ids: vec![],
             created_at: 0,
             created_by: command.updated_by.clone(),
+            hourly_rate: None,
+            breaks: vec![],
         };
         let decision = decide_set_ended_at(&state, command);
         match decision {
@@ -196,11 +243,12 @@ mod decide_set_ended_at_tests {
         let state = TimeEntryState::Registered {
             time_entry_id: command.time_entry_id.clone(),
             user_id: command.user_id.clone(),
-            started_at: 1_000,
-            ended_at: 2_000,
+            interval: TimeInterval::new(1_000, 2_000).unwrap(),
             tag_ids: vec![],
             created_at: 0,
             created_by: command.updated_by.clone(),
+            hourly_rate: None,
+            breaks: vec![],
         };
         let decision = decide_set_ended_at(&state, command);
         assert!(matches!(
@@ -217,11 +265,12 @@ mod decide_set_ended_at_tests {
         let state = TimeEntryState::Registered {
             time_entry_id: command.time_entry_id.clone(),
             user_id: command.user_id.clone(),
-            started_at: 1_000,
-            ended_at: 2_000,
+            interval: TimeInterval::new(1_000, 2_000).unwrap(),
             tag_ids: vec![],
             created_at: 0,
             created_by: command.updated_by.clone(),
+            hourly_rate: None,
+            breaks: vec![],
         };
         let decision = decide_set_ended_at(&state, command);
         assert!(matches!(
@@ -231,4 +280,78 @@ mod decide_set_ended_at_tests {
             }
         ));
     }
+
+    #[rstest]
+    fn it_should_reject_changes_to_an_approved_entry(command: SetEndedAt) {
+        let state = TimeEntryState::Approved {
+            time_entry_id: command.time_entry_id.clone(),
+            user_id: command.user_id.clone(),
+            interval: TimeInterval::new(1_000, 2_000).unwrap(),
+            tag_ids: vec![],
+            created_at: 0,
+            created_by: command.updated_by.clone(),
+            hourly_rate: None,
+            approved_at: 3_000,
+            approved_by: "manager-0001".into(),
+            breaks: vec![],
+        };
+        match decide_set_ended_at(&state, command) {
+            Decision::Rejected { reason } => assert_eq!(reason, DecideError::Approved),
+            Decision::Accepted { .. } => panic!("expected Rejected"),
+        }
+    }
+
+    #[rstest]
+    #[case::unrounded(None, 1_000_000, None)]
+    #[case::rounded(Some(RoundingMode::Up), 1_800_000, Some(1_000_000))]
+    fn it_should_record_the_rounded_end_and_the_end_as_given(
+        #[case] mode: Option<RoundingMode>,
+        #[case] ended_at: i64,
+        #[case] raw_ended_at: Option<i64>,
+    ) {
+        let mut command = SetEndedAtBuilder::new().ended_at(1_000_000).build();
+        command.rounding = mode.map(|mode| Rounding {
+            increment_minutes: 30,
+            mode,
+        });
+
+        match decide_set_ended_at(&TimeEntryState::None, command) {
+            Decision::Accepted { events, .. } => match &events[1] {
+                TimeEntryEvent::TimeEntryEndSetV1(event) => {
+                    assert_eq!(event.ended_at, ended_at);
+                    assert_eq!(event.raw_ended_at, raw_ended_at);
+                }
+                other => panic!("expected TimeEntryEndSetV1, got {other:?}"),
+            },
+            Decision::Rejected { .. } => panic!("expected Accepted"),
+        }
+    }
+
+    #[rstest]
+    fn it_should_reject_an_end_that_rounds_onto_the_start() {
+        let command = SetEndedAtBuilder::new()
+            .ended_at(1_000_000)
+            .rounding(Rounding {
+                increment_minutes: 15,
+                mode: RoundingMode::Down,
+            })
+            .build();
+        let state = TimeEntryState::Registered {
+            time_entry_id: command.time_entry_id.clone(),
+            user_id: command.user_id.clone(),
+            interval: TimeInterval::new(900_000, 2_000_000).unwrap(),
+            tag_ids: vec![],
+            created_at: 0,
+            created_by: command.updated_by.clone(),
+            hourly_rate: None,
+            breaks: vec![],
+        };
+
+        assert!(matches!(
+            decide_set_ended_at(&state, command),
+            Decision::Rejected {
+                reason: DecideError::InvalidInterval
+            }
+        ));
+    }
 }
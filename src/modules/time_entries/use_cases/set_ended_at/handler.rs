@@ -1,28 +1,28 @@
-use crate::modules::time_entries::adapters::outbound::intent_outbox::dispatch_intents;
+use crate::modules::time_entries::adapters::outbound::intent_outbox::TimeEntryIntentDispatcher;
+use crate::modules::time_entries::core::day_capacity::{CapacityWarning, DayCapacity};
+use crate::modules::time_entries::core::days_off::AbsencePolicy;
 use crate::modules::time_entries::core::events::TimeEntryEvent;
-use crate::modules::time_entries::core::evolve::evolve;
-use crate::modules::time_entries::core::state::TimeEntryState;
+use crate::modules::time_entries::core::policies::Policies;
 use crate::modules::time_entries::use_cases::set_ended_at::command::SetEndedAt;
-use crate::modules::time_entries::use_cases::set_ended_at::decide::decide_set_ended_at;
-use crate::modules::time_entries::use_cases::set_ended_at::decision::{DecideError, Decision};
-use crate::shared::infrastructure::event_store::{EventStore, EventStoreError};
+use crate::modules::time_entries::use_cases::set_ended_at::decide::SetEndedAtDecider;
+use crate::modules::time_entries::use_cases::set_ended_at::decision::DecideError;
+use crate::modules::time_entries::use_cases::user_time_entries::day_totals::SharedDayTotals;
+use crate::modules::time_entries::use_cases::user_time_entries::sharded_handler::{
+    PeriodLockStreams, SharedCalendar, UserShardedHandler, UserStreams,
+};
+use crate::shared::application::command_bus::CommandHandler;
+use crate::shared::application::event_sourced_handler::EventSourcedError;
+use crate::shared::application::server_time::SkewWindow;
+use crate::shared::application::slo::{WriteSlo, observe};
+use crate::shared::infrastructure::clock::SharedClock;
+use crate::shared::infrastructure::event_bus::SharedEventBus;
+use crate::shared::infrastructure::event_store::EventStore;
+use crate::shared::infrastructure::feature_flags::SharedFeatureFlags;
 use crate::shared::infrastructure::intent_outbox::{DomainOutbox, OutboxError};
-use thiserror::Error;
+use crate::shared::infrastructure::policy_store::SharedPolicyStore;
+use async_trait::async_trait;
 
-#[derive(Debug, Error)]
-pub enum ApplicationError {
-    #[error(transparent)]
-    VersionConflict(#[from] EventStoreError),
-
-    #[error(transparent)]
-    Outbox(#[from] OutboxError),
-
-    #[error("domain rejected: {0}")]
-    Domain(DecideError),
-
-    #[error("unexpected: {0}")]
-    Unexpected(String),
-}
+pub type ApplicationError = EventSourcedError<DecideError, OutboxError>;
 
 #[derive(Debug, Clone)]
 pub struct SetEndedAtHandler<TEventStore, TOutbox>
@@ -30,9 +30,8 @@ where
     TEventStore: EventStore<TimeEntryEvent> + Send + Sync + 'static,
     TOutbox: DomainOutbox + Send + Sync + 'static,
 {
-    topic: String,
-    event_store: TEventStore,
-    outbox: TOutbox,
+    inner: UserShardedHandler<SetEndedAtDecider, TEventStore, TimeEntryIntentDispatcher<TOutbox>>,
+    slo: Option<WriteSlo>,
 }
 
 impl<TEventStore, TOutbox> SetEndedAtHandler<TEventStore, TOutbox>
@@ -40,72 +39,170 @@ where
     TEventStore: EventStore<TimeEntryEvent> + Send + Sync + 'static,
     TOutbox: DomainOutbox + Send + Sync + 'static,
 {
-    pub fn new(topic: impl Into<String>, event_store: TEventStore, outbox: TOutbox) -> Self {
+    pub fn new(event_store: TEventStore, outbox: TOutbox) -> Self {
         Self {
-            topic: topic.into(),
-            event_store,
-            outbox,
+            inner: UserShardedHandler::new(event_store, TimeEntryIntentDispatcher::new(outbox)),
+            slo: None,
         }
     }
 
+    /// Also maintain the per-user `UserTimeEntries-{user_id}` streams, enforcing that a
+    /// user's entries never overlap and at most one timer runs at a time.
+    pub fn with_user_streams(mut self, user_streams: UserStreams) -> Self {
+        self.inner = self.inner.with_user_streams(user_streams);
+        self
+    }
+
+    /// Refuse changes to entries inside a locked payroll period.
+    pub fn with_period_locks(mut self, period_locks: PeriodLockStreams) -> Self {
+        self.inner = self.inner.with_period_locks(period_locks);
+        self
+    }
+
+    /// Warn about or refuse registering time on holidays and approved absences.
+    pub fn with_calendar(mut self, calendar: SharedCalendar, policy: AbsencePolicy) -> Self {
+        self.inner = self.inner.with_calendar(calendar, policy);
+        self
+    }
+
+    /// Warn about or refuse registering more time on a day than `capacity` allows.
+    pub fn with_day_capacity(mut self, day_totals: SharedDayTotals, capacity: DayCapacity) -> Self {
+        self.inner = self.inner.with_day_capacity(day_totals, capacity);
+        self
+    }
+
+    /// Read the overlap and maximum duration rules rolled out per tenant from `feature_flags`.
+    pub fn with_feature_flags(mut self, feature_flags: SharedFeatureFlags) -> Self {
+        self.inner = self.inner.with_feature_flags(feature_flags);
+        self
+    }
+
+    /// Hold each tenant to the policies in `store`, or to `defaults` where it has none.
+    pub fn with_policies(mut self, store: SharedPolicyStore, defaults: Policies) -> Self {
+        self.inner = self.inner.with_policies(store, defaults);
+        self
+    }
+
+    /// Read the time commands are recorded at from `clock` instead of the system clock.
+    pub fn with_clock(mut self, clock: SharedClock) -> Self {
+        self.inner = self.inner.with_clock(clock);
+        self
+    }
+
+    /// Refuse client-supplied instants outside `skew_window` of server time.
+    pub fn with_skew_window(mut self, skew_window: SkewWindow) -> Self {
+        self.inner = self.inner.with_skew_window(skew_window);
+        self
+    }
+
+    /// Record every run in `slo`, the write path's service level indicators.
+    pub fn with_slo(mut self, slo: WriteSlo) -> Self {
+        self.slo = Some(slo);
+        self
+    }
+
+    /// Announce the appended events on `event_bus`, for the projectors to pick up.
+    pub fn with_event_bus(mut self, event_bus: SharedEventBus<TimeEntryEvent>) -> Self {
+        self.inner = self.inner.with_event_bus(event_bus);
+        self
+    }
+
     pub async fn handle(
         &self,
         stream_id: &str,
         command: SetEndedAt,
     ) -> Result<(), ApplicationError> {
-        let stream = self
-            .event_store
-            .load(stream_id)
-            .await
-            .map_err(ApplicationError::VersionConflict)?;
-
-        let state = stream
-            .events
-            .iter()
-            .cloned()
-            .fold(TimeEntryState::None, evolve);
-
-        match decide_set_ended_at(&state, command) {
-            Decision::Accepted { events, intents } => {
-                let events_len = events.len();
-                self.event_store
-                    .append(stream_id, stream.version, &events)
-                    .await
-                    .map_err(ApplicationError::VersionConflict)?;
-                dispatch_intents(
-                    &self.outbox,
-                    stream_id,
-                    stream.version,
-                    events_len,
-                    &self.topic,
-                    intents,
-                )
-                .await
-                .map_err(ApplicationError::Outbox)?;
-                Ok(())
-            }
-            Decision::Rejected { reason } => Err(ApplicationError::Domain(reason)),
-        }
+        observe(
+            self.slo.as_ref(),
+            "set_ended_at",
+            ApplicationError::outcome,
+            self.inner.handle(stream_id, command),
+        )
+        .await
+    }
+
+    /// Handles the command under the rules rolled out to `tenant_id` and its policies.
+    pub async fn handle_for_tenant(
+        &self,
+        tenant_id: &str,
+        stream_id: &str,
+        command: SetEndedAt,
+    ) -> Result<(), ApplicationError> {
+        observe(
+            self.slo.as_ref(),
+            "set_ended_at",
+            ApplicationError::outcome,
+            self.inner
+                .handle_for_tenant(Some(tenant_id), stream_id, command),
+        )
+        .await
+    }
+
+    /// Handles the command like `handle_for_tenant`, answering with the days it took over
+    /// capacity when the capacity policy warns.
+    pub async fn handle_for_tenant_with_warnings(
+        &self,
+        tenant_id: &str,
+        stream_id: &str,
+        command: SetEndedAt,
+    ) -> Result<Vec<CapacityWarning>, ApplicationError> {
+        observe(
+            self.slo.as_ref(),
+            "set_ended_at",
+            ApplicationError::outcome,
+            self.inner
+                .handle_with_warnings(Some(tenant_id), stream_id, None, command),
+        )
+        .await
+    }
+}
+
+#[async_trait]
+impl<TEventStore, TOutbox> CommandHandler<SetEndedAt> for SetEndedAtHandler<TEventStore, TOutbox>
+where
+    TEventStore: EventStore<TimeEntryEvent> + Send + Sync + 'static,
+    TOutbox: DomainOutbox + Send + Sync + 'static,
+{
+    type Error = ApplicationError;
+
+    async fn handle(&self, stream_id: &str, command: SetEndedAt) -> Result<(), ApplicationError> {
+        SetEndedAtHandler::handle(self, stream_id, command).await
+    }
+
+    async fn handle_for_tenant(
+        &self,
+        tenant_id: &str,
+        stream_id: &str,
+        command: SetEndedAt,
+    ) -> Result<(), ApplicationError> {
+        SetEndedAtHandler::handle_for_tenant(self, tenant_id, stream_id, command).await
     }
 }
 
 #[cfg(test)]
 mod set_ended_at_handler_tests {
+    use crate::modules::time_entries::adapters::outbound::intent_outbox::OUTBOX_TOPIC;
     use crate::modules::time_entries::core::events::TimeEntryEvent;
     use crate::modules::time_entries::use_cases::set_ended_at::decision::DecideError;
     use crate::modules::time_entries::use_cases::set_ended_at::handler::{
         ApplicationError, SetEndedAtHandler,
     };
+    use crate::shared::application::command_bus::CommandBus;
+    use crate::shared::application::command_bus::CommandEnvelope;
+    use crate::shared::application::command_bus::middleware::RetryOnConflictMiddleware;
+    use crate::shared::application::server_time::SkewWindow;
+    use crate::shared::infrastructure::clock::FixedClock;
     use crate::shared::infrastructure::event_store::in_memory::InMemoryEventStore;
     use crate::shared::infrastructure::event_store::{EventStore, EventStoreError};
     use crate::shared::infrastructure::intent_outbox::in_memory::InMemoryDomainOutbox;
-    use crate::shared::infrastructure::intent_outbox::{DomainOutbox, OutboxError, OutboxRow};
+    use crate::shared::infrastructure::intent_outbox::{
+        DomainOutbox, OutboxError, OutboxRow, OutboxStatus,
+    };
     use crate::tests::fixtures::commands::set_ended_at::SetEndedAtBuilder;
     use rstest::{fixture, rstest};
+    use std::sync::Arc;
     use tokio::join;
 
-    const TOPIC: &str = "time-entries";
-
     type BeforeEachReturn = (
         &'static str,
         InMemoryEventStore<TimeEntryEvent>,
@@ -124,7 +221,7 @@ mod set_ended_at_handler_tests {
     #[tokio::test]
     async fn handle_set_ended_at_creates_draft_on_new_stream(before_each: BeforeEachReturn) {
         let (stream_id, event_store, outbox) = before_each;
-        let handler = SetEndedAtHandler::new(TOPIC, event_store.clone(), outbox);
+        let handler = SetEndedAtHandler::new(event_store.clone(), outbox);
         handler
             .handle(stream_id, SetEndedAtBuilder::new().build())
             .await
@@ -138,7 +235,7 @@ mod set_ended_at_handler_tests {
     #[tokio::test]
     async fn handle_set_ended_at_on_existing_draft_emits_end_set(before_each: BeforeEachReturn) {
         let (stream_id, event_store, outbox) = before_each;
-        let handler = SetEndedAtHandler::new(TOPIC, event_store.clone(), outbox);
+        let handler = SetEndedAtHandler::new(event_store.clone(), outbox);
         // First call creates draft
         handler
             .handle(stream_id, SetEndedAtBuilder::new().build())
@@ -161,11 +258,11 @@ mod set_ended_at_handler_tests {
     #[tokio::test]
     async fn handle_set_ended_at_rejects_invalid_interval(before_each: BeforeEachReturn) {
         let (stream_id, event_store, outbox) = before_each;
-        let handler = SetEndedAtHandler::new(TOPIC, event_store.clone(), outbox.clone());
+        let handler = SetEndedAtHandler::new(event_store.clone(), outbox.clone());
         // Create a draft with started_at via set_started_at first
         use crate::modules::time_entries::use_cases::set_started_at::handler::SetStartedAtHandler;
         use crate::tests::fixtures::commands::set_started_at::SetStartedAtBuilder;
-        SetStartedAtHandler::new(TOPIC, event_store.clone(), outbox)
+        SetStartedAtHandler::new(event_store.clone(), outbox)
             .handle(
                 stream_id,
                 SetStartedAtBuilder::new().started_at(5_000).build(),
@@ -187,7 +284,7 @@ mod set_ended_at_handler_tests {
     async fn handle_set_ended_at_fails_if_event_store_is_offline(before_each: BeforeEachReturn) {
         let (stream_id, event_store, outbox) = before_each;
         event_store.toggle_offline();
-        let handler = SetEndedAtHandler::new(TOPIC, event_store, outbox);
+        let handler = SetEndedAtHandler::new(event_store, outbox);
         let result = handler
             .handle(stream_id, SetEndedAtBuilder::new().build())
             .await;
@@ -208,8 +305,8 @@ mod set_ended_at_handler_tests {
         event_store.set_delay_append_ms(10);
         let es = event_store;
         let ob = outbox;
-        let handler1 = SetEndedAtHandler::new(TOPIC, es.clone(), ob.clone());
-        let handler2 = SetEndedAtHandler::new(TOPIC, es, ob);
+        let handler1 = SetEndedAtHandler::new(es.clone(), ob.clone());
+        let handler2 = SetEndedAtHandler::new(es, ob);
         let (result1, result2) = join!(
             handler1.handle(stream_id, SetEndedAtBuilder::new().build()),
             handler2.handle(stream_id, SetEndedAtBuilder::new().build())
@@ -238,7 +335,7 @@ mod set_ended_at_handler_tests {
         // Create a draft with started_at first (Initiated v1, StartSet v2)
         use crate::modules::time_entries::use_cases::set_started_at::handler::SetStartedAtHandler;
         use crate::tests::fixtures::commands::set_started_at::SetStartedAtBuilder;
-        SetStartedAtHandler::new(TOPIC, event_store.clone(), outbox.clone())
+        SetStartedAtHandler::new(event_store.clone(), outbox.clone())
             .handle(
                 stream_id,
                 SetStartedAtBuilder::new()
@@ -250,17 +347,23 @@ mod set_ended_at_handler_tests {
         // Pre-seed outbox at version 4 (stream v2, 2 more events appended → v4)
         outbox
             .enqueue(OutboxRow {
-                topic: TOPIC.to_string(),
+                topic: OUTBOX_TOPIC.to_string(),
                 event_type: "TimeEntryRegistered".to_string(),
                 event_version: 1,
                 stream_id: stream_id.to_string(),
                 stream_version: 4,
+                intent_no: 0,
                 occurred_at: 0,
                 payload: serde_json::json!({}),
+                content_encoding: None,
+                status: OutboxStatus::Pending,
+                attempts: 0,
+                last_error: None,
+                published_at: None,
             })
             .await
             .unwrap();
-        let handler = SetEndedAtHandler::new(TOPIC, event_store, outbox);
+        let handler = SetEndedAtHandler::new(event_store, outbox);
         let result = handler
             .handle(
                 stream_id,
@@ -272,4 +375,65 @@ mod set_ended_at_handler_tests {
             Err(ApplicationError::Outbox(OutboxError::Duplicate { .. }))
         ));
     }
+
+    #[rstest]
+    #[tokio::test]
+    async fn handle_set_ended_at_retries_version_conflicts_via_command_bus(
+        before_each: BeforeEachReturn,
+    ) {
+        let (stream_id, event_store, outbox) = before_each;
+        event_store.set_delay_append_ms(10);
+        let bus =
+            CommandBus::new(SetEndedAtHandler::new(event_store.clone(), outbox)).with_middleware(
+                RetryOnConflictMiddleware::new(3, |error: &ApplicationError| {
+                    matches!(
+                        error,
+                        ApplicationError::VersionConflict(EventStoreError::VersionMismatch { .. })
+                    )
+                }),
+            );
+        let (result1, result2) = join!(
+            bus.dispatch(CommandEnvelope::new(
+                stream_id,
+                SetEndedAtBuilder::new().build()
+            )),
+            bus.dispatch(CommandEnvelope::new(
+                stream_id,
+                SetEndedAtBuilder::new().build()
+            ))
+        );
+        assert!(result1.is_ok() && result2.is_ok());
+        let stream = event_store.load(stream_id).await.unwrap();
+        assert_eq!(stream.events.len(), 3);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn handle_set_ended_at_stamps_commands_and_refuses_instants_ahead_of_the_clock(
+        before_each: BeforeEachReturn,
+    ) {
+        let (stream_id, event_store, outbox) = before_each;
+        let now = 1_800_000_000_000;
+        let handler = SetEndedAtHandler::new(event_store.clone(), outbox)
+            .with_clock(Arc::new(FixedClock::at(now)))
+            .with_skew_window(SkewWindow::default());
+
+        let ahead = handler
+            .handle(
+                stream_id,
+                SetEndedAtBuilder::new().ended_at(now + 3_600_000).build(),
+            )
+            .await;
+        handler
+            .handle(stream_id, SetEndedAtBuilder::new().updated_at(1).build())
+            .await
+            .expect("handle failed");
+
+        assert!(matches!(
+            ahead,
+            Err(ApplicationError::Domain(DecideError::ClockSkew(_)))
+        ));
+        let stream = event_store.load(stream_id).await.expect("load failed");
+        assert!(stream.events.iter().all(|event| event.occurred_at() == now));
+    }
 }
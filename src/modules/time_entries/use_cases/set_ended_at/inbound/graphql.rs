@@ -1,8 +1,7 @@
 use async_graphql::{Context, Object, Result as GqlResult};
-use chrono::Utc;
-use uuid::{Uuid, Version};
 
 use crate::modules::time_entries::use_cases::set_ended_at::command::SetEndedAt;
+use crate::shared::core::primitives::TimeEntryId;
 use crate::shared::infrastructure::request_context::RequestContext;
 use crate::shell::state::AppState;
 
@@ -10,9 +9,11 @@ use crate::shell::state::AppState;
 mod set_ended_at_graphql_inbound_tests {
     use async_graphql::{EmptySubscription, Schema};
 
+    use crate::shared::auth::rbac::Scope;
+    use crate::shared::core::stream_naming::{DefaultStreamNaming, StreamNaming};
     use crate::shared::infrastructure::request_context::RequestContext;
     use crate::shell::graphql::{MutationRoot, QueryRoot};
-    use crate::tests::fixtures::tags::make_test_app_state;
+    use crate::tests::fixtures::tags::{make_test_app_state, make_test_app_state_with_store};
 
     fn make_schema_from_state(
         state: crate::shell::state::AppState,
@@ -30,6 +31,8 @@ mod set_ended_at_graphql_inbound_tests {
         RequestContext {
             user_id: "u-1".to_string(),
             tenant_id: "tenant-test".to_string(),
+            role: Default::default(),
+            scope: Default::default(),
         }
     }
 
@@ -53,6 +56,24 @@ mod set_ended_at_graphql_inbound_tests {
         assert_eq!(result.data.to_string(), "{setEndedAt: true}");
     }
 
+    #[tokio::test]
+    async fn returns_forbidden_for_read_only_api_keys() {
+        let te_id = valid_v7_id();
+        let schema = make_schema_from_state(make_test_app_state());
+        let result = schema
+            .execute(
+                async_graphql::Request::new(format!(
+                    r#"mutation {{ setEndedAt(timeEntryId: "{te_id}", endedAt: 2000) }}"#
+                ))
+                .data(RequestContext {
+                    scope: Scope::ReadOnly,
+                    ..req_ctx()
+                }),
+            )
+            .await;
+        assert_eq!(result.errors[0].message, "Forbidden");
+    }
+
     #[tokio::test]
     async fn returns_error_on_non_v7_uuid() {
         let v4_id = "550e8400-e29b-41d4-a716-446655440000";
@@ -74,7 +95,7 @@ mod set_ended_at_graphql_inbound_tests {
 
         let state = make_test_app_state();
         let te_id = valid_v7_id();
-        let stream_id = format!("TimeEntry-{te_id}");
+        let stream_id = DefaultStreamNaming.time_entry(None, &te_id);
 
         // Seed started_at=2000; then ended_at=1000 creates an invalid interval
         state
@@ -103,8 +124,8 @@ mod set_ended_at_graphql_inbound_tests {
 
     #[tokio::test]
     async fn returns_error_when_event_store_offline() {
-        let state = make_test_app_state();
-        state.event_store.toggle_offline();
+        let (state, event_store) = make_test_app_state_with_store();
+        event_store.toggle_offline();
         let te_id = valid_v7_id();
         let schema = make_schema_from_state(state);
         let result = schema
@@ -130,28 +151,25 @@ impl SetEndedAtMutation {
         time_entry_id: String,
         ended_at: i64,
     ) -> GqlResult<bool> {
-        Uuid::parse_str(&time_entry_id)
-            .ok()
-            .filter(|u| u.get_version() == Some(Version::SortRand))
-            .ok_or_else(|| async_graphql::Error::new("time_entry_id must be a valid UUID v7"))?;
+        let time_entry_id = TimeEntryId::parse_v7(&time_entry_id)
+            .map_err(|_| async_graphql::Error::new("time_entry_id must be a valid UUID v7"))?;
 
         let req_ctx = context
             .data::<RequestContext>()
             .map_err(|_| async_graphql::Error::new("Unauthorized"))?;
+        if !req_ctx.principal().can_register_for(&req_ctx.user_id) {
+            return Err(async_graphql::Error::new("Forbidden"));
+        }
         let state = context.data_unchecked::<AppState>();
-        let stream_id = format!("TimeEntry-{time_entry_id}");
+        let stream_id = state
+            .stream_naming
+            .time_entry(Some(&req_ctx.tenant_id), &time_entry_id);
 
-        let command = SetEndedAt {
-            time_entry_id,
-            user_id: req_ctx.user_id.clone(),
-            ended_at,
-            updated_at: Utc::now().timestamp_millis(),
-            updated_by: req_ctx.user_id.clone(),
-        };
+        let command = SetEndedAt::new(time_entry_id, req_ctx.user_id.clone().into(), ended_at);
 
         state
             .set_ended_at_handler
-            .handle(&stream_id, command)
+            .handle_for_tenant(&req_ctx.tenant_id, &stream_id, command)
             .await
             .map_err(|e| async_graphql::Error::new(e.to_string()))?;
 
@@ -4,16 +4,17 @@ use axum::{
     http::StatusCode,
     response::IntoResponse,
 };
-use chrono::Utc;
-use serde::Deserialize;
-use uuid::{Uuid, Version};
+use serde::{Deserialize, Serialize};
 
 use crate::modules::time_entries::use_cases::set_ended_at::command::SetEndedAt;
+use crate::modules::time_entries::use_cases::set_ended_at::decision::DecideError;
 use crate::modules::time_entries::use_cases::set_ended_at::handler::ApplicationError;
-use crate::shared::infrastructure::request_context::RequestContext;
+use crate::shared::application::command_bus::{CommandBusError, CommandEnvelope};
+use crate::shared::core::primitives::TimeEntryId;
+use crate::shared::infrastructure::request_context::{IdempotencyKey, RequestContext};
 use crate::shell::state::AppState;
 
-#[derive(Deserialize)]
+#[derive(Serialize, Deserialize)]
 pub struct SetEndedAtBody {
     pub ended_at: i64,
 }
@@ -22,35 +23,53 @@ pub struct SetEndedAtBody {
 pub async fn handle_put(
     State(state): State<AppState>,
     request_ctx: RequestContext,
+    idempotency_key: IdempotencyKey,
     Path(time_entry_id): Path<String>,
     body: Result<Json<SetEndedAtBody>, JsonRejection>,
 ) -> impl IntoResponse {
-    let is_valid_v7 = Uuid::parse_str(&time_entry_id)
-        .ok()
-        .filter(|u| u.get_version() == Some(Version::SortRand))
-        .is_some();
-    if !is_valid_v7 {
-        return StatusCode::UNPROCESSABLE_ENTITY.into_response();
+    if !request_ctx
+        .principal()
+        .can_register_for(&request_ctx.user_id)
+    {
+        return StatusCode::FORBIDDEN.into_response();
     }
+    let Ok(time_entry_id) = TimeEntryId::parse_v7(&time_entry_id) else {
+        return StatusCode::UNPROCESSABLE_ENTITY.into_response();
+    };
 
     let Json(body) = match body {
         Ok(b) => b,
         Err(_) => return StatusCode::UNPROCESSABLE_ENTITY.into_response(),
     };
 
-    let stream_id = format!("TimeEntry-{time_entry_id}");
+    let stream_id = state
+        .stream_naming
+        .time_entry(Some(&request_ctx.tenant_id), &time_entry_id);
 
-    let command = SetEndedAt {
-        time_entry_id: time_entry_id.clone(),
-        user_id: request_ctx.user_id.clone(),
-        ended_at: body.ended_at,
-        updated_at: Utc::now().timestamp_millis(),
-        updated_by: request_ctx.user_id,
-    };
+    let command = SetEndedAt::new(
+        time_entry_id,
+        request_ctx.user_id.clone().into(),
+        body.ended_at,
+    );
+
+    let mut envelope = CommandEnvelope::new(stream_id, command)
+        .with_user_id(request_ctx.user_id)
+        .with_tenant_id(request_ctx.tenant_id);
+    envelope.idempotency_key = idempotency_key.0;
 
-    match state.set_ended_at_handler.handle(&stream_id, command).await {
+    match state
+        .command_pipeline
+        .dispatch("set_ended_at", state.set_ended_at_handler.clone(), envelope)
+        .await
+    {
         Ok(()) => StatusCode::OK.into_response(),
-        Err(ApplicationError::Domain(_)) => StatusCode::CONFLICT.into_response(),
+        Err(CommandBusError::Unauthorized) => StatusCode::UNAUTHORIZED.into_response(),
+        Err(CommandBusError::Handler(ApplicationError::Domain(DecideError::ClockSkew(_)))) => {
+            StatusCode::UNPROCESSABLE_ENTITY.into_response()
+        }
+        Err(CommandBusError::Handler(ApplicationError::Domain(_))) => {
+            StatusCode::CONFLICT.into_response()
+        }
         Err(_) => StatusCode::INTERNAL_SERVER_ERROR.into_response(),
     }
 }
@@ -66,16 +85,17 @@ mod set_ended_at_http_inbound_tests {
     use tower::ServiceExt;
 
     use super::handle_put;
+    use crate::shared::core::stream_naming::{DefaultStreamNaming, StreamNaming};
     use crate::shell::state::AppState;
-    use crate::tests::fixtures::tags::make_test_app_state;
+    use crate::tests::fixtures::tags::{make_test_app_state, make_test_app_state_with_store};
 
     fn make_test_state() -> AppState {
         make_test_app_state()
     }
 
     fn make_offline_state() -> AppState {
-        let state = make_test_app_state();
-        state.event_store.toggle_offline();
+        let (state, event_store) = make_test_app_state_with_store();
+        event_store.toggle_offline();
         state
     }
 
@@ -118,10 +138,10 @@ mod set_ended_at_http_inbound_tests {
         use crate::tests::fixtures::commands::set_started_at::SetStartedAtBuilder;
 
         let te_id = valid_v7_id();
-        let stream_id = format!("TimeEntry-{te_id}");
+        let stream_id = DefaultStreamNaming.time_entry(None, &te_id);
 
         // Seed a draft with started_at=5000 via the handler directly
-        SetStartedAtHandler::new("t", state.event_store.clone(), InMemoryDomainOutbox::new())
+        SetStartedAtHandler::new(state.event_store.clone(), InMemoryDomainOutbox::new())
             .handle(
                 &stream_id,
                 SetStartedAtBuilder::new()
@@ -151,6 +171,27 @@ mod set_ended_at_http_inbound_tests {
         assert_eq!(response.status(), StatusCode::CONFLICT);
     }
 
+    #[tokio::test]
+    async fn put_returns_422_when_ended_at_is_far_ahead_of_server_time() {
+        let te_id = valid_v7_id();
+        // 2100-01-01
+        let body = r#"{"ended_at":4102444800000}"#;
+        let response = app(make_test_state())
+            .oneshot(
+                Request::builder()
+                    .method("PUT")
+                    .uri(format!("/time-entries/{te_id}/end"))
+                    .header("content-type", "application/json")
+                    .header("x-user-id", "u-1")
+                    .header("x-tenant-id", "tenant-test")
+                    .body(Body::from(body))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
     #[tokio::test]
     async fn put_returns_422_on_non_uuid() {
         let body = r#"{"ended_at":1000}"#;
@@ -0,0 +1,41 @@
+use crate::modules::time_entries::core::policies::RoundedCommand;
+use crate::shared::application::server_time::StampedCommand;
+use crate::shared::core::primitives::{TimeEntryId, Timestamp, UserId};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SetHourlyRate {
+    pub time_entry_id: TimeEntryId,
+    pub user_id: UserId,
+    pub hourly_rate_cents: i64,
+    pub currency: String,
+    /// Stamped by the handler from its clock.
+    pub updated_at: i64,
+    pub updated_by: UserId,
+}
+
+impl SetHourlyRate {
+    /// `user_id` prices their own entry; the handler stamps `updated_at`.
+    pub fn new(
+        time_entry_id: TimeEntryId,
+        user_id: UserId,
+        hourly_rate_cents: i64,
+        currency: String,
+    ) -> Self {
+        Self {
+            time_entry_id,
+            updated_by: user_id.clone(),
+            user_id,
+            hourly_rate_cents,
+            currency,
+            updated_at: 0,
+        }
+    }
+}
+
+impl StampedCommand for SetHourlyRate {
+    fn stamp(&mut self, now: Timestamp) {
+        self.updated_at = now.as_millis();
+    }
+}
+
+impl RoundedCommand for SetHourlyRate {}
@@ -0,0 +1,233 @@
+use crate::modules::time_entries::core::events::TimeEntryEvent;
+use crate::modules::time_entries::core::events::v1::time_entry_hourly_rate_set::TimeEntryHourlyRateSetV1;
+use crate::modules::time_entries::core::events::v1::time_entry_initiated::TimeEntryInitiatedV1;
+use crate::modules::time_entries::core::evolve::evolve;
+use crate::modules::time_entries::core::hourly_rate::is_currency_code;
+use crate::modules::time_entries::core::intents::TimeEntryIntent;
+use crate::modules::time_entries::core::state::TimeEntryState;
+use crate::modules::time_entries::use_cases::set_hourly_rate::command::SetHourlyRate;
+use crate::modules::time_entries::use_cases::set_hourly_rate::decision::{DecideError, Decision};
+use crate::shared::core::decider::Decider;
+
+pub fn decide_set_hourly_rate(state: &TimeEntryState, command: SetHourlyRate) -> Decision {
+    if !is_currency_code(&command.currency) {
+        return Decision::Rejected {
+            reason: DecideError::InvalidCurrency,
+        };
+    }
+    if command.hourly_rate_cents < 0 {
+        return Decision::Rejected {
+            reason: DecideError::NegativeRate,
+        };
+    }
+
+    let rate_set_event = TimeEntryEvent::TimeEntryHourlyRateSetV1(TimeEntryHourlyRateSetV1 {
+        time_entry_id: command.time_entry_id.clone(),
+        hourly_rate_cents: command.hourly_rate_cents,
+        currency: command.currency.clone(),
+        updated_at: command.updated_at,
+        updated_by: command.updated_by.clone(),
+    });
+
+    let notify = TimeEntryIntent::NotifyUser {
+        time_entry_id: command.time_entry_id.clone(),
+        occurred_at: command.updated_at,
+    };
+
+    match state {
+        TimeEntryState::None => {
+            let initiated = TimeEntryEvent::TimeEntryInitiatedV1(TimeEntryInitiatedV1 {
+                time_entry_id: command.time_entry_id,
+                user_id: command.user_id,
+                created_at: command.updated_at,
+                created_by: command.updated_by,
+            });
+            Decision::Accepted {
+                events: vec![initiated, rate_set_event],
+                intents: vec![notify],
+            }
+        }
+        TimeEntryState::Draft { hourly_rate, .. }
+        | TimeEntryState::Registered { hourly_rate, .. } => match hourly_rate {
+            Some(current) if current.currency != command.currency => Decision::Rejected {
+                reason: DecideError::CurrencyMismatch {
+                    current: current.currency.clone(),
+                },
+            },
+            _ => Decision::Accepted {
+                events: vec![rate_set_event],
+                intents: vec![notify],
+            },
+        },
+        TimeEntryState::Approved { .. } => Decision::Rejected {
+            reason: DecideError::Approved,
+        },
+        TimeEntryState::Deleted { .. } => Decision::Rejected {
+            reason: DecideError::Deleted,
+        },
+    }
+}
+
+pub struct SetHourlyRateDecider;
+
+impl Decider for SetHourlyRateDecider {
+    type State = TimeEntryState;
+    type Command = SetHourlyRate;
+    type Event = TimeEntryEvent;
+    type Intent = TimeEntryIntent;
+    type Error = DecideError;
+
+    fn initial_state() -> TimeEntryState {
+        TimeEntryState::None
+    }
+
+    fn evolve(state: TimeEntryState, event: TimeEntryEvent) -> TimeEntryState {
+        evolve(state, event)
+    }
+
+    fn decide(state: &TimeEntryState, command: SetHourlyRate) -> Decision {
+        decide_set_hourly_rate(state, command)
+    }
+}
+
+#[cfg(test)]
+mod decide_set_hourly_rate_tests {
+    use super::*;
+    use crate::modules::time_entries::core::hourly_rate::HourlyRate;
+    use crate::modules::time_entries::core::time_interval::TimeInterval;
+    use crate::tests::fixtures::commands::set_hourly_rate::SetHourlyRateBuilder;
+    use rstest::{fixture, rstest};
+
+    #[fixture]
+    fn command() -> SetHourlyRate {
+        SetHourlyRateBuilder::new().build()
+    }
+
+    fn registered(command: &SetHourlyRate, hourly_rate: Option<HourlyRate>) -> TimeEntryState {
+        TimeEntryState::Registered {
+            time_entry_id: command.time_entry_id.clone(),
+            user_id: command.user_id.clone(),
+            interval: TimeInterval::new(1_000, 2_000).unwrap(),
+            tag_ids: vec![],
+            created_at: 0,
+            created_by: command.updated_by.clone(),
+            hourly_rate,
+            breaks: vec![],
+        }
+    }
+
+    fn rate(currency: &str) -> Option<HourlyRate> {
+        Some(HourlyRate {
+            cents: 5_000,
+            currency: currency.to_string(),
+        })
+    }
+
+    #[rstest]
+    fn it_should_emit_initiated_and_rate_set_when_none(command: SetHourlyRate) {
+        match decide_set_hourly_rate(&TimeEntryState::None, command) {
+            Decision::Accepted { events, intents } => {
+                assert_eq!(events.len(), 2);
+                assert!(matches!(
+                    &events[0],
+                    TimeEntryEvent::TimeEntryInitiatedV1(_)
+                ));
+                assert!(matches!(
+                    &events[1],
+                    TimeEntryEvent::TimeEntryHourlyRateSetV1(e) if e.hourly_rate_cents == 9_500 && e.currency == "EUR"
+                ));
+                assert!(matches!(&intents[..], [TimeEntryIntent::NotifyUser { .. }]));
+            }
+            Decision::Rejected { .. } => panic!("expected Accepted"),
+        }
+    }
+
+    #[rstest]
+    fn it_should_emit_rate_set_when_draft(command: SetHourlyRate) {
+        let state = TimeEntryState::Draft {
+            time_entry_id: command.time_entry_id.clone(),
+            user_id: command.user_id.clone(),
+            started_at: None,
+            ended_at: None,
+            tag_ids: vec![],
+            created_at: 0,
+            created_by: command.updated_by.clone(),
+            hourly_rate: None,
+        };
+        match decide_set_hourly_rate(&state, command) {
+            Decision::Accepted { events, .. } => {
+                assert!(matches!(
+                    &events[..],
+                    [TimeEntryEvent::TimeEntryHourlyRateSetV1(_)]
+                ));
+            }
+            Decision::Rejected { .. } => panic!("expected Accepted"),
+        }
+    }
+
+    #[rstest]
+    #[case::unpriced(None)]
+    #[case::same_currency(rate("EUR"))]
+    fn it_should_emit_rate_set_when_registered(
+        command: SetHourlyRate,
+        #[case] hourly_rate: Option<HourlyRate>,
+    ) {
+        let state = registered(&command, hourly_rate);
+        match decide_set_hourly_rate(&state, command) {
+            Decision::Accepted { events, .. } => {
+                assert!(matches!(
+                    &events[..],
+                    [TimeEntryEvent::TimeEntryHourlyRateSetV1(_)]
+                ));
+            }
+            Decision::Rejected { .. } => panic!("expected Accepted"),
+        }
+    }
+
+    #[rstest]
+    fn it_should_reject_a_currency_change(command: SetHourlyRate) {
+        let state = registered(&command, rate("USD"));
+        match decide_set_hourly_rate(&state, command) {
+            Decision::Rejected { reason } => assert_eq!(
+                reason,
+                DecideError::CurrencyMismatch {
+                    current: "USD".to_string()
+                }
+            ),
+            Decision::Accepted { .. } => panic!("expected Rejected"),
+        }
+    }
+
+    #[rstest]
+    #[case::lowercase_currency(SetHourlyRateBuilder::new().currency("eur").build(), DecideError::InvalidCurrency)]
+    #[case::negative_rate(SetHourlyRateBuilder::new().hourly_rate_cents(-1).build(), DecideError::NegativeRate)]
+    fn it_should_reject_invalid_rates(
+        #[case] command: SetHourlyRate,
+        #[case] expected: DecideError,
+    ) {
+        match decide_set_hourly_rate(&TimeEntryState::None, command) {
+            Decision::Rejected { reason } => assert_eq!(reason, expected),
+            Decision::Accepted { .. } => panic!("expected Rejected"),
+        }
+    }
+
+    #[rstest]
+    fn it_should_reject_changes_to_an_approved_entry(command: SetHourlyRate) {
+        let state = TimeEntryState::Approved {
+            time_entry_id: command.time_entry_id.clone(),
+            user_id: command.user_id.clone(),
+            interval: TimeInterval::new(1_000, 2_000).unwrap(),
+            tag_ids: vec![],
+            created_at: 0,
+            created_by: command.updated_by.clone(),
+            hourly_rate: None,
+            approved_at: 3_000,
+            approved_by: "manager-0001".into(),
+            breaks: vec![],
+        };
+        match decide_set_hourly_rate(&state, command) {
+            Decision::Rejected { reason } => assert_eq!(reason, DecideError::Approved),
+            Decision::Accepted { .. } => panic!("expected Rejected"),
+        }
+    }
+}
@@ -0,0 +1,223 @@
+use crate::modules::time_entries::adapters::outbound::intent_outbox::TimeEntryIntentDispatcher;
+use crate::modules::time_entries::core::events::TimeEntryEvent;
+use crate::modules::time_entries::use_cases::set_hourly_rate::command::SetHourlyRate;
+use crate::modules::time_entries::use_cases::set_hourly_rate::decide::SetHourlyRateDecider;
+use crate::modules::time_entries::use_cases::set_hourly_rate::decision::DecideError;
+use crate::modules::time_entries::use_cases::user_time_entries::sharded_handler::{
+    PeriodLockStreams, UserShardedHandler,
+};
+use crate::shared::application::command_bus::CommandHandler;
+use crate::shared::application::event_sourced_handler::EventSourcedError;
+use crate::shared::infrastructure::clock::SharedClock;
+use crate::shared::infrastructure::event_bus::SharedEventBus;
+use crate::shared::infrastructure::event_store::EventStore;
+use crate::shared::infrastructure::intent_outbox::{DomainOutbox, OutboxError};
+use async_trait::async_trait;
+
+pub type ApplicationError = EventSourcedError<DecideError, OutboxError>;
+
+#[derive(Debug, Clone)]
+pub struct SetHourlyRateHandler<TEventStore, TOutbox>
+where
+    TEventStore: EventStore<TimeEntryEvent> + Send + Sync + 'static,
+    TOutbox: DomainOutbox + Send + Sync + 'static,
+{
+    inner:
+        UserShardedHandler<SetHourlyRateDecider, TEventStore, TimeEntryIntentDispatcher<TOutbox>>,
+}
+
+impl<TEventStore, TOutbox> SetHourlyRateHandler<TEventStore, TOutbox>
+where
+    TEventStore: EventStore<TimeEntryEvent> + Send + Sync + 'static,
+    TOutbox: DomainOutbox + Send + Sync + 'static,
+{
+    pub fn new(event_store: TEventStore, outbox: TOutbox) -> Self {
+        Self {
+            inner: UserShardedHandler::new(event_store, TimeEntryIntentDispatcher::new(outbox)),
+        }
+    }
+
+    /// Refuse changes to entries inside a locked payroll period.
+    pub fn with_period_locks(mut self, period_locks: PeriodLockStreams) -> Self {
+        self.inner = self.inner.with_period_locks(period_locks);
+        self
+    }
+
+    /// Read the time commands are recorded at from `clock` instead of the system clock.
+    pub fn with_clock(mut self, clock: SharedClock) -> Self {
+        self.inner = self.inner.with_clock(clock);
+        self
+    }
+
+    /// Announce the appended events on `event_bus`, for the projectors to pick up.
+    pub fn with_event_bus(mut self, event_bus: SharedEventBus<TimeEntryEvent>) -> Self {
+        self.inner = self.inner.with_event_bus(event_bus);
+        self
+    }
+
+    pub async fn handle(
+        &self,
+        stream_id: &str,
+        command: SetHourlyRate,
+    ) -> Result<(), ApplicationError> {
+        self.inner.handle(stream_id, command).await
+    }
+}
+
+#[async_trait]
+impl<TEventStore, TOutbox> CommandHandler<SetHourlyRate>
+    for SetHourlyRateHandler<TEventStore, TOutbox>
+where
+    TEventStore: EventStore<TimeEntryEvent> + Send + Sync + 'static,
+    TOutbox: DomainOutbox + Send + Sync + 'static,
+{
+    type Error = ApplicationError;
+
+    async fn handle(
+        &self,
+        stream_id: &str,
+        command: SetHourlyRate,
+    ) -> Result<(), ApplicationError> {
+        SetHourlyRateHandler::handle(self, stream_id, command).await
+    }
+}
+
+#[cfg(test)]
+mod set_hourly_rate_handler_tests {
+    use crate::modules::time_entries::core::events::TimeEntryEvent;
+    use crate::modules::time_entries::core::evolve::evolve;
+    use crate::modules::time_entries::core::hourly_rate::HourlyRate;
+    use crate::modules::time_entries::core::state::TimeEntryState;
+    use crate::modules::time_entries::use_cases::set_hourly_rate::decision::DecideError;
+    use crate::modules::time_entries::use_cases::set_hourly_rate::handler::{
+        ApplicationError, SetHourlyRateHandler,
+    };
+    use crate::shared::application::command_bus::{CommandBus, CommandEnvelope};
+    use crate::shared::infrastructure::clock::FixedClock;
+    use crate::shared::infrastructure::event_store::in_memory::InMemoryEventStore;
+    use crate::shared::infrastructure::event_store::{EventStore, EventStoreError};
+    use crate::shared::infrastructure::intent_outbox::in_memory::InMemoryDomainOutbox;
+    use crate::tests::fixtures::commands::set_hourly_rate::SetHourlyRateBuilder;
+    use rstest::{fixture, rstest};
+    use std::sync::Arc;
+
+    type BeforeEachReturn = (
+        &'static str,
+        InMemoryEventStore<TimeEntryEvent>,
+        InMemoryDomainOutbox,
+    );
+
+    #[fixture]
+    fn before_each() -> BeforeEachReturn {
+        (
+            "TimeEntry-te-fixed-0001",
+            InMemoryEventStore::<TimeEntryEvent>::new(),
+            InMemoryDomainOutbox::new(),
+        )
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_price_a_new_entry(before_each: BeforeEachReturn) {
+        let (stream_id, event_store, outbox) = before_each;
+        let handler = SetHourlyRateHandler::new(event_store.clone(), outbox);
+        handler
+            .handle(stream_id, SetHourlyRateBuilder::new().build())
+            .await
+            .unwrap();
+        handler
+            .handle(
+                stream_id,
+                SetHourlyRateBuilder::new()
+                    .hourly_rate_cents(12_000)
+                    .build(),
+            )
+            .await
+            .unwrap();
+
+        let stream = event_store.load(stream_id).await.unwrap();
+        let state = stream.events.into_iter().fold(TimeEntryState::None, evolve);
+        assert!(matches!(
+            state,
+            TimeEntryState::Draft {
+                hourly_rate: Some(HourlyRate { cents: 12_000, .. }),
+                ..
+            }
+        ));
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_refuse_to_reprice_in_another_currency(before_each: BeforeEachReturn) {
+        let (stream_id, event_store, outbox) = before_each;
+        let handler = SetHourlyRateHandler::new(event_store, outbox);
+        handler
+            .handle(stream_id, SetHourlyRateBuilder::new().build())
+            .await
+            .unwrap();
+
+        let result = handler
+            .handle(
+                stream_id,
+                SetHourlyRateBuilder::new().currency("USD").build(),
+            )
+            .await;
+
+        assert!(matches!(
+            result,
+            Err(ApplicationError::Domain(DecideError::CurrencyMismatch { current })) if current == "EUR"
+        ));
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_fail_if_event_store_is_offline(before_each: BeforeEachReturn) {
+        let (stream_id, event_store, outbox) = before_each;
+        event_store.toggle_offline();
+        let handler = SetHourlyRateHandler::new(event_store, outbox);
+
+        let result = handler
+            .handle(stream_id, SetHourlyRateBuilder::new().build())
+            .await;
+
+        assert!(matches!(
+            result,
+            Err(ApplicationError::VersionConflict(EventStoreError::Backend(
+                _
+            )))
+        ));
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_dispatch_through_the_command_bus(before_each: BeforeEachReturn) {
+        let (stream_id, event_store, outbox) = before_each;
+        let bus = CommandBus::new(SetHourlyRateHandler::new(event_store.clone(), outbox));
+
+        bus.dispatch(CommandEnvelope::new(
+            stream_id,
+            SetHourlyRateBuilder::new().build(),
+        ))
+        .await
+        .unwrap();
+
+        assert_eq!(event_store.load(stream_id).await.unwrap().events.len(), 2);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn handle_set_hourly_rate_stamps_commands_with_the_clock(before_each: BeforeEachReturn) {
+        let (stream_id, event_store, outbox) = before_each;
+        let now = 1_800_000_000_000;
+        let handler = SetHourlyRateHandler::new(event_store.clone(), outbox)
+            .with_clock(Arc::new(FixedClock::at(now)));
+
+        handler
+            .handle(stream_id, SetHourlyRateBuilder::new().updated_at(1).build())
+            .await
+            .expect("handle failed");
+
+        let stream = event_store.load(stream_id).await.expect("load failed");
+        assert!(stream.events.iter().all(|event| event.occurred_at() == now));
+    }
+}
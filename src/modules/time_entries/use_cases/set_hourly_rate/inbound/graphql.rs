@@ -0,0 +1,140 @@
+use async_graphql::{Context, Object, Result as GqlResult};
+
+use crate::modules::time_entries::use_cases::set_hourly_rate::command::SetHourlyRate;
+use crate::shared::core::primitives::TimeEntryId;
+use crate::shared::infrastructure::request_context::RequestContext;
+use crate::shell::state::AppState;
+
+#[cfg(test)]
+mod set_hourly_rate_graphql_inbound_tests {
+    use async_graphql::{EmptySubscription, Schema};
+
+    use crate::shared::auth::rbac::Scope;
+    use crate::shared::infrastructure::request_context::RequestContext;
+    use crate::shell::graphql::{MutationRoot, QueryRoot};
+    use crate::tests::fixtures::tags::make_test_app_state;
+
+    fn make_schema_from_state(
+        state: crate::shell::state::AppState,
+    ) -> Schema<QueryRoot, MutationRoot, EmptySubscription> {
+        Schema::build(
+            QueryRoot::default(),
+            MutationRoot::default(),
+            EmptySubscription,
+        )
+        .data(state)
+        .finish()
+    }
+
+    fn req_ctx() -> RequestContext {
+        RequestContext {
+            user_id: "u-1".to_string(),
+            tenant_id: "tenant-test".to_string(),
+            role: Default::default(),
+            scope: Default::default(),
+        }
+    }
+
+    fn mutation(time_entry_id: &str, currency: &str) -> String {
+        format!(
+            r#"mutation {{ setHourlyRate(timeEntryId: "{time_entry_id}", hourlyRateCents: 9500, currency: "{currency}") }}"#
+        )
+    }
+
+    #[tokio::test]
+    async fn returns_true_on_valid_input() {
+        let te_id = uuid::Uuid::now_v7().to_string();
+        let schema = make_schema_from_state(make_test_app_state());
+        let result = schema
+            .execute(async_graphql::Request::new(mutation(&te_id, "EUR")).data(req_ctx()))
+            .await;
+        assert!(result.errors.is_empty());
+        assert_eq!(result.data.to_string(), "{setHourlyRate: true}");
+    }
+
+    #[tokio::test]
+    async fn returns_forbidden_for_read_only_api_keys() {
+        let te_id = uuid::Uuid::now_v7().to_string();
+        let schema = make_schema_from_state(make_test_app_state());
+        let result = schema
+            .execute(
+                async_graphql::Request::new(mutation(&te_id, "EUR")).data(RequestContext {
+                    scope: Scope::ReadOnly,
+                    ..req_ctx()
+                }),
+            )
+            .await;
+        assert_eq!(result.errors[0].message, "Forbidden");
+    }
+
+    #[tokio::test]
+    async fn returns_error_on_non_v7_uuid() {
+        let schema = make_schema_from_state(make_test_app_state());
+        let result = schema
+            .execute(
+                async_graphql::Request::new(mutation(
+                    "550e8400-e29b-41d4-a716-446655440000",
+                    "EUR",
+                ))
+                .data(req_ctx()),
+            )
+            .await;
+        assert!(!result.errors.is_empty());
+    }
+
+    #[tokio::test]
+    async fn returns_error_on_invalid_currency() {
+        let te_id = uuid::Uuid::now_v7().to_string();
+        let schema = make_schema_from_state(make_test_app_state());
+        let result = schema
+            .execute(async_graphql::Request::new(mutation(&te_id, "euro")).data(req_ctx()))
+            .await;
+        assert_eq!(
+            result.errors[0].message,
+            "domain rejected: currency must be an ISO 4217 code such as EUR"
+        );
+    }
+}
+
+#[derive(Default)]
+pub struct SetHourlyRateMutation;
+
+#[Object]
+impl SetHourlyRateMutation {
+    async fn set_hourly_rate(
+        &self,
+        context: &Context<'_>,
+        time_entry_id: String,
+        hourly_rate_cents: i64,
+        currency: String,
+    ) -> GqlResult<bool> {
+        let time_entry_id = TimeEntryId::parse_v7(&time_entry_id)
+            .map_err(|_| async_graphql::Error::new("time_entry_id must be a valid UUID v7"))?;
+
+        let req_ctx = context
+            .data::<RequestContext>()
+            .map_err(|_| async_graphql::Error::new("Unauthorized"))?;
+        if !req_ctx.principal().can_register_for(&req_ctx.user_id) {
+            return Err(async_graphql::Error::new("Forbidden"));
+        }
+        let state = context.data_unchecked::<AppState>();
+        let stream_id = state
+            .stream_naming
+            .time_entry(Some(&req_ctx.tenant_id), &time_entry_id);
+
+        let command = SetHourlyRate::new(
+            time_entry_id,
+            req_ctx.user_id.clone().into(),
+            hourly_rate_cents,
+            currency,
+        );
+
+        state
+            .set_hourly_rate_handler
+            .handle(&stream_id, command)
+            .await
+            .map_err(|e| async_graphql::Error::new(e.to_string()))?;
+
+        Ok(true)
+    }
+}
@@ -0,0 +1,172 @@
+use axum::{
+    Json,
+    extract::{Path, State, rejection::JsonRejection},
+    http::StatusCode,
+    response::IntoResponse,
+};
+use serde::Deserialize;
+
+use crate::modules::time_entries::use_cases::set_hourly_rate::command::SetHourlyRate;
+use crate::modules::time_entries::use_cases::set_hourly_rate::decision::DecideError;
+use crate::modules::time_entries::use_cases::set_hourly_rate::handler::ApplicationError;
+use crate::shared::application::command_bus::{CommandBusError, CommandEnvelope};
+use crate::shared::core::primitives::TimeEntryId;
+use crate::shared::infrastructure::request_context::{IdempotencyKey, RequestContext};
+use crate::shell::state::AppState;
+
+#[derive(Deserialize)]
+pub struct SetHourlyRateBody {
+    pub hourly_rate_cents: i64,
+    pub currency: String,
+}
+
+/// PUT /time-entries/{id}/rate — prices a time entry per hour (creates if new)
+pub async fn handle_put(
+    State(state): State<AppState>,
+    request_ctx: RequestContext,
+    idempotency_key: IdempotencyKey,
+    Path(time_entry_id): Path<String>,
+    body: Result<Json<SetHourlyRateBody>, JsonRejection>,
+) -> impl IntoResponse {
+    if !request_ctx
+        .principal()
+        .can_register_for(&request_ctx.user_id)
+    {
+        return StatusCode::FORBIDDEN.into_response();
+    }
+    let Ok(time_entry_id) = TimeEntryId::parse_v7(&time_entry_id) else {
+        return StatusCode::UNPROCESSABLE_ENTITY.into_response();
+    };
+
+    let Json(body) = match body {
+        Ok(b) => b,
+        Err(_) => return StatusCode::UNPROCESSABLE_ENTITY.into_response(),
+    };
+
+    let stream_id = state
+        .stream_naming
+        .time_entry(Some(&request_ctx.tenant_id), &time_entry_id);
+
+    let command = SetHourlyRate::new(
+        time_entry_id,
+        request_ctx.user_id.clone().into(),
+        body.hourly_rate_cents,
+        body.currency,
+    );
+
+    let mut envelope = CommandEnvelope::new(stream_id, command)
+        .with_user_id(request_ctx.user_id)
+        .with_tenant_id(request_ctx.tenant_id);
+    envelope.idempotency_key = idempotency_key.0;
+
+    match state
+        .command_pipeline
+        .dispatch(
+            "set_hourly_rate",
+            state.set_hourly_rate_handler.clone(),
+            envelope,
+        )
+        .await
+    {
+        Ok(()) => StatusCode::OK.into_response(),
+        Err(CommandBusError::Unauthorized) => StatusCode::UNAUTHORIZED.into_response(),
+        Err(CommandBusError::Handler(ApplicationError::Domain(
+            DecideError::InvalidCurrency | DecideError::NegativeRate,
+        ))) => StatusCode::UNPROCESSABLE_ENTITY.into_response(),
+        Err(CommandBusError::Handler(ApplicationError::Domain(_))) => {
+            StatusCode::CONFLICT.into_response()
+        }
+        Err(_) => StatusCode::INTERNAL_SERVER_ERROR.into_response(),
+    }
+}
+
+#[cfg(test)]
+mod set_hourly_rate_http_inbound_tests {
+    use axum::{
+        Router,
+        body::Body,
+        http::{Request, StatusCode},
+        routing::put,
+    };
+    use rstest::rstest;
+    use tower::ServiceExt;
+
+    use super::handle_put;
+    use crate::shell::state::AppState;
+    use crate::tests::fixtures::tags::{make_test_app_state, make_test_app_state_with_store};
+
+    fn app(state: AppState) -> Router {
+        Router::new()
+            .route("/time-entries/{id}/rate", put(handle_put))
+            .with_state(state)
+    }
+
+    async fn send(state: AppState, te_id: &str, body: &str) -> StatusCode {
+        app(state)
+            .oneshot(
+                Request::builder()
+                    .method("PUT")
+                    .uri(format!("/time-entries/{te_id}/rate"))
+                    .header("content-type", "application/json")
+                    .header("x-user-id", "u-1")
+                    .header("x-tenant-id", "tenant-test")
+                    .body(Body::from(body.to_string()))
+                    .unwrap(),
+            )
+            .await
+            .unwrap()
+            .status()
+    }
+
+    const EUR: &str = r#"{"hourly_rate_cents":9500,"currency":"EUR"}"#;
+
+    #[tokio::test]
+    async fn put_returns_200_on_valid_request() {
+        let te_id = uuid::Uuid::now_v7().to_string();
+        assert_eq!(
+            send(make_test_app_state(), &te_id, EUR).await,
+            StatusCode::OK
+        );
+    }
+
+    #[tokio::test]
+    async fn put_returns_409_when_the_currency_changes() {
+        let state = make_test_app_state();
+        let te_id = uuid::Uuid::now_v7().to_string();
+        send(state.clone(), &te_id, EUR).await;
+
+        let status = send(
+            state,
+            &te_id,
+            r#"{"hourly_rate_cents":9500,"currency":"USD"}"#,
+        )
+        .await;
+
+        assert_eq!(status, StatusCode::CONFLICT);
+    }
+
+    #[rstest]
+    #[case::non_uuid("not-a-uuid", EUR)]
+    #[case::non_v7_uuid("550e8400-e29b-41d4-a716-446655440000", EUR)]
+    #[case::invalid_json(&uuid::Uuid::now_v7().to_string(), "not-json")]
+    #[case::invalid_currency(&uuid::Uuid::now_v7().to_string(), r#"{"hourly_rate_cents":9500,"currency":"euro"}"#)]
+    #[case::negative_rate(&uuid::Uuid::now_v7().to_string(), r#"{"hourly_rate_cents":-1,"currency":"EUR"}"#)]
+    #[tokio::test]
+    async fn put_returns_422_on_invalid_input(#[case] te_id: &str, #[case] body: &str) {
+        assert_eq!(
+            send(make_test_app_state(), te_id, body).await,
+            StatusCode::UNPROCESSABLE_ENTITY
+        );
+    }
+
+    #[tokio::test]
+    async fn put_returns_500_when_event_store_offline() {
+        let (state, event_store) = make_test_app_state_with_store();
+        event_store.toggle_offline();
+        let te_id = uuid::Uuid::now_v7().to_string();
+        assert_eq!(
+            send(state, &te_id, EUR).await,
+            StatusCode::INTERNAL_SERVER_ERROR
+        );
+    }
+}
@@ -1,7 +1,45 @@
+use crate::modules::time_entries::core::policies::{RoundedCommand, Rounding};
+use crate::shared::application::server_time::StampedCommand;
+use crate::shared::core::primitives::{TimeEntryId, Timestamp, UserId};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct SetStartedAt {
-    pub time_entry_id: String,
-    pub user_id: String,
+    pub time_entry_id: TimeEntryId,
+    pub user_id: UserId,
+    /// As supplied by the client; the decider records it rounded per `rounding`.
     pub started_at: i64,
+    pub rounding: Option<Rounding>,
+    /// Stamped by the handler from its clock.
     pub updated_at: i64,
-    pub updated_by: String,
+    pub updated_by: UserId,
+}
+
+impl SetStartedAt {
+    /// `user_id` sets the start of their own entry; the handler stamps `updated_at`.
+    pub fn new(time_entry_id: TimeEntryId, user_id: UserId, started_at: i64) -> Self {
+        Self {
+            time_entry_id,
+            updated_by: user_id.clone(),
+            user_id,
+            started_at,
+            rounding: None,
+            updated_at: 0,
+        }
+    }
+}
+
+impl StampedCommand for SetStartedAt {
+    fn stamp(&mut self, now: Timestamp) {
+        self.updated_at = now.as_millis();
+    }
+
+    fn client_instants(&self) -> Vec<i64> {
+        vec![self.started_at]
+    }
+}
+
+impl RoundedCommand for SetStartedAt {
+    fn set_rounding(&mut self, rounding: Rounding) {
+        self.rounding = Some(rounding);
+    }
 }
@@ -1,16 +1,23 @@
+use crate::modules::time_entries::core::breaks::ensure_valid_breaks;
 use crate::modules::time_entries::core::events::TimeEntryEvent;
 use crate::modules::time_entries::core::events::v1::time_entry_initiated::TimeEntryInitiatedV1;
 use crate::modules::time_entries::core::events::v1::time_entry_registered::TimeEntryRegisteredV1;
 use crate::modules::time_entries::core::events::v1::time_entry_start_set::TimeEntryStartSetV1;
+use crate::modules::time_entries::core::evolve::evolve;
 use crate::modules::time_entries::core::intents::TimeEntryIntent;
+use crate::modules::time_entries::core::policies::round_instant;
 use crate::modules::time_entries::core::state::TimeEntryState;
+use crate::modules::time_entries::core::time_interval::TimeInterval;
 use crate::modules::time_entries::use_cases::set_started_at::command::SetStartedAt;
 use crate::modules::time_entries::use_cases::set_started_at::decision::{DecideError, Decision};
+use crate::shared::core::decider::Decider;
 
 pub fn decide_set_started_at(state: &TimeEntryState, command: SetStartedAt) -> Decision {
+    let (started_at, raw_started_at) = round_instant(command.rounding, command.started_at);
     let start_set_event = TimeEntryEvent::TimeEntryStartSetV1(TimeEntryStartSetV1 {
         time_entry_id: command.time_entry_id.clone(),
-        started_at: command.started_at,
+        started_at,
+        raw_started_at,
         updated_at: command.updated_at,
         updated_by: command.updated_by.clone(),
     });
@@ -35,7 +42,7 @@ pub fn decide_set_started_at(state: &TimeEntryState, command: SetStartedAt) -> D
         TimeEntryState::Draft {
             ended_at: Some(e), ..
         } => {
-            if command.started_at >= *e {
+            if TimeInterval::new(started_at, *e).is_err() {
                 return Decision::Rejected {
                     reason: DecideError::InvalidInterval,
                 };
@@ -52,23 +59,59 @@ pub fn decide_set_started_at(state: &TimeEntryState, command: SetStartedAt) -> D
                 }],
             }
         }
-        TimeEntryState::Registered { ended_at, .. } => {
-            if command.started_at >= *ended_at {
+        TimeEntryState::Registered {
+            interval, breaks, ..
+        } => {
+            let Ok(moved) = interval.with_start(started_at) else {
                 return Decision::Rejected {
                     reason: DecideError::InvalidInterval,
                 };
+            };
+            if let Err(reason) = ensure_valid_breaks(&moved, breaks) {
+                return Decision::Rejected {
+                    reason: reason.into(),
+                };
             }
             Decision::Accepted {
                 events: vec![start_set_event],
                 intents: vec![],
             }
         }
+        TimeEntryState::Approved { .. } => Decision::Rejected {
+            reason: DecideError::Approved,
+        },
+        TimeEntryState::Deleted { .. } => Decision::Rejected {
+            reason: DecideError::Deleted,
+        },
+    }
+}
+
+pub struct SetStartedAtDecider;
+
+impl Decider for SetStartedAtDecider {
+    type State = TimeEntryState;
+    type Command = SetStartedAt;
+    type Event = TimeEntryEvent;
+    type Intent = TimeEntryIntent;
+    type Error = DecideError;
+
+    fn initial_state() -> TimeEntryState {
+        TimeEntryState::None
+    }
+
+    fn evolve(state: TimeEntryState, event: TimeEntryEvent) -> TimeEntryState {
+        evolve(state, event)
+    }
+
+    fn decide(state: &TimeEntryState, command: SetStartedAt) -> Decision {
+        decide_set_started_at(state, command)
     }
 }
 
 #[cfg(test)]
 mod decide_set_started_at_tests {
     use super::*;
+    use crate::modules::time_entries::core::policies::{Rounding, RoundingMode};
     use crate::tests::fixtures::commands::set_started_at::SetStartedAtBuilder;
     use rstest::{fixture, rstest};
 
@@ -104,6 +147,7 @@ mod decide_set_started_at_tests {
             tag_ids: vec![],
             created_at: 0,
             created_by: command.updated_by.clone(),
+            hourly_rate: None,
         };
         let decision = decide_set_started_at(&state, command);
         match decision {
@@ -127,6 +171,7 @@ mod decide_set_started_at_tests {
             tag_ids: vec![],
             created_at: 0,
             created_by: command.updated_by.clone(),
+            hourly_rate: None,
         };
         let decision = decide_set_started_at(&state, command);
         match decision {
@@ -155,6 +200,7 @@ mod decide_set_started_at_tests {
             tag_ids: vec![],
             created_at: 0,
             created_by: command.updated_by.clone(),
+            hourly_rate: None,
         };
         let decision = decide_set_started_at(&state, command);
         assert!(matches!(
@@ -170,11 +216,12 @@ mod decide_set_started_at_tests {
         let state = TimeEntryState::Registered {
             time_entry_id: command.time_entry_id.clone(),
             user_id: command.user_id.clone(),
-            started_at: command.started_at,
-            ended_at: command.started_at + 100_000,
+            interval: TimeInterval::new(command.started_at, command.started_at + 100_000).unwrap(),
             tag_ids: vec![],
             created_at: 0,
             created_by: command.updated_by.clone(),
+            hourly_rate: None,
+            breaks: vec![],
         };
         let decision = decide_set_started_at(&state, command);
         match decision {
@@ -193,11 +240,12 @@ mod decide_set_started_at_tests {
         let state = TimeEntryState::Registered {
             time_entry_id: command.time_entry_id.clone(),
             user_id: command.user_id.clone(),
-            started_at: 1_000,
-            ended_at: 3_000,
+            interval: TimeInterval::new(1_000, 3_000).unwrap(),
             tag_ids: vec![],
             created_at: 0,
             created_by: command.updated_by.clone(),
+            hourly_rate: None,
+            breaks: vec![],
         };
         let decision = decide_set_started_at(&state, command);
         assert!(matches!(
@@ -214,11 +262,12 @@ mod decide_set_started_at_tests {
         let state = TimeEntryState::Registered {
             time_entry_id: command.time_entry_id.clone(),
             user_id: command.user_id.clone(),
-            started_at: 1_000,
-            ended_at: 2_000,
+            interval: TimeInterval::new(1_000, 2_000).unwrap(),
             tag_ids: vec![],
             created_at: 0,
             created_by: command.updated_by.clone(),
+            hourly_rate: None,
+            breaks: vec![],
         };
         let decision = decide_set_started_at(&state, command);
         assert!(matches!(
@@ -228,4 +277,80 @@ mod decide_set_started_at_tests {
             }
         ));
     }
+
+    #[rstest]
+    fn it_should_reject_changes_to_an_approved_entry(command: SetStartedAt) {
+        let state = TimeEntryState::Approved {
+            time_entry_id: command.time_entry_id.clone(),
+            user_id: command.user_id.clone(),
+            interval: TimeInterval::new(1_000, 2_000).unwrap(),
+            tag_ids: vec![],
+            created_at: 0,
+            created_by: command.updated_by.clone(),
+            hourly_rate: None,
+            approved_at: 3_000,
+            approved_by: "manager-0001".into(),
+            breaks: vec![],
+        };
+        match decide_set_started_at(&state, command) {
+            Decision::Rejected { reason } => assert_eq!(reason, DecideError::Approved),
+            Decision::Accepted { .. } => panic!("expected Rejected"),
+        }
+    }
+
+    const QUARTER_HOUR: Rounding = Rounding {
+        increment_minutes: 15,
+        mode: RoundingMode::Nearest,
+    };
+
+    #[rstest]
+    #[case::unrounded(None, 1_000_000, None)]
+    #[case::rounded(Some(QUARTER_HOUR), 900_000, Some(1_000_000))]
+    fn it_should_record_the_rounded_start_and_the_start_as_given(
+        #[case] rounding: Option<Rounding>,
+        #[case] started_at: i64,
+        #[case] raw_started_at: Option<i64>,
+    ) {
+        let mut command = SetStartedAtBuilder::new().started_at(1_000_000).build();
+        command.rounding = rounding;
+
+        match decide_set_started_at(&TimeEntryState::None, command) {
+            Decision::Accepted { events, .. } => match &events[1] {
+                TimeEntryEvent::TimeEntryStartSetV1(event) => {
+                    assert_eq!(event.started_at, started_at);
+                    assert_eq!(event.raw_started_at, raw_started_at);
+                }
+                other => panic!("expected TimeEntryStartSetV1, got {other:?}"),
+            },
+            Decision::Rejected { .. } => panic!("expected Accepted"),
+        }
+    }
+
+    #[rstest]
+    fn it_should_reject_a_start_that_rounds_onto_the_end() {
+        let command = SetStartedAtBuilder::new()
+            .started_at(1_000_000)
+            .rounding(Rounding {
+                increment_minutes: 15,
+                mode: RoundingMode::Up,
+            })
+            .build();
+        let state = TimeEntryState::Registered {
+            time_entry_id: command.time_entry_id.clone(),
+            user_id: command.user_id.clone(),
+            interval: TimeInterval::new(0, 1_800_000).unwrap(),
+            tag_ids: vec![],
+            created_at: 0,
+            created_by: command.updated_by.clone(),
+            hourly_rate: None,
+            breaks: vec![],
+        };
+
+        assert!(matches!(
+            decide_set_started_at(&state, command),
+            Decision::Rejected {
+                reason: DecideError::InvalidInterval
+            }
+        ));
+    }
 }
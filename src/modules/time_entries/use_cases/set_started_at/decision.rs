@@ -1,19 +1,47 @@
+use crate::modules::time_entries::core::breaks::BreakError;
+use crate::modules::time_entries::core::day_capacity::DayCapacityError;
+use crate::modules::time_entries::core::days_off::DayOffError;
 use crate::modules::time_entries::core::events::TimeEntryEvent;
 use crate::modules::time_entries::core::intents::TimeEntryIntent;
+use crate::modules::time_entries::core::period_locks::PeriodLockError;
+use crate::modules::time_entries::core::policies::PolicyError;
+use crate::modules::time_entries::core::user_time_entries::UserTimeEntriesError;
+use crate::shared::application::server_time::ClockSkewError;
+use crate::shared::core::decider;
 use thiserror::Error;
 
 #[derive(Debug, Error, PartialEq, Eq)]
 pub enum DecideError {
     #[error("interval is invalid: started_at must be less than ended_at")]
     InvalidInterval,
-}
 
-pub enum Decision {
-    Accepted {
-        events: Vec<TimeEntryEvent>,
-        intents: Vec<TimeEntryIntent>,
-    },
-    Rejected {
-        reason: DecideError,
-    },
+    #[error("time entry is approved and can no longer be changed")]
+    Approved,
+
+    #[error("time entry is deleted")]
+    Deleted,
+
+    /// Moving a bound of a registered entry must keep its breaks inside it.
+    #[error(transparent)]
+    Breaks(#[from] BreakError),
+
+    #[error(transparent)]
+    UserTimeEntries(#[from] UserTimeEntriesError),
+
+    #[error(transparent)]
+    PeriodLocked(#[from] PeriodLockError),
+
+    #[error(transparent)]
+    Policy(#[from] PolicyError),
+
+    #[error(transparent)]
+    DayOff(#[from] DayOffError),
+
+    #[error(transparent)]
+    OverCapacity(#[from] DayCapacityError),
+
+    #[error(transparent)]
+    ClockSkew(#[from] ClockSkewError),
 }
+
+pub type Decision = decider::Decision<TimeEntryEvent, TimeEntryIntent, DecideError>;
@@ -4,16 +4,17 @@ use axum::{
     http::StatusCode,
     response::IntoResponse,
 };
-use chrono::Utc;
-use serde::Deserialize;
-use uuid::{Uuid, Version};
+use serde::{Deserialize, Serialize};
 
 use crate::modules::time_entries::use_cases::set_started_at::command::SetStartedAt;
+use crate::modules::time_entries::use_cases::set_started_at::decision::DecideError;
 use crate::modules::time_entries::use_cases::set_started_at::handler::ApplicationError;
-use crate::shared::infrastructure::request_context::RequestContext;
+use crate::shared::application::command_bus::{CommandBusError, CommandEnvelope};
+use crate::shared::core::primitives::TimeEntryId;
+use crate::shared::infrastructure::request_context::{IdempotencyKey, RequestContext};
 use crate::shell::state::AppState;
 
-#[derive(Deserialize)]
+#[derive(Serialize, Deserialize)]
 pub struct SetStartedAtBody {
     pub started_at: i64,
 }
@@ -22,39 +23,57 @@ pub struct SetStartedAtBody {
 pub async fn handle_put(
     State(state): State<AppState>,
     request_ctx: RequestContext,
+    idempotency_key: IdempotencyKey,
     Path(time_entry_id): Path<String>,
     body: Result<Json<SetStartedAtBody>, JsonRejection>,
 ) -> impl IntoResponse {
-    let is_valid_v7 = Uuid::parse_str(&time_entry_id)
-        .ok()
-        .filter(|u| u.get_version() == Some(Version::SortRand))
-        .is_some();
-    if !is_valid_v7 {
-        return StatusCode::UNPROCESSABLE_ENTITY.into_response();
+    if !request_ctx
+        .principal()
+        .can_register_for(&request_ctx.user_id)
+    {
+        return StatusCode::FORBIDDEN.into_response();
     }
+    let Ok(time_entry_id) = TimeEntryId::parse_v7(&time_entry_id) else {
+        return StatusCode::UNPROCESSABLE_ENTITY.into_response();
+    };
 
     let Json(body) = match body {
         Ok(b) => b,
         Err(_) => return StatusCode::UNPROCESSABLE_ENTITY.into_response(),
     };
 
-    let stream_id = format!("TimeEntry-{time_entry_id}");
+    let stream_id = state
+        .stream_naming
+        .time_entry(Some(&request_ctx.tenant_id), &time_entry_id);
 
-    let command = SetStartedAt {
-        time_entry_id: time_entry_id.clone(),
-        user_id: request_ctx.user_id.clone(),
-        started_at: body.started_at,
-        updated_at: Utc::now().timestamp_millis(),
-        updated_by: request_ctx.user_id,
-    };
+    let command = SetStartedAt::new(
+        time_entry_id,
+        request_ctx.user_id.clone().into(),
+        body.started_at,
+    );
+
+    let mut envelope = CommandEnvelope::new(stream_id, command)
+        .with_user_id(request_ctx.user_id)
+        .with_tenant_id(request_ctx.tenant_id);
+    envelope.idempotency_key = idempotency_key.0;
 
     match state
-        .set_started_at_handler
-        .handle(&stream_id, command)
+        .command_pipeline
+        .dispatch(
+            "set_started_at",
+            state.set_started_at_handler.clone(),
+            envelope,
+        )
         .await
     {
         Ok(()) => StatusCode::OK.into_response(),
-        Err(ApplicationError::Domain(_)) => StatusCode::CONFLICT.into_response(),
+        Err(CommandBusError::Unauthorized) => StatusCode::UNAUTHORIZED.into_response(),
+        Err(CommandBusError::Handler(ApplicationError::Domain(DecideError::ClockSkew(_)))) => {
+            StatusCode::UNPROCESSABLE_ENTITY.into_response()
+        }
+        Err(CommandBusError::Handler(ApplicationError::Domain(_))) => {
+            StatusCode::CONFLICT.into_response()
+        }
         Err(_) => StatusCode::INTERNAL_SERVER_ERROR.into_response(),
     }
 }
@@ -70,16 +89,17 @@ mod set_started_at_http_inbound_tests {
     use tower::ServiceExt;
 
     use super::handle_put;
+    use crate::shared::core::stream_naming::{DefaultStreamNaming, StreamNaming};
     use crate::shell::state::AppState;
-    use crate::tests::fixtures::tags::make_test_app_state;
+    use crate::tests::fixtures::tags::{make_test_app_state, make_test_app_state_with_store};
 
     fn make_test_state() -> AppState {
         make_test_app_state()
     }
 
     fn make_offline_state() -> AppState {
-        let state = make_test_app_state();
-        state.event_store.toggle_offline();
+        let (state, event_store) = make_test_app_state_with_store();
+        event_store.toggle_offline();
         state
     }
 
@@ -114,6 +134,69 @@ mod set_started_at_http_inbound_tests {
         assert_eq!(response.status(), StatusCode::OK);
     }
 
+    #[tokio::test]
+    async fn put_returns_409_when_another_timer_is_running() {
+        let app = app(make_test_state());
+        let mut statuses = vec![];
+        for _ in 0..2 {
+            let te_id = valid_v7_id();
+            let response = app
+                .clone()
+                .oneshot(
+                    Request::builder()
+                        .method("PUT")
+                        .uri(format!("/time-entries/{te_id}/start"))
+                        .header("content-type", "application/json")
+                        .header("x-user-id", "u-1")
+                        .header("x-tenant-id", "tenant-test")
+                        .body(Body::from(r#"{"started_at":1000}"#))
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+            statuses.push(response.status());
+        }
+
+        assert_eq!(statuses, vec![StatusCode::OK, StatusCode::CONFLICT]);
+    }
+
+    #[tokio::test]
+    async fn put_runs_a_command_repeated_with_the_same_idempotency_key_once() {
+        use crate::shared::infrastructure::event_store::EventStore;
+
+        let state = make_test_state();
+        let te_id = valid_v7_id();
+        let put = |started_at: i64| {
+            Request::builder()
+                .method("PUT")
+                .uri(format!("/time-entries/{te_id}/start"))
+                .header("content-type", "application/json")
+                .header("x-user-id", "u-1")
+                .header("x-tenant-id", "tenant-test")
+                .header("idempotency-key", "k-1")
+                .body(Body::from(format!(r#"{{"started_at":{started_at}}}"#)))
+                .unwrap()
+        };
+
+        let stream_id = state.stream_naming.time_entry(Some("tenant-test"), &te_id);
+
+        let first = app(state.clone()).oneshot(put(1_000)).await.unwrap();
+        let handled = state.event_store.load(&stream_id).await.unwrap();
+        let retried = app(state.clone()).oneshot(put(2_000)).await.unwrap();
+
+        assert_eq!(first.status(), StatusCode::OK);
+        assert_eq!(retried.status(), StatusCode::OK);
+        let stream = state.event_store.load(&stream_id).await.unwrap();
+        assert_eq!(stream.version, handled.version);
+        assert_eq!(
+            state
+                .command_pipeline
+                .metrics()
+                .outcomes("set_started_at", "tenant-test", "accepted"),
+            1
+        );
+    }
+
     #[tokio::test]
     async fn put_returns_409_on_invalid_interval() {
         let state = make_test_app_state();
@@ -122,10 +205,10 @@ mod set_started_at_http_inbound_tests {
         use crate::tests::fixtures::commands::set_ended_at::SetEndedAtBuilder;
 
         let te_id = valid_v7_id();
-        let stream_id = format!("TimeEntry-{te_id}");
+        let stream_id = DefaultStreamNaming.time_entry(None, &te_id);
 
         // Seed a draft with ended_at=1000 via the handler directly
-        SetEndedAtHandler::new("t", state.event_store.clone(), InMemoryDomainOutbox::new())
+        SetEndedAtHandler::new(state.event_store.clone(), InMemoryDomainOutbox::new())
             .handle(
                 &stream_id,
                 SetEndedAtBuilder::new()
@@ -155,6 +238,27 @@ mod set_started_at_http_inbound_tests {
         assert_eq!(response.status(), StatusCode::CONFLICT);
     }
 
+    #[tokio::test]
+    async fn put_returns_422_when_started_at_is_far_ahead_of_server_time() {
+        let te_id = valid_v7_id();
+        // 2100-01-01
+        let body = r#"{"started_at":4102444800000}"#;
+        let response = app(make_test_state())
+            .oneshot(
+                Request::builder()
+                    .method("PUT")
+                    .uri(format!("/time-entries/{te_id}/start"))
+                    .header("content-type", "application/json")
+                    .header("x-user-id", "u-1")
+                    .header("x-tenant-id", "tenant-test")
+                    .body(Body::from(body))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
     #[tokio::test]
     async fn put_returns_422_on_non_uuid() {
         let body = r#"{"started_at":1000}"#;
@@ -1,7 +1,35 @@
+use crate::modules::time_entries::core::policies::RoundedCommand;
+use crate::modules::time_entries::core::tag::Tag;
+use crate::shared::application::server_time::StampedCommand;
+use crate::shared::core::primitives::{TimeEntryId, Timestamp, UserId};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct SetTimeEntryTags {
-    pub time_entry_id: String,
-    pub user_id: String,
-    pub tag_ids: Vec<String>,
+    pub time_entry_id: TimeEntryId,
+    pub user_id: UserId,
+    pub tag_ids: Vec<Tag>,
+    /// Stamped by the handler from its clock.
     pub updated_at: i64,
-    pub updated_by: String,
+    pub updated_by: UserId,
 }
+
+impl SetTimeEntryTags {
+    /// `user_id` tags their own entry; the handler stamps `updated_at`.
+    pub fn new(time_entry_id: TimeEntryId, user_id: UserId, tag_ids: Vec<Tag>) -> Self {
+        Self {
+            time_entry_id,
+            updated_by: user_id.clone(),
+            user_id,
+            tag_ids,
+            updated_at: 0,
+        }
+    }
+}
+
+impl StampedCommand for SetTimeEntryTags {
+    fn stamp(&mut self, now: Timestamp) {
+        self.updated_at = now.as_millis();
+    }
+}
+
+impl RoundedCommand for SetTimeEntryTags {}
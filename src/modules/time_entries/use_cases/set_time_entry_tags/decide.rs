@@ -1,10 +1,14 @@
 use crate::modules::time_entries::core::events::TimeEntryEvent;
 use crate::modules::time_entries::core::events::v1::time_entry_initiated::TimeEntryInitiatedV1;
 use crate::modules::time_entries::core::events::v1::time_entry_tags_set::TimeEntryTagsSetV1;
+use crate::modules::time_entries::core::evolve::evolve;
 use crate::modules::time_entries::core::intents::TimeEntryIntent;
 use crate::modules::time_entries::core::state::TimeEntryState;
 use crate::modules::time_entries::use_cases::set_time_entry_tags::command::SetTimeEntryTags;
-use crate::modules::time_entries::use_cases::set_time_entry_tags::decision::Decision;
+use crate::modules::time_entries::use_cases::set_time_entry_tags::decision::{
+    DecideError, Decision,
+};
+use crate::shared::core::decider::Decider;
 
 pub fn decide_set_time_entry_tags(state: &TimeEntryState, command: SetTimeEntryTags) -> Decision {
     let tags_set_event = TimeEntryEvent::TimeEntryTagsSetV1(TimeEntryTagsSetV1 {
@@ -40,6 +44,34 @@ pub fn decide_set_time_entry_tags(state: &TimeEntryState, command: SetTimeEntryT
             events: vec![tags_set_event],
             intents: vec![notify],
         },
+        TimeEntryState::Approved { .. } => Decision::Rejected {
+            reason: DecideError::Approved,
+        },
+        TimeEntryState::Deleted { .. } => Decision::Rejected {
+            reason: DecideError::Deleted,
+        },
+    }
+}
+
+pub struct SetTimeEntryTagsDecider;
+
+impl Decider for SetTimeEntryTagsDecider {
+    type State = TimeEntryState;
+    type Command = SetTimeEntryTags;
+    type Event = TimeEntryEvent;
+    type Intent = TimeEntryIntent;
+    type Error = DecideError;
+
+    fn initial_state() -> TimeEntryState {
+        TimeEntryState::None
+    }
+
+    fn evolve(state: TimeEntryState, event: TimeEntryEvent) -> TimeEntryState {
+        evolve(state, event)
+    }
+
+    fn decide(state: &TimeEntryState, command: SetTimeEntryTags) -> Decision {
+        decide_set_time_entry_tags(state, command)
     }
 }
 
@@ -47,6 +79,8 @@ pub fn decide_set_time_entry_tags(state: &TimeEntryState, command: SetTimeEntryT
 mod decide_set_time_entry_tags_tests {
     use super::*;
     use crate::modules::time_entries::core::intents::TimeEntryIntent;
+    use crate::modules::time_entries::core::tag::Tag;
+    use crate::modules::time_entries::core::time_interval::TimeInterval;
     use crate::tests::fixtures::commands::set_time_entry_tags::SetTimeEntryTagsBuilder;
     use rstest::{fixture, rstest};
 
@@ -83,6 +117,7 @@ mod decide_set_time_entry_tags_tests {
             tag_ids: vec![],
             created_at: 0,
             created_by: command.updated_by.clone(),
+            hourly_rate: None,
         };
         let decision = decide_set_time_entry_tags(&state, command);
         match decision {
@@ -101,11 +136,12 @@ mod decide_set_time_entry_tags_tests {
         let state = TimeEntryState::Registered {
             time_entry_id: command.time_entry_id.clone(),
             user_id: command.user_id.clone(),
-            started_at: 1_000,
-            ended_at: 2_000,
+            interval: TimeInterval::new(1_000, 2_000).unwrap(),
             tag_ids: vec![],
             created_at: 0,
             created_by: command.updated_by.clone(),
+            hourly_rate: None,
+            breaks: vec![],
         };
         let decision = decide_set_time_entry_tags(&state, command);
         match decision {
@@ -129,9 +165,10 @@ mod decide_set_time_entry_tags_tests {
             user_id: command.user_id.clone(),
             started_at: None,
             ended_at: None,
-            tag_ids: vec!["old-tag".to_string()],
+            tag_ids: vec![Tag::parse("old-tag").unwrap()],
             created_at: 0,
             created_by: command.updated_by.clone(),
+            hourly_rate: None,
         };
         let decision = decide_set_time_entry_tags(&state, command);
         match decision {
@@ -152,9 +189,10 @@ mod decide_set_time_entry_tags_tests {
             user_id: command.user_id.clone(),
             started_at: None,
             ended_at: None,
-            tag_ids: vec!["tag-1".to_string()],
+            tag_ids: vec![Tag::parse("tag-1").unwrap()],
             created_at: 0,
             created_by: command.updated_by.clone(),
+            hourly_rate: None,
         };
         let decision = decide_set_time_entry_tags(&state, command);
         match decision {
@@ -166,4 +204,24 @@ mod decide_set_time_entry_tags_tests {
             Decision::Rejected { .. } => panic!("expected Accepted"),
         }
     }
+
+    #[rstest]
+    fn it_should_reject_changes_to_an_approved_entry(command: SetTimeEntryTags) {
+        let state = TimeEntryState::Approved {
+            time_entry_id: command.time_entry_id.clone(),
+            user_id: command.user_id.clone(),
+            interval: TimeInterval::new(1_000, 2_000).unwrap(),
+            tag_ids: vec![],
+            created_at: 0,
+            created_by: command.updated_by.clone(),
+            hourly_rate: None,
+            approved_at: 3_000,
+            approved_by: "manager-0001".into(),
+            breaks: vec![],
+        };
+        match decide_set_time_entry_tags(&state, command) {
+            Decision::Rejected { reason } => assert_eq!(reason, DecideError::Approved),
+            Decision::Accepted { .. } => panic!("expected Rejected"),
+        }
+    }
 }
@@ -1,16 +1,41 @@
+use crate::modules::time_entries::core::day_capacity::DayCapacityError;
+use crate::modules::time_entries::core::days_off::DayOffError;
 use crate::modules::time_entries::core::events::TimeEntryEvent;
 use crate::modules::time_entries::core::intents::TimeEntryIntent;
+use crate::modules::time_entries::core::period_locks::PeriodLockError;
+use crate::modules::time_entries::core::policies::PolicyError;
+use crate::modules::time_entries::core::user_time_entries::UserTimeEntriesError;
+use crate::shared::application::server_time::ClockSkewError;
+use crate::shared::core::decider;
 use thiserror::Error;
 
 #[derive(Debug, Error, PartialEq, Eq)]
-pub enum DecideError {}
-
-pub enum Decision {
-    Accepted {
-        events: Vec<TimeEntryEvent>,
-        intents: Vec<TimeEntryIntent>,
-    },
-    Rejected {
-        reason: DecideError,
-    },
+pub enum DecideError {
+    #[error("time entry is approved and can no longer be changed")]
+    Approved,
+
+    #[error("time entry is deleted")]
+    Deleted,
+
+    /// Required by `UserShardedHandler`; never produced, as tags do not claim intervals.
+    #[error(transparent)]
+    UserTimeEntries(#[from] UserTimeEntriesError),
+
+    #[error(transparent)]
+    PeriodLocked(#[from] PeriodLockError),
+
+    #[error(transparent)]
+    Policy(#[from] PolicyError),
+
+    /// Required by `UserShardedHandler`; never produced, as tags do not move intervals.
+    #[error(transparent)]
+    DayOff(#[from] DayOffError),
+
+    #[error(transparent)]
+    OverCapacity(#[from] DayCapacityError),
+
+    #[error(transparent)]
+    ClockSkew(#[from] ClockSkewError),
 }
+
+pub type Decision = decider::Decision<TimeEntryEvent, TimeEntryIntent, DecideError>;
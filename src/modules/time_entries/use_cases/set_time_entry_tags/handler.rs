@@ -1,30 +1,22 @@
-use crate::modules::time_entries::adapters::outbound::intent_outbox::dispatch_intents;
+use crate::modules::time_entries::adapters::outbound::intent_outbox::TimeEntryIntentDispatcher;
 use crate::modules::time_entries::core::events::TimeEntryEvent;
-use crate::modules::time_entries::core::evolve::evolve;
-use crate::modules::time_entries::core::state::TimeEntryState;
+use crate::modules::time_entries::core::policies::Policies;
 use crate::modules::time_entries::use_cases::set_time_entry_tags::command::SetTimeEntryTags;
-use crate::modules::time_entries::use_cases::set_time_entry_tags::decide::decide_set_time_entry_tags;
-use crate::modules::time_entries::use_cases::set_time_entry_tags::decision::{
-    DecideError, Decision,
+use crate::modules::time_entries::use_cases::set_time_entry_tags::decide::SetTimeEntryTagsDecider;
+use crate::modules::time_entries::use_cases::set_time_entry_tags::decision::DecideError;
+use crate::modules::time_entries::use_cases::user_time_entries::sharded_handler::{
+    PeriodLockStreams, UserShardedHandler,
 };
-use crate::shared::infrastructure::event_store::{EventStore, EventStoreError};
+use crate::shared::application::command_bus::CommandHandler;
+use crate::shared::application::event_sourced_handler::EventSourcedError;
+use crate::shared::infrastructure::clock::SharedClock;
+use crate::shared::infrastructure::event_bus::SharedEventBus;
+use crate::shared::infrastructure::event_store::EventStore;
 use crate::shared::infrastructure::intent_outbox::{DomainOutbox, OutboxError};
-use thiserror::Error;
+use crate::shared::infrastructure::policy_store::SharedPolicyStore;
+use async_trait::async_trait;
 
-#[derive(Debug, Error)]
-pub enum ApplicationError {
-    #[error(transparent)]
-    VersionConflict(#[from] EventStoreError),
-
-    #[error(transparent)]
-    Outbox(#[from] OutboxError),
-
-    #[error("domain rejected: {0}")]
-    Domain(DecideError),
-
-    #[error("unexpected: {0}")]
-    Unexpected(String),
-}
+pub type ApplicationError = EventSourcedError<DecideError, OutboxError>;
 
 #[derive(Debug, Clone)]
 pub struct SetTimeEntryTagsHandler<TEventStore, TOutbox>
@@ -32,9 +24,11 @@ where
     TEventStore: EventStore<TimeEntryEvent> + Send + Sync + 'static,
     TOutbox: DomainOutbox + Send + Sync + 'static,
 {
-    topic: String,
-    event_store: TEventStore,
-    outbox: TOutbox,
+    inner: UserShardedHandler<
+        SetTimeEntryTagsDecider,
+        TEventStore,
+        TimeEntryIntentDispatcher<TOutbox>,
+    >,
 }
 
 impl<TEventStore, TOutbox> SetTimeEntryTagsHandler<TEventStore, TOutbox>
@@ -42,71 +36,106 @@ where
     TEventStore: EventStore<TimeEntryEvent> + Send + Sync + 'static,
     TOutbox: DomainOutbox + Send + Sync + 'static,
 {
-    pub fn new(topic: impl Into<String>, event_store: TEventStore, outbox: TOutbox) -> Self {
+    pub fn new(event_store: TEventStore, outbox: TOutbox) -> Self {
         Self {
-            topic: topic.into(),
-            event_store,
-            outbox,
+            inner: UserShardedHandler::new(event_store, TimeEntryIntentDispatcher::new(outbox)),
         }
     }
 
+    /// Refuse changes to entries inside a locked payroll period.
+    pub fn with_period_locks(mut self, period_locks: PeriodLockStreams) -> Self {
+        self.inner = self.inner.with_period_locks(period_locks);
+        self
+    }
+
+    /// Hold each tenant to the policies in `store`, or to `defaults` where it has none.
+    pub fn with_policies(mut self, store: SharedPolicyStore, defaults: Policies) -> Self {
+        self.inner = self.inner.with_policies(store, defaults);
+        self
+    }
+
+    /// Read the time commands are recorded at from `clock` instead of the system clock.
+    pub fn with_clock(mut self, clock: SharedClock) -> Self {
+        self.inner = self.inner.with_clock(clock);
+        self
+    }
+
+    /// Announce the appended events on `event_bus`, for the projectors to pick up.
+    pub fn with_event_bus(mut self, event_bus: SharedEventBus<TimeEntryEvent>) -> Self {
+        self.inner = self.inner.with_event_bus(event_bus);
+        self
+    }
+
     pub async fn handle(
         &self,
         stream_id: &str,
         command: SetTimeEntryTags,
     ) -> Result<(), ApplicationError> {
-        let stream = self
-            .event_store
-            .load(stream_id)
+        self.inner.handle(stream_id, command).await
+    }
+
+    /// Handles the command under the policies of `tenant_id`.
+    pub async fn handle_for_tenant(
+        &self,
+        tenant_id: &str,
+        stream_id: &str,
+        command: SetTimeEntryTags,
+    ) -> Result<(), ApplicationError> {
+        self.inner
+            .handle_for_tenant(Some(tenant_id), stream_id, command)
             .await
-            .map_err(ApplicationError::VersionConflict)?;
-
-        let state = stream
-            .events
-            .iter()
-            .cloned()
-            .fold(TimeEntryState::None, evolve);
-
-        match decide_set_time_entry_tags(&state, command) {
-            Decision::Accepted { events, intents } => {
-                let events_len = events.len();
-                self.event_store
-                    .append(stream_id, stream.version, &events)
-                    .await
-                    .map_err(ApplicationError::VersionConflict)?;
-                dispatch_intents(
-                    &self.outbox,
-                    stream_id,
-                    stream.version,
-                    events_len,
-                    &self.topic,
-                    intents,
-                )
-                .await
-                .map_err(ApplicationError::Outbox)?;
-                Ok(())
-            }
-            Decision::Rejected { reason } => Err(ApplicationError::Domain(reason)),
-        }
+    }
+}
+
+#[async_trait]
+impl<TEventStore, TOutbox> CommandHandler<SetTimeEntryTags>
+    for SetTimeEntryTagsHandler<TEventStore, TOutbox>
+where
+    TEventStore: EventStore<TimeEntryEvent> + Send + Sync + 'static,
+    TOutbox: DomainOutbox + Send + Sync + 'static,
+{
+    type Error = ApplicationError;
+
+    async fn handle(
+        &self,
+        stream_id: &str,
+        command: SetTimeEntryTags,
+    ) -> Result<(), ApplicationError> {
+        SetTimeEntryTagsHandler::handle(self, stream_id, command).await
+    }
+
+    async fn handle_for_tenant(
+        &self,
+        tenant_id: &str,
+        stream_id: &str,
+        command: SetTimeEntryTags,
+    ) -> Result<(), ApplicationError> {
+        SetTimeEntryTagsHandler::handle_for_tenant(self, tenant_id, stream_id, command).await
     }
 }
 
 #[cfg(test)]
 mod set_time_entry_tags_handler_tests {
+    use crate::modules::time_entries::adapters::outbound::intent_outbox::OUTBOX_TOPIC;
     use crate::modules::time_entries::core::events::TimeEntryEvent;
     use crate::modules::time_entries::use_cases::set_time_entry_tags::handler::{
         ApplicationError, SetTimeEntryTagsHandler,
     };
+    use crate::shared::application::command_bus::CommandBus;
+    use crate::shared::application::command_bus::CommandEnvelope;
+    use crate::shared::application::command_bus::middleware::RetryOnConflictMiddleware;
+    use crate::shared::infrastructure::clock::FixedClock;
     use crate::shared::infrastructure::event_store::in_memory::InMemoryEventStore;
     use crate::shared::infrastructure::event_store::{EventStore, EventStoreError};
     use crate::shared::infrastructure::intent_outbox::in_memory::InMemoryDomainOutbox;
-    use crate::shared::infrastructure::intent_outbox::{DomainOutbox, OutboxError, OutboxRow};
+    use crate::shared::infrastructure::intent_outbox::{
+        DomainOutbox, OutboxError, OutboxRow, OutboxStatus,
+    };
     use crate::tests::fixtures::commands::set_time_entry_tags::SetTimeEntryTagsBuilder;
     use rstest::{fixture, rstest};
+    use std::sync::Arc;
     use tokio::join;
 
-    const TOPIC: &str = "time-entries";
-
     type BeforeEachReturn = (
         &'static str,
         InMemoryEventStore<TimeEntryEvent>,
@@ -125,7 +154,7 @@ mod set_time_entry_tags_handler_tests {
     #[tokio::test]
     async fn handle_set_time_entry_tags_creates_draft_on_new_stream(before_each: BeforeEachReturn) {
         let (stream_id, event_store, outbox) = before_each;
-        let handler = SetTimeEntryTagsHandler::new(TOPIC, event_store.clone(), outbox);
+        let handler = SetTimeEntryTagsHandler::new(event_store.clone(), outbox);
         handler
             .handle(stream_id, SetTimeEntryTagsBuilder::new().build())
             .await
@@ -141,7 +170,7 @@ mod set_time_entry_tags_handler_tests {
         before_each: BeforeEachReturn,
     ) {
         let (stream_id, event_store, outbox) = before_each;
-        let handler = SetTimeEntryTagsHandler::new(TOPIC, event_store.clone(), outbox);
+        let handler = SetTimeEntryTagsHandler::new(event_store.clone(), outbox);
         // First call creates draft
         handler
             .handle(stream_id, SetTimeEntryTagsBuilder::new().build())
@@ -169,7 +198,7 @@ mod set_time_entry_tags_handler_tests {
     ) {
         let (stream_id, event_store, outbox) = before_each;
         event_store.toggle_offline();
-        let handler = SetTimeEntryTagsHandler::new(TOPIC, event_store, outbox);
+        let handler = SetTimeEntryTagsHandler::new(event_store, outbox);
         let result = handler
             .handle(stream_id, SetTimeEntryTagsBuilder::new().build())
             .await;
@@ -193,17 +222,23 @@ mod set_time_entry_tags_handler_tests {
         // intent_offset = 2 - 1 = 1 → stream_version = 0 + (1 + 0) + 1 = 2
         outbox
             .enqueue(OutboxRow {
-                topic: TOPIC.to_string(),
+                topic: OUTBOX_TOPIC.to_string(),
                 event_type: "TimeEntryTagsSet".to_string(),
                 event_version: 1,
                 stream_id: stream_id.to_string(),
                 stream_version: 2,
+                intent_no: 0,
                 occurred_at: 0,
                 payload: serde_json::json!({}),
+                content_encoding: None,
+                status: OutboxStatus::Pending,
+                attempts: 0,
+                last_error: None,
+                published_at: None,
             })
             .await
             .unwrap();
-        let handler = SetTimeEntryTagsHandler::new(TOPIC, event_store, outbox);
+        let handler = SetTimeEntryTagsHandler::new(event_store, outbox);
         let result = handler
             .handle(stream_id, SetTimeEntryTagsBuilder::new().build())
             .await;
@@ -220,8 +255,8 @@ mod set_time_entry_tags_handler_tests {
         event_store.set_delay_append_ms(10);
         let es = event_store;
         let ob = outbox;
-        let handler1 = SetTimeEntryTagsHandler::new(TOPIC, es.clone(), ob.clone());
-        let handler2 = SetTimeEntryTagsHandler::new(TOPIC, es, ob);
+        let handler1 = SetTimeEntryTagsHandler::new(es.clone(), ob.clone());
+        let handler2 = SetTimeEntryTagsHandler::new(es, ob);
         let (result1, result2) = join!(
             handler1.handle(stream_id, SetTimeEntryTagsBuilder::new().build()),
             handler2.handle(stream_id, SetTimeEntryTagsBuilder::new().build())
@@ -242,4 +277,58 @@ mod set_time_entry_tags_handler_tests {
             e => panic!("unexpected error: {e:?}"),
         }
     }
+
+    #[rstest]
+    #[tokio::test]
+    async fn handle_set_time_entry_tags_retries_version_conflicts_via_command_bus(
+        before_each: BeforeEachReturn,
+    ) {
+        let (stream_id, event_store, outbox) = before_each;
+        event_store.set_delay_append_ms(10);
+        let bus = CommandBus::new(SetTimeEntryTagsHandler::new(event_store.clone(), outbox))
+            .with_middleware(RetryOnConflictMiddleware::new(
+                3,
+                |error: &ApplicationError| {
+                    matches!(
+                        error,
+                        ApplicationError::VersionConflict(EventStoreError::VersionMismatch { .. })
+                    )
+                },
+            ));
+        let (result1, result2) = join!(
+            bus.dispatch(CommandEnvelope::new(
+                stream_id,
+                SetTimeEntryTagsBuilder::new().build()
+            )),
+            bus.dispatch(CommandEnvelope::new(
+                stream_id,
+                SetTimeEntryTagsBuilder::new().build()
+            ))
+        );
+        assert!(result1.is_ok() && result2.is_ok());
+        let stream = event_store.load(stream_id).await.unwrap();
+        assert_eq!(stream.events.len(), 3);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn handle_set_time_entry_tags_stamps_commands_with_the_clock(
+        before_each: BeforeEachReturn,
+    ) {
+        let (stream_id, event_store, outbox) = before_each;
+        let now = 1_800_000_000_000;
+        let handler = SetTimeEntryTagsHandler::new(event_store.clone(), outbox)
+            .with_clock(Arc::new(FixedClock::at(now)));
+
+        handler
+            .handle(
+                stream_id,
+                SetTimeEntryTagsBuilder::new().updated_at(1).build(),
+            )
+            .await
+            .expect("handle failed");
+
+        let stream = event_store.load(stream_id).await.expect("load failed");
+        assert!(stream.events.iter().all(|event| event.occurred_at() == now));
+    }
 }
@@ -4,15 +4,17 @@ use axum::{
     http::StatusCode,
     response::IntoResponse,
 };
-use chrono::Utc;
-use serde::Deserialize;
-use uuid::{Uuid, Version};
+use serde::{Deserialize, Serialize};
 
+use crate::modules::time_entries::core::tag::Tag;
 use crate::modules::time_entries::use_cases::set_time_entry_tags::command::SetTimeEntryTags;
-use crate::shared::infrastructure::request_context::RequestContext;
+use crate::modules::time_entries::use_cases::set_time_entry_tags::handler::ApplicationError;
+use crate::shared::application::command_bus::{CommandBusError, CommandEnvelope};
+use crate::shared::core::primitives::TimeEntryId;
+use crate::shared::infrastructure::request_context::{IdempotencyKey, RequestContext};
 use crate::shell::state::AppState;
 
-#[derive(Deserialize)]
+#[derive(Serialize, Deserialize)]
 pub struct SetTimeEntryTagsBody {
     pub tag_ids: Vec<String>,
 }
@@ -21,38 +23,53 @@ pub struct SetTimeEntryTagsBody {
 pub async fn handle_put(
     State(state): State<AppState>,
     request_ctx: RequestContext,
+    idempotency_key: IdempotencyKey,
     Path(time_entry_id): Path<String>,
     body: Result<Json<SetTimeEntryTagsBody>, JsonRejection>,
 ) -> impl IntoResponse {
-    let is_valid_v7 = Uuid::parse_str(&time_entry_id)
-        .ok()
-        .filter(|u| u.get_version() == Some(Version::SortRand))
-        .is_some();
-    if !is_valid_v7 {
-        return StatusCode::UNPROCESSABLE_ENTITY.into_response();
+    if !request_ctx
+        .principal()
+        .can_register_for(&request_ctx.user_id)
+    {
+        return StatusCode::FORBIDDEN.into_response();
     }
+    let Ok(time_entry_id) = TimeEntryId::parse_v7(&time_entry_id) else {
+        return StatusCode::UNPROCESSABLE_ENTITY.into_response();
+    };
 
     let Json(body) = match body {
         Ok(b) => b,
         Err(_) => return StatusCode::UNPROCESSABLE_ENTITY.into_response(),
     };
+    let Ok(tag_ids) = Tag::parse_all(&body.tag_ids) else {
+        return StatusCode::UNPROCESSABLE_ENTITY.into_response();
+    };
 
-    let stream_id = format!("TimeEntry-{time_entry_id}");
+    let stream_id = state
+        .stream_naming
+        .time_entry(Some(&request_ctx.tenant_id), &time_entry_id);
 
-    let command = SetTimeEntryTags {
-        time_entry_id: time_entry_id.clone(),
-        user_id: request_ctx.user_id.clone(),
-        tag_ids: body.tag_ids,
-        updated_at: Utc::now().timestamp_millis(),
-        updated_by: request_ctx.user_id,
-    };
+    let command = SetTimeEntryTags::new(time_entry_id, request_ctx.user_id.clone().into(), tag_ids);
+
+    let mut envelope = CommandEnvelope::new(stream_id, command)
+        .with_user_id(request_ctx.user_id)
+        .with_tenant_id(request_ctx.tenant_id);
+    envelope.idempotency_key = idempotency_key.0;
 
     match state
-        .set_time_entry_tags_handler
-        .handle(&stream_id, command)
+        .command_pipeline
+        .dispatch(
+            "set_time_entry_tags",
+            state.set_time_entry_tags_handler.clone(),
+            envelope,
+        )
         .await
     {
         Ok(()) => StatusCode::OK.into_response(),
+        Err(CommandBusError::Unauthorized) => StatusCode::UNAUTHORIZED.into_response(),
+        Err(CommandBusError::Handler(ApplicationError::Domain(_))) => {
+            StatusCode::CONFLICT.into_response()
+        }
         Err(_) => StatusCode::INTERNAL_SERVER_ERROR.into_response(),
     }
 }
@@ -68,16 +85,21 @@ mod set_time_entry_tags_http_inbound_tests {
     use tower::ServiceExt;
 
     use super::handle_put;
+    use crate::modules::time_entries::core::events::TimeEntryEvent;
+    use crate::modules::time_entries::core::policies::Policies;
+    use crate::shared::core::stream_naming::{DefaultStreamNaming, StreamNaming};
+    use crate::shared::infrastructure::event_store::EventStore;
+    use crate::shared::infrastructure::policy_store::{PolicyStore, TenantPolicies};
     use crate::shell::state::AppState;
-    use crate::tests::fixtures::tags::make_test_app_state;
+    use crate::tests::fixtures::tags::{make_test_app_state, make_test_app_state_with_store};
 
     fn make_test_state() -> AppState {
         make_test_app_state()
     }
 
     fn make_offline_state() -> AppState {
-        let state = make_test_app_state();
-        state.event_store.toggle_offline();
+        let (state, event_store) = make_test_app_state_with_store();
+        event_store.toggle_offline();
         state
     }
 
@@ -112,6 +134,41 @@ mod set_time_entry_tags_http_inbound_tests {
         assert_eq!(response.status(), StatusCode::OK);
     }
 
+    #[tokio::test]
+    async fn put_returns_409_when_the_tenant_allows_fewer_tags() {
+        let state = make_test_state();
+        state
+            .policy_store
+            .put(TenantPolicies {
+                tenant_id: "tenant-test".to_string(),
+                policies: Policies {
+                    max_tags: Some(1),
+                    ..Policies::default()
+                },
+                updated_at: 0,
+                updated_by: "admin-1".to_string(),
+            })
+            .await
+            .unwrap();
+        let te_id = valid_v7_id();
+        let body = r#"{"tag_ids":["tag-1","tag-2"]}"#;
+        let response = app(state)
+            .oneshot(
+                Request::builder()
+                    .method("PUT")
+                    .uri(format!("/time-entries/{te_id}/tags"))
+                    .header("content-type", "application/json")
+                    .header("x-user-id", "u-1")
+                    .header("x-tenant-id", "tenant-test")
+                    .body(Body::from(body))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+    }
+
     #[tokio::test]
     async fn put_returns_200_with_empty_tags() {
         let te_id = valid_v7_id();
@@ -133,6 +190,57 @@ mod set_time_entry_tags_http_inbound_tests {
         assert_eq!(response.status(), StatusCode::OK);
     }
 
+    #[tokio::test]
+    async fn put_stores_tags_in_canonical_form() {
+        let te_id = valid_v7_id();
+        let state = make_test_state();
+        let body = r#"{"tag_ids":[" Dev ","DEV","Client X"]}"#;
+        let response = app(state.clone())
+            .oneshot(
+                Request::builder()
+                    .method("PUT")
+                    .uri(format!("/time-entries/{te_id}/tags"))
+                    .header("content-type", "application/json")
+                    .header("x-user-id", "u-1")
+                    .header("x-tenant-id", "tenant-test")
+                    .body(Body::from(body))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let stream = state
+            .event_store
+            .load(&DefaultStreamNaming.time_entry(None, &te_id))
+            .await
+            .unwrap();
+        let Some(TimeEntryEvent::TimeEntryTagsSetV1(event)) = stream.events.last() else {
+            panic!("expected TimeEntryTagsSetV1");
+        };
+        assert_eq!(event.tag_ids, vec!["dev", "client x"]);
+    }
+
+    #[tokio::test]
+    async fn put_returns_422_on_invalid_tag() {
+        let te_id = valid_v7_id();
+        let body = r#"{"tag_ids":["tag-1","  "]}"#;
+        let response = app(make_test_state())
+            .oneshot(
+                Request::builder()
+                    .method("PUT")
+                    .uri(format!("/time-entries/{te_id}/tags"))
+                    .header("content-type", "application/json")
+                    .header("x-user-id", "u-1")
+                    .header("x-tenant-id", "tenant-test")
+                    .body(Body::from(body))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
     #[tokio::test]
     async fn put_returns_422_on_non_uuid() {
         let body = r#"{"tag_ids":["tag-1"]}"#;
@@ -229,4 +337,56 @@ mod set_time_entry_tags_http_inbound_tests {
             .unwrap();
         assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
     }
+
+    #[tokio::test]
+    async fn put_returns_409_when_entry_is_approved() {
+        use crate::modules::time_entries::core::events::TimeEntryEvent;
+        use crate::modules::time_entries::core::events::v1::time_entry_approved::TimeEntryApprovedV1;
+        use crate::modules::time_entries::core::events::v1::time_entry_initiated::TimeEntryInitiatedV1;
+        use crate::modules::time_entries::core::events::v1::time_entry_registered::TimeEntryRegisteredV1;
+        use crate::shared::infrastructure::event_store::EventStore;
+
+        let state = make_test_state();
+        let te_id = valid_v7_id();
+        state
+            .event_store
+            .append(
+                &DefaultStreamNaming.time_entry(None, &te_id),
+                0,
+                &[
+                    TimeEntryEvent::TimeEntryInitiatedV1(TimeEntryInitiatedV1 {
+                        time_entry_id: te_id.clone().into(),
+                        user_id: "u-1".into(),
+                        created_at: 1_000,
+                        created_by: "u-1".into(),
+                    }),
+                    TimeEntryEvent::TimeEntryRegisteredV1(TimeEntryRegisteredV1 {
+                        time_entry_id: te_id.clone().into(),
+                        occurred_at: 1_000,
+                    }),
+                    TimeEntryEvent::TimeEntryApprovedV1(TimeEntryApprovedV1 {
+                        time_entry_id: te_id.clone().into(),
+                        approved_at: 2_000,
+                        approved_by: "m-1".into(),
+                    }),
+                ],
+            )
+            .await
+            .unwrap();
+
+        let response = app(state)
+            .oneshot(
+                Request::builder()
+                    .method("PUT")
+                    .uri(format!("/time-entries/{te_id}/tags"))
+                    .header("content-type", "application/json")
+                    .header("x-user-id", "u-1")
+                    .header("x-tenant-id", "tenant-test")
+                    .body(Body::from(r#"{"tag_ids":["tag-1"]}"#))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+    }
 }
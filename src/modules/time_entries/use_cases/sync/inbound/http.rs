@@ -0,0 +1,545 @@
+use axum::{
+    Json,
+    extract::{State, rejection::JsonRejection},
+    http::StatusCode,
+    response::IntoResponse,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap};
+use std::fmt::Display;
+use uuid::{Uuid, Version};
+
+use crate::modules::time_entries::core::tag::Tag;
+use crate::modules::time_entries::use_cases::list_time_entries::inbound::api_v1::TimeEntryV1;
+use crate::modules::time_entries::use_cases::list_time_entries::projection::TimeEntryView;
+use crate::modules::time_entries::use_cases::set_ended_at::command::SetEndedAt;
+use crate::modules::time_entries::use_cases::set_started_at::command::SetStartedAt;
+use crate::modules::time_entries::use_cases::set_time_entry_tags::command::SetTimeEntryTags;
+use crate::shared::application::event_sourced_handler::EventSourcedError;
+use crate::shared::infrastructure::event_store::{EventStore, EventStoreError};
+use crate::shared::infrastructure::intent_outbox::OutboxError;
+use crate::shared::infrastructure::projection_store::ProjectionStore;
+use crate::shared::infrastructure::request_context::RequestContext;
+use crate::shell::state::AppState;
+
+/// Mutations accepted per sync; clients with more queued send them over several syncs.
+pub const MAX_SYNC_MUTATIONS: usize = 100;
+
+#[derive(Debug, Deserialize)]
+pub struct SyncBody {
+    /// The `watermark` of the client's previous sync; 0 downloads every entry.
+    #[serde(default)]
+    pub since: u64,
+    #[serde(default)]
+    pub mutations: Vec<SyncMutation>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct SyncMutation {
+    /// Generated by the client, UUID v7, for entries it created offline.
+    pub time_entry_id: String,
+    /// The entry's version the change was made against; 0 for entries created offline.
+    pub base_version: i64,
+    #[serde(flatten)]
+    pub change: SyncChange,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum SyncChange {
+    SetStartedAt {
+        started_at: i64,
+    },
+    SetEndedAt {
+        ended_at: i64,
+    },
+    SetTags {
+        tag_ids: Vec<String>,
+    },
+    /// Accepted in the protocol, rejected until time entries can be deleted.
+    Delete,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SyncStatus {
+    Applied,
+    /// The entry changed since `base_version`; merge with `changes` and resubmit.
+    Conflict,
+    /// Refused by the domain; resubmitting the same change will not help.
+    Rejected,
+    /// Could not be processed; safe to resubmit.
+    Failed,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SyncResult {
+    pub time_entry_id: String,
+    pub status: SyncStatus,
+    /// The entry's version after this mutation; the base for the next change.
+    pub version: i64,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SyncResponse {
+    /// One per mutation, in order.
+    pub results: Vec<SyncResult>,
+    /// The caller's entries changed since `since`, as of `watermark`.
+    pub changes: Vec<TimeEntryV1>,
+    /// Pass as `since` next time.
+    pub watermark: u64,
+}
+
+/// POST /sync — applies a batch of offline mutations in order, then returns the caller's
+/// entries that changed on the server since their last sync.
+///
+/// Each mutation carries the entry version it was made against. Every mutation on an entry
+/// within one batch is checked against the version the entry had before the batch, so a
+/// client can queue several changes to the same entry offline.
+pub async fn handle(
+    State(state): State<AppState>,
+    request_ctx: RequestContext,
+    body: Result<Json<SyncBody>, JsonRejection>,
+) -> impl IntoResponse {
+    if !request_ctx
+        .principal()
+        .can_register_for(&request_ctx.user_id)
+    {
+        return StatusCode::FORBIDDEN.into_response();
+    }
+    let Ok(Json(body)) = body else {
+        return StatusCode::UNPROCESSABLE_ENTITY.into_response();
+    };
+    if body.mutations.len() > MAX_SYNC_MUTATIONS {
+        return StatusCode::UNPROCESSABLE_ENTITY.into_response();
+    }
+
+    let user_id = request_ctx.user_id;
+    let mut base_versions = HashMap::new();
+    let mut results = Vec::with_capacity(body.mutations.len());
+    for mutation in body.mutations {
+        results.push(
+            apply(
+                &state,
+                &user_id,
+                &request_ctx.tenant_id,
+                &mut base_versions,
+                mutation,
+            )
+            .await,
+        );
+    }
+
+    match changes_since(&state, &user_id, body.since).await {
+        Ok((changes, watermark)) => Json(SyncResponse {
+            results,
+            changes: changes.into_iter().map(TimeEntryV1::from).collect(),
+            watermark,
+        })
+        .into_response(),
+        Err(_) => StatusCode::INTERNAL_SERVER_ERROR.into_response(),
+    }
+}
+
+async fn apply(
+    state: &AppState,
+    user_id: &str,
+    tenant_id: &str,
+    base_versions: &mut HashMap<String, i64>,
+    mutation: SyncMutation,
+) -> SyncResult {
+    let SyncMutation {
+        time_entry_id,
+        base_version,
+        change,
+    } = mutation;
+    let result = |status, version, error: Option<String>| SyncResult {
+        time_entry_id: time_entry_id.clone(),
+        status,
+        version,
+        error,
+    };
+
+    let is_valid_v7 = Uuid::parse_str(&time_entry_id)
+        .ok()
+        .filter(|u| u.get_version() == Some(Version::SortRand))
+        .is_some();
+    if !is_valid_v7 {
+        let error = "time_entry_id must be a UUID v7".to_string();
+        return result(SyncStatus::Rejected, 0, Some(error));
+    }
+    let stream_id = state
+        .stream_naming
+        .time_entry(Some(tenant_id), &time_entry_id);
+    let current = match state.event_store.load(&stream_id).await {
+        Ok(stream) => stream.version,
+        Err(error) => return result(SyncStatus::Failed, 0, Some(error.to_string())),
+    };
+    let expected = *base_versions
+        .entry(time_entry_id.clone())
+        .or_insert(current);
+    if base_version != expected {
+        return result(SyncStatus::Conflict, current, None);
+    }
+
+    let outcome = match change {
+        SyncChange::SetStartedAt { started_at } => outcome_of(
+            state
+                .set_started_at_handler
+                .handle_for_tenant(
+                    tenant_id,
+                    &stream_id,
+                    SetStartedAt::new(time_entry_id.clone().into(), user_id.into(), started_at),
+                )
+                .await,
+        ),
+        SyncChange::SetEndedAt { ended_at } => outcome_of(
+            state
+                .set_ended_at_handler
+                .handle_for_tenant(
+                    tenant_id,
+                    &stream_id,
+                    SetEndedAt::new(time_entry_id.clone().into(), user_id.into(), ended_at),
+                )
+                .await,
+        ),
+        SyncChange::SetTags { tag_ids } => match Tag::parse_all(&tag_ids) {
+            Ok(tag_ids) => outcome_of(
+                state
+                    .set_time_entry_tags_handler
+                    .handle_for_tenant(
+                        tenant_id,
+                        &stream_id,
+                        SetTimeEntryTags::new(
+                            time_entry_id.clone().into(),
+                            user_id.into(),
+                            tag_ids,
+                        ),
+                    )
+                    .await,
+            ),
+            Err(error) => Err((SyncStatus::Rejected, Some(error.to_string()))),
+        },
+        SyncChange::Delete => Err((
+            SyncStatus::Rejected,
+            Some("deleting time entries is not supported yet".to_string()),
+        )),
+    };
+
+    let version = match state.event_store.load(&stream_id).await {
+        Ok(stream) => stream.version,
+        Err(_) => current,
+    };
+    match outcome {
+        Ok(()) => result(SyncStatus::Applied, version, None),
+        Err((status, error)) => result(status, version, error),
+    }
+}
+
+fn outcome_of<TReason: Display>(
+    outcome: Result<(), EventSourcedError<TReason, OutboxError>>,
+) -> Result<(), (SyncStatus, Option<String>)> {
+    outcome.map_err(|error| match error {
+        EventSourcedError::Domain(reason) => (SyncStatus::Rejected, Some(reason.to_string())),
+        EventSourcedError::VersionConflict(EventStoreError::VersionMismatch { .. }) => {
+            (SyncStatus::Conflict, None)
+        }
+        other => (SyncStatus::Failed, Some(other.to_string())),
+    })
+}
+
+/// The caller's entries touched by events from global position `since` up to what the list
+/// projection has caught up with, and that position as the next watermark.
+async fn changes_since(
+    state: &AppState,
+    user_id: &str,
+    since: u64,
+) -> anyhow::Result<(Vec<TimeEntryView>, u64)> {
+    let queries = &state.list_time_entries_handler;
+    let watermark = queries.store().checkpoint().await?;
+    let time_entry_ids: BTreeSet<String> = state
+        .event_store
+        .load_all_from(since)
+        .await?
+        .into_iter()
+        .filter(|stored| stored.global_position < watermark)
+        .filter_map(|stored| {
+            state
+                .stream_naming
+                .time_entry_id(&stored.stream_id)
+                .map(str::to_string)
+        })
+        .collect();
+    let changes = queries.list_by_ids(user_id, &time_entry_ids).await?;
+    Ok((changes, watermark.max(since)))
+}
+
+#[cfg(test)]
+mod sync_http_inbound_tests {
+    use super::*;
+    use crate::modules::time_entries::core::events::TimeEntryEvent;
+    use crate::modules::time_entries::use_cases::list_time_entries::projection::{
+        ListTimeEntriesState, TimeEntryRow, TimeEntryStatus,
+    };
+    use crate::modules::time_entries::use_cases::list_time_entries::queries::ListTimeEntriesQueryHandler;
+    use crate::shared::infrastructure::event_store::in_memory::InMemoryEventStore;
+    use crate::shared::infrastructure::projection_store::in_memory::InMemoryProjectionStore;
+    use crate::shared::infrastructure::projection_store::partitioned::PartitionedProjectionStore;
+    use crate::tests::fixtures::tags::{
+        make_test_app_state, make_test_app_state_with, make_test_app_state_with_store,
+    };
+    use axum::{Router, body::Body, http::Request, routing::post};
+    use http_body_util::BodyExt;
+    use rstest::rstest;
+    use serde_json::{Value, json};
+    use tower::ServiceExt;
+
+    fn app(state: AppState) -> Router {
+        Router::new().route("/sync", post(handle)).with_state(state)
+    }
+
+    async fn sync(state: AppState, body: Value) -> (StatusCode, Value) {
+        let response = app(state)
+            .oneshot(
+                Request::post("/sync")
+                    .header("x-user-id", "u-1")
+                    .header("x-tenant-id", "tenant-test")
+                    .header("content-type", "application/json")
+                    .body(Body::from(body.to_string()))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        let status = response.status();
+        let bytes = response.into_body().collect().await.unwrap().to_bytes();
+        (
+            status,
+            serde_json::from_slice(&bytes).unwrap_or(Value::Null),
+        )
+    }
+
+    fn id() -> String {
+        Uuid::now_v7().to_string()
+    }
+
+    fn row(time_entry_id: &str, user_id: &str) -> TimeEntryRow {
+        TimeEntryRow {
+            time_entry_id: time_entry_id.to_string(),
+            user_id: user_id.to_string(),
+            started_at: Some(1_000),
+            ended_at: None,
+            tag_ids: vec![],
+            status: TimeEntryStatus::Draft,
+            created_at: 0,
+            created_by: user_id.to_string(),
+            updated_at: 0,
+            updated_by: user_id.to_string(),
+            deleted_at: None,
+            hourly_rate: None,
+            last_event_id: None,
+            breaks: vec![],
+        }
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_apply_queued_changes_to_one_entry_against_the_same_base() {
+        let time_entry_id = id();
+        let (status, body) = sync(
+            make_test_app_state(),
+            json!({ "mutations": [
+                { "op": "set_started_at", "time_entry_id": time_entry_id, "base_version": 0, "started_at": 1_000 },
+                { "op": "set_tags", "time_entry_id": time_entry_id, "base_version": 0, "tag_ids": ["dev"] },
+                { "op": "set_ended_at", "time_entry_id": time_entry_id, "base_version": 0, "ended_at": 61_000 },
+            ]}),
+        )
+        .await;
+
+        assert_eq!(status, StatusCode::OK);
+        let results = body["results"].as_array().unwrap();
+        assert_eq!(results.len(), 3);
+        assert!(results.iter().all(|r| r["status"] == "applied"), "{body}");
+        let versions: Vec<i64> = results
+            .iter()
+            .map(|r| r["version"].as_i64().unwrap())
+            .collect();
+        assert!(versions.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_report_a_conflict_when_the_entry_moved_past_the_base_version() {
+        let state = make_test_app_state();
+        let time_entry_id = id();
+        let create = json!({ "mutations": [
+            { "op": "set_started_at", "time_entry_id": time_entry_id, "base_version": 0, "started_at": 1_000 },
+        ]});
+        sync(state.clone(), create.clone()).await;
+
+        let (_, body) = sync(state, create).await;
+
+        let result = &body["results"][0];
+        assert_eq!(result["status"], "conflict");
+        assert_eq!(result["version"], 2);
+        assert_eq!(result["error"], Value::Null);
+    }
+
+    #[rstest]
+    #[case::not_a_v7("te-1", json!({ "op": "set_tags", "tag_ids": [] }), "time_entry_id must be a UUID v7")]
+    #[case::invalid_tag(&id(), json!({ "op": "set_tags", "tag_ids": [""] }), "tag must not be blank")]
+    #[case::delete(&id(), json!({ "op": "delete" }), "deleting time entries is not supported yet")]
+    #[tokio::test]
+    async fn it_should_reject_mutations_that_cannot_apply(
+        #[case] time_entry_id: &str,
+        #[case] change: Value,
+        #[case] error: &str,
+    ) {
+        let mut mutation = change;
+        mutation["time_entry_id"] = json!(time_entry_id);
+        mutation["base_version"] = json!(0);
+
+        let (_, body) = sync(make_test_app_state(), json!({ "mutations": [mutation] })).await;
+
+        let result = &body["results"][0];
+        assert_eq!(result["status"], "rejected", "{body}");
+        assert!(result["error"].as_str().unwrap().contains(error));
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_reject_changes_the_domain_refuses_and_carry_on() {
+        let time_entry_id = id();
+        let (_, body) = sync(
+            make_test_app_state(),
+            json!({ "mutations": [
+                { "op": "set_started_at", "time_entry_id": time_entry_id, "base_version": 0, "started_at": 61_000 },
+                { "op": "set_ended_at", "time_entry_id": time_entry_id, "base_version": 0, "ended_at": 1_000 },
+                { "op": "set_ended_at", "time_entry_id": time_entry_id, "base_version": 0, "ended_at": 121_000 },
+            ]}),
+        )
+        .await;
+
+        let statuses: Vec<&str> = body["results"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|r| r["status"].as_str().unwrap())
+            .collect();
+        assert_eq!(statuses, vec!["applied", "rejected", "applied"]);
+        assert_eq!(body["results"][1]["version"], body["results"][0]["version"]);
+        assert!(body["results"][1]["error"].is_string());
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_fail_the_sync_while_the_event_store_is_offline() {
+        let (state, event_store) = make_test_app_state_with_store();
+        event_store.toggle_offline();
+
+        let (status, body) = sync(
+            state,
+            json!({ "mutations": [
+                { "op": "set_started_at", "time_entry_id": id(), "base_version": 0, "started_at": 1_000 },
+            ]}),
+        )
+        .await;
+
+        assert_eq!(status, StatusCode::INTERNAL_SERVER_ERROR);
+        assert_eq!(body, Value::Null);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_return_the_callers_entries_changed_since_the_watermark() {
+        let (event_tx, _) = tokio::sync::broadcast::channel(16);
+        let event_store = InMemoryEventStore::<TimeEntryEvent>::new_with_sender(event_tx);
+        let projection_store = InMemoryProjectionStore::<ListTimeEntriesState>::new();
+        let state = make_test_app_state_with(event_store.clone(), projection_store.clone());
+        let (mine, theirs, later) = (id(), id(), id());
+        for (time_entry_id, user_id) in [(&mine, "u-1"), (&theirs, "u-2"), (&later, "u-1")] {
+            event_store
+                .append(
+                    &format!("TimeEntry-{time_entry_id}"),
+                    0,
+                    &[TimeEntryEvent::TimeEntryInitiatedV1(
+                        crate::modules::time_entries::core::events::v1::time_entry_initiated::TimeEntryInitiatedV1 {
+                            time_entry_id: time_entry_id.clone().into(),
+                            user_id: user_id.into(),
+                            created_at: 0,
+                            created_by: user_id.into(),
+                        },
+                    )],
+                )
+                .await
+                .unwrap();
+        }
+        let mut projection = ListTimeEntriesState::default();
+        for (time_entry_id, user_id) in [(&mine, "u-1"), (&theirs, "u-2"), (&later, "u-1")] {
+            projection
+                .rows
+                .insert(time_entry_id.clone(), row(time_entry_id, user_id));
+        }
+        // The projection has caught up with the first two events only.
+        projection_store.save(projection, 2).await.unwrap();
+
+        let (status, body) = sync(state.clone(), json!({ "since": 0 })).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["watermark"], 2);
+        assert_eq!(body["results"], json!([]));
+        let changes: Vec<&str> = body["changes"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|change| change["time_entry_id"].as_str().unwrap())
+            .collect();
+        assert_eq!(changes, vec![mine.as_str()]);
+
+        let (_, body) = sync(state, json!({ "since": 2 })).await;
+        assert_eq!(body["watermark"], 2);
+        assert_eq!(body["changes"], json!([]));
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_fail_when_the_projection_is_unavailable() {
+        let mut state = make_test_app_state();
+        let mut projection_store = InMemoryProjectionStore::<ListTimeEntriesState>::new();
+        projection_store.toggle_offline();
+        state.list_time_entries_handler =
+            ListTimeEntriesQueryHandler::new(PartitionedProjectionStore::single(projection_store));
+
+        let (status, _) = sync(state, json!({})).await;
+
+        assert_eq!(status, StatusCode::INTERNAL_SERVER_ERROR);
+    }
+
+    #[rstest]
+    #[case::malformed(json!({ "mutations": [{ "op": "rename" }] }))]
+    #[case::too_many(json!({ "mutations": vec![
+        json!({ "op": "delete", "time_entry_id": "te-1", "base_version": 0 });
+        MAX_SYNC_MUTATIONS + 1
+    ]}))]
+    #[tokio::test]
+    async fn it_should_refuse_unprocessable_batches(#[case] body: Value) {
+        let (status, _) = sync(make_test_app_state(), body).await;
+
+        assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    #[rstest]
+    #[case::domain(EventSourcedError::Domain("too late".to_string()), SyncStatus::Rejected, Some("too late"))]
+    #[case::race(EventSourcedError::VersionConflict(EventStoreError::VersionMismatch { expected: 1, actual: 2 }), SyncStatus::Conflict, None)]
+    #[case::store(EventSourcedError::VersionConflict(EventStoreError::Backend("down".to_string())), SyncStatus::Failed, Some("backend error: down"))]
+    #[case::outbox(EventSourcedError::Outbox(OutboxError::Transient("busy".to_string())), SyncStatus::Failed, Some("transient backend error: busy"))]
+    fn it_should_map_handler_errors_to_statuses(
+        #[case] error: EventSourcedError<String, OutboxError>,
+        #[case] status: SyncStatus,
+        #[case] message: Option<&str>,
+    ) {
+        assert_eq!(
+            outcome_of(Err(error)),
+            Err((status, message.map(str::to_string)))
+        );
+    }
+}
@@ -0,0 +1,182 @@
+use async_graphql::{ComplexObject, Context, Result as GqlResult, SimpleObject};
+
+use crate::modules::time_entries::use_cases::time_entry_attachments::projection::AttachmentRow;
+use crate::shared::infrastructure::attachment_storage::AttachmentStorage;
+use crate::shell::state::AppState;
+
+/// Resolved as `TimeEntry.attachments`.
+#[derive(SimpleObject, Clone)]
+#[graphql(name = "TimeEntryAttachment", complex)]
+pub struct GqlTimeEntryAttachment {
+    pub attachment_id: String,
+    pub file_name: String,
+    pub content_type: String,
+    pub size_bytes: i64,
+    pub added_at: i64,
+    pub added_by: String,
+    #[graphql(skip)]
+    pub object_key: String,
+}
+
+impl From<AttachmentRow> for GqlTimeEntryAttachment {
+    fn from(row: AttachmentRow) -> Self {
+        Self {
+            attachment_id: row.attachment_id,
+            file_name: row.file_name,
+            content_type: row.content_type,
+            size_bytes: row.size_bytes as i64,
+            added_at: row.added_at,
+            added_by: row.added_by,
+            object_key: row.object_key,
+        }
+    }
+}
+
+#[ComplexObject]
+impl GqlTimeEntryAttachment {
+    /// A pre-signed URL to download the file, valid for 15 minutes.
+    async fn download_url(&self, context: &Context<'_>) -> GqlResult<String> {
+        let state = context.data_unchecked::<AppState>();
+        let url = state
+            .attachment_storage
+            .download_url(&self.object_key)
+            .await?;
+        Ok(url.url)
+    }
+}
+
+#[cfg(test)]
+mod time_entry_attachments_graphql_inbound_tests {
+    use async_graphql::{EmptySubscription, Schema};
+
+    use crate::modules::time_entries::use_cases::list_time_entries::projection::{
+        ListTimeEntriesState, TimeEntryRow, TimeEntryStatus,
+    };
+    use crate::modules::time_entries::use_cases::list_time_entries::queries::ListTimeEntriesQueryHandler;
+    use crate::modules::time_entries::use_cases::time_entry_attachments::projection::{
+        AttachmentRow, TimeEntryAttachmentsState,
+    };
+    use crate::modules::time_entries::use_cases::time_entry_attachments::queries::TimeEntryAttachmentsQueryHandler;
+    use crate::shared::infrastructure::projection_store::ProjectionStore;
+    use crate::shared::infrastructure::projection_store::in_memory::InMemoryProjectionStore;
+    use crate::shared::infrastructure::projection_store::partitioned::PartitionedProjectionStore;
+    use crate::shared::infrastructure::request_context::RequestContext;
+    use crate::shell::graphql::{MutationRoot, QueryRoot};
+    use crate::shell::state::AppState;
+    use crate::tests::fixtures::tags::make_test_app_state;
+
+    fn make_schema_from_state(
+        state: AppState,
+    ) -> Schema<QueryRoot, MutationRoot, EmptySubscription> {
+        Schema::build(
+            QueryRoot::default(),
+            MutationRoot::default(),
+            EmptySubscription,
+        )
+        .data(state)
+        .finish()
+    }
+
+    fn req_ctx() -> RequestContext {
+        RequestContext {
+            user_id: "u-1".to_string(),
+            tenant_id: "tenant-test".to_string(),
+            role: Default::default(),
+            scope: Default::default(),
+        }
+    }
+
+    /// One entry of `u-1` with a receipt attached.
+    async fn make_seeded_state() -> AppState {
+        let mut state = make_test_app_state();
+        let rows = InMemoryProjectionStore::<ListTimeEntriesState>::new();
+        let mut list = ListTimeEntriesState::default();
+        list.rows.insert(
+            "te-1".to_string(),
+            TimeEntryRow {
+                time_entry_id: "te-1".to_string(),
+                user_id: "u-1".to_string(),
+                started_at: Some(1_000),
+                ended_at: Some(2_000),
+                tag_ids: vec![],
+                status: TimeEntryStatus::Registered,
+                created_at: 0,
+                created_by: "u-1".to_string(),
+                updated_at: 0,
+                updated_by: "u-1".to_string(),
+                deleted_at: None,
+                hourly_rate: None,
+                breaks: vec![],
+                last_event_id: None,
+            },
+        );
+        rows.save(list, 1).await.unwrap();
+        state.list_time_entries_handler =
+            ListTimeEntriesQueryHandler::new(PartitionedProjectionStore::single(rows));
+
+        let attachments = InMemoryProjectionStore::<TimeEntryAttachmentsState>::new();
+        let mut files = TimeEntryAttachmentsState::default();
+        files.attachments.insert(
+            "te-1".to_string(),
+            vec![AttachmentRow {
+                attachment_id: "a-1".to_string(),
+                time_entry_id: "te-1".to_string(),
+                object_key: "attachments/te-1/a-1".to_string(),
+                file_name: "receipt.pdf".to_string(),
+                content_type: "application/pdf".to_string(),
+                size_bytes: 1_024,
+                added_at: 1,
+                added_by: "u-1".to_string(),
+            }],
+        );
+        attachments.save(files, 1).await.unwrap();
+        state.time_entry_attachments_handler = TimeEntryAttachmentsQueryHandler::new(attachments);
+        state
+    }
+
+    #[tokio::test]
+    async fn resolver_lists_attachments_with_download_urls() {
+        let schema = make_schema_from_state(make_seeded_state().await);
+
+        let result = schema
+            .execute(
+                async_graphql::Request::new(
+                    "{ listTimeEntries { attachments { attachmentId fileName contentType sizeBytes downloadUrl } } }",
+                )
+                .data(req_ctx()),
+            )
+            .await;
+
+        assert!(result.errors.is_empty(), "{:?}", result.errors);
+        let data = result.data.into_json().unwrap();
+        let attachment = &data["listTimeEntries"][0]["attachments"][0];
+        assert_eq!(attachment["attachmentId"], "a-1");
+        assert_eq!(attachment["fileName"], "receipt.pdf");
+        assert_eq!(attachment["sizeBytes"], 1_024);
+        assert!(
+            attachment["downloadUrl"]
+                .as_str()
+                .unwrap()
+                .starts_with("memory:attachments/te-1/a-1?method=GET")
+        );
+    }
+
+    #[tokio::test]
+    async fn resolver_fails_when_the_storage_is_offline() {
+        let state = make_seeded_state().await;
+        state.attachment_storage.toggle_offline();
+        let schema = make_schema_from_state(state);
+
+        let result = schema
+            .execute(
+                async_graphql::Request::new("{ listTimeEntries { attachments { downloadUrl } } }")
+                    .data(req_ctx()),
+            )
+            .await;
+
+        assert_eq!(
+            result.errors[0].message,
+            "backend error: Attachment storage offline"
+        );
+    }
+}
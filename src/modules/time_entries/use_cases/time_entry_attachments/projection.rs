@@ -0,0 +1,127 @@
+use crate::modules::time_entries::core::events::TimeEntryEvent;
+use crate::shared::infrastructure::capacity::{EvictRows, evict_oldest};
+use std::collections::HashMap;
+
+pub const SCHEMA_VERSION: u32 = 1;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AttachmentRow {
+    pub attachment_id: String,
+    pub time_entry_id: String,
+    pub object_key: String,
+    pub file_name: String,
+    pub content_type: String,
+    pub size_bytes: u64,
+    pub added_at: i64,
+    pub added_by: String,
+}
+
+/// The attachments of every entry, keyed by entry and in the order they were added.
+#[derive(Clone, Default)]
+pub struct TimeEntryAttachmentsState {
+    pub attachments: HashMap<String, Vec<AttachmentRow>>,
+}
+
+impl TimeEntryAttachmentsState {
+    /// Adds an attachment once, however often its event is replayed, and drops the
+    /// attachments of deleted entries. Other events leave the attachments as they are.
+    pub fn apply(&mut self, event: &TimeEntryEvent) {
+        match event {
+            TimeEntryEvent::TimeEntryAttachmentAddedV1(e) => {
+                let attachments = self
+                    .attachments
+                    .entry(e.time_entry_id.to_string())
+                    .or_default();
+                if attachments
+                    .iter()
+                    .any(|attachment| attachment.attachment_id == e.attachment_id)
+                {
+                    return;
+                }
+                attachments.push(AttachmentRow {
+                    attachment_id: e.attachment_id.clone(),
+                    time_entry_id: e.time_entry_id.to_string(),
+                    object_key: e.object_key.clone(),
+                    file_name: e.file_name.clone(),
+                    content_type: e.content_type.clone(),
+                    size_bytes: e.size_bytes,
+                    added_at: e.added_at,
+                    added_by: e.added_by.to_string(),
+                });
+            }
+            TimeEntryEvent::TimeEntryDeletedV1(e) => {
+                self.attachments.remove(e.time_entry_id.as_str());
+            }
+            _ => {}
+        }
+    }
+}
+
+/// A row is an entry with its attachments, last updated when the latest was added.
+impl EvictRows for TimeEntryAttachmentsState {
+    fn row_count(&self) -> usize {
+        self.attachments.len()
+    }
+
+    fn evict_rows(&mut self, max_rows: usize) -> usize {
+        evict_oldest(&mut self.attachments, max_rows, |rows| {
+            rows.iter().map(|row| row.added_at).max().unwrap_or(0)
+        })
+    }
+}
+
+#[cfg(test)]
+mod time_entry_attachments_projection_tests {
+    use super::*;
+    use crate::modules::time_entries::core::events::v1::time_entry_attachment_added::TimeEntryAttachmentAddedV1;
+    use crate::modules::time_entries::core::events::v1::time_entry_deleted::TimeEntryDeletedV1;
+    use rstest::rstest;
+
+    fn attachment_added(attachment_id: &str) -> TimeEntryEvent {
+        TimeEntryEvent::TimeEntryAttachmentAddedV1(TimeEntryAttachmentAddedV1 {
+            time_entry_id: "te-fixed-0001".into(),
+            attachment_id: attachment_id.to_string(),
+            object_key: format!("attachments/te-fixed-0001/{attachment_id}"),
+            file_name: "receipt.pdf".to_string(),
+            content_type: "application/pdf".to_string(),
+            size_bytes: 1_024,
+            added_at: 1_000,
+            added_by: "user-fixed-0001".into(),
+        })
+    }
+
+    #[rstest]
+    fn it_should_keep_each_attachment_once() {
+        let mut state = TimeEntryAttachmentsState::default();
+
+        state.apply(&attachment_added("a-1"));
+        state.apply(&attachment_added("a-2"));
+        state.apply(&attachment_added("a-1"));
+
+        let object_keys: Vec<&str> = state.attachments["te-fixed-0001"]
+            .iter()
+            .map(|attachment| attachment.object_key.as_str())
+            .collect();
+        assert_eq!(
+            object_keys,
+            vec![
+                "attachments/te-fixed-0001/a-1",
+                "attachments/te-fixed-0001/a-2"
+            ]
+        );
+    }
+
+    #[rstest]
+    fn it_should_drop_the_attachments_of_deleted_entries() {
+        let mut state = TimeEntryAttachmentsState::default();
+        state.apply(&attachment_added("a-1"));
+
+        state.apply(&TimeEntryEvent::TimeEntryDeletedV1(TimeEntryDeletedV1 {
+            time_entry_id: "te-fixed-0001".into(),
+            deleted_at: 2_000,
+            deleted_by: "user-fixed-0001".into(),
+        }));
+
+        assert!(state.attachments.is_empty());
+    }
+}
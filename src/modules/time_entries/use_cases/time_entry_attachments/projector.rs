@@ -0,0 +1,216 @@
+use crate::modules::time_entries::core::events::TimeEntryEvent;
+use crate::modules::time_entries::use_cases::list_time_entries::projector::ProjectionTechnicalEvent;
+use crate::modules::time_entries::use_cases::time_entry_attachments::projection::{
+    SCHEMA_VERSION, TimeEntryAttachmentsState,
+};
+use crate::shared::infrastructure::event_store::{EventLog, SharedEventLog, StoredEvent};
+use crate::shared::infrastructure::projection_store::ProjectionStore;
+use std::sync::Arc;
+use tokio::sync::broadcast;
+
+/// Keeps the attachments projection in step with the time entry feed. It rebuilds from the
+/// event log on a schema change and whenever it falls behind the channel.
+pub struct TimeEntryAttachmentsProjector<TStore>
+where
+    TStore: ProjectionStore<TimeEntryAttachmentsState> + Send + Sync + 'static,
+{
+    pub name: String,
+    pub store: TStore,
+    pub event_store: SharedEventLog<TimeEntryEvent>,
+    pub technical_tx: broadcast::Sender<ProjectionTechnicalEvent>,
+}
+
+impl<TStore> TimeEntryAttachmentsProjector<TStore>
+where
+    TStore: ProjectionStore<TimeEntryAttachmentsState> + Send + Sync + 'static,
+{
+    pub fn new(
+        name: impl Into<String>,
+        store: TStore,
+        event_store: impl EventLog<TimeEntryEvent> + 'static,
+        technical_tx: broadcast::Sender<ProjectionTechnicalEvent>,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            store,
+            event_store: Arc::new(event_store),
+            technical_tx,
+        }
+    }
+
+    pub async fn run(self, mut receiver: broadcast::Receiver<StoredEvent<TimeEntryEvent>>) {
+        let stored_schema = self.store.schema_version().await.unwrap_or(None);
+        if stored_schema != Some(SCHEMA_VERSION)
+            && let Err(reason) = self.rebuild().await
+        {
+            self.rebuild_failed(reason);
+            return;
+        }
+
+        loop {
+            match receiver.recv().await {
+                Ok(stored_event) => {
+                    let checkpoint = self.store.checkpoint().await.unwrap_or(0);
+                    if stored_event.global_position < checkpoint {
+                        continue;
+                    }
+                    let start = std::time::Instant::now();
+                    if self.apply_stored_event(&stored_event).await.is_err() {
+                        continue;
+                    }
+                    let _ = self
+                        .technical_tx
+                        .send(ProjectionTechnicalEvent::EventApplied {
+                            projection_name: self.name.clone(),
+                            checkpoint: stored_event.global_position + 1,
+                            duration_ms: start.elapsed().as_millis() as u64,
+                        });
+                }
+                Err(broadcast::error::RecvError::Lagged(_)) => {
+                    if let Err(reason) = self.rebuild().await {
+                        self.rebuild_failed(reason);
+                        return;
+                    }
+                }
+                Err(broadcast::error::RecvError::Closed) => break,
+            }
+        }
+    }
+
+    pub async fn rebuild(&self) -> anyhow::Result<()> {
+        let start = std::time::Instant::now();
+        let _ = self
+            .technical_tx
+            .send(ProjectionTechnicalEvent::RebuildStarted {
+                projection_name: self.name.clone(),
+                schema_version: SCHEMA_VERSION,
+                timestamp: chrono::Utc::now().timestamp_millis(),
+            });
+        self.store.clear().await?;
+        let all_events = self.event_store.load_all_from(0).await?;
+        let events_replayed = all_events.len() as u64;
+        for stored_event in all_events {
+            self.apply_stored_event(&stored_event).await?;
+        }
+        self.store.save_schema_version(SCHEMA_VERSION).await?;
+        let _ = self
+            .technical_tx
+            .send(ProjectionTechnicalEvent::RebuildCompleted {
+                projection_name: self.name.clone(),
+                events_replayed,
+                duration_ms: start.elapsed().as_millis() as u64,
+                timestamp: chrono::Utc::now().timestamp_millis(),
+            });
+        Ok(())
+    }
+
+    fn rebuild_failed(&self, reason: anyhow::Error) {
+        let _ = self
+            .technical_tx
+            .send(ProjectionTechnicalEvent::RebuildFailed {
+                projection_name: self.name.clone(),
+                reason: reason.to_string(),
+                timestamp: chrono::Utc::now().timestamp_millis(),
+            });
+    }
+
+    async fn apply_stored_event(
+        &self,
+        stored_event: &StoredEvent<TimeEntryEvent>,
+    ) -> anyhow::Result<()> {
+        let mut state = self.store.state().await?.unwrap_or_default();
+        state.apply(&stored_event.event);
+        self.store
+            .save(state, stored_event.global_position + 1)
+            .await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod time_entry_attachments_projector_tests {
+    use super::*;
+    use crate::modules::time_entries::use_cases::add_time_entry_attachment::handler::AddTimeEntryAttachmentHandler;
+    use crate::modules::time_entries::use_cases::set_started_at::handler::SetStartedAtHandler;
+    use crate::shared::infrastructure::event_store::in_memory::InMemoryEventStore;
+    use crate::shared::infrastructure::intent_outbox::in_memory::InMemoryDomainOutbox;
+    use crate::shared::infrastructure::projection_store::in_memory::InMemoryProjectionStore;
+    use crate::tests::fixtures::commands::add_time_entry_attachment::AddTimeEntryAttachmentBuilder;
+    use crate::tests::fixtures::commands::set_started_at::SetStartedAtBuilder;
+    use rstest::rstest;
+
+    const STREAM_ID: &str = "TimeEntry-te-fixed-0001";
+
+    async fn attach(event_store: InMemoryEventStore<TimeEntryEvent>, attachment_id: &str) {
+        AddTimeEntryAttachmentHandler::new(event_store, InMemoryDomainOutbox::new())
+            .handle(
+                STREAM_ID,
+                AddTimeEntryAttachmentBuilder::new()
+                    .attachment_id(attachment_id)
+                    .build(),
+            )
+            .await
+            .unwrap();
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_rebuild_then_follow_the_feed() {
+        let (tx, _) = broadcast::channel::<StoredEvent<TimeEntryEvent>>(16);
+        let event_store = InMemoryEventStore::<TimeEntryEvent>::new_with_sender(tx.clone());
+        SetStartedAtHandler::new(event_store.clone(), InMemoryDomainOutbox::new())
+            .handle(STREAM_ID, SetStartedAtBuilder::new().build())
+            .await
+            .unwrap();
+        attach(event_store.clone(), "a-1").await;
+        let projection_store = InMemoryProjectionStore::<TimeEntryAttachmentsState>::new();
+        let (tech_tx, mut tech_rx) = broadcast::channel(16);
+        let projector = TimeEntryAttachmentsProjector::new(
+            "time_entry_attachments",
+            projection_store.clone(),
+            event_store.clone(),
+            tech_tx,
+        );
+        let running = tokio::spawn(projector.run(tx.subscribe()));
+
+        attach(event_store, "a-2").await;
+        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+        running.abort();
+
+        let state = projection_store.state().await.unwrap().unwrap();
+        let attachment_ids: Vec<&str> = state.attachments["te-fixed-0001"]
+            .iter()
+            .map(|attachment| attachment.attachment_id.as_str())
+            .collect();
+        assert_eq!(attachment_ids, vec!["a-1", "a-2"]);
+        assert!(matches!(
+            tech_rx.try_recv(),
+            Ok(ProjectionTechnicalEvent::RebuildStarted { .. })
+        ));
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_report_a_failed_rebuild() {
+        let event_store = InMemoryEventStore::<TimeEntryEvent>::new();
+        let mut projection_store = InMemoryProjectionStore::<TimeEntryAttachmentsState>::new();
+        projection_store.toggle_offline();
+        let (tech_tx, mut tech_rx) = broadcast::channel(16);
+        let (_tx, receiver) = broadcast::channel::<StoredEvent<TimeEntryEvent>>(16);
+
+        TimeEntryAttachmentsProjector::new(
+            "time_entry_attachments",
+            projection_store,
+            event_store,
+            tech_tx,
+        )
+        .run(receiver)
+        .await;
+
+        let mut failed = false;
+        while let Ok(event) = tech_rx.try_recv() {
+            failed |= matches!(event, ProjectionTechnicalEvent::RebuildFailed { .. });
+        }
+        assert!(failed);
+    }
+}
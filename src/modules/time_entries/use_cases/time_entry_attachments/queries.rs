@@ -0,0 +1,88 @@
+use crate::modules::time_entries::use_cases::time_entry_attachments::projection::{
+    AttachmentRow, TimeEntryAttachmentsState,
+};
+use crate::shared::infrastructure::projection_store::ProjectionStore;
+
+#[derive(Clone)]
+pub struct TimeEntryAttachmentsQueryHandler<TStore>
+where
+    TStore: ProjectionStore<TimeEntryAttachmentsState> + Send + Sync + 'static,
+{
+    store: TStore,
+}
+
+impl<TStore> TimeEntryAttachmentsQueryHandler<TStore>
+where
+    TStore: ProjectionStore<TimeEntryAttachmentsState> + Send + Sync + 'static,
+{
+    pub fn new(store: TStore) -> Self {
+        Self { store }
+    }
+
+    pub fn store(&self) -> &TStore {
+        &self.store
+    }
+
+    /// The attachments of `time_entry_id`, in the order they were added.
+    pub async fn attachments(&self, time_entry_id: &str) -> anyhow::Result<Vec<AttachmentRow>> {
+        let state = self.store.state().await?.unwrap_or_default();
+        Ok(state
+            .attachments
+            .get(time_entry_id)
+            .cloned()
+            .unwrap_or_default())
+    }
+}
+
+#[cfg(test)]
+mod time_entry_attachments_queries_tests {
+    use super::*;
+    use crate::shared::infrastructure::projection_store::in_memory::InMemoryProjectionStore;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case::with_attachments("te-1", 1)]
+    #[case::without_attachments("te-2", 0)]
+    #[tokio::test]
+    async fn it_should_list_the_attachments_of_an_entry(
+        #[case] time_entry_id: &str,
+        #[case] expected: usize,
+    ) {
+        let store = InMemoryProjectionStore::<TimeEntryAttachmentsState>::new();
+        let mut state = TimeEntryAttachmentsState::default();
+        state.attachments.insert(
+            "te-1".to_string(),
+            vec![AttachmentRow {
+                attachment_id: "a-1".to_string(),
+                time_entry_id: "te-1".to_string(),
+                object_key: "attachments/te-1/a-1".to_string(),
+                file_name: "receipt.pdf".to_string(),
+                content_type: "application/pdf".to_string(),
+                size_bytes: 1_024,
+                added_at: 1,
+                added_by: "u-1".to_string(),
+            }],
+        );
+        store.save(state, 1).await.unwrap();
+
+        let attachments = TimeEntryAttachmentsQueryHandler::new(store)
+            .attachments(time_entry_id)
+            .await
+            .unwrap();
+
+        assert_eq!(attachments.len(), expected);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_fail_when_the_store_is_offline() {
+        let mut store = InMemoryProjectionStore::<TimeEntryAttachmentsState>::new();
+        store.toggle_offline();
+
+        let result = TimeEntryAttachmentsQueryHandler::new(store)
+            .attachments("te-1")
+            .await;
+
+        assert!(result.is_err());
+    }
+}
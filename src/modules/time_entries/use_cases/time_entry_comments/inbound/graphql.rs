@@ -0,0 +1,154 @@
+use async_graphql::SimpleObject;
+
+use crate::modules::time_entries::use_cases::time_entry_comments::projection::CommentRow;
+
+/// Resolved as `TimeEntry.comments`.
+#[derive(SimpleObject, Clone)]
+#[graphql(name = "TimeEntryComment")]
+pub struct GqlTimeEntryComment {
+    pub comment_id: String,
+    pub body: String,
+    pub added_at: i64,
+    pub added_by: String,
+}
+
+impl From<CommentRow> for GqlTimeEntryComment {
+    fn from(row: CommentRow) -> Self {
+        Self {
+            comment_id: row.comment_id,
+            body: row.body,
+            added_at: row.added_at,
+            added_by: row.added_by,
+        }
+    }
+}
+
+#[cfg(test)]
+mod time_entry_comments_graphql_inbound_tests {
+    use async_graphql::{EmptySubscription, Schema};
+
+    use crate::modules::time_entries::use_cases::list_time_entries::projection::{
+        ListTimeEntriesState, TimeEntryRow, TimeEntryStatus,
+    };
+    use crate::modules::time_entries::use_cases::list_time_entries::queries::ListTimeEntriesQueryHandler;
+    use crate::modules::time_entries::use_cases::time_entry_comments::projection::{
+        CommentRow, TimeEntryCommentsState,
+    };
+    use crate::modules::time_entries::use_cases::time_entry_comments::queries::TimeEntryCommentsQueryHandler;
+    use crate::shared::infrastructure::projection_store::ProjectionStore;
+    use crate::shared::infrastructure::projection_store::in_memory::InMemoryProjectionStore;
+    use crate::shared::infrastructure::projection_store::partitioned::PartitionedProjectionStore;
+    use crate::shared::infrastructure::request_context::RequestContext;
+    use crate::shell::graphql::{MutationRoot, QueryRoot};
+    use crate::shell::state::AppState;
+    use crate::tests::fixtures::tags::make_test_app_state;
+
+    fn make_schema_from_state(
+        state: AppState,
+    ) -> Schema<QueryRoot, MutationRoot, EmptySubscription> {
+        Schema::build(
+            QueryRoot::default(),
+            MutationRoot::default(),
+            EmptySubscription,
+        )
+        .data(state)
+        .finish()
+    }
+
+    fn req_ctx() -> RequestContext {
+        RequestContext {
+            user_id: "u-1".to_string(),
+            tenant_id: "tenant-test".to_string(),
+            role: Default::default(),
+            scope: Default::default(),
+        }
+    }
+
+    /// One entry of `u-1` with three comments.
+    async fn make_seeded_state() -> AppState {
+        let mut state = make_test_app_state();
+        let rows = InMemoryProjectionStore::<ListTimeEntriesState>::new();
+        let mut list = ListTimeEntriesState::default();
+        list.rows.insert(
+            "te-1".to_string(),
+            TimeEntryRow {
+                time_entry_id: "te-1".to_string(),
+                user_id: "u-1".to_string(),
+                started_at: Some(1_000),
+                ended_at: Some(2_000),
+                tag_ids: vec![],
+                status: TimeEntryStatus::Registered,
+                created_at: 0,
+                created_by: "u-1".to_string(),
+                updated_at: 0,
+                updated_by: "u-1".to_string(),
+                deleted_at: None,
+                hourly_rate: None,
+                breaks: vec![],
+                last_event_id: None,
+            },
+        );
+        rows.save(list, 1).await.unwrap();
+        state.list_time_entries_handler =
+            ListTimeEntriesQueryHandler::new(PartitionedProjectionStore::single(rows));
+
+        let comments = InMemoryProjectionStore::<TimeEntryCommentsState>::new();
+        let mut thread = TimeEntryCommentsState::default();
+        thread.comments.insert(
+            "te-1".to_string(),
+            (1..=3)
+                .map(|n| CommentRow {
+                    comment_id: format!("c-{n}"),
+                    time_entry_id: "te-1".to_string(),
+                    body: format!("comment {n}"),
+                    added_at: n,
+                    added_by: "manager-1".to_string(),
+                })
+                .collect(),
+        );
+        comments.save(thread, 3).await.unwrap();
+        state.time_entry_comments_handler = TimeEntryCommentsQueryHandler::new(comments);
+        state
+    }
+
+    #[tokio::test]
+    async fn resolver_pages_through_the_comments_of_each_entry() {
+        let schema = make_schema_from_state(make_seeded_state().await);
+
+        let result = schema
+            .execute(
+                async_graphql::Request::new(
+                    "{ listTimeEntries { timeEntryId comments(offset: 1, limit: 1) { commentId body addedBy } } }",
+                )
+                .data(req_ctx()),
+            )
+            .await;
+
+        assert!(result.errors.is_empty(), "{:?}", result.errors);
+        assert_eq!(
+            result.data.to_string(),
+            r#"{listTimeEntries: [{timeEntryId: "te-1", comments: [{commentId: "c-2", body: "comment 2", addedBy: "manager-1"}]}]}"#
+        );
+    }
+
+    #[tokio::test]
+    async fn resolver_lists_twenty_comments_by_default() {
+        let schema = make_schema_from_state(make_seeded_state().await);
+
+        let result = schema
+            .execute(
+                async_graphql::Request::new("{ listTimeEntries { comments { commentId } } }")
+                    .data(req_ctx()),
+            )
+            .await;
+
+        let data = result.data.into_json().unwrap();
+        assert_eq!(
+            data["listTimeEntries"][0]["comments"]
+                .as_array()
+                .unwrap()
+                .len(),
+            3
+        );
+    }
+}
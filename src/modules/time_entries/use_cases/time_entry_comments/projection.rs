@@ -0,0 +1,120 @@
+use crate::modules::time_entries::core::events::TimeEntryEvent;
+use crate::shared::infrastructure::capacity::{EvictRows, evict_oldest};
+use std::collections::HashMap;
+
+pub const SCHEMA_VERSION: u32 = 1;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommentRow {
+    pub comment_id: String,
+    pub time_entry_id: String,
+    pub body: String,
+    pub added_at: i64,
+    pub added_by: String,
+}
+
+/// The comments of every entry, keyed by entry and oldest first.
+#[derive(Clone, Default)]
+pub struct TimeEntryCommentsState {
+    pub comments: HashMap<String, Vec<CommentRow>>,
+}
+
+impl TimeEntryCommentsState {
+    /// Adds a comment once, however often its event is replayed, and drops the comments of
+    /// deleted entries. Other events leave the comments as they are.
+    pub fn apply(&mut self, event: &TimeEntryEvent) {
+        match event {
+            TimeEntryEvent::TimeEntryCommentAddedV1(e) => {
+                let comments = self
+                    .comments
+                    .entry(e.time_entry_id.to_string())
+                    .or_default();
+                if comments
+                    .iter()
+                    .any(|comment| comment.comment_id == e.comment_id)
+                {
+                    return;
+                }
+                comments.push(CommentRow {
+                    comment_id: e.comment_id.clone(),
+                    time_entry_id: e.time_entry_id.to_string(),
+                    body: e.body.clone(),
+                    added_at: e.added_at,
+                    added_by: e.added_by.to_string(),
+                });
+            }
+            TimeEntryEvent::TimeEntryDeletedV1(e) => {
+                self.comments.remove(e.time_entry_id.as_str());
+            }
+            _ => {}
+        }
+    }
+}
+
+/// A row is an entry with its comments, last updated when the latest was added.
+impl EvictRows for TimeEntryCommentsState {
+    fn row_count(&self) -> usize {
+        self.comments.len()
+    }
+
+    fn evict_rows(&mut self, max_rows: usize) -> usize {
+        evict_oldest(&mut self.comments, max_rows, |rows| {
+            rows.iter().map(|row| row.added_at).max().unwrap_or(0)
+        })
+    }
+}
+
+#[cfg(test)]
+mod time_entry_comments_projection_tests {
+    use super::*;
+    use crate::modules::time_entries::core::events::v1::time_entry_comment_added::TimeEntryCommentAddedV1;
+    use crate::modules::time_entries::core::events::v1::time_entry_deleted::TimeEntryDeletedV1;
+    use crate::tests::fixtures::events::time_entry_start_set_v1::make_time_entry_start_set_v1_event;
+    use rstest::rstest;
+
+    fn comment_added(comment_id: &str, added_at: i64) -> TimeEntryEvent {
+        TimeEntryEvent::TimeEntryCommentAddedV1(TimeEntryCommentAddedV1 {
+            time_entry_id: "te-fixed-0001".into(),
+            comment_id: comment_id.to_string(),
+            body: format!("comment {comment_id}"),
+            added_at,
+            added_by: "manager-fixed-0001".into(),
+        })
+    }
+
+    fn comment_ids(state: &TimeEntryCommentsState) -> Vec<&str> {
+        state.comments["te-fixed-0001"]
+            .iter()
+            .map(|comment| comment.comment_id.as_str())
+            .collect()
+    }
+
+    #[rstest]
+    fn it_should_keep_comments_in_order_once() {
+        let mut state = TimeEntryCommentsState::default();
+
+        state.apply(&TimeEntryEvent::TimeEntryStartSetV1(
+            make_time_entry_start_set_v1_event(),
+        ));
+        state.apply(&comment_added("c-1", 1_000));
+        state.apply(&comment_added("c-2", 2_000));
+        state.apply(&comment_added("c-1", 1_000));
+
+        assert_eq!(comment_ids(&state), vec!["c-1", "c-2"]);
+        assert_eq!(state.comments["te-fixed-0001"][0].body, "comment c-1");
+    }
+
+    #[rstest]
+    fn it_should_drop_the_comments_of_deleted_entries() {
+        let mut state = TimeEntryCommentsState::default();
+        state.apply(&comment_added("c-1", 1_000));
+
+        state.apply(&TimeEntryEvent::TimeEntryDeletedV1(TimeEntryDeletedV1 {
+            time_entry_id: "te-fixed-0001".into(),
+            deleted_at: 2_000,
+            deleted_by: "user-fixed-0001".into(),
+        }));
+
+        assert!(state.comments.is_empty());
+    }
+}
@@ -0,0 +1,112 @@
+use crate::modules::time_entries::use_cases::time_entry_comments::projection::{
+    CommentRow, TimeEntryCommentsState,
+};
+use crate::shared::infrastructure::projection_store::ProjectionStore;
+
+#[derive(Clone)]
+pub struct TimeEntryCommentsQueryHandler<TStore>
+where
+    TStore: ProjectionStore<TimeEntryCommentsState> + Send + Sync + 'static,
+{
+    store: TStore,
+}
+
+impl<TStore> TimeEntryCommentsQueryHandler<TStore>
+where
+    TStore: ProjectionStore<TimeEntryCommentsState> + Send + Sync + 'static,
+{
+    pub fn new(store: TStore) -> Self {
+        Self { store }
+    }
+
+    pub fn store(&self) -> &TStore {
+        &self.store
+    }
+
+    /// A page of the comments on `time_entry_id`, oldest first.
+    pub async fn comments(
+        &self,
+        time_entry_id: &str,
+        offset: u64,
+        limit: u64,
+    ) -> anyhow::Result<Vec<CommentRow>> {
+        let state = self.store.state().await?.unwrap_or_default();
+        Ok(state
+            .comments
+            .get(time_entry_id)
+            .map(|comments| {
+                comments
+                    .iter()
+                    .skip(offset as usize)
+                    .take(limit as usize)
+                    .cloned()
+                    .collect()
+            })
+            .unwrap_or_default())
+    }
+}
+
+#[cfg(test)]
+mod time_entry_comments_queries_tests {
+    use super::*;
+    use crate::shared::infrastructure::projection_store::in_memory::InMemoryProjectionStore;
+    use rstest::rstest;
+
+    async fn handler()
+    -> TimeEntryCommentsQueryHandler<InMemoryProjectionStore<TimeEntryCommentsState>> {
+        let store = InMemoryProjectionStore::<TimeEntryCommentsState>::new();
+        let mut state = TimeEntryCommentsState::default();
+        state.comments.insert(
+            "te-1".to_string(),
+            (1..=3)
+                .map(|n| CommentRow {
+                    comment_id: format!("c-{n}"),
+                    time_entry_id: "te-1".to_string(),
+                    body: format!("comment {n}"),
+                    added_at: n,
+                    added_by: "manager-1".to_string(),
+                })
+                .collect(),
+        );
+        store.save(state, 3).await.unwrap();
+        TimeEntryCommentsQueryHandler::new(store)
+    }
+
+    #[rstest]
+    #[case::first_page("te-1", 0, 2, vec!["c-1", "c-2"])]
+    #[case::last_page("te-1", 2, 2, vec!["c-3"])]
+    #[case::past_the_end("te-1", 5, 2, vec![])]
+    #[case::without_comments("te-2", 0, 2, vec![])]
+    #[tokio::test]
+    async fn it_should_page_through_the_comments_oldest_first(
+        #[case] time_entry_id: &str,
+        #[case] offset: u64,
+        #[case] limit: u64,
+        #[case] expected: Vec<&str>,
+    ) {
+        let page = handler()
+            .await
+            .comments(time_entry_id, offset, limit)
+            .await
+            .unwrap();
+
+        let comment_ids: Vec<&str> = page
+            .iter()
+            .map(|comment| comment.comment_id.as_str())
+            .collect();
+        assert_eq!(comment_ids, expected);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_fail_when_the_store_is_offline() {
+        let mut store = InMemoryProjectionStore::<TimeEntryCommentsState>::new();
+        store.toggle_offline();
+
+        let result = TimeEntryCommentsQueryHandler::new(store)
+            .comments("te-1", 0, 20)
+            .await;
+
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,92 @@
+use crate::modules::time_entries::core::policies::{RoundedCommand, Rounding};
+use crate::modules::time_entries::core::tag::Tag;
+use crate::modules::time_entries::use_cases::set_ended_at::command::SetEndedAt;
+use crate::modules::time_entries::use_cases::set_started_at::command::SetStartedAt;
+use crate::modules::time_entries::use_cases::set_time_entry_tags::command::SetTimeEntryTags;
+use crate::shared::application::server_time::StampedCommand;
+use crate::shared::core::primitives::{TimeEntryId, Timestamp, UserId};
+
+/// Replaces the interval and tags of an existing entry at once.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UpdateTimeEntry {
+    pub time_entry_id: TimeEntryId,
+    pub user_id: UserId,
+    /// As supplied by the client; the decider records them rounded per `rounding`.
+    pub started_at: i64,
+    pub ended_at: i64,
+    pub tag_ids: Vec<Tag>,
+    pub rounding: Option<Rounding>,
+    /// Stamped by the handler from its clock.
+    pub updated_at: i64,
+    pub updated_by: UserId,
+}
+
+impl UpdateTimeEntry {
+    /// `user_id` updates their own entry; the handler stamps `updated_at`.
+    pub fn new(
+        time_entry_id: TimeEntryId,
+        user_id: UserId,
+        started_at: i64,
+        ended_at: i64,
+        tag_ids: Vec<Tag>,
+    ) -> Self {
+        Self {
+            time_entry_id,
+            updated_by: user_id.clone(),
+            user_id,
+            started_at,
+            ended_at,
+            tag_ids,
+            rounding: None,
+            updated_at: 0,
+        }
+    }
+
+    pub fn set_started_at(&self) -> SetStartedAt {
+        SetStartedAt {
+            time_entry_id: self.time_entry_id.clone(),
+            user_id: self.user_id.clone(),
+            started_at: self.started_at,
+            rounding: self.rounding,
+            updated_at: self.updated_at,
+            updated_by: self.updated_by.clone(),
+        }
+    }
+
+    pub fn set_ended_at(&self) -> SetEndedAt {
+        SetEndedAt {
+            time_entry_id: self.time_entry_id.clone(),
+            user_id: self.user_id.clone(),
+            ended_at: self.ended_at,
+            rounding: self.rounding,
+            updated_at: self.updated_at,
+            updated_by: self.updated_by.clone(),
+        }
+    }
+
+    pub fn set_tags(&self) -> SetTimeEntryTags {
+        SetTimeEntryTags {
+            time_entry_id: self.time_entry_id.clone(),
+            user_id: self.user_id.clone(),
+            tag_ids: self.tag_ids.clone(),
+            updated_at: self.updated_at,
+            updated_by: self.updated_by.clone(),
+        }
+    }
+}
+
+impl StampedCommand for UpdateTimeEntry {
+    fn stamp(&mut self, now: Timestamp) {
+        self.updated_at = now.as_millis();
+    }
+
+    fn client_instants(&self) -> Vec<i64> {
+        vec![self.started_at, self.ended_at]
+    }
+}
+
+impl RoundedCommand for UpdateTimeEntry {
+    fn set_rounding(&mut self, rounding: Rounding) {
+        self.rounding = Some(rounding);
+    }
+}
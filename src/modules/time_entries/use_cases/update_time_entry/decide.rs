@@ -0,0 +1,258 @@
+use crate::modules::time_entries::core::events::TimeEntryEvent;
+use crate::modules::time_entries::core::evolve::evolve;
+use crate::modules::time_entries::core::intents::TimeEntryIntent;
+use crate::modules::time_entries::core::state::TimeEntryState;
+use crate::modules::time_entries::use_cases::set_ended_at::decide::decide_set_ended_at;
+use crate::modules::time_entries::use_cases::set_started_at::decide::decide_set_started_at;
+use crate::modules::time_entries::use_cases::set_time_entry_tags::decide::decide_set_time_entry_tags;
+use crate::modules::time_entries::use_cases::update_time_entry::command::UpdateTimeEntry;
+use crate::modules::time_entries::use_cases::update_time_entry::decision::{DecideError, Decision};
+use crate::shared::core::decider::{self, Decider};
+
+/// The decisions of the single-field deciders, applied one after the other so each decides
+/// on the state the previous one left.
+struct Steps {
+    state: TimeEntryState,
+    events: Vec<TimeEntryEvent>,
+    intents: Vec<TimeEntryIntent>,
+}
+
+impl Steps {
+    fn apply<E>(
+        &mut self,
+        decision: decider::Decision<TimeEntryEvent, TimeEntryIntent, E>,
+        rejected: fn(E) -> DecideError,
+    ) -> Result<(), DecideError> {
+        match decision {
+            decider::Decision::Accepted { events, intents } => {
+                for event in events {
+                    self.state = evolve(self.state.clone(), event.clone());
+                    self.events.push(event);
+                }
+                self.intents.extend(intents);
+                Ok(())
+            }
+            decider::Decision::Rejected { reason } => Err(rejected(reason)),
+        }
+    }
+}
+
+/// Sets the start, end and tags of an existing entry, each only when it changes. The end is
+/// moved first when the new start lies at or past the current end, so the interval stays
+/// valid in between.
+pub fn decide_update_time_entry(state: &TimeEntryState, command: UpdateTimeEntry) -> Decision {
+    let (started_at, ended_at, tag_ids) = match state {
+        TimeEntryState::None | TimeEntryState::Deleted { .. } => {
+            return Decision::Rejected {
+                reason: DecideError::NotFound,
+            };
+        }
+        TimeEntryState::Approved { .. } => {
+            return Decision::Rejected {
+                reason: DecideError::Approved,
+            };
+        }
+        TimeEntryState::Draft {
+            started_at,
+            ended_at,
+            tag_ids,
+            ..
+        } => (*started_at, *ended_at, tag_ids),
+        TimeEntryState::Registered {
+            interval, tag_ids, ..
+        } => (
+            Some(interval.start().as_millis()),
+            Some(interval.end().as_millis()),
+            tag_ids,
+        ),
+    };
+
+    let mut steps = Steps {
+        state: state.clone(),
+        events: vec![],
+        intents: vec![],
+    };
+    let end_first = ended_at.is_some_and(|ended_at| command.started_at >= ended_at);
+    let result = (|| {
+        if end_first {
+            steps.apply(
+                decide_set_ended_at(&steps.state, command.set_ended_at()),
+                DecideError::EndedAt,
+            )?;
+        }
+        if started_at != Some(command.started_at) {
+            steps.apply(
+                decide_set_started_at(&steps.state, command.set_started_at()),
+                DecideError::StartedAt,
+            )?;
+        }
+        if !end_first && ended_at != Some(command.ended_at) {
+            steps.apply(
+                decide_set_ended_at(&steps.state, command.set_ended_at()),
+                DecideError::EndedAt,
+            )?;
+        }
+        if *tag_ids != command.tag_ids {
+            steps.apply(
+                decide_set_time_entry_tags(&steps.state, command.set_tags()),
+                DecideError::Tags,
+            )?;
+        }
+        Ok(())
+    })();
+
+    match result {
+        Ok(()) => Decision::Accepted {
+            events: steps.events,
+            intents: steps.intents,
+        },
+        Err(reason) => Decision::Rejected { reason },
+    }
+}
+
+pub struct UpdateTimeEntryDecider;
+
+impl Decider for UpdateTimeEntryDecider {
+    type State = TimeEntryState;
+    type Command = UpdateTimeEntry;
+    type Event = TimeEntryEvent;
+    type Intent = TimeEntryIntent;
+    type Error = DecideError;
+
+    fn initial_state() -> TimeEntryState {
+        TimeEntryState::None
+    }
+
+    fn evolve(state: TimeEntryState, event: TimeEntryEvent) -> TimeEntryState {
+        evolve(state, event)
+    }
+
+    fn decide(state: &TimeEntryState, command: UpdateTimeEntry) -> Decision {
+        decide_update_time_entry(state, command)
+    }
+}
+
+#[cfg(test)]
+mod decide_update_time_entry_tests {
+    use super::*;
+    use crate::modules::time_entries::core::tag::Tag;
+    use crate::modules::time_entries::core::time_interval::TimeInterval;
+    use crate::modules::time_entries::use_cases::set_started_at::decision::DecideError as StartedAtError;
+    use rstest::rstest;
+
+    fn registered(started_at: i64, ended_at: i64) -> TimeEntryState {
+        TimeEntryState::Registered {
+            time_entry_id: "te-1".into(),
+            user_id: "u-1".into(),
+            interval: TimeInterval::new(started_at, ended_at).unwrap(),
+            breaks: vec![],
+            tag_ids: vec![Tag::parse("tag-1").unwrap()],
+            created_at: 0,
+            created_by: "u-1".into(),
+            hourly_rate: None,
+        }
+    }
+
+    fn update(started_at: i64, ended_at: i64, tag_ids: &[&str]) -> UpdateTimeEntry {
+        UpdateTimeEntry {
+            updated_at: 10_000,
+            ..UpdateTimeEntry::new(
+                "te-1".into(),
+                "u-1".into(),
+                started_at,
+                ended_at,
+                Tag::parse_all(tag_ids).unwrap(),
+            )
+        }
+    }
+
+    fn accepted(decision: Decision) -> Vec<TimeEntryEvent> {
+        match decision {
+            Decision::Accepted { events, .. } => events,
+            Decision::Rejected { reason } => panic!("expected accepted, got {reason:?}"),
+        }
+    }
+
+    #[rstest]
+    fn it_should_only_set_what_changed() {
+        let events = accepted(decide_update_time_entry(
+            &registered(1_000, 2_000),
+            update(1_000, 3_000, &["tag-1"]),
+        ));
+
+        assert!(matches!(
+            &events[..],
+            [TimeEntryEvent::TimeEntryEndSetV1(e)] if e.ended_at == 3_000
+        ));
+    }
+
+    #[rstest]
+    fn it_should_append_nothing_when_nothing_changed() {
+        assert!(
+            accepted(decide_update_time_entry(
+                &registered(1_000, 2_000),
+                update(1_000, 2_000, &["tag-1"]),
+            ))
+            .is_empty()
+        );
+    }
+
+    #[rstest]
+    fn it_should_move_the_end_first_when_the_entry_moves_past_it() {
+        let state = registered(1_000, 2_000);
+
+        let events = accepted(decide_update_time_entry(
+            &state,
+            update(5_000, 6_000, &["tag-2"]),
+        ));
+
+        assert!(matches!(
+            &events[..],
+            [
+                TimeEntryEvent::TimeEntryEndSetV1(_),
+                TimeEntryEvent::TimeEntryStartSetV1(_),
+                TimeEntryEvent::TimeEntryTagsSetV1(_),
+            ]
+        ));
+        assert_eq!(
+            events.into_iter().fold(state, evolve),
+            TimeEntryState::Registered {
+                time_entry_id: "te-1".into(),
+                user_id: "u-1".into(),
+                interval: TimeInterval::new(5_000, 6_000).unwrap(),
+                breaks: vec![],
+                tag_ids: vec![Tag::parse("tag-2").unwrap()],
+                created_at: 0,
+                created_by: "u-1".into(),
+                hourly_rate: None,
+            }
+        );
+    }
+
+    #[rstest]
+    #[case::missing(TimeEntryState::None)]
+    #[case::deleted(TimeEntryState::Deleted {
+        time_entry_id: "te-1".into(),
+        user_id: "u-1".into(),
+        deleted_at: 1,
+        deleted_by: "u-1".into(),
+    })]
+    fn it_should_not_create_entries(#[case] state: TimeEntryState) {
+        assert!(matches!(
+            decide_update_time_entry(&state, update(1_000, 2_000, &[])),
+            Decision::Rejected {
+                reason: DecideError::NotFound
+            }
+        ));
+    }
+
+    #[rstest]
+    fn it_should_surface_what_the_field_deciders_reject() {
+        assert!(matches!(
+            decide_update_time_entry(&registered(1_000, 2_000), update(3_000, 2_500, &[])),
+            Decision::Rejected {
+                reason: DecideError::StartedAt(StartedAtError::InvalidInterval)
+            }
+        ));
+    }
+}
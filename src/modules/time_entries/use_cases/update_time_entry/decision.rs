@@ -0,0 +1,52 @@
+use crate::modules::time_entries::core::day_capacity::DayCapacityError;
+use crate::modules::time_entries::core::days_off::DayOffError;
+use crate::modules::time_entries::core::events::TimeEntryEvent;
+use crate::modules::time_entries::core::intents::TimeEntryIntent;
+use crate::modules::time_entries::core::period_locks::PeriodLockError;
+use crate::modules::time_entries::core::policies::PolicyError;
+use crate::modules::time_entries::core::user_time_entries::UserTimeEntriesError;
+use crate::modules::time_entries::use_cases::set_ended_at::decision::DecideError as EndedAtError;
+use crate::modules::time_entries::use_cases::set_started_at::decision::DecideError as StartedAtError;
+use crate::modules::time_entries::use_cases::set_time_entry_tags::decision::DecideError as TagsError;
+use crate::shared::application::server_time::ClockSkewError;
+use crate::shared::core::decider;
+use thiserror::Error;
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum DecideError {
+    /// Updating never creates an entry; it is missing or was deleted.
+    #[error("time entry not found")]
+    NotFound,
+
+    #[error("time entry is approved and can no longer be changed")]
+    Approved,
+
+    #[error(transparent)]
+    StartedAt(StartedAtError),
+
+    #[error(transparent)]
+    EndedAt(EndedAtError),
+
+    #[error(transparent)]
+    Tags(TagsError),
+
+    #[error(transparent)]
+    UserTimeEntries(#[from] UserTimeEntriesError),
+
+    #[error(transparent)]
+    PeriodLocked(#[from] PeriodLockError),
+
+    #[error(transparent)]
+    Policy(#[from] PolicyError),
+
+    #[error(transparent)]
+    DayOff(#[from] DayOffError),
+
+    #[error(transparent)]
+    OverCapacity(#[from] DayCapacityError),
+
+    #[error(transparent)]
+    ClockSkew(#[from] ClockSkewError),
+}
+
+pub type Decision = decider::Decision<TimeEntryEvent, TimeEntryIntent, DecideError>;
@@ -0,0 +1,255 @@
+use crate::modules::time_entries::adapters::outbound::intent_outbox::TimeEntryIntentDispatcher;
+use crate::modules::time_entries::core::day_capacity::DayCapacity;
+use crate::modules::time_entries::core::days_off::AbsencePolicy;
+use crate::modules::time_entries::core::events::TimeEntryEvent;
+use crate::modules::time_entries::core::policies::Policies;
+use crate::modules::time_entries::use_cases::update_time_entry::command::UpdateTimeEntry;
+use crate::modules::time_entries::use_cases::update_time_entry::decide::UpdateTimeEntryDecider;
+use crate::modules::time_entries::use_cases::update_time_entry::decision::DecideError;
+use crate::modules::time_entries::use_cases::user_time_entries::day_totals::SharedDayTotals;
+use crate::modules::time_entries::use_cases::user_time_entries::sharded_handler::{
+    PeriodLockStreams, SharedCalendar, UserShardedHandler, UserStreams,
+};
+use crate::shared::application::command_bus::CommandHandler;
+use crate::shared::application::event_sourced_handler::EventSourcedError;
+use crate::shared::application::server_time::SkewWindow;
+use crate::shared::infrastructure::clock::SharedClock;
+use crate::shared::infrastructure::event_bus::SharedEventBus;
+use crate::shared::infrastructure::event_store::EventStore;
+use crate::shared::infrastructure::feature_flags::SharedFeatureFlags;
+use crate::shared::infrastructure::intent_outbox::{DomainOutbox, OutboxError};
+use crate::shared::infrastructure::policy_store::SharedPolicyStore;
+use async_trait::async_trait;
+
+pub type ApplicationError = EventSourcedError<DecideError, OutboxError>;
+
+#[derive(Debug, Clone)]
+pub struct UpdateTimeEntryHandler<TEventStore, TOutbox>
+where
+    TEventStore: EventStore<TimeEntryEvent> + Send + Sync + 'static,
+    TOutbox: DomainOutbox + Send + Sync + 'static,
+{
+    inner:
+        UserShardedHandler<UpdateTimeEntryDecider, TEventStore, TimeEntryIntentDispatcher<TOutbox>>,
+}
+
+impl<TEventStore, TOutbox> UpdateTimeEntryHandler<TEventStore, TOutbox>
+where
+    TEventStore: EventStore<TimeEntryEvent> + Send + Sync + 'static,
+    TOutbox: DomainOutbox + Send + Sync + 'static,
+{
+    pub fn new(event_store: TEventStore, outbox: TOutbox) -> Self {
+        Self {
+            inner: UserShardedHandler::new(event_store, TimeEntryIntentDispatcher::new(outbox)),
+        }
+    }
+
+    /// Also maintain the per-user `UserTimeEntries-{user_id}` streams, enforcing that a
+    /// user's entries never overlap.
+    pub fn with_user_streams(mut self, user_streams: UserStreams) -> Self {
+        self.inner = self.inner.with_user_streams(user_streams);
+        self
+    }
+
+    /// Refuse changes to entries inside a locked payroll period.
+    pub fn with_period_locks(mut self, period_locks: PeriodLockStreams) -> Self {
+        self.inner = self.inner.with_period_locks(period_locks);
+        self
+    }
+
+    /// Warn about or refuse moving entries onto holidays and approved absences.
+    pub fn with_calendar(mut self, calendar: SharedCalendar, policy: AbsencePolicy) -> Self {
+        self.inner = self.inner.with_calendar(calendar, policy);
+        self
+    }
+
+    /// Warn about or refuse moving entries onto days already holding what `capacity` allows.
+    pub fn with_day_capacity(mut self, day_totals: SharedDayTotals, capacity: DayCapacity) -> Self {
+        self.inner = self.inner.with_day_capacity(day_totals, capacity);
+        self
+    }
+
+    /// Read the overlap and maximum duration rules rolled out per tenant from `feature_flags`.
+    pub fn with_feature_flags(mut self, feature_flags: SharedFeatureFlags) -> Self {
+        self.inner = self.inner.with_feature_flags(feature_flags);
+        self
+    }
+
+    /// Hold each tenant to the policies in `store`, or to `defaults` where it has none.
+    pub fn with_policies(mut self, store: SharedPolicyStore, defaults: Policies) -> Self {
+        self.inner = self.inner.with_policies(store, defaults);
+        self
+    }
+
+    /// Read the time commands are recorded at from `clock` instead of the system clock.
+    pub fn with_clock(mut self, clock: SharedClock) -> Self {
+        self.inner = self.inner.with_clock(clock);
+        self
+    }
+
+    /// Refuse client-supplied instants outside `skew_window` of server time.
+    pub fn with_skew_window(mut self, skew_window: SkewWindow) -> Self {
+        self.inner = self.inner.with_skew_window(skew_window);
+        self
+    }
+
+    /// Announce the appended events on `event_bus`, for the projectors to pick up.
+    pub fn with_event_bus(mut self, event_bus: SharedEventBus<TimeEntryEvent>) -> Self {
+        self.inner = self.inner.with_event_bus(event_bus);
+        self
+    }
+
+    pub async fn handle(
+        &self,
+        stream_id: &str,
+        command: UpdateTimeEntry,
+    ) -> Result<(), ApplicationError> {
+        self.inner.handle(stream_id, command).await
+    }
+
+    /// Updates the entry under the rules rolled out to `tenant_id` and its policies, only if
+    /// its stream is still at `expected_version` when one is given.
+    pub async fn handle_at_version(
+        &self,
+        tenant_id: &str,
+        stream_id: &str,
+        expected_version: Option<i64>,
+        command: UpdateTimeEntry,
+    ) -> Result<(), ApplicationError> {
+        self.inner
+            .handle_at_version(Some(tenant_id), stream_id, expected_version, command)
+            .await
+    }
+}
+
+#[async_trait]
+impl<TEventStore, TOutbox> CommandHandler<UpdateTimeEntry>
+    for UpdateTimeEntryHandler<TEventStore, TOutbox>
+where
+    TEventStore: EventStore<TimeEntryEvent> + Send + Sync + 'static,
+    TOutbox: DomainOutbox + Send + Sync + 'static,
+{
+    type Error = ApplicationError;
+
+    async fn handle(
+        &self,
+        stream_id: &str,
+        command: UpdateTimeEntry,
+    ) -> Result<(), ApplicationError> {
+        UpdateTimeEntryHandler::handle(self, stream_id, command).await
+    }
+}
+
+#[cfg(test)]
+mod update_time_entry_handler_tests {
+    use crate::modules::time_entries::core::events::TimeEntryEvent;
+    use crate::modules::time_entries::core::user_time_entries::{
+        UserTimeEntriesEvent, UserTimeEntriesState, evolve_user_time_entries, user_stream_id,
+    };
+    use crate::modules::time_entries::use_cases::set_ended_at::handler::SetEndedAtHandler;
+    use crate::modules::time_entries::use_cases::set_started_at::handler::SetStartedAtHandler;
+    use crate::modules::time_entries::use_cases::update_time_entry::command::UpdateTimeEntry;
+    use crate::modules::time_entries::use_cases::update_time_entry::decision::DecideError;
+    use crate::modules::time_entries::use_cases::update_time_entry::handler::{
+        ApplicationError, UpdateTimeEntryHandler,
+    };
+    use crate::shared::infrastructure::event_store::in_memory::InMemoryEventStore;
+    use crate::shared::infrastructure::event_store::{EventStore, EventStoreError};
+    use crate::shared::infrastructure::intent_outbox::in_memory::InMemoryDomainOutbox;
+    use crate::tests::fixtures::commands::set_ended_at::SetEndedAtBuilder;
+    use crate::tests::fixtures::commands::set_started_at::SetStartedAtBuilder;
+    use rstest::rstest;
+    use std::sync::Arc;
+
+    const STREAM_ID: &str = "TimeEntry-te-fixed-0001";
+
+    fn update(started_at: i64, ended_at: i64) -> UpdateTimeEntry {
+        UpdateTimeEntry::new(
+            "te-fixed-0001".into(),
+            "user-fixed-0001".into(),
+            started_at,
+            ended_at,
+            vec![],
+        )
+    }
+
+    async fn registered(
+        event_store: &InMemoryEventStore<TimeEntryEvent>,
+        user_streams: &InMemoryEventStore<UserTimeEntriesEvent>,
+    ) {
+        let outbox = InMemoryDomainOutbox::new();
+        SetStartedAtHandler::new(event_store.clone(), outbox.clone())
+            .with_user_streams(Arc::new(user_streams.clone()))
+            .handle(STREAM_ID, SetStartedAtBuilder::new().build())
+            .await
+            .unwrap();
+        SetEndedAtHandler::new(event_store.clone(), outbox)
+            .with_user_streams(Arc::new(user_streams.clone()))
+            .handle(STREAM_ID, SetEndedAtBuilder::new().build())
+            .await
+            .unwrap();
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_move_the_entry_and_its_claim() {
+        let event_store = InMemoryEventStore::<TimeEntryEvent>::new();
+        let user_streams = InMemoryEventStore::<UserTimeEntriesEvent>::new();
+        registered(&event_store, &user_streams).await;
+        let version = event_store.load(STREAM_ID).await.unwrap().version;
+        let handler = UpdateTimeEntryHandler::new(event_store.clone(), InMemoryDomainOutbox::new())
+            .with_user_streams(Arc::new(user_streams.clone()));
+
+        handler
+            .handle_at_version("tenant-1", STREAM_ID, Some(version), update(1_000, 2_000))
+            .await
+            .unwrap();
+
+        let user_state = user_streams
+            .load(&user_stream_id("user-fixed-0001"))
+            .await
+            .unwrap()
+            .events
+            .into_iter()
+            .fold(UserTimeEntriesState::default(), evolve_user_time_entries);
+        let interval = user_state.intervals["te-fixed-0001"];
+        assert_eq!(
+            (interval.started_at, interval.ended_at),
+            (Some(1_000), Some(2_000))
+        );
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_refuse_updates_decided_on_an_older_version() {
+        let event_store = InMemoryEventStore::<TimeEntryEvent>::new();
+        let user_streams = InMemoryEventStore::<UserTimeEntriesEvent>::new();
+        registered(&event_store, &user_streams).await;
+        let handler = UpdateTimeEntryHandler::new(event_store, InMemoryDomainOutbox::new());
+
+        let result = handler
+            .handle_at_version("tenant-1", STREAM_ID, Some(1), update(1_000, 2_000))
+            .await;
+
+        assert!(matches!(
+            result,
+            Err(ApplicationError::VersionConflict(
+                EventStoreError::VersionMismatch { expected: 1, .. }
+            ))
+        ));
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_not_create_entries() {
+        let handler = UpdateTimeEntryHandler::new(
+            InMemoryEventStore::<TimeEntryEvent>::new(),
+            InMemoryDomainOutbox::new(),
+        );
+
+        assert!(matches!(
+            handler.handle(STREAM_ID, update(1_000, 2_000)).await,
+            Err(ApplicationError::Domain(DecideError::NotFound))
+        ));
+    }
+}
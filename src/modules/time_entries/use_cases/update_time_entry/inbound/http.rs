@@ -0,0 +1,244 @@
+use axum::{
+    Json,
+    extract::{Path, State, rejection::JsonRejection},
+    http::{HeaderMap, StatusCode, header},
+    response::IntoResponse,
+};
+use serde::Deserialize;
+
+use crate::modules::time_entries::core::tag::Tag;
+use crate::modules::time_entries::use_cases::set_ended_at::decision::DecideError as EndedAtError;
+use crate::modules::time_entries::use_cases::set_started_at::decision::DecideError as StartedAtError;
+use crate::modules::time_entries::use_cases::update_time_entry::command::UpdateTimeEntry;
+use crate::modules::time_entries::use_cases::update_time_entry::decision::DecideError;
+use crate::modules::time_entries::use_cases::update_time_entry::handler::ApplicationError;
+use crate::shared::core::primitives::TimeEntryId;
+use crate::shared::infrastructure::event_store::{EventStore, EventStoreError};
+use crate::shared::infrastructure::request_context::RequestContext;
+use crate::shell::http::etag::{etag, if_match};
+use crate::shell::state::AppState;
+
+#[derive(Deserialize)]
+pub struct UpdateTimeEntryBody {
+    pub started_at: i64,
+    pub ended_at: i64,
+    pub tag_ids: Vec<String>,
+}
+
+/// PUT /time-entries/{id} — replaces the interval and tags of an existing entry. With
+/// `If-Match`, only while the entry is still at that version (412 otherwise). Answers with
+/// the entry's new `ETag`.
+pub async fn handle_put(
+    State(state): State<AppState>,
+    request_ctx: RequestContext,
+    Path(time_entry_id): Path<String>,
+    headers: HeaderMap,
+    body: Result<Json<UpdateTimeEntryBody>, JsonRejection>,
+) -> impl IntoResponse {
+    if !request_ctx
+        .principal()
+        .can_register_for(&request_ctx.user_id)
+    {
+        return StatusCode::FORBIDDEN.into_response();
+    }
+    let Ok(time_entry_id) = TimeEntryId::parse_v7(&time_entry_id) else {
+        return StatusCode::UNPROCESSABLE_ENTITY.into_response();
+    };
+    let Ok(expected_version) = if_match(&headers) else {
+        return StatusCode::PRECONDITION_FAILED.into_response();
+    };
+    let Json(body) = match body {
+        Ok(b) => b,
+        Err(_) => return StatusCode::UNPROCESSABLE_ENTITY.into_response(),
+    };
+    let Ok(tag_ids) = Tag::parse_all(&body.tag_ids) else {
+        return StatusCode::UNPROCESSABLE_ENTITY.into_response();
+    };
+    // Entries of users the caller may not register for are not theirs to find.
+    if let Ok(Some((view, _))) = state
+        .list_time_entries_handler
+        .get(time_entry_id.as_str())
+        .await
+        && !request_ctx.principal().can_register_for(&view.user_id)
+    {
+        return (StatusCode::NOT_FOUND, "time entry not found").into_response();
+    }
+
+    let stream_id = state
+        .stream_naming
+        .time_entry(Some(&request_ctx.tenant_id), &time_entry_id);
+    let command = UpdateTimeEntry::new(
+        time_entry_id,
+        request_ctx.user_id.into(),
+        body.started_at,
+        body.ended_at,
+        tag_ids,
+    );
+
+    match state
+        .update_time_entry_handler
+        .handle_at_version(
+            &request_ctx.tenant_id,
+            &stream_id,
+            expected_version,
+            command,
+        )
+        .await
+    {
+        Ok(()) => match state.event_store.load(&stream_id).await {
+            Ok(stream) => (StatusCode::OK, [(header::ETAG, etag(stream.version))]).into_response(),
+            Err(_) => StatusCode::OK.into_response(),
+        },
+        Err(ApplicationError::VersionConflict(EventStoreError::VersionMismatch { .. }))
+            if expected_version.is_some() =>
+        {
+            StatusCode::PRECONDITION_FAILED.into_response()
+        }
+        Err(ApplicationError::Domain(DecideError::NotFound)) => {
+            (StatusCode::NOT_FOUND, "time entry not found").into_response()
+        }
+        Err(ApplicationError::Domain(
+            DecideError::ClockSkew(_)
+            | DecideError::StartedAt(StartedAtError::InvalidInterval)
+            | DecideError::EndedAt(EndedAtError::InvalidInterval),
+        )) => StatusCode::UNPROCESSABLE_ENTITY.into_response(),
+        Err(ApplicationError::Domain(_)) => StatusCode::CONFLICT.into_response(),
+        Err(_) => StatusCode::INTERNAL_SERVER_ERROR.into_response(),
+    }
+}
+
+#[cfg(test)]
+mod update_time_entry_http_inbound_tests {
+    use axum::{
+        Router,
+        body::Body,
+        http::{Request, Response, StatusCode},
+        routing::put,
+    };
+    use rstest::rstest;
+    use tower::ServiceExt;
+
+    use super::handle_put;
+    use crate::modules::time_entries::use_cases::set_ended_at::command::SetEndedAt;
+    use crate::modules::time_entries::use_cases::set_started_at::command::SetStartedAt;
+    use crate::shared::infrastructure::event_store::EventStore;
+    use crate::shell::state::AppState;
+    use crate::tests::fixtures::tags::make_test_app_state;
+
+    /// A registered entry of `u-1` from 1s to 2s, and its stream version.
+    async fn registered(state: &AppState, te_id: &str) -> i64 {
+        let stream_id = state.stream_naming.time_entry(Some("tenant-test"), &te_id);
+        state
+            .set_started_at_handler
+            .handle_for_tenant(
+                "tenant-test",
+                &stream_id,
+                SetStartedAt::new(te_id.into(), "u-1".into(), 1_000),
+            )
+            .await
+            .unwrap();
+        state
+            .set_ended_at_handler
+            .handle_for_tenant(
+                "tenant-test",
+                &stream_id,
+                SetEndedAt::new(te_id.into(), "u-1".into(), 2_000),
+            )
+            .await
+            .unwrap();
+        state.event_store.load(&stream_id).await.unwrap().version
+    }
+
+    async fn send(
+        state: AppState,
+        te_id: &str,
+        if_match: Option<String>,
+        body: &str,
+    ) -> Response<Body> {
+        let mut request = Request::builder()
+            .method("PUT")
+            .uri(format!("/time-entries/{te_id}"))
+            .header("content-type", "application/json")
+            .header("x-user-id", "u-1")
+            .header("x-tenant-id", "tenant-test");
+        if let Some(if_match) = if_match {
+            request = request.header("if-match", if_match);
+        }
+        Router::new()
+            .route("/time-entries/{id}", put(handle_put))
+            .with_state(state)
+            .oneshot(request.body(Body::from(body.to_string())).unwrap())
+            .await
+            .unwrap()
+    }
+
+    const MOVED: &str = r#"{"started_at":5000,"ended_at":6000,"tag_ids":["dev"]}"#;
+
+    #[tokio::test]
+    async fn put_updates_the_entry_and_answers_with_its_new_etag() {
+        let te_id = uuid::Uuid::now_v7().to_string();
+        let state = make_test_app_state();
+        let version = registered(&state, &te_id).await;
+
+        let response = send(state, &te_id, Some(format!("\"{version}\"")), MOVED).await;
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            response.headers()["etag"],
+            format!("\"{}\"", version + 3).as_str()
+        );
+    }
+
+    #[tokio::test]
+    async fn put_updates_without_if_match() {
+        let te_id = uuid::Uuid::now_v7().to_string();
+        let state = make_test_app_state();
+        registered(&state, &te_id).await;
+
+        assert_eq!(
+            send(state, &te_id, None, MOVED).await.status(),
+            StatusCode::OK
+        );
+    }
+
+    #[rstest]
+    #[case::stale(Some("\"1\""))]
+    #[case::weak(Some("W/\"4\""))]
+    #[tokio::test]
+    async fn put_returns_412_when_the_entry_changed_since(#[case] if_match: Option<&str>) {
+        let te_id = uuid::Uuid::now_v7().to_string();
+        let state = make_test_app_state();
+        registered(&state, &te_id).await;
+
+        let response = send(state, &te_id, if_match.map(str::to_string), MOVED).await;
+
+        assert_eq!(response.status(), StatusCode::PRECONDITION_FAILED);
+    }
+
+    #[rstest]
+    #[case::missing(false, MOVED, StatusCode::NOT_FOUND)]
+    #[case::backwards(
+        true,
+        r#"{"started_at":6000,"ended_at":5000,"tag_ids":[]}"#,
+        StatusCode::UNPROCESSABLE_ENTITY
+    )]
+    #[case::invalid_tag(
+        true,
+        r#"{"started_at":5000,"ended_at":6000,"tag_ids":[" "]}"#,
+        StatusCode::UNPROCESSABLE_ENTITY
+    )]
+    #[tokio::test]
+    async fn put_refuses_what_it_cannot_update(
+        #[case] exists: bool,
+        #[case] body: &str,
+        #[case] expected: StatusCode,
+    ) {
+        let te_id = uuid::Uuid::now_v7().to_string();
+        let state = make_test_app_state();
+        if exists {
+            registered(&state, &te_id).await;
+        }
+
+        assert_eq!(send(state, &te_id, None, body).await.status(), expected);
+    }
+}
@@ -0,0 +1,180 @@
+use async_graphql::{Context, InputObject, Object, Result as GqlResult, SimpleObject};
+use chrono::NaiveDate;
+
+use crate::modules::time_entries::use_cases::list_time_entries::inbound::graphql::GqlTagTotal;
+use crate::modules::time_entries::use_cases::user_stats::queries::UserStats;
+use crate::shared::infrastructure::request_context::RequestContext;
+use crate::shell::state::AppState;
+
+/// Longest range a single set of stats may span.
+const MAX_DAYS: i64 = 366;
+
+/// Inclusive `YYYY-MM-DD` dates.
+#[derive(InputObject)]
+pub struct DateRangeInput {
+    pub from: String,
+    pub to: String,
+}
+
+#[derive(SimpleObject, Clone)]
+#[graphql(name = "UserStats")]
+pub struct GqlUserStats {
+    pub user_id: String,
+    pub from: String,
+    pub to: String,
+    pub total_minutes: i64,
+    /// Days with registered time.
+    pub active_days: i64,
+    /// Most consecutive days with registered time.
+    pub longest_streak_days: i64,
+    /// Registered hours per day with registered time; 0 without any.
+    pub average_daily_hours: f64,
+    /// At most five, largest first.
+    pub top_tags: Vec<GqlTagTotal>,
+}
+
+impl From<UserStats> for GqlUserStats {
+    fn from(stats: UserStats) -> Self {
+        Self {
+            user_id: stats.user_id,
+            from: stats.from.to_string(),
+            to: stats.to.to_string(),
+            total_minutes: stats.total_minutes,
+            active_days: stats.active_days,
+            longest_streak_days: stats.longest_streak_days,
+            average_daily_hours: stats.average_daily_hours,
+            top_tags: stats.top_tags.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct UserStatsQuery;
+
+#[Object]
+impl UserStatsQuery {
+    /// Streak, average and top tags of the registered time in `range`, for the dashboard.
+    /// Days are UTC days. `userId` defaults to the caller.
+    async fn user_stats(
+        &self,
+        context: &Context<'_>,
+        user_id: Option<String>,
+        range: DateRangeInput,
+    ) -> GqlResult<GqlUserStats> {
+        let req_ctx = context
+            .data::<RequestContext>()
+            .map_err(|_| async_graphql::Error::new("Unauthorized"))?;
+        let user_id = user_id.unwrap_or(req_ctx.user_id.clone());
+        if !req_ctx.principal().can_view_user(&user_id) {
+            return Err(async_graphql::Error::new("Forbidden"));
+        }
+        let (Ok(from), Ok(to)) = (
+            range.from.parse::<NaiveDate>(),
+            range.to.parse::<NaiveDate>(),
+        ) else {
+            return Err(async_graphql::Error::new("from and to must be YYYY-MM-DD"));
+        };
+        if !(0..MAX_DAYS).contains(&(to - from).num_days()) {
+            return Err(async_graphql::Error::new(
+                "to must not be before from, and the range at most 366 days",
+            ));
+        }
+        let state = context.data_unchecked::<AppState>();
+        let stats = state
+            .user_stats_handler
+            .user_stats(&user_id, from, to)
+            .await?;
+        Ok(stats.into())
+    }
+}
+
+#[cfg(test)]
+mod user_stats_graphql_tests {
+    use async_graphql::{EmptySubscription, Schema};
+    use rstest::rstest;
+
+    use crate::modules::time_entries::use_cases::user_stats::projection::{
+        DayTotals, UserStatsState,
+    };
+    use crate::modules::time_entries::use_cases::user_stats::queries::UserStatsQueryHandler;
+    use crate::shared::auth::rbac::Role;
+    use crate::shared::infrastructure::projection_store::ProjectionStore;
+    use crate::shared::infrastructure::projection_store::in_memory::InMemoryProjectionStore;
+    use crate::shared::infrastructure::request_context::RequestContext;
+    use crate::shell::graphql::{MutationRoot, QueryRoot};
+    use crate::shell::state::AppState;
+    use crate::tests::fixtures::tags::make_test_app_state;
+
+    async fn execute(state: AppState, query: &str) -> async_graphql::Response {
+        Schema::build(
+            QueryRoot::default(),
+            MutationRoot::default(),
+            EmptySubscription,
+        )
+        .data(state)
+        .finish()
+        .execute(async_graphql::Request::new(query).data(RequestContext {
+            user_id: "u-1".to_string(),
+            tenant_id: "tenant-test".to_string(),
+            role: Role::Employee,
+            scope: Default::default(),
+        }))
+        .await
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn resolver_returns_the_callers_stats() {
+        let mut state = make_test_app_state();
+        let store = InMemoryProjectionStore::<UserStatsState>::new();
+        let mut projection = UserStatsState::default();
+        projection.days.insert(
+            "u-1".to_string(),
+            [(
+                "2026-12-01".parse().unwrap(),
+                DayTotals {
+                    millis: 5_400_000,
+                    tag_millis: Default::default(),
+                },
+            )]
+            .into(),
+        );
+        store.save(projection, 1).await.unwrap();
+        state.user_stats_handler = UserStatsQueryHandler::new(store);
+
+        let result = execute(
+            state,
+            r#"{ userStats(range: { from: "2026-12-01", to: "2026-12-07" }) {
+                totalMinutes activeDays longestStreakDays averageDailyHours topTags { tagId } } }"#,
+        )
+        .await;
+
+        assert!(result.errors.is_empty(), "{:?}", result.errors);
+        assert_eq!(
+            result.data.to_string(),
+            "{userStats: {totalMinutes: 90, activeDays: 1, longestStreakDays: 1, averageDailyHours: 1.5, topTags: []}}"
+        );
+    }
+
+    #[rstest]
+    #[case::other_user(
+        r#"{ userStats(userId: "u-2", range: { from: "2026-12-01", to: "2026-12-07" }) { activeDays } }"#,
+        "Forbidden"
+    )]
+    #[case::bad_date(
+        r#"{ userStats(range: { from: "01-12-2026", to: "2026-12-07" }) { activeDays } }"#,
+        "from and to must be YYYY-MM-DD"
+    )]
+    #[case::too_long(
+        r#"{ userStats(range: { from: "2025-01-01", to: "2026-12-07" }) { activeDays } }"#,
+        "to must not be before from, and the range at most 366 days"
+    )]
+    #[tokio::test]
+    async fn resolver_rejects_forbidden_or_invalid_requests(
+        #[case] query: &str,
+        #[case] expected: &str,
+    ) {
+        let result = execute(make_test_app_state(), query).await;
+        assert_eq!(result.errors[0].message, expected);
+    }
+}
@@ -0,0 +1,320 @@
+use crate::modules::time_entries::core::breaks::{Break, net_millis_within};
+use crate::modules::time_entries::core::projections::Mutation;
+use crate::modules::time_entries::core::tag::Tag;
+use crate::modules::time_entries::use_cases::list_time_entries::projection::TimeEntryStatus;
+use crate::shared::core::primitives::last_event_version;
+use chrono::{DateTime, NaiveDate};
+use std::collections::{BTreeMap, HashMap};
+
+pub const SCHEMA_VERSION: u32 = 1;
+
+const DAY: i64 = 24 * 60 * 60 * 1000;
+
+/// Registered time of one user on one UTC day.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DayTotals {
+    pub millis: i64,
+    /// An entry with several tags counts in full towards each of them.
+    pub tag_millis: BTreeMap<Tag, i64>,
+}
+
+/// What the stats need of one entry, kept so a later event can take back what the entry
+/// contributed before.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct StatsEntry {
+    pub user_id: String,
+    pub started_at: Option<i64>,
+    pub ended_at: Option<i64>,
+    pub tag_ids: Vec<Tag>,
+    pub breaks: Vec<Break>,
+    /// Registered or approved; drafts do not count.
+    pub counted: bool,
+    pub deleted: bool,
+    pub last_event_id: Option<String>,
+}
+
+impl StatsEntry {
+    /// Whether the event at `stream_version` is already reflected in this entry.
+    pub fn has_applied(&self, stream_version: i64) -> bool {
+        last_event_version(self.last_event_id.as_deref())
+            .is_some_and(|applied| applied >= stream_version)
+    }
+
+    /// The milliseconds the entry works in each UTC day it touches, leaving out its breaks;
+    /// nothing until it is registered and stopped, or once deleted.
+    fn daily_millis(&self) -> Vec<(NaiveDate, i64)> {
+        let (Some(start), Some(end)) = (self.started_at, self.ended_at) else {
+            return vec![];
+        };
+        if !self.counted || self.deleted {
+            return vec![];
+        }
+        let mut days = vec![];
+        let mut at = start;
+        while at < end {
+            let until = (at.div_euclid(DAY) + 1) * DAY;
+            let Some(date) = DateTime::from_timestamp_millis(at).map(|at| at.date_naive()) else {
+                break;
+            };
+            days.push((date, net_millis_within(start, end, &self.breaks, at, until)));
+            at = until;
+        }
+        days
+    }
+}
+
+/// Per-day totals for the statistics dashboard, kept up to date event by event so a query
+/// only sums the days of its range.
+#[derive(Clone, Default)]
+pub struct UserStatsState {
+    pub entries: HashMap<String, StatsEntry>,
+    /// By user id, then UTC date. Days without registered time are absent.
+    pub days: HashMap<String, BTreeMap<NaiveDate, DayTotals>>,
+}
+
+impl UserStatsState {
+    /// Applies one mutation of the event at `version`, replacing what the entry contributed
+    /// to the day totals. Mutations the entry already reflects, or of entries never
+    /// initiated, are ignored.
+    pub fn apply(&mut self, mutation: Mutation, version: i64) {
+        let time_entry_id = match &mutation {
+            Mutation::Upsert(row) => &row.time_entry_id,
+            Mutation::SetStartedAt { time_entry_id, .. }
+            | Mutation::SetEndedAt { time_entry_id, .. }
+            | Mutation::SetRegistered { time_entry_id, .. }
+            | Mutation::SetDeleted { time_entry_id, .. }
+            | Mutation::SetTags { time_entry_id, .. }
+            | Mutation::SetApproved { time_entry_id, .. }
+            | Mutation::SetRejected { time_entry_id, .. }
+            | Mutation::SetHourlyRate { time_entry_id, .. }
+            | Mutation::SetBreaks { time_entry_id, .. } => time_entry_id,
+        }
+        .clone();
+        let mut entry = match (self.entries.get(&time_entry_id), &mutation) {
+            (Some(existing), _) if existing.has_applied(version) => return,
+            (_, Mutation::Upsert(_)) => StatsEntry::default(),
+            (Some(existing), _) => existing.clone(),
+            (None, _) => return,
+        };
+        let last_event_id = match mutation {
+            Mutation::Upsert(row) => {
+                entry = StatsEntry {
+                    user_id: row.user_id,
+                    started_at: row.started_at,
+                    ended_at: row.ended_at,
+                    tag_ids: row.tag_ids,
+                    breaks: row.breaks,
+                    counted: row.status != TimeEntryStatus::Draft,
+                    deleted: row.deleted_at.is_some(),
+                    last_event_id: None,
+                };
+                row.last_event_id
+            }
+            Mutation::SetStartedAt {
+                started_at,
+                last_event_id,
+                ..
+            } => {
+                entry.started_at = Some(started_at);
+                Some(last_event_id)
+            }
+            Mutation::SetEndedAt {
+                ended_at,
+                last_event_id,
+                ..
+            } => {
+                entry.ended_at = Some(ended_at);
+                Some(last_event_id)
+            }
+            Mutation::SetRegistered { last_event_id, .. }
+            | Mutation::SetApproved { last_event_id, .. }
+            | Mutation::SetRejected { last_event_id, .. } => {
+                entry.counted = true;
+                Some(last_event_id)
+            }
+            Mutation::SetDeleted { last_event_id, .. } => {
+                entry.deleted = true;
+                Some(last_event_id)
+            }
+            Mutation::SetTags {
+                tag_ids,
+                last_event_id,
+                ..
+            } => {
+                entry.tag_ids = tag_ids;
+                Some(last_event_id)
+            }
+            Mutation::SetHourlyRate { last_event_id, .. } => Some(last_event_id),
+            Mutation::SetBreaks {
+                breaks,
+                last_event_id,
+                ..
+            } => {
+                entry.breaks = breaks;
+                Some(last_event_id)
+            }
+        };
+        entry.last_event_id = last_event_id;
+        if let Some(previous) = self.entries.remove(&time_entry_id) {
+            self.add(&previous, -1);
+        }
+        self.add(&entry, 1);
+        self.entries.insert(time_entry_id, entry);
+    }
+
+    /// Adds (`sign` 1) or takes back (`sign` -1) what `entry` contributes to its user's days.
+    fn add(&mut self, entry: &StatsEntry, sign: i64) {
+        let daily_millis = entry.daily_millis();
+        if daily_millis.is_empty() {
+            return;
+        }
+        let days = self.days.entry(entry.user_id.clone()).or_default();
+        for (date, millis) in daily_millis {
+            let totals = days.entry(date).or_default();
+            totals.millis += sign * millis;
+            for tag_id in &entry.tag_ids {
+                let tag_millis = totals.tag_millis.entry(tag_id.clone()).or_default();
+                *tag_millis += sign * millis;
+                if *tag_millis == 0 {
+                    totals.tag_millis.remove(tag_id);
+                }
+            }
+            if totals.millis == 0 && totals.tag_millis.is_empty() {
+                days.remove(&date);
+            }
+        }
+        if days.is_empty() {
+            self.days.remove(&entry.user_id);
+        }
+    }
+}
+
+#[cfg(test)]
+mod user_stats_projection_tests {
+    use super::*;
+    use crate::modules::time_entries::use_cases::list_time_entries::projection::TimeEntryRow;
+    use rstest::rstest;
+
+    const HOUR: i64 = 60 * 60 * 1000;
+
+    fn date(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(1970, 1, day).unwrap()
+    }
+
+    fn initiated(time_entry_id: &str) -> Mutation {
+        Mutation::Upsert(TimeEntryRow {
+            time_entry_id: time_entry_id.to_string(),
+            user_id: "u1".to_string(),
+            started_at: None,
+            ended_at: None,
+            tag_ids: vec![],
+            status: TimeEntryStatus::Draft,
+            created_at: 0,
+            created_by: "u1".to_string(),
+            updated_at: 0,
+            updated_by: "u1".to_string(),
+            deleted_at: None,
+            hourly_rate: None,
+            last_event_id: Some(format!("TimeEntry-{time_entry_id}:1")),
+            breaks: vec![],
+        })
+    }
+
+    fn registered(time_entry_id: &str, started_at: i64, ended_at: i64) -> Vec<Mutation> {
+        let last_event_id = |version: i64| format!("TimeEntry-{time_entry_id}:{version}");
+        vec![
+            initiated(time_entry_id),
+            Mutation::SetStartedAt {
+                time_entry_id: time_entry_id.to_string(),
+                started_at,
+                updated_at: 0,
+                updated_by: "u1".to_string(),
+                last_event_id: last_event_id(2),
+            },
+            Mutation::SetEndedAt {
+                time_entry_id: time_entry_id.to_string(),
+                ended_at,
+                updated_at: 0,
+                updated_by: "u1".to_string(),
+                last_event_id: last_event_id(3),
+            },
+            Mutation::SetRegistered {
+                time_entry_id: time_entry_id.to_string(),
+                last_event_id: last_event_id(4),
+            },
+        ]
+    }
+
+    fn apply_all(state: &mut UserStatsState, mutations: Vec<Mutation>) {
+        for (version, mutation) in (1..).zip(mutations) {
+            state.apply(mutation, version);
+        }
+    }
+
+    fn tags_set(time_entry_id: &str, tags: &[&str], version: i64) -> Mutation {
+        Mutation::SetTags {
+            time_entry_id: time_entry_id.to_string(),
+            tag_ids: tags.iter().map(|tag| Tag::parse(tag).unwrap()).collect(),
+            updated_at: 0,
+            updated_by: "u1".to_string(),
+            last_event_id: format!("TimeEntry-{time_entry_id}:{version}"),
+        }
+    }
+
+    #[rstest]
+    fn it_should_count_registered_time_per_day_split_at_midnight() {
+        let mut state = UserStatsState::default();
+        apply_all(&mut state, registered("te-1", 22 * HOUR, 26 * HOUR));
+
+        let days = &state.days["u1"];
+        assert_eq!(days[&date(1)].millis, 2 * HOUR);
+        assert_eq!(days[&date(2)].millis, 2 * HOUR);
+    }
+
+    #[rstest]
+    fn it_should_not_count_drafts() {
+        let mut state = UserStatsState::default();
+        let mut mutations = registered("te-1", 0, HOUR);
+        mutations.pop();
+        apply_all(&mut state, mutations);
+
+        assert!(state.days.is_empty());
+        assert!(state.entries["te-1"].started_at.is_some());
+    }
+
+    #[rstest]
+    fn it_should_move_time_between_tags_and_drop_it_when_deleted() {
+        let mut state = UserStatsState::default();
+        apply_all(&mut state, registered("te-1", 0, HOUR));
+        state.apply(tags_set("te-1", &["dev", "ops"], 5), 5);
+        state.apply(tags_set("te-1", &["dev"], 6), 6);
+
+        let totals = &state.days["u1"][&date(1)];
+        assert_eq!(totals.millis, HOUR);
+        assert_eq!(
+            totals.tag_millis,
+            BTreeMap::from([(Tag::parse("dev").unwrap(), HOUR)])
+        );
+
+        state.apply(
+            Mutation::SetDeleted {
+                time_entry_id: "te-1".to_string(),
+                deleted_at: 0,
+                last_event_id: "TimeEntry-te-1:7".to_string(),
+            },
+            7,
+        );
+        assert!(state.days.is_empty());
+    }
+
+    #[rstest]
+    fn it_should_ignore_replayed_and_orphaned_mutations() {
+        let mut state = UserStatsState::default();
+        apply_all(&mut state, registered("te-1", 0, HOUR));
+        state.apply(tags_set("te-1", &["dev"], 2), 2);
+        state.apply(tags_set("te-2", &["dev"], 2), 2);
+
+        assert!(state.days["u1"][&date(1)].tag_millis.is_empty());
+        assert!(!state.entries.contains_key("te-2"));
+    }
+}
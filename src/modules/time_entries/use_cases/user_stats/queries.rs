@@ -0,0 +1,218 @@
+use crate::modules::time_entries::use_cases::list_time_entries::queries::TagTotal;
+use crate::modules::time_entries::use_cases::user_stats::projection::UserStatsState;
+use crate::shared::infrastructure::projection_store::ProjectionStore;
+use chrono::NaiveDate;
+use std::collections::BTreeMap;
+
+const MINUTE: i64 = 60_000;
+const HOUR: f64 = 3_600_000.0;
+
+/// How many tags `UserStats::top_tags` lists.
+pub const TOP_TAGS: usize = 5;
+
+/// Dashboard figures for one user between two inclusive UTC dates.
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+pub struct UserStats {
+    pub user_id: String,
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+    pub total_minutes: i64,
+    /// Days with registered time.
+    pub active_days: i64,
+    /// Most consecutive days with registered time.
+    pub longest_streak_days: i64,
+    /// Registered hours per active day; 0 without any.
+    pub average_daily_hours: f64,
+    /// The `TOP_TAGS` tags with the most time, largest first.
+    pub top_tags: Vec<TagTotal>,
+}
+
+#[derive(Clone)]
+pub struct UserStatsQueryHandler<TStore>
+where
+    TStore: ProjectionStore<UserStatsState> + Send + Sync + 'static,
+{
+    store: TStore,
+}
+
+impl<TStore> UserStatsQueryHandler<TStore>
+where
+    TStore: ProjectionStore<UserStatsState> + Send + Sync + 'static,
+{
+    pub fn new(store: TStore) -> Self {
+        Self { store }
+    }
+
+    pub fn store(&self) -> &TStore {
+        &self.store
+    }
+
+    /// Registered and approved time of `user_id` between `from` and `to`, read from the
+    /// per-day totals of the stats projection. Like the time-by-tag breakdown, an entry with
+    /// several tags counts in full towards each of them.
+    pub async fn user_stats(
+        &self,
+        user_id: &str,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> anyhow::Result<UserStats> {
+        let state = self.store.state().await?.unwrap_or_default();
+        let mut total_millis = 0;
+        let mut active_days = 0;
+        let mut longest_streak_days = 0;
+        let mut streak: Option<(NaiveDate, i64)> = None;
+        let mut tag_millis: BTreeMap<String, i64> = BTreeMap::new();
+        let days = state.days.get(user_id).into_iter().flat_map(|days| {
+            days.range(from..=to)
+                .filter(|(_, totals)| totals.millis > 0)
+        });
+        for (date, totals) in days {
+            total_millis += totals.millis;
+            active_days += 1;
+            let length = match streak {
+                Some((last, length)) if last.succ_opt() == Some(*date) => length + 1,
+                _ => 1,
+            };
+            streak = Some((*date, length));
+            longest_streak_days = longest_streak_days.max(length);
+            for (tag_id, millis) in &totals.tag_millis {
+                *tag_millis.entry(tag_id.to_string()).or_default() += millis;
+            }
+        }
+        let mut top_tags: Vec<TagTotal> = tag_millis
+            .into_iter()
+            .map(|(tag_id, millis)| TagTotal {
+                tag_id: Some(tag_id),
+                minutes: millis / MINUTE,
+            })
+            .filter(|total| total.minutes > 0)
+            .collect();
+        top_tags.sort_by_key(|total| std::cmp::Reverse(total.minutes));
+        top_tags.truncate(TOP_TAGS);
+        let average_daily_hours = if active_days == 0 {
+            0.0
+        } else {
+            total_millis as f64 / HOUR / active_days as f64
+        };
+        Ok(UserStats {
+            user_id: user_id.to_string(),
+            from,
+            to,
+            total_minutes: total_millis / MINUTE,
+            active_days,
+            longest_streak_days,
+            average_daily_hours,
+            top_tags,
+        })
+    }
+}
+
+#[cfg(test)]
+mod user_stats_query_handler_tests {
+    use super::*;
+    use crate::modules::time_entries::core::tag::Tag;
+    use crate::modules::time_entries::use_cases::user_stats::projection::DayTotals;
+    use crate::shared::infrastructure::projection_store::in_memory::InMemoryProjectionStore;
+    use rstest::rstest;
+
+    const HOUR_MS: i64 = 3_600_000;
+
+    fn date(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 12, day).unwrap()
+    }
+
+    fn totals(hours: i64, tags: &[&str]) -> DayTotals {
+        DayTotals {
+            millis: hours * HOUR_MS,
+            tag_millis: tags
+                .iter()
+                .map(|tag| (Tag::parse(tag).unwrap(), hours * HOUR_MS))
+                .collect(),
+        }
+    }
+
+    async fn handler(
+        days: Vec<(u32, DayTotals)>,
+    ) -> UserStatsQueryHandler<InMemoryProjectionStore<UserStatsState>> {
+        let store = InMemoryProjectionStore::<UserStatsState>::new();
+        let mut state = UserStatsState::default();
+        state.days.insert(
+            "u1".to_string(),
+            days.into_iter()
+                .map(|(day, totals)| (date(day), totals))
+                .collect(),
+        );
+        store.save(state, 1).await.unwrap();
+        UserStatsQueryHandler::new(store)
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_sum_streaks_averages_and_tags_within_the_range() {
+        let handler = handler(vec![
+            (1, totals(8, &["dev"])),
+            (2, totals(6, &["dev", "ops"])),
+            (3, totals(4, &["ops"])),
+            (5, totals(2, &["dev"])),
+            (9, totals(9, &["dev"])),
+        ])
+        .await;
+
+        let stats = handler.user_stats("u1", date(2), date(8)).await.unwrap();
+
+        assert_eq!(stats.total_minutes, 12 * 60);
+        assert_eq!(stats.active_days, 3);
+        assert_eq!(stats.longest_streak_days, 2);
+        assert_eq!(stats.average_daily_hours, 4.0);
+        assert_eq!(
+            stats.top_tags,
+            vec![
+                TagTotal {
+                    tag_id: Some("ops".to_string()),
+                    minutes: 600,
+                },
+                TagTotal {
+                    tag_id: Some("dev".to_string()),
+                    minutes: 480,
+                },
+            ]
+        );
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_keep_only_the_top_five_tags() {
+        let tags = ["a", "b", "c", "d", "e", "f"];
+        let days = (1..=6)
+            .zip(tags)
+            .map(|(day, tag)| (day, totals(day as i64, &[tag])))
+            .collect();
+        let stats = handler(days)
+            .await
+            .user_stats("u1", date(1), date(31))
+            .await
+            .unwrap();
+
+        let top: Vec<_> = stats.top_tags.iter().map(|t| t.tag_id.clone()).collect();
+        assert_eq!(
+            top,
+            ["f", "e", "d", "c", "b"].map(|tag| Some(tag.to_string()))
+        );
+        assert_eq!(stats.longest_streak_days, 6);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_report_zeros_without_registered_time() {
+        let stats = handler(vec![(1, totals(8, &[]))])
+            .await
+            .user_stats("u2", date(1), date(31))
+            .await
+            .unwrap();
+
+        assert_eq!(stats.total_minutes, 0);
+        assert_eq!(stats.longest_streak_days, 0);
+        assert_eq!(stats.average_daily_hours, 0.0);
+        assert!(stats.top_tags.is_empty());
+    }
+}
@@ -0,0 +1,263 @@
+// Day totals for the capacity check on the write side.
+//
+// Commands cannot wait for the list projection to catch up, so the time a user already
+// registered on a day is read from their `UserTimeEntries-{user_id}` stream, which holds the
+// interval every entry claims and is written before the entries themselves. Only finished
+// intervals count; running timers are left to the auto-stop. Breaks are not on the stream,
+// so totals are gross.
+//
+// With contracts configured, each weekday carries a fifth of the user's contracted week, as
+// the utilization report counts it, and weekends none.
+
+use async_trait::async_trait;
+use chrono::NaiveDate;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use thiserror::Error;
+
+use crate::modules::contracts::core::events::ContractEvent;
+use crate::modules::contracts::use_cases::set_contract::handler::load_contracts;
+use crate::modules::contracts::use_cases::utilization::queries::WORKING_DAYS_PER_WEEK;
+use crate::modules::time_entries::core::day_capacity::{DayTotal, ms_per_day};
+use crate::modules::time_entries::core::user_time_entries::{
+    UserTimeEntriesState, evolve_user_time_entries, user_stream_id,
+};
+use crate::modules::time_entries::use_cases::hours_balance::queries::working_days;
+use crate::modules::time_entries::use_cases::user_time_entries::sharded_handler::UserStreams;
+use crate::shared::infrastructure::event_store::{EventStore, EventStoreError};
+
+const MINUTE_MS: i64 = 60 * 1000;
+
+#[derive(Debug, Clone, Error, PartialEq, Eq)]
+pub enum DayTotalsError {
+    #[error("backend error: {0}")]
+    Backend(String),
+}
+
+impl From<EventStoreError> for DayTotalsError {
+    fn from(error: EventStoreError) -> Self {
+        Self::Backend(error.to_string())
+    }
+}
+
+/// The time users registered per day, for holding new entries to a day's capacity.
+#[async_trait]
+pub trait DayTotalsPort: Send + Sync {
+    /// `user_id`'s registered time on each day from `from` to `to`, both inclusive, leaving
+    /// out `time_entry_id`, ordered by date. Days without time are left out unless the user
+    /// has a contract.
+    async fn day_totals(
+        &self,
+        user_id: &str,
+        from: NaiveDate,
+        to: NaiveDate,
+        time_entry_id: &str,
+    ) -> Result<Vec<DayTotal>, DayTotalsError>;
+}
+
+pub type SharedDayTotals = Arc<dyn DayTotalsPort>;
+
+#[derive(Clone)]
+pub struct UserStreamDayTotals {
+    user_streams: UserStreams,
+    contracts: Option<Arc<dyn EventStore<ContractEvent>>>,
+}
+
+impl UserStreamDayTotals {
+    pub fn new(user_streams: UserStreams) -> Self {
+        Self {
+            user_streams,
+            contracts: None,
+        }
+    }
+
+    /// Read each user's contracted time from `contracts`.
+    pub fn with_contracts(mut self, contracts: Arc<dyn EventStore<ContractEvent>>) -> Self {
+        self.contracts = Some(contracts);
+        self
+    }
+}
+
+#[async_trait]
+impl DayTotalsPort for UserStreamDayTotals {
+    async fn day_totals(
+        &self,
+        user_id: &str,
+        from: NaiveDate,
+        to: NaiveDate,
+        time_entry_id: &str,
+    ) -> Result<Vec<DayTotal>, DayTotalsError> {
+        let stream = self.user_streams.load(&user_stream_id(user_id)).await?;
+        let state = stream
+            .events
+            .into_iter()
+            .fold(UserTimeEntriesState::default(), evolve_user_time_entries);
+        let mut registered: BTreeMap<NaiveDate, i64> = BTreeMap::new();
+        for (id, interval) in &state.intervals {
+            let (Some(started_at), Some(ended_at)) = (interval.started_at, interval.ended_at)
+            else {
+                continue;
+            };
+            if id == time_entry_id {
+                continue;
+            }
+            for (date, ms) in ms_per_day(started_at, ended_at) {
+                if (from..=to).contains(&date) {
+                    *registered.entry(date).or_default() += ms;
+                }
+            }
+        }
+
+        let Some(contracts) = &self.contracts else {
+            return Ok(registered
+                .into_iter()
+                .map(|(date, registered_ms)| DayTotal {
+                    date,
+                    registered_ms,
+                    contracted_ms: None,
+                })
+                .collect());
+        };
+        let contracts = load_contracts(contracts.as_ref(), user_id).await?;
+        Ok(from
+            .iter_days()
+            .take_while(|date| *date <= to)
+            .filter_map(|date| {
+                let registered_ms = registered.get(&date).copied().unwrap_or(0);
+                let contracted_ms = contracts.minutes_per_week_on(date).map(|minutes| {
+                    if working_days(date, date, &[]).is_empty() {
+                        0
+                    } else {
+                        minutes / WORKING_DAYS_PER_WEEK * MINUTE_MS
+                    }
+                });
+                (registered_ms > 0 || contracted_ms.is_some()).then_some(DayTotal {
+                    date,
+                    registered_ms,
+                    contracted_ms,
+                })
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod user_stream_day_totals_tests {
+    use super::*;
+    use crate::modules::contracts::core::events::v1::contract_set::ContractSetV1;
+    use crate::modules::contracts::core::state::contract_stream_id;
+    use crate::modules::time_entries::core::user_time_entries::{
+        Interval, IntervalClaimedV1, UserTimeEntriesEvent,
+    };
+    use crate::shared::infrastructure::event_store::in_memory::InMemoryEventStore;
+    use rstest::rstest;
+
+    const HOUR: i64 = 60 * 60 * 1000;
+
+    /// 2026-03-02, a Monday.
+    const MONDAY: i64 = 1_772_409_600_000;
+
+    fn date(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 3, day).unwrap()
+    }
+
+    fn claimed(
+        time_entry_id: &str,
+        started_at: i64,
+        ended_at: Option<i64>,
+    ) -> UserTimeEntriesEvent {
+        UserTimeEntriesEvent::IntervalClaimedV1(IntervalClaimedV1 {
+            time_entry_id: time_entry_id.to_string(),
+            interval: Interval {
+                started_at: Some(started_at),
+                ended_at,
+            },
+            occurred_at: 0,
+        })
+    }
+
+    async fn user_streams() -> UserStreams {
+        let user_streams = InMemoryEventStore::<UserTimeEntriesEvent>::new();
+        user_streams
+            .append(
+                "UserTimeEntries-u-1",
+                0,
+                &[
+                    claimed("te-1", MONDAY + 9 * HOUR, Some(MONDAY + 12 * HOUR)),
+                    claimed("te-2", MONDAY + 13 * HOUR, Some(MONDAY + 17 * HOUR)),
+                    claimed("te-3", MONDAY + 23 * HOUR, Some(MONDAY + 25 * HOUR)),
+                    claimed("te-4", MONDAY + 30 * HOUR, None),
+                ],
+            )
+            .await
+            .unwrap();
+        Arc::new(user_streams)
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_total_finished_intervals_per_day_leaving_out_the_entry() {
+        let totals = UserStreamDayTotals::new(user_streams().await)
+            .day_totals("u-1", date(2), date(3), "te-2")
+            .await
+            .unwrap();
+
+        assert_eq!(
+            totals,
+            vec![
+                DayTotal {
+                    date: date(2),
+                    registered_ms: 4 * HOUR,
+                    contracted_ms: None,
+                },
+                DayTotal {
+                    date: date(3),
+                    registered_ms: HOUR,
+                    contracted_ms: None,
+                },
+            ]
+        );
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_add_the_contracted_time_of_weekdays() {
+        let contracts = InMemoryEventStore::<ContractEvent>::new();
+        contracts
+            .append(
+                &contract_stream_id("u-1"),
+                0,
+                &[ContractEvent::ContractSetV1(ContractSetV1 {
+                    user_id: "u-1".to_string(),
+                    minutes_per_week: 2400,
+                    start_date: date(1),
+                    set_at: 0,
+                    set_by: "admin".to_string(),
+                })],
+            )
+            .await
+            .unwrap();
+
+        let totals = UserStreamDayTotals::new(user_streams().await)
+            .with_contracts(Arc::new(contracts))
+            .day_totals("u-1", date(1), date(2), "te-9")
+            .await
+            .unwrap();
+
+        assert_eq!(
+            totals,
+            vec![
+                DayTotal {
+                    date: date(1),
+                    registered_ms: 0,
+                    contracted_ms: Some(0),
+                },
+                DayTotal {
+                    date: date(2),
+                    registered_ms: 8 * HOUR,
+                    contracted_ms: Some(8 * HOUR),
+                },
+            ]
+        );
+    }
+}
@@ -0,0 +1,1337 @@
+// Command handler for time entry use cases that also maintains the per-user
+// `UserTimeEntries-{user_id}` stream.
+//
+// Coordination: claim first, then write. After the entry decider accepts, the interval the
+// entry will have is claimed on the user stream (optimistically, against its loaded
+// version) and only then are the entry events appended. Consistency guarantees:
+//
+// - Two commands racing for overlapping time on the same user conflict on the user stream,
+//   so at most one of them is appended; the loser surfaces as a version conflict and is
+//   retried against fresh state by `RetryOnConflictMiddleware`.
+// - A claim can lead its entry stream but never lag it. If the entry append fails after the
+//   claim was written, the previous claim is restored as a compensating event, under a
+//   short deadline of its own when the request's has run out. Should that also fail, the stale claim stays until the entry's next command re-claims its interval
+//   (a retry of the same command is enough) and is otherwise only visible as a conflict.
+// - Re-claiming an unchanged interval appends nothing, so retries are idempotent.
+//
+// With period locks configured, a change is refused when the entry's interval before or
+// after it touches a locked payroll period. The check reads the lock stream before the
+// claim; a lock written while the command is in flight does not stop it.
+//
+// With a calendar configured, registering an entry or moving a registered entry onto a
+// holiday or approved absence is handled per `AbsencePolicy`. An unreachable calendar never
+// blocks registration; the check is skipped with a warning.
+//
+// With feature flags configured, the claim rules rolled out per tenant are read from them:
+// `reject_overlaps` (on unless switched off) and `max_entry_duration` (off unless switched
+// on). Commands handled without a tenant use the flags' defaults.
+//
+// With a policy store configured, the tenant's policies are read from it, or the defaults the
+// handler was given when the tenant has none. Client-supplied instants are rounded per the
+// tenant's rounding rule after the skew check, and the change is refused when it breaks the
+// tag limit or touches an entry past the lock period. `max_entry_duration` caps entries at
+// the tenant's maximum duration.
+//
+// With day capacity configured, registering an entry or moving a registered entry is held to
+// the capacity of the days it covers, counting the user's other entries through the day
+// totals port; days over capacity are handled per `CapacityPolicy`. As with the calendar, an
+// unreachable port never blocks registration.
+//
+// Before deciding, the command is stamped with the handler's clock and the instants the
+// client supplied are checked against the configured skew window.
+//
+// Without user streams, period locks or a calendar configured the handler behaves exactly
+// like `EventSourcedHandler`, apart from the stamping.
+
+use std::marker::PhantomData;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::modules::time_entries::core::day_capacity::{
+    CapacityPolicy, CapacityWarning, DayCapacity, DayCapacityError, check_capacity, ms_per_day,
+};
+use crate::modules::time_entries::core::days_off::{
+    AbsencePolicy, DayOffError, check_days_off, registered_days,
+};
+use crate::modules::time_entries::core::events::TimeEntryEvent;
+use crate::modules::time_entries::core::period_locks::{
+    PeriodLockError, PeriodLocksEvent, ensure_unlocked,
+};
+use crate::modules::time_entries::core::policies::{
+    Policies, PolicyError, RoundedCommand, ensure_within_policies,
+};
+use crate::modules::time_entries::core::state::TimeEntryState;
+use crate::modules::time_entries::core::user_time_entries::{
+    ClaimInterval, ClaimRules, IntervalClaimedV1, IntervalReleasedV1, UserTimeEntriesError,
+    UserTimeEntriesEvent, UserTimeEntriesState, claim_of, decide_claim, evolve_user_time_entries,
+    user_stream_id,
+};
+use crate::modules::time_entries::use_cases::period_locks::handler::load_period_locks;
+use crate::modules::time_entries::use_cases::user_time_entries::day_totals::SharedDayTotals;
+use crate::shared::application::event_sourced_handler::{EventSourcedError, IntentDispatcher};
+use crate::shared::application::server_time::{ClockSkewError, SkewWindow, StampedCommand};
+use crate::shared::core::decider::{Decider, Decision};
+use crate::shared::infrastructure::calendar::CalendarPort;
+use crate::shared::infrastructure::clock::{SharedClock, SystemClock};
+use crate::shared::infrastructure::deadline;
+use crate::shared::infrastructure::event_bus::{SharedEventBus, stored_events};
+use crate::shared::infrastructure::event_store::crypto_shredding::FORGOTTEN;
+use crate::shared::infrastructure::event_store::paged::{DEFAULT_PAGE_SIZE, fold_paged};
+use crate::shared::infrastructure::event_store::{EventStore, EventStoreError};
+use crate::shared::infrastructure::feature_flags::{FeatureFlag, SharedFeatureFlags, is_enabled};
+use crate::shared::infrastructure::policy_store::{SharedPolicyStore, policies_for};
+
+pub type UserStreams = Arc<dyn EventStore<UserTimeEntriesEvent>>;
+pub type PeriodLockStreams = Arc<dyn EventStore<PeriodLocksEvent>>;
+pub type SharedCalendar = Arc<dyn CalendarPort>;
+
+pub const REJECT_OVERLAPS: FeatureFlag = FeatureFlag {
+    name: "reject_overlaps",
+    description: "Refuse entries that overlap another entry of the same user.",
+    default: true,
+};
+
+pub const MAX_ENTRY_DURATION: FeatureFlag = FeatureFlag {
+    name: "max_entry_duration",
+    description: "Refuse finished entries longer than the maximum entry duration.",
+    default: false,
+};
+
+/// The flags time entry commands consult.
+pub const FEATURE_FLAGS: &[FeatureFlag] = &[REJECT_OVERLAPS, MAX_ENTRY_DURATION];
+
+/// How long releasing a claim may take once the entry append failed.
+const COMPENSATION_GRACE: Duration = Duration::from_secs(2);
+
+/// A claim written to a user stream, kept so it can be compensated.
+struct WrittenClaim {
+    stream_id: String,
+    version: i64,
+    time_entry_id: String,
+    previous: Option<UserTimeEntriesEvent>,
+    occurred_at: i64,
+}
+
+pub struct UserShardedHandler<TDecider, TEventStore, TDispatcher> {
+    event_store: TEventStore,
+    user_streams: Option<UserStreams>,
+    period_locks: Option<PeriodLockStreams>,
+    calendar: Option<(SharedCalendar, AbsencePolicy)>,
+    capacity: Option<(SharedDayTotals, DayCapacity)>,
+    feature_flags: Option<SharedFeatureFlags>,
+    policies: Option<(SharedPolicyStore, Policies)>,
+    clock: SharedClock,
+    skew_window: SkewWindow,
+    dispatcher: TDispatcher,
+    event_bus: Option<SharedEventBus<TimeEntryEvent>>,
+    _decider: PhantomData<fn() -> TDecider>,
+}
+
+impl<TDecider, TEventStore, TDispatcher> Clone
+    for UserShardedHandler<TDecider, TEventStore, TDispatcher>
+where
+    TEventStore: Clone,
+    TDispatcher: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            event_store: self.event_store.clone(),
+            user_streams: self.user_streams.clone(),
+            period_locks: self.period_locks.clone(),
+            calendar: self.calendar.clone(),
+            capacity: self.capacity.clone(),
+            feature_flags: self.feature_flags.clone(),
+            policies: self.policies.clone(),
+            clock: self.clock.clone(),
+            skew_window: self.skew_window,
+            dispatcher: self.dispatcher.clone(),
+            event_bus: self.event_bus.clone(),
+            _decider: PhantomData,
+        }
+    }
+}
+
+impl<TDecider, TEventStore, TDispatcher> std::fmt::Debug
+    for UserShardedHandler<TDecider, TEventStore, TDispatcher>
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("UserShardedHandler")
+            .field("user_streams", &self.user_streams.is_some())
+            .field("period_locks", &self.period_locks.is_some())
+            .field(
+                "absence_policy",
+                &self.calendar.as_ref().map(|(_, policy)| policy),
+            )
+            .field(
+                "day_capacity",
+                &self.capacity.as_ref().map(|(_, capacity)| capacity),
+            )
+            .field("feature_flags", &self.feature_flags.is_some())
+            .field(
+                "policies",
+                &self.policies.as_ref().map(|(_, defaults)| defaults),
+            )
+            .field("event_bus", &self.event_bus.is_some())
+            .finish_non_exhaustive()
+    }
+}
+
+impl<TDecider, TEventStore, TDispatcher> UserShardedHandler<TDecider, TEventStore, TDispatcher>
+where
+    TDecider: Decider<State = TimeEntryState, Event = TimeEntryEvent>,
+    TDecider::Command: StampedCommand + RoundedCommand + Send,
+    TDecider::Intent: Send,
+    TDecider::Error: From<UserTimeEntriesError>
+        + From<PeriodLockError>
+        + From<PolicyError>
+        + From<DayOffError>
+        + From<DayCapacityError>
+        + From<ClockSkewError>,
+    TEventStore: EventStore<TimeEntryEvent> + Send + Sync + 'static,
+    TDispatcher: IntentDispatcher<TDecider::Intent> + Send + Sync + 'static,
+{
+    pub fn new(event_store: TEventStore, dispatcher: TDispatcher) -> Self {
+        Self {
+            event_store,
+            user_streams: None,
+            period_locks: None,
+            calendar: None,
+            capacity: None,
+            feature_flags: None,
+            policies: None,
+            clock: Arc::new(SystemClock),
+            skew_window: SkewWindow::default(),
+            dispatcher,
+            event_bus: None,
+            _decider: PhantomData,
+        }
+    }
+
+    pub fn with_user_streams(mut self, user_streams: UserStreams) -> Self {
+        self.user_streams = Some(user_streams);
+        self
+    }
+
+    pub fn with_period_locks(mut self, period_locks: PeriodLockStreams) -> Self {
+        self.period_locks = Some(period_locks);
+        self
+    }
+
+    pub fn with_calendar(mut self, calendar: SharedCalendar, policy: AbsencePolicy) -> Self {
+        self.calendar = Some((calendar, policy));
+        self
+    }
+
+    pub fn with_day_capacity(mut self, day_totals: SharedDayTotals, capacity: DayCapacity) -> Self {
+        self.capacity = Some((day_totals, capacity));
+        self
+    }
+
+    pub fn with_feature_flags(mut self, feature_flags: SharedFeatureFlags) -> Self {
+        self.feature_flags = Some(feature_flags);
+        self
+    }
+
+    /// Read each tenant's policies from `store`, using `defaults` for tenants without any.
+    pub fn with_policies(mut self, store: SharedPolicyStore, defaults: Policies) -> Self {
+        self.policies = Some((store, defaults));
+        self
+    }
+
+    pub fn with_clock(mut self, clock: SharedClock) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    pub fn with_skew_window(mut self, skew_window: SkewWindow) -> Self {
+        self.skew_window = skew_window;
+        self
+    }
+
+    /// Publish the events of every append to `event_bus`, after the append and before the
+    /// intents are dispatched.
+    pub fn with_event_bus(mut self, event_bus: SharedEventBus<TimeEntryEvent>) -> Self {
+        self.event_bus = Some(event_bus);
+        self
+    }
+
+    pub async fn handle(
+        &self,
+        stream_id: &str,
+        command: TDecider::Command,
+    ) -> Result<(), EventSourcedError<TDecider::Error, TDispatcher::Error>> {
+        self.handle_for_tenant(None, stream_id, command).await
+    }
+
+    /// Handles the command under the rules rolled out to `tenant_id`.
+    pub async fn handle_for_tenant(
+        &self,
+        tenant_id: Option<&str>,
+        stream_id: &str,
+        command: TDecider::Command,
+    ) -> Result<(), EventSourcedError<TDecider::Error, TDispatcher::Error>> {
+        self.handle_at_version(tenant_id, stream_id, None, command)
+            .await
+    }
+
+    /// Handles the command only if the stream is still at `expected_version`, for callers
+    /// that decided on a version they read earlier; `None` handles it at any version.
+    pub async fn handle_at_version(
+        &self,
+        tenant_id: Option<&str>,
+        stream_id: &str,
+        expected_version: Option<i64>,
+        command: TDecider::Command,
+    ) -> Result<(), EventSourcedError<TDecider::Error, TDispatcher::Error>> {
+        self.handle_with_warnings(tenant_id, stream_id, expected_version, command)
+            .await
+            .map(|_| ())
+    }
+
+    /// Handles the command like `handle_at_version`, answering with the days it took over
+    /// capacity when the capacity policy warns.
+    pub async fn handle_with_warnings(
+        &self,
+        tenant_id: Option<&str>,
+        stream_id: &str,
+        expected_version: Option<i64>,
+        mut command: TDecider::Command,
+    ) -> Result<Vec<CapacityWarning>, EventSourcedError<TDecider::Error, TDispatcher::Error>> {
+        let now = self.clock.now();
+        for at in command.client_instants() {
+            self.skew_window
+                .check(now, at)
+                .map_err(|reason| EventSourcedError::Domain(reason.into()))?;
+        }
+        let policies = self.policies(tenant_id).await;
+        if let Some(rounding) = policies.rounding {
+            command.set_rounding(rounding);
+        }
+        command.stamp(now);
+
+        let (state, version) = fold_paged(
+            &self.event_store,
+            stream_id,
+            DEFAULT_PAGE_SIZE,
+            TDecider::initial_state(),
+            TDecider::evolve,
+        )
+        .await?;
+        if let Some(expected) = expected_version
+            && expected != version
+        {
+            return Err(EventStoreError::VersionMismatch {
+                expected,
+                actual: version,
+            }
+            .into());
+        }
+
+        let (events, intents) = match TDecider::decide(&state, command) {
+            Decision::Accepted { events, intents } => (events, intents),
+            Decision::Rejected { reason } => return Err(EventSourcedError::Domain(reason)),
+        };
+
+        let next_state = events.iter().cloned().fold(state.clone(), TDecider::evolve);
+        ensure_within_policies(&policies, &state, &next_state, now.as_millis())
+            .map_err(|reason| EventSourcedError::Domain(reason.into()))?;
+        if let Some(period_locks) = &self.period_locks {
+            let locks = load_period_locks(period_locks.as_ref()).await?;
+            ensure_unlocked(&locks, &state, &next_state)
+                .map_err(|reason| EventSourcedError::Domain(reason.into()))?;
+        }
+        if let Some((calendar, policy)) = &self.calendar
+            && let Some((user_id, from, to)) = registered_days(&state, &next_state)
+        {
+            match calendar.days_off(user_id, from, to).await {
+                Ok(days_off) => {
+                    if let Some(day_off) = check_days_off(*policy, days_off)
+                        .map_err(|reason| EventSourcedError::Domain(reason.into()))?
+                    {
+                        tracing::warn!(%user_id, date = %day_off.date, ?day_off.kind, "time registered on a day off");
+                    }
+                }
+                Err(reason) => {
+                    tracing::warn!(%reason, %user_id, "calendar unavailable, day off check skipped")
+                }
+            }
+        }
+        let mut warnings = Vec::new();
+        if let Some((day_totals, capacity)) = &self.capacity
+            && capacity.policy != CapacityPolicy::Allow
+            && let Some((user_id, from, to)) = registered_days(&state, &next_state)
+            && let TimeEntryState::Registered {
+                time_entry_id,
+                interval,
+                ..
+            } = &next_state
+        {
+            match day_totals
+                .day_totals(user_id, from, to, time_entry_id.as_str())
+                .await
+            {
+                Ok(totals) => {
+                    let entry_days =
+                        ms_per_day(interval.start().as_millis(), interval.end().as_millis());
+                    warnings = check_capacity(capacity, &entry_days, &totals)
+                        .map_err(|reason| EventSourcedError::Domain(reason.into()))?;
+                    for warning in &warnings {
+                        tracing::warn!(%user_id, date = %warning.date, warning.total_ms, warning.limit_ms, "time registered over a day's capacity");
+                    }
+                }
+                Err(reason) => {
+                    tracing::warn!(%reason, %user_id, "day totals unavailable, capacity check skipped")
+                }
+            }
+        }
+
+        let claim = match &self.user_streams {
+            Some(user_streams) => {
+                let occurred_at = events.last().map(TimeEntryEvent::occurred_at);
+                let rules = self.claim_rules(tenant_id, &policies).await;
+                claim_interval(
+                    user_streams,
+                    &next_state,
+                    occurred_at.unwrap_or_default(),
+                    rules,
+                )
+                .await?
+            }
+            None => None,
+        };
+
+        let appended = match self.event_store.append(stream_id, version, &events).await {
+            Ok(appended) => appended,
+            Err(error) => {
+                if let (Some(user_streams), Some(claim)) = (&self.user_streams, claim) {
+                    compensate(user_streams, claim).await;
+                }
+                return Err(error.into());
+            }
+        };
+        if let Some(event_bus) = &self.event_bus {
+            event_bus.publish(stored_events(stream_id, version, &events, &appended));
+        }
+        self.dispatcher
+            .dispatch(stream_id, version, events.len(), intents)
+            .await
+            .map_err(EventSourcedError::Outbox)?;
+        Ok(warnings)
+    }
+
+    async fn policies(&self, tenant_id: Option<&str>) -> Policies {
+        match &self.policies {
+            Some((store, defaults)) => policies_for(store.as_ref(), tenant_id, *defaults).await,
+            None => Policies::default(),
+        }
+    }
+
+    async fn claim_rules(&self, tenant_id: Option<&str>, policies: &Policies) -> ClaimRules {
+        let Some(flags) = &self.feature_flags else {
+            return ClaimRules::default();
+        };
+        ClaimRules {
+            reject_overlaps: is_enabled(flags.as_ref(), &REJECT_OVERLAPS, tenant_id).await,
+            max_duration_ms: is_enabled(flags.as_ref(), &MAX_ENTRY_DURATION, tenant_id)
+                .await
+                .then_some(policies.max_entry_duration_ms),
+        }
+    }
+}
+
+async fn claim_interval<TReason, TDispatchError>(
+    user_streams: &UserStreams,
+    next_state: &TimeEntryState,
+    occurred_at: i64,
+    rules: ClaimRules,
+) -> Result<Option<WrittenClaim>, EventSourcedError<TReason, TDispatchError>>
+where
+    TReason: From<UserTimeEntriesError>,
+{
+    // A deleted entry releases its claim, so its time can be registered again.
+    let (user_id, time_entry_id, interval) = match next_state {
+        TimeEntryState::Deleted {
+            time_entry_id,
+            user_id,
+            ..
+        } => (user_id.as_str(), time_entry_id.as_str(), None),
+        _ => match claim_of(next_state) {
+            Some((user_id, time_entry_id, interval)) => (user_id, time_entry_id, Some(interval)),
+            None => return Ok(None),
+        },
+    };
+    // A forgotten owner has no user stream left to claim on.
+    if user_id == FORGOTTEN {
+        return Ok(None);
+    }
+    let stream_id = user_stream_id(user_id);
+    let user_stream = user_streams.load(&stream_id).await?;
+    let user_state = user_stream
+        .events
+        .into_iter()
+        .fold(UserTimeEntriesState::default(), evolve_user_time_entries);
+    let claims = match interval {
+        Some(interval) => {
+            let command = ClaimInterval {
+                time_entry_id: time_entry_id.to_string(),
+                interval,
+                occurred_at,
+                rules,
+            };
+            match decide_claim(&user_state, command) {
+                Decision::Accepted { events, .. } => events,
+                Decision::Rejected { reason } => {
+                    return Err(EventSourcedError::Domain(reason.into()));
+                }
+            }
+        }
+        None if user_state.intervals.contains_key(time_entry_id) => {
+            vec![UserTimeEntriesEvent::IntervalReleasedV1(
+                IntervalReleasedV1 {
+                    time_entry_id: time_entry_id.to_string(),
+                    occurred_at,
+                },
+            )]
+        }
+        None => vec![],
+    };
+    if claims.is_empty() {
+        return Ok(None);
+    }
+    let appended = user_streams
+        .append(&stream_id, user_stream.version, &claims)
+        .await?;
+    let previous = user_state.intervals.get(time_entry_id).map(|interval| {
+        UserTimeEntriesEvent::IntervalClaimedV1(IntervalClaimedV1 {
+            time_entry_id: time_entry_id.to_string(),
+            interval: *interval,
+            occurred_at,
+        })
+    });
+    Ok(Some(WrittenClaim {
+        stream_id,
+        version: appended.next_version,
+        time_entry_id: time_entry_id.to_string(),
+        previous,
+        occurred_at,
+    }))
+}
+
+async fn compensate(user_streams: &UserStreams, claim: WrittenClaim) {
+    let restore = claim.previous.unwrap_or_else(|| {
+        UserTimeEntriesEvent::IntervalReleasedV1(IntervalReleasedV1 {
+            time_entry_id: claim.time_entry_id.clone(),
+            occurred_at: claim.occurred_at,
+        })
+    });
+    // The entry append may have failed because the request ran out of time; releasing the
+    // claim must not be refused for the same reason.
+    let released = deadline::with_grace(
+        COMPENSATION_GRACE,
+        user_streams.append(&claim.stream_id, claim.version, &[restore]),
+    )
+    .await;
+    if let Err(reason) = released {
+        tracing::warn!(
+            %reason,
+            stream_id = %claim.stream_id,
+            time_entry_id = %claim.time_entry_id,
+            "failed to release interval claim after entry append failed"
+        );
+    }
+}
+
+#[cfg(test)]
+mod user_sharded_handler_tests {
+    use super::*;
+    use crate::modules::time_entries::core::day_capacity::DayCapacityError;
+    use crate::modules::time_entries::core::intents::TimeEntryIntent;
+    use crate::modules::time_entries::core::period_locks::{
+        PERIOD_LOCKS_STREAM_ID, PeriodLockedV1,
+    };
+    use crate::modules::time_entries::core::policies::{Rounding, RoundingMode};
+    use crate::modules::time_entries::core::user_time_entries::Interval;
+    use crate::modules::time_entries::use_cases::set_ended_at::command::SetEndedAt;
+    use crate::modules::time_entries::use_cases::set_ended_at::decide::SetEndedAtDecider;
+    use crate::modules::time_entries::use_cases::set_ended_at::decision::DecideError as EndError;
+    use crate::modules::time_entries::use_cases::set_started_at::command::SetStartedAt;
+    use crate::modules::time_entries::use_cases::set_started_at::decide::SetStartedAtDecider;
+    use crate::modules::time_entries::use_cases::set_started_at::decision::DecideError as StartError;
+    use crate::modules::time_entries::use_cases::user_time_entries::day_totals::UserStreamDayTotals;
+    use crate::shared::infrastructure::calendar::static_config::StaticCalendar;
+    use crate::shared::infrastructure::clock::FixedClock;
+    use crate::shared::infrastructure::deadline::Deadline;
+    use crate::shared::infrastructure::event_store::in_memory::InMemoryEventStore;
+    use crate::shared::infrastructure::event_store::{AppendResult, LoadedStream};
+    use crate::shared::infrastructure::feature_flags::env::EnvFeatureFlags;
+    use crate::shared::infrastructure::intent_outbox::OutboxError;
+    use crate::shared::infrastructure::policy_store::in_memory::InMemoryPolicyStore;
+    use crate::shared::infrastructure::policy_store::{PolicyStore, TenantPolicies};
+    use async_trait::async_trait;
+    use chrono::TimeDelta;
+    use std::convert::Infallible;
+    use std::sync::atomic::{AtomicU32, Ordering};
+    use tokio::time::Instant;
+
+    #[derive(Clone, Copy, Default)]
+    struct DiscardIntents;
+
+    #[async_trait]
+    impl IntentDispatcher<TimeEntryIntent> for DiscardIntents {
+        type Error = Infallible;
+
+        async fn dispatch(
+            &self,
+            _stream_id: &str,
+            _starting_version: i64,
+            _events_len: usize,
+            _intents: Vec<TimeEntryIntent>,
+        ) -> Result<(), Infallible> {
+            Ok(())
+        }
+    }
+
+    #[derive(Clone, Copy, Default)]
+    struct FailingIntents;
+
+    #[async_trait]
+    impl IntentDispatcher<TimeEntryIntent> for FailingIntents {
+        type Error = OutboxError;
+
+        async fn dispatch(
+            &self,
+            _stream_id: &str,
+            _starting_version: i64,
+            _events_len: usize,
+            _intents: Vec<TimeEntryIntent>,
+        ) -> Result<(), OutboxError> {
+            Err(OutboxError::Backend("outbox offline".into()))
+        }
+    }
+
+    /// Loads from the inner store but refuses appends once `allowed_appends` runs out.
+    #[derive(Clone)]
+    struct FlakyAppends<E: Clone + Send + Sync + 'static> {
+        inner: InMemoryEventStore<E>,
+        allowed_appends: Arc<AtomicU32>,
+    }
+
+    impl<E: Clone + Send + Sync + 'static> FlakyAppends<E> {
+        fn new(inner: InMemoryEventStore<E>) -> Self {
+            Self {
+                inner,
+                allowed_appends: Arc::new(AtomicU32::new(u32::MAX)),
+            }
+        }
+
+        fn allow_appends(&self, count: u32) {
+            self.allowed_appends.store(count, Ordering::SeqCst);
+        }
+    }
+
+    #[async_trait]
+    impl<E: Clone + Send + Sync + 'static> EventStore<E> for FlakyAppends<E> {
+        async fn load(&self, stream_id: &str) -> Result<LoadedStream<E>, EventStoreError> {
+            self.inner.load(stream_id).await
+        }
+
+        async fn append(
+            &self,
+            stream_id: &str,
+            expected_version: i64,
+            new_events: &[E],
+        ) -> Result<AppendResult, EventStoreError> {
+            let allowed =
+                self.allowed_appends
+                    .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |left| {
+                        left.checked_sub(1)
+                    });
+            if allowed.is_err() {
+                return Err(EventStoreError::Backend("append refused".into()));
+            }
+            self.inner
+                .append(stream_id, expected_version, new_events)
+                .await
+        }
+    }
+
+    type StartHandler =
+        UserShardedHandler<SetStartedAtDecider, InMemoryEventStore<TimeEntryEvent>, DiscardIntents>;
+    type EndHandler =
+        UserShardedHandler<SetEndedAtDecider, InMemoryEventStore<TimeEntryEvent>, DiscardIntents>;
+
+    fn start(time_entry_id: &str, started_at: i64) -> SetStartedAt {
+        SetStartedAt {
+            time_entry_id: time_entry_id.into(),
+            user_id: "u-1".into(),
+            started_at,
+            updated_at: 1_000,
+            updated_by: "u-1".into(),
+            rounding: None,
+        }
+    }
+
+    fn end(time_entry_id: &str, ended_at: i64) -> SetEndedAt {
+        SetEndedAt {
+            time_entry_id: time_entry_id.into(),
+            user_id: "u-1".into(),
+            ended_at,
+            updated_at: 2_000,
+            updated_by: "u-1".into(),
+            rounding: None,
+        }
+    }
+
+    fn handlers(
+        user_streams: &InMemoryEventStore<UserTimeEntriesEvent>,
+    ) -> (StartHandler, EndHandler) {
+        let event_store = InMemoryEventStore::<TimeEntryEvent>::new();
+        let user_streams: UserStreams = Arc::new(user_streams.clone());
+        (
+            UserShardedHandler::new(event_store.clone(), DiscardIntents)
+                .with_user_streams(user_streams.clone()),
+            UserShardedHandler::new(event_store, DiscardIntents).with_user_streams(user_streams),
+        )
+    }
+
+    async fn user_state(
+        user_streams: &InMemoryEventStore<UserTimeEntriesEvent>,
+    ) -> UserTimeEntriesState {
+        user_streams
+            .load("UserTimeEntries-u-1")
+            .await
+            .unwrap()
+            .events
+            .into_iter()
+            .fold(UserTimeEntriesState::default(), evolve_user_time_entries)
+    }
+
+    #[tokio::test]
+    async fn it_should_claim_each_interval_change_on_the_user_stream() {
+        let user_streams = InMemoryEventStore::new();
+        let (start_handler, end_handler) = handlers(&user_streams);
+
+        start_handler
+            .handle("TimeEntry-te-1", start("te-1", 100))
+            .await
+            .unwrap();
+        end_handler
+            .handle("TimeEntry-te-1", end("te-1", 200))
+            .await
+            .unwrap();
+        start_handler
+            .handle("TimeEntry-te-1", start("te-1", 100))
+            .await
+            .unwrap();
+
+        let stream = user_streams.load("UserTimeEntries-u-1").await.unwrap();
+        assert_eq!(stream.version, 2);
+        assert_eq!(
+            user_state(&user_streams).await.intervals["te-1"],
+            Interval {
+                started_at: Some(100),
+                ended_at: Some(200),
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn it_should_reject_a_second_running_timer_without_touching_the_entry() {
+        let user_streams = InMemoryEventStore::new();
+        let (start_handler, _) = handlers(&user_streams);
+        start_handler
+            .handle("TimeEntry-te-1", start("te-1", 100))
+            .await
+            .unwrap();
+
+        let result = start_handler
+            .handle("TimeEntry-te-2", start("te-2", 500))
+            .await;
+
+        assert!(matches!(
+            result,
+            Err(EventSourcedError::Domain(StartError::UserTimeEntries(
+                UserTimeEntriesError::TimerAlreadyRunning { .. }
+            )))
+        ));
+        assert_eq!(
+            start_handler
+                .event_store
+                .load("TimeEntry-te-2")
+                .await
+                .unwrap()
+                .version,
+            0
+        );
+    }
+
+    #[tokio::test]
+    async fn it_should_reject_overlapping_entries() {
+        let user_streams = InMemoryEventStore::new();
+        let (start_handler, end_handler) = handlers(&user_streams);
+        start_handler
+            .handle("TimeEntry-te-1", start("te-1", 100))
+            .await
+            .unwrap();
+        end_handler
+            .handle("TimeEntry-te-1", end("te-1", 200))
+            .await
+            .unwrap();
+        end_handler
+            .handle("TimeEntry-te-2", end("te-2", 150))
+            .await
+            .unwrap();
+
+        let result = start_handler
+            .handle("TimeEntry-te-2", start("te-2", 120))
+            .await;
+
+        assert!(matches!(
+            result,
+            Err(EventSourcedError::Domain(StartError::UserTimeEntries(
+                UserTimeEntriesError::Overlaps { .. }
+            )))
+        ));
+        // An end without a start claims no time yet.
+        end_handler
+            .handle("TimeEntry-te-3", end("te-3", 120))
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn it_should_apply_the_rules_rolled_out_to_the_tenant() {
+        let user_streams = InMemoryEventStore::new();
+        let flags: SharedFeatureFlags = Arc::new(
+            EnvFeatureFlags::parse("reject_overlaps=t-strict;max_entry_duration=t-strict").unwrap(),
+        );
+        let (start_handler, end_handler) = handlers(&user_streams);
+        let start_handler = start_handler.with_feature_flags(flags.clone());
+        let end_handler = end_handler.with_feature_flags(flags).with_policies(
+            Arc::new(InMemoryPolicyStore::new()),
+            Policies {
+                max_entry_duration_ms: 60,
+                ..Policies::default()
+            },
+        );
+        for (id, at) in [("te-1", 100), ("te-2", 120)] {
+            let stream_id = format!("TimeEntry-{id}");
+            start_handler
+                .handle_for_tenant(Some("t-lenient"), &stream_id, start(id, at))
+                .await
+                .unwrap();
+            end_handler
+                .handle_for_tenant(Some("t-lenient"), &stream_id, end(id, at + 100))
+                .await
+                .unwrap();
+        }
+        start_handler
+            .handle_for_tenant(Some("t-strict"), "TimeEntry-te-3", start("te-3", 1_000))
+            .await
+            .unwrap();
+
+        let too_long = end_handler
+            .handle_for_tenant(Some("t-strict"), "TimeEntry-te-3", end("te-3", 1_100))
+            .await;
+        let overlapping = start_handler
+            .handle_for_tenant(Some("t-strict"), "TimeEntry-te-4", start("te-4", 150))
+            .await;
+
+        assert!(matches!(
+            too_long,
+            Err(EventSourcedError::Domain(EndError::UserTimeEntries(
+                UserTimeEntriesError::TooLong {
+                    max_duration_ms: 60
+                }
+            )))
+        ));
+        assert!(matches!(
+            overlapping,
+            Err(EventSourcedError::Domain(StartError::UserTimeEntries(
+                UserTimeEntriesError::Overlaps { .. }
+            )))
+        ));
+    }
+
+    #[tokio::test]
+    async fn it_should_hold_commands_to_the_policies_of_the_tenant() {
+        let user_streams = InMemoryEventStore::new();
+        let store = InMemoryPolicyStore::new();
+        for (tenant_id, policies) in [
+            (
+                "t-locking",
+                Policies {
+                    lock_after_days: Some(7),
+                    ..Policies::default()
+                },
+            ),
+            (
+                "t-rounding",
+                Policies {
+                    rounding: Some(Rounding {
+                        increment_minutes: 5,
+                        mode: RoundingMode::Up,
+                    }),
+                    ..Policies::default()
+                },
+            ),
+        ] {
+            store
+                .put(TenantPolicies {
+                    tenant_id: tenant_id.to_string(),
+                    policies,
+                    updated_at: 0,
+                    updated_by: "admin-1".to_string(),
+                })
+                .await
+                .unwrap();
+        }
+        let (start_handler, _) = handlers(&user_streams);
+        let start_handler = start_handler.with_policies(Arc::new(store), Policies::default());
+
+        let closed = start_handler
+            .handle_for_tenant(Some("t-locking"), "TimeEntry-te-1", start("te-1", 100))
+            .await;
+        start_handler
+            .handle_for_tenant(Some("t-rounding"), "TimeEntry-te-2", start("te-2", 100))
+            .await
+            .unwrap();
+
+        assert!(matches!(
+            closed,
+            Err(EventSourcedError::Domain(StartError::Policy(
+                PolicyError::Closed { lock_after_days: 7 }
+            )))
+        ));
+        let intervals = user_state(&user_streams).await.intervals;
+        assert!(!intervals.contains_key("te-1"));
+        assert_eq!(intervals["te-2"].started_at, Some(300_000));
+    }
+
+    #[tokio::test]
+    async fn it_should_release_a_new_claim_when_the_entry_append_fails() {
+        let user_streams = InMemoryEventStore::new();
+        let event_store = FlakyAppends::new(InMemoryEventStore::<TimeEntryEvent>::new());
+        let handler = UserShardedHandler::<SetStartedAtDecider, _, _>::new(
+            event_store.clone(),
+            DiscardIntents,
+        )
+        .with_user_streams(Arc::new(user_streams.clone()));
+        event_store.allow_appends(0);
+
+        let result = handler.handle("TimeEntry-te-1", start("te-1", 100)).await;
+
+        assert!(matches!(
+            result,
+            Err(EventSourcedError::VersionConflict(
+                EventStoreError::Backend(_)
+            ))
+        ));
+        assert!(user_state(&user_streams).await.intervals.is_empty());
+        event_store.allow_appends(u32::MAX);
+        handler
+            .handle("TimeEntry-te-1", start("te-1", 100))
+            .await
+            .unwrap();
+        assert_eq!(user_state(&user_streams).await.intervals.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn it_should_restore_the_previous_claim_when_the_entry_append_fails() {
+        let user_streams = InMemoryEventStore::new();
+        let event_store = FlakyAppends::new(InMemoryEventStore::<TimeEntryEvent>::new());
+        let handler = UserShardedHandler::<SetStartedAtDecider, _, _>::new(
+            event_store.clone(),
+            DiscardIntents,
+        )
+        .with_user_streams(Arc::new(user_streams.clone()));
+        handler
+            .handle("TimeEntry-te-1", start("te-1", 100))
+            .await
+            .unwrap();
+        event_store.allow_appends(0);
+
+        assert!(
+            handler
+                .handle("TimeEntry-te-1", start("te-1", 300))
+                .await
+                .is_err()
+        );
+
+        assert_eq!(
+            user_state(&user_streams).await.intervals["te-1"].started_at,
+            Some(100)
+        );
+    }
+
+    #[tokio::test]
+    async fn it_should_release_the_claim_when_the_entry_append_runs_out_of_time() {
+        let user_streams = InMemoryEventStore::new();
+        let event_store = InMemoryEventStore::<TimeEntryEvent>::new();
+        event_store.set_delay_append_ms(200);
+        let handler =
+            UserShardedHandler::<SetStartedAtDecider, _, _>::new(event_store, DiscardIntents)
+                .with_user_streams(Arc::new(user_streams.clone()));
+        let deadline = Deadline::at(Instant::now() + Duration::from_millis(50));
+
+        let result = deadline::scope(
+            deadline.clone(),
+            handler.handle("TimeEntry-te-1", start("te-1", 100)),
+        )
+        .await;
+
+        assert!(matches!(
+            result,
+            Err(EventSourcedError::VersionConflict(
+                EventStoreError::DeadlineExceeded
+            ))
+        ));
+        assert!(deadline.is_exceeded());
+        assert!(user_state(&user_streams).await.intervals.is_empty());
+    }
+
+    #[tokio::test]
+    async fn it_should_keep_the_claim_when_compensation_fails_too() {
+        let user_streams = FlakyAppends::new(InMemoryEventStore::new());
+        let event_store = FlakyAppends::new(InMemoryEventStore::<TimeEntryEvent>::new());
+        let handler = UserShardedHandler::<SetStartedAtDecider, _, _>::new(
+            event_store.clone(),
+            DiscardIntents,
+        )
+        .with_user_streams(Arc::new(user_streams.clone()));
+        event_store.allow_appends(0);
+        user_streams.allow_appends(1);
+
+        let result = handler.handle("TimeEntry-te-1", start("te-1", 100)).await;
+
+        assert!(result.is_err());
+        assert_eq!(user_state(&user_streams.inner).await.intervals.len(), 1);
+        // The stale claim is harmless to the entry itself: its retry re-claims the same interval.
+        event_store.allow_appends(u32::MAX);
+        handler
+            .handle("TimeEntry-te-1", start("te-1", 100))
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn it_should_surface_user_stream_failures() {
+        let user_streams = InMemoryEventStore::<UserTimeEntriesEvent>::new();
+        let (start_handler, _) = handlers(&user_streams);
+        user_streams.toggle_offline();
+
+        let result = start_handler
+            .handle("TimeEntry-te-1", start("te-1", 100))
+            .await;
+
+        assert!(matches!(
+            result,
+            Err(EventSourcedError::VersionConflict(
+                EventStoreError::Backend(_)
+            ))
+        ));
+    }
+
+    async fn locked_handler(
+        period_locks: &InMemoryEventStore<PeriodLocksEvent>,
+    ) -> (StartHandler, InMemoryEventStore<TimeEntryEvent>) {
+        let event_store = InMemoryEventStore::<TimeEntryEvent>::new();
+        let handler = UserShardedHandler::new(event_store.clone(), DiscardIntents)
+            .with_period_locks(Arc::new(period_locks.clone()));
+        (handler, event_store)
+    }
+
+    #[tokio::test]
+    async fn it_should_refuse_changes_inside_a_locked_period() {
+        let period_locks = InMemoryEventStore::<PeriodLocksEvent>::new();
+        period_locks
+            .append(
+                PERIOD_LOCKS_STREAM_ID,
+                0,
+                &[PeriodLocksEvent::PeriodLockedV1(PeriodLockedV1 {
+                    lock_id: "lock-1".to_string(),
+                    user_id: Some("u-1".to_string()),
+                    from: 0,
+                    to: 1_000,
+                    locked_at: 1,
+                    locked_by: "admin-1".to_string(),
+                })],
+            )
+            .await
+            .unwrap();
+        let (handler, event_store) = locked_handler(&period_locks).await;
+
+        let inside = handler.handle("TimeEntry-te-1", start("te-1", 100)).await;
+        let outside = handler.handle("TimeEntry-te-2", start("te-2", 5_000)).await;
+
+        assert!(matches!(
+            inside,
+            Err(EventSourcedError::Domain(StartError::PeriodLocked(
+                PeriodLockError::Locked { ref lock_id }
+            ))) if lock_id == "lock-1"
+        ));
+        assert!(outside.is_ok());
+        assert_eq!(event_store.load("TimeEntry-te-1").await.unwrap().version, 0);
+        assert_eq!(
+            format!("{handler:?}"),
+            "UserShardedHandler { user_streams: false, period_locks: true, absence_policy: None, day_capacity: None, feature_flags: false, policies: None, event_bus: false, .. }"
+        );
+    }
+
+    #[tokio::test]
+    async fn it_should_surface_period_lock_failures() {
+        let period_locks = InMemoryEventStore::<PeriodLocksEvent>::new();
+        let (handler, _) = locked_handler(&period_locks).await;
+        period_locks.toggle_offline();
+
+        let result = handler.handle("TimeEntry-te-1", start("te-1", 100)).await;
+
+        assert!(matches!(
+            result,
+            Err(EventSourcedError::VersionConflict(
+                EventStoreError::Backend(_)
+            ))
+        ));
+    }
+
+    fn calendar_handlers(
+        calendar: &StaticCalendar,
+        policy: AbsencePolicy,
+    ) -> (StartHandler, EndHandler, InMemoryEventStore<TimeEntryEvent>) {
+        let event_store = InMemoryEventStore::<TimeEntryEvent>::new();
+        let calendar: SharedCalendar = Arc::new(calendar.clone());
+        (
+            UserShardedHandler::new(event_store.clone(), DiscardIntents)
+                .with_calendar(calendar.clone(), policy),
+            UserShardedHandler::new(event_store.clone(), DiscardIntents)
+                .with_calendar(calendar, policy),
+            event_store,
+        )
+    }
+
+    fn new_year() -> StaticCalendar {
+        StaticCalendar::from_json(r#"{"holidays":[{"date":"1970-01-01","name":"New Year"}]}"#)
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn it_should_refuse_registering_on_a_day_off_when_rejecting() {
+        let (start_handler, end_handler, event_store) =
+            calendar_handlers(&new_year(), AbsencePolicy::Reject);
+
+        start_handler
+            .handle("TimeEntry-te-1", start("te-1", 100))
+            .await
+            .unwrap();
+        let result = end_handler.handle("TimeEntry-te-1", end("te-1", 200)).await;
+
+        assert!(matches!(
+            result,
+            Err(EventSourcedError::Domain(EndError::DayOff(
+                DayOffError::DayOff { .. }
+            )))
+        ));
+        assert_eq!(event_store.load("TimeEntry-te-1").await.unwrap().version, 2);
+        assert_eq!(
+            format!("{end_handler:?}"),
+            "UserShardedHandler { user_streams: false, period_locks: false, absence_policy: Some(Reject), day_capacity: None, feature_flags: false, policies: None, event_bus: false, .. }"
+        );
+    }
+
+    #[rstest::rstest]
+    #[case::warn(AbsencePolicy::Warn, false)]
+    #[case::calendar_offline(AbsencePolicy::Reject, true)]
+    #[tokio::test]
+    async fn it_should_still_register_when_warning_or_the_calendar_is_down(
+        #[case] policy: AbsencePolicy,
+        #[case] offline: bool,
+    ) {
+        let calendar = new_year();
+        if offline {
+            calendar.toggle_offline();
+        }
+        let (start_handler, end_handler, event_store) = calendar_handlers(&calendar, policy);
+
+        start_handler
+            .handle("TimeEntry-te-1", start("te-1", 100))
+            .await
+            .unwrap();
+        end_handler
+            .handle("TimeEntry-te-1", end("te-1", 200))
+            .await
+            .unwrap();
+
+        assert_eq!(event_store.load("TimeEntry-te-1").await.unwrap().version, 4);
+    }
+
+    #[tokio::test]
+    async fn it_should_not_check_unchanged_intervals() {
+        let (start_handler, end_handler, event_store) =
+            calendar_handlers(&new_year(), AbsencePolicy::Reject);
+        let unchecked_end: EndHandler = UserShardedHandler::new(event_store, DiscardIntents);
+        start_handler
+            .handle("TimeEntry-te-1", start("te-1", 100))
+            .await
+            .unwrap();
+        unchecked_end
+            .handle("TimeEntry-te-1", end("te-1", 200))
+            .await
+            .unwrap();
+
+        let result = end_handler.handle("TimeEntry-te-1", end("te-1", 200)).await;
+
+        assert!(result.is_ok());
+    }
+
+    fn capacity_handlers(policy: CapacityPolicy) -> (StartHandler, EndHandler) {
+        let user_streams = InMemoryEventStore::<UserTimeEntriesEvent>::new();
+        let day_totals: SharedDayTotals =
+            Arc::new(UserStreamDayTotals::new(Arc::new(user_streams.clone())));
+        let capacity = DayCapacity {
+            policy,
+            max_day_ms: 150,
+            overtime_ms: None,
+        };
+        let (start_handler, end_handler) = handlers(&user_streams);
+        (
+            start_handler.with_day_capacity(day_totals.clone(), capacity),
+            end_handler.with_day_capacity(day_totals, capacity),
+        )
+    }
+
+    async fn register(
+        start_handler: &StartHandler,
+        end_handler: &EndHandler,
+        time_entry_id: &str,
+        started_at: i64,
+        ended_at: i64,
+    ) -> Result<Vec<CapacityWarning>, EventSourcedError<EndError, Infallible>> {
+        let stream_id = format!("TimeEntry-{time_entry_id}");
+        start_handler
+            .handle(&stream_id, start(time_entry_id, started_at))
+            .await
+            .unwrap();
+        end_handler
+            .handle_with_warnings(None, &stream_id, None, end(time_entry_id, ended_at))
+            .await
+    }
+
+    #[tokio::test]
+    async fn it_should_refuse_taking_a_day_over_capacity_when_rejecting() {
+        let (start_handler, end_handler) = capacity_handlers(CapacityPolicy::Reject);
+        register(&start_handler, &end_handler, "te-1", 100, 200)
+            .await
+            .unwrap();
+
+        let result = register(&start_handler, &end_handler, "te-2", 300, 400).await;
+
+        assert!(matches!(
+            result,
+            Err(EventSourcedError::Domain(EndError::OverCapacity(
+                DayCapacityError::OverCapacity {
+                    total_ms: 200,
+                    limit_ms: 150,
+                    ..
+                }
+            )))
+        ));
+    }
+
+    #[tokio::test]
+    async fn it_should_register_and_answer_with_warnings_when_warning() {
+        let (start_handler, end_handler) = capacity_handlers(CapacityPolicy::Warn);
+        let first = register(&start_handler, &end_handler, "te-1", 100, 200)
+            .await
+            .unwrap();
+
+        let second = register(&start_handler, &end_handler, "te-2", 300, 400)
+            .await
+            .unwrap();
+
+        assert_eq!(first, vec![]);
+        assert_eq!(
+            second,
+            vec![CapacityWarning {
+                date: chrono::NaiveDate::from_ymd_opt(1970, 1, 1).unwrap(),
+                total_ms: 200,
+                limit_ms: 150,
+            }]
+        );
+    }
+
+    #[tokio::test]
+    async fn it_should_behave_like_a_plain_handler_without_user_streams() {
+        let event_store = InMemoryEventStore::<TimeEntryEvent>::new();
+        let end_handler = EndHandler::new(event_store.clone(), DiscardIntents);
+        let start_handler = UserShardedHandler::<SetStartedAtDecider, _, _>::new(
+            event_store.clone(),
+            FailingIntents,
+        );
+
+        end_handler
+            .handle("TimeEntry-te-1", end("te-1", 50))
+            .await
+            .unwrap();
+        let rejected = start_handler
+            .handle("TimeEntry-te-1", start("te-1", 100))
+            .await;
+        let failed_dispatch = start_handler
+            .handle("TimeEntry-te-1", start("te-1", 10))
+            .await;
+
+        assert!(matches!(
+            rejected,
+            Err(EventSourcedError::Domain(StartError::InvalidInterval))
+        ));
+        assert!(matches!(failed_dispatch, Err(EventSourcedError::Outbox(_))));
+        assert_eq!(event_store.load("TimeEntry-te-1").await.unwrap().version, 4);
+        assert_eq!(
+            format!("{:?}", start_handler.clone()),
+            "UserShardedHandler { user_streams: false, period_locks: false, absence_policy: None, day_capacity: None, feature_flags: false, policies: None, event_bus: false, .. }"
+        );
+    }
+
+    #[tokio::test]
+    async fn it_should_stamp_commands_with_its_clock_and_refuse_skewed_instants() {
+        const DAY: i64 = 86_400_000;
+        let event_store = InMemoryEventStore::<TimeEntryEvent>::new();
+        let handler = StartHandler::new(event_store.clone(), DiscardIntents)
+            .with_clock(Arc::new(FixedClock::at(10 * DAY)))
+            .with_skew_window(SkewWindow {
+                max_ahead: TimeDelta::minutes(5),
+                max_behind: Some(TimeDelta::days(1)),
+            });
+
+        let too_far_ahead = handler
+            .handle("TimeEntry-te-1", start("te-1", 11 * DAY))
+            .await;
+        let too_far_behind = handler
+            .handle("TimeEntry-te-1", start("te-1", 8 * DAY))
+            .await;
+        handler
+            .handle("TimeEntry-te-1", start("te-1", 10 * DAY - 1))
+            .await
+            .unwrap();
+
+        assert!(matches!(
+            too_far_ahead,
+            Err(EventSourcedError::Domain(StartError::ClockSkew(
+                ClockSkewError::TooFarAhead { .. }
+            )))
+        ));
+        assert!(matches!(
+            too_far_behind,
+            Err(EventSourcedError::Domain(StartError::ClockSkew(
+                ClockSkewError::TooFarBehind { .. }
+            )))
+        ));
+        let events = event_store.load("TimeEntry-te-1").await.unwrap().events;
+        assert_eq!(
+            events
+                .iter()
+                .map(TimeEntryEvent::occurred_at)
+                .collect::<Vec<_>>(),
+            vec![10 * DAY, 10 * DAY]
+        );
+    }
+}
@@ -0,0 +1,102 @@
+// Copies new events into cold storage as a job. Queued on a schedule by the job runner; a
+// run that fails is retried like any job and picks up from the archiver's own watermark.
+
+use async_trait::async_trait;
+use serde_json::{Value as Json, json};
+
+use crate::shared::application::jobs::{JobContext, JobHandler};
+use crate::shared::infrastructure::cold_storage::ColdStorage;
+use crate::shared::infrastructure::event_archiver::EventArchiver;
+use crate::shared::infrastructure::job_store::Job;
+
+pub const JOB_KIND: &str = "events.archive";
+
+pub struct ArchiveEventsJob<Event, TColdStorage>
+where
+    Event: Clone + Send + Sync + 'static,
+{
+    archiver: EventArchiver<Event, TColdStorage>,
+}
+
+impl<Event, TColdStorage> ArchiveEventsJob<Event, TColdStorage>
+where
+    Event: Clone + Send + Sync + serde::Serialize + 'static,
+    TColdStorage: ColdStorage + 'static,
+{
+    pub fn new(archiver: EventArchiver<Event, TColdStorage>) -> Self {
+        Self { archiver }
+    }
+}
+
+#[async_trait]
+impl<Event, TColdStorage> JobHandler for ArchiveEventsJob<Event, TColdStorage>
+where
+    Event: Clone + Send + Sync + serde::Serialize + 'static,
+    TColdStorage: ColdStorage + 'static,
+{
+    async fn run(&self, _job: &Job, _ctx: &JobContext<'_>) -> anyhow::Result<Json> {
+        let archived = self.archiver.archive_pending().await?;
+        Ok(json!({ "archived": archived }))
+    }
+}
+
+#[cfg(test)]
+mod archive_events_job_tests {
+    use super::*;
+    use crate::shared::application::jobs::JobRunner;
+    use crate::shared::infrastructure::clock::FixedClock;
+    use crate::shared::infrastructure::cold_storage::in_memory::InMemoryColdStorage;
+    use crate::shared::infrastructure::event_store::EventStore;
+    use crate::shared::infrastructure::event_store::in_memory::InMemoryEventStore;
+    use crate::shared::infrastructure::job_store::in_memory::InMemoryJobStore;
+    use crate::shared::infrastructure::job_store::{JobQuery, JobStatus, JobStore};
+    use rstest::rstest;
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_archive_new_events_on_schedule() {
+        let event_store = InMemoryEventStore::<u32>::new();
+        event_store.append("Counter-1", 0, &[1, 2]).await.unwrap();
+        let cold_storage = InMemoryColdStorage::new();
+        let archiver = EventArchiver::new(
+            Arc::new(event_store.clone()),
+            cold_storage.clone(),
+            "events",
+            10,
+        );
+        let (job_store, clock) = (InMemoryJobStore::new(), FixedClock::at(0));
+        let runner = JobRunner::new(job_store.clone())
+            .with_clock(Arc::new(clock.clone()))
+            .with_handler(JOB_KIND, Arc::new(ArchiveEventsJob::new(archiver)))
+            .with_schedule(JOB_KIND, json!({}), Duration::from_secs(60));
+
+        runner.run_due().await.unwrap();
+        event_store.append("Counter-1", 2, &[3]).await.unwrap();
+        clock.set(60_000);
+        runner.run_due().await.unwrap();
+
+        let jobs = job_store
+            .list(&JobQuery {
+                kind: Some(JOB_KIND.to_string()),
+                status: Some(JobStatus::Succeeded),
+                limit: 10,
+            })
+            .await
+            .unwrap();
+        let results: Vec<_> = jobs.into_iter().filter_map(|job| job.result).collect();
+        assert_eq!(
+            results,
+            vec![json!({ "archived": 1 }), json!({ "archived": 2 })]
+        );
+        assert_eq!(
+            cold_storage
+                .read_lines("events/00000000000000000000.jsonl")
+                .await
+                .unwrap()
+                .len(),
+            3
+        );
+    }
+}
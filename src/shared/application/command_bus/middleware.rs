@@ -0,0 +1,890 @@
+use async_trait::async_trait;
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::fmt::{Display, Write};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Instant;
+use tokio::sync::watch;
+
+use crate::shared::application::command_bus::{CommandBusError, CommandEnvelope, Middleware, Next};
+use crate::shared::infrastructure::clock::{SharedClock, SystemClock};
+
+/// Logs every dispatched command together with its outcome and duration. The acting user id
+/// is masked in log output when the log redactor is configured to mask `user_id`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LoggingMiddleware;
+
+#[async_trait]
+impl<C, E> Middleware<C, E> for LoggingMiddleware
+where
+    C: Send + 'static,
+    E: Display + Send + 'static,
+{
+    async fn handle(
+        &self,
+        envelope: CommandEnvelope<C>,
+        next: Next<'_, C, E>,
+    ) -> Result<(), CommandBusError<E>> {
+        let command = std::any::type_name::<C>();
+        let stream_id = envelope.stream_id.clone();
+        let user_id = envelope.user_id.clone();
+        let started = Instant::now();
+        let result = next.run(envelope).await;
+        let elapsed_ms = started.elapsed().as_millis() as u64;
+        match &result {
+            Ok(()) => {
+                tracing::info!(command, %stream_id, ?user_id, elapsed_ms, "command handled")
+            }
+            Err(error) => {
+                tracing::warn!(command, %stream_id, ?user_id, elapsed_ms, %error, "command failed")
+            }
+        }
+        result
+    }
+}
+
+#[derive(Debug, Default)]
+struct CommandMetricsInner {
+    dispatched: AtomicU64,
+    succeeded: AtomicU64,
+    failed: AtomicU64,
+    total_duration_micros: AtomicU64,
+}
+
+/// Shared counters written by `MetricsMiddleware`; clones observe the same values.
+#[derive(Debug, Clone, Default)]
+pub struct CommandMetrics {
+    inner: Arc<CommandMetricsInner>,
+}
+
+impl CommandMetrics {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn dispatched(&self) -> u64 {
+        self.inner.dispatched.load(Ordering::SeqCst)
+    }
+
+    pub fn succeeded(&self) -> u64 {
+        self.inner.succeeded.load(Ordering::SeqCst)
+    }
+
+    pub fn failed(&self) -> u64 {
+        self.inner.failed.load(Ordering::SeqCst)
+    }
+
+    pub fn total_duration_micros(&self) -> u64 {
+        self.inner.total_duration_micros.load(Ordering::SeqCst)
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct MetricsMiddleware {
+    metrics: CommandMetrics,
+}
+
+impl MetricsMiddleware {
+    pub fn new(metrics: CommandMetrics) -> Self {
+        Self { metrics }
+    }
+}
+
+#[async_trait]
+impl<C, E> Middleware<C, E> for MetricsMiddleware
+where
+    C: Send + 'static,
+    E: Send + 'static,
+{
+    async fn handle(
+        &self,
+        envelope: CommandEnvelope<C>,
+        next: Next<'_, C, E>,
+    ) -> Result<(), CommandBusError<E>> {
+        let inner = &self.metrics.inner;
+        inner.dispatched.fetch_add(1, Ordering::SeqCst);
+        let started = Instant::now();
+        let result = next.run(envelope).await;
+        inner
+            .total_duration_micros
+            .fetch_add(started.elapsed().as_micros() as u64, Ordering::SeqCst);
+        match result {
+            Ok(()) => inner.succeeded.fetch_add(1, Ordering::SeqCst),
+            Err(_) => inner.failed.fetch_add(1, Ordering::SeqCst),
+        };
+        result
+    }
+}
+
+/// Label for commands dispatched without a tenant id.
+pub const UNKNOWN_TENANT: &str = "unknown";
+
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+struct Latency {
+    count: u64,
+    sum_seconds: f64,
+}
+
+#[derive(Debug, Default)]
+struct HandlerMetricsInner {
+    /// Keyed by `(use_case, tenant, outcome)`.
+    outcomes: BTreeMap<(String, String, String), u64>,
+    /// Keyed by `(use_case, tenant)`.
+    latencies: BTreeMap<(String, String), Latency>,
+}
+
+/// Per use case, tenant and outcome counts written by `HandlerMetricsMiddleware`, rendered in
+/// the Prometheus text format for scraping. Clones observe the same values.
+#[derive(Debug, Clone, Default)]
+pub struct HandlerMetrics {
+    inner: Arc<std::sync::Mutex<HandlerMetricsInner>>,
+}
+
+impl HandlerMetrics {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn outcomes(&self, use_case: &str, tenant: &str, outcome: &str) -> u64 {
+        let key = (
+            use_case.to_string(),
+            tenant.to_string(),
+            outcome.to_string(),
+        );
+        let inner = self.inner.lock().unwrap();
+        inner.outcomes.get(&key).copied().unwrap_or_default()
+    }
+
+    /// Handler runs timed for `use_case` and `tenant`.
+    pub fn timed(&self, use_case: &str, tenant: &str) -> u64 {
+        let key = (use_case.to_string(), tenant.to_string());
+        let inner = self.inner.lock().unwrap();
+        inner.latencies.get(&key).map_or(0, |latency| latency.count)
+    }
+
+    fn record(&self, use_case: &str, tenant: &str, outcome: String, seconds: f64) {
+        let mut inner = self.inner.lock().unwrap();
+        *inner
+            .outcomes
+            .entry((use_case.to_string(), tenant.to_string(), outcome))
+            .or_default() += 1;
+        let latency = inner
+            .latencies
+            .entry((use_case.to_string(), tenant.to_string()))
+            .or_default();
+        latency.count += 1;
+        latency.sum_seconds += seconds;
+    }
+
+    /// `command_outcomes_total` counters and the `command_duration_seconds` summary.
+    pub fn render(&self) -> String {
+        let inner = self.inner.lock().unwrap();
+        let mut out = String::new();
+        out.push_str("# HELP command_outcomes_total Commands handled, by outcome.\n");
+        out.push_str("# TYPE command_outcomes_total counter\n");
+        for ((use_case, tenant, outcome), count) in &inner.outcomes {
+            let _ = writeln!(
+                out,
+                "command_outcomes_total{{use_case=\"{}\",tenant=\"{}\",outcome=\"{}\"}} {count}",
+                escape_label(use_case),
+                escape_label(tenant),
+                escape_label(outcome),
+            );
+        }
+        out.push_str("# HELP command_duration_seconds Time spent handling a command.\n");
+        out.push_str("# TYPE command_duration_seconds summary\n");
+        for ((use_case, tenant), latency) in &inner.latencies {
+            let labels = format!(
+                "use_case=\"{}\",tenant=\"{}\"",
+                escape_label(use_case),
+                escape_label(tenant),
+            );
+            let _ = writeln!(
+                out,
+                "command_duration_seconds_sum{{{labels}}} {}",
+                latency.sum_seconds
+            );
+            let _ = writeln!(
+                out,
+                "command_duration_seconds_count{{{labels}}} {}",
+                latency.count
+            );
+        }
+        out
+    }
+}
+
+fn escape_label(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+/// Counts every handler run under `use_case` and the envelope's tenant, labelled `accepted`,
+/// `unauthorized` or whatever `outcome_of` names the handler's error, and times it.
+///
+/// Register it after `RetryOnConflictMiddleware` so each attempt is counted: a command that
+/// conflicted twice before succeeding records two `version_mismatch` outcomes and one
+/// `accepted`.
+#[derive(Debug, Clone)]
+pub struct HandlerMetricsMiddleware<E> {
+    use_case: &'static str,
+    metrics: HandlerMetrics,
+    outcome_of: fn(&E) -> String,
+}
+
+impl<E> HandlerMetricsMiddleware<E> {
+    pub fn new(
+        use_case: &'static str,
+        metrics: HandlerMetrics,
+        outcome_of: fn(&E) -> String,
+    ) -> Self {
+        Self {
+            use_case,
+            metrics,
+            outcome_of,
+        }
+    }
+}
+
+#[async_trait]
+impl<C, E> Middleware<C, E> for HandlerMetricsMiddleware<E>
+where
+    C: Send + 'static,
+    E: Send + 'static,
+{
+    async fn handle(
+        &self,
+        envelope: CommandEnvelope<C>,
+        next: Next<'_, C, E>,
+    ) -> Result<(), CommandBusError<E>> {
+        let tenant = envelope
+            .tenant_id
+            .clone()
+            .unwrap_or_else(|| UNKNOWN_TENANT.to_string());
+        let started = Instant::now();
+        let result = next.run(envelope).await;
+        let outcome = match &result {
+            Ok(()) => "accepted".to_string(),
+            Err(CommandBusError::Unauthorized) => "unauthorized".to_string(),
+            Err(CommandBusError::Handler(error)) => (self.outcome_of)(error),
+        };
+        self.metrics.record(
+            self.use_case,
+            &tenant,
+            outcome,
+            started.elapsed().as_secs_f64(),
+        );
+        result
+    }
+}
+
+/// Rejects commands that do not carry a user id.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct AuthMiddleware;
+
+#[async_trait]
+impl<C, E> Middleware<C, E> for AuthMiddleware
+where
+    C: Send + 'static,
+    E: Send + 'static,
+{
+    async fn handle(
+        &self,
+        envelope: CommandEnvelope<C>,
+        next: Next<'_, C, E>,
+    ) -> Result<(), CommandBusError<E>> {
+        match envelope.user_id.as_deref() {
+            Some(user_id) if !user_id.trim().is_empty() => next.run(envelope).await,
+            _ => Err(CommandBusError::Unauthorized),
+        }
+    }
+}
+
+/// Completed idempotency keys are remembered this long by default.
+pub const DEFAULT_IDEMPOTENCY_TTL_MS: i64 = 24 * 60 * 60 * 1000;
+
+/// At most this many completed idempotency keys are remembered by default.
+pub const DEFAULT_MAX_IDEMPOTENCY_KEYS: usize = 10_000;
+
+#[derive(Debug)]
+enum KeyState {
+    /// A command holds the key; duplicates wait for its sender to be dropped.
+    Running(watch::Receiver<()>),
+    Completed,
+}
+
+enum Claim {
+    Completed,
+    Running(watch::Receiver<()>),
+    Reserved(watch::Sender<()>),
+}
+
+#[derive(Debug, Default)]
+struct IdempotencyKeys {
+    states: HashMap<String, KeyState>,
+    /// Completed keys with when they completed, oldest first.
+    completed: VecDeque<(i64, String)>,
+}
+
+impl IdempotencyKeys {
+    fn forget_completed(&mut self, until: usize, expired_before: i64) {
+        while let Some((completed_at, _)) = self.completed.front()
+            && (self.completed.len() > until || *completed_at < expired_before)
+        {
+            let (_, key) = self.completed.pop_front().expect("front was just read");
+            if matches!(self.states.get(&key), Some(KeyState::Completed)) {
+                self.states.remove(&key);
+            }
+        }
+    }
+}
+
+/// Skips commands whose idempotency key already completed successfully.
+///
+/// The key is reserved before the command runs, so a duplicate arriving meanwhile waits for
+/// the outcome: it is skipped once the first succeeded and runs itself if the first failed or
+/// was cancelled. Keys are only kept on success, so a failed command can be retried with the
+/// same key. Completed keys are forgotten after `ttl_ms`, and oldest first past `max_keys`.
+/// A key only matches commands of the same type, tenant, user and stream.
+#[derive(Debug, Clone)]
+pub struct IdempotencyMiddleware {
+    keys: Arc<std::sync::Mutex<IdempotencyKeys>>,
+    ttl_ms: i64,
+    max_keys: usize,
+    clock: SharedClock,
+}
+
+impl Default for IdempotencyMiddleware {
+    fn default() -> Self {
+        Self {
+            keys: Arc::default(),
+            ttl_ms: DEFAULT_IDEMPOTENCY_TTL_MS,
+            max_keys: DEFAULT_MAX_IDEMPOTENCY_KEYS,
+            clock: Arc::new(SystemClock),
+        }
+    }
+}
+
+impl IdempotencyMiddleware {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_ttl_ms(mut self, ttl_ms: i64) -> Self {
+        self.ttl_ms = ttl_ms;
+        self
+    }
+
+    pub fn with_max_keys(mut self, max_keys: usize) -> Self {
+        self.max_keys = max_keys;
+        self
+    }
+
+    pub fn with_clock(mut self, clock: SharedClock) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Completed keys currently remembered.
+    pub fn remembered(&self) -> usize {
+        self.keys.lock().unwrap().completed.len()
+    }
+
+    fn now(&self) -> i64 {
+        self.clock.now().as_millis()
+    }
+
+    fn claim(&self, key: &str) -> Claim {
+        let mut keys = self.keys.lock().unwrap();
+        keys.forget_completed(self.max_keys, self.now() - self.ttl_ms);
+        match keys.states.get(key) {
+            Some(KeyState::Completed) => Claim::Completed,
+            Some(KeyState::Running(settled)) => Claim::Running(settled.clone()),
+            None => {
+                let (settle, settled) = watch::channel(());
+                keys.states
+                    .insert(key.to_string(), KeyState::Running(settled));
+                Claim::Reserved(settle)
+            }
+        }
+    }
+}
+
+/// A key held while its command runs. Settles as failed when dropped unsettled, so a
+/// cancelled command never leaves its duplicates waiting; they wake once `_settled` drops.
+struct Reservation<'a> {
+    middleware: &'a IdempotencyMiddleware,
+    key: Option<String>,
+    _settled: watch::Sender<()>,
+}
+
+impl Reservation<'_> {
+    fn settle(&mut self, succeeded: bool) {
+        let Some(key) = self.key.take() else {
+            return;
+        };
+        let middleware = self.middleware;
+        let mut keys = middleware.keys.lock().unwrap();
+        if succeeded {
+            keys.completed.push_back((middleware.now(), key.clone()));
+            keys.states.insert(key, KeyState::Completed);
+        } else {
+            keys.states.remove(&key);
+        }
+        keys.forget_completed(middleware.max_keys, middleware.now() - middleware.ttl_ms);
+    }
+}
+
+impl Drop for Reservation<'_> {
+    fn drop(&mut self) {
+        self.settle(false);
+    }
+}
+
+#[async_trait]
+impl<C, E> Middleware<C, E> for IdempotencyMiddleware
+where
+    C: Send + 'static,
+    E: Send + 'static,
+{
+    async fn handle(
+        &self,
+        envelope: CommandEnvelope<C>,
+        next: Next<'_, C, E>,
+    ) -> Result<(), CommandBusError<E>> {
+        let Some(key) = envelope.idempotency_key.as_deref() else {
+            return next.run(envelope).await;
+        };
+        let key = format!(
+            "{}|{}|{}|{}|{key}",
+            std::any::type_name::<C>(),
+            envelope.tenant_id.as_deref().unwrap_or_default(),
+            envelope.user_id.as_deref().unwrap_or_default(),
+            envelope.stream_id,
+        );
+        let settle = loop {
+            match self.claim(&key) {
+                Claim::Completed => return Ok(()),
+                // Errs once the running command settled, which is all there is to wait for.
+                Claim::Running(mut settled) => drop(settled.changed().await),
+                Claim::Reserved(settle) => break settle,
+            }
+        };
+        let mut reservation = Reservation {
+            middleware: self,
+            key: Some(key),
+            _settled: settle,
+        };
+        let result = next.run(envelope).await;
+        reservation.settle(result.is_ok());
+        result
+    }
+}
+
+/// Re-runs the rest of the pipeline when the handler reports an optimistic concurrency conflict.
+/// Each attempt reloads the stream, so the command is decided against the latest state.
+#[derive(Debug, Clone, Copy)]
+pub struct RetryOnConflictMiddleware<E> {
+    max_attempts: u32,
+    is_conflict: fn(&E) -> bool,
+}
+
+impl<E> RetryOnConflictMiddleware<E> {
+    pub fn new(max_attempts: u32, is_conflict: fn(&E) -> bool) -> Self {
+        Self {
+            max_attempts: max_attempts.max(1),
+            is_conflict,
+        }
+    }
+}
+
+#[async_trait]
+impl<C, E> Middleware<C, E> for RetryOnConflictMiddleware<E>
+where
+    C: Clone + Send + Sync + 'static,
+    E: Send + 'static,
+{
+    async fn handle(
+        &self,
+        envelope: CommandEnvelope<C>,
+        next: Next<'_, C, E>,
+    ) -> Result<(), CommandBusError<E>> {
+        let mut attempt = 1;
+        loop {
+            match next.run(envelope.clone()).await {
+                Err(CommandBusError::Handler(error))
+                    if attempt < self.max_attempts && (self.is_conflict)(&error) =>
+                {
+                    attempt += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod command_bus_middleware_tests {
+    use super::*;
+    use crate::shared::application::command_bus::{CommandBus, CommandHandler};
+    use crate::shared::infrastructure::clock::FixedClock;
+    use rstest::rstest;
+    use std::sync::atomic::AtomicU32;
+    use thiserror::Error;
+
+    #[derive(Debug, Error, PartialEq, Eq)]
+    enum StubError {
+        #[error("conflict")]
+        Conflict,
+        #[error("rejected")]
+        Rejected,
+    }
+
+    /// Fails with the scripted errors first, then succeeds.
+    #[derive(Clone, Default)]
+    struct ScriptedHandler {
+        calls: Arc<AtomicU32>,
+        conflicts_before_success: u32,
+        reject: bool,
+        /// How long each call takes before it is counted.
+        delay_ms: u64,
+    }
+
+    #[async_trait]
+    impl CommandHandler<&'static str> for ScriptedHandler {
+        type Error = StubError;
+
+        async fn handle(&self, _stream_id: &str, _command: &'static str) -> Result<(), StubError> {
+            tokio::time::sleep(std::time::Duration::from_millis(self.delay_ms)).await;
+            let call = self.calls.fetch_add(1, Ordering::SeqCst) + 1;
+            if self.reject {
+                return Err(StubError::Rejected);
+            }
+            if call <= self.conflicts_before_success {
+                return Err(StubError::Conflict);
+            }
+            Ok(())
+        }
+    }
+
+    fn envelope() -> CommandEnvelope<&'static str> {
+        CommandEnvelope::new("Stream-1", "command")
+    }
+
+    fn is_conflict(error: &StubError) -> bool {
+        matches!(error, StubError::Conflict)
+    }
+
+    #[tokio::test]
+    async fn logging_passes_results_through() {
+        let ok_bus = CommandBus::new(ScriptedHandler::default()).with_middleware(LoggingMiddleware);
+        assert!(ok_bus.dispatch(envelope()).await.is_ok());
+        assert!(
+            ok_bus
+                .dispatch(envelope().with_user_id("u-1"))
+                .await
+                .is_ok()
+        );
+
+        let failing_bus = CommandBus::new(ScriptedHandler {
+            reject: true,
+            ..Default::default()
+        })
+        .with_middleware(LoggingMiddleware);
+        assert!(matches!(
+            failing_bus.dispatch(envelope()).await,
+            Err(CommandBusError::Handler(StubError::Rejected))
+        ));
+    }
+
+    #[tokio::test]
+    async fn metrics_count_successes_and_failures() {
+        let metrics = CommandMetrics::new();
+        let ok_bus = CommandBus::new(ScriptedHandler::default())
+            .with_middleware(MetricsMiddleware::new(metrics.clone()));
+        let failing_bus = CommandBus::new(ScriptedHandler {
+            reject: true,
+            ..Default::default()
+        })
+        .with_middleware(MetricsMiddleware::new(metrics.clone()));
+
+        ok_bus.dispatch(envelope()).await.unwrap();
+        ok_bus.dispatch(envelope()).await.unwrap();
+        let _ = failing_bus.dispatch(envelope()).await;
+
+        assert_eq!(metrics.dispatched(), 3);
+        assert_eq!(metrics.succeeded(), 2);
+        assert_eq!(metrics.failed(), 1);
+        assert!(metrics.total_duration_micros() < 60_000_000);
+    }
+
+    fn outcome_of(error: &StubError) -> String {
+        match error {
+            StubError::Conflict => "version_mismatch".to_string(),
+            StubError::Rejected => "rejected".to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn handler_metrics_count_every_attempt_by_outcome_and_tenant() {
+        let metrics = HandlerMetrics::new();
+        let bus = CommandBus::new(ScriptedHandler {
+            conflicts_before_success: 2,
+            ..Default::default()
+        })
+        .with_middleware(RetryOnConflictMiddleware::new(3, is_conflict))
+        .with_middleware(HandlerMetricsMiddleware::new(
+            "stub",
+            metrics.clone(),
+            outcome_of,
+        ));
+
+        bus.dispatch(envelope().with_tenant_id("t-1"))
+            .await
+            .unwrap();
+        bus.dispatch(envelope()).await.unwrap();
+
+        assert_eq!(metrics.outcomes("stub", "t-1", "version_mismatch"), 2);
+        assert_eq!(metrics.outcomes("stub", "t-1", "accepted"), 1);
+        assert_eq!(metrics.outcomes("stub", UNKNOWN_TENANT, "accepted"), 1);
+        assert_eq!(metrics.timed("stub", "t-1"), 3);
+        assert_eq!(metrics.timed("stub", "t-2"), 0);
+    }
+
+    #[rstest]
+    #[case::rejected(ScriptedHandler { reject: true, ..Default::default() }, false, "rejected")]
+    #[case::unauthorized(ScriptedHandler::default(), true, "unauthorized")]
+    #[tokio::test]
+    async fn handler_metrics_label_failures(
+        #[case] handler: ScriptedHandler,
+        #[case] require_user: bool,
+        #[case] outcome: &str,
+    ) {
+        let metrics = HandlerMetrics::new();
+        let mut bus = CommandBus::new(handler).with_middleware(HandlerMetricsMiddleware::new(
+            "stub",
+            metrics.clone(),
+            outcome_of,
+        ));
+        if require_user {
+            bus = bus.with_middleware(AuthMiddleware);
+        }
+
+        assert!(
+            bus.dispatch(envelope().with_tenant_id("t-1"))
+                .await
+                .is_err()
+        );
+
+        assert_eq!(metrics.outcomes("stub", "t-1", outcome), 1);
+    }
+
+    #[test]
+    fn handler_metrics_render_the_prometheus_text_format() {
+        let metrics = HandlerMetrics::new();
+        metrics.record("set_started_at", "t-\"1\"", "accepted".to_string(), 0.25);
+        metrics.record("set_started_at", "t-\"1\"", "accepted".to_string(), 0.5);
+
+        assert_eq!(
+            metrics.render(),
+            [
+                "# HELP command_outcomes_total Commands handled, by outcome.",
+                "# TYPE command_outcomes_total counter",
+                r#"command_outcomes_total{use_case="set_started_at",tenant="t-\"1\"",outcome="accepted"} 2"#,
+                "# HELP command_duration_seconds Time spent handling a command.",
+                "# TYPE command_duration_seconds summary",
+                r#"command_duration_seconds_sum{use_case="set_started_at",tenant="t-\"1\""} 0.75"#,
+                r#"command_duration_seconds_count{use_case="set_started_at",tenant="t-\"1\""} 2"#,
+                "",
+            ]
+            .join("\n")
+        );
+    }
+
+    #[rstest]
+    #[case::missing(None)]
+    #[case::blank(Some("  "))]
+    #[tokio::test]
+    async fn auth_rejects_commands_without_user(#[case] user_id: Option<&str>) {
+        let handler = ScriptedHandler::default();
+        let bus = CommandBus::new(handler.clone()).with_middleware(AuthMiddleware);
+        let mut envelope = envelope();
+        envelope.user_id = user_id.map(str::to_string);
+        let result = bus.dispatch(envelope).await;
+        assert!(matches!(result, Err(CommandBusError::Unauthorized)));
+        assert_eq!(handler.calls.load(Ordering::SeqCst), 0);
+    }
+
+    #[tokio::test]
+    async fn auth_allows_commands_with_user() {
+        let bus = CommandBus::new(ScriptedHandler::default()).with_middleware(AuthMiddleware);
+        assert!(bus.dispatch(envelope().with_user_id("u-1")).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn idempotency_skips_completed_keys() {
+        let handler = ScriptedHandler::default();
+        let bus = CommandBus::new(handler.clone()).with_middleware(IdempotencyMiddleware::new());
+        bus.dispatch(envelope().with_idempotency_key("key-1"))
+            .await
+            .unwrap();
+        bus.dispatch(envelope().with_idempotency_key("key-1"))
+            .await
+            .unwrap();
+        bus.dispatch(envelope().with_idempotency_key("key-2"))
+            .await
+            .unwrap();
+        assert_eq!(handler.calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn idempotency_ignores_commands_without_key() {
+        let handler = ScriptedHandler::default();
+        let bus = CommandBus::new(handler.clone()).with_middleware(IdempotencyMiddleware::new());
+        bus.dispatch(envelope()).await.unwrap();
+        bus.dispatch(envelope()).await.unwrap();
+        assert_eq!(handler.calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn idempotency_does_not_record_failed_commands() {
+        let handler = ScriptedHandler {
+            conflicts_before_success: 1,
+            ..Default::default()
+        };
+        let bus = CommandBus::new(handler.clone()).with_middleware(IdempotencyMiddleware::new());
+        assert!(
+            bus.dispatch(envelope().with_idempotency_key("key-1"))
+                .await
+                .is_err()
+        );
+        assert!(
+            bus.dispatch(envelope().with_idempotency_key("key-1"))
+                .await
+                .is_ok()
+        );
+        assert_eq!(handler.calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn idempotency_runs_concurrent_duplicates_once() {
+        let handler = ScriptedHandler {
+            delay_ms: 20,
+            ..Default::default()
+        };
+        let bus = CommandBus::new(handler.clone()).with_middleware(IdempotencyMiddleware::new());
+
+        let (first, second) = tokio::join!(
+            bus.dispatch(envelope().with_idempotency_key("key-1")),
+            bus.dispatch(envelope().with_idempotency_key("key-1")),
+        );
+
+        assert!(first.is_ok() && second.is_ok());
+        assert_eq!(handler.calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn idempotency_releases_keys_of_cancelled_commands() {
+        let handler = ScriptedHandler {
+            delay_ms: 50,
+            ..Default::default()
+        };
+        let bus = CommandBus::new(handler.clone()).with_middleware(IdempotencyMiddleware::new());
+
+        let cancelled = tokio::time::timeout(
+            std::time::Duration::from_millis(5),
+            bus.dispatch(envelope().with_idempotency_key("key-1")),
+        )
+        .await;
+        assert!(cancelled.is_err());
+        bus.dispatch(envelope().with_idempotency_key("key-1"))
+            .await
+            .unwrap();
+
+        assert_eq!(handler.calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn idempotency_scopes_keys_to_the_user() {
+        let handler = ScriptedHandler::default();
+        let bus = CommandBus::new(handler.clone()).with_middleware(IdempotencyMiddleware::new());
+        for user_id in ["u-1", "u-2", "u-1"] {
+            bus.dispatch(
+                envelope()
+                    .with_user_id(user_id)
+                    .with_idempotency_key("key-1"),
+            )
+            .await
+            .unwrap();
+        }
+        assert_eq!(handler.calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn idempotency_forgets_expired_and_excess_keys() {
+        let handler = ScriptedHandler::default();
+        let clock = FixedClock::at(0);
+        let idempotency = IdempotencyMiddleware::new()
+            .with_ttl_ms(1_000)
+            .with_max_keys(2)
+            .with_clock(Arc::new(clock.clone()));
+        let bus = CommandBus::new(handler.clone()).with_middleware(idempotency.clone());
+        let dispatch = |key: &'static str| bus.dispatch(envelope().with_idempotency_key(key));
+
+        for key in ["key-1", "key-2", "key-3", "key-1"] {
+            dispatch(key).await.unwrap();
+        }
+        assert_eq!(handler.calls.load(Ordering::SeqCst), 4);
+        assert_eq!(idempotency.remembered(), 2);
+
+        dispatch("key-3").await.unwrap();
+        assert_eq!(handler.calls.load(Ordering::SeqCst), 4);
+        clock.advance(1_001);
+        dispatch("key-3").await.unwrap();
+        assert_eq!(handler.calls.load(Ordering::SeqCst), 5);
+    }
+
+    #[tokio::test]
+    async fn retry_recovers_from_conflicts() {
+        let handler = ScriptedHandler {
+            conflicts_before_success: 2,
+            ..Default::default()
+        };
+        let bus = CommandBus::new(handler.clone())
+            .with_middleware(RetryOnConflictMiddleware::new(3, is_conflict));
+        assert!(bus.dispatch(envelope()).await.is_ok());
+        assert_eq!(handler.calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn retry_gives_up_after_max_attempts() {
+        let handler = ScriptedHandler {
+            conflicts_before_success: 5,
+            ..Default::default()
+        };
+        let bus = CommandBus::new(handler.clone())
+            .with_middleware(RetryOnConflictMiddleware::new(2, is_conflict));
+        assert!(matches!(
+            bus.dispatch(envelope()).await,
+            Err(CommandBusError::Handler(StubError::Conflict))
+        ));
+        assert_eq!(handler.calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn retry_does_not_retry_other_errors() {
+        let handler = ScriptedHandler {
+            reject: true,
+            ..Default::default()
+        };
+        let bus = CommandBus::new(handler.clone())
+            .with_middleware(RetryOnConflictMiddleware::new(3, is_conflict));
+        assert!(matches!(
+            bus.dispatch(envelope()).await,
+            Err(CommandBusError::Handler(StubError::Rejected))
+        ));
+        assert_eq!(handler.calls.load(Ordering::SeqCst), 1);
+    }
+}
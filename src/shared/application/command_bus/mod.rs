@@ -0,0 +1,325 @@
+use async_trait::async_trait;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use thiserror::Error;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommandEnvelope<C> {
+    pub stream_id: String,
+    pub command: C,
+    pub user_id: Option<String>,
+    pub tenant_id: Option<String>,
+    pub idempotency_key: Option<String>,
+}
+
+impl<C> CommandEnvelope<C> {
+    pub fn new(stream_id: impl Into<String>, command: C) -> Self {
+        Self {
+            stream_id: stream_id.into(),
+            command,
+            user_id: None,
+            tenant_id: None,
+            idempotency_key: None,
+        }
+    }
+
+    pub fn with_user_id(mut self, user_id: impl Into<String>) -> Self {
+        self.user_id = Some(user_id.into());
+        self
+    }
+
+    pub fn with_tenant_id(mut self, tenant_id: impl Into<String>) -> Self {
+        self.tenant_id = Some(tenant_id.into());
+        self
+    }
+
+    pub fn with_idempotency_key(mut self, idempotency_key: impl Into<String>) -> Self {
+        self.idempotency_key = Some(idempotency_key.into());
+        self
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum CommandBusError<E> {
+    #[error("unauthorized")]
+    Unauthorized,
+
+    #[error(transparent)]
+    Handler(E),
+}
+
+/// A use case handler that can sit at the end of a command bus pipeline.
+#[async_trait]
+pub trait CommandHandler<C: Send + 'static>: Send + Sync {
+    type Error;
+
+    async fn handle(&self, stream_id: &str, command: C) -> Result<(), Self::Error>;
+
+    /// Handles a command dispatched on behalf of `tenant_id`. Handlers whose rules are rolled
+    /// out per tenant override this; the others handle it like any command.
+    async fn handle_for_tenant(
+        &self,
+        tenant_id: &str,
+        stream_id: &str,
+        command: C,
+    ) -> Result<(), Self::Error> {
+        let _ = tenant_id;
+        self.handle(stream_id, command).await
+    }
+}
+
+/// A cross-cutting concern wrapped around every dispatched command.
+/// Call `next.run(envelope)` to continue the pipeline, or return early to short-circuit it.
+#[async_trait]
+pub trait Middleware<C, E>: Send + Sync {
+    async fn handle(
+        &self,
+        envelope: CommandEnvelope<C>,
+        next: Next<'_, C, E>,
+    ) -> Result<(), CommandBusError<E>>;
+}
+
+/// A dispatched command, boxed with its `Send` bound spelled out. Request handlers holding a
+/// bus across an await cannot always be proven `Send`; ones holding a `Dispatch` can.
+pub type Dispatch<'a, E> =
+    Pin<Box<dyn Future<Output = Result<(), CommandBusError<E>>> + Send + 'a>>;
+
+pub struct Next<'a, C, E> {
+    handler: &'a (dyn CommandHandler<C, Error = E> + 'a),
+    middlewares: &'a [Arc<dyn Middleware<C, E>>],
+}
+
+impl<C, E> Clone for Next<'_, C, E> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<C, E> Copy for Next<'_, C, E> {}
+
+impl<C, E> Next<'_, C, E>
+where
+    C: Send + 'static,
+    E: Send + 'static,
+{
+    pub async fn run(self, envelope: CommandEnvelope<C>) -> Result<(), CommandBusError<E>> {
+        match self.middlewares.split_first() {
+            Some((middleware, rest)) => {
+                let next = Next {
+                    handler: self.handler,
+                    middlewares: rest,
+                };
+                middleware.handle(envelope, next).await
+            }
+            None => match envelope.tenant_id.as_deref() {
+                Some(tenant_id) => {
+                    self.handler
+                        .handle_for_tenant(tenant_id, &envelope.stream_id, envelope.command)
+                        .await
+                }
+                None => {
+                    self.handler
+                        .handle(&envelope.stream_id, envelope.command)
+                        .await
+                }
+            }
+            .map_err(CommandBusError::Handler),
+        }
+    }
+}
+
+pub struct CommandBus<C, THandler>
+where
+    C: Send + 'static,
+    THandler: CommandHandler<C>,
+{
+    handler: THandler,
+    middlewares: Vec<Arc<dyn Middleware<C, THandler::Error>>>,
+}
+
+impl<C, THandler> Clone for CommandBus<C, THandler>
+where
+    C: Send + 'static,
+    THandler: CommandHandler<C> + Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            handler: self.handler.clone(),
+            middlewares: self.middlewares.clone(),
+        }
+    }
+}
+
+impl<C, THandler> CommandBus<C, THandler>
+where
+    C: Send + 'static,
+    THandler: CommandHandler<C>,
+    THandler::Error: Send + 'static,
+{
+    pub fn new(handler: THandler) -> Self {
+        Self {
+            handler,
+            middlewares: Vec::new(),
+        }
+    }
+
+    /// Middlewares run in registration order: the first one added is the outermost.
+    pub fn with_middleware(
+        mut self,
+        middleware: impl Middleware<C, THandler::Error> + 'static,
+    ) -> Self {
+        self.middlewares.push(Arc::new(middleware));
+        self
+    }
+
+    pub async fn dispatch(
+        &self,
+        envelope: CommandEnvelope<C>,
+    ) -> Result<(), CommandBusError<THandler::Error>> {
+        Next {
+            handler: &self.handler,
+            middlewares: &self.middlewares,
+        }
+        .run(envelope)
+        .await
+    }
+}
+
+pub mod middleware;
+pub mod pipeline;
+
+#[cfg(test)]
+mod command_bus_tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    #[derive(Debug, Error, PartialEq, Eq)]
+    #[error("rejected: {0}")]
+    struct StubError(String);
+
+    #[derive(Clone, Default)]
+    struct RecordingHandler {
+        calls: Arc<Mutex<Vec<(String, u32)>>>,
+    }
+
+    #[async_trait]
+    impl CommandHandler<u32> for RecordingHandler {
+        type Error = StubError;
+
+        async fn handle(&self, stream_id: &str, command: u32) -> Result<(), StubError> {
+            self.calls
+                .lock()
+                .unwrap()
+                .push((stream_id.to_string(), command));
+            if command == 0 {
+                return Err(StubError("zero".into()));
+            }
+            Ok(())
+        }
+    }
+
+    struct Tracing {
+        name: &'static str,
+        trail: Arc<Mutex<Vec<String>>>,
+    }
+
+    #[async_trait]
+    impl Middleware<u32, StubError> for Tracing {
+        async fn handle(
+            &self,
+            envelope: CommandEnvelope<u32>,
+            next: Next<'_, u32, StubError>,
+        ) -> Result<(), CommandBusError<StubError>> {
+            self.trail
+                .lock()
+                .unwrap()
+                .push(format!("{}:before", self.name));
+            let result = next.run(envelope).await;
+            self.trail
+                .lock()
+                .unwrap()
+                .push(format!("{}:after", self.name));
+            result
+        }
+    }
+
+    struct ShortCircuit;
+
+    #[async_trait]
+    impl Middleware<u32, StubError> for ShortCircuit {
+        async fn handle(
+            &self,
+            _envelope: CommandEnvelope<u32>,
+            _next: Next<'_, u32, StubError>,
+        ) -> Result<(), CommandBusError<StubError>> {
+            Err(CommandBusError::Unauthorized)
+        }
+    }
+
+    #[tokio::test]
+    async fn it_should_dispatch_to_the_handler_without_middlewares() {
+        let handler = RecordingHandler::default();
+        let bus = CommandBus::new(handler.clone());
+        bus.dispatch(CommandEnvelope::new("Stream-1", 7))
+            .await
+            .unwrap();
+        assert_eq!(
+            *handler.calls.lock().unwrap(),
+            vec![("Stream-1".to_string(), 7)]
+        );
+    }
+
+    #[tokio::test]
+    async fn it_should_wrap_handler_errors() {
+        let bus = CommandBus::new(RecordingHandler::default());
+        let result = bus.dispatch(CommandEnvelope::new("Stream-1", 0)).await;
+        assert!(matches!(
+            result,
+            Err(CommandBusError::Handler(StubError(ref reason))) if reason == "zero"
+        ));
+        assert_eq!(result.unwrap_err().to_string(), "rejected: zero");
+    }
+
+    #[tokio::test]
+    async fn it_should_run_middlewares_in_registration_order() {
+        let trail = Arc::new(Mutex::new(Vec::new()));
+        let bus = CommandBus::new(RecordingHandler::default())
+            .with_middleware(Tracing {
+                name: "outer",
+                trail: trail.clone(),
+            })
+            .with_middleware(Tracing {
+                name: "inner",
+                trail: trail.clone(),
+            });
+        bus.clone()
+            .dispatch(CommandEnvelope::new("Stream-1", 1))
+            .await
+            .unwrap();
+        assert_eq!(
+            *trail.lock().unwrap(),
+            vec!["outer:before", "inner:before", "inner:after", "outer:after"]
+        );
+    }
+
+    #[tokio::test]
+    async fn it_should_not_reach_the_handler_when_a_middleware_short_circuits() {
+        let handler = RecordingHandler::default();
+        let bus = CommandBus::new(handler.clone()).with_middleware(ShortCircuit);
+        let result = bus.dispatch(CommandEnvelope::new("Stream-1", 1)).await;
+        assert!(matches!(result, Err(CommandBusError::Unauthorized)));
+        assert!(handler.calls.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn it_should_build_envelopes_with_metadata() {
+        let envelope = CommandEnvelope::new("Stream-1", 1)
+            .with_user_id("u-1")
+            .with_tenant_id("t-1")
+            .with_idempotency_key("key-1");
+        assert_eq!(envelope.user_id.as_deref(), Some("u-1"));
+        assert_eq!(envelope.tenant_id.as_deref(), Some("t-1"));
+        assert_eq!(envelope.idempotency_key.as_deref(), Some("key-1"));
+    }
+}
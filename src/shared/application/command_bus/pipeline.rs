@@ -0,0 +1,172 @@
+use std::fmt::{Debug, Display};
+
+use crate::shared::application::command_bus::middleware::{
+    AuthMiddleware, HandlerMetrics, HandlerMetricsMiddleware, IdempotencyMiddleware,
+    LoggingMiddleware, RetryOnConflictMiddleware,
+};
+use crate::shared::application::command_bus::{
+    CommandBus, CommandEnvelope, CommandHandler, Dispatch,
+};
+use crate::shared::application::event_sourced_handler::EventSourcedError;
+use crate::shared::infrastructure::event_store::EventStoreError;
+
+/// Attempts a command gets before a version conflict is handed back to the caller.
+pub const MAX_CONFLICT_ATTEMPTS: u32 = 3;
+
+/// The middlewares inbound adapters dispatch event-sourced commands through: logging,
+/// authentication, idempotency, retrying version conflicts and per use case metrics, outermost
+/// first. Clones share their idempotency keys and metrics, so a bus can be built per request
+/// around the handler the state holds at the time.
+#[derive(Debug, Clone, Default)]
+pub struct CommandPipeline {
+    idempotency: IdempotencyMiddleware,
+    metrics: HandlerMetrics,
+}
+
+impl CommandPipeline {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_idempotency(mut self, idempotency: IdempotencyMiddleware) -> Self {
+        self.idempotency = idempotency;
+        self
+    }
+
+    pub fn with_metrics(mut self, metrics: HandlerMetrics) -> Self {
+        self.metrics = metrics;
+        self
+    }
+
+    pub fn metrics(&self) -> &HandlerMetrics {
+        &self.metrics
+    }
+
+    /// Dispatches `envelope` to `handler` behind the pipeline, recording its outcomes as
+    /// `use_case`. The bus moves into the returned future, which adapters can hold across their
+    /// own awaits and stay `Send`.
+    pub fn dispatch<C, THandler, TReason, TDispatchError>(
+        &self,
+        use_case: &'static str,
+        handler: THandler,
+        envelope: CommandEnvelope<C>,
+    ) -> Dispatch<'static, EventSourcedError<TReason, TDispatchError>>
+    where
+        C: Clone + Send + Sync + 'static,
+        THandler: CommandHandler<C, Error = EventSourcedError<TReason, TDispatchError>> + 'static,
+        TReason: Debug + Send + 'static,
+        TDispatchError: Send + 'static,
+        EventSourcedError<TReason, TDispatchError>: Display,
+    {
+        let bus = self.bus(use_case, handler);
+        Box::pin(async move { bus.dispatch(envelope).await })
+    }
+
+    /// A bus running `handler` behind the pipeline, recording its outcomes as `use_case`.
+    pub fn bus<C, THandler, TReason, TDispatchError>(
+        &self,
+        use_case: &'static str,
+        handler: THandler,
+    ) -> CommandBus<C, THandler>
+    where
+        C: Clone + Send + Sync + 'static,
+        THandler: CommandHandler<C, Error = EventSourcedError<TReason, TDispatchError>>,
+        TReason: Debug + Send + 'static,
+        TDispatchError: Send + 'static,
+        EventSourcedError<TReason, TDispatchError>: Display,
+    {
+        CommandBus::new(handler)
+            .with_middleware(LoggingMiddleware)
+            .with_middleware(AuthMiddleware)
+            .with_middleware(self.idempotency.clone())
+            .with_middleware(RetryOnConflictMiddleware::new(
+                MAX_CONFLICT_ATTEMPTS,
+                is_version_mismatch::<TReason, TDispatchError>,
+            ))
+            .with_middleware(HandlerMetricsMiddleware::new(
+                use_case,
+                self.metrics.clone(),
+                EventSourcedError::<TReason, TDispatchError>::outcome,
+            ))
+    }
+}
+
+fn is_version_mismatch<TReason, TDispatchError>(
+    error: &EventSourcedError<TReason, TDispatchError>,
+) -> bool {
+    matches!(
+        error,
+        EventSourcedError::VersionConflict(EventStoreError::VersionMismatch { .. })
+    )
+}
+
+#[cfg(test)]
+mod command_pipeline_tests {
+    use super::*;
+    use crate::shared::application::command_bus::CommandBusError;
+    use async_trait::async_trait;
+    use rstest::rstest;
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicU32, Ordering};
+    use thiserror::Error;
+
+    #[derive(Debug, Error)]
+    #[error("rejected")]
+    struct Rejected;
+
+    type StubError = EventSourcedError<Rejected, Rejected>;
+
+    /// Conflicts on its first attempt, then succeeds.
+    #[derive(Clone, Default)]
+    struct ConflictingOnceHandler {
+        attempts: Arc<AtomicU32>,
+    }
+
+    #[async_trait]
+    impl CommandHandler<u32> for ConflictingOnceHandler {
+        type Error = StubError;
+
+        async fn handle(&self, _stream_id: &str, _command: u32) -> Result<(), StubError> {
+            if self.attempts.fetch_add(1, Ordering::SeqCst) == 0 {
+                return Err(EventSourcedError::VersionConflict(
+                    EventStoreError::VersionMismatch {
+                        expected: 0,
+                        actual: 1,
+                    },
+                ));
+            }
+            Ok(())
+        }
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_authenticate_deduplicate_retry_and_measure() {
+        let pipeline = CommandPipeline::new();
+        let handler = ConflictingOnceHandler::default();
+        let envelope = || {
+            CommandEnvelope::new("TimeEntry-1", 1)
+                .with_user_id("u-1")
+                .with_tenant_id("t-1")
+                .with_idempotency_key("k-1")
+        };
+
+        let anonymous = pipeline
+            .dispatch(
+                "stub",
+                handler.clone(),
+                CommandEnvelope::new("TimeEntry-1", 1),
+            )
+            .await;
+        // A later dispatch still knows the key.
+        let first = pipeline.dispatch("stub", handler.clone(), envelope()).await;
+        let repeated = pipeline.dispatch("stub", handler.clone(), envelope()).await;
+
+        assert!(matches!(anonymous, Err(CommandBusError::Unauthorized)));
+        assert!(first.is_ok() && repeated.is_ok());
+        assert_eq!(handler.attempts.load(Ordering::SeqCst), 2);
+        let metrics = pipeline.metrics();
+        assert_eq!(metrics.outcomes("stub", "t-1", "version_mismatch"), 1);
+        assert_eq!(metrics.outcomes("stub", "t-1", "accepted"), 1);
+    }
+}
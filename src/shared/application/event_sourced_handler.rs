@@ -0,0 +1,383 @@
+use async_trait::async_trait;
+use std::convert::Infallible;
+use std::marker::PhantomData;
+use thiserror::Error;
+
+use crate::shared::core::decider::{Decider, Decision};
+use crate::shared::infrastructure::event_bus::{SharedEventBus, stored_events};
+use crate::shared::infrastructure::event_store::paged::{DEFAULT_PAGE_SIZE, fold_paged};
+use crate::shared::infrastructure::event_store::{EventStore, EventStoreError};
+
+#[derive(Debug, Error)]
+pub enum EventSourcedError<TReason, TDispatchError> {
+    #[error(transparent)]
+    VersionConflict(#[from] EventStoreError),
+
+    #[error(transparent)]
+    Outbox(TDispatchError),
+
+    #[error("domain rejected: {0}")]
+    Domain(TReason),
+}
+
+impl<TReason: std::fmt::Debug, TDispatchError> EventSourcedError<TReason, TDispatchError> {
+    /// Metric label for the failure: `version_mismatch`, `event_store`, `outbox`, or the
+    /// rejection's variant in snake case, e.g. `invalid_interval` for
+    /// `DecideError::InvalidInterval`.
+    pub fn outcome(&self) -> String {
+        match self {
+            EventSourcedError::VersionConflict(EventStoreError::VersionMismatch { .. }) => {
+                "version_mismatch".to_string()
+            }
+            EventSourcedError::VersionConflict(_) => "event_store".to_string(),
+            EventSourcedError::Outbox(_) => "outbox".to_string(),
+            EventSourcedError::Domain(reason) => variant_name(reason),
+        }
+    }
+}
+
+/// The variant name as the derived `Debug` prints it, in snake case.
+fn variant_name(reason: &impl std::fmt::Debug) -> String {
+    let debug = format!("{reason:?}");
+    let mut name = String::new();
+    for c in debug
+        .chars()
+        .take_while(|c| c.is_alphanumeric() || *c == '_')
+    {
+        if c.is_uppercase() && !name.is_empty() {
+            name.push('_');
+        }
+        name.extend(c.to_lowercase());
+    }
+    name
+}
+
+/// Hands the intents of an accepted decision to the outside world, typically an outbox.
+/// `starting_version` is the stream version before the append; `events_len` the number of
+/// events appended, so intents can be keyed to the event versions they follow.
+#[async_trait]
+pub trait IntentDispatcher<TIntent>: Send + Sync {
+    type Error;
+
+    async fn dispatch(
+        &self,
+        stream_id: &str,
+        starting_version: i64,
+        events_len: usize,
+        intents: Vec<TIntent>,
+    ) -> Result<(), Self::Error>;
+}
+
+/// Dispatcher for deciders that never produce intents.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoIntents;
+
+#[async_trait]
+impl IntentDispatcher<Infallible> for NoIntents {
+    type Error = Infallible;
+
+    async fn dispatch(
+        &self,
+        _stream_id: &str,
+        _starting_version: i64,
+        _events_len: usize,
+        _intents: Vec<Infallible>,
+    ) -> Result<(), Infallible> {
+        Ok(())
+    }
+}
+
+/// The load → fold → decide → append → dispatch loop shared by every command handler.
+pub struct EventSourcedHandler<TDecider: Decider, TEventStore, TDispatcher> {
+    event_store: TEventStore,
+    dispatcher: TDispatcher,
+    event_bus: Option<SharedEventBus<TDecider::Event>>,
+    _decider: PhantomData<fn() -> TDecider>,
+}
+
+impl<TDecider, TEventStore, TDispatcher> Clone
+    for EventSourcedHandler<TDecider, TEventStore, TDispatcher>
+where
+    TDecider: Decider,
+    TEventStore: Clone,
+    TDispatcher: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            event_store: self.event_store.clone(),
+            dispatcher: self.dispatcher.clone(),
+            event_bus: self.event_bus.clone(),
+            _decider: PhantomData,
+        }
+    }
+}
+
+impl<TDecider: Decider, TEventStore, TDispatcher> std::fmt::Debug
+    for EventSourcedHandler<TDecider, TEventStore, TDispatcher>
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EventSourcedHandler")
+            .finish_non_exhaustive()
+    }
+}
+
+impl<TDecider, TEventStore, TDispatcher> EventSourcedHandler<TDecider, TEventStore, TDispatcher>
+where
+    TDecider: Decider,
+    TDecider::State: Send,
+    TDecider::Command: Send,
+    TDecider::Event: Clone + Send + Sync + 'static,
+    TDecider::Intent: Send,
+    TEventStore: EventStore<TDecider::Event> + Send + Sync + 'static,
+    TDispatcher: IntentDispatcher<TDecider::Intent> + Send + Sync + 'static,
+{
+    pub fn new(event_store: TEventStore, dispatcher: TDispatcher) -> Self {
+        Self {
+            event_store,
+            dispatcher,
+            event_bus: None,
+            _decider: PhantomData,
+        }
+    }
+
+    /// Publish the events of every append to `event_bus`, after the append and before the
+    /// intents are dispatched.
+    pub fn with_event_bus(mut self, event_bus: SharedEventBus<TDecider::Event>) -> Self {
+        self.event_bus = Some(event_bus);
+        self
+    }
+
+    pub async fn handle(
+        &self,
+        stream_id: &str,
+        command: TDecider::Command,
+    ) -> Result<(), EventSourcedError<TDecider::Error, TDispatcher::Error>> {
+        let (state, version) = fold_paged(
+            &self.event_store,
+            stream_id,
+            DEFAULT_PAGE_SIZE,
+            TDecider::initial_state(),
+            TDecider::evolve,
+        )
+        .await?;
+
+        match TDecider::decide(&state, command) {
+            Decision::Accepted { events, intents } => {
+                let appended = self.event_store.append(stream_id, version, &events).await?;
+                if let Some(event_bus) = &self.event_bus {
+                    event_bus.publish(stored_events(stream_id, version, &events, &appended));
+                }
+                self.dispatcher
+                    .dispatch(stream_id, version, events.len(), intents)
+                    .await
+                    .map_err(EventSourcedError::Outbox)
+            }
+            Decision::Rejected { reason } => Err(EventSourcedError::Domain(reason)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod event_sourced_handler_tests {
+    use super::*;
+    use crate::shared::infrastructure::event_bus::in_memory::InMemoryEventBus;
+    use crate::shared::infrastructure::event_store::in_memory::InMemoryEventStore;
+    use crate::shared::infrastructure::intent_outbox::OutboxError;
+    use std::sync::{Arc, Mutex};
+
+    struct Counter;
+
+    impl Decider for Counter {
+        type State = u32;
+        type Command = u32;
+        type Event = u32;
+        type Intent = String;
+        type Error = &'static str;
+
+        fn initial_state() -> u32 {
+            0
+        }
+
+        fn evolve(state: u32, event: u32) -> u32 {
+            state + event
+        }
+
+        fn decide(state: &u32, command: u32) -> Decision<u32, String, &'static str> {
+            if *state + command > 10 {
+                return Decision::Rejected { reason: "overflow" };
+            }
+            Decision::Accepted {
+                events: vec![command],
+                intents: vec![format!("added {command}")],
+            }
+        }
+    }
+
+    type Dispatched = Vec<(String, i64, usize, Vec<String>)>;
+
+    #[derive(Clone, Default)]
+    struct RecordingDispatcher {
+        dispatched: Arc<Mutex<Dispatched>>,
+        fail: bool,
+    }
+
+    #[async_trait]
+    impl IntentDispatcher<String> for RecordingDispatcher {
+        type Error = OutboxError;
+
+        async fn dispatch(
+            &self,
+            stream_id: &str,
+            starting_version: i64,
+            events_len: usize,
+            intents: Vec<String>,
+        ) -> Result<(), OutboxError> {
+            if self.fail {
+                return Err(OutboxError::Backend("outbox offline".into()));
+            }
+            self.dispatched.lock().unwrap().push((
+                stream_id.to_string(),
+                starting_version,
+                events_len,
+                intents,
+            ));
+            Ok(())
+        }
+    }
+
+    const STREAM_ID: &str = "Counter-1";
+
+    #[tokio::test]
+    async fn it_should_append_events_and_dispatch_intents() {
+        let event_store = InMemoryEventStore::<u32>::new();
+        let dispatcher = RecordingDispatcher::default();
+        let handler =
+            EventSourcedHandler::<Counter, _, _>::new(event_store.clone(), dispatcher.clone());
+
+        handler.handle(STREAM_ID, 3).await.unwrap();
+        handler.clone().handle(STREAM_ID, 4).await.unwrap();
+
+        let stream = event_store.load(STREAM_ID).await.unwrap();
+        assert_eq!(stream.events, vec![3, 4]);
+        assert_eq!(
+            *dispatcher.dispatched.lock().unwrap(),
+            vec![
+                (STREAM_ID.to_string(), 0, 1, vec!["added 3".to_string()]),
+                (STREAM_ID.to_string(), 1, 1, vec!["added 4".to_string()]),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn it_should_publish_appended_events_to_the_event_bus() {
+        let event_store = InMemoryEventStore::<u32>::new();
+        let event_bus = InMemoryEventBus::<u32>::new(16);
+        let mut receiver = event_bus.subscribe();
+        let handler =
+            EventSourcedHandler::<Counter, _, _>::new(event_store, RecordingDispatcher::default())
+                .with_event_bus(Arc::new(event_bus));
+        handler.handle(STREAM_ID, 3).await.unwrap();
+
+        let _ = handler.handle(STREAM_ID, 9).await;
+        handler.handle(STREAM_ID, 4).await.unwrap();
+
+        let first = receiver.recv().await.unwrap();
+        let second = receiver.recv().await.unwrap();
+        assert_eq!(
+            (first.global_position, first.stream_version, first.event),
+            (0, 1, 3)
+        );
+        assert_eq!(
+            (second.global_position, second.stream_version, second.event),
+            (1, 2, 4)
+        );
+        assert!(receiver.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn it_should_reject_against_folded_state() {
+        let event_store = InMemoryEventStore::<u32>::new();
+        let handler = EventSourcedHandler::<Counter, _, _>::new(
+            event_store.clone(),
+            RecordingDispatcher::default(),
+        );
+        handler.handle(STREAM_ID, 8).await.unwrap();
+
+        let result = handler.handle(STREAM_ID, 3).await;
+
+        assert!(matches!(result, Err(EventSourcedError::Domain("overflow"))));
+        assert_eq!(result.unwrap_err().to_string(), "domain rejected: overflow");
+        assert_eq!(event_store.load(STREAM_ID).await.unwrap().events, vec![8]);
+    }
+
+    #[tokio::test]
+    async fn it_should_fail_when_event_store_is_offline() {
+        let event_store = InMemoryEventStore::<u32>::new();
+        event_store.toggle_offline();
+        let handler =
+            EventSourcedHandler::<Counter, _, _>::new(event_store, RecordingDispatcher::default());
+
+        let result = handler.handle(STREAM_ID, 1).await;
+
+        assert!(matches!(
+            result,
+            Err(EventSourcedError::VersionConflict(
+                EventStoreError::Backend(_)
+            ))
+        ));
+    }
+
+    #[tokio::test]
+    async fn it_should_surface_dispatch_failures() {
+        let handler = EventSourcedHandler::<Counter, _, _>::new(
+            InMemoryEventStore::<u32>::new(),
+            RecordingDispatcher {
+                fail: true,
+                ..Default::default()
+            },
+        );
+
+        let result = handler.handle(STREAM_ID, 1).await;
+
+        assert!(matches!(
+            result,
+            Err(EventSourcedError::Outbox(OutboxError::Backend(_)))
+        ));
+    }
+
+    #[tokio::test]
+    async fn no_intents_dispatcher_accepts_empty_batches() {
+        assert!(NoIntents.dispatch(STREAM_ID, 0, 1, vec![]).await.is_ok());
+    }
+
+    #[derive(Debug)]
+    #[allow(dead_code)]
+    enum Reason {
+        InvalidInterval,
+        CurrencyMismatch { current: String },
+        PeriodLocked(u32),
+    }
+
+    #[rstest::rstest]
+    #[case::mismatch(EventSourcedError::VersionConflict(EventStoreError::VersionMismatch { expected: 1, actual: 2 }), "version_mismatch")]
+    #[case::backend(EventSourcedError::VersionConflict(EventStoreError::Backend("down".into())), "event_store")]
+    #[case::outbox(EventSourcedError::Outbox(OutboxError::Backend("down".into())), "outbox")]
+    #[case::unit(EventSourcedError::Domain(Reason::InvalidInterval), "invalid_interval")]
+    #[case::named(EventSourcedError::Domain(Reason::CurrencyMismatch { current: "EUR".into() }), "currency_mismatch")]
+    #[case::tuple(EventSourcedError::Domain(Reason::PeriodLocked(1)), "period_locked")]
+    fn it_should_label_outcomes(
+        #[case] error: EventSourcedError<Reason, OutboxError>,
+        #[case] expected: &str,
+    ) {
+        assert_eq!(error.outcome(), expected);
+    }
+
+    #[test]
+    fn it_should_format_debug_output() {
+        let handler = EventSourcedHandler::<Counter, _, _>::new(
+            InMemoryEventStore::<u32>::new(),
+            RecordingDispatcher::default(),
+        );
+        assert_eq!(format!("{handler:?}"), "EventSourcedHandler { .. }");
+    }
+}
@@ -0,0 +1,165 @@
+// GDPR erasure. Forgetting a user deletes their data key, so every personal data field
+// encrypted by `CryptoShreddingEventStore` for them reads as forgotten from then on.
+// Events themselves are never rewritten.
+//
+// Read models hold what was projected before the key went. Each module holding personal
+// data in them registers a `ReadModelEraser`, which overwrites it the way a rebuild would
+// project it. The key goes first; when an eraser fails, the request fails and can be
+// repeated, since erasing twice changes nothing.
+
+use crate::shared::infrastructure::key_store::{KeyStore, KeyStoreError};
+use async_trait::async_trait;
+use std::sync::Arc;
+use thiserror::Error;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ForgetUser {
+    pub user_id: String,
+}
+
+#[derive(Debug, Error)]
+pub enum ForgetUserError {
+    #[error(transparent)]
+    KeyStore(#[from] KeyStoreError),
+
+    #[error("read model erasure failed: {0}")]
+    ReadModel(String),
+}
+
+/// Overwrites a user's personal data in the read models of one module.
+#[async_trait]
+pub trait ReadModelEraser: Send + Sync {
+    async fn erase(&self, user_id: &str) -> anyhow::Result<()>;
+}
+
+#[derive(Clone)]
+pub struct ForgetUserHandler<TKeyStore> {
+    key_store: TKeyStore,
+    erasers: Vec<Arc<dyn ReadModelEraser>>,
+}
+
+impl<TKeyStore> ForgetUserHandler<TKeyStore>
+where
+    TKeyStore: KeyStore,
+{
+    pub fn new(key_store: TKeyStore) -> Self {
+        Self {
+            key_store,
+            erasers: Vec::new(),
+        }
+    }
+
+    pub fn with_eraser(mut self, eraser: Arc<dyn ReadModelEraser>) -> Self {
+        self.erasers.push(eraser);
+        self
+    }
+
+    /// Returns the id of the user's deleted key, if they had one. Forgetting an unknown user
+    /// is not an error, and read models are erased either way.
+    pub async fn handle(&self, command: ForgetUser) -> Result<Option<String>, ForgetUserError> {
+        let key_id = self.key_store.forget_subject(&command.user_id).await?;
+        for eraser in &self.erasers {
+            eraser
+                .erase(&command.user_id)
+                .await
+                .map_err(|error| ForgetUserError::ReadModel(error.to_string()))?;
+        }
+        Ok(key_id)
+    }
+}
+
+#[cfg(test)]
+mod forget_user_handler_tests {
+    use super::*;
+    use crate::modules::time_entries::core::events::TimeEntryEvent;
+    use crate::shared::core::personal_data::PersonalData;
+    use crate::shared::infrastructure::event_store::EventStore;
+    use crate::shared::infrastructure::event_store::crypto_shredding::{
+        CryptoShreddingEventStore, FORGOTTEN,
+    };
+    use crate::shared::infrastructure::event_store::in_memory::InMemoryEventStore;
+    use crate::shared::infrastructure::key_store::in_memory::InMemoryKeyStore;
+    use crate::tests::fixtures::crypto::XorHexCipher;
+    use crate::tests::fixtures::events::time_entry_initiated_v1::make_time_entry_initiated_v1_event;
+    use rstest::rstest;
+    use tokio::sync::Mutex;
+
+    #[derive(Default)]
+    struct RecordingEraser {
+        erased: Mutex<Vec<String>>,
+        fails: bool,
+    }
+
+    #[async_trait]
+    impl ReadModelEraser for RecordingEraser {
+        async fn erase(&self, user_id: &str) -> anyhow::Result<()> {
+            if self.fails {
+                anyhow::bail!("projection store offline");
+            }
+            self.erased.lock().await.push(user_id.to_string());
+            Ok(())
+        }
+    }
+
+    fn forget(user_id: &str) -> ForgetUser {
+        ForgetUser {
+            user_id: user_id.to_string(),
+        }
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_render_the_users_history_unreadable() {
+        let key_store = InMemoryKeyStore::new();
+        let event_store = CryptoShreddingEventStore::new(
+            InMemoryEventStore::<TimeEntryEvent>::new(),
+            key_store.clone(),
+            XorHexCipher,
+        );
+        let initiated = TimeEntryEvent::TimeEntryInitiatedV1(make_time_entry_initiated_v1_event());
+        event_store
+            .append("TimeEntry-1", 0, &[initiated])
+            .await
+            .unwrap();
+        let key = key_store.key_for_subject("user-fixed-0001").await.unwrap();
+        let handler = ForgetUserHandler::new(key_store);
+
+        let forgotten = handler.handle(forget("user-fixed-0001")).await.unwrap();
+
+        assert_eq!(forgotten, Some(key.id));
+        let events = event_store.load("TimeEntry-1").await.unwrap().events;
+        assert_eq!(events[0].personal_data(), vec![FORGOTTEN; 2]);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_erase_the_read_models_of_known_and_unknown_users() {
+        let eraser = Arc::new(RecordingEraser::default());
+        let key_store = InMemoryKeyStore::new();
+        key_store.key_for_subject("u-1").await.unwrap();
+        let handler = ForgetUserHandler::new(key_store).with_eraser(eraser.clone());
+
+        handler.handle(forget("u-1")).await.unwrap();
+        let forgotten = handler.handle(forget("nobody")).await.unwrap();
+
+        assert_eq!(forgotten, None);
+        assert_eq!(*eraser.erased.lock().await, vec!["u-1", "nobody"]);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_delete_the_key_even_when_an_eraser_fails() {
+        let key_store = InMemoryKeyStore::new();
+        let key = key_store.key_for_subject("u-1").await.unwrap();
+        let handler =
+            ForgetUserHandler::new(key_store.clone()).with_eraser(Arc::new(RecordingEraser {
+                fails: true,
+                ..RecordingEraser::default()
+            }));
+
+        let result = handler.handle(forget("u-1")).await;
+
+        assert!(matches!(result, Err(ForgetUserError::ReadModel(_))));
+        assert_eq!(key_store.key_by_id(&key.id).await.unwrap(), None);
+    }
+}
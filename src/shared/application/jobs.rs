@@ -0,0 +1,505 @@
+// Runs persisted jobs. A job kind names a `JobHandler`; the runner claims due jobs from the
+// job store, runs up to `workers` of them at once and records each outcome. Failed runs are
+// retried with exponential backoff until the retry policy gives up. Jobs left running by an
+// instance that stopped are picked up again through `recover`, so handlers must be safe to
+// run twice.
+//
+// Recurring work is registered as a schedule: the runner queues a job of the scheduled kind
+// once the last one is at least `every` old and none is still queued or running. The job
+// table is the only state, so schedules survive restarts and instances sharing a table
+// queue one job between them, give or take a race on the first tick.
+
+use async_graphql::futures_util::future::join_all;
+use async_trait::async_trait;
+use serde_json::Value as Json;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::watch;
+
+use crate::shared::infrastructure::clock::{SharedClock, SystemClock};
+use crate::shared::infrastructure::job_store::{
+    Job, JobProgress, JobQuery, JobStatus, JobStore, JobStoreError,
+};
+
+/// Runs the jobs of one kind. The value returned becomes the job's `result`.
+#[async_trait]
+pub trait JobHandler: Send + Sync {
+    async fn run(&self, job: &Job, ctx: &JobContext<'_>) -> anyhow::Result<Json>;
+}
+
+/// What a handler can tell the runner while it works.
+pub struct JobContext<'a> {
+    job_id: &'a str,
+    store: &'a dyn JobStore,
+    clock: &'a SharedClock,
+}
+
+impl JobContext<'_> {
+    /// Records how far the job has come. Reporting also keeps the job from being taken for
+    /// stale, so long jobs should report at least once per `stale_after`.
+    pub async fn report_progress(
+        &self,
+        done: u64,
+        total: Option<u64>,
+    ) -> Result<(), JobStoreError> {
+        self.store
+            .report_progress(
+                self.job_id,
+                JobProgress { done, total },
+                self.clock.now().as_millis(),
+            )
+            .await
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Runs before a job fails for good, the first included.
+    pub max_attempts: u32,
+    /// Wait before the first retry; doubles for every retry after it.
+    pub backoff: Duration,
+    pub max_backoff: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 5,
+            backoff: Duration::from_secs(1),
+            max_backoff: Duration::from_secs(5 * 60),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// When to run a job again that failed its `attempts`th run; `None` once it has had all
+    /// its attempts.
+    pub fn retry_at(&self, attempts: u32, now: i64) -> Option<i64> {
+        if attempts >= self.max_attempts {
+            return None;
+        }
+        let factor = 2u32.saturating_pow(attempts.saturating_sub(1));
+        let backoff = self.backoff.saturating_mul(factor).min(self.max_backoff);
+        Some(now + backoff.as_millis() as i64)
+    }
+}
+
+/// A job kind queued on a fixed interval.
+#[derive(Debug, Clone)]
+struct Schedule {
+    kind: String,
+    payload: Json,
+    every: Duration,
+}
+
+pub struct JobRunner<TStore> {
+    store: TStore,
+    handlers: HashMap<String, Arc<dyn JobHandler>>,
+    schedules: Vec<Schedule>,
+    retry_policy: RetryPolicy,
+    workers: usize,
+    worker_updates: Option<watch::Receiver<usize>>,
+    stale_after: Duration,
+    clock: SharedClock,
+}
+
+impl<TStore> JobRunner<TStore>
+where
+    TStore: JobStore,
+{
+    pub fn new(store: TStore) -> Self {
+        Self {
+            store,
+            handlers: HashMap::new(),
+            schedules: Vec::new(),
+            retry_policy: RetryPolicy::default(),
+            workers: 4,
+            worker_updates: None,
+            stale_after: Duration::from_secs(10 * 60),
+            clock: Arc::new(SystemClock),
+        }
+    }
+
+    pub fn with_handler(mut self, kind: impl Into<String>, handler: Arc<dyn JobHandler>) -> Self {
+        self.handlers.insert(kind.into(), handler);
+        self
+    }
+
+    /// Queues a `kind` job with `payload` every `every`. The kind needs a handler as well.
+    pub fn with_schedule(
+        mut self,
+        kind: impl Into<String>,
+        payload: Json,
+        every: Duration,
+    ) -> Self {
+        self.schedules.push(Schedule {
+            kind: kind.into(),
+            payload,
+            every,
+        });
+        self
+    }
+
+    pub fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// How many jobs run at once.
+    pub fn with_workers(mut self, workers: usize) -> Self {
+        self.workers = workers.max(1);
+        self
+    }
+
+    /// Follows `updates` instead of `with_workers` from the next run on.
+    pub fn with_worker_updates(mut self, updates: watch::Receiver<usize>) -> Self {
+        self.worker_updates = Some(updates);
+        self
+    }
+
+    fn workers(&self) -> usize {
+        match &self.worker_updates {
+            Some(updates) => (*updates.borrow()).max(1),
+            None => self.workers,
+        }
+    }
+
+    /// How long a running job may go without an update before `recover` requeues it.
+    pub fn with_stale_after(mut self, stale_after: Duration) -> Self {
+        self.stale_after = stale_after;
+        self
+    }
+
+    pub fn with_clock(mut self, clock: SharedClock) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Requeues running jobs whose worker has gone quiet, typically because the process
+    /// running them restarted. Returns how many were requeued.
+    pub async fn recover(&self) -> Result<usize, JobStoreError> {
+        let now = self.clock.now().as_millis();
+        self.store
+            .requeue_stale(now - self.stale_after.as_millis() as i64, now)
+            .await
+    }
+
+    /// Queues the scheduled jobs that are due. Returns how many were queued.
+    pub async fn enqueue_scheduled(&self) -> Result<usize, JobStoreError> {
+        let now = self.clock.now().as_millis();
+        let mut queued = 0;
+        for schedule in &self.schedules {
+            let latest = self
+                .store
+                .list(&JobQuery {
+                    kind: Some(schedule.kind.clone()),
+                    status: None,
+                    limit: 1,
+                })
+                .await?;
+            let due = match latest.first() {
+                None => true,
+                Some(job) if matches!(job.status, JobStatus::Queued | JobStatus::Running) => false,
+                Some(job) => job.created_at + schedule.every.as_millis() as i64 <= now,
+            };
+            if due {
+                self.store
+                    .enqueue(Job::queued(&schedule.kind, schedule.payload.clone(), now))
+                    .await?;
+                queued += 1;
+            }
+        }
+        Ok(queued)
+    }
+
+    /// Queues the scheduled jobs that are due, then claims the jobs due now, up to one per
+    /// worker, and runs them to completion. Returns how many ran.
+    pub async fn run_due(&self) -> Result<usize, JobStoreError> {
+        self.enqueue_scheduled().await?;
+        let jobs = self
+            .store
+            .claim_due(self.clock.now().as_millis(), self.workers())
+            .await?;
+        let outcomes = join_all(jobs.iter().map(|job| self.run(job))).await;
+        outcomes.into_iter().collect::<Result<Vec<_>, _>>()?;
+        Ok(jobs.len())
+    }
+
+    async fn run(&self, job: &Job) -> Result<(), JobStoreError> {
+        let Some(handler) = self.handlers.get(&job.kind) else {
+            let error = format!("no handler for job kind {}", job.kind);
+            let now = self.clock.now().as_millis();
+            return self.store.fail(&job.job_id, &error, None, now).await;
+        };
+        let ctx = JobContext {
+            job_id: &job.job_id,
+            store: &self.store,
+            clock: &self.clock,
+        };
+        let outcome = handler.run(job, &ctx).await;
+        let now = self.clock.now().as_millis();
+        match outcome {
+            Ok(result) => self.store.succeed(&job.job_id, result, now).await,
+            Err(error) => {
+                let retry_at = self.retry_policy.retry_at(job.attempts, now);
+                if retry_at.is_none() {
+                    tracing::warn!(job_id = %job.job_id, kind = %job.kind, %error, "job failed");
+                }
+                self.store
+                    .fail(&job.job_id, &error.to_string(), retry_at, now)
+                    .await
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod jobs_tests {
+    use super::*;
+    use crate::shared::infrastructure::clock::FixedClock;
+    use crate::shared::infrastructure::job_store::in_memory::InMemoryJobStore;
+    use rstest::rstest;
+    use serde_json::json;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    /// Fails its first `failures` runs, then echoes the payload after reporting progress.
+    struct Flaky {
+        failures: u32,
+        runs: AtomicU32,
+    }
+
+    impl Flaky {
+        fn new(failures: u32) -> Arc<Self> {
+            Arc::new(Self {
+                failures,
+                runs: AtomicU32::new(0),
+            })
+        }
+    }
+
+    #[async_trait]
+    impl JobHandler for Flaky {
+        async fn run(&self, job: &Job, ctx: &JobContext<'_>) -> anyhow::Result<Json> {
+            if self.runs.fetch_add(1, Ordering::SeqCst) < self.failures {
+                anyhow::bail!("not yet");
+            }
+            ctx.report_progress(1, Some(1)).await?;
+            Ok(job.payload.clone())
+        }
+    }
+
+    fn runner(store: &InMemoryJobStore, clock: &FixedClock) -> JobRunner<InMemoryJobStore> {
+        JobRunner::new(store.clone())
+            .with_clock(Arc::new(clock.clone()))
+            .with_retry_policy(RetryPolicy {
+                max_attempts: 3,
+                backoff: Duration::from_millis(100),
+                max_backoff: Duration::from_millis(150),
+            })
+    }
+
+    async fn enqueue(store: &InMemoryJobStore, kind: &str) -> String {
+        let job = Job::queued(kind, json!({"n": 1}), 0);
+        store.enqueue(job.clone()).await.unwrap();
+        job.job_id
+    }
+
+    #[rstest]
+    #[case::first_retry(1, Some(1_100))]
+    #[case::doubled(2, Some(1_200))]
+    #[case::capped(3, Some(1_300))]
+    #[case::exhausted(4, None)]
+    fn it_should_back_off_exponentially_up_to_the_cap(
+        #[case] attempts: u32,
+        #[case] expected: Option<i64>,
+    ) {
+        let policy = RetryPolicy {
+            max_attempts: 4,
+            backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_millis(300),
+        };
+        assert_eq!(policy.retry_at(attempts, 1_000), expected);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_run_due_jobs_and_record_their_results() {
+        let (store, clock) = (InMemoryJobStore::new(), FixedClock::at(0));
+        let job_id = enqueue(&store, "echo").await;
+
+        let ran = runner(&store, &clock)
+            .with_handler("echo", Flaky::new(0))
+            .run_due()
+            .await
+            .unwrap();
+
+        assert_eq!(ran, 1);
+        let job = store.get(&job_id).await.unwrap().unwrap();
+        assert_eq!(job.status, JobStatus::Succeeded);
+        assert_eq!(job.result, Some(json!({"n": 1})));
+        assert_eq!(
+            job.progress,
+            Some(JobProgress {
+                done: 1,
+                total: Some(1)
+            })
+        );
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_retry_failed_runs_after_the_backoff() {
+        let (store, clock) = (InMemoryJobStore::new(), FixedClock::at(0));
+        let job_id = enqueue(&store, "flaky").await;
+        let runner = runner(&store, &clock).with_handler("flaky", Flaky::new(1));
+
+        runner.run_due().await.unwrap();
+        let job = store.get(&job_id).await.unwrap().unwrap();
+        assert_eq!(job.status, JobStatus::Queued);
+        assert_eq!(job.run_after, 100);
+        assert_eq!(job.last_error.as_deref(), Some("not yet"));
+        assert_eq!(runner.run_due().await.unwrap(), 0);
+
+        clock.set(100);
+        runner.run_due().await.unwrap();
+        let job = store.get(&job_id).await.unwrap().unwrap();
+        assert_eq!(job.status, JobStatus::Succeeded);
+        assert_eq!(job.attempts, 2);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_fail_jobs_for_good_after_the_last_attempt() {
+        let (store, clock) = (InMemoryJobStore::new(), FixedClock::at(0));
+        let job_id = enqueue(&store, "flaky").await;
+        let runner = runner(&store, &clock).with_handler("flaky", Flaky::new(u32::MAX));
+
+        for _ in 0..3 {
+            clock.advance(1_000);
+            runner.run_due().await.unwrap();
+        }
+
+        let job = store.get(&job_id).await.unwrap().unwrap();
+        assert_eq!(job.status, JobStatus::Failed);
+        assert_eq!(job.attempts, 3);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_fail_jobs_of_unknown_kinds_without_retrying() {
+        let (store, clock) = (InMemoryJobStore::new(), FixedClock::at(0));
+        let job_id = enqueue(&store, "unknown").await;
+
+        runner(&store, &clock).run_due().await.unwrap();
+
+        let job = store.get(&job_id).await.unwrap().unwrap();
+        assert_eq!(job.status, JobStatus::Failed);
+        assert_eq!(
+            job.last_error.as_deref(),
+            Some("no handler for job kind unknown")
+        );
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_run_at_most_one_job_per_worker_at_a_time() {
+        let (store, clock) = (InMemoryJobStore::new(), FixedClock::at(0));
+        for _ in 0..3 {
+            enqueue(&store, "echo").await;
+        }
+        let runner = runner(&store, &clock)
+            .with_handler("echo", Flaky::new(0))
+            .with_workers(2);
+
+        assert_eq!(runner.run_due().await.unwrap(), 2);
+        assert_eq!(runner.run_due().await.unwrap(), 1);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_follow_worker_updates() {
+        let (store, clock) = (InMemoryJobStore::new(), FixedClock::at(0));
+        for _ in 0..4 {
+            enqueue(&store, "echo").await;
+        }
+        let (updates, receiver) = watch::channel(1);
+        let runner = runner(&store, &clock)
+            .with_handler("echo", Flaky::new(0))
+            .with_worker_updates(receiver);
+
+        assert_eq!(runner.run_due().await.unwrap(), 1);
+        updates.send_replace(2);
+        assert_eq!(runner.run_due().await.unwrap(), 2);
+        updates.send_replace(0);
+        assert_eq!(runner.run_due().await.unwrap(), 1);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_recover_jobs_orphaned_by_a_restart() {
+        let (store, clock) = (InMemoryJobStore::new(), FixedClock::at(0));
+        let job_id = enqueue(&store, "echo").await;
+        store.claim_due(0, 1).await.unwrap();
+        let runner = runner(&store, &clock)
+            .with_handler("echo", Flaky::new(0))
+            .with_stale_after(Duration::from_secs(60));
+
+        clock.set(59_000);
+        assert_eq!(runner.recover().await.unwrap(), 0);
+        clock.set(61_000);
+        assert_eq!(runner.recover().await.unwrap(), 1);
+        runner.run_due().await.unwrap();
+
+        let job = store.get(&job_id).await.unwrap().unwrap();
+        assert_eq!(job.status, JobStatus::Succeeded);
+        assert_eq!(job.attempts, 2);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_queue_scheduled_jobs_once_the_last_one_is_old_enough() {
+        let (store, clock) = (InMemoryJobStore::new(), FixedClock::at(0));
+        let runner = runner(&store, &clock)
+            .with_handler("tick", Flaky::new(0))
+            .with_schedule("tick", json!({"n": 2}), Duration::from_secs(60));
+        let ticks = || async {
+            store
+                .list(&JobQuery {
+                    kind: Some("tick".to_string()),
+                    status: None,
+                    limit: 10,
+                })
+                .await
+                .unwrap()
+        };
+
+        assert_eq!(runner.run_due().await.unwrap(), 1);
+        clock.set(59_999);
+        assert_eq!(runner.run_due().await.unwrap(), 0);
+        clock.set(60_000);
+        assert_eq!(runner.run_due().await.unwrap(), 1);
+
+        let jobs = ticks().await;
+        assert_eq!(jobs.len(), 2);
+        assert!(jobs.iter().all(|job| job.status == JobStatus::Succeeded));
+        assert_eq!(jobs[0].result, Some(json!({"n": 2})));
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_not_queue_a_scheduled_job_while_one_is_pending() {
+        let (store, clock) = (InMemoryJobStore::new(), FixedClock::at(0));
+        let runner = runner(&store, &clock)
+            .with_handler("tick", Flaky::new(1))
+            .with_schedule("tick", json!({}), Duration::from_millis(10));
+
+        // The first run fails and waits for its retry at 100.
+        runner.run_due().await.unwrap();
+        clock.set(50);
+        assert_eq!(runner.enqueue_scheduled().await.unwrap(), 0);
+        clock.set(100);
+        assert_eq!(runner.run_due().await.unwrap(), 1);
+        assert_eq!(runner.enqueue_scheduled().await.unwrap(), 1);
+    }
+}
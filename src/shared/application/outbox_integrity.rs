@@ -0,0 +1,251 @@
+// Cross-checks an event store against its outbox, for confirming after an incident that no
+// intent was dropped. Events and outbox rows are matched on `(stream_id, stream_version)`:
+// every event whose decision emitted an intent must have a first row (`intent_no` 0) of that
+// intent's type, and every row must belong to such an event. Further intents of the same
+// decision are only checked for belonging to it.
+
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::shared::infrastructure::event_store::StoredEvent;
+use crate::shared::infrastructure::intent_outbox::OutboxRow;
+
+/// An event whose intent never reached the outbox.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct MissingRow {
+    pub stream_id: String,
+    pub stream_version: i64,
+    pub expected_type: String,
+}
+
+/// An outbox row no event accounts for.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct OrphanRow {
+    pub stream_id: String,
+    pub stream_version: i64,
+    pub event_type: String,
+    /// The intent type the event at that version emits; `None` when there is no such
+    /// event or it emits none.
+    pub expected_type: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct IntegrityReport {
+    pub events_checked: usize,
+    pub rows_checked: usize,
+    pub missing_rows: Vec<MissingRow>,
+    pub orphan_rows: Vec<OrphanRow>,
+}
+
+impl IntegrityReport {
+    pub fn is_consistent(&self) -> bool {
+        self.missing_rows.is_empty() && self.orphan_rows.is_empty()
+    }
+}
+
+/// Checks `rows` against `events`. `expected_intent` is the domain's rule for which events
+/// carry an intent: given the event before it in the same stream (if any) and the event, the
+/// outbox `event_type` the decision enqueued for it. Both lists may be in any order.
+pub fn check_outbox_integrity<TEvent>(
+    events: &[StoredEvent<TEvent>],
+    rows: &[OutboxRow],
+    expected_intent: impl Fn(Option<&TEvent>, &TEvent) -> Option<&'static str>,
+) -> IntegrityReport {
+    let mut streams: BTreeMap<&str, Vec<&StoredEvent<TEvent>>> = BTreeMap::new();
+    for stored in events {
+        streams.entry(&stored.stream_id).or_default().push(stored);
+    }
+    let mut expected: BTreeMap<(&str, i64), &'static str> = BTreeMap::new();
+    for (stream_id, mut stream) in streams {
+        stream.sort_by_key(|stored| stored.stream_version);
+        let mut previous = None;
+        for stored in stream {
+            if let Some(intent_type) = expected_intent(previous, &stored.event) {
+                expected.insert((stream_id, stored.stream_version), intent_type);
+            }
+            previous = Some(&stored.event);
+        }
+    }
+
+    let mut report = IntegrityReport {
+        events_checked: events.len(),
+        rows_checked: rows.len(),
+        ..IntegrityReport::default()
+    };
+    let mut matched = BTreeSet::new();
+    for row in rows {
+        let key = (row.stream_id.as_str(), row.stream_version);
+        let expected_type = expected.get(&key).copied();
+        if row.intent_no > 0 && expected_type.is_some() {
+            continue;
+        }
+        // A second first row for the same event is an orphan too; the outbox should refuse it.
+        if expected_type == Some(row.event_type.as_str()) && matched.insert(key) {
+            continue;
+        }
+        report.orphan_rows.push(OrphanRow {
+            stream_id: row.stream_id.clone(),
+            stream_version: row.stream_version,
+            event_type: row.event_type.clone(),
+            expected_type: expected_type.map(str::to_string),
+        });
+    }
+    for ((stream_id, stream_version), expected_type) in expected {
+        if !matched.contains(&(stream_id, stream_version)) {
+            report.missing_rows.push(MissingRow {
+                stream_id: stream_id.to_string(),
+                stream_version,
+                expected_type: expected_type.to_string(),
+            });
+        }
+    }
+    report
+        .orphan_rows
+        .sort_by(|a, b| (&a.stream_id, a.stream_version).cmp(&(&b.stream_id, b.stream_version)));
+    report
+}
+
+#[cfg(test)]
+mod outbox_integrity_tests {
+    use super::*;
+    use crate::shared::infrastructure::intent_outbox::OutboxStatus;
+    use rstest::rstest;
+
+    /// `notify` events carry a `Notified` intent; `close` does too, but only right after `open`.
+    fn expected_intent(previous: Option<&&str>, event: &&str) -> Option<&'static str> {
+        match (previous, *event) {
+            (_, "notify") => Some("Notified"),
+            (Some(&"open"), "close") => Some("Closed"),
+            _ => None,
+        }
+    }
+
+    fn event(
+        stream_id: &str,
+        stream_version: i64,
+        event: &'static str,
+    ) -> StoredEvent<&'static str> {
+        StoredEvent {
+            global_position: 0,
+            stream_id: stream_id.to_string(),
+            stream_version,
+            event,
+        }
+    }
+
+    fn row(stream_id: &str, stream_version: i64, event_type: &str) -> OutboxRow {
+        OutboxRow {
+            topic: "t".to_string(),
+            event_type: event_type.to_string(),
+            event_version: 1,
+            stream_id: stream_id.to_string(),
+            stream_version,
+            intent_no: 0,
+            occurred_at: 0,
+            payload: serde_json::Value::Null,
+            content_encoding: None,
+            status: OutboxStatus::Published,
+            attempts: 1,
+            last_error: None,
+            published_at: Some(0),
+        }
+    }
+
+    fn history() -> Vec<StoredEvent<&'static str>> {
+        // Out of order on purpose: checks must not rely on load order.
+        vec![
+            event("S-2", 2, "close"),
+            event("S-1", 1, "notify"),
+            event("S-1", 2, "open"),
+            event("S-2", 1, "open"),
+            event("S-1", 3, "close"),
+            event("S-1", 4, "close"),
+        ]
+    }
+
+    #[rstest]
+    fn it_should_accept_an_outbox_matching_every_intent() {
+        let rows = [
+            row("S-2", 2, "Closed"),
+            row("S-1", 1, "Notified"),
+            row("S-1", 3, "Closed"),
+        ];
+
+        let report = check_outbox_integrity(&history(), &rows, expected_intent);
+
+        assert!(report.is_consistent(), "{report:?}");
+        assert_eq!((report.events_checked, report.rows_checked), (6, 3));
+    }
+
+    #[rstest]
+    fn it_should_accept_further_intents_of_a_decision() {
+        let further = OutboxRow {
+            intent_no: 1,
+            ..row("S-1", 3, "Notified")
+        };
+        let rows = [
+            row("S-2", 2, "Closed"),
+            row("S-1", 1, "Notified"),
+            row("S-1", 3, "Closed"),
+            further,
+        ];
+
+        let report = check_outbox_integrity(&history(), &rows, expected_intent);
+
+        assert!(report.is_consistent(), "{report:?}");
+    }
+
+    #[rstest]
+    fn it_should_report_events_whose_intent_has_no_row() {
+        let rows = [row("S-1", 1, "Notified")];
+
+        let report = check_outbox_integrity(&history(), &rows, expected_intent);
+
+        assert_eq!(
+            report.missing_rows,
+            vec![
+                MissingRow {
+                    stream_id: "S-1".to_string(),
+                    stream_version: 3,
+                    expected_type: "Closed".to_string(),
+                },
+                MissingRow {
+                    stream_id: "S-2".to_string(),
+                    stream_version: 2,
+                    expected_type: "Closed".to_string(),
+                },
+            ]
+        );
+        assert!(report.orphan_rows.is_empty());
+    }
+
+    #[rstest]
+    #[case::no_event(row("S-9", 1, "Notified"), None, 0)]
+    #[case::no_intent(row("S-1", 4, "Closed"), None, 0)]
+    #[case::duplicate(row("S-1", 3, "Closed"), Some("Closed"), 0)]
+    #[case::other_type(row("S-2", 2, "Notified"), Some("Closed"), 1)]
+    fn it_should_report_rows_no_event_accounts_for(
+        #[case] orphan: OutboxRow,
+        #[case] expected_type: Option<&str>,
+        #[case] missing: usize,
+    ) {
+        let mut rows = vec![row("S-1", 1, "Notified"), row("S-1", 3, "Closed")];
+        if orphan.stream_id != "S-2" {
+            rows.push(row("S-2", 2, "Closed"));
+        }
+        rows.push(orphan.clone());
+
+        let report = check_outbox_integrity(&history(), &rows, expected_intent);
+
+        assert_eq!(
+            report.orphan_rows,
+            vec![OrphanRow {
+                stream_id: orphan.stream_id,
+                stream_version: orphan.stream_version,
+                event_type: orphan.event_type,
+                expected_type: expected_type.map(str::to_string),
+            }]
+        );
+        assert_eq!(report.missing_rows.len(), missing);
+    }
+}
@@ -0,0 +1,129 @@
+// Server-side time for commands.
+//
+// Handlers stamp commands with the time they were recorded at, read from an injected
+// `Clock`, instead of trusting whatever the inbound adapter or client supplied. Instants a
+// client does supply (when work started or ended) are checked against that clock so that
+// a device with a wrong clock or a typo in the year cannot record absurd values.
+
+use chrono::TimeDelta;
+use thiserror::Error;
+
+use crate::shared::core::primitives::Timestamp;
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum ClockSkewError {
+    #[error("{at} is too far in the future (server time {now})")]
+    TooFarAhead { at: Timestamp, now: Timestamp },
+
+    #[error("{at} is too far in the past (server time {now})")]
+    TooFarBehind { at: Timestamp, now: Timestamp },
+}
+
+/// How far client-supplied instants may lie from server time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SkewWindow {
+    /// Allowance for clients whose clock runs ahead of the server.
+    pub max_ahead: TimeDelta,
+    /// How far back time may be registered; `None` allows any past instant.
+    pub max_behind: Option<TimeDelta>,
+}
+
+impl Default for SkewWindow {
+    fn default() -> Self {
+        Self {
+            max_ahead: TimeDelta::minutes(5),
+            max_behind: None,
+        }
+    }
+}
+
+impl SkewWindow {
+    pub fn check(&self, now: Timestamp, at: i64) -> Result<(), ClockSkewError> {
+        let (at, offset) = (Timestamp::from_millis(at), at - now.as_millis());
+        if offset > self.max_ahead.num_milliseconds() {
+            return Err(ClockSkewError::TooFarAhead { at, now });
+        }
+        if let Some(max_behind) = self.max_behind
+            && -offset > max_behind.num_milliseconds()
+        {
+            return Err(ClockSkewError::TooFarBehind { at, now });
+        }
+        Ok(())
+    }
+}
+
+/// A command whose record time the handler assigns.
+pub trait StampedCommand {
+    /// Records `now` as the time the command was handled.
+    fn stamp(&mut self, now: Timestamp);
+
+    /// Instants supplied by the client, checked against the skew window.
+    fn client_instants(&self) -> Vec<i64> {
+        vec![]
+    }
+}
+
+#[cfg(test)]
+mod server_time_tests {
+    use super::*;
+    use rstest::rstest;
+
+    const NOW: i64 = 1_700_000_000_000;
+    const MINUTE: i64 = 60_000;
+
+    fn window() -> SkewWindow {
+        SkewWindow {
+            max_ahead: TimeDelta::minutes(5),
+            max_behind: Some(TimeDelta::days(1)),
+        }
+    }
+
+    #[rstest]
+    #[case::now(NOW)]
+    #[case::slightly_ahead(NOW + 5 * MINUTE)]
+    #[case::a_day_back(NOW - 24 * 60 * MINUTE)]
+    fn it_should_accept_instants_within_the_window(#[case] at: i64) {
+        assert_eq!(window().check(Timestamp::from_millis(NOW), at), Ok(()));
+    }
+
+    #[rstest]
+    fn it_should_reject_instants_too_far_ahead() {
+        let now = Timestamp::from_millis(NOW);
+
+        assert_eq!(
+            window().check(now, NOW + 5 * MINUTE + 1),
+            Err(ClockSkewError::TooFarAhead {
+                at: Timestamp::from_millis(NOW + 5 * MINUTE + 1),
+                now,
+            })
+        );
+    }
+
+    #[rstest]
+    fn it_should_reject_instants_too_far_behind_only_when_bounded() {
+        let now = Timestamp::from_millis(NOW);
+        let at = NOW - 24 * 60 * MINUTE - 1;
+
+        assert_eq!(
+            window().check(now, at),
+            Err(ClockSkewError::TooFarBehind {
+                at: Timestamp::from_millis(at),
+                now,
+            })
+        );
+        assert_eq!(SkewWindow::default().check(now, 0), Ok(()));
+    }
+
+    #[rstest]
+    fn it_should_describe_the_offending_instant() {
+        let error = ClockSkewError::TooFarAhead {
+            at: Timestamp::from_millis(0),
+            now: Timestamp::from_millis(0),
+        };
+
+        assert_eq!(
+            error.to_string(),
+            "1970-01-01T00:00:00.000Z is too far in the future (server time 1970-01-01T00:00:00.000Z)"
+        );
+    }
+}
@@ -0,0 +1,387 @@
+// Service level indicators of the write path, and how fast they burn the error budget.
+//
+// Handlers of the commands that register time record every run: whether it failed on the
+// service's side and how long it took. A command the domain rejected was still served, so only
+// event store and outbox failures count against availability. Runs are kept in minute buckets
+// for the longest burn window, from which each window's success ratio, p99 latency and burn
+// rates are derived. A burn rate of 1 spends the error budget exactly over the SLO period;
+// alerting rules compare the short and long window against thresholds, such as 14.4 for both
+// to page on a budget gone in two days of a 30 day period.
+
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
+const MINUTE_MS: i64 = 60 * 1000;
+
+/// Upper bounds of the latency buckets p99 is read from.
+pub const LATENCY_BUCKETS_MS: [u64; 11] =
+    [5, 10, 25, 50, 100, 250, 500, 1_000, 2_500, 5_000, 10_000];
+
+/// The windows burn rates are rendered for, by label.
+pub const BURN_WINDOWS: [(&str, i64); 2] = [("5m", 5 * MINUTE_MS), ("1h", 60 * MINUTE_MS)];
+
+/// Outcomes of a run, as `EventSourcedError::outcome` names them, that are the service's
+/// fault.
+const SERVER_FAILURES: [&str; 2] = ["event_store", "outbox"];
+
+/// Whether a run with `outcome` counts against availability.
+pub fn is_server_failure(outcome: &str) -> bool {
+    SERVER_FAILURES.contains(&outcome)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SloTargets {
+    /// Share of commands that must not fail on the service's side.
+    pub availability: f64,
+    /// Share of commands that must finish within `latency_threshold_ms`.
+    pub latency: f64,
+    pub latency_threshold_ms: u64,
+}
+
+impl Default for SloTargets {
+    /// 99.9% available, 99% within half a second.
+    fn default() -> Self {
+        Self {
+            availability: 0.999,
+            latency: 0.99,
+            latency_threshold_ms: 500,
+        }
+    }
+}
+
+/// The runs of one use case in one minute, or summed over a window.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Runs {
+    pub total: u64,
+    pub failed: u64,
+    /// Runs slower than the latency threshold.
+    pub slow: u64,
+    /// Runs per `LATENCY_BUCKETS_MS` bucket, and above the last one.
+    latencies: [u64; LATENCY_BUCKETS_MS.len() + 1],
+}
+
+impl Runs {
+    fn add(&mut self, other: &Runs) {
+        self.total += other.total;
+        self.failed += other.failed;
+        self.slow += other.slow;
+        for (sum, count) in self.latencies.iter_mut().zip(other.latencies) {
+            *sum += count;
+        }
+    }
+
+    /// Share of runs that did not fail; 1 without runs.
+    pub fn availability(&self) -> f64 {
+        ratio(self.total - self.failed, self.total, 1.0)
+    }
+
+    /// The upper bound of the bucket the 99th percentile falls in; `None` without runs or
+    /// when it lies above the last bucket.
+    pub fn p99_ms(&self) -> Option<u64> {
+        let rank = (self.total as f64 * 0.99).ceil() as u64;
+        let mut seen = 0;
+        for (bound, count) in LATENCY_BUCKETS_MS.iter().zip(self.latencies) {
+            seen += count;
+            if self.total > 0 && seen >= rank {
+                return Some(*bound);
+            }
+        }
+        None
+    }
+
+    /// How many times faster than allowed the availability budget is spent.
+    pub fn availability_burn_rate(&self, targets: &SloTargets) -> f64 {
+        ratio(self.failed, self.total, 0.0) / (1.0 - targets.availability)
+    }
+
+    /// How many times faster than allowed the latency budget is spent.
+    pub fn latency_burn_rate(&self, targets: &SloTargets) -> f64 {
+        ratio(self.slow, self.total, 0.0) / (1.0 - targets.latency)
+    }
+}
+
+fn ratio(part: u64, total: u64, empty: f64) -> f64 {
+    if total == 0 {
+        empty
+    } else {
+        part as f64 / total as f64
+    }
+}
+
+/// Runs per use case and minute.
+type Minutes = BTreeMap<String, BTreeMap<i64, Runs>>;
+
+/// Recent runs of the write path's commands; clones record into the same buckets.
+#[derive(Debug, Clone, Default)]
+pub struct WriteSlo {
+    targets: SloTargets,
+    minutes: Arc<Mutex<Minutes>>,
+}
+
+impl WriteSlo {
+    pub fn new(targets: SloTargets) -> Self {
+        Self {
+            targets,
+            minutes: Arc::default(),
+        }
+    }
+
+    pub fn targets(&self) -> SloTargets {
+        self.targets
+    }
+
+    /// Records a run of `use_case` that ended at `now`, dropping the minutes no window
+    /// reaches anymore.
+    pub fn record(&self, use_case: &str, failed: bool, latency_ms: u64, now: i64) {
+        let minute = now.div_euclid(MINUTE_MS);
+        let bucket = LATENCY_BUCKETS_MS
+            .iter()
+            .position(|bound| latency_ms <= *bound)
+            .unwrap_or(LATENCY_BUCKETS_MS.len());
+        let mut minutes = self.minutes.lock().unwrap();
+        let buckets = minutes.entry(use_case.to_string()).or_default();
+        let runs = buckets.entry(minute).or_default();
+        runs.total += 1;
+        runs.failed += u64::from(failed);
+        runs.slow += u64::from(latency_ms > self.targets.latency_threshold_ms);
+        runs.latencies[bucket] += 1;
+
+        let oldest = minute - longest_window_ms() / MINUTE_MS;
+        *buckets = buckets.split_off(&(oldest + 1));
+    }
+
+    /// The runs of `use_case` in the `window_ms` up to `now`, whole minutes.
+    pub fn window(&self, use_case: &str, window_ms: i64, now: i64) -> Runs {
+        let oldest = now.div_euclid(MINUTE_MS) - window_ms / MINUTE_MS;
+        let minutes = self.minutes.lock().unwrap();
+        let mut window = Runs::default();
+        for runs in minutes
+            .get(use_case)
+            .into_iter()
+            .flat_map(|buckets| buckets.range(oldest + 1..).map(|(_, runs)| runs))
+        {
+            window.add(runs);
+        }
+        window
+    }
+
+    /// The targets, and per use case and burn window the availability, p99 latency and burn
+    /// rates, in the Prometheus text format.
+    pub fn render(&self, now: i64) -> String {
+        let use_cases: Vec<String> = self.minutes.lock().unwrap().keys().cloned().collect();
+        let windows: Vec<(String, &str, Runs)> = use_cases
+            .iter()
+            .flat_map(|use_case| {
+                BURN_WINDOWS.iter().map(move |(label, window_ms)| {
+                    (
+                        use_case.clone(),
+                        *label,
+                        self.window(use_case, *window_ms, now),
+                    )
+                })
+            })
+            .collect();
+        let targets = &self.targets;
+
+        let mut out = String::new();
+        out.push_str("# HELP slo_target Objective of the write path, as a ratio.\n");
+        out.push_str("# TYPE slo_target gauge\n");
+        let _ = writeln!(
+            out,
+            "slo_target{{slo=\"availability\"}} {}",
+            targets.availability
+        );
+        let _ = writeln!(out, "slo_target{{slo=\"latency\"}} {}", targets.latency);
+        out.push_str(
+            "# HELP slo_latency_threshold_seconds Latency the latency objective allows.\n",
+        );
+        out.push_str("# TYPE slo_latency_threshold_seconds gauge\n");
+        let _ = writeln!(
+            out,
+            "slo_latency_threshold_seconds {}",
+            targets.latency_threshold_ms as f64 / 1_000.0
+        );
+        out.push_str("# HELP slo_availability_ratio Share of write commands that did not fail.\n");
+        out.push_str("# TYPE slo_availability_ratio gauge\n");
+        for (use_case, window, runs) in &windows {
+            let _ = writeln!(
+                out,
+                "slo_availability_ratio{{use_case=\"{use_case}\",window=\"{window}\"}} {}",
+                runs.availability()
+            );
+        }
+        out.push_str("# HELP slo_latency_p99_seconds 99th percentile of write command latency.\n");
+        out.push_str("# TYPE slo_latency_p99_seconds gauge\n");
+        for (use_case, window, runs) in windows.iter().filter(|(_, _, runs)| runs.total > 0) {
+            let p99 = runs
+                .p99_ms()
+                .map_or("+Inf".to_string(), |ms| (ms as f64 / 1_000.0).to_string());
+            let _ = writeln!(
+                out,
+                "slo_latency_p99_seconds{{use_case=\"{use_case}\",window=\"{window}\"}} {p99}"
+            );
+        }
+        out.push_str("# HELP slo_burn_rate Rate the error budget is spent at; 1 spends it over the SLO period.\n");
+        out.push_str("# TYPE slo_burn_rate gauge\n");
+        for (use_case, window, runs) in &windows {
+            for (slo, burn_rate) in [
+                ("availability", runs.availability_burn_rate(targets)),
+                ("latency", runs.latency_burn_rate(targets)),
+            ] {
+                let _ = writeln!(
+                    out,
+                    "slo_burn_rate{{use_case=\"{use_case}\",slo=\"{slo}\",window=\"{window}\"}} {burn_rate}"
+                );
+            }
+        }
+        out
+    }
+}
+
+/// Runs `handled` and, with `slo` configured, records it under `use_case`: as failed when
+/// `outcome_of` names the error a server failure, and as taking as long as it ran.
+pub async fn observe<T, E>(
+    slo: Option<&WriteSlo>,
+    use_case: &str,
+    outcome_of: fn(&E) -> String,
+    handled: impl Future<Output = Result<T, E>>,
+) -> Result<T, E> {
+    let Some(slo) = slo else {
+        return handled.await;
+    };
+    let started = Instant::now();
+    let result = handled.await;
+    slo.record(
+        use_case,
+        result
+            .as_ref()
+            .is_err_and(|error| is_server_failure(&outcome_of(error))),
+        started.elapsed().as_millis() as u64,
+        chrono::Utc::now().timestamp_millis(),
+    );
+    result
+}
+
+fn longest_window_ms() -> i64 {
+    BURN_WINDOWS
+        .iter()
+        .map(|(_, window_ms)| *window_ms)
+        .max()
+        .unwrap_or(MINUTE_MS)
+}
+
+#[cfg(test)]
+mod slo_tests {
+    use super::*;
+    use rstest::rstest;
+
+    const NOW: i64 = 1_000 * MINUTE_MS;
+
+    fn slo() -> WriteSlo {
+        WriteSlo::new(SloTargets {
+            availability: 0.99,
+            latency: 0.9,
+            latency_threshold_ms: 100,
+        })
+    }
+
+    #[rstest]
+    #[case::event_store("event_store", true)]
+    #[case::outbox("outbox", true)]
+    #[case::conflict("version_mismatch", false)]
+    #[case::rejected("invalid_interval", false)]
+    fn it_should_count_only_the_services_failures(#[case] outcome: &str, #[case] failed: bool) {
+        assert_eq!(is_server_failure(outcome), failed);
+    }
+
+    #[rstest]
+    fn it_should_derive_the_slis_of_a_window() {
+        let slo = slo();
+        for latency_ms in 1..=98 {
+            slo.record("set_started_at", false, latency_ms, NOW);
+        }
+        slo.record("set_started_at", true, 200, NOW);
+        slo.record("set_started_at", false, 3_000, NOW);
+
+        let runs = slo.window("set_started_at", 5 * MINUTE_MS, NOW);
+
+        assert_eq!(runs.total, 100);
+        assert_eq!(runs.availability(), 0.99);
+        assert_eq!(runs.p99_ms(), Some(250));
+        assert_eq!(runs.slow, 2);
+        assert!((runs.availability_burn_rate(&slo.targets()) - 1.0).abs() < 1e-9);
+        assert!((runs.latency_burn_rate(&slo.targets()) - 0.2).abs() < 1e-9);
+    }
+
+    #[rstest]
+    #[case::within_the_window(4 * MINUTE_MS, 1)]
+    #[case::past_the_window(5 * MINUTE_MS, 0)]
+    fn it_should_count_whole_minutes_of_the_window(#[case] ago: i64, #[case] total: u64) {
+        let slo = slo();
+        slo.record("set_ended_at", false, 10, NOW - ago);
+
+        assert_eq!(slo.window("set_ended_at", 5 * MINUTE_MS, NOW).total, total);
+    }
+
+    #[rstest]
+    fn it_should_forget_runs_past_the_longest_window() {
+        let slo = slo();
+        slo.record("set_ended_at", true, 10, NOW - 2 * 60 * MINUTE_MS);
+        slo.record("set_ended_at", false, 10, NOW);
+
+        let runs = slo.window("set_ended_at", 24 * 60 * MINUTE_MS, NOW);
+
+        assert_eq!((runs.total, runs.failed), (1, 0));
+    }
+
+    #[rstest]
+    fn it_should_report_full_availability_without_runs() {
+        let runs = slo().window("set_started_at", 5 * MINUTE_MS, NOW);
+
+        assert_eq!(runs.availability(), 1.0);
+        assert_eq!(runs.p99_ms(), None);
+        assert_eq!(runs.availability_burn_rate(&slo().targets()), 0.0);
+    }
+
+    #[rstest]
+    fn it_should_render_the_prometheus_text_format() {
+        let slo = WriteSlo::new(SloTargets {
+            availability: 0.75,
+            latency: 0.5,
+            latency_threshold_ms: 100,
+        });
+        slo.record("set_started_at", true, 20, NOW);
+        slo.record("set_started_at", false, 20_000, NOW - 30 * MINUTE_MS);
+
+        assert_eq!(
+            slo.render(NOW),
+            [
+                "# HELP slo_target Objective of the write path, as a ratio.",
+                "# TYPE slo_target gauge",
+                r#"slo_target{slo="availability"} 0.75"#,
+                r#"slo_target{slo="latency"} 0.5"#,
+                "# HELP slo_latency_threshold_seconds Latency the latency objective allows.",
+                "# TYPE slo_latency_threshold_seconds gauge",
+                "slo_latency_threshold_seconds 0.1",
+                "# HELP slo_availability_ratio Share of write commands that did not fail.",
+                "# TYPE slo_availability_ratio gauge",
+                r#"slo_availability_ratio{use_case="set_started_at",window="5m"} 0"#,
+                r#"slo_availability_ratio{use_case="set_started_at",window="1h"} 0.5"#,
+                "# HELP slo_latency_p99_seconds 99th percentile of write command latency.",
+                "# TYPE slo_latency_p99_seconds gauge",
+                r#"slo_latency_p99_seconds{use_case="set_started_at",window="5m"} 0.025"#,
+                r#"slo_latency_p99_seconds{use_case="set_started_at",window="1h"} +Inf"#,
+                "# HELP slo_burn_rate Rate the error budget is spent at; 1 spends it over the SLO period.",
+                "# TYPE slo_burn_rate gauge",
+                r#"slo_burn_rate{use_case="set_started_at",slo="availability",window="5m"} 4"#,
+                r#"slo_burn_rate{use_case="set_started_at",slo="latency",window="5m"} 0"#,
+                r#"slo_burn_rate{use_case="set_started_at",slo="availability",window="1h"} 2"#,
+                r#"slo_burn_rate{use_case="set_started_at",slo="latency",window="1h"} 1"#,
+                "",
+            ]
+            .join("\n")
+        );
+    }
+}
@@ -0,0 +1,293 @@
+// Alerts when processing stalls.
+//
+// Probes read how far each projector's watermark is behind the event log and how many rows
+// each outbox topic still has to relay. Falling behind is normal under load, so the watchdog
+// only alerts once a reading stays over its threshold without making progress (the watermark
+// did not advance, nothing was published) for longer than `stall_after_ms`. It alerts once
+// per stall, and again with the all-clear when processing resumes or catches up.
+
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::marker::PhantomData;
+use std::sync::Arc;
+
+use crate::shared::infrastructure::event_store::{EventLog, SharedEventLog};
+use crate::shared::infrastructure::intent_outbox::OutboxRelaySource;
+use crate::shared::infrastructure::notifier::{Alert, AlertStatus};
+use crate::shared::infrastructure::projection_store::ProjectionStore;
+
+/// How far one thing is behind, as a probe read it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Reading {
+    /// What was read, such as `projector:list_time_entries` or `outbox:time-entries.v1`.
+    pub subject: String,
+    /// Events or rows left to process.
+    pub lag: u64,
+    /// The most `lag` may be before a lack of progress counts as a stall.
+    pub max_lag: u64,
+    /// Grows whenever processing makes progress: a checkpoint, a published row count.
+    pub position: u64,
+}
+
+#[async_trait]
+pub trait Probe: Send + Sync {
+    async fn read(&self) -> anyhow::Result<Reading>;
+}
+
+/// Reads a projector's checkpoint against the head of the log it projects.
+pub struct ProjectorProbe<TEvent, TStore, P>
+where
+    TEvent: Clone + Send + Sync + 'static,
+{
+    name: String,
+    event_store: SharedEventLog<TEvent>,
+    store: TStore,
+    max_lag: u64,
+    _state: PhantomData<fn() -> P>,
+}
+
+impl<TEvent, TStore, P> ProjectorProbe<TEvent, TStore, P>
+where
+    TEvent: Clone + Send + Sync + 'static,
+{
+    pub fn new(
+        name: impl Into<String>,
+        event_store: impl EventLog<TEvent> + 'static,
+        store: TStore,
+        max_lag: u64,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            event_store: Arc::new(event_store),
+            store,
+            max_lag,
+            _state: PhantomData,
+        }
+    }
+}
+
+#[async_trait]
+impl<TEvent, TStore, P> Probe for ProjectorProbe<TEvent, TStore, P>
+where
+    TEvent: Clone + Send + Sync + 'static,
+    TStore: ProjectionStore<P>,
+    P: Clone + Send + Sync + 'static,
+{
+    async fn read(&self) -> anyhow::Result<Reading> {
+        let head = self.event_store.head().await?;
+        let checkpoint = self.store.checkpoint().await?;
+        Ok(Reading {
+            subject: format!("projector:{}", self.name),
+            lag: head.saturating_sub(checkpoint),
+            max_lag: self.max_lag,
+            position: checkpoint,
+        })
+    }
+}
+
+/// Reads the rows of an outbox topic its relay has yet to publish.
+pub struct OutboxProbe<TOutbox> {
+    topic: String,
+    outbox: TOutbox,
+    max_backlog: u64,
+}
+
+impl<TOutbox> OutboxProbe<TOutbox> {
+    pub fn new(topic: impl Into<String>, outbox: TOutbox, max_backlog: u64) -> Self {
+        Self {
+            topic: topic.into(),
+            outbox,
+            max_backlog,
+        }
+    }
+}
+
+#[async_trait]
+impl<TOutbox: OutboxRelaySource> Probe for OutboxProbe<TOutbox> {
+    async fn read(&self) -> anyhow::Result<Reading> {
+        let backlog = self.outbox.backlog(&self.topic).await?;
+        Ok(Reading {
+            subject: format!("outbox:{}", self.topic),
+            lag: backlog.unpublished,
+            max_lag: self.max_backlog,
+            position: backlog.published,
+        })
+    }
+}
+
+struct Tracked {
+    position: u64,
+    /// When the subject was last healthy or made progress.
+    progressed_at: i64,
+    alerted: bool,
+}
+
+/// What the watchdog remembers of each subject between readings.
+pub struct Watchdog {
+    stall_after_ms: i64,
+    tracked: HashMap<String, Tracked>,
+}
+
+impl Watchdog {
+    pub fn new(stall_after_ms: i64) -> Self {
+        Self {
+            stall_after_ms,
+            tracked: HashMap::new(),
+        }
+    }
+
+    /// Takes in `readings` made at `now`; answers the alerts they raise or resolve.
+    pub fn observe(&mut self, readings: &[Reading], now: i64) -> Vec<Alert> {
+        let mut alerts = Vec::new();
+        for reading in readings {
+            let tracked = self
+                .tracked
+                .entry(reading.subject.clone())
+                .or_insert(Tracked {
+                    position: reading.position,
+                    progressed_at: now,
+                    alerted: false,
+                });
+            let healthy = reading.lag <= reading.max_lag || reading.position != tracked.position;
+            tracked.position = reading.position;
+            if healthy {
+                tracked.progressed_at = now;
+                if tracked.alerted {
+                    tracked.alerted = false;
+                    alerts.push(Alert {
+                        key: reading.subject.clone(),
+                        status: AlertStatus::Resolved,
+                        summary: format!(
+                            "{} is processing again, {} behind",
+                            reading.subject, reading.lag
+                        ),
+                        raised_at: now,
+                    });
+                }
+            } else if !tracked.alerted && now - tracked.progressed_at >= self.stall_after_ms {
+                tracked.alerted = true;
+                alerts.push(Alert {
+                    key: reading.subject.clone(),
+                    status: AlertStatus::Firing,
+                    summary: format!(
+                        "{} stalled: {} behind (threshold {}) without progress for {}s",
+                        reading.subject,
+                        reading.lag,
+                        reading.max_lag,
+                        (now - tracked.progressed_at) / 1000
+                    ),
+                    raised_at: now,
+                });
+            }
+        }
+        alerts
+    }
+}
+
+#[cfg(test)]
+mod watchdog_tests {
+    use super::*;
+    use crate::shared::infrastructure::event_store::EventStore;
+    use crate::shared::infrastructure::event_store::in_memory::InMemoryEventStore;
+    use crate::shared::infrastructure::intent_outbox::in_memory::InMemoryDomainOutbox;
+    use crate::shared::infrastructure::intent_outbox::{DomainOutbox, OutboxRow};
+    use crate::shared::infrastructure::projection_store::in_memory::InMemoryProjectionStore;
+    use rstest::rstest;
+
+    fn reading(lag: u64, position: u64) -> Reading {
+        Reading {
+            subject: "outbox:a".to_string(),
+            lag,
+            max_lag: 2,
+            position,
+        }
+    }
+
+    fn statuses(alerts: &[Alert]) -> Vec<AlertStatus> {
+        alerts.iter().map(|alert| alert.status).collect()
+    }
+
+    #[rstest]
+    fn it_should_alert_once_when_a_backlog_stops_moving_and_resolve_when_it_moves() {
+        let mut watchdog = Watchdog::new(1_000);
+
+        assert!(watchdog.observe(&[reading(5, 10)], 0).is_empty());
+        assert!(watchdog.observe(&[reading(5, 10)], 999).is_empty());
+        let fired = watchdog.observe(&[reading(5, 10)], 1_000);
+        assert!(watchdog.observe(&[reading(6, 10)], 5_000).is_empty());
+        let resolved = watchdog.observe(&[reading(6, 11)], 6_000);
+
+        assert_eq!(statuses(&fired), vec![AlertStatus::Firing]);
+        assert_eq!(
+            fired[0].summary,
+            "outbox:a stalled: 5 behind (threshold 2) without progress for 1s"
+        );
+        assert_eq!(statuses(&resolved), vec![AlertStatus::Resolved]);
+        assert_eq!(resolved[0].key, "outbox:a");
+    }
+
+    #[rstest]
+    #[case::within_threshold(&[(2, 10), (2, 10), (2, 10)])]
+    #[case::progressing(&[(5, 10), (5, 11), (5, 12)])]
+    fn it_should_not_alert_while_within_threshold_or_progressing(#[case] readings: &[(u64, u64)]) {
+        let mut watchdog = Watchdog::new(1_000);
+
+        for (i, (lag, position)) in readings.iter().enumerate() {
+            let alerts = watchdog.observe(&[reading(*lag, *position)], i as i64 * 1_000);
+            assert!(alerts.is_empty(), "{alerts:?}");
+        }
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_read_projector_lag_and_outbox_backlog() {
+        let event_store = InMemoryEventStore::<u32>::new();
+        event_store.append("s-1", 0, &[1, 2, 3]).await.unwrap();
+        let store = InMemoryProjectionStore::<u32>::new();
+        store.save(0, 1).await.unwrap();
+        let outbox = InMemoryDomainOutbox::new();
+        outbox
+            .enqueue(OutboxRow {
+                topic: "a".to_string(),
+                event_type: "e".to_string(),
+                event_version: 1,
+                stream_id: "s-1".to_string(),
+                stream_version: 1,
+                intent_no: 0,
+                occurred_at: 0,
+                payload: serde_json::Value::Null,
+                content_encoding: None,
+                status: Default::default(),
+                attempts: 0,
+                last_error: None,
+                published_at: None,
+            })
+            .await
+            .unwrap();
+
+        let projector = ProjectorProbe::new("p", event_store, store, 0)
+            .read()
+            .await
+            .unwrap();
+        let backlog = OutboxProbe::new("a", outbox, 0).read().await.unwrap();
+
+        assert_eq!(
+            projector,
+            Reading {
+                subject: "projector:p".to_string(),
+                lag: 2,
+                max_lag: 0,
+                position: 1,
+            }
+        );
+        assert_eq!(
+            backlog,
+            Reading {
+                subject: "outbox:a".to_string(),
+                lag: 1,
+                max_lag: 0,
+                position: 0,
+            }
+        );
+    }
+}
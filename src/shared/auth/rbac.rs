@@ -0,0 +1,232 @@
+// Role-based access control. Pure policy: adapters build a `Principal` from the request
+// and ask it before acting on another user's data.
+//
+// | permission          | employee | manager | admin |
+// |---------------------|----------|---------|-------|
+// | register for self   | yes      | yes     | yes   |
+// | register for others | no       | yes     | yes   |
+// | view self           | yes      | yes     | yes   |
+// | view others         | no       | yes     | yes   |
+// | approve self        | no       | no      | no    |
+// | approve others      | no       | yes     | yes   |
+// | administer          | no       | no      | yes   |
+//
+// A `Scope` narrows a principal further. People always act with the full scope; API keys
+// for machine clients carry a narrower one:
+//
+// | api key scope  | acts as  | scope         |
+// |----------------|----------|---------------|
+// | register-only  | employee | register only |
+// | read-only      | manager  | read only     |
+// | admin          | admin    | full          |
+
+use std::str::FromStr;
+use thiserror::Error;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Role {
+    #[default]
+    Employee,
+    Manager,
+    Admin,
+}
+
+#[derive(Debug, Clone, Error, PartialEq, Eq)]
+#[error("unknown role: {0}")]
+pub struct UnknownRole(pub String);
+
+impl FromStr for Role {
+    type Err = UnknownRole;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "employee" => Ok(Role::Employee),
+            "manager" => Ok(Role::Manager),
+            "admin" => Ok(Role::Admin),
+            _ => Err(UnknownRole(value.to_string())),
+        }
+    }
+}
+
+/// Which kinds of operation a principal may perform at all, regardless of role.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Scope {
+    #[default]
+    Full,
+    RegisterOnly,
+    ReadOnly,
+}
+
+impl Scope {
+    fn allows_writes(self) -> bool {
+        matches!(self, Scope::Full | Scope::RegisterOnly)
+    }
+
+    fn allows_reads(self) -> bool {
+        matches!(self, Scope::Full | Scope::ReadOnly)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ApiKeyScope {
+    RegisterOnly,
+    ReadOnly,
+    Admin,
+}
+
+impl ApiKeyScope {
+    pub fn role(self) -> Role {
+        match self {
+            ApiKeyScope::RegisterOnly => Role::Employee,
+            ApiKeyScope::ReadOnly => Role::Manager,
+            ApiKeyScope::Admin => Role::Admin,
+        }
+    }
+
+    pub fn scope(self) -> Scope {
+        match self {
+            ApiKeyScope::RegisterOnly => Scope::RegisterOnly,
+            ApiKeyScope::ReadOnly => Scope::ReadOnly,
+            ApiKeyScope::Admin => Scope::Full,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Principal {
+    pub user_id: String,
+    pub role: Role,
+    pub scope: Scope,
+}
+
+impl Principal {
+    pub fn new(user_id: impl Into<String>, role: Role) -> Self {
+        Self {
+            user_id: user_id.into(),
+            role,
+            scope: Scope::Full,
+        }
+    }
+
+    pub fn with_scope(mut self, scope: Scope) -> Self {
+        self.scope = scope;
+        self
+    }
+
+    fn is_self(&self, user_id: &str) -> bool {
+        self.user_id == user_id
+    }
+
+    fn is_manager_or_admin(&self) -> bool {
+        matches!(self.role, Role::Manager | Role::Admin)
+    }
+
+    /// Changes to shared, tenant-wide data such as tags.
+    pub fn can_write(&self) -> bool {
+        self.scope.allows_writes()
+    }
+
+    pub fn can_register_for(&self, user_id: &str) -> bool {
+        self.can_write() && (self.is_self(user_id) || self.is_manager_or_admin())
+    }
+
+    pub fn can_view_user(&self, user_id: &str) -> bool {
+        self.scope.allows_reads() && (self.is_self(user_id) || self.is_manager_or_admin())
+    }
+
+    /// Admin-only operations such as reading the API audit trail.
+    pub fn can_administer(&self) -> bool {
+        self.scope == Scope::Full && self.role == Role::Admin
+    }
+
+    /// Nobody approves their own time.
+    pub fn can_approve(&self, user_id: &str) -> bool {
+        self.scope == Scope::Full && !self.is_self(user_id) && self.is_manager_or_admin()
+    }
+}
+
+#[cfg(test)]
+mod rbac_tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case::employee("employee", Role::Employee)]
+    #[case::manager("Manager", Role::Manager)]
+    #[case::admin(" ADMIN ", Role::Admin)]
+    fn it_should_parse_role_claims(#[case] claim: &str, #[case] expected: Role) {
+        assert_eq!(claim.parse::<Role>(), Ok(expected));
+    }
+
+    #[test]
+    fn it_should_reject_unknown_role_claims() {
+        let error = "owner".parse::<Role>().unwrap_err();
+        assert_eq!(error.to_string(), "unknown role: owner");
+        assert_eq!(Role::default(), Role::Employee);
+    }
+
+    #[rstest]
+    #[case::employee_self(Role::Employee, "u-1", true, true, false, false)]
+    #[case::employee_other(Role::Employee, "u-2", false, false, false, false)]
+    #[case::manager_self(Role::Manager, "u-1", true, true, false, false)]
+    #[case::manager_other(Role::Manager, "u-2", true, true, true, false)]
+    #[case::admin_self(Role::Admin, "u-1", true, true, false, true)]
+    #[case::admin_other(Role::Admin, "u-2", true, true, true, true)]
+    fn it_should_apply_the_policy_table(
+        #[case] role: Role,
+        #[case] target: &str,
+        #[case] register: bool,
+        #[case] view: bool,
+        #[case] approve: bool,
+        #[case] administer: bool,
+    ) {
+        let principal = Principal::new("u-1", role);
+        assert_eq!(principal.can_register_for(target), register);
+        assert_eq!(principal.can_view_user(target), view);
+        assert_eq!(principal.can_approve(target), approve);
+        assert_eq!(principal.can_administer(), administer);
+    }
+
+    #[test]
+    fn it_should_not_let_read_only_admins_administer() {
+        let principal = Principal::new("u-1", Role::Admin).with_scope(Scope::ReadOnly);
+        assert!(!principal.can_administer());
+    }
+
+    #[rstest]
+    #[case::register_only_self(ApiKeyScope::RegisterOnly, "u-1", true, false, false, true)]
+    #[case::register_only_other(ApiKeyScope::RegisterOnly, "u-2", false, false, false, true)]
+    #[case::read_only_self(ApiKeyScope::ReadOnly, "u-1", false, true, false, false)]
+    #[case::read_only_other(ApiKeyScope::ReadOnly, "u-2", false, true, false, false)]
+    #[case::admin_other(ApiKeyScope::Admin, "u-2", true, true, true, true)]
+    fn it_should_narrow_api_keys_to_their_scope(
+        #[case] key_scope: ApiKeyScope,
+        #[case] target: &str,
+        #[case] register: bool,
+        #[case] view: bool,
+        #[case] approve: bool,
+        #[case] write: bool,
+    ) {
+        let principal = Principal::new("u-1", key_scope.role()).with_scope(key_scope.scope());
+        assert_eq!(principal.can_register_for(target), register);
+        assert_eq!(principal.can_view_user(target), view);
+        assert_eq!(principal.can_approve(target), approve);
+        assert_eq!(principal.can_write(), write);
+    }
+
+    #[rstest]
+    #[case::register_only(ApiKeyScope::RegisterOnly, r#""register-only""#)]
+    #[case::read_only(ApiKeyScope::ReadOnly, r#""read-only""#)]
+    #[case::admin(ApiKeyScope::Admin, r#""admin""#)]
+    fn it_should_serialize_api_key_scopes_in_kebab_case(
+        #[case] key_scope: ApiKeyScope,
+        #[case] json: &str,
+    ) {
+        assert_eq!(serde_json::to_string(&key_scope).unwrap(), json);
+        assert_eq!(
+            serde_json::from_str::<ApiKeyScope>(json).unwrap(),
+            key_scope
+        );
+    }
+}
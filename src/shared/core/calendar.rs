@@ -0,0 +1,38 @@
+use chrono::NaiveDate;
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum DayOffKind {
+    /// Public holiday, applies to everyone.
+    Holiday { name: String },
+    /// Approved leave of a single user.
+    Absence { reason: String },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct DayOff {
+    pub date: NaiveDate,
+    #[serde(flatten)]
+    pub kind: DayOffKind,
+}
+
+#[cfg(test)]
+mod day_off_tests {
+    use super::*;
+
+    #[test]
+    fn it_should_serialize_flat_with_a_kind_tag() {
+        let day_off = DayOff {
+            date: NaiveDate::from_ymd_opt(2026, 12, 25).unwrap(),
+            kind: DayOffKind::Holiday {
+                name: "Christmas".to_string(),
+            },
+        };
+        let json = serde_json::to_value(&day_off).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({ "date": "2026-12-25", "kind": "holiday", "name": "Christmas" })
+        );
+        assert_eq!(serde_json::from_value::<DayOff>(json).unwrap(), day_off);
+    }
+}
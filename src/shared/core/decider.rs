@@ -0,0 +1,87 @@
+// The decider pattern shared by all event-sourced use cases.
+// A decider is pure: it folds past events into state and decides which new events and
+// intents a command produces. Loading, appending and dispatching live in the shell.
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Decision<TEvent, TIntent, TReason> {
+    Accepted {
+        events: Vec<TEvent>,
+        intents: Vec<TIntent>,
+    },
+    Rejected {
+        reason: TReason,
+    },
+}
+
+pub trait Decider {
+    type State;
+    type Command;
+    type Event;
+    type Intent;
+    type Error;
+
+    fn initial_state() -> Self::State;
+
+    fn evolve(state: Self::State, event: Self::Event) -> Self::State;
+
+    fn decide(
+        state: &Self::State,
+        command: Self::Command,
+    ) -> Decision<Self::Event, Self::Intent, Self::Error>;
+
+    fn fold(events: impl IntoIterator<Item = Self::Event>) -> Self::State {
+        events.into_iter().fold(Self::initial_state(), Self::evolve)
+    }
+}
+
+#[cfg(test)]
+mod decider_tests {
+    use super::*;
+
+    struct Counter;
+
+    impl Decider for Counter {
+        type State = u32;
+        type Command = u32;
+        type Event = u32;
+        type Intent = ();
+        type Error = &'static str;
+
+        fn initial_state() -> u32 {
+            0
+        }
+
+        fn evolve(state: u32, event: u32) -> u32 {
+            state + event
+        }
+
+        fn decide(state: &u32, command: u32) -> Decision<u32, (), &'static str> {
+            if *state + command > 10 {
+                return Decision::Rejected { reason: "overflow" };
+            }
+            Decision::Accepted {
+                events: vec![command],
+                intents: vec![],
+            }
+        }
+    }
+
+    #[test]
+    fn it_should_fold_events_from_the_initial_state() {
+        assert_eq!(Counter::fold(vec![]), 0);
+        assert_eq!(Counter::fold(vec![1, 2, 3]), 6);
+    }
+
+    #[test]
+    fn it_should_decide_against_folded_state() {
+        let state = Counter::fold(vec![4, 4]);
+        assert!(matches!(
+            Counter::decide(&state, 2),
+            Decision::Accepted { events, .. } if events == vec![2]
+        ));
+        assert!(matches!(
+            Counter::decide(&state, 3),
+            Decision::Rejected { reason: "overflow" }
+        ));
+    }
+}
@@ -0,0 +1,143 @@
+// What every event enum knows about the versions it stores.
+//
+// Implemented with `#[derive(DomainEvent)]` (see the `time_entries_macros` crate) rather than
+// by hand: the catalog and each event's type and version are read off the variant names, so a
+// new version only needs its variant. Versions no longer in the enum are read through an
+// upcaster that rewrites their stored JSON into a current version before deserializing.
+
+use serde::de::DeserializeOwned;
+use serde_json::Value as Json;
+
+pub use time_entries_macros::DomainEvent;
+
+/// One stored version: its serde tag, and the event type and version the tag names.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EventRegistration {
+    pub tag: &'static str,
+    pub event_type: &'static str,
+    pub event_version: i32,
+}
+
+pub trait DomainEvent: DeserializeOwned {
+    /// Every version the enum stores, in declaration order.
+    const CATALOG: &'static [EventRegistration];
+
+    fn event_type(&self) -> &'static str;
+
+    fn event_version(&self) -> i32;
+
+    /// Rewrites a payload stored under a retired tag into a current version. The derive
+    /// overrides it with the enum's declared upcasters.
+    fn upcast(stored: Json) -> Json {
+        stored
+    }
+
+    /// Deserializes a stored payload, upcasting it first.
+    fn from_stored(stored: Json) -> serde_json::Result<Self> {
+        serde_json::from_value(Self::upcast(stored))
+    }
+
+    fn registration(tag: &str) -> Option<&'static EventRegistration> {
+        Self::CATALOG
+            .iter()
+            .find(|registration| registration.tag == tag)
+    }
+
+    /// The newest version of `event_type` in the catalog.
+    fn latest_version(event_type: &str) -> Option<i32> {
+        Self::CATALOG
+            .iter()
+            .filter(|registration| registration.event_type == event_type)
+            .map(|registration| registration.event_version)
+            .max()
+    }
+}
+
+#[cfg(test)]
+mod domain_event_tests {
+    use super::*;
+    use rstest::rstest;
+    use serde::{Deserialize, Serialize};
+    use serde_json::json;
+
+    #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+    struct NoteAddedV2 {
+        text: String,
+        author: String,
+    }
+
+    #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+    struct NoteRemovedV1 {
+        id: u32,
+    }
+
+    /// `NoteAddedV1` had no author.
+    fn note_added_v1(mut stored: Json) -> Json {
+        stored["type"] = json!("NoteAddedV2");
+        stored["author"] = json!("unknown");
+        stored
+    }
+
+    #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, DomainEvent)]
+    #[serde(tag = "type")]
+    #[domain_event(upcast("NoteAddedV1", note_added_v1))]
+    enum NoteEvent {
+        NoteAddedV2(NoteAddedV2),
+        NoteRemovedV1(NoteRemovedV1),
+    }
+
+    #[rstest]
+    fn it_should_catalog_every_variant_by_type_and_version() {
+        assert_eq!(
+            NoteEvent::CATALOG,
+            &[
+                EventRegistration {
+                    tag: "NoteAddedV2",
+                    event_type: "NoteAdded",
+                    event_version: 2,
+                },
+                EventRegistration {
+                    tag: "NoteRemovedV1",
+                    event_type: "NoteRemoved",
+                    event_version: 1,
+                },
+            ]
+        );
+        assert_eq!(NoteEvent::latest_version("NoteAdded"), Some(2));
+        assert_eq!(NoteEvent::latest_version("NoteEdited"), None);
+        assert_eq!(
+            NoteEvent::registration("NoteRemovedV1").map(|r| r.event_type),
+            Some("NoteRemoved")
+        );
+    }
+
+    #[rstest]
+    fn it_should_name_an_event_and_wrap_its_payload() {
+        let event = NoteEvent::from(NoteRemovedV1 { id: 3 });
+
+        assert_eq!(event, NoteEvent::NoteRemovedV1(NoteRemovedV1 { id: 3 }));
+        assert_eq!(
+            (event.event_type(), event.event_version()),
+            ("NoteRemoved", 1)
+        );
+    }
+
+    #[rstest]
+    #[case::retired(
+        json!({ "type": "NoteAddedV1", "text": "hi" }),
+        NoteAddedV2 { text: "hi".into(), author: "unknown".into() }
+    )]
+    #[case::current(
+        json!({ "type": "NoteAddedV2", "text": "hi", "author": "ann" }),
+        NoteAddedV2 { text: "hi".into(), author: "ann".into() }
+    )]
+    fn it_should_upcast_retired_versions_on_the_way_in(
+        #[case] stored: Json,
+        #[case] expected: NoteAddedV2,
+    ) {
+        assert_eq!(
+            NoteEvent::from_stored(stored).unwrap(),
+            NoteEvent::NoteAddedV2(expected)
+        );
+    }
+}
@@ -0,0 +1,63 @@
+// Hash partitioning of stream ids, used to split one projector over several workers.
+//
+// Every event of a stream lands in the same partition, so per-stream ordering is preserved.
+// The hash (FNV-1a) is fixed rather than `DefaultHasher`, whose output may change between
+// Rust releases: partition assignments are persisted alongside each partition's checkpoint.
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+pub struct Partition {
+    pub index: usize,
+    pub count: usize,
+}
+
+impl Partition {
+    /// All `count` partitions, in index order. At least one.
+    pub fn all(count: usize) -> Vec<Partition> {
+        let count = count.max(1);
+        (0..count).map(|index| Partition { index, count }).collect()
+    }
+
+    pub fn owns(&self, stream_id: &str) -> bool {
+        partition_of(stream_id, self.count) == self.index
+    }
+}
+
+pub fn partition_of(stream_id: &str, count: usize) -> usize {
+    const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0100_0000_01b3;
+    let hash = stream_id.bytes().fold(OFFSET_BASIS, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(PRIME)
+    });
+    (hash % count.max(1) as u64) as usize
+}
+
+#[cfg(test)]
+mod partitioning_tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case::empty("", 4, 1)]
+    #[case::time_entry("TimeEntry-te-1", 4, 2)]
+    #[case::single_partition("TimeEntry-te-1", 1, 0)]
+    #[case::zero_is_one("TimeEntry-te-1", 0, 0)]
+    fn it_should_assign_stable_partitions(
+        #[case] stream_id: &str,
+        #[case] count: usize,
+        #[case] expected: usize,
+    ) {
+        assert_eq!(partition_of(stream_id, count), expected);
+    }
+
+    #[test]
+    fn it_should_give_every_stream_exactly_one_owner() {
+        let partitions = Partition::all(3);
+        assert_eq!(partitions.len(), 3);
+        for i in 0..50 {
+            let stream_id = format!("TimeEntry-te-{i}");
+            let owners = partitions.iter().filter(|p| p.owns(&stream_id)).count();
+            assert_eq!(owners, 1);
+        }
+        assert_eq!(Partition::all(0), vec![Partition { index: 0, count: 1 }]);
+    }
+}
@@ -0,0 +1,67 @@
+// Marks the fields of an event that hold personal data.
+// Adapters use it to encrypt, redact or erase those fields without knowing the event shape.
+
+pub trait PersonalData: Sized {
+    /// The person this value's personal data belongs to, when the value names them. Values of a
+    /// stream that name nobody belong to whoever its first value names.
+    fn data_subject(&self) -> Option<String> {
+        None
+    }
+
+    /// Rebuilds the value with `f` applied to every personal data field.
+    fn map_personal_data(self, f: &mut dyn FnMut(String) -> String) -> Self;
+
+    /// The personal data fields, in the order `map_personal_data` visits them.
+    fn personal_data(&self) -> Vec<String>
+    where
+        Self: Clone,
+    {
+        let mut values = Vec::new();
+        self.clone().map_personal_data(&mut |value| {
+            values.push(value.clone());
+            value
+        });
+        values
+    }
+}
+
+#[cfg(test)]
+mod personal_data_tests {
+    use super::*;
+
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    struct Note {
+        author: String,
+        body: String,
+        pages: u32,
+    }
+
+    impl PersonalData for Note {
+        fn map_personal_data(self, f: &mut dyn FnMut(String) -> String) -> Self {
+            Self {
+                author: f(self.author),
+                body: f(self.body),
+                ..self
+            }
+        }
+    }
+
+    #[test]
+    fn it_should_list_and_map_personal_data_fields() {
+        let note = Note {
+            author: "alice".to_string(),
+            body: "hello".to_string(),
+            pages: 2,
+        };
+
+        assert_eq!(note.personal_data(), vec!["alice", "hello"]);
+        assert_eq!(
+            note.map_personal_data(&mut |value| value.to_uppercase()),
+            Note {
+                author: "ALICE".to_string(),
+                body: "HELLO".to_string(),
+                pages: 2,
+            }
+        );
+    }
+}
@@ -1,2 +1,373 @@
 // Bounded context-wide primitive types shared across all modules.
 // Add types here only when two or more modules need the same type.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+use thiserror::Error;
+use uuid::{Uuid, Version};
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum PrimitiveError {
+    #[error("{0} must not be blank")]
+    Blank(&'static str),
+
+    #[error("{0} must be a UUID v7")]
+    NotUuidV7(&'static str),
+
+    #[error("stream id must look like <category>-<id>")]
+    MalformedStreamId,
+
+    #[error("timestamp must be RFC 3339 or milliseconds since the epoch")]
+    MalformedTimestamp,
+}
+
+/// Opaque string ids. Parsing only rejects blanks: ids minted before v7 was required, and
+/// system actors such as `system:timer-auto-stop`, are still valid; `parse_v7` is the strict
+/// form for ids clients create.
+macro_rules! string_id {
+    ($(#[$doc:meta])* $name:ident, $label:literal) => {
+        $(#[$doc])*
+        #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+        #[serde(transparent)]
+        pub struct $name(String);
+
+        impl $name {
+            pub fn new_v7() -> Self {
+                Self(Uuid::now_v7().to_string())
+            }
+
+            pub fn parse_v7(value: &str) -> Result<Self, PrimitiveError> {
+                Uuid::parse_str(value)
+                    .ok()
+                    .filter(|uuid| uuid.get_version() == Some(Version::SortRand))
+                    .map(|_| Self(value.to_string()))
+                    .ok_or(PrimitiveError::NotUuidV7($label))
+            }
+
+            pub fn as_str(&self) -> &str {
+                &self.0
+            }
+
+            pub fn into_inner(self) -> String {
+                self.0
+            }
+        }
+
+        impl FromStr for $name {
+            type Err = PrimitiveError;
+
+            fn from_str(value: &str) -> Result<Self, PrimitiveError> {
+                if value.trim().is_empty() {
+                    return Err(PrimitiveError::Blank($label));
+                }
+                Ok(Self(value.to_string()))
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str(&self.0)
+            }
+        }
+
+        impl From<String> for $name {
+            fn from(value: String) -> Self {
+                Self(value)
+            }
+        }
+
+        impl From<&str> for $name {
+            fn from(value: &str) -> Self {
+                Self(value.to_string())
+            }
+        }
+
+        impl From<$name> for String {
+            fn from(value: $name) -> Self {
+                value.0
+            }
+        }
+
+        impl AsRef<str> for $name {
+            fn as_ref(&self) -> &str {
+                &self.0
+            }
+        }
+
+        impl PartialEq<str> for $name {
+            fn eq(&self, other: &str) -> bool {
+                self.0 == other
+            }
+        }
+
+        impl PartialEq<&str> for $name {
+            fn eq(&self, other: &&str) -> bool {
+                self.0 == *other
+            }
+        }
+
+        impl PartialEq<String> for $name {
+            fn eq(&self, other: &String) -> bool {
+                &self.0 == other
+            }
+        }
+    };
+}
+
+string_id!(TimeEntryId, "time entry id");
+string_id!(
+    /// The person an entry belongs to, or who acted on it.
+    UserId,
+    "user id"
+);
+
+/// An event stream, `<category>-<id>`, e.g. `TimeEntry-0190…`.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct StreamId(String);
+
+impl StreamId {
+    pub fn new(category: &str, id: impl fmt::Display) -> Self {
+        Self(format!("{category}-{id}"))
+    }
+
+    pub fn time_entry(time_entry_id: &TimeEntryId) -> Self {
+        Self::new("TimeEntry", time_entry_id)
+    }
+
+    /// A new time entry stream and the id it is for.
+    pub fn new_time_entry() -> (Self, TimeEntryId) {
+        let time_entry_id = TimeEntryId::new_v7();
+        (Self::time_entry(&time_entry_id), time_entry_id)
+    }
+
+    pub fn category(&self) -> &str {
+        self.0.split_once('-').map_or("", |(category, _)| category)
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl FromStr for StreamId {
+    type Err = PrimitiveError;
+
+    fn from_str(value: &str) -> Result<Self, PrimitiveError> {
+        match value.split_once('-') {
+            Some((category, id)) if !category.is_empty() && !id.is_empty() => {
+                Ok(Self(value.to_string()))
+            }
+            _ => Err(PrimitiveError::MalformedStreamId),
+        }
+    }
+}
+
+impl fmt::Display for StreamId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl AsRef<str> for StreamId {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+/// Milliseconds since the Unix epoch, UTC; the wire form of every `*_at` field.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+#[serde(transparent)]
+pub struct Timestamp(i64);
+
+impl Timestamp {
+    pub const fn from_millis(millis: i64) -> Self {
+        Self(millis)
+    }
+
+    pub fn now() -> Self {
+        Self(Utc::now().timestamp_millis())
+    }
+
+    pub const fn as_millis(self) -> i64 {
+        self.0
+    }
+}
+
+impl From<i64> for Timestamp {
+    fn from(millis: i64) -> Self {
+        Self(millis)
+    }
+}
+
+impl From<Timestamp> for i64 {
+    fn from(timestamp: Timestamp) -> Self {
+        timestamp.0
+    }
+}
+
+/// RFC 3339 with millisecond precision; out-of-range values print as raw milliseconds.
+impl fmt::Display for Timestamp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match DateTime::<Utc>::from_timestamp_millis(self.0) {
+            Some(at) => write!(f, "{}", at.format("%Y-%m-%dT%H:%M:%S%.3fZ")),
+            None => write!(f, "{}", self.0),
+        }
+    }
+}
+
+/// Accepts RFC 3339 or plain milliseconds, so `Display` output and wire values both parse.
+impl FromStr for Timestamp {
+    type Err = PrimitiveError;
+
+    fn from_str(value: &str) -> Result<Self, PrimitiveError> {
+        if let Ok(millis) = value.parse::<i64>() {
+            return Ok(Self(millis));
+        }
+        DateTime::parse_from_rfc3339(value)
+            .map(|at| Self(at.timestamp_millis()))
+            .map_err(|_| PrimitiveError::MalformedTimestamp)
+    }
+}
+
+/// Stream version encoded in a projection row's `last_event_id` (`{stream_id}:{stream_version}`).
+pub fn last_event_version(last_event_id: Option<&str>) -> Option<i64> {
+    last_event_id
+        .and_then(|id| id.rsplit_once(':'))
+        .and_then(|(_, version)| version.parse().ok())
+}
+
+/// Stream encoded in a projection row's `last_event_id`, the one the row was projected from.
+pub fn last_event_stream(last_event_id: Option<&str>) -> Option<&str> {
+    last_event_id
+        .and_then(|id| id.rsplit_once(':'))
+        .filter(|(_, version)| version.parse::<i64>().is_ok())
+        .map(|(stream_id, _)| stream_id)
+}
+
+#[cfg(test)]
+mod primitives_tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    fn it_should_mint_and_parse_v7_ids() {
+        let time_entry_id = TimeEntryId::new_v7();
+
+        assert_eq!(
+            TimeEntryId::parse_v7(time_entry_id.as_str()),
+            Ok(time_entry_id.clone())
+        );
+        assert_eq!(time_entry_id.to_string().parse(), Ok(time_entry_id));
+        assert!(UserId::parse_v7(UserId::new_v7().as_str()).is_ok());
+    }
+
+    #[rstest]
+    #[case::not_a_uuid("te-1")]
+    #[case::v4("67e55044-10b1-426f-9247-bb680e5fe0c8")]
+    fn it_should_refuse_ids_that_are_not_v7(#[case] value: &str) {
+        assert_eq!(
+            TimeEntryId::parse_v7(value),
+            Err(PrimitiveError::NotUuidV7("time entry id"))
+        );
+    }
+
+    #[rstest]
+    #[case::any_text("te-1", Ok(TimeEntryId::from("te-1")))]
+    #[case::blank(" ", Err(PrimitiveError::Blank("time entry id")))]
+    fn it_should_parse_ids(
+        #[case] value: &str,
+        #[case] expected: Result<TimeEntryId, PrimitiveError>,
+    ) {
+        assert_eq!(value.parse::<TimeEntryId>(), expected);
+    }
+
+    #[rstest]
+    fn it_should_serialize_ids_as_plain_strings() {
+        let user_id = UserId::from("u-1");
+
+        assert_eq!(serde_json::to_string(&user_id).unwrap(), r#""u-1""#);
+        assert_eq!(serde_json::from_str::<UserId>(r#""u-1""#).unwrap(), user_id);
+        assert_eq!(String::from(user_id.clone()), "u-1");
+        assert_eq!(user_id.clone().into_inner(), "u-1");
+        assert_eq!(user_id.as_ref(), "u-1");
+        let owned = String::from("u-1");
+        assert!(user_id == "u-1" && user_id == *"u-1" && user_id == owned);
+    }
+
+    #[rstest]
+    fn it_should_name_time_entry_streams() {
+        let (stream_id, time_entry_id) = StreamId::new_time_entry();
+
+        assert_eq!(stream_id.to_string(), format!("TimeEntry-{time_entry_id}"));
+        assert_eq!(stream_id.category(), "TimeEntry");
+        assert_eq!(stream_id.as_str().parse(), Ok(stream_id.clone()));
+        assert_eq!(stream_id.as_ref(), stream_id.as_str());
+    }
+
+    #[rstest]
+    #[case::no_separator("TimeEntry")]
+    #[case::no_category("-te-1")]
+    #[case::no_id("TimeEntry-")]
+    fn it_should_refuse_malformed_stream_ids(#[case] value: &str) {
+        assert_eq!(
+            value.parse::<StreamId>(),
+            Err(PrimitiveError::MalformedStreamId)
+        );
+    }
+
+    #[rstest]
+    #[case::rfc3339("2026-10-16T09:30:00.250Z", 1_792_143_000_250)]
+    #[case::offset("2026-10-16T11:30:00.250+02:00", 1_792_143_000_250)]
+    #[case::millis("1792143000250", 1_792_143_000_250)]
+    fn it_should_parse_timestamps(#[case] value: &str, #[case] millis: i64) {
+        assert_eq!(value.parse(), Ok(Timestamp::from_millis(millis)));
+    }
+
+    #[rstest]
+    fn it_should_display_timestamps_as_rfc3339() {
+        let timestamp = Timestamp::from(1_792_143_000_250);
+
+        assert_eq!(timestamp.to_string(), "2026-10-16T09:30:00.250Z");
+        assert_eq!(timestamp.to_string().parse(), Ok(timestamp));
+        assert_eq!(
+            Timestamp::from_millis(i64::MAX).to_string(),
+            i64::MAX.to_string()
+        );
+        assert_eq!(i64::from(timestamp), timestamp.as_millis());
+        assert_eq!(serde_json::to_string(&timestamp).unwrap(), "1792143000250");
+        assert!(Timestamp::now() > Timestamp::default());
+        assert_eq!(
+            "yesterday".parse::<Timestamp>(),
+            Err(PrimitiveError::MalformedTimestamp)
+        );
+    }
+
+    #[rstest]
+    #[case::missing(None, None)]
+    #[case::well_formed(Some("TimeEntry-te-1:3"), Some(3))]
+    #[case::colon_in_stream_id(Some("Tag:t1:12"), Some(12))]
+    #[case::no_version(Some("TimeEntry-te-1"), None)]
+    #[case::non_numeric(Some("TimeEntry-te-1:x"), None)]
+    fn it_should_parse_last_event_version(
+        #[case] last_event_id: Option<&str>,
+        #[case] expected: Option<i64>,
+    ) {
+        assert_eq!(last_event_version(last_event_id), expected);
+    }
+
+    #[rstest]
+    #[case::missing(None, None)]
+    #[case::well_formed(Some("t-1/TimeEntry-te-1:3"), Some("t-1/TimeEntry-te-1"))]
+    #[case::no_version(Some("TimeEntry-te-1"), None)]
+    fn it_should_parse_last_event_stream(
+        #[case] last_event_id: Option<&str>,
+        #[case] expected: Option<&str>,
+    ) {
+        assert_eq!(last_event_stream(last_event_id), expected);
+    }
+}
@@ -0,0 +1,194 @@
+// Masks personal data before it leaves the process through log and trace output or published
+// payloads. Fields are picked by name, so log statements and event shapes stay as they are and
+// deployments choose what counts as personal data for them. Typed values are redacted
+// through `PersonalData` instead.
+
+use crate::shared::core::personal_data::PersonalData;
+use serde_json::Value as Json;
+use std::sync::Arc;
+
+pub const MASK: &str = "***";
+
+/// Field names masked unless configured otherwise.
+pub const DEFAULT_REDACTED_FIELDS: &[&str] = &[
+    "user_id",
+    "created_by",
+    "updated_by",
+    "deleted_by",
+    "approved_by",
+    "added_by",
+    "description",
+];
+
+/// Which fields to mask, read from config. Masking is on unless explicitly turned off.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Redactor {
+    enabled: bool,
+    fields: Arc<[String]>,
+}
+
+impl Default for Redactor {
+    fn default() -> Self {
+        Self::new(true)
+    }
+}
+
+impl Redactor {
+    pub fn new(enabled: bool) -> Self {
+        Self {
+            enabled,
+            fields: DEFAULT_REDACTED_FIELDS
+                .iter()
+                .map(|field| field.to_string())
+                .collect(),
+        }
+    }
+
+    pub fn disabled() -> Self {
+        Self::new(false)
+    }
+
+    /// Masks `fields` instead of the defaults.
+    pub fn with_fields(mut self, fields: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.fields = fields.into_iter().map(Into::into).collect();
+        self
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    pub fn fields(&self) -> &[String] {
+        &self.fields
+    }
+
+    /// Whether values recorded under `field` are masked.
+    pub fn masks(&self, field: &str) -> bool {
+        self.enabled && self.fields.iter().any(|masked| masked == field)
+    }
+
+    /// Masks every personal data field of `value`, whatever the configured field names.
+    pub fn redact<T: PersonalData>(&self, value: T) -> T {
+        if !self.enabled {
+            return value;
+        }
+        value.map_personal_data(&mut |_| MASK.to_string())
+    }
+
+    /// Masks every string or array of strings stored under a masked field, at any depth.
+    pub fn redact_json(&self, json: Json) -> Json {
+        if !self.enabled {
+            return json;
+        }
+        self.mask_fields(json)
+    }
+
+    fn mask_fields(&self, json: Json) -> Json {
+        match json {
+            Json::Object(map) => Json::Object(
+                map.into_iter()
+                    .map(|(key, value)| {
+                        let value = if self.masks(&key) {
+                            mask_value(value)
+                        } else {
+                            self.mask_fields(value)
+                        };
+                        (key, value)
+                    })
+                    .collect(),
+            ),
+            Json::Array(items) => Json::Array(
+                items
+                    .into_iter()
+                    .map(|item| self.mask_fields(item))
+                    .collect(),
+            ),
+            other => other,
+        }
+    }
+}
+
+fn mask_value(value: Json) -> Json {
+    match value {
+        Json::String(_) => Json::String(MASK.to_string()),
+        Json::Array(items) => Json::Array(items.into_iter().map(mask_value).collect()),
+        other => other,
+    }
+}
+
+#[cfg(test)]
+mod redactor_tests {
+    use super::*;
+    use serde_json::json;
+
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    struct Actor(String);
+
+    impl PersonalData for Actor {
+        fn map_personal_data(self, f: &mut dyn FnMut(String) -> String) -> Self {
+            Actor(f(self.0))
+        }
+    }
+
+    #[test]
+    fn it_should_mask_the_default_fields_unless_disabled() {
+        let redactor = Redactor::default();
+
+        assert!(redactor.masks("description"));
+        assert!(redactor.masks("created_by"));
+        assert!(redactor.masks("user_id"));
+        assert!(!redactor.masks("time_entry_id"));
+        assert!(!Redactor::disabled().masks("description"));
+    }
+
+    #[test]
+    fn it_should_mask_the_configured_fields_instead() {
+        let redactor = Redactor::default().with_fields(["user_id"]);
+
+        assert!(redactor.masks("user_id"));
+        assert!(!redactor.masks("description"));
+        assert_eq!(redactor.fields(), ["user_id".to_string()]);
+    }
+
+    #[test]
+    fn it_should_redact_typed_personal_data_when_enabled() {
+        let actor = Actor("u-1".to_string());
+
+        assert_eq!(
+            Redactor::default().redact(actor.clone()),
+            Actor(MASK.into())
+        );
+        assert_eq!(Redactor::disabled().redact(actor.clone()), actor);
+    }
+
+    #[test]
+    fn it_should_redact_masked_json_fields_at_any_depth() {
+        let payload = json!({
+            "time_entry_id": "te-1",
+            "user_id": "u-1",
+            "count": 3,
+            "entries": [{ "created_by": "u-2", "tags": ["t-1"] }],
+            "description": null,
+            "deleted_by": ["u-3", 4],
+        });
+
+        assert_eq!(
+            Redactor::default().redact_json(payload.clone()),
+            json!({
+                "time_entry_id": "te-1",
+                "user_id": MASK,
+                "count": 3,
+                "entries": [{ "created_by": MASK, "tags": ["t-1"] }],
+                "description": null,
+                "deleted_by": [MASK, 4],
+            })
+        );
+        assert_eq!(
+            Redactor::default()
+                .with_fields(["description"])
+                .redact_json(json!({ "user_id": "u-1", "description": "dentist" })),
+            json!({ "user_id": "u-1", "description": MASK })
+        );
+        assert_eq!(Redactor::disabled().redact_json(payload.clone()), payload);
+    }
+}
@@ -0,0 +1,115 @@
+// How time entry streams are named.
+//
+// Every place that turns a time entry id into its stream, and every tool that reads the id
+// back out of a stream, goes through one `StreamNaming`, so a stream written under a naming
+// is found again under it. Deployments sharing an event store between tenants or services
+// pick the tenant-aware or prefixed naming; the default is `TimeEntry-{id}`.
+
+use std::fmt;
+
+const TIME_ENTRY_CATEGORY: &str = "TimeEntry-";
+
+pub trait StreamNaming: fmt::Debug + Send + Sync {
+    /// The stream of `time_entry_id`, in `tenant_id` when the caller knows the tenant.
+    fn time_entry(&self, tenant_id: Option<&str>, time_entry_id: &dyn fmt::Display) -> String;
+
+    /// The time entry `stream_id` is the stream of; `None` for any other stream.
+    fn time_entry_id<'a>(&self, stream_id: &'a str) -> Option<&'a str>;
+}
+
+/// `TimeEntry-{id}`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DefaultStreamNaming;
+
+impl StreamNaming for DefaultStreamNaming {
+    fn time_entry(&self, _tenant_id: Option<&str>, time_entry_id: &dyn fmt::Display) -> String {
+        format!("{TIME_ENTRY_CATEGORY}{time_entry_id}")
+    }
+
+    fn time_entry_id<'a>(&self, stream_id: &'a str) -> Option<&'a str> {
+        stream_id
+            .strip_prefix(TIME_ENTRY_CATEGORY)
+            .filter(|id| !id.is_empty())
+    }
+}
+
+/// `{tenant_id}/TimeEntry-{id}`, so tenants sharing an event store never share a stream.
+/// Callers that do not know the tenant get the default name.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TenantStreamNaming;
+
+impl StreamNaming for TenantStreamNaming {
+    fn time_entry(&self, tenant_id: Option<&str>, time_entry_id: &dyn fmt::Display) -> String {
+        match tenant_id {
+            Some(tenant_id) => format!("{tenant_id}/{TIME_ENTRY_CATEGORY}{time_entry_id}"),
+            None => DefaultStreamNaming.time_entry(None, time_entry_id),
+        }
+    }
+
+    fn time_entry_id<'a>(&self, stream_id: &'a str) -> Option<&'a str> {
+        let unscoped = stream_id
+            .split_once('/')
+            .map_or(stream_id, |(_, stream_id)| stream_id);
+        DefaultStreamNaming.time_entry_id(unscoped)
+    }
+}
+
+/// `{prefix}TimeEntry-{id}`, for services sharing an event store.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PrefixedStreamNaming {
+    pub prefix: String,
+}
+
+impl PrefixedStreamNaming {
+    pub fn new(prefix: impl Into<String>) -> Self {
+        Self {
+            prefix: prefix.into(),
+        }
+    }
+}
+
+impl StreamNaming for PrefixedStreamNaming {
+    fn time_entry(&self, _tenant_id: Option<&str>, time_entry_id: &dyn fmt::Display) -> String {
+        format!("{}{TIME_ENTRY_CATEGORY}{time_entry_id}", self.prefix)
+    }
+
+    fn time_entry_id<'a>(&self, stream_id: &'a str) -> Option<&'a str> {
+        stream_id
+            .strip_prefix(self.prefix.as_str())
+            .and_then(|stream_id| DefaultStreamNaming.time_entry_id(stream_id))
+    }
+}
+
+#[cfg(test)]
+mod stream_naming_tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case::default(&DefaultStreamNaming, Some("t-1"), "TimeEntry-te-1")]
+    #[case::tenant(&TenantStreamNaming, Some("t-1"), "t-1/TimeEntry-te-1")]
+    #[case::tenant_unknown(&TenantStreamNaming, None, "TimeEntry-te-1")]
+    #[case::prefixed(&PrefixedStreamNaming::new("billing."), Some("t-1"), "billing.TimeEntry-te-1")]
+    fn it_should_name_and_read_back_time_entry_streams(
+        #[case] naming: &dyn StreamNaming,
+        #[case] tenant_id: Option<&str>,
+        #[case] expected: &str,
+    ) {
+        let stream_id = naming.time_entry(tenant_id, &"te-1");
+
+        assert_eq!(stream_id, expected);
+        assert_eq!(naming.time_entry_id(&stream_id), Some("te-1"));
+    }
+
+    #[rstest]
+    #[case::default(&DefaultStreamNaming, "UserTimeEntries-u-1")]
+    #[case::default_without_id(&DefaultStreamNaming, "TimeEntry-")]
+    #[case::tenant(&TenantStreamNaming, "t-1/Tag-tag-1")]
+    #[case::prefixed_other_prefix(&PrefixedStreamNaming::new("billing."), "TimeEntry-te-1")]
+    fn it_should_not_read_ids_from_other_streams(
+        #[case] naming: &dyn StreamNaming,
+        #[case] stream_id: &str,
+    ) {
+        assert_eq!(naming.time_entry_id(stream_id), None);
+    }
+}
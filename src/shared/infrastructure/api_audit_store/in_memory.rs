@@ -0,0 +1,122 @@
+use crate::shared::infrastructure::api_audit_store::{
+    ApiAuditStore, ApiAuditStoreError, AuditQuery, AuditRecord,
+};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use tokio::sync::RwLock;
+
+#[derive(Default)]
+struct Inner {
+    records: RwLock<Vec<AuditRecord>>,
+    is_offline: AtomicBool,
+}
+
+#[derive(Clone, Default)]
+pub struct InMemoryApiAuditStore {
+    inner: Arc<Inner>,
+}
+
+impl InMemoryApiAuditStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn toggle_offline(&self) {
+        self.inner.is_offline.fetch_xor(true, Ordering::SeqCst);
+    }
+
+    fn ensure_online(&self) -> Result<(), ApiAuditStoreError> {
+        if self.inner.is_offline.load(Ordering::SeqCst) {
+            return Err(ApiAuditStoreError::Backend(
+                "Audit store offline".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl ApiAuditStore for InMemoryApiAuditStore {
+    async fn record(&self, record: AuditRecord) -> Result<(), ApiAuditStoreError> {
+        self.ensure_online()?;
+        self.inner.records.write().await.push(record);
+        Ok(())
+    }
+
+    async fn list(&self, query: &AuditQuery) -> Result<Vec<AuditRecord>, ApiAuditStoreError> {
+        self.ensure_online()?;
+        Ok(self
+            .inner
+            .records
+            .read()
+            .await
+            .iter()
+            .rev()
+            .filter(|record| query.actor.is_none() || record.actor == query.actor)
+            .take(query.limit)
+            .cloned()
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod in_memory_api_audit_store_tests {
+    use super::*;
+
+    fn record(actor: &str, occurred_at: i64) -> AuditRecord {
+        AuditRecord {
+            occurred_at,
+            actor: Some(actor.to_string()),
+            tenant_id: Some("t-1".to_string()),
+            operation: "POST /tags".to_string(),
+            arguments_hash: "0".to_string(),
+            status: 201,
+            latency_ms: 1,
+        }
+    }
+
+    #[tokio::test]
+    async fn it_should_list_newest_first_filtered_by_actor() {
+        let store = InMemoryApiAuditStore::new();
+        store.record(record("u-1", 1)).await.unwrap();
+        store.record(record("u-2", 2)).await.unwrap();
+        store.record(record("u-1", 3)).await.unwrap();
+
+        let all = store
+            .list(&AuditQuery {
+                actor: None,
+                limit: 2,
+            })
+            .await
+            .unwrap();
+        assert_eq!(all, vec![record("u-1", 3), record("u-2", 2)]);
+
+        let by_actor = store
+            .list(&AuditQuery {
+                actor: Some("u-1".to_string()),
+                limit: 10,
+            })
+            .await
+            .unwrap();
+        assert_eq!(by_actor, vec![record("u-1", 3), record("u-1", 1)]);
+    }
+
+    #[tokio::test]
+    async fn it_should_fail_when_offline() {
+        let store = InMemoryApiAuditStore::new();
+        store.toggle_offline();
+
+        assert!(store.record(record("u-1", 1)).await.is_err());
+        assert_eq!(
+            store
+                .list(&AuditQuery {
+                    actor: None,
+                    limit: 1
+                })
+                .await,
+            Err(ApiAuditStoreError::Backend(
+                "Audit store offline".to_string()
+            ))
+        );
+    }
+}
@@ -0,0 +1,68 @@
+use async_trait::async_trait;
+use thiserror::Error;
+
+#[derive(Debug, Clone, Error, PartialEq, Eq)]
+pub enum ApiAuditStoreError {
+    #[error("backend error: {0}")]
+    Backend(String),
+}
+
+/// One inbound mutation attempt, recorded whether or not it succeeded.
+/// Only a hash of the arguments is kept so the audit trail holds no personal data.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct AuditRecord {
+    pub occurred_at: i64,
+    pub actor: Option<String>,
+    pub tenant_id: Option<String>,
+    pub operation: String,
+    pub arguments_hash: String,
+    pub status: u16,
+    pub latency_ms: u64,
+}
+
+impl AuditRecord {
+    pub fn succeeded(&self) -> bool {
+        (200..300).contains(&self.status)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AuditQuery {
+    pub actor: Option<String>,
+    pub limit: usize,
+}
+
+/// Append-only trail of API mutation attempts, independent of domain events.
+#[async_trait]
+pub trait ApiAuditStore: Send + Sync {
+    async fn record(&self, record: AuditRecord) -> Result<(), ApiAuditStoreError>;
+
+    /// Newest first.
+    async fn list(&self, query: &AuditQuery) -> Result<Vec<AuditRecord>, ApiAuditStoreError>;
+}
+
+pub mod in_memory;
+
+#[cfg(test)]
+mod audit_record_tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case::ok(200, true)]
+    #[case::no_content(204, true)]
+    #[case::forbidden(403, false)]
+    #[case::server_error(500, false)]
+    fn it_should_treat_2xx_as_success(#[case] status: u16, #[case] expected: bool) {
+        let record = AuditRecord {
+            occurred_at: 0,
+            actor: None,
+            tenant_id: None,
+            operation: "POST /tags".to_string(),
+            arguments_hash: "0".to_string(),
+            status,
+            latency_ms: 0,
+        };
+        assert_eq!(record.succeeded(), expected);
+    }
+}
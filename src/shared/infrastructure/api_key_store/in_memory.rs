@@ -0,0 +1,230 @@
+use crate::shared::infrastructure::api_key_store::{
+    ApiKey, ApiKeyRecord, ApiKeyStore, ApiKeyStoreError, IssuedApiKey, generate_key, hash_key,
+};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use tokio::sync::RwLock;
+
+#[derive(Default)]
+struct Inner {
+    /// By `hash_key` of the key.
+    keys: RwLock<HashMap<String, ApiKeyRecord>>,
+    is_offline: AtomicBool,
+}
+
+/// Keys held by their hash, in memory: they last until the process restarts, apart from the
+/// ones provisioned at startup.
+#[derive(Clone, Default)]
+pub struct InMemoryApiKeyStore {
+    inner: Arc<Inner>,
+}
+
+impl InMemoryApiKeyStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Keys provisioned out of band, as `<hash_key hex>:<user_id>:<tenant_id>:<scope>` separated
+    /// by commas, `scope` being `register-only`, `read-only` or `admin`. Each gets its hash as
+    /// record id.
+    pub fn parse(value: &str) -> Result<Self, ApiKeyStoreError> {
+        let mut keys = HashMap::new();
+        let entries = value
+            .split(',')
+            .map(str::trim)
+            .filter(|entry| !entry.is_empty());
+        for (index, entry) in entries.enumerate() {
+            // Not echoing the entry, in case a key was configured instead of its hash.
+            let invalid = || {
+                ApiKeyStoreError::Backend(format!(
+                    "key {index}: expected <sha-256 hex>:<user_id>:<tenant_id>:<scope>"
+                ))
+            };
+            let [hash, user_id, tenant_id, scope] = entry
+                .splitn(4, ':')
+                .collect::<Vec<_>>()
+                .try_into()
+                .map_err(|_| invalid())?;
+            if hash.len() != 64 || !hash.bytes().all(|byte| byte.is_ascii_hexdigit()) {
+                return Err(invalid());
+            }
+            let scope =
+                serde_json::from_value(serde_json::Value::from(scope)).map_err(|_| invalid())?;
+            let hash = hash.to_ascii_lowercase();
+            keys.insert(
+                hash.clone(),
+                ApiKeyRecord {
+                    id: hash,
+                    api_key: ApiKey {
+                        user_id: user_id.to_string(),
+                        tenant_id: tenant_id.to_string(),
+                        scope,
+                    },
+                    created_at: 0,
+                },
+            );
+        }
+        Ok(Self {
+            inner: Arc::new(Inner {
+                keys: RwLock::new(keys),
+                is_offline: AtomicBool::default(),
+            }),
+        })
+    }
+
+    /// Accepts `key` for `api_key`, with the key's hash as record id.
+    pub async fn insert(&self, key: &str, api_key: ApiKey) {
+        let hash = hash_key(key);
+        self.inner.keys.write().await.insert(
+            hash.clone(),
+            ApiKeyRecord {
+                id: hash,
+                api_key,
+                created_at: 0,
+            },
+        );
+    }
+
+    pub fn toggle_offline(&self) {
+        self.inner.is_offline.fetch_xor(true, Ordering::SeqCst);
+    }
+
+    fn ensure_online(&self) -> Result<(), ApiKeyStoreError> {
+        if self.inner.is_offline.load(Ordering::SeqCst) {
+            return Err(ApiKeyStoreError::Backend(
+                "API key store offline".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl ApiKeyStore for InMemoryApiKeyStore {
+    async fn find(&self, key: &str) -> Result<Option<ApiKey>, ApiKeyStoreError> {
+        self.ensure_online()?;
+        Ok(self
+            .inner
+            .keys
+            .read()
+            .await
+            .get(&hash_key(key))
+            .map(|record| record.api_key.clone()))
+    }
+
+    async fn issue(&self, api_key: ApiKey, now: i64) -> Result<IssuedApiKey, ApiKeyStoreError> {
+        self.ensure_online()?;
+        let key = generate_key();
+        let record = ApiKeyRecord {
+            id: uuid::Uuid::now_v7().to_string(),
+            api_key,
+            created_at: now,
+        };
+        self.inner
+            .keys
+            .write()
+            .await
+            .insert(hash_key(&key), record.clone());
+        Ok(IssuedApiKey { record, key })
+    }
+
+    async fn list(&self) -> Result<Vec<ApiKeyRecord>, ApiKeyStoreError> {
+        self.ensure_online()?;
+        let mut records: Vec<_> = self.inner.keys.read().await.values().cloned().collect();
+        records.sort_by(|a, b| (a.created_at, &a.id).cmp(&(b.created_at, &b.id)));
+        Ok(records)
+    }
+
+    async fn revoke(&self, id: &str) -> Result<bool, ApiKeyStoreError> {
+        self.ensure_online()?;
+        let mut keys = self.inner.keys.write().await;
+        let before = keys.len();
+        keys.retain(|_, record| record.id != id);
+        Ok(keys.len() < before)
+    }
+}
+
+#[cfg(test)]
+mod in_memory_api_key_store_tests {
+    use super::*;
+    use crate::shared::auth::rbac::ApiKeyScope;
+
+    fn ci_key() -> ApiKey {
+        ApiKey {
+            user_id: "ci-bot".to_string(),
+            tenant_id: "t-1".to_string(),
+            scope: ApiKeyScope::RegisterOnly,
+        }
+    }
+
+    #[tokio::test]
+    async fn it_should_find_inserted_keys_until_revoked() {
+        let store = InMemoryApiKeyStore::new();
+        store.insert("secret", ci_key()).await;
+
+        assert_eq!(store.find("secret").await.unwrap(), Some(ci_key()));
+        assert_eq!(store.find("other").await.unwrap(), None);
+        let id = hash_key("secret");
+        assert!(store.revoke(&id).await.unwrap());
+        assert!(!store.revoke(&id).await.unwrap());
+        assert_eq!(store.find("secret").await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn it_should_issue_keys_it_only_keeps_the_hash_of() {
+        let store = InMemoryApiKeyStore::new();
+
+        let issued = store.issue(ci_key(), 1_000).await.unwrap();
+
+        assert_eq!(store.find(&issued.key).await.unwrap(), Some(ci_key()));
+        assert_eq!(store.list().await.unwrap(), vec![issued.record.clone()]);
+        let stored = store.inner.keys.read().await;
+        assert!(!stored.contains_key(&issued.key));
+        assert!(stored.contains_key(&hash_key(&issued.key)));
+        drop(stored);
+        assert!(store.revoke(&issued.record.id).await.unwrap());
+        assert_eq!(store.find(&issued.key).await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn it_should_accept_keys_provisioned_by_hash() {
+        let store = InMemoryApiKeyStore::parse(&format!(
+            "{}:ci-bot:t-1:register-only, {}:auditor:t-1:read-only",
+            hash_key("secret"),
+            hash_key("other")
+        ))
+        .unwrap();
+
+        assert_eq!(store.find("secret").await.unwrap(), Some(ci_key()));
+        assert_eq!(
+            store.find("other").await.unwrap().map(|key| key.scope),
+            Some(ApiKeyScope::ReadOnly)
+        );
+        assert_eq!(store.list().await.unwrap().len(), 2);
+    }
+
+    #[rstest::rstest]
+    #[case::missing_fields("ab:ci-bot")]
+    #[case::not_a_hash("secret:ci-bot:t-1:admin")]
+    #[case::unknown_scope(&format!("{}:ci-bot:t-1:root", "ab".repeat(32)))]
+    fn it_should_refuse_malformed_provisioned_keys(#[case] value: &str) {
+        assert!(InMemoryApiKeyStore::parse(value).is_err());
+    }
+
+    #[tokio::test]
+    async fn it_should_fail_when_offline() {
+        let store = InMemoryApiKeyStore::new();
+        store.toggle_offline();
+
+        assert_eq!(
+            store.find("secret").await,
+            Err(ApiKeyStoreError::Backend(
+                "API key store offline".to_string()
+            ))
+        );
+        assert!(store.issue(ci_key(), 0).await.is_err());
+        assert!(store.list().await.is_err());
+        assert!(store.revoke("secret").await.is_err());
+    }
+}
@@ -0,0 +1,109 @@
+use aes_gcm::aead::OsRng;
+use aes_gcm::aead::rand_core::RngCore;
+use async_trait::async_trait;
+use sha2::{Digest, Sha256};
+use thiserror::Error;
+
+use crate::shared::auth::rbac::{ApiKeyScope, Principal};
+use crate::shared::infrastructure::key_provider::hex_encode;
+
+#[derive(Debug, Clone, Error, PartialEq, Eq)]
+pub enum ApiKeyStoreError {
+    #[error("backend error: {0}")]
+    Backend(String),
+}
+
+/// What an API key authenticates as. Machine clients act as `user_id` within `tenant_id`,
+/// limited to `scope`.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct ApiKey {
+    pub user_id: String,
+    pub tenant_id: String,
+    pub scope: ApiKeyScope,
+}
+
+impl ApiKey {
+    pub fn principal(&self) -> Principal {
+        Principal::new(self.user_id.clone(), self.scope.role()).with_scope(self.scope.scope())
+    }
+}
+
+/// A provisioned key as listed to admins. Only the key's hash is kept, so the key itself
+/// cannot be shown again after it was issued.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct ApiKeyRecord {
+    pub id: String,
+    #[serde(flatten)]
+    pub api_key: ApiKey,
+    pub created_at: i64,
+}
+
+/// A newly issued key: its record, and the key for the client to send as `X-Api-Key`.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct IssuedApiKey {
+    #[serde(flatten)]
+    pub record: ApiKeyRecord,
+    pub key: String,
+}
+
+/// Resolves `X-Api-Key` secrets for CI jobs and integrations that cannot do interactive login.
+/// Adapters store `hash_key` of each key, never the key.
+#[async_trait]
+pub trait ApiKeyStore: Send + Sync {
+    /// `None` when the key is unknown or has been revoked.
+    async fn find(&self, key: &str) -> Result<Option<ApiKey>, ApiKeyStoreError>;
+
+    /// Provisions a fresh random key for `api_key`.
+    async fn issue(&self, api_key: ApiKey, now: i64) -> Result<IssuedApiKey, ApiKeyStoreError>;
+
+    /// Every key not revoked, oldest first.
+    async fn list(&self) -> Result<Vec<ApiKeyRecord>, ApiKeyStoreError>;
+
+    /// Revokes the key with record id `id`. Returns whether it existed.
+    async fn revoke(&self, id: &str) -> Result<bool, ApiKeyStoreError>;
+}
+
+/// Hex SHA-256 of `key`. Keys are 256 random bits, so a fast hash is enough to keep them
+/// from being read back out of the store.
+pub fn hash_key(key: &str) -> String {
+    hex_encode(&Sha256::digest(key.as_bytes()))
+}
+
+/// 256 random bits, as 64 hex digits.
+pub fn generate_key() -> String {
+    let mut bytes = [0u8; 32];
+    OsRng.fill_bytes(&mut bytes);
+    hex_encode(&bytes)
+}
+
+pub mod in_memory;
+
+#[cfg(test)]
+mod api_key_tests {
+    use super::*;
+    use crate::shared::auth::rbac::{Role, Scope};
+
+    #[test]
+    fn it_should_act_as_its_user_within_its_scope() {
+        let key = ApiKey {
+            user_id: "ci-bot".to_string(),
+            tenant_id: "t-1".to_string(),
+            scope: ApiKeyScope::ReadOnly,
+        };
+        assert_eq!(
+            key.principal(),
+            Principal::new("ci-bot", Role::Manager).with_scope(Scope::ReadOnly)
+        );
+    }
+
+    #[test]
+    fn it_should_hash_keys_and_generate_distinct_ones() {
+        assert_eq!(
+            hash_key("secret"),
+            "2bb80d537b1da3e38bd30361aa855686bde0eacd7162fef6a25fe97bf527a25b"
+        );
+        let key = generate_key();
+        assert_eq!(key.len(), 64);
+        assert_ne!(key, generate_key());
+    }
+}
@@ -0,0 +1,90 @@
+use crate::shared::infrastructure::attachment_storage::{
+    AttachmentStorage, AttachmentStorageError, PresignedUrl, URL_TTL_SECS,
+};
+use async_trait::async_trait;
+use chrono::Utc;
+use std::io::ErrorKind;
+use std::path::PathBuf;
+
+/// Attachments as files under `root`, for local development. A file server in front of
+/// `root` answers the URLs, which are plain `{base_url}/{key}` and are not signed.
+#[derive(Clone)]
+pub struct FilesystemAttachmentStorage {
+    root: PathBuf,
+    base_url: String,
+}
+
+impl FilesystemAttachmentStorage {
+    pub fn new(root: impl Into<PathBuf>, base_url: impl Into<String>) -> Self {
+        Self {
+            root: root.into(),
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+        }
+    }
+
+    fn url(&self, key: &str) -> PresignedUrl {
+        PresignedUrl {
+            url: format!("{}/{key}", self.base_url),
+            expires_at: Utc::now().timestamp_millis() + URL_TTL_SECS * 1000,
+        }
+    }
+}
+
+#[async_trait]
+impl AttachmentStorage for FilesystemAttachmentStorage {
+    async fn upload_url(
+        &self,
+        key: &str,
+        _content_type: &str,
+    ) -> Result<PresignedUrl, AttachmentStorageError> {
+        Ok(self.url(key))
+    }
+
+    async fn download_url(&self, key: &str) -> Result<PresignedUrl, AttachmentStorageError> {
+        Ok(self.url(key))
+    }
+
+    async fn object_size(&self, key: &str) -> Result<Option<u64>, AttachmentStorageError> {
+        match std::fs::metadata(self.root.join(key)) {
+            Ok(metadata) => Ok(Some(metadata.len())),
+            Err(error) if error.kind() == ErrorKind::NotFound => Ok(None),
+            Err(error) => Err(AttachmentStorageError::Backend(error.to_string())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod filesystem_attachment_storage_tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_read_sizes_of_files_under_the_root() {
+        let root = std::env::temp_dir().join(format!("attachments-{}", uuid::Uuid::now_v7()));
+        std::fs::create_dir_all(root.join("attachments/te-1")).unwrap();
+        std::fs::write(root.join("attachments/te-1/a-1"), b"%PDF-1.7").unwrap();
+        let storage = FilesystemAttachmentStorage::new(&root, "http://localhost:9000/files/");
+
+        let size = storage.object_size("attachments/te-1/a-1").await;
+        let missing = storage.object_size("attachments/te-1/a-2").await;
+        std::fs::remove_dir_all(&root).unwrap();
+
+        assert_eq!(size, Ok(Some(8)));
+        assert_eq!(missing, Ok(None));
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_point_urls_at_the_file_server() {
+        let storage =
+            FilesystemAttachmentStorage::new("/var/attachments", "http://localhost:9000/files/");
+
+        let download = storage.download_url("attachments/te-1/a-1").await.unwrap();
+
+        assert_eq!(
+            download.url,
+            "http://localhost:9000/files/attachments/te-1/a-1"
+        );
+    }
+}
@@ -0,0 +1,125 @@
+use crate::shared::infrastructure::attachment_storage::{
+    AttachmentStorage, AttachmentStorageError, PresignedUrl, URL_TTL_SECS,
+};
+use chrono::Utc;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use tokio::sync::RwLock;
+
+#[derive(Default)]
+struct Inner {
+    sizes: RwLock<HashMap<String, u64>>,
+    is_offline: AtomicBool,
+}
+
+/// Keeps only the size of each object; URLs use the `memory:` scheme and lead nowhere.
+#[derive(Clone, Default)]
+pub struct InMemoryAttachmentStorage {
+    inner: Arc<Inner>,
+}
+
+impl InMemoryAttachmentStorage {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn toggle_offline(&self) {
+        self.inner.is_offline.fetch_xor(true, Ordering::SeqCst);
+    }
+
+    /// Stands in for the client's upload.
+    pub async fn put_object(&self, key: &str, size_bytes: u64) {
+        self.inner
+            .sizes
+            .write()
+            .await
+            .insert(key.to_string(), size_bytes);
+    }
+
+    fn presign(&self, method: &str, key: &str) -> Result<PresignedUrl, AttachmentStorageError> {
+        if self.inner.is_offline.load(Ordering::SeqCst) {
+            return Err(AttachmentStorageError::Backend(
+                "Attachment storage offline".to_string(),
+            ));
+        }
+        let expires_at = Utc::now().timestamp_millis() + URL_TTL_SECS * 1000;
+        Ok(PresignedUrl {
+            url: format!("memory:{key}?method={method}&expires={expires_at}"),
+            expires_at,
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl AttachmentStorage for InMemoryAttachmentStorage {
+    async fn upload_url(
+        &self,
+        key: &str,
+        _content_type: &str,
+    ) -> Result<PresignedUrl, AttachmentStorageError> {
+        self.presign("PUT", key)
+    }
+
+    async fn download_url(&self, key: &str) -> Result<PresignedUrl, AttachmentStorageError> {
+        self.presign("GET", key)
+    }
+
+    async fn object_size(&self, key: &str) -> Result<Option<u64>, AttachmentStorageError> {
+        if self.inner.is_offline.load(Ordering::SeqCst) {
+            return Err(AttachmentStorageError::Backend(
+                "Attachment storage offline".to_string(),
+            ));
+        }
+        Ok(self.inner.sizes.read().await.get(key).copied())
+    }
+}
+
+#[cfg(test)]
+mod in_memory_attachment_storage_tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_report_the_size_of_uploaded_objects_only() {
+        let storage = InMemoryAttachmentStorage::new();
+        storage.put_object("attachments/te-1/a-1", 2_048).await;
+
+        assert_eq!(
+            storage.object_size("attachments/te-1/a-1").await,
+            Ok(Some(2_048))
+        );
+        assert_eq!(storage.object_size("attachments/te-1/a-2").await, Ok(None));
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_hand_out_expiring_urls() {
+        let storage = InMemoryAttachmentStorage::new();
+        let before = Utc::now().timestamp_millis();
+
+        let upload = storage
+            .upload_url("attachments/te-1/a-1", "image/png")
+            .await
+            .unwrap();
+
+        assert!(
+            upload
+                .url
+                .starts_with("memory:attachments/te-1/a-1?method=PUT")
+        );
+        assert!(upload.expires_at >= before + URL_TTL_SECS * 1000);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_fail_all_operations_when_offline() {
+        let storage = InMemoryAttachmentStorage::new();
+        storage.toggle_offline();
+
+        assert!(storage.upload_url("k", "image/png").await.is_err());
+        assert!(storage.download_url("k").await.is_err());
+        assert!(storage.object_size("k").await.is_err());
+    }
+}
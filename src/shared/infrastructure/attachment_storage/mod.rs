@@ -0,0 +1,41 @@
+use async_trait::async_trait;
+use thiserror::Error;
+
+/// How long clients have to start an upload or a download with a URL they were handed.
+pub const URL_TTL_SECS: i64 = 15 * 60;
+
+#[derive(Debug, Clone, Error, PartialEq, Eq)]
+pub enum AttachmentStorageError {
+    #[error("backend error: {0}")]
+    Backend(String),
+}
+
+/// A URL a client uses directly against the storage, without credentials of its own.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PresignedUrl {
+    pub url: String,
+    /// Epoch milliseconds after which the storage refuses the URL.
+    pub expires_at: i64,
+}
+
+/// Object storage for the files attached to time entries (S3, filesystem). Files never pass
+/// through the API: clients upload and download them with pre-signed URLs.
+#[async_trait]
+pub trait AttachmentStorage: Send + Sync {
+    /// A URL to `PUT` the file under `key` with the given `Content-Type`.
+    async fn upload_url(
+        &self,
+        key: &str,
+        content_type: &str,
+    ) -> Result<PresignedUrl, AttachmentStorageError>;
+
+    /// A URL to `GET` the file under `key`.
+    async fn download_url(&self, key: &str) -> Result<PresignedUrl, AttachmentStorageError>;
+
+    /// The size of the file under `key`; `None` until one was uploaded.
+    async fn object_size(&self, key: &str) -> Result<Option<u64>, AttachmentStorageError>;
+}
+
+pub mod filesystem;
+pub mod in_memory;
+pub mod s3;
@@ -0,0 +1,169 @@
+use crate::shared::infrastructure::attachment_storage::{
+    AttachmentStorage, AttachmentStorageError, PresignedUrl, URL_TTL_SECS,
+};
+use async_trait::async_trait;
+use chrono::Utc;
+
+/// The S3 calls the storage makes, so the adapter stays independent of an AWS SDK.
+#[async_trait]
+pub trait S3Client: Send + Sync {
+    /// A SigV4 pre-signed URL for `method` on `bucket`/`key`, valid for `expires_in_secs`.
+    /// `content_type` is signed along for uploads, so the client must send the same.
+    async fn presign(
+        &self,
+        method: &str,
+        bucket: &str,
+        key: &str,
+        content_type: Option<&str>,
+        expires_in_secs: i64,
+    ) -> Result<String, String>;
+
+    /// `HeadObject`'s `Content-Length`; `None` when the object does not exist.
+    async fn head_object(&self, bucket: &str, key: &str) -> Result<Option<u64>, String>;
+}
+
+/// Attachments as objects in one S3 bucket, under the keys they are given.
+#[derive(Clone)]
+pub struct S3AttachmentStorage<TClient> {
+    bucket: String,
+    client: TClient,
+}
+
+impl<TClient: S3Client> S3AttachmentStorage<TClient> {
+    pub fn new(bucket: impl Into<String>, client: TClient) -> Self {
+        Self {
+            bucket: bucket.into(),
+            client,
+        }
+    }
+
+    async fn presign(
+        &self,
+        method: &str,
+        key: &str,
+        content_type: Option<&str>,
+    ) -> Result<PresignedUrl, AttachmentStorageError> {
+        let expires_at = Utc::now().timestamp_millis() + URL_TTL_SECS * 1000;
+        let url = self
+            .client
+            .presign(method, &self.bucket, key, content_type, URL_TTL_SECS)
+            .await
+            .map_err(AttachmentStorageError::Backend)?;
+        Ok(PresignedUrl { url, expires_at })
+    }
+}
+
+#[async_trait]
+impl<TClient: S3Client> AttachmentStorage for S3AttachmentStorage<TClient> {
+    async fn upload_url(
+        &self,
+        key: &str,
+        content_type: &str,
+    ) -> Result<PresignedUrl, AttachmentStorageError> {
+        self.presign("PUT", key, Some(content_type)).await
+    }
+
+    async fn download_url(&self, key: &str) -> Result<PresignedUrl, AttachmentStorageError> {
+        self.presign("GET", key, None).await
+    }
+
+    async fn object_size(&self, key: &str) -> Result<Option<u64>, AttachmentStorageError> {
+        self.client
+            .head_object(&self.bucket, key)
+            .await
+            .map_err(AttachmentStorageError::Backend)
+    }
+}
+
+#[cfg(test)]
+mod s3_attachment_storage_tests {
+    use super::*;
+    use rstest::rstest;
+    use std::sync::{Arc, Mutex};
+
+    #[derive(Clone, Default)]
+    struct StubClient {
+        calls: Arc<Mutex<Vec<String>>>,
+        fail: bool,
+    }
+
+    #[async_trait]
+    impl S3Client for StubClient {
+        async fn presign(
+            &self,
+            method: &str,
+            bucket: &str,
+            key: &str,
+            content_type: Option<&str>,
+            expires_in_secs: i64,
+        ) -> Result<String, String> {
+            if self.fail {
+                return Err("access denied".to_string());
+            }
+            self.calls.lock().unwrap().push(format!(
+                "{method} {bucket}/{key} {content_type:?} {expires_in_secs}"
+            ));
+            Ok(format!(
+                "https://{bucket}.s3.test/{key}?X-Amz-Signature=sig"
+            ))
+        }
+
+        async fn head_object(&self, _bucket: &str, key: &str) -> Result<Option<u64>, String> {
+            Ok((key == "attachments/te-1/a-1").then_some(4_096))
+        }
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_sign_uploads_with_their_content_type() {
+        let client = StubClient::default();
+        let storage = S3AttachmentStorage::new("receipts", client.clone());
+
+        let upload = storage
+            .upload_url("attachments/te-1/a-1", "application/pdf")
+            .await
+            .unwrap();
+        storage.download_url("attachments/te-1/a-1").await.unwrap();
+
+        assert_eq!(
+            upload.url,
+            "https://receipts.s3.test/attachments/te-1/a-1?X-Amz-Signature=sig"
+        );
+        assert_eq!(
+            *client.calls.lock().unwrap(),
+            vec![
+                "PUT receipts/attachments/te-1/a-1 Some(\"application/pdf\") 900".to_string(),
+                "GET receipts/attachments/te-1/a-1 None 900".to_string(),
+            ]
+        );
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_read_object_sizes_from_head_object() {
+        let storage = S3AttachmentStorage::new("receipts", StubClient::default());
+
+        assert_eq!(
+            storage.object_size("attachments/te-1/a-1").await,
+            Ok(Some(4_096))
+        );
+        assert_eq!(storage.object_size("attachments/te-1/a-2").await, Ok(None));
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_report_client_failures_as_backend_errors() {
+        let storage = S3AttachmentStorage::new(
+            "receipts",
+            StubClient {
+                fail: true,
+                ..StubClient::default()
+            },
+        );
+
+        assert_eq!(
+            storage.download_url("k").await,
+            Err(AttachmentStorageError::Backend("access denied".to_string()))
+        );
+    }
+}
@@ -0,0 +1,276 @@
+use crate::shared::infrastructure::calendar::{CalendarError, CalendarPort, DayOff, DayOffKind};
+use async_trait::async_trait;
+use chrono::NaiveDate;
+use serde::Deserialize;
+use std::fmt::Write;
+use std::time::Duration;
+
+/// How long one request to the HR service may take before the lookup fails.
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(2);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HttpResponse {
+    pub status: u16,
+    pub body: String,
+}
+
+/// The transport the HTTP calendar uses; `reqwest::Client` in production, a stub in tests.
+#[async_trait]
+pub trait HttpGet: Send + Sync {
+    async fn get(&self, url: &str) -> Result<HttpResponse, String>;
+}
+
+#[async_trait]
+impl HttpGet for reqwest::Client {
+    async fn get(&self, url: &str) -> Result<HttpResponse, String> {
+        let response = reqwest::Client::get(self, url)
+            .send()
+            .await
+            .map_err(|error| error.to_string())?;
+        let status = response.status().as_u16();
+        let body = response.text().await.map_err(|error| error.to_string())?;
+        Ok(HttpResponse { status, body })
+    }
+}
+
+#[derive(Deserialize)]
+struct HolidayDto {
+    date: NaiveDate,
+    name: String,
+}
+
+#[derive(Deserialize)]
+struct AbsenceDto {
+    date: NaiveDate,
+    reason: String,
+}
+
+/// Calendar backed by an HR service exposing
+/// - `GET {base_url}/holidays?from=&to=` returning `[{"date", "name"}]`
+/// - `GET {base_url}/users/{user_id}/absences?from=&to=&status=approved` returning
+///   `[{"date", "reason"}]`
+///
+/// Dates are `YYYY-MM-DD`, ranges inclusive.
+#[derive(Clone)]
+pub struct HttpCalendar<TClient> {
+    base_url: String,
+    client: TClient,
+}
+
+impl<TClient: HttpGet> HttpCalendar<TClient> {
+    pub fn new(base_url: impl Into<String>, client: TClient) -> Self {
+        Self {
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+            client,
+        }
+    }
+
+    async fn fetch<T: serde::de::DeserializeOwned>(&self, url: &str) -> Result<T, CalendarError> {
+        let response = self.client.get(url).await.map_err(CalendarError::Backend)?;
+        if response.status != 200 {
+            return Err(CalendarError::Backend(format!(
+                "GET {url} returned {}",
+                response.status
+            )));
+        }
+        serde_json::from_str(&response.body)
+            .map_err(|error| CalendarError::Backend(format!("GET {url}: {error}")))
+    }
+}
+
+#[async_trait]
+impl<TClient: HttpGet> CalendarPort for HttpCalendar<TClient> {
+    async fn days_off(
+        &self,
+        user_id: &str,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> Result<Vec<DayOff>, CalendarError> {
+        let range = format!("from={from}&to={to}");
+        let holidays: Vec<HolidayDto> = self
+            .fetch(&format!("{}/holidays?{range}", self.base_url))
+            .await?;
+        let absences: Vec<AbsenceDto> = self
+            .fetch(&format!(
+                "{}/users/{}/absences?{range}&status=approved",
+                self.base_url,
+                encode_path_segment(user_id)
+            ))
+            .await?;
+        let mut days_off: Vec<DayOff> = holidays
+            .into_iter()
+            .map(|holiday| DayOff {
+                date: holiday.date,
+                kind: DayOffKind::Holiday { name: holiday.name },
+            })
+            .chain(absences.into_iter().map(|absence| DayOff {
+                date: absence.date,
+                kind: DayOffKind::Absence {
+                    reason: absence.reason,
+                },
+            }))
+            .collect();
+        days_off.sort_by_key(|day_off| day_off.date);
+        Ok(days_off)
+    }
+}
+
+fn encode_path_segment(segment: &str) -> String {
+    segment.bytes().fold(String::new(), |mut encoded, byte| {
+        if byte.is_ascii_alphanumeric() || b"-._~".contains(&byte) {
+            encoded.push(byte as char);
+        } else {
+            let _ = write!(encoded, "%{byte:02X}");
+        }
+        encoded
+    })
+}
+
+#[cfg(test)]
+mod http_calendar_tests {
+    use super::*;
+    use rstest::rstest;
+    use std::collections::HashMap;
+    use std::sync::{Arc, Mutex};
+
+    #[derive(Clone, Default)]
+    struct StubClient {
+        responses: Arc<HashMap<String, Result<HttpResponse, String>>>,
+        requested: Arc<Mutex<Vec<String>>>,
+    }
+
+    impl StubClient {
+        fn with(responses: &[(&str, Result<HttpResponse, String>)]) -> Self {
+            Self {
+                responses: Arc::new(
+                    responses
+                        .iter()
+                        .map(|(url, response)| (url.to_string(), response.clone()))
+                        .collect(),
+                ),
+                requested: Arc::default(),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl HttpGet for StubClient {
+        async fn get(&self, url: &str) -> Result<HttpResponse, String> {
+            self.requested.lock().unwrap().push(url.to_string());
+            self.responses.get(url).cloned().unwrap_or(Ok(HttpResponse {
+                status: 404,
+                body: String::new(),
+            }))
+        }
+    }
+
+    fn ok(body: &str) -> Result<HttpResponse, String> {
+        Ok(HttpResponse {
+            status: 200,
+            body: body.to_string(),
+        })
+    }
+
+    const HOLIDAYS: &str = "https://hr.test/holidays?from=2026-12-01&to=2026-12-31";
+    const ABSENCES: &str =
+        "https://hr.test/users/u%201/absences?from=2026-12-01&to=2026-12-31&status=approved";
+
+    fn date(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 12, day).unwrap()
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_merge_holidays_and_absences_by_date() {
+        let client = StubClient::with(&[
+            (
+                HOLIDAYS,
+                ok(r#"[{"date":"2026-12-25","name":"Christmas"}]"#),
+            ),
+            (
+                ABSENCES,
+                ok(r#"[{"date":"2026-12-24","reason":"vacation"}]"#),
+            ),
+        ]);
+        let calendar = HttpCalendar::new("https://hr.test/", client.clone());
+
+        let days_off = calendar.days_off("u 1", date(1), date(31)).await.unwrap();
+
+        assert_eq!(
+            days_off,
+            vec![
+                DayOff {
+                    date: date(24),
+                    kind: DayOffKind::Absence {
+                        reason: "vacation".to_string()
+                    },
+                },
+                DayOff {
+                    date: date(25),
+                    kind: DayOffKind::Holiday {
+                        name: "Christmas".to_string()
+                    },
+                },
+            ]
+        );
+        assert_eq!(*client.requested.lock().unwrap(), vec![HOLIDAYS, ABSENCES]);
+    }
+
+    #[rstest]
+    #[case::transport(Err("connection refused".to_string()), "connection refused")]
+    #[case::status(
+        Ok(HttpResponse { status: 503, body: String::new() }),
+        "GET https://hr.test/holidays?from=2026-12-01&to=2026-12-31 returned 503"
+    )]
+    #[case::body(
+        ok("{}"),
+        "GET https://hr.test/holidays?from=2026-12-01&to=2026-12-31: invalid type"
+    )]
+    #[tokio::test]
+    async fn it_should_surface_failures_as_backend_errors(
+        #[case] response: Result<HttpResponse, String>,
+        #[case] expected: &str,
+    ) {
+        let calendar =
+            HttpCalendar::new("https://hr.test", StubClient::with(&[(HOLIDAYS, response)]));
+
+        let Err(CalendarError::Backend(message)) =
+            calendar.days_off("u 1", date(1), date(31)).await
+        else {
+            panic!("expected a backend error");
+        };
+        assert!(message.starts_with(expected), "{message}");
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_fetch_over_reqwest() {
+        use axum::{Router, extract::RawQuery, routing::get};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        let router = Router::new()
+            .route(
+                "/holidays",
+                get(|RawQuery(query): RawQuery| async move {
+                    assert_eq!(query.as_deref(), Some("from=2026-12-01&to=2026-12-31"));
+                    r#"[{"date":"2026-12-25","name":"Christmas"}]"#
+                }),
+            )
+            .route("/users/{user_id}/absences", get(|| async { "[]" }));
+        tokio::spawn(async move { axum::serve(listener, router).await.unwrap() });
+        let calendar = HttpCalendar::new(format!("http://{address}/"), reqwest::Client::new());
+
+        let days_off = calendar.days_off("u 1", date(1), date(31)).await.unwrap();
+
+        assert_eq!(
+            days_off,
+            vec![DayOff {
+                date: date(25),
+                kind: DayOffKind::Holiday {
+                    name: "Christmas".to_string()
+                },
+            }]
+        );
+    }
+}
@@ -0,0 +1,28 @@
+use async_trait::async_trait;
+use chrono::NaiveDate;
+use thiserror::Error;
+
+pub use crate::shared::core::calendar::{DayOff, DayOffKind};
+
+#[derive(Debug, Clone, Error, PartialEq, Eq)]
+pub enum CalendarError {
+    #[error("backend error: {0}")]
+    Backend(String),
+}
+
+/// Public holidays and approved absences, used to validate entries and to compute expected
+/// hours.
+#[async_trait]
+pub trait CalendarPort: Send + Sync {
+    /// Holidays and the user's approved absences between `from` and `to`, both inclusive,
+    /// ordered by date.
+    async fn days_off(
+        &self,
+        user_id: &str,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> Result<Vec<DayOff>, CalendarError>;
+}
+
+pub mod http;
+pub mod static_config;
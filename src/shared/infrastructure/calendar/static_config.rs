@@ -0,0 +1,167 @@
+use crate::shared::infrastructure::calendar::{CalendarError, CalendarPort, DayOff, DayOffKind};
+use chrono::NaiveDate;
+use serde::Deserialize;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct CalendarConfig {
+    #[serde(default)]
+    pub holidays: Vec<HolidayConfig>,
+    #[serde(default)]
+    pub absences: Vec<AbsenceConfig>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct HolidayConfig {
+    pub date: NaiveDate,
+    pub name: String,
+}
+
+/// Only approved absences belong in the config.
+#[derive(Debug, Clone, Deserialize)]
+pub struct AbsenceConfig {
+    pub user_id: String,
+    pub date: NaiveDate,
+    pub reason: String,
+}
+
+/// Calendar served from a fixed configuration, e.g. a JSON file shipped with the deployment.
+#[derive(Clone, Default)]
+pub struct StaticCalendar {
+    config: Arc<CalendarConfig>,
+    is_offline: Arc<AtomicBool>,
+}
+
+impl StaticCalendar {
+    pub fn new(config: CalendarConfig) -> Self {
+        Self {
+            config: Arc::new(config),
+            is_offline: Arc::default(),
+        }
+    }
+
+    /// `{"holidays": [{"date", "name"}], "absences": [{"user_id", "date", "reason"}]}`;
+    /// both lists are optional.
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        Ok(Self::new(serde_json::from_str(json)?))
+    }
+
+    pub fn toggle_offline(&self) {
+        self.is_offline.fetch_xor(true, Ordering::SeqCst);
+    }
+}
+
+#[async_trait::async_trait]
+impl CalendarPort for StaticCalendar {
+    async fn days_off(
+        &self,
+        user_id: &str,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> Result<Vec<DayOff>, CalendarError> {
+        if self.is_offline.load(Ordering::SeqCst) {
+            return Err(CalendarError::Backend("Calendar offline".to_string()));
+        }
+        let in_range = |date: &NaiveDate| from <= *date && *date <= to;
+        let holidays = self
+            .config
+            .holidays
+            .iter()
+            .filter(|holiday| in_range(&holiday.date))
+            .map(|holiday| DayOff {
+                date: holiday.date,
+                kind: DayOffKind::Holiday {
+                    name: holiday.name.clone(),
+                },
+            });
+        let absences = self
+            .config
+            .absences
+            .iter()
+            .filter(|absence| absence.user_id == user_id && in_range(&absence.date))
+            .map(|absence| DayOff {
+                date: absence.date,
+                kind: DayOffKind::Absence {
+                    reason: absence.reason.clone(),
+                },
+            });
+        let mut days_off: Vec<DayOff> = holidays.chain(absences).collect();
+        days_off.sort_by_key(|day_off| day_off.date);
+        Ok(days_off)
+    }
+}
+
+#[cfg(test)]
+mod static_calendar_tests {
+    use super::*;
+    use rstest::rstest;
+
+    fn date(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 12, day).unwrap()
+    }
+
+    fn calendar() -> StaticCalendar {
+        StaticCalendar::from_json(
+            r#"{
+                "holidays": [
+                    { "date": "2026-12-26", "name": "Boxing Day" },
+                    { "date": "2026-12-25", "name": "Christmas" }
+                ],
+                "absences": [
+                    { "user_id": "u-1", "date": "2026-12-24", "reason": "vacation" },
+                    { "user_id": "u-2", "date": "2026-12-24", "reason": "sick" }
+                ]
+            }"#,
+        )
+        .unwrap()
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_return_holidays_and_the_users_absences_in_range() {
+        let days_off = calendar()
+            .days_off("u-1", date(24), date(25))
+            .await
+            .unwrap();
+        assert_eq!(
+            days_off,
+            vec![
+                DayOff {
+                    date: date(24),
+                    kind: DayOffKind::Absence {
+                        reason: "vacation".to_string()
+                    },
+                },
+                DayOff {
+                    date: date(25),
+                    kind: DayOffKind::Holiday {
+                        name: "Christmas".to_string()
+                    },
+                },
+            ]
+        );
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_default_missing_lists_to_empty() {
+        let calendar = StaticCalendar::from_json("{}").unwrap();
+        assert_eq!(
+            calendar.days_off("u-1", date(1), date(31)).await,
+            Ok(vec![])
+        );
+        assert!(StaticCalendar::from_json("not json").is_err());
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_fail_when_offline() {
+        let calendar = StaticCalendar::default();
+        calendar.toggle_offline();
+        assert_eq!(
+            calendar.days_off("u-1", date(1), date(31)).await,
+            Err(CalendarError::Backend("Calendar offline".to_string()))
+        );
+    }
+}
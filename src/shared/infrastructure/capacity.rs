@@ -0,0 +1,198 @@
+// Capacity of the in-memory adapters.
+//
+// In-memory mode keeps everything in the process, so a dev or demo environment that runs for
+// weeks grows until it is restarted. The in-memory event store can instead keep a bounded
+// number of streams and projection stores a bounded number of rows, evicting the least
+// recently used. An evicted stream or row reads as if it was never written, which is why the
+// bounds are meant for environments whose data can be lost. Every store reports what it
+// holds, its bound and how much it evicted, which `/metrics` renders for scraping.
+
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::hash::Hash;
+use std::sync::{Arc, Mutex};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Capacity {
+    /// Streams or rows held.
+    pub used: usize,
+    /// The most the store keeps; `None` when unbounded.
+    pub max: Option<usize>,
+    /// Streams or rows evicted since the store was created.
+    pub evicted: u64,
+}
+
+/// Implemented by the in-memory adapters that can be bounded.
+pub trait HasCapacity: Send + Sync {
+    fn capacity(&self) -> Capacity;
+}
+
+/// Projection state made of rows a bounded projection store can evict.
+pub trait EvictRows {
+    fn row_count(&self) -> usize;
+
+    /// Drops the least recently updated rows until at most `max_rows` remain. Returns how
+    /// many were dropped.
+    fn evict_rows(&mut self, max_rows: usize) -> usize;
+}
+
+/// Removes the entries of `map` with the oldest `updated_at` until at most `max` remain;
+/// ties go by key. Returns how many were removed.
+pub fn evict_oldest<K, V>(
+    map: &mut HashMap<K, V>,
+    max: usize,
+    updated_at: impl Fn(&V) -> i64,
+) -> usize
+where
+    K: Clone + Eq + Hash + Ord,
+{
+    let excess = map.len().saturating_sub(max);
+    if excess == 0 {
+        return 0;
+    }
+    let mut by_age: Vec<(i64, K)> = map
+        .iter()
+        .map(|(key, value)| (updated_at(value), key.clone()))
+        .collect();
+    by_age.sort();
+    for (_, key) in by_age.into_iter().take(excess) {
+        map.remove(&key);
+    }
+    excess
+}
+
+type NamedStores = Vec<(String, Arc<dyn HasCapacity>)>;
+
+/// The stores `/metrics` reports the capacity of, by name; clones report the same stores.
+#[derive(Clone, Default)]
+pub struct CapacityGauges {
+    stores: Arc<Mutex<NamedStores>>,
+}
+
+impl CapacityGauges {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn register(&self, name: &str, store: impl HasCapacity + 'static) {
+        self.stores
+            .lock()
+            .unwrap()
+            .push((name.to_string(), Arc::new(store)));
+    }
+
+    /// Every registered store's capacity, in registration order.
+    pub fn capacities(&self) -> Vec<(String, Capacity)> {
+        self.stores
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(name, store)| (name.clone(), store.capacity()))
+            .collect()
+    }
+
+    /// `in_memory_used`, `in_memory_max` and `in_memory_evicted_total` per store.
+    pub fn render(&self) -> String {
+        let capacities = self.capacities();
+        let mut out = String::new();
+        out.push_str("# HELP in_memory_used Streams or rows an in-memory store holds.\n");
+        out.push_str("# TYPE in_memory_used gauge\n");
+        for (name, capacity) in &capacities {
+            let _ = writeln!(out, "in_memory_used{{store=\"{name}\"}} {}", capacity.used);
+        }
+        out.push_str("# HELP in_memory_max Streams or rows a bounded in-memory store keeps.\n");
+        out.push_str("# TYPE in_memory_max gauge\n");
+        for (name, capacity) in &capacities {
+            if let Some(max) = capacity.max {
+                let _ = writeln!(out, "in_memory_max{{store=\"{name}\"}} {max}");
+            }
+        }
+        out.push_str(
+            "# HELP in_memory_evicted_total Streams or rows an in-memory store evicted.\n",
+        );
+        out.push_str("# TYPE in_memory_evicted_total counter\n");
+        for (name, capacity) in &capacities {
+            let _ = writeln!(
+                out,
+                "in_memory_evicted_total{{store=\"{name}\"}} {}",
+                capacity.evicted
+            );
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod capacity_tests {
+    use super::*;
+    use rstest::rstest;
+
+    struct Fixed(Capacity);
+
+    impl HasCapacity for Fixed {
+        fn capacity(&self) -> Capacity {
+            self.0
+        }
+    }
+
+    #[rstest]
+    #[case::within(3, 0, &["a", "b", "c"])]
+    #[case::over(1, 2, &["c"])]
+    #[case::empty(0, 3, &[])]
+    fn it_should_evict_the_oldest_entries(
+        #[case] max: usize,
+        #[case] evicted: usize,
+        #[case] kept: &[&str],
+    ) {
+        let mut map = HashMap::from([("b", 1), ("a", 1), ("c", 2)]);
+
+        assert_eq!(
+            evict_oldest(&mut map, max, |updated_at| *updated_at),
+            evicted
+        );
+
+        let mut keys: Vec<_> = map.into_keys().collect();
+        keys.sort();
+        assert_eq!(keys, kept);
+    }
+
+    #[rstest]
+    fn it_should_render_the_prometheus_text_format() {
+        let gauges = CapacityGauges::new();
+        gauges.register(
+            "event_store",
+            Fixed(Capacity {
+                used: 10,
+                max: Some(10),
+                evicted: 4,
+            }),
+        );
+        gauges.register(
+            "tags",
+            Fixed(Capacity {
+                used: 2,
+                max: None,
+                evicted: 0,
+            }),
+        );
+
+        assert_eq!(
+            gauges.render(),
+            [
+                "# HELP in_memory_used Streams or rows an in-memory store holds.",
+                "# TYPE in_memory_used gauge",
+                r#"in_memory_used{store="event_store"} 10"#,
+                r#"in_memory_used{store="tags"} 2"#,
+                "# HELP in_memory_max Streams or rows a bounded in-memory store keeps.",
+                "# TYPE in_memory_max gauge",
+                r#"in_memory_max{store="event_store"} 10"#,
+                "# HELP in_memory_evicted_total Streams or rows an in-memory store evicted.",
+                "# TYPE in_memory_evicted_total counter",
+                r#"in_memory_evicted_total{store="event_store"} 4"#,
+                r#"in_memory_evicted_total{store="tags"} 0"#,
+                "",
+            ]
+            .join("\n")
+        );
+    }
+}
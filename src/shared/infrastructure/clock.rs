@@ -0,0 +1,74 @@
+use std::fmt::Debug;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicI64, Ordering};
+
+use crate::shared::core::primitives::Timestamp;
+
+/// Where handlers read the current time, so tests can pin it.
+pub trait Clock: Debug + Send + Sync {
+    fn now(&self) -> Timestamp;
+}
+
+pub type SharedClock = Arc<dyn Clock>;
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> Timestamp {
+        Timestamp::now()
+    }
+}
+
+/// A clock that only moves when told to. Clones share the same reading.
+#[derive(Debug, Clone, Default)]
+pub struct FixedClock {
+    millis: Arc<AtomicI64>,
+}
+
+impl FixedClock {
+    pub fn at(now: impl Into<Timestamp>) -> Self {
+        Self {
+            millis: Arc::new(AtomicI64::new(now.into().as_millis())),
+        }
+    }
+
+    pub fn set(&self, now: impl Into<Timestamp>) {
+        self.millis.store(now.into().as_millis(), Ordering::SeqCst);
+    }
+
+    pub fn advance(&self, millis: i64) {
+        self.millis.fetch_add(millis, Ordering::SeqCst);
+    }
+}
+
+impl Clock for FixedClock {
+    fn now(&self) -> Timestamp {
+        Timestamp::from_millis(self.millis.load(Ordering::SeqCst))
+    }
+}
+
+#[cfg(test)]
+mod clock_tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    fn it_should_read_the_system_time() {
+        let before = Timestamp::now();
+
+        assert!(SystemClock.now() >= before);
+    }
+
+    #[rstest]
+    fn it_should_only_move_a_fixed_clock_when_told() {
+        let clock = FixedClock::at(1_000);
+        let shared = clock.clone();
+
+        clock.advance(500);
+        assert_eq!(shared.now(), Timestamp::from_millis(1_500));
+
+        clock.set(10);
+        assert_eq!(shared.now(), Timestamp::from_millis(10));
+    }
+}
@@ -0,0 +1,166 @@
+use crate::shared::infrastructure::cold_storage::{ColdStorage, ColdStorageError};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use tokio::sync::RwLock;
+
+#[derive(Default)]
+struct Inner {
+    objects: RwLock<HashMap<String, Vec<String>>>,
+    binaries: RwLock<HashMap<String, Vec<u8>>>,
+    is_offline: AtomicBool,
+}
+
+#[derive(Clone, Default)]
+pub struct InMemoryColdStorage {
+    inner: Arc<Inner>,
+}
+
+impl InMemoryColdStorage {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn toggle_offline(&self) {
+        self.inner.is_offline.fetch_xor(true, Ordering::SeqCst);
+    }
+
+    pub async fn keys(&self) -> Vec<String> {
+        let mut keys: Vec<_> = self.inner.objects.read().await.keys().cloned().collect();
+        keys.extend(self.inner.binaries.read().await.keys().cloned());
+        keys.sort();
+        keys
+    }
+
+    fn ensure_online(&self) -> Result<(), ColdStorageError> {
+        if self.inner.is_offline.load(Ordering::SeqCst) {
+            return Err(ColdStorageError::Backend(
+                "Cold storage offline".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl ColdStorage for InMemoryColdStorage {
+    async fn append_lines(&self, key: &str, lines: Vec<String>) -> Result<(), ColdStorageError> {
+        self.ensure_online()?;
+        self.inner
+            .objects
+            .write()
+            .await
+            .entry(key.to_string())
+            .or_default()
+            .extend(lines);
+        Ok(())
+    }
+
+    async fn read_lines(&self, key: &str) -> Result<Vec<String>, ColdStorageError> {
+        self.ensure_online()?;
+        Ok(self
+            .inner
+            .objects
+            .read()
+            .await
+            .get(key)
+            .cloned()
+            .unwrap_or_default())
+    }
+
+    async fn write_lines(&self, key: &str, lines: Vec<String>) -> Result<(), ColdStorageError> {
+        self.ensure_online()?;
+        let mut objects = self.inner.objects.write().await;
+        if lines.is_empty() {
+            objects.remove(key);
+        } else {
+            objects.insert(key.to_string(), lines);
+        }
+        Ok(())
+    }
+
+    async fn write_object(&self, key: &str, bytes: Vec<u8>) -> Result<(), ColdStorageError> {
+        self.ensure_online()?;
+        self.inner
+            .binaries
+            .write()
+            .await
+            .insert(key.to_string(), bytes);
+        Ok(())
+    }
+
+    async fn read_object(&self, key: &str) -> Result<Option<Vec<u8>>, ColdStorageError> {
+        self.ensure_online()?;
+        Ok(self.inner.binaries.read().await.get(key).cloned())
+    }
+}
+
+#[cfg(test)]
+mod in_memory_cold_storage_tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn it_should_append_and_read_lines_per_key() {
+        let storage = InMemoryColdStorage::new();
+        storage
+            .append_lines("a.jsonl", vec!["1".into()])
+            .await
+            .unwrap();
+        storage
+            .append_lines("a.jsonl", vec!["2".into()])
+            .await
+            .unwrap();
+
+        assert_eq!(storage.read_lines("a.jsonl").await.unwrap(), vec!["1", "2"]);
+        assert!(storage.read_lines("b.jsonl").await.unwrap().is_empty());
+        assert_eq!(storage.keys().await, vec!["a.jsonl"]);
+    }
+
+    #[tokio::test]
+    async fn it_should_replace_and_remove_keys() {
+        let storage = InMemoryColdStorage::new();
+        storage
+            .append_lines("a.jsonl", vec!["1".into(), "2".into()])
+            .await
+            .unwrap();
+
+        storage
+            .write_lines("a.jsonl", vec!["3".into()])
+            .await
+            .unwrap();
+        assert_eq!(storage.read_lines("a.jsonl").await.unwrap(), vec!["3"]);
+
+        storage.write_lines("a.jsonl", vec![]).await.unwrap();
+        assert!(storage.keys().await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn it_should_replace_and_read_binary_objects() {
+        let storage = InMemoryColdStorage::new();
+        storage.write_object("a.parquet", vec![1]).await.unwrap();
+        storage.write_object("a.parquet", vec![2, 3]).await.unwrap();
+
+        assert_eq!(
+            storage.read_object("a.parquet").await.unwrap(),
+            Some(vec![2, 3])
+        );
+        assert_eq!(storage.read_object("b.parquet").await.unwrap(), None);
+        assert_eq!(storage.keys().await, vec!["a.parquet"]);
+    }
+
+    #[tokio::test]
+    async fn it_should_fail_every_call_when_offline() {
+        let storage = InMemoryColdStorage::new();
+        storage.toggle_offline();
+
+        let expected = Err(ColdStorageError::Backend("Cold storage offline".into()));
+        assert_eq!(storage.append_lines("a", vec![]).await, expected);
+        assert_eq!(
+            storage.read_lines("a").await,
+            expected.clone().map(|_| vec![])
+        );
+        assert_eq!(storage.write_lines("a", vec![]).await, expected);
+        assert_eq!(storage.write_object("a", vec![]).await, expected);
+        assert_eq!(storage.read_object("a").await, expected.map(|_| None));
+    }
+}
@@ -0,0 +1,57 @@
+use async_trait::async_trait;
+use std::sync::Arc;
+use thiserror::Error;
+
+#[derive(Debug, Clone, Error, PartialEq, Eq)]
+pub enum ColdStorageError {
+    #[error("backend error: {0}")]
+    Backend(String),
+}
+
+/// Object storage for data moved out of the hot path (S3, filesystem JSONL). Line objects hold
+/// an ordered list of lines and a missing one reads as empty; binary objects, such as Parquet
+/// files, are written and read whole.
+#[async_trait]
+pub trait ColdStorage: Send + Sync {
+    async fn append_lines(&self, key: &str, lines: Vec<String>) -> Result<(), ColdStorageError>;
+
+    async fn read_lines(&self, key: &str) -> Result<Vec<String>, ColdStorageError>;
+
+    /// Replaces the contents of `key`; writing no lines removes it.
+    async fn write_lines(&self, key: &str, lines: Vec<String>) -> Result<(), ColdStorageError>;
+
+    /// Replaces the binary object under `key`.
+    async fn write_object(&self, key: &str, bytes: Vec<u8>) -> Result<(), ColdStorageError>;
+
+    /// The binary object under `key`; `None` when nothing was written there.
+    async fn read_object(&self, key: &str) -> Result<Option<Vec<u8>>, ColdStorageError>;
+}
+
+/// Cold storage behind a pointer, so the shell can pick the backend at startup.
+pub type SharedColdStorage = Arc<dyn ColdStorage>;
+
+#[async_trait]
+impl<T: ColdStorage + ?Sized> ColdStorage for Arc<T> {
+    async fn append_lines(&self, key: &str, lines: Vec<String>) -> Result<(), ColdStorageError> {
+        (**self).append_lines(key, lines).await
+    }
+
+    async fn read_lines(&self, key: &str) -> Result<Vec<String>, ColdStorageError> {
+        (**self).read_lines(key).await
+    }
+
+    async fn write_lines(&self, key: &str, lines: Vec<String>) -> Result<(), ColdStorageError> {
+        (**self).write_lines(key, lines).await
+    }
+
+    async fn write_object(&self, key: &str, bytes: Vec<u8>) -> Result<(), ColdStorageError> {
+        (**self).write_object(key, bytes).await
+    }
+
+    async fn read_object(&self, key: &str) -> Result<Option<Vec<u8>>, ColdStorageError> {
+        (**self).read_object(key).await
+    }
+}
+
+pub mod in_memory;
+pub mod object_store;
@@ -0,0 +1,163 @@
+// Cold storage on an object store: S3 in production, a local directory for development.
+//
+// Line objects are stored as JSONL bodies, one line per row. Object stores cannot append,
+// so appends read the object and write it back whole under a lock; writers in other
+// processes are not coordinated, which is fine while cold storage is written by jobs the
+// runner hands to one worker at a time.
+
+use async_trait::async_trait;
+use object_store::path::Path;
+use object_store::{ObjectStore, PutPayload};
+use std::sync::Arc;
+use tokio::sync::Mutex;
+
+use crate::shared::infrastructure::cold_storage::{ColdStorage, ColdStorageError};
+
+#[derive(Clone)]
+pub struct ObjectStoreColdStorage {
+    store: Arc<dyn ObjectStore>,
+    appends: Arc<Mutex<()>>,
+}
+
+impl ObjectStoreColdStorage {
+    pub fn new(store: Arc<dyn ObjectStore>) -> Self {
+        Self {
+            store,
+            appends: Arc::new(Mutex::new(())),
+        }
+    }
+
+    async fn put(&self, key: &str, bytes: Vec<u8>) -> Result<(), ColdStorageError> {
+        self.store
+            .put(&Path::from(key), PutPayload::from(bytes))
+            .await
+            .map(|_| ())
+            .map_err(backend)
+    }
+}
+
+fn backend(error: object_store::Error) -> ColdStorageError {
+    ColdStorageError::Backend(error.to_string())
+}
+
+fn to_body(lines: Vec<String>) -> Vec<u8> {
+    let mut body = lines.join("\n");
+    body.push('\n');
+    body.into_bytes()
+}
+
+#[async_trait]
+impl ColdStorage for ObjectStoreColdStorage {
+    async fn append_lines(&self, key: &str, lines: Vec<String>) -> Result<(), ColdStorageError> {
+        let _append = self.appends.lock().await;
+        let mut stored = self.read_lines(key).await?;
+        stored.extend(lines);
+        self.put(key, to_body(stored)).await
+    }
+
+    async fn read_lines(&self, key: &str) -> Result<Vec<String>, ColdStorageError> {
+        let Some(bytes) = self.read_object(key).await? else {
+            return Ok(Vec::new());
+        };
+        let body = String::from_utf8(bytes)
+            .map_err(|error| ColdStorageError::Backend(format!("{key}: {error}")))?;
+        Ok(body.lines().map(str::to_string).collect())
+    }
+
+    async fn write_lines(&self, key: &str, lines: Vec<String>) -> Result<(), ColdStorageError> {
+        if !lines.is_empty() {
+            return self.put(key, to_body(lines)).await;
+        }
+        match self.store.delete(&Path::from(key)).await {
+            Ok(()) | Err(object_store::Error::NotFound { .. }) => Ok(()),
+            Err(error) => Err(backend(error)),
+        }
+    }
+
+    async fn write_object(&self, key: &str, bytes: Vec<u8>) -> Result<(), ColdStorageError> {
+        self.put(key, bytes).await
+    }
+
+    async fn read_object(&self, key: &str) -> Result<Option<Vec<u8>>, ColdStorageError> {
+        let object = match self.store.get(&Path::from(key)).await {
+            Ok(object) => object,
+            Err(object_store::Error::NotFound { .. }) => return Ok(None),
+            Err(error) => return Err(backend(error)),
+        };
+        let bytes = object.bytes().await.map_err(backend)?;
+        Ok(Some(bytes.to_vec()))
+    }
+}
+
+#[cfg(test)]
+mod object_store_cold_storage_tests {
+    use super::*;
+    use object_store::local::LocalFileSystem;
+    use object_store::memory::InMemory;
+    use rstest::rstest;
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_append_replace_and_remove_line_objects() {
+        let storage = ObjectStoreColdStorage::new(Arc::new(InMemory::new()));
+
+        storage
+            .append_lines("events/a.jsonl", vec!["1".into()])
+            .await
+            .unwrap();
+        storage
+            .append_lines("events/a.jsonl", vec!["2".into(), "3".into()])
+            .await
+            .unwrap();
+        assert_eq!(
+            storage.read_lines("events/a.jsonl").await.unwrap(),
+            vec!["1", "2", "3"]
+        );
+
+        storage
+            .write_lines("events/a.jsonl", vec!["4".into()])
+            .await
+            .unwrap();
+        assert_eq!(
+            storage.read_lines("events/a.jsonl").await.unwrap(),
+            vec!["4"]
+        );
+
+        storage.write_lines("events/a.jsonl", vec![]).await.unwrap();
+        storage.write_lines("events/b.jsonl", vec![]).await.unwrap();
+        assert!(
+            storage
+                .read_lines("events/a.jsonl")
+                .await
+                .unwrap()
+                .is_empty()
+        );
+        assert_eq!(storage.read_object("events/a.jsonl").await, Ok(None));
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_keep_files_under_a_local_directory() {
+        let root = std::env::temp_dir().join(format!("cold-storage-{}", uuid::Uuid::now_v7()));
+        std::fs::create_dir_all(&root).unwrap();
+        let storage =
+            ObjectStoreColdStorage::new(Arc::new(LocalFileSystem::new_with_prefix(&root).unwrap()));
+
+        storage
+            .write_object("exports/month=2026-01/time_entries.parquet", vec![1, 2])
+            .await
+            .unwrap();
+        storage
+            .append_lines("events/_watermark", vec!["7".into()])
+            .await
+            .unwrap();
+        let object = storage
+            .read_object("exports/month=2026-01/time_entries.parquet")
+            .await;
+        let watermark = std::fs::read_to_string(root.join("events/_watermark")).unwrap();
+        std::fs::remove_dir_all(&root).unwrap();
+
+        assert_eq!(object, Ok(Some(vec![1, 2])));
+        assert_eq!(watermark, "7\n");
+    }
+}
@@ -0,0 +1,108 @@
+use crate::shared::infrastructure::control_store::{
+    ControlStore, ControlStoreError, WorkerControl,
+};
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use tokio::sync::RwLock;
+
+#[derive(Default)]
+struct Inner {
+    controls: RwLock<BTreeMap<String, WorkerControl>>,
+    is_offline: AtomicBool,
+}
+
+/// Holds controls for the life of the process; a restart resumes every worker.
+#[derive(Clone, Default)]
+pub struct InMemoryControlStore {
+    inner: Arc<Inner>,
+}
+
+impl InMemoryControlStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn toggle_offline(&self) {
+        self.inner.is_offline.fetch_xor(true, Ordering::SeqCst);
+    }
+
+    fn ensure_online(&self) -> Result<(), ControlStoreError> {
+        if self.inner.is_offline.load(Ordering::SeqCst) {
+            return Err(ControlStoreError::Backend(
+                "Control store offline".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl ControlStore for InMemoryControlStore {
+    async fn put(&self, control: WorkerControl) -> Result<(), ControlStoreError> {
+        self.ensure_online()?;
+        self.inner
+            .controls
+            .write()
+            .await
+            .insert(control.worker.clone(), control);
+        Ok(())
+    }
+
+    async fn get(&self, worker: &str) -> Result<Option<WorkerControl>, ControlStoreError> {
+        self.ensure_online()?;
+        Ok(self.inner.controls.read().await.get(worker).cloned())
+    }
+
+    async fn list(&self) -> Result<Vec<WorkerControl>, ControlStoreError> {
+        self.ensure_online()?;
+        Ok(self.inner.controls.read().await.values().cloned().collect())
+    }
+}
+
+#[cfg(test)]
+mod in_memory_control_store_tests {
+    use super::*;
+    use rstest::rstest;
+
+    fn control(worker: &str, paused: bool) -> WorkerControl {
+        WorkerControl {
+            worker: worker.to_string(),
+            paused,
+            updated_at: 0,
+            updated_by: "admin-1".to_string(),
+        }
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_keep_the_latest_control_per_worker() {
+        let store = InMemoryControlStore::new();
+        store.put(control("b", true)).await.unwrap();
+        store.put(control("a", true)).await.unwrap();
+        store.put(control("b", false)).await.unwrap();
+
+        assert_eq!(store.get("b").await.unwrap(), Some(control("b", false)));
+        assert_eq!(store.get("c").await.unwrap(), None);
+        assert_eq!(
+            store.list().await.unwrap(),
+            vec![control("a", true), control("b", false)]
+        );
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_fail_when_offline() {
+        let store = InMemoryControlStore::new();
+        store.toggle_offline();
+
+        assert!(store.put(control("a", true)).await.is_err());
+        assert!(store.list().await.is_err());
+        assert_eq!(
+            store.get("a").await,
+            Err(ControlStoreError::Backend(
+                "Control store offline".to_string()
+            ))
+        );
+    }
+}
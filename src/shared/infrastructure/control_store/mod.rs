@@ -0,0 +1,178 @@
+use async_trait::async_trait;
+use std::sync::Arc;
+use std::time::Duration;
+use thiserror::Error;
+use tokio::sync::broadcast;
+
+#[derive(Debug, Clone, Error, PartialEq, Eq)]
+pub enum ControlStoreError {
+    #[error("backend error: {0}")]
+    Backend(String),
+}
+
+/// An operator's switch for one background worker. Times are epoch milliseconds.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct WorkerControl {
+    /// `projector_worker` or `outbox_relay_worker` of the worker it controls.
+    pub worker: String,
+    pub paused: bool,
+    pub updated_at: i64,
+    pub updated_by: String,
+}
+
+/// Control-plane flags shared by every instance (a Postgres table, a Redis hash), so a pause
+/// holds across instances and restarts.
+#[async_trait]
+pub trait ControlStore: Send + Sync {
+    /// Replaces the control of `control.worker`.
+    async fn put(&self, control: WorkerControl) -> Result<(), ControlStoreError>;
+
+    async fn get(&self, worker: &str) -> Result<Option<WorkerControl>, ControlStoreError>;
+
+    /// By worker.
+    async fn list(&self) -> Result<Vec<WorkerControl>, ControlStoreError>;
+}
+
+pub type SharedControlStore = Arc<dyn ControlStore>;
+
+pub fn projector_worker(name: &str) -> String {
+    format!("projector:{name}")
+}
+
+pub fn outbox_relay_worker(topic: &str) -> String {
+    format!("outbox_relay:{topic}")
+}
+
+/// A worker's view of its own control. Workers poll it between units of work, so a pause
+/// takes effect within a poll of being set. A control store that cannot be read leaves the
+/// worker running: the switch is for remediation, not a reason to stop.
+#[derive(Clone)]
+pub struct PauseSwitch {
+    store: SharedControlStore,
+    worker: String,
+    poll: Duration,
+}
+
+impl PauseSwitch {
+    pub fn new(store: SharedControlStore, worker: impl Into<String>) -> Self {
+        Self {
+            store,
+            worker: worker.into(),
+            poll: Duration::from_secs(1),
+        }
+    }
+
+    /// How often a paused worker checks whether it was resumed.
+    pub fn with_poll(mut self, poll: Duration) -> Self {
+        self.poll = poll;
+        self
+    }
+
+    pub fn poll(&self) -> Duration {
+        self.poll
+    }
+
+    pub async fn is_paused(&self) -> bool {
+        match self.store.get(&self.worker).await {
+            Ok(control) => control.is_some_and(|control| control.paused),
+            Err(reason) => {
+                tracing::warn!(%reason, worker = %self.worker, "control store unavailable");
+                false
+            }
+        }
+    }
+
+    /// Waits until the worker is resumed, discarding whatever `receiver` gets meanwhile so the
+    /// channel never lags; the worker catches up from its checkpoint afterwards. Returns
+    /// `false` when the channel closed while paused.
+    pub async fn wait_discarding<T: Clone>(&self, receiver: &mut broadcast::Receiver<T>) -> bool {
+        tracing::info!(worker = %self.worker, "paused");
+        loop {
+            loop {
+                match receiver.try_recv() {
+                    Ok(_) | Err(broadcast::error::TryRecvError::Lagged(_)) => {}
+                    Err(broadcast::error::TryRecvError::Empty) => break,
+                    Err(broadcast::error::TryRecvError::Closed) => return false,
+                }
+            }
+            if !self.is_paused().await {
+                tracing::info!(worker = %self.worker, "resumed");
+                return true;
+            }
+            tokio::time::sleep(self.poll).await;
+        }
+    }
+}
+
+pub mod in_memory;
+
+#[cfg(test)]
+mod pause_switch_tests {
+    use super::*;
+    use crate::shared::infrastructure::control_store::in_memory::InMemoryControlStore;
+    use rstest::rstest;
+
+    fn control(paused: bool) -> WorkerControl {
+        WorkerControl {
+            worker: "projector:p".to_string(),
+            paused,
+            updated_at: 0,
+            updated_by: "admin-1".to_string(),
+        }
+    }
+
+    fn switch(store: &InMemoryControlStore) -> PauseSwitch {
+        PauseSwitch::new(Arc::new(store.clone()), "projector:p").with_poll(Duration::from_millis(5))
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_follow_the_stored_control_and_fail_open() {
+        let store = InMemoryControlStore::new();
+        let switch = switch(&store);
+        assert!(!switch.is_paused().await);
+
+        store.put(control(true)).await.unwrap();
+        assert!(switch.is_paused().await);
+
+        store.toggle_offline();
+        assert!(!switch.is_paused().await);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_drain_the_channel_until_resumed() {
+        let store = InMemoryControlStore::new();
+        store.put(control(true)).await.unwrap();
+        let (sender, mut receiver) = broadcast::channel::<u32>(2);
+        let waiting = tokio::spawn({
+            let switch = switch(&store);
+            async move {
+                let resumed = switch.wait_discarding(&mut receiver).await;
+                (resumed, receiver)
+            }
+        });
+        for n in 0..10 {
+            sender.send(n).unwrap();
+            tokio::time::sleep(Duration::from_millis(2)).await;
+        }
+
+        store.put(control(false)).await.unwrap();
+        let (resumed, mut receiver) = waiting.await.unwrap();
+
+        assert!(resumed);
+        sender.send(10).unwrap();
+        assert_eq!(receiver.recv().await.unwrap(), 10);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_stop_waiting_when_the_channel_closes() {
+        let store = InMemoryControlStore::new();
+        store.put(control(true)).await.unwrap();
+        let (sender, mut receiver) = broadcast::channel::<u32>(2);
+        drop(sender);
+
+        assert!(!switch(&store).wait_discarding(&mut receiver).await);
+    }
+}
@@ -0,0 +1,151 @@
+// The deadline of the request being handled, for adapters to honor without it being threaded
+// through every handler and port.
+//
+// The HTTP layer runs each request inside `scope`; adapters wrap their waits (locks, backend
+// calls) in `within`, which refuses to start once the deadline has passed and cancels what is
+// still pending when it does. An adapter giving up marks the deadline exceeded, so the layer
+// can tell a backend that ran out of time from one that failed. Outside a request, such as in
+// projectors and jobs, there is no deadline and `within` waits as long as it takes. Cleanup
+// that must still happen after the deadline ran out, like undoing a half-written change, runs
+// under `with_grace` instead.
+
+use std::future::Future;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+use thiserror::Error;
+use tokio::time::Instant;
+
+tokio::task_local! {
+    static CURRENT: Deadline;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Error)]
+#[error("deadline exceeded")]
+pub struct DeadlineExceeded;
+
+#[derive(Debug, Clone)]
+pub struct Deadline {
+    at: Instant,
+    exceeded: Arc<AtomicBool>,
+}
+
+impl Deadline {
+    pub fn at(at: Instant) -> Self {
+        Self {
+            at,
+            exceeded: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    pub fn instant(&self) -> Instant {
+        self.at
+    }
+
+    /// Whether an adapter gave up on this deadline.
+    pub fn is_exceeded(&self) -> bool {
+        self.exceeded.load(Ordering::SeqCst)
+    }
+}
+
+/// Runs `future` with `deadline` as the current deadline.
+pub async fn scope<F: Future>(deadline: Deadline, future: F) -> F::Output {
+    CURRENT.scope(deadline, future).await
+}
+
+pub fn current() -> Option<Deadline> {
+    CURRENT.try_with(Deadline::clone).ok()
+}
+
+/// Awaits `future` if the current deadline allows: not at all once it has passed, and no
+/// longer than until it passes.
+pub async fn within<F: Future>(future: F) -> Result<F::Output, DeadlineExceeded> {
+    let Some(deadline) = current() else {
+        return Ok(future.await);
+    };
+    if Instant::now() < deadline.at
+        && let Ok(output) = tokio::time::timeout_at(deadline.at, future).await
+    {
+        return Ok(output);
+    }
+    deadline.exceeded.store(true, Ordering::SeqCst);
+    Err(DeadlineExceeded)
+}
+
+/// Runs `future` under a fresh deadline `grace` from now in place of the current one, which
+/// may already have passed. Without a current deadline it waits as long as it takes.
+pub async fn with_grace<F: Future>(grace: Duration, future: F) -> F::Output {
+    if current().is_none() {
+        return future.await;
+    }
+    scope(Deadline::at(Instant::now() + grace), future).await
+}
+
+#[cfg(test)]
+mod deadline_tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_wait_as_long_as_it_takes_without_a_deadline() {
+        let output = within(async {
+            tokio::time::sleep(Duration::from_millis(5)).await;
+            1
+        })
+        .await;
+
+        assert_eq!(output, Ok(1));
+        assert!(current().is_none());
+    }
+
+    #[rstest]
+    #[case::in_time(Duration::from_secs(5), Ok(1), false)]
+    #[case::too_slow(Duration::from_millis(5), Err(DeadlineExceeded), true)]
+    #[tokio::test]
+    async fn it_should_cancel_what_outlives_the_deadline(
+        #[case] budget: Duration,
+        #[case] expected: Result<u32, DeadlineExceeded>,
+        #[case] exceeded: bool,
+    ) {
+        let deadline = Deadline::at(Instant::now() + budget);
+
+        let output = scope(
+            deadline.clone(),
+            within(async {
+                tokio::time::sleep(Duration::from_millis(50)).await;
+                1
+            }),
+        )
+        .await;
+
+        assert_eq!(output, expected);
+        assert_eq!(deadline.is_exceeded(), exceeded);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_not_start_once_the_deadline_has_passed() {
+        let deadline = Deadline::at(Instant::now());
+
+        let output = scope(deadline.clone(), within(async { 1 })).await;
+
+        assert_eq!(output, Err(DeadlineExceeded));
+        assert!(deadline.is_exceeded());
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_give_cleanup_its_own_deadline_once_the_current_one_has_passed() {
+        let deadline = Deadline::at(Instant::now());
+
+        let output = scope(
+            deadline.clone(),
+            with_grace(Duration::from_secs(5), within(async { 1 })),
+        )
+        .await;
+
+        assert_eq!(output, Ok(1));
+        assert!(!deadline.is_exceeded());
+    }
+}
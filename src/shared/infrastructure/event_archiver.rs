@@ -0,0 +1,269 @@
+// Copies the global event feed into cold storage for analytics and disaster recovery.
+//
+// Events are written as JSONL, partitioned into fixed blocks of global positions
+// (`{prefix}/{block_start:020}.jsonl`). The archiver keeps its own watermark under
+// `{prefix}/_watermark`, independent of any projector checkpoint. A partition is rewritten
+// with everything below the batch start kept, so a run that dies before moving the
+// watermark is simply repeated without duplicating lines. The job runner runs it on a
+// schedule, see `archive_events_job`.
+
+use crate::shared::infrastructure::cold_storage::ColdStorage;
+use crate::shared::infrastructure::event_store::{EventLog, SharedEventLog, StoredEvent};
+use std::collections::BTreeMap;
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct ArchivedEvent<E> {
+    pub global_position: u64,
+    pub stream_id: String,
+    pub stream_version: i64,
+    pub event: E,
+}
+
+impl<E> From<StoredEvent<E>> for ArchivedEvent<E> {
+    fn from(stored: StoredEvent<E>) -> Self {
+        Self {
+            global_position: stored.global_position,
+            stream_id: stored.stream_id,
+            stream_version: stored.stream_version,
+            event: stored.event,
+        }
+    }
+}
+
+pub struct EventArchiver<Event, TColdStorage>
+where
+    Event: Clone + Send + Sync + 'static,
+{
+    event_store: SharedEventLog<Event>,
+    cold_storage: TColdStorage,
+    prefix: String,
+    partition_size: u64,
+}
+
+impl<Event, TColdStorage> EventArchiver<Event, TColdStorage>
+where
+    Event: Clone + Send + Sync + serde::Serialize + 'static,
+    TColdStorage: ColdStorage + 'static,
+{
+    pub fn new(
+        event_store: SharedEventLog<Event>,
+        cold_storage: TColdStorage,
+        prefix: impl Into<String>,
+        partition_size: u64,
+    ) -> Self {
+        Self {
+            event_store,
+            cold_storage,
+            prefix: prefix.into(),
+            partition_size: partition_size.max(1),
+        }
+    }
+
+    pub fn partition_key(&self, global_position: u64) -> String {
+        let block_start = global_position - global_position % self.partition_size;
+        format!("{}/{block_start:020}.jsonl", self.prefix)
+    }
+
+    fn watermark_key(&self) -> String {
+        format!("{}/_watermark", self.prefix)
+    }
+
+    /// The next global position to archive.
+    pub async fn watermark(&self) -> anyhow::Result<u64> {
+        let lines = self.cold_storage.read_lines(&self.watermark_key()).await?;
+        match lines.first() {
+            Some(line) => Ok(line.parse()?),
+            None => Ok(0),
+        }
+    }
+
+    /// Archives every event past the watermark. Returns how many events were written.
+    pub async fn archive_pending(&self) -> anyhow::Result<u64> {
+        let watermark = self.watermark().await?;
+        let pending = self.event_store.load_all_from(watermark).await?;
+        let Some(last) = pending.last() else {
+            return Ok(0);
+        };
+        let next_watermark = last.global_position + 1;
+        let archived = pending.len() as u64;
+
+        let mut partitions: BTreeMap<String, Vec<String>> = BTreeMap::new();
+        for stored in pending {
+            let line = serde_json::to_string(&ArchivedEvent::from(stored.clone()))
+                .expect("events serialize to JSON");
+            partitions
+                .entry(self.partition_key(stored.global_position))
+                .or_default()
+                .push(line);
+        }
+        for (key, lines) in partitions {
+            let mut kept = Vec::new();
+            for line in self.cold_storage.read_lines(&key).await? {
+                let position = serde_json::from_str::<ArchivedEvent<serde_json::Value>>(&line)?
+                    .global_position;
+                if position < watermark {
+                    kept.push(line);
+                }
+            }
+            kept.extend(lines);
+            self.cold_storage.write_lines(&key, kept).await?;
+        }
+        self.cold_storage
+            .write_lines(&self.watermark_key(), vec![next_watermark.to_string()])
+            .await?;
+        Ok(archived)
+    }
+}
+
+#[cfg(test)]
+mod event_archiver_tests {
+    use super::*;
+    use crate::shared::infrastructure::cold_storage::in_memory::InMemoryColdStorage;
+    use crate::shared::infrastructure::event_store::EventStore;
+    use crate::shared::infrastructure::event_store::in_memory::InMemoryEventStore;
+    use rstest::rstest;
+    use std::sync::Arc;
+
+    async fn store_with_events(count: u32) -> InMemoryEventStore<u32> {
+        let event_store = InMemoryEventStore::<u32>::new();
+        for i in 0..count {
+            event_store
+                .append("Counter-1", i as i64, &[i])
+                .await
+                .unwrap();
+        }
+        event_store
+    }
+
+    async fn archived_positions(cold_storage: &InMemoryColdStorage, key: &str) -> Vec<u64> {
+        cold_storage
+            .read_lines(key)
+            .await
+            .unwrap()
+            .iter()
+            .map(|line| {
+                serde_json::from_str::<ArchivedEvent<u32>>(line)
+                    .unwrap()
+                    .global_position
+            })
+            .collect()
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_write_events_into_position_partitions() {
+        let event_store = store_with_events(5).await;
+        let cold_storage = InMemoryColdStorage::new();
+        let archiver = EventArchiver::new(Arc::new(event_store), cold_storage.clone(), "events", 2);
+
+        assert_eq!(archiver.archive_pending().await.unwrap(), 5);
+
+        assert_eq!(archiver.watermark().await.unwrap(), 5);
+        assert_eq!(
+            cold_storage.keys().await,
+            vec![
+                "events/00000000000000000000.jsonl",
+                "events/00000000000000000002.jsonl",
+                "events/00000000000000000004.jsonl",
+                "events/_watermark",
+            ]
+        );
+        let line = &cold_storage
+            .read_lines("events/00000000000000000002.jsonl")
+            .await
+            .unwrap()[1];
+        assert_eq!(
+            serde_json::from_str::<ArchivedEvent<u32>>(line).unwrap(),
+            ArchivedEvent {
+                global_position: 3,
+                stream_id: "Counter-1".to_string(),
+                stream_version: 4,
+                event: 3,
+            }
+        );
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_continue_from_its_watermark() {
+        let event_store = store_with_events(3).await;
+        let cold_storage = InMemoryColdStorage::new();
+        let archiver = EventArchiver::new(
+            Arc::new(event_store.clone()),
+            cold_storage.clone(),
+            "events",
+            4,
+        );
+        archiver.archive_pending().await.unwrap();
+
+        assert_eq!(archiver.archive_pending().await.unwrap(), 0);
+        event_store.append("Counter-1", 3, &[3, 4]).await.unwrap();
+        assert_eq!(archiver.archive_pending().await.unwrap(), 2);
+
+        assert_eq!(
+            archived_positions(&cold_storage, "events/00000000000000000000.jsonl").await,
+            vec![0, 1, 2, 3]
+        );
+        assert_eq!(
+            archived_positions(&cold_storage, "events/00000000000000000004.jsonl").await,
+            vec![4]
+        );
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_not_duplicate_lines_when_a_run_is_repeated() {
+        let event_store = store_with_events(3).await;
+        let cold_storage = InMemoryColdStorage::new();
+        let archiver =
+            EventArchiver::new(Arc::new(event_store), cold_storage.clone(), "events", 10);
+        archiver.archive_pending().await.unwrap();
+        // Simulate a crash between writing partitions and moving the watermark.
+        cold_storage
+            .write_lines("events/_watermark", vec!["1".to_string()])
+            .await
+            .unwrap();
+
+        assert_eq!(archiver.archive_pending().await.unwrap(), 2);
+
+        assert_eq!(
+            archived_positions(&cold_storage, "events/00000000000000000000.jsonl").await,
+            vec![0, 1, 2]
+        );
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_fail_without_moving_the_watermark_when_storage_fails() {
+        let event_store = store_with_events(2).await;
+        let cold_storage = InMemoryColdStorage::new();
+        let archiver = EventArchiver::new(
+            Arc::new(event_store.clone()),
+            cold_storage.clone(),
+            "events",
+            0,
+        );
+
+        event_store.toggle_offline();
+        assert!(archiver.archive_pending().await.is_err());
+        event_store.toggle_offline();
+
+        cold_storage
+            .write_lines(
+                "events/00000000000000000000.jsonl",
+                vec!["corrupt".to_string()],
+            )
+            .await
+            .unwrap();
+        assert!(archiver.archive_pending().await.is_err());
+
+        cold_storage
+            .write_lines("events/_watermark", vec!["not a number".to_string()])
+            .await
+            .unwrap();
+        assert!(archiver.watermark().await.is_err());
+
+        cold_storage.toggle_offline();
+        assert!(archiver.archive_pending().await.is_err());
+    }
+}
@@ -0,0 +1,81 @@
+use crate::shared::infrastructure::event_bus::EventBus;
+use crate::shared::infrastructure::event_store::StoredEvent;
+use tokio::sync::broadcast;
+
+/// Event bus on a tokio broadcast channel. Subscribers that fall more than `capacity` events
+/// behind get `RecvError::Lagged` and should rebuild from the log.
+#[derive(Clone)]
+pub struct InMemoryEventBus<E> {
+    sender: broadcast::Sender<StoredEvent<E>>,
+}
+
+impl<E: Clone + Send + Sync + 'static> InMemoryEventBus<E> {
+    pub fn new(capacity: usize) -> Self {
+        let (sender, _) = broadcast::channel(capacity);
+        Self { sender }
+    }
+
+    /// A bus publishing on an existing channel, next to the other senders it already has.
+    pub fn from_sender(sender: broadcast::Sender<StoredEvent<E>>) -> Self {
+        Self { sender }
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<StoredEvent<E>> {
+        self.sender.subscribe()
+    }
+}
+
+impl<E: Clone + Send + Sync + 'static> EventBus<E> for InMemoryEventBus<E> {
+    fn publish(&self, events: Vec<StoredEvent<E>>) {
+        for event in events {
+            // Nobody subscribed yet: there is nobody to tell.
+            let _ = self.sender.send(event);
+        }
+    }
+}
+
+#[cfg(test)]
+mod in_memory_event_bus_tests {
+    use super::*;
+    use crate::shared::infrastructure::event_bus::stored_events;
+    use crate::shared::infrastructure::event_store::AppendResult;
+
+    #[tokio::test]
+    async fn it_should_deliver_published_events_in_order() {
+        let bus = InMemoryEventBus::<u32>::new(16);
+        let mut receiver = bus.subscribe();
+        let appended = AppendResult {
+            next_version: 3,
+            global_positions: vec![7, 8],
+        };
+
+        bus.publish(stored_events("Counter-1", 1, &[10, 20], &appended));
+
+        let first = receiver.recv().await.unwrap();
+        let second = receiver.recv().await.unwrap();
+        assert_eq!(
+            (first.global_position, first.stream_version, first.event),
+            (7, 2, 10)
+        );
+        assert_eq!(
+            (second.global_position, second.stream_version, second.event),
+            (8, 3, 20)
+        );
+        assert_eq!(second.stream_id, "Counter-1");
+    }
+
+    #[test]
+    fn it_should_publish_without_subscribers() {
+        let bus = InMemoryEventBus::<u32>::new(16);
+
+        bus.publish(stored_events(
+            "Counter-1",
+            0,
+            &[1],
+            &AppendResult {
+                next_version: 1,
+                global_positions: vec![0],
+            },
+        ));
+    }
+}
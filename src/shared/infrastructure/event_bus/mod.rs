@@ -0,0 +1,34 @@
+use std::sync::Arc;
+
+use crate::shared::infrastructure::event_store::{AppendResult, StoredEvent};
+
+/// Where command handlers announce the events they appended, for subscribers in the same
+/// process such as projectors. Publishing never fails the command: a subscriber that misses
+/// events catches up from the event log.
+pub trait EventBus<E>: Send + Sync {
+    fn publish(&self, events: Vec<StoredEvent<E>>);
+}
+
+pub type SharedEventBus<E> = Arc<dyn EventBus<E>>;
+
+/// `events`, appended to `stream_id` at `starting_version`, as the append stored them.
+pub fn stored_events<E: Clone>(
+    stream_id: &str,
+    starting_version: i64,
+    events: &[E],
+    appended: &AppendResult,
+) -> Vec<StoredEvent<E>> {
+    events
+        .iter()
+        .zip(&appended.global_positions)
+        .zip(starting_version + 1..)
+        .map(|((event, global_position), stream_version)| StoredEvent {
+            global_position: *global_position,
+            stream_id: stream_id.to_string(),
+            stream_version,
+            event: event.clone(),
+        })
+        .collect()
+}
+
+pub mod in_memory;
@@ -0,0 +1,157 @@
+// Write buffer for bulk imports.
+//
+// Appends are collected and sent to the store through `append_many` once the buffered event
+// count reaches the batch size, so importing thousands of entries takes a handful of
+// round-trips instead of one per stream. Callers must `flush` after the last push. On an
+// atomic adapter a failed flush writes nothing; the batch is dropped either way and the error
+// returned, so the importer decides whether to reload and retry.
+
+use crate::shared::infrastructure::event_store::{EventStore, EventStoreError, StreamAppend};
+
+pub const DEFAULT_MAX_BATCH_EVENTS: usize = 500;
+
+pub struct BufferedAppender<Event, TEventStore> {
+    event_store: TEventStore,
+    pending: Vec<StreamAppend<Event>>,
+    pending_events: usize,
+    max_batch_events: usize,
+}
+
+impl<Event, TEventStore> BufferedAppender<Event, TEventStore>
+where
+    Event: Clone + Send + Sync + 'static,
+    TEventStore: EventStore<Event>,
+{
+    pub fn new(event_store: TEventStore) -> Self {
+        Self::with_max_batch_events(event_store, DEFAULT_MAX_BATCH_EVENTS)
+    }
+
+    pub fn with_max_batch_events(event_store: TEventStore, max_batch_events: usize) -> Self {
+        Self {
+            event_store,
+            pending: Vec::new(),
+            pending_events: 0,
+            max_batch_events: max_batch_events.max(1),
+        }
+    }
+
+    pub fn pending_events(&self) -> usize {
+        self.pending_events
+    }
+
+    /// Buffers an append and flushes once the batch size is reached.
+    /// Returns how many events were written by this call.
+    pub async fn push(
+        &mut self,
+        stream_id: impl Into<String>,
+        expected_version: i64,
+        events: Vec<Event>,
+    ) -> Result<usize, EventStoreError> {
+        self.pending_events += events.len();
+        self.pending
+            .push(StreamAppend::new(stream_id, expected_version, events));
+        if self.pending_events >= self.max_batch_events {
+            return self.flush().await;
+        }
+        Ok(0)
+    }
+
+    /// Writes everything buffered in one `append_many`. Returns how many events were written.
+    pub async fn flush(&mut self) -> Result<usize, EventStoreError> {
+        if self.pending.is_empty() {
+            return Ok(0);
+        }
+        let batch = std::mem::take(&mut self.pending);
+        let written = std::mem::take(&mut self.pending_events);
+        self.event_store.append_many(batch).await?;
+        Ok(written)
+    }
+}
+
+#[cfg(test)]
+mod buffered_appender_tests {
+    use super::*;
+    use crate::shared::infrastructure::event_store::in_memory::InMemoryEventStore;
+    use crate::shared::infrastructure::event_store::{AppendResult, LoadedStream};
+    use rstest::rstest;
+
+    /// Relies on the trait's default, non-atomic `append_many`.
+    struct PerStreamAppends(InMemoryEventStore<u32>);
+
+    #[async_trait::async_trait]
+    impl EventStore<u32> for PerStreamAppends {
+        async fn load(&self, stream_id: &str) -> Result<LoadedStream<u32>, EventStoreError> {
+            self.0.load(stream_id).await
+        }
+
+        async fn append(
+            &self,
+            stream_id: &str,
+            expected_version: i64,
+            new_events: &[u32],
+        ) -> Result<AppendResult, EventStoreError> {
+            self.0.append(stream_id, expected_version, new_events).await
+        }
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_hold_appends_until_the_batch_is_full() {
+        let store = InMemoryEventStore::<u32>::new();
+        let mut appender = BufferedAppender::with_max_batch_events(store.clone(), 3);
+
+        assert_eq!(appender.push("s1", 0, vec![1]).await.unwrap(), 0);
+        assert_eq!(appender.push("s2", 0, vec![2]).await.unwrap(), 0);
+        assert_eq!(appender.pending_events(), 2);
+        assert!(store.load_all_from(0).await.unwrap().is_empty());
+
+        assert_eq!(appender.push("s1", 1, vec![3]).await.unwrap(), 3);
+        assert_eq!(appender.pending_events(), 0);
+        assert_eq!(store.load("s1").await.unwrap().events, vec![1, 3]);
+        assert_eq!(store.load("s2").await.unwrap().events, vec![2]);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_flush_the_remainder_on_demand() {
+        let store = InMemoryEventStore::<u32>::new();
+        let mut appender = BufferedAppender::new(store.clone());
+
+        assert_eq!(appender.flush().await.unwrap(), 0);
+        appender.push("s1", 0, vec![1, 2]).await.unwrap();
+        assert_eq!(appender.flush().await.unwrap(), 2);
+        assert_eq!(store.load("s1").await.unwrap().version, 2);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_drop_a_rejected_batch_and_report_the_error() {
+        let store = InMemoryEventStore::<u32>::new();
+        store.append("s1", 0, &[0]).await.unwrap();
+        let mut appender = BufferedAppender::with_max_batch_events(store.clone(), 0);
+
+        let result = appender.push("s1", 0, vec![1]).await;
+
+        assert!(matches!(
+            result,
+            Err(EventStoreError::VersionMismatch { .. })
+        ));
+        assert_eq!(appender.pending_events(), 0);
+        assert_eq!(store.load("s1").await.unwrap().events, vec![0]);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_fall_back_to_appending_stream_by_stream() {
+        let store = InMemoryEventStore::<u32>::new();
+        let mut appender = BufferedAppender::new(PerStreamAppends(store.clone()));
+        appender.push("s1", 0, vec![1]).await.unwrap();
+        appender.push("s2", 5, vec![2]).await.unwrap();
+        appender.push("s3", 0, vec![3]).await.unwrap();
+
+        assert!(appender.flush().await.is_err());
+
+        assert_eq!(store.load("s1").await.unwrap().events, vec![1]);
+        assert_eq!(store.load("s3").await.unwrap().version, 0);
+    }
+}
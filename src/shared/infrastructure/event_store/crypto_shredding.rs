@@ -0,0 +1,654 @@
+// Event store decorator that encrypts personal data with per-subject keys (crypto-shredding).
+//
+// On append every personal data field (see `PersonalData`) is encrypted with the key of the
+// stream's owner and stored as `pii:{key_id}:{ciphertext}`. The owner is the subject the
+// event starting the stream names; later appends find their key through the key id of the
+// stream's first stored event. On load the field is decrypted; once the owner is forgotten
+// the key is gone and the field reads as `FORGOTTEN`, as do fields appended after that.
+// Values without the prefix predate encryption and pass through unchanged.
+//
+// The global log is revealed the same way, so projectors rebuilding after a subject was
+// forgotten project the forgotten marker. `reveal_stored` opens the live feed for them.
+
+use crate::shared::core::personal_data::PersonalData;
+use crate::shared::infrastructure::event_store::{
+    AppendResult, EventLog, EventStore, EventStoreError, LoadedStream, StoredEvent, StreamAppend,
+};
+use crate::shared::infrastructure::key_store::{DataKey, KeyStore, KeyStoreError};
+use aes_gcm::aead::{Aead, AeadCore, KeyInit, OsRng, Payload};
+use aes_gcm::{Aes256Gcm, Nonce};
+use async_trait::async_trait;
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use std::collections::HashMap;
+use std::collections::hash_map::Entry;
+
+pub const ENCRYPTED_PREFIX: &str = "pii:";
+pub const FORGOTTEN: &str = "[forgotten]";
+
+/// Symmetric cipher used for personal data fields. Ciphertexts must be plain strings.
+pub trait FieldCipher: Send + Sync {
+    fn encrypt(&self, key: &DataKey, plaintext: &str) -> String;
+
+    /// `None` when the ciphertext does not decrypt under `key`.
+    fn decrypt(&self, key: &DataKey, ciphertext: &str) -> Option<String>;
+}
+
+/// AES-256-GCM under the subject's data key, with a random nonce and the key id as associated
+/// data. The ciphertext is the nonce followed by the sealed value, base64 encoded.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct AesGcmFieldCipher;
+
+const NONCE_BYTES: usize = 12;
+
+impl FieldCipher for AesGcmFieldCipher {
+    fn encrypt(&self, key: &DataKey, plaintext: &str) -> String {
+        let cipher =
+            Aes256Gcm::new_from_slice(&key.material).expect("data keys should be 256 bits");
+        let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+        let sealed = cipher
+            .encrypt(
+                &nonce,
+                Payload {
+                    msg: plaintext.as_bytes(),
+                    aad: key.id.as_bytes(),
+                },
+            )
+            .expect("sealing into memory never fails");
+        STANDARD.encode([nonce.as_slice(), &sealed].concat())
+    }
+
+    fn decrypt(&self, key: &DataKey, ciphertext: &str) -> Option<String> {
+        let cipher = Aes256Gcm::new_from_slice(&key.material).ok()?;
+        let bytes = STANDARD.decode(ciphertext).ok()?;
+        if bytes.len() < NONCE_BYTES {
+            return None;
+        }
+        let (nonce, sealed) = bytes.split_at(NONCE_BYTES);
+        let plain = cipher
+            .decrypt(
+                Nonce::from_slice(nonce),
+                Payload {
+                    msg: sealed,
+                    aad: key.id.as_bytes(),
+                },
+            )
+            .ok()?;
+        String::from_utf8(plain).ok()
+    }
+}
+
+impl From<KeyStoreError> for EventStoreError {
+    fn from(error: KeyStoreError) -> Self {
+        EventStoreError::Backend(error.to_string())
+    }
+}
+
+#[derive(Clone)]
+pub struct CryptoShreddingEventStore<TInner, TKeyStore, TCipher> {
+    inner: TInner,
+    key_store: TKeyStore,
+    cipher: TCipher,
+}
+
+impl<TInner, TKeyStore, TCipher> CryptoShreddingEventStore<TInner, TKeyStore, TCipher>
+where
+    TKeyStore: KeyStore,
+    TCipher: FieldCipher,
+{
+    pub fn new(inner: TInner, key_store: TKeyStore, cipher: TCipher) -> Self {
+        Self {
+            inner,
+            key_store,
+            cipher,
+        }
+    }
+
+    /// Encrypts the personal data of `events`, each paired with the stream it goes to, under
+    /// the key of the stream's owner (see the module comment).
+    pub async fn conceal<E>(&self, events: Vec<(String, E)>) -> Result<Vec<E>, EventStoreError>
+    where
+        E: PersonalData + Clone + Send + Sync + 'static,
+        TInner: EventStore<E>,
+    {
+        let mut owners: HashMap<String, Option<DataKey>> = HashMap::new();
+        let mut concealed = Vec::with_capacity(events.len());
+        for (stream_id, event) in events {
+            if event.personal_data().is_empty() {
+                concealed.push(event);
+                continue;
+            }
+            let key = match (event.data_subject(), owners.entry(stream_id)) {
+                (Some(subject), Entry::Occupied(mut owner)) => {
+                    owner.insert(Some(self.key_store.key_for_subject(&subject).await?));
+                    owner.into_mut()
+                }
+                (Some(subject), Entry::Vacant(slot)) => {
+                    slot.insert(Some(self.key_store.key_for_subject(&subject).await?))
+                }
+                (None, Entry::Occupied(owner)) => owner.into_mut(),
+                (None, Entry::Vacant(slot)) => {
+                    let key = self.stored_owner_key(slot.key()).await?;
+                    slot.insert(key)
+                }
+            };
+            concealed.push(event.map_personal_data(&mut |value| match key {
+                Some(key) => format!(
+                    "{ENCRYPTED_PREFIX}{}:{}",
+                    key.id,
+                    self.cipher.encrypt(key, &value)
+                ),
+                None => FORGOTTEN.to_string(),
+            }));
+        }
+        Ok(concealed)
+    }
+
+    /// The key of the owner the first stored event of `stream_id` names; `None` once they are
+    /// forgotten.
+    async fn stored_owner_key<E>(&self, stream_id: &str) -> Result<Option<DataKey>, EventStoreError>
+    where
+        E: PersonalData + Clone + Send + Sync + 'static,
+        TInner: EventStore<E>,
+    {
+        let first = self.inner.load_paged(stream_id, 0, 1).await?.events;
+        let Some(first) = first.first() else {
+            return Err(EventStoreError::Backend(format!(
+                "{stream_id} names no owner to encrypt personal data for"
+            )));
+        };
+        let values = first.personal_data();
+        match values.first().map(|value| (value, split_encrypted(value))) {
+            Some((_, Some((key_id, _)))) => Ok(self.key_store.key_by_id(key_id).await?),
+            Some((value, None)) if value == FORGOTTEN => Ok(None),
+            _ => match first.data_subject() {
+                Some(subject) => Ok(Some(self.key_store.key_for_subject(&subject).await?)),
+                None => Err(EventStoreError::Backend(format!(
+                    "{stream_id} names no owner to encrypt personal data for"
+                ))),
+            },
+        }
+    }
+
+    /// Decrypts personal data fields. Read models consuming the raw feed use this too.
+    pub async fn reveal<E>(&self, events: Vec<E>) -> Result<Vec<E>, EventStoreError>
+    where
+        E: PersonalData + Clone,
+    {
+        let mut keys: HashMap<String, Option<DataKey>> = HashMap::new();
+        for value in events.iter().flat_map(PersonalData::personal_data) {
+            if let Some((key_id, _)) = split_encrypted(&value)
+                && !keys.contains_key(key_id)
+            {
+                let key = self.key_store.key_by_id(key_id).await?;
+                keys.insert(key_id.to_string(), key);
+            }
+        }
+        Ok(events
+            .into_iter()
+            .map(|event| {
+                event.map_personal_data(&mut |value| match split_encrypted(&value) {
+                    Some((key_id, ciphertext)) => keys[key_id]
+                        .as_ref()
+                        .and_then(|key| self.cipher.decrypt(key, ciphertext))
+                        .unwrap_or_else(|| FORGOTTEN.to_string()),
+                    None => value,
+                })
+            })
+            .collect())
+    }
+
+    /// `stored` with its personal data decrypted, for projectors reading the live feed.
+    pub async fn reveal_stored<E>(
+        &self,
+        stored: StoredEvent<E>,
+    ) -> Result<StoredEvent<E>, EventStoreError>
+    where
+        E: PersonalData + Clone,
+    {
+        let event = self.reveal(vec![stored.event]).await?.remove(0);
+        Ok(StoredEvent { event, ..stored })
+    }
+}
+
+fn split_encrypted(value: &str) -> Option<(&str, &str)> {
+    value.strip_prefix(ENCRYPTED_PREFIX)?.split_once(':')
+}
+
+#[async_trait]
+impl<E, TInner, TKeyStore, TCipher> EventStore<E>
+    for CryptoShreddingEventStore<TInner, TKeyStore, TCipher>
+where
+    E: PersonalData + Clone + Send + Sync + 'static,
+    TInner: EventStore<E>,
+    TKeyStore: KeyStore,
+    TCipher: FieldCipher,
+{
+    async fn load(&self, stream_id: &str) -> Result<LoadedStream<E>, EventStoreError> {
+        let stream = self.inner.load(stream_id).await?;
+        Ok(LoadedStream {
+            events: self.reveal(stream.events).await?,
+            version: stream.version,
+        })
+    }
+
+    async fn load_paged(
+        &self,
+        stream_id: &str,
+        from_version: i64,
+        limit: usize,
+    ) -> Result<LoadedStream<E>, EventStoreError> {
+        let page = self
+            .inner
+            .load_paged(stream_id, from_version, limit)
+            .await?;
+        Ok(LoadedStream {
+            events: self.reveal(page.events).await?,
+            version: page.version,
+        })
+    }
+
+    async fn append(
+        &self,
+        stream_id: &str,
+        expected_version: i64,
+        new_events: &[E],
+    ) -> Result<AppendResult, EventStoreError> {
+        let concealed = self
+            .conceal(
+                new_events
+                    .iter()
+                    .map(|event| (stream_id.to_string(), event.clone()))
+                    .collect(),
+            )
+            .await?;
+        self.inner
+            .append(stream_id, expected_version, &concealed)
+            .await
+    }
+
+    async fn append_many(
+        &self,
+        batch: Vec<StreamAppend<E>>,
+    ) -> Result<Vec<AppendResult>, EventStoreError> {
+        let mut concealed = Vec::with_capacity(batch.len());
+        for append in batch {
+            let events = append
+                .events
+                .iter()
+                .map(|event| (append.stream_id.clone(), event.clone()))
+                .collect();
+            concealed.push(StreamAppend {
+                events: self.conceal(events).await?,
+                ..append
+            });
+        }
+        self.inner.append_many(concealed).await
+    }
+}
+
+#[async_trait]
+impl<E, TInner, TKeyStore, TCipher> EventLog<E>
+    for CryptoShreddingEventStore<TInner, TKeyStore, TCipher>
+where
+    E: PersonalData + Clone + Send + Sync + 'static,
+    TInner: EventLog<E>,
+    TKeyStore: KeyStore,
+    TCipher: FieldCipher,
+{
+    async fn load_all_from(&self, from: u64) -> Result<Vec<StoredEvent<E>>, EventStoreError> {
+        let stored = self.inner.load_all_from(from).await?;
+        let events = self
+            .reveal(stored.iter().map(|stored| stored.event.clone()).collect())
+            .await?;
+        Ok(stored
+            .into_iter()
+            .zip(events)
+            .map(|(stored, event)| StoredEvent { event, ..stored })
+            .collect())
+    }
+
+    async fn head(&self) -> Result<u64, EventStoreError> {
+        self.inner.head().await
+    }
+
+    async fn compact(
+        &self,
+        stream_id: &str,
+        expected_version: i64,
+        tombstone: E,
+    ) -> Result<usize, EventStoreError> {
+        let tombstone = self
+            .conceal(vec![(stream_id.to_string(), tombstone)])
+            .await?
+            .remove(0);
+        self.inner
+            .compact(stream_id, expected_version, tombstone)
+            .await
+    }
+
+    /// Imported events are concealed like appended ones.
+    async fn import(&self, events: Vec<StoredEvent<E>>) -> Result<(), EventStoreError> {
+        let concealed = self
+            .conceal(
+                events
+                    .iter()
+                    .map(|stored| (stored.stream_id.clone(), stored.event.clone()))
+                    .collect(),
+            )
+            .await?;
+        self.inner
+            .import(
+                events
+                    .into_iter()
+                    .zip(concealed)
+                    .map(|(stored, event)| StoredEvent { event, ..stored })
+                    .collect(),
+            )
+            .await
+    }
+}
+
+#[cfg(test)]
+mod crypto_shredding_event_store_tests {
+    use super::*;
+    use crate::modules::time_entries::core::events::TimeEntryEvent;
+    use crate::modules::time_entries::core::events::v1::time_entry_approved::TimeEntryApprovedV1;
+    use crate::modules::time_entries::core::events::v1::time_entry_registered::TimeEntryRegisteredV1;
+    use crate::shared::infrastructure::event_store::in_memory::InMemoryEventStore;
+    use crate::shared::infrastructure::key_store::in_memory::InMemoryKeyStore;
+    use crate::tests::fixtures::crypto::XorHexCipher;
+    use crate::tests::fixtures::events::time_entry_initiated_v1::make_time_entry_initiated_v1_event;
+    use rstest::rstest;
+
+    const STREAM_ID: &str = "TimeEntry-te-fixed-0001";
+
+    fn initiated() -> TimeEntryEvent {
+        TimeEntryEvent::TimeEntryInitiatedV1(make_time_entry_initiated_v1_event())
+    }
+
+    fn approved_by(approver: &str) -> TimeEntryEvent {
+        TimeEntryEvent::TimeEntryApprovedV1(TimeEntryApprovedV1 {
+            time_entry_id: "te-fixed-0001".into(),
+            approved_at: 1_700_000_000_000,
+            approved_by: approver.into(),
+        })
+    }
+
+    fn created_by(event: &TimeEntryEvent) -> String {
+        event.personal_data().remove(0)
+    }
+
+    fn key_id(value: &str) -> &str {
+        split_encrypted(value).unwrap().0
+    }
+
+    type Store = CryptoShreddingEventStore<
+        InMemoryEventStore<TimeEntryEvent>,
+        InMemoryKeyStore,
+        XorHexCipher,
+    >;
+
+    fn setup() -> (InMemoryEventStore<TimeEntryEvent>, InMemoryKeyStore, Store) {
+        let inner = InMemoryEventStore::<TimeEntryEvent>::new();
+        let key_store = InMemoryKeyStore::new();
+        let store = CryptoShreddingEventStore::new(inner.clone(), key_store.clone(), XorHexCipher);
+        (inner, key_store, store)
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_store_personal_data_encrypted_and_load_it_decrypted() {
+        let (inner, _, store) = setup();
+
+        store.append(STREAM_ID, 0, &[initiated()]).await.unwrap();
+
+        let raw = inner.load(STREAM_ID).await.unwrap().events;
+        assert!(created_by(&raw[0]).starts_with(ENCRYPTED_PREFIX));
+        assert!(!created_by(&raw[0]).contains("user-fixed-0001"));
+        let loaded = store.load(STREAM_ID).await.unwrap();
+        assert_eq!(loaded.events, vec![initiated()]);
+        assert_eq!(loaded.version, 1);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_make_personal_data_unreadable_once_the_subject_is_forgotten() {
+        let (_, key_store, store) = setup();
+        store.append(STREAM_ID, 0, &[initiated()]).await.unwrap();
+
+        key_store.forget_subject("user-fixed-0001").await.unwrap();
+
+        let events = store.load(STREAM_ID).await.unwrap().events;
+        assert_eq!(created_by(&events[0]), FORGOTTEN);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_encrypt_every_field_under_the_key_of_the_stream_owner() {
+        let (inner, key_store, store) = setup();
+
+        store.append(STREAM_ID, 0, &[initiated()]).await.unwrap();
+        store
+            .append(STREAM_ID, 1, &[approved_by("manager-1")])
+            .await
+            .unwrap();
+
+        let owner_key = key_store.key_for_subject("user-fixed-0001").await.unwrap();
+        let raw = inner.load(STREAM_ID).await.unwrap().events;
+        let values: Vec<String> = raw.iter().flat_map(|e| e.personal_data()).collect();
+        assert_eq!(values.len(), 3);
+        assert!(values.iter().all(|value| key_id(value) == owner_key.id));
+        assert_eq!(key_store.forget_subject("manager-1").await.unwrap(), None);
+        assert_eq!(
+            store.load(STREAM_ID).await.unwrap().events[1],
+            approved_by("manager-1")
+        );
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_store_data_appended_after_the_owner_was_forgotten_as_forgotten() {
+        let (inner, key_store, store) = setup();
+        store.append(STREAM_ID, 0, &[initiated()]).await.unwrap();
+        key_store.forget_subject("user-fixed-0001").await.unwrap();
+
+        store
+            .append(STREAM_ID, 1, &[approved_by("manager-1")])
+            .await
+            .unwrap();
+
+        let raw = inner.load(STREAM_ID).await.unwrap().events;
+        assert_eq!(raw[1].personal_data(), vec![FORGOTTEN]);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_refuse_personal_data_for_a_stream_without_an_owner() {
+        let (inner, _, store) = setup();
+
+        let result = store
+            .append(STREAM_ID, 0, &[approved_by("manager-1")])
+            .await;
+
+        assert!(matches!(result, Err(EventStoreError::Backend(_))));
+        assert_eq!(inner.head().await.unwrap(), 0);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_pass_through_values_written_before_encryption() {
+        let (inner, _, store) = setup();
+        inner.append(STREAM_ID, 0, &[initiated()]).await.unwrap();
+        let registered = TimeEntryEvent::TimeEntryRegisteredV1(TimeEntryRegisteredV1 {
+            time_entry_id: "te-fixed-0001".into(),
+            occurred_at: 1,
+        });
+        store
+            .append(STREAM_ID, 1, std::slice::from_ref(&registered))
+            .await
+            .unwrap();
+
+        let events = store.load(STREAM_ID).await.unwrap().events;
+        assert_eq!(events, vec![initiated(), registered]);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_treat_undecryptable_values_as_forgotten() {
+        let (inner, key_store, store) = setup();
+        let key = key_store.key_for_subject("user-fixed-0001").await.unwrap();
+        let tampered =
+            initiated().map_personal_data(&mut |_| format!("{ENCRYPTED_PREFIX}{}:not-hex", key.id));
+        inner.append(STREAM_ID, 0, &[tampered]).await.unwrap();
+
+        let events = store.load(STREAM_ID).await.unwrap().events;
+        assert_eq!(created_by(&events[0]), FORGOTTEN);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_fail_when_the_key_store_is_offline() {
+        let (_, key_store, store) = setup();
+        store.append(STREAM_ID, 0, &[initiated()]).await.unwrap();
+        key_store.toggle_offline();
+
+        assert!(matches!(
+            store.append(STREAM_ID, 1, &[initiated()]).await,
+            Err(EventStoreError::Backend(_))
+        ));
+        assert!(matches!(
+            store.load(STREAM_ID).await,
+            Err(EventStoreError::Backend(_))
+        ));
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_propagate_inner_store_errors() {
+        let (inner, _, store) = setup();
+        inner.toggle_offline();
+
+        assert!(store.load(STREAM_ID).await.is_err());
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_reveal_the_global_log_until_the_subject_is_forgotten() {
+        let (inner, key_store, store) = setup();
+        store.append(STREAM_ID, 0, &[initiated()]).await.unwrap();
+
+        let log = store.load_all_from(0).await.unwrap();
+        assert_eq!(log[0].event, initiated());
+        assert_eq!(log[0].global_position, 0);
+        let revealed = store
+            .reveal_stored(inner.load_all_from(0).await.unwrap().remove(0))
+            .await
+            .unwrap();
+        assert_eq!(revealed.event, initiated());
+
+        key_store.forget_subject("user-fixed-0001").await.unwrap();
+        let log = store.load_all_from(0).await.unwrap();
+        assert_eq!(created_by(&log[0].event), FORGOTTEN);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_encrypt_compaction_tombstones_and_imports() {
+        let (inner, _, store) = setup();
+        store.append(STREAM_ID, 0, &[initiated()]).await.unwrap();
+
+        store.compact(STREAM_ID, 1, initiated()).await.unwrap();
+        store
+            .import(vec![StoredEvent {
+                global_position: 5,
+                stream_id: "TimeEntry-te-fixed-0002".to_string(),
+                stream_version: 1,
+                event: initiated(),
+            }])
+            .await
+            .unwrap();
+
+        for stored in inner.load_all_from(0).await.unwrap() {
+            assert!(created_by(&stored.event).starts_with(ENCRYPTED_PREFIX));
+        }
+        assert_eq!(store.head().await.unwrap(), 6);
+        assert_eq!(
+            store.load("TimeEntry-te-fixed-0002").await.unwrap().events,
+            vec![initiated()]
+        );
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_encrypt_every_stream_in_a_batch() {
+        let (inner, key_store, store) = setup();
+
+        store
+            .append_many(vec![
+                StreamAppend::new(STREAM_ID, 0, vec![initiated()]),
+                StreamAppend::new("TimeEntry-te-fixed-0002", 0, vec![initiated()]),
+            ])
+            .await
+            .unwrap();
+
+        let raw = inner.load("TimeEntry-te-fixed-0002").await.unwrap().events;
+        assert!(created_by(&raw[0]).starts_with(ENCRYPTED_PREFIX));
+        assert_eq!(
+            store.load(STREAM_ID).await.unwrap().events,
+            vec![initiated()]
+        );
+
+        key_store.toggle_offline();
+        assert!(matches!(
+            store
+                .append_many(vec![StreamAppend::new(STREAM_ID, 1, vec![initiated()])])
+                .await,
+            Err(EventStoreError::Backend(_))
+        ));
+    }
+}
+
+#[cfg(test)]
+mod aes_gcm_field_cipher_tests {
+    use super::*;
+    use rstest::rstest;
+
+    fn key(id: &str, byte: u8) -> DataKey {
+        DataKey {
+            id: id.to_string(),
+            material: vec![byte; 32],
+        }
+    }
+
+    #[test]
+    fn it_should_round_trip_under_a_fresh_nonce_each_time() {
+        let first = AesGcmFieldCipher.encrypt(&key("k-1", 1), "user-fixed-0001");
+        let second = AesGcmFieldCipher.encrypt(&key("k-1", 1), "user-fixed-0001");
+
+        assert_ne!(first, second);
+        assert!(!first.contains("user-fixed-0001"));
+        assert!(!first.contains(':'));
+        assert_eq!(
+            AesGcmFieldCipher.decrypt(&key("k-1", 1), &first),
+            Some("user-fixed-0001".to_string())
+        );
+    }
+
+    #[rstest]
+    #[case::another_key(key("k-1", 2))]
+    #[case::another_key_id(key("k-2", 1))]
+    fn it_should_not_decrypt_under_another_key(#[case] other: DataKey) {
+        let ciphertext = AesGcmFieldCipher.encrypt(&key("k-1", 1), "user-fixed-0001");
+
+        assert_eq!(AesGcmFieldCipher.decrypt(&other, &ciphertext), None);
+    }
+
+    #[rstest]
+    #[case::not_base64("not base64!")]
+    #[case::too_short("AAAA")]
+    #[case::tampered("AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA")]
+    fn it_should_reject_malformed_ciphertexts(#[case] ciphertext: &str) {
+        assert_eq!(AesGcmFieldCipher.decrypt(&key("k-1", 1), ciphertext), None);
+    }
+}
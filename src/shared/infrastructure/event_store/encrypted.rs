@@ -0,0 +1,404 @@
+// Event store decorator that encrypts whole event payloads at rest.
+//
+// On append each event is serialized to JSON and sealed with AES-256-GCM under the key
+// provider's current key, with a random nonce and the stream id as associated data, so a
+// payload copied into another stream fails to open. On load the payload is opened with the
+// key named next to it. Handlers and projectors keep working with plain events, and
+// compaction and imports seal what they write like appends do.
+
+use crate::shared::infrastructure::event_store::{
+    AppendResult, EventLog, EventStore, EventStoreError, LoadedStream, StoredEvent, StreamAppend,
+};
+use crate::shared::infrastructure::key_provider::{EncryptionKey, KeyProvider, KeyProviderError};
+use aes_gcm::aead::{Aead, AeadCore, KeyInit, OsRng, Payload};
+use aes_gcm::{Aes256Gcm, Nonce};
+use async_trait::async_trait;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use std::collections::HashMap;
+
+/// An event as persisted: its JSON, sealed under the key `key_id`.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct SealedEvent {
+    pub key_id: String,
+    /// 96 bits, fresh for every event.
+    pub nonce: Vec<u8>,
+    /// Includes the 128-bit authentication tag.
+    pub ciphertext: Vec<u8>,
+}
+
+impl From<KeyProviderError> for EventStoreError {
+    fn from(error: KeyProviderError) -> Self {
+        EventStoreError::Backend(error.to_string())
+    }
+}
+
+/// Seals events into `SealedEvent`s and opens them again.
+#[derive(Clone)]
+pub struct EventCodec<TKeys> {
+    keys: TKeys,
+}
+
+impl<TKeys: KeyProvider> EventCodec<TKeys> {
+    pub fn new(keys: TKeys) -> Self {
+        Self { keys }
+    }
+
+    pub async fn seal<E: Serialize>(
+        &self,
+        stream_id: &str,
+        events: &[E],
+    ) -> Result<Vec<SealedEvent>, EventStoreError> {
+        let key = self.keys.current_key().await?;
+        let cipher = Aes256Gcm::new(&key.material.into());
+        events
+            .iter()
+            .map(|event| {
+                let json = serde_json::to_vec(event)
+                    .map_err(|error| EventStoreError::Backend(error.to_string()))?;
+                let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+                let ciphertext = cipher
+                    .encrypt(
+                        &nonce,
+                        Payload {
+                            msg: &json,
+                            aad: stream_id.as_bytes(),
+                        },
+                    )
+                    .map_err(|_| EventStoreError::Backend("failed to seal event".to_string()))?;
+                Ok(SealedEvent {
+                    key_id: key.id.clone(),
+                    nonce: nonce.to_vec(),
+                    ciphertext,
+                })
+            })
+            .collect()
+    }
+
+    /// Fails on a payload that was tampered with, sealed for another stream or sealed under a
+    /// key the provider no longer knows.
+    pub async fn open<E: DeserializeOwned>(
+        &self,
+        stream_id: &str,
+        sealed: &[SealedEvent],
+    ) -> Result<Vec<E>, EventStoreError> {
+        let mut keys: HashMap<&str, EncryptionKey> = HashMap::new();
+        let mut events = Vec::with_capacity(sealed.len());
+        for event in sealed {
+            if !keys.contains_key(event.key_id.as_str()) {
+                let key = self.keys.key(&event.key_id).await?;
+                keys.insert(&event.key_id, key);
+            }
+            let cipher = Aes256Gcm::new(&keys[event.key_id.as_str()].material.into());
+            let unreadable =
+                || EventStoreError::Backend(format!("cannot open an event of stream {stream_id}"));
+            if event.nonce.len() != 12 {
+                return Err(unreadable());
+            }
+            let json = cipher
+                .decrypt(
+                    Nonce::from_slice(&event.nonce),
+                    Payload {
+                        msg: &event.ciphertext,
+                        aad: stream_id.as_bytes(),
+                    },
+                )
+                .map_err(|_| unreadable())?;
+            events.push(serde_json::from_slice(&json).map_err(|_| unreadable())?);
+        }
+        Ok(events)
+    }
+
+    pub async fn open_stored<E: DeserializeOwned>(
+        &self,
+        stored: StoredEvent<SealedEvent>,
+    ) -> Result<StoredEvent<E>, EventStoreError> {
+        let event = self
+            .open(&stored.stream_id, std::slice::from_ref(&stored.event))
+            .await?
+            .remove(0);
+        Ok(StoredEvent {
+            global_position: stored.global_position,
+            stream_id: stored.stream_id,
+            stream_version: stored.stream_version,
+            event,
+        })
+    }
+}
+
+#[derive(Clone)]
+pub struct EncryptedEventStore<TInner, TKeys> {
+    inner: TInner,
+    codec: EventCodec<TKeys>,
+}
+
+impl<TInner, TKeys: KeyProvider> EncryptedEventStore<TInner, TKeys> {
+    pub fn new(inner: TInner, keys: TKeys) -> Self {
+        Self {
+            inner,
+            codec: EventCodec::new(keys),
+        }
+    }
+
+    /// Projectors open the sealed feed of the inner store with this.
+    pub fn codec(&self) -> &EventCodec<TKeys> {
+        &self.codec
+    }
+}
+
+#[async_trait]
+impl<E, TInner, TKeys> EventStore<E> for EncryptedEventStore<TInner, TKeys>
+where
+    E: Serialize + DeserializeOwned + Clone + Send + Sync + 'static,
+    TInner: EventStore<SealedEvent>,
+    TKeys: KeyProvider,
+{
+    async fn load(&self, stream_id: &str) -> Result<LoadedStream<E>, EventStoreError> {
+        let stream = self.inner.load(stream_id).await?;
+        Ok(LoadedStream {
+            events: self.codec.open(stream_id, &stream.events).await?,
+            version: stream.version,
+        })
+    }
+
+    async fn load_paged(
+        &self,
+        stream_id: &str,
+        from_version: i64,
+        limit: usize,
+    ) -> Result<LoadedStream<E>, EventStoreError> {
+        let page = self
+            .inner
+            .load_paged(stream_id, from_version, limit)
+            .await?;
+        Ok(LoadedStream {
+            events: self.codec.open(stream_id, &page.events).await?,
+            version: page.version,
+        })
+    }
+
+    async fn append(
+        &self,
+        stream_id: &str,
+        expected_version: i64,
+        new_events: &[E],
+    ) -> Result<AppendResult, EventStoreError> {
+        let sealed = self.codec.seal(stream_id, new_events).await?;
+        self.inner
+            .append(stream_id, expected_version, &sealed)
+            .await
+    }
+
+    async fn append_many(
+        &self,
+        batch: Vec<StreamAppend<E>>,
+    ) -> Result<Vec<AppendResult>, EventStoreError> {
+        let mut sealed = Vec::with_capacity(batch.len());
+        for append in batch {
+            sealed.push(StreamAppend {
+                events: self.codec.seal(&append.stream_id, &append.events).await?,
+                stream_id: append.stream_id,
+                expected_version: append.expected_version,
+            });
+        }
+        self.inner.append_many(sealed).await
+    }
+}
+
+#[async_trait]
+impl<E, TInner, TKeys> EventLog<E> for EncryptedEventStore<TInner, TKeys>
+where
+    E: Serialize + DeserializeOwned + Clone + Send + Sync + 'static,
+    TInner: EventLog<SealedEvent>,
+    TKeys: KeyProvider,
+{
+    async fn load_all_from(&self, from: u64) -> Result<Vec<StoredEvent<E>>, EventStoreError> {
+        let mut events = vec![];
+        for stored in self.inner.load_all_from(from).await? {
+            events.push(self.codec.open_stored(stored).await?);
+        }
+        Ok(events)
+    }
+
+    async fn head(&self) -> Result<u64, EventStoreError> {
+        self.inner.head().await
+    }
+
+    async fn compact(
+        &self,
+        stream_id: &str,
+        expected_version: i64,
+        tombstone: E,
+    ) -> Result<usize, EventStoreError> {
+        let sealed = self.codec.seal(stream_id, &[tombstone]).await?.remove(0);
+        self.inner
+            .compact(stream_id, expected_version, sealed)
+            .await
+    }
+
+    async fn import(&self, events: Vec<StoredEvent<E>>) -> Result<(), EventStoreError> {
+        let mut sealed = Vec::with_capacity(events.len());
+        for stored in events {
+            sealed.push(StoredEvent {
+                event: self
+                    .codec
+                    .seal(&stored.stream_id, &[stored.event])
+                    .await?
+                    .remove(0),
+                global_position: stored.global_position,
+                stream_id: stored.stream_id,
+                stream_version: stored.stream_version,
+            });
+        }
+        self.inner.import(sealed).await
+    }
+}
+
+#[cfg(test)]
+mod encrypted_event_store_tests {
+    use super::*;
+    use crate::modules::time_entries::core::events::TimeEntryEvent;
+    use crate::shared::infrastructure::event_store::in_memory::InMemoryEventStore;
+    use crate::shared::infrastructure::key_provider::env::EnvKeyProvider;
+    use crate::tests::fixtures::events::time_entry_initiated_v1::make_time_entry_initiated_v1_event;
+    use rstest::rstest;
+
+    const STREAM_ID: &str = "TimeEntry-te-fixed-0001";
+
+    fn initiated() -> TimeEntryEvent {
+        TimeEntryEvent::TimeEntryInitiatedV1(make_time_entry_initiated_v1_event())
+    }
+
+    fn keys(value: &str) -> EnvKeyProvider {
+        EnvKeyProvider::parse(value).unwrap()
+    }
+
+    fn key(id: &str, byte: &str) -> String {
+        format!("{id}:{}", byte.repeat(32))
+    }
+
+    fn setup() -> (
+        InMemoryEventStore<SealedEvent>,
+        EncryptedEventStore<InMemoryEventStore<SealedEvent>, EnvKeyProvider>,
+    ) {
+        let inner = InMemoryEventStore::<SealedEvent>::new();
+        let store = EncryptedEventStore::new(inner.clone(), keys(&key("k-1", "01")));
+        (inner, store)
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_store_sealed_payloads_and_load_plain_events() {
+        let (inner, store) = setup();
+
+        store.append(STREAM_ID, 0, &[initiated()]).await.unwrap();
+
+        let raw = inner.load(STREAM_ID).await.unwrap().events;
+        assert_eq!(raw[0].key_id, "k-1");
+        assert!(
+            !String::from_utf8_lossy(&raw[0].ciphertext).contains("user-fixed-0001"),
+            "payload stored in the clear"
+        );
+        let loaded: LoadedStream<TimeEntryEvent> = store.load(STREAM_ID).await.unwrap();
+        assert_eq!(loaded.events, vec![initiated()]);
+        assert_eq!(loaded.version, 1);
+        let all: Vec<StoredEvent<TimeEntryEvent>> = store.load_all_from(0).await.unwrap();
+        assert_eq!(all[0].event, initiated());
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_open_payloads_sealed_before_a_key_rotation() {
+        let (inner, store) = setup();
+        store.append(STREAM_ID, 0, &[initiated()]).await.unwrap();
+        let rotated = EncryptedEventStore::new(
+            inner.clone(),
+            keys(&format!("{},{}", key("k-2", "02"), key("k-1", "01"))),
+        );
+
+        rotated.append(STREAM_ID, 1, &[initiated()]).await.unwrap();
+
+        let raw = inner.load(STREAM_ID).await.unwrap().events;
+        assert_eq!(raw[1].key_id, "k-2");
+        let loaded: LoadedStream<TimeEntryEvent> = rotated.load(STREAM_ID).await.unwrap();
+        assert_eq!(loaded.events, vec![initiated(), initiated()]);
+    }
+
+    #[rstest]
+    #[case::tampered(|sealed: &mut SealedEvent| sealed.ciphertext[0] ^= 1)]
+    #[case::bad_nonce(|sealed: &mut SealedEvent| sealed.nonce.truncate(8))]
+    #[case::unknown_key(|sealed: &mut SealedEvent| sealed.key_id = "k-0".to_string())]
+    #[tokio::test]
+    async fn it_should_refuse_payloads_it_cannot_open(#[case] corrupt: fn(&mut SealedEvent)) {
+        let (inner, store) = setup();
+        let mut sealed = store.codec().seal(STREAM_ID, &[initiated()]).await.unwrap();
+        corrupt(&mut sealed[0]);
+        inner.append(STREAM_ID, 0, &sealed).await.unwrap();
+
+        let loaded: Result<LoadedStream<TimeEntryEvent>, _> = store.load(STREAM_ID).await;
+        assert!(matches!(loaded, Err(EventStoreError::Backend(_))));
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_bind_payloads_to_their_stream() {
+        let (inner, store) = setup();
+        let sealed = store.codec().seal(STREAM_ID, &[initiated()]).await.unwrap();
+        inner
+            .append("TimeEntry-te-fixed-0002", 0, &sealed)
+            .await
+            .unwrap();
+
+        let loaded: Result<LoadedStream<TimeEntryEvent>, _> =
+            store.load("TimeEntry-te-fixed-0002").await;
+        assert!(loaded.is_err());
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_seal_compaction_tombstones_and_imports() {
+        let (inner, store) = setup();
+        store.append(STREAM_ID, 0, &[initiated()]).await.unwrap();
+
+        store.compact(STREAM_ID, 1, initiated()).await.unwrap();
+        store
+            .import(vec![StoredEvent {
+                global_position: 5,
+                stream_id: "TimeEntry-te-fixed-0002".to_string(),
+                stream_version: 1,
+                event: initiated(),
+            }])
+            .await
+            .unwrap();
+
+        for stored in inner.load_all_from(0).await.unwrap() {
+            assert_eq!(stored.event.key_id, "k-1");
+        }
+        assert_eq!(EventLog::<TimeEntryEvent>::head(&store).await.unwrap(), 6);
+        let imported: LoadedStream<TimeEntryEvent> =
+            store.load("TimeEntry-te-fixed-0002").await.unwrap();
+        assert_eq!(imported.events, vec![initiated()]);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_seal_every_stream_in_a_batch() {
+        let (inner, store) = setup();
+
+        store
+            .append_many(vec![
+                StreamAppend::new(STREAM_ID, 0, vec![initiated()]),
+                StreamAppend::new("TimeEntry-te-fixed-0002", 0, vec![initiated()]),
+            ])
+            .await
+            .unwrap();
+
+        let raw = inner.load("TimeEntry-te-fixed-0002").await.unwrap().events;
+        assert_eq!(raw[0].key_id, "k-1");
+        let page: LoadedStream<TimeEntryEvent> = store
+            .load_paged("TimeEntry-te-fixed-0002", 0, 10)
+            .await
+            .unwrap();
+        assert_eq!(page.events, vec![initiated()]);
+    }
+}
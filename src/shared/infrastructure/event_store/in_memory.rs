@@ -1,15 +1,19 @@
+use crate::shared::infrastructure::capacity::{Capacity, HasCapacity};
+use crate::shared::infrastructure::deadline::within;
 use crate::shared::infrastructure::event_store::{
-    EventStore, EventStoreError, LoadedStream, StoredEvent,
+    AppendResult, EventLog, EventStore, EventStoreError, LoadedStream, StoredEvent, StreamAppend,
 };
 use std::collections::HashMap;
-use std::sync::Arc;
-use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
 use std::time::Duration;
 use tokio::sync::RwLock;
 
 struct InnerState<Event> {
     streams: HashMap<String, Vec<Event>>,
     global_log: Vec<StoredEvent<Event>>,
+    /// The global position of the next event; evicted events keep their positions taken.
+    next_position: u64,
 }
 
 struct Inner<Event> {
@@ -17,11 +21,18 @@ struct Inner<Event> {
     is_offline: AtomicBool,
     delay_append_ms: AtomicU64,
     sender: Option<tokio::sync::broadcast::Sender<StoredEvent<Event>>>,
+    /// When each stream was last loaded or appended to, by a tick of `clock`; kept only
+    /// when the store is bounded.
+    last_used: Mutex<HashMap<String, u64>>,
+    clock: AtomicU64,
+    stream_count: AtomicUsize,
+    evicted: AtomicU64,
 }
 
 #[derive(Clone)]
 pub struct InMemoryEventStore<Event: Clone + Send + Sync + 'static> {
     inner: Arc<Inner<Event>>,
+    max_streams: Option<usize>,
 }
 
 impl<Event: Clone + Send + Sync + 'static> Default for InMemoryEventStore<Event> {
@@ -32,33 +43,91 @@ impl<Event: Clone + Send + Sync + 'static> Default for InMemoryEventStore<Event>
 
 impl<Event: Clone + Send + Sync + 'static> InMemoryEventStore<Event> {
     pub fn new() -> Self {
-        Self {
-            inner: Arc::new(Inner {
-                state: RwLock::new(InnerState {
-                    streams: HashMap::new(),
-                    global_log: Vec::new(),
-                }),
-                is_offline: AtomicBool::new(false),
-                delay_append_ms: AtomicU64::new(0),
-                sender: None,
-            }),
-        }
+        Self::with_sender(None)
     }
 
     pub fn new_with_sender(sender: tokio::sync::broadcast::Sender<StoredEvent<Event>>) -> Self {
+        Self::with_sender(Some(sender))
+    }
+
+    fn with_sender(sender: Option<tokio::sync::broadcast::Sender<StoredEvent<Event>>>) -> Self {
         Self {
             inner: Arc::new(Inner {
                 state: RwLock::new(InnerState {
                     streams: HashMap::new(),
                     global_log: Vec::new(),
+                    next_position: 0,
                 }),
                 is_offline: AtomicBool::new(false),
                 delay_append_ms: AtomicU64::new(0),
-                sender: Some(sender),
+                sender,
+                last_used: Mutex::new(HashMap::new()),
+                clock: AtomicU64::new(0),
+                stream_count: AtomicUsize::new(0),
+                evicted: AtomicU64::new(0),
             }),
+            max_streams: None,
         }
     }
 
+    /// Keeps at most `max_streams` streams (at least one), evicting the least recently
+    /// loaded or appended to, with their events, when an append goes over. For long-running
+    /// environments whose data can be lost: an evicted stream reads as never written.
+    pub fn with_max_streams(mut self, max_streams: usize) -> Self {
+        self.max_streams = Some(max_streams.max(1));
+        self
+    }
+
+    fn touch(&self, stream_id: &str) {
+        if self.max_streams.is_some() {
+            let tick = self.inner.clock.fetch_add(1, Ordering::SeqCst);
+            self.inner
+                .last_used
+                .lock()
+                .unwrap()
+                .insert(stream_id.to_string(), tick);
+        }
+    }
+
+    /// Evicts the least recently used streams over `max_streams`, sparing `appended`.
+    fn evict(&self, state: &mut InnerState<Event>, appended: &[&str]) {
+        let Some(max_streams) = self.max_streams else {
+            return;
+        };
+        let excess = state.streams.len().saturating_sub(max_streams);
+        if excess == 0 {
+            return;
+        }
+        let mut last_used = self.inner.last_used.lock().unwrap();
+        let mut by_use: Vec<(u64, String)> = state
+            .streams
+            .keys()
+            .filter(|stream_id| !appended.contains(&stream_id.as_str()))
+            .map(|stream_id| {
+                (
+                    last_used.get(stream_id).copied().unwrap_or(0),
+                    stream_id.clone(),
+                )
+            })
+            .collect();
+        by_use.sort();
+        let evicted: Vec<String> = by_use
+            .into_iter()
+            .take(excess)
+            .map(|(_, stream_id)| stream_id)
+            .collect();
+        for stream_id in &evicted {
+            state.streams.remove(stream_id);
+            last_used.remove(stream_id);
+        }
+        state
+            .global_log
+            .retain(|stored| !evicted.contains(&stored.stream_id));
+        self.inner
+            .evicted
+            .fetch_add(evicted.len() as u64, Ordering::SeqCst);
+    }
+
     pub fn toggle_offline(&self) {
         self.inner.is_offline.fetch_xor(true, Ordering::SeqCst);
     }
@@ -78,13 +147,107 @@ impl<Event: Clone + Send + Sync + 'static> InMemoryEventStore<Event> {
         if self.inner.is_offline.load(Ordering::SeqCst) {
             return Err(EventStoreError::Backend("Event store offline".to_string()));
         }
-        let g = self.inner.state.read().await;
+        let g = within(self.inner.state.read()).await?;
         Ok(g.global_log
             .iter()
             .filter(|e| e.global_position >= from)
             .cloned()
             .collect())
     }
+
+    /// The global position the next appended event gets, one past the last in the log.
+    pub async fn head(&self) -> Result<u64, EventStoreError> {
+        if self.inner.is_offline.load(Ordering::SeqCst) {
+            return Err(EventStoreError::Backend("Event store offline".to_string()));
+        }
+        Ok(self.inner.state.read().await.next_position)
+    }
+
+    /// Replaces every event of `stream_id` with `tombstone`, if the stream is still at
+    /// `expected_version`. The tombstone takes the global position of the event it follows
+    /// last, so the head and projector checkpoints stay where they are, and it is not
+    /// broadcast. The stream's version becomes 1; answers how many events were removed.
+    pub async fn compact(
+        &self,
+        stream_id: &str,
+        expected_version: i64,
+        tombstone: Event,
+    ) -> Result<usize, EventStoreError> {
+        if self.inner.is_offline.load(Ordering::SeqCst) {
+            return Err(EventStoreError::Backend("Event store offline".to_string()));
+        }
+        let mut g = self.inner.state.write().await;
+        let actual = g.streams.get(stream_id).map(Vec::len).unwrap_or(0) as i64;
+        if actual == 0 || actual != expected_version {
+            return Err(EventStoreError::VersionMismatch {
+                expected: expected_version,
+                actual,
+            });
+        }
+        let last_position = g
+            .global_log
+            .iter()
+            .rev()
+            .find(|stored| stored.stream_id == stream_id)
+            .map(|stored| stored.global_position);
+        g.global_log.retain(|stored| {
+            stored.stream_id != stream_id || Some(stored.global_position) == last_position
+        });
+        if let Some(stored) = g
+            .global_log
+            .iter_mut()
+            .find(|stored| stored.stream_id == stream_id)
+        {
+            stored.stream_version = 1;
+            stored.event = tombstone.clone();
+        }
+        g.streams.insert(stream_id.to_string(), vec![tombstone]);
+        Ok(actual as usize - 1)
+    }
+
+    /// Adds events exported from another store with the stream versions and global positions
+    /// they had there, gaps left by compaction or eviction included. Each must follow on from
+    /// its stream and lie past the head; otherwise nothing is imported. Like `compact`, the
+    /// events are not broadcast: import before the projectors start.
+    pub async fn import(&self, events: Vec<StoredEvent<Event>>) -> Result<(), EventStoreError> {
+        if self.inner.is_offline.load(Ordering::SeqCst) {
+            return Err(EventStoreError::Backend("Event store offline".to_string()));
+        }
+        let mut g = self.inner.state.write().await;
+        let mut versions: HashMap<&str, i64> = HashMap::new();
+        let mut next_position = g.next_position;
+        for stored in &events {
+            if stored.global_position < next_position {
+                return Err(EventStoreError::Backend(format!(
+                    "global position {} is not past the head {next_position}",
+                    stored.global_position
+                )));
+            }
+            let actual = versions
+                .entry(stored.stream_id.as_str())
+                .or_insert_with(|| g.streams.get(&stored.stream_id).map_or(0, Vec::len) as i64);
+            if stored.stream_version != *actual + 1 {
+                return Err(EventStoreError::VersionMismatch {
+                    expected: stored.stream_version - 1,
+                    actual: *actual,
+                });
+            }
+            *actual += 1;
+            next_position = stored.global_position + 1;
+        }
+        for stored in &events {
+            g.streams
+                .entry(stored.stream_id.clone())
+                .or_default()
+                .push(stored.event.clone());
+        }
+        g.global_log.extend(events);
+        g.next_position = next_position;
+        self.inner
+            .stream_count
+            .store(g.streams.len(), Ordering::SeqCst);
+        Ok(())
+    }
 }
 
 #[async_trait::async_trait]
@@ -96,7 +259,8 @@ where
         if self.inner.is_offline.load(Ordering::SeqCst) {
             return Err(EventStoreError::Backend("Event store offline".to_string()));
         }
-        let guard = self.inner.state.read().await;
+        self.touch(id);
+        let guard = within(self.inner.state.read()).await?;
         let events = guard.streams.get(id).cloned().unwrap_or_default();
         Ok(LoadedStream {
             version: guard.streams.get(id).map(|v| v.len()).unwrap_or(0) as i64,
@@ -104,43 +268,114 @@ where
         })
     }
 
+    async fn load_paged(
+        &self,
+        id: &str,
+        from_version: i64,
+        limit: usize,
+    ) -> Result<LoadedStream<Event>, EventStoreError> {
+        if self.inner.is_offline.load(Ordering::SeqCst) {
+            return Err(EventStoreError::Backend("Event store offline".to_string()));
+        }
+        self.touch(id);
+        let guard = within(self.inner.state.read()).await?;
+        let stream = guard.streams.get(id).map(Vec::as_slice).unwrap_or_default();
+        Ok(LoadedStream {
+            events: stream
+                .iter()
+                .skip(from_version.max(0) as usize)
+                .take(limit)
+                .cloned()
+                .collect(),
+            version: stream.len() as i64,
+        })
+    }
+
     async fn append(
         &self,
         stream_id: &str,
         expected_version: i64,
         new_events: &[Event],
-    ) -> Result<(), EventStoreError> {
+    ) -> Result<AppendResult, EventStoreError> {
+        let mut results = self
+            .append_many(vec![StreamAppend::new(
+                stream_id,
+                expected_version,
+                new_events.to_vec(),
+            )])
+            .await?;
+        Ok(results.pop().unwrap_or_default())
+    }
+
+    /// All or nothing: every expected version is checked under one write lock before any
+    /// event is written, so a single mismatch rejects the whole batch.
+    async fn append_many(
+        &self,
+        batch: Vec<StreamAppend<Event>>,
+    ) -> Result<Vec<AppendResult>, EventStoreError> {
         let ms = self.inner.delay_append_ms.load(Ordering::SeqCst);
         if ms > 0 {
-            tokio::time::sleep(Duration::from_millis(ms)).await;
+            within(tokio::time::sleep(Duration::from_millis(ms))).await?;
         }
 
-        let stored_events = {
-            let mut g = self.inner.state.write().await;
-            let actual = g.streams.get(stream_id).map(|v| v.len()).unwrap_or(0) as i64;
-            if actual != expected_version {
-                return Err(EventStoreError::VersionMismatch {
-                    expected: expected_version,
-                    actual,
+        let (stored_events, results) = {
+            let mut g = within(self.inner.state.write()).await?;
+            let mut versions: HashMap<&str, i64> = HashMap::new();
+            for append in &batch {
+                let actual = *versions
+                    .entry(append.stream_id.as_str())
+                    .or_insert_with(|| {
+                        g.streams
+                            .get(&append.stream_id)
+                            .map(|v| v.len())
+                            .unwrap_or(0) as i64
+                    });
+                if actual != append.expected_version {
+                    return Err(EventStoreError::VersionMismatch {
+                        expected: append.expected_version,
+                        actual,
+                    });
+                }
+                versions.insert(&append.stream_id, actual + append.events.len() as i64);
+            }
+
+            let mut stored: Vec<StoredEvent<Event>> = Vec::new();
+            let mut results = Vec::with_capacity(batch.len());
+            for append in &batch {
+                let global_start = g.next_position;
+                let appended: Vec<StoredEvent<Event>> = append
+                    .events
+                    .iter()
+                    .enumerate()
+                    .map(|(i, event)| StoredEvent {
+                        global_position: global_start + i as u64,
+                        stream_id: append.stream_id.clone(),
+                        stream_version: append.expected_version + i as i64 + 1,
+                        event: event.clone(),
+                    })
+                    .collect();
+                g.streams
+                    .entry(append.stream_id.clone())
+                    .or_default()
+                    .extend_from_slice(&append.events);
+                results.push(AppendResult {
+                    next_version: append.expected_version + append.events.len() as i64,
+                    global_positions: appended.iter().map(|e| e.global_position).collect(),
                 });
+                g.next_position += append.events.len() as u64;
+                g.global_log.extend(appended.clone());
+                stored.extend(appended);
+                self.touch(&append.stream_id);
             }
-            let global_start = g.global_log.len() as u64;
-            let stored: Vec<StoredEvent<Event>> = new_events
+            let appended: Vec<&str> = batch
                 .iter()
-                .enumerate()
-                .map(|(i, event)| StoredEvent {
-                    global_position: global_start + i as u64,
-                    stream_id: stream_id.to_string(),
-                    stream_version: expected_version + i as i64 + 1,
-                    event: event.clone(),
-                })
+                .map(|append| append.stream_id.as_str())
                 .collect();
-            g.streams
-                .entry(stream_id.to_string())
-                .or_default()
-                .extend_from_slice(new_events);
-            g.global_log.extend(stored.clone());
-            stored
+            self.evict(&mut g, &appended);
+            self.inner
+                .stream_count
+                .store(g.streams.len(), Ordering::SeqCst);
+            (stored, results)
         };
 
         if let Some(sender) = &self.inner.sender {
@@ -149,13 +384,51 @@ where
             }
         }
 
-        Ok(())
+        Ok(results)
+    }
+}
+
+#[async_trait::async_trait]
+impl<Event> EventLog<Event> for InMemoryEventStore<Event>
+where
+    Event: Clone + Send + Sync + 'static,
+{
+    async fn load_all_from(&self, from: u64) -> Result<Vec<StoredEvent<Event>>, EventStoreError> {
+        InMemoryEventStore::load_all_from(self, from).await
+    }
+
+    async fn head(&self) -> Result<u64, EventStoreError> {
+        InMemoryEventStore::head(self).await
+    }
+
+    async fn compact(
+        &self,
+        stream_id: &str,
+        expected_version: i64,
+        tombstone: Event,
+    ) -> Result<usize, EventStoreError> {
+        InMemoryEventStore::compact(self, stream_id, expected_version, tombstone).await
+    }
+
+    async fn import(&self, events: Vec<StoredEvent<Event>>) -> Result<(), EventStoreError> {
+        InMemoryEventStore::import(self, events).await
+    }
+}
+
+impl<Event: Clone + Send + Sync + 'static> HasCapacity for InMemoryEventStore<Event> {
+    fn capacity(&self) -> Capacity {
+        Capacity {
+            used: self.inner.stream_count.load(Ordering::SeqCst),
+            max: self.max_streams,
+            evicted: self.inner.evicted.load(Ordering::SeqCst),
+        }
     }
 }
 
 #[cfg(test)]
 mod time_entry_in_memory_event_store_tests {
     use super::*;
+    use crate::shared::infrastructure::deadline::{self, Deadline};
     use crate::tests::fixtures::events::domain_event::DomainEvent;
     use rstest::rstest;
 
@@ -202,6 +475,24 @@ mod time_entry_in_memory_event_store_tests {
         assert_eq!(stream.events.first().unwrap().name, "Teddy Test");
     }
 
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_give_up_an_append_that_outlives_the_deadline() {
+        let store = InMemoryEventStore::<DomainEvent>::new();
+        store.set_delay_append_ms(100);
+        let deadline = Deadline::at(tokio::time::Instant::now() + Duration::from_millis(10));
+
+        let result = deadline::scope(
+            deadline.clone(),
+            store.append("1", 0, &[DomainEvent { name: "late" }]),
+        )
+        .await;
+
+        assert!(matches!(result, Err(EventStoreError::DeadlineExceeded)));
+        assert!(deadline.is_exceeded());
+        assert_eq!(store.load("1").await.unwrap().version, 0);
+    }
+
     #[rstest]
     #[tokio::test]
     async fn it_should_append_and_load_multiple_events() {
@@ -293,6 +584,7 @@ mod time_entry_in_memory_event_store_tests {
         assert_eq!(from_1.len(), 2);
         assert_eq!(from_1[0].global_position, 1);
         assert_eq!(from_1[1].global_position, 2);
+        assert_eq!(store.head().await.unwrap(), 3);
     }
 
     #[rstest]
@@ -329,11 +621,257 @@ mod time_entry_in_memory_event_store_tests {
     async fn it_should_assign_sequential_stream_versions_for_multiple_appended_events() {
         let store = InMemoryEventStore::<DomainEvent>::new();
         let events = vec![DomainEvent { name: "a" }, DomainEvent { name: "b" }];
-        store.append("s1", 0, &events).await.unwrap();
+        let appended = store.append("s1", 0, &events).await.unwrap();
+        assert_eq!(
+            appended,
+            AppendResult {
+                next_version: 2,
+                global_positions: vec![0, 1],
+            }
+        );
         let log = store.load_all_from(0).await.unwrap();
         assert_eq!(log[0].stream_version, 1);
         assert_eq!(log[1].stream_version, 2);
         assert_eq!(log[0].global_position, 0);
         assert_eq!(log[1].global_position, 1);
     }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_append_to_several_streams_in_one_batch() {
+        let (tx, mut rx) = tokio::sync::broadcast::channel::<StoredEvent<DomainEvent>>(16);
+        let store = InMemoryEventStore::<DomainEvent>::new_with_sender(tx);
+        store
+            .append("s1", 0, &[DomainEvent { name: "existing" }])
+            .await
+            .unwrap();
+
+        let appended = store
+            .append_many(vec![
+                StreamAppend::new("s1", 1, vec![DomainEvent { name: "a" }]),
+                StreamAppend::new("s2", 0, vec![DomainEvent { name: "b" }]),
+                StreamAppend::new("s1", 2, vec![DomainEvent { name: "c" }]),
+            ])
+            .await
+            .unwrap();
+        let results: Vec<_> = appended
+            .iter()
+            .map(|result| (result.next_version, result.global_positions.clone()))
+            .collect();
+        assert_eq!(results, vec![(2, vec![1]), (1, vec![2]), (3, vec![3])]);
+
+        assert_eq!(store.load("s1").await.unwrap().version, 3);
+        assert_eq!(store.load("s2").await.unwrap().version, 1);
+        let log = store.load_all_from(1).await.unwrap();
+        let positions: Vec<_> = log
+            .iter()
+            .map(|e| (e.global_position, e.stream_id.as_str(), e.stream_version))
+            .collect();
+        assert_eq!(positions, vec![(1, "s1", 2), (2, "s2", 1), (3, "s1", 3)]);
+        assert_eq!(rx.recv().await.unwrap().event.name, "existing");
+        assert_eq!(rx.recv().await.unwrap().event.name, "a");
+    }
+
+    #[rstest]
+    #[case::stale_version(vec![("s1", 0), ("s2", 0)])]
+    #[case::unchained_repeat(vec![("s2", 0), ("s2", 0)])]
+    #[tokio::test]
+    async fn it_should_reject_the_whole_batch_on_any_version_mismatch(
+        #[case] entries: Vec<(&'static str, i64)>,
+    ) {
+        let store = InMemoryEventStore::<DomainEvent>::new();
+        store
+            .append("s1", 0, &[DomainEvent { name: "existing" }])
+            .await
+            .unwrap();
+
+        let batch = entries
+            .into_iter()
+            .map(|(stream_id, version)| {
+                StreamAppend::new(stream_id, version, vec![DomainEvent { name: "new" }])
+            })
+            .collect();
+        let result = store.append_many(batch).await;
+
+        assert!(matches!(
+            result,
+            Err(EventStoreError::VersionMismatch { .. })
+        ));
+        assert_eq!(store.load_all_from(0).await.unwrap().len(), 1);
+        assert_eq!(store.load("s2").await.unwrap().version, 0);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_evict_the_least_recently_used_streams() {
+        let store = InMemoryEventStore::<DomainEvent>::new().with_max_streams(2);
+        let event = || [DomainEvent { name: "event" }];
+        store.append("s1", 0, &event()).await.unwrap();
+        store.append("s2", 0, &event()).await.unwrap();
+        store.load("s1").await.unwrap();
+
+        store.append("s3", 0, &event()).await.unwrap();
+
+        assert_eq!(store.load("s1").await.unwrap().version, 1);
+        assert_eq!(store.load("s2").await.unwrap().version, 0);
+        let log = store.load_all_from(0).await.unwrap();
+        assert_eq!(
+            log.iter().map(|e| e.global_position).collect::<Vec<_>>(),
+            vec![0, 2]
+        );
+        assert_eq!(store.head().await.unwrap(), 3);
+        assert_eq!(
+            store.capacity(),
+            Capacity {
+                used: 2,
+                max: Some(2),
+                evicted: 1,
+            }
+        );
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_keep_every_stream_when_unbounded() {
+        let store = InMemoryEventStore::<DomainEvent>::new();
+        for stream_id in ["s1", "s2", "s3"] {
+            store
+                .append(stream_id, 0, &[DomainEvent { name: "event" }])
+                .await
+                .unwrap();
+        }
+
+        assert_eq!(
+            store.capacity(),
+            Capacity {
+                used: 3,
+                max: None,
+                evicted: 0,
+            }
+        );
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_compact_a_stream_to_its_tombstone_in_place() {
+        let store = InMemoryEventStore::<DomainEvent>::new();
+        let event = |name| DomainEvent { name };
+        store
+            .append("s1", 0, &[event("a"), event("b")])
+            .await
+            .unwrap();
+        store.append("s2", 0, &[event("other")]).await.unwrap();
+        store.append("s1", 2, &[event("c")]).await.unwrap();
+
+        let removed = store.compact("s1", 3, event("tombstone")).await.unwrap();
+
+        assert_eq!(removed, 2);
+        let stream = store.load("s1").await.unwrap();
+        assert_eq!(stream.version, 1);
+        assert_eq!(
+            stream.events.iter().map(|e| e.name).collect::<Vec<_>>(),
+            vec!["tombstone"]
+        );
+        let log = store.load_all_from(0).await.unwrap();
+        assert_eq!(
+            log.iter()
+                .map(|e| (e.global_position, e.stream_version, e.event.name))
+                .collect::<Vec<_>>(),
+            vec![(2, 1, "other"), (3, 1, "tombstone")]
+        );
+        assert_eq!(store.head().await.unwrap(), 4);
+    }
+
+    #[rstest]
+    #[case::moved_on(2)]
+    #[case::unknown_stream(0)]
+    #[tokio::test]
+    async fn it_should_refuse_to_compact_a_stream_not_at_the_expected_version(
+        #[case] expected_version: i64,
+    ) {
+        let store = InMemoryEventStore::<DomainEvent>::new();
+        let stream_id = if expected_version == 0 { "s2" } else { "s1" };
+        store
+            .append("s1", 0, &vec![DomainEvent { name: "a" }; 3])
+            .await
+            .unwrap();
+
+        let result = store
+            .compact(
+                stream_id,
+                expected_version,
+                DomainEvent { name: "tombstone" },
+            )
+            .await;
+
+        assert!(matches!(
+            result,
+            Err(EventStoreError::VersionMismatch { .. })
+        ));
+        assert_eq!(store.load("s1").await.unwrap().version, 3);
+    }
+
+    fn exported(
+        position: u64,
+        stream_id: &str,
+        stream_version: i64,
+    ) -> StoredEvent<DomainEvent<'static>> {
+        StoredEvent {
+            global_position: position,
+            stream_id: stream_id.to_string(),
+            stream_version,
+            event: DomainEvent { name: "imported" },
+        }
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_import_events_at_their_exported_positions() {
+        let store = InMemoryEventStore::<DomainEvent>::new();
+
+        store
+            .import(vec![
+                exported(0, "s1", 1),
+                exported(3, "s2", 1),
+                exported(4, "s1", 2),
+            ])
+            .await
+            .unwrap();
+        store
+            .append("s1", 2, &[DomainEvent { name: "new" }])
+            .await
+            .unwrap();
+
+        assert_eq!(store.load("s1").await.unwrap().version, 3);
+        assert_eq!(
+            store
+                .load_all_from(0)
+                .await
+                .unwrap()
+                .iter()
+                .map(|e| (e.global_position, e.stream_version))
+                .collect::<Vec<_>>(),
+            vec![(0, 1), (3, 1), (4, 2), (5, 3)]
+        );
+        assert_eq!(store.capacity().used, 2);
+    }
+
+    #[rstest]
+    #[case::behind_the_head(vec![exported(1, "s2", 1)])]
+    #[case::version_gap(vec![exported(5, "s1", 4)])]
+    #[case::later_event_invalid(vec![exported(5, "s2", 1), exported(6, "s2", 1)])]
+    #[tokio::test]
+    async fn it_should_import_nothing_when_an_event_does_not_follow_on(
+        #[case] events: Vec<StoredEvent<DomainEvent<'static>>>,
+    ) {
+        let store = InMemoryEventStore::<DomainEvent>::new();
+        store
+            .append("s1", 0, &vec![DomainEvent { name: "a" }; 2])
+            .await
+            .unwrap();
+
+        assert!(store.import(events).await.is_err());
+        assert_eq!(store.load("s2").await.unwrap().version, 0);
+        assert_eq!(store.head().await.unwrap(), 2);
+    }
 }
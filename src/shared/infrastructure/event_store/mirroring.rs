@@ -0,0 +1,486 @@
+// Event store decorator for migrating between backends with both running side by side.
+//
+// Every read and write is served by the leading backend: the primary until cutover, the
+// secondary after it. Writes the leader accepted are then replayed on the other backend in
+// the background, one at a time and in the order they were made, so the follower sees each
+// stream at the version the leader had. A replay the follower rejects or answers with another
+// version counts as a divergent append; one it fails on counts as a mirror failure. Every load
+// is shadowed too: the follower's version of the stream is compared once the writes before it
+// are mirrored. Nothing is retried or repaired, and history written before the decorator was
+// installed is not copied; backfill the follower first.
+//
+// The leader is configured with `with_cutover`, parsed from `primary` | `secondary`, and
+// `set_cutover` switches it while running, after `flush` has drained the mirror.
+
+use async_trait::async_trait;
+use std::fmt::Write;
+use std::marker::PhantomData;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use tokio::sync::{mpsc, oneshot};
+
+use crate::shared::infrastructure::event_store::{
+    AppendResult, EventStore, EventStoreError, LoadedStream, StreamAppend,
+};
+
+/// Which backend leads.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MirrorCutover {
+    #[default]
+    Primary,
+    Secondary,
+}
+
+impl FromStr for MirrorCutover {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "primary" => Ok(Self::Primary),
+            "secondary" => Ok(Self::Secondary),
+            other => Err(format!("unknown event store cutover: {other}")),
+        }
+    }
+}
+
+/// How far the follower has drifted from the leader, since the decorator was created.
+#[derive(Debug, Default)]
+pub struct MirrorMetrics {
+    mirrored_events: AtomicU64,
+    mirror_failures: AtomicU64,
+    divergent_appends: AtomicU64,
+    divergent_reads: AtomicU64,
+}
+
+impl MirrorMetrics {
+    pub fn mirrored_events(&self) -> u64 {
+        self.mirrored_events.load(Ordering::SeqCst)
+    }
+
+    pub fn mirror_failures(&self) -> u64 {
+        self.mirror_failures.load(Ordering::SeqCst)
+    }
+
+    pub fn divergent_appends(&self) -> u64 {
+        self.divergent_appends.load(Ordering::SeqCst)
+    }
+
+    pub fn divergent_reads(&self) -> u64 {
+        self.divergent_reads.load(Ordering::SeqCst)
+    }
+
+    /// The counters in the Prometheus text format.
+    pub fn render(&self) -> String {
+        let mut out = String::new();
+        for (name, help, value) in [
+            (
+                "event_store_mirrored_events_total",
+                "Events replayed on the following event store.",
+                self.mirrored_events(),
+            ),
+            (
+                "event_store_mirror_failures_total",
+                "Replays the following event store failed on.",
+                self.mirror_failures(),
+            ),
+            (
+                "event_store_divergent_appends_total",
+                "Replays the following event store rejected or answered with another version.",
+                self.divergent_appends(),
+            ),
+            (
+                "event_store_divergent_reads_total",
+                "Loads whose stream version differs on the following event store.",
+                self.divergent_reads(),
+            ),
+        ] {
+            let _ = writeln!(out, "# HELP {name} {help}");
+            let _ = writeln!(out, "# TYPE {name} counter");
+            let _ = writeln!(out, "{name} {value}");
+        }
+        out
+    }
+}
+
+enum MirrorOp<Event> {
+    Append {
+        to: MirrorCutover,
+        batch: Vec<StreamAppend<Event>>,
+        expected: Vec<AppendResult>,
+    },
+    Compare {
+        on: MirrorCutover,
+        stream_id: String,
+        version: i64,
+    },
+    Flush(oneshot::Sender<()>),
+}
+
+pub struct MirroringEventStore<Event, TPrimary, TSecondary> {
+    primary: TPrimary,
+    secondary: TSecondary,
+    secondary_leads: Arc<AtomicBool>,
+    metrics: Arc<MirrorMetrics>,
+    mirror: mpsc::UnboundedSender<MirrorOp<Event>>,
+}
+
+impl<Event, TPrimary, TSecondary> MirroringEventStore<Event, TPrimary, TSecondary>
+where
+    Event: Clone + Send + Sync + 'static,
+    TPrimary: EventStore<Event> + Clone + 'static,
+    TSecondary: EventStore<Event> + Clone + 'static,
+{
+    /// Starts the mirroring task; call from within the runtime.
+    pub fn new(primary: TPrimary, secondary: TSecondary) -> Self {
+        let (mirror, ops) = mpsc::unbounded_channel();
+        let metrics = Arc::new(MirrorMetrics::default());
+        tokio::spawn(
+            Mirror {
+                primary: primary.clone(),
+                secondary: secondary.clone(),
+                metrics: metrics.clone(),
+                _event: PhantomData,
+            }
+            .run(ops),
+        );
+        Self {
+            primary,
+            secondary,
+            secondary_leads: Arc::new(AtomicBool::new(false)),
+            metrics,
+            mirror,
+        }
+    }
+
+    pub fn with_cutover(self, cutover: MirrorCutover) -> Self {
+        self.set_cutover(cutover);
+        self
+    }
+
+    pub fn set_cutover(&self, cutover: MirrorCutover) {
+        self.secondary_leads
+            .store(cutover == MirrorCutover::Secondary, Ordering::SeqCst);
+    }
+
+    pub fn cutover(&self) -> MirrorCutover {
+        if self.secondary_leads.load(Ordering::SeqCst) {
+            MirrorCutover::Secondary
+        } else {
+            MirrorCutover::Primary
+        }
+    }
+
+    pub fn metrics(&self) -> &MirrorMetrics {
+        &self.metrics
+    }
+
+    /// Waits until every write and load made so far is mirrored and compared.
+    pub async fn flush(&self) {
+        let (done, flushed) = oneshot::channel();
+        if self.mirror.send(MirrorOp::Flush(done)).is_ok() {
+            let _ = flushed.await;
+        }
+    }
+
+    fn follower(&self) -> MirrorCutover {
+        match self.cutover() {
+            MirrorCutover::Primary => MirrorCutover::Secondary,
+            MirrorCutover::Secondary => MirrorCutover::Primary,
+        }
+    }
+
+    fn enqueue(&self, op: MirrorOp<Event>) {
+        // The task only ends with the runtime; a lost op is as good as a failed one then.
+        let _ = self.mirror.send(op);
+    }
+}
+
+#[async_trait]
+impl<Event, TPrimary, TSecondary> EventStore<Event>
+    for MirroringEventStore<Event, TPrimary, TSecondary>
+where
+    Event: Clone + Send + Sync + 'static,
+    TPrimary: EventStore<Event> + Clone + 'static,
+    TSecondary: EventStore<Event> + Clone + 'static,
+{
+    async fn load(&self, stream_id: &str) -> Result<LoadedStream<Event>, EventStoreError> {
+        let stream = match self.cutover() {
+            MirrorCutover::Primary => self.primary.load(stream_id).await?,
+            MirrorCutover::Secondary => self.secondary.load(stream_id).await?,
+        };
+        self.enqueue(MirrorOp::Compare {
+            on: self.follower(),
+            stream_id: stream_id.to_string(),
+            version: stream.version,
+        });
+        Ok(stream)
+    }
+
+    async fn load_paged(
+        &self,
+        stream_id: &str,
+        from_version: i64,
+        limit: usize,
+    ) -> Result<LoadedStream<Event>, EventStoreError> {
+        match self.cutover() {
+            MirrorCutover::Primary => {
+                self.primary
+                    .load_paged(stream_id, from_version, limit)
+                    .await
+            }
+            MirrorCutover::Secondary => {
+                self.secondary
+                    .load_paged(stream_id, from_version, limit)
+                    .await
+            }
+        }
+    }
+
+    async fn append(
+        &self,
+        stream_id: &str,
+        expected_version: i64,
+        new_events: &[Event],
+    ) -> Result<AppendResult, EventStoreError> {
+        let mut results = self
+            .append_many(vec![StreamAppend::new(
+                stream_id,
+                expected_version,
+                new_events.to_vec(),
+            )])
+            .await?;
+        Ok(results.pop().unwrap_or_default())
+    }
+
+    async fn append_many(
+        &self,
+        batch: Vec<StreamAppend<Event>>,
+    ) -> Result<Vec<AppendResult>, EventStoreError> {
+        let results = match self.cutover() {
+            MirrorCutover::Primary => self.primary.append_many(batch.clone()).await?,
+            MirrorCutover::Secondary => self.secondary.append_many(batch.clone()).await?,
+        };
+        self.enqueue(MirrorOp::Append {
+            to: self.follower(),
+            batch,
+            expected: results.clone(),
+        });
+        Ok(results)
+    }
+}
+
+struct Mirror<Event, TPrimary, TSecondary> {
+    primary: TPrimary,
+    secondary: TSecondary,
+    metrics: Arc<MirrorMetrics>,
+    _event: PhantomData<fn() -> Event>,
+}
+
+impl<Event, TPrimary, TSecondary> Mirror<Event, TPrimary, TSecondary>
+where
+    Event: Clone + Send + Sync + 'static,
+    TPrimary: EventStore<Event>,
+    TSecondary: EventStore<Event>,
+{
+    fn store(&self, side: MirrorCutover) -> &dyn EventStore<Event> {
+        match side {
+            MirrorCutover::Primary => &self.primary,
+            MirrorCutover::Secondary => &self.secondary,
+        }
+    }
+
+    async fn run(self, mut ops: mpsc::UnboundedReceiver<MirrorOp<Event>>) {
+        while let Some(op) = ops.recv().await {
+            match op {
+                MirrorOp::Append {
+                    to,
+                    batch,
+                    expected,
+                } => self.append(to, batch, expected).await,
+                MirrorOp::Compare {
+                    on,
+                    stream_id,
+                    version,
+                } => self.compare(on, &stream_id, version).await,
+                MirrorOp::Flush(done) => {
+                    let _ = done.send(());
+                }
+            }
+        }
+    }
+
+    async fn append(
+        &self,
+        to: MirrorCutover,
+        batch: Vec<StreamAppend<Event>>,
+        expected: Vec<AppendResult>,
+    ) {
+        let events: u64 = batch.iter().map(|append| append.events.len() as u64).sum();
+        let streams: Vec<String> = batch
+            .iter()
+            .map(|append| append.stream_id.clone())
+            .collect();
+        match self.store(to).append_many(batch).await {
+            Ok(results)
+                if results
+                    .iter()
+                    .map(|result| result.next_version)
+                    .eq(expected.iter().map(|result| result.next_version)) =>
+            {
+                self.metrics
+                    .mirrored_events
+                    .fetch_add(events, Ordering::SeqCst);
+            }
+            Ok(_) | Err(EventStoreError::VersionMismatch { .. }) => {
+                self.metrics
+                    .divergent_appends
+                    .fetch_add(1, Ordering::SeqCst);
+                tracing::warn!(?streams, "mirrored append diverged");
+            }
+            Err(error) => {
+                self.metrics.mirror_failures.fetch_add(1, Ordering::SeqCst);
+                tracing::warn!(?streams, %error, "mirrored append failed");
+            }
+        }
+    }
+
+    async fn compare(&self, on: MirrorCutover, stream_id: &str, version: i64) {
+        match self.store(on).load(stream_id).await {
+            Ok(stream) if stream.version == version => {}
+            Ok(stream) => {
+                self.metrics.divergent_reads.fetch_add(1, Ordering::SeqCst);
+                tracing::warn!(
+                    stream_id,
+                    leader = version,
+                    follower = stream.version,
+                    "mirrored stream diverged"
+                );
+            }
+            Err(error) => {
+                self.metrics.mirror_failures.fetch_add(1, Ordering::SeqCst);
+                tracing::warn!(stream_id, %error, "mirrored load failed");
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod mirroring_event_store_tests {
+    use super::*;
+    use crate::shared::infrastructure::event_store::in_memory::InMemoryEventStore;
+    use rstest::rstest;
+
+    type Stores = (
+        InMemoryEventStore<u32>,
+        InMemoryEventStore<u32>,
+        MirroringEventStore<u32, InMemoryEventStore<u32>, InMemoryEventStore<u32>>,
+    );
+
+    fn stores() -> Stores {
+        let (primary, secondary) = (InMemoryEventStore::new(), InMemoryEventStore::new());
+        let mirroring = MirroringEventStore::new(primary.clone(), secondary.clone());
+        (primary, secondary, mirroring)
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_mirror_writes_to_the_secondary_in_order() {
+        let (primary, secondary, mirroring) = stores();
+
+        mirroring.append("s1", 0, &[1, 2]).await.unwrap();
+        mirroring.append("s1", 2, &[3]).await.unwrap();
+        mirroring
+            .append_many(vec![StreamAppend::new("s2", 0, vec![4])])
+            .await
+            .unwrap();
+        mirroring.flush().await;
+
+        assert_eq!(primary.load("s1").await.unwrap().events, vec![1, 2, 3]);
+        assert_eq!(secondary.load("s1").await.unwrap().events, vec![1, 2, 3]);
+        assert_eq!(secondary.load("s2").await.unwrap().events, vec![4]);
+        assert_eq!(mirroring.metrics().mirrored_events(), 4);
+        assert_eq!(mirroring.metrics().divergent_appends(), 0);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_count_divergence_without_failing_the_write() {
+        let (primary, secondary, mirroring) = stores();
+        secondary.append("s1", 0, &[9]).await.unwrap();
+
+        mirroring.append("s1", 0, &[1]).await.unwrap();
+        mirroring.load("s1").await.unwrap();
+        mirroring.flush().await;
+
+        assert_eq!(primary.load("s1").await.unwrap().events, vec![1]);
+        assert_eq!(mirroring.metrics().divergent_appends(), 1);
+        assert_eq!(mirroring.metrics().divergent_reads(), 0);
+
+        secondary.append("s1", 1, &[9]).await.unwrap();
+        mirroring.load("s1").await.unwrap();
+        mirroring.flush().await;
+
+        assert_eq!(mirroring.metrics().divergent_reads(), 1);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_count_failures_of_the_secondary() {
+        let (primary, secondary, mirroring) = stores();
+        secondary.toggle_offline();
+
+        mirroring.append("s1", 0, &[1]).await.unwrap();
+        let loaded = mirroring.load("s1").await.unwrap();
+        mirroring.flush().await;
+
+        assert_eq!(loaded.version, 1);
+        assert_eq!(primary.load("s1").await.unwrap().version, 1);
+        assert_eq!(mirroring.metrics().mirror_failures(), 1);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_lead_with_the_secondary_after_cutover() {
+        let (primary, secondary, mirroring) = stores();
+        mirroring.append("s1", 0, &[1]).await.unwrap();
+        mirroring.flush().await;
+
+        mirroring.set_cutover(MirrorCutover::Secondary);
+        mirroring.append("s1", 1, &[2]).await.unwrap();
+        secondary.append("s1", 2, &[3]).await.unwrap();
+        let loaded = mirroring.load("s1").await.unwrap();
+        mirroring.flush().await;
+
+        assert_eq!(loaded.events, vec![1, 2, 3]);
+        assert_eq!(primary.load("s1").await.unwrap().events, vec![1, 2]);
+        assert_eq!(mirroring.metrics().mirrored_events(), 2);
+        assert_eq!(mirroring.metrics().divergent_reads(), 1);
+    }
+
+    #[rstest]
+    #[case::primary("primary", Ok(MirrorCutover::Primary))]
+    #[case::secondary("secondary", Ok(MirrorCutover::Secondary))]
+    #[case::unknown(
+        "postgres",
+        Err("unknown event store cutover: postgres".to_string())
+    )]
+    fn it_should_parse_the_cutover(
+        #[case] value: &str,
+        #[case] expected: Result<MirrorCutover, String>,
+    ) {
+        assert_eq!(value.parse::<MirrorCutover>(), expected);
+    }
+
+    #[rstest]
+    fn it_should_render_the_counters_for_prometheus() {
+        let metrics = MirrorMetrics::default();
+        metrics.divergent_appends.fetch_add(2, Ordering::SeqCst);
+
+        let rendered = metrics.render();
+
+        assert!(rendered.contains("# TYPE event_store_divergent_appends_total counter\n"));
+        assert!(rendered.contains("\nevent_store_divergent_appends_total 2\n"));
+        assert!(rendered.contains("\nevent_store_mirrored_events_total 0\n"));
+    }
+}
@@ -1,6 +1,9 @@
 use async_trait::async_trait;
+use std::sync::Arc;
 use thiserror::Error;
 
+use crate::shared::infrastructure::deadline::DeadlineExceeded;
+
 #[derive(Debug, Error)]
 pub enum EventStoreError {
     #[error("version mismatch: expected {expected}, actual {actual}")]
@@ -8,6 +11,16 @@ pub enum EventStoreError {
 
     #[error("backend error: {0}")]
     Backend(String),
+
+    /// The request's deadline passed before the store answered.
+    #[error("deadline exceeded")]
+    DeadlineExceeded,
+}
+
+impl From<DeadlineExceeded> for EventStoreError {
+    fn from(_: DeadlineExceeded) -> Self {
+        EventStoreError::DeadlineExceeded
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -24,6 +37,33 @@ pub struct StoredEvent<E> {
     pub event: E,
 }
 
+/// Where an append left its stream: the stream's version after it and the position of each
+/// appended event in the global log, in order. Lets callers key outbox rows and hand out
+/// read-your-writes positions without loading the stream again.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AppendResult {
+    pub next_version: i64,
+    pub global_positions: Vec<u64>,
+}
+
+/// One stream's share of an `append_many` batch.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StreamAppend<E> {
+    pub stream_id: String,
+    pub expected_version: i64,
+    pub events: Vec<E>,
+}
+
+impl<E> StreamAppend<E> {
+    pub fn new(stream_id: impl Into<String>, expected_version: i64, events: Vec<E>) -> Self {
+        Self {
+            stream_id: stream_id.into(),
+            expected_version,
+            events,
+        }
+    }
+}
+
 #[async_trait]
 pub trait EventStore<Event: Clone + Send + Sync + 'static>: Send + Sync {
     async fn load(&self, stream_id: &str) -> Result<LoadedStream<Event>, EventStoreError>;
@@ -32,7 +72,149 @@ pub trait EventStore<Event: Clone + Send + Sync + 'static>: Send + Sync {
         stream_id: &str,
         expected_version: i64,
         new_events: &[Event],
-    ) -> Result<(), EventStoreError>;
+    ) -> Result<AppendResult, EventStoreError>;
+
+    /// Up to `limit` events of `stream_id` following `from_version`, so long streams can be
+    /// read a page at a time. `version` is the stream's current version. This default loads
+    /// the whole stream and slices it; adapters that can should read only the page.
+    async fn load_paged(
+        &self,
+        stream_id: &str,
+        from_version: i64,
+        limit: usize,
+    ) -> Result<LoadedStream<Event>, EventStoreError> {
+        let stream = self.load(stream_id).await?;
+        Ok(LoadedStream {
+            events: stream
+                .events
+                .into_iter()
+                .skip(from_version.max(0) as usize)
+                .take(limit)
+                .collect(),
+            version: stream.version,
+        })
+    }
+
+    /// Appends to several streams in one call. A stream may appear more than once as long as
+    /// each expected version follows on from the previous entry. Adapters that can should apply
+    /// the batch atomically; this default appends entry by entry and stops at the first failure.
+    /// Returns one result per entry, in batch order.
+    async fn append_many(
+        &self,
+        batch: Vec<StreamAppend<Event>>,
+    ) -> Result<Vec<AppendResult>, EventStoreError> {
+        let mut results = Vec::with_capacity(batch.len());
+        for append in batch {
+            results.push(
+                self.append(&append.stream_id, append.expected_version, &append.events)
+                    .await?,
+            );
+        }
+        Ok(results)
+    }
+}
+
+/// An event store whose streams also make up one global log, in append order. Projectors
+/// replay and catch up from it; maintenance jobs compact and import it. Decorators that
+/// change how events are stored pass these through, translating events on the way.
+#[async_trait]
+pub trait EventLog<Event: Clone + Send + Sync + 'static>: EventStore<Event> {
+    /// The events at global position `from` and after.
+    async fn load_all_from(&self, from: u64) -> Result<Vec<StoredEvent<Event>>, EventStoreError>;
+
+    /// The global position the next appended event gets, one past the last in the log.
+    async fn head(&self) -> Result<u64, EventStoreError>;
+
+    /// Replaces every event of `stream_id` with `tombstone`, if the stream is still at
+    /// `expected_version`. Answers how many events were removed.
+    async fn compact(
+        &self,
+        stream_id: &str,
+        expected_version: i64,
+        tombstone: Event,
+    ) -> Result<usize, EventStoreError>;
+
+    /// Adds events exported from another store with the versions and positions they had there.
+    async fn import(&self, events: Vec<StoredEvent<Event>>) -> Result<(), EventStoreError>;
+}
+
+/// An event log behind a pointer, so the shell can pick its decorators at startup.
+pub type SharedEventLog<Event> = Arc<dyn EventLog<Event>>;
+
+#[async_trait]
+impl<Event, T> EventStore<Event> for Arc<T>
+where
+    Event: Clone + Send + Sync + 'static,
+    T: EventStore<Event> + ?Sized,
+{
+    async fn load(&self, stream_id: &str) -> Result<LoadedStream<Event>, EventStoreError> {
+        (**self).load(stream_id).await
+    }
+
+    async fn append(
+        &self,
+        stream_id: &str,
+        expected_version: i64,
+        new_events: &[Event],
+    ) -> Result<AppendResult, EventStoreError> {
+        (**self)
+            .append(stream_id, expected_version, new_events)
+            .await
+    }
+
+    async fn load_paged(
+        &self,
+        stream_id: &str,
+        from_version: i64,
+        limit: usize,
+    ) -> Result<LoadedStream<Event>, EventStoreError> {
+        (**self).load_paged(stream_id, from_version, limit).await
+    }
+
+    async fn append_many(
+        &self,
+        batch: Vec<StreamAppend<Event>>,
+    ) -> Result<Vec<AppendResult>, EventStoreError> {
+        (**self).append_many(batch).await
+    }
+}
+
+#[async_trait]
+impl<Event, T> EventLog<Event> for Arc<T>
+where
+    Event: Clone + Send + Sync + 'static,
+    T: EventLog<Event> + ?Sized,
+{
+    async fn load_all_from(&self, from: u64) -> Result<Vec<StoredEvent<Event>>, EventStoreError> {
+        (**self).load_all_from(from).await
+    }
+
+    async fn head(&self) -> Result<u64, EventStoreError> {
+        (**self).head().await
+    }
+
+    async fn compact(
+        &self,
+        stream_id: &str,
+        expected_version: i64,
+        tombstone: Event,
+    ) -> Result<usize, EventStoreError> {
+        (**self)
+            .compact(stream_id, expected_version, tombstone)
+            .await
+    }
+
+    async fn import(&self, events: Vec<StoredEvent<Event>>) -> Result<(), EventStoreError> {
+        (**self).import(events).await
+    }
 }
 
+pub mod buffered;
+pub mod crypto_shredding;
+pub mod encrypted;
 pub mod in_memory;
+pub mod mirroring;
+pub mod paged;
+#[cfg(feature = "postgres")]
+pub mod postgres;
+pub mod pseudonymous;
@@ -0,0 +1,277 @@
+// Reads a stream a page at a time.
+//
+// `EventStream` yields events one by one while holding at most one page in memory, and
+// `fold_paged` folds a whole stream that way, so rebuilding the state of a long-lived
+// aggregate costs a page of memory rather than its full history. The crate has no `futures`
+// dependency, so `EventStream` is a plain async iterator rather than a `futures::Stream`.
+
+use crate::shared::infrastructure::event_store::{EventStore, EventStoreError};
+use std::collections::VecDeque;
+use std::marker::PhantomData;
+
+pub const DEFAULT_PAGE_SIZE: usize = 256;
+
+pub struct EventStream<'a, Event, TEventStore: ?Sized> {
+    event_store: &'a TEventStore,
+    stream_id: String,
+    page_size: usize,
+    read: i64,
+    page: VecDeque<Event>,
+    exhausted: bool,
+    _event: PhantomData<fn() -> Event>,
+}
+
+impl<'a, Event, TEventStore> EventStream<'a, Event, TEventStore>
+where
+    Event: Clone + Send + Sync + 'static,
+    TEventStore: EventStore<Event> + ?Sized,
+{
+    /// Starts after `from_version`; pass 0 to read the stream from its first event.
+    pub fn new(
+        event_store: &'a TEventStore,
+        stream_id: impl Into<String>,
+        from_version: i64,
+        page_size: usize,
+    ) -> Self {
+        Self {
+            event_store,
+            stream_id: stream_id.into(),
+            page_size: page_size.max(1),
+            read: from_version.max(0),
+            page: VecDeque::new(),
+            exhausted: false,
+            _event: PhantomData,
+        }
+    }
+
+    /// The next event, loading the next page when the current one is used up. `None` once the
+    /// stream is read to its end.
+    pub async fn next(&mut self) -> Option<Result<Event, EventStoreError>> {
+        if self.page.is_empty() && !self.exhausted {
+            match self
+                .event_store
+                .load_paged(&self.stream_id, self.read, self.page_size)
+                .await
+            {
+                Ok(page) => {
+                    self.read += page.events.len() as i64;
+                    self.exhausted =
+                        page.events.len() < self.page_size || self.read >= page.version;
+                    self.page = page.events.into();
+                }
+                Err(error) => return Some(Err(error)),
+            }
+        }
+        self.page.pop_front().map(Ok)
+    }
+
+    /// Stream version of the last event read so far, the expected version for an append
+    /// based on what was read.
+    pub fn version(&self) -> i64 {
+        self.read - self.page.len() as i64
+    }
+}
+
+/// Folds every event of `stream_id` into `initial`, a page at a time. Returns the state and
+/// the stream version it reflects.
+pub async fn fold_paged<Event, TEventStore, TState>(
+    event_store: &TEventStore,
+    stream_id: &str,
+    page_size: usize,
+    initial: TState,
+    mut evolve: impl FnMut(TState, Event) -> TState,
+) -> Result<(TState, i64), EventStoreError>
+where
+    Event: Clone + Send + Sync + 'static,
+    TEventStore: EventStore<Event> + ?Sized,
+{
+    let mut events = EventStream::new(event_store, stream_id, 0, page_size);
+    let mut state = initial;
+    while let Some(event) = events.next().await {
+        state = evolve(state, event?);
+    }
+    Ok((state, events.version()))
+}
+
+#[cfg(test)]
+mod paged_event_store_tests {
+    use super::*;
+    use crate::shared::infrastructure::event_store::in_memory::InMemoryEventStore;
+    use crate::shared::infrastructure::event_store::{AppendResult, LoadedStream};
+    use async_trait::async_trait;
+    use rstest::rstest;
+    use std::sync::Mutex;
+
+    /// Pages through the trait default and records the size of every page it hands out.
+    struct CountingPages {
+        inner: InMemoryEventStore<u32>,
+        pages: Mutex<Vec<usize>>,
+    }
+
+    #[async_trait]
+    impl EventStore<u32> for CountingPages {
+        async fn load(&self, stream_id: &str) -> Result<LoadedStream<u32>, EventStoreError> {
+            self.inner.load(stream_id).await
+        }
+
+        async fn append(
+            &self,
+            stream_id: &str,
+            expected_version: i64,
+            new_events: &[u32],
+        ) -> Result<AppendResult, EventStoreError> {
+            self.inner
+                .append(stream_id, expected_version, new_events)
+                .await
+        }
+
+        async fn load_paged(
+            &self,
+            stream_id: &str,
+            from_version: i64,
+            limit: usize,
+        ) -> Result<LoadedStream<u32>, EventStoreError> {
+            let page = EventStore::<u32>::load_paged(
+                &Unpaged(&self.inner),
+                stream_id,
+                from_version,
+                limit,
+            )
+            .await?;
+            self.pages.lock().unwrap().push(page.events.len());
+            Ok(page)
+        }
+    }
+
+    /// Implements only `load` and `append`, leaving `load_paged` to the trait default.
+    struct Unpaged<'a>(&'a InMemoryEventStore<u32>);
+
+    #[async_trait]
+    impl EventStore<u32> for Unpaged<'_> {
+        async fn load(&self, stream_id: &str) -> Result<LoadedStream<u32>, EventStoreError> {
+            self.0.load(stream_id).await
+        }
+
+        async fn append(
+            &self,
+            stream_id: &str,
+            expected_version: i64,
+            new_events: &[u32],
+        ) -> Result<AppendResult, EventStoreError> {
+            self.0.append(stream_id, expected_version, new_events).await
+        }
+    }
+
+    async fn store_with(events: &[u32]) -> InMemoryEventStore<u32> {
+        let store = InMemoryEventStore::<u32>::new();
+        store.append("s-1", 0, events).await.unwrap();
+        store
+    }
+
+    #[rstest]
+    #[case(0, 2, vec![1, 2])]
+    #[case(3, 10, vec![4, 5])]
+    #[case(5, 2, vec![])]
+    #[case(-1, 1, vec![1])]
+    #[tokio::test]
+    async fn it_should_load_a_page_after_the_given_version(
+        #[case] from_version: i64,
+        #[case] limit: usize,
+        #[case] expected: Vec<u32>,
+    ) {
+        let store = store_with(&[1, 2, 3, 4, 5]).await;
+
+        let paged = store.load_paged("s-1", from_version, limit).await.unwrap();
+        let defaulted = Unpaged(&store)
+            .load_paged("s-1", from_version, limit)
+            .await
+            .unwrap();
+
+        assert_eq!(paged.events, expected);
+        assert_eq!(paged.version, 5);
+        assert_eq!(defaulted.events, expected);
+        assert_eq!(defaulted.version, 5);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_fail_to_load_a_page_when_offline() {
+        let store = InMemoryEventStore::<u32>::new();
+        store.toggle_offline();
+
+        assert!(matches!(
+            store.load_paged("s-1", 0, 10).await,
+            Err(EventStoreError::Backend(_))
+        ));
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_fold_a_stream_one_page_at_a_time() {
+        let store = CountingPages {
+            inner: store_with(&[1, 2, 3, 4, 5]).await,
+            pages: Mutex::new(Vec::new()),
+        };
+
+        let (sum, version) = fold_paged(&store, "s-1", 2, 0, |sum, event| sum + event)
+            .await
+            .unwrap();
+
+        assert_eq!((sum, version), (15, 5));
+        assert_eq!(*store.pages.lock().unwrap(), vec![2, 2, 1]);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_stop_without_an_extra_load_when_the_last_page_is_full() {
+        let store = CountingPages {
+            inner: store_with(&[1, 2, 3, 4]).await,
+            pages: Mutex::new(Vec::new()),
+        };
+
+        let (_, version) = fold_paged(&store, "s-1", 2, 0, |sum, event| sum + event)
+            .await
+            .unwrap();
+
+        assert_eq!(version, 4);
+        assert_eq!(*store.pages.lock().unwrap(), vec![2, 2]);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_fold_an_empty_stream_to_the_initial_state() {
+        let store = InMemoryEventStore::<u32>::new();
+
+        let folded = fold_paged(&store, "s-1", DEFAULT_PAGE_SIZE, 7, |sum, event| {
+            sum + event
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(folded, (7, 0));
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_report_the_version_of_the_last_event_read() {
+        let store = store_with(&[1, 2, 3]).await;
+        let mut events = EventStream::new(&store, "s-1", 1, 0);
+
+        assert_eq!(events.next().await.unwrap().unwrap(), 2);
+        assert_eq!(events.version(), 2);
+        assert_eq!(events.next().await.unwrap().unwrap(), 3);
+        assert!(events.next().await.is_none());
+        assert_eq!(events.version(), 3);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_propagate_load_errors_while_folding() {
+        let store = store_with(&[1]).await;
+        store.toggle_offline();
+
+        let result = fold_paged(&store, "s-1", 2, 0, |sum, event| sum + event).await;
+
+        assert!(matches!(result, Err(EventStoreError::Backend(_))));
+    }
+}
@@ -0,0 +1,566 @@
+// Event store on Postgres, with events stored as JSONB.
+//
+// An append locks the rows of its streams in `streams` (creating them at version 0 first) and
+// checks the expected versions against them, so of two appends racing on one stream the second
+// waits for the first to commit and then sees its version. Global positions come from the
+// single-row `event_log_head`, locked last, so they follow commit order. There is no live feed:
+// projectors catch up from `load_all_from`.
+//
+// `append_with_outbox` writes outbox rows in the same transaction as the events, so an append
+// and its intents are stored together or not at all.
+
+use crate::shared::infrastructure::event_store::{
+    AppendResult, EventLog, EventStore, EventStoreError, LoadedStream, StoredEvent, StreamAppend,
+};
+use crate::shared::infrastructure::intent_outbox::OutboxRow;
+use crate::shared::infrastructure::intent_outbox::postgres::insert_row;
+use crate::shared::infrastructure::postgres::{PgPool, is_unique_violation};
+use async_trait::async_trait;
+use deadpool_postgres::Transaction;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use serde_json::Value as Json;
+use std::collections::BTreeMap;
+use std::marker::PhantomData;
+
+fn backend(error: impl std::fmt::Display) -> EventStoreError {
+    EventStoreError::Backend(error.to_string())
+}
+
+#[derive(Clone)]
+pub struct PostgresEventStore<E> {
+    pool: PgPool,
+    event: PhantomData<fn() -> E>,
+}
+
+impl<E> PostgresEventStore<E>
+where
+    E: Serialize + DeserializeOwned + Clone + Send + Sync + 'static,
+{
+    /// Over a database `postgres::migrate` has been run on.
+    pub fn new(pool: PgPool) -> Self {
+        Self {
+            pool,
+            event: PhantomData,
+        }
+    }
+
+    /// Appends like `append`, and enqueues `rows` in the same transaction. A duplicate row
+    /// fails the whole append with a `Backend` error.
+    pub async fn append_with_outbox(
+        &self,
+        stream_id: &str,
+        expected_version: i64,
+        new_events: &[E],
+        rows: &[OutboxRow],
+    ) -> Result<AppendResult, EventStoreError> {
+        let mut client = self.pool.get().await.map_err(backend)?;
+        let transaction = client.transaction().await.map_err(backend)?;
+        let mut results = append_in(
+            &transaction,
+            vec![StreamAppend::new(
+                stream_id,
+                expected_version,
+                new_events.to_vec(),
+            )],
+        )
+        .await?;
+        for row in rows {
+            if !insert_row(&transaction, row).await.map_err(backend)? {
+                return Err(EventStoreError::Backend(format!(
+                    "outbox row {} is already enqueued",
+                    row.dedupe_key()
+                )));
+            }
+        }
+        transaction.commit().await.map_err(backend)?;
+        Ok(results.pop().unwrap_or_default())
+    }
+}
+
+/// Locks the rows of `stream_ids` in a fixed order, so batches touching the same streams
+/// cannot deadlock, and answers their versions.
+async fn lock_streams(
+    transaction: &Transaction<'_>,
+    stream_ids: impl IntoIterator<Item = &str>,
+) -> Result<BTreeMap<String, i64>, EventStoreError> {
+    let mut stream_ids: Vec<&str> = stream_ids.into_iter().collect();
+    stream_ids.sort_unstable();
+    stream_ids.dedup();
+    let mut versions = BTreeMap::new();
+    for stream_id in stream_ids {
+        transaction
+            .execute(
+                "INSERT INTO streams (stream_id, version) VALUES ($1, 0) ON CONFLICT DO NOTHING",
+                &[&stream_id],
+            )
+            .await
+            .map_err(backend)?;
+        let row = transaction
+            .query_one(
+                "SELECT version FROM streams WHERE stream_id = $1 FOR UPDATE",
+                &[&stream_id],
+            )
+            .await
+            .map_err(backend)?;
+        versions.insert(stream_id.to_string(), row.get(0));
+    }
+    Ok(versions)
+}
+
+async fn lock_head(transaction: &Transaction<'_>) -> Result<i64, EventStoreError> {
+    let row = transaction
+        .query_one("SELECT next_position FROM event_log_head FOR UPDATE", &[])
+        .await
+        .map_err(backend)?;
+    Ok(row.get(0))
+}
+
+async fn insert_event(
+    transaction: &Transaction<'_>,
+    global_position: i64,
+    stream_id: &str,
+    stream_version: i64,
+    payload: &Json,
+) -> Result<(), EventStoreError> {
+    transaction
+        .execute(
+            "INSERT INTO events (global_position, stream_id, stream_version, payload) \
+             VALUES ($1, $2, $3, $4)",
+            &[&global_position, &stream_id, &stream_version, payload],
+        )
+        .await
+        .map_err(|error| match is_unique_violation(&error) {
+            true => EventStoreError::Backend(format!(
+                "event {stream_id} v{stream_version} is already stored"
+            )),
+            false => backend(error),
+        })?;
+    Ok(())
+}
+
+async fn set_versions(
+    transaction: &Transaction<'_>,
+    versions: &BTreeMap<String, i64>,
+) -> Result<(), EventStoreError> {
+    for (stream_id, version) in versions {
+        transaction
+            .execute(
+                "UPDATE streams SET version = $2 WHERE stream_id = $1",
+                &[stream_id, version],
+            )
+            .await
+            .map_err(backend)?;
+    }
+    Ok(())
+}
+
+async fn set_head(
+    transaction: &Transaction<'_>,
+    next_position: i64,
+) -> Result<(), EventStoreError> {
+    transaction
+        .execute(
+            "UPDATE event_log_head SET next_position = $1",
+            &[&next_position],
+        )
+        .await
+        .map_err(backend)?;
+    Ok(())
+}
+
+/// All or nothing, like the in-memory store: every expected version is checked before any
+/// event is written.
+async fn append_in<E: Serialize>(
+    transaction: &Transaction<'_>,
+    batch: Vec<StreamAppend<E>>,
+) -> Result<Vec<AppendResult>, EventStoreError> {
+    let mut versions = lock_streams(
+        transaction,
+        batch.iter().map(|append| append.stream_id.as_str()),
+    )
+    .await?;
+    for append in &batch {
+        let actual = versions[&append.stream_id];
+        if actual != append.expected_version {
+            return Err(EventStoreError::VersionMismatch {
+                expected: append.expected_version,
+                actual,
+            });
+        }
+        versions.insert(
+            append.stream_id.clone(),
+            actual + append.events.len() as i64,
+        );
+    }
+
+    let mut next_position = lock_head(transaction).await?;
+    let mut results = Vec::with_capacity(batch.len());
+    for append in &batch {
+        let mut global_positions = Vec::with_capacity(append.events.len());
+        for (i, event) in append.events.iter().enumerate() {
+            let payload = serde_json::to_value(event).map_err(backend)?;
+            let stream_version = append.expected_version + i as i64 + 1;
+            insert_event(
+                transaction,
+                next_position,
+                &append.stream_id,
+                stream_version,
+                &payload,
+            )
+            .await?;
+            global_positions.push(next_position as u64);
+            next_position += 1;
+        }
+        results.push(AppendResult {
+            next_version: append.expected_version + append.events.len() as i64,
+            global_positions,
+        });
+    }
+    set_versions(transaction, &versions).await?;
+    set_head(transaction, next_position).await?;
+    Ok(results)
+}
+
+fn decode<E: DeserializeOwned>(payload: Json) -> Result<E, EventStoreError> {
+    serde_json::from_value(payload).map_err(backend)
+}
+
+#[async_trait]
+impl<E> EventStore<E> for PostgresEventStore<E>
+where
+    E: Serialize + DeserializeOwned + Clone + Send + Sync + 'static,
+{
+    async fn load(&self, stream_id: &str) -> Result<LoadedStream<E>, EventStoreError> {
+        self.load_paged(stream_id, 0, usize::MAX).await
+    }
+
+    async fn load_paged(
+        &self,
+        stream_id: &str,
+        from_version: i64,
+        limit: usize,
+    ) -> Result<LoadedStream<E>, EventStoreError> {
+        let mut client = self.pool.get().await.map_err(backend)?;
+        // One snapshot for the events and the version.
+        let transaction = client
+            .build_transaction()
+            .isolation_level(tokio_postgres::IsolationLevel::RepeatableRead)
+            .read_only(true)
+            .start()
+            .await
+            .map_err(backend)?;
+        let version = transaction
+            .query_opt(
+                "SELECT version FROM streams WHERE stream_id = $1",
+                &[&stream_id],
+            )
+            .await
+            .map_err(backend)?
+            .map_or(0, |row| row.get(0));
+        let limit = i64::try_from(limit).unwrap_or(i64::MAX);
+        let events = transaction
+            .query(
+                "SELECT payload FROM events WHERE stream_id = $1 AND stream_version > $2 \
+                 ORDER BY stream_version LIMIT $3",
+                &[&stream_id, &from_version.max(0), &limit],
+            )
+            .await
+            .map_err(backend)?
+            .into_iter()
+            .map(|row| decode(row.get(0)))
+            .collect::<Result<_, _>>()?;
+        transaction.commit().await.map_err(backend)?;
+        Ok(LoadedStream { events, version })
+    }
+
+    async fn append(
+        &self,
+        stream_id: &str,
+        expected_version: i64,
+        new_events: &[E],
+    ) -> Result<AppendResult, EventStoreError> {
+        self.append_with_outbox(stream_id, expected_version, new_events, &[])
+            .await
+    }
+
+    async fn append_many(
+        &self,
+        batch: Vec<StreamAppend<E>>,
+    ) -> Result<Vec<AppendResult>, EventStoreError> {
+        let mut client = self.pool.get().await.map_err(backend)?;
+        let transaction = client.transaction().await.map_err(backend)?;
+        let results = append_in(&transaction, batch).await?;
+        transaction.commit().await.map_err(backend)?;
+        Ok(results)
+    }
+}
+
+#[async_trait]
+impl<E> EventLog<E> for PostgresEventStore<E>
+where
+    E: Serialize + DeserializeOwned + Clone + Send + Sync + 'static,
+{
+    async fn load_all_from(&self, from: u64) -> Result<Vec<StoredEvent<E>>, EventStoreError> {
+        let client = self.pool.get().await.map_err(backend)?;
+        let from = i64::try_from(from).unwrap_or(i64::MAX);
+        client
+            .query(
+                "SELECT global_position, stream_id, stream_version, payload FROM events \
+                 WHERE global_position >= $1 ORDER BY global_position",
+                &[&from],
+            )
+            .await
+            .map_err(backend)?
+            .into_iter()
+            .map(|row| {
+                Ok(StoredEvent {
+                    global_position: row.get::<_, i64>(0) as u64,
+                    stream_id: row.get(1),
+                    stream_version: row.get(2),
+                    event: decode(row.get(3))?,
+                })
+            })
+            .collect()
+    }
+
+    async fn head(&self) -> Result<u64, EventStoreError> {
+        let client = self.pool.get().await.map_err(backend)?;
+        let row = client
+            .query_one("SELECT next_position FROM event_log_head", &[])
+            .await
+            .map_err(backend)?;
+        Ok(row.get::<_, i64>(0) as u64)
+    }
+
+    /// Keeps the stream's last event, at its global position, with `tombstone` in it as
+    /// version 1, like the in-memory store.
+    async fn compact(
+        &self,
+        stream_id: &str,
+        expected_version: i64,
+        tombstone: E,
+    ) -> Result<usize, EventStoreError> {
+        let payload = serde_json::to_value(&tombstone).map_err(backend)?;
+        let mut client = self.pool.get().await.map_err(backend)?;
+        let transaction = client.transaction().await.map_err(backend)?;
+        let actual = lock_streams(&transaction, [stream_id]).await?[stream_id];
+        if actual == 0 || actual != expected_version {
+            return Err(EventStoreError::VersionMismatch {
+                expected: expected_version,
+                actual,
+            });
+        }
+        transaction
+            .execute(
+                "DELETE FROM events WHERE stream_id = $1 AND stream_version < $2",
+                &[&stream_id, &actual],
+            )
+            .await
+            .map_err(backend)?;
+        transaction
+            .execute(
+                "UPDATE events SET stream_version = 1, payload = $2 WHERE stream_id = $1",
+                &[&stream_id, &payload],
+            )
+            .await
+            .map_err(backend)?;
+        set_versions(&transaction, &BTreeMap::from([(stream_id.to_string(), 1)])).await?;
+        transaction.commit().await.map_err(backend)?;
+        Ok(actual as usize - 1)
+    }
+
+    /// Each event must follow on from its stream and lie past the head; otherwise nothing is
+    /// imported.
+    async fn import(&self, events: Vec<StoredEvent<E>>) -> Result<(), EventStoreError> {
+        let mut client = self.pool.get().await.map_err(backend)?;
+        let transaction = client.transaction().await.map_err(backend)?;
+        let mut versions = lock_streams(
+            &transaction,
+            events.iter().map(|stored| stored.stream_id.as_str()),
+        )
+        .await?;
+        let mut next_position = lock_head(&transaction).await?;
+        for stored in &events {
+            let global_position = i64::try_from(stored.global_position).map_err(backend)?;
+            if global_position < next_position {
+                return Err(EventStoreError::Backend(format!(
+                    "global position {global_position} is not past the head {next_position}"
+                )));
+            }
+            let actual = versions[&stored.stream_id];
+            if stored.stream_version != actual + 1 {
+                return Err(EventStoreError::VersionMismatch {
+                    expected: stored.stream_version - 1,
+                    actual,
+                });
+            }
+            let payload = serde_json::to_value(&stored.event).map_err(backend)?;
+            insert_event(
+                &transaction,
+                global_position,
+                &stored.stream_id,
+                stored.stream_version,
+                &payload,
+            )
+            .await?;
+            versions.insert(stored.stream_id.clone(), stored.stream_version);
+            next_position = global_position + 1;
+        }
+        set_versions(&transaction, &versions).await?;
+        set_head(&transaction, next_position).await?;
+        transaction.commit().await.map_err(backend)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod postgres_event_store_integration_tests {
+    use super::*;
+    use crate::shared::infrastructure::intent_outbox::OutboxStatus;
+    use crate::shared::infrastructure::intent_outbox::postgres::PostgresDomainOutbox;
+    use crate::shared::infrastructure::postgres::postgres_test_support::start;
+    use crate::tests::contracts::{RACERS, parallel_appends_admit_exactly_one};
+    use std::sync::Arc;
+    use tokio::task::JoinSet;
+
+    fn row(stream_id: &str, stream_version: i64) -> OutboxRow {
+        OutboxRow {
+            topic: "time-entries.v1".to_string(),
+            event_type: "TimeEntryRegistered".to_string(),
+            event_version: 1,
+            stream_id: stream_id.to_string(),
+            stream_version,
+            intent_no: 0,
+            occurred_at: 0,
+            payload: Json::Null,
+            content_encoding: None,
+            status: OutboxStatus::Pending,
+            attempts: 0,
+            last_error: None,
+            published_at: None,
+        }
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    #[ignore = "starts Postgres in Docker"]
+    async fn integration_it_should_admit_exactly_one_parallel_append() {
+        let (_container, pool) = start().await;
+
+        parallel_appends_admit_exactly_one(PostgresEventStore::<String>::new(pool)).await;
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    #[ignore = "starts Postgres in Docker"]
+    async fn integration_it_should_store_each_winning_append_with_its_outbox_rows_only() {
+        let (_container, pool) = start().await;
+        let store = Arc::new(PostgresEventStore::<String>::new(pool.clone()));
+        let mut racers = JoinSet::new();
+        for racer in 0..RACERS {
+            let store = store.clone();
+            racers.spawn(async move {
+                let event = format!("appended-by-{racer}");
+                store
+                    .append_with_outbox("Race-1", 0, &[event], &[row("Race-1", 1)])
+                    .await
+            });
+        }
+        let mut won = 0;
+        while let Some(joined) = racers.join_next().await {
+            match joined.unwrap() {
+                Ok(_) => won += 1,
+                Err(EventStoreError::VersionMismatch { .. }) => {}
+                Err(other) => panic!("unexpected append error: {other}"),
+            }
+        }
+
+        assert_eq!(won, 1);
+        let outbox = PostgresDomainOutbox::new(pool);
+        assert_eq!(outbox.rows().await.unwrap().len(), 1);
+        assert_eq!(store.load_all_from(0).await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    #[ignore = "starts Postgres in Docker"]
+    async fn integration_it_should_roll_the_append_back_when_its_outbox_row_is_a_duplicate() {
+        let (_container, pool) = start().await;
+        let store = PostgresEventStore::<String>::new(pool.clone());
+        store
+            .append_with_outbox("Stream-1", 0, &["first".to_string()], &[row("Stream-1", 1)])
+            .await
+            .unwrap();
+
+        let appended = store
+            .append_with_outbox(
+                "Stream-1",
+                1,
+                &["second".to_string()],
+                &[row("Stream-1", 1)],
+            )
+            .await;
+
+        assert!(matches!(appended, Err(EventStoreError::Backend(_))));
+        let stream = store.load("Stream-1").await.unwrap();
+        assert_eq!(stream.events, vec!["first".to_string()]);
+        assert_eq!(stream.version, 1);
+        assert_eq!(store.head().await.unwrap(), 1);
+    }
+
+    #[tokio::test]
+    #[ignore = "starts Postgres in Docker"]
+    async fn integration_it_should_append_load_compact_and_import() {
+        let (_container, pool) = start().await;
+        let store = PostgresEventStore::<String>::new(pool);
+
+        store
+            .append_many(vec![
+                StreamAppend::new("A", 0, vec!["a1".to_string(), "a2".to_string()]),
+                StreamAppend::new("B", 0, vec!["b1".to_string()]),
+            ])
+            .await
+            .unwrap();
+        assert!(matches!(
+            store.append("A", 1, &["a3".to_string()]).await,
+            Err(EventStoreError::VersionMismatch {
+                expected: 1,
+                actual: 2
+            })
+        ));
+        let page = store.load_paged("A", 1, 10).await.unwrap();
+        assert_eq!((page.events, page.version), (vec!["a2".to_string()], 2));
+
+        let removed = store.compact("A", 2, "a-compacted".to_string()).await;
+        assert_eq!(removed.unwrap(), 1);
+        let stream = store.load("A").await.unwrap();
+        assert_eq!(
+            (stream.events, stream.version),
+            (vec!["a-compacted".to_string()], 1)
+        );
+        store
+            .import(vec![StoredEvent {
+                global_position: 7,
+                stream_id: "B".to_string(),
+                stream_version: 2,
+                event: "b2".to_string(),
+            }])
+            .await
+            .unwrap();
+
+        let log: Vec<_> = store
+            .load_all_from(0)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|stored| (stored.global_position, stored.stream_id, stored.event))
+            .collect();
+        assert_eq!(
+            log,
+            vec![
+                (1, "A".to_string(), "a-compacted".to_string()),
+                (2, "B".to_string(), "b1".to_string()),
+                (7, "B".to_string(), "b2".to_string()),
+            ]
+        );
+        assert_eq!(store.head().await.unwrap(), 8);
+    }
+}
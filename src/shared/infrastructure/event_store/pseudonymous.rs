@@ -0,0 +1,175 @@
+// Event store decorator that keeps subjects out of stream ids.
+//
+// Streams named `{prefix}{subject}`, such as the per-user `UserTimeEntries-{user_id}`, are
+// stored as `{prefix}{key_id}` with the id of the subject's data key, which says nothing of
+// whose it is. Forgetting the subject deletes the key, so nothing leads to the stream
+// anymore and the subject starts afresh with a new one. Other stream ids pass unchanged.
+
+use async_trait::async_trait;
+
+use crate::shared::infrastructure::event_store::{
+    AppendResult, EventStore, EventStoreError, LoadedStream, StreamAppend,
+};
+use crate::shared::infrastructure::key_store::KeyStore;
+
+pub struct PseudonymousStreamStore<TInner, TKeyStore> {
+    inner: TInner,
+    key_store: TKeyStore,
+    prefix: &'static str,
+}
+
+impl<TInner, TKeyStore> PseudonymousStreamStore<TInner, TKeyStore>
+where
+    TKeyStore: KeyStore,
+{
+    pub fn new(inner: TInner, key_store: TKeyStore, prefix: &'static str) -> Self {
+        Self {
+            inner,
+            key_store,
+            prefix,
+        }
+    }
+
+    /// The id `stream_id` is stored under.
+    async fn stored_id(&self, stream_id: &str) -> Result<String, EventStoreError> {
+        let Some(subject) = stream_id.strip_prefix(self.prefix) else {
+            return Ok(stream_id.to_string());
+        };
+        let key = self.key_store.key_for_subject(subject).await?;
+        Ok(format!("{}{}", self.prefix, key.id))
+    }
+}
+
+#[async_trait]
+impl<E, TInner, TKeyStore> EventStore<E> for PseudonymousStreamStore<TInner, TKeyStore>
+where
+    E: Clone + Send + Sync + 'static,
+    TInner: EventStore<E>,
+    TKeyStore: KeyStore,
+{
+    async fn load(&self, stream_id: &str) -> Result<LoadedStream<E>, EventStoreError> {
+        self.inner.load(&self.stored_id(stream_id).await?).await
+    }
+
+    async fn append(
+        &self,
+        stream_id: &str,
+        expected_version: i64,
+        new_events: &[E],
+    ) -> Result<AppendResult, EventStoreError> {
+        self.inner
+            .append(
+                &self.stored_id(stream_id).await?,
+                expected_version,
+                new_events,
+            )
+            .await
+    }
+
+    async fn load_paged(
+        &self,
+        stream_id: &str,
+        from_version: i64,
+        limit: usize,
+    ) -> Result<LoadedStream<E>, EventStoreError> {
+        self.inner
+            .load_paged(&self.stored_id(stream_id).await?, from_version, limit)
+            .await
+    }
+
+    async fn append_many(
+        &self,
+        batch: Vec<StreamAppend<E>>,
+    ) -> Result<Vec<AppendResult>, EventStoreError> {
+        let mut stored = Vec::with_capacity(batch.len());
+        for append in batch {
+            stored.push(StreamAppend {
+                stream_id: self.stored_id(&append.stream_id).await?,
+                ..append
+            });
+        }
+        self.inner.append_many(stored).await
+    }
+}
+
+#[cfg(test)]
+mod pseudonymous_stream_store_tests {
+    use super::*;
+    use crate::shared::infrastructure::event_store::in_memory::InMemoryEventStore;
+    use crate::shared::infrastructure::key_store::in_memory::InMemoryKeyStore;
+    use rstest::rstest;
+
+    const PREFIX: &str = "UserTimeEntries-";
+
+    fn setup() -> (
+        InMemoryEventStore<String>,
+        InMemoryKeyStore,
+        PseudonymousStreamStore<InMemoryEventStore<String>, InMemoryKeyStore>,
+    ) {
+        let inner = InMemoryEventStore::<String>::new();
+        let key_store = InMemoryKeyStore::new();
+        let store = PseudonymousStreamStore::new(inner.clone(), key_store.clone(), PREFIX);
+        (inner, key_store, store)
+    }
+
+    async fn stored_ids(inner: &InMemoryEventStore<String>) -> Vec<String> {
+        let log = inner.load_all_from(0).await.unwrap();
+        log.into_iter().map(|stored| stored.stream_id).collect()
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_store_subject_streams_under_the_subjects_key_id() {
+        let (inner, key_store, store) = setup();
+
+        store
+            .append("UserTimeEntries-u-1", 0, &["claimed".to_string()])
+            .await
+            .unwrap();
+        store
+            .append_many(vec![
+                StreamAppend::new("UserTimeEntries-u-1", 1, vec!["released".to_string()]),
+                StreamAppend::new("Other-u-1", 0, vec!["other".to_string()]),
+            ])
+            .await
+            .unwrap();
+
+        let key = key_store.key_for_subject("u-1").await.unwrap();
+        let pseudonym = format!("{PREFIX}{}", key.id);
+        assert_eq!(
+            stored_ids(&inner).await,
+            vec![pseudonym.clone(), pseudonym, "Other-u-1".to_string()]
+        );
+        let stream = store.load("UserTimeEntries-u-1").await.unwrap();
+        assert_eq!(stream.events, vec!["claimed", "released"]);
+        let page = store.load_paged("UserTimeEntries-u-1", 1, 1).await.unwrap();
+        assert_eq!(page.events, vec!["released"]);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_start_a_forgotten_subject_afresh() {
+        let (_, key_store, store) = setup();
+        store
+            .append("UserTimeEntries-u-1", 0, &["claimed".to_string()])
+            .await
+            .unwrap();
+
+        key_store.forget_subject("u-1").await.unwrap();
+
+        let stream = store.load("UserTimeEntries-u-1").await.unwrap();
+        assert_eq!((stream.events.len(), stream.version), (0, 0));
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_fail_when_the_key_store_is_offline() {
+        let (_, key_store, store) = setup();
+        key_store.toggle_offline();
+
+        assert!(matches!(
+            store.load("UserTimeEntries-u-1").await,
+            Err(EventStoreError::Backend(_))
+        ));
+    }
+}
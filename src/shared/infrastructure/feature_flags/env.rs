@@ -0,0 +1,110 @@
+use crate::shared::infrastructure::feature_flags::{FeatureFlags, FeatureFlagsError};
+use async_trait::async_trait;
+use std::collections::{BTreeSet, HashMap};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Setting {
+    On,
+    Off,
+    /// On for these tenants, off for the rest.
+    Tenants(BTreeSet<String>),
+}
+
+/// Flags fixed in configuration, such as `reject_overlaps=off;max_entry_duration=t-1,t-2`:
+/// `on` or `off` for every tenant, or the tenants a flag is on for. Changing them takes a
+/// restart; operators override them at runtime through the admin API instead.
+#[derive(Debug, Clone, Default)]
+pub struct EnvFeatureFlags {
+    settings: HashMap<String, Setting>,
+}
+
+impl EnvFeatureFlags {
+    pub fn parse(spec: &str) -> Result<Self, FeatureFlagsError> {
+        let mut settings = HashMap::new();
+        for entry in spec
+            .split(';')
+            .map(str::trim)
+            .filter(|entry| !entry.is_empty())
+        {
+            let Some((name, value)) = entry.split_once('=') else {
+                return Err(FeatureFlagsError::Invalid(format!(
+                    "expected name=value, got {entry}"
+                )));
+            };
+            let setting = match value.trim() {
+                "on" | "true" => Setting::On,
+                "off" | "false" => Setting::Off,
+                tenants => Setting::Tenants(
+                    tenants
+                        .split(',')
+                        .map(str::trim)
+                        .filter(|tenant| !tenant.is_empty())
+                        .map(str::to_string)
+                        .collect(),
+                ),
+            };
+            settings.insert(name.trim().to_string(), setting);
+        }
+        Ok(Self { settings })
+    }
+
+    /// Reads the spec from `var`; an unset variable sets no flags.
+    pub fn from_env(var: &str) -> Result<Self, FeatureFlagsError> {
+        match std::env::var(var) {
+            Ok(spec) => Self::parse(&spec),
+            Err(_) => Ok(Self::default()),
+        }
+    }
+}
+
+#[async_trait]
+impl FeatureFlags for EnvFeatureFlags {
+    async fn lookup(
+        &self,
+        flag: &str,
+        tenant_id: Option<&str>,
+    ) -> Result<Option<bool>, FeatureFlagsError> {
+        Ok(self.settings.get(flag).map(|setting| match setting {
+            Setting::On => true,
+            Setting::Off => false,
+            Setting::Tenants(tenants) => tenant_id.is_some_and(|tenant| tenants.contains(tenant)),
+        }))
+    }
+}
+
+#[cfg(test)]
+mod env_feature_flags_tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case::off("reject_overlaps", Some("t-1"), Some(false))]
+    #[case::on("auto_approve", Some("t-9"), Some(true))]
+    #[case::listed_tenant("max_entry_duration", Some("t-2"), Some(true))]
+    #[case::other_tenant("max_entry_duration", Some("t-3"), Some(false))]
+    #[case::no_tenant("max_entry_duration", None, Some(false))]
+    #[case::unset("unknown", Some("t-1"), None)]
+    #[tokio::test]
+    async fn it_should_look_flags_up_per_tenant(
+        #[case] flag: &str,
+        #[case] tenant_id: Option<&str>,
+        #[case] expected: Option<bool>,
+    ) {
+        let flags = EnvFeatureFlags::parse(
+            "reject_overlaps=off; auto_approve=on; max_entry_duration=t-1, t-2;",
+        )
+        .unwrap();
+
+        assert_eq!(flags.lookup(flag, tenant_id).await, Ok(expected));
+    }
+
+    #[rstest]
+    fn it_should_reject_entries_without_a_value() {
+        assert_eq!(
+            EnvFeatureFlags::parse("reject_overlaps").err(),
+            Some(FeatureFlagsError::Invalid(
+                "expected name=value, got reject_overlaps".to_string()
+            ))
+        );
+    }
+}
@@ -0,0 +1,200 @@
+use crate::shared::infrastructure::feature_flags::{
+    FeatureFlags, FeatureFlagsError, SharedFeatureFlags,
+};
+use async_trait::async_trait;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use tokio::sync::RwLock;
+
+/// An operator's setting of one flag, for one tenant or, without one, for all of them.
+/// Times are epoch milliseconds.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct FlagOverride {
+    pub flag: String,
+    pub tenant_id: Option<String>,
+    pub enabled: bool,
+    pub updated_at: i64,
+    pub updated_by: String,
+}
+
+type OverrideKey = (String, Option<String>);
+
+#[derive(Default)]
+struct Inner {
+    overrides: RwLock<BTreeMap<OverrideKey, FlagOverride>>,
+    is_offline: AtomicBool,
+}
+
+/// Overrides set through the admin API, held for the life of the process. A tenant's
+/// override wins over one for every tenant; flags without either are looked up in the
+/// fallback, typically the configured or flag service source. Clones share the overrides.
+#[derive(Clone, Default)]
+pub struct InMemoryFeatureFlags {
+    inner: Arc<Inner>,
+    fallback: Option<SharedFeatureFlags>,
+}
+
+impl InMemoryFeatureFlags {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_fallback(mut self, fallback: SharedFeatureFlags) -> Self {
+        self.fallback = Some(fallback);
+        self
+    }
+
+    pub fn toggle_offline(&self) {
+        self.inner.is_offline.fetch_xor(true, Ordering::SeqCst);
+    }
+
+    fn ensure_online(&self) -> Result<(), FeatureFlagsError> {
+        if self.inner.is_offline.load(Ordering::SeqCst) {
+            return Err(FeatureFlagsError::Backend(
+                "Feature flag store offline".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Replaces the override for the flag and tenant of `flag_override`.
+    pub async fn set(&self, flag_override: FlagOverride) -> Result<(), FeatureFlagsError> {
+        self.ensure_online()?;
+        let key = (flag_override.flag.clone(), flag_override.tenant_id.clone());
+        self.inner
+            .overrides
+            .write()
+            .await
+            .insert(key, flag_override);
+        Ok(())
+    }
+
+    /// Removes the override, so the flag falls back again. Returns whether there was one.
+    pub async fn clear(
+        &self,
+        flag: &str,
+        tenant_id: Option<&str>,
+    ) -> Result<bool, FeatureFlagsError> {
+        self.ensure_online()?;
+        let key = (flag.to_string(), tenant_id.map(str::to_string));
+        Ok(self.inner.overrides.write().await.remove(&key).is_some())
+    }
+
+    /// By flag, the override for every tenant first.
+    pub async fn list(&self) -> Result<Vec<FlagOverride>, FeatureFlagsError> {
+        self.ensure_online()?;
+        Ok(self
+            .inner
+            .overrides
+            .read()
+            .await
+            .values()
+            .cloned()
+            .collect())
+    }
+}
+
+#[async_trait]
+impl FeatureFlags for InMemoryFeatureFlags {
+    async fn lookup(
+        &self,
+        flag: &str,
+        tenant_id: Option<&str>,
+    ) -> Result<Option<bool>, FeatureFlagsError> {
+        self.ensure_online()?;
+        let overridden = {
+            let overrides = self.inner.overrides.read().await;
+            tenant_id
+                .and_then(|tenant| overrides.get(&(flag.to_string(), Some(tenant.to_string()))))
+                .or_else(|| overrides.get(&(flag.to_string(), None)))
+                .map(|flag_override| flag_override.enabled)
+        };
+        match (overridden, &self.fallback) {
+            (Some(enabled), _) => Ok(Some(enabled)),
+            (None, Some(fallback)) => fallback.lookup(flag, tenant_id).await,
+            (None, None) => Ok(None),
+        }
+    }
+}
+
+#[cfg(test)]
+mod in_memory_feature_flags_tests {
+    use super::*;
+    use crate::shared::infrastructure::feature_flags::env::EnvFeatureFlags;
+    use rstest::rstest;
+
+    fn flag_override(tenant_id: Option<&str>, enabled: bool) -> FlagOverride {
+        FlagOverride {
+            flag: "max_entry_duration".to_string(),
+            tenant_id: tenant_id.map(str::to_string),
+            enabled,
+            updated_at: 1,
+            updated_by: "admin-1".to_string(),
+        }
+    }
+
+    fn flags() -> InMemoryFeatureFlags {
+        let configured = EnvFeatureFlags::parse("max_entry_duration=t-3").unwrap();
+        InMemoryFeatureFlags::new().with_fallback(Arc::new(configured))
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_prefer_tenant_overrides_then_global_ones_then_the_fallback() {
+        let flags = flags();
+        assert_eq!(
+            flags.lookup("max_entry_duration", Some("t-3")).await,
+            Ok(Some(true))
+        );
+
+        flags.set(flag_override(None, false)).await.unwrap();
+        flags.set(flag_override(Some("t-1"), true)).await.unwrap();
+
+        assert_eq!(
+            flags.lookup("max_entry_duration", Some("t-1")).await,
+            Ok(Some(true))
+        );
+        assert_eq!(
+            flags.lookup("max_entry_duration", Some("t-3")).await,
+            Ok(Some(false))
+        );
+        assert_eq!(flags.lookup("reject_overlaps", Some("t-1")).await, Ok(None));
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_fall_back_once_an_override_is_cleared() {
+        let flags = flags();
+        flags.set(flag_override(Some("t-3"), false)).await.unwrap();
+
+        assert_eq!(
+            flags.clear("max_entry_duration", Some("t-3")).await,
+            Ok(true)
+        );
+        assert_eq!(
+            flags.clear("max_entry_duration", Some("t-3")).await,
+            Ok(false)
+        );
+        assert_eq!(
+            flags.lookup("max_entry_duration", Some("t-3")).await,
+            Ok(Some(true))
+        );
+        assert!(flags.list().await.unwrap().is_empty());
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_list_global_overrides_before_tenant_ones() {
+        let flags = flags();
+        flags.set(flag_override(Some("t-1"), true)).await.unwrap();
+        flags.set(flag_override(None, false)).await.unwrap();
+
+        let listed = flags.list().await.unwrap();
+
+        assert_eq!(
+            listed,
+            vec![flag_override(None, false), flag_override(Some("t-1"), true)]
+        );
+    }
+}
@@ -0,0 +1,94 @@
+use async_trait::async_trait;
+use std::sync::Arc;
+use thiserror::Error;
+
+#[derive(Debug, Clone, Error, PartialEq, Eq)]
+pub enum FeatureFlagsError {
+    #[error("invalid flag configuration: {0}")]
+    Invalid(String),
+
+    #[error("backend error: {0}")]
+    Backend(String),
+}
+
+/// A switch for a rule being rolled out, with the state it has wherever no source sets it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+pub struct FeatureFlag {
+    pub name: &'static str,
+    pub description: &'static str,
+    pub default: bool,
+}
+
+/// Where flag states come from: configuration, an operator's override, a flag service.
+#[async_trait]
+pub trait FeatureFlags: Send + Sync {
+    /// Whether `flag` is on for `tenant_id`, or `None` when this source does not set it.
+    /// Without a tenant only settings for every tenant apply.
+    async fn lookup(
+        &self,
+        flag: &str,
+        tenant_id: Option<&str>,
+    ) -> Result<Option<bool>, FeatureFlagsError>;
+}
+
+pub type SharedFeatureFlags = Arc<dyn FeatureFlags>;
+
+/// Whether `flag` is on for `tenant_id`. A flag no source sets, or one that cannot be read,
+/// takes its default, so an unreachable flag service never changes the rules.
+pub async fn is_enabled(
+    flags: &dyn FeatureFlags,
+    flag: &FeatureFlag,
+    tenant_id: Option<&str>,
+) -> bool {
+    match flags.lookup(flag.name, tenant_id).await {
+        Ok(enabled) => enabled.unwrap_or(flag.default),
+        Err(reason) => {
+            tracing::warn!(%reason, flag = flag.name, "feature flags unavailable, using the default");
+            flag.default
+        }
+    }
+}
+
+pub mod env;
+pub mod in_memory;
+pub mod unleash;
+
+#[cfg(test)]
+mod feature_flags_tests {
+    use super::*;
+    use rstest::rstest;
+
+    struct Fixed(Result<Option<bool>, FeatureFlagsError>);
+
+    #[async_trait]
+    impl FeatureFlags for Fixed {
+        async fn lookup(
+            &self,
+            _flag: &str,
+            _tenant_id: Option<&str>,
+        ) -> Result<Option<bool>, FeatureFlagsError> {
+            self.0.clone()
+        }
+    }
+
+    const FLAG: FeatureFlag = FeatureFlag {
+        name: "reject_overlaps",
+        description: "",
+        default: true,
+    };
+
+    #[rstest]
+    #[case::set(Ok(Some(false)), false)]
+    #[case::unset(Ok(None), true)]
+    #[case::unavailable(Err(FeatureFlagsError::Backend("down".to_string())), true)]
+    #[tokio::test]
+    async fn it_should_fall_back_to_the_default(
+        #[case] lookup: Result<Option<bool>, FeatureFlagsError>,
+        #[case] expected: bool,
+    ) {
+        assert_eq!(
+            is_enabled(&Fixed(lookup), &FLAG, Some("t-1")).await,
+            expected
+        );
+    }
+}
@@ -0,0 +1,242 @@
+use crate::shared::infrastructure::calendar::http::HttpResponse;
+use crate::shared::infrastructure::feature_flags::{FeatureFlags, FeatureFlagsError};
+use async_trait::async_trait;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+/// The transport the Unleash adapter uses, so it stays independent of an HTTP client crate.
+/// Implementations send `api_token` as the `Authorization` header.
+#[async_trait]
+pub trait UnleashHttp: Send + Sync {
+    async fn get(&self, url: &str, api_token: &str) -> Result<HttpResponse, String>;
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct Features {
+    features: Vec<Feature>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct Feature {
+    name: String,
+    enabled: bool,
+    #[serde(default)]
+    strategies: Vec<Strategy>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct Strategy {
+    name: String,
+    #[serde(default)]
+    parameters: HashMap<String, String>,
+}
+
+/// The custom strategy that turns a flag on for the tenants in its `tenantIds` parameter.
+pub const TENANT_STRATEGY: &str = "tenantWithId";
+
+impl Feature {
+    /// Unleash semantics: an enabled toggle is on when it has no strategies or any of them
+    /// matches. Strategies other than `default` and `tenantWithId` never match.
+    fn is_on(&self, tenant_id: Option<&str>) -> bool {
+        self.enabled
+            && (self.strategies.is_empty()
+                || self
+                    .strategies
+                    .iter()
+                    .any(|strategy| match strategy.name.as_str() {
+                        "default" => true,
+                        TENANT_STRATEGY => tenant_id.is_some_and(|tenant| {
+                            strategy
+                                .parameters
+                                .get("tenantIds")
+                                .is_some_and(|ids| ids.split(',').any(|id| id.trim() == tenant))
+                        }),
+                        _ => false,
+                    }))
+    }
+}
+
+/// Flags from an Unleash server, read from the client API at `{api_url}/client/features`.
+/// Toggles are evaluated locally against the last successful `refresh`, so a command never
+/// waits on Unleash; until the first refresh no flag is set. Clones share the toggles.
+#[derive(Clone)]
+pub struct UnleashFeatureFlags<TClient> {
+    api_url: String,
+    api_token: String,
+    client: TClient,
+    features: Arc<RwLock<HashMap<String, Feature>>>,
+}
+
+impl<TClient: UnleashHttp> UnleashFeatureFlags<TClient> {
+    pub fn new(api_url: impl Into<String>, api_token: impl Into<String>, client: TClient) -> Self {
+        Self {
+            api_url: api_url.into().trim_end_matches('/').to_string(),
+            api_token: api_token.into(),
+            client,
+            features: Arc::default(),
+        }
+    }
+
+    /// Fetches every toggle and replaces the ones held. Returns how many there are; on
+    /// failure the previous toggles stay.
+    pub async fn refresh(&self) -> Result<usize, FeatureFlagsError> {
+        let url = format!("{}/client/features", self.api_url);
+        let response = self
+            .client
+            .get(&url, &self.api_token)
+            .await
+            .map_err(FeatureFlagsError::Backend)?;
+        if response.status != 200 {
+            return Err(FeatureFlagsError::Backend(format!(
+                "GET {url} returned {}",
+                response.status
+            )));
+        }
+        let fetched: Features = serde_json::from_str(&response.body)
+            .map_err(|error| FeatureFlagsError::Backend(format!("GET {url}: {error}")))?;
+        let count = fetched.features.len();
+        *self.features.write().await = fetched
+            .features
+            .into_iter()
+            .map(|feature| (feature.name.clone(), feature))
+            .collect();
+        Ok(count)
+    }
+}
+
+#[async_trait]
+impl<TClient: UnleashHttp> FeatureFlags for UnleashFeatureFlags<TClient> {
+    async fn lookup(
+        &self,
+        flag: &str,
+        tenant_id: Option<&str>,
+    ) -> Result<Option<bool>, FeatureFlagsError> {
+        Ok(self
+            .features
+            .read()
+            .await
+            .get(flag)
+            .map(|feature| feature.is_on(tenant_id)))
+    }
+}
+
+#[cfg(test)]
+mod unleash_feature_flags_tests {
+    use super::*;
+    use rstest::rstest;
+    use std::sync::Mutex;
+
+    #[derive(Clone)]
+    struct StubClient {
+        response: Arc<Mutex<Result<HttpResponse, String>>>,
+        calls: Arc<Mutex<Vec<(String, String)>>>,
+    }
+
+    impl StubClient {
+        fn answering(response: Result<HttpResponse, String>) -> Self {
+            Self {
+                response: Arc::new(Mutex::new(response)),
+                calls: Arc::default(),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl UnleashHttp for StubClient {
+        async fn get(&self, url: &str, api_token: &str) -> Result<HttpResponse, String> {
+            self.calls
+                .lock()
+                .unwrap()
+                .push((url.to_string(), api_token.to_string()));
+            self.response.lock().unwrap().clone()
+        }
+    }
+
+    const FEATURES: &str = r#"{
+        "version": 2,
+        "features": [
+            { "name": "reject_overlaps", "enabled": false, "strategies": [{ "name": "default" }] },
+            { "name": "auto_approve", "enabled": true, "strategies": [] },
+            {
+                "name": "max_entry_duration",
+                "enabled": true,
+                "strategies": [
+                    { "name": "tenantWithId", "parameters": { "tenantIds": "t-1, t-2" } },
+                    { "name": "gradualRolloutRandom", "parameters": { "percentage": "50" } }
+                ]
+            }
+        ]
+    }"#;
+
+    fn ok(body: &str) -> Result<HttpResponse, String> {
+        Ok(HttpResponse {
+            status: 200,
+            body: body.to_string(),
+        })
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_fetch_toggles_with_the_api_token() {
+        let client = StubClient::answering(ok(FEATURES));
+        let flags = UnleashFeatureFlags::new(
+            "https://unleash.test/api/",
+            "*:production.abc",
+            client.clone(),
+        );
+
+        assert_eq!(flags.lookup("auto_approve", None).await, Ok(None));
+        assert_eq!(flags.refresh().await, Ok(3));
+        assert_eq!(
+            *client.calls.lock().unwrap(),
+            vec![(
+                "https://unleash.test/api/client/features".to_string(),
+                "*:production.abc".to_string()
+            )]
+        );
+    }
+
+    #[rstest]
+    #[case::disabled("reject_overlaps", Some("t-1"), Some(false))]
+    #[case::no_strategies("auto_approve", None, Some(true))]
+    #[case::listed_tenant("max_entry_duration", Some("t-2"), Some(true))]
+    #[case::unlisted_tenant("max_entry_duration", Some("t-3"), Some(false))]
+    #[case::unknown("unknown", Some("t-1"), None)]
+    #[tokio::test]
+    async fn it_should_evaluate_strategies_per_tenant(
+        #[case] flag: &str,
+        #[case] tenant_id: Option<&str>,
+        #[case] expected: Option<bool>,
+    ) {
+        let flags = UnleashFeatureFlags::new(
+            "https://unleash.test/api",
+            "token",
+            StubClient::answering(ok(FEATURES)),
+        );
+        flags.refresh().await.unwrap();
+
+        assert_eq!(flags.lookup(flag, tenant_id).await, Ok(expected));
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_keep_the_last_toggles_when_a_refresh_fails() {
+        let client = StubClient::answering(ok(FEATURES));
+        let flags = UnleashFeatureFlags::new("https://unleash.test/api", "token", client.clone());
+        flags.refresh().await.unwrap();
+        *client.response.lock().unwrap() = Ok(HttpResponse {
+            status: 503,
+            body: String::new(),
+        });
+
+        assert_eq!(
+            flags.refresh().await,
+            Err(FeatureFlagsError::Backend(
+                "GET https://unleash.test/api/client/features returned 503".to_string()
+            ))
+        );
+        assert_eq!(flags.lookup("auto_approve", None).await, Ok(Some(true)));
+    }
+}
@@ -0,0 +1,131 @@
+use crate::shared::infrastructure::inbox::{Inbox, InboxError};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use tokio::sync::Mutex;
+
+#[derive(Default)]
+struct Inner {
+    processed: Mutex<HashMap<(String, String), i64>>,
+    is_offline: AtomicBool,
+}
+
+#[derive(Clone, Default)]
+pub struct InMemoryInbox {
+    inner: Arc<Inner>,
+}
+
+impl InMemoryInbox {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn toggle_offline(&self) {
+        self.inner.is_offline.fetch_xor(true, Ordering::SeqCst);
+    }
+
+    pub async fn len(&self) -> usize {
+        self.inner.processed.lock().await.len()
+    }
+
+    pub async fn is_empty(&self) -> bool {
+        self.len().await == 0
+    }
+
+    fn ensure_online(&self) -> Result<(), InboxError> {
+        if self.inner.is_offline.load(Ordering::SeqCst) {
+            return Err(InboxError::Backend("Inbox offline".to_string()));
+        }
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl Inbox for InMemoryInbox {
+    async fn contains(&self, consumer: &str, message_id: &str) -> Result<bool, InboxError> {
+        self.ensure_online()?;
+        Ok(self
+            .inner
+            .processed
+            .lock()
+            .await
+            .contains_key(&(consumer.to_string(), message_id.to_string())))
+    }
+
+    async fn record(
+        &self,
+        consumer: &str,
+        message_id: &str,
+        processed_at: i64,
+    ) -> Result<bool, InboxError> {
+        self.ensure_online()?;
+        let mut processed = self.inner.processed.lock().await;
+        let key = (consumer.to_string(), message_id.to_string());
+        if processed.contains_key(&key) {
+            return Ok(false);
+        }
+        processed.insert(key, processed_at);
+        Ok(true)
+    }
+
+    async fn purge_before(&self, cutoff: i64) -> Result<u64, InboxError> {
+        self.ensure_online()?;
+        let mut processed = self.inner.processed.lock().await;
+        let before = processed.len();
+        processed.retain(|_, processed_at| *processed_at >= cutoff);
+        Ok((before - processed.len()) as u64)
+    }
+}
+
+#[cfg(test)]
+mod in_memory_inbox_tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_record_each_message_once_per_consumer() {
+        let inbox = InMemoryInbox::new();
+
+        assert!(inbox.record("billing", "m-1", 1).await.unwrap());
+        assert!(!inbox.record("billing", "m-1", 2).await.unwrap());
+        assert!(inbox.record("jira-sync", "m-1", 3).await.unwrap());
+
+        assert!(inbox.contains("billing", "m-1").await.unwrap());
+        assert!(!inbox.contains("billing", "m-2").await.unwrap());
+        assert_eq!(inbox.len().await, 2);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_purge_entries_processed_before_the_cutoff() {
+        let inbox = InMemoryInbox::new();
+        for (message_id, processed_at) in [("m-1", 10), ("m-2", 20), ("m-3", 30)] {
+            inbox
+                .record("billing", message_id, processed_at)
+                .await
+                .unwrap();
+        }
+
+        assert_eq!(inbox.purge_before(20).await.unwrap(), 1);
+
+        assert!(!inbox.contains("billing", "m-1").await.unwrap());
+        assert!(inbox.contains("billing", "m-2").await.unwrap());
+        assert_eq!(inbox.purge_before(100).await.unwrap(), 2);
+        assert!(inbox.is_empty().await);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_fail_every_operation_when_offline() {
+        let inbox = InMemoryInbox::new();
+        inbox.toggle_offline();
+
+        assert!(inbox.contains("billing", "m-1").await.is_err());
+        assert!(inbox.record("billing", "m-1", 1).await.is_err());
+        assert!(matches!(
+            inbox.purge_before(1).await,
+            Err(InboxError::Backend(_))
+        ));
+    }
+}
@@ -0,0 +1,138 @@
+// Inbox for messages consumed from outside (Kafka, Pulsar, NATS).
+//
+// Brokers deliver at least once, so a consumer feeding a process manager sees some messages
+// twice. The inbox records which message ids each consumer has processed; `process_once`
+// skips messages already recorded and records the others once their handler succeeded.
+// Adapters sharing a database with the handler's writes should record in the same
+// transaction; otherwise a crash between the two repeats the handler on redelivery, so
+// handlers must still tolerate the rare repeat. Entries are purged after a retention period
+// longer than any redelivery window.
+
+use async_trait::async_trait;
+use std::future::Future;
+use thiserror::Error;
+
+pub const DEFAULT_RETENTION_MS: i64 = 7 * 24 * 60 * 60 * 1000;
+
+#[derive(Debug, Clone, Error, PartialEq, Eq)]
+pub enum InboxError {
+    #[error("backend error: {0}")]
+    Backend(String),
+}
+
+/// Processed message ids per consumer. Times are epoch milliseconds.
+#[async_trait]
+pub trait Inbox: Send + Sync {
+    async fn contains(&self, consumer: &str, message_id: &str) -> Result<bool, InboxError>;
+
+    /// Records `message_id` as processed by `consumer`. Returns `false` when it already was.
+    async fn record(
+        &self,
+        consumer: &str,
+        message_id: &str,
+        processed_at: i64,
+    ) -> Result<bool, InboxError>;
+
+    /// Forgets every entry processed before `cutoff`. Returns how many were removed.
+    async fn purge_before(&self, cutoff: i64) -> Result<u64, InboxError>;
+}
+
+#[derive(Debug, Error)]
+pub enum InboxProcessError<E> {
+    #[error(transparent)]
+    Inbox(#[from] InboxError),
+
+    #[error(transparent)]
+    Handler(E),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InboxOutcome<T> {
+    Processed(T),
+    /// Already processed by this consumer; the handler did not run.
+    Duplicate,
+}
+
+/// Runs `handle` unless `consumer` already processed `message_id`, then records it. A failed
+/// handler is not recorded, so the message is processed again when redelivered.
+pub async fn process_once<T, E, Fut>(
+    inbox: &(impl Inbox + ?Sized),
+    consumer: &str,
+    message_id: &str,
+    now: i64,
+    handle: impl FnOnce() -> Fut,
+) -> Result<InboxOutcome<T>, InboxProcessError<E>>
+where
+    Fut: Future<Output = Result<T, E>>,
+{
+    if inbox.contains(consumer, message_id).await? {
+        return Ok(InboxOutcome::Duplicate);
+    }
+    let output = handle().await.map_err(InboxProcessError::Handler)?;
+    inbox.record(consumer, message_id, now).await?;
+    Ok(InboxOutcome::Processed(output))
+}
+
+pub mod in_memory;
+
+#[cfg(test)]
+mod process_once_tests {
+    use super::*;
+    use crate::shared::infrastructure::inbox::in_memory::InMemoryInbox;
+    use rstest::rstest;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_run_the_handler_once_per_consumer_and_message() {
+        let inbox = InMemoryInbox::new();
+        let runs = AtomicU32::new(0);
+        let handle = || async {
+            runs.fetch_add(1, Ordering::SeqCst);
+            Ok::<_, String>("done")
+        };
+
+        let first = process_once(&inbox, "jira-sync", "m-1", 1, handle).await;
+        let again = process_once(&inbox, "jira-sync", "m-1", 2, handle).await;
+        let other = process_once(&inbox, "billing", "m-1", 3, handle).await;
+
+        assert_eq!(first.unwrap(), InboxOutcome::Processed("done"));
+        assert_eq!(again.unwrap(), InboxOutcome::Duplicate);
+        assert_eq!(other.unwrap(), InboxOutcome::Processed("done"));
+        assert_eq!(runs.load(Ordering::SeqCst), 2);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_not_record_messages_whose_handler_failed() {
+        let inbox = InMemoryInbox::new();
+
+        let failed = process_once(&inbox, "jira-sync", "m-1", 1, || async {
+            Err::<(), _>("jira down")
+        })
+        .await;
+
+        assert!(matches!(
+            failed,
+            Err(InboxProcessError::Handler("jira down"))
+        ));
+        assert!(!inbox.contains("jira-sync", "m-1").await.unwrap());
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_fail_without_running_the_handler_when_the_inbox_is_offline() {
+        let inbox = InMemoryInbox::new();
+        inbox.toggle_offline();
+        let runs = AtomicU32::new(0);
+
+        let result = process_once(&inbox, "jira-sync", "m-1", 1, || async {
+            runs.fetch_add(1, Ordering::SeqCst);
+            Ok::<(), String>(())
+        })
+        .await;
+
+        assert!(matches!(result, Err(InboxProcessError::Inbox(_))));
+        assert_eq!(runs.load(Ordering::SeqCst), 0);
+    }
+}
@@ -0,0 +1,154 @@
+use crate::shared::infrastructure::inbox::Inbox;
+use crate::shared::infrastructure::intent_handlers::{IntentHandler, IntentHandlerError};
+use crate::shared::infrastructure::intent_outbox::OutboxRow;
+use async_trait::async_trait;
+
+/// Runs `inner` only over the rows it has not handled yet, recorded in the inbox under
+/// `consumer` by `OutboxRow::dedupe_key`. When another handler fails a batch and the relay
+/// resends it, the rows this one already executed are skipped. Rows are recorded after `inner`
+/// succeeds, so a crash in between still repeats them.
+pub struct DeduplicatedIntentHandler<TInner, TInbox> {
+    consumer: String,
+    inner: TInner,
+    inbox: TInbox,
+}
+
+impl<TInner, TInbox> DeduplicatedIntentHandler<TInner, TInbox> {
+    pub fn new(consumer: impl Into<String>, inner: TInner, inbox: TInbox) -> Self {
+        Self {
+            consumer: consumer.into(),
+            inner,
+            inbox,
+        }
+    }
+}
+
+#[async_trait]
+impl<TInner, TInbox> IntentHandler for DeduplicatedIntentHandler<TInner, TInbox>
+where
+    TInner: IntentHandler,
+    TInbox: Inbox,
+{
+    async fn handle(&self, topic: &str, rows: &[OutboxRow]) -> Result<(), IntentHandlerError> {
+        let inbox_failed = |error| IntentHandlerError::Failed(format!("inbox: {error}"));
+        let mut fresh = Vec::with_capacity(rows.len());
+        for row in rows {
+            let handled = self
+                .inbox
+                .contains(&self.consumer, &row.dedupe_key())
+                .await
+                .map_err(inbox_failed)?;
+            if !handled {
+                fresh.push(row.clone());
+            }
+        }
+        if fresh.is_empty() {
+            return Ok(());
+        }
+        self.inner.handle(topic, &fresh).await?;
+        let now = chrono::Utc::now().timestamp_millis();
+        for row in &fresh {
+            self.inbox
+                .record(&self.consumer, &row.dedupe_key(), now)
+                .await
+                .map_err(inbox_failed)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod deduplicated_intent_handler_tests {
+    use super::*;
+    use crate::shared::infrastructure::inbox::in_memory::InMemoryInbox;
+    use crate::shared::infrastructure::intent_outbox::OutboxStatus;
+    use rstest::rstest;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use tokio::sync::Mutex;
+
+    #[derive(Default)]
+    struct Recording {
+        handled: Mutex<Vec<i64>>,
+        fail: AtomicBool,
+    }
+
+    #[async_trait]
+    impl IntentHandler for &Recording {
+        async fn handle(&self, _topic: &str, rows: &[OutboxRow]) -> Result<(), IntentHandlerError> {
+            if self.fail.load(Ordering::SeqCst) {
+                return Err(IntentHandlerError::Failed("webhook down".to_string()));
+            }
+            self.handled
+                .lock()
+                .await
+                .extend(rows.iter().map(|row| row.stream_version));
+            Ok(())
+        }
+    }
+
+    fn row(stream_version: i64) -> OutboxRow {
+        OutboxRow {
+            topic: "time-entries.v1".to_string(),
+            event_type: "TimerAutoStopped".to_string(),
+            event_version: 1,
+            stream_id: "TimeEntry-te-1".to_string(),
+            stream_version,
+            intent_no: 0,
+            occurred_at: 0,
+            payload: serde_json::Value::Null,
+            content_encoding: None,
+            status: OutboxStatus::Pending,
+            attempts: 0,
+            last_error: None,
+            published_at: None,
+        }
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_skip_rows_it_already_handled_when_a_batch_is_resent() {
+        let recording = Recording::default();
+        let inbox = InMemoryInbox::new();
+        let handler = DeduplicatedIntentHandler::new("broker-publisher", &recording, inbox.clone());
+
+        handler.handle("t", &[row(1), row(2)]).await.unwrap();
+        handler
+            .handle("t", &[row(1), row(2), row(3)])
+            .await
+            .unwrap();
+        handler.handle("t", &[row(3)]).await.unwrap();
+
+        assert_eq!(*recording.handled.lock().await, vec![1, 2, 3]);
+        assert_eq!(inbox.len().await, 3);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_run_rows_again_when_the_handler_failed() {
+        let recording = Recording::default();
+        let inbox = InMemoryInbox::new();
+        let handler = DeduplicatedIntentHandler::new("broker-publisher", &recording, inbox.clone());
+        recording.fail.store(true, Ordering::SeqCst);
+
+        let failed = handler.handle("t", &[row(1)]).await;
+        recording.fail.store(false, Ordering::SeqCst);
+        handler.handle("t", &[row(1)]).await.unwrap();
+
+        assert!(failed.is_err());
+        assert_eq!(*recording.handled.lock().await, vec![1]);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_fail_without_running_the_handler_when_the_inbox_is_offline() {
+        let recording = Recording::default();
+        let inbox = InMemoryInbox::new();
+        inbox.toggle_offline();
+        let handler = DeduplicatedIntentHandler::new("broker-publisher", &recording, inbox);
+
+        let result = handler.handle("t", &[row(1)]).await;
+
+        assert!(matches!(result, Err(IntentHandlerError::Failed(_))));
+        assert!(recording.handled.lock().await.is_empty());
+    }
+}
@@ -0,0 +1,211 @@
+// Intent handler registry: routes relayed outbox rows to executors by intent type.
+//
+// Each outbox row's `event_type` names the intent it carries. Executors (publishing to the
+// broker, webhooks, emails, Jira sync) implement `IntentHandler` and are registered per type;
+// types without a handler go to the fallback. The registry is a `MessageBroker`, so the
+// outbox relay hands it batches as it would a broker. A failing handler fails the whole
+// batch and the relay resends it, so handlers must tolerate rows they already executed, or be
+// wrapped in `DeduplicatedIntentHandler`.
+
+use crate::shared::infrastructure::intent_outbox::OutboxRow;
+use crate::shared::infrastructure::message_broker::{BrokerError, MessageBroker};
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::sync::Arc;
+use thiserror::Error;
+
+#[derive(Debug, Clone, Error, PartialEq, Eq)]
+pub enum IntentHandlerError {
+    #[error("no handler registered for intent type {0}")]
+    Unhandled(String),
+
+    #[error("intent handler failed: {0}")]
+    Failed(String),
+}
+
+/// Executes the intents of one or more types. `rows` keeps outbox order.
+#[async_trait]
+pub trait IntentHandler: Send + Sync {
+    async fn handle(&self, topic: &str, rows: &[OutboxRow]) -> Result<(), IntentHandlerError>;
+}
+
+#[derive(Clone, Default)]
+pub struct IntentHandlerRegistry {
+    handlers: HashMap<String, Vec<Arc<dyn IntentHandler>>>,
+    fallback: Option<Arc<dyn IntentHandler>>,
+}
+
+impl IntentHandlerRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds `handler` for `intent_type`. Handlers of one type run in registration order.
+    pub fn register(
+        mut self,
+        intent_type: impl Into<String>,
+        handler: Arc<dyn IntentHandler>,
+    ) -> Self {
+        self.handlers
+            .entry(intent_type.into())
+            .or_default()
+            .push(handler);
+        self
+    }
+
+    /// Handles intent types without a registered handler; without one they fail.
+    pub fn with_fallback(mut self, handler: Arc<dyn IntentHandler>) -> Self {
+        self.fallback = Some(handler);
+        self
+    }
+
+    /// Runs every handler over the rows routed to it, in outbox order.
+    pub async fn execute(&self, topic: &str, rows: &[OutboxRow]) -> Result<(), IntentHandlerError> {
+        let mut routed: Vec<(&Arc<dyn IntentHandler>, Vec<OutboxRow>)> = Vec::new();
+        for row in rows {
+            let handlers = match self.handlers.get(&row.event_type) {
+                Some(handlers) => handlers.iter().collect::<Vec<_>>(),
+                None => match &self.fallback {
+                    Some(fallback) => vec![fallback],
+                    None => return Err(IntentHandlerError::Unhandled(row.event_type.clone())),
+                },
+            };
+            for handler in handlers {
+                match routed
+                    .iter_mut()
+                    .find(|(routed_to, _)| Arc::ptr_eq(routed_to, handler))
+                {
+                    Some((_, batch)) => batch.push(row.clone()),
+                    None => routed.push((handler, vec![row.clone()])),
+                }
+            }
+        }
+        for (handler, batch) in routed {
+            handler.handle(topic, &batch).await?;
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl MessageBroker for IntentHandlerRegistry {
+    async fn publish(&self, topic: &str, rows: &[OutboxRow]) -> Result<(), BrokerError> {
+        self.execute(topic, rows).await.map_err(BrokerError::from)
+    }
+}
+
+pub mod deduplicated;
+pub mod publish;
+
+#[cfg(test)]
+mod intent_handler_registry_tests {
+    use super::*;
+    use crate::shared::infrastructure::intent_outbox::OutboxStatus;
+    use rstest::rstest;
+    use tokio::sync::Mutex;
+
+    #[derive(Default)]
+    struct Recording {
+        handled: Mutex<Vec<(String, i64)>>,
+        fail: bool,
+    }
+
+    #[async_trait]
+    impl IntentHandler for Recording {
+        async fn handle(&self, topic: &str, rows: &[OutboxRow]) -> Result<(), IntentHandlerError> {
+            if self.fail {
+                return Err(IntentHandlerError::Failed("webhook down".to_string()));
+            }
+            self.handled.lock().await.extend(
+                rows.iter()
+                    .map(|row| (format!("{topic}:{}", row.event_type), row.stream_version)),
+            );
+            Ok(())
+        }
+    }
+
+    fn row(event_type: &str, stream_version: i64) -> OutboxRow {
+        OutboxRow {
+            topic: "time-entries.v1".to_string(),
+            event_type: event_type.to_string(),
+            event_version: 1,
+            stream_id: "TimeEntry-te-1".to_string(),
+            stream_version,
+            intent_no: 0,
+            occurred_at: 0,
+            payload: serde_json::Value::Null,
+            content_encoding: None,
+            status: OutboxStatus::Pending,
+            attempts: 0,
+            last_error: None,
+            published_at: None,
+        }
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_route_rows_to_the_handlers_of_their_type_and_the_rest_to_the_fallback() {
+        let email = Arc::new(Recording::default());
+        let webhook = Arc::new(Recording::default());
+        let fallback = Arc::new(Recording::default());
+        let registry = IntentHandlerRegistry::new()
+            .register("TimerAutoStopped", email.clone())
+            .register("TimerAutoStopped", webhook.clone())
+            .with_fallback(fallback.clone());
+
+        registry
+            .publish(
+                "t",
+                &[
+                    row("TimeEntryTagsSet", 1),
+                    row("TimerAutoStopped", 2),
+                    row("TimeEntryTagsSet", 3),
+                ],
+            )
+            .await
+            .unwrap();
+
+        let auto_stopped = vec![("t:TimerAutoStopped".to_string(), 2)];
+        assert_eq!(*email.handled.lock().await, auto_stopped);
+        assert_eq!(*webhook.handled.lock().await, auto_stopped);
+        assert_eq!(
+            *fallback.handled.lock().await,
+            vec![
+                ("t:TimeEntryTagsSet".to_string(), 1),
+                ("t:TimeEntryTagsSet".to_string(), 3),
+            ]
+        );
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_fail_rows_without_a_handler_when_there_is_no_fallback() {
+        let registry = IntentHandlerRegistry::new();
+
+        let result = registry.publish("t", &[row("TimerAutoStopped", 1)]).await;
+
+        assert_eq!(
+            result,
+            Err(BrokerError::Handler(IntentHandlerError::Unhandled(
+                "TimerAutoStopped".to_string()
+            )))
+        );
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_fail_the_batch_when_a_handler_fails() {
+        let failing = Arc::new(Recording {
+            fail: true,
+            ..Recording::default()
+        });
+        let registry = IntentHandlerRegistry::new().register("TimerAutoStopped", failing);
+
+        let result = registry.execute("t", &[row("TimerAutoStopped", 1)]).await;
+
+        assert_eq!(
+            result,
+            Err(IntentHandlerError::Failed("webhook down".to_string()))
+        );
+    }
+}
@@ -0,0 +1,85 @@
+use crate::shared::infrastructure::intent_handlers::{IntentHandler, IntentHandlerError};
+use crate::shared::infrastructure::intent_outbox::OutboxRow;
+use crate::shared::infrastructure::message_broker::MessageBroker;
+use async_trait::async_trait;
+
+/// Publishes intents to the message broker, on the topic they were relayed from.
+#[derive(Debug, Clone)]
+pub struct BrokerPublisher<TBroker> {
+    broker: TBroker,
+}
+
+impl<TBroker> BrokerPublisher<TBroker> {
+    pub fn new(broker: TBroker) -> Self {
+        Self { broker }
+    }
+}
+
+#[async_trait]
+impl<TBroker> IntentHandler for BrokerPublisher<TBroker>
+where
+    TBroker: MessageBroker,
+{
+    async fn handle(&self, topic: &str, rows: &[OutboxRow]) -> Result<(), IntentHandlerError> {
+        self.broker
+            .publish(topic, rows)
+            .await
+            .map_err(|error| IntentHandlerError::Failed(error.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod broker_publisher_tests {
+    use super::*;
+    use crate::shared::infrastructure::intent_outbox::OutboxStatus;
+    use crate::shared::infrastructure::message_broker::in_memory::InMemoryMessageBroker;
+    use rstest::rstest;
+
+    fn row() -> OutboxRow {
+        OutboxRow {
+            topic: "time-entries.v1".to_string(),
+            event_type: "TimerAutoStopped".to_string(),
+            event_version: 1,
+            stream_id: "TimeEntry-te-1".to_string(),
+            stream_version: 1,
+            intent_no: 0,
+            occurred_at: 0,
+            payload: serde_json::Value::Null,
+            content_encoding: None,
+            status: OutboxStatus::Pending,
+            attempts: 0,
+            last_error: None,
+            published_at: None,
+        }
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_publish_rows_to_the_broker() {
+        let broker = InMemoryMessageBroker::new();
+        let publisher = BrokerPublisher::new(broker.clone());
+
+        publisher.handle("time-entries.v1", &[row()]).await.unwrap();
+
+        let published = broker.published().await;
+        assert_eq!(published.len(), 1);
+        assert_eq!(published[0].0, "time-entries.v1");
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_fail_when_the_broker_is_unavailable() {
+        let broker = InMemoryMessageBroker::new();
+        broker.toggle_offline();
+        let publisher = BrokerPublisher::new(broker);
+
+        let result = publisher.handle("time-entries.v1", &[row()]).await;
+
+        assert_eq!(
+            result,
+            Err(IntentHandlerError::Failed(
+                "broker unavailable: Broker offline".to_string()
+            ))
+        );
+    }
+}
@@ -0,0 +1,320 @@
+// Compression of large outbox payloads.
+//
+// Entries with long descriptions make for large rows and broker messages. `CompressingOutbox`
+// compresses payloads whose JSON is longer than a threshold before they are enqueued: the
+// row's `payload` then holds the compressed JSON, base64 encoded, as a JSON string, and its
+// `content_encoding` names the algorithm. Smaller payloads, and payloads compression would not
+// shrink, are enqueued as they are.
+//
+// Whatever reads a payload goes through `OutboxRow::decoded_payload`. Publishers encode the
+// decoded row and compress the message body the same way, sending it with a
+// `content-encoding` header, so broker messages stay small too.
+
+use crate::shared::infrastructure::intent_outbox::{DomainOutbox, OutboxError, OutboxRow};
+use async_trait::async_trait;
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use serde_json::Value as Json;
+use std::fmt;
+use std::io::{Read, Write};
+use std::str::FromStr;
+use thiserror::Error;
+
+/// Payloads up to this many bytes of JSON are left uncompressed by default.
+pub const DEFAULT_THRESHOLD_BYTES: usize = 4 * 1024;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ContentEncoding {
+    Gzip,
+    Zstd,
+}
+
+impl ContentEncoding {
+    /// The `content-encoding` token, as HTTP and broker headers name it.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ContentEncoding::Gzip => "gzip",
+            ContentEncoding::Zstd => "zstd",
+        }
+    }
+
+    pub fn compress(self, bytes: &[u8]) -> Vec<u8> {
+        match self {
+            ContentEncoding::Gzip => {
+                let mut encoder =
+                    flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+                encoder
+                    .write_all(bytes)
+                    .expect("writing to memory never fails");
+                encoder.finish().expect("writing to memory never fails")
+            }
+            ContentEncoding::Zstd => {
+                zstd::encode_all(bytes, 0).expect("writing to memory never fails")
+            }
+        }
+    }
+
+    pub fn decompress(self, bytes: &[u8]) -> Result<Vec<u8>, CompressionError> {
+        let mut decompressed = Vec::new();
+        match self {
+            ContentEncoding::Gzip => flate2::read::GzDecoder::new(bytes)
+                .read_to_end(&mut decompressed)
+                .map(|_| decompressed),
+            ContentEncoding::Zstd => zstd::decode_all(bytes),
+        }
+        .map_err(|error| self.corrupt(error))
+    }
+
+    fn corrupt(self, reason: impl ToString) -> CompressionError {
+        CompressionError::Corrupt {
+            encoding: self,
+            reason: reason.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for ContentEncoding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for ContentEncoding {
+    type Err = CompressionError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "gzip" => Ok(ContentEncoding::Gzip),
+            "zstd" => Ok(ContentEncoding::Zstd),
+            _ => Err(CompressionError::UnknownEncoding(value.to_string())),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Error, PartialEq, Eq)]
+pub enum CompressionError {
+    #[error("unknown content encoding {0:?}; expected gzip or zstd")]
+    UnknownEncoding(String),
+
+    #[error("undecodable {encoding} payload: {reason}")]
+    Corrupt {
+        encoding: ContentEncoding,
+        reason: String,
+    },
+}
+
+/// Which payloads to compress, and how.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PayloadCompression {
+    pub encoding: ContentEncoding,
+    /// Payloads whose JSON is at most this many bytes stay uncompressed.
+    pub threshold_bytes: usize,
+}
+
+impl PayloadCompression {
+    pub fn new(encoding: ContentEncoding) -> Self {
+        Self {
+            encoding,
+            threshold_bytes: DEFAULT_THRESHOLD_BYTES,
+        }
+    }
+
+    pub fn with_threshold_bytes(mut self, threshold_bytes: usize) -> Self {
+        self.threshold_bytes = threshold_bytes;
+        self
+    }
+
+    /// `row` with its payload compressed, when its JSON is over the threshold and compressing
+    /// shrinks it. Compressed rows are returned as they are.
+    pub fn compress(&self, mut row: OutboxRow) -> OutboxRow {
+        if row.content_encoding.is_some() {
+            return row;
+        }
+        let json = serde_json::to_vec(&row.payload).expect("a JSON value always serializes");
+        if json.len() <= self.threshold_bytes {
+            return row;
+        }
+        let compressed = STANDARD.encode(self.encoding.compress(&json));
+        if compressed.len() >= json.len() {
+            return row;
+        }
+        row.payload = Json::String(compressed);
+        row.content_encoding = Some(self.encoding);
+        row
+    }
+}
+
+impl OutboxRow {
+    /// The payload as it was enqueued, decompressed when `content_encoding` says it is
+    /// compressed.
+    pub fn decoded_payload(&self) -> Result<Json, CompressionError> {
+        let Some(encoding) = self.content_encoding else {
+            return Ok(self.payload.clone());
+        };
+        let encoded = self
+            .payload
+            .as_str()
+            .ok_or_else(|| encoding.corrupt("not a base64 string"))?;
+        let compressed = STANDARD
+            .decode(encoded)
+            .map_err(|error| encoding.corrupt(error))?;
+        serde_json::from_slice(&encoding.decompress(&compressed)?)
+            .map_err(|error| encoding.corrupt(error))
+    }
+
+    /// This row with its payload decoded and no content encoding.
+    pub fn decompressed(&self) -> Result<OutboxRow, CompressionError> {
+        Ok(OutboxRow {
+            payload: self.decoded_payload()?,
+            content_encoding: None,
+            ..self.clone()
+        })
+    }
+}
+
+/// Compresses large payloads before they are enqueued for publishing.
+#[derive(Debug, Clone)]
+pub struct CompressingOutbox<TOutbox> {
+    inner: TOutbox,
+    compression: Option<PayloadCompression>,
+}
+
+impl<TOutbox> CompressingOutbox<TOutbox> {
+    pub fn new(inner: TOutbox, compression: PayloadCompression) -> Self {
+        Self {
+            inner,
+            compression: Some(compression),
+        }
+    }
+
+    /// Enqueues rows as they are, for deployments that leave compression off.
+    pub fn uncompressed(inner: TOutbox) -> Self {
+        Self {
+            inner,
+            compression: None,
+        }
+    }
+
+    pub fn compression(&self) -> Option<PayloadCompression> {
+        self.compression
+    }
+}
+
+#[async_trait]
+impl<TOutbox> DomainOutbox for CompressingOutbox<TOutbox>
+where
+    TOutbox: DomainOutbox,
+{
+    async fn enqueue(&self, row: OutboxRow) -> Result<(), OutboxError> {
+        let row = match &self.compression {
+            Some(compression) => compression.compress(row),
+            None => row,
+        };
+        self.inner.enqueue(row).await
+    }
+}
+
+#[cfg(test)]
+mod compressing_outbox_tests {
+    use super::*;
+    use crate::shared::infrastructure::intent_outbox::OutboxStatus;
+    use crate::shared::infrastructure::intent_outbox::in_memory::InMemoryDomainOutbox;
+    use rstest::rstest;
+    use serde_json::json;
+
+    fn row(description: &str) -> OutboxRow {
+        OutboxRow {
+            topic: "time-entries.v1".to_string(),
+            event_type: "TimeEntryRegistered".to_string(),
+            event_version: 1,
+            stream_id: "TimeEntry-te-1".to_string(),
+            stream_version: 1,
+            intent_no: 0,
+            occurred_at: 1_000,
+            payload: json!({ "time_entry_id": "te-1", "description": description }),
+            content_encoding: None,
+            status: OutboxStatus::Pending,
+            attempts: 0,
+            last_error: None,
+            published_at: None,
+        }
+    }
+
+    #[rstest]
+    #[case::gzip(ContentEncoding::Gzip)]
+    #[case::zstd(ContentEncoding::Zstd)]
+    #[tokio::test]
+    async fn it_should_compress_payloads_over_the_threshold(#[case] encoding: ContentEncoding) {
+        let inner = InMemoryDomainOutbox::new();
+        let outbox = CompressingOutbox::new(
+            inner.clone(),
+            PayloadCompression::new(encoding).with_threshold_bytes(100),
+        );
+        let verbose = row(&"standup notes ".repeat(50));
+
+        outbox.enqueue(verbose.clone()).await.unwrap();
+
+        let stored = &inner.rows().await[0];
+        assert_eq!(stored.content_encoding, Some(encoding));
+        assert!(stored.payload.as_str().unwrap().len() < verbose.payload.to_string().len());
+        assert_eq!(stored.decoded_payload().unwrap(), verbose.payload);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_enqueue_rows_as_they_are_when_uncompressed() {
+        let inner = InMemoryDomainOutbox::new();
+        let verbose = row(&"standup notes ".repeat(50));
+
+        CompressingOutbox::uncompressed(inner.clone())
+            .enqueue(verbose.clone())
+            .await
+            .unwrap();
+
+        let stored = &inner.rows().await[0];
+        assert_eq!(stored.content_encoding, None);
+        assert_eq!(stored.payload, verbose.payload);
+    }
+
+    #[rstest]
+    #[case::under_the_threshold(1_000)]
+    #[case::too_small_to_shrink(10)]
+    fn it_should_leave_other_payloads_as_they_are(#[case] threshold_bytes: usize) {
+        let compression =
+            PayloadCompression::new(ContentEncoding::Gzip).with_threshold_bytes(threshold_bytes);
+
+        let compressed = compression.compress(row("standup"));
+
+        assert_eq!(compressed.content_encoding, None);
+        assert_eq!(compressed.payload, row("standup").payload);
+    }
+
+    #[rstest]
+    fn it_should_refuse_corrupt_payloads() {
+        let corrupt = OutboxRow {
+            payload: Json::String("bm90IGd6aXA=".to_string()),
+            content_encoding: Some(ContentEncoding::Gzip),
+            ..row("")
+        };
+
+        assert!(matches!(
+            corrupt.decoded_payload(),
+            Err(CompressionError::Corrupt {
+                encoding: ContentEncoding::Gzip,
+                ..
+            })
+        ));
+    }
+
+    #[rstest]
+    #[case("gzip", Ok(ContentEncoding::Gzip))]
+    #[case(" ZSTD ", Ok(ContentEncoding::Zstd))]
+    #[case("br", Err(CompressionError::UnknownEncoding("br".to_string())))]
+    fn it_should_parse_encodings(
+        #[case] value: &str,
+        #[case] expected: Result<ContentEncoding, CompressionError>,
+    ) {
+        assert_eq!(value.parse::<ContentEncoding>(), expected);
+    }
+}
@@ -1,12 +1,16 @@
-use crate::shared::infrastructure::intent_outbox::{DomainOutbox, OutboxError, OutboxRow};
+use crate::shared::infrastructure::intent_outbox::{
+    DomainOutbox, OutboxBacklog, OutboxError, OutboxRelaySource, OutboxRow, OutboxStatus,
+};
 use std::collections::HashSet;
 use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
 use tokio::sync::Mutex;
 
 #[derive(Default)]
 pub struct Inner {
     pub rows: Mutex<Vec<OutboxRow>>,
-    seen: Mutex<HashSet<(String, i64)>>,
+    seen: Mutex<HashSet<(String, i64, u32)>>,
+    is_offline: AtomicBool,
 }
 
 #[derive(Clone, Default)]
@@ -20,18 +24,48 @@ impl InMemoryDomainOutbox {
             inner: Arc::new(Inner::default()),
         }
     }
+
+    pub async fn rows(&self) -> Vec<OutboxRow> {
+        self.inner.rows.lock().await.clone()
+    }
+
+    /// Takes the relay side offline; enqueueing is unaffected.
+    pub fn toggle_offline(&self) {
+        self.inner.is_offline.fetch_xor(true, Ordering::SeqCst);
+    }
+
+    fn ensure_online(&self) -> Result<(), OutboxError> {
+        if self.inner.is_offline.load(Ordering::SeqCst) {
+            return Err(OutboxError::Transient("Outbox offline".to_string()));
+        }
+        Ok(())
+    }
+
+    async fn update(&self, rows: &[OutboxRow], mut apply: impl FnMut(&mut OutboxRow)) {
+        let keys: HashSet<(&str, i64, u32)> = rows
+            .iter()
+            .map(|row| (row.stream_id.as_str(), row.stream_version, row.intent_no))
+            .collect();
+        for row in self.inner.rows.lock().await.iter_mut() {
+            if keys.contains(&(row.stream_id.as_str(), row.stream_version, row.intent_no)) {
+                row.attempts += 1;
+                apply(row);
+            }
+        }
+    }
 }
 
 #[async_trait::async_trait]
 impl DomainOutbox for InMemoryDomainOutbox {
     async fn enqueue(&self, row: OutboxRow) -> Result<(), OutboxError> {
-        let key = (row.stream_id.clone(), row.stream_version);
+        let key = (row.stream_id.clone(), row.stream_version, row.intent_no);
         {
             let mut s = self.inner.seen.lock().await;
             if !s.insert(key) {
                 return Err(OutboxError::Duplicate {
                     stream_id: row.stream_id,
                     stream_version: row.stream_version,
+                    intent_no: row.intent_no,
                 });
             }
         }
@@ -40,6 +74,68 @@ impl DomainOutbox for InMemoryDomainOutbox {
     }
 }
 
+#[async_trait::async_trait]
+impl OutboxRelaySource for InMemoryDomainOutbox {
+    async fn claim_batch(&self, topic: &str, limit: usize) -> Result<Vec<OutboxRow>, OutboxError> {
+        self.ensure_online()?;
+        Ok(self
+            .inner
+            .rows
+            .lock()
+            .await
+            .iter()
+            .filter(|row| row.topic == topic && row.status != OutboxStatus::Published)
+            .take(limit)
+            .cloned()
+            .collect())
+    }
+
+    async fn mark_published(
+        &self,
+        rows: &[OutboxRow],
+        published_at: i64,
+    ) -> Result<(), OutboxError> {
+        self.ensure_online()?;
+        self.update(rows, |row| {
+            row.status = OutboxStatus::Published;
+            row.published_at = Some(published_at);
+        })
+        .await;
+        Ok(())
+    }
+
+    async fn mark_failed(&self, rows: &[OutboxRow], error: &str) -> Result<(), OutboxError> {
+        self.ensure_online()?;
+        self.update(rows, |row| {
+            row.status = OutboxStatus::Failed;
+            row.last_error = Some(error.to_string());
+        })
+        .await;
+        Ok(())
+    }
+
+    async fn backlog(&self, topic: &str) -> Result<OutboxBacklog, OutboxError> {
+        self.ensure_online()?;
+        let mut backlog = OutboxBacklog::default();
+        for row in self.inner.rows.lock().await.iter() {
+            match row.status {
+                _ if row.topic != topic => {}
+                OutboxStatus::Published => backlog.published += 1,
+                OutboxStatus::Pending | OutboxStatus::Failed => {
+                    backlog.unpublished += 1;
+                    backlog.failed += u64::from(row.status == OutboxStatus::Failed);
+                    backlog.oldest_unpublished_at = Some(
+                        backlog
+                            .oldest_unpublished_at
+                            .map_or(row.occurred_at, |at| at.min(row.occurred_at)),
+                    );
+                }
+            }
+        }
+        Ok(backlog)
+    }
+}
+
 #[cfg(test)]
 mod time_entry_in_memory_domain_outbox_tests {
     use super::*;
@@ -57,8 +153,14 @@ mod time_entry_in_memory_domain_outbox_tests {
             event_version: 0,
             stream_id: "123".to_string(),
             stream_version: 0,
+            intent_no: 0,
             occurred_at: 0,
             payload: serde_json::to_value(&event).unwrap(),
+            content_encoding: None,
+            status: OutboxStatus::Pending,
+            attempts: 0,
+            last_error: None,
+            published_at: None,
         };
         assert!(outbox.enqueue(row).await.is_ok());
     }
@@ -74,8 +176,14 @@ mod time_entry_in_memory_domain_outbox_tests {
             event_version: 0,
             stream_id: "123".to_string(),
             stream_version: 0,
+            intent_no: 0,
             occurred_at: 0,
             payload: serde_json::to_value(&event).unwrap(),
+            content_encoding: None,
+            status: OutboxStatus::Pending,
+            attempts: 0,
+            last_error: None,
+            published_at: None,
         };
         outbox.enqueue(row.clone()).await.unwrap();
         let result = outbox.enqueue(row).await;
@@ -83,9 +191,122 @@ mod time_entry_in_memory_domain_outbox_tests {
         assert!(matches!(
             result,
             Err(OutboxError::Duplicate {
-                stream_id: _,
-                stream_version: 0
+                stream_version: 0,
+                intent_no: 0,
+                ..
             })
         ));
     }
+
+    fn row(topic: &str, stream_version: i64) -> OutboxRow {
+        OutboxRow {
+            topic: topic.to_string(),
+            event_type: "test_event_type".to_string(),
+            event_version: 1,
+            stream_id: "123".to_string(),
+            stream_version,
+            intent_no: 0,
+            occurred_at: 0,
+            payload: serde_json::Value::Null,
+            content_encoding: None,
+            status: OutboxStatus::Pending,
+            attempts: 0,
+            last_error: None,
+            published_at: None,
+        }
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_relay_pending_rows_of_a_topic_until_delivered() {
+        let outbox = InMemoryDomainOutbox::new();
+        for (topic, version) in [("a", 1), ("b", 2), ("a", 3), ("a", 4)] {
+            outbox.enqueue(row(topic, version)).await.unwrap();
+        }
+
+        let pending = outbox.claim_batch("a", 2).await.unwrap();
+        assert_eq!(
+            pending.iter().map(|r| r.stream_version).collect::<Vec<_>>(),
+            vec![1, 3]
+        );
+        outbox.mark_published(&pending, 5_000).await.unwrap();
+
+        let pending = outbox.claim_batch("a", 10).await.unwrap();
+        assert_eq!(
+            pending.iter().map(|r| r.stream_version).collect::<Vec<_>>(),
+            vec![4]
+        );
+        let rows = outbox.rows().await;
+        assert_eq!(rows.len(), 4);
+        assert_eq!(rows[0].status, OutboxStatus::Published);
+        assert_eq!(rows[0].published_at, Some(5_000));
+        assert_eq!(rows[0].attempts, 1);
+        assert_eq!(rows[1].status, OutboxStatus::Pending);
+        assert_eq!(
+            outbox.backlog("a").await.unwrap(),
+            OutboxBacklog {
+                unpublished: 1,
+                published: 2,
+                failed: 0,
+                oldest_unpublished_at: Some(0),
+            }
+        );
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_retry_failed_rows_and_keep_the_last_error() {
+        let outbox = InMemoryDomainOutbox::new();
+        outbox.enqueue(row("a", 1)).await.unwrap();
+
+        let claimed = outbox.claim_batch("a", 10).await.unwrap();
+        outbox.mark_failed(&claimed, "broker down").await.unwrap();
+        let retried = outbox.claim_batch("a", 10).await.unwrap();
+        outbox.mark_failed(&retried, "still down").await.unwrap();
+
+        let rows = outbox.rows().await;
+        assert_eq!(retried.len(), 1);
+        assert_eq!(rows[0].status, OutboxStatus::Failed);
+        assert_eq!(rows[0].attempts, 2);
+        assert_eq!(rows[0].last_error.as_deref(), Some("still down"));
+        assert_eq!(rows[0].published_at, None);
+        assert_eq!(
+            outbox.backlog("a").await.unwrap(),
+            OutboxBacklog {
+                unpublished: 1,
+                published: 0,
+                failed: 1,
+                oldest_unpublished_at: Some(0),
+            }
+        );
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_reject_rows_already_published_as_duplicates() {
+        let outbox = InMemoryDomainOutbox::new();
+        outbox.enqueue(row("a", 1)).await.unwrap();
+        outbox.mark_published(&[row("a", 1)], 1).await.unwrap();
+
+        assert!(matches!(
+            outbox.enqueue(row("a", 1)).await,
+            Err(OutboxError::Duplicate { .. })
+        ));
+        assert!(outbox.claim_batch("a", 10).await.unwrap().is_empty());
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_fail_relay_reads_and_acks_when_offline() {
+        let outbox = InMemoryDomainOutbox::new();
+        outbox.toggle_offline();
+
+        assert!(matches!(
+            outbox.claim_batch("a", 1).await,
+            Err(OutboxError::Transient(_))
+        ));
+        assert!(outbox.mark_published(&[row("a", 1)], 1).await.is_err());
+        assert!(outbox.mark_failed(&[row("a", 1)], "down").await.is_err());
+        assert!(outbox.enqueue(row("a", 1)).await.is_ok());
+    }
 }
@@ -1,7 +1,17 @@
+use crate::shared::infrastructure::intent_outbox::compression::ContentEncoding;
 use async_trait::async_trait;
 use serde_json::Value as Json;
 use thiserror::Error;
 
+/// Where a row is in publishing. Failed rows are retried until published.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum OutboxStatus {
+    #[default]
+    Pending,
+    Published,
+    Failed,
+}
+
 #[derive(Debug, Clone)]
 pub struct OutboxRow {
     pub topic: String,
@@ -9,16 +19,90 @@ pub struct OutboxRow {
     pub event_version: i32,
     pub stream_id: String,
     pub stream_version: i64,
+    /// Position among the intents its decision emitted, so one decision can enqueue several.
+    pub intent_no: u32,
     pub occurred_at: i64,
+    /// The intent as JSON, or as compressed JSON when `content_encoding` says so; read it
+    /// through `decoded_payload`.
     pub payload: Json,
+    pub content_encoding: Option<ContentEncoding>,
+    pub status: OutboxStatus,
+    /// Publish attempts so far, successful or not.
+    pub attempts: u32,
+    pub last_error: Option<String>,
+    pub published_at: Option<i64>,
+}
+
+impl OutboxRow {
+    /// A row not yet published, for `registration`'s type, topic and schema version.
+    pub fn pending(
+        registration: &IntentRegistration,
+        stream_id: impl Into<String>,
+        stream_version: i64,
+        intent_no: u32,
+        occurred_at: i64,
+        payload: Json,
+    ) -> Self {
+        Self {
+            topic: registration.topic.to_string(),
+            event_type: registration.event_type.to_string(),
+            event_version: registration.event_version,
+            stream_id: stream_id.into(),
+            stream_version,
+            intent_no,
+            occurred_at,
+            payload,
+            content_encoding: None,
+            status: OutboxStatus::Pending,
+            attempts: 0,
+            last_error: None,
+            published_at: None,
+        }
+    }
+
+    /// The row's idempotency key, `{stream_id}:{stream_version}` for a decision's first
+    /// intent and `{stream_id}:{stream_version}:{intent_no}` for the rest, so keys of rows
+    /// enqueued before intents were numbered are unchanged.
+    pub fn dedupe_key(&self) -> String {
+        match self.intent_no {
+            0 => format!("{}:{}", self.stream_id, self.stream_version),
+            intent_no => format!("{}:{}:{intent_no}", self.stream_id, self.stream_version),
+        }
+    }
+}
+
+/// How one intent type is written to the outbox: the `event_type` string relays and intent
+/// handlers key on, its payload schema version, and the outbox topic a relay drains it from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IntentRegistration {
+    pub event_type: &'static str,
+    pub event_version: i32,
+    pub topic: &'static str,
+}
+
+/// The intent types a module enqueues. Handlers hand over intents only; the module's
+/// dispatcher looks their outbox shape up here.
+#[derive(Debug, Clone, Copy)]
+pub struct IntentRegistry(pub &'static [IntentRegistration]);
+
+impl IntentRegistry {
+    pub fn resolve(&self, event_type: &str) -> Result<&'static IntentRegistration, OutboxError> {
+        self.0
+            .iter()
+            .find(|registration| registration.event_type == event_type)
+            .ok_or_else(|| {
+                OutboxError::Validation(format!("intent type {event_type} is not registered"))
+            })
+    }
 }
 
 #[derive(Debug, Error)]
 pub enum OutboxError {
-    #[error("duplicate outbox row for stream {stream_id} v{stream_version}")]
+    #[error("duplicate outbox row for stream {stream_id} v{stream_version} intent {intent_no}")]
     Duplicate {
         stream_id: String,
         stream_version: i64,
+        intent_no: u32,
     },
 
     #[error("validation failed: {0}")]
@@ -33,7 +117,95 @@ pub enum OutboxError {
 
 #[async_trait]
 pub trait DomainOutbox: Send + Sync {
+    /// Rows are keyed by `(stream_id, stream_version, intent_no)`. A key is a duplicate for as
+    /// long as the row is kept, whatever its status, so replaying a dispatch never publishes
+    /// twice.
     async fn enqueue(&self, row: OutboxRow) -> Result<(), OutboxError>;
 }
 
+/// Rows of a topic the relays still have to publish, and the ones they published.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct OutboxBacklog {
+    pub unpublished: u64,
+    pub published: u64,
+    /// The unpublished rows whose last attempt failed.
+    pub failed: u64,
+    /// When the oldest unpublished row's intent occurred.
+    pub oldest_unpublished_at: Option<i64>,
+}
+
+/// Read side of the outbox used by relays. Rows are keyed by
+/// `(stream_id, stream_version, intent_no)`.
+#[async_trait]
+pub trait OutboxRelaySource: Send + Sync {
+    /// Up to `limit` pending or failed rows for `topic`, oldest first. The in-memory outbox's
+    /// claims are not exclusive, relying on leader election to run one relay per topic; the
+    /// Postgres outbox leases the rows it hands out, so concurrent relays claim disjoint rows.
+    async fn claim_batch(&self, topic: &str, limit: usize) -> Result<Vec<OutboxRow>, OutboxError>;
+
+    /// Marks `rows` published at `published_at` and counts the attempt.
+    async fn mark_published(
+        &self,
+        rows: &[OutboxRow],
+        published_at: i64,
+    ) -> Result<(), OutboxError>;
+
+    /// Marks `rows` failed with `error` and counts the attempt; they are claimed again later.
+    async fn mark_failed(&self, rows: &[OutboxRow], error: &str) -> Result<(), OutboxError>;
+
+    /// How far the relays of `topic` are behind, for monitoring.
+    async fn backlog(&self, topic: &str) -> Result<OutboxBacklog, OutboxError>;
+}
+
+pub mod compression;
 pub mod in_memory;
+#[cfg(feature = "postgres")]
+pub mod postgres;
+pub mod redacting;
+
+#[cfg(test)]
+mod intent_registry_tests {
+    use super::*;
+    use rstest::rstest;
+
+    const REGISTRY: IntentRegistry = IntentRegistry(&[IntentRegistration {
+        event_type: "TimerAutoStopped",
+        event_version: 2,
+        topic: "time-entries.v1",
+    }]);
+
+    #[rstest]
+    fn it_should_build_pending_rows_from_a_registration() {
+        let registration = REGISTRY.resolve("TimerAutoStopped").unwrap();
+        let row = OutboxRow::pending(registration, "TimeEntry-te-1", 3, 0, 10, Json::Null);
+
+        assert_eq!(row.topic, "time-entries.v1");
+        assert_eq!(row.event_type, "TimerAutoStopped");
+        assert_eq!(row.event_version, 2);
+        assert_eq!(row.stream_version, 3);
+        assert_eq!(row.intent_no, 0);
+        assert_eq!(row.status, OutboxStatus::Pending);
+        assert_eq!(row.attempts, 0);
+    }
+
+    #[rstest]
+    #[case::first_intent(0, "TimeEntry-te-1:3")]
+    #[case::later_intent(2, "TimeEntry-te-1:3:2")]
+    fn it_should_number_dedupe_keys_after_the_first_intent(
+        #[case] intent_no: u32,
+        #[case] expected: &str,
+    ) {
+        let registration = REGISTRY.resolve("TimerAutoStopped").unwrap();
+        let row = OutboxRow::pending(registration, "TimeEntry-te-1", 3, intent_no, 10, Json::Null);
+
+        assert_eq!(row.dedupe_key(), expected);
+    }
+
+    #[rstest]
+    fn it_should_reject_unregistered_intent_types() {
+        assert!(matches!(
+            REGISTRY.resolve("TimeEntryTagsSet"),
+            Err(OutboxError::Validation(_))
+        ));
+    }
+}
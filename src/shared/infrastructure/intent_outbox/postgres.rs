@@ -0,0 +1,373 @@
+// Outbox on Postgres. The unique `(stream_id, stream_version, intent_no)` key rejects duplicates
+// whatever the row's status, so concurrent enqueues of one key admit exactly one. A claim
+// selects and leases its rows in one statement, skipping rows another claim holds locked, so
+// relays running side by side never hand out the same row; marking a row releases its lease.
+// `PostgresEventStore::append_with_outbox` writes rows through `insert_row` in the append's own
+// transaction.
+
+use crate::shared::infrastructure::intent_outbox::{
+    DomainOutbox, OutboxBacklog, OutboxError, OutboxRelaySource, OutboxRow, OutboxStatus,
+};
+use crate::shared::infrastructure::postgres::PgPool;
+use async_trait::async_trait;
+use deadpool_postgres::GenericClient;
+use tokio_postgres::Row;
+
+/// How long a claimed row stays leased to its relay, should it never mark the row.
+pub const CLAIM_LEASE_MS: i64 = 60_000;
+
+/// The database's clock in epoch milliseconds, so relays on skewed hosts agree on leases.
+const NOW_MS: &str = "(extract(epoch FROM clock_timestamp()) * 1000)::bigint";
+
+const COLUMNS: &str = "topic, event_type, event_version, stream_id, stream_version, intent_no, \
+                       occurred_at, payload, content_encoding, status, attempts, last_error, \
+                       published_at";
+
+fn backend(error: impl std::fmt::Display) -> OutboxError {
+    OutboxError::Backend(error.to_string())
+}
+
+fn status_text(status: OutboxStatus) -> &'static str {
+    match status {
+        OutboxStatus::Pending => "Pending",
+        OutboxStatus::Published => "Published",
+        OutboxStatus::Failed => "Failed",
+    }
+}
+
+fn parse_status(text: &str) -> Result<OutboxStatus, OutboxError> {
+    match text {
+        "Pending" => Ok(OutboxStatus::Pending),
+        "Published" => Ok(OutboxStatus::Published),
+        "Failed" => Ok(OutboxStatus::Failed),
+        other => Err(OutboxError::Backend(format!(
+            "unknown outbox status {other}"
+        ))),
+    }
+}
+
+fn decode(row: &Row) -> Result<OutboxRow, OutboxError> {
+    Ok(OutboxRow {
+        topic: row.get(0),
+        event_type: row.get(1),
+        event_version: row.get(2),
+        stream_id: row.get(3),
+        stream_version: row.get(4),
+        intent_no: u32::try_from(row.get::<_, i64>(5)).map_err(backend)?,
+        occurred_at: row.get(6),
+        payload: row.get(7),
+        content_encoding: row
+            .get::<_, Option<&str>>(8)
+            .map(str::parse)
+            .transpose()
+            .map_err(backend)?,
+        status: parse_status(row.get(9))?,
+        attempts: u32::try_from(row.get::<_, i64>(10)).map_err(backend)?,
+        last_error: row.get(11),
+        published_at: row.get(12),
+    })
+}
+
+/// Inserts `row` unless its key is taken. Answers whether it was inserted.
+pub(crate) async fn insert_row(
+    client: &impl GenericClient,
+    row: &OutboxRow,
+) -> Result<bool, tokio_postgres::Error> {
+    let inserted = client
+        .execute(
+            &format!(
+                "INSERT INTO outbox ({COLUMNS}) \
+                 VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13) \
+                 ON CONFLICT (stream_id, stream_version, intent_no) DO NOTHING"
+            ),
+            &[
+                &row.topic,
+                &row.event_type,
+                &row.event_version,
+                &row.stream_id,
+                &row.stream_version,
+                &i64::from(row.intent_no),
+                &row.occurred_at,
+                &row.payload,
+                &row.content_encoding.map(|encoding| encoding.as_str()),
+                &status_text(row.status),
+                &i64::from(row.attempts),
+                &row.last_error,
+                &row.published_at,
+            ],
+        )
+        .await?;
+    Ok(inserted == 1)
+}
+
+#[derive(Clone)]
+pub struct PostgresDomainOutbox {
+    pool: PgPool,
+}
+
+impl PostgresDomainOutbox {
+    /// Over a database `postgres::migrate` has been run on.
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Every row, in the order they were enqueued.
+    pub async fn rows(&self) -> Result<Vec<OutboxRow>, OutboxError> {
+        let client = self.pool.get().await.map_err(backend)?;
+        client
+            .query(
+                &format!("SELECT {COLUMNS} FROM outbox ORDER BY position"),
+                &[],
+            )
+            .await
+            .map_err(backend)?
+            .iter()
+            .map(decode)
+            .collect()
+    }
+
+    /// Sets `assignments` on `rows` and counts the attempt. `$1` to `$3` are the keys.
+    async fn update(
+        &self,
+        rows: &[OutboxRow],
+        assignments: &str,
+        params: &[&(dyn tokio_postgres::types::ToSql + Sync)],
+    ) -> Result<(), OutboxError> {
+        let stream_ids: Vec<&str> = rows.iter().map(|row| row.stream_id.as_str()).collect();
+        let stream_versions: Vec<i64> = rows.iter().map(|row| row.stream_version).collect();
+        let intent_nos: Vec<i64> = rows.iter().map(|row| i64::from(row.intent_no)).collect();
+        let mut all: Vec<&(dyn tokio_postgres::types::ToSql + Sync)> =
+            vec![&stream_ids, &stream_versions, &intent_nos];
+        all.extend_from_slice(params);
+        let client = self.pool.get().await.map_err(backend)?;
+        client
+            .execute(
+                &format!(
+                    "UPDATE outbox SET attempts = attempts + 1, leased_until = NULL, {assignments} \
+                     WHERE (stream_id, stream_version, intent_no) IN \
+                     (SELECT * FROM unnest($1::text[], $2::bigint[], $3::bigint[]))"
+                ),
+                &all,
+            )
+            .await
+            .map_err(backend)?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl DomainOutbox for PostgresDomainOutbox {
+    async fn enqueue(&self, row: OutboxRow) -> Result<(), OutboxError> {
+        let client = self.pool.get().await.map_err(backend)?;
+        match insert_row(&client, &row).await.map_err(backend)? {
+            true => Ok(()),
+            false => Err(OutboxError::Duplicate {
+                stream_id: row.stream_id,
+                stream_version: row.stream_version,
+                intent_no: row.intent_no,
+            }),
+        }
+    }
+}
+
+#[async_trait]
+impl OutboxRelaySource for PostgresDomainOutbox {
+    async fn claim_batch(&self, topic: &str, limit: usize) -> Result<Vec<OutboxRow>, OutboxError> {
+        let client = self.pool.get().await.map_err(backend)?;
+        let limit = i64::try_from(limit).unwrap_or(i64::MAX);
+        let mut rows = client
+            .query(
+                &format!(
+                    "UPDATE outbox SET leased_until = {NOW_MS} + $3 \
+                     WHERE position IN ( \
+                         SELECT position FROM outbox \
+                         WHERE topic = $1 AND status <> 'Published' \
+                           AND (leased_until IS NULL OR leased_until <= {NOW_MS}) \
+                         ORDER BY position LIMIT $2 \
+                         FOR UPDATE SKIP LOCKED) \
+                     RETURNING {COLUMNS}, position"
+                ),
+                &[&topic, &limit, &CLAIM_LEASE_MS],
+            )
+            .await
+            .map_err(backend)?;
+        // RETURNING follows no order; hand the rows out oldest first.
+        rows.sort_by_key(|row| row.get::<_, i64>(13));
+        rows.iter().map(decode).collect()
+    }
+
+    async fn mark_published(
+        &self,
+        rows: &[OutboxRow],
+        published_at: i64,
+    ) -> Result<(), OutboxError> {
+        self.update(
+            rows,
+            "status = 'Published', published_at = $4",
+            &[&published_at],
+        )
+        .await
+    }
+
+    async fn mark_failed(&self, rows: &[OutboxRow], error: &str) -> Result<(), OutboxError> {
+        self.update(rows, "status = 'Failed', last_error = $4", &[&error])
+            .await
+    }
+
+    async fn backlog(&self, topic: &str) -> Result<OutboxBacklog, OutboxError> {
+        let client = self.pool.get().await.map_err(backend)?;
+        let row = client
+            .query_one(
+                "SELECT count(*) FILTER (WHERE status <> 'Published'), \
+                        count(*) FILTER (WHERE status = 'Published'), \
+                        count(*) FILTER (WHERE status = 'Failed'), \
+                        min(occurred_at) FILTER (WHERE status <> 'Published') \
+                 FROM outbox WHERE topic = $1",
+                &[&topic],
+            )
+            .await
+            .map_err(backend)?;
+        Ok(OutboxBacklog {
+            unpublished: row.get::<_, i64>(0) as u64,
+            published: row.get::<_, i64>(1) as u64,
+            failed: row.get::<_, i64>(2) as u64,
+            oldest_unpublished_at: row.get(3),
+        })
+    }
+}
+
+#[cfg(test)]
+mod postgres_domain_outbox_integration_tests {
+    use super::*;
+    use crate::shared::infrastructure::intent_outbox::compression::ContentEncoding;
+    use crate::shared::infrastructure::postgres::postgres_test_support::start;
+    use crate::tests::contracts::parallel_enqueues_admit_exactly_one;
+    use serde_json::json;
+
+    fn row(stream_version: i64, occurred_at: i64) -> OutboxRow {
+        OutboxRow {
+            topic: "time-entries.v1".to_string(),
+            event_type: "TimeEntryTagsSet".to_string(),
+            event_version: 1,
+            stream_id: "TimeEntry-te-1".to_string(),
+            stream_version,
+            intent_no: 0,
+            occurred_at,
+            payload: json!({"tags": ["billable"]}),
+            content_encoding: None,
+            status: OutboxStatus::Pending,
+            attempts: 0,
+            last_error: None,
+            published_at: None,
+        }
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    #[ignore = "starts Postgres in Docker"]
+    async fn integration_it_should_admit_exactly_one_parallel_enqueue() {
+        let (_container, pool) = start().await;
+
+        let outbox =
+            parallel_enqueues_admit_exactly_one(PostgresDomainOutbox::new(pool), row(1, 0)).await;
+
+        assert_eq!(outbox.rows().await.unwrap().len(), 1);
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    #[ignore = "starts Postgres in Docker"]
+    async fn integration_it_should_lease_each_row_to_one_claim() {
+        let (_container, pool) = start().await;
+        let outbox = PostgresDomainOutbox::new(pool);
+        for stream_version in 1..=20 {
+            outbox
+                .enqueue(row(stream_version, stream_version * 100))
+                .await
+                .unwrap();
+        }
+
+        let claims = claim_in_parallel(&outbox).await;
+
+        let mut claimed: Vec<i64> = claims
+            .iter()
+            .flatten()
+            .map(|row| row.stream_version)
+            .collect();
+        claimed.sort();
+        assert_eq!(claimed, (1..=20).collect::<Vec<_>>());
+        assert!(
+            outbox
+                .claim_batch("time-entries.v1", 10)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+        outbox.mark_failed(&claims[0], "broker down").await.unwrap();
+        assert_eq!(
+            outbox
+                .claim_batch("time-entries.v1", 20)
+                .await
+                .unwrap()
+                .len(),
+            claims[0].len()
+        );
+    }
+
+    /// Four relays claiming up to five rows each at once.
+    async fn claim_in_parallel(outbox: &PostgresDomainOutbox) -> Vec<Vec<OutboxRow>> {
+        let handles: Vec<_> = (0..4)
+            .map(|_| {
+                let outbox = outbox.clone();
+                tokio::spawn(async move { outbox.claim_batch("time-entries.v1", 5).await })
+            })
+            .collect();
+        let mut claims = Vec::new();
+        for handle in handles {
+            claims.push(handle.await.unwrap().unwrap());
+        }
+        claims
+    }
+
+    #[tokio::test]
+    #[ignore = "starts Postgres in Docker"]
+    async fn integration_it_should_relay_rows_until_published() {
+        let (_container, pool) = start().await;
+        let outbox = PostgresDomainOutbox::new(pool);
+        let compressed = OutboxRow {
+            content_encoding: Some(ContentEncoding::Gzip),
+            ..row(2, 200)
+        };
+        outbox.enqueue(row(1, 100)).await.unwrap();
+        outbox.enqueue(compressed).await.unwrap();
+
+        let claimed = outbox.claim_batch("time-entries.v1", 10).await.unwrap();
+        assert_eq!(claimed.len(), 2);
+        assert_eq!(claimed[1].content_encoding, Some(ContentEncoding::Gzip));
+        outbox
+            .mark_failed(&claimed[..1], "broker down")
+            .await
+            .unwrap();
+        outbox.mark_published(&claimed[1..], 300).await.unwrap();
+
+        assert_eq!(
+            outbox.backlog("time-entries.v1").await.unwrap(),
+            OutboxBacklog {
+                unpublished: 1,
+                published: 1,
+                failed: 1,
+                oldest_unpublished_at: Some(100),
+            }
+        );
+        let retried = outbox.claim_batch("time-entries.v1", 10).await.unwrap();
+        assert_eq!(retried.len(), 1);
+        assert_eq!(retried[0].status, OutboxStatus::Failed);
+        assert_eq!(retried[0].attempts, 1);
+        assert_eq!(retried[0].last_error.as_deref(), Some("broker down"));
+        assert!(matches!(
+            outbox.enqueue(row(2, 200)).await,
+            Err(OutboxError::Duplicate {
+                stream_version: 2,
+                ..
+            })
+        ));
+    }
+}
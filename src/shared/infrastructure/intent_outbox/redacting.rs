@@ -0,0 +1,136 @@
+use crate::shared::core::redaction::Redactor;
+use crate::shared::infrastructure::intent_outbox::OutboxRow;
+use crate::shared::infrastructure::intent_outbox::compression::PayloadCompression;
+use crate::shared::infrastructure::message_broker::{BrokerError, MessageBroker};
+use async_trait::async_trait;
+
+/// Masks personal data fields in payloads as rows are published. The rows in the outbox keep
+/// them, so a redaction change applies to what is published from then on, resends included.
+#[derive(Debug, Clone)]
+pub struct RedactingBroker<TBroker> {
+    inner: TBroker,
+    redactor: Redactor,
+}
+
+impl<TBroker> RedactingBroker<TBroker> {
+    pub fn new(inner: TBroker, redactor: Redactor) -> Self {
+        Self { inner, redactor }
+    }
+
+    /// `row` with its payload redacted, compressed again the way it was.
+    fn redact(&self, row: &OutboxRow) -> Result<OutboxRow, BrokerError> {
+        let redacted = OutboxRow {
+            payload: self.redactor.redact_json(row.decoded_payload()?),
+            content_encoding: None,
+            ..row.clone()
+        };
+        Ok(match row.content_encoding {
+            Some(encoding) => PayloadCompression::new(encoding)
+                .with_threshold_bytes(0)
+                .compress(redacted),
+            None => redacted,
+        })
+    }
+}
+
+#[async_trait]
+impl<TBroker> MessageBroker for RedactingBroker<TBroker>
+where
+    TBroker: MessageBroker,
+{
+    async fn publish(&self, topic: &str, rows: &[OutboxRow]) -> Result<(), BrokerError> {
+        if !self.redactor.is_enabled() {
+            return self.inner.publish(topic, rows).await;
+        }
+        let rows = rows
+            .iter()
+            .map(|row| self.redact(row))
+            .collect::<Result<Vec<_>, _>>()?;
+        self.inner.publish(topic, &rows).await
+    }
+}
+
+#[cfg(test)]
+mod redacting_broker_tests {
+    use super::*;
+    use crate::shared::core::redaction::MASK;
+    use crate::shared::infrastructure::intent_outbox::OutboxStatus;
+    use crate::shared::infrastructure::intent_outbox::compression::ContentEncoding;
+    use crate::shared::infrastructure::message_broker::in_memory::InMemoryMessageBroker;
+    use rstest::rstest;
+    use serde_json::json;
+
+    fn row() -> OutboxRow {
+        OutboxRow {
+            topic: "time-entries.v1".to_string(),
+            event_type: "TimeEntryTagsSet".to_string(),
+            event_version: 1,
+            stream_id: "TimeEntry-te-1".to_string(),
+            stream_version: 1,
+            intent_no: 0,
+            occurred_at: 1_000,
+            payload: json!({ "time_entry_id": "te-1", "user_id": "u-1", "updated_by": "u-2" }),
+            content_encoding: None,
+            status: OutboxStatus::Pending,
+            attempts: 0,
+            last_error: None,
+            published_at: None,
+        }
+    }
+
+    #[rstest]
+    #[case::defaults(
+        Redactor::default(),
+        json!({ "time_entry_id": "te-1", "user_id": MASK, "updated_by": MASK })
+    )]
+    #[case::configured(
+        Redactor::default().with_fields(["updated_by"]),
+        json!({ "time_entry_id": "te-1", "user_id": "u-1", "updated_by": MASK })
+    )]
+    #[case::disabled(
+        Redactor::disabled(),
+        json!({ "time_entry_id": "te-1", "user_id": "u-1", "updated_by": "u-2" })
+    )]
+    #[tokio::test]
+    async fn it_should_publish_payloads_redacted_per_config(
+        #[case] redactor: Redactor,
+        #[case] expected: serde_json::Value,
+    ) {
+        let broker = InMemoryMessageBroker::new();
+        let redacting = RedactingBroker::new(broker.clone(), redactor);
+        let row = row();
+
+        redacting
+            .publish("time-entries.v1", std::slice::from_ref(&row))
+            .await
+            .unwrap();
+
+        let messages = broker.messages().await;
+        let body: serde_json::Value = serde_json::from_slice(&messages[0].body).unwrap();
+        assert_eq!(body["payload"], expected);
+        assert_eq!(row.payload["user_id"], "u-1");
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_redact_compressed_payloads_and_keep_them_compressed() {
+        let broker = InMemoryMessageBroker::new();
+        let redacting = RedactingBroker::new(broker.clone(), Redactor::default());
+        let row = PayloadCompression::new(ContentEncoding::Gzip)
+            .with_threshold_bytes(0)
+            .compress(OutboxRow {
+                payload: json!({ "description": "dentist", "tags": vec!["t-1"; 64] }),
+                ..row()
+            });
+        assert_eq!(row.content_encoding, Some(ContentEncoding::Gzip));
+
+        redacting.publish("time-entries.v1", &[row]).await.unwrap();
+
+        let published = &broker.published().await[0].1;
+        assert_eq!(published.content_encoding, Some(ContentEncoding::Gzip));
+        assert_eq!(
+            published.decoded_payload().unwrap(),
+            json!({ "description": MASK, "tags": vec!["t-1"; 64] })
+        );
+    }
+}
@@ -0,0 +1,321 @@
+use crate::shared::infrastructure::job_store::{
+    Job, JobProgress, JobQuery, JobStatus, JobStore, JobStoreError,
+};
+use serde_json::Value as Json;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use tokio::sync::RwLock;
+
+#[derive(Default)]
+struct Inner {
+    /// In enqueue order.
+    jobs: RwLock<Vec<Job>>,
+    is_offline: AtomicBool,
+}
+
+/// Keeps jobs for the life of the process only; a restart loses them.
+#[derive(Clone, Default)]
+pub struct InMemoryJobStore {
+    inner: Arc<Inner>,
+}
+
+impl InMemoryJobStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn toggle_offline(&self) {
+        self.inner.is_offline.fetch_xor(true, Ordering::SeqCst);
+    }
+
+    fn ensure_online(&self) -> Result<(), JobStoreError> {
+        if self.inner.is_offline.load(Ordering::SeqCst) {
+            return Err(JobStoreError::Backend("Job store offline".to_string()));
+        }
+        Ok(())
+    }
+
+    async fn update(
+        &self,
+        job_id: &str,
+        change: impl FnOnce(&mut Job),
+    ) -> Result<(), JobStoreError> {
+        self.ensure_online()?;
+        let mut jobs = self.inner.jobs.write().await;
+        let job = jobs
+            .iter_mut()
+            .find(|job| job.job_id == job_id)
+            .ok_or_else(|| JobStoreError::NotFound(job_id.to_string()))?;
+        change(job);
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl JobStore for InMemoryJobStore {
+    async fn enqueue(&self, job: Job) -> Result<(), JobStoreError> {
+        self.ensure_online()?;
+        self.inner.jobs.write().await.push(job);
+        Ok(())
+    }
+
+    async fn get(&self, job_id: &str) -> Result<Option<Job>, JobStoreError> {
+        self.ensure_online()?;
+        Ok(self
+            .inner
+            .jobs
+            .read()
+            .await
+            .iter()
+            .find(|job| job.job_id == job_id)
+            .cloned())
+    }
+
+    async fn list(&self, query: &JobQuery) -> Result<Vec<Job>, JobStoreError> {
+        self.ensure_online()?;
+        Ok(self
+            .inner
+            .jobs
+            .read()
+            .await
+            .iter()
+            .rev()
+            .filter(|job| query.kind.as_ref().is_none_or(|kind| &job.kind == kind))
+            .filter(|job| query.status.is_none_or(|status| job.status == status))
+            .take(query.limit)
+            .cloned()
+            .collect())
+    }
+
+    async fn claim_due(&self, now: i64, limit: usize) -> Result<Vec<Job>, JobStoreError> {
+        self.ensure_online()?;
+        let mut jobs = self.inner.jobs.write().await;
+        let mut due: Vec<&mut Job> = jobs
+            .iter_mut()
+            .filter(|job| job.status == JobStatus::Queued && job.run_after <= now)
+            .collect();
+        due.sort_by_key(|job| job.run_after);
+        Ok(due
+            .into_iter()
+            .take(limit)
+            .map(|job| {
+                job.status = JobStatus::Running;
+                job.attempts += 1;
+                job.updated_at = now;
+                job.clone()
+            })
+            .collect())
+    }
+
+    async fn report_progress(
+        &self,
+        job_id: &str,
+        progress: JobProgress,
+        now: i64,
+    ) -> Result<(), JobStoreError> {
+        self.update(job_id, |job| {
+            job.progress = Some(progress);
+            job.updated_at = now;
+        })
+        .await
+    }
+
+    async fn succeed(&self, job_id: &str, result: Json, now: i64) -> Result<(), JobStoreError> {
+        self.update(job_id, |job| {
+            job.status = JobStatus::Succeeded;
+            job.result = Some(result);
+            job.updated_at = now;
+        })
+        .await
+    }
+
+    async fn fail(
+        &self,
+        job_id: &str,
+        error: &str,
+        retry_at: Option<i64>,
+        now: i64,
+    ) -> Result<(), JobStoreError> {
+        self.update(job_id, |job| {
+            job.last_error = Some(error.to_string());
+            job.updated_at = now;
+            match retry_at {
+                Some(retry_at) => {
+                    job.status = JobStatus::Queued;
+                    job.run_after = retry_at;
+                }
+                None => job.status = JobStatus::Failed,
+            }
+        })
+        .await
+    }
+
+    async fn requeue_stale(&self, stale_before: i64, now: i64) -> Result<usize, JobStoreError> {
+        self.ensure_online()?;
+        let mut requeued = 0;
+        for job in self.inner.jobs.write().await.iter_mut() {
+            if job.status == JobStatus::Running && job.updated_at < stale_before {
+                job.status = JobStatus::Queued;
+                job.run_after = now;
+                job.updated_at = now;
+                requeued += 1;
+            }
+        }
+        Ok(requeued)
+    }
+}
+
+#[cfg(test)]
+mod in_memory_job_store_tests {
+    use super::*;
+    use rstest::rstest;
+    use serde_json::json;
+
+    fn job(kind: &str, run_after: i64) -> Job {
+        Job {
+            run_after,
+            ..Job::queued(kind, json!({}), 0)
+        }
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_claim_due_jobs_once_oldest_first() {
+        let store = InMemoryJobStore::new();
+        let (later, earlier, future) = (job("a", 20), job("b", 10), job("c", 1_000));
+        for job in [&later, &earlier, &future] {
+            store.enqueue(job.clone()).await.unwrap();
+        }
+
+        let claimed = store.claim_due(100, 10).await.unwrap();
+
+        let ids: Vec<&str> = claimed.iter().map(|job| job.job_id.as_str()).collect();
+        assert_eq!(ids, vec![earlier.job_id.as_str(), later.job_id.as_str()]);
+        assert!(claimed.iter().all(|job| job.status == JobStatus::Running));
+        assert!(claimed.iter().all(|job| job.attempts == 1));
+        assert!(store.claim_due(100, 10).await.unwrap().is_empty());
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_limit_claims() {
+        let store = InMemoryJobStore::new();
+        for run_after in [1, 2, 3] {
+            store.enqueue(job("a", run_after)).await.unwrap();
+        }
+        assert_eq!(store.claim_due(10, 2).await.unwrap().len(), 2);
+        assert_eq!(store.claim_due(10, 2).await.unwrap().len(), 1);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_record_progress_results_and_failures() {
+        let store = InMemoryJobStore::new();
+        let (done, retried, failed) = (job("a", 0), job("a", 0), job("a", 0));
+        for job in [&done, &retried, &failed] {
+            store.enqueue(job.clone()).await.unwrap();
+        }
+        store.claim_due(0, 3).await.unwrap();
+
+        let progress = JobProgress {
+            done: 1,
+            total: Some(2),
+        };
+        store
+            .report_progress(&done.job_id, progress, 5)
+            .await
+            .unwrap();
+        store
+            .succeed(&done.job_id, json!({"rows": 2}), 6)
+            .await
+            .unwrap();
+        store
+            .fail(&retried.job_id, "busy", Some(50), 7)
+            .await
+            .unwrap();
+        store.fail(&failed.job_id, "broken", None, 8).await.unwrap();
+
+        let done = store.get(&done.job_id).await.unwrap().unwrap();
+        assert_eq!(done.status, JobStatus::Succeeded);
+        assert_eq!(done.progress, Some(progress));
+        assert_eq!(done.result, Some(json!({"rows": 2})));
+        assert_eq!(done.updated_at, 6);
+        let retried = store.get(&retried.job_id).await.unwrap().unwrap();
+        assert_eq!(retried.status, JobStatus::Queued);
+        assert_eq!(retried.run_after, 50);
+        assert_eq!(retried.last_error.as_deref(), Some("busy"));
+        let failed = store.get(&failed.job_id).await.unwrap().unwrap();
+        assert_eq!(failed.status, JobStatus::Failed);
+        assert_eq!(
+            store.succeed("unknown", json!(null), 9).await,
+            Err(JobStoreError::NotFound("unknown".to_string()))
+        );
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_requeue_running_jobs_without_a_recent_heartbeat() {
+        let store = InMemoryJobStore::new();
+        let (stale, fresh) = (job("a", 0), job("a", 0));
+        store.enqueue(stale.clone()).await.unwrap();
+        store.claim_due(0, 1).await.unwrap();
+        store.enqueue(fresh.clone()).await.unwrap();
+        store.claim_due(50, 1).await.unwrap();
+
+        assert_eq!(store.requeue_stale(10, 60).await.unwrap(), 1);
+
+        let stale = store.get(&stale.job_id).await.unwrap().unwrap();
+        assert_eq!(stale.status, JobStatus::Queued);
+        assert_eq!(stale.run_after, 60);
+        let fresh = store.get(&fresh.job_id).await.unwrap().unwrap();
+        assert_eq!(fresh.status, JobStatus::Running);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_list_newest_first_by_kind_and_status() {
+        let store = InMemoryJobStore::new();
+        let (first, second, other) = (job("a", 0), job("a", 1), job("b", 2));
+        for job in [&first, &second, &other] {
+            store.enqueue(job.clone()).await.unwrap();
+        }
+        store.claim_due(0, 1).await.unwrap();
+
+        let by_kind = store
+            .list(&JobQuery {
+                kind: Some("a".to_string()),
+                status: None,
+                limit: 10,
+            })
+            .await
+            .unwrap();
+        assert_eq!(by_kind[0].job_id, second.job_id);
+        assert_eq!(by_kind.len(), 2);
+        let running = store
+            .list(&JobQuery {
+                kind: None,
+                status: Some(JobStatus::Running),
+                limit: 10,
+            })
+            .await
+            .unwrap();
+        assert_eq!(running.len(), 1);
+        assert_eq!(running[0].job_id, first.job_id);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_fail_when_offline() {
+        let store = InMemoryJobStore::new();
+        store.toggle_offline();
+
+        assert!(store.enqueue(job("a", 0)).await.is_err());
+        assert!(store.get("j-1").await.is_err());
+        assert!(store.claim_due(0, 1).await.is_err());
+        assert!(store.requeue_stale(0, 0).await.is_err());
+        assert_eq!(
+            store.fail("j-1", "x", None, 0).await,
+            Err(JobStoreError::Backend("Job store offline".to_string()))
+        );
+    }
+}
@@ -0,0 +1,116 @@
+use async_trait::async_trait;
+use serde_json::Value as Json;
+use thiserror::Error;
+
+#[derive(Debug, Clone, Error, PartialEq, Eq)]
+pub enum JobStoreError {
+    #[error("job {0} not found")]
+    NotFound(String),
+
+    #[error("backend error: {0}")]
+    Backend(String),
+}
+
+/// Where a job is in its life. Queued jobs wait for `run_after`; running jobs are claimed by
+/// a worker; failed jobs have used up their attempts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum JobStatus {
+    Queued,
+    Running,
+    Succeeded,
+    Failed,
+}
+
+/// How far a running job has come, in whatever unit its kind counts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct JobProgress {
+    pub done: u64,
+    pub total: Option<u64>,
+}
+
+/// A long-running operation, persisted so it survives restarts. `kind` selects the handler
+/// that runs it and `payload` is that handler's input; `result` is its output once it
+/// succeeded. Times are epoch milliseconds.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct Job {
+    pub job_id: String,
+    pub kind: String,
+    pub payload: Json,
+    pub status: JobStatus,
+    /// Runs started so far, including the current one.
+    pub attempts: u32,
+    pub progress: Option<JobProgress>,
+    pub result: Option<Json>,
+    pub last_error: Option<String>,
+    pub run_after: i64,
+    pub created_at: i64,
+    /// Moves on every change, progress included, so it doubles as the worker's heartbeat.
+    pub updated_at: i64,
+}
+
+impl Job {
+    /// A new job of `kind`, due straight away.
+    pub fn queued(kind: impl Into<String>, payload: Json, now: i64) -> Self {
+        Self {
+            job_id: uuid::Uuid::now_v7().to_string(),
+            kind: kind.into(),
+            payload,
+            status: JobStatus::Queued,
+            attempts: 0,
+            progress: None,
+            result: None,
+            last_error: None,
+            run_after: now,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JobQuery {
+    pub kind: Option<String>,
+    pub status: Option<JobStatus>,
+    pub limit: usize,
+}
+
+/// The job table (a Postgres table polled with `FOR UPDATE SKIP LOCKED`, a Redis stream).
+#[async_trait]
+pub trait JobStore: Send + Sync {
+    async fn enqueue(&self, job: Job) -> Result<(), JobStoreError>;
+
+    async fn get(&self, job_id: &str) -> Result<Option<Job>, JobStoreError>;
+
+    /// Newest first.
+    async fn list(&self, query: &JobQuery) -> Result<Vec<Job>, JobStoreError>;
+
+    /// Up to `limit` queued jobs due at `now`, oldest first, marked running with the attempt
+    /// counted. A job is handed to one claimer only.
+    async fn claim_due(&self, now: i64, limit: usize) -> Result<Vec<Job>, JobStoreError>;
+
+    async fn report_progress(
+        &self,
+        job_id: &str,
+        progress: JobProgress,
+        now: i64,
+    ) -> Result<(), JobStoreError>;
+
+    async fn succeed(&self, job_id: &str, result: Json, now: i64) -> Result<(), JobStoreError>;
+
+    /// Records `error`; with `retry_at` the job is queued again for then, without it the job
+    /// has failed for good.
+    async fn fail(
+        &self,
+        job_id: &str,
+        error: &str,
+        retry_at: Option<i64>,
+        now: i64,
+    ) -> Result<(), JobStoreError>;
+
+    /// Queues running jobs last updated before `stale_before` again: their worker went away
+    /// without finishing them. Returns how many were requeued.
+    async fn requeue_stale(&self, stale_before: i64, now: i64) -> Result<usize, JobStoreError>;
+}
+
+pub mod in_memory;
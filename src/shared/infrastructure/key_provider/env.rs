@@ -0,0 +1,97 @@
+use crate::shared::infrastructure::key_provider::{
+    EncryptionKey, KeyProvider, KeyProviderError, hex_decode,
+};
+use async_trait::async_trait;
+
+/// Keys configured as `id:hex,id:hex,...`, each 64 hex digits (32 bytes). The first key
+/// seals new payloads; the others only open payloads sealed before a rotation.
+#[derive(Clone)]
+pub struct EnvKeyProvider {
+    keys: Vec<EncryptionKey>,
+}
+
+impl EnvKeyProvider {
+    pub fn parse(value: &str) -> Result<Self, KeyProviderError> {
+        let keys = value
+            .split(',')
+            .map(str::trim)
+            .filter(|pair| !pair.is_empty())
+            .map(|pair| {
+                let invalid = || {
+                    KeyProviderError::Backend(format!(
+                        "expected id:<64 hex digits>, got {}",
+                        pair.split(':').next().unwrap_or_default()
+                    ))
+                };
+                let (id, hex) = pair.split_once(':').ok_or_else(invalid)?;
+                let material = hex_decode(hex)
+                    .and_then(|bytes| <[u8; 32]>::try_from(bytes).ok())
+                    .ok_or_else(invalid)?;
+                Ok(EncryptionKey {
+                    id: id.to_string(),
+                    material,
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        if keys.is_empty() {
+            return Err(KeyProviderError::Backend("no keys configured".to_string()));
+        }
+        Ok(Self { keys })
+    }
+}
+
+#[async_trait]
+impl KeyProvider for EnvKeyProvider {
+    async fn current_key(&self) -> Result<EncryptionKey, KeyProviderError> {
+        Ok(self.keys[0].clone())
+    }
+
+    async fn key(&self, key_id: &str) -> Result<EncryptionKey, KeyProviderError> {
+        self.keys
+            .iter()
+            .find(|key| key.id == key_id)
+            .cloned()
+            .ok_or_else(|| KeyProviderError::UnknownKey(key_id.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod env_key_provider_tests {
+    use super::*;
+    use rstest::rstest;
+
+    fn hex(byte: &str) -> String {
+        byte.repeat(32)
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_seal_with_the_first_key_and_open_with_any() {
+        let provider =
+            EnvKeyProvider::parse(&format!("k-2:{}, k-1:{}", hex("02"), hex("01"))).unwrap();
+
+        assert_eq!(provider.current_key().await.unwrap().id, "k-2");
+        assert_eq!(provider.key("k-1").await.unwrap().material, [1; 32]);
+        assert_eq!(
+            provider.key("k-0").await,
+            Err(KeyProviderError::UnknownKey("k-0".to_string()))
+        );
+    }
+
+    #[rstest]
+    #[case::empty("")]
+    #[case::no_id("0101")]
+    #[case::short_key("k-1:0101")]
+    #[case::not_hex("k-1:zz")]
+    fn it_should_reject_malformed_keys(#[case] value: &str) {
+        assert!(EnvKeyProvider::parse(value).is_err());
+    }
+
+    #[rstest]
+    fn it_should_not_echo_key_material_in_errors() {
+        let Err(error) = EnvKeyProvider::parse("k-1:abcdef") else {
+            panic!("expected an error");
+        };
+        assert!(!error.to_string().contains("abcdef"));
+    }
+}
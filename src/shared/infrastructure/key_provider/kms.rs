@@ -0,0 +1,324 @@
+use crate::shared::infrastructure::key_provider::{
+    EncryptionKey, KeyProvider, KeyProviderError, hex_decode, hex_encode,
+};
+use async_trait::async_trait;
+use aws_sdk_kms::error::DisplayErrorContext;
+use aws_sdk_kms::primitives::Blob;
+use aws_sdk_kms::types::DataKeySpec;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+/// A data key fresh from the KMS: the key itself and the same key wrapped by the master key.
+pub struct GeneratedDataKey {
+    pub plaintext: Vec<u8>,
+    pub ciphertext_blob: Vec<u8>,
+}
+
+/// The AWS KMS calls the provider makes, so the adapter stays independent of an AWS SDK.
+#[async_trait]
+pub trait KmsClient: Send + Sync {
+    /// `GenerateDataKey` with `KeySpec=AES_256` under the master key `key_id`.
+    async fn generate_data_key(&self, key_id: &str) -> Result<GeneratedDataKey, String>;
+
+    /// `Decrypt` of a blob `generate_data_key` returned.
+    async fn decrypt(&self, ciphertext_blob: &[u8]) -> Result<Vec<u8>, String>;
+}
+
+#[derive(Default)]
+struct Keys {
+    current: Option<EncryptionKey>,
+    by_id: HashMap<String, EncryptionKey>,
+}
+
+/// Envelope encryption under a KMS master key. Each instance generates one data key on
+/// first use; its id is the hex of the wrapped key, so any instance can unwrap it with
+/// `Decrypt` without a key table. Unwrapped keys are cached for the life of the process.
+#[derive(Clone)]
+pub struct KmsKeyProvider<TClient> {
+    key_id: String,
+    client: TClient,
+    keys: Arc<RwLock<Keys>>,
+}
+
+impl<TClient: KmsClient> KmsKeyProvider<TClient> {
+    /// `key_id` is the master key's id, ARN or alias.
+    pub fn new(key_id: impl Into<String>, client: TClient) -> Self {
+        Self {
+            key_id: key_id.into(),
+            client,
+            keys: Arc::new(RwLock::new(Keys::default())),
+        }
+    }
+}
+
+fn data_key(id: String, plaintext: Vec<u8>) -> Result<EncryptionKey, KeyProviderError> {
+    let material = <[u8; 32]>::try_from(plaintext).map_err(|_| {
+        KeyProviderError::Backend("KMS returned a data key that is not 256 bits".to_string())
+    })?;
+    Ok(EncryptionKey { id, material })
+}
+
+#[async_trait]
+impl<TClient: KmsClient> KeyProvider for KmsKeyProvider<TClient> {
+    async fn current_key(&self) -> Result<EncryptionKey, KeyProviderError> {
+        if let Some(key) = &self.keys.read().await.current {
+            return Ok(key.clone());
+        }
+        let mut keys = self.keys.write().await;
+        if let Some(key) = &keys.current {
+            return Ok(key.clone());
+        }
+        let generated = self
+            .client
+            .generate_data_key(&self.key_id)
+            .await
+            .map_err(KeyProviderError::Backend)?;
+        let key = data_key(hex_encode(&generated.ciphertext_blob), generated.plaintext)?;
+        keys.by_id.insert(key.id.clone(), key.clone());
+        keys.current = Some(key.clone());
+        Ok(key)
+    }
+
+    async fn key(&self, key_id: &str) -> Result<EncryptionKey, KeyProviderError> {
+        if let Some(key) = self.keys.read().await.by_id.get(key_id) {
+            return Ok(key.clone());
+        }
+        let blob =
+            hex_decode(key_id).ok_or_else(|| KeyProviderError::UnknownKey(key_id.to_string()))?;
+        let plaintext = self
+            .client
+            .decrypt(&blob)
+            .await
+            .map_err(KeyProviderError::Backend)?;
+        let key = data_key(key_id.to_string(), plaintext)?;
+        self.keys
+            .write()
+            .await
+            .by_id
+            .insert(key.id.clone(), key.clone());
+        Ok(key)
+    }
+}
+
+/// `KmsClient` on the AWS SDK.
+#[derive(Clone)]
+pub struct AwsKmsClient {
+    client: aws_sdk_kms::Client,
+}
+
+impl AwsKmsClient {
+    pub fn new(client: aws_sdk_kms::Client) -> Self {
+        Self { client }
+    }
+
+    /// A client with the region and credentials of the usual `AWS_*` variables and profiles.
+    pub async fn from_env() -> Self {
+        Self::new(aws_sdk_kms::Client::new(&aws_config::load_from_env().await))
+    }
+}
+
+#[async_trait]
+impl KmsClient for AwsKmsClient {
+    async fn generate_data_key(&self, key_id: &str) -> Result<GeneratedDataKey, String> {
+        let output = self
+            .client
+            .generate_data_key()
+            .key_id(key_id)
+            .key_spec(DataKeySpec::Aes256)
+            .send()
+            .await
+            .map_err(|error| DisplayErrorContext(error).to_string())?;
+        match (output.plaintext(), output.ciphertext_blob()) {
+            (Some(plaintext), Some(ciphertext_blob)) => Ok(GeneratedDataKey {
+                plaintext: plaintext.as_ref().to_vec(),
+                ciphertext_blob: ciphertext_blob.as_ref().to_vec(),
+            }),
+            _ => Err("KMS returned no data key".to_string()),
+        }
+    }
+
+    async fn decrypt(&self, ciphertext_blob: &[u8]) -> Result<Vec<u8>, String> {
+        let output = self
+            .client
+            .decrypt()
+            .ciphertext_blob(Blob::new(ciphertext_blob))
+            .send()
+            .await
+            .map_err(|error| DisplayErrorContext(error).to_string())?;
+        output
+            .plaintext()
+            .map(|plaintext| plaintext.as_ref().to_vec())
+            .ok_or_else(|| "KMS returned no plaintext".to_string())
+    }
+}
+
+#[cfg(test)]
+mod kms_key_provider_tests {
+    use super::*;
+    use rstest::rstest;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// Wraps data keys by flipping their bits; counts the calls it receives.
+    #[derive(Clone, Default)]
+    struct FakeKms {
+        calls: Arc<AtomicUsize>,
+    }
+
+    #[async_trait]
+    impl KmsClient for FakeKms {
+        async fn generate_data_key(&self, key_id: &str) -> Result<GeneratedDataKey, String> {
+            let n = self.calls.fetch_add(1, Ordering::SeqCst) as u8;
+            if key_id != "alias/events" {
+                return Err(format!("NotFoundException: {key_id}"));
+            }
+            Ok(GeneratedDataKey {
+                plaintext: vec![n; 32],
+                ciphertext_blob: vec![!n; 32],
+            })
+        }
+
+        async fn decrypt(&self, ciphertext_blob: &[u8]) -> Result<Vec<u8>, String> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            Ok(ciphertext_blob.iter().map(|byte| !byte).collect())
+        }
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_generate_one_data_key_and_name_it_by_its_wrapped_form() {
+        let kms = FakeKms::default();
+        let provider = KmsKeyProvider::new("alias/events", kms.clone());
+
+        let key = provider.current_key().await.unwrap();
+
+        assert_eq!(key.id, "ff".repeat(32));
+        assert_eq!(key.material, [0; 32]);
+        assert_eq!(provider.current_key().await.unwrap(), key);
+        assert_eq!(provider.key(&key.id).await.unwrap(), key);
+        assert_eq!(kms.calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_unwrap_keys_of_other_instances_once() {
+        let kms = FakeKms::default();
+        let provider = KmsKeyProvider::new("alias/events", kms.clone());
+        let other_instance_key = "fe".repeat(32);
+
+        let key = provider.key(&other_instance_key).await.unwrap();
+        provider.key(&other_instance_key).await.unwrap();
+
+        assert_eq!(key.material, [1; 32]);
+        assert_eq!(kms.calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_surface_kms_failures() {
+        let provider = KmsKeyProvider::new("alias/missing", FakeKms::default());
+
+        assert_eq!(
+            provider.current_key().await,
+            Err(KeyProviderError::Backend(
+                "NotFoundException: alias/missing".to_string()
+            ))
+        );
+        assert_eq!(
+            provider.key("not-hex").await,
+            Err(KeyProviderError::UnknownKey("not-hex".to_string()))
+        );
+        assert!(matches!(
+            provider.key("ffff").await,
+            Err(KeyProviderError::Backend(_))
+        ));
+    }
+
+    mod aws_kms_client_tests {
+        use super::*;
+        use axum::body::Bytes;
+        use axum::http::{HeaderMap, StatusCode, header};
+        use axum::response::IntoResponse;
+        use axum::{Router, routing::post};
+        use base64::Engine;
+        use base64::engine::general_purpose::STANDARD;
+        use serde_json::{Value, json};
+
+        /// Answers the KMS JSON protocol: data keys are 32 bytes of 7, wrapped as 32 of 9.
+        async fn kms(headers: HeaderMap, body: Bytes) -> impl IntoResponse {
+            let body: Value = serde_json::from_slice(&body).unwrap();
+            let target = headers["x-amz-target"].to_str().unwrap().to_string();
+            let (status, answer) = match target.as_str() {
+                "TrentService.GenerateDataKey" if body["KeyId"] == "alias/events" => {
+                    assert_eq!(body["KeySpec"], "AES_256");
+                    (
+                        StatusCode::OK,
+                        json!({
+                            "KeyId": "alias/events",
+                            "Plaintext": STANDARD.encode([7; 32]),
+                            "CiphertextBlob": STANDARD.encode([9; 32]),
+                        }),
+                    )
+                }
+                "TrentService.Decrypt" => {
+                    assert_eq!(body["CiphertextBlob"], STANDARD.encode([9; 32]));
+                    (
+                        StatusCode::OK,
+                        json!({ "Plaintext": STANDARD.encode([7; 32]) }),
+                    )
+                }
+                _ => (
+                    StatusCode::BAD_REQUEST,
+                    json!({ "__type": "NotFoundException", "message": "no such key" }),
+                ),
+            };
+            (
+                status,
+                [(header::CONTENT_TYPE, "application/x-amz-json-1.1")],
+                answer.to_string(),
+            )
+        }
+
+        async fn client() -> AwsKmsClient {
+            let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+            let address = listener.local_addr().unwrap();
+            let router = Router::new().route("/", post(kms));
+            tokio::spawn(async move { axum::serve(listener, router).await.unwrap() });
+            let config = aws_sdk_kms::Config::builder()
+                .region(aws_sdk_kms::config::Region::new("eu-west-1"))
+                .endpoint_url(format!("http://{address}"))
+                .credentials_provider(aws_sdk_kms::config::Credentials::new(
+                    "test", "test", None, None, "test",
+                ))
+                .build();
+            AwsKmsClient::new(aws_sdk_kms::Client::from_conf(config))
+        }
+
+        #[rstest]
+        #[tokio::test]
+        async fn it_should_generate_and_unwrap_data_keys_through_the_sdk() {
+            let provider = KmsKeyProvider::new("alias/events", client().await);
+
+            let key = provider.current_key().await.unwrap();
+            let unwrapped = KmsKeyProvider::new("alias/events", client().await)
+                .key(&key.id)
+                .await
+                .unwrap();
+
+            assert_eq!(key.id, "09".repeat(32));
+            assert_eq!(key.material, [7; 32]);
+            assert_eq!(unwrapped, key);
+        }
+
+        #[rstest]
+        #[tokio::test]
+        async fn it_should_report_service_errors() {
+            let provider = KmsKeyProvider::new("alias/missing", client().await);
+
+            let Err(KeyProviderError::Backend(reason)) = provider.current_key().await else {
+                panic!("expected a backend error");
+            };
+            assert!(reason.contains("NotFoundException"), "{reason}");
+        }
+    }
+}
@@ -0,0 +1,97 @@
+use async_trait::async_trait;
+use std::sync::Arc;
+use thiserror::Error;
+
+#[derive(Debug, Clone, Error, PartialEq, Eq)]
+pub enum KeyProviderError {
+    #[error("unknown encryption key: {0}")]
+    UnknownKey(String),
+
+    #[error("backend error: {0}")]
+    Backend(String),
+}
+
+/// A 256-bit key for sealing event payloads. `id` is stored next to each payload so the key
+/// can be found again after rotation.
+#[derive(Clone, PartialEq, Eq)]
+pub struct EncryptionKey {
+    pub id: String,
+    pub material: [u8; 32],
+}
+
+impl std::fmt::Debug for EncryptionKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EncryptionKey")
+            .field("id", &self.id)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Where the keys for encrypting event payloads at rest come from (an environment variable,
+/// a KMS). Unlike the per-subject `KeyStore`, keys here are shared and never deleted.
+#[async_trait]
+pub trait KeyProvider: Send + Sync {
+    /// The key new payloads are sealed with.
+    async fn current_key(&self) -> Result<EncryptionKey, KeyProviderError>;
+
+    /// The key a payload was sealed with, which may have been rotated out since.
+    async fn key(&self, key_id: &str) -> Result<EncryptionKey, KeyProviderError>;
+}
+
+/// A key provider behind a pointer, so the shell can pick the source of keys at startup.
+pub type SharedKeyProvider = Arc<dyn KeyProvider>;
+
+#[async_trait]
+impl<T: KeyProvider + ?Sized> KeyProvider for Arc<T> {
+    async fn current_key(&self) -> Result<EncryptionKey, KeyProviderError> {
+        (**self).current_key().await
+    }
+
+    async fn key(&self, key_id: &str) -> Result<EncryptionKey, KeyProviderError> {
+        (**self).key(key_id).await
+    }
+}
+
+pub fn hex_encode(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+/// `None` unless `hex` is an even number of hex digits.
+pub fn hex_decode(hex: &str) -> Option<Vec<u8>> {
+    if !hex.len().is_multiple_of(2) {
+        return None;
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok())
+        .collect()
+}
+
+pub mod env;
+pub mod kms;
+
+#[cfg(test)]
+mod key_provider_tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[test]
+    fn it_should_not_print_key_material() {
+        let key = EncryptionKey {
+            id: "k-1".to_string(),
+            material: [7; 32],
+        };
+        assert_eq!(format!("{key:?}"), r#"EncryptionKey { id: "k-1", .. }"#);
+    }
+
+    #[rstest]
+    #[case::round_trip("00ff10", Some(vec![0x00, 0xff, 0x10]))]
+    #[case::odd_length("0ff", None)]
+    #[case::not_hex("zz", None)]
+    fn it_should_decode_hex(#[case] hex: &str, #[case] expected: Option<Vec<u8>>) {
+        assert_eq!(hex_decode(hex), expected);
+        if let Some(bytes) = expected {
+            assert_eq!(hex_encode(&bytes), hex);
+        }
+    }
+}
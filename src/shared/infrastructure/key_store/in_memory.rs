@@ -0,0 +1,131 @@
+use crate::shared::infrastructure::key_store::{DataKey, KeyStore, KeyStoreError};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use tokio::sync::RwLock;
+
+#[derive(Default)]
+struct Keys {
+    by_subject: HashMap<String, DataKey>,
+    subject_by_id: HashMap<String, String>,
+}
+
+#[derive(Default)]
+struct Inner {
+    keys: RwLock<Keys>,
+    is_offline: AtomicBool,
+}
+
+/// Development key store. Key material comes from UUIDv7 random bits, which is not a
+/// substitute for a KMS-generated key.
+#[derive(Clone, Default)]
+pub struct InMemoryKeyStore {
+    inner: Arc<Inner>,
+}
+
+impl InMemoryKeyStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn toggle_offline(&self) {
+        self.inner.is_offline.fetch_xor(true, Ordering::SeqCst);
+    }
+
+    fn ensure_online(&self) -> Result<(), KeyStoreError> {
+        if self.inner.is_offline.load(Ordering::SeqCst) {
+            return Err(KeyStoreError::Backend("Key store offline".to_string()));
+        }
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl KeyStore for InMemoryKeyStore {
+    async fn key_for_subject(&self, subject: &str) -> Result<DataKey, KeyStoreError> {
+        self.ensure_online()?;
+        let mut keys = self.inner.keys.write().await;
+        if let Some(key) = keys.by_subject.get(subject) {
+            return Ok(key.clone());
+        }
+        let material = [uuid::Uuid::now_v7(), uuid::Uuid::now_v7()]
+            .iter()
+            .flat_map(|id| id.as_bytes().to_vec())
+            .collect();
+        let key = DataKey {
+            id: uuid::Uuid::now_v7().to_string(),
+            material,
+        };
+        keys.subject_by_id
+            .insert(key.id.clone(), subject.to_string());
+        keys.by_subject.insert(subject.to_string(), key.clone());
+        Ok(key)
+    }
+
+    async fn key_by_id(&self, key_id: &str) -> Result<Option<DataKey>, KeyStoreError> {
+        self.ensure_online()?;
+        let keys = self.inner.keys.read().await;
+        Ok(keys
+            .subject_by_id
+            .get(key_id)
+            .and_then(|subject| keys.by_subject.get(subject))
+            .cloned())
+    }
+
+    async fn forget_subject(&self, subject: &str) -> Result<Option<String>, KeyStoreError> {
+        self.ensure_online()?;
+        let mut keys = self.inner.keys.write().await;
+        let key = keys.by_subject.remove(subject);
+        if let Some(key) = &key {
+            keys.subject_by_id.remove(&key.id);
+        }
+        Ok(key.map(|key| key.id))
+    }
+}
+
+#[cfg(test)]
+mod in_memory_key_store_tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn it_should_create_one_key_per_subject() {
+        let store = InMemoryKeyStore::new();
+
+        let first = store.key_for_subject("u1").await.unwrap();
+        let again = store.key_for_subject("u1").await.unwrap();
+        let other = store.key_for_subject("u2").await.unwrap();
+
+        assert_eq!(first, again);
+        assert_ne!(first.id, other.id);
+        assert_eq!(first.material.len(), 32);
+        assert_eq!(store.key_by_id(&first.id).await.unwrap(), Some(first));
+    }
+
+    #[tokio::test]
+    async fn it_should_forget_a_subject_key() {
+        let store = InMemoryKeyStore::new();
+        let key = store.key_for_subject("u1").await.unwrap();
+
+        assert_eq!(
+            store.forget_subject("u1").await.unwrap(),
+            Some(key.id.clone())
+        );
+        assert_eq!(store.forget_subject("u1").await.unwrap(), None);
+
+        assert_eq!(store.key_by_id(&key.id).await.unwrap(), None);
+        assert_ne!(store.key_for_subject("u1").await.unwrap().id, key.id);
+    }
+
+    #[tokio::test]
+    async fn it_should_fail_every_call_when_offline() {
+        let store = InMemoryKeyStore::new();
+        store.toggle_offline();
+
+        assert!(store.key_for_subject("u1").await.is_err());
+        assert!(store.key_by_id("k").await.is_err());
+        assert_eq!(
+            store.forget_subject("u1").await,
+            Err(KeyStoreError::Backend("Key store offline".to_string()))
+        );
+    }
+}
@@ -0,0 +1,53 @@
+use async_trait::async_trait;
+use thiserror::Error;
+
+#[derive(Debug, Clone, Error, PartialEq, Eq)]
+pub enum KeyStoreError {
+    #[error("backend error: {0}")]
+    Backend(String),
+}
+
+/// A per-subject data key. `id` is opaque so ciphertexts never reveal whose key they use.
+#[derive(Clone, PartialEq, Eq)]
+pub struct DataKey {
+    pub id: String,
+    pub material: Vec<u8>,
+}
+
+impl std::fmt::Debug for DataKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DataKey")
+            .field("id", &self.id)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Holds one encryption key per data subject (user). Deleting a subject's key makes every
+/// value encrypted with it unreadable, which is how personal data in immutable events is erased.
+#[async_trait]
+pub trait KeyStore: Send + Sync {
+    /// Returns the subject's key, creating one on first use.
+    async fn key_for_subject(&self, subject: &str) -> Result<DataKey, KeyStoreError>;
+
+    /// Looks a key up by id; `None` once the owning subject has been forgotten.
+    async fn key_by_id(&self, key_id: &str) -> Result<Option<DataKey>, KeyStoreError>;
+
+    /// Deletes the subject's key. Returns the id of the deleted key, if there was one.
+    async fn forget_subject(&self, subject: &str) -> Result<Option<String>, KeyStoreError>;
+}
+
+pub mod in_memory;
+
+#[cfg(test)]
+mod data_key_tests {
+    use super::*;
+
+    #[test]
+    fn it_should_not_print_key_material() {
+        let key = DataKey {
+            id: "k-1".to_string(),
+            material: vec![1, 2, 3],
+        };
+        assert_eq!(format!("{key:?}"), r#"DataKey { id: "k-1", .. }"#);
+    }
+}
@@ -0,0 +1,141 @@
+use crate::shared::infrastructure::lease_store::{LeaseStore, LeaseStoreError};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use tokio::sync::Mutex;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Lease {
+    holder: String,
+    expires_at: i64,
+}
+
+#[derive(Default)]
+struct Inner {
+    leases: Mutex<HashMap<String, Lease>>,
+    is_offline: AtomicBool,
+}
+
+#[derive(Clone, Default)]
+pub struct InMemoryLeaseStore {
+    inner: Arc<Inner>,
+}
+
+impl InMemoryLeaseStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn toggle_offline(&self) {
+        self.inner.is_offline.fetch_xor(true, Ordering::SeqCst);
+    }
+
+    /// The current holder of `name`, expired or not.
+    pub async fn holder(&self, name: &str) -> Option<String> {
+        self.inner
+            .leases
+            .lock()
+            .await
+            .get(name)
+            .map(|lease| lease.holder.clone())
+    }
+
+    fn ensure_online(&self) -> Result<(), LeaseStoreError> {
+        if self.inner.is_offline.load(Ordering::SeqCst) {
+            return Err(LeaseStoreError::Backend("Lease store offline".to_string()));
+        }
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl LeaseStore for InMemoryLeaseStore {
+    async fn try_acquire(
+        &self,
+        name: &str,
+        holder: &str,
+        now: i64,
+        ttl_ms: i64,
+    ) -> Result<bool, LeaseStoreError> {
+        self.ensure_online()?;
+        let mut leases = self.inner.leases.lock().await;
+        if let Some(lease) = leases.get(name)
+            && lease.holder != holder
+            && lease.expires_at > now
+        {
+            return Ok(false);
+        }
+        leases.insert(
+            name.to_string(),
+            Lease {
+                holder: holder.to_string(),
+                expires_at: now + ttl_ms,
+            },
+        );
+        Ok(true)
+    }
+
+    async fn release(&self, name: &str, holder: &str) -> Result<(), LeaseStoreError> {
+        self.ensure_online()?;
+        let mut leases = self.inner.leases.lock().await;
+        if leases.get(name).is_some_and(|lease| lease.holder == holder) {
+            leases.remove(name);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod in_memory_lease_store_tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_grant_a_lease_to_one_holder_at_a_time() {
+        let store = InMemoryLeaseStore::new();
+
+        assert!(store.try_acquire("relay", "a", 0, 100).await.unwrap());
+        assert!(!store.try_acquire("relay", "b", 50, 100).await.unwrap());
+        assert!(store.try_acquire("projector", "b", 50, 100).await.unwrap());
+        assert_eq!(store.holder("relay").await.as_deref(), Some("a"));
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_let_the_holder_renew_and_others_take_over_once_expired() {
+        let store = InMemoryLeaseStore::new();
+        store.try_acquire("relay", "a", 0, 100).await.unwrap();
+
+        assert!(store.try_acquire("relay", "a", 90, 100).await.unwrap());
+        assert!(!store.try_acquire("relay", "b", 150, 100).await.unwrap());
+        assert!(store.try_acquire("relay", "b", 190, 100).await.unwrap());
+        assert_eq!(store.holder("relay").await.as_deref(), Some("b"));
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_only_release_a_lease_for_its_holder() {
+        let store = InMemoryLeaseStore::new();
+        store.try_acquire("relay", "a", 0, 100).await.unwrap();
+
+        store.release("relay", "b").await.unwrap();
+        assert_eq!(store.holder("relay").await.as_deref(), Some("a"));
+        store.release("relay", "a").await.unwrap();
+        assert_eq!(store.holder("relay").await, None);
+        assert!(store.try_acquire("relay", "b", 10, 100).await.unwrap());
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_fail_when_offline() {
+        let store = InMemoryLeaseStore::new();
+        store.toggle_offline();
+
+        assert_eq!(
+            store.try_acquire("relay", "a", 0, 100).await,
+            Err(LeaseStoreError::Backend("Lease store offline".to_string()))
+        );
+        assert!(store.release("relay", "a").await.is_err());
+    }
+}
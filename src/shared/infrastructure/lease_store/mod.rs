@@ -0,0 +1,29 @@
+use async_trait::async_trait;
+use thiserror::Error;
+
+#[derive(Debug, Clone, Error, PartialEq, Eq)]
+pub enum LeaseStoreError {
+    #[error("backend error: {0}")]
+    Backend(String),
+}
+
+/// Named, expiring leases so one of several service instances drives each projector or relay
+/// (Postgres advisory locks, Redis `SET NX PX`). Times are epoch milliseconds.
+#[async_trait]
+pub trait LeaseStore: Send + Sync {
+    /// Grants `name` to `holder` until `now + ttl_ms`, or extends it if `holder` already has it.
+    /// Returns `false` while another holder's lease is unexpired.
+    async fn try_acquire(
+        &self,
+        name: &str,
+        holder: &str,
+        now: i64,
+        ttl_ms: i64,
+    ) -> Result<bool, LeaseStoreError>;
+
+    /// Gives the lease up so a standby can take over without waiting for it to expire.
+    /// Does nothing when `holder` no longer holds it.
+    async fn release(&self, name: &str, holder: &str) -> Result<(), LeaseStoreError>;
+}
+
+pub mod in_memory;
@@ -0,0 +1,149 @@
+// Applies the redactor to log and trace output.
+//
+// `RedactingFields` formats event and span fields like the default formatter, but writes the
+// mask for any field the redactor names. Plug it into the subscriber with `fmt_fields`, and
+// every `tracing` call site is covered without changing it.
+
+use std::fmt;
+
+use tracing::field::{Field, Visit};
+use tracing_subscriber::field::{MakeVisitor, VisitFmt, VisitOutput};
+use tracing_subscriber::fmt::format::{DefaultVisitor, Writer};
+
+use crate::shared::core::redaction::{MASK, Redactor};
+
+#[derive(Debug, Clone, Default)]
+pub struct RedactingFields {
+    redactor: Redactor,
+}
+
+impl RedactingFields {
+    pub fn new(redactor: Redactor) -> Self {
+        Self { redactor }
+    }
+}
+
+impl<'a> MakeVisitor<Writer<'a>> for RedactingFields {
+    type Visitor = RedactingVisitor<'a>;
+
+    fn make_visitor(&self, target: Writer<'a>) -> Self::Visitor {
+        RedactingVisitor {
+            inner: DefaultVisitor::new(target, true),
+            redactor: self.redactor.clone(),
+        }
+    }
+}
+
+pub struct RedactingVisitor<'a> {
+    inner: DefaultVisitor<'a>,
+    redactor: Redactor,
+}
+
+impl RedactingVisitor<'_> {
+    fn mask(&mut self, field: &Field) {
+        self.inner.record_debug(field, &format_args!("{MASK}"));
+    }
+}
+
+impl Visit for RedactingVisitor<'_> {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if self.redactor.masks(field.name()) {
+            self.mask(field);
+        } else {
+            self.inner.record_str(field, value);
+        }
+    }
+
+    fn record_error(&mut self, field: &Field, value: &(dyn std::error::Error + 'static)) {
+        if self.redactor.masks(field.name()) {
+            self.mask(field);
+        } else {
+            self.inner.record_error(field, value);
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        if self.redactor.masks(field.name()) {
+            self.mask(field);
+        } else {
+            self.inner.record_debug(field, value);
+        }
+    }
+}
+
+impl VisitOutput<fmt::Result> for RedactingVisitor<'_> {
+    fn finish(self) -> fmt::Result {
+        self.inner.finish()
+    }
+}
+
+impl VisitFmt for RedactingVisitor<'_> {
+    fn writer(&mut self) -> &mut dyn fmt::Write {
+        self.inner.writer()
+    }
+}
+
+#[cfg(test)]
+mod redacting_fields_tests {
+    use super::*;
+    use rstest::rstest;
+    use std::io;
+    use std::sync::{Arc, Mutex};
+
+    #[derive(Clone, Default)]
+    struct Captured(Arc<Mutex<Vec<u8>>>);
+
+    impl io::Write for Captured {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    fn logged(redactor: Redactor) -> String {
+        let captured = Captured::default();
+        let writer = captured.clone();
+        let subscriber = tracing_subscriber::fmt()
+            .with_ansi(false)
+            .with_writer(move || writer.clone())
+            .fmt_fields(RedactingFields::new(redactor))
+            .finish();
+        tracing::subscriber::with_default(subscriber, || {
+            let span = tracing::info_span!("command", created_by = "u-2");
+            let _entered = span.enter();
+            tracing::info!(
+                user_id = "u-1",
+                description = %"dentist",
+                tags = ?["t-1"],
+                "entry registered"
+            );
+        });
+        String::from_utf8(captured.0.lock().unwrap().clone()).unwrap()
+    }
+
+    #[rstest]
+    fn it_should_mask_the_redacted_fields_of_events_and_spans() {
+        let line = logged(Redactor::default());
+
+        assert!(line.contains("description=***"), "{line}");
+        assert!(line.contains("created_by=***"), "{line}");
+        assert!(line.contains("user_id=***"), "{line}");
+        assert!(line.contains("tags=[\"t-1\"]"), "{line}");
+        assert!(line.contains("entry registered"), "{line}");
+    }
+
+    #[rstest]
+    fn it_should_mask_configured_fields_and_nothing_when_disabled() {
+        let line = logged(Redactor::default().with_fields(["description"]));
+        assert!(line.contains("description=***"), "{line}");
+        assert!(line.contains("user_id=\"u-1\""), "{line}");
+
+        let line = logged(Redactor::disabled());
+        assert!(line.contains("description=dentist"), "{line}");
+        assert!(line.contains("created_by=\"u-2\""), "{line}");
+    }
+}
@@ -0,0 +1,236 @@
+// CloudEvents 1.0 envelopes for published outbox rows.
+//
+// JSON messages use the structured content mode: the body is the CloudEvent itself, with the
+// row's payload as `data`. Avro and Protobuf messages use the binary mode: the body stays as
+// encoded and the attributes travel as headers, `ce_` prefixed for brokers (Kafka protocol
+// binding) or `ce-` prefixed for HTTP webhooks.
+//
+// The event id is the row's outbox idempotency key (`OutboxRow::dedupe_key`), so a resent
+// batch carries the same ids and consumers can deduplicate on them.
+
+use crate::shared::core::primitives::Timestamp;
+use crate::shared::infrastructure::intent_outbox::OutboxRow;
+use crate::shared::infrastructure::message_broker::encoding::{EncodedMessage, PayloadEncoding};
+use serde::{Deserialize, Serialize};
+
+pub const SPEC_VERSION: &str = "1.0";
+pub const STRUCTURED_CONTENT_TYPE: &str = "application/cloudevents+json";
+/// Header prefix of the Kafka protocol binding.
+pub const BROKER_HEADER_PREFIX: &str = "ce_";
+/// Header prefix of the HTTP protocol binding.
+pub const HTTP_HEADER_PREFIX: &str = "ce-";
+/// Prefixes every event `type`, reverse-DNS style as the spec recommends.
+const TYPE_PREFIX: &str = "time_entries";
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CloudEvent {
+    pub specversion: String,
+    pub id: String,
+    pub source: String,
+    #[serde(rename = "type")]
+    pub event_type: String,
+    /// RFC 3339.
+    pub time: String,
+    pub subject: String,
+    pub datacontenttype: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data: Option<serde_json::Value>,
+}
+
+impl CloudEvent {
+    /// `source` identifies this service, e.g. `https://time.example.com/time-entries`.
+    pub fn from_row(row: &OutboxRow, source: &str, datacontenttype: &str) -> Self {
+        Self {
+            specversion: SPEC_VERSION.to_string(),
+            id: row.dedupe_key(),
+            source: source.to_string(),
+            event_type: format!("{TYPE_PREFIX}.{}.v{}", row.event_type, row.event_version),
+            time: Timestamp::from(row.occurred_at).to_string(),
+            subject: row.stream_id.clone(),
+            datacontenttype: datacontenttype.to_string(),
+            data: None,
+        }
+    }
+
+    /// The attributes as binary content mode headers; `data` is the message body instead.
+    pub fn headers(&self, prefix: &str) -> Vec<(String, String)> {
+        [
+            ("specversion", &self.specversion),
+            ("id", &self.id),
+            ("source", &self.source),
+            ("type", &self.event_type),
+            ("time", &self.time),
+            ("subject", &self.subject),
+        ]
+        .into_iter()
+        .map(|(name, value)| (format!("{prefix}{name}"), value.clone()))
+        .chain([("content-type".to_string(), self.datacontenttype.clone())])
+        .collect()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CloudEventsConfig {
+    pub source: String,
+}
+
+impl CloudEventsConfig {
+    pub fn new(source: impl Into<String>) -> Self {
+        Self {
+            source: source.into(),
+        }
+    }
+
+    /// Wraps a message `encode` produced from `row` in a CloudEvent.
+    pub fn wrap(&self, row: &OutboxRow, message: EncodedMessage) -> EncodedMessage {
+        let event = CloudEvent::from_row(row, &self.source, message.encoding.content_type());
+        match message.encoding {
+            PayloadEncoding::Json => EncodedMessage {
+                body: serde_json::to_vec(&CloudEvent {
+                    data: Some(row.payload.clone()),
+                    ..event
+                })
+                .expect("a CloudEvent always serializes"),
+                headers: vec![(
+                    "content-type".to_string(),
+                    STRUCTURED_CONTENT_TYPE.to_string(),
+                )],
+                ..message
+            },
+            PayloadEncoding::Avro | PayloadEncoding::Protobuf => EncodedMessage {
+                headers: event.headers(BROKER_HEADER_PREFIX),
+                ..message
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod cloud_events_tests {
+    use super::*;
+    use crate::shared::infrastructure::intent_outbox::OutboxStatus;
+    use crate::shared::infrastructure::message_broker::encoding::encode;
+    use rstest::rstest;
+    use serde_json::json;
+
+    const SOURCE: &str = "https://time.example.com/time-entries";
+
+    fn row() -> OutboxRow {
+        OutboxRow {
+            topic: "time-entries.v1".to_string(),
+            event_type: "TimerAutoStopped".to_string(),
+            event_version: 1,
+            stream_id: "TimeEntry-te-1".to_string(),
+            stream_version: 4,
+            intent_no: 0,
+            occurred_at: 1_700_000_000_000,
+            payload: json!({ "stopped_at": 1_700_000_000_000_i64 }),
+            content_encoding: None,
+            status: OutboxStatus::Pending,
+            attempts: 0,
+            last_error: None,
+            published_at: None,
+        }
+    }
+
+    #[test]
+    fn it_should_map_a_row_onto_the_required_and_optional_attributes() {
+        let event = CloudEvent::from_row(&row(), SOURCE, "application/json");
+
+        assert_eq!(event.specversion, "1.0");
+        assert_eq!(event.id, "TimeEntry-te-1:4");
+        assert_eq!(event.source, SOURCE);
+        assert_eq!(event.event_type, "time_entries.TimerAutoStopped.v1");
+        assert_eq!(event.time, "2023-11-14T22:13:20.000Z");
+        assert_eq!(event.subject, "TimeEntry-te-1");
+        assert_eq!(event.datacontenttype, "application/json");
+    }
+
+    #[test]
+    fn it_should_send_json_messages_in_structured_mode() {
+        let config = CloudEventsConfig::new(SOURCE);
+        let message = config.wrap(
+            &row(),
+            encode("time-entries.v1", &row(), PayloadEncoding::Json),
+        );
+
+        let body: serde_json::Value = serde_json::from_slice(&message.body).unwrap();
+        assert_eq!(
+            body,
+            json!({
+                "specversion": "1.0",
+                "id": "TimeEntry-te-1:4",
+                "source": SOURCE,
+                "type": "time_entries.TimerAutoStopped.v1",
+                "time": "2023-11-14T22:13:20.000Z",
+                "subject": "TimeEntry-te-1",
+                "datacontenttype": "application/json",
+                "data": { "stopped_at": 1_700_000_000_000_i64 },
+            })
+        );
+        assert_eq!(
+            message.headers,
+            vec![(
+                "content-type".to_string(),
+                STRUCTURED_CONTENT_TYPE.to_string()
+            )]
+        );
+    }
+
+    #[rstest]
+    #[case(PayloadEncoding::Avro, "avro/binary")]
+    #[case(PayloadEncoding::Protobuf, "application/x-protobuf")]
+    fn it_should_send_binary_messages_with_attribute_headers(
+        #[case] encoding: PayloadEncoding,
+        #[case] content_type: &str,
+    ) {
+        let encoded = encode("time-entries.v1", &row(), encoding);
+        let message = CloudEventsConfig::new(SOURCE).wrap(&row(), encoded.clone());
+
+        assert_eq!(message.body, encoded.body);
+        assert!(
+            message
+                .headers
+                .contains(&("ce_id".to_string(), "TimeEntry-te-1:4".to_string()))
+        );
+        assert!(
+            message
+                .headers
+                .contains(&("content-type".to_string(), content_type.to_string()))
+        );
+    }
+
+    #[test]
+    fn it_should_prefix_headers_for_the_http_binding() {
+        let event = CloudEvent::from_row(&row(), SOURCE, "application/json");
+        let names: Vec<String> = event
+            .headers(HTTP_HEADER_PREFIX)
+            .into_iter()
+            .map(|(name, _)| name)
+            .collect();
+
+        assert_eq!(
+            names,
+            [
+                "ce-specversion",
+                "ce-id",
+                "ce-source",
+                "ce-type",
+                "ce-time",
+                "ce-subject",
+                "content-type"
+            ]
+        );
+    }
+
+    #[test]
+    fn it_should_round_trip_through_json() {
+        let event = CloudEvent {
+            data: Some(json!({ "a": 1 })),
+            ..CloudEvent::from_row(&row(), SOURCE, "application/json")
+        };
+        let parsed: CloudEvent =
+            serde_json::from_str(&serde_json::to_string(&event).unwrap()).unwrap();
+        assert_eq!(parsed, event);
+    }
+}
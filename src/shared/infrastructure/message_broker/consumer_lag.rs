@@ -0,0 +1,325 @@
+// Lag of the consumers reading from the broker.
+//
+// Each broker consumer adapter reports, per topic partition, the head offset and the offset
+// its consumer group committed. `ConsumerLag` keeps the last report of every consumer and
+// derives the lag: how many messages the group has yet to process. A partition is stalled
+// when it has lag and its committed offset has not moved for `stall_after_ms`, which is how
+// a stuck consumer shows before the data it feeds goes stale. The lag is exposed to
+// operators through the admin API and rendered in the Prometheus text format for scraping.
+
+use crate::shared::infrastructure::message_broker::BrokerError;
+use async_trait::async_trait;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::sync::{Arc, Mutex};
+
+pub const DEFAULT_STALL_AFTER_MS: i64 = 5 * 60 * 1000;
+
+/// Offsets of one topic partition as a consumer group sees them. `committed_offset` is the
+/// next offset the group will process, `None` before it committed anything.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PartitionOffsets {
+    pub topic: String,
+    pub partition: u32,
+    pub head_offset: i64,
+    pub committed_offset: Option<i64>,
+}
+
+impl PartitionOffsets {
+    pub fn lag(&self) -> i64 {
+        (self.head_offset - self.committed_offset.unwrap_or(0)).max(0)
+    }
+}
+
+/// Implemented by every broker consumer adapter (Kafka consumer groups, Pulsar subscriptions).
+#[async_trait]
+pub trait ConsumerOffsets: Send + Sync {
+    /// The consumer group, or subscription, the offsets are committed under.
+    fn consumer(&self) -> &str;
+
+    /// The offsets of every partition the consumer reads from.
+    async fn offsets(&self) -> Result<Vec<PartitionOffsets>, BrokerError>;
+}
+
+/// The lag of one consumer on one topic partition, as of `observed_at`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PartitionLag {
+    pub consumer: String,
+    pub topic: String,
+    pub partition: u32,
+    pub head_offset: i64,
+    pub committed_offset: Option<i64>,
+    pub lag: i64,
+    pub observed_at: i64,
+    /// When the committed offset last moved, or the lag was last zero.
+    pub progressed_at: i64,
+    pub stalled: bool,
+}
+
+/// Keyed by `(consumer, topic, partition)`.
+type Partitions = BTreeMap<(String, String, u32), Observed>;
+
+#[derive(Debug, Clone)]
+struct Observed {
+    offsets: PartitionOffsets,
+    observed_at: i64,
+    progressed_at: i64,
+}
+
+/// Last reported offsets per consumer and partition; clones observe the same values.
+#[derive(Debug, Clone)]
+pub struct ConsumerLag {
+    partitions: Arc<Mutex<Partitions>>,
+    stall_after_ms: i64,
+}
+
+impl Default for ConsumerLag {
+    fn default() -> Self {
+        Self {
+            partitions: Arc::default(),
+            stall_after_ms: DEFAULT_STALL_AFTER_MS,
+        }
+    }
+}
+
+impl ConsumerLag {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_stall_after_ms(mut self, stall_after_ms: i64) -> Self {
+        self.stall_after_ms = stall_after_ms;
+        self
+    }
+
+    /// Records what `consumer` reported at `now`. Partitions it no longer reports stay at
+    /// their last offsets.
+    pub fn record(&self, consumer: &str, offsets: Vec<PartitionOffsets>, now: i64) {
+        let mut partitions = self.partitions.lock().unwrap();
+        for offsets in offsets {
+            let key = (
+                consumer.to_string(),
+                offsets.topic.clone(),
+                offsets.partition,
+            );
+            let progressed_at = match partitions.get(&key) {
+                Some(previous)
+                    if previous.offsets.committed_offset == offsets.committed_offset
+                        && offsets.lag() > 0 =>
+                {
+                    previous.progressed_at
+                }
+                _ => now,
+            };
+            partitions.insert(
+                key,
+                Observed {
+                    offsets,
+                    observed_at: now,
+                    progressed_at,
+                },
+            );
+        }
+    }
+
+    /// Every partition of every consumer, ordered by consumer, topic and partition.
+    pub fn partitions(&self, now: i64) -> Vec<PartitionLag> {
+        let partitions = self.partitions.lock().unwrap();
+        partitions
+            .iter()
+            .map(|((consumer, _, _), observed)| {
+                let lag = observed.offsets.lag();
+                PartitionLag {
+                    consumer: consumer.clone(),
+                    topic: observed.offsets.topic.clone(),
+                    partition: observed.offsets.partition,
+                    head_offset: observed.offsets.head_offset,
+                    committed_offset: observed.offsets.committed_offset,
+                    lag,
+                    observed_at: observed.observed_at,
+                    progressed_at: observed.progressed_at,
+                    stalled: lag > 0 && now - observed.progressed_at >= self.stall_after_ms,
+                }
+            })
+            .collect()
+    }
+
+    /// `consumer_lag_messages` and `consumer_stalled` gauges per consumer and partition.
+    pub fn render(&self, now: i64) -> String {
+        let partitions = self.partitions(now);
+        let labels = |partition: &PartitionLag| {
+            format!(
+                "consumer=\"{}\",topic=\"{}\",partition=\"{}\"",
+                escape_label(&partition.consumer),
+                escape_label(&partition.topic),
+                partition.partition,
+            )
+        };
+        let mut out = String::new();
+        out.push_str("# HELP consumer_lag_messages Messages a consumer has yet to process.\n");
+        out.push_str("# TYPE consumer_lag_messages gauge\n");
+        for partition in &partitions {
+            let _ = writeln!(
+                out,
+                "consumer_lag_messages{{{}}} {}",
+                labels(partition),
+                partition.lag
+            );
+        }
+        out.push_str("# HELP consumer_stalled Whether a consumer stopped committing with lag.\n");
+        out.push_str("# TYPE consumer_stalled gauge\n");
+        for partition in &partitions {
+            let _ = writeln!(
+                out,
+                "consumer_stalled{{{}}} {}",
+                labels(partition),
+                u8::from(partition.stalled)
+            );
+        }
+        out
+    }
+}
+
+fn escape_label(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+/// Fetches the offsets of every consumer and records them in `lag`. A consumer whose offsets
+/// cannot be fetched keeps its last report; the others are still recorded.
+pub async fn observe(
+    consumers: &[Arc<dyn ConsumerOffsets>],
+    lag: &ConsumerLag,
+    now: i64,
+) -> Vec<(String, BrokerError)> {
+    let mut failures = Vec::new();
+    for consumer in consumers {
+        match consumer.offsets().await {
+            Ok(offsets) => lag.record(consumer.consumer(), offsets, now),
+            Err(error) => failures.push((consumer.consumer().to_string(), error)),
+        }
+    }
+    failures
+}
+
+#[cfg(test)]
+mod consumer_lag_tests {
+    use super::*;
+    use crate::shared::infrastructure::message_broker::in_memory::InMemoryConsumer;
+    use rstest::rstest;
+
+    const MINUTE_MS: i64 = 60 * 1000;
+
+    fn offsets(partition: u32, head: i64, committed: Option<i64>) -> PartitionOffsets {
+        PartitionOffsets {
+            topic: "time-entries.v1".to_string(),
+            partition,
+            head_offset: head,
+            committed_offset: committed,
+        }
+    }
+
+    #[rstest]
+    #[case::caught_up(offsets(0, 10, Some(10)), 0)]
+    #[case::behind(offsets(0, 10, Some(4)), 6)]
+    #[case::nothing_committed(offsets(0, 10, None), 10)]
+    #[case::committed_past_a_truncated_head(offsets(0, 3, Some(4)), 0)]
+    fn it_should_count_the_messages_left(#[case] offsets: PartitionOffsets, #[case] lag: i64) {
+        assert_eq!(offsets.lag(), lag);
+    }
+
+    #[rstest]
+    fn it_should_report_lag_per_partition() {
+        let lag = ConsumerLag::new();
+
+        lag.record(
+            "billing",
+            vec![offsets(1, 8, Some(8)), offsets(0, 10, Some(7))],
+            1_000,
+        );
+
+        let partitions = lag.partitions(1_000);
+        assert_eq!(partitions.len(), 2);
+        assert_eq!(partitions[0].partition, 0);
+        assert_eq!(partitions[0].lag, 3);
+        assert_eq!(partitions[1].lag, 0);
+        assert!(partitions.iter().all(|partition| !partition.stalled));
+    }
+
+    #[rstest]
+    #[case::stuck(Some(7), 12, true)]
+    #[case::committing(Some(9), 12, false)]
+    #[case::idle(Some(10), 10, false)]
+    fn it_should_flag_consumers_that_stopped_committing(
+        #[case] committed_later: Option<i64>,
+        #[case] head_later: i64,
+        #[case] stalled: bool,
+    ) {
+        let lag = ConsumerLag::new().with_stall_after_ms(5 * MINUTE_MS);
+        lag.record("billing", vec![offsets(0, 10, Some(7))], 0);
+
+        lag.record(
+            "billing",
+            vec![offsets(0, head_later, committed_later)],
+            3 * MINUTE_MS,
+        );
+
+        assert!(!lag.partitions(3 * MINUTE_MS)[0].stalled);
+        assert_eq!(lag.partitions(6 * MINUTE_MS)[0].stalled, stalled);
+    }
+
+    #[rstest]
+    fn it_should_render_the_prometheus_text_format() {
+        let lag = ConsumerLag::new().with_stall_after_ms(MINUTE_MS);
+        lag.record("jira-\"sync\"", vec![offsets(2, 10, Some(4))], 0);
+
+        assert_eq!(
+            lag.render(MINUTE_MS),
+            [
+                "# HELP consumer_lag_messages Messages a consumer has yet to process.",
+                "# TYPE consumer_lag_messages gauge",
+                r#"consumer_lag_messages{consumer="jira-\"sync\"",topic="time-entries.v1",partition="2"} 6"#,
+                "# HELP consumer_stalled Whether a consumer stopped committing with lag.",
+                "# TYPE consumer_stalled gauge",
+                r#"consumer_stalled{consumer="jira-\"sync\"",topic="time-entries.v1",partition="2"} 1"#,
+                "",
+            ]
+            .join("\n")
+        );
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_keep_the_last_report_of_consumers_that_fail() {
+        let billing = InMemoryConsumer::new("billing");
+        let jira = InMemoryConsumer::new("jira-sync");
+        billing.set_head("time-entries.v1", 0, 5).await;
+        jira.set_head("time-entries.v1", 0, 5).await;
+        let consumers: Vec<Arc<dyn ConsumerOffsets>> =
+            vec![Arc::new(billing.clone()), Arc::new(jira.clone())];
+        let lag = ConsumerLag::new();
+        observe(&consumers, &lag, 1_000).await;
+
+        jira.toggle_offline();
+        billing.commit("time-entries.v1", 0, 5).await;
+        jira.commit("time-entries.v1", 0, 5).await;
+        let failures = observe(&consumers, &lag, 2_000).await;
+
+        assert_eq!(
+            failures,
+            vec![(
+                "jira-sync".to_string(),
+                BrokerError::Unavailable("Broker offline".to_string())
+            )]
+        );
+        let partitions = lag.partitions(2_000);
+        assert_eq!(partitions[0].consumer, "billing");
+        assert_eq!(partitions[0].lag, 0);
+        assert_eq!(partitions[1].consumer, "jira-sync");
+        assert_eq!(partitions[1].lag, 5);
+        assert_eq!(partitions[1].observed_at, 1_000);
+    }
+}
@@ -0,0 +1,413 @@
+// Wire encodings for published outbox rows, chosen per topic.
+//
+// JSON stays the default. Avro and Protobuf serve consumers whose ecosystems require a schema:
+// both encode the envelope below, field for field, without a code generator. The event
+// payload travels inside the envelope as a JSON string, so event schemas can evolve without
+// a new wire schema; consumers that need typed payloads decode it by `event_type` and
+// `event_version`. The message key is the stream id, so a stream stays on one partition.
+
+use crate::shared::infrastructure::intent_outbox::OutboxRow;
+use crate::shared::infrastructure::intent_outbox::compression::ContentEncoding;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+use thiserror::Error;
+
+/// The Avro schema of `PayloadEncoding::Avro` messages (binary encoding, no container).
+pub const AVRO_SCHEMA: &str = r#"{
+  "type": "record",
+  "name": "OutboxEvent",
+  "namespace": "time_entries",
+  "fields": [
+    {"name": "event_type", "type": "string"},
+    {"name": "event_version", "type": "int"},
+    {"name": "stream_id", "type": "string"},
+    {"name": "stream_version", "type": "long"},
+    {"name": "occurred_at", "type": "long"},
+    {"name": "payload_json", "type": "string"}
+  ]
+}"#;
+
+/// The Protobuf schema of `PayloadEncoding::Protobuf` messages.
+pub const PROTOBUF_SCHEMA: &str = r#"syntax = "proto3";
+package time_entries;
+
+message OutboxEvent {
+  string event_type = 1;
+  int32 event_version = 2;
+  string stream_id = 3;
+  int64 stream_version = 4;
+  int64 occurred_at = 5;
+  string payload_json = 6;
+}"#;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PayloadEncoding {
+    #[default]
+    Json,
+    Avro,
+    Protobuf,
+}
+
+impl PayloadEncoding {
+    pub fn content_type(self) -> &'static str {
+        match self {
+            PayloadEncoding::Json => "application/json",
+            PayloadEncoding::Avro => "avro/binary",
+            PayloadEncoding::Protobuf => "application/x-protobuf",
+        }
+    }
+}
+
+impl fmt::Display for PayloadEncoding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            PayloadEncoding::Json => "json",
+            PayloadEncoding::Avro => "avro",
+            PayloadEncoding::Protobuf => "protobuf",
+        })
+    }
+}
+
+#[derive(Debug, Clone, Error, PartialEq, Eq)]
+pub enum EncodingConfigError {
+    #[error("unknown payload encoding {0:?}; expected json, avro or protobuf")]
+    UnknownEncoding(String),
+
+    #[error("topic encoding {0:?} should look like topic=encoding")]
+    Malformed(String),
+}
+
+impl FromStr for PayloadEncoding {
+    type Err = EncodingConfigError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "json" => Ok(PayloadEncoding::Json),
+            "avro" => Ok(PayloadEncoding::Avro),
+            "protobuf" | "proto" => Ok(PayloadEncoding::Protobuf),
+            _ => Err(EncodingConfigError::UnknownEncoding(value.to_string())),
+        }
+    }
+}
+
+/// Which encoding each topic is published in; topics without an entry use the default.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TopicEncodings {
+    default: PayloadEncoding,
+    by_topic: HashMap<String, PayloadEncoding>,
+}
+
+impl TopicEncodings {
+    pub fn new(default: PayloadEncoding) -> Self {
+        Self {
+            default,
+            by_topic: HashMap::new(),
+        }
+    }
+
+    pub fn with_topic(mut self, topic: impl Into<String>, encoding: PayloadEncoding) -> Self {
+        self.by_topic.insert(topic.into(), encoding);
+        self
+    }
+
+    /// Reads a comma separated list such as `time-entries.v1=avro,billing.v1=protobuf`.
+    pub fn parse(default: PayloadEncoding, topics: &str) -> Result<Self, EncodingConfigError> {
+        topics
+            .split(',')
+            .map(str::trim)
+            .filter(|entry| !entry.is_empty())
+            .try_fold(Self::new(default), |encodings, entry| {
+                let (topic, encoding) = entry
+                    .split_once('=')
+                    .filter(|(topic, _)| !topic.trim().is_empty())
+                    .ok_or_else(|| EncodingConfigError::Malformed(entry.to_string()))?;
+                Ok(encodings.with_topic(topic.trim(), encoding.parse()?))
+            })
+    }
+
+    pub fn for_topic(&self, topic: &str) -> PayloadEncoding {
+        self.by_topic.get(topic).copied().unwrap_or(self.default)
+    }
+}
+
+/// A row as it goes over the wire.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EncodedMessage {
+    pub topic: String,
+    pub key: String,
+    pub encoding: PayloadEncoding,
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+}
+
+impl EncodedMessage {
+    /// This message with its body compressed per `encoding`, announced in a
+    /// `content-encoding` header.
+    pub fn compressed(mut self, encoding: ContentEncoding) -> Self {
+        self.body = encoding.compress(&self.body);
+        self.headers
+            .push(("content-encoding".to_string(), encoding.to_string()));
+        self
+    }
+}
+
+#[derive(Serialize)]
+struct JsonEnvelope<'a> {
+    event_type: &'a str,
+    event_version: i32,
+    stream_id: &'a str,
+    stream_version: i64,
+    occurred_at: i64,
+    payload: &'a serde_json::Value,
+}
+
+pub fn encode(topic: &str, row: &OutboxRow, encoding: PayloadEncoding) -> EncodedMessage {
+    let body = match encoding {
+        PayloadEncoding::Json => serde_json::to_vec(&JsonEnvelope {
+            event_type: &row.event_type,
+            event_version: row.event_version,
+            stream_id: &row.stream_id,
+            stream_version: row.stream_version,
+            occurred_at: row.occurred_at,
+            payload: &row.payload,
+        })
+        .expect("a JSON value always serializes"),
+        PayloadEncoding::Avro => encode_avro(row),
+        PayloadEncoding::Protobuf => encode_protobuf(row),
+    };
+    EncodedMessage {
+        topic: topic.to_string(),
+        key: row.stream_id.clone(),
+        encoding,
+        headers: vec![(
+            "content-type".to_string(),
+            encoding.content_type().to_string(),
+        )],
+        body,
+    }
+}
+
+/// Appends `value` as a base 128 varint, least significant group first.
+fn put_varint(buffer: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        buffer.push((value as u8 & 0x7f) | 0x80);
+        value >>= 7;
+    }
+    buffer.push(value as u8);
+}
+
+/// Maps signed to unsigned so small magnitudes of either sign stay short: 0, -1, 1, -2, ...
+fn zigzag(value: i64) -> u64 {
+    ((value << 1) ^ (value >> 63)) as u64
+}
+
+fn encode_avro(row: &OutboxRow) -> Vec<u8> {
+    fn put_long(buffer: &mut Vec<u8>, value: i64) {
+        put_varint(buffer, zigzag(value));
+    }
+    fn put_string(buffer: &mut Vec<u8>, value: &str) {
+        put_long(buffer, value.len() as i64);
+        buffer.extend_from_slice(value.as_bytes());
+    }
+    let mut buffer = Vec::new();
+    put_string(&mut buffer, &row.event_type);
+    put_long(&mut buffer, i64::from(row.event_version));
+    put_string(&mut buffer, &row.stream_id);
+    put_long(&mut buffer, row.stream_version);
+    put_long(&mut buffer, row.occurred_at);
+    put_string(&mut buffer, &row.payload.to_string());
+    buffer
+}
+
+/// Fields holding their type's default are left out, as proto3 encoders do.
+fn encode_protobuf(row: &OutboxRow) -> Vec<u8> {
+    const VARINT: u64 = 0;
+    const LENGTH_DELIMITED: u64 = 2;
+    fn put_int(buffer: &mut Vec<u8>, field: u64, value: i64) {
+        if value != 0 {
+            put_varint(buffer, (field << 3) | VARINT);
+            put_varint(buffer, value as u64);
+        }
+    }
+    fn put_string(buffer: &mut Vec<u8>, field: u64, value: &str) {
+        if !value.is_empty() {
+            put_varint(buffer, (field << 3) | LENGTH_DELIMITED);
+            put_varint(buffer, value.len() as u64);
+            buffer.extend_from_slice(value.as_bytes());
+        }
+    }
+    let mut buffer = Vec::new();
+    put_string(&mut buffer, 1, &row.event_type);
+    put_int(&mut buffer, 2, i64::from(row.event_version));
+    put_string(&mut buffer, 3, &row.stream_id);
+    put_int(&mut buffer, 4, row.stream_version);
+    put_int(&mut buffer, 5, row.occurred_at);
+    put_string(&mut buffer, 6, &row.payload.to_string());
+    buffer
+}
+
+#[cfg(test)]
+mod encoding_tests {
+    use super::*;
+    use crate::shared::infrastructure::intent_outbox::OutboxStatus;
+    use rstest::rstest;
+    use serde_json::json;
+
+    fn row() -> OutboxRow {
+        OutboxRow {
+            topic: "time-entries.v1".to_string(),
+            event_type: "A".to_string(),
+            event_version: 1,
+            stream_id: "s".to_string(),
+            stream_version: 2,
+            intent_no: 0,
+            occurred_at: 3,
+            payload: serde_json::Value::Null,
+            content_encoding: None,
+            status: OutboxStatus::Pending,
+            attempts: 0,
+            last_error: None,
+            published_at: None,
+        }
+    }
+
+    #[rstest]
+    #[case(0, &[0x00])]
+    #[case(1, &[0x01])]
+    #[case(127, &[0x7f])]
+    #[case(300, &[0xac, 0x02])]
+    #[case(u64::MAX, &[0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x01])]
+    fn it_should_write_base_128_varints(#[case] value: u64, #[case] expected: &[u8]) {
+        let mut buffer = Vec::new();
+        put_varint(&mut buffer, value);
+        assert_eq!(buffer, expected);
+    }
+
+    #[rstest]
+    #[case(0, 0)]
+    #[case(-1, 1)]
+    #[case(1, 2)]
+    #[case(-2, 3)]
+    #[case(i64::MAX, u64::MAX - 1)]
+    #[case(i64::MIN, u64::MAX)]
+    fn it_should_zigzag_signed_values(#[case] value: i64, #[case] expected: u64) {
+        assert_eq!(zigzag(value), expected);
+    }
+
+    #[test]
+    fn it_should_encode_the_avro_record_in_schema_order() {
+        let message = encode("time-entries.v1", &row(), PayloadEncoding::Avro);
+        assert_eq!(
+            message.body,
+            [
+                &[0x02, b'A', 0x02, 0x02, b's', 0x04, 0x06, 0x08][..],
+                b"null"
+            ]
+            .concat()
+        );
+    }
+
+    #[test]
+    fn it_should_encode_tagged_protobuf_fields() {
+        let message = encode("time-entries.v1", &row(), PayloadEncoding::Protobuf);
+        assert_eq!(
+            message.body,
+            [
+                &[
+                    0x0a, 0x01, b'A', 0x10, 0x01, 0x1a, 0x01, b's', 0x20, 0x02, 0x28, 0x03, 0x32,
+                    0x04
+                ][..],
+                b"null"
+            ]
+            .concat()
+        );
+    }
+
+    #[test]
+    fn it_should_leave_out_protobuf_fields_holding_defaults() {
+        let message = encode(
+            "time-entries.v1",
+            &OutboxRow {
+                event_version: 0,
+                stream_version: 0,
+                occurred_at: 0,
+                ..row()
+            },
+            PayloadEncoding::Protobuf,
+        );
+        assert_eq!(
+            message.body,
+            [
+                &[0x0a, 0x01, b'A', 0x1a, 0x01, b's', 0x32, 0x04][..],
+                b"null"
+            ]
+            .concat()
+        );
+    }
+
+    #[test]
+    fn it_should_wrap_the_payload_in_a_json_envelope() {
+        let message = encode(
+            "time-entries.v1",
+            &OutboxRow {
+                payload: json!({ "minutes": 5 }),
+                ..row()
+            },
+            PayloadEncoding::Json,
+        );
+        let body: serde_json::Value = serde_json::from_slice(&message.body).unwrap();
+        assert_eq!(
+            body,
+            json!({
+                "event_type": "A",
+                "event_version": 1,
+                "stream_id": "s",
+                "stream_version": 2,
+                "occurred_at": 3,
+                "payload": { "minutes": 5 },
+            })
+        );
+        assert_eq!(message.key, "s");
+        assert_eq!(message.encoding.content_type(), "application/json");
+    }
+
+    #[test]
+    fn it_should_pick_the_encoding_per_topic() {
+        let encodings = TopicEncodings::parse(
+            PayloadEncoding::Json,
+            " time-entries.v1=avro, billing.v1 = Protobuf ,",
+        )
+        .unwrap();
+
+        assert_eq!(
+            encodings.for_topic("time-entries.v1"),
+            PayloadEncoding::Avro
+        );
+        assert_eq!(encodings.for_topic("billing.v1"), PayloadEncoding::Protobuf);
+        assert_eq!(encodings.for_topic("tags.v1"), PayloadEncoding::Json);
+    }
+
+    #[rstest]
+    #[case("time-entries.v1=xml", EncodingConfigError::UnknownEncoding("xml".to_string()))]
+    #[case("time-entries.v1", EncodingConfigError::Malformed("time-entries.v1".to_string()))]
+    #[case("=avro", EncodingConfigError::Malformed("=avro".to_string()))]
+    fn it_should_reject_malformed_topic_encodings(
+        #[case] topics: &str,
+        #[case] expected: EncodingConfigError,
+    ) {
+        assert_eq!(
+            TopicEncodings::parse(PayloadEncoding::Json, topics),
+            Err(expected)
+        );
+    }
+
+    #[rstest]
+    #[case(PayloadEncoding::Json)]
+    #[case(PayloadEncoding::Avro)]
+    #[case(PayloadEncoding::Protobuf)]
+    fn it_should_parse_its_own_display_form(#[case] encoding: PayloadEncoding) {
+        assert_eq!(encoding.to_string().parse(), Ok(encoding));
+    }
+}
@@ -0,0 +1,315 @@
+use crate::shared::infrastructure::intent_outbox::OutboxRow;
+use crate::shared::infrastructure::message_broker::cloud_events::CloudEventsConfig;
+use crate::shared::infrastructure::message_broker::consumer_lag::{
+    ConsumerOffsets, PartitionOffsets,
+};
+use crate::shared::infrastructure::message_broker::encoding::{
+    EncodedMessage, TopicEncodings, encode,
+};
+use crate::shared::infrastructure::message_broker::{BrokerError, MessageBroker};
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::time::Duration;
+use tokio::sync::Mutex;
+
+#[derive(Default)]
+struct Inner {
+    published: Mutex<Vec<(String, OutboxRow)>>,
+    messages: Mutex<Vec<EncodedMessage>>,
+    is_offline: AtomicBool,
+    delay_publish_ms: AtomicU64,
+}
+
+#[derive(Clone, Default)]
+pub struct InMemoryMessageBroker {
+    inner: Arc<Inner>,
+    encodings: TopicEncodings,
+    cloud_events: Option<CloudEventsConfig>,
+}
+
+impl InMemoryMessageBroker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Encodes each topic's messages as `encodings` says; JSON for all of them otherwise.
+    pub fn with_encodings(mut self, encodings: TopicEncodings) -> Self {
+        self.encodings = encodings;
+        self
+    }
+
+    /// Wraps every message in a CloudEvents envelope naming `cloud_events.source`.
+    pub fn with_cloud_events(mut self, cloud_events: CloudEventsConfig) -> Self {
+        self.cloud_events = Some(cloud_events);
+        self
+    }
+
+    pub fn toggle_offline(&self) {
+        self.inner.is_offline.fetch_xor(true, Ordering::SeqCst);
+    }
+
+    /// Simulates broker latency for every publish call.
+    pub fn set_delay_publish_ms(&self, ms: u64) {
+        self.inner.delay_publish_ms.store(ms, Ordering::SeqCst);
+    }
+
+    /// `(topic, row)` pairs in publish order.
+    pub async fn published(&self) -> Vec<(String, OutboxRow)> {
+        self.inner.published.lock().await.clone()
+    }
+
+    /// The published rows as they went over the wire, in publish order.
+    pub async fn messages(&self) -> Vec<EncodedMessage> {
+        self.inner.messages.lock().await.clone()
+    }
+}
+
+#[async_trait::async_trait]
+impl MessageBroker for InMemoryMessageBroker {
+    async fn publish(&self, topic: &str, rows: &[OutboxRow]) -> Result<(), BrokerError> {
+        let ms = self.inner.delay_publish_ms.load(Ordering::SeqCst);
+        if ms > 0 {
+            tokio::time::sleep(Duration::from_millis(ms)).await;
+        }
+        if self.inner.is_offline.load(Ordering::SeqCst) {
+            return Err(BrokerError::Unavailable("Broker offline".to_string()));
+        }
+        let encoding = self.encodings.for_topic(topic);
+        let messages = rows
+            .iter()
+            .map(|row| {
+                let decoded = row.decompressed()?;
+                let message = encode(topic, &decoded, encoding);
+                let message = match &self.cloud_events {
+                    Some(cloud_events) => cloud_events.wrap(&decoded, message),
+                    None => message,
+                };
+                Ok(match row.content_encoding {
+                    Some(content_encoding) => message.compressed(content_encoding),
+                    None => message,
+                })
+            })
+            .collect::<Result<Vec<_>, BrokerError>>()?;
+        self.inner.messages.lock().await.extend(messages);
+        self.inner
+            .published
+            .lock()
+            .await
+            .extend(rows.iter().map(|row| (topic.to_string(), row.clone())));
+        Ok(())
+    }
+}
+
+#[derive(Default)]
+struct ConsumerInner {
+    offsets: Mutex<BTreeMap<(String, u32), PartitionOffsets>>,
+    is_offline: AtomicBool,
+}
+
+/// A consumer group whose offsets tests and demos move by hand.
+#[derive(Clone)]
+pub struct InMemoryConsumer {
+    consumer: String,
+    inner: Arc<ConsumerInner>,
+}
+
+impl InMemoryConsumer {
+    pub fn new(consumer: impl Into<String>) -> Self {
+        Self {
+            consumer: consumer.into(),
+            inner: Arc::default(),
+        }
+    }
+
+    pub fn toggle_offline(&self) {
+        self.inner.is_offline.fetch_xor(true, Ordering::SeqCst);
+    }
+
+    async fn update(
+        &self,
+        topic: &str,
+        partition: u32,
+        update: impl FnOnce(&mut PartitionOffsets),
+    ) {
+        let mut offsets = self.inner.offsets.lock().await;
+        update(
+            offsets
+                .entry((topic.to_string(), partition))
+                .or_insert_with(|| PartitionOffsets {
+                    topic: topic.to_string(),
+                    partition,
+                    head_offset: 0,
+                    committed_offset: None,
+                }),
+        );
+    }
+
+    /// Messages were published up to `head_offset`.
+    pub async fn set_head(&self, topic: &str, partition: u32, head_offset: i64) {
+        self.update(topic, partition, |offsets| {
+            offsets.head_offset = head_offset
+        })
+        .await;
+    }
+
+    /// The group processed every message before `offset`.
+    pub async fn commit(&self, topic: &str, partition: u32, offset: i64) {
+        self.update(topic, partition, |offsets| {
+            offsets.committed_offset = Some(offset)
+        })
+        .await;
+    }
+}
+
+#[async_trait::async_trait]
+impl ConsumerOffsets for InMemoryConsumer {
+    fn consumer(&self) -> &str {
+        &self.consumer
+    }
+
+    async fn offsets(&self) -> Result<Vec<PartitionOffsets>, BrokerError> {
+        if self.inner.is_offline.load(Ordering::SeqCst) {
+            return Err(BrokerError::Unavailable("Broker offline".to_string()));
+        }
+        Ok(self.inner.offsets.lock().await.values().cloned().collect())
+    }
+}
+
+#[cfg(test)]
+mod in_memory_message_broker_tests {
+    use super::*;
+    use crate::shared::infrastructure::intent_outbox::OutboxStatus;
+    use crate::shared::infrastructure::intent_outbox::compression::{
+        ContentEncoding, PayloadCompression,
+    };
+    use crate::shared::infrastructure::message_broker::encoding::PayloadEncoding;
+    use rstest::rstest;
+
+    fn row(stream_version: i64) -> OutboxRow {
+        OutboxRow {
+            topic: "time-entries.v1".to_string(),
+            event_type: "TimeEntryRegistered".to_string(),
+            event_version: 1,
+            stream_id: "TimeEntry-te-1".to_string(),
+            stream_version,
+            intent_no: 0,
+            occurred_at: 0,
+            payload: serde_json::Value::Null,
+            content_encoding: None,
+            status: OutboxStatus::Pending,
+            attempts: 0,
+            last_error: None,
+            published_at: None,
+        }
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_record_published_rows_in_order() {
+        let broker = InMemoryMessageBroker::new();
+        broker.set_delay_publish_ms(1);
+
+        broker
+            .publish("time-entries.v1", &[row(1), row(2)])
+            .await
+            .unwrap();
+
+        let published = broker.published().await;
+        assert_eq!(published.len(), 2);
+        assert_eq!(published[0].0, "time-entries.v1");
+        assert_eq!(published[1].1.stream_version, 2);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_encode_messages_as_configured_for_their_topic() {
+        let broker = InMemoryMessageBroker::new().with_encodings(
+            TopicEncodings::new(PayloadEncoding::Json)
+                .with_topic("time-entries.v1", PayloadEncoding::Avro),
+        );
+
+        broker.publish("time-entries.v1", &[row(1)]).await.unwrap();
+        broker.publish("tags.v1", &[row(2)]).await.unwrap();
+
+        let messages = broker.messages().await;
+        assert_eq!(messages.len(), 2);
+        assert_eq!(messages[0].encoding, PayloadEncoding::Avro);
+        assert_eq!(messages[0].key, "TimeEntry-te-1");
+        assert_eq!(messages[1].topic, "tags.v1");
+        assert_eq!(messages[1].encoding, PayloadEncoding::Json);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_wrap_messages_in_cloud_events_when_configured() {
+        let broker = InMemoryMessageBroker::new()
+            .with_cloud_events(CloudEventsConfig::new("urn:time-entries"));
+
+        broker.publish("time-entries.v1", &[row(3)]).await.unwrap();
+
+        let messages = broker.messages().await;
+        let body: serde_json::Value = serde_json::from_slice(&messages[0].body).unwrap();
+        assert_eq!(body["id"], "TimeEntry-te-1:3");
+        assert_eq!(body["source"], "urn:time-entries");
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_publish_compressed_rows_in_compressed_bodies() {
+        let broker = InMemoryMessageBroker::new();
+        let payload = serde_json::json!({ "description": "standup notes ".repeat(50) });
+        let compression = PayloadCompression::new(ContentEncoding::Zstd).with_threshold_bytes(100);
+        let compressed = compression.compress(OutboxRow {
+            payload: payload.clone(),
+            ..row(1)
+        });
+
+        broker
+            .publish("time-entries.v1", &[compressed])
+            .await
+            .unwrap();
+
+        let message = &broker.messages().await[0];
+        assert!(
+            message
+                .headers
+                .contains(&("content-encoding".to_string(), "zstd".to_string()))
+        );
+        let body: serde_json::Value =
+            serde_json::from_slice(&ContentEncoding::Zstd.decompress(&message.body).unwrap())
+                .unwrap();
+        assert_eq!(body["payload"], payload);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_refuse_rows_whose_payload_does_not_decode() {
+        let broker = InMemoryMessageBroker::new();
+        let corrupt = OutboxRow {
+            payload: serde_json::Value::Null,
+            content_encoding: Some(ContentEncoding::Gzip),
+            ..row(1)
+        };
+
+        let result = broker.publish("time-entries.v1", &[corrupt]).await;
+
+        assert!(matches!(result, Err(BrokerError::Payload(_))));
+        assert!(broker.published().await.is_empty());
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_fail_when_offline() {
+        let broker = InMemoryMessageBroker::new();
+        broker.toggle_offline();
+
+        let result = broker.publish("time-entries.v1", &[row(1)]).await;
+
+        assert_eq!(
+            result,
+            Err(BrokerError::Unavailable("Broker offline".to_string()))
+        );
+        assert!(broker.published().await.is_empty());
+    }
+}
@@ -0,0 +1,30 @@
+use crate::shared::infrastructure::intent_handlers::IntentHandlerError;
+use crate::shared::infrastructure::intent_outbox::OutboxRow;
+use crate::shared::infrastructure::intent_outbox::compression::CompressionError;
+use async_trait::async_trait;
+use thiserror::Error;
+
+#[derive(Debug, Clone, Error, PartialEq, Eq)]
+pub enum BrokerError {
+    #[error("broker unavailable: {0}")]
+    Unavailable(String),
+
+    #[error(transparent)]
+    Payload(#[from] CompressionError),
+
+    #[error(transparent)]
+    Handler(#[from] IntentHandlerError),
+}
+
+/// Outbound message broker (Pulsar, Kafka). A batch is published in order; on error the
+/// relay resends the whole batch, so consumers deduplicate on `OutboxRow::dedupe_key`. Rows
+/// with a `content_encoding` are published decoded, in a body compressed the same way.
+#[async_trait]
+pub trait MessageBroker: Send + Sync {
+    async fn publish(&self, topic: &str, rows: &[OutboxRow]) -> Result<(), BrokerError>;
+}
+
+pub mod cloud_events;
+pub mod consumer_lag;
+pub mod encoding;
+pub mod in_memory;
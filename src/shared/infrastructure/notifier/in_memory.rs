@@ -0,0 +1,40 @@
+use crate::shared::infrastructure::notifier::{Alert, Notifier, NotifierError};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+
+#[derive(Default)]
+struct Inner {
+    alerts: Mutex<Vec<Alert>>,
+    is_offline: AtomicBool,
+}
+
+/// Keeps every alert it was sent, oldest first.
+#[derive(Clone, Default)]
+pub struct InMemoryNotifier {
+    inner: Arc<Inner>,
+}
+
+impl InMemoryNotifier {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn alerts(&self) -> Vec<Alert> {
+        self.inner.alerts.lock().unwrap().clone()
+    }
+
+    pub fn toggle_offline(&self) {
+        self.inner.is_offline.fetch_xor(true, Ordering::SeqCst);
+    }
+}
+
+#[async_trait::async_trait]
+impl Notifier for InMemoryNotifier {
+    async fn notify(&self, alert: &Alert) -> Result<(), NotifierError> {
+        if self.inner.is_offline.load(Ordering::SeqCst) {
+            return Err(NotifierError::Backend("Notifier offline".to_string()));
+        }
+        self.inner.alerts.lock().unwrap().push(alert.clone());
+        Ok(())
+    }
+}
@@ -0,0 +1,16 @@
+use crate::shared::infrastructure::notifier::{Alert, AlertStatus, Notifier, NotifierError};
+
+/// Writes alerts to the log, as warnings while firing; for deployments without a channel.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LogNotifier;
+
+#[async_trait::async_trait]
+impl Notifier for LogNotifier {
+    async fn notify(&self, alert: &Alert) -> Result<(), NotifierError> {
+        match alert.status {
+            AlertStatus::Firing => tracing::warn!(key = alert.key, "{}", alert.summary),
+            AlertStatus::Resolved => tracing::info!(key = alert.key, "{}", alert.summary),
+        }
+        Ok(())
+    }
+}
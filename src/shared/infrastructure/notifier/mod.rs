@@ -0,0 +1,37 @@
+use async_trait::async_trait;
+use serde::Serialize;
+use thiserror::Error;
+
+#[derive(Debug, Clone, Error, PartialEq, Eq)]
+pub enum NotifierError {
+    #[error("backend error: {0}")]
+    Backend(String),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AlertStatus {
+    Firing,
+    Resolved,
+}
+
+/// Something operators should look at, or the all-clear for it. Alerts about the same
+/// problem share their `key`, so a receiver can pair a resolution with what it resolves.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Alert {
+    pub key: String,
+    pub status: AlertStatus,
+    pub summary: String,
+    /// Epoch milliseconds.
+    pub raised_at: i64,
+}
+
+/// Where operators are alerted (a Slack channel, an incident webhook, the log).
+#[async_trait]
+pub trait Notifier: Send + Sync {
+    async fn notify(&self, alert: &Alert) -> Result<(), NotifierError>;
+}
+
+pub mod in_memory;
+pub mod log;
+pub mod webhook;
@@ -0,0 +1,167 @@
+use crate::shared::infrastructure::calendar::http::HttpResponse;
+use crate::shared::infrastructure::notifier::{Alert, AlertStatus, Notifier, NotifierError};
+use async_trait::async_trait;
+use serde_json::json;
+
+/// The transport the webhook notifier uses, so it stays independent of an HTTP client crate.
+/// Implementations send `body` with `Content-Type: application/json`.
+#[async_trait]
+pub trait HttpPost: Send + Sync {
+    async fn post(&self, url: &str, body: &str) -> Result<HttpResponse, String>;
+}
+
+/// What the webhook is sent.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum WebhookFormat {
+    /// The alert as JSON: `{"key", "status", "summary", "raised_at"}`.
+    #[default]
+    Json,
+    /// A Slack incoming webhook message: `{"text"}`.
+    Slack,
+}
+
+/// Posts every alert to `url`; any status but 2xx fails the notification.
+#[derive(Clone)]
+pub struct WebhookNotifier<TClient> {
+    url: String,
+    format: WebhookFormat,
+    client: TClient,
+}
+
+impl<TClient: HttpPost> WebhookNotifier<TClient> {
+    pub fn new(url: impl Into<String>, client: TClient) -> Self {
+        Self {
+            url: url.into(),
+            format: WebhookFormat::default(),
+            client,
+        }
+    }
+
+    pub fn with_format(mut self, format: WebhookFormat) -> Self {
+        self.format = format;
+        self
+    }
+
+    fn body(&self, alert: &Alert) -> String {
+        match self.format {
+            WebhookFormat::Json => serde_json::to_string(alert).expect("alerts serialize to JSON"),
+            WebhookFormat::Slack => {
+                let icon = match alert.status {
+                    AlertStatus::Firing => ":rotating_light:",
+                    AlertStatus::Resolved => ":white_check_mark:",
+                };
+                json!({ "text": format!("{icon} {}", alert.summary) }).to_string()
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl<TClient: HttpPost> Notifier for WebhookNotifier<TClient> {
+    async fn notify(&self, alert: &Alert) -> Result<(), NotifierError> {
+        let response = self
+            .client
+            .post(&self.url, &self.body(alert))
+            .await
+            .map_err(NotifierError::Backend)?;
+        if !(200..300).contains(&response.status) {
+            return Err(NotifierError::Backend(format!(
+                "POST {} returned {}",
+                self.url, response.status
+            )));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod webhook_notifier_tests {
+    use super::*;
+    use rstest::rstest;
+    use std::sync::Mutex;
+
+    struct Recording {
+        status: u16,
+        posted: Mutex<Vec<(String, String)>>,
+    }
+
+    #[async_trait]
+    impl HttpPost for &Recording {
+        async fn post(&self, url: &str, body: &str) -> Result<HttpResponse, String> {
+            self.posted
+                .lock()
+                .unwrap()
+                .push((url.to_string(), body.to_string()));
+            Ok(HttpResponse {
+                status: self.status,
+                body: String::new(),
+            })
+        }
+    }
+
+    fn alert(status: AlertStatus) -> Alert {
+        Alert {
+            key: "outbox".to_string(),
+            status,
+            summary: "outbox stalled".to_string(),
+            raised_at: 1_000,
+        }
+    }
+
+    #[rstest]
+    #[case::json(
+        WebhookFormat::Json,
+        AlertStatus::Firing,
+        r#"{"key":"outbox","status":"firing","summary":"outbox stalled","raised_at":1000}"#
+    )]
+    #[case::slack_firing(
+        WebhookFormat::Slack,
+        AlertStatus::Firing,
+        r#"{"text":":rotating_light: outbox stalled"}"#
+    )]
+    #[case::slack_resolved(
+        WebhookFormat::Slack,
+        AlertStatus::Resolved,
+        r#"{"text":":white_check_mark: outbox stalled"}"#
+    )]
+    #[tokio::test]
+    async fn it_should_post_the_alert_in_the_format(
+        #[case] format: WebhookFormat,
+        #[case] status: AlertStatus,
+        #[case] expected: &str,
+    ) {
+        let client = Recording {
+            status: 200,
+            posted: Mutex::default(),
+        };
+        let notifier =
+            WebhookNotifier::new("https://hooks.example/alerts", &client).with_format(format);
+
+        notifier.notify(&alert(status)).await.unwrap();
+
+        assert_eq!(
+            client.posted.into_inner().unwrap(),
+            vec![(
+                "https://hooks.example/alerts".to_string(),
+                expected.to_string()
+            )]
+        );
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_fail_on_an_error_status() {
+        let client = Recording {
+            status: 500,
+            posted: Mutex::default(),
+        };
+        let notifier = WebhookNotifier::new("https://hooks.example/alerts", &client);
+
+        assert_eq!(
+            notifier.notify(&alert(AlertStatus::Firing)).await,
+            Err(NotifierError::Backend(
+                "POST https://hooks.example/alerts returned 500".to_string()
+            ))
+        );
+    }
+}
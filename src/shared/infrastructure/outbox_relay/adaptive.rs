@@ -0,0 +1,227 @@
+// AIMD (additive increase, multiplicative decrease) control of relay batch size and poll
+// interval.
+//
+// A full batch published within the latency target means the broker keeps up and there is a
+// backlog: grow the batch by a fixed step and poll again at the minimum interval. A slow
+// publish or an error halves the batch so a struggling broker sheds load quickly. An empty
+// poll doubles the interval up to the maximum, so a quiet outbox is polled rarely; errors back
+// off the interval the same way.
+
+use std::time::Duration;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct AdaptiveBatchConfig {
+    pub min_batch: usize,
+    pub max_batch: usize,
+    pub additive_step: usize,
+    /// Applied to the batch size on a slow publish or an error; between 0 and 1.
+    pub decrease_factor: f64,
+    pub target_latency: Duration,
+    pub min_interval: Duration,
+    pub max_interval: Duration,
+}
+
+impl Default for AdaptiveBatchConfig {
+    fn default() -> Self {
+        Self {
+            min_batch: 1,
+            max_batch: 500,
+            additive_step: 10,
+            decrease_factor: 0.5,
+            target_latency: Duration::from_millis(200),
+            min_interval: Duration::from_millis(50),
+            max_interval: Duration::from_secs(5),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct AdaptiveBatch {
+    config: AdaptiveBatchConfig,
+    min_batch: usize,
+    max_batch: usize,
+    batch_size: usize,
+    poll_interval: Duration,
+}
+
+impl AdaptiveBatch {
+    /// Starts at the minimum batch size and interval and grows from there.
+    pub fn new(config: AdaptiveBatchConfig) -> Self {
+        let min_batch = config.min_batch.max(1);
+        Self {
+            min_batch,
+            max_batch: config.max_batch.max(min_batch),
+            batch_size: min_batch,
+            poll_interval: config.min_interval,
+            config,
+        }
+    }
+
+    pub fn batch_size(&self) -> usize {
+        self.batch_size
+    }
+
+    pub fn poll_interval(&self) -> Duration {
+        self.poll_interval
+    }
+
+    /// Switches to `config` without starting over: the current batch size and poll interval
+    /// are kept, clamped to the new bounds.
+    pub fn reconfigure(&mut self, config: AdaptiveBatchConfig) {
+        self.min_batch = config.min_batch.max(1);
+        self.max_batch = config.max_batch.max(self.min_batch);
+        self.batch_size = self.batch_size.clamp(self.min_batch, self.max_batch);
+        self.poll_interval = self
+            .poll_interval
+            .max(config.min_interval)
+            .min(config.max_interval);
+        self.config = config;
+    }
+
+    pub fn on_published(&mut self, delivered: usize, latency: Duration) {
+        if delivered == 0 {
+            self.back_off_interval();
+            return;
+        }
+        let was_full = delivered >= self.batch_size;
+        if latency > self.config.target_latency {
+            self.decrease_batch();
+        } else if was_full {
+            self.batch_size = (self.batch_size + self.config.additive_step).min(self.max_batch);
+        }
+        self.poll_interval = self.config.min_interval;
+    }
+
+    pub fn on_failure(&mut self) {
+        self.decrease_batch();
+        self.back_off_interval();
+    }
+
+    fn decrease_batch(&mut self) {
+        let decreased = (self.batch_size as f64 * self.config.decrease_factor) as usize;
+        self.batch_size = decreased.max(self.min_batch);
+    }
+
+    fn back_off_interval(&mut self) {
+        self.poll_interval = (self.poll_interval * 2)
+            .max(self.config.min_interval)
+            .min(self.config.max_interval);
+    }
+}
+
+#[cfg(test)]
+mod adaptive_batch_tests {
+    use super::*;
+    use rstest::rstest;
+
+    fn config() -> AdaptiveBatchConfig {
+        AdaptiveBatchConfig {
+            min_batch: 2,
+            max_batch: 25,
+            additive_step: 10,
+            decrease_factor: 0.5,
+            target_latency: Duration::from_millis(100),
+            min_interval: Duration::from_millis(10),
+            max_interval: Duration::from_millis(35),
+        }
+    }
+
+    const FAST: Duration = Duration::from_millis(5);
+    const SLOW: Duration = Duration::from_millis(500);
+
+    #[rstest]
+    fn it_should_grow_additively_while_full_batches_are_fast() {
+        let mut batch = AdaptiveBatch::new(config());
+        assert_eq!(batch.batch_size(), 2);
+
+        batch.on_published(2, FAST);
+        assert_eq!(batch.batch_size(), 12);
+        batch.on_published(12, FAST);
+        batch.on_published(22, FAST);
+        assert_eq!(batch.batch_size(), 25);
+    }
+
+    #[rstest]
+    fn it_should_hold_the_batch_size_when_the_backlog_is_drained() {
+        let mut batch = AdaptiveBatch::new(config());
+        batch.on_published(2, FAST);
+
+        batch.on_published(3, FAST);
+
+        assert_eq!(batch.batch_size(), 12);
+        assert_eq!(batch.poll_interval(), Duration::from_millis(10));
+    }
+
+    #[rstest]
+    fn it_should_halve_the_batch_on_slow_publishes_down_to_the_minimum() {
+        let mut batch = AdaptiveBatch::new(config());
+        batch.on_published(2, FAST);
+        batch.on_published(12, FAST);
+
+        batch.on_published(22, SLOW);
+        assert_eq!(batch.batch_size(), 11);
+        batch.on_published(11, SLOW);
+        batch.on_published(5, SLOW);
+        batch.on_published(2, SLOW);
+        assert_eq!(batch.batch_size(), 2);
+    }
+
+    #[rstest]
+    fn it_should_back_off_the_interval_when_idle_and_reset_on_work() {
+        let mut batch = AdaptiveBatch::new(config());
+
+        batch.on_published(0, Duration::ZERO);
+        assert_eq!(batch.poll_interval(), Duration::from_millis(20));
+        batch.on_published(0, Duration::ZERO);
+        batch.on_published(0, Duration::ZERO);
+        assert_eq!(batch.poll_interval(), Duration::from_millis(35));
+
+        batch.on_published(1, FAST);
+        assert_eq!(batch.poll_interval(), Duration::from_millis(10));
+    }
+
+    #[rstest]
+    fn it_should_decrease_and_back_off_on_failures() {
+        let mut batch = AdaptiveBatch::new(config());
+        batch.on_published(2, FAST);
+
+        batch.on_failure();
+
+        assert_eq!(batch.batch_size(), 6);
+        assert_eq!(batch.poll_interval(), Duration::from_millis(20));
+    }
+
+    #[rstest]
+    fn it_should_clamp_the_current_rate_to_a_new_config() {
+        let mut batch = AdaptiveBatch::new(config());
+        batch.on_published(2, FAST);
+        batch.on_published(0, Duration::ZERO);
+
+        batch.reconfigure(AdaptiveBatchConfig {
+            max_batch: 5,
+            min_interval: Duration::from_millis(40),
+            max_interval: Duration::from_millis(80),
+            ..config()
+        });
+        assert_eq!(batch.batch_size(), 5);
+        assert_eq!(batch.poll_interval(), Duration::from_millis(40));
+
+        batch.on_published(5, FAST);
+        assert_eq!(batch.batch_size(), 5);
+    }
+
+    #[rstest]
+    fn it_should_keep_a_batch_of_at_least_one() {
+        let mut batch = AdaptiveBatch::new(AdaptiveBatchConfig {
+            min_batch: 0,
+            max_batch: 0,
+            ..AdaptiveBatchConfig::default()
+        });
+        assert_eq!(batch.batch_size(), 1);
+
+        batch.on_published(1, Duration::ZERO);
+        batch.on_failure();
+
+        assert_eq!(batch.batch_size(), 1);
+    }
+}
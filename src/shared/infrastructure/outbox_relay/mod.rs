@@ -0,0 +1,501 @@
+// Outbox relay: moves undelivered rows of one topic from the outbox to the message broker.
+//
+// Delivery is at-least-once. Rows are marked published only after the broker accepted them,
+// so a crash or failed ack resends the batch; a batch the broker rejects is marked failed with
+// the error and claimed again on a later run. Batch size and poll interval adapt to broker
+// latency and errors (see `adaptive`); the current values are exposed through `RelayMetrics`.
+// Each row is published on the broker topic `routing` picks for its event type, by default
+// the outbox topic itself. A batch spanning several topics is marked failed as a whole when
+// any of them rejects it, so rows already accepted on another topic are sent again.
+// A relay given config updates picks up a new `AdaptiveBatchConfig` before its next run.
+
+use crate::shared::infrastructure::control_store::PauseSwitch;
+use crate::shared::infrastructure::intent_outbox::{OutboxError, OutboxRelaySource};
+use crate::shared::infrastructure::message_broker::{BrokerError, MessageBroker};
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+use thiserror::Error;
+use tokio::sync::watch;
+
+use self::adaptive::{AdaptiveBatch, AdaptiveBatchConfig};
+use self::routing::TopicRoutes;
+
+#[derive(Debug, Error)]
+pub enum RelayError {
+    #[error(transparent)]
+    Outbox(#[from] OutboxError),
+
+    #[error(transparent)]
+    Broker(#[from] BrokerError),
+}
+
+#[derive(Debug, Default)]
+struct RelayMetricsInner {
+    batch_size: AtomicU64,
+    poll_interval_ms: AtomicU64,
+    last_latency_ms: AtomicU64,
+    delivered: AtomicU64,
+    failures: AtomicU64,
+    /// Rows delivered per minute, over the last `RATE_WINDOW_MS`.
+    delivered_per_minute: Mutex<BTreeMap<i64, u64>>,
+}
+
+const MINUTE_MS: i64 = 60 * 1000;
+
+/// The window the publish rate is averaged over.
+pub const RATE_WINDOW_MS: i64 = 5 * MINUTE_MS;
+
+/// Current relay rate and totals; clones observe the same values.
+#[derive(Debug, Clone, Default)]
+pub struct RelayMetrics {
+    inner: Arc<RelayMetricsInner>,
+}
+
+impl RelayMetrics {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn batch_size(&self) -> u64 {
+        self.inner.batch_size.load(Ordering::SeqCst)
+    }
+
+    pub fn poll_interval_ms(&self) -> u64 {
+        self.inner.poll_interval_ms.load(Ordering::SeqCst)
+    }
+
+    pub fn last_latency_ms(&self) -> u64 {
+        self.inner.last_latency_ms.load(Ordering::SeqCst)
+    }
+
+    pub fn delivered(&self) -> u64 {
+        self.inner.delivered.load(Ordering::SeqCst)
+    }
+
+    pub fn failures(&self) -> u64 {
+        self.inner.failures.load(Ordering::SeqCst)
+    }
+
+    /// Rows delivered per second, averaged over the `RATE_WINDOW_MS` before `now`.
+    pub fn publish_rate(&self, now: i64) -> f64 {
+        let since = now - RATE_WINDOW_MS;
+        let delivered: u64 = self
+            .inner
+            .delivered_per_minute
+            .lock()
+            .unwrap()
+            .range(since / MINUTE_MS..)
+            .map(|(_, count)| count)
+            .sum();
+        delivered as f64 / (RATE_WINDOW_MS / 1000) as f64
+    }
+
+    fn record_delivered(&self, count: usize, at: i64) {
+        self.inner
+            .delivered
+            .fetch_add(count as u64, Ordering::SeqCst);
+        let mut minutes = self.inner.delivered_per_minute.lock().unwrap();
+        *minutes.entry(at / MINUTE_MS).or_default() += count as u64;
+        let oldest = (at - RATE_WINDOW_MS) / MINUTE_MS;
+        minutes.retain(|minute, _| *minute >= oldest);
+    }
+
+    fn record_rate(&self, control: &AdaptiveBatch) {
+        self.inner
+            .batch_size
+            .store(control.batch_size() as u64, Ordering::SeqCst);
+        self.inner
+            .poll_interval_ms
+            .store(control.poll_interval().as_millis() as u64, Ordering::SeqCst);
+    }
+}
+
+pub struct OutboxRelay<TOutbox, TBroker> {
+    topic: String,
+    routes: TopicRoutes,
+    outbox: TOutbox,
+    broker: TBroker,
+    control: AdaptiveBatch,
+    metrics: RelayMetrics,
+    pause: Option<PauseSwitch>,
+    config_updates: Option<watch::Receiver<AdaptiveBatchConfig>>,
+}
+
+impl<TOutbox, TBroker> OutboxRelay<TOutbox, TBroker>
+where
+    TOutbox: OutboxRelaySource,
+    TBroker: MessageBroker,
+{
+    pub fn new(
+        topic: impl Into<String>,
+        outbox: TOutbox,
+        broker: TBroker,
+        config: AdaptiveBatchConfig,
+    ) -> Self {
+        let control = AdaptiveBatch::new(config);
+        let metrics = RelayMetrics::new();
+        metrics.record_rate(&control);
+        Self {
+            topic: topic.into(),
+            routes: TopicRoutes::new(),
+            outbox,
+            broker,
+            control,
+            metrics,
+            pause: None,
+            config_updates: None,
+        }
+    }
+
+    /// Publishes rows on the broker topics `routes` picks instead of on the outbox topic.
+    pub fn with_routes(mut self, routes: TopicRoutes) -> Self {
+        self.routes = routes;
+        self
+    }
+
+    /// Leaves rows pending while `pause` is set.
+    pub fn with_pause_switch(mut self, pause: PauseSwitch) -> Self {
+        self.pause = Some(pause);
+        self
+    }
+
+    /// Retunes batching whenever `updates` changes; the value it holds now is not applied.
+    pub fn with_config_updates(mut self, updates: watch::Receiver<AdaptiveBatchConfig>) -> Self {
+        self.config_updates = Some(updates);
+        self
+    }
+
+    /// Records into `metrics`, so that relays taking over from one another keep adding to
+    /// the same totals.
+    pub fn with_metrics(mut self, metrics: RelayMetrics) -> Self {
+        metrics.record_rate(&self.control);
+        self.metrics = metrics;
+        self
+    }
+
+    pub fn metrics(&self) -> RelayMetrics {
+        self.metrics.clone()
+    }
+
+    /// Relays one batch. Returns how many rows were delivered.
+    pub async fn relay_once(&mut self) -> Result<usize, RelayError> {
+        let result = self.deliver_batch().await;
+        if result.is_err() {
+            self.control.on_failure();
+            self.metrics.inner.failures.fetch_add(1, Ordering::SeqCst);
+        }
+        self.metrics.record_rate(&self.control);
+        result
+    }
+
+    async fn deliver_batch(&mut self) -> Result<usize, RelayError> {
+        let rows = self
+            .outbox
+            .claim_batch(&self.topic, self.control.batch_size())
+            .await?;
+        if rows.is_empty() {
+            self.control.on_published(0, Default::default());
+            return Ok(0);
+        }
+        let started = Instant::now();
+        for (topic, batch) in self.routes.split(&rows) {
+            if let Err(error) = self.broker.publish(&topic, &batch).await {
+                self.outbox.mark_failed(&rows, &error.to_string()).await?;
+                return Err(error.into());
+            }
+        }
+        let latency = started.elapsed();
+        let published_at = chrono::Utc::now().timestamp_millis();
+        self.outbox.mark_published(&rows, published_at).await?;
+
+        self.control.on_published(rows.len(), latency);
+        self.metrics
+            .inner
+            .last_latency_ms
+            .store(latency.as_millis() as u64, Ordering::SeqCst);
+        self.metrics.record_delivered(rows.len(), published_at);
+        Ok(rows.len())
+    }
+
+    /// Relays until the task is dropped, sleeping for the adaptive poll interval between runs.
+    /// Runs are skipped while paused.
+    pub async fn run(mut self) {
+        loop {
+            if let Some(updates) = &mut self.config_updates
+                && updates.has_changed().unwrap_or(false)
+            {
+                let config = updates.borrow_and_update().clone();
+                tracing::info!(?config, topic = %self.topic, "outbox relay retuned");
+                self.control.reconfigure(config);
+                self.metrics.record_rate(&self.control);
+            }
+            let paused = match &self.pause {
+                Some(pause) => pause.is_paused().await,
+                None => false,
+            };
+            if !paused && let Err(reason) = self.relay_once().await {
+                tracing::warn!(%reason, topic = %self.topic, "outbox relay run failed");
+            }
+            tokio::time::sleep(self.control.poll_interval()).await;
+        }
+    }
+}
+
+pub mod adaptive;
+pub mod routing;
+
+#[cfg(test)]
+mod outbox_relay_tests {
+    use super::*;
+    use crate::shared::infrastructure::control_store::in_memory::InMemoryControlStore;
+    use crate::shared::infrastructure::control_store::{
+        ControlStore, WorkerControl, outbox_relay_worker,
+    };
+    use crate::shared::infrastructure::intent_outbox::in_memory::InMemoryDomainOutbox;
+    use crate::shared::infrastructure::intent_outbox::{DomainOutbox, OutboxRow, OutboxStatus};
+    use crate::shared::infrastructure::message_broker::in_memory::InMemoryMessageBroker;
+    use rstest::rstest;
+    use std::time::Duration;
+
+    const TOPIC: &str = "time-entries.v1";
+
+    fn config() -> AdaptiveBatchConfig {
+        AdaptiveBatchConfig {
+            min_batch: 2,
+            max_batch: 10,
+            additive_step: 2,
+            decrease_factor: 0.5,
+            target_latency: Duration::from_millis(50),
+            min_interval: Duration::from_millis(5),
+            max_interval: Duration::from_millis(20),
+        }
+    }
+
+    async fn outbox_with_rows(count: i64) -> InMemoryDomainOutbox {
+        let outbox = InMemoryDomainOutbox::new();
+        for stream_version in 1..=count {
+            outbox
+                .enqueue(OutboxRow {
+                    topic: TOPIC.to_string(),
+                    event_type: "TimeEntryRegistered".to_string(),
+                    event_version: 1,
+                    stream_id: "TimeEntry-te-1".to_string(),
+                    stream_version,
+                    intent_no: 0,
+                    occurred_at: 0,
+                    payload: serde_json::Value::Null,
+                    content_encoding: None,
+                    status: OutboxStatus::Pending,
+                    attempts: 0,
+                    last_error: None,
+                    published_at: None,
+                })
+                .await
+                .unwrap();
+        }
+        outbox
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_grow_batches_while_the_broker_keeps_up() {
+        let outbox = outbox_with_rows(7).await;
+        let broker = InMemoryMessageBroker::new();
+        let mut relay = OutboxRelay::new(TOPIC, outbox.clone(), broker.clone(), config());
+        let metrics = relay.metrics();
+        assert_eq!(metrics.batch_size(), 2);
+
+        assert_eq!(relay.relay_once().await.unwrap(), 2);
+        assert_eq!(metrics.batch_size(), 4);
+        assert_eq!(relay.relay_once().await.unwrap(), 4);
+        assert_eq!(relay.relay_once().await.unwrap(), 1);
+        assert_eq!(relay.relay_once().await.unwrap(), 0);
+
+        assert_eq!(metrics.delivered(), 7);
+        assert_eq!(metrics.batch_size(), 6);
+        assert_eq!(metrics.poll_interval_ms(), 10);
+        assert_eq!(broker.published().await.len(), 7);
+        assert!(outbox.claim_batch(TOPIC, 10).await.unwrap().is_empty());
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_keep_the_totals_and_rate_of_shared_metrics_across_relays() {
+        let outbox = outbox_with_rows(3).await;
+        let broker = InMemoryMessageBroker::new();
+        let metrics = RelayMetrics::new();
+        let mut first = OutboxRelay::new(TOPIC, outbox.clone(), broker.clone(), config())
+            .with_metrics(metrics.clone());
+        first.relay_once().await.unwrap();
+        let mut second =
+            OutboxRelay::new(TOPIC, outbox, broker, config()).with_metrics(metrics.clone());
+        second.relay_once().await.unwrap();
+
+        let now = chrono::Utc::now().timestamp_millis();
+        assert_eq!(metrics.delivered(), 3);
+        assert_eq!(metrics.publish_rate(now), 3.0 / 300.0);
+        assert_eq!(metrics.publish_rate(now + 2 * RATE_WINDOW_MS), 0.0);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_shrink_batches_when_the_broker_slows_down() {
+        let outbox = outbox_with_rows(20).await;
+        let broker = InMemoryMessageBroker::new();
+        let mut relay = OutboxRelay::new(TOPIC, outbox, broker.clone(), config());
+        relay.relay_once().await.unwrap();
+        relay.relay_once().await.unwrap();
+        assert_eq!(relay.metrics().batch_size(), 6);
+
+        broker.set_delay_publish_ms(60);
+        assert_eq!(relay.relay_once().await.unwrap(), 6);
+
+        assert_eq!(relay.metrics().batch_size(), 3);
+        assert!(relay.metrics().last_latency_ms() >= 60);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_keep_rows_pending_and_back_off_when_delivery_fails() {
+        let outbox = outbox_with_rows(3).await;
+        let broker = InMemoryMessageBroker::new();
+        let mut relay = OutboxRelay::new(TOPIC, outbox.clone(), broker.clone(), config());
+
+        broker.toggle_offline();
+        assert!(matches!(
+            relay.relay_once().await,
+            Err(RelayError::Broker(_))
+        ));
+        broker.toggle_offline();
+        outbox.toggle_offline();
+        assert!(matches!(
+            relay.relay_once().await,
+            Err(RelayError::Outbox(_))
+        ));
+        outbox.toggle_offline();
+
+        let metrics = relay.metrics();
+        assert_eq!(metrics.failures(), 2);
+        assert_eq!(metrics.poll_interval_ms(), 20);
+        assert_eq!(outbox.claim_batch(TOPIC, 10).await.unwrap().len(), 3);
+        let rows = outbox.rows().await;
+        assert_eq!(rows[0].status, OutboxStatus::Failed);
+        assert_eq!(rows[0].attempts, 1);
+        assert!(rows[0].last_error.is_some());
+        assert_eq!(relay.relay_once().await.unwrap(), 2);
+        let rows = outbox.rows().await;
+        assert_eq!(rows[0].status, OutboxStatus::Published);
+        assert_eq!(rows[0].attempts, 2);
+        assert_eq!(rows[2].status, OutboxStatus::Pending);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_publish_rows_on_their_routed_topics() {
+        let outbox = outbox_with_rows(2).await;
+        outbox
+            .enqueue(OutboxRow {
+                event_type: "TimerAutoStopped".to_string(),
+                stream_version: 3,
+                ..outbox.rows().await[0].clone()
+            })
+            .await
+            .unwrap();
+        let broker = InMemoryMessageBroker::new();
+        let mut relay = OutboxRelay::new(TOPIC, outbox.clone(), broker.clone(), config())
+            .with_routes(TopicRoutes::new().route("TimerAutoStopped", "timers.v1"));
+
+        assert_eq!(relay.relay_once().await.unwrap(), 2);
+        assert_eq!(relay.relay_once().await.unwrap(), 1);
+
+        let topics: Vec<(String, i64)> = broker
+            .published()
+            .await
+            .into_iter()
+            .map(|(topic, row)| (topic, row.stream_version))
+            .collect();
+        assert_eq!(
+            topics,
+            vec![
+                (TOPIC.to_string(), 1),
+                (TOPIC.to_string(), 2),
+                ("timers.v1".to_string(), 3),
+            ]
+        );
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_relay_in_the_background_and_survive_failed_runs() {
+        let outbox = outbox_with_rows(5).await;
+        let broker = InMemoryMessageBroker::new();
+        broker.toggle_offline();
+        let relay = OutboxRelay::new(TOPIC, outbox, broker.clone(), config());
+        let metrics = relay.metrics();
+
+        let handle = tokio::spawn(relay.run());
+        tokio::time::sleep(Duration::from_millis(15)).await;
+        broker.toggle_offline();
+        tokio::time::sleep(Duration::from_millis(80)).await;
+        handle.abort();
+
+        assert!(metrics.failures() >= 1);
+        assert_eq!(metrics.delivered(), 5);
+        assert_eq!(broker.published().await.len(), 5);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_pick_up_config_updates_between_runs() {
+        let outbox = outbox_with_rows(30).await;
+        let broker = InMemoryMessageBroker::new();
+        let (updates, receiver) = watch::channel(config());
+        let relay =
+            OutboxRelay::new(TOPIC, outbox, broker.clone(), config()).with_config_updates(receiver);
+        let metrics = relay.metrics();
+
+        updates.send_replace(AdaptiveBatchConfig {
+            min_batch: 3,
+            max_batch: 3,
+            min_interval: Duration::from_millis(100),
+            max_interval: Duration::from_millis(100),
+            ..config()
+        });
+        let handle = tokio::spawn(relay.run());
+        tokio::time::sleep(Duration::from_millis(30)).await;
+        handle.abort();
+
+        assert_eq!(metrics.batch_size(), 3);
+        assert_eq!(broker.published().await.len(), 3);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_hold_rows_while_paused() {
+        let outbox = outbox_with_rows(3).await;
+        let broker = InMemoryMessageBroker::new();
+        let controls = InMemoryControlStore::new();
+        let pause = |paused| WorkerControl {
+            worker: outbox_relay_worker(TOPIC),
+            paused,
+            updated_at: 0,
+            updated_by: "admin-1".to_string(),
+        };
+        controls.put(pause(true)).await.unwrap();
+        let relay = OutboxRelay::new(TOPIC, outbox, broker.clone(), config()).with_pause_switch(
+            PauseSwitch::new(Arc::new(controls.clone()), outbox_relay_worker(TOPIC)),
+        );
+
+        let handle = tokio::spawn(relay.run());
+        tokio::time::sleep(Duration::from_millis(40)).await;
+        assert!(broker.published().await.is_empty());
+
+        controls.put(pause(false)).await.unwrap();
+        tokio::time::sleep(Duration::from_millis(40)).await;
+        handle.abort();
+
+        assert_eq!(broker.published().await.len(), 3);
+    }
+}
@@ -0,0 +1,217 @@
+// Broker topic routing for relayed outbox rows.
+//
+// A row's own `topic` names the outbox queue it was written to and that a relay drains. The
+// broker topic it is published on is looked up by event type: a route for the exact version
+// wins over one for every version, then the configured default, and finally the row's own
+// topic, so an empty table publishes exactly as before.
+//
+// Rows of one stream may end up on different topics; ordering only holds within a topic.
+
+use crate::shared::infrastructure::intent_outbox::OutboxRow;
+use std::collections::HashMap;
+use thiserror::Error;
+
+#[derive(Debug, Clone, Error, PartialEq, Eq)]
+pub enum RoutingConfigError {
+    #[error("topic route {0:?} should look like EventType=topic or EventType@version=topic")]
+    Malformed(String),
+
+    #[error("topic route {0:?} has a version that is not a number")]
+    InvalidVersion(String),
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TopicRoutes {
+    routes: HashMap<(String, Option<i32>), String>,
+    default: Option<String>,
+}
+
+impl TopicRoutes {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Publishes every version of `event_type` on `topic`.
+    pub fn route(mut self, event_type: impl Into<String>, topic: impl Into<String>) -> Self {
+        self.routes.insert((event_type.into(), None), topic.into());
+        self
+    }
+
+    /// Publishes version `event_version` of `event_type` on `topic`.
+    pub fn route_version(
+        mut self,
+        event_type: impl Into<String>,
+        event_version: i32,
+        topic: impl Into<String>,
+    ) -> Self {
+        self.routes
+            .insert((event_type.into(), Some(event_version)), topic.into());
+        self
+    }
+
+    /// Publishes event types without a route on `topic` rather than on their outbox topic.
+    pub fn with_default(mut self, topic: impl Into<String>) -> Self {
+        self.default = Some(topic.into());
+        self
+    }
+
+    /// Reads a comma separated table such as
+    /// `TimerAutoStopped=timers.v1,TimeEntryRegistered@2=time-entries.v2,*=time-entries.v1`,
+    /// where `*` sets the default.
+    pub fn parse(table: &str) -> Result<Self, RoutingConfigError> {
+        table
+            .split(',')
+            .map(str::trim)
+            .filter(|entry| !entry.is_empty())
+            .try_fold(Self::new(), |routes, entry| {
+                let malformed = || RoutingConfigError::Malformed(entry.to_string());
+                let (key, topic) = entry.split_once('=').ok_or_else(malformed)?;
+                let (key, topic) = (key.trim(), topic.trim());
+                if key.is_empty() || topic.is_empty() {
+                    return Err(malformed());
+                }
+                if key == "*" {
+                    return Ok(routes.with_default(topic));
+                }
+                match key.split_once('@') {
+                    Some((event_type, version)) => {
+                        let version = version
+                            .trim()
+                            .parse()
+                            .map_err(|_| RoutingConfigError::InvalidVersion(entry.to_string()))?;
+                        Ok(routes.route_version(event_type.trim(), version, topic))
+                    }
+                    None => Ok(routes.route(key, topic)),
+                }
+            })
+    }
+
+    pub fn topic_for<'a>(&'a self, row: &'a OutboxRow) -> &'a str {
+        self.routes
+            .get(&(row.event_type.clone(), Some(row.event_version)))
+            .or_else(|| self.routes.get(&(row.event_type.clone(), None)))
+            .or(self.default.as_ref())
+            .unwrap_or(&row.topic)
+    }
+
+    /// Splits `rows` by broker topic, keeping outbox order within each topic. Topics come in
+    /// the order their first row appears.
+    pub fn split(&self, rows: &[OutboxRow]) -> Vec<(String, Vec<OutboxRow>)> {
+        let mut batches: Vec<(String, Vec<OutboxRow>)> = Vec::new();
+        for row in rows {
+            let topic = self.topic_for(row);
+            match batches
+                .iter_mut()
+                .find(|(batch_topic, _)| batch_topic == topic)
+            {
+                Some((_, batch)) => batch.push(row.clone()),
+                None => batches.push((topic.to_string(), vec![row.clone()])),
+            }
+        }
+        batches
+    }
+}
+
+#[cfg(test)]
+mod routing_tests {
+    use super::*;
+    use crate::shared::infrastructure::intent_outbox::OutboxStatus;
+    use rstest::rstest;
+
+    fn row(event_type: &str, event_version: i32) -> OutboxRow {
+        OutboxRow {
+            topic: "time-entries.v1".to_string(),
+            event_type: event_type.to_string(),
+            event_version,
+            stream_id: "TimeEntry-te-1".to_string(),
+            stream_version: 1,
+            intent_no: 0,
+            occurred_at: 0,
+            payload: serde_json::Value::Null,
+            content_encoding: None,
+            status: OutboxStatus::Pending,
+            attempts: 0,
+            last_error: None,
+            published_at: None,
+        }
+    }
+
+    #[rstest]
+    #[case::exact_version(row("TimeEntryRegistered", 2), "time-entries.v2")]
+    #[case::any_version(row("TimeEntryRegistered", 1), "registrations")]
+    #[case::default(row("TimeEntryTagsSet", 1), "everything-else")]
+    fn it_should_prefer_the_most_specific_route(#[case] row: OutboxRow, #[case] expected: &str) {
+        let routes = TopicRoutes::new()
+            .route("TimeEntryRegistered", "registrations")
+            .route_version("TimeEntryRegistered", 2, "time-entries.v2")
+            .with_default("everything-else");
+
+        assert_eq!(routes.topic_for(&row), expected);
+    }
+
+    #[test]
+    fn it_should_fall_back_to_the_outbox_topic_without_a_default() {
+        let routes = TopicRoutes::new().route("TimerAutoStopped", "timers.v1");
+        assert_eq!(
+            routes.topic_for(&row("TimeEntryRegistered", 1)),
+            "time-entries.v1"
+        );
+    }
+
+    #[test]
+    fn it_should_split_rows_by_topic_keeping_their_order() {
+        let routes = TopicRoutes::new().route("TimerAutoStopped", "timers.v1");
+        let mut rows = vec![
+            row("TimeEntryRegistered", 1),
+            row("TimerAutoStopped", 1),
+            row("TimeEntryRegistered", 1),
+        ];
+        for (stream_version, row) in rows.iter_mut().enumerate() {
+            row.stream_version = stream_version as i64;
+        }
+
+        let batches = routes.split(&rows);
+
+        let versions: Vec<(&str, Vec<i64>)> = batches
+            .iter()
+            .map(|(topic, rows)| {
+                (
+                    topic.as_str(),
+                    rows.iter().map(|row| row.stream_version).collect(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            versions,
+            vec![("time-entries.v1", vec![0, 2]), ("timers.v1", vec![1])]
+        );
+    }
+
+    #[test]
+    fn it_should_parse_a_routing_table() {
+        let routes = TopicRoutes::parse(
+            " TimerAutoStopped = timers.v1, TimeEntryRegistered@2=time-entries.v2 ,*=all,",
+        )
+        .unwrap();
+
+        assert_eq!(
+            routes,
+            TopicRoutes::new()
+                .route("TimerAutoStopped", "timers.v1")
+                .route_version("TimeEntryRegistered", 2, "time-entries.v2")
+                .with_default("all")
+        );
+    }
+
+    #[rstest]
+    #[case("TimerAutoStopped", RoutingConfigError::Malformed("TimerAutoStopped".to_string()))]
+    #[case("=timers.v1", RoutingConfigError::Malformed("=timers.v1".to_string()))]
+    #[case("TimerAutoStopped=", RoutingConfigError::Malformed("TimerAutoStopped=".to_string()))]
+    #[case("TimerAutoStopped@x=timers", RoutingConfigError::InvalidVersion("TimerAutoStopped@x=timers".to_string()))]
+    fn it_should_reject_malformed_routes(
+        #[case] table: &str,
+        #[case] expected: RoutingConfigError,
+    ) {
+        assert_eq!(TopicRoutes::parse(table), Err(expected));
+    }
+}
@@ -0,0 +1,126 @@
+use crate::shared::infrastructure::policy_store::{PolicyStore, PolicyStoreError, TenantPolicies};
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use tokio::sync::RwLock;
+
+#[derive(Default)]
+struct Inner {
+    policies: RwLock<BTreeMap<String, TenantPolicies>>,
+    is_offline: AtomicBool,
+}
+
+/// Holds policies for the life of the process; a restart returns every tenant to the defaults.
+#[derive(Clone, Default)]
+pub struct InMemoryPolicyStore {
+    inner: Arc<Inner>,
+}
+
+impl InMemoryPolicyStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn toggle_offline(&self) {
+        self.inner.is_offline.fetch_xor(true, Ordering::SeqCst);
+    }
+
+    fn ensure_online(&self) -> Result<(), PolicyStoreError> {
+        if self.inner.is_offline.load(Ordering::SeqCst) {
+            return Err(PolicyStoreError::Backend(
+                "Policy store offline".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl PolicyStore for InMemoryPolicyStore {
+    async fn put(&self, policies: TenantPolicies) -> Result<(), PolicyStoreError> {
+        self.ensure_online()?;
+        self.inner
+            .policies
+            .write()
+            .await
+            .insert(policies.tenant_id.clone(), policies);
+        Ok(())
+    }
+
+    async fn get(&self, tenant_id: &str) -> Result<Option<TenantPolicies>, PolicyStoreError> {
+        self.ensure_online()?;
+        Ok(self.inner.policies.read().await.get(tenant_id).cloned())
+    }
+
+    async fn remove(&self, tenant_id: &str) -> Result<bool, PolicyStoreError> {
+        self.ensure_online()?;
+        Ok(self
+            .inner
+            .policies
+            .write()
+            .await
+            .remove(tenant_id)
+            .is_some())
+    }
+
+    async fn list(&self) -> Result<Vec<TenantPolicies>, PolicyStoreError> {
+        self.ensure_online()?;
+        Ok(self.inner.policies.read().await.values().cloned().collect())
+    }
+}
+
+#[cfg(test)]
+mod in_memory_policy_store_tests {
+    use super::*;
+    use crate::modules::time_entries::core::policies::Policies;
+    use rstest::rstest;
+
+    fn tenant_policies(tenant_id: &str, lock_after_days: u32) -> TenantPolicies {
+        TenantPolicies {
+            tenant_id: tenant_id.to_string(),
+            policies: Policies {
+                lock_after_days: Some(lock_after_days),
+                ..Policies::default()
+            },
+            updated_at: 0,
+            updated_by: "admin-1".to_string(),
+        }
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_keep_the_latest_policies_per_tenant() {
+        let store = InMemoryPolicyStore::new();
+        store.put(tenant_policies("t-2", 7)).await.unwrap();
+        store.put(tenant_policies("t-1", 7)).await.unwrap();
+        store.put(tenant_policies("t-2", 30)).await.unwrap();
+
+        assert_eq!(
+            store.get("t-2").await.unwrap(),
+            Some(tenant_policies("t-2", 30))
+        );
+        assert_eq!(
+            store.list().await.unwrap(),
+            vec![tenant_policies("t-1", 7), tenant_policies("t-2", 30)]
+        );
+        assert_eq!(store.remove("t-1").await, Ok(true));
+        assert_eq!(store.remove("t-1").await, Ok(false));
+        assert_eq!(store.get("t-1").await.unwrap(), None);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_fail_when_offline() {
+        let store = InMemoryPolicyStore::new();
+        store.toggle_offline();
+
+        assert!(store.put(tenant_policies("t-1", 7)).await.is_err());
+        assert!(store.remove("t-1").await.is_err());
+        assert_eq!(
+            store.get("t-1").await,
+            Err(PolicyStoreError::Backend(
+                "Policy store offline".to_string()
+            ))
+        );
+    }
+}
@@ -0,0 +1,108 @@
+use async_trait::async_trait;
+use std::sync::Arc;
+use thiserror::Error;
+
+use crate::modules::time_entries::core::policies::Policies;
+
+#[derive(Debug, Clone, Error, PartialEq, Eq)]
+pub enum PolicyStoreError {
+    #[error("backend error: {0}")]
+    Backend(String),
+}
+
+/// The policies an admin set for one tenant. Times are epoch milliseconds.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct TenantPolicies {
+    pub tenant_id: String,
+    pub policies: Policies,
+    pub updated_at: i64,
+    pub updated_by: String,
+}
+
+/// Per-tenant policies shared by every instance (a Postgres table, a Redis hash), so a change
+/// holds across instances and restarts.
+#[async_trait]
+pub trait PolicyStore: Send + Sync {
+    /// Replaces the policies of `policies.tenant_id`.
+    async fn put(&self, policies: TenantPolicies) -> Result<(), PolicyStoreError>;
+
+    async fn get(&self, tenant_id: &str) -> Result<Option<TenantPolicies>, PolicyStoreError>;
+
+    /// Returns whether the tenant had policies of its own.
+    async fn remove(&self, tenant_id: &str) -> Result<bool, PolicyStoreError>;
+
+    /// By tenant.
+    async fn list(&self) -> Result<Vec<TenantPolicies>, PolicyStoreError>;
+}
+
+pub type SharedPolicyStore = Arc<dyn PolicyStore>;
+
+/// The policies `tenant_id` runs under: its own, or `defaults` when it has none, when there is
+/// no tenant or when the store cannot be read.
+pub async fn policies_for(
+    store: &dyn PolicyStore,
+    tenant_id: Option<&str>,
+    defaults: Policies,
+) -> Policies {
+    let Some(tenant_id) = tenant_id else {
+        return defaults;
+    };
+    match store.get(tenant_id).await {
+        Ok(stored) => stored.map_or(defaults, |stored| stored.policies),
+        Err(reason) => {
+            tracing::warn!(%reason, %tenant_id, "policy store unavailable, using the defaults");
+            defaults
+        }
+    }
+}
+
+pub mod in_memory;
+
+#[cfg(test)]
+mod policy_store_tests {
+    use super::*;
+    use crate::shared::infrastructure::policy_store::in_memory::InMemoryPolicyStore;
+    use rstest::rstest;
+
+    fn limited(max_tags: usize) -> Policies {
+        Policies {
+            max_tags: Some(max_tags),
+            ..Policies::default()
+        }
+    }
+
+    #[rstest]
+    #[case::own(Some("t-1"), limited(3))]
+    #[case::none_of_its_own(Some("t-2"), limited(5))]
+    #[case::no_tenant(None, limited(5))]
+    #[tokio::test]
+    async fn it_should_fall_back_to_the_defaults(
+        #[case] tenant_id: Option<&str>,
+        #[case] expected: Policies,
+    ) {
+        let store = InMemoryPolicyStore::new();
+        store
+            .put(TenantPolicies {
+                tenant_id: "t-1".to_string(),
+                policies: limited(3),
+                updated_at: 0,
+                updated_by: "admin-1".to_string(),
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(policies_for(&store, tenant_id, limited(5)).await, expected);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_use_the_defaults_while_the_store_is_offline() {
+        let store = InMemoryPolicyStore::new();
+        store.toggle_offline();
+
+        assert_eq!(
+            policies_for(&store, Some("t-1"), limited(5)).await,
+            limited(5)
+        );
+    }
+}
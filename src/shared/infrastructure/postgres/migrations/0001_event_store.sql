@@ -0,0 +1,24 @@
+-- Every stream's current version. Appends lock a stream's row to check their expected version,
+-- so appends to one stream serialize while appends to different streams do not.
+CREATE TABLE streams (
+    stream_id TEXT PRIMARY KEY,
+    version BIGINT NOT NULL
+);
+
+-- The global log. Positions are handed out under the lock on `event_log_head`, so they follow
+-- commit order without gaps and a projector reading up to the head never skips an event that
+-- commits later with a lower position.
+CREATE TABLE events (
+    global_position BIGINT PRIMARY KEY,
+    stream_id TEXT NOT NULL,
+    stream_version BIGINT NOT NULL,
+    payload JSONB NOT NULL,
+    UNIQUE (stream_id, stream_version)
+);
+
+CREATE TABLE event_log_head (
+    id BOOLEAN PRIMARY KEY DEFAULT TRUE CHECK (id),
+    next_position BIGINT NOT NULL
+);
+
+INSERT INTO event_log_head (next_position) VALUES (0);
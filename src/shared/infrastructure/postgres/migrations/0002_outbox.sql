@@ -0,0 +1,19 @@
+CREATE TABLE outbox (
+    position BIGSERIAL PRIMARY KEY,
+    topic TEXT NOT NULL,
+    event_type TEXT NOT NULL,
+    event_version INTEGER NOT NULL,
+    stream_id TEXT NOT NULL,
+    stream_version BIGINT NOT NULL,
+    intent_no BIGINT NOT NULL,
+    occurred_at BIGINT NOT NULL,
+    payload JSONB NOT NULL,
+    content_encoding TEXT,
+    status TEXT NOT NULL,
+    attempts BIGINT NOT NULL DEFAULT 0,
+    last_error TEXT,
+    published_at BIGINT,
+    UNIQUE (stream_id, stream_version, intent_no)
+);
+
+CREATE INDEX outbox_unpublished ON outbox (topic, position) WHERE status <> 'Published';
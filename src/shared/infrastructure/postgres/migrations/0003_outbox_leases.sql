@@ -0,0 +1,2 @@
+-- Until when, in epoch milliseconds, the relay that claimed a row has it to itself.
+ALTER TABLE outbox ADD COLUMN leased_until BIGINT;
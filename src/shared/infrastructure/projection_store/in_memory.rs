@@ -1,6 +1,8 @@
 use super::ProjectionStore;
+use crate::shared::infrastructure::capacity::{Capacity, EvictRows, HasCapacity};
+use crate::shared::infrastructure::deadline::within;
 use std::sync::Arc;
-use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
 use tokio::sync::RwLock;
 
 struct InnerState<Projection> {
@@ -14,11 +16,21 @@ struct Inner<Projection: Clone + Send + Sync + 'static> {
     is_offline: AtomicBool,
     fail_next_save: AtomicBool,
     fail_next_save_schema: AtomicBool,
+    row_count: AtomicUsize,
+    evicted: AtomicU64,
+}
+
+#[derive(Clone)]
+struct RowBound<Projection> {
+    max_rows: usize,
+    row_count: fn(&Projection) -> usize,
+    evict_rows: fn(&mut Projection, usize) -> usize,
 }
 
 #[derive(Clone)]
 pub struct InMemoryProjectionStore<Projection: Clone + Send + Sync + 'static> {
     inner: Arc<Inner<Projection>>,
+    bound: Option<RowBound<Projection>>,
 }
 
 impl<P: Clone + Send + Sync + 'static> Default for InMemoryProjectionStore<P> {
@@ -39,10 +51,28 @@ impl<P: Clone + Send + Sync + 'static> InMemoryProjectionStore<P> {
                 is_offline: AtomicBool::new(false),
                 fail_next_save: AtomicBool::new(false),
                 fail_next_save_schema: AtomicBool::new(false),
+                row_count: AtomicUsize::new(0),
+                evicted: AtomicU64::new(0),
             }),
+            bound: None,
         }
     }
 
+    /// Keeps at most `max_rows` rows, evicting the least recently updated on every save.
+    /// For long-running environments whose data can be lost: an evicted row reads as never
+    /// projected until its stream changes again.
+    pub fn with_max_rows(mut self, max_rows: usize) -> Self
+    where
+        P: EvictRows,
+    {
+        self.bound = Some(RowBound {
+            max_rows,
+            row_count: P::row_count,
+            evict_rows: P::evict_rows,
+        });
+        self
+    }
+
     pub fn toggle_offline(&mut self) {
         self.inner.is_offline.fetch_xor(true, Ordering::SeqCst);
     }
@@ -60,6 +90,28 @@ impl<P: Clone + Send + Sync + 'static> InMemoryProjectionStore<P> {
             .fail_next_save_schema
             .store(true, Ordering::SeqCst);
     }
+
+    fn ensure_saveable(&self) -> anyhow::Result<()> {
+        if self.is_offline() {
+            return Err(anyhow::anyhow!("Projection store offline"));
+        }
+        if self.inner.fail_next_save.swap(false, Ordering::SeqCst) {
+            return Err(anyhow::anyhow!("Injected save failure"));
+        }
+        Ok(())
+    }
+
+    fn evict_over_bound(&self, state: &mut P) {
+        if let Some(bound) = &self.bound {
+            let evicted = (bound.evict_rows)(state, bound.max_rows);
+            self.inner
+                .evicted
+                .fetch_add(evicted as u64, Ordering::SeqCst);
+            self.inner
+                .row_count
+                .store((bound.row_count)(state), Ordering::SeqCst);
+        }
+    }
 }
 
 #[async_trait::async_trait]
@@ -68,36 +120,43 @@ impl<P: Clone + Send + Sync + 'static> ProjectionStore<P> for InMemoryProjection
         if self.is_offline() {
             return Err(anyhow::anyhow!("Projection store offline"));
         }
-        Ok(self.inner.state.read().await.state.clone())
+        Ok(within(self.inner.state.read()).await?.state.clone())
     }
 
     async fn checkpoint(&self) -> anyhow::Result<u64> {
         if self.is_offline() {
             return Err(anyhow::anyhow!("Projection store offline"));
         }
-        Ok(self.inner.state.read().await.checkpoint)
+        Ok(within(self.inner.state.read()).await?.checkpoint)
     }
 
     async fn schema_version(&self) -> anyhow::Result<Option<u32>> {
         if self.is_offline() {
             return Err(anyhow::anyhow!("Projection store offline"));
         }
-        Ok(self.inner.state.read().await.schema_version)
+        Ok(within(self.inner.state.read()).await?.schema_version)
     }
 
-    async fn save(&self, state: P, checkpoint: u64) -> anyhow::Result<()> {
-        if self.is_offline() {
-            return Err(anyhow::anyhow!("Projection store offline"));
-        }
-        if self.inner.fail_next_save.swap(false, Ordering::SeqCst) {
-            return Err(anyhow::anyhow!("Injected save failure"));
-        }
+    async fn save(&self, mut state: P, checkpoint: u64) -> anyhow::Result<()> {
+        self.ensure_saveable()?;
+        self.evict_over_bound(&mut state);
         let mut inner = self.inner.state.write().await;
         inner.state = Some(state);
         inner.checkpoint = checkpoint;
         Ok(())
     }
 
+    async fn save_if_unchanged(&self, mut state: P, checkpoint: u64) -> anyhow::Result<bool> {
+        self.ensure_saveable()?;
+        let mut inner = self.inner.state.write().await;
+        if inner.checkpoint != checkpoint {
+            return Ok(false);
+        }
+        self.evict_over_bound(&mut state);
+        inner.state = Some(state);
+        Ok(true)
+    }
+
     async fn save_schema_version(&self, version: u32) -> anyhow::Result<()> {
         if self.is_offline() {
             return Err(anyhow::anyhow!("Projection store offline"));
@@ -120,15 +179,44 @@ impl<P: Clone + Send + Sync + 'static> ProjectionStore<P> for InMemoryProjection
         let mut inner = self.inner.state.write().await;
         inner.state = None;
         inner.checkpoint = 0;
+        self.inner.row_count.store(0, Ordering::SeqCst);
         Ok(())
     }
 }
 
+/// Only bounded stores count their rows; unbounded ones report none used.
+impl<P: Clone + Send + Sync + 'static> HasCapacity for InMemoryProjectionStore<P> {
+    fn capacity(&self) -> Capacity {
+        Capacity {
+            used: self.inner.row_count.load(Ordering::SeqCst),
+            max: self.bound.as_ref().map(|bound| bound.max_rows),
+            evicted: self.inner.evicted.load(Ordering::SeqCst),
+        }
+    }
+}
+
 #[cfg(test)]
 mod in_memory_projection_store_tests {
     use super::*;
+    use crate::shared::infrastructure::deadline::{self, Deadline, DeadlineExceeded};
     use rstest::rstest;
 
+    /// Rows in the order they were last updated, oldest first.
+    #[derive(Debug, Clone, PartialEq)]
+    struct Names(Vec<String>);
+
+    impl EvictRows for Names {
+        fn row_count(&self) -> usize {
+            self.0.len()
+        }
+
+        fn evict_rows(&mut self, max_rows: usize) -> usize {
+            let excess = self.0.len().saturating_sub(max_rows);
+            self.0.drain(..excess);
+            excess
+        }
+    }
+
     #[rstest]
     #[tokio::test]
     async fn it_should_return_none_state_and_zero_checkpoint_initially() {
@@ -150,6 +238,26 @@ mod in_memory_projection_store_tests {
         assert_eq!(store.checkpoint().await.unwrap(), 42);
     }
 
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_only_save_if_the_checkpoint_is_unchanged() {
+        let store = InMemoryProjectionStore::<String>::new();
+        store.save("applied".to_string(), 42).await.unwrap();
+
+        let stale = store
+            .save_if_unchanged("stale".to_string(), 41)
+            .await
+            .unwrap();
+        let current = store
+            .save_if_unchanged("current".to_string(), 42)
+            .await
+            .unwrap();
+
+        assert!(!stale && current);
+        assert_eq!(store.state().await.unwrap(), Some("current".to_string()));
+        assert_eq!(store.checkpoint().await.unwrap(), 42);
+    }
+
     #[rstest]
     #[tokio::test]
     async fn it_should_save_and_load_schema_version() {
@@ -158,6 +266,17 @@ mod in_memory_projection_store_tests {
         assert_eq!(store.schema_version().await.unwrap(), Some(3));
     }
 
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_not_read_once_the_deadline_has_passed() {
+        let store = InMemoryProjectionStore::<String>::new();
+        store.save("data".to_string(), 10).await.unwrap();
+
+        let read = deadline::scope(Deadline::at(tokio::time::Instant::now()), store.state()).await;
+
+        assert!(read.unwrap_err().is::<DeadlineExceeded>());
+    }
+
     #[rstest]
     #[tokio::test]
     async fn it_should_clear_state_and_reset_checkpoint() {
@@ -175,6 +294,32 @@ mod in_memory_projection_store_tests {
         assert_eq!(store.state().await.unwrap(), None);
     }
 
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_evict_rows_over_the_bound_on_save() {
+        let store = InMemoryProjectionStore::<Names>::new().with_max_rows(2);
+
+        store
+            .save(Names(vec!["a".into(), "b".into(), "c".into()]), 3)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            store.state().await.unwrap(),
+            Some(Names(vec!["b".into(), "c".into()]))
+        );
+        assert_eq!(
+            store.capacity(),
+            Capacity {
+                used: 2,
+                max: Some(2),
+                evicted: 1,
+            }
+        );
+        store.clear().await.unwrap();
+        assert_eq!(store.capacity().used, 0);
+    }
+
     #[rstest]
     #[tokio::test]
     async fn it_should_fail_all_operations_when_offline() {
@@ -184,6 +329,7 @@ mod in_memory_projection_store_tests {
         assert!(store.checkpoint().await.is_err());
         assert!(store.schema_version().await.is_err());
         assert!(store.save("x".to_string(), 1).await.is_err());
+        assert!(store.save_if_unchanged("x".to_string(), 0).await.is_err());
         assert!(store.save_schema_version(1).await.is_err());
         assert!(store.clear().await.is_err());
     }
@@ -6,8 +6,13 @@ pub trait ProjectionStore<P: Clone + Send + Sync + 'static>: Send + Sync {
     async fn checkpoint(&self) -> anyhow::Result<u64>;
     async fn schema_version(&self) -> anyhow::Result<Option<u32>>;
     async fn save(&self, state: P, checkpoint: u64) -> anyhow::Result<()>;
+    /// Saves `state` if the stored checkpoint is still `checkpoint`, leaving it there. Writers
+    /// other than the projector save what they read back through this, so they cannot undo
+    /// events the projector applied in between. Returns whether it saved.
+    async fn save_if_unchanged(&self, state: P, checkpoint: u64) -> anyhow::Result<bool>;
     async fn save_schema_version(&self, version: u32) -> anyhow::Result<()>;
     async fn clear(&self) -> anyhow::Result<()>;
 }
 
 pub mod in_memory;
+pub mod partitioned;
@@ -0,0 +1,223 @@
+// One projection split over several stores, one per hash partition of stream ids.
+//
+// Each partition's projector writes only to its own store and keeps its own checkpoint, so
+// partitions advance independently. Queries read through this store, which merges every
+// partition's state; its checkpoint is the lowest partition checkpoint, the position up to
+// which the merged view is complete. Writes must go to a partition store directly.
+
+use super::ProjectionStore;
+use crate::shared::core::partitioning::Partition;
+use async_trait::async_trait;
+use std::sync::Arc;
+
+/// Projection state that can be assembled from per-partition states.
+pub trait MergeProjection: Clone + Default + Send + Sync + 'static {
+    fn merge(&mut self, partition: Self);
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct PartitionStatus {
+    pub partition: Partition,
+    pub checkpoint: u64,
+    pub schema_version: Option<u32>,
+}
+
+#[derive(Clone)]
+pub struct PartitionedProjectionStore<TStore> {
+    partitions: Arc<Vec<(Partition, TStore)>>,
+}
+
+impl<TStore> PartitionedProjectionStore<TStore>
+where
+    TStore: Clone,
+{
+    /// Builds `count` partitions (at least one), creating each store with `make_store`.
+    pub fn new(count: usize, mut make_store: impl FnMut(Partition) -> TStore) -> Self {
+        Self {
+            partitions: Arc::new(
+                Partition::all(count)
+                    .into_iter()
+                    .map(|partition| (partition, make_store(partition)))
+                    .collect(),
+            ),
+        }
+    }
+
+    /// A single partition owning every stream, wrapping an existing store.
+    pub fn single(store: TStore) -> Self {
+        Self::new(1, |_| store.clone())
+    }
+
+    pub fn partitions(&self) -> Vec<(Partition, TStore)> {
+        self.partitions.to_vec()
+    }
+
+    pub async fn status<P>(&self) -> anyhow::Result<Vec<PartitionStatus>>
+    where
+        P: Clone + Send + Sync + 'static,
+        TStore: ProjectionStore<P>,
+    {
+        let mut statuses = Vec::with_capacity(self.partitions.len());
+        for (partition, store) in self.partitions.iter() {
+            statuses.push(PartitionStatus {
+                partition: *partition,
+                checkpoint: store.checkpoint().await?,
+                schema_version: store.schema_version().await?,
+            });
+        }
+        Ok(statuses)
+    }
+}
+
+#[async_trait]
+impl<P, TStore> ProjectionStore<P> for PartitionedProjectionStore<TStore>
+where
+    P: MergeProjection,
+    TStore: ProjectionStore<P> + Clone,
+{
+    async fn state(&self) -> anyhow::Result<Option<P>> {
+        let mut merged: Option<P> = None;
+        for (_, store) in self.partitions.iter() {
+            if let Some(state) = store.state().await? {
+                merged.get_or_insert_with(P::default).merge(state);
+            }
+        }
+        Ok(merged)
+    }
+
+    async fn checkpoint(&self) -> anyhow::Result<u64> {
+        let mut lowest = u64::MAX;
+        for (_, store) in self.partitions.iter() {
+            lowest = lowest.min(store.checkpoint().await?);
+        }
+        Ok(lowest)
+    }
+
+    /// The shared schema version, or `None` while partitions disagree.
+    async fn schema_version(&self) -> anyhow::Result<Option<u32>> {
+        let mut versions = Vec::with_capacity(self.partitions.len());
+        for (_, store) in self.partitions.iter() {
+            versions.push(store.schema_version().await?);
+        }
+        versions.dedup();
+        Ok(match versions.as_slice() {
+            [version] => *version,
+            _ => None,
+        })
+    }
+
+    async fn save(&self, _state: P, _checkpoint: u64) -> anyhow::Result<()> {
+        anyhow::bail!("partitioned projections are written per partition")
+    }
+
+    async fn save_if_unchanged(&self, _state: P, _checkpoint: u64) -> anyhow::Result<bool> {
+        anyhow::bail!("partitioned projections are written per partition")
+    }
+
+    async fn save_schema_version(&self, _version: u32) -> anyhow::Result<()> {
+        anyhow::bail!("partitioned projections are written per partition")
+    }
+
+    async fn clear(&self) -> anyhow::Result<()> {
+        for (_, store) in self.partitions.iter() {
+            store.clear().await?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod partitioned_projection_store_tests {
+    use super::*;
+    use crate::shared::infrastructure::projection_store::in_memory::InMemoryProjectionStore;
+    use rstest::rstest;
+
+    #[derive(Debug, Clone, Default, PartialEq)]
+    struct Names(Vec<&'static str>);
+
+    impl MergeProjection for Names {
+        fn merge(&mut self, partition: Self) {
+            self.0.extend(partition.0);
+        }
+    }
+
+    fn store(count: usize) -> PartitionedProjectionStore<InMemoryProjectionStore<Names>> {
+        PartitionedProjectionStore::new(count, |_| InMemoryProjectionStore::new())
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_merge_partition_states_and_report_the_lowest_checkpoint() {
+        let store = store(3);
+        assert_eq!(store.state().await.unwrap(), None);
+        let partitions = store.partitions();
+        partitions[0].1.save(Names(vec!["a"]), 7).await.unwrap();
+        partitions[2].1.save(Names(vec!["c"]), 5).await.unwrap();
+
+        assert_eq!(store.state().await.unwrap(), Some(Names(vec!["a", "c"])));
+        assert_eq!(store.checkpoint().await.unwrap(), 0);
+        partitions[1].1.save(Names(vec![]), 6).await.unwrap();
+        assert_eq!(store.checkpoint().await.unwrap(), 5);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_report_a_schema_version_only_when_partitions_agree() {
+        let store = store(2);
+        let partitions = store.partitions();
+        partitions[0].1.save_schema_version(2).await.unwrap();
+        assert_eq!(store.schema_version().await.unwrap(), None);
+
+        partitions[1].1.save_schema_version(2).await.unwrap();
+        assert_eq!(store.schema_version().await.unwrap(), Some(2));
+
+        let statuses = store.status().await.unwrap();
+        assert_eq!(statuses.len(), 2);
+        assert_eq!(statuses[1].partition, Partition { index: 1, count: 2 });
+        assert_eq!(statuses[1].schema_version, Some(2));
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_clear_every_partition_and_refuse_direct_writes() {
+        let store = store(2);
+        store.partitions()[1]
+            .1
+            .save(Names(vec!["b"]), 3)
+            .await
+            .unwrap();
+
+        store.clear().await.unwrap();
+
+        assert_eq!(store.state().await.unwrap(), None);
+        assert!(store.save(Names(vec![]), 1).await.is_err());
+        assert!(store.save_if_unchanged(Names(vec![]), 0).await.is_err());
+        assert!(store.save_schema_version(1).await.is_err());
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_wrap_a_single_store() {
+        let inner = InMemoryProjectionStore::<Names>::new();
+        inner.save(Names(vec!["x"]), 4).await.unwrap();
+
+        let store = PartitionedProjectionStore::single(inner);
+
+        assert_eq!(store.state().await.unwrap(), Some(Names(vec!["x"])));
+        assert_eq!(store.checkpoint().await.unwrap(), 4);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_fail_when_a_partition_is_offline() {
+        let store = store(2);
+        let mut offline = store.partitions()[1].1.clone();
+        offline.toggle_offline();
+
+        assert!(store.state().await.is_err());
+        assert!(store.checkpoint().await.is_err());
+        assert!(store.schema_version().await.is_err());
+        assert!(store.clear().await.is_err());
+        assert!(store.status::<Names>().await.is_err());
+    }
+}
@@ -0,0 +1,223 @@
+use crate::shared::infrastructure::query_cache::QueryCache;
+use moka::future::Cache;
+use moka::notification::RemovalCause;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+pub const DEFAULT_MAX_ENTRIES: u64 = 10_000;
+pub const DEFAULT_TIME_TO_LIVE: Duration = Duration::from_secs(300);
+
+struct Generations {
+    /// Bumped by every invalidation.
+    current: AtomicU64,
+    /// Values filled before this are stale in every partition: set by `invalidate_all`, and
+    /// raised when the invalidation of a partition is evicted and can no longer be checked.
+    floor: AtomicU64,
+}
+
+/// Cached values stay for `time_to_live` at most, and the least recently used go once there
+/// are more than `max_entries`. Each value carries the generation it was loaded at and is
+/// only served while no invalidation of its partition came after it.
+#[derive(Clone)]
+pub struct InMemoryQueryCache<V> {
+    entries: Cache<(String, String), (u64, V)>,
+    invalidated_at: Cache<String, u64>,
+    generations: Arc<Generations>,
+}
+
+impl<V> Default for InMemoryQueryCache<V>
+where
+    V: Clone + Send + Sync + 'static,
+{
+    fn default() -> Self {
+        Self::bounded(DEFAULT_MAX_ENTRIES, DEFAULT_TIME_TO_LIVE)
+    }
+}
+
+impl<V> InMemoryQueryCache<V>
+where
+    V: Clone + Send + Sync + 'static,
+{
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn bounded(max_entries: u64, time_to_live: Duration) -> Self {
+        let generations = Arc::new(Generations {
+            current: AtomicU64::new(0),
+            floor: AtomicU64::new(0),
+        });
+        let evicted = generations.clone();
+        Self {
+            entries: Cache::builder()
+                .max_capacity(max_entries)
+                .time_to_live(time_to_live)
+                .build(),
+            invalidated_at: Cache::builder()
+                .max_capacity(max_entries)
+                .time_to_live(time_to_live)
+                .eviction_listener(move |_, generation, cause| {
+                    if matches!(cause, RemovalCause::Expired | RemovalCause::Size) {
+                        evicted.floor.fetch_max(generation, Ordering::SeqCst);
+                    }
+                })
+                .build(),
+            generations,
+        }
+    }
+
+    /// Values held, including stale ones not yet evicted.
+    pub async fn len(&self) -> usize {
+        self.entries.run_pending_tasks().await;
+        self.entries.entry_count() as usize
+    }
+
+    pub async fn is_empty(&self) -> bool {
+        self.len().await == 0
+    }
+
+    async fn is_stale(&self, partition: &str, generation: u64) -> bool {
+        generation < self.generations.floor.load(Ordering::SeqCst)
+            || generation < self.invalidated_at.get(partition).await.unwrap_or_default()
+    }
+}
+
+#[async_trait::async_trait]
+impl<V> QueryCache<V> for InMemoryQueryCache<V>
+where
+    V: Clone + Send + Sync + 'static,
+{
+    async fn get(&self, partition: &str, key: &str) -> Option<V> {
+        let (generation, value) = self
+            .entries
+            .get(&(partition.to_string(), key.to_string()))
+            .await?;
+        if self.is_stale(partition, generation).await {
+            return None;
+        }
+        Some(value)
+    }
+
+    async fn generation(&self, _partition: &str) -> u64 {
+        self.generations.current.load(Ordering::SeqCst)
+    }
+
+    async fn put(&self, partition: &str, key: &str, generation: u64, value: V) {
+        if self.is_stale(partition, generation).await {
+            return;
+        }
+        self.entries
+            .insert(
+                (partition.to_string(), key.to_string()),
+                (generation, value),
+            )
+            .await;
+    }
+
+    async fn invalidate(&self, partition: &str) {
+        let generation = self.generations.current.fetch_add(1, Ordering::SeqCst) + 1;
+        self.invalidated_at
+            .insert(partition.to_string(), generation)
+            .await;
+    }
+
+    async fn invalidate_all(&self) {
+        let generation = self.generations.current.fetch_add(1, Ordering::SeqCst) + 1;
+        self.generations
+            .floor
+            .fetch_max(generation, Ordering::SeqCst);
+        self.entries.invalidate_all();
+        self.invalidated_at.invalidate_all();
+    }
+}
+
+#[cfg(test)]
+mod in_memory_query_cache_tests {
+    use super::*;
+
+    async fn put(cache: &InMemoryQueryCache<u32>, partition: &str, key: &str, value: u32) {
+        let generation = cache.generation(partition).await;
+        cache.put(partition, key, generation, value).await;
+    }
+
+    #[tokio::test]
+    async fn it_should_return_cached_values_per_partition_and_key() {
+        let cache = InMemoryQueryCache::<u32>::new();
+        assert!(cache.is_empty().await);
+
+        put(&cache, "u1", "page-0", 1).await;
+        put(&cache, "u1", "page-1", 2).await;
+        put(&cache, "u2", "page-0", 3).await;
+
+        assert_eq!(cache.get("u1", "page-0").await, Some(1));
+        assert_eq!(cache.get("u1", "page-1").await, Some(2));
+        assert_eq!(cache.get("u2", "page-0").await, Some(3));
+        assert_eq!(cache.get("u2", "page-1").await, None);
+        assert_eq!(cache.get("u3", "page-0").await, None);
+        assert_eq!(cache.len().await, 3);
+    }
+
+    #[tokio::test]
+    async fn it_should_invalidate_a_single_partition() {
+        let cache = InMemoryQueryCache::<u32>::new();
+        put(&cache, "u1", "page-0", 1).await;
+        put(&cache, "u2", "page-0", 2).await;
+
+        cache.invalidate("u1").await;
+
+        assert_eq!(cache.get("u1", "page-0").await, None);
+        assert_eq!(cache.get("u2", "page-0").await, Some(2));
+        put(&cache, "u1", "page-0", 3).await;
+        assert_eq!(cache.get("u1", "page-0").await, Some(3));
+    }
+
+    #[tokio::test]
+    async fn it_should_invalidate_all_partitions() {
+        let cache = InMemoryQueryCache::<u32>::new();
+        put(&cache, "u1", "page-0", 1).await;
+        put(&cache, "u2", "page-0", 2).await;
+
+        cache.invalidate_all().await;
+
+        assert!(cache.is_empty().await);
+    }
+
+    #[tokio::test]
+    async fn it_should_skip_fills_loaded_before_an_invalidation() {
+        let cache = InMemoryQueryCache::<u32>::new();
+        let loaded_at = cache.generation("u1").await;
+
+        cache.invalidate("u1").await;
+        cache.put("u1", "page-0", loaded_at, 1).await;
+        assert_eq!(cache.get("u1", "page-0").await, None);
+
+        let loaded_at = cache.generation("u2").await;
+        cache.invalidate_all().await;
+        cache.put("u2", "page-0", loaded_at, 2).await;
+        assert_eq!(cache.get("u2", "page-0").await, None);
+    }
+
+    #[tokio::test]
+    async fn it_should_keep_invalidations_whose_marker_was_evicted() {
+        let cache = InMemoryQueryCache::<u32>::bounded(1, DEFAULT_TIME_TO_LIVE);
+        let loaded_at = cache.generation("u1").await;
+
+        cache.invalidate("u1").await;
+        cache.invalidate("u2").await;
+        cache.invalidated_at.run_pending_tasks().await;
+        cache.put("u1", "page-0", loaded_at, 1).await;
+
+        assert_eq!(cache.get("u1", "page-0").await, None);
+    }
+
+    #[tokio::test]
+    async fn it_should_drop_values_past_their_time_to_live() {
+        let cache = InMemoryQueryCache::<u32>::bounded(10, Duration::from_millis(20));
+        put(&cache, "u1", "page-0", 1).await;
+
+        tokio::time::sleep(Duration::from_millis(40)).await;
+
+        assert_eq!(cache.get("u1", "page-0").await, None);
+    }
+}
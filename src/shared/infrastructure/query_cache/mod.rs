@@ -0,0 +1,78 @@
+use async_trait::async_trait;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Read-through cache for query results.
+/// Entries are grouped per partition (typically a user id) so a projector can drop every
+/// cached page for that partition in one call when it writes rows for it.
+/// Caching is best-effort: implementations treat backend failures as misses.
+#[async_trait]
+pub trait QueryCache<V>: Send + Sync {
+    async fn get(&self, partition: &str, key: &str) -> Option<V>;
+
+    /// Where `partition` stands; every invalidation moves it on. Read it before loading the
+    /// value to `put`.
+    async fn generation(&self, partition: &str) -> u64;
+
+    /// Caches `value`, unless `partition` was invalidated after `generation`: a value loaded
+    /// before a projector write must not outlive it.
+    async fn put(&self, partition: &str, key: &str, generation: u64, value: V);
+
+    async fn invalidate(&self, partition: &str);
+
+    async fn invalidate_all(&self);
+}
+
+#[derive(Debug, Default)]
+struct QueryCacheMetricsInner {
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+/// Shared hit/miss counters; clones observe the same values.
+#[derive(Debug, Clone, Default)]
+pub struct QueryCacheMetrics {
+    inner: Arc<QueryCacheMetricsInner>,
+}
+
+impl QueryCacheMetrics {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn hits(&self) -> u64 {
+        self.inner.hits.load(Ordering::SeqCst)
+    }
+
+    pub fn misses(&self) -> u64 {
+        self.inner.misses.load(Ordering::SeqCst)
+    }
+
+    pub fn record_hit(&self) {
+        self.inner.hits.fetch_add(1, Ordering::SeqCst);
+    }
+
+    pub fn record_miss(&self) {
+        self.inner.misses.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+pub mod in_memory;
+
+#[cfg(test)]
+mod query_cache_metrics_tests {
+    use super::*;
+
+    #[test]
+    fn it_should_share_counters_between_clones() {
+        let metrics = QueryCacheMetrics::new();
+        let observer = metrics.clone();
+
+        metrics.record_hit();
+        metrics.record_miss();
+        metrics.record_miss();
+
+        assert_eq!(observer.hits(), 1);
+        assert_eq!(observer.misses(), 2);
+    }
+}
@@ -1,30 +1,161 @@
-use axum::extract::FromRequestParts;
-use axum::http::StatusCode;
+use axum::extract::{FromRequestParts, Request, State};
 use axum::http::request::Parts;
+use axum::http::{HeaderMap, HeaderName, HeaderValue, StatusCode};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+
+use crate::shared::auth::rbac::{Principal, Role, Scope};
+use crate::shared::infrastructure::api_key_store::{ApiKey, ApiKeyStore};
+
+pub const API_KEY_HEADER: &str = "x-api-key";
+
+/// Clients retrying a command send the same value in this header so it runs only once.
+pub const IDEMPOTENCY_KEY_HEADER: &str = "idempotency-key";
 
 pub struct RequestContext {
     pub user_id: String,
     pub tenant_id: String,
+    pub role: Role,
+    pub scope: Scope,
 }
 
-impl<S: Send + Sync> FromRequestParts<S> for RequestContext {
-    type Rejection = StatusCode;
+impl RequestContext {
+    pub fn principal(&self) -> Principal {
+        Principal::new(self.user_id.clone(), self.role).with_scope(self.scope)
+    }
 
-    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, StatusCode> {
-        let user_id = parts
-            .headers
+    /// An authenticated API key wins over identity headers; otherwise the caller is taken
+    /// from `x-user-id`, `x-tenant-id` and the optional `x-user-role` claim.
+    pub fn from_headers(headers: &HeaderMap, api_key: Option<&ApiKey>) -> Result<Self, StatusCode> {
+        if let Some(api_key) = api_key {
+            return Ok(RequestContext {
+                user_id: api_key.user_id.clone(),
+                tenant_id: api_key.tenant_id.clone(),
+                role: api_key.scope.role(),
+                scope: api_key.scope.scope(),
+            });
+        }
+        let user_id = headers
             .get("x-user-id")
             .and_then(|v| v.to_str().ok())
             .map(str::to_string)
             .ok_or(StatusCode::UNAUTHORIZED)?;
-        let tenant_id = parts
-            .headers
+        let tenant_id = headers
             .get("x-tenant-id")
             .and_then(|v| v.to_str().ok())
             .map(str::to_string)
             .ok_or(StatusCode::UNAUTHORIZED)?;
-        Ok(RequestContext { user_id, tenant_id })
+        // A missing role claim means the least privileged role; a malformed one is rejected.
+        let role = match headers.get("x-user-role") {
+            Some(value) => value
+                .to_str()
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .ok_or(StatusCode::UNAUTHORIZED)?,
+            None => Role::default(),
+        };
+        Ok(RequestContext {
+            user_id,
+            tenant_id,
+            role,
+            scope: Scope::Full,
+        })
+    }
+}
+
+impl<S: Send + Sync> FromRequestParts<S> for RequestContext {
+    type Rejection = StatusCode;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, StatusCode> {
+        RequestContext::from_headers(&parts.headers, parts.extensions.get::<ApiKey>())
+    }
+}
+
+/// The request's `Idempotency-Key`, if it carries one.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct IdempotencyKey(pub Option<String>);
+
+impl<S: Send + Sync> FromRequestParts<S> for IdempotencyKey {
+    type Rejection = std::convert::Infallible;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        _state: &S,
+    ) -> Result<Self, std::convert::Infallible> {
+        Ok(IdempotencyKey(
+            parts
+                .headers
+                .get(IDEMPOTENCY_KEY_HEADER)
+                .and_then(|v| v.to_str().ok())
+                .map(str::trim)
+                .filter(|key| !key.is_empty())
+                .map(str::to_string),
+        ))
+    }
+}
+
+/// Middleware that authenticates `X-Api-Key`. A known key is stored as a request extension
+/// for `RequestContext`, and as a response extension for the request log; an unknown or
+/// revoked key is rejected with 401. Requests without the header pass through untouched.
+pub async fn resolve_api_key<TStore>(
+    State(store): State<TStore>,
+    mut request: Request,
+    next: Next,
+) -> Response
+where
+    TStore: ApiKeyStore,
+{
+    let Some(value) = request.headers().get(API_KEY_HEADER) else {
+        return next.run(request).await;
+    };
+    let Ok(key) = value.to_str() else {
+        return StatusCode::UNAUTHORIZED.into_response();
+    };
+    match store.find(key).await {
+        Ok(Some(api_key)) => {
+            request.extensions_mut().insert(api_key.clone());
+            let mut response = next.run(request).await;
+            response.extensions_mut().insert(api_key);
+            response
+        }
+        Ok(None) => StatusCode::UNAUTHORIZED.into_response(),
+        Err(_) => StatusCode::INTERNAL_SERVER_ERROR.into_response(),
+    }
+}
+
+/// Authenticates a GraphQL WebSocket `connection_init` payload. Browsers cannot set headers
+/// on a WebSocket upgrade, so the payload carries them instead, as string members named like
+/// the headers (`x-api-key`, or `x-user-id`, `x-tenant-id` and `x-user-role`). They override
+/// the upgrade request's own headers, which non-browser clients may still use.
+pub async fn from_connection_init<TStore>(
+    store: &TStore,
+    payload: &serde_json::Value,
+    upgrade_headers: &HeaderMap,
+) -> Result<RequestContext, StatusCode>
+where
+    TStore: ApiKeyStore,
+{
+    let mut headers = upgrade_headers.clone();
+    for (name, value) in payload.as_object().into_iter().flatten() {
+        if let (Ok(name), Some(Ok(value))) = (
+            HeaderName::from_bytes(name.as_bytes()),
+            value.as_str().map(HeaderValue::from_str),
+        ) {
+            headers.insert(name, value);
+        }
     }
+    let api_key = match headers.get(API_KEY_HEADER) {
+        Some(value) => {
+            let key = value.to_str().map_err(|_| StatusCode::UNAUTHORIZED)?;
+            match store.find(key).await {
+                Ok(Some(api_key)) => Some(api_key),
+                Ok(None) => return Err(StatusCode::UNAUTHORIZED),
+                Err(_) => return Err(StatusCode::INTERNAL_SERVER_ERROR),
+            }
+        }
+        None => None,
+    };
+    RequestContext::from_headers(&headers, api_key.as_ref())
 }
 
 #[cfg(test)]
@@ -35,18 +166,52 @@ mod request_context_tests {
         http::{Request, StatusCode},
         routing::get,
     };
+    use http_body_util::BodyExt;
+    use rstest::rstest;
     use tower::ServiceExt;
 
-    use super::RequestContext;
+    use super::{RequestContext, from_connection_init, resolve_api_key};
+    use crate::shared::auth::rbac::{ApiKeyScope, Principal, Role, Scope};
+    use crate::shared::infrastructure::api_key_store::ApiKey;
+    use crate::shared::infrastructure::api_key_store::in_memory::InMemoryApiKeyStore;
+    use axum::http::HeaderMap;
+    use serde_json::{Value, json};
 
     async fn handler(ctx: RequestContext) -> String {
-        format!("{}:{}", ctx.user_id, ctx.tenant_id)
+        format!(
+            "{}:{}:{:?}:{:?}",
+            ctx.user_id, ctx.tenant_id, ctx.role, ctx.scope
+        )
     }
 
     fn app() -> Router {
         Router::new().route("/", get(handler))
     }
 
+    async fn app_with_api_keys() -> (Router, InMemoryApiKeyStore) {
+        let store = InMemoryApiKeyStore::new();
+        store
+            .insert(
+                "ci-secret",
+                ApiKey {
+                    user_id: "ci-bot".to_string(),
+                    tenant_id: "t-ci".to_string(),
+                    scope: ApiKeyScope::RegisterOnly,
+                },
+            )
+            .await;
+        let router = app().layer(axum::middleware::from_fn_with_state(
+            store.clone(),
+            resolve_api_key::<InMemoryApiKeyStore>,
+        ));
+        (router, store)
+    }
+
+    async fn body_of(response: axum::response::Response) -> String {
+        let bytes = response.into_body().collect().await.unwrap().to_bytes();
+        String::from_utf8(bytes.to_vec()).unwrap()
+    }
+
     #[tokio::test]
     async fn extracts_both_headers_successfully() {
         let response = app()
@@ -89,4 +254,179 @@ mod request_context_tests {
             .unwrap();
         assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
     }
+
+    #[rstest]
+    #[case::missing(None, StatusCode::OK, "u-1:t-1:Employee:Full")]
+    #[case::manager(Some("manager"), StatusCode::OK, "u-1:t-1:Manager:Full")]
+    #[case::unknown(Some("owner"), StatusCode::UNAUTHORIZED, "")]
+    #[tokio::test]
+    async fn extracts_the_role_claim(
+        #[case] role: Option<&str>,
+        #[case] status: StatusCode,
+        #[case] body: &str,
+    ) {
+        let mut request = Request::get("/")
+            .header("x-user-id", "u-1")
+            .header("x-tenant-id", "t-1");
+        if let Some(role) = role {
+            request = request.header("x-user-role", role);
+        }
+        let response = app()
+            .oneshot(request.body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), status);
+        let bytes = response.into_body().collect().await.unwrap().to_bytes();
+        assert_eq!(bytes, body.as_bytes());
+    }
+
+    #[test]
+    fn builds_a_principal_from_the_context() {
+        let ctx = RequestContext {
+            user_id: "u-1".to_string(),
+            tenant_id: "t-1".to_string(),
+            role: Role::Admin,
+            scope: Scope::ReadOnly,
+        };
+        assert_eq!(
+            ctx.principal(),
+            Principal::new("u-1", Role::Admin).with_scope(Scope::ReadOnly)
+        );
+    }
+
+    #[tokio::test]
+    async fn authenticates_as_the_api_key_owner_over_identity_headers() {
+        let (router, _) = app_with_api_keys().await;
+        let response = router
+            .oneshot(
+                Request::get("/")
+                    .header("x-api-key", "ci-secret")
+                    .header("x-user-id", "u-1")
+                    .header("x-tenant-id", "t-1")
+                    .header("x-user-role", "admin")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(body_of(response).await, "ci-bot:t-ci:Employee:RegisterOnly");
+    }
+
+    #[tokio::test]
+    async fn passes_requests_without_an_api_key_through() {
+        let (router, _) = app_with_api_keys().await;
+        let response = router
+            .oneshot(
+                Request::get("/")
+                    .header("x-user-id", "u-1")
+                    .header("x-tenant-id", "t-1")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(body_of(response).await, "u-1:t-1:Employee:Full");
+    }
+
+    #[rstest]
+    #[case::unknown(b"wrong".as_slice())]
+    #[case::not_visible_ascii(b"\xff".as_slice())]
+    #[tokio::test]
+    async fn rejects_unknown_api_keys(#[case] key: &[u8]) {
+        let (router, _) = app_with_api_keys().await;
+        let response = router
+            .oneshot(
+                Request::get("/")
+                    .header(
+                        "x-api-key",
+                        axum::http::HeaderValue::from_bytes(key).unwrap(),
+                    )
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    }
+
+    #[tokio::test]
+    async fn returns_500_when_the_api_key_store_is_offline() {
+        let (router, store) = app_with_api_keys().await;
+        store.toggle_offline();
+        let response = router
+            .oneshot(
+                Request::get("/")
+                    .header("x-api-key", "ci-secret")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+    }
+
+    fn described(ctx: Result<RequestContext, StatusCode>) -> Result<String, StatusCode> {
+        ctx.map(|ctx| {
+            format!(
+                "{}:{}:{:?}:{:?}",
+                ctx.user_id, ctx.tenant_id, ctx.role, ctx.scope
+            )
+        })
+    }
+
+    #[rstest]
+    #[case::identity(
+        json!({ "x-user-id": "u-1", "x-tenant-id": "t-1", "x-user-role": "manager" }),
+        Ok("u-1:t-1:Manager:Full")
+    )]
+    #[case::api_key(
+        json!({ "x-api-key": "ci-secret", "x-user-id": "u-1" }),
+        Ok("ci-bot:t-ci:Employee:RegisterOnly")
+    )]
+    #[case::overrides_upgrade_headers(
+        json!({ "x-user-id": "u-2", "x-tenant-id": "t-2" }),
+        Ok("u-2:t-2:Employee:Full")
+    )]
+    #[case::falls_back_to_upgrade_headers(json!({}), Ok("u-upgrade:t-upgrade:Employee:Full"))]
+    #[case::no_payload(Value::Null, Ok("u-upgrade:t-upgrade:Employee:Full"))]
+    #[case::ignores_non_strings(
+        json!({ "x-user-id": 7, "bad header": "x" }),
+        Ok("u-upgrade:t-upgrade:Employee:Full")
+    )]
+    #[case::unknown_api_key(json!({ "x-api-key": "wrong" }), Err(StatusCode::UNAUTHORIZED))]
+    #[case::non_ascii_api_key(json!({ "x-api-key": "clé" }), Err(StatusCode::UNAUTHORIZED))]
+    #[case::unknown_role(json!({ "x-user-role": "owner" }), Err(StatusCode::UNAUTHORIZED))]
+    #[tokio::test]
+    async fn authenticates_websocket_connection_init_payloads(
+        #[case] payload: Value,
+        #[case] expected: Result<&str, StatusCode>,
+    ) {
+        let (_, store) = app_with_api_keys().await;
+        let mut upgrade_headers = HeaderMap::new();
+        upgrade_headers.insert("x-user-id", "u-upgrade".parse().unwrap());
+        upgrade_headers.insert("x-tenant-id", "t-upgrade".parse().unwrap());
+
+        let ctx = from_connection_init(&store, &payload, &upgrade_headers).await;
+
+        assert_eq!(described(ctx), expected.map(str::to_string));
+    }
+
+    #[rstest]
+    #[case::anonymous(json!({}), StatusCode::UNAUTHORIZED)]
+    #[case::store_offline(json!({ "x-api-key": "ci-secret" }), StatusCode::INTERNAL_SERVER_ERROR)]
+    #[tokio::test]
+    async fn rejects_websocket_connection_init_payloads(
+        #[case] payload: Value,
+        #[case] status: StatusCode,
+    ) {
+        let (_, store) = app_with_api_keys().await;
+        if status == StatusCode::INTERNAL_SERVER_ERROR {
+            store.toggle_offline();
+        }
+
+        let ctx = from_connection_init(&store, &payload, &HeaderMap::new()).await;
+
+        assert_eq!(described(ctx), Err(status));
+    }
 }
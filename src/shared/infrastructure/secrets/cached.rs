@@ -0,0 +1,275 @@
+use crate::shared::infrastructure::clock::{SharedClock, SystemClock};
+use crate::shared::infrastructure::secrets::{Secret, SecretsError, SecretsProvider};
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::RwLock;
+
+struct CachedSecret {
+    secret: Secret,
+    /// Epoch millis from which the value is refetched; `None` never expires.
+    renew_at: Option<i64>,
+    /// Epoch millis after which the value must not be served.
+    expires_at: Option<i64>,
+}
+
+/// Caches another provider's secrets so startup and hot paths do not call the backend each
+/// time. A secret with a TTL (its own, else `default_ttl`) is refetched once it is within
+/// `renew_before` of expiring, or past half its TTL for short leases; if that fetch fails the
+/// cached value is served until it actually expires. Clones share the cache.
+#[derive(Clone)]
+pub struct CachedSecrets<TProvider> {
+    inner: TProvider,
+    clock: SharedClock,
+    default_ttl: Option<Duration>,
+    renew_before: Duration,
+    entries: Arc<RwLock<HashMap<String, CachedSecret>>>,
+}
+
+impl<TProvider: SecretsProvider> CachedSecrets<TProvider> {
+    pub fn new(inner: TProvider) -> Self {
+        Self {
+            inner,
+            clock: Arc::new(SystemClock),
+            default_ttl: None,
+            renew_before: Duration::from_secs(60),
+            entries: Arc::default(),
+        }
+    }
+
+    /// The TTL for secrets the backend does not lease, so rotated values are picked up.
+    pub fn with_default_ttl(mut self, ttl: Duration) -> Self {
+        self.default_ttl = Some(ttl);
+        self
+    }
+
+    pub fn with_renew_before(mut self, renew_before: Duration) -> Self {
+        self.renew_before = renew_before;
+        self
+    }
+
+    pub fn with_clock(mut self, clock: SharedClock) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    fn now(&self) -> i64 {
+        self.clock.now().as_millis()
+    }
+
+    fn cache(&self, secret: Secret, now: i64) -> CachedSecret {
+        let ttl = secret.ttl.or(self.default_ttl);
+        CachedSecret {
+            secret,
+            renew_at: ttl
+                .map(|ttl| now + (ttl - self.renew_before.min(ttl / 2)).as_millis() as i64),
+            expires_at: ttl.map(|ttl| now + ttl.as_millis() as i64),
+        }
+    }
+
+    fn is_due(cached: &CachedSecret, now: i64) -> bool {
+        cached.renew_at.is_some_and(|renew_at| now >= renew_at)
+    }
+
+    async fn fetch(&self, name: &str) -> Result<Secret, SecretsError> {
+        let fetched = self.inner.get(name).await;
+        let now = self.now();
+        let mut entries = self.entries.write().await;
+        match fetched {
+            Ok(secret) => {
+                entries.insert(name.to_string(), self.cache(secret.clone(), now));
+                Ok(secret)
+            }
+            Err(reason) => match entries.get(name) {
+                Some(cached) if cached.expires_at.is_none_or(|expires_at| now < expires_at) => {
+                    tracing::warn!(name, %reason, "secret renewal failed, serving the cached value");
+                    Ok(cached.secret.clone())
+                }
+                _ => {
+                    entries.remove(name);
+                    Err(reason)
+                }
+            },
+        }
+    }
+
+    /// Refetches every cached secret that is due for renewal. Returns how many were renewed
+    /// and the names that failed; those keep their value until it expires.
+    pub async fn renew_expiring(&self) -> (usize, Vec<String>) {
+        let now = self.now();
+        let due: Vec<String> = self
+            .entries
+            .read()
+            .await
+            .iter()
+            .filter(|(_, cached)| Self::is_due(cached, now))
+            .map(|(name, _)| name.clone())
+            .collect();
+        let mut renewed = 0;
+        let mut failed = Vec::new();
+        for name in due {
+            match self.inner.get(&name).await {
+                Ok(secret) => {
+                    let cached = self.cache(secret, self.now());
+                    self.entries.write().await.insert(name, cached);
+                    renewed += 1;
+                }
+                Err(reason) => {
+                    tracing::warn!(name, %reason, "secret renewal failed");
+                    failed.push(name);
+                }
+            }
+        }
+        (renewed, failed)
+    }
+}
+
+#[async_trait]
+impl<TProvider: SecretsProvider> SecretsProvider for CachedSecrets<TProvider> {
+    async fn get(&self, name: &str) -> Result<Secret, SecretsError> {
+        let now = self.now();
+        if let Some(cached) = self.entries.read().await.get(name)
+            && !Self::is_due(cached, now)
+        {
+            return Ok(cached.secret.clone());
+        }
+        self.fetch(name).await
+    }
+}
+
+#[cfg(test)]
+mod cached_secrets_tests {
+    use super::*;
+    use crate::shared::infrastructure::clock::FixedClock;
+    use rstest::rstest;
+    use std::sync::Mutex;
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    /// Returns `{name}-{n}` for the n-th fetch of a name, leased for `ttl`.
+    #[derive(Clone, Default)]
+    struct CountingProvider {
+        ttl: Option<Duration>,
+        fetches: Arc<Mutex<HashMap<String, usize>>>,
+        is_offline: Arc<AtomicBool>,
+    }
+
+    #[async_trait]
+    impl SecretsProvider for CountingProvider {
+        async fn get(&self, name: &str) -> Result<Secret, SecretsError> {
+            if self.is_offline.load(Ordering::SeqCst) {
+                return Err(SecretsError::Backend("vault sealed".to_string()));
+            }
+            let mut fetches = self.fetches.lock().unwrap();
+            let count = fetches.entry(name.to_string()).or_default();
+            *count += 1;
+            Ok(Secret {
+                value: format!("{name}-{count}"),
+                ttl: self.ttl,
+            })
+        }
+    }
+
+    fn leased(seconds: u64) -> (CountingProvider, FixedClock) {
+        let provider = CountingProvider {
+            ttl: Some(Duration::from_secs(seconds)),
+            ..CountingProvider::default()
+        };
+        (provider, FixedClock::at(0))
+    }
+
+    fn cache(provider: &CountingProvider, clock: &FixedClock) -> CachedSecrets<CountingProvider> {
+        CachedSecrets::new(provider.clone())
+            .with_renew_before(Duration::from_secs(10))
+            .with_clock(Arc::new(clock.clone()))
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_cache_unleased_secrets_forever() {
+        let provider = CountingProvider::default();
+        let clock = FixedClock::at(0);
+        let secrets = cache(&provider, &clock);
+
+        assert_eq!(secrets.get("db").await.unwrap().value, "db-1");
+        clock.advance(86_400_000);
+        assert_eq!(secrets.get("db").await.unwrap().value, "db-1");
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_refetch_shortly_before_the_lease_expires() {
+        let (provider, clock) = leased(60);
+        let secrets = cache(&provider, &clock);
+
+        assert_eq!(secrets.get("db").await.unwrap().value, "db-1");
+        clock.advance(49_000);
+        assert_eq!(secrets.get("db").await.unwrap().value, "db-1");
+        clock.advance(1_000);
+        assert_eq!(secrets.get("db").await.unwrap().value, "db-2");
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_renew_short_leases_halfway() {
+        let (provider, clock) = leased(10);
+        let secrets = cache(&provider, &clock);
+
+        secrets.get("db").await.unwrap();
+        clock.advance(4_000);
+        assert_eq!(secrets.get("db").await.unwrap().value, "db-1");
+        clock.advance(1_000);
+        assert_eq!(secrets.get("db").await.unwrap().value, "db-2");
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_apply_the_default_ttl_to_unleased_secrets() {
+        let provider = CountingProvider::default();
+        let clock = FixedClock::at(0);
+        let secrets = cache(&provider, &clock).with_default_ttl(Duration::from_secs(300));
+
+        secrets.get("db").await.unwrap();
+        clock.advance(290_000);
+        assert_eq!(secrets.get("db").await.unwrap().value, "db-2");
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_serve_the_cached_value_until_it_expires_when_renewal_fails() {
+        let (provider, clock) = leased(60);
+        let secrets = cache(&provider, &clock);
+        secrets.get("db").await.unwrap();
+        provider.is_offline.store(true, Ordering::SeqCst);
+
+        clock.advance(55_000);
+        assert_eq!(secrets.get("db").await.unwrap().value, "db-1");
+        clock.advance(5_000);
+        assert_eq!(
+            secrets.get("db").await,
+            Err(SecretsError::Backend("vault sealed".to_string()))
+        );
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_renew_only_expiring_secrets() {
+        let (provider, clock) = leased(60);
+        let secrets = cache(&provider, &clock);
+        secrets.get("db").await.unwrap();
+        clock.advance(30_000);
+        secrets.get("broker").await.unwrap();
+
+        clock.advance(20_000);
+        assert_eq!(secrets.renew_expiring().await, (1, vec![]));
+        assert_eq!(secrets.get("db").await.unwrap().value, "db-2");
+        assert_eq!(secrets.get("broker").await.unwrap().value, "broker-1");
+
+        clock.advance(30_000);
+        provider.is_offline.store(true, Ordering::SeqCst);
+        assert_eq!(
+            secrets.renew_expiring().await,
+            (0, vec!["broker".to_string()])
+        );
+    }
+}
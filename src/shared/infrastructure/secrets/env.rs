@@ -0,0 +1,69 @@
+use crate::shared::infrastructure::secrets::{Secret, SecretsError, SecretsProvider};
+use async_trait::async_trait;
+use std::collections::HashMap;
+
+/// Secrets from environment variables, `database_url` read from `{prefix}DATABASE_URL`.
+/// The fallback for local development; prefer files or Vault elsewhere.
+#[derive(Clone)]
+pub struct EnvSecrets {
+    prefix: String,
+    vars: HashMap<String, String>,
+}
+
+impl EnvSecrets {
+    /// Snapshots the process environment.
+    pub fn new(prefix: impl Into<String>) -> Self {
+        Self::from_vars(prefix, std::env::vars())
+    }
+
+    pub fn from_vars(
+        prefix: impl Into<String>,
+        vars: impl IntoIterator<Item = (String, String)>,
+    ) -> Self {
+        Self {
+            prefix: prefix.into(),
+            vars: vars.into_iter().collect(),
+        }
+    }
+}
+
+#[async_trait]
+impl SecretsProvider for EnvSecrets {
+    async fn get(&self, name: &str) -> Result<Secret, SecretsError> {
+        let var = format!("{}{}", self.prefix, name.to_ascii_uppercase());
+        self.vars
+            .get(&var)
+            .map(Secret::new)
+            .ok_or_else(|| SecretsError::NotFound(name.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod env_secrets_tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_read_prefixed_uppercase_variables() {
+        let secrets = EnvSecrets::from_vars(
+            "SECRET_",
+            [
+                (
+                    "SECRET_DATABASE_URL".to_string(),
+                    "postgres://db".to_string(),
+                ),
+                ("DATABASE_URL".to_string(), "unprefixed".to_string()),
+            ],
+        );
+
+        assert_eq!(
+            secrets.get("database_url").await,
+            Ok(Secret::new("postgres://db"))
+        );
+        assert_eq!(
+            secrets.get("jwt_signing_key").await,
+            Err(SecretsError::NotFound("jwt_signing_key".to_string()))
+        );
+    }
+}
@@ -0,0 +1,82 @@
+use crate::shared::infrastructure::secrets::{Secret, SecretsError, SecretsProvider};
+use async_trait::async_trait;
+use std::path::PathBuf;
+
+/// Secrets mounted as files, one per name, such as Docker secrets in `/run/secrets` or a
+/// Kubernetes secret volume. Trailing newlines are dropped. Files are read on every call, so
+/// a rotated mount is picked up by the next uncached read.
+#[derive(Clone)]
+pub struct FileSecrets {
+    dir: PathBuf,
+}
+
+impl FileSecrets {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+}
+
+#[async_trait]
+impl SecretsProvider for FileSecrets {
+    async fn get(&self, name: &str) -> Result<Secret, SecretsError> {
+        if name.is_empty() || name.contains(['/', '\\']) || name.starts_with('.') {
+            return Err(SecretsError::NotFound(name.to_string()));
+        }
+        let path = self.dir.join(name);
+        match std::fs::read_to_string(&path) {
+            Ok(value) => Ok(Secret::new(value.trim_end_matches(['\r', '\n']))),
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => {
+                Err(SecretsError::NotFound(name.to_string()))
+            }
+            Err(error) => Err(SecretsError::Backend(format!(
+                "{}: {error}",
+                path.display()
+            ))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod file_secrets_tests {
+    use super::*;
+    use rstest::rstest;
+
+    fn secrets_dir(test: &str) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("file-secrets-{test}-{}", uuid::Uuid::now_v7()));
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_read_one_file_per_secret() {
+        let dir = secrets_dir("read");
+        std::fs::write(dir.join("broker_password"), "s3cret\n").unwrap();
+        let secrets = FileSecrets::new(&dir);
+
+        assert_eq!(
+            secrets.get("broker_password").await,
+            Ok(Secret::new("s3cret"))
+        );
+        assert_eq!(
+            secrets.get("database_url").await,
+            Err(SecretsError::NotFound("database_url".to_string()))
+        );
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[rstest]
+    #[case::parent("../passwd")]
+    #[case::nested("nested/secret")]
+    #[case::hidden(".env")]
+    #[case::empty("")]
+    #[tokio::test]
+    async fn it_should_not_leave_the_directory(#[case] name: &str) {
+        let secrets = FileSecrets::new(std::env::temp_dir());
+        assert_eq!(
+            secrets.get(name).await,
+            Err(SecretsError::NotFound(name.to_string()))
+        );
+    }
+}
@@ -0,0 +1,61 @@
+use async_trait::async_trait;
+use std::time::Duration;
+use thiserror::Error;
+
+#[derive(Debug, Clone, Error, PartialEq, Eq)]
+pub enum SecretsError {
+    #[error("secret not found: {0}")]
+    NotFound(String),
+
+    #[error("backend error: {0}")]
+    Backend(String),
+}
+
+/// A credential such as a database URL, broker password or signing key.
+#[derive(Clone, PartialEq, Eq)]
+pub struct Secret {
+    pub value: String,
+    /// How long the value stays valid, for leased secrets; `None` until it is rotated.
+    pub ttl: Option<Duration>,
+}
+
+impl Secret {
+    pub fn new(value: impl Into<String>) -> Self {
+        Self {
+            value: value.into(),
+            ttl: None,
+        }
+    }
+}
+
+impl std::fmt::Debug for Secret {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Secret")
+            .field("ttl", &self.ttl)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Where the shell fetches its credentials at startup (the environment, mounted files,
+/// Vault), so they need not sit in plain environment variables. Names are lowercase
+/// `snake_case`, such as `database_url`.
+#[async_trait]
+pub trait SecretsProvider: Send + Sync {
+    async fn get(&self, name: &str) -> Result<Secret, SecretsError>;
+}
+
+pub mod cached;
+pub mod env;
+pub mod file;
+pub mod vault;
+
+#[cfg(test)]
+mod secret_tests {
+    use super::*;
+
+    #[test]
+    fn it_should_not_print_the_value() {
+        let secret = Secret::new("postgres://app:hunter2@db/app");
+        assert_eq!(format!("{secret:?}"), "Secret { ttl: None, .. }");
+    }
+}
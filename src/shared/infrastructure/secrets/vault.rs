@@ -0,0 +1,200 @@
+use crate::shared::infrastructure::calendar::http::HttpResponse;
+use crate::shared::infrastructure::secrets::{Secret, SecretsError, SecretsProvider};
+use async_trait::async_trait;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::time::Duration;
+
+/// The transport the Vault adapter uses, so it stays independent of an HTTP client crate.
+/// Implementations send `token` as the `X-Vault-Token` header.
+#[async_trait]
+pub trait VaultHttp: Send + Sync {
+    async fn get(&self, url: &str, token: &str) -> Result<HttpResponse, String>;
+}
+
+#[derive(Deserialize)]
+struct KvResponse {
+    data: KvData,
+    #[serde(default)]
+    lease_duration: u64,
+}
+
+#[derive(Deserialize)]
+struct KvData {
+    data: HashMap<String, serde_json::Value>,
+}
+
+const DEFAULT_FIELD: &str = "value";
+
+/// Secrets from a Vault KV version 2 engine mounted at `mount`. The name `database_url` reads
+/// field `value` of `GET {address}/v1/{mount}/data/database_url`; `broker#password` reads
+/// field `password` of the secret `broker`. A positive `lease_duration` becomes the TTL.
+#[derive(Clone)]
+pub struct VaultSecrets<TClient> {
+    address: String,
+    mount: String,
+    token: String,
+    client: TClient,
+}
+
+impl<TClient: VaultHttp> VaultSecrets<TClient> {
+    pub fn new(
+        address: impl Into<String>,
+        mount: impl Into<String>,
+        token: impl Into<String>,
+        client: TClient,
+    ) -> Self {
+        Self {
+            address: address.into().trim_end_matches('/').to_string(),
+            mount: mount.into().trim_matches('/').to_string(),
+            token: token.into(),
+            client,
+        }
+    }
+}
+
+#[async_trait]
+impl<TClient: VaultHttp> SecretsProvider for VaultSecrets<TClient> {
+    async fn get(&self, name: &str) -> Result<Secret, SecretsError> {
+        let (path, field) = name.split_once('#').unwrap_or((name, DEFAULT_FIELD));
+        let url = format!("{}/v1/{}/data/{path}", self.address, self.mount);
+        let response = self
+            .client
+            .get(&url, &self.token)
+            .await
+            .map_err(SecretsError::Backend)?;
+        match response.status {
+            200 => {}
+            404 => return Err(SecretsError::NotFound(name.to_string())),
+            status => {
+                return Err(SecretsError::Backend(format!(
+                    "GET {url} returned {status}"
+                )));
+            }
+        }
+        let kv: KvResponse = serde_json::from_str(&response.body)
+            .map_err(|error| SecretsError::Backend(format!("GET {url}: {error}")))?;
+        let value = match kv.data.data.get(field) {
+            Some(serde_json::Value::String(value)) => value.clone(),
+            Some(_) => {
+                return Err(SecretsError::Backend(format!(
+                    "{name} is not a string in Vault"
+                )));
+            }
+            None => return Err(SecretsError::NotFound(name.to_string())),
+        };
+        Ok(Secret {
+            value,
+            ttl: (kv.lease_duration > 0).then(|| Duration::from_secs(kv.lease_duration)),
+        })
+    }
+}
+
+#[cfg(test)]
+mod vault_secrets_tests {
+    use super::*;
+    use rstest::rstest;
+    use std::sync::{Arc, Mutex};
+
+    #[derive(Clone, Default)]
+    struct StubClient {
+        responses: Arc<HashMap<String, Result<HttpResponse, String>>>,
+        tokens: Arc<Mutex<Vec<String>>>,
+    }
+
+    impl StubClient {
+        fn with(responses: &[(&str, Result<HttpResponse, String>)]) -> Self {
+            Self {
+                responses: Arc::new(
+                    responses
+                        .iter()
+                        .map(|(url, response)| (url.to_string(), response.clone()))
+                        .collect(),
+                ),
+                tokens: Arc::default(),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl VaultHttp for StubClient {
+        async fn get(&self, url: &str, token: &str) -> Result<HttpResponse, String> {
+            self.tokens.lock().unwrap().push(token.to_string());
+            self.responses.get(url).cloned().unwrap_or(Ok(HttpResponse {
+                status: 404,
+                body: String::new(),
+            }))
+        }
+    }
+
+    fn ok(body: &str) -> Result<HttpResponse, String> {
+        Ok(HttpResponse {
+            status: 200,
+            body: body.to_string(),
+        })
+    }
+
+    const DATABASE_URL: &str = "https://vault.test/v1/secret/data/database_url";
+    const BROKER: &str = "https://vault.test/v1/secret/data/broker";
+
+    fn secrets(client: StubClient) -> VaultSecrets<StubClient> {
+        VaultSecrets::new("https://vault.test/", "/secret/", "s.token", client)
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_read_the_value_field_with_the_token() {
+        let client = StubClient::with(&[(
+            DATABASE_URL,
+            ok(r#"{"data":{"data":{"value":"postgres://db"},"metadata":{}}}"#),
+        )]);
+        let tokens = client.tokens.clone();
+
+        assert_eq!(
+            secrets(client).get("database_url").await,
+            Ok(Secret::new("postgres://db"))
+        );
+        assert_eq!(*tokens.lock().unwrap(), vec!["s.token".to_string()]);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_read_a_named_field_with_its_lease() {
+        let client = StubClient::with(&[(
+            BROKER,
+            ok(r#"{"data":{"data":{"password":"hunter2"}},"lease_duration":3600}"#),
+        )]);
+
+        assert_eq!(
+            secrets(client).get("broker#password").await,
+            Ok(Secret {
+                value: "hunter2".to_string(),
+                ttl: Some(Duration::from_secs(3600)),
+            })
+        );
+    }
+
+    #[rstest]
+    #[case::missing_secret("jwt_signing_key", SecretsError::NotFound("jwt_signing_key".to_string()))]
+    #[case::missing_field("broker#username", SecretsError::NotFound("broker#username".to_string()))]
+    #[case::not_a_string("broker#port", SecretsError::Backend("broker#port is not a string in Vault".to_string()))]
+    #[case::sealed("database_url", SecretsError::Backend(format!("GET {DATABASE_URL} returned 503")))]
+    #[tokio::test]
+    async fn it_should_report_failures(#[case] name: &str, #[case] expected: SecretsError) {
+        let client = StubClient::with(&[
+            (
+                BROKER,
+                ok(r#"{"data":{"data":{"password":"hunter2","port":5672}}}"#),
+            ),
+            (
+                DATABASE_URL,
+                Ok(HttpResponse {
+                    status: 503,
+                    body: String::new(),
+                }),
+            ),
+        ]);
+
+        assert_eq!(secrets(client).get(name).await, Err(expected));
+    }
+}
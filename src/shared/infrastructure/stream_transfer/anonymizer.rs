@@ -0,0 +1,250 @@
+// Makes exported events safe to load into test environments.
+//
+// Works on the JSON of each exported event by field name, at any depth, so it needs no
+// knowledge of the event shapes. User id fields get a
+// pseudonym, the same one for the same id throughout the export, so an entry's owner and the
+// people who changed it still line up. Free-text fields are masked. Timestamp fields shift by
+// a random offset drawn per stream within the policy's jitter, the same for every timestamp
+// of that stream, so an entry keeps its duration, breaks and the order of its history.
+
+use serde::Deserialize;
+use serde_json::Value as Json;
+use std::collections::HashMap;
+
+use crate::shared::core::redaction::MASK;
+use crate::shared::infrastructure::event_archiver::ArchivedEvent;
+
+/// Which fields to anonymize and how, read from a JSON file; fields left out keep their
+/// defaults.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default)]
+pub struct AnonymizationPolicy {
+    /// Fields holding user ids.
+    pub pseudonymize: Vec<String>,
+    /// Free-text fields.
+    pub scrub: Vec<String>,
+    /// Timestamp fields, in milliseconds.
+    pub jitter: Vec<String>,
+    /// The largest shift either way.
+    pub max_jitter_ms: i64,
+    /// Seeds the shifts; a random one when unset, so the shifts cannot be worked out by
+    /// running the export again.
+    pub seed: Option<u64>,
+}
+
+impl Default for AnonymizationPolicy {
+    fn default() -> Self {
+        let fields = |names: &[&str]| names.iter().map(|name| name.to_string()).collect();
+        Self {
+            pseudonymize: fields(&[
+                "user_id",
+                "created_by",
+                "updated_by",
+                "deleted_by",
+                "approved_by",
+                "rejected_by",
+                "added_by",
+            ]),
+            scrub: fields(&["description", "body", "file_name", "reason"]),
+            jitter: fields(&[
+                "occurred_at",
+                "created_at",
+                "updated_at",
+                "deleted_at",
+                "approved_at",
+                "rejected_at",
+                "added_at",
+                "started_at",
+                "ended_at",
+                "raw_started_at",
+                "raw_ended_at",
+                "stopped_at",
+                "compacted_at",
+            ]),
+            max_jitter_ms: 6 * 60 * 60 * 1000,
+            seed: None,
+        }
+    }
+}
+
+pub struct Anonymizer {
+    policy: AnonymizationPolicy,
+    seed: u64,
+    pseudonyms: HashMap<String, String>,
+}
+
+impl Anonymizer {
+    pub fn new(policy: AnonymizationPolicy) -> Self {
+        let seed = policy
+            .seed
+            .unwrap_or_else(|| uuid::Uuid::now_v7().as_u128() as u64);
+        Self {
+            policy,
+            seed,
+            pseudonyms: HashMap::new(),
+        }
+    }
+
+    pub fn anonymize(&mut self, mut archived: ArchivedEvent<Json>) -> ArchivedEvent<Json> {
+        let shift = self.shift(&archived.stream_id);
+        self.walk(&mut archived.event, shift);
+        archived
+    }
+
+    /// The stream's shift, within the jitter either way.
+    fn shift(&self, stream_id: &str) -> i64 {
+        let max = self.policy.max_jitter_ms.max(0);
+        if max == 0 {
+            return 0;
+        }
+        let span = 2 * max as u64 + 1;
+        (fnv1a(self.seed, stream_id) % span) as i64 - max
+    }
+
+    fn walk(&mut self, json: &mut Json, shift: i64) {
+        match json {
+            Json::Object(map) => {
+                for (key, value) in map.iter_mut() {
+                    let key = key.as_str();
+                    match value {
+                        Json::String(id) if self.policy.pseudonymize.iter().any(|f| f == key) => {
+                            *id = self.pseudonym(std::mem::take(id));
+                        }
+                        Json::String(text) if self.policy.scrub.iter().any(|f| f == key) => {
+                            *text = MASK.to_string();
+                        }
+                        Json::Number(at) if self.policy.jitter.iter().any(|f| f == key) => {
+                            if let Some(ms) = at.as_i64() {
+                                *at = (ms + shift).into();
+                            }
+                        }
+                        _ => self.walk(value, shift),
+                    }
+                }
+            }
+            Json::Array(items) => items.iter_mut().for_each(|item| self.walk(item, shift)),
+            _ => {}
+        }
+    }
+
+    /// `person-1`, `person-2`, … in the order ids are first seen.
+    fn pseudonym(&mut self, id: String) -> String {
+        let next = self.pseudonyms.len() + 1;
+        self.pseudonyms
+            .entry(id)
+            .or_insert_with(|| format!("person-{next}"))
+            .clone()
+    }
+}
+
+/// FNV-1a over the seed and `value`: stable across builds, unlike the std hasher.
+fn fnv1a(seed: u64, value: &str) -> u64 {
+    seed.to_le_bytes()
+        .iter()
+        .chain(value.as_bytes())
+        .fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+            (hash ^ u64::from(*byte)).wrapping_mul(0x0000_0100_0000_01b3)
+        })
+}
+
+#[cfg(test)]
+mod anonymizer_tests {
+    use super::*;
+    use rstest::rstest;
+    use serde_json::json;
+
+    fn exported(stream_id: &str, event: Json) -> ArchivedEvent<Json> {
+        ArchivedEvent {
+            global_position: 0,
+            stream_id: stream_id.to_string(),
+            stream_version: 1,
+            event,
+        }
+    }
+
+    fn anonymizer(max_jitter_ms: i64) -> Anonymizer {
+        Anonymizer::new(AnonymizationPolicy {
+            max_jitter_ms,
+            seed: Some(7),
+            ..AnonymizationPolicy::default()
+        })
+    }
+
+    #[rstest]
+    fn it_should_pseudonymize_user_ids_consistently_and_scrub_free_text() {
+        let mut anonymizer = anonymizer(0);
+
+        let initiated = anonymizer.anonymize(exported(
+            "TimeEntry-te-1",
+            json!({ "type": "TimeEntryInitiatedV1", "user_id": "alice", "created_at": 1 }),
+        ));
+        let commented = anonymizer.anonymize(exported(
+            "TimeEntry-te-2",
+            json!({ "type": "TimeEntryCommentAddedV1", "body": "call Carol", "added_by": "bob" }),
+        ));
+        let approved = anonymizer.anonymize(exported(
+            "TimeEntry-te-1",
+            json!({ "type": "TimeEntryApprovedV1", "approved_by": "alice" }),
+        ));
+
+        assert_eq!(
+            initiated.event,
+            json!({ "type": "TimeEntryInitiatedV1", "user_id": "person-1", "created_at": 1 })
+        );
+        assert_eq!(
+            commented.event,
+            json!({ "type": "TimeEntryCommentAddedV1", "body": MASK, "added_by": "person-2" })
+        );
+        assert_eq!(
+            approved.event,
+            json!({ "type": "TimeEntryApprovedV1", "approved_by": "person-1" })
+        );
+    }
+
+    #[rstest]
+    fn it_should_shift_the_timestamps_of_a_stream_alike_within_the_jitter() {
+        let max_jitter_ms = 60_000;
+        let mut anonymizer = anonymizer(max_jitter_ms);
+
+        let breaks_set = anonymizer.anonymize(exported(
+            "TimeEntry-te-1",
+            json!({
+                "breaks": [{ "started_at": 1_000_000, "ended_at": 1_600_000 }],
+                "updated_at": 2_000_000,
+                "raw_started_at": null,
+            }),
+        ));
+        let deleted = anonymizer.anonymize(exported(
+            "TimeEntry-te-1",
+            json!({ "deleted_at": 3_000_000 }),
+        ));
+
+        let at = |json: &Json, pointer: &str| json.pointer(pointer).unwrap().as_i64().unwrap();
+        let shift = at(&breaks_set.event, "/updated_at") - 2_000_000;
+        assert!(shift.abs() <= max_jitter_ms);
+        assert_eq!(
+            at(&breaks_set.event, "/breaks/0/started_at"),
+            1_000_000 + shift
+        );
+        assert_eq!(
+            at(&breaks_set.event, "/breaks/0/ended_at"),
+            1_600_000 + shift
+        );
+        assert_eq!(breaks_set.event["raw_started_at"], Json::Null);
+        assert_eq!(at(&deleted.event, "/deleted_at"), 3_000_000 + shift);
+    }
+
+    #[rstest]
+    fn it_should_read_a_policy_keeping_the_defaults_left_out() {
+        let policy: AnonymizationPolicy =
+            serde_json::from_str(r#"{ "scrub": ["body"], "max_jitter_ms": 0 }"#).unwrap();
+
+        assert_eq!(policy.scrub, vec!["body"]);
+        assert_eq!(policy.max_jitter_ms, 0);
+        assert_eq!(
+            policy.pseudonymize,
+            AnonymizationPolicy::default().pseudonymize
+        );
+        assert_eq!(policy.seed, None);
+    }
+}
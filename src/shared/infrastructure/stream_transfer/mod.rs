@@ -0,0 +1,205 @@
+// Exports and imports the raw event log as NDJSON, to seed one environment with another's
+// history.
+//
+// Each line is an `ArchivedEvent`, the envelope the event archiver writes, in global position
+// order; a load keeps every stream version and global position as dumped, gaps included. An
+// export can be passed through an `Anonymizer` first, to be safe to load for testing.
+
+use anyhow::Context;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use std::io::{BufRead, Write};
+
+use crate::shared::infrastructure::event_archiver::ArchivedEvent;
+use crate::shared::infrastructure::event_store::{EventLog, StoredEvent};
+use crate::shared::infrastructure::stream_transfer::anonymizer::Anonymizer;
+
+/// Writes every event in `store` to `out`, one per line and anonymized when given an
+/// anonymizer. Answers how many were written.
+pub async fn dump_streams<E, W>(
+    store: &impl EventLog<E>,
+    mut out: W,
+    mut anonymizer: Option<&mut Anonymizer>,
+) -> anyhow::Result<usize>
+where
+    E: Clone + Send + Sync + Serialize + 'static,
+    W: Write,
+{
+    let events = store.load_all_from(0).await?;
+    let dumped = events.len();
+    for stored in events {
+        let mut archived = ArchivedEvent {
+            global_position: stored.global_position,
+            stream_id: stored.stream_id,
+            stream_version: stored.stream_version,
+            event: serde_json::to_value(&stored.event)?,
+        };
+        if let Some(anonymizer) = anonymizer.as_deref_mut() {
+            archived = anonymizer.anonymize(archived);
+        }
+        serde_json::to_writer(&mut out, &archived)?;
+        out.write_all(b"\n")?;
+    }
+    out.flush()?;
+    Ok(dumped)
+}
+
+/// Imports every line of `input` into `store`, all or nothing. Answers how many events were
+/// imported.
+pub async fn load_streams<E, R>(store: &impl EventLog<E>, input: R) -> anyhow::Result<usize>
+where
+    E: Clone + Send + Sync + DeserializeOwned + 'static,
+    R: BufRead,
+{
+    let mut events = Vec::new();
+    for (index, line) in input.lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let archived: ArchivedEvent<E> = serde_json::from_str(&line)
+            .with_context(|| format!("line {} is not an archived event", index + 1))?;
+        events.push(StoredEvent {
+            global_position: archived.global_position,
+            stream_id: archived.stream_id,
+            stream_version: archived.stream_version,
+            event: archived.event,
+        });
+    }
+    let loaded = events.len();
+    store.import(events).await?;
+    Ok(loaded)
+}
+
+pub mod anonymizer;
+
+#[cfg(test)]
+mod stream_transfer_tests {
+    use super::*;
+    use crate::modules::time_entries::core::events::TimeEntryEvent;
+    use crate::modules::time_entries::core::events::v1::time_entry_deleted::TimeEntryDeletedV1;
+    use crate::modules::time_entries::core::events::v1::time_entry_initiated::TimeEntryInitiatedV1;
+    use crate::shared::infrastructure::event_store::EventStore;
+    use crate::shared::infrastructure::event_store::in_memory::InMemoryEventStore;
+    use crate::shared::infrastructure::stream_transfer::anonymizer::AnonymizationPolicy;
+    use rstest::rstest;
+
+    fn initiated(time_entry_id: &str, created_by: &str) -> TimeEntryEvent {
+        TimeEntryEvent::TimeEntryInitiatedV1(TimeEntryInitiatedV1 {
+            time_entry_id: time_entry_id.into(),
+            user_id: "u1".into(),
+            created_at: 1,
+            created_by: created_by.into(),
+        })
+    }
+
+    fn deleted(time_entry_id: &str, deleted_by: &str) -> TimeEntryEvent {
+        TimeEntryEvent::TimeEntryDeletedV1(TimeEntryDeletedV1 {
+            time_entry_id: time_entry_id.into(),
+            deleted_at: 2,
+            deleted_by: deleted_by.into(),
+        })
+    }
+
+    /// Two streams, the first compacted, leaving a gap in the global positions.
+    async fn source() -> InMemoryEventStore<TimeEntryEvent> {
+        let store = InMemoryEventStore::new();
+        store
+            .append(
+                "TimeEntry-te-1",
+                0,
+                &[initiated("te-1", "alice"), deleted("te-1", "bob")],
+            )
+            .await
+            .unwrap();
+        store
+            .append("TimeEntry-te-2", 0, &[initiated("te-2", "alice")])
+            .await
+            .unwrap();
+        store
+            .compact("TimeEntry-te-1", 2, deleted("te-1", "bob"))
+            .await
+            .unwrap();
+        store
+    }
+
+    async fn dump(
+        store: &InMemoryEventStore<TimeEntryEvent>,
+        anonymizer: Option<&mut Anonymizer>,
+    ) -> Vec<u8> {
+        let mut out = Vec::new();
+        dump_streams(store, &mut out, anonymizer).await.unwrap();
+        out
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_load_a_dump_at_its_versions_and_positions() {
+        let source = source().await;
+        let target = InMemoryEventStore::<TimeEntryEvent>::new();
+
+        let loaded = load_streams(&target, dump(&source, None).await.as_slice())
+            .await
+            .unwrap();
+
+        assert_eq!(loaded, 2);
+        let positions = |log: Vec<StoredEvent<TimeEntryEvent>>| {
+            log.into_iter()
+                .map(|e| (e.global_position, e.stream_id, e.stream_version, e.event))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            positions(target.load_all_from(0).await.unwrap()),
+            positions(source.load_all_from(0).await.unwrap())
+        );
+        assert_eq!(target.head().await.unwrap(), 3);
+        target
+            .append("TimeEntry-te-2", 1, &[deleted("te-2", "alice")])
+            .await
+            .unwrap();
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_load_an_anonymized_dump() {
+        let source = source().await;
+        let target = InMemoryEventStore::<TimeEntryEvent>::new();
+        let mut anonymizer = Anonymizer::new(AnonymizationPolicy {
+            seed: Some(1),
+            ..AnonymizationPolicy::default()
+        });
+
+        load_streams(
+            &target,
+            dump(&source, Some(&mut anonymizer)).await.as_slice(),
+        )
+        .await
+        .unwrap();
+
+        assert!(matches!(
+            &target.load("TimeEntry-te-1").await.unwrap().events[0],
+            TimeEntryEvent::TimeEntryDeletedV1(e) if e.deleted_by == "person-1"
+        ));
+        let TimeEntryEvent::TimeEntryInitiatedV1(initiated) =
+            &target.load("TimeEntry-te-2").await.unwrap().events[0]
+        else {
+            panic!("expected te-2 to start with its initiation");
+        };
+        assert!(initiated.user_id.as_str().starts_with("person-"));
+        assert!(initiated.created_by.as_str().starts_with("person-"));
+        assert_ne!(initiated.user_id, initiated.created_by);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_load_nothing_from_a_malformed_dump() {
+        let mut dumped = dump(&source().await, None).await;
+        dumped.extend_from_slice(b"\nnot json\n");
+        let target = InMemoryEventStore::<TimeEntryEvent>::new();
+
+        let error = load_streams(&target, dumped.as_slice()).await.unwrap_err();
+
+        assert_eq!(error.to_string(), "line 4 is not an archived event");
+        assert_eq!(target.head().await.unwrap(), 0);
+    }
+}
@@ -0,0 +1,137 @@
+// Resolves display names from the identity service over HTTP.
+//
+// One batch is one `POST {base_url}/users/display-names` with `{"user_ids": [...]}`, answered
+// with `{"display_names": {"<user_id>": "<name>"}}` for the ids it knows. Failures, timeouts
+// and non-2xx answers all surface as backend errors, which the GraphQL field reports.
+
+use crate::shared::infrastructure::user_directory::{UserDirectory, UserDirectoryError};
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::time::Duration;
+
+/// How long a batch may take before the lookup fails.
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(2);
+
+#[derive(Debug, serde::Serialize)]
+struct DisplayNamesRequest<'a> {
+    user_ids: &'a [String],
+}
+
+#[derive(Debug, serde::Deserialize)]
+struct DisplayNamesResponse {
+    display_names: HashMap<String, String>,
+}
+
+#[derive(Clone)]
+pub struct HttpUserDirectory {
+    client: reqwest::Client,
+    url: String,
+}
+
+impl HttpUserDirectory {
+    pub fn new(base_url: &str, timeout: Duration) -> Result<Self, UserDirectoryError> {
+        let client = reqwest::Client::builder()
+            .timeout(timeout)
+            .build()
+            .map_err(backend)?;
+        Ok(Self {
+            client,
+            url: format!("{}/users/display-names", base_url.trim_end_matches('/')),
+        })
+    }
+}
+
+fn backend(error: reqwest::Error) -> UserDirectoryError {
+    UserDirectoryError::Backend(error.to_string())
+}
+
+#[async_trait]
+impl UserDirectory for HttpUserDirectory {
+    async fn display_names(
+        &self,
+        user_ids: &[String],
+    ) -> Result<HashMap<String, String>, UserDirectoryError> {
+        let response = self
+            .client
+            .post(&self.url)
+            .json(&DisplayNamesRequest { user_ids })
+            .send()
+            .await
+            .and_then(reqwest::Response::error_for_status)
+            .map_err(backend)?;
+        let body: DisplayNamesResponse = response.json().await.map_err(backend)?;
+        Ok(body.display_names)
+    }
+}
+
+#[cfg(test)]
+mod http_user_directory_tests {
+    use super::*;
+    use axum::{Json, Router, http::StatusCode, routing::post};
+    use rstest::rstest;
+    use serde_json::{Value, json};
+
+    async fn serve(router: Router) -> String {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, router).await.unwrap() });
+        format!("http://{address}/")
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_resolve_a_batch_with_one_request() {
+        let base_url = serve(Router::new().route(
+            "/users/display-names",
+            post(|Json(body): Json<Value>| async move {
+                assert_eq!(body, json!({ "user_ids": ["u-1", "u-2"] }));
+                Json(json!({ "display_names": { "u-1": "Teddy Test" } }))
+            }),
+        ))
+        .await;
+        let directory = HttpUserDirectory::new(&base_url, DEFAULT_TIMEOUT).unwrap();
+
+        let names = directory
+            .display_names(&["u-1".to_string(), "u-2".to_string()])
+            .await
+            .unwrap();
+
+        assert_eq!(
+            names,
+            HashMap::from([("u-1".to_string(), "Teddy Test".to_string())])
+        );
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_fail_on_error_statuses_and_timeouts() {
+        let base_url = serve(
+            Router::new()
+                .route(
+                    "/users/display-names",
+                    post(|| async { StatusCode::SERVICE_UNAVAILABLE }),
+                )
+                .route(
+                    "/slow/users/display-names",
+                    post(|| async {
+                        tokio::time::sleep(Duration::from_secs(5)).await;
+                        Json(json!({ "display_names": {} }))
+                    }),
+                ),
+        )
+        .await;
+        let failing = HttpUserDirectory::new(&base_url, DEFAULT_TIMEOUT).unwrap();
+        let slow =
+            HttpUserDirectory::new(&format!("{base_url}slow"), Duration::from_millis(50)).unwrap();
+
+        let ids = ["u-1".to_string()];
+        assert!(matches!(
+            failing.display_names(&ids).await,
+            Err(UserDirectoryError::Backend(_))
+        ));
+        assert!(matches!(
+            slow.display_names(&ids).await,
+            Err(UserDirectoryError::Backend(_))
+        ));
+    }
+}
@@ -0,0 +1,95 @@
+use crate::shared::infrastructure::user_directory::{UserDirectory, UserDirectoryError};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use tokio::sync::RwLock;
+
+#[derive(Default)]
+struct Inner {
+    display_names: RwLock<HashMap<String, String>>,
+    is_offline: AtomicBool,
+    batch_calls: AtomicU64,
+}
+
+#[derive(Clone, Default)]
+pub struct InMemoryUserDirectory {
+    inner: Arc<Inner>,
+}
+
+impl InMemoryUserDirectory {
+    pub fn new() -> Self {
+        Self {
+            inner: Arc::new(Inner::default()),
+        }
+    }
+
+    pub async fn insert(&self, user_id: impl Into<String>, display_name: impl Into<String>) {
+        self.inner
+            .display_names
+            .write()
+            .await
+            .insert(user_id.into(), display_name.into());
+    }
+
+    pub fn toggle_offline(&self) {
+        self.inner.is_offline.fetch_xor(true, Ordering::SeqCst);
+    }
+
+    pub fn batch_calls(&self) -> u64 {
+        self.inner.batch_calls.load(Ordering::SeqCst)
+    }
+}
+
+#[async_trait::async_trait]
+impl UserDirectory for InMemoryUserDirectory {
+    async fn display_names(
+        &self,
+        user_ids: &[String],
+    ) -> Result<HashMap<String, String>, UserDirectoryError> {
+        self.inner.batch_calls.fetch_add(1, Ordering::SeqCst);
+        if self.inner.is_offline.load(Ordering::SeqCst) {
+            return Err(UserDirectoryError::Backend(
+                "User directory offline".to_string(),
+            ));
+        }
+        let guard = self.inner.display_names.read().await;
+        Ok(user_ids
+            .iter()
+            .filter_map(|id| guard.get(id).map(|name| (id.clone(), name.clone())))
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod in_memory_user_directory_tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_return_known_display_names_only() {
+        let directory = InMemoryUserDirectory::default();
+        directory.insert("u-1", "Teddy Test").await;
+        let names = directory
+            .display_names(&["u-1".to_string(), "u-unknown".to_string()])
+            .await
+            .unwrap();
+        assert_eq!(names.len(), 1);
+        assert_eq!(names.get("u-1"), Some(&"Teddy Test".to_string()));
+        assert_eq!(directory.batch_calls(), 1);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_fail_when_offline() {
+        let directory = InMemoryUserDirectory::new();
+        directory.toggle_offline();
+        let result = directory.display_names(&["u-1".to_string()]).await;
+        assert_eq!(
+            result,
+            Err(UserDirectoryError::Backend(
+                "User directory offline".to_string()
+            ))
+        );
+    }
+}
@@ -0,0 +1,186 @@
+// Batches concurrent display-name lookups into a single directory call.
+//
+// GraphQL resolves the fields of every list item concurrently within one task. The first
+// `load` call opens a batch and yields once, giving sibling resolvers the chance to add
+// their ids before the batch is sent to the directory. Nothing is cached between batches.
+//
+// The batch is sent from a task of its own, so it completes for every waiter even when the
+// load that opened it is cancelled, say because its GraphQL request was dropped.
+
+use crate::shared::infrastructure::user_directory::{UserDirectory, UserDirectoryError};
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use tokio::sync::{Mutex, watch};
+
+type BatchResult = Option<Result<Arc<HashMap<String, String>>, UserDirectoryError>>;
+
+struct PendingBatch {
+    user_ids: HashSet<String>,
+    sender: watch::Sender<BatchResult>,
+}
+
+/// Sends the pending batch to the directory from its own task once dropped, whether the
+/// leader moved on or was cancelled while yielding.
+struct DispatchOnDrop<TDirectory>
+where
+    TDirectory: UserDirectory + Clone + 'static,
+{
+    directory: TDirectory,
+    pending: Arc<Mutex<Option<PendingBatch>>>,
+}
+
+impl<TDirectory> Drop for DispatchOnDrop<TDirectory>
+where
+    TDirectory: UserDirectory + Clone + 'static,
+{
+    fn drop(&mut self) {
+        let directory = self.directory.clone();
+        let pending = self.pending.clone();
+        tokio::spawn(async move {
+            let Some(batch) = pending.lock().await.take() else {
+                return;
+            };
+            let user_ids: Vec<String> = batch.user_ids.into_iter().collect();
+            let result = directory.display_names(&user_ids).await.map(Arc::new);
+            let _ = batch.sender.send(Some(result));
+        });
+    }
+}
+
+#[derive(Clone)]
+pub struct UserDisplayNameLoader<TDirectory>
+where
+    TDirectory: UserDirectory + Clone + 'static,
+{
+    directory: TDirectory,
+    pending: Arc<Mutex<Option<PendingBatch>>>,
+}
+
+impl<TDirectory> UserDisplayNameLoader<TDirectory>
+where
+    TDirectory: UserDirectory + Clone + 'static,
+{
+    pub fn new(directory: TDirectory) -> Self {
+        Self {
+            directory,
+            pending: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    pub async fn load(&self, user_id: &str) -> Result<Option<String>, UserDirectoryError> {
+        let (mut receiver, is_leader) = {
+            let mut pending = self.pending.lock().await;
+            match pending.as_mut() {
+                Some(batch) => {
+                    batch.user_ids.insert(user_id.to_string());
+                    (batch.sender.subscribe(), false)
+                }
+                None => {
+                    let (sender, receiver) = watch::channel(None);
+                    *pending = Some(PendingBatch {
+                        user_ids: HashSet::from([user_id.to_string()]),
+                        sender,
+                    });
+                    (receiver, true)
+                }
+            }
+        };
+
+        if is_leader {
+            let dispatch = DispatchOnDrop {
+                directory: self.directory.clone(),
+                pending: self.pending.clone(),
+            };
+            tokio::task::yield_now().await;
+            drop(dispatch);
+        }
+
+        let result = receiver
+            .wait_for(Option::is_some)
+            .await
+            .map_err(|_| UserDirectoryError::Backend("batch was dropped".to_string()))?
+            .clone()
+            .expect("batch result is present");
+        result.map(|names| names.get(user_id).cloned())
+    }
+}
+
+#[cfg(test)]
+mod user_display_name_loader_tests {
+    use super::*;
+    use crate::shared::infrastructure::user_directory::in_memory::InMemoryUserDirectory;
+    use rstest::rstest;
+    use std::time::Duration;
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_resolve_a_single_display_name() {
+        let directory = InMemoryUserDirectory::new();
+        directory.insert("u-1", "Teddy Test").await;
+        let loader = UserDisplayNameLoader::new(directory);
+        let name = loader.load("u-1").await.unwrap();
+        assert_eq!(name, Some("Teddy Test".to_string()));
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_return_none_for_unknown_users() {
+        let loader = UserDisplayNameLoader::new(InMemoryUserDirectory::new());
+        assert_eq!(loader.load("u-unknown").await.unwrap(), None);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_batch_concurrent_loads_into_one_directory_call() {
+        let directory = InMemoryUserDirectory::new();
+        directory.insert("u-1", "Teddy Test").await;
+        directory.insert("u-2", "Tina Test").await;
+        let loader = UserDisplayNameLoader::new(directory.clone());
+        let (a, b, c) = tokio::join!(loader.load("u-1"), loader.load("u-2"), loader.load("u-1"));
+        assert_eq!(a.unwrap(), Some("Teddy Test".to_string()));
+        assert_eq!(b.unwrap(), Some("Tina Test".to_string()));
+        assert_eq!(c.unwrap(), Some("Teddy Test".to_string()));
+        assert_eq!(directory.batch_calls(), 1);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_start_a_new_batch_after_the_previous_completed() {
+        let directory = InMemoryUserDirectory::new();
+        let loader = UserDisplayNameLoader::new(directory.clone());
+        loader.load("u-1").await.unwrap();
+        loader.load("u-2").await.unwrap();
+        assert_eq!(directory.batch_calls(), 2);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_propagate_directory_errors_to_every_waiter() {
+        let directory = InMemoryUserDirectory::new();
+        directory.toggle_offline();
+        let loader = UserDisplayNameLoader::new(directory);
+        let (a, b) = tokio::join!(loader.load("u-1"), loader.load("u-2"));
+        assert!(matches!(a, Err(UserDirectoryError::Backend(_))));
+        assert!(matches!(b, Err(UserDirectoryError::Backend(_))));
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_complete_the_batch_when_its_leader_is_cancelled() {
+        let directory = InMemoryUserDirectory::new();
+        directory.insert("u-2", "Tina Test").await;
+        let loader = UserDisplayNameLoader::new(directory.clone());
+
+        // The leader opens the batch, yields and is dropped before it gets to send it.
+        tokio::select! {
+            biased;
+            _ = loader.load("u-1") => panic!("the leader should still be yielding"),
+            _ = std::future::ready(()) => {}
+        }
+        let name = tokio::time::timeout(Duration::from_secs(1), loader.load("u-2"))
+            .await
+            .expect("the next load should not hang");
+
+        assert_eq!(name.unwrap(), Some("Tina Test".to_string()));
+    }
+}
@@ -0,0 +1,37 @@
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::sync::Arc;
+use thiserror::Error;
+
+#[derive(Debug, Clone, Error, PartialEq, Eq)]
+pub enum UserDirectoryError {
+    #[error("backend error: {0}")]
+    Backend(String),
+}
+
+/// Resolves user display names from an external directory.
+/// Implementations receive a whole batch of ids per call; unknown ids are omitted from the result.
+#[async_trait]
+pub trait UserDirectory: Send + Sync {
+    async fn display_names(
+        &self,
+        user_ids: &[String],
+    ) -> Result<HashMap<String, String>, UserDirectoryError>;
+}
+
+/// A user directory behind a pointer, so the shell can pick the adapter at startup.
+pub type SharedUserDirectory = Arc<dyn UserDirectory>;
+
+#[async_trait]
+impl<T: UserDirectory + ?Sized> UserDirectory for Arc<T> {
+    async fn display_names(
+        &self,
+        user_ids: &[String],
+    ) -> Result<HashMap<String, String>, UserDirectoryError> {
+        (**self).display_names(user_ids).await
+    }
+}
+
+pub mod http;
+pub mod in_memory;
+pub mod loader;
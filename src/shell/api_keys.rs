@@ -0,0 +1,194 @@
+// Admin API for provisioning the API keys machine clients send as `X-Api-Key`. An issued key
+// is shown once, in the response that issues it; the store keeps only its hash. Rotating a
+// key is issuing a new one and revoking the old one once the client switched.
+
+use axum::{
+    Json,
+    extract::{Path, State},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+};
+use chrono::Utc;
+use serde::Deserialize;
+
+use crate::shared::auth::rbac::ApiKeyScope;
+use crate::shared::infrastructure::api_key_store::{ApiKey, ApiKeyStore};
+use crate::shared::infrastructure::request_context::RequestContext;
+use crate::shell::state::AppState;
+
+#[derive(Deserialize)]
+pub struct IssueApiKeyBody {
+    pub user_id: String,
+    /// The admin's own tenant when left out.
+    pub tenant_id: Option<String>,
+    pub scope: ApiKeyScope,
+}
+
+/// GET /admin/api-keys — every key not revoked, oldest first, without the keys themselves.
+/// Admins only.
+pub async fn handle_list(State(state): State<AppState>, request_ctx: RequestContext) -> Response {
+    if !request_ctx.principal().can_administer() {
+        return StatusCode::FORBIDDEN.into_response();
+    }
+    match state.api_key_store.list().await {
+        Ok(records) => Json(records).into_response(),
+        Err(_) => StatusCode::INTERNAL_SERVER_ERROR.into_response(),
+    }
+}
+
+/// POST /admin/api-keys — issues a key acting as `user_id` within `scope`. Admins only.
+pub async fn handle_issue(
+    State(state): State<AppState>,
+    request_ctx: RequestContext,
+    Json(body): Json<IssueApiKeyBody>,
+) -> Response {
+    if !request_ctx.principal().can_administer() {
+        return StatusCode::FORBIDDEN.into_response();
+    }
+    let api_key = ApiKey {
+        user_id: body.user_id,
+        tenant_id: body.tenant_id.unwrap_or(request_ctx.tenant_id),
+        scope: body.scope,
+    };
+    match state
+        .api_key_store
+        .issue(api_key, Utc::now().timestamp_millis())
+        .await
+    {
+        Ok(issued) => (StatusCode::CREATED, Json(issued)).into_response(),
+        Err(_) => StatusCode::INTERNAL_SERVER_ERROR.into_response(),
+    }
+}
+
+/// DELETE /admin/api-keys/{id} — revokes the key with that record id. Admins only.
+pub async fn handle_revoke(
+    State(state): State<AppState>,
+    request_ctx: RequestContext,
+    Path(id): Path<String>,
+) -> Response {
+    if !request_ctx.principal().can_administer() {
+        return StatusCode::FORBIDDEN.into_response();
+    }
+    match state.api_key_store.revoke(&id).await {
+        Ok(true) => StatusCode::NO_CONTENT.into_response(),
+        Ok(false) => StatusCode::NOT_FOUND.into_response(),
+        Err(_) => StatusCode::INTERNAL_SERVER_ERROR.into_response(),
+    }
+}
+
+#[cfg(test)]
+mod api_keys_tests {
+    use super::*;
+    use crate::tests::fixtures::tags::make_test_app_state;
+    use axum::{
+        Router,
+        body::Body,
+        http::Request,
+        routing::{delete, get},
+    };
+    use http_body_util::BodyExt;
+    use rstest::rstest;
+    use serde_json::{Value, json};
+    use tower::ServiceExt;
+
+    fn app(state: AppState) -> Router {
+        Router::new()
+            .route("/admin/api-keys", get(handle_list).post(handle_issue))
+            .route("/admin/api-keys/{id}", delete(handle_revoke))
+            .with_state(state)
+    }
+
+    async fn send(
+        state: &AppState,
+        method: &str,
+        uri: &str,
+        role: &str,
+        body: Option<Value>,
+    ) -> (StatusCode, Value) {
+        let request = Request::builder()
+            .method(method)
+            .uri(uri)
+            .header("x-user-id", "admin-1")
+            .header("x-tenant-id", "tenant-test")
+            .header("x-user-role", role)
+            .header("content-type", "application/json");
+        let body = body.map_or_else(Body::empty, |body| Body::from(body.to_string()));
+        let response = app(state.clone())
+            .oneshot(request.body(body).unwrap())
+            .await
+            .unwrap();
+        let status = response.status();
+        let bytes = response.into_body().collect().await.unwrap().to_bytes();
+        (
+            status,
+            serde_json::from_slice(&bytes).unwrap_or(Value::Null),
+        )
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_issue_list_and_revoke_keys() {
+        let state = make_test_app_state();
+
+        let (status, issued) = send(
+            &state,
+            "POST",
+            "/admin/api-keys",
+            "admin",
+            Some(json!({"user_id": "ci-bot", "scope": "register-only"})),
+        )
+        .await;
+
+        assert_eq!(status, StatusCode::CREATED);
+        assert_eq!(issued["tenant_id"], "tenant-test");
+        let key = issued["key"].as_str().unwrap();
+        let found = state.api_key_store.find(key).await.unwrap().unwrap();
+        assert_eq!(found.user_id, "ci-bot");
+        assert_eq!(found.scope, ApiKeyScope::RegisterOnly);
+
+        let (status, listed) = send(&state, "GET", "/admin/api-keys", "admin", None).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(listed[0]["id"], issued["id"]);
+        assert_eq!(listed[0]["key"], Value::Null);
+
+        let uri = format!("/admin/api-keys/{}", issued["id"].as_str().unwrap());
+        let (revoked, _) = send(&state, "DELETE", &uri, "admin", None).await;
+        let (again, _) = send(&state, "DELETE", &uri, "admin", None).await;
+        assert_eq!(revoked, StatusCode::NO_CONTENT);
+        assert_eq!(again, StatusCode::NOT_FOUND);
+        assert_eq!(state.api_key_store.find(key).await.unwrap(), None);
+    }
+
+    #[rstest]
+    #[case::list("GET", "/admin/api-keys", None)]
+    #[case::issue(
+        "POST",
+        "/admin/api-keys",
+        Some(json!({"user_id": "ci-bot", "scope": "admin"}))
+    )]
+    #[case::revoke("DELETE", "/admin/api-keys/k-1", None)]
+    #[tokio::test]
+    async fn it_should_be_reserved_for_admins(
+        #[case] method: &str,
+        #[case] uri: &str,
+        #[case] body: Option<Value>,
+    ) {
+        let state = make_test_app_state();
+
+        let (status, _) = send(&state, method, uri, "manager", body).await;
+
+        assert_eq!(status, StatusCode::FORBIDDEN);
+        assert!(state.api_key_store.list().await.unwrap().is_empty());
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_report_an_unavailable_store() {
+        let state = make_test_app_state();
+        state.api_key_store.toggle_offline();
+
+        let (status, _) = send(&state, "GET", "/admin/api-keys", "admin", None).await;
+
+        assert_eq!(status, StatusCode::INTERNAL_SERVER_ERROR);
+    }
+}
@@ -0,0 +1,322 @@
+// API audit trail: every mutation attempt that reaches the router is recorded with its actor,
+// operation, a hash of its arguments, the response status and the latency. This is separate
+// from domain events, so rejected and unauthorized attempts are audited too.
+
+use axum::{
+    Json,
+    body::{Body, to_bytes},
+    extract::{MatchedPath, Query, Request, State},
+    http::{Method, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use chrono::Utc;
+use serde::Deserialize;
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::time::Instant;
+
+use crate::shared::infrastructure::api_audit_store::{ApiAuditStore, AuditQuery, AuditRecord};
+use crate::shared::infrastructure::api_key_store::ApiKey;
+use crate::shared::infrastructure::request_context::RequestContext;
+use crate::shell::state::AppState;
+
+/// A backstop only: routes sit behind `shell::http::limits`, which rejects oversized bodies
+/// well before this. Keep it above every configured route limit.
+pub const MAX_AUDITED_BODY_BYTES: usize = 32 * 1024 * 1024;
+
+const DEFAULT_LIMIT: usize = 100;
+const MAX_LIMIT: usize = 1_000;
+
+fn is_mutation(method: &Method) -> bool {
+    !matches!(*method, Method::GET | Method::HEAD | Method::OPTIONS)
+}
+
+/// Hashes the query string and body. Not cryptographic: it lets auditors match repeated
+/// attempts without the trail storing the arguments themselves.
+pub fn arguments_hash(query: Option<&str>, body: &[u8]) -> String {
+    let mut hasher = DefaultHasher::new();
+    query.unwrap_or_default().hash(&mut hasher);
+    body.hash(&mut hasher);
+    format!("{:016x}", hasher.finish())
+}
+
+/// Must run inside `resolve_api_key` so API key callers are attributed to their key's user.
+/// A failing audit store is logged rather than failing a request that has already been handled.
+pub async fn audit_mutations<TStore>(
+    State(store): State<TStore>,
+    request: Request,
+    next: Next,
+) -> Response
+where
+    TStore: ApiAuditStore,
+{
+    if !is_mutation(request.method()) {
+        return next.run(request).await;
+    }
+    let started = Instant::now();
+    let context =
+        RequestContext::from_headers(request.headers(), request.extensions().get::<ApiKey>()).ok();
+    let path = request
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|path| path.as_str().to_string())
+        .unwrap_or_else(|| request.uri().path().to_string());
+    let operation = format!("{} {path}", request.method());
+    let query = request.uri().query().map(str::to_string);
+
+    let (parts, body) = request.into_parts();
+    let (arguments_hash, response) = match to_bytes(body, MAX_AUDITED_BODY_BYTES).await {
+        Ok(bytes) => (
+            arguments_hash(query.as_deref(), &bytes),
+            next.run(Request::from_parts(parts, Body::from(bytes)))
+                .await,
+        ),
+        Err(_) => (
+            arguments_hash(query.as_deref(), &[]),
+            StatusCode::PAYLOAD_TOO_LARGE.into_response(),
+        ),
+    };
+
+    let record = AuditRecord {
+        occurred_at: Utc::now().timestamp_millis(),
+        actor: context.as_ref().map(|ctx| ctx.user_id.clone()),
+        tenant_id: context.map(|ctx| ctx.tenant_id),
+        operation,
+        arguments_hash,
+        status: response.status().as_u16(),
+        latency_ms: started.elapsed().as_millis() as u64,
+    };
+    if let Err(reason) = store.record(record).await {
+        tracing::warn!(%reason, "failed to record audit entry");
+    }
+    response
+}
+
+#[derive(Deserialize)]
+pub struct AuditParams {
+    pub actor: Option<String>,
+    pub limit: Option<usize>,
+}
+
+/// GET /admin/audit — newest audit records first, optionally for one actor. Admins only.
+pub async fn list_audit_records(
+    State(state): State<AppState>,
+    request_ctx: RequestContext,
+    Query(params): Query<AuditParams>,
+) -> impl IntoResponse {
+    if !request_ctx.principal().can_administer() {
+        return StatusCode::FORBIDDEN.into_response();
+    }
+    let query = AuditQuery {
+        actor: params.actor,
+        limit: params.limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT),
+    };
+    match state.audit_store.list(&query).await {
+        Ok(records) => Json(records).into_response(),
+        Err(_) => StatusCode::INTERNAL_SERVER_ERROR.into_response(),
+    }
+}
+
+#[cfg(test)]
+mod audit_tests {
+    use axum::{
+        Router,
+        body::Body,
+        http::{Request, StatusCode},
+    };
+    use http_body_util::BodyExt;
+    use rstest::rstest;
+    use tower::ServiceExt;
+
+    use super::*;
+    use crate::shared::auth::rbac::ApiKeyScope;
+    use crate::shell::http::limits::RouteLimits;
+    use crate::shell::http::routes::RouterBuilder;
+    use crate::tests::fixtures::tags::make_test_app_state;
+
+    fn router(state: AppState) -> Router {
+        RouterBuilder::new(state).build()
+    }
+
+    async fn all_records(state: &AppState) -> Vec<AuditRecord> {
+        state
+            .audit_store
+            .list(&AuditQuery {
+                actor: None,
+                limit: usize::MAX,
+            })
+            .await
+            .unwrap()
+    }
+
+    fn create_tag(body: &str) -> Request<Body> {
+        Request::post("/api/v1/tags")
+            .header("x-user-id", "u-1")
+            .header("x-tenant-id", "t-1")
+            .header("content-type", "application/json")
+            .body(Body::from(body.to_string()))
+            .unwrap()
+    }
+
+    #[test]
+    fn it_should_hash_query_and_body() {
+        let hash = arguments_hash(None, b"{}");
+        assert_eq!(hash.len(), 16);
+        assert_eq!(hash, arguments_hash(Some(""), b"{}"));
+        assert_ne!(hash, arguments_hash(Some("a=1"), b"{}"));
+        assert_ne!(hash, arguments_hash(None, b"{\"a\":1}"));
+    }
+
+    #[tokio::test]
+    async fn it_should_record_mutation_attempts_with_their_outcome() {
+        let state = make_test_app_state();
+        let app = router(state.clone());
+
+        let created = app
+            .clone()
+            .oneshot(create_tag(r#"{"name":"billable"}"#))
+            .await
+            .unwrap();
+        assert_eq!(created.status(), StatusCode::CREATED);
+        let rejected = app.oneshot(create_tag("not json")).await.unwrap();
+
+        let records = all_records(&state).await;
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].status, rejected.status().as_u16());
+        assert!(!records[0].succeeded());
+        assert!(records[1].succeeded());
+        assert_eq!(records[1].actor.as_deref(), Some("u-1"));
+        assert_eq!(records[1].tenant_id.as_deref(), Some("t-1"));
+        assert_eq!(records[1].operation, "POST /api/v1/tags");
+        assert_eq!(
+            records[1].arguments_hash,
+            arguments_hash(None, br#"{"name":"billable"}"#)
+        );
+    }
+
+    #[tokio::test]
+    async fn it_should_use_the_route_template_and_api_key_owner() {
+        let state = make_test_app_state();
+        state
+            .api_key_store
+            .insert(
+                "secret",
+                ApiKey {
+                    user_id: "ci-bot".to_string(),
+                    tenant_id: "t-ci".to_string(),
+                    scope: ApiKeyScope::ReadOnly,
+                },
+            )
+            .await;
+        let response = router(state.clone())
+            .oneshot(
+                Request::delete("/api/v1/tags/01900000-0000-7000-8000-000000000001")
+                    .header("x-api-key", "secret")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+        let records = all_records(&state).await;
+        assert_eq!(records[0].operation, "DELETE /api/v1/tags/{tag_id}");
+        assert_eq!(records[0].actor.as_deref(), Some("ci-bot"));
+        assert_eq!(records[0].status, 403);
+    }
+
+    #[tokio::test]
+    async fn it_should_record_anonymous_attempts_and_skip_reads() {
+        let state = make_test_app_state();
+        let app = router(state.clone());
+
+        app.clone()
+            .oneshot(Request::get("/health").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        let response = app
+            .oneshot(Request::post("/api/v1/tags").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+        let records = all_records(&state).await;
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].actor, None);
+    }
+
+    #[tokio::test]
+    async fn it_should_reject_oversized_bodies_and_still_audit_them() {
+        let state = make_test_app_state();
+        let body = "x".repeat(RouteLimits::STANDARD.max_body_bytes + 1);
+        let response = router(state.clone())
+            .oneshot(create_tag(&body))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+        assert_eq!(all_records(&state).await[0].status, 413);
+    }
+
+    #[tokio::test]
+    async fn it_should_not_fail_requests_when_the_audit_store_is_offline() {
+        let state = make_test_app_state();
+        state.audit_store.toggle_offline();
+        let response = router(state)
+            .oneshot(create_tag(r#"{"name":"billable"}"#))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::CREATED);
+    }
+
+    #[rstest]
+    #[case::admin(Some("admin"), StatusCode::OK)]
+    #[case::manager(Some("manager"), StatusCode::FORBIDDEN)]
+    #[case::employee(None, StatusCode::FORBIDDEN)]
+    #[tokio::test]
+    async fn it_should_only_list_audit_records_for_admins(
+        #[case] role: Option<&str>,
+        #[case] expected: StatusCode,
+    ) {
+        let state = make_test_app_state();
+        let app = router(state);
+        app.clone()
+            .oneshot(create_tag(r#"{"name":"billable"}"#))
+            .await
+            .unwrap();
+        let mut request = Request::get("/api/v1/admin/audit?actor=u-1&limit=5")
+            .header("x-user-id", "u-admin")
+            .header("x-tenant-id", "t-1");
+        if let Some(role) = role {
+            request = request.header("x-user-role", role);
+        }
+        let response = app
+            .oneshot(request.body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), expected);
+        if expected == StatusCode::OK {
+            let bytes = response.into_body().collect().await.unwrap().to_bytes();
+            let records: Vec<AuditRecord> = serde_json::from_slice(&bytes).unwrap();
+            assert_eq!(records.len(), 1);
+            assert_eq!(records[0].operation, "POST /api/v1/tags");
+        }
+    }
+
+    #[tokio::test]
+    async fn it_should_return_500_when_listing_from_an_offline_store() {
+        let state = make_test_app_state();
+        state.audit_store.toggle_offline();
+        let response = router(state)
+            .oneshot(
+                Request::get("/api/v1/admin/audit")
+                    .header("x-user-id", "u-admin")
+                    .header("x-tenant-id", "t-1")
+                    .header("x-user-role", "admin")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+    }
+}
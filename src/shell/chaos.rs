@@ -0,0 +1,199 @@
+// Fault injection for rehearsing client retries against the real router. Never enable it in
+// production.
+//
+// Of every 100 requests, the configured percentages are delayed, answered with a 503, or cut
+// off: the response announces a body it never sends, so the server closes the connection
+// mid-response. Selection is deterministic, spread over each block of 100 requests, so a
+// rehearsal sees exactly the configured rates.
+
+use axum::{
+    body::Body,
+    extract::{Request, State},
+    http::{HeaderValue, StatusCode, header},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+/// Marks responses produced by the chaos layer, so they are not mistaken for real failures.
+pub const CHAOS_HEADER: &str = "x-chaos";
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ChaosConfig {
+    pub latency_percent: u8,
+    pub latency: Duration,
+    pub error_percent: u8,
+    pub reset_percent: u8,
+}
+
+impl ChaosConfig {
+    pub fn is_active(&self) -> bool {
+        (self.latency_percent > 0 && !self.latency.is_zero())
+            || self.error_percent > 0
+            || self.reset_percent > 0
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Chaos {
+    config: ChaosConfig,
+    requests: Arc<AtomicU64>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Fault {
+    Error,
+    Reset,
+}
+
+impl Chaos {
+    pub fn new(config: ChaosConfig) -> Self {
+        Self {
+            config,
+            requests: Arc::new(AtomicU64::new(0)),
+        }
+    }
+
+    /// Request `n`'s slot within its block of 100. Multiplying by a number coprime to 100
+    /// visits every slot once per block while scattering neighbouring requests.
+    fn slot(n: u64, stride: u64) -> u64 {
+        (n * stride) % 100
+    }
+
+    fn next(&self) -> (bool, Option<Fault>) {
+        let n = self.requests.fetch_add(1, Ordering::SeqCst);
+        let delayed = Self::slot(n, 73) < u64::from(self.config.latency_percent);
+        let fault_slot = Self::slot(n, 37);
+        let reset = u64::from(self.config.reset_percent);
+        let fault = if fault_slot < reset {
+            Some(Fault::Reset)
+        } else if fault_slot < reset + u64::from(self.config.error_percent) {
+            Some(Fault::Error)
+        } else {
+            None
+        };
+        (delayed, fault)
+    }
+}
+
+pub async fn inject_chaos(State(chaos): State<Chaos>, request: Request, next: Next) -> Response {
+    let (delayed, fault) = chaos.next();
+    if delayed {
+        tokio::time::sleep(chaos.config.latency).await;
+    }
+    let (mut response, marker) = match fault {
+        None => return next.run(request).await,
+        Some(Fault::Error) => (StatusCode::SERVICE_UNAVAILABLE.into_response(), "error"),
+        Some(Fault::Reset) => {
+            let mut response = Response::new(Body::empty());
+            response
+                .headers_mut()
+                .insert(header::CONTENT_LENGTH, HeaderValue::from_static("1024"));
+            (response, "reset")
+        }
+    };
+    response
+        .headers_mut()
+        .insert(CHAOS_HEADER, HeaderValue::from_static(marker));
+    response
+}
+
+#[cfg(test)]
+mod chaos_tests {
+    use super::*;
+    use axum::{Router, middleware, routing::get};
+    use rstest::rstest;
+    use std::time::Instant;
+    use tower::ServiceExt;
+
+    fn app(config: ChaosConfig) -> Router {
+        Router::new()
+            .route("/", get(|| async { "ok" }))
+            .layer(middleware::from_fn_with_state(
+                Chaos::new(config),
+                inject_chaos,
+            ))
+    }
+
+    async fn send(app: &Router) -> Response {
+        app.clone()
+            .oneshot(Request::builder().uri("/").body(Body::empty()).unwrap())
+            .await
+            .unwrap()
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_inject_exactly_the_configured_rates_per_hundred_requests() {
+        let app = app(ChaosConfig {
+            error_percent: 10,
+            reset_percent: 5,
+            ..ChaosConfig::default()
+        });
+        let mut counts = std::collections::HashMap::new();
+
+        for _ in 0..200 {
+            let response = send(&app).await;
+            let marker = response
+                .headers()
+                .get(CHAOS_HEADER)
+                .map(|value| value.to_str().unwrap().to_string());
+            *counts.entry((response.status(), marker)).or_insert(0) += 1;
+        }
+
+        assert_eq!(counts[&(StatusCode::OK, None)], 170);
+        assert_eq!(
+            counts[&(StatusCode::SERVICE_UNAVAILABLE, Some("error".to_string()))],
+            20
+        );
+        assert_eq!(counts[&(StatusCode::OK, Some("reset".to_string()))], 10);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_cut_off_reset_responses_before_their_announced_body() {
+        let app = app(ChaosConfig {
+            reset_percent: 100,
+            ..ChaosConfig::default()
+        });
+
+        let response = send(&app).await;
+
+        assert_eq!(response.headers()[header::CONTENT_LENGTH], "1024");
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        assert!(body.is_empty());
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_delay_the_configured_share_of_requests() {
+        let app = app(ChaosConfig {
+            latency_percent: 100,
+            latency: Duration::from_millis(20),
+            ..ChaosConfig::default()
+        });
+
+        let started = Instant::now();
+        let response = send(&app).await;
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(started.elapsed() >= Duration::from_millis(20));
+    }
+
+    #[rstest]
+    #[case(ChaosConfig::default(), false)]
+    #[case(ChaosConfig { latency_percent: 50, ..ChaosConfig::default() }, false)]
+    #[case(ChaosConfig { latency_percent: 50, latency: Duration::from_millis(1), ..ChaosConfig::default() }, true)]
+    #[case(ChaosConfig { error_percent: 1, ..ChaosConfig::default() }, true)]
+    #[case(ChaosConfig { reset_percent: 1, ..ChaosConfig::default() }, true)]
+    fn it_should_only_be_active_when_it_injects_something(
+        #[case] config: ChaosConfig,
+        #[case] expected: bool,
+    ) {
+        assert_eq!(config.is_active(), expected);
+    }
+}
@@ -0,0 +1,114 @@
+// Lag of the broker consumers, for operators. The admin API lists it per consumer and topic
+// partition, flagging the partitions that stalled; `/metrics` renders the same for scraping.
+
+use axum::{
+    Json,
+    extract::{Query, State},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+};
+use chrono::Utc;
+use serde::Deserialize;
+
+use crate::shared::infrastructure::request_context::RequestContext;
+use crate::shell::state::AppState;
+
+#[derive(Deserialize)]
+pub struct ConsumerLagParams {
+    /// Only the stalled partitions.
+    #[serde(default)]
+    pub stalled: bool,
+}
+
+/// GET /admin/consumers/lag?stalled= — lag per consumer and topic partition. Admins only.
+pub async fn handle_list(
+    State(state): State<AppState>,
+    request_ctx: RequestContext,
+    Query(params): Query<ConsumerLagParams>,
+) -> Response {
+    if !request_ctx.principal().can_administer() {
+        return StatusCode::FORBIDDEN.into_response();
+    }
+    let partitions: Vec<_> = state
+        .consumer_lag
+        .partitions(Utc::now().timestamp_millis())
+        .into_iter()
+        .filter(|partition| !params.stalled || partition.stalled)
+        .collect();
+    Json(partitions).into_response()
+}
+
+#[cfg(test)]
+mod consumer_lag_tests {
+    use super::*;
+    use crate::shared::infrastructure::message_broker::consumer_lag::PartitionOffsets;
+    use crate::tests::fixtures::tags::make_test_app_state;
+    use axum::{Router, body::Body, http::Request, routing::get};
+    use http_body_util::BodyExt;
+    use rstest::rstest;
+    use serde_json::Value;
+    use tower::ServiceExt;
+
+    fn app(state: AppState) -> Router {
+        Router::new()
+            .route("/admin/consumers/lag", get(handle_list))
+            .with_state(state)
+    }
+
+    fn offsets(partition: u32, committed_offset: i64) -> PartitionOffsets {
+        PartitionOffsets {
+            topic: "time-entries.v1".to_string(),
+            partition,
+            head_offset: 10,
+            committed_offset: Some(committed_offset),
+        }
+    }
+
+    /// Partition 0 stopped committing an hour ago, partition 1 is caught up.
+    fn make_state() -> AppState {
+        let state = make_test_app_state();
+        let an_hour_ago = Utc::now().timestamp_millis() - 60 * 60 * 1000;
+        state
+            .consumer_lag
+            .record("billing", vec![offsets(0, 4), offsets(1, 10)], an_hour_ago);
+        state
+    }
+
+    async fn send(state: AppState, uri: &str, role: &str) -> (StatusCode, String) {
+        let request = Request::builder()
+            .uri(uri)
+            .header("x-user-id", "admin-1")
+            .header("x-tenant-id", "tenant-test")
+            .header("x-user-role", role)
+            .body(Body::empty())
+            .unwrap();
+        let response = app(state).oneshot(request).await.unwrap();
+        let status = response.status();
+        let bytes = response.into_body().collect().await.unwrap().to_bytes();
+        (status, String::from_utf8(bytes.to_vec()).unwrap())
+    }
+
+    #[rstest]
+    #[case::all("/admin/consumers/lag", 2)]
+    #[case::stalled("/admin/consumers/lag?stalled=true", 1)]
+    #[tokio::test]
+    async fn it_should_list_the_lag_per_partition(#[case] uri: &str, #[case] count: usize) {
+        let (status, body) = send(make_state(), uri, "admin").await;
+
+        assert_eq!(status, StatusCode::OK);
+        let partitions: Vec<Value> = serde_json::from_str(&body).unwrap();
+        assert_eq!(partitions.len(), count);
+        assert_eq!(partitions[0]["consumer"], "billing");
+        assert_eq!(partitions[0]["partition"], 0);
+        assert_eq!(partitions[0]["lag"], 6);
+        assert_eq!(partitions[0]["stalled"], true);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_be_reserved_for_admins() {
+        let (status, _) = send(make_state(), "/admin/consumers/lag", "manager").await;
+
+        assert_eq!(status, StatusCode::FORBIDDEN);
+    }
+}
@@ -0,0 +1,296 @@
+// Control plane for background workers. Admins pause a list time entries projector or the
+// outbox relay while remediating an incident, and resume it afterwards, without a redeploy.
+// Pauses are kept in the control store, which the workers poll; a resumed projector catches
+// up from its checkpoint and a resumed relay sends the rows that piled up.
+
+use async_graphql::{Context, Object, Result as GqlResult, SimpleObject};
+use chrono::Utc;
+
+use crate::modules::time_entries::adapters::outbound::intent_outbox::OUTBOX_TOPIC;
+use crate::modules::time_entries::use_cases::list_time_entries::projector::partition_projector_name;
+use crate::shared::infrastructure::control_store::{
+    ControlStore, WorkerControl, outbox_relay_worker, projector_worker,
+};
+use crate::shared::infrastructure::request_context::RequestContext;
+use crate::shell::state::AppState;
+
+#[derive(SimpleObject, Clone)]
+#[graphql(name = "WorkerControl")]
+pub struct GqlWorkerControl {
+    /// `projector:<name>` or `outbox_relay:<topic>`.
+    pub worker: String,
+    pub paused: bool,
+    pub updated_at: i64,
+    pub updated_by: String,
+}
+
+impl From<WorkerControl> for GqlWorkerControl {
+    fn from(control: WorkerControl) -> Self {
+        Self {
+            worker: control.worker,
+            paused: control.paused,
+            updated_at: control.updated_at,
+            updated_by: control.updated_by,
+        }
+    }
+}
+
+fn admin<'a>(context: &'a Context<'_>) -> GqlResult<&'a RequestContext> {
+    let req_ctx = context
+        .data::<RequestContext>()
+        .map_err(|_| async_graphql::Error::new("Unauthorized"))?;
+    if !req_ctx.principal().can_administer() {
+        return Err(async_graphql::Error::new("Forbidden"));
+    }
+    Ok(req_ctx)
+}
+
+/// The projectors this instance can run, one per list time entries partition.
+fn projector_names(state: &AppState) -> Vec<String> {
+    state
+        .list_time_entries_handler
+        .store()
+        .partitions()
+        .into_iter()
+        .map(|(partition, _)| partition_projector_name(partition))
+        .collect()
+}
+
+async fn set_paused(
+    context: &Context<'_>,
+    worker: String,
+    paused: bool,
+) -> GqlResult<GqlWorkerControl> {
+    let req_ctx = admin(context)?;
+    let state = context.data_unchecked::<AppState>();
+    let control = WorkerControl {
+        worker,
+        paused,
+        updated_at: Utc::now().timestamp_millis(),
+        updated_by: req_ctx.user_id.clone(),
+    };
+    state
+        .control_store
+        .put(control.clone())
+        .await
+        .map_err(|e| async_graphql::Error::new(e.to_string()))?;
+    Ok(control.into())
+}
+
+fn projector(context: &Context<'_>, name: &str) -> GqlResult<String> {
+    admin(context)?;
+    if !projector_names(context.data_unchecked::<AppState>()).contains(&name.to_string()) {
+        return Err(async_graphql::Error::new(format!(
+            "Unknown projector {name}"
+        )));
+    }
+    Ok(projector_worker(name))
+}
+
+fn outbox_relay(context: &Context<'_>, topic: Option<String>) -> GqlResult<String> {
+    admin(context)?;
+    let topic = topic.unwrap_or_else(|| OUTBOX_TOPIC.to_string());
+    if topic != OUTBOX_TOPIC {
+        return Err(async_graphql::Error::new(format!(
+            "Unknown outbox relay {topic}"
+        )));
+    }
+    Ok(outbox_relay_worker(&topic))
+}
+
+#[derive(Default)]
+pub struct ControlMutation;
+
+#[Object]
+impl ControlMutation {
+    /// Stops a projector, such as `list_time_entries:0`, from applying events until resumed.
+    /// Admins only.
+    async fn pause_projector(
+        &self,
+        context: &Context<'_>,
+        name: String,
+    ) -> GqlResult<GqlWorkerControl> {
+        let worker = projector(context, &name)?;
+        set_paused(context, worker, true).await
+    }
+
+    /// Lets a paused projector catch up and carry on. Admins only.
+    async fn resume_projector(
+        &self,
+        context: &Context<'_>,
+        name: String,
+    ) -> GqlResult<GqlWorkerControl> {
+        let worker = projector(context, &name)?;
+        set_paused(context, worker, false).await
+    }
+
+    /// Stops relaying outbox rows of `topic` (the time entries topic by default) to the
+    /// broker; rows stay pending until resumed. Admins only.
+    async fn pause_outbox_relay(
+        &self,
+        context: &Context<'_>,
+        topic: Option<String>,
+    ) -> GqlResult<GqlWorkerControl> {
+        let worker = outbox_relay(context, topic)?;
+        set_paused(context, worker, true).await
+    }
+
+    /// Resumes relaying outbox rows of `topic`. Admins only.
+    async fn resume_outbox_relay(
+        &self,
+        context: &Context<'_>,
+        topic: Option<String>,
+    ) -> GqlResult<GqlWorkerControl> {
+        let worker = outbox_relay(context, topic)?;
+        set_paused(context, worker, false).await
+    }
+}
+
+#[derive(Default)]
+pub struct ControlQuery;
+
+#[Object]
+impl ControlQuery {
+    /// Every worker an admin has paused or resumed, with who did so last. Admins only.
+    async fn worker_controls(&self, context: &Context<'_>) -> GqlResult<Vec<GqlWorkerControl>> {
+        admin(context)?;
+        let state = context.data_unchecked::<AppState>();
+        let controls = state
+            .control_store
+            .list()
+            .await
+            .map_err(|e| async_graphql::Error::new(e.to_string()))?;
+        Ok(controls.into_iter().map(Into::into).collect())
+    }
+}
+
+#[cfg(test)]
+mod control_tests {
+    use async_graphql::{EmptySubscription, Schema};
+    use rstest::rstest;
+
+    use crate::shared::auth::rbac::Role;
+    use crate::shared::infrastructure::control_store::ControlStore;
+    use crate::shared::infrastructure::request_context::RequestContext;
+    use crate::shell::graphql::{MutationRoot, QueryRoot};
+    use crate::shell::state::AppState;
+    use crate::tests::fixtures::tags::make_test_app_state;
+
+    fn schema(state: AppState) -> Schema<QueryRoot, MutationRoot, EmptySubscription> {
+        Schema::build(
+            QueryRoot::default(),
+            MutationRoot::default(),
+            EmptySubscription,
+        )
+        .data(state)
+        .finish()
+    }
+
+    fn req_ctx(role: Role) -> RequestContext {
+        RequestContext {
+            user_id: "admin-1".to_string(),
+            tenant_id: "tenant-test".to_string(),
+            role,
+            scope: Default::default(),
+        }
+    }
+
+    async fn execute(state: &AppState, query: &str, role: Role) -> async_graphql::Response {
+        schema(state.clone())
+            .execute(async_graphql::Request::new(query).data(req_ctx(role)))
+            .await
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_pause_and_resume_a_projector() {
+        let state = make_test_app_state();
+
+        let result = execute(
+            &state,
+            r#"mutation { pauseProjector(name: "list_time_entries:0") { worker paused updatedBy } }"#,
+            Role::Admin,
+        )
+        .await;
+        assert!(result.errors.is_empty(), "{:?}", result.errors);
+        assert_eq!(
+            result.data.to_string(),
+            r#"{pauseProjector: {worker: "projector:list_time_entries:0", paused: true, updatedBy: "admin-1"}}"#
+        );
+        let stored = state.control_store.get("projector:list_time_entries:0");
+        assert!(stored.await.unwrap().unwrap().paused);
+
+        execute(
+            &state,
+            r#"mutation { resumeProjector(name: "list_time_entries:0") { paused } }"#,
+            Role::Admin,
+        )
+        .await;
+        let stored = state.control_store.get("projector:list_time_entries:0");
+        assert!(!stored.await.unwrap().unwrap().paused);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_pause_the_outbox_relay_and_list_controls() {
+        let state = make_test_app_state();
+
+        execute(
+            &state,
+            "mutation { pauseOutboxRelay { paused } }",
+            Role::Admin,
+        )
+        .await;
+        let result = execute(&state, "{ workerControls { worker paused } }", Role::Admin).await;
+
+        assert_eq!(
+            result.data.to_string(),
+            r#"{workerControls: [{worker: "outbox_relay:time-entries.v1", paused: true}]}"#
+        );
+    }
+
+    #[rstest]
+    #[case::unknown_projector(r#"mutation { pauseProjector(name: "list_tags") { paused } }"#)]
+    #[case::unknown_partition(
+        r#"mutation { resumeProjector(name: "list_time_entries:7") { paused } }"#
+    )]
+    #[case::unknown_topic(r#"mutation { pauseOutboxRelay(topic: "billing.v1") { paused } }"#)]
+    #[tokio::test]
+    async fn it_should_reject_unknown_workers(#[case] query: &str) {
+        let state = make_test_app_state();
+
+        let result = execute(&state, query, Role::Admin).await;
+
+        assert!(result.errors[0].message.starts_with("Unknown"));
+        assert!(state.control_store.list().await.unwrap().is_empty());
+    }
+
+    #[rstest]
+    #[case::pause(r#"mutation { pauseProjector(name: "list_time_entries:0") { paused } }"#)]
+    #[case::relay("mutation { resumeOutboxRelay { paused } }")]
+    #[case::list("{ workerControls { worker } }")]
+    #[tokio::test]
+    async fn it_should_be_reserved_for_admins(#[case] query: &str) {
+        let result = execute(&make_test_app_state(), query, Role::Manager).await;
+        assert_eq!(result.errors[0].message, "Forbidden");
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_report_an_unavailable_control_store() {
+        let state = make_test_app_state();
+        state.control_store.toggle_offline();
+
+        let result = execute(
+            &state,
+            "mutation { pauseOutboxRelay { paused } }",
+            Role::Admin,
+        )
+        .await;
+
+        assert_eq!(
+            result.errors[0].message,
+            "backend error: Control store offline"
+        );
+    }
+}
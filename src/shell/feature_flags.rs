@@ -0,0 +1,280 @@
+// Admin API over the feature flags time entry commands consult. Admins turn a rule on or off
+// for one tenant, or for all of them, without a redeploy; clearing the override hands the
+// flag back to the configured source. Overrides last until the process restarts.
+
+use axum::{
+    Json,
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+
+use crate::modules::time_entries::use_cases::user_time_entries::sharded_handler::FEATURE_FLAGS;
+use crate::shared::infrastructure::feature_flags::FeatureFlag;
+use crate::shared::infrastructure::feature_flags::in_memory::FlagOverride;
+use crate::shared::infrastructure::request_context::RequestContext;
+use crate::shell::state::AppState;
+
+#[derive(Serialize)]
+pub struct FeatureFlagView {
+    #[serde(flatten)]
+    pub flag: FeatureFlag,
+    pub overrides: Vec<FlagOverride>,
+}
+
+#[derive(Deserialize)]
+pub struct SetFeatureFlagBody {
+    pub enabled: bool,
+    pub tenant_id: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct FeatureFlagParams {
+    pub tenant_id: Option<String>,
+}
+
+fn known(name: &str) -> bool {
+    FEATURE_FLAGS.iter().any(|flag| flag.name == name)
+}
+
+/// GET /admin/feature-flags — every flag with its default and overrides. Admins only.
+pub async fn handle_list(State(state): State<AppState>, request_ctx: RequestContext) -> Response {
+    if !request_ctx.principal().can_administer() {
+        return StatusCode::FORBIDDEN.into_response();
+    }
+    let Ok(overrides) = state.feature_flags.list().await else {
+        return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+    };
+    let flags: Vec<FeatureFlagView> = FEATURE_FLAGS
+        .iter()
+        .map(|flag| FeatureFlagView {
+            flag: *flag,
+            overrides: overrides
+                .iter()
+                .filter(|flag_override| flag_override.flag == flag.name)
+                .cloned()
+                .collect(),
+        })
+        .collect();
+    Json(flags).into_response()
+}
+
+/// PUT /admin/feature-flags/{name} — turns the flag on or off for `tenant_id`, or for every
+/// tenant without one. Admins only.
+pub async fn handle_set(
+    State(state): State<AppState>,
+    request_ctx: RequestContext,
+    Path(name): Path<String>,
+    Json(body): Json<SetFeatureFlagBody>,
+) -> Response {
+    if !request_ctx.principal().can_administer() {
+        return StatusCode::FORBIDDEN.into_response();
+    }
+    if !known(&name) {
+        return StatusCode::NOT_FOUND.into_response();
+    }
+    let flag_override = FlagOverride {
+        flag: name,
+        tenant_id: body.tenant_id,
+        enabled: body.enabled,
+        updated_at: Utc::now().timestamp_millis(),
+        updated_by: request_ctx.user_id,
+    };
+    match state.feature_flags.set(flag_override.clone()).await {
+        Ok(()) => Json(flag_override).into_response(),
+        Err(_) => StatusCode::INTERNAL_SERVER_ERROR.into_response(),
+    }
+}
+
+/// DELETE /admin/feature-flags/{name}?tenant_id= — removes the override, so the configured
+/// state applies again. Admins only.
+pub async fn handle_clear(
+    State(state): State<AppState>,
+    request_ctx: RequestContext,
+    Path(name): Path<String>,
+    Query(params): Query<FeatureFlagParams>,
+) -> Response {
+    if !request_ctx.principal().can_administer() {
+        return StatusCode::FORBIDDEN.into_response();
+    }
+    if !known(&name) {
+        return StatusCode::NOT_FOUND.into_response();
+    }
+    match state
+        .feature_flags
+        .clear(&name, params.tenant_id.as_deref())
+        .await
+    {
+        Ok(true) => StatusCode::NO_CONTENT.into_response(),
+        Ok(false) => StatusCode::NOT_FOUND.into_response(),
+        Err(_) => StatusCode::INTERNAL_SERVER_ERROR.into_response(),
+    }
+}
+
+#[cfg(test)]
+mod feature_flags_tests {
+    use super::*;
+    use crate::shared::infrastructure::feature_flags::FeatureFlags;
+    use crate::tests::fixtures::tags::make_test_app_state;
+    use axum::{
+        Router,
+        body::Body,
+        http::Request,
+        routing::{get, put},
+    };
+    use http_body_util::BodyExt;
+    use rstest::rstest;
+    use serde_json::{Value, json};
+    use tower::ServiceExt;
+
+    fn app(state: AppState) -> Router {
+        Router::new()
+            .route("/admin/feature-flags", get(handle_list))
+            .route(
+                "/admin/feature-flags/{name}",
+                put(handle_set).delete(handle_clear),
+            )
+            .with_state(state)
+    }
+
+    async fn send(
+        state: &AppState,
+        method: &str,
+        uri: &str,
+        role: &str,
+        body: Option<Value>,
+    ) -> (StatusCode, Value) {
+        let request = Request::builder()
+            .method(method)
+            .uri(uri)
+            .header("x-user-id", "admin-1")
+            .header("x-tenant-id", "tenant-test")
+            .header("x-user-role", role)
+            .header("content-type", "application/json");
+        let body = body.map_or_else(Body::empty, |body| Body::from(body.to_string()));
+        let response = app(state.clone())
+            .oneshot(request.body(body).unwrap())
+            .await
+            .unwrap();
+        let status = response.status();
+        let bytes = response.into_body().collect().await.unwrap().to_bytes();
+        (
+            status,
+            serde_json::from_slice(&bytes).unwrap_or(Value::Null),
+        )
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_roll_a_flag_out_to_one_tenant() {
+        let state = make_test_app_state();
+
+        let (status, body) = send(
+            &state,
+            "PUT",
+            "/admin/feature-flags/max_entry_duration",
+            "admin",
+            Some(json!({"enabled": true, "tenant_id": "t-1"})),
+        )
+        .await;
+
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["updated_by"], "admin-1");
+        assert_eq!(
+            state
+                .feature_flags
+                .lookup("max_entry_duration", Some("t-1"))
+                .await,
+            Ok(Some(true))
+        );
+        assert_eq!(
+            state
+                .feature_flags
+                .lookup("max_entry_duration", Some("t-2"))
+                .await,
+            Ok(None)
+        );
+
+        let (status, body) = send(&state, "GET", "/admin/feature-flags", "admin", None).await;
+
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body[0]["name"], "reject_overlaps");
+        assert_eq!(body[0]["default"], true);
+        assert_eq!(body[0]["overrides"], json!([]));
+        assert_eq!(body[1]["name"], "max_entry_duration");
+        assert_eq!(body[1]["overrides"][0]["tenant_id"], "t-1");
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_clear_an_override() {
+        let state = make_test_app_state();
+        send(
+            &state,
+            "PUT",
+            "/admin/feature-flags/reject_overlaps",
+            "admin",
+            Some(json!({"enabled": false})),
+        )
+        .await;
+
+        let uri = "/admin/feature-flags/reject_overlaps";
+        let (cleared, _) = send(&state, "DELETE", uri, "admin", None).await;
+        let (again, _) = send(&state, "DELETE", uri, "admin", None).await;
+
+        assert_eq!(cleared, StatusCode::NO_CONTENT);
+        assert_eq!(again, StatusCode::NOT_FOUND);
+        assert!(state.feature_flags.list().await.unwrap().is_empty());
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_reject_unknown_flags() {
+        let state = make_test_app_state();
+
+        let (status, _) = send(
+            &state,
+            "PUT",
+            "/admin/feature-flags/auto_approve",
+            "admin",
+            Some(json!({"enabled": true})),
+        )
+        .await;
+
+        assert_eq!(status, StatusCode::NOT_FOUND);
+    }
+
+    #[rstest]
+    #[case::list("GET", "/admin/feature-flags", None)]
+    #[case::set(
+        "PUT",
+        "/admin/feature-flags/reject_overlaps",
+        Some(json!({"enabled": false}))
+    )]
+    #[case::clear("DELETE", "/admin/feature-flags/reject_overlaps", None)]
+    #[tokio::test]
+    async fn it_should_be_reserved_for_admins(
+        #[case] method: &str,
+        #[case] uri: &str,
+        #[case] body: Option<Value>,
+    ) {
+        let state = make_test_app_state();
+
+        let (status, _) = send(&state, method, uri, "manager", body).await;
+
+        assert_eq!(status, StatusCode::FORBIDDEN);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_return_500_when_the_store_is_offline() {
+        let state = make_test_app_state();
+        state.feature_flags.toggle_offline();
+
+        let (status, _) = send(&state, "GET", "/admin/feature-flags", "admin", None).await;
+
+        assert_eq!(status, StatusCode::INTERNAL_SERVER_ERROR);
+    }
+}
@@ -0,0 +1,98 @@
+// GDPR erasure requests. Forgetting a user deletes their data key: every personal data field
+// of their entries reads as `[forgotten]` from then on, in loads, data exports and projector
+// rebuilds alike, and the time entry read models are erased to match. The events themselves
+// stay where they are.
+//
+// The user id is personal data too, so the request is logged by the id of the deleted key.
+
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+};
+
+use crate::shared::application::forget_user::ForgetUser;
+use crate::shared::infrastructure::request_context::RequestContext;
+use crate::shell::state::AppState;
+
+/// POST /admin/users/{user_id}/forget — shreds the user's personal data. `204 No Content`
+/// whether or not anything was stored for them, so requests can be repeated. Admins only.
+pub async fn handle_forget(
+    State(state): State<AppState>,
+    request_ctx: RequestContext,
+    Path(user_id): Path<String>,
+) -> impl IntoResponse {
+    if !request_ctx.principal().can_administer() {
+        return StatusCode::FORBIDDEN;
+    }
+    match state
+        .forget_user_handler
+        .handle(ForgetUser { user_id })
+        .await
+    {
+        Ok(key_id) => {
+            tracing::info!(key_id, "user forgotten");
+            StatusCode::NO_CONTENT
+        }
+        Err(error) => {
+            tracing::error!(%error, "forgetting a user failed");
+            StatusCode::INTERNAL_SERVER_ERROR
+        }
+    }
+}
+
+#[cfg(test)]
+mod forget_user_tests {
+    use super::*;
+    use crate::shared::application::forget_user::ForgetUserHandler;
+    use crate::shared::infrastructure::key_store::KeyStore;
+    use crate::shared::infrastructure::key_store::in_memory::InMemoryKeyStore;
+    use crate::tests::fixtures::tags::make_test_app_state;
+    use axum::{Router, body::Body, http::Request, routing::post};
+    use rstest::rstest;
+    use tower::ServiceExt;
+
+    async fn send(state: AppState, role: &str) -> StatusCode {
+        Router::new()
+            .route("/admin/users/{user_id}/forget", post(handle_forget))
+            .with_state(state)
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri("/admin/users/u-1/forget")
+                    .header("x-user-id", "admin-1")
+                    .header("x-tenant-id", "tenant-test")
+                    .header("x-user-role", role)
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap()
+            .status()
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_delete_the_users_key() {
+        let key_store = InMemoryKeyStore::new();
+        let key = key_store.key_for_subject("u-1").await.unwrap();
+        let mut state = make_test_app_state();
+        state.forget_user_handler = ForgetUserHandler::new(key_store.clone());
+
+        assert_eq!(send(state.clone(), "admin").await, StatusCode::NO_CONTENT);
+        assert_eq!(send(state, "admin").await, StatusCode::NO_CONTENT);
+        assert!(key_store.key_by_id(&key.id).await.unwrap().is_none());
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_be_reserved_for_admins() {
+        let key_store = InMemoryKeyStore::new();
+        let key = key_store.key_for_subject("u-1").await.unwrap();
+        let mut state = make_test_app_state();
+        state.forget_user_handler = ForgetUserHandler::new(key_store.clone());
+
+        assert_eq!(send(state, "employee").await, StatusCode::FORBIDDEN);
+        assert!(key_store.key_by_id(&key.id).await.unwrap().is_some());
+    }
+}
@@ -1,16 +1,50 @@
-use async_graphql::{EmptySubscription, MergedObject, Schema};
+use async_graphql::http::ALL_WEBSOCKET_PROTOCOLS;
+use async_graphql::{Data, EmptyMutation, MergedObject, MergedSubscription, Schema, SchemaBuilder};
+use async_graphql_axum::{GraphQLProtocol, GraphQLRequest, GraphQLResponse, GraphQLWebSocket};
+use axum::extract::WebSocketUpgrade;
+use axum::response::Response;
+use axum::{Extension, Router, http::HeaderMap, middleware, routing::get};
+use std::time::Duration;
 
+use crate::modules::contracts::use_cases::set_contract::inbound::graphql::SetContractMutation;
+use crate::modules::contracts::use_cases::utilization::inbound::graphql::UtilizationQuery;
+use crate::modules::schedules::use_cases::cancel_schedule::inbound::graphql::CancelScheduleMutation;
+use crate::modules::schedules::use_cases::define_schedule::inbound::graphql::DefineScheduleMutation;
+use crate::modules::schedules::use_cases::list_schedules::inbound::graphql::ListSchedulesQuery;
 use crate::modules::tags::use_cases::create_tag::inbound::graphql::CreateTagMutation;
 use crate::modules::tags::use_cases::delete_tag::inbound::graphql::DeleteTagMutation;
 use crate::modules::tags::use_cases::list_tags::inbound::graphql::ListTagsQuery;
 use crate::modules::tags::use_cases::set_tag_color::inbound::graphql::SetTagColorMutation;
 use crate::modules::tags::use_cases::set_tag_description::inbound::graphql::SetTagDescriptionMutation;
 use crate::modules::tags::use_cases::set_tag_name::inbound::graphql::SetTagNameMutation;
-use crate::modules::time_entries::use_cases::list_time_entries::inbound::graphql::TimeEntryQueries;
+use crate::modules::templates::use_cases::apply_template::inbound::graphql::ApplyTemplateMutation;
+use crate::modules::templates::use_cases::list_templates::inbound::graphql::ListTemplatesQuery;
+use crate::modules::templates::use_cases::save_template::inbound::graphql::SaveTemplateMutation;
+use crate::modules::time_entries::use_cases::add_time_entry_attachment::inbound::graphql::AddTimeEntryAttachmentMutation;
+use crate::modules::time_entries::use_cases::add_time_entry_comment::inbound::graphql::AddTimeEntryCommentMutation;
+use crate::modules::time_entries::use_cases::approve_time_entry::inbound::graphql::ApproveTimeEntriesMutation;
+use crate::modules::time_entries::use_cases::list_time_entries::inbound::graphql::{
+    TimeEntryQueries, TimeEntrySubscriptions,
+};
+use crate::modules::time_entries::use_cases::register_time_entry::inbound::graphql::RegisterTimeEntryMutation;
+use crate::modules::time_entries::use_cases::reject_time_entry::inbound::graphql::RejectTimeEntriesMutation;
+use crate::modules::time_entries::use_cases::set_breaks::inbound::graphql::SetBreaksMutation;
 use crate::modules::time_entries::use_cases::set_ended_at::inbound::graphql::SetEndedAtMutation;
+use crate::modules::time_entries::use_cases::set_hourly_rate::inbound::graphql::SetHourlyRateMutation;
 use crate::modules::time_entries::use_cases::set_started_at::inbound::graphql::SetStartedAtMutation;
 use crate::modules::time_entries::use_cases::set_time_entry_tags::inbound::graphql::SetTimeEntryTagsMutation;
+use crate::modules::time_entries::use_cases::user_stats::inbound::graphql::UserStatsQuery;
+use crate::shared::infrastructure::api_audit_store::in_memory::InMemoryApiAuditStore;
+use crate::shared::infrastructure::api_key_store::ApiKey;
+use crate::shared::infrastructure::api_key_store::in_memory::InMemoryApiKeyStore;
+use crate::shared::infrastructure::request_context::{
+    RequestContext, from_connection_init, resolve_api_key,
+};
+use crate::shell::audit::audit_mutations;
+use crate::shell::control::{ControlMutation, ControlQuery};
+use crate::shell::policies::{PoliciesMutation, PoliciesQuery};
 pub use crate::shell::state::AppState;
+use crate::shell::tuning::{TuningMutation, TuningQuery};
 
 #[derive(MergedObject, Default)]
 pub struct MutationRoot(
@@ -21,10 +55,181 @@ pub struct MutationRoot(
     SetTagDescriptionMutation,
     SetStartedAtMutation,
     SetEndedAtMutation,
+    RegisterTimeEntryMutation,
     SetTimeEntryTagsMutation,
+    SetHourlyRateMutation,
+    SetBreaksMutation,
+    ApproveTimeEntriesMutation,
+    RejectTimeEntriesMutation,
+    AddTimeEntryCommentMutation,
+    AddTimeEntryAttachmentMutation,
+    SaveTemplateMutation,
+    ApplyTemplateMutation,
+    DefineScheduleMutation,
+    CancelScheduleMutation,
+    SetContractMutation,
+    ControlMutation,
+    TuningMutation,
+    PoliciesMutation,
 );
 
 #[derive(MergedObject, Default)]
-pub struct QueryRoot(TimeEntryQueries, ListTagsQuery);
+pub struct QueryRoot(
+    TimeEntryQueries,
+    ListTagsQuery,
+    ListTemplatesQuery,
+    ListSchedulesQuery,
+    UtilizationQuery,
+    UserStatsQuery,
+    ControlQuery,
+    TuningQuery,
+    PoliciesQuery,
+);
+
+#[derive(MergedSubscription, Default)]
+pub struct SubscriptionRoot(TimeEntrySubscriptions);
+
+pub type AppSchema = Schema<QueryRoot, MutationRoot, SubscriptionRoot>;
+
+/// Served over WebSocket. Mutations stay on `POST /gql`, behind the audit layer.
+pub type WsSchema = Schema<QueryRoot, EmptyMutation, SubscriptionRoot>;
+
+/// GraphQL over WebSocket settings. Clients should speak `graphql-transport-ws`; the legacy
+/// `graphql-ws` (subscriptions-transport-ws) protocol is still accepted.
+#[derive(Debug, Clone, Copy)]
+pub struct WsConfig {
+    /// A connection that sends nothing, not even a `ping`, for this long is closed (3008 on
+    /// `graphql-transport-ws`). Clients ping more often than this to stay connected; `None`
+    /// never closes.
+    pub keepalive_timeout: Option<Duration>,
+}
+
+impl Default for WsConfig {
+    fn default() -> Self {
+        Self {
+            keepalive_timeout: Some(Duration::from_secs(30)),
+        }
+    }
+}
+
+/// Checked-in SDL of `AppSchema`, for frontend consumers. Regenerate it with
+/// `cargo run -- --print-schema > schema.graphql`.
+pub const SDL_PATH: &str = "./schema.graphql";
+
+fn builder() -> SchemaBuilder<QueryRoot, MutationRoot, SubscriptionRoot> {
+    Schema::build(
+        QueryRoot::default(),
+        MutationRoot::default(),
+        SubscriptionRoot::default(),
+    )
+}
+
+pub fn schema(state: AppState) -> AppSchema {
+    builder().data(state).finish()
+}
+
+pub fn ws_schema(state: AppState) -> WsSchema {
+    Schema::build(
+        QueryRoot::default(),
+        EmptyMutation,
+        SubscriptionRoot::default(),
+    )
+    .data(state)
+    .finish()
+}
+
+/// The schema in SDL. Needs no app state; resolvers never run.
+pub fn sdl() -> String {
+    builder().finish().sdl()
+}
+
+/// `/gql`: GraphiQL on GET, queries on POST, behind the same API key and audit layers as
+/// the REST routes. `/gql/ws`: subscriptions over WebSocket, authenticated on
+/// `connection_init`.
+pub fn router(state: AppState, ws: WsConfig) -> Router {
+    Router::new()
+        .route("/gql/ws", get(graphql_ws))
+        .layer(Extension(ws_schema(state.clone())))
+        .layer(Extension(state.api_key_store.clone()))
+        .layer(Extension(ws))
+        .route(
+            "/gql",
+            get(graphiql)
+                .post(graphql)
+                .layer(middleware::from_fn_with_state(
+                    state.audit_store.clone(),
+                    audit_mutations::<InMemoryApiAuditStore>,
+                ))
+                .layer(middleware::from_fn_with_state(
+                    state.api_key_store.clone(),
+                    resolve_api_key::<InMemoryApiKeyStore>,
+                )),
+        )
+        .layer(Extension(schema(state)))
+}
+
+async fn graphql(
+    Extension(schema): Extension<AppSchema>,
+    api_key: Option<Extension<ApiKey>>,
+    headers: HeaderMap,
+    req: GraphQLRequest,
+) -> GraphQLResponse {
+    let mut inner = req.into_inner();
+    let api_key = api_key.map(|Extension(api_key)| api_key);
+    if let Ok(request_ctx) = RequestContext::from_headers(&headers, api_key.as_ref()) {
+        inner = inner.data(request_ctx);
+    }
+    schema.execute(inner).await.into()
+}
+
+async fn graphql_ws(
+    Extension(schema): Extension<WsSchema>,
+    Extension(api_key_store): Extension<InMemoryApiKeyStore>,
+    Extension(ws): Extension<WsConfig>,
+    protocol: GraphQLProtocol,
+    headers: HeaderMap,
+    upgrade: WebSocketUpgrade,
+) -> Response {
+    upgrade
+        .protocols(ALL_WEBSOCKET_PROTOCOLS)
+        .on_upgrade(move |socket| {
+            GraphQLWebSocket::new(socket, schema, protocol)
+                .on_connection_init(move |payload| async move {
+                    let request_ctx = from_connection_init(&api_key_store, &payload, &headers)
+                        .await
+                        .map_err(|_| async_graphql::Error::new("Unauthorized"))?;
+                    let mut data = Data::default();
+                    data.insert(request_ctx);
+                    Ok(data)
+                })
+                .keepalive_timeout(ws.keepalive_timeout)
+                .serve()
+        })
+}
+
+async fn graphiql() -> axum::response::Html<String> {
+    use async_graphql::http::GraphiQLSource;
+    axum::response::Html(
+        GraphiQLSource::build()
+            .endpoint("/gql")
+            .subscription_endpoint("/gql/ws")
+            .finish(),
+    )
+}
+
+#[cfg(test)]
+mod schema_sdl_tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    fn the_checked_in_sdl_should_match_the_schema() {
+        let checked_in = std::fs::read_to_string(SDL_PATH).unwrap_or_default();
 
-pub type AppSchema = Schema<QueryRoot, MutationRoot, EmptySubscription>;
+        assert!(
+            checked_in == sdl(),
+            "schema.graphql is out of date; review the change for frontend breaks and \
+             regenerate it with `cargo run -- --print-schema > schema.graphql`"
+        );
+    }
+}
@@ -0,0 +1,113 @@
+// Gives every request a deadline its backend calls must finish by: the route group's default,
+// or less when the caller asks for it in `x-request-timeout-ms`; a caller cannot ask for more.
+// Adapters honor it through `shared::infrastructure::deadline`. A request an adapter gave up
+// on, or that is still running once the deadline passes, is answered with a 504 instead of a
+// 500 or a hung connection. Responses the handler already decided on, such as a 409, pass as
+// they are.
+
+use axum::{
+    extract::{Request, State},
+    http::StatusCode,
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use std::time::Duration;
+use tokio::time::Instant;
+
+use crate::shared::infrastructure::deadline::{self, Deadline};
+
+pub const REQUEST_TIMEOUT_HEADER: &str = "x-request-timeout-ms";
+
+/// Middleware running the request under a deadline `default` from now at the latest. A
+/// malformed `x-request-timeout-ms` is rejected with 400.
+pub async fn enforce_deadline(
+    State(default): State<Duration>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let budget = match request.headers().get(REQUEST_TIMEOUT_HEADER) {
+        None => default,
+        Some(value) => match value.to_str().ok().and_then(|ms| ms.parse().ok()) {
+            Some(ms) => Duration::from_millis(ms).min(default),
+            None => return StatusCode::BAD_REQUEST.into_response(),
+        },
+    };
+    let deadline = Deadline::at(Instant::now() + budget);
+    let handled = deadline::scope(deadline.clone(), next.run(request));
+    match tokio::time::timeout_at(deadline.instant(), handled).await {
+        Ok(response) if deadline.is_exceeded() && response.status().is_server_error() => {
+            StatusCode::GATEWAY_TIMEOUT.into_response()
+        }
+        Ok(response) => response,
+        Err(_) => StatusCode::GATEWAY_TIMEOUT.into_response(),
+    }
+}
+
+#[cfg(test)]
+mod deadline_middleware_tests {
+    use super::*;
+    use crate::shared::infrastructure::deadline::within;
+    use axum::{Router, body::Body, middleware, routing::get};
+    use rstest::rstest;
+    use tower::ServiceExt;
+
+    /// `/slow` waits for a backend taking 200ms; `/hung` ignores the deadline altogether;
+    /// `/conflict` fails on its own after a backend gave up.
+    fn app(default: Duration) -> Router {
+        let backend = || within(tokio::time::sleep(Duration::from_millis(200)));
+        Router::new()
+            .route(
+                "/slow",
+                get(move || async move {
+                    match backend().await {
+                        Ok(()) => StatusCode::OK,
+                        Err(_) => StatusCode::INTERNAL_SERVER_ERROR,
+                    }
+                }),
+            )
+            .route(
+                "/hung",
+                get(|| async {
+                    tokio::time::sleep(Duration::from_secs(5)).await;
+                    StatusCode::OK
+                }),
+            )
+            .route(
+                "/conflict",
+                get(move || async move {
+                    let _ = backend().await;
+                    StatusCode::CONFLICT
+                }),
+            )
+            .layer(middleware::from_fn_with_state(default, enforce_deadline))
+    }
+
+    async fn send(app: Router, uri: &str, timeout_ms: Option<&str>) -> StatusCode {
+        let mut request = Request::get(uri);
+        if let Some(timeout_ms) = timeout_ms {
+            request = request.header(REQUEST_TIMEOUT_HEADER, timeout_ms);
+        }
+        app.oneshot(request.body(Body::empty()).unwrap())
+            .await
+            .unwrap()
+            .status()
+    }
+
+    #[rstest]
+    #[case::within_the_default("/slow", None, StatusCode::OK)]
+    #[case::caller_asks_for_less("/slow", Some("20"), StatusCode::GATEWAY_TIMEOUT)]
+    #[case::caller_cannot_ask_for_more("/hung", Some("60000"), StatusCode::GATEWAY_TIMEOUT)]
+    #[case::handler_decided("/conflict", Some("20"), StatusCode::CONFLICT)]
+    #[case::malformed("/slow", Some("soon"), StatusCode::BAD_REQUEST)]
+    #[tokio::test]
+    async fn it_should_answer_504_once_the_deadline_passes(
+        #[case] uri: &str,
+        #[case] timeout_ms: Option<&str>,
+        #[case] expected: StatusCode,
+    ) {
+        assert_eq!(
+            send(app(Duration::from_millis(500)), uri, timeout_ms).await,
+            expected
+        );
+    }
+}
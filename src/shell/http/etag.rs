@@ -0,0 +1,68 @@
+// Optimistic concurrency over HTTP. A resource backed by an event stream is tagged with the
+// stream's version, and a write carrying `If-Match` only goes through while the stream is
+// still at that version; otherwise it is answered with 412 and the client re-reads.
+
+use axum::http::{HeaderMap, HeaderValue, header};
+
+/// The strong entity tag of a stream at `version`, e.g. `"7"`.
+pub fn etag(version: i64) -> HeaderValue {
+    HeaderValue::from_str(&format!("\"{version}\"")).expect("a quoted number is a valid header")
+}
+
+/// Why a precondition cannot hold.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PreconditionFailed;
+
+/// The stream version `If-Match` requires; `None` without the header or for `*`. A header
+/// naming anything but one entity tag of this resource can never match.
+pub fn if_match(headers: &HeaderMap) -> Result<Option<i64>, PreconditionFailed> {
+    let Some(value) = headers.get(header::IF_MATCH) else {
+        return Ok(None);
+    };
+    let value = value.to_str().map_err(|_| PreconditionFailed)?.trim();
+    if value == "*" {
+        return Ok(None);
+    }
+    value
+        .strip_prefix('"')
+        .and_then(|value| value.strip_suffix('"'))
+        .and_then(|version| version.parse().ok())
+        .map(Some)
+        .ok_or(PreconditionFailed)
+}
+
+#[cfg(test)]
+mod etag_tests {
+    use super::*;
+    use rstest::rstest;
+
+    fn headers(if_match: Option<&'static str>) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        if let Some(value) = if_match {
+            headers.insert(header::IF_MATCH, HeaderValue::from_static(value));
+        }
+        headers
+    }
+
+    #[rstest]
+    #[case::absent(None, Ok(None))]
+    #[case::any(Some("*"), Ok(None))]
+    #[case::version(Some("\"7\""), Ok(Some(7)))]
+    #[case::weak(Some("W/\"7\""), Err(PreconditionFailed))]
+    #[case::unquoted(Some("7"), Err(PreconditionFailed))]
+    #[case::several(Some("\"6\", \"7\""), Err(PreconditionFailed))]
+    fn it_should_read_the_version_if_match_requires(
+        #[case] if_match_header: Option<&'static str>,
+        #[case] expected: Result<Option<i64>, PreconditionFailed>,
+    ) {
+        assert_eq!(if_match(&headers(if_match_header)), expected);
+    }
+
+    #[rstest]
+    fn it_should_tag_round_trip_through_if_match() {
+        let mut headers = HeaderMap::new();
+        headers.insert(header::IF_MATCH, etag(12));
+
+        assert_eq!(if_match(&headers), Ok(Some(12)));
+    }
+}
@@ -0,0 +1,159 @@
+// Body size limits, deadlines and timeouts, applied per group of routes so bulk endpoints
+// (`/sync`) can accept larger payloads and more time than the rest. An oversized body is
+// answered with 413 before a handler buffers it; a request whose backends outlive its deadline
+// gets a 504, and one that still outlives its timeout is dropped with a 408.
+//
+// The limits wrap the audit layer: a 413 still shows up in the audit trail, but a timeout
+// drops the request mid-audit, so 408s are only visible in the trace logs.
+
+use axum::{Router, extract::DefaultBodyLimit, http::StatusCode, middleware};
+use std::time::Duration;
+use tower_http::limit::RequestBodyLimitLayer;
+use tower_http::timeout::TimeoutLayer;
+
+use crate::shell::http::deadline::enforce_deadline;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RouteLimits {
+    pub max_body_bytes: usize,
+    /// The default deadline for a request's backend calls, and the longest a caller may ask
+    /// for; kept under `timeout` so a slow backend answers 504 rather than 408.
+    pub deadline: Duration,
+    pub timeout: Duration,
+}
+
+impl RouteLimits {
+    pub const STANDARD: Self = Self {
+        max_body_bytes: 2 * 1024 * 1024,
+        deadline: Duration::from_secs(8),
+        timeout: Duration::from_secs(10),
+    };
+    pub const BULK: Self = Self {
+        max_body_bytes: 8 * 1024 * 1024,
+        deadline: Duration::from_secs(50),
+        timeout: Duration::from_secs(60),
+    };
+
+    /// Wraps every route already on `router`. axum's own 2 MiB extractor limit is lifted, so
+    /// `max_body_bytes` is the only limit in force.
+    pub fn apply<S>(self, router: Router<S>) -> Router<S>
+    where
+        S: Clone + Send + Sync + 'static,
+    {
+        router
+            .layer(middleware::from_fn_with_state(
+                self.deadline,
+                enforce_deadline,
+            ))
+            .layer(DefaultBodyLimit::disable())
+            .layer(RequestBodyLimitLayer::new(self.max_body_bytes))
+            .layer(TimeoutLayer::with_status_code(
+                StatusCode::REQUEST_TIMEOUT,
+                self.timeout,
+            ))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RequestLimits {
+    pub standard: RouteLimits,
+    /// For routes taking many changes per request.
+    pub bulk: RouteLimits,
+}
+
+impl Default for RequestLimits {
+    fn default() -> Self {
+        Self {
+            standard: RouteLimits::STANDARD,
+            bulk: RouteLimits::BULK,
+        }
+    }
+}
+
+#[cfg(test)]
+mod limits_tests {
+    use super::*;
+    use axum::{body::Body, extract::Request, routing::post};
+    use rstest::rstest;
+    use tower::ServiceExt;
+
+    fn app(limits: RouteLimits) -> Router {
+        limits.apply(
+            Router::new()
+                .route("/echo", post(|body: String| async move { body }))
+                .route(
+                    "/slow",
+                    post(|| async {
+                        tokio::time::sleep(Duration::from_secs(5)).await;
+                        "done"
+                    }),
+                ),
+        )
+    }
+
+    async fn send(app: Router, uri: &str, body: Body) -> StatusCode {
+        app.oneshot(Request::post(uri).body(body).unwrap())
+            .await
+            .unwrap()
+            .status()
+    }
+
+    #[rstest]
+    #[case::at_the_limit(16, StatusCode::OK)]
+    #[case::over_the_limit(17, StatusCode::PAYLOAD_TOO_LARGE)]
+    #[tokio::test]
+    async fn it_should_reject_bodies_over_the_limit(
+        #[case] size: usize,
+        #[case] expected: StatusCode,
+    ) {
+        let limits = RouteLimits {
+            max_body_bytes: 16,
+            ..RouteLimits::STANDARD
+        };
+        let status = send(app(limits), "/echo", Body::from("x".repeat(size))).await;
+        assert_eq!(status, expected);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_reject_streamed_bodies_that_grow_over_the_limit() {
+        let limits = RouteLimits {
+            max_body_bytes: 16,
+            ..RouteLimits::STANDARD
+        };
+        let chunks = async_graphql::futures_util::stream::iter(
+            ["x".repeat(10), "x".repeat(10)].map(Ok::<_, std::io::Error>),
+        );
+        let status = send(app(limits), "/echo", Body::from_stream(chunks)).await;
+        assert_eq!(status, StatusCode::PAYLOAD_TOO_LARGE);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_lift_axums_default_limit_for_larger_configured_limits() {
+        let body = Body::from("x".repeat(RouteLimits::STANDARD.max_body_bytes + 1));
+        let status = send(app(RouteLimits::BULK), "/echo", body).await;
+        assert_eq!(status, StatusCode::OK);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_answer_408_when_a_request_outlives_its_timeout() {
+        let limits = RouteLimits {
+            timeout: Duration::from_millis(10),
+            ..RouteLimits::STANDARD
+        };
+        let status = send(app(limits), "/slow", Body::empty()).await;
+        assert_eq!(status, StatusCode::REQUEST_TIMEOUT);
+    }
+
+    #[test]
+    fn it_should_give_bulk_routes_more_room_by_default() {
+        let limits = RequestLimits::default();
+        assert!(limits.bulk.max_body_bytes > limits.standard.max_body_bytes);
+        assert!(limits.bulk.timeout > limits.standard.timeout);
+        assert!(limits.bulk.deadline > limits.standard.deadline);
+        assert!(limits.standard.deadline < limits.standard.timeout);
+        assert!(limits.bulk.deadline < limits.bulk.timeout);
+    }
+}
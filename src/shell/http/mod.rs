@@ -0,0 +1,9 @@
+// REST surface of the service: the versioned use-case routes and the layers every request
+// passes through on its way to them.
+
+pub mod deadline;
+pub mod etag;
+pub mod limits;
+pub mod rate_limit;
+pub mod request_log;
+pub mod routes;
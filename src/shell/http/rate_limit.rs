@@ -0,0 +1,247 @@
+// Per-caller request budget in front of the whole router. A caller is its API key, else its
+// tenant and user headers; requests without either share one anonymous budget.
+//
+// Budgets are fixed windows kept in memory, so each instance counts on its own: behind a
+// load balancer the effective limit is the configured one times the number of instances.
+// A limiter watching config updates applies a new budget to the next request; a budget of
+// zero requests turns the limit off.
+
+use axum::{
+    extract::{Request, State},
+    http::{HeaderMap, HeaderValue, StatusCode, header},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::{Mutex, watch};
+
+use crate::shared::infrastructure::request_context::API_KEY_HEADER;
+
+/// Above this many tracked callers, expired windows are dropped before a new one is opened.
+const MAX_TRACKED_CALLERS: usize = 10_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RateLimitConfig {
+    pub requests: u32,
+    pub window: Duration,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Window {
+    started: Instant,
+    used: u32,
+}
+
+#[derive(Debug, Clone)]
+pub struct RateLimiter {
+    config: watch::Receiver<RateLimitConfig>,
+    windows: Arc<Mutex<HashMap<String, Window>>>,
+}
+
+impl RateLimiter {
+    pub fn new(config: RateLimitConfig) -> Self {
+        Self::watching(watch::channel(config).1)
+    }
+
+    pub fn watching(config: watch::Receiver<RateLimitConfig>) -> Self {
+        Self {
+            config,
+            windows: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// Spends one request of `caller`'s budget, or returns how long until the budget resets.
+    async fn admit(&self, caller: String, now: Instant) -> Result<(), Duration> {
+        let config = *self.config.borrow();
+        if config.requests == 0 {
+            return Ok(());
+        }
+        let mut windows = self.windows.lock().await;
+        if windows.len() >= MAX_TRACKED_CALLERS && !windows.contains_key(&caller) {
+            windows.retain(|_, window| now.duration_since(window.started) < config.window);
+        }
+        let window = windows.entry(caller).or_insert(Window {
+            started: now,
+            used: 0,
+        });
+        let elapsed = now.duration_since(window.started);
+        if elapsed >= config.window {
+            *window = Window {
+                started: now,
+                used: 0,
+            };
+        }
+        if window.used >= config.requests {
+            return Err(config.window - now.duration_since(window.started));
+        }
+        window.used += 1;
+        Ok(())
+    }
+}
+
+fn caller(headers: &HeaderMap) -> String {
+    let header = |name: &str| headers.get(name).and_then(|value| value.to_str().ok());
+    if let Some(key) = header(API_KEY_HEADER) {
+        return format!("key:{key}");
+    }
+    match (header("x-tenant-id"), header("x-user-id")) {
+        (Some(tenant_id), Some(user_id)) => format!("user:{tenant_id}/{user_id}"),
+        _ => "anonymous".to_string(),
+    }
+}
+
+/// Answers 429 with `Retry-After` (whole seconds, rounded up) once a caller's budget for the
+/// current window is spent.
+pub async fn limit_requests(
+    State(limiter): State<RateLimiter>,
+    request: Request,
+    next: Next,
+) -> Response {
+    match limiter
+        .admit(caller(request.headers()), Instant::now())
+        .await
+    {
+        Ok(()) => next.run(request).await,
+        Err(retry_after) => {
+            let seconds = retry_after.as_secs() + u64::from(retry_after.subsec_nanos() > 0);
+            let mut response = StatusCode::TOO_MANY_REQUESTS.into_response();
+            response
+                .headers_mut()
+                .insert(header::RETRY_AFTER, HeaderValue::from(seconds.max(1)));
+            response
+        }
+    }
+}
+
+#[cfg(test)]
+mod rate_limit_tests {
+    use super::*;
+    use axum::{Router, body::Body, middleware, routing::get};
+    use rstest::rstest;
+    use tower::ServiceExt;
+
+    fn limiter(requests: u32) -> RateLimiter {
+        RateLimiter::new(RateLimitConfig {
+            requests,
+            window: Duration::from_secs(60),
+        })
+    }
+
+    async fn send(app: &Router, headers: &[(&str, &str)]) -> Response {
+        let mut request = Request::builder().uri("/");
+        for (name, value) in headers {
+            request = request.header(*name, *value);
+        }
+        app.clone()
+            .oneshot(request.body(Body::empty()).unwrap())
+            .await
+            .unwrap()
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_reject_requests_over_the_budget_with_retry_after() {
+        let app = Router::new()
+            .route("/", get(|| async { "ok" }))
+            .layer(middleware::from_fn_with_state(limiter(2), limit_requests));
+        let alice = [("x-user-id", "u-1"), ("x-tenant-id", "t-1")];
+
+        assert_eq!(send(&app, &alice).await.status(), StatusCode::OK);
+        assert_eq!(send(&app, &alice).await.status(), StatusCode::OK);
+        let limited = send(&app, &alice).await;
+
+        assert_eq!(limited.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(limited.headers()[header::RETRY_AFTER], "60");
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_budget_each_caller_separately() {
+        let app = Router::new()
+            .route("/", get(|| async { "ok" }))
+            .layer(middleware::from_fn_with_state(limiter(1), limit_requests));
+
+        assert_eq!(
+            send(&app, &[("x-user-id", "u-1"), ("x-tenant-id", "t-1")])
+                .await
+                .status(),
+            StatusCode::OK
+        );
+        assert_eq!(
+            send(&app, &[("x-user-id", "u-2"), ("x-tenant-id", "t-1")])
+                .await
+                .status(),
+            StatusCode::OK
+        );
+        assert_eq!(
+            send(&app, &[("x-api-key", "secret")]).await.status(),
+            StatusCode::OK
+        );
+        assert_eq!(send(&app, &[]).await.status(), StatusCode::OK);
+        assert_eq!(
+            send(&app, &[("x-user-id", "u-1"), ("x-tenant-id", "t-1")])
+                .await
+                .status(),
+            StatusCode::TOO_MANY_REQUESTS
+        );
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_open_a_new_window_once_the_previous_one_elapsed() {
+        let limiter = limiter(1);
+        let start = Instant::now();
+
+        assert_eq!(limiter.admit("a".to_string(), start).await, Ok(()));
+        assert_eq!(
+            limiter
+                .admit("a".to_string(), start + Duration::from_secs(45))
+                .await,
+            Err(Duration::from_secs(15))
+        );
+        assert_eq!(
+            limiter
+                .admit("a".to_string(), start + Duration::from_secs(60))
+                .await,
+            Ok(())
+        );
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_apply_config_updates_to_the_next_request() {
+        let (updates, receiver) = watch::channel(RateLimitConfig {
+            requests: 1,
+            window: Duration::from_secs(60),
+        });
+        let limiter = RateLimiter::watching(receiver);
+        let start = Instant::now();
+        assert_eq!(limiter.admit("a".to_string(), start).await, Ok(()));
+        assert!(limiter.admit("a".to_string(), start).await.is_err());
+
+        updates.send_modify(|config| config.requests = 2);
+        assert_eq!(limiter.admit("a".to_string(), start).await, Ok(()));
+        assert!(limiter.admit("a".to_string(), start).await.is_err());
+
+        updates.send_modify(|config| config.requests = 0);
+        assert_eq!(limiter.admit("a".to_string(), start).await, Ok(()));
+    }
+
+    #[rstest]
+    #[case(&[("x-api-key", "k"), ("x-user-id", "u"), ("x-tenant-id", "t")], "key:k")]
+    #[case(&[("x-user-id", "u"), ("x-tenant-id", "t")], "user:t/u")]
+    #[case(&[("x-user-id", "u")], "anonymous")]
+    #[case(&[], "anonymous")]
+    fn it_should_identify_the_caller(#[case] headers: &[(&str, &str)], #[case] expected: &str) {
+        let mut map = HeaderMap::new();
+        for (name, value) in headers {
+            map.insert(
+                axum::http::HeaderName::from_bytes(name.as_bytes()).unwrap(),
+                HeaderValue::from_str(value).unwrap(),
+            );
+        }
+        assert_eq!(caller(&map), expected);
+    }
+}
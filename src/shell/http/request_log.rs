@@ -0,0 +1,254 @@
+// One structured JSON line per request, in place of free-form tracing output, so requests can
+// be searched by route, caller and trace id.
+//
+// Every request gets a trace id: the one in an incoming W3C `traceparent` or `x-trace-id`
+// header, else a new one. It is returned in the `x-trace-id` response header for support to
+// correlate a user's report with the logs, and is set on the span the request runs in, so
+// whatever the handlers log carries it too. The route is the matched template, such as
+// `/api/v1/time-entries/{id}/start`, so ids do not end up in the logs.
+
+use axum::{
+    extract::{MatchedPath, Request, State},
+    http::{HeaderMap, HeaderName, HeaderValue},
+    middleware::Next,
+    response::Response,
+};
+use chrono::Utc;
+use serde::Serialize;
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+use tracing::Instrument;
+use uuid::Uuid;
+
+use crate::shared::infrastructure::api_key_store::ApiKey;
+use crate::shared::infrastructure::request_context::RequestContext;
+
+pub const TRACE_ID_HEADER: HeaderName = HeaderName::from_static("x-trace-id");
+const TRACEPARENT_HEADER: &str = "traceparent";
+const MAX_TRACE_ID_LEN: usize = 64;
+
+/// The trace id of the request being handled, as a request extension.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TraceId(pub String);
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct RequestLine {
+    pub timestamp: String,
+    pub method: String,
+    /// The matched route template; `None` when no route matched.
+    pub route: Option<String>,
+    pub status: u16,
+    pub latency_ms: u64,
+    pub user_id: Option<String>,
+    pub tenant_id: Option<String>,
+    pub trace_id: String,
+}
+
+/// Where request lines go.
+pub trait RequestLogSink: Send + Sync {
+    fn write(&self, line: &RequestLine);
+}
+
+/// Writes each line to standard output, next to the tracing output.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct StdoutRequestLog;
+
+impl RequestLogSink for StdoutRequestLog {
+    fn write(&self, line: &RequestLine) {
+        if let Ok(json) = serde_json::to_string(line) {
+            println!("{json}");
+        }
+    }
+}
+
+/// Keeps the lines for tests; clones share them.
+#[derive(Debug, Clone, Default)]
+pub struct InMemoryRequestLog {
+    lines: Arc<Mutex<Vec<RequestLine>>>,
+}
+
+impl InMemoryRequestLog {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn lines(&self) -> Vec<RequestLine> {
+        self.lines.lock().unwrap().clone()
+    }
+}
+
+impl RequestLogSink for InMemoryRequestLog {
+    fn write(&self, line: &RequestLine) {
+        self.lines.lock().unwrap().push(line.clone());
+    }
+}
+
+fn is_valid_trace_id(value: &str) -> bool {
+    !value.is_empty()
+        && value.len() <= MAX_TRACE_ID_LEN
+        && value.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+}
+
+/// The caller's trace id: the trace id of a `traceparent`, else a well-formed `x-trace-id`,
+/// else a new one, formatted like a W3C trace id.
+pub fn trace_id(headers: &HeaderMap) -> String {
+    let header = |name: &str| headers.get(name).and_then(|value| value.to_str().ok());
+    header(TRACEPARENT_HEADER)
+        .and_then(|traceparent| traceparent.split('-').nth(1))
+        .filter(|trace_id| trace_id.len() == 32 && trace_id.chars().all(|c| c.is_ascii_hexdigit()))
+        .or_else(|| header(TRACE_ID_HEADER.as_str()).filter(|id| is_valid_trace_id(id)))
+        .map(str::to_string)
+        .unwrap_or_else(|| Uuid::now_v7().simple().to_string())
+}
+
+/// Logs the request once it was handled. API key callers are attributed to their key's user
+/// through the key `resolve_api_key` leaves on the response.
+pub async fn log_requests(
+    State(sink): State<Arc<dyn RequestLogSink>>,
+    mut request: Request,
+    next: Next,
+) -> Response {
+    let started = Instant::now();
+    let trace_id = trace_id(request.headers());
+    let method = request.method().to_string();
+    let route = request
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|path| path.as_str().to_string());
+    let headers = request.headers().clone();
+    request.extensions_mut().insert(TraceId(trace_id.clone()));
+
+    let span = tracing::info_span!("request", trace_id = %trace_id);
+    let mut response = next.run(request).instrument(span).await;
+
+    let context =
+        RequestContext::from_headers(&headers, response.extensions().get::<ApiKey>()).ok();
+    sink.write(&RequestLine {
+        timestamp: Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Millis, true),
+        method,
+        route,
+        status: response.status().as_u16(),
+        latency_ms: started.elapsed().as_millis() as u64,
+        user_id: context.as_ref().map(|ctx| ctx.user_id.clone()),
+        tenant_id: context.map(|ctx| ctx.tenant_id),
+        trace_id: trace_id.clone(),
+    });
+    if let Ok(value) = HeaderValue::from_str(&trace_id) {
+        response.headers_mut().insert(TRACE_ID_HEADER, value);
+    }
+    response
+}
+
+#[cfg(test)]
+mod request_log_tests {
+    use super::*;
+    use axum::{Router, body::Body, middleware, routing::get};
+    use rstest::rstest;
+    use tower::ServiceExt;
+
+    fn app(log: InMemoryRequestLog) -> Router {
+        Router::new()
+            .route("/time-entries/{id}", get(|| async { "ok" }))
+            .layer(middleware::from_fn_with_state(
+                Arc::new(log) as Arc<dyn RequestLogSink>,
+                log_requests,
+            ))
+    }
+
+    async fn send(log: &InMemoryRequestLog, uri: &str, headers: &[(&str, &str)]) -> Response {
+        let mut request = Request::builder().uri(uri);
+        for (name, value) in headers {
+            request = request.header(*name, *value);
+        }
+        app(log.clone())
+            .oneshot(request.body(Body::empty()).unwrap())
+            .await
+            .unwrap()
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_log_one_line_per_request_with_the_route_template() {
+        let log = InMemoryRequestLog::new();
+
+        let response = send(
+            &log,
+            "/time-entries/te-1",
+            &[("x-user-id", "u-1"), ("x-tenant-id", "t-1")],
+        )
+        .await;
+
+        let lines = log.lines();
+        assert_eq!(lines.len(), 1);
+        assert_eq!(lines[0].method, "GET");
+        assert_eq!(lines[0].route.as_deref(), Some("/time-entries/{id}"));
+        assert_eq!(lines[0].status, 200);
+        assert_eq!(lines[0].user_id.as_deref(), Some("u-1"));
+        assert_eq!(lines[0].tenant_id.as_deref(), Some("t-1"));
+        assert_eq!(
+            response.headers()[TRACE_ID_HEADER].to_str().unwrap(),
+            lines[0].trace_id
+        );
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_log_anonymous_and_unmatched_requests() {
+        let log = InMemoryRequestLog::new();
+
+        send(&log, "/nothing-here", &[]).await;
+
+        let lines = log.lines();
+        assert_eq!(lines[0].status, 404);
+        assert_eq!(lines[0].route, None);
+        assert_eq!(lines[0].user_id, None);
+    }
+
+    #[rstest]
+    #[case::traceparent(
+        &[("traceparent", "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01")],
+        Some("4bf92f3577b34da6a3ce929d0e0e4736")
+    )]
+    #[case::trace_id_header(&[("x-trace-id", "support-1234")], Some("support-1234"))]
+    #[case::malformed_traceparent(&[("traceparent", "00-not-hex-01"), ("x-trace-id", "abc")], Some("abc"))]
+    #[case::malformed_trace_id(&[("x-trace-id", "a b\"c")], None)]
+    #[case::none(&[], None)]
+    fn it_should_keep_the_callers_trace_id(
+        #[case] headers: &[(&str, &str)],
+        #[case] expected: Option<&str>,
+    ) {
+        let mut map = HeaderMap::new();
+        for (name, value) in headers {
+            map.insert(
+                HeaderName::from_bytes(name.as_bytes()).unwrap(),
+                value.parse().unwrap(),
+            );
+        }
+
+        let trace_id = trace_id(&map);
+
+        match expected {
+            Some(expected) => assert_eq!(trace_id, expected),
+            None => assert_eq!(trace_id.len(), 32),
+        }
+    }
+
+    #[rstest]
+    fn it_should_serialize_to_one_json_line() {
+        let line = RequestLine {
+            timestamp: "2026-10-16T08:00:00.000Z".to_string(),
+            method: "PUT".to_string(),
+            route: Some("/api/v1/time-entries/{id}/start".to_string()),
+            status: 204,
+            latency_ms: 3,
+            user_id: Some("u-1".to_string()),
+            tenant_id: Some("t-1".to_string()),
+            trace_id: "abc".to_string(),
+        };
+
+        assert_eq!(
+            serde_json::to_string(&line).unwrap(),
+            r#"{"timestamp":"2026-10-16T08:00:00.000Z","method":"PUT","route":"/api/v1/time-entries/{id}/start","status":204,"latency_ms":3,"user_id":"u-1","tenant_id":"t-1","trace_id":"abc"}"#
+        );
+    }
+}
@@ -0,0 +1,1076 @@
+// The one place the service's router is assembled. Use-case routes live under `API_PREFIX`;
+// `/health`, `/metrics`, `/internal/outbox/stats` and the GraphQL endpoints stay unversioned.
+// The unversioned REST paths are still served during the migration, marked with a
+// `Deprecation` header.
+//
+// Layers wrap outwards: per route group, body limits and timeouts come first, then API keys
+// are resolved before the audit records the actor; around the whole app, chaos runs innermost, then the rate limit, the request log and CORS.
+
+use axum::{
+    Json, Router,
+    http::{HeaderName, HeaderValue},
+    middleware,
+    response::{IntoResponse, Response},
+    routing::{delete, get, patch, post, put},
+};
+use std::sync::Arc;
+use tokio::sync::watch;
+use tower_http::cors::CorsLayer;
+
+use crate::modules::contracts::use_cases::set_contract::inbound::http as set_contract_http;
+use crate::modules::tags::use_cases::create_tag::inbound::http as create_tag_http;
+use crate::modules::tags::use_cases::delete_tag::inbound::http as delete_tag_http;
+use crate::modules::tags::use_cases::list_tags::inbound::http as list_tags_http;
+use crate::modules::tags::use_cases::set_tag_color::inbound::http as set_tag_color_http;
+use crate::modules::tags::use_cases::set_tag_description::inbound::http as set_tag_description_http;
+use crate::modules::tags::use_cases::set_tag_name::inbound::http as set_tag_name_http;
+use crate::modules::time_entries::use_cases::archive_time_entries::inbound::http as archive_http;
+use crate::modules::time_entries::use_cases::bulk_delete_time_entries::inbound::http as bulk_delete_http;
+use crate::modules::time_entries::use_cases::compact_time_entries::inbound::http as compact_http;
+use crate::modules::time_entries::use_cases::delete_time_entry::inbound::http as delete_time_entry_http;
+use crate::modules::time_entries::use_cases::export_time_entries::inbound::http as export_http;
+use crate::modules::time_entries::use_cases::hours_balance::inbound::http as hours_balance_http;
+use crate::modules::time_entries::use_cases::list_time_entries::inbound::http as list_http;
+use crate::modules::time_entries::use_cases::list_time_entries::inbound::sse as list_sse;
+use crate::modules::time_entries::use_cases::outbox_integrity::inbound::http as outbox_integrity_http;
+use crate::modules::time_entries::use_cases::period_locks::inbound::http as period_locks_http;
+use crate::modules::time_entries::use_cases::set_breaks::inbound::http as set_breaks_http;
+use crate::modules::time_entries::use_cases::set_ended_at::inbound::http as set_ended_at_http;
+use crate::modules::time_entries::use_cases::set_hourly_rate::inbound::http as set_hourly_rate_http;
+use crate::modules::time_entries::use_cases::set_started_at::inbound::http as set_started_at_http;
+use crate::modules::time_entries::use_cases::set_time_entry_tags::inbound::http as set_time_entry_tags_http;
+use crate::modules::time_entries::use_cases::sync::inbound::http as sync_http;
+use crate::modules::time_entries::use_cases::update_time_entry::inbound::http as update_time_entry_http;
+use crate::shared::infrastructure::api_audit_store::in_memory::InMemoryApiAuditStore;
+use crate::shared::infrastructure::api_key_store::in_memory::InMemoryApiKeyStore;
+use crate::shared::infrastructure::request_context::resolve_api_key;
+use crate::shell::api_keys;
+use crate::shell::audit;
+use crate::shell::chaos::{Chaos, ChaosConfig, inject_chaos};
+use crate::shell::consumer_lag;
+use crate::shell::feature_flags;
+use crate::shell::forget_user;
+use crate::shell::graphql::{self as shell_graphql, WsConfig};
+use crate::shell::http::limits::RequestLimits;
+use crate::shell::http::rate_limit::{RateLimitConfig, RateLimiter, limit_requests};
+use crate::shell::http::request_log::{RequestLogSink, StdoutRequestLog, log_requests};
+use crate::shell::jobs;
+use crate::shell::metrics;
+use crate::shell::outbox_inspector;
+use crate::shell::outbox_stats;
+use crate::shell::state::AppState;
+use crate::shell::user_data_export;
+
+pub const API_PREFIX: &str = "/api/v1";
+
+/// Every use-case route as `(method, path)`, relative to `API_PREFIX`. Adding a route to
+/// `use_case_routes` without listing it here, or listing it without a permission in the
+/// tests' `ROUTE_POLICIES`, fails the completeness tests.
+pub const ROUTE_TABLE: &[(&str, &str)] = &[
+    ("GET", "/time-entries/{id}"),
+    ("PUT", "/time-entries/{id}"),
+    ("DELETE", "/time-entries/{id}"),
+    ("PUT", "/time-entries/{id}/start"),
+    ("PUT", "/time-entries/{id}/end"),
+    ("PUT", "/time-entries/{id}/tags"),
+    ("PUT", "/time-entries/{id}/rate"),
+    ("PUT", "/time-entries/{id}/breaks"),
+    ("GET", "/list-time-entries"),
+    ("GET", "/users/{user_id}/time-entries/stream"),
+    ("POST", "/sync"),
+    ("GET", "/hours-balance"),
+    ("PUT", "/users/{user_id}/contract"),
+    ("GET", "/period-locks"),
+    ("POST", "/period-locks"),
+    ("DELETE", "/period-locks/{lock_id}"),
+    ("GET", "/tags"),
+    ("POST", "/tags"),
+    ("DELETE", "/tags/{tag_id}"),
+    ("PATCH", "/tags/{tag_id}/name"),
+    ("PATCH", "/tags/{tag_id}/color"),
+    ("PATCH", "/tags/{tag_id}/description"),
+    ("GET", "/admin/audit"),
+    ("GET", "/admin/projections/list-time-entries"),
+    ("POST", "/admin/projections/list-time-entries/rebuild"),
+    ("POST", "/admin/time-entries/archive"),
+    ("POST", "/admin/time-entries/compact"),
+    ("POST", "/admin/time-entries/export"),
+    ("POST", "/admin/time-entries/bulk-delete"),
+    ("POST", "/admin/time-entries/bulk-delete/confirm"),
+    ("GET", "/admin/outbox/integrity"),
+    ("GET", "/admin/outbox/rows"),
+    ("GET", "/admin/users/{user_id}/data-export"),
+    ("POST", "/admin/users/{user_id}/data-export"),
+    ("POST", "/admin/users/{user_id}/forget"),
+    (
+        "POST",
+        "/admin/users/{user_id}/time-entries/{time_entry_id}/restore",
+    ),
+    ("GET", "/admin/data-exports/{job_id}/bundle"),
+    ("GET", "/admin/jobs"),
+    ("GET", "/admin/jobs/{job_id}"),
+    ("GET", "/admin/feature-flags"),
+    ("PUT", "/admin/feature-flags/{name}"),
+    ("DELETE", "/admin/feature-flags/{name}"),
+    ("GET", "/admin/consumers/lag"),
+    ("GET", "/admin/api-keys"),
+    ("POST", "/admin/api-keys"),
+    ("DELETE", "/admin/api-keys/{id}"),
+];
+
+const DEPRECATION: HeaderName = HeaderName::from_static("deprecation");
+
+async fn health() -> impl IntoResponse {
+    Json(serde_json::json!({"status": "ok"}))
+}
+
+async fn mark_deprecated(mut response: Response) -> Response {
+    response
+        .headers_mut()
+        .insert(DEPRECATION, HeaderValue::from_static("true"));
+    response
+}
+
+/// Routes taking many changes per request, under `RequestLimits::bulk`.
+fn bulk_routes() -> Router<AppState> {
+    Router::new().route("/sync", post(sync_http::handle))
+}
+
+fn standard_routes() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/time-entries/{id}",
+            get(list_http::handle_get)
+                .put(update_time_entry_http::handle_put)
+                .delete(delete_time_entry_http::handle_delete),
+        )
+        .route(
+            "/time-entries/{id}/start",
+            put(set_started_at_http::handle_put),
+        )
+        .route("/time-entries/{id}/end", put(set_ended_at_http::handle_put))
+        .route(
+            "/time-entries/{id}/tags",
+            put(set_time_entry_tags_http::handle_put),
+        )
+        .route(
+            "/time-entries/{id}/rate",
+            put(set_hourly_rate_http::handle_put),
+        )
+        .route(
+            "/time-entries/{id}/breaks",
+            put(set_breaks_http::handle_put),
+        )
+        .route("/list-time-entries", get(list_http::handle))
+        .route(
+            "/users/{user_id}/time-entries/stream",
+            get(list_sse::handle),
+        )
+        .route("/hours-balance", get(hours_balance_http::handle))
+        .route("/users/{user_id}/contract", put(set_contract_http::handle))
+        .route(
+            "/period-locks",
+            get(period_locks_http::handle_list).post(period_locks_http::handle_lock),
+        )
+        .route(
+            "/period-locks/{lock_id}",
+            delete(period_locks_http::handle_unlock),
+        )
+        .route(
+            "/tags",
+            get(list_tags_http::handle).post(create_tag_http::handle),
+        )
+        .route("/tags/{tag_id}", delete(delete_tag_http::handle))
+        .route("/tags/{tag_id}/name", patch(set_tag_name_http::handle))
+        .route("/tags/{tag_id}/color", patch(set_tag_color_http::handle))
+        .route(
+            "/tags/{tag_id}/description",
+            patch(set_tag_description_http::handle),
+        )
+        .route("/admin/audit", get(audit::list_audit_records))
+        .route(
+            "/admin/projections/list-time-entries",
+            get(list_http::handle_partitions),
+        )
+        .route(
+            "/admin/projections/list-time-entries/rebuild",
+            post(list_http::handle_rebuild),
+        )
+        .route("/admin/time-entries/archive", post(archive_http::handle))
+        .route("/admin/time-entries/compact", post(compact_http::handle))
+        .route("/admin/time-entries/export", post(export_http::handle))
+        .route(
+            "/admin/time-entries/bulk-delete",
+            post(bulk_delete_http::handle_request),
+        )
+        .route(
+            "/admin/time-entries/bulk-delete/confirm",
+            post(bulk_delete_http::handle_confirm),
+        )
+        .route(
+            "/admin/outbox/integrity",
+            get(outbox_integrity_http::handle),
+        )
+        .route("/admin/outbox/rows", get(outbox_inspector::handle_list))
+        .route(
+            "/admin/users/{user_id}/data-export",
+            get(user_data_export::handle_export).post(user_data_export::handle_start_export),
+        )
+        .route(
+            "/admin/users/{user_id}/forget",
+            post(forget_user::handle_forget),
+        )
+        .route(
+            "/admin/users/{user_id}/time-entries/{time_entry_id}/restore",
+            post(archive_http::handle_restore),
+        )
+        .route(
+            "/admin/data-exports/{job_id}/bundle",
+            get(user_data_export::handle_job_bundle),
+        )
+        .route("/admin/jobs", get(jobs::handle_list))
+        .route("/admin/jobs/{job_id}", get(jobs::handle_get))
+        .route("/admin/feature-flags", get(feature_flags::handle_list))
+        .route(
+            "/admin/feature-flags/{name}",
+            put(feature_flags::handle_set).delete(feature_flags::handle_clear),
+        )
+        .route("/admin/consumers/lag", get(consumer_lag::handle_list))
+        .route(
+            "/admin/api-keys",
+            get(api_keys::handle_list).post(api_keys::handle_issue),
+        )
+        .route("/admin/api-keys/{id}", delete(api_keys::handle_revoke))
+}
+
+fn authenticated(routes: Router<AppState>, state: &AppState) -> Router<AppState> {
+    routes
+        .layer(middleware::from_fn_with_state(
+            state.audit_store.clone(),
+            audit::audit_mutations::<InMemoryApiAuditStore>,
+        ))
+        .layer(middleware::from_fn_with_state(
+            state.api_key_store.clone(),
+            resolve_api_key::<InMemoryApiKeyStore>,
+        ))
+}
+
+fn use_case_routes(state: &AppState, limits: RequestLimits) -> Router<AppState> {
+    limits
+        .bulk
+        .apply(authenticated(bulk_routes(), state))
+        .merge(
+            limits
+                .standard
+                .apply(authenticated(standard_routes(), state)),
+        )
+}
+
+/// Assembles the REST and GraphQL routers with the shared layers. Without further
+/// configuration the default request limits apply, there is no rate limit, no chaos, requests
+/// are logged to standard output and CORS is permissive.
+#[derive(Clone)]
+pub struct RouterBuilder {
+    state: AppState,
+    ws: WsConfig,
+    limits: RequestLimits,
+    rate_limit: Option<RateLimiter>,
+    chaos: ChaosConfig,
+    request_log: Arc<dyn RequestLogSink>,
+    cors: CorsLayer,
+}
+
+impl RouterBuilder {
+    pub fn new(state: AppState) -> Self {
+        Self {
+            state,
+            ws: WsConfig::default(),
+            limits: RequestLimits::default(),
+            rate_limit: None,
+            chaos: ChaosConfig::default(),
+            request_log: Arc::new(StdoutRequestLog),
+            cors: CorsLayer::permissive(),
+        }
+    }
+
+    pub fn with_ws(mut self, ws: WsConfig) -> Self {
+        self.ws = ws;
+        self
+    }
+
+    pub fn with_limits(mut self, limits: RequestLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    pub fn with_rate_limit(mut self, rate_limit: RateLimitConfig) -> Self {
+        self.rate_limit = Some(RateLimiter::new(rate_limit));
+        self
+    }
+
+    /// Rate limits with whatever `updates` holds at the time of each request.
+    pub fn with_rate_limit_updates(mut self, updates: watch::Receiver<RateLimitConfig>) -> Self {
+        self.rate_limit = Some(RateLimiter::watching(updates));
+        self
+    }
+
+    pub fn with_chaos(mut self, chaos: ChaosConfig) -> Self {
+        self.chaos = chaos;
+        self
+    }
+
+    pub fn with_request_log(mut self, request_log: Arc<dyn RequestLogSink>) -> Self {
+        self.request_log = request_log;
+        self
+    }
+
+    pub fn with_cors(mut self, cors: CorsLayer) -> Self {
+        self.cors = cors;
+        self
+    }
+
+    pub fn build(self) -> Router {
+        let api = use_case_routes(&self.state, self.limits);
+        let mut app = Router::new()
+            .route("/health", get(health))
+            .route("/metrics", get(metrics::handle))
+            .route("/internal/outbox/stats", get(outbox_stats::handle))
+            .nest(API_PREFIX, api.clone())
+            .merge(api.layer(middleware::map_response(mark_deprecated)))
+            .with_state(self.state.clone())
+            .merge(
+                self.limits
+                    .standard
+                    .apply(shell_graphql::router(self.state, self.ws)),
+            );
+        if self.chaos.is_active() {
+            app = app.layer(middleware::from_fn_with_state(
+                Chaos::new(self.chaos),
+                inject_chaos,
+            ));
+        }
+        if let Some(limiter) = self.rate_limit {
+            app = app.layer(middleware::from_fn_with_state(limiter, limit_requests));
+        }
+        app.layer(middleware::from_fn_with_state(
+            self.request_log,
+            log_requests,
+        ))
+        .layer(self.cors)
+    }
+}
+
+#[cfg(test)]
+mod routes_tests {
+    use axum::{
+        body::{Body, HttpBody},
+        http::{Method, Request, StatusCode, header},
+    };
+    use rstest::rstest;
+    use std::time::Duration;
+    use tower::ServiceExt;
+
+    use super::*;
+    use crate::shared::auth::rbac::ApiKeyScope;
+    use crate::shared::infrastructure::api_key_store::ApiKey;
+    use crate::shell::http::limits::RouteLimits;
+    use crate::shell::http::request_log::InMemoryRequestLog;
+    use crate::tests::fixtures::tags::make_test_app_state;
+
+    const TIME_ENTRY_ID: &str = "01900000-0000-7000-8000-000000000000";
+    const TAG_ID: &str = "01900000-0000-7000-8000-000000000001";
+
+    fn app() -> Router {
+        RouterBuilder::new(make_test_app_state()).build()
+    }
+
+    /// `path` with every `{param}` segment filled in with a UUID.
+    fn concrete(path: &str) -> String {
+        path.split('/')
+            .map(|segment| {
+                if segment.starts_with('{') {
+                    TIME_ENTRY_ID
+                } else {
+                    segment
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("/")
+    }
+
+    async fn send(app: Router, method: &str, uri: &str) -> Response {
+        app.oneshot(
+            Request::builder()
+                .method(method)
+                .uri(uri)
+                .header("x-user-id", "u-1")
+                .header("x-tenant-id", "t-1")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap()
+    }
+
+    async fn send_with_api_key(
+        scope: ApiKeyScope,
+        method: Method,
+        uri: String,
+        body: &str,
+    ) -> StatusCode {
+        let state = make_test_app_state();
+        state
+            .api_key_store
+            .insert(
+                "secret",
+                ApiKey {
+                    user_id: "ci-bot".to_string(),
+                    tenant_id: "t-1".to_string(),
+                    scope,
+                },
+            )
+            .await;
+        RouterBuilder::new(state)
+            .build()
+            .oneshot(
+                Request::builder()
+                    .method(method)
+                    .uri(format!("{API_PREFIX}{uri}"))
+                    .header("x-api-key", "secret")
+                    .header("content-type", "application/json")
+                    .body(Body::from(body.to_string()))
+                    .unwrap(),
+            )
+            .await
+            .unwrap()
+            .status()
+    }
+
+    /// Whether no route matched: the router answers 404 without a body, while handlers that
+    /// do not find a resource say what they did not find.
+    fn unrouted(response: &Response) -> bool {
+        response.status() == StatusCode::NOT_FOUND && response.body().size_hint().exact() == Some(0)
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_serve_every_route_in_the_table_under_the_api_prefix() {
+        for (method, path) in ROUTE_TABLE {
+            let uri = format!("{API_PREFIX}{}", concrete(path));
+            let response = send(app(), method, &uri).await;
+            assert!(!unrouted(&response), "{method} {uri}");
+            assert_ne!(
+                response.status(),
+                StatusCode::METHOD_NOT_ALLOWED,
+                "{method} {uri}"
+            );
+            assert!(!response.headers().contains_key(DEPRECATION), "{uri}");
+        }
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_serve_unversioned_paths_as_deprecated_aliases() {
+        for (method, path) in ROUTE_TABLE {
+            let uri = concrete(path);
+            let response = send(app(), method, &uri).await;
+            assert!(!unrouted(&response), "{method} {uri}");
+            assert_eq!(response.headers()[DEPRECATION], "true", "{method} {uri}");
+        }
+    }
+
+    #[test]
+    fn it_should_list_each_route_once() {
+        let mut routes = ROUTE_TABLE.to_vec();
+        routes.sort();
+        routes.dedup();
+        assert_eq!(routes.len(), ROUTE_TABLE.len());
+    }
+
+    /// The check a route's handler makes before acting, from `rbac::Principal`.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    enum Permission {
+        /// None beyond being authenticated.
+        Caller,
+        /// `can_view_user` for the user whose data is read, the caller by default.
+        ViewUser,
+        /// `can_view_user` for the entry's owner, answering 404 so the entry stays hidden.
+        ViewOwner,
+        /// `can_register_for` the entry's owner.
+        RegisterFor,
+        /// `can_write`, for tenant-wide data.
+        Write,
+        /// `can_approve` the user concerned.
+        Approve,
+        /// `can_administer`.
+        Administer,
+    }
+
+    /// Every route in `ROUTE_TABLE` with its permission, and a query string and body the
+    /// handler accepts up to that check.
+    const ROUTE_POLICIES: &[(&str, &str, Permission, &str, &str)] = &[
+        ("GET", "/time-entries/{id}", Permission::ViewOwner, "", ""),
+        (
+            "PUT",
+            "/time-entries/{id}",
+            Permission::RegisterFor,
+            "",
+            r#"{"started_at":1,"ended_at":2}"#,
+        ),
+        (
+            "DELETE",
+            "/time-entries/{id}",
+            Permission::RegisterFor,
+            "",
+            "",
+        ),
+        (
+            "PUT",
+            "/time-entries/{id}/start",
+            Permission::RegisterFor,
+            "",
+            r#"{"started_at":1}"#,
+        ),
+        (
+            "PUT",
+            "/time-entries/{id}/end",
+            Permission::RegisterFor,
+            "",
+            r#"{"ended_at":2}"#,
+        ),
+        (
+            "PUT",
+            "/time-entries/{id}/tags",
+            Permission::RegisterFor,
+            "",
+            r#"{"tag_ids":[]}"#,
+        ),
+        (
+            "PUT",
+            "/time-entries/{id}/rate",
+            Permission::RegisterFor,
+            "",
+            r#"{"hourly_rate_cents":9500,"currency":"EUR"}"#,
+        ),
+        (
+            "PUT",
+            "/time-entries/{id}/breaks",
+            Permission::RegisterFor,
+            "",
+            r#"{"breaks":[]}"#,
+        ),
+        ("GET", "/list-time-entries", Permission::ViewUser, "", ""),
+        (
+            "GET",
+            "/users/{user_id}/time-entries/stream",
+            Permission::ViewUser,
+            "",
+            "",
+        ),
+        (
+            "POST",
+            "/sync",
+            Permission::RegisterFor,
+            "",
+            r#"{"since":0,"mutations":[]}"#,
+        ),
+        (
+            "GET",
+            "/hours-balance",
+            Permission::ViewUser,
+            "?from=2026-12-21&to=2026-12-25",
+            "",
+        ),
+        (
+            "PUT",
+            "/users/{user_id}/contract",
+            Permission::Administer,
+            "",
+            r#"{"weekly_hours":40}"#,
+        ),
+        ("GET", "/period-locks", Permission::ViewUser, "", ""),
+        (
+            "POST",
+            "/period-locks",
+            Permission::Approve,
+            "",
+            r#"{"user_id":"u-2","from":0,"to":1}"#,
+        ),
+        (
+            "DELETE",
+            "/period-locks/{lock_id}",
+            Permission::Administer,
+            "",
+            "",
+        ),
+        ("GET", "/tags", Permission::Caller, "", ""),
+        ("POST", "/tags", Permission::Write, "", r#"{"name":"ci"}"#),
+        ("DELETE", "/tags/{tag_id}", Permission::Write, "", ""),
+        (
+            "PATCH",
+            "/tags/{tag_id}/name",
+            Permission::Write,
+            "",
+            r#"{"name":"ci"}"#,
+        ),
+        (
+            "PATCH",
+            "/tags/{tag_id}/color",
+            Permission::Write,
+            "",
+            r##"{"color":"#fff"}"##,
+        ),
+        (
+            "PATCH",
+            "/tags/{tag_id}/description",
+            Permission::Write,
+            "",
+            r#"{"description":null}"#,
+        ),
+        ("GET", "/admin/audit", Permission::Administer, "", ""),
+        (
+            "GET",
+            "/admin/projections/list-time-entries",
+            Permission::Administer,
+            "",
+            "",
+        ),
+        (
+            "POST",
+            "/admin/projections/list-time-entries/rebuild",
+            Permission::Administer,
+            "",
+            "",
+        ),
+        (
+            "POST",
+            "/admin/time-entries/archive",
+            Permission::Administer,
+            "",
+            "{}",
+        ),
+        (
+            "POST",
+            "/admin/time-entries/compact",
+            Permission::Administer,
+            "",
+            "{}",
+        ),
+        (
+            "POST",
+            "/admin/time-entries/export",
+            Permission::Administer,
+            "",
+            "{}",
+        ),
+        (
+            "POST",
+            "/admin/time-entries/bulk-delete",
+            Permission::Administer,
+            "",
+            "{}",
+        ),
+        (
+            "POST",
+            "/admin/time-entries/bulk-delete/confirm",
+            Permission::Administer,
+            "",
+            "{}",
+        ),
+        (
+            "GET",
+            "/admin/outbox/integrity",
+            Permission::Administer,
+            "",
+            "",
+        ),
+        ("GET", "/admin/outbox/rows", Permission::Administer, "", ""),
+        (
+            "GET",
+            "/admin/users/{user_id}/data-export",
+            Permission::Administer,
+            "",
+            "",
+        ),
+        (
+            "POST",
+            "/admin/users/{user_id}/data-export",
+            Permission::Administer,
+            "",
+            "",
+        ),
+        (
+            "POST",
+            "/admin/users/{user_id}/forget",
+            Permission::Administer,
+            "",
+            "",
+        ),
+        (
+            "POST",
+            "/admin/users/{user_id}/time-entries/{time_entry_id}/restore",
+            Permission::Administer,
+            "",
+            "",
+        ),
+        (
+            "GET",
+            "/admin/data-exports/{job_id}/bundle",
+            Permission::Administer,
+            "",
+            "",
+        ),
+        ("GET", "/admin/jobs", Permission::Administer, "", ""),
+        (
+            "GET",
+            "/admin/jobs/{job_id}",
+            Permission::Administer,
+            "",
+            "",
+        ),
+        (
+            "GET",
+            "/admin/feature-flags",
+            Permission::Administer,
+            "",
+            "",
+        ),
+        (
+            "PUT",
+            "/admin/feature-flags/{name}",
+            Permission::Administer,
+            "",
+            r#"{"enabled":true}"#,
+        ),
+        (
+            "DELETE",
+            "/admin/feature-flags/{name}",
+            Permission::Administer,
+            "",
+            "",
+        ),
+        (
+            "GET",
+            "/admin/consumers/lag",
+            Permission::Administer,
+            "",
+            "",
+        ),
+        ("GET", "/admin/api-keys", Permission::Administer, "", ""),
+        (
+            "POST",
+            "/admin/api-keys",
+            Permission::Administer,
+            "",
+            r#"{"user_id":"ci-bot","scope":"read-only"}"#,
+        ),
+        (
+            "DELETE",
+            "/admin/api-keys/{id}",
+            Permission::Administer,
+            "",
+            "",
+        ),
+    ];
+
+    /// Sends the request as a caller who lacks `permission` but holds what they can besides:
+    /// API keys narrowed to registering or reading for the view and write checks, an employee
+    /// for approvals and a manager for administration. `Caller` sends it as an employee.
+    async fn send_without(permission: Permission, method: &str, uri: &str, body: &str) -> Response {
+        let state = make_test_app_state();
+        for (key, scope) in [
+            ("register-only", ApiKeyScope::RegisterOnly),
+            ("read-only", ApiKeyScope::ReadOnly),
+        ] {
+            state
+                .api_key_store
+                .insert(
+                    key,
+                    ApiKey {
+                        user_id: "ci-bot".to_string(),
+                        tenant_id: "t-1".to_string(),
+                        scope,
+                    },
+                )
+                .await;
+        }
+        let request = Request::builder()
+            .method(method)
+            .uri(uri)
+            .header("content-type", "application/json");
+        let request = match permission {
+            Permission::ViewUser | Permission::ViewOwner => {
+                request.header("x-api-key", "register-only")
+            }
+            Permission::RegisterFor | Permission::Write => request.header("x-api-key", "read-only"),
+            Permission::Caller | Permission::Approve | Permission::Administer => {
+                let role = match permission {
+                    Permission::Administer => "manager",
+                    _ => "employee",
+                };
+                request
+                    .header("x-user-id", "u-1")
+                    .header("x-tenant-id", "t-1")
+                    .header("x-user-role", role)
+            }
+        };
+        RouterBuilder::new(state)
+            .build()
+            .oneshot(request.body(Body::from(body.to_string())).unwrap())
+            .await
+            .unwrap()
+    }
+
+    #[test]
+    fn it_should_give_every_route_a_policy() {
+        let mut routes = ROUTE_TABLE.to_vec();
+        routes.sort();
+        let mut policed: Vec<_> = ROUTE_POLICIES
+            .iter()
+            .map(|(method, path, ..)| (*method, *path))
+            .collect();
+        policed.sort();
+        assert_eq!(policed, routes);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_refuse_callers_without_the_routes_permission() {
+        for (method, path, permission, query, body) in ROUTE_POLICIES {
+            let uri = format!("{API_PREFIX}{}{query}", concrete(path));
+            let status = send_without(*permission, method, &uri, body).await.status();
+            match permission {
+                Permission::Caller => {
+                    assert_ne!(status, StatusCode::FORBIDDEN, "{method} {uri}")
+                }
+                Permission::ViewOwner => {
+                    assert_eq!(status, StatusCode::NOT_FOUND, "{method} {uri}")
+                }
+                _ => assert_eq!(status, StatusCode::FORBIDDEN, "{method} {uri}"),
+            }
+        }
+    }
+
+    #[rstest]
+    #[case::health("GET", "/health", StatusCode::OK)]
+    #[case::graphql_playground("GET", "/gql", StatusCode::OK)]
+    #[case::unknown_version("GET", "/api/v2/tags", StatusCode::NOT_FOUND)]
+    #[case::unknown_route("GET", "/api/v1/nothing-here", StatusCode::NOT_FOUND)]
+    #[case::versioned_health("GET", "/api/v1/health", StatusCode::NOT_FOUND)]
+    #[tokio::test]
+    async fn it_should_keep_health_and_graphql_unversioned(
+        #[case] method: &str,
+        #[case] uri: &str,
+        #[case] expected: StatusCode,
+    ) {
+        assert_eq!(send(app(), method, uri).await.status(), expected);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_rate_limit_across_the_whole_app() {
+        let app = RouterBuilder::new(make_test_app_state())
+            .with_rate_limit(RateLimitConfig {
+                requests: 2,
+                window: Duration::from_secs(60),
+            })
+            .build();
+
+        assert_eq!(
+            send(app.clone(), "GET", "/health").await.status(),
+            StatusCode::OK
+        );
+        assert_eq!(
+            send(app.clone(), "GET", "/api/v1/tags").await.status(),
+            StatusCode::OK
+        );
+        let limited = send(app, "GET", "/gql").await;
+
+        assert_eq!(limited.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert!(limited.headers().contains_key(header::RETRY_AFTER));
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_log_api_key_callers_under_the_route_template() {
+        let state = make_test_app_state();
+        state
+            .api_key_store
+            .insert(
+                "secret",
+                ApiKey {
+                    user_id: "ci-bot".to_string(),
+                    tenant_id: "t-ci".to_string(),
+                    scope: ApiKeyScope::ReadOnly,
+                },
+            )
+            .await;
+        let log = InMemoryRequestLog::new();
+        let app = RouterBuilder::new(state)
+            .with_request_log(Arc::new(log.clone()))
+            .build();
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method(Method::PATCH)
+                    .uri(format!("{API_PREFIX}/tags/{TAG_ID}/name"))
+                    .header("x-api-key", "secret")
+                    .header("x-trace-id", "support-1234")
+                    .header("content-type", "application/json")
+                    .body(Body::from(r#"{"name":"Billable"}"#))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        let lines = log.lines();
+        assert_eq!(lines.len(), 1);
+        assert_eq!(
+            lines[0].route.as_deref(),
+            Some("/api/v1/tags/{tag_id}/name")
+        );
+        assert_eq!(lines[0].status, 403);
+        assert_eq!(lines[0].user_id.as_deref(), Some("ci-bot"));
+        assert_eq!(lines[0].tenant_id.as_deref(), Some("t-ci"));
+        assert_eq!(response.headers()["x-trace-id"], "support-1234");
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_give_bulk_routes_their_own_body_limit() {
+        let limits = RequestLimits {
+            standard: RouteLimits {
+                max_body_bytes: 64,
+                ..RouteLimits::STANDARD
+            },
+            bulk: RouteLimits {
+                max_body_bytes: 4096,
+                ..RouteLimits::BULK
+            },
+        };
+        let app = RouterBuilder::new(make_test_app_state())
+            .with_limits(limits)
+            .build();
+        let body = format!(
+            r#"{{"since":0,"mutations":[],"padding":"{}"}}"#,
+            "x".repeat(100)
+        );
+        let post = |uri: &str| {
+            Request::post(uri)
+                .header("x-user-id", "u-1")
+                .header("x-tenant-id", "t-1")
+                .header("content-type", "application/json")
+                .body(Body::from(body.clone()))
+                .unwrap()
+        };
+
+        let synced = app.clone().oneshot(post("/api/v1/sync")).await.unwrap();
+        let tagged = app.clone().oneshot(post("/api/v1/tags")).await.unwrap();
+        let queried = app.oneshot(post("/gql")).await.unwrap();
+
+        assert_eq!(synced.status(), StatusCode::OK);
+        assert_eq!(tagged.status(), StatusCode::PAYLOAD_TOO_LARGE);
+        assert_eq!(queried.status(), StatusCode::PAYLOAD_TOO_LARGE);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_answer_504_when_the_event_store_outlives_the_deadline() {
+        let state = make_test_app_state();
+        state.tag_event_store.set_delay_append_ms(200);
+        let app = RouterBuilder::new(state).build();
+
+        let response = app
+            .oneshot(
+                Request::post("/api/v1/tags")
+                    .header("x-user-id", "u-1")
+                    .header("x-tenant-id", "t-1")
+                    .header("x-request-timeout-ms", "20")
+                    .header("content-type", "application/json")
+                    .body(Body::from(r#"{"name":"Deep work"}"#))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::GATEWAY_TIMEOUT);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_answer_cors_preflight_requests() {
+        let response = app()
+            .oneshot(
+                Request::builder()
+                    .method(Method::OPTIONS)
+                    .uri("/api/v1/tags")
+                    .header(header::ORIGIN, "https://app.example")
+                    .header(header::ACCESS_CONTROL_REQUEST_METHOD, "POST")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(
+            response
+                .headers()
+                .contains_key(header::ACCESS_CONTROL_ALLOW_ORIGIN)
+        );
+    }
+
+    #[rstest]
+    #[case::set_started_at(Method::PUT, format!("/time-entries/{TIME_ENTRY_ID}/start"), r#"{"started_at":1}"#)]
+    #[case::set_ended_at(Method::PUT, format!("/time-entries/{TIME_ENTRY_ID}/end"), r#"{"ended_at":2}"#)]
+    #[case::set_time_entry_tags(Method::PUT, format!("/time-entries/{TIME_ENTRY_ID}/tags"), r#"{"tag_ids":[]}"#)]
+    #[case::set_hourly_rate(Method::PUT, format!("/time-entries/{TIME_ENTRY_ID}/rate"), r#"{"hourly_rate_cents":9500,"currency":"EUR"}"#)]
+    #[case::set_breaks(Method::PUT, format!("/time-entries/{TIME_ENTRY_ID}/breaks"), r#"{"breaks":[]}"#)]
+    #[case::sync(Method::POST, "/sync".to_string(), "{}")]
+    #[case::create_tag(Method::POST, "/tags".to_string(), r#"{"name":"ci"}"#)]
+    #[case::delete_tag(Method::DELETE, format!("/tags/{TAG_ID}"), "")]
+    #[case::set_tag_name(Method::PATCH, format!("/tags/{TAG_ID}/name"), r#"{"name":"ci"}"#)]
+    #[case::set_tag_color(Method::PATCH, format!("/tags/{TAG_ID}/color"), r##"{"color":"#fff"}"##)]
+    #[case::set_tag_description(Method::PATCH, format!("/tags/{TAG_ID}/description"), r#"{"description":null}"#)]
+    #[tokio::test]
+    async fn it_should_forbid_writes_with_read_only_api_keys(
+        #[case] method: Method,
+        #[case] uri: String,
+        #[case] body: &str,
+    ) {
+        let status = send_with_api_key(ApiKeyScope::ReadOnly, method, uri, body).await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+    }
+
+    #[rstest]
+    #[case::read_only(ApiKeyScope::ReadOnly, StatusCode::OK)]
+    #[case::register_only(ApiKeyScope::RegisterOnly, StatusCode::FORBIDDEN)]
+    #[case::admin(ApiKeyScope::Admin, StatusCode::OK)]
+    #[tokio::test]
+    async fn it_should_scope_reads_by_api_key(
+        #[case] scope: ApiKeyScope,
+        #[case] expected: StatusCode,
+    ) {
+        let status = send_with_api_key(
+            scope,
+            Method::GET,
+            "/list-time-entries?user_id=u-2".to_string(),
+            "",
+        )
+        .await;
+        assert_eq!(status, expected);
+    }
+
+    #[tokio::test]
+    async fn it_should_let_register_only_api_keys_register_their_own_time() {
+        let status = send_with_api_key(
+            ApiKeyScope::RegisterOnly,
+            Method::PUT,
+            format!("/time-entries/{TIME_ENTRY_ID}/start"),
+            r#"{"started_at":1}"#,
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+    }
+}
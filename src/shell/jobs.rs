@@ -0,0 +1,173 @@
+// Admin API over the job table: what is queued, running, done or failed, with each job's
+// progress and result. Routes that start long-running work enqueue a job through `accept`
+// and answer with where to poll it.
+
+use axum::{
+    Json,
+    extract::{Path, Query, State},
+    http::{StatusCode, header},
+    response::{IntoResponse, Response},
+};
+use serde::Deserialize;
+
+use crate::shared::infrastructure::job_store::{Job, JobQuery, JobStatus, JobStore};
+use crate::shared::infrastructure::request_context::RequestContext;
+use crate::shell::state::AppState;
+
+const DEFAULT_LIMIT: usize = 100;
+const MAX_LIMIT: usize = 1_000;
+
+/// Enqueues `job` and answers `202 Accepted` with the job, and its status URL as `Location`.
+pub async fn accept(state: &AppState, job: Job) -> Response {
+    if state.job_store.enqueue(job.clone()).await.is_err() {
+        return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+    }
+    (
+        StatusCode::ACCEPTED,
+        [(header::LOCATION, format!("/admin/jobs/{}", job.job_id))],
+        Json(job),
+    )
+        .into_response()
+}
+
+#[derive(Deserialize)]
+pub struct JobParams {
+    pub kind: Option<String>,
+    pub status: Option<JobStatus>,
+    pub limit: Option<usize>,
+}
+
+/// GET /admin/jobs — newest jobs first, optionally of one kind or status. Admins only.
+pub async fn handle_list(
+    State(state): State<AppState>,
+    request_ctx: RequestContext,
+    Query(params): Query<JobParams>,
+) -> impl IntoResponse {
+    if !request_ctx.principal().can_administer() {
+        return StatusCode::FORBIDDEN.into_response();
+    }
+    let query = JobQuery {
+        kind: params.kind,
+        status: params.status,
+        limit: params.limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT),
+    };
+    match state.job_store.list(&query).await {
+        Ok(jobs) => Json(jobs).into_response(),
+        Err(_) => StatusCode::INTERNAL_SERVER_ERROR.into_response(),
+    }
+}
+
+/// GET /admin/jobs/{job_id} — one job's status, progress and result. Admins only.
+pub async fn handle_get(
+    State(state): State<AppState>,
+    request_ctx: RequestContext,
+    Path(job_id): Path<String>,
+) -> impl IntoResponse {
+    if !request_ctx.principal().can_administer() {
+        return StatusCode::FORBIDDEN.into_response();
+    }
+    match state.job_store.get(&job_id).await {
+        Ok(Some(job)) => Json(job).into_response(),
+        Ok(None) => StatusCode::NOT_FOUND.into_response(),
+        Err(_) => StatusCode::INTERNAL_SERVER_ERROR.into_response(),
+    }
+}
+
+#[cfg(test)]
+mod jobs_tests {
+    use super::*;
+    use crate::tests::fixtures::tags::make_test_app_state;
+    use axum::{Router, body::Body, http::Request, routing::get};
+    use http_body_util::BodyExt;
+    use rstest::rstest;
+    use serde_json::{Value, json};
+    use tower::ServiceExt;
+
+    fn app(state: AppState) -> Router {
+        Router::new()
+            .route("/admin/jobs", get(handle_list))
+            .route("/admin/jobs/{job_id}", get(handle_get))
+            .with_state(state)
+    }
+
+    async fn send(state: &AppState, uri: &str, role: &str) -> (StatusCode, Value) {
+        let response = app(state.clone())
+            .oneshot(
+                Request::get(uri)
+                    .header("x-user-id", "admin-1")
+                    .header("x-tenant-id", "tenant-test")
+                    .header("x-user-role", role)
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        let status = response.status();
+        let bytes = response.into_body().collect().await.unwrap().to_bytes();
+        (
+            status,
+            serde_json::from_slice(&bytes).unwrap_or(Value::Null),
+        )
+    }
+
+    async fn enqueue(state: &AppState, kind: &str) -> Job {
+        let job = Job::queued(kind, json!({}), 0);
+        state.job_store.enqueue(job.clone()).await.unwrap();
+        job
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_list_jobs_newest_first_filtered_by_kind_and_status() {
+        let state = make_test_app_state();
+        let first = enqueue(&state, "a").await;
+        let second = enqueue(&state, "a").await;
+        enqueue(&state, "b").await;
+        state.job_store.claim_due(0, 1).await.unwrap();
+
+        let (status, body) = send(&state, "/admin/jobs?kind=a", "admin").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body[0]["job_id"], second.job_id.as_str());
+        assert_eq!(body.as_array().unwrap().len(), 2);
+
+        let (_, body) = send(&state, "/admin/jobs?status=running", "admin").await;
+        assert_eq!(body.as_array().unwrap().len(), 1);
+        assert_eq!(body[0]["job_id"], first.job_id.as_str());
+        assert_eq!(body[0]["attempts"], 1);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_return_one_job_or_404() {
+        let state = make_test_app_state();
+        let job = enqueue(&state, "a").await;
+
+        let (status, body) = send(&state, &format!("/admin/jobs/{}", job.job_id), "admin").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["status"], "queued");
+        assert_eq!(body["kind"], "a");
+
+        let (status, _) = send(&state, "/admin/jobs/unknown", "admin").await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_return_500_when_the_job_store_is_offline() {
+        let state = make_test_app_state();
+        state.job_store.toggle_offline();
+
+        let (status, _) = send(&state, "/admin/jobs", "admin").await;
+
+        assert_eq!(status, StatusCode::INTERNAL_SERVER_ERROR);
+    }
+
+    #[rstest]
+    #[case::list("/admin/jobs")]
+    #[case::get("/admin/jobs/j-1")]
+    #[tokio::test]
+    async fn it_should_be_reserved_for_admins(#[case] uri: &str) {
+        let (status, _) = send(&make_test_app_state(), uri, "employee").await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+    }
+}
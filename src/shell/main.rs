@@ -1,13 +1,28 @@
-use async_graphql::{EmptySubscription, Schema};
-use async_graphql_axum::{GraphQLRequest, GraphQLResponse};
-use axum::{Extension, Router, http::HeaderMap, routing::get};
+use chrono::TimeDelta;
 use std::net::SocketAddr;
-use time_entries::shared::infrastructure::request_context::RequestContext;
-use tower_http::trace::TraceLayer;
+use std::sync::Arc;
+use std::time::Duration;
+use time_entries::shared::infrastructure::api_audit_store::in_memory::InMemoryApiAuditStore;
+use time_entries::shared::infrastructure::api_key_store::in_memory::InMemoryApiKeyStore;
 use tracing_subscriber::{EnvFilter, fmt};
 
+use time_entries::modules::contracts::core::events::ContractEvent;
+use time_entries::modules::contracts::use_cases::set_contract::handler::SetContractHandler;
+use time_entries::modules::contracts::use_cases::utilization::queries::UtilizationQueryHandler;
+use time_entries::modules::schedules::core::events::ScheduleEvent;
+use time_entries::modules::schedules::use_cases::cancel_schedule::handler::CancelScheduleHandler;
+use time_entries::modules::schedules::use_cases::define_schedule::handler::DefineScheduleHandler;
+use time_entries::modules::schedules::use_cases::list_schedules::projection::ListSchedulesState;
+use time_entries::modules::schedules::use_cases::list_schedules::projector::ListSchedulesProjector;
+use time_entries::modules::schedules::use_cases::list_schedules::queries::ListSchedulesQueryHandler;
+use time_entries::modules::schedules::use_cases::record_occurrence::handler::RecordOccurrenceHandler;
+use time_entries::modules::schedules::use_cases::record_occurrence::materializer::{
+    EntryRegistration, ScheduleMaterializer,
+};
 use time_entries::modules::tags::core::events::TagEvent;
-use time_entries::modules::tags::use_cases::create_tag::handler::CreateTagHandler;
+use time_entries::modules::tags::use_cases::create_tag::handler::{
+    CreateTagHandler, DuplicateCreatePolicy,
+};
 use time_entries::modules::tags::use_cases::delete_tag::handler::DeleteTagHandler;
 use time_entries::modules::tags::use_cases::list_tags::projection::ListTagsState;
 use time_entries::modules::tags::use_cases::list_tags::projector::{
@@ -17,49 +32,932 @@ use time_entries::modules::tags::use_cases::list_tags::queries::ListTagsQueryHan
 use time_entries::modules::tags::use_cases::set_tag_color::handler::SetTagColorHandler;
 use time_entries::modules::tags::use_cases::set_tag_description::handler::SetTagDescriptionHandler;
 use time_entries::modules::tags::use_cases::set_tag_name::handler::SetTagNameHandler;
+use time_entries::modules::templates::core::events::TemplateEvent;
+use time_entries::modules::templates::use_cases::list_templates::projection::ListTemplatesState;
+use time_entries::modules::templates::use_cases::list_templates::projector::ListTemplatesProjector;
+use time_entries::modules::templates::use_cases::list_templates::queries::ListTemplatesQueryHandler;
+use time_entries::modules::templates::use_cases::save_template::handler::SaveTemplateHandler;
+use time_entries::modules::time_entries::adapters::outbound::intent_outbox::OUTBOX_TOPIC;
+use time_entries::modules::time_entries::core::day_capacity::DayCapacity;
+use time_entries::modules::time_entries::core::days_off::AbsencePolicy;
 use time_entries::modules::time_entries::core::events::TimeEntryEvent;
+use time_entries::modules::time_entries::core::period_locks::PeriodLocksEvent;
+use time_entries::modules::time_entries::core::policies::{
+    DEFAULT_MAX_ENTRY_DURATION_MS, Policies, ROUNDING_INCREMENTS, Rounding, RoundingMode,
+};
+use time_entries::modules::time_entries::core::user_time_entries::{
+    USER_STREAM_PREFIX, UserTimeEntriesEvent,
+};
+use time_entries::modules::time_entries::use_cases::add_time_entry_attachment::handler::AddTimeEntryAttachmentHandler;
+use time_entries::modules::time_entries::use_cases::add_time_entry_comment::handler::AddTimeEntryCommentHandler;
+use time_entries::modules::time_entries::use_cases::approve_time_entry::handler::ApproveTimeEntryHandler;
+use time_entries::modules::time_entries::use_cases::archive_time_entries::job::{
+    self as archive_job, ArchiveTimeEntriesJob,
+};
+use time_entries::modules::time_entries::use_cases::auto_stop_timers::handler::AutoStopTimerHandler;
+use time_entries::modules::time_entries::use_cases::auto_stop_timers::stopper::{
+    DEFAULT_MAX_DURATION_MS, TimerAutoStopper,
+};
+use time_entries::modules::time_entries::use_cases::bulk_delete_time_entries::confirmations::BulkDeleteConfirmations;
+use time_entries::modules::time_entries::use_cases::bulk_delete_time_entries::job::{
+    self as bulk_delete_job, BulkDeleteTimeEntriesJob,
+};
+use time_entries::modules::time_entries::use_cases::compact_time_entries::job::{
+    self as compact_job, CompactTimeEntriesJob,
+};
+use time_entries::modules::time_entries::use_cases::delete_time_entry::handler::DeleteTimeEntryHandler;
+use time_entries::modules::time_entries::use_cases::erase_personal_data::eraser::TimeEntriesEraser;
+use time_entries::modules::time_entries::use_cases::export_time_entries::exporter::TimeEntryExporter;
+use time_entries::modules::time_entries::use_cases::export_time_entries::job::{
+    self as export_job, DEFAULT_EXPORT_PREFIX, ExportTimeEntriesJob,
+};
+use time_entries::modules::time_entries::use_cases::hours_balance::queries::HoursBalanceQueryHandler;
 use time_entries::modules::time_entries::use_cases::list_time_entries::projection::ListTimeEntriesState;
 use time_entries::modules::time_entries::use_cases::list_time_entries::projector::{
-    ListTimeEntriesProjector, ProjectionTechnicalEvent,
+    ListTimeEntriesProjector, ProjectionTechnicalEvent, partition_projector_name,
+};
+use time_entries::modules::time_entries::use_cases::list_time_entries::queries::{
+    ListTimeEntriesCache, ListTimeEntriesQueryHandler,
 };
-use time_entries::modules::time_entries::use_cases::list_time_entries::queries::ListTimeEntriesQueryHandler;
+use time_entries::modules::time_entries::use_cases::list_time_entries::rebuild_job::{
+    self, RebuildListTimeEntriesJob,
+};
+use time_entries::modules::time_entries::use_cases::list_time_entries::self_check::{
+    SelfCheck, SelfCheckMode,
+};
+use time_entries::modules::time_entries::use_cases::list_time_entries::shadow::ShadowProjector;
+use time_entries::modules::time_entries::use_cases::list_time_entries::updates::TimeEntryUpdates;
+use time_entries::modules::time_entries::use_cases::period_locks::handler::PeriodLocksHandler;
+use time_entries::modules::time_entries::use_cases::reject_time_entry::handler::RejectTimeEntryHandler;
+use time_entries::modules::time_entries::use_cases::set_breaks::handler::SetBreaksHandler;
 use time_entries::modules::time_entries::use_cases::set_ended_at::handler::SetEndedAtHandler;
+use time_entries::modules::time_entries::use_cases::set_hourly_rate::handler::SetHourlyRateHandler;
 use time_entries::modules::time_entries::use_cases::set_started_at::handler::SetStartedAtHandler;
 use time_entries::modules::time_entries::use_cases::set_time_entry_tags::handler::SetTimeEntryTagsHandler;
-use time_entries::shared::infrastructure::event_store::StoredEvent;
+use time_entries::modules::time_entries::use_cases::time_entry_attachments::projection::TimeEntryAttachmentsState;
+use time_entries::modules::time_entries::use_cases::time_entry_attachments::projector::TimeEntryAttachmentsProjector;
+use time_entries::modules::time_entries::use_cases::time_entry_attachments::queries::TimeEntryAttachmentsQueryHandler;
+use time_entries::modules::time_entries::use_cases::time_entry_comments::projection::TimeEntryCommentsState;
+use time_entries::modules::time_entries::use_cases::time_entry_comments::projector::TimeEntryCommentsProjector;
+use time_entries::modules::time_entries::use_cases::time_entry_comments::queries::TimeEntryCommentsQueryHandler;
+use time_entries::modules::time_entries::use_cases::update_time_entry::handler::UpdateTimeEntryHandler;
+use time_entries::modules::time_entries::use_cases::user_stats::projection::UserStatsState;
+use time_entries::modules::time_entries::use_cases::user_stats::projector::UserStatsProjector;
+use time_entries::modules::time_entries::use_cases::user_stats::queries::UserStatsQueryHandler;
+use time_entries::modules::time_entries::use_cases::user_time_entries::day_totals::{
+    SharedDayTotals, UserStreamDayTotals,
+};
+use time_entries::modules::time_entries::use_cases::user_time_entries::sharded_handler::{
+    SharedCalendar, UserStreams,
+};
+use time_entries::shared::application::archive_events_job::{self, ArchiveEventsJob};
+use time_entries::shared::application::command_bus::middleware::{
+    DEFAULT_IDEMPOTENCY_TTL_MS, DEFAULT_MAX_IDEMPOTENCY_KEYS, IdempotencyMiddleware,
+};
+use time_entries::shared::application::command_bus::pipeline::CommandPipeline;
+use time_entries::shared::application::forget_user::ForgetUserHandler;
+use time_entries::shared::application::jobs::{JobHandler, JobRunner};
+use time_entries::shared::application::server_time::SkewWindow;
+use time_entries::shared::application::slo::{SloTargets, WriteSlo};
+use time_entries::shared::application::watchdog::{OutboxProbe, Probe, ProjectorProbe, Watchdog};
+use time_entries::shared::core::redaction::Redactor;
+use time_entries::shared::core::stream_naming::{
+    DefaultStreamNaming, PrefixedStreamNaming, StreamNaming, TenantStreamNaming,
+};
+use time_entries::shared::infrastructure::attachment_storage::in_memory::InMemoryAttachmentStorage;
+use time_entries::shared::infrastructure::calendar::http::{
+    DEFAULT_TIMEOUT as DEFAULT_CALENDAR_TIMEOUT, HttpCalendar,
+};
+use time_entries::shared::infrastructure::calendar::static_config::StaticCalendar;
+use time_entries::shared::infrastructure::capacity::CapacityGauges;
+use time_entries::shared::infrastructure::cold_storage::SharedColdStorage;
+use time_entries::shared::infrastructure::cold_storage::in_memory::InMemoryColdStorage;
+use time_entries::shared::infrastructure::cold_storage::object_store::ObjectStoreColdStorage;
+use time_entries::shared::infrastructure::control_store::in_memory::InMemoryControlStore;
+use time_entries::shared::infrastructure::control_store::{
+    PauseSwitch, SharedControlStore, outbox_relay_worker, projector_worker,
+};
+use time_entries::shared::infrastructure::event_archiver::EventArchiver;
+use time_entries::shared::infrastructure::event_bus::SharedEventBus;
+use time_entries::shared::infrastructure::event_bus::in_memory::InMemoryEventBus;
+use time_entries::shared::infrastructure::event_store::crypto_shredding::{
+    AesGcmFieldCipher, CryptoShreddingEventStore,
+};
+use time_entries::shared::infrastructure::event_store::encrypted::{
+    EncryptedEventStore, SealedEvent,
+};
 use time_entries::shared::infrastructure::event_store::in_memory::InMemoryEventStore;
+use time_entries::shared::infrastructure::event_store::pseudonymous::PseudonymousStreamStore;
+use time_entries::shared::infrastructure::event_store::{SharedEventLog, StoredEvent};
+use time_entries::shared::infrastructure::feature_flags::env::EnvFeatureFlags;
+use time_entries::shared::infrastructure::feature_flags::in_memory::InMemoryFeatureFlags;
+use time_entries::shared::infrastructure::inbox::DEFAULT_RETENTION_MS as INBOX_DEFAULT_RETENTION_MS;
+use time_entries::shared::infrastructure::inbox::in_memory::InMemoryInbox;
+use time_entries::shared::infrastructure::intent_handlers::IntentHandlerRegistry;
+use time_entries::shared::infrastructure::intent_handlers::deduplicated::DeduplicatedIntentHandler;
+use time_entries::shared::infrastructure::intent_handlers::publish::BrokerPublisher;
+use time_entries::shared::infrastructure::intent_outbox::compression::{
+    CompressingOutbox, DEFAULT_THRESHOLD_BYTES, PayloadCompression,
+};
 use time_entries::shared::infrastructure::intent_outbox::in_memory::InMemoryDomainOutbox;
+use time_entries::shared::infrastructure::intent_outbox::redacting::RedactingBroker;
+use time_entries::shared::infrastructure::job_store::in_memory::InMemoryJobStore;
+use time_entries::shared::infrastructure::key_provider::SharedKeyProvider;
+use time_entries::shared::infrastructure::key_provider::env::EnvKeyProvider;
+use time_entries::shared::infrastructure::key_provider::kms::{AwsKmsClient, KmsKeyProvider};
+use time_entries::shared::infrastructure::key_store::in_memory::InMemoryKeyStore;
+use time_entries::shared::infrastructure::lease_store::in_memory::InMemoryLeaseStore;
+use time_entries::shared::infrastructure::log_redaction::RedactingFields;
+use time_entries::shared::infrastructure::message_broker::cloud_events::CloudEventsConfig;
+use time_entries::shared::infrastructure::message_broker::consumer_lag::ConsumerLag;
+use time_entries::shared::infrastructure::message_broker::encoding::{
+    PayloadEncoding, TopicEncodings,
+};
+use time_entries::shared::infrastructure::message_broker::in_memory::InMemoryMessageBroker;
+use time_entries::shared::infrastructure::notifier::log::LogNotifier;
+use time_entries::shared::infrastructure::outbox_relay::adaptive::AdaptiveBatchConfig;
+use time_entries::shared::infrastructure::outbox_relay::routing::TopicRoutes;
+use time_entries::shared::infrastructure::outbox_relay::{OutboxRelay, RelayMetrics};
+use time_entries::shared::infrastructure::policy_store::in_memory::InMemoryPolicyStore;
 use time_entries::shared::infrastructure::projection_store::in_memory::InMemoryProjectionStore;
-use time_entries::shell::graphql::{AppSchema, AppState, MutationRoot, QueryRoot};
-use time_entries::shell::http as shell_http;
-use time_entries::shell::workers::projector_runner;
+use time_entries::shared::infrastructure::projection_store::partitioned::PartitionedProjectionStore;
+use time_entries::shared::infrastructure::query_cache::in_memory::{
+    DEFAULT_MAX_ENTRIES, DEFAULT_TIME_TO_LIVE, InMemoryQueryCache,
+};
+use time_entries::shared::infrastructure::stream_transfer;
+use time_entries::shared::infrastructure::stream_transfer::anonymizer::{
+    AnonymizationPolicy, Anonymizer,
+};
+use time_entries::shared::infrastructure::user_directory::SharedUserDirectory;
+use time_entries::shared::infrastructure::user_directory::http::{
+    DEFAULT_TIMEOUT as DEFAULT_USER_DIRECTORY_TIMEOUT, HttpUserDirectory,
+};
+use time_entries::shared::infrastructure::user_directory::in_memory::InMemoryUserDirectory;
+use time_entries::shared::infrastructure::user_directory::loader::UserDisplayNameLoader;
+use time_entries::shell::audit::MAX_AUDITED_BODY_BYTES;
+use time_entries::shell::chaos::ChaosConfig;
+use time_entries::shell::graphql::{self as shell_graphql, AppState, WsConfig};
+use time_entries::shell::http::limits::{RequestLimits, RouteLimits};
+use time_entries::shell::http::rate_limit::RateLimitConfig;
+use time_entries::shell::http::routes::{API_PREFIX, RouterBuilder};
+use time_entries::shell::state::{ListTimeEntriesStore, TimeEntryEventStore, TimeEntryOutbox};
+use time_entries::shell::tuning::WorkerTuning;
+use time_entries::shell::user_data_export::{self, ExportUserDataJob};
+use time_entries::shell::workers::inbox_cleanup_runner;
+use time_entries::shell::workers::job_runner;
+use time_entries::shell::workers::leader_election::LeaderElection;
+use time_entries::shell::workers::revealed_feed_runner;
+use time_entries::shell::workers::schedule_runner;
+use time_entries::shell::workers::sealed_feed_runner;
+use time_entries::shell::workers::shadow_runner;
+use time_entries::shell::workers::timer_auto_stop_runner;
+use time_entries::shell::workers::watchdog_runner;
+
+const LEASE_TTL: Duration = Duration::from_secs(15);
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
-    fmt().with_env_filter(EnvFilter::from_default_env()).init();
+    if std::env::args().any(|arg| arg == "--print-schema") {
+        print!("{}", shell_graphql::sdl());
+        return Ok(());
+    }
+    let redactor_with_fields =
+        |redactor: Redactor, fields_var: &str| match std::env::var(fields_var) {
+            Ok(fields) => redactor.with_fields(
+                fields
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|field| !field.is_empty())
+                    .map(str::to_string)
+                    .collect::<Vec<_>>(),
+            ),
+            Err(_) => redactor,
+        };
+    // LOG_REDACTION=off logs personal data as is. LOG_REDACTED_FIELDS: comma-separated field
+    // names masked in log and trace output, instead of the user ids, `*_by` fields and
+    // `description`
+    let redactor = redactor_with_fields(
+        Redactor::new(std::env::var("LOG_REDACTION").as_deref() != Ok("off")),
+        "LOG_REDACTED_FIELDS",
+    );
+    fmt()
+        .with_env_filter(EnvFilter::from_default_env())
+        .fmt_fields(RedactingFields::new(redactor))
+        .init();
 
     // Time entries event store + projector
     let (event_tx, _) = tokio::sync::broadcast::channel::<StoredEvent<TimeEntryEvent>>(1024);
-    let event_store = InMemoryEventStore::<TimeEntryEvent>::new_with_sender(event_tx.clone());
+    // IN_MEMORY_MAX_STREAMS / IN_MEMORY_MAX_ROWS: bound the time entry event store and its
+    // projections for long-running dev and demo environments, evicting the least recently
+    // used; unset, they grow without limit. `/metrics` reports what each holds and evicted.
+    let in_memory_capacity = CapacityGauges::new();
+    let env_max = |var: &str| {
+        std::env::var(var)
+            .ok()
+            .and_then(|max| max.parse::<usize>().ok())
+    };
+    let max_rows = env_max("IN_MEMORY_MAX_ROWS");
+    // Cold storage for archived entries, exports and the event archive.
+    // COLD_STORAGE_BUCKET: keep cold storage in this S3 bucket, with credentials and region
+    // from the usual AWS_* variables; COLD_STORAGE_DIR: keep it under this local directory.
+    // Unset, it lives in memory.
+    let cold_storage: SharedColdStorage = match (
+        std::env::var("COLD_STORAGE_BUCKET"),
+        std::env::var("COLD_STORAGE_DIR"),
+    ) {
+        (Ok(bucket), _) => Arc::new(ObjectStoreColdStorage::new(Arc::new(
+            object_store::aws::AmazonS3Builder::from_env()
+                .with_bucket_name(bucket)
+                .build()
+                .expect("COLD_STORAGE_BUCKET should be reachable with the AWS_* settings"),
+        ))),
+        (_, Ok(dir)) => {
+            std::fs::create_dir_all(&dir).expect("COLD_STORAGE_DIR should be creatable");
+            Arc::new(ObjectStoreColdStorage::new(Arc::new(
+                object_store::local::LocalFileSystem::new_with_prefix(dir)
+                    .expect("COLD_STORAGE_DIR should be a directory"),
+            )))
+        }
+        _ => Arc::new(InMemoryColdStorage::new()),
+    };
+    // EVENT_ARCHIVE_EVERY_SECS: copy new time entry events into cold storage this often, as
+    // JSONL under EVENT_ARCHIVE_PREFIX (default events/time_entries) in blocks of
+    // EVENT_ARCHIVE_PARTITION_SIZE (default 10000) positions; unset, events are not archived.
+    // The archive copies the events as stored, so forgotten users stay unreadable there, and
+    // so do all payloads when they are encrypted at rest.
+    let archive_prefix =
+        std::env::var("EVENT_ARCHIVE_PREFIX").unwrap_or_else(|_| "events/time_entries".to_string());
+    let archive_partition_size =
+        env_max("EVENT_ARCHIVE_PARTITION_SIZE").map_or(10_000, |size| size as u64);
+    // EVENT_ENCRYPTION=env|kms: store time entry events sealed with AES-256-GCM, under the keys
+    // in EVENT_ENCRYPTION_KEYS (`id:<64 hex digits>,...`, the first seals new events) or under
+    // data keys the KMS key EVENT_ENCRYPTION_KMS_KEY_ID generates and unwraps, with region and
+    // credentials from the usual AWS_* variables. Unset, events are stored as JSON.
+    let encryption_keys: Option<SharedKeyProvider> =
+        match std::env::var("EVENT_ENCRYPTION").as_deref() {
+            Ok("env") => Some(Arc::new(
+                EnvKeyProvider::parse(
+                    &std::env::var("EVENT_ENCRYPTION_KEYS")
+                        .expect("EVENT_ENCRYPTION=env needs EVENT_ENCRYPTION_KEYS"),
+                )
+                .expect("EVENT_ENCRYPTION_KEYS should be id:<64 hex digits>,..."),
+            )),
+            Ok("kms") => Some(Arc::new(KmsKeyProvider::new(
+                std::env::var("EVENT_ENCRYPTION_KMS_KEY_ID")
+                    .expect("EVENT_ENCRYPTION=kms needs EVENT_ENCRYPTION_KMS_KEY_ID"),
+                AwsKmsClient::from_env().await,
+            ))),
+            Ok(other) => panic!("EVENT_ENCRYPTION should be env or kms, got {other}"),
+            Err(_) => None,
+        };
+    let (stored_event_tx, _) = tokio::sync::broadcast::channel::<StoredEvent<TimeEntryEvent>>(1024);
+    let (stored_events, archive_events_job): (SharedEventLog<TimeEntryEvent>, Arc<dyn JobHandler>) =
+        match encryption_keys {
+            None => {
+                let stored_events =
+                    InMemoryEventStore::<TimeEntryEvent>::new_with_sender(stored_event_tx.clone());
+                let stored_events = match env_max("IN_MEMORY_MAX_STREAMS") {
+                    Some(max_streams) => stored_events.with_max_streams(max_streams),
+                    None => stored_events,
+                };
+                in_memory_capacity.register("time_entry_events", stored_events.clone());
+                let stored_events = Arc::new(stored_events);
+                let archiver = EventArchiver::new(
+                    stored_events.clone(),
+                    cold_storage.clone(),
+                    archive_prefix,
+                    archive_partition_size,
+                );
+                (stored_events, Arc::new(ArchiveEventsJob::new(archiver)))
+            }
+            Some(keys) => {
+                let (sealed_event_tx, _) =
+                    tokio::sync::broadcast::channel::<StoredEvent<SealedEvent>>(1024);
+                let sealed_events =
+                    InMemoryEventStore::<SealedEvent>::new_with_sender(sealed_event_tx.clone());
+                let sealed_events = match env_max("IN_MEMORY_MAX_STREAMS") {
+                    Some(max_streams) => sealed_events.with_max_streams(max_streams),
+                    None => sealed_events,
+                };
+                in_memory_capacity.register("time_entry_events", sealed_events.clone());
+                let archiver = EventArchiver::new(
+                    Arc::new(sealed_events.clone()),
+                    cold_storage.clone(),
+                    archive_prefix,
+                    archive_partition_size,
+                );
+                let encrypted_store = EncryptedEventStore::new(sealed_events, keys);
+                sealed_feed_runner::spawn(
+                    encrypted_store.codec().clone(),
+                    sealed_event_tx.subscribe(),
+                    stored_event_tx.clone(),
+                );
+                (
+                    Arc::new(encrypted_store),
+                    Arc::new(ArchiveEventsJob::new(archiver)),
+                )
+            }
+        };
+    // Personal data in time entry events is stored encrypted under the key of the entry's
+    // owner, which `POST /admin/users/{user_id}/forget` deletes (crypto-shredding). Everything
+    // reads the events decrypted: the handlers and catch-ups through the decorated store, the
+    // projectors' live feed through the revealed feed runner. Keys live in memory, like the
+    // events.
+    let key_store = InMemoryKeyStore::new();
+    let shredding_store =
+        CryptoShreddingEventStore::new(stored_events, key_store.clone(), AesGcmFieldCipher);
+    revealed_feed_runner::spawn(
+        shredding_store.clone(),
+        stored_event_tx.subscribe(),
+        event_tx.clone(),
+    );
+    let event_store: TimeEntryEventStore = Arc::new(shredding_store);
+    // load-streams --in <file>: seed the event store from an NDJSON dump before anything reads
+    // it, then serve as usual. dump-streams --out <file> [--anonymize | --anonymize-policy
+    // <file>]: write the event store as NDJSON and exit, anonymized by the default or a JSON
+    // `AnonymizationPolicy`; as the in-memory store starts out empty, combine it with `--in`
+    // to anonymize a dump taken elsewhere.
+    let args: Vec<String> = std::env::args().collect();
+    let arg_value = |flag: &str| args.iter().skip_while(|arg| *arg != flag).nth(1).cloned();
+    if let Some(path) = arg_value("--in") {
+        let file = std::fs::File::open(&path).expect("--in should be a readable file");
+        let loaded =
+            stream_transfer::load_streams(&event_store, std::io::BufReader::new(file)).await?;
+        tracing::info!(loaded, path, "event streams loaded");
+    }
+    if args.iter().any(|arg| arg == "dump-streams") {
+        let path = arg_value("--out").expect("dump-streams should be given --out <file>");
+        let file = std::fs::File::create(&path)?;
+        let policy = match arg_value("--anonymize-policy") {
+            Some(policy) => Some(
+                serde_json::from_str::<AnonymizationPolicy>(
+                    &std::fs::read_to_string(&policy)
+                        .expect("--anonymize-policy should be a readable file"),
+                )
+                .expect("--anonymize-policy should hold an anonymization policy"),
+            ),
+            None if args.iter().any(|arg| arg == "--anonymize") => {
+                Some(AnonymizationPolicy::default())
+            }
+            None => None,
+        };
+        let mut anonymizer = policy.map(Anonymizer::new);
+        let dumped = stream_transfer::dump_streams(
+            &event_store,
+            std::io::BufWriter::new(file),
+            anonymizer.as_mut(),
+        )
+        .await?;
+        tracing::info!(dumped, path, "event streams dumped");
+        return Ok(());
+    }
     let outbox = InMemoryDomainOutbox::new();
+    // OUTBOX_COMPRESSION: gzip or zstd to compress outbox payloads whose JSON is over
+    // OUTBOX_COMPRESSION_THRESHOLD_BYTES (default 4096); relays publish them compressed as
+    // well. Unset, payloads are stored as they are.
+    let domain_outbox: TimeEntryOutbox = match std::env::var("OUTBOX_COMPRESSION") {
+        Ok(encoding) => CompressingOutbox::new(
+            outbox.clone(),
+            PayloadCompression::new(
+                encoding
+                    .parse()
+                    .expect("OUTBOX_COMPRESSION should be gzip or zstd"),
+            )
+            .with_threshold_bytes(
+                std::env::var("OUTBOX_COMPRESSION_THRESHOLD_BYTES")
+                    .ok()
+                    .and_then(|bytes| bytes.parse().ok())
+                    .unwrap_or(DEFAULT_THRESHOLD_BYTES),
+            ),
+        ),
+        Err(_) => CompressingOutbox::uncompressed(outbox.clone()),
+    };
+    // STREAM_NAMING: how time entry streams are named; `default` (`TimeEntry-{id}`),
+    // `tenant` (`{tenant}/TimeEntry-{id}`) or `prefix:<prefix>` (`<prefix>TimeEntry-{id}`)
+    let stream_naming: Arc<dyn StreamNaming> = match std::env::var("STREAM_NAMING").as_deref() {
+        Ok("tenant") => Arc::new(TenantStreamNaming),
+        Ok(naming) if naming.starts_with("prefix:") => {
+            Arc::new(PrefixedStreamNaming::new(&naming["prefix:".len()..]))
+        }
+        Ok("default") | Err(_) => Arc::new(DefaultStreamNaming),
+        Ok(_) => panic!("STREAM_NAMING should be default, tenant or prefix:<prefix>"),
+    };
 
-    let projection_store = InMemoryProjectionStore::<ListTimeEntriesState>::new();
+    // LIST_TIME_ENTRIES_PARTITIONS: projector workers, each owning a hash partition of streams
+    let partition_count = std::env::var("LIST_TIME_ENTRIES_PARTITIONS")
+        .ok()
+        .and_then(|count| count.parse().ok())
+        .unwrap_or(1);
+    let projection_store: ListTimeEntriesStore =
+        PartitionedProjectionStore::new(partition_count, |partition| {
+            let store = InMemoryProjectionStore::<ListTimeEntriesState>::new();
+            let store = match max_rows {
+                Some(max_rows) => store.with_max_rows(max_rows),
+                None => store,
+            };
+            in_memory_capacity.register(
+                &format!("list_time_entries_{}", partition.index),
+                store.clone(),
+            );
+            store
+        });
+    // QUERY_CACHE_MAX_ENTRIES (default 10000) / QUERY_CACHE_TTL_SECS (default 300): bound the
+    // cache of listed time entry pages, dropping the least recently used and the oldest
+    let list_time_entries_cache: ListTimeEntriesCache = Arc::new(InMemoryQueryCache::bounded(
+        env_max("QUERY_CACHE_MAX_ENTRIES").map_or(DEFAULT_MAX_ENTRIES, |max| max as u64),
+        env_max("QUERY_CACHE_TTL_SECS").map_or(DEFAULT_TIME_TO_LIVE, |secs| {
+            Duration::from_secs(secs as u64)
+        }),
+    ));
     let (tech_tx, _) = tokio::sync::broadcast::channel::<ProjectionTechnicalEvent>(256);
-    let projector = ListTimeEntriesProjector::new(
-        "list_time_entries",
+    // Rows the projectors write, streamed to SSE clients and GraphQL subscriptions
+    let time_entry_updates = TimeEntryUpdates::default();
+
+    // STARTUP_SELF_CHECK: off (default) | warn | strict; before the projectors start, check
+    // each partition's checkpoint and a sample of its rows (STARTUP_SELF_CHECK_SAMPLE, default
+    // 100) against the event log. Strict refuses to start on any inconsistency.
+    let self_check_mode: SelfCheckMode = std::env::var("STARTUP_SELF_CHECK")
+        .map(|mode| {
+            mode.parse()
+                .expect("STARTUP_SELF_CHECK should be off, warn or strict")
+        })
+        .unwrap_or_default();
+    if self_check_mode != SelfCheckMode::Off {
+        let mut self_check = SelfCheck::new(event_store.clone());
+        if let Some(sample_size) = std::env::var("STARTUP_SELF_CHECK_SAMPLE")
+            .ok()
+            .and_then(|size| size.parse().ok())
+        {
+            self_check = self_check.with_sample_size(sample_size);
+        }
+        let stores: Vec<_> = projection_store
+            .partitions()
+            .into_iter()
+            .map(|(partition, store)| (partition_projector_name(partition), store))
+            .collect();
+        let report = self_check.run(&stores).await?;
+        for inconsistency in &report.inconsistencies {
+            tracing::error!(
+                ?inconsistency,
+                "list projection inconsistent with the event log"
+            );
+        }
+        if !report.is_clean() && self_check_mode == SelfCheckMode::Strict {
+            anyhow::bail!(
+                "startup self-check found {} inconsistencies",
+                report.inconsistencies.len()
+            );
+        }
+        tracing::info!(
+            head = report.head,
+            stores = report.stores_checked,
+            rows = report.rows_sampled,
+            inconsistencies = report.inconsistencies.len(),
+            "startup self-check done"
+        );
+    }
+
+    // Leases so that, across instances, only one drives each projector and relay.
+    // The in-memory store only coordinates within this process.
+    let lease_store = InMemoryLeaseStore::new();
+    let instance_id =
+        std::env::var("INSTANCE_ID").unwrap_or_else(|_| uuid::Uuid::now_v7().to_string());
+    // Operator pauses of projectors and relays, set through the control mutations
+    let control_store = InMemoryControlStore::new();
+    let controls: SharedControlStore = Arc::new(control_store.clone());
+
+    for (partition, partition_store) in projection_store.partitions() {
+        let name = partition_projector_name(partition);
+        let projector_election = LeaderElection::new(
+            lease_store.clone(),
+            name.clone(),
+            instance_id.clone(),
+            LEASE_TTL,
+        );
+        let event_store = event_store.clone();
+        let event_tx = event_tx.clone();
+        let tech_tx = tech_tx.clone();
+        let list_time_entries_cache = list_time_entries_cache.clone();
+        let time_entry_updates = time_entry_updates.clone();
+        let pause = PauseSwitch::new(controls.clone(), projector_worker(&name));
+        tokio::spawn(projector_election.run(move || {
+            ListTimeEntriesProjector::new(
+                name.clone(),
+                partition_store.clone(),
+                event_store.clone(),
+                tech_tx.clone(),
+            )
+            .with_partition(partition)
+            .with_query_cache(list_time_entries_cache.clone())
+            .with_updates(time_entry_updates.clone())
+            .with_pause_switch(pause.clone())
+            .run(event_tx.subscribe())
+        }));
+    }
+    // LIST_TIME_ENTRIES_SHADOW_SECS: every so often, replay the feed into a scratch store and
+    // log where it diverges from each partition's live rows; unset or 0 disables it
+    let shadow_every = std::env::var("LIST_TIME_ENTRIES_SHADOW_SECS")
+        .ok()
+        .and_then(|secs| secs.parse().ok())
+        .filter(|secs| *secs > 0)
+        .map(Duration::from_secs);
+    if let Some(every) = shadow_every {
+        let shadows = projection_store
+            .partitions()
+            .into_iter()
+            .map(|(partition, partition_store)| {
+                let shadow =
+                    ShadowProjector::new(partition_projector_name(partition), event_store.clone())
+                        .with_partition(partition);
+                (shadow, partition_store)
+            })
+            .collect();
+        shadow_runner::spawn(shadows, every);
+    }
+    // CALENDAR_URL: fetch holidays and approved absences from the HR service at this base URL,
+    // giving up on a request after CALENDAR_TIMEOUT_MS (default 2000); unset, CALENDAR_CONFIG:
+    // JSON file with them. ABSENCE_POLICY: allow | warn | reject registering time on those days
+    let calendar: SharedCalendar = match std::env::var("CALENDAR_URL") {
+        Ok(base_url) => Arc::new(HttpCalendar::new(
+            base_url,
+            reqwest::Client::builder()
+                .timeout(
+                    env_max("CALENDAR_TIMEOUT_MS").map_or(DEFAULT_CALENDAR_TIMEOUT, |ms| {
+                        Duration::from_millis(ms as u64)
+                    }),
+                )
+                .build()
+                .expect("the calendar client should build"),
+        )),
+        Err(_) => Arc::new(match std::env::var("CALENDAR_CONFIG") {
+            Ok(path) => StaticCalendar::from_json(
+                &std::fs::read_to_string(&path).expect("CALENDAR_CONFIG should be readable"),
+            )
+            .expect("CALENDAR_CONFIG should be a valid calendar"),
+            Err(_) => StaticCalendar::default(),
+        }),
+    };
+    let absence_policy: AbsencePolicy = std::env::var("ABSENCE_POLICY")
+        .map(|policy| {
+            policy
+                .parse()
+                .expect("ABSENCE_POLICY should be allow, warn or reject")
+        })
+        .unwrap_or_default();
+    let hours_balance_handler =
+        HoursBalanceQueryHandler::new(projection_store.clone(), calendar.clone());
+    // Contracted weekly hours per user, the baseline for utilization
+    let contract_event_store = InMemoryEventStore::<ContractEvent>::new();
+    let set_contract_handler = SetContractHandler::new(contract_event_store.clone());
+    let utilization_handler = UtilizationQueryHandler::new(
+        contract_event_store.clone(),
         projection_store.clone(),
+        calendar.clone(),
+    );
+    // Per-day totals behind the statistics dashboard
+    let user_stats_store = InMemoryProjectionStore::<UserStatsState>::new();
+    let user_stats_projector = UserStatsProjector::new(
+        "user_stats",
+        user_stats_store.clone(),
+        event_store.clone(),
+        tech_tx.clone(),
+    );
+    tokio::spawn(user_stats_projector.run(event_tx.subscribe()));
+    let user_stats_handler = UserStatsQueryHandler::new(user_stats_store.clone());
+    // Comment threads on entries, for discussing approvals
+    let time_entry_comments_store = match max_rows {
+        Some(max_rows) => {
+            InMemoryProjectionStore::<TimeEntryCommentsState>::new().with_max_rows(max_rows)
+        }
+        None => InMemoryProjectionStore::new(),
+    };
+    in_memory_capacity.register("time_entry_comments", time_entry_comments_store.clone());
+    let time_entry_comments_projector = TimeEntryCommentsProjector::new(
+        "time_entry_comments",
+        time_entry_comments_store.clone(),
+        event_store.clone(),
+        tech_tx.clone(),
+    );
+    tokio::spawn(time_entry_comments_projector.run(event_tx.subscribe()));
+    let time_entry_comments_handler =
+        TimeEntryCommentsQueryHandler::new(time_entry_comments_store.clone());
+    // Receipts and screenshots attached to entries
+    let time_entry_attachments_store = match max_rows {
+        Some(max_rows) => {
+            InMemoryProjectionStore::<TimeEntryAttachmentsState>::new().with_max_rows(max_rows)
+        }
+        None => InMemoryProjectionStore::new(),
+    };
+    in_memory_capacity.register(
+        "time_entry_attachments",
+        time_entry_attachments_store.clone(),
+    );
+    let time_entry_attachments_projector = TimeEntryAttachmentsProjector::new(
+        "time_entry_attachments",
+        time_entry_attachments_store.clone(),
         event_store.clone(),
-        tech_tx,
+        tech_tx.clone(),
     );
-    let receiver = event_tx.subscribe();
-    projector_runner::spawn(projector, receiver);
-    let list_time_entries_handler = ListTimeEntriesQueryHandler::new(projection_store);
+    tokio::spawn(time_entry_attachments_projector.run(event_tx.subscribe()));
+    let time_entry_attachments_handler =
+        TimeEntryAttachmentsQueryHandler::new(time_entry_attachments_store.clone());
+    let list_time_entries_handler = ListTimeEntriesQueryHandler::new(projection_store.clone())
+        .with_cache(list_time_entries_cache.clone());
+    // Per-user streams guarding overlap and running-timer invariants across entries, stored
+    // under the id of the user's data key rather than their user id
+    let user_streams: UserStreams = Arc::new(PseudonymousStreamStore::new(
+        InMemoryEventStore::<UserTimeEntriesEvent>::new(),
+        key_store.clone(),
+        USER_STREAM_PREFIX,
+    ));
+    // Forgetting a user also erases their personal data from the time entry read models
+    let time_entries_eraser = TimeEntriesEraser::new(
+        projection_store
+            .partitions()
+            .into_iter()
+            .map(|(_, partition_store)| partition_store)
+            .collect(),
+        user_stats_store.clone(),
+        time_entry_comments_store.clone(),
+        time_entry_attachments_store.clone(),
+    )
+    .with_query_cache(list_time_entries_cache.clone());
+    // Locked payroll periods, checked before any entry inside them changes
+    let period_lock_store = InMemoryEventStore::<PeriodLocksEvent>::new();
+    let period_locks_handler = PeriodLocksHandler::new(period_lock_store.clone());
+    // CLOCK_SKEW_AHEAD_SECS: how far ahead of server time a client may place a start or
+    // end (default 300); MAX_BACKDATE_DAYS: how far back time may be registered (unbounded)
+    let default_skew = SkewWindow::default();
+    let skew_window = SkewWindow {
+        max_ahead: std::env::var("CLOCK_SKEW_AHEAD_SECS")
+            .ok()
+            .and_then(|secs| secs.parse::<i64>().ok())
+            .map(TimeDelta::seconds)
+            .unwrap_or(default_skew.max_ahead),
+        max_behind: std::env::var("MAX_BACKDATE_DAYS")
+            .ok()
+            .and_then(|days| days.parse::<i64>().ok())
+            .map(TimeDelta::days)
+            .or(default_skew.max_behind),
+    };
+    // FEATURE_FLAGS: rules rolled out per tenant, e.g. `max_entry_duration=t-1,t-2`, which
+    // admins override at runtime
+    let feature_flags = InMemoryFeatureFlags::new().with_fallback(Arc::new(
+        EnvFeatureFlags::from_env("FEATURE_FLAGS")
+            .expect("FEATURE_FLAGS should list name=on|off|tenants entries"),
+    ));
+    // The policies of tenants without their own, which admins set per tenant at runtime.
+    // MAX_ENTRY_HOURS: the longest a finished entry may be where `max_entry_duration` is on
+    // (default 24); MAX_TAGS: the most tags per entry; LOCK_AFTER_DAYS: how long entries stay
+    // open for changes; ROUND_TO_MINUTES (5, 15 or 30) with ROUNDING_MODE (nearest, up or
+    // down): how starts and ends are rounded. Unset, there is no tag limit, lock or rounding.
+    let env_number = |var: &str| {
+        std::env::var(var)
+            .ok()
+            .and_then(|value| value.parse::<u32>().ok())
+    };
+    let default_policies = Policies {
+        max_tags: env_number("MAX_TAGS").map(|max| max as usize),
+        max_entry_duration_ms: env_number("MAX_ENTRY_HOURS")
+            .map_or(DEFAULT_MAX_ENTRY_DURATION_MS, |hours| {
+                i64::from(hours) * 60 * 60 * 1000
+            }),
+        lock_after_days: env_number("LOCK_AFTER_DAYS"),
+        rounding: env_number("ROUND_TO_MINUTES").map(|increment_minutes| Rounding {
+            increment_minutes: Some(increment_minutes)
+                .filter(|minutes| ROUNDING_INCREMENTS.contains(minutes))
+                .expect("ROUND_TO_MINUTES should be 5, 15 or 30"),
+            mode: match std::env::var("ROUNDING_MODE").as_deref() {
+                Ok("up") => RoundingMode::Up,
+                Ok("down") => RoundingMode::Down,
+                _ => RoundingMode::Nearest,
+            },
+        }),
+    };
+    let policy_store = InMemoryPolicyStore::new();
+    // SLO_AVAILABILITY: the share of register commands that must not fail on the server
+    // (default 0.999); SLO_LATENCY: the share that must finish within SLO_LATENCY_MS
+    // (default 0.99 within 500)
+    let default_targets = SloTargets::default();
+    let env_ratio = |var: &str| {
+        std::env::var(var)
+            .ok()
+            .and_then(|value| value.parse::<f64>().ok())
+            .filter(|ratio| (0.0..1.0).contains(ratio))
+    };
+    let write_slo = WriteSlo::new(SloTargets {
+        availability: env_ratio("SLO_AVAILABILITY").unwrap_or(default_targets.availability),
+        latency: env_ratio("SLO_LATENCY").unwrap_or(default_targets.latency),
+        latency_threshold_ms: env_number("SLO_LATENCY_MS")
+            .map_or(default_targets.latency_threshold_ms, u64::from),
+    });
+    // DAY_CAPACITY: allow | warn | reject registering more time on a day than
+    // DAY_CAPACITY_MAX_HOURS (default 24) or, with DAY_CAPACITY_OVERTIME_HOURS, than the
+    // user's contracted hours plus that overtime
+    let hours_ms = |hours: u32| i64::from(hours) * 60 * 60 * 1000;
+    let default_capacity = DayCapacity::default();
+    let day_capacity = DayCapacity {
+        policy: std::env::var("DAY_CAPACITY")
+            .map(|policy| {
+                policy
+                    .parse()
+                    .expect("DAY_CAPACITY should be allow, warn or reject")
+            })
+            .unwrap_or_default(),
+        max_day_ms: env_number("DAY_CAPACITY_MAX_HOURS")
+            .map_or(default_capacity.max_day_ms, hours_ms),
+        overtime_ms: env_number("DAY_CAPACITY_OVERTIME_HOURS").map(hours_ms),
+    };
+    let day_totals: SharedDayTotals = Arc::new(
+        UserStreamDayTotals::new(user_streams.clone())
+            .with_contracts(Arc::new(contract_event_store.clone())),
+    );
+    // The time entry handlers publish what they append straight to the projectors' channel.
+    // The store's feed carries the same events, along with those of jobs and imports that
+    // append to it directly; projectors skip whichever copy arrives second.
+    let time_entry_event_bus: SharedEventBus<TimeEntryEvent> =
+        Arc::new(InMemoryEventBus::from_sender(event_tx.clone()));
     let set_started_at_handler =
-        SetStartedAtHandler::new("time-entries.v1", event_store.clone(), outbox.clone());
-    let set_ended_at_handler =
-        SetEndedAtHandler::new("time-entries.v1", event_store.clone(), outbox.clone());
+        SetStartedAtHandler::new(event_store.clone(), domain_outbox.clone())
+            .with_user_streams(user_streams.clone())
+            .with_period_locks(Arc::new(period_lock_store.clone()))
+            .with_calendar(calendar.clone(), absence_policy)
+            .with_day_capacity(day_totals.clone(), day_capacity)
+            .with_feature_flags(Arc::new(feature_flags.clone()))
+            .with_policies(Arc::new(policy_store.clone()), default_policies)
+            .with_skew_window(skew_window)
+            .with_slo(write_slo.clone())
+            .with_event_bus(time_entry_event_bus.clone());
+    let set_ended_at_handler = SetEndedAtHandler::new(event_store.clone(), domain_outbox.clone())
+        .with_user_streams(user_streams.clone())
+        .with_period_locks(Arc::new(period_lock_store.clone()))
+        .with_calendar(calendar.clone(), absence_policy)
+        .with_day_capacity(day_totals.clone(), day_capacity)
+        .with_feature_flags(Arc::new(feature_flags.clone()))
+        .with_policies(Arc::new(policy_store.clone()), default_policies)
+        .with_skew_window(skew_window)
+        .with_slo(write_slo.clone())
+        .with_event_bus(time_entry_event_bus.clone());
     let set_time_entry_tags_handler =
-        SetTimeEntryTagsHandler::new("time-entries.v1", event_store.clone(), outbox.clone());
+        SetTimeEntryTagsHandler::new(event_store.clone(), domain_outbox.clone())
+            .with_period_locks(Arc::new(period_lock_store.clone()))
+            .with_policies(Arc::new(policy_store.clone()), default_policies)
+            .with_event_bus(time_entry_event_bus.clone());
+    let set_hourly_rate_handler =
+        SetHourlyRateHandler::new(event_store.clone(), domain_outbox.clone())
+            .with_period_locks(Arc::new(period_lock_store.clone()))
+            .with_event_bus(time_entry_event_bus.clone());
+    let set_breaks_handler = SetBreaksHandler::new(event_store.clone(), domain_outbox.clone())
+        .with_period_locks(Arc::new(period_lock_store.clone()))
+        .with_policies(Arc::new(policy_store.clone()), default_policies)
+        .with_event_bus(time_entry_event_bus.clone());
+    let update_time_entry_handler =
+        UpdateTimeEntryHandler::new(event_store.clone(), domain_outbox.clone())
+            .with_user_streams(user_streams.clone())
+            .with_period_locks(Arc::new(period_lock_store.clone()))
+            .with_calendar(calendar.clone(), absence_policy)
+            .with_day_capacity(day_totals, day_capacity)
+            .with_feature_flags(Arc::new(feature_flags.clone()))
+            .with_policies(Arc::new(policy_store.clone()), default_policies)
+            .with_skew_window(skew_window)
+            .with_event_bus(time_entry_event_bus.clone());
+    let delete_time_entry_handler =
+        DeleteTimeEntryHandler::new(event_store.clone(), domain_outbox.clone())
+            .with_user_streams(user_streams.clone())
+            .with_period_locks(Arc::new(period_lock_store.clone()))
+            .with_policies(Arc::new(policy_store.clone()), default_policies)
+            .with_event_bus(time_entry_event_bus.clone());
+    let approve_time_entry_handler =
+        ApproveTimeEntryHandler::new(event_store.clone(), domain_outbox.clone())
+            .with_event_bus(time_entry_event_bus.clone());
+    let reject_time_entry_handler =
+        RejectTimeEntryHandler::new(event_store.clone(), domain_outbox.clone())
+            .with_event_bus(time_entry_event_bus.clone());
+    let add_time_entry_comment_handler =
+        AddTimeEntryCommentHandler::new(event_store.clone(), domain_outbox.clone())
+            .with_event_bus(time_entry_event_bus.clone());
+    let add_time_entry_attachment_handler =
+        AddTimeEntryAttachmentHandler::new(event_store.clone(), domain_outbox.clone())
+            .with_event_bus(time_entry_event_bus.clone());
+    // TIMER_MAX_HOURS: running timers are stopped this many hours after they started
+    let timer_max_duration_ms = std::env::var("TIMER_MAX_HOURS")
+        .ok()
+        .and_then(|hours| hours.parse::<i64>().ok())
+        .map(|hours| hours * 60 * 60 * 1000)
+        .unwrap_or(DEFAULT_MAX_DURATION_MS);
+    timer_auto_stop_runner::spawn(
+        TimerAutoStopper::new(
+            projection_store.clone(),
+            AutoStopTimerHandler::new(event_store.clone(), domain_outbox.clone())
+                .with_user_streams(user_streams)
+                .with_period_locks(Arc::new(period_lock_store.clone()))
+                .with_event_bus(time_entry_event_bus),
+        )
+        .with_max_duration_ms(timer_max_duration_ms)
+        .with_stream_naming(stream_naming.clone()),
+        Duration::from_secs(60),
+    );
+
+    // Worker tuning, retunable at runtime through the `updateWorkerTuning` mutation.
+    // JOB_WORKERS: jobs run at once (default 4); RATE_LIMIT_PER_MINUTE: requests each caller
+    // may make per minute on this instance, unset or 0 disables the limit
+    let job_workers = std::env::var("JOB_WORKERS")
+        .ok()
+        .and_then(|workers| workers.parse().ok())
+        .unwrap_or(4);
+    let rate_limit = std::env::var("RATE_LIMIT_PER_MINUTE")
+        .map(|limit| {
+            limit
+                .parse::<u32>()
+                .expect("RATE_LIMIT_PER_MINUTE should be a number of requests")
+        })
+        .unwrap_or(0);
+    let tuning = WorkerTuning::new(
+        AdaptiveBatchConfig::default(),
+        job_workers,
+        RateLimitConfig {
+            requests: rate_limit,
+            window: Duration::from_secs(60),
+        },
+    );
+
+    // Outbox relay with adaptive batching; the in-memory broker stands in for Pulsar/Kafka
+    let relay_election = LeaderElection::new(
+        lease_store,
+        format!("outbox_relay:{OUTBOX_TOPIC}"),
+        instance_id,
+        LEASE_TTL,
+    );
+    // BROKER_ENCODING: json (default) | avro | protobuf for every topic; BROKER_TOPIC_ENCODINGS:
+    // per-topic overrides such as `time-entries.v1=avro,billing.v1=protobuf`
+    let default_encoding: PayloadEncoding = std::env::var("BROKER_ENCODING")
+        .map(|encoding| {
+            encoding
+                .parse()
+                .expect("BROKER_ENCODING should be json, avro or protobuf")
+        })
+        .unwrap_or_default();
+    let topic_encodings = TopicEncodings::parse(
+        default_encoding,
+        &std::env::var("BROKER_TOPIC_ENCODINGS").unwrap_or_default(),
+    )
+    .expect("BROKER_TOPIC_ENCODINGS should list topic=encoding pairs");
+    let mut broker = InMemoryMessageBroker::new().with_encodings(topic_encodings);
+    // CLOUD_EVENTS_SOURCE: URI naming this service; when set, messages are CloudEvents 1.0
+    if let Ok(source) = std::env::var("CLOUD_EVENTS_SOURCE") {
+        broker = broker.with_cloud_events(CloudEventsConfig::new(source));
+    }
+    // Executors record the rows they handled in the inbox, so a batch the relay resends
+    // after a failure does not repeat them. INBOX_RETENTION_HOURS: how long they are
+    // remembered, longer than the relay keeps retrying a batch
+    let inbox = InMemoryInbox::new();
+    let inbox_retention_ms =
+        env_number("INBOX_RETENTION_HOURS").map_or(INBOX_DEFAULT_RETENTION_MS, hours_ms);
+    inbox_cleanup_runner::spawn(
+        inbox.clone(),
+        inbox_retention_ms,
+        Duration::from_secs(60 * 60),
+    );
+    // BROKER_REDACTION=on masks personal data in published payloads; the outbox keeps it.
+    // BROKER_REDACTED_FIELDS: comma-separated field names masked instead of the defaults
+    let broker = RedactingBroker::new(
+        broker,
+        redactor_with_fields(
+            Redactor::new(std::env::var("BROKER_REDACTION").as_deref() == Ok("on")),
+            "BROKER_REDACTED_FIELDS",
+        ),
+    );
+    // Intents are executed by type; everything is published to the broker until other
+    // executors (webhooks, emails) are registered
+    let intent_handlers = IntentHandlerRegistry::new().with_fallback(Arc::new(
+        DeduplicatedIntentHandler::new("broker-publisher", BrokerPublisher::new(broker), inbox),
+    ));
+    // OUTBOX_TOPIC_ROUTES: broker topic per event type, such as
+    // `TimerAutoStopped=timers.v1,TimeEntryRegistered@2=time-entries.v2,*=time-entries.v1`;
+    // unrouted events keep the outbox topic
+    let topic_routes =
+        TopicRoutes::parse(&std::env::var("OUTBOX_TOPIC_ROUTES").unwrap_or_default())
+            .expect("OUTBOX_TOPIC_ROUTES should list EventType[@version]=topic pairs");
+    let relay_pause = PauseSwitch::new(controls, outbox_relay_worker(OUTBOX_TOPIC));
+    let relay_metrics = RelayMetrics::new();
+    tokio::spawn({
+        let outbox = outbox.clone();
+        let tuning = tuning.clone();
+        let relay_metrics = relay_metrics.clone();
+        relay_election.run(move || {
+            OutboxRelay::new(
+                OUTBOX_TOPIC,
+                outbox.clone(),
+                intent_handlers.clone(),
+                tuning.relay(),
+            )
+            .with_routes(topic_routes.clone())
+            .with_metrics(relay_metrics.clone())
+            .with_pause_switch(relay_pause.clone())
+            .with_config_updates(tuning.relay_updates())
+            .run()
+        })
+    });
 
     // Tags event store + projector
     let (tag_event_tx, _) = tokio::sync::broadcast::channel::<StoredEvent<TagEvent>>(1024);
@@ -77,19 +975,216 @@ async fn main() -> anyhow::Result<()> {
     tokio::spawn(tag_projector.run(tag_receiver));
 
     let list_tags_handler = ListTagsQueryHandler::new(tag_projection_store.clone());
-    let create_tag_handler = CreateTagHandler::new(tag_event_store.clone());
+    // TAG_DUPLICATE_CREATE: conflict (default) | idempotent; idempotent answers a create for an
+    // existing tag with success when name, color and description match, instead of 409
+    let duplicate_create_policy: DuplicateCreatePolicy = std::env::var("TAG_DUPLICATE_CREATE")
+        .map(|policy| {
+            policy
+                .parse()
+                .expect("TAG_DUPLICATE_CREATE should be conflict or idempotent")
+        })
+        .unwrap_or_default();
+    let create_tag_handler = CreateTagHandler::new(tag_event_store.clone())
+        .with_duplicate_policy(duplicate_create_policy);
     let delete_tag_handler = DeleteTagHandler::new(tag_event_store.clone());
     let set_tag_name_handler = SetTagNameHandler::new(tag_event_store.clone());
     let set_tag_color_handler = SetTagColorHandler::new(tag_event_store.clone());
     let set_tag_description_handler = SetTagDescriptionHandler::new(tag_event_store.clone());
 
+    // Templates event store + projector. Their handlers are the only writers, so the projector
+    // follows the event bus they publish to after each append.
+    let template_event_bus = InMemoryEventBus::<TemplateEvent>::new(1024);
+    let template_event_store = InMemoryEventStore::<TemplateEvent>::new();
+    let template_projection_store = InMemoryProjectionStore::<ListTemplatesState>::new();
+    let template_projector = ListTemplatesProjector::new(
+        "list_templates",
+        template_projection_store.clone(),
+        template_event_store.clone(),
+        tech_tx.clone(),
+    );
+    tokio::spawn(template_projector.run(template_event_bus.subscribe()));
+    let list_templates_handler = ListTemplatesQueryHandler::new(template_projection_store.clone());
+    let save_template_handler = SaveTemplateHandler::new(template_event_store.clone())
+        .with_event_bus(Arc::new(template_event_bus));
+
+    // Schedules event store + projector, following the event bus like templates, and the
+    // worker registering their occurrences
+    let schedule_event_bus = InMemoryEventBus::<ScheduleEvent>::new(1024);
+    let schedule_event_store = InMemoryEventStore::<ScheduleEvent>::new();
+    let schedule_projection_store = InMemoryProjectionStore::<ListSchedulesState>::new();
+    let schedule_projector = ListSchedulesProjector::new(
+        "list_schedules",
+        schedule_projection_store.clone(),
+        schedule_event_store.clone(),
+        tech_tx.clone(),
+    );
+    tokio::spawn(schedule_projector.run(schedule_event_bus.subscribe()));
+    let list_schedules_handler = ListSchedulesQueryHandler::new(schedule_projection_store.clone());
+    let define_schedule_handler = DefineScheduleHandler::new(schedule_event_store.clone())
+        .with_event_bus(Arc::new(schedule_event_bus.clone()));
+    let cancel_schedule_handler = CancelScheduleHandler::new(schedule_event_store.clone())
+        .with_event_bus(Arc::new(schedule_event_bus.clone()));
+    schedule_runner::spawn(
+        ScheduleMaterializer::new(
+            schedule_projection_store.clone(),
+            projection_store.clone(),
+            RecordOccurrenceHandler::new(schedule_event_store.clone())
+                .with_event_bus(Arc::new(schedule_event_bus)),
+            EntryRegistration {
+                set_started_at: set_started_at_handler.clone(),
+                set_ended_at: set_ended_at_handler.clone(),
+                set_tags: set_time_entry_tags_handler.clone(),
+            },
+        )
+        .with_stream_naming(stream_naming.clone()),
+        Duration::from_secs(300),
+    );
+
+    // Watchdog alerting when projectors or the outbox relay stall. WATCHDOG_SECS: how often it
+    // checks (default 30, 0 disables it); WATCHDOG_STALL_AFTER_SECS: how long processing may
+    // make no progress before it alerts (default 300); WATCHDOG_MAX_PROJECTOR_LAG and
+    // WATCHDOG_MAX_OUTBOX_BACKLOG: events or rows behind that never count as a stall
+    // (default 0). Alerts are logged until a Slack or webhook transport is wired in.
+    let env_u64 = |var: &str, default: u64| {
+        std::env::var(var)
+            .ok()
+            .and_then(|value| value.parse::<u64>().ok())
+            .unwrap_or(default)
+    };
+    let watchdog_every = env_u64("WATCHDOG_SECS", 30);
+    if watchdog_every > 0 {
+        let max_projector_lag = env_u64("WATCHDOG_MAX_PROJECTOR_LAG", 0);
+        let mut probes: Vec<Arc<dyn Probe>> = projection_store
+            .partitions()
+            .into_iter()
+            .map(|(partition, partition_store)| {
+                Arc::new(ProjectorProbe::new(
+                    partition_projector_name(partition),
+                    event_store.clone(),
+                    partition_store,
+                    max_projector_lag,
+                )) as Arc<dyn Probe>
+            })
+            .collect();
+        probes.push(Arc::new(ProjectorProbe::new(
+            "user_stats",
+            event_store.clone(),
+            user_stats_store,
+            max_projector_lag,
+        )));
+        probes.push(Arc::new(ProjectorProbe::new(
+            "time_entry_comments",
+            event_store.clone(),
+            time_entry_comments_store,
+            max_projector_lag,
+        )));
+        probes.push(Arc::new(ProjectorProbe::new(
+            "time_entry_attachments",
+            event_store.clone(),
+            time_entry_attachments_store,
+            max_projector_lag,
+        )));
+        probes.push(Arc::new(ProjectorProbe::new(
+            "list_tags",
+            tag_event_store.clone(),
+            tag_projection_store.clone(),
+            max_projector_lag,
+        )));
+        probes.push(Arc::new(ProjectorProbe::new(
+            "list_templates",
+            template_event_store.clone(),
+            template_projection_store,
+            max_projector_lag,
+        )));
+        probes.push(Arc::new(ProjectorProbe::new(
+            "list_schedules",
+            schedule_event_store.clone(),
+            schedule_projection_store,
+            max_projector_lag,
+        )));
+        probes.push(Arc::new(OutboxProbe::new(
+            OUTBOX_TOPIC,
+            outbox.clone(),
+            env_u64("WATCHDOG_MAX_OUTBOX_BACKLOG", 0),
+        )));
+        let stall_after_secs = env_u64("WATCHDOG_STALL_AFTER_SECS", 300);
+        watchdog_runner::spawn(
+            probes,
+            Watchdog::new(stall_after_secs as i64 * 1000),
+            Arc::new(LogNotifier),
+            Duration::from_secs(watchdog_every),
+        );
+    }
+
+    // Job table
+    let job_store = InMemoryJobStore::new();
+
+    // API_KEYS: keys machine clients may send as X-Api-Key from startup on, as comma-separated
+    // `<sha-256 hex of the key>:<user_id>:<tenant_id>:<register-only|read-only|admin>`; admins
+    // issue and revoke more through /admin/api-keys, which last until the process restarts
+    let api_key_store = match std::env::var("API_KEYS") {
+        Ok(keys) => InMemoryApiKeyStore::parse(&keys).expect("API_KEYS should list hashed keys"),
+        Err(_) => InMemoryApiKeyStore::new(),
+    };
+
+    // USER_DIRECTORY_URL: resolve display names from the identity service at this base URL,
+    // giving up on a batch after USER_DIRECTORY_TIMEOUT_MS (default 2000); unset, from an
+    // empty in-memory directory
+    let user_directory: SharedUserDirectory = match std::env::var("USER_DIRECTORY_URL") {
+        Ok(base_url) => Arc::new(
+            HttpUserDirectory::new(
+                &base_url,
+                env_number("USER_DIRECTORY_TIMEOUT_MS")
+                    .map_or(DEFAULT_USER_DIRECTORY_TIMEOUT, |ms| {
+                        Duration::from_millis(ms.into())
+                    }),
+            )
+            .expect("the user directory client should build"),
+        ),
+        Err(_) => Arc::new(InMemoryUserDirectory::new()),
+    };
+    let user_display_name_loader = UserDisplayNameLoader::new(user_directory);
+    // IDEMPOTENCY_TTL_HOURS (default 24) and IDEMPOTENCY_MAX_KEYS (default 10000): how long,
+    // and how many of, the Idempotency-Key headers of handled commands are remembered
+    let command_pipeline = CommandPipeline::new().with_idempotency(
+        IdempotencyMiddleware::new()
+            .with_ttl_ms(
+                env_number("IDEMPOTENCY_TTL_HOURS").map_or(DEFAULT_IDEMPOTENCY_TTL_MS, hours_ms),
+            )
+            .with_max_keys(
+                env_number("IDEMPOTENCY_MAX_KEYS")
+                    .map_or(DEFAULT_MAX_IDEMPOTENCY_KEYS, |max| max as usize),
+            ),
+    );
+
     let state = AppState {
         list_time_entries_handler,
+        time_entry_updates,
+        hours_balance_handler,
+        user_stats_handler,
+        time_entry_comments_handler,
+        time_entry_attachments_handler,
+        contract_event_store,
+        set_contract_handler,
+        utilization_handler,
         set_started_at_handler,
         set_ended_at_handler,
         set_time_entry_tags_handler,
+        set_hourly_rate_handler,
+        set_breaks_handler,
+        command_pipeline,
+        update_time_entry_handler,
+        delete_time_entry_handler,
+        approve_time_entry_handler,
+        reject_time_entry_handler,
+        add_time_entry_comment_handler,
+        add_time_entry_attachment_handler,
+        period_locks_handler,
+        period_lock_store,
         event_store,
         outbox,
+        forget_user_handler: ForgetUserHandler::new(key_store)
+            .with_eraser(Arc::new(time_entries_eraser)),
         tag_event_store,
         create_tag_handler,
         delete_tag_handler,
@@ -98,54 +1193,196 @@ async fn main() -> anyhow::Result<()> {
         set_tag_description_handler,
         list_tags_handler,
         tag_projection_store,
+        template_event_store,
+        save_template_handler,
+        list_templates_handler,
+        schedule_event_store,
+        define_schedule_handler,
+        cancel_schedule_handler,
+        list_schedules_handler,
+        user_display_name_loader,
+        api_key_store,
+        audit_store: InMemoryApiAuditStore::new(),
+        job_store: job_store.clone(),
+        bulk_delete_confirmations: BulkDeleteConfirmations::new(),
+        cold_storage: cold_storage.clone(),
+        attachment_storage: InMemoryAttachmentStorage::new(),
+        control_store,
+        consumer_lag: ConsumerLag::new(),
+        relay_metrics,
+        write_slo,
+        in_memory_capacity,
+        stream_naming,
+        feature_flags,
+        policy_store,
+        default_policies,
+        tuning: tuning.clone(),
     };
 
-    let http_router = shell_http::router(state.clone());
-
-    let schema: AppSchema = Schema::build(
-        QueryRoot::default(),
-        MutationRoot::default(),
-        EmptySubscription,
-    )
-    .data(state)
-    .finish();
+    // Exports, projection rebuilds and archival run as persisted jobs
+    let rebuild_projectors = projection_store
+        .partitions()
+        .into_iter()
+        .map(|(partition, partition_store)| {
+            ListTimeEntriesProjector::new(
+                partition_projector_name(partition),
+                partition_store,
+                state.event_store.clone(),
+                tech_tx.clone(),
+            )
+            .with_partition(partition)
+            .with_query_cache(list_time_entries_cache.clone())
+            .with_updates(state.time_entry_updates.clone())
+        })
+        .collect();
+    // TIME_ENTRY_EXPORT_PREFIX: cold storage prefix of the monthly Parquet exports
+    let export_prefix = std::env::var("TIME_ENTRY_EXPORT_PREFIX")
+        .unwrap_or_else(|_| DEFAULT_EXPORT_PREFIX.to_string());
+    let exporter = TimeEntryExporter::new(
+        projection_store.clone(),
+        cold_storage.clone(),
+        export_prefix,
+    );
+    let partition_stores = projection_store
+        .partitions()
+        .into_iter()
+        .map(|(_, partition_store)| partition_store)
+        .collect();
+    let mut job_runner = JobRunner::new(job_store)
+        .with_worker_updates(tuning.job_worker_updates())
+        .with_handler(archive_events_job::JOB_KIND, archive_events_job)
+        .with_handler(
+            user_data_export::JOB_KIND,
+            Arc::new(ExportUserDataJob::new(state.clone())),
+        )
+        .with_handler(
+            bulk_delete_job::JOB_KIND,
+            Arc::new(BulkDeleteTimeEntriesJob::new(state.clone())),
+        )
+        .with_handler(
+            compact_job::JOB_KIND,
+            Arc::new(CompactTimeEntriesJob::new(state.clone())),
+        )
+        .with_handler(
+            rebuild_job::JOB_KIND,
+            Arc::new(RebuildListTimeEntriesJob::new(rebuild_projectors)),
+        )
+        .with_handler(
+            export_job::JOB_KIND,
+            Arc::new(ExportTimeEntriesJob::new(exporter)),
+        )
+        .with_handler(
+            archive_job::JOB_KIND,
+            Arc::new(
+                ArchiveTimeEntriesJob::new(partition_stores, cold_storage)
+                    .with_query_cache(list_time_entries_cache.clone()),
+            ),
+        );
+    if let Some(every) = env_number("EVENT_ARCHIVE_EVERY_SECS").filter(|secs| *secs > 0) {
+        job_runner = job_runner.with_schedule(
+            archive_events_job::JOB_KIND,
+            serde_json::json!({}),
+            Duration::from_secs(every.into()),
+        );
+    }
+    job_runner::spawn(job_runner, Duration::from_secs(1));
 
-    let app = Router::new()
-        .merge(http_router)
-        .route("/gql", get(graphiql).post(graphql))
-        .layer(Extension(schema))
-        .layer(TraceLayer::new_for_http())
-        .layer(tower_http::cors::CorsLayer::permissive());
+    // GRAPHQL_WS_KEEPALIVE_SECS: close WebSocket connections silent (no ping) for this long;
+    // 0 keeps them open
+    let ws = match std::env::var("GRAPHQL_WS_KEEPALIVE_SECS") {
+        Ok(secs) => WsConfig {
+            keepalive_timeout: Some(Duration::from_secs(
+                secs.parse()
+                    .expect("GRAPHQL_WS_KEEPALIVE_SECS should be a number of seconds"),
+            ))
+            .filter(|timeout| !timeout.is_zero()),
+        },
+        Err(_) => WsConfig::default(),
+    };
+    // CHAOS_*: fault injection for rehearsing client retries; development and test only
+    let chaos_percent = |name: &str| {
+        std::env::var(name)
+            .ok()
+            .and_then(|percent| percent.parse::<u8>().ok())
+            .unwrap_or(0)
+            .min(100)
+    };
+    let chaos = ChaosConfig {
+        latency_percent: chaos_percent("CHAOS_LATENCY_PERCENT"),
+        latency: Duration::from_millis(
+            std::env::var("CHAOS_LATENCY_MS")
+                .ok()
+                .and_then(|ms| ms.parse().ok())
+                .unwrap_or(0),
+        ),
+        error_percent: chaos_percent("CHAOS_ERROR_PERCENT"),
+        reset_percent: chaos_percent("CHAOS_RESET_PERCENT"),
+    };
+    if chaos.is_active() {
+        tracing::warn!(?chaos, "chaos layer enabled");
+    }
+    // HTTP_MAX_BODY_BYTES / HTTP_TIMEOUT_SECS: per-request limits for most routes;
+    // HTTP_MAX_BULK_BODY_BYTES / HTTP_BULK_TIMEOUT_SECS: the same for bulk routes (/sync).
+    // HTTP_DEADLINE_MS / HTTP_BULK_DEADLINE_MS: how long backend calls may take before the
+    // request is answered with 504; callers may ask for less with `x-request-timeout-ms`.
+    // Body limits are capped at what the audit layer buffers.
+    let body_limit = |name: &str, default: usize| {
+        std::env::var(name)
+            .map(|bytes| {
+                bytes
+                    .parse()
+                    .expect("HTTP body limits should be a number of bytes")
+            })
+            .unwrap_or(default)
+            .min(MAX_AUDITED_BODY_BYTES)
+    };
+    let timeout = |name: &str, default: Duration| {
+        std::env::var(name)
+            .map(|secs| {
+                Duration::from_secs(
+                    secs.parse()
+                        .expect("HTTP timeouts should be a number of seconds"),
+                )
+            })
+            .unwrap_or(default)
+    };
+    let deadline = |name: &str, default: Duration| {
+        std::env::var(name)
+            .map(|ms| {
+                Duration::from_millis(
+                    ms.parse()
+                        .expect("HTTP deadlines should be a number of milliseconds"),
+                )
+            })
+            .unwrap_or(default)
+    };
+    let limits = RequestLimits {
+        standard: RouteLimits {
+            max_body_bytes: body_limit("HTTP_MAX_BODY_BYTES", RouteLimits::STANDARD.max_body_bytes),
+            deadline: deadline("HTTP_DEADLINE_MS", RouteLimits::STANDARD.deadline),
+            timeout: timeout("HTTP_TIMEOUT_SECS", RouteLimits::STANDARD.timeout),
+        },
+        bulk: RouteLimits {
+            max_body_bytes: body_limit(
+                "HTTP_MAX_BULK_BODY_BYTES",
+                RouteLimits::BULK.max_body_bytes,
+            ),
+            deadline: deadline("HTTP_BULK_DEADLINE_MS", RouteLimits::BULK.deadline),
+            timeout: timeout("HTTP_BULK_TIMEOUT_SECS", RouteLimits::BULK.timeout),
+        },
+    };
+    let app = RouterBuilder::new(state)
+        .with_ws(ws)
+        .with_limits(limits)
+        .with_chaos(chaos)
+        .with_rate_limit_updates(tuning.rate_limit_updates())
+        .build();
 
     let addr: SocketAddr = "[::]:8080".parse().unwrap();
-    tracing::info!("Server running: http://{}/*", addr);
+    tracing::info!("Server running: http://{}{}/*", addr, API_PREFIX);
     tracing::info!("GraphQL endpoint: http://{}/gql", addr);
+    tracing::info!("GraphQL subscriptions: ws://{}/gql/ws", addr);
     let listener = tokio::net::TcpListener::bind(addr).await.unwrap();
     axum::serve(listener, app).await?;
     Ok(())
 }
-
-async fn graphql(
-    Extension(schema): Extension<AppSchema>,
-    headers: HeaderMap,
-    req: GraphQLRequest,
-) -> GraphQLResponse {
-    let user_id = headers
-        .get("x-user-id")
-        .and_then(|v| v.to_str().ok())
-        .map(str::to_string);
-    let tenant_id = headers
-        .get("x-tenant-id")
-        .and_then(|v| v.to_str().ok())
-        .map(str::to_string);
-    let mut inner = req.into_inner();
-    if let (Some(user_id), Some(tenant_id)) = (user_id, tenant_id) {
-        inner = inner.data(RequestContext { user_id, tenant_id });
-    }
-    schema.execute(inner).await.into()
-}
-
-async fn graphiql() -> axum::response::Html<String> {
-    use async_graphql::http::GraphiQLSource;
-    axum::response::Html(GraphiQLSource::build().endpoint("/gql").finish())
-}
@@ -0,0 +1,117 @@
+// Process-wide metrics in the Prometheus text format, for scraping. `/metrics` gathers what
+// the rest of the shell tracks: broker consumer lag, the write path's service level
+// indicators, the in-memory stores' capacity, the command bus outcomes and the outbox relay's
+// stats.
+
+use axum::{
+    extract::State,
+    http::header,
+    response::{IntoResponse, Response},
+};
+use chrono::Utc;
+
+use crate::shell::outbox_stats::OutboxStats;
+use crate::shell::state::AppState;
+
+/// GET /metrics — every metric the process keeps. The outbox stats are left out while the
+/// outbox is unreachable.
+pub async fn handle(State(state): State<AppState>) -> Response {
+    let now = Utc::now().timestamp_millis();
+    let outbox_stats = OutboxStats::collect(&state, now)
+        .await
+        .map(|stats| stats.render())
+        .unwrap_or_default();
+    (
+        [(header::CONTENT_TYPE, "text/plain; version=0.0.4")],
+        state.consumer_lag.render(now)
+            + &state.write_slo.render(now)
+            + &state.in_memory_capacity.render()
+            + &state.command_pipeline.metrics().render()
+            + &outbox_stats,
+    )
+        .into_response()
+}
+
+#[cfg(test)]
+mod metrics_tests {
+    use super::*;
+    use crate::shared::infrastructure::message_broker::consumer_lag::PartitionOffsets;
+    use crate::tests::fixtures::tags::make_test_app_state;
+    use axum::{
+        Router,
+        body::Body,
+        http::{Request, StatusCode},
+        routing::get,
+    };
+    use http_body_util::BodyExt;
+    use rstest::rstest;
+    use tower::ServiceExt;
+
+    fn offsets(partition: u32, committed_offset: i64) -> PartitionOffsets {
+        PartitionOffsets {
+            topic: "time-entries.v1".to_string(),
+            partition,
+            head_offset: 10,
+            committed_offset: Some(committed_offset),
+        }
+    }
+
+    /// Partition 0 stopped committing an hour ago, partition 1 is caught up.
+    fn make_state() -> AppState {
+        let state = make_test_app_state();
+        let an_hour_ago = Utc::now().timestamp_millis() - 60 * 60 * 1000;
+        state
+            .consumer_lag
+            .record("billing", vec![offsets(0, 4), offsets(1, 10)], an_hour_ago);
+        state
+    }
+
+    async fn scrape(state: AppState) -> (StatusCode, String) {
+        let response = Router::new()
+            .route("/metrics", get(handle))
+            .with_state(state)
+            .oneshot(Request::get("/metrics").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        let status = response.status();
+        let bytes = response.into_body().collect().await.unwrap().to_bytes();
+        (status, String::from_utf8(bytes.to_vec()).unwrap())
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_expose_the_consumer_lag() {
+        let (status, body) = scrape(make_state()).await;
+
+        assert_eq!(status, StatusCode::OK);
+        assert!(body.contains(
+            r#"consumer_lag_messages{consumer="billing",topic="time-entries.v1",partition="0"} 6"#
+        ));
+        assert!(body.contains(
+            r#"consumer_stalled{consumer="billing",topic="time-entries.v1",partition="1"} 0"#
+        ));
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_expose_the_write_slo() {
+        let state = make_state();
+        state
+            .write_slo
+            .record("set_started_at", false, 20, Utc::now().timestamp_millis());
+
+        let (_, body) = scrape(state).await;
+
+        assert!(
+            body.contains(r#"slo_availability_ratio{use_case="set_started_at",window="5m"} 1"#)
+        );
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_expose_the_outbox_stats() {
+        let (_, body) = scrape(make_state()).await;
+
+        assert!(body.contains(r#"outbox_backlog_rows{topic="time-entries.v1"} 0"#));
+    }
+}
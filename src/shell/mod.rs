@@ -6,7 +6,21 @@
 // - Wire implementations into use case handlers.
 // - Spawn background workers (projector runner, intent relay runner, event relay runner).
 
+pub mod api_keys;
+pub mod audit;
+pub mod chaos;
+pub mod consumer_lag;
+pub mod control;
+pub mod feature_flags;
+pub mod forget_user;
 pub mod graphql;
 pub mod http;
+pub mod jobs;
+pub mod metrics;
+pub mod outbox_inspector;
+pub mod outbox_stats;
+pub mod policies;
 pub mod state;
+pub mod tuning;
+pub mod user_data_export;
 pub mod workers;
@@ -0,0 +1,214 @@
+// Admin view of the outbox rows, for seeing what a stuck relay keeps failing on. Payloads are
+// shown as they were enqueued, decompressed when the row was stored compressed; a payload
+// that does not decode is reported next to its row instead of failing the listing.
+
+use axum::{
+    Json,
+    extract::{Query, State},
+    http::StatusCode,
+    response::IntoResponse,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::shared::infrastructure::intent_outbox::compression::ContentEncoding;
+use crate::shared::infrastructure::intent_outbox::{OutboxRow, OutboxStatus};
+use crate::shared::infrastructure::request_context::RequestContext;
+use crate::shell::state::AppState;
+
+const DEFAULT_LIMIT: usize = 100;
+const MAX_LIMIT: usize = 1_000;
+
+#[derive(Deserialize)]
+pub struct OutboxRowParams {
+    pub status: Option<OutboxStatus>,
+    pub stream_id: Option<String>,
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct InspectedRow {
+    pub topic: String,
+    pub event_type: String,
+    pub event_version: i32,
+    pub stream_id: String,
+    pub stream_version: i64,
+    pub intent_no: u32,
+    pub occurred_at: i64,
+    /// How the row is stored; the payload below is always decoded.
+    pub content_encoding: Option<ContentEncoding>,
+    pub payload: Option<Value>,
+    pub decode_error: Option<String>,
+    pub status: OutboxStatus,
+    pub attempts: u32,
+    pub last_error: Option<String>,
+    pub published_at: Option<i64>,
+}
+
+impl From<OutboxRow> for InspectedRow {
+    fn from(row: OutboxRow) -> Self {
+        let (payload, decode_error) = match row.decoded_payload() {
+            Ok(payload) => (Some(payload), None),
+            Err(error) => (None, Some(error.to_string())),
+        };
+        Self {
+            topic: row.topic,
+            event_type: row.event_type,
+            event_version: row.event_version,
+            stream_id: row.stream_id,
+            stream_version: row.stream_version,
+            intent_no: row.intent_no,
+            occurred_at: row.occurred_at,
+            content_encoding: row.content_encoding,
+            payload,
+            decode_error,
+            status: row.status,
+            attempts: row.attempts,
+            last_error: row.last_error,
+            published_at: row.published_at,
+        }
+    }
+}
+
+/// GET /admin/outbox/rows — newest rows first, optionally of one status or stream. Admins only.
+pub async fn handle_list(
+    State(state): State<AppState>,
+    request_ctx: RequestContext,
+    Query(params): Query<OutboxRowParams>,
+) -> impl IntoResponse {
+    if !request_ctx.principal().can_administer() {
+        return StatusCode::FORBIDDEN.into_response();
+    }
+    let rows: Vec<InspectedRow> = state
+        .outbox
+        .rows()
+        .await
+        .into_iter()
+        .rev()
+        .filter(|row| params.status.is_none_or(|status| row.status == status))
+        .filter(|row| {
+            params
+                .stream_id
+                .as_ref()
+                .is_none_or(|stream_id| &row.stream_id == stream_id)
+        })
+        .take(params.limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT))
+        .map(InspectedRow::from)
+        .collect();
+    Json(rows).into_response()
+}
+
+#[cfg(test)]
+mod outbox_inspector_tests {
+    use super::*;
+    use crate::shared::infrastructure::intent_outbox::DomainOutbox;
+    use crate::shared::infrastructure::intent_outbox::compression::{
+        CompressingOutbox, PayloadCompression,
+    };
+    use crate::tests::fixtures::tags::make_test_app_state;
+    use axum::{Router, body::Body, http::Request, routing::get};
+    use http_body_util::BodyExt;
+    use rstest::rstest;
+    use serde_json::json;
+    use tower::ServiceExt;
+
+    fn row(stream_version: i64, payload: Value) -> OutboxRow {
+        OutboxRow {
+            topic: "time-entries.v1".to_string(),
+            event_type: "TimeEntryRegistered".to_string(),
+            event_version: 1,
+            stream_id: "TimeEntry-te-1".to_string(),
+            stream_version,
+            intent_no: 0,
+            occurred_at: 1_000,
+            payload,
+            content_encoding: None,
+            status: OutboxStatus::Pending,
+            attempts: 0,
+            last_error: None,
+            published_at: None,
+        }
+    }
+
+    async fn list(state: AppState, uri: &str, role: &str) -> (StatusCode, Value) {
+        let response = Router::new()
+            .route("/admin/outbox/rows", get(handle_list))
+            .with_state(state)
+            .oneshot(
+                Request::get(uri)
+                    .header("x-user-id", "admin-1")
+                    .header("x-tenant-id", "tenant-test")
+                    .header("x-user-role", role)
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        let status = response.status();
+        let bytes = response.into_body().collect().await.unwrap().to_bytes();
+        (
+            status,
+            serde_json::from_slice(&bytes).unwrap_or(Value::Null),
+        )
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_show_compressed_payloads_decoded() {
+        let state = make_test_app_state();
+        let description = "standup notes ".repeat(50);
+        CompressingOutbox::new(
+            state.outbox.clone(),
+            PayloadCompression::new(ContentEncoding::Zstd).with_threshold_bytes(100),
+        )
+        .enqueue(row(1, json!({ "description": description })))
+        .await
+        .unwrap();
+        state
+            .outbox
+            .enqueue(OutboxRow {
+                content_encoding: Some(ContentEncoding::Gzip),
+                ..row(2, Value::String("bm90IGd6aXA=".to_string()))
+            })
+            .await
+            .unwrap();
+
+        let (status, body) = list(state, "/admin/outbox/rows", "admin").await;
+
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body[0]["stream_version"], 2);
+        assert_eq!(body[0]["payload"], Value::Null);
+        assert!(body[0]["decode_error"].as_str().unwrap().contains("gzip"));
+        assert_eq!(body[1]["content_encoding"], "zstd");
+        assert_eq!(body[1]["payload"], json!({ "description": description }));
+        assert_eq!(body[1]["decode_error"], Value::Null);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_filter_and_limit_rows() {
+        let state = make_test_app_state();
+        for stream_version in 1..=3 {
+            state
+                .outbox
+                .enqueue(row(stream_version, json!({})))
+                .await
+                .unwrap();
+        }
+
+        let (_, limited) = list(state.clone(), "/admin/outbox/rows?limit=2", "admin").await;
+        let (_, published) = list(state, "/admin/outbox/rows?status=Published", "admin").await;
+
+        assert_eq!(limited.as_array().unwrap().len(), 2);
+        assert_eq!(limited[0]["stream_version"], 3);
+        assert_eq!(published, json!([]));
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_be_reserved_for_admins() {
+        let (status, _) = list(make_test_app_state(), "/admin/outbox/rows", "employee").await;
+
+        assert_eq!(status, StatusCode::FORBIDDEN);
+    }
+}
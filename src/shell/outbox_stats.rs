@@ -0,0 +1,243 @@
+// Health of the outbox relay, for status pages that read JSON rather than scrape metrics.
+// `/internal/outbox/stats` answers with the backlog of the relayed topic, how long its oldest
+// row has waited, the relay's publish rate and its failures; `/metrics` renders the same.
+
+use axum::{
+    Json,
+    extract::State,
+    http::StatusCode,
+    response::{IntoResponse, Response},
+};
+use chrono::Utc;
+use serde::Serialize;
+use std::fmt::Write;
+
+use crate::modules::time_entries::adapters::outbound::intent_outbox::OUTBOX_TOPIC;
+use crate::shared::infrastructure::intent_outbox::{OutboxError, OutboxRelaySource};
+use crate::shell::state::AppState;
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct OutboxStats {
+    pub topic: String,
+    /// Rows not yet published, including the ones awaiting a retry.
+    pub backlog: u64,
+    /// How long the oldest unpublished row has waited; `None` without a backlog.
+    pub oldest_pending_age_ms: Option<i64>,
+    /// Rows published per second over the last five minutes.
+    pub publish_rate_per_sec: f64,
+    pub published_total: u64,
+    /// Unpublished rows whose last attempt failed.
+    pub failed_rows: u64,
+    /// Relay runs that failed since the service started.
+    pub relay_failures_total: u64,
+}
+
+impl OutboxStats {
+    pub async fn collect(state: &AppState, now: i64) -> Result<Self, OutboxError> {
+        let backlog = state.outbox.backlog(OUTBOX_TOPIC).await?;
+        Ok(Self {
+            topic: OUTBOX_TOPIC.to_string(),
+            backlog: backlog.unpublished,
+            oldest_pending_age_ms: backlog.oldest_unpublished_at.map(|at| (now - at).max(0)),
+            publish_rate_per_sec: state.relay_metrics.publish_rate(now),
+            published_total: state.relay_metrics.delivered(),
+            failed_rows: backlog.failed,
+            relay_failures_total: state.relay_metrics.failures(),
+        })
+    }
+
+    /// The stats in the Prometheus text format.
+    pub fn render(&self) -> String {
+        let topic = &self.topic;
+        let mut out = String::new();
+        let mut metric = |name: &str, help: &str, kind: &str, value: String| {
+            let _ = writeln!(out, "# HELP {name} {help}");
+            let _ = writeln!(out, "# TYPE {name} {kind}");
+            let _ = writeln!(out, "{name}{{topic=\"{topic}\"}} {value}");
+        };
+        metric(
+            "outbox_backlog_rows",
+            "Rows not yet published.",
+            "gauge",
+            self.backlog.to_string(),
+        );
+        metric(
+            "outbox_oldest_pending_age_seconds",
+            "How long the oldest unpublished row has waited.",
+            "gauge",
+            (self.oldest_pending_age_ms.unwrap_or(0) as f64 / 1000.0).to_string(),
+        );
+        metric(
+            "outbox_publish_rate",
+            "Rows published per second over the last five minutes.",
+            "gauge",
+            self.publish_rate_per_sec.to_string(),
+        );
+        metric(
+            "outbox_published_total",
+            "Rows published since the service started.",
+            "counter",
+            self.published_total.to_string(),
+        );
+        metric(
+            "outbox_failed_rows",
+            "Unpublished rows whose last attempt failed.",
+            "gauge",
+            self.failed_rows.to_string(),
+        );
+        metric(
+            "outbox_relay_failures_total",
+            "Relay runs that failed since the service started.",
+            "counter",
+            self.relay_failures_total.to_string(),
+        );
+        out
+    }
+}
+
+/// GET /internal/outbox/stats — the outbox relay's backlog, rate and failures as JSON.
+pub async fn handle(State(state): State<AppState>) -> Response {
+    match OutboxStats::collect(&state, Utc::now().timestamp_millis()).await {
+        Ok(stats) => Json(stats).into_response(),
+        Err(error) => {
+            tracing::warn!(%error, "outbox stats unavailable");
+            StatusCode::SERVICE_UNAVAILABLE.into_response()
+        }
+    }
+}
+
+#[cfg(test)]
+mod outbox_stats_tests {
+    use super::*;
+    use crate::shared::infrastructure::intent_outbox::{DomainOutbox, OutboxRow, OutboxStatus};
+    use crate::tests::fixtures::tags::make_test_app_state;
+    use axum::{Router, body::Body, http::Request, routing::get};
+    use http_body_util::BodyExt;
+    use rstest::rstest;
+    use serde_json::{Value, json};
+    use tower::ServiceExt;
+
+    fn row(stream_version: i64, occurred_at: i64) -> OutboxRow {
+        OutboxRow {
+            topic: OUTBOX_TOPIC.to_string(),
+            event_type: "TimeEntryRegistered".to_string(),
+            event_version: 1,
+            stream_id: "TimeEntry-te-1".to_string(),
+            stream_version,
+            intent_no: 0,
+            occurred_at,
+            payload: Value::Null,
+            content_encoding: None,
+            status: OutboxStatus::Pending,
+            attempts: 0,
+            last_error: None,
+            published_at: None,
+        }
+    }
+
+    /// Three rows: one published, one that failed and one pending.
+    async fn make_state(now: i64) -> AppState {
+        let state = make_test_app_state();
+        for (version, occurred_at) in [(1, now - 90_000), (2, now - 60_000), (3, now - 1_000)] {
+            state
+                .outbox
+                .enqueue(row(version, occurred_at))
+                .await
+                .unwrap();
+        }
+        let rows = state.outbox.claim_batch(OUTBOX_TOPIC, 2).await.unwrap();
+        state.outbox.mark_published(&rows[..1], now).await.unwrap();
+        state
+            .outbox
+            .mark_failed(&rows[1..], "broker down")
+            .await
+            .unwrap();
+        state
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_collect_the_backlog_and_its_oldest_row() {
+        let now = 1_000_000;
+        let stats = OutboxStats::collect(&make_state(now).await, now)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            stats,
+            OutboxStats {
+                topic: OUTBOX_TOPIC.to_string(),
+                backlog: 2,
+                oldest_pending_age_ms: Some(60_000),
+                publish_rate_per_sec: 0.0,
+                published_total: 0,
+                failed_rows: 1,
+                relay_failures_total: 0,
+            }
+        );
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_serve_the_stats_as_json() {
+        let state = make_test_app_state();
+        state.outbox.enqueue(row(1, 0)).await.unwrap();
+        let app = Router::new()
+            .route("/internal/outbox/stats", get(handle))
+            .with_state(state);
+
+        let response = app
+            .oneshot(
+                Request::get("/internal/outbox/stats")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let bytes = response.into_body().collect().await.unwrap().to_bytes();
+        let json: Value = serde_json::from_slice(&bytes).unwrap();
+        assert_eq!(json["topic"], json!(OUTBOX_TOPIC));
+        assert_eq!(json["backlog"], json!(1));
+        assert!(json["oldest_pending_age_ms"].as_i64().unwrap() > 0);
+        assert_eq!(json["failed_rows"], json!(0));
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_answer_503_when_the_outbox_is_offline() {
+        let state = make_test_app_state();
+        state.outbox.toggle_offline();
+
+        let response = Router::new()
+            .route("/internal/outbox/stats", get(handle))
+            .with_state(state)
+            .oneshot(
+                Request::get("/internal/outbox/stats")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_render_the_stats_for_scraping() {
+        let now = 1_000_000;
+        let rendered = OutboxStats::collect(&make_state(now).await, now)
+            .await
+            .unwrap()
+            .render();
+
+        assert!(rendered.contains(r#"outbox_backlog_rows{topic="time-entries.v1"} 2"#));
+        assert!(
+            rendered.contains(r#"outbox_oldest_pending_age_seconds{topic="time-entries.v1"} 60"#)
+        );
+        assert!(rendered.contains(r#"outbox_failed_rows{topic="time-entries.v1"} 1"#));
+        assert!(rendered.contains("# TYPE outbox_relay_failures_total counter"));
+    }
+}
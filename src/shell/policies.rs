@@ -0,0 +1,363 @@
+// Per-tenant domain policies. Admins set the tag limit, maximum entry duration, lock period
+// and rounding of their own tenant; the time entry handlers read them on every command, so a
+// change applies to the next one. Tenants without policies of their own run under the
+// deployment's defaults, and resetting returns a tenant to them.
+
+use async_graphql::{Context, Enum, InputObject, Object, Result as GqlResult, SimpleObject};
+use chrono::Utc;
+
+use crate::modules::time_entries::core::policies::{
+    Policies, ROUNDING_INCREMENTS, Rounding, RoundingMode,
+};
+use crate::shared::infrastructure::policy_store::{PolicyStore, TenantPolicies};
+use crate::shared::infrastructure::request_context::RequestContext;
+use crate::shell::state::AppState;
+
+#[derive(Enum, Copy, Clone, Eq, PartialEq)]
+#[graphql(name = "RoundingMode")]
+pub enum GqlRoundingMode {
+    Nearest,
+    Up,
+    Down,
+}
+
+impl From<RoundingMode> for GqlRoundingMode {
+    fn from(mode: RoundingMode) -> Self {
+        match mode {
+            RoundingMode::Nearest => Self::Nearest,
+            RoundingMode::Up => Self::Up,
+            RoundingMode::Down => Self::Down,
+        }
+    }
+}
+
+impl From<GqlRoundingMode> for RoundingMode {
+    fn from(mode: GqlRoundingMode) -> Self {
+        match mode {
+            GqlRoundingMode::Nearest => Self::Nearest,
+            GqlRoundingMode::Up => Self::Up,
+            GqlRoundingMode::Down => Self::Down,
+        }
+    }
+}
+
+#[derive(SimpleObject, Clone)]
+#[graphql(name = "TenantPolicies")]
+pub struct GqlTenantPolicies {
+    pub tenant_id: String,
+    /// The most tags an entry may carry; null for no limit.
+    pub max_tags: Option<i32>,
+    /// The longest a finished entry may be where the `max_entry_duration` rule is rolled out.
+    pub max_entry_duration_minutes: i32,
+    /// Entries that ended more than this many days ago can no longer be changed; null keeps
+    /// them open.
+    pub lock_after_days: Option<i32>,
+    /// Starts and ends are rounded to this many minutes; null keeps them as given.
+    pub round_to_minutes: Option<i32>,
+    pub rounding_mode: Option<GqlRoundingMode>,
+    /// False while the tenant runs under the deployment's defaults.
+    pub customized: bool,
+    pub updated_at: Option<i64>,
+    pub updated_by: Option<String>,
+}
+
+impl GqlTenantPolicies {
+    fn new(tenant_id: &str, policies: Policies, stored: Option<&TenantPolicies>) -> Self {
+        Self {
+            tenant_id: tenant_id.to_string(),
+            max_tags: policies.max_tags.map(|max| max as i32),
+            max_entry_duration_minutes: (policies.max_entry_duration_ms / 60_000) as i32,
+            lock_after_days: policies.lock_after_days.map(|days| days as i32),
+            round_to_minutes: policies
+                .rounding
+                .map(|rounding| rounding.increment_minutes as i32),
+            rounding_mode: policies.rounding.map(|rounding| rounding.mode.into()),
+            customized: stored.is_some(),
+            updated_at: stored.map(|stored| stored.updated_at),
+            updated_by: stored.map(|stored| stored.updated_by.clone()),
+        }
+    }
+}
+
+/// Replaces the tenant's policies as a whole.
+#[derive(InputObject)]
+pub struct TenantPoliciesInput {
+    /// Left out for no limit.
+    pub max_tags: Option<i32>,
+    /// Left out for the deployment's default.
+    pub max_entry_duration_minutes: Option<i32>,
+    /// Left out to keep entries open.
+    pub lock_after_days: Option<i32>,
+    /// 5, 15 or 30; left out to keep times as given.
+    pub round_to_minutes: Option<i32>,
+    /// Defaults to `NEAREST`.
+    pub rounding_mode: Option<GqlRoundingMode>,
+}
+
+impl TenantPoliciesInput {
+    fn into_policies(self, defaults: Policies) -> Result<Policies, String> {
+        let at_least = |value: Option<i32>, min: i32, name: &str| match value {
+            Some(value) if value < min => Err(format!("{name} must be at least {min}")),
+            value => Ok(value.map(|value| value as u32)),
+        };
+        let max_tags = at_least(self.max_tags, 0, "maxTags")?;
+        let max_entry_duration_minutes = at_least(
+            self.max_entry_duration_minutes,
+            1,
+            "maxEntryDurationMinutes",
+        )?;
+        let lock_after_days = at_least(self.lock_after_days, 1, "lockAfterDays")?;
+        let round_to_minutes = match self.round_to_minutes {
+            Some(minutes) if !ROUNDING_INCREMENTS.contains(&(minutes as u32)) => {
+                return Err("roundToMinutes must be 5, 15 or 30".to_string());
+            }
+            minutes => minutes.map(|minutes| minutes as u32),
+        };
+        if round_to_minutes.is_none() && self.rounding_mode.is_some() {
+            return Err("roundingMode needs roundToMinutes".to_string());
+        }
+        Ok(Policies {
+            max_tags: max_tags.map(|max| max as usize),
+            max_entry_duration_ms: max_entry_duration_minutes
+                .map_or(defaults.max_entry_duration_ms, |minutes| {
+                    i64::from(minutes) * 60_000
+                }),
+            lock_after_days,
+            rounding: round_to_minutes.map(|increment_minutes| Rounding {
+                increment_minutes,
+                mode: self
+                    .rounding_mode
+                    .map_or(RoundingMode::Nearest, RoundingMode::from),
+            }),
+        })
+    }
+}
+
+fn caller<'a>(context: &'a Context<'_>) -> GqlResult<&'a RequestContext> {
+    context
+        .data::<RequestContext>()
+        .map_err(|_| async_graphql::Error::new("Unauthorized"))
+}
+
+fn admin<'a>(context: &'a Context<'_>) -> GqlResult<&'a RequestContext> {
+    let req_ctx = caller(context)?;
+    if !req_ctx.principal().can_administer() {
+        return Err(async_graphql::Error::new("Forbidden"));
+    }
+    Ok(req_ctx)
+}
+
+async fn current(state: &AppState, tenant_id: &str) -> GqlResult<GqlTenantPolicies> {
+    let stored = state
+        .policy_store
+        .get(tenant_id)
+        .await
+        .map_err(|e| async_graphql::Error::new(e.to_string()))?;
+    let policies = stored
+        .as_ref()
+        .map_or(state.default_policies, |stored| stored.policies);
+    Ok(GqlTenantPolicies::new(tenant_id, policies, stored.as_ref()))
+}
+
+#[derive(Default)]
+pub struct PoliciesMutation;
+
+#[Object]
+impl PoliciesMutation {
+    /// Replaces the policies of the caller's tenant; the next command is held to them.
+    /// Admins only.
+    async fn set_tenant_policies(
+        &self,
+        context: &Context<'_>,
+        input: TenantPoliciesInput,
+    ) -> GqlResult<GqlTenantPolicies> {
+        let req_ctx = admin(context)?;
+        let state = context.data_unchecked::<AppState>();
+        let stored = TenantPolicies {
+            tenant_id: req_ctx.tenant_id.clone(),
+            policies: input
+                .into_policies(state.default_policies)
+                .map_err(async_graphql::Error::new)?,
+            updated_at: Utc::now().timestamp_millis(),
+            updated_by: req_ctx.user_id.clone(),
+        };
+        state
+            .policy_store
+            .put(stored.clone())
+            .await
+            .map_err(|e| async_graphql::Error::new(e.to_string()))?;
+        Ok(GqlTenantPolicies::new(
+            &stored.tenant_id,
+            stored.policies,
+            Some(&stored),
+        ))
+    }
+
+    /// Returns the caller's tenant to the deployment's default policies. Admins only.
+    async fn reset_tenant_policies(&self, context: &Context<'_>) -> GqlResult<GqlTenantPolicies> {
+        let req_ctx = admin(context)?;
+        let state = context.data_unchecked::<AppState>();
+        state
+            .policy_store
+            .remove(&req_ctx.tenant_id)
+            .await
+            .map_err(|e| async_graphql::Error::new(e.to_string()))?;
+        Ok(GqlTenantPolicies::new(
+            &req_ctx.tenant_id,
+            state.default_policies,
+            None,
+        ))
+    }
+}
+
+#[derive(Default)]
+pub struct PoliciesQuery;
+
+#[Object]
+impl PoliciesQuery {
+    /// The policies the caller's tenant runs under, so clients can apply the same limits
+    /// before submitting.
+    async fn tenant_policies(&self, context: &Context<'_>) -> GqlResult<GqlTenantPolicies> {
+        let req_ctx = caller(context)?;
+        current(context.data_unchecked::<AppState>(), &req_ctx.tenant_id).await
+    }
+}
+
+#[cfg(test)]
+mod policies_tests {
+    use async_graphql::{EmptySubscription, Schema};
+    use rstest::rstest;
+
+    use crate::modules::time_entries::core::policies::{Policies, Rounding, RoundingMode};
+    use crate::shared::auth::rbac::Role;
+    use crate::shared::infrastructure::policy_store::PolicyStore;
+    use crate::shared::infrastructure::request_context::RequestContext;
+    use crate::shell::graphql::{MutationRoot, QueryRoot};
+    use crate::shell::state::AppState;
+    use crate::tests::fixtures::tags::make_test_app_state;
+
+    async fn execute(state: &AppState, query: &str, role: Role) -> async_graphql::Response {
+        Schema::build(
+            QueryRoot::default(),
+            MutationRoot::default(),
+            EmptySubscription,
+        )
+        .data(state.clone())
+        .finish()
+        .execute(async_graphql::Request::new(query).data(RequestContext {
+            user_id: "admin-1".to_string(),
+            tenant_id: "tenant-test".to_string(),
+            role,
+            scope: Default::default(),
+        }))
+        .await
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_store_the_policies_of_the_admins_tenant() {
+        let state = make_test_app_state();
+
+        let result = execute(
+            &state,
+            "mutation { setTenantPolicies(input: { maxTags: 3, lockAfterDays: 7, \
+             roundToMinutes: 15, roundingMode: UP }) \
+             { tenantId maxTags maxEntryDurationMinutes roundingMode customized updatedBy } }",
+            Role::Admin,
+        )
+        .await;
+
+        assert!(result.errors.is_empty(), "{:?}", result.errors);
+        assert_eq!(
+            result.data.to_string(),
+            "{setTenantPolicies: {tenantId: \"tenant-test\", maxTags: 3, \
+             maxEntryDurationMinutes: 1440, roundingMode: UP, customized: true, \
+             updatedBy: \"admin-1\"}}"
+        );
+        assert_eq!(
+            state
+                .policy_store
+                .get("tenant-test")
+                .await
+                .unwrap()
+                .unwrap()
+                .policies,
+            Policies {
+                max_tags: Some(3),
+                lock_after_days: Some(7),
+                rounding: Some(Rounding {
+                    increment_minutes: 15,
+                    mode: RoundingMode::Up,
+                }),
+                ..Policies::default()
+            }
+        );
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_return_to_the_defaults_on_reset() {
+        let state = make_test_app_state();
+        execute(
+            &state,
+            "mutation { setTenantPolicies(input: { maxTags: 3 }) { customized } }",
+            Role::Admin,
+        )
+        .await;
+
+        let reset = execute(
+            &state,
+            "mutation { resetTenantPolicies { maxTags customized } }",
+            Role::Admin,
+        )
+        .await;
+        let read = execute(
+            &state,
+            "{ tenantPolicies { maxTags lockAfterDays customized } }",
+            Role::Employee,
+        )
+        .await;
+
+        assert_eq!(
+            reset.data.to_string(),
+            "{resetTenantPolicies: {maxTags: null, customized: false}}"
+        );
+        assert_eq!(
+            read.data.to_string(),
+            "{tenantPolicies: {maxTags: null, lockAfterDays: null, customized: false}}"
+        );
+    }
+
+    #[rstest]
+    #[case::tags("maxTags: -1", "maxTags must be at least 0")]
+    #[case::duration(
+        "maxEntryDurationMinutes: 0",
+        "maxEntryDurationMinutes must be at least 1"
+    )]
+    #[case::lock("lockAfterDays: 0", "lockAfterDays must be at least 1")]
+    #[case::rounding("roundToMinutes: 10", "roundToMinutes must be 5, 15 or 30")]
+    #[case::negative_rounding("roundToMinutes: -15", "roundToMinutes must be 5, 15 or 30")]
+    #[case::mode_alone("roundingMode: DOWN", "roundingMode needs roundToMinutes")]
+    #[tokio::test]
+    async fn it_should_reject_invalid_policies(#[case] input: &str, #[case] expected: &str) {
+        let state = make_test_app_state();
+        let query =
+            format!("mutation {{ setTenantPolicies(input: {{ {input} }}) {{ customized }} }}");
+
+        let result = execute(&state, &query, Role::Admin).await;
+
+        assert_eq!(result.errors[0].message, expected);
+        assert_eq!(state.policy_store.get("tenant-test").await.unwrap(), None);
+    }
+
+    #[rstest]
+    #[case::set("mutation { setTenantPolicies(input: { maxTags: 1 }) { customized } }")]
+    #[case::reset("mutation { resetTenantPolicies { customized } }")]
+    #[tokio::test]
+    async fn it_should_be_reserved_for_admins(#[case] query: &str) {
+        let state = make_test_app_state();
+        let result = execute(&state, query, Role::Manager).await;
+        assert_eq!(result.errors[0].message, "Forbidden");
+        assert_eq!(state.policy_store.get("tenant-test").await.unwrap(), None);
+    }
+}
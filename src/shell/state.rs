@@ -1,3 +1,11 @@
+use crate::modules::contracts::core::events::ContractEvent;
+use crate::modules::contracts::use_cases::set_contract::handler::SetContractHandler;
+use crate::modules::contracts::use_cases::utilization::queries::UtilizationQueryHandler;
+use crate::modules::schedules::core::events::ScheduleEvent;
+use crate::modules::schedules::use_cases::cancel_schedule::handler::CancelScheduleHandler;
+use crate::modules::schedules::use_cases::define_schedule::handler::DefineScheduleHandler;
+use crate::modules::schedules::use_cases::list_schedules::projection::ListSchedulesState;
+use crate::modules::schedules::use_cases::list_schedules::queries::ListSchedulesQueryHandler;
 use crate::modules::tags::core::events::TagEvent;
 use crate::modules::tags::use_cases::create_tag::handler::CreateTagHandler;
 use crate::modules::tags::use_cases::delete_tag::handler::DeleteTagHandler;
@@ -6,28 +14,109 @@ use crate::modules::tags::use_cases::list_tags::queries::ListTagsQueryHandler;
 use crate::modules::tags::use_cases::set_tag_color::handler::SetTagColorHandler;
 use crate::modules::tags::use_cases::set_tag_description::handler::SetTagDescriptionHandler;
 use crate::modules::tags::use_cases::set_tag_name::handler::SetTagNameHandler;
+use crate::modules::templates::core::events::TemplateEvent;
+use crate::modules::templates::use_cases::list_templates::projection::ListTemplatesState;
+use crate::modules::templates::use_cases::list_templates::queries::ListTemplatesQueryHandler;
+use crate::modules::templates::use_cases::save_template::handler::SaveTemplateHandler;
 use crate::modules::time_entries::core::events::TimeEntryEvent;
+use crate::modules::time_entries::core::period_locks::PeriodLocksEvent;
+use crate::modules::time_entries::core::policies::Policies;
+use crate::modules::time_entries::use_cases::add_time_entry_attachment::handler::AddTimeEntryAttachmentHandler;
+use crate::modules::time_entries::use_cases::add_time_entry_comment::handler::AddTimeEntryCommentHandler;
+use crate::modules::time_entries::use_cases::approve_time_entry::handler::ApproveTimeEntryHandler;
+use crate::modules::time_entries::use_cases::bulk_delete_time_entries::confirmations::BulkDeleteConfirmations;
+use crate::modules::time_entries::use_cases::delete_time_entry::handler::DeleteTimeEntryHandler;
+use crate::modules::time_entries::use_cases::hours_balance::queries::HoursBalanceQueryHandler;
 use crate::modules::time_entries::use_cases::list_time_entries::projection::ListTimeEntriesState;
 use crate::modules::time_entries::use_cases::list_time_entries::queries::ListTimeEntriesQueryHandler;
+use crate::modules::time_entries::use_cases::list_time_entries::updates::TimeEntryUpdates;
+use crate::modules::time_entries::use_cases::period_locks::handler::PeriodLocksHandler;
+use crate::modules::time_entries::use_cases::reject_time_entry::handler::RejectTimeEntryHandler;
+use crate::modules::time_entries::use_cases::set_breaks::handler::SetBreaksHandler;
 use crate::modules::time_entries::use_cases::set_ended_at::handler::SetEndedAtHandler;
+use crate::modules::time_entries::use_cases::set_hourly_rate::handler::SetHourlyRateHandler;
 use crate::modules::time_entries::use_cases::set_started_at::handler::SetStartedAtHandler;
 use crate::modules::time_entries::use_cases::set_time_entry_tags::handler::SetTimeEntryTagsHandler;
+use crate::modules::time_entries::use_cases::time_entry_attachments::projection::TimeEntryAttachmentsState;
+use crate::modules::time_entries::use_cases::time_entry_attachments::queries::TimeEntryAttachmentsQueryHandler;
+use crate::modules::time_entries::use_cases::time_entry_comments::projection::TimeEntryCommentsState;
+use crate::modules::time_entries::use_cases::time_entry_comments::queries::TimeEntryCommentsQueryHandler;
+use crate::modules::time_entries::use_cases::update_time_entry::handler::UpdateTimeEntryHandler;
+use crate::modules::time_entries::use_cases::user_stats::projection::UserStatsState;
+use crate::modules::time_entries::use_cases::user_stats::queries::UserStatsQueryHandler;
+use crate::shared::application::command_bus::pipeline::CommandPipeline;
+use crate::shared::application::forget_user::ForgetUserHandler;
+use crate::shared::application::slo::WriteSlo;
+use crate::shared::core::stream_naming::StreamNaming;
+use crate::shared::infrastructure::api_audit_store::in_memory::InMemoryApiAuditStore;
+use crate::shared::infrastructure::api_key_store::in_memory::InMemoryApiKeyStore;
+use crate::shared::infrastructure::attachment_storage::in_memory::InMemoryAttachmentStorage;
+use crate::shared::infrastructure::capacity::CapacityGauges;
+use crate::shared::infrastructure::cold_storage::SharedColdStorage;
+use crate::shared::infrastructure::control_store::in_memory::InMemoryControlStore;
+use crate::shared::infrastructure::event_store::SharedEventLog;
 use crate::shared::infrastructure::event_store::in_memory::InMemoryEventStore;
+use crate::shared::infrastructure::feature_flags::in_memory::InMemoryFeatureFlags;
+use crate::shared::infrastructure::intent_outbox::compression::CompressingOutbox;
 use crate::shared::infrastructure::intent_outbox::in_memory::InMemoryDomainOutbox;
+use crate::shared::infrastructure::job_store::in_memory::InMemoryJobStore;
+use crate::shared::infrastructure::key_store::in_memory::InMemoryKeyStore;
+use crate::shared::infrastructure::message_broker::consumer_lag::ConsumerLag;
+use crate::shared::infrastructure::outbox_relay::RelayMetrics;
+use crate::shared::infrastructure::policy_store::in_memory::InMemoryPolicyStore;
 use crate::shared::infrastructure::projection_store::in_memory::InMemoryProjectionStore;
+use crate::shared::infrastructure::projection_store::partitioned::PartitionedProjectionStore;
+use crate::shared::infrastructure::user_directory::SharedUserDirectory;
+use crate::shared::infrastructure::user_directory::loader::UserDisplayNameLoader;
+use crate::shell::tuning::WorkerTuning;
+use std::sync::Arc;
+
+/// The time entry event log, with personal data encrypted per user at rest.
+pub type TimeEntryEventStore = SharedEventLog<TimeEntryEvent>;
+/// The outbox the time entry handlers enqueue to, compressing large payloads when configured.
+pub type TimeEntryOutbox = CompressingOutbox<InMemoryDomainOutbox>;
+
+/// List time entries read model, one in-memory store per projector partition.
+pub type ListTimeEntriesStore =
+    PartitionedProjectionStore<InMemoryProjectionStore<ListTimeEntriesState>>;
 
 #[derive(Clone)]
 pub struct AppState {
-    pub set_started_at_handler:
-        SetStartedAtHandler<InMemoryEventStore<TimeEntryEvent>, InMemoryDomainOutbox>,
-    pub set_ended_at_handler:
-        SetEndedAtHandler<InMemoryEventStore<TimeEntryEvent>, InMemoryDomainOutbox>,
-    pub set_time_entry_tags_handler:
-        SetTimeEntryTagsHandler<InMemoryEventStore<TimeEntryEvent>, InMemoryDomainOutbox>,
-    pub event_store: InMemoryEventStore<TimeEntryEvent>,
+    /// How time entry ids map to their streams, for every adapter that sends commands.
+    pub stream_naming: Arc<dyn StreamNaming>,
+    pub set_started_at_handler: SetStartedAtHandler<TimeEntryEventStore, TimeEntryOutbox>,
+    pub set_ended_at_handler: SetEndedAtHandler<TimeEntryEventStore, TimeEntryOutbox>,
+    pub set_time_entry_tags_handler: SetTimeEntryTagsHandler<TimeEntryEventStore, TimeEntryOutbox>,
+    pub set_hourly_rate_handler: SetHourlyRateHandler<TimeEntryEventStore, TimeEntryOutbox>,
+    pub set_breaks_handler: SetBreaksHandler<TimeEntryEventStore, TimeEntryOutbox>,
+    /// Logging, auth, idempotency, conflict retries and metrics for the REST command adapters.
+    pub command_pipeline: CommandPipeline,
+    pub update_time_entry_handler: UpdateTimeEntryHandler<TimeEntryEventStore, TimeEntryOutbox>,
+    pub delete_time_entry_handler: DeleteTimeEntryHandler<TimeEntryEventStore, TimeEntryOutbox>,
+    pub approve_time_entry_handler: ApproveTimeEntryHandler<TimeEntryEventStore, TimeEntryOutbox>,
+    pub reject_time_entry_handler: RejectTimeEntryHandler<TimeEntryEventStore, TimeEntryOutbox>,
+    pub add_time_entry_comment_handler:
+        AddTimeEntryCommentHandler<TimeEntryEventStore, TimeEntryOutbox>,
+    pub add_time_entry_attachment_handler:
+        AddTimeEntryAttachmentHandler<TimeEntryEventStore, TimeEntryOutbox>,
+    pub period_locks_handler: PeriodLocksHandler<InMemoryEventStore<PeriodLocksEvent>>,
+    pub period_lock_store: InMemoryEventStore<PeriodLocksEvent>,
+    pub event_store: TimeEntryEventStore,
     pub outbox: InMemoryDomainOutbox,
-    pub list_time_entries_handler:
-        ListTimeEntriesQueryHandler<InMemoryProjectionStore<ListTimeEntriesState>>,
+    /// Deletes a user's data key, leaving their personal data in `event_store` unreadable.
+    pub forget_user_handler: ForgetUserHandler<InMemoryKeyStore>,
+    pub list_time_entries_handler: ListTimeEntriesQueryHandler<ListTimeEntriesStore>,
+    pub time_entry_updates: TimeEntryUpdates,
+    pub hours_balance_handler: HoursBalanceQueryHandler<ListTimeEntriesStore>,
+    pub user_stats_handler: UserStatsQueryHandler<InMemoryProjectionStore<UserStatsState>>,
+    pub time_entry_comments_handler:
+        TimeEntryCommentsQueryHandler<InMemoryProjectionStore<TimeEntryCommentsState>>,
+    pub time_entry_attachments_handler:
+        TimeEntryAttachmentsQueryHandler<InMemoryProjectionStore<TimeEntryAttachmentsState>>,
+    pub contract_event_store: InMemoryEventStore<ContractEvent>,
+    pub set_contract_handler: SetContractHandler<InMemoryEventStore<ContractEvent>>,
+    pub utilization_handler:
+        UtilizationQueryHandler<InMemoryEventStore<ContractEvent>, ListTimeEntriesStore>,
     pub tag_event_store: InMemoryEventStore<TagEvent>,
     pub create_tag_handler: CreateTagHandler<InMemoryEventStore<TagEvent>>,
     pub delete_tag_handler: DeleteTagHandler<InMemoryEventStore<TagEvent>>,
@@ -36,4 +125,40 @@ pub struct AppState {
     pub set_tag_description_handler: SetTagDescriptionHandler<InMemoryEventStore<TagEvent>>,
     pub list_tags_handler: ListTagsQueryHandler<InMemoryProjectionStore<ListTagsState>>,
     pub tag_projection_store: InMemoryProjectionStore<ListTagsState>,
+    pub template_event_store: InMemoryEventStore<TemplateEvent>,
+    pub save_template_handler: SaveTemplateHandler<InMemoryEventStore<TemplateEvent>>,
+    pub list_templates_handler:
+        ListTemplatesQueryHandler<InMemoryProjectionStore<ListTemplatesState>>,
+    pub schedule_event_store: InMemoryEventStore<ScheduleEvent>,
+    pub define_schedule_handler: DefineScheduleHandler<InMemoryEventStore<ScheduleEvent>>,
+    pub cancel_schedule_handler: CancelScheduleHandler<InMemoryEventStore<ScheduleEvent>>,
+    pub list_schedules_handler:
+        ListSchedulesQueryHandler<InMemoryProjectionStore<ListSchedulesState>>,
+    pub user_display_name_loader: UserDisplayNameLoader<SharedUserDirectory>,
+    pub api_key_store: InMemoryApiKeyStore,
+    pub audit_store: InMemoryApiAuditStore,
+    pub job_store: InMemoryJobStore,
+    /// Bulk deletes an admin asked for, until they confirm them.
+    pub bulk_delete_confirmations: BulkDeleteConfirmations,
+    /// Archived time entries and export bundles, under separate key prefixes.
+    pub cold_storage: SharedColdStorage,
+    /// Files attached to time entries, uploaded and downloaded through pre-signed URLs.
+    pub attachment_storage: InMemoryAttachmentStorage,
+    /// Pauses of projectors and relays, which the workers poll.
+    pub control_store: InMemoryControlStore,
+    /// Lag of the broker consumers, as `consumer_lag_runner` last recorded it.
+    pub consumer_lag: ConsumerLag,
+    /// Totals and rate of the outbox relay, across the relays taking over from one another.
+    pub relay_metrics: RelayMetrics,
+    /// Service level indicators of the write path, which the register handlers record.
+    pub write_slo: WriteSlo,
+    /// What the in-memory stores hold, against their bounds.
+    pub in_memory_capacity: CapacityGauges,
+    /// Operators' per-tenant overrides of the rollout flags, over the configured ones.
+    pub feature_flags: InMemoryFeatureFlags,
+    /// Admins' per-tenant policies; tenants without their own run under `default_policies`.
+    pub policy_store: InMemoryPolicyStore,
+    pub default_policies: Policies,
+    /// Relay batching, job concurrency and rate limit, retunable at runtime.
+    pub tuning: WorkerTuning,
 }
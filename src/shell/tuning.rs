@@ -0,0 +1,306 @@
+// Live tuning of background workers. Admins change relay batching, job concurrency and the
+// rate limit without a restart; each value sits in a `watch` channel the worker it tunes
+// reads from. Tuning is per instance and lasts until the process restarts, when the
+// environment applies again.
+
+use async_graphql::{Context, InputObject, Object, Result as GqlResult, SimpleObject};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::watch;
+
+use crate::shared::infrastructure::outbox_relay::adaptive::AdaptiveBatchConfig;
+use crate::shared::infrastructure::request_context::RequestContext;
+use crate::shell::http::rate_limit::RateLimitConfig;
+use crate::shell::state::AppState;
+
+struct Channels {
+    relay: watch::Sender<AdaptiveBatchConfig>,
+    job_workers: watch::Sender<usize>,
+    rate_limit: watch::Sender<RateLimitConfig>,
+}
+
+/// The tunable parameters of this instance's workers; clones share the same channels.
+#[derive(Clone)]
+pub struct WorkerTuning {
+    channels: Arc<Channels>,
+}
+
+impl Default for WorkerTuning {
+    /// Default relay batching, 4 job workers and no rate limit.
+    fn default() -> Self {
+        Self::new(
+            AdaptiveBatchConfig::default(),
+            4,
+            RateLimitConfig {
+                requests: 0,
+                window: Duration::from_secs(60),
+            },
+        )
+    }
+}
+
+impl WorkerTuning {
+    /// `rate_limit` with zero `requests` leaves requests unlimited until tuned.
+    pub fn new(
+        relay: AdaptiveBatchConfig,
+        job_workers: usize,
+        rate_limit: RateLimitConfig,
+    ) -> Self {
+        Self {
+            channels: Arc::new(Channels {
+                relay: watch::channel(relay).0,
+                job_workers: watch::channel(job_workers).0,
+                rate_limit: watch::channel(rate_limit).0,
+            }),
+        }
+    }
+
+    pub fn relay(&self) -> AdaptiveBatchConfig {
+        self.channels.relay.borrow().clone()
+    }
+
+    pub fn relay_updates(&self) -> watch::Receiver<AdaptiveBatchConfig> {
+        self.channels.relay.subscribe()
+    }
+
+    pub fn job_workers(&self) -> usize {
+        *self.channels.job_workers.borrow()
+    }
+
+    pub fn job_worker_updates(&self) -> watch::Receiver<usize> {
+        self.channels.job_workers.subscribe()
+    }
+
+    pub fn rate_limit(&self) -> RateLimitConfig {
+        *self.channels.rate_limit.borrow()
+    }
+
+    pub fn rate_limit_updates(&self) -> watch::Receiver<RateLimitConfig> {
+        self.channels.rate_limit.subscribe()
+    }
+
+    /// Applies the fields `input` sets, or none of them when the result would be invalid.
+    fn update(&self, input: WorkerTuningInput) -> Result<(), String> {
+        let mut relay = self.relay();
+        let set = |field: &mut usize, value: Option<i32>, name: &str| match value {
+            Some(value) if value < 1 => Err(format!("{name} must be at least 1")),
+            Some(value) => {
+                *field = value as usize;
+                Ok(())
+            }
+            None => Ok(()),
+        };
+        set(&mut relay.min_batch, input.relay_min_batch, "relayMinBatch")?;
+        set(&mut relay.max_batch, input.relay_max_batch, "relayMaxBatch")?;
+        if relay.max_batch < relay.min_batch {
+            return Err("relayMaxBatch must not be below relayMinBatch".to_string());
+        }
+        if let Some(ms) = input.relay_min_interval_ms {
+            relay.min_interval = Duration::from_millis(ms.max(0) as u64);
+        }
+        if let Some(ms) = input.relay_max_interval_ms {
+            relay.max_interval = Duration::from_millis(ms.max(0) as u64);
+        }
+        if relay.max_interval < relay.min_interval {
+            return Err("relayMaxIntervalMs must not be below relayMinIntervalMs".to_string());
+        }
+        let mut job_workers = self.job_workers();
+        set(&mut job_workers, input.job_workers, "jobWorkers")?;
+        let mut rate_limit = self.rate_limit();
+        if let Some(requests) = input.rate_limit_per_minute {
+            if requests < 0 {
+                return Err("rateLimitPerMinute must not be negative".to_string());
+            }
+            rate_limit.requests = requests as u32;
+        }
+
+        self.channels.relay.send_if_modified(|current| {
+            let changed = *current != relay;
+            *current = relay;
+            changed
+        });
+        self.channels.job_workers.send_if_modified(|current| {
+            let changed = *current != job_workers;
+            *current = job_workers;
+            changed
+        });
+        self.channels.rate_limit.send_replace(rate_limit);
+        Ok(())
+    }
+}
+
+#[derive(SimpleObject, Clone)]
+#[graphql(name = "WorkerTuning")]
+pub struct GqlWorkerTuning {
+    pub relay_min_batch: i32,
+    pub relay_max_batch: i32,
+    pub relay_min_interval_ms: i32,
+    pub relay_max_interval_ms: i32,
+    pub job_workers: i32,
+    /// Requests each caller may make per minute; 0 when unlimited.
+    pub rate_limit_per_minute: i32,
+}
+
+impl From<&WorkerTuning> for GqlWorkerTuning {
+    fn from(tuning: &WorkerTuning) -> Self {
+        let relay = tuning.relay();
+        Self {
+            relay_min_batch: relay.min_batch as i32,
+            relay_max_batch: relay.max_batch as i32,
+            relay_min_interval_ms: relay.min_interval.as_millis() as i32,
+            relay_max_interval_ms: relay.max_interval.as_millis() as i32,
+            job_workers: tuning.job_workers() as i32,
+            rate_limit_per_minute: tuning.rate_limit().requests as i32,
+        }
+    }
+}
+
+/// Only the fields given change.
+#[derive(InputObject)]
+pub struct WorkerTuningInput {
+    pub relay_min_batch: Option<i32>,
+    pub relay_max_batch: Option<i32>,
+    pub relay_min_interval_ms: Option<i32>,
+    pub relay_max_interval_ms: Option<i32>,
+    pub job_workers: Option<i32>,
+    /// 0 turns the rate limit off.
+    pub rate_limit_per_minute: Option<i32>,
+}
+
+fn admin(context: &Context<'_>) -> GqlResult<()> {
+    let req_ctx = context
+        .data::<RequestContext>()
+        .map_err(|_| async_graphql::Error::new("Unauthorized"))?;
+    if !req_ctx.principal().can_administer() {
+        return Err(async_graphql::Error::new("Forbidden"));
+    }
+    Ok(())
+}
+
+#[derive(Default)]
+pub struct TuningMutation;
+
+#[Object]
+impl TuningMutation {
+    /// Retunes this instance's workers; the relay applies it before its next batch, the job
+    /// runner on its next tick and the rate limit on the next request. Admins only.
+    async fn update_worker_tuning(
+        &self,
+        context: &Context<'_>,
+        input: WorkerTuningInput,
+    ) -> GqlResult<GqlWorkerTuning> {
+        admin(context)?;
+        let tuning = &context.data_unchecked::<AppState>().tuning;
+        tuning.update(input).map_err(async_graphql::Error::new)?;
+        Ok(tuning.into())
+    }
+}
+
+#[derive(Default)]
+pub struct TuningQuery;
+
+#[Object]
+impl TuningQuery {
+    /// The tuning this instance's workers run with. Admins only.
+    async fn worker_tuning(&self, context: &Context<'_>) -> GqlResult<GqlWorkerTuning> {
+        admin(context)?;
+        Ok((&context.data_unchecked::<AppState>().tuning).into())
+    }
+}
+
+#[cfg(test)]
+mod tuning_tests {
+    use async_graphql::{EmptySubscription, Schema};
+    use rstest::rstest;
+
+    use crate::shared::auth::rbac::Role;
+    use crate::shared::infrastructure::outbox_relay::adaptive::AdaptiveBatchConfig;
+    use crate::shared::infrastructure::request_context::RequestContext;
+    use crate::shell::graphql::{MutationRoot, QueryRoot};
+    use crate::shell::state::AppState;
+    use crate::tests::fixtures::tags::make_test_app_state;
+
+    async fn execute(state: &AppState, query: &str, role: Role) -> async_graphql::Response {
+        Schema::build(
+            QueryRoot::default(),
+            MutationRoot::default(),
+            EmptySubscription,
+        )
+        .data(state.clone())
+        .finish()
+        .execute(async_graphql::Request::new(query).data(RequestContext {
+            user_id: "admin-1".to_string(),
+            tenant_id: "tenant-test".to_string(),
+            role,
+            scope: Default::default(),
+        }))
+        .await
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_push_updated_fields_to_the_workers() {
+        let state = make_test_app_state();
+        let mut relay = state.tuning.relay_updates();
+        let mut job_workers = state.tuning.job_worker_updates();
+        let rate_limit = state.tuning.rate_limit_updates();
+
+        let result = execute(
+            &state,
+            "mutation { updateWorkerTuning(input: { relayMaxBatch: 50, rateLimitPerMinute: 120 }) \
+             { relayMinBatch relayMaxBatch jobWorkers rateLimitPerMinute } }",
+            Role::Admin,
+        )
+        .await;
+
+        assert!(result.errors.is_empty(), "{:?}", result.errors);
+        assert_eq!(
+            result.data.to_string(),
+            "{updateWorkerTuning: {relayMinBatch: 1, relayMaxBatch: 50, jobWorkers: 4, rateLimitPerMinute: 120}}"
+        );
+        assert!(relay.has_changed().unwrap());
+        assert_eq!(relay.borrow_and_update().max_batch, 50);
+        assert!(!job_workers.has_changed().unwrap());
+        assert_eq!(rate_limit.borrow().requests, 120);
+        assert_eq!(*job_workers.borrow_and_update(), 4);
+    }
+
+    #[rstest]
+    #[case::batch_below_one("jobWorkers: 8, relayMinBatch: 0", "relayMinBatch must be at least 1")]
+    #[case::max_below_min("relayMinBatch: 600", "relayMaxBatch must not be below relayMinBatch")]
+    #[case::interval(
+        "jobWorkers: 8, relayMinIntervalMs: 9000",
+        "relayMaxIntervalMs must not be below relayMinIntervalMs"
+    )]
+    #[case::workers("relayMaxBatch: 20, jobWorkers: 0", "jobWorkers must be at least 1")]
+    #[case::rate_limit(
+        "jobWorkers: 8, rateLimitPerMinute: -1",
+        "rateLimitPerMinute must not be negative"
+    )]
+    #[tokio::test]
+    async fn it_should_reject_invalid_tuning_as_a_whole(
+        #[case] input: &str,
+        #[case] expected: &str,
+    ) {
+        let state = make_test_app_state();
+        let query =
+            format!("mutation {{ updateWorkerTuning(input: {{ {input} }}) {{ jobWorkers }} }}");
+
+        let result = execute(&state, &query, Role::Admin).await;
+
+        assert_eq!(result.errors[0].message, expected);
+        assert_eq!(state.tuning.relay(), AdaptiveBatchConfig::default());
+        assert_eq!(state.tuning.job_workers(), 4);
+    }
+
+    #[rstest]
+    #[case::update("mutation { updateWorkerTuning(input: { jobWorkers: 2 }) { jobWorkers } }")]
+    #[case::read("{ workerTuning { jobWorkers } }")]
+    #[tokio::test]
+    async fn it_should_be_reserved_for_admins(#[case] query: &str) {
+        let state = make_test_app_state();
+        let result = execute(&state, query, Role::Manager).await;
+        assert_eq!(result.errors[0].message, "Forbidden");
+        assert_eq!(state.tuning.job_workers(), 4);
+    }
+}
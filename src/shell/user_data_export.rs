@@ -0,0 +1,543 @@
+// GDPR access requests: everything the service holds about one user, as an NDJSON bundle.
+// The first line describes the export; every other line is one record tagged with its `kind`:
+// an event from the user's time entry and contract streams, a row of their time entry list,
+// or an audit record of a mutation they attempted.
+//
+// Small exports stream straight back from GET. For large users an admin queues an export
+// job with POST, polls it through the job API and downloads the bundle once it succeeded.
+// Bundles are kept in cold storage, so they survive restarts along with the job.
+
+use async_graphql::futures_util::stream;
+use async_trait::async_trait;
+use axum::{
+    Json,
+    body::{Body, Bytes},
+    extract::{Path, State},
+    http::{StatusCode, header},
+    response::{IntoResponse, Response},
+};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+use std::collections::BTreeSet;
+use std::convert::Infallible;
+use std::sync::Arc;
+
+use crate::modules::contracts::core::events::ContractEvent;
+use crate::modules::contracts::core::state::contract_stream_id;
+use crate::modules::time_entries::core::events::TimeEntryEvent;
+use crate::modules::time_entries::use_cases::list_time_entries::projection::TimeEntryRow;
+use crate::shared::application::jobs::{JobContext, JobHandler};
+use crate::shared::infrastructure::api_audit_store::{ApiAuditStore, AuditQuery, AuditRecord};
+use crate::shared::infrastructure::cold_storage::ColdStorage;
+use crate::shared::infrastructure::event_store::EventStore;
+use crate::shared::infrastructure::job_store::{Job, JobStatus, JobStore};
+use crate::shared::infrastructure::projection_store::ProjectionStore;
+use crate::shared::infrastructure::request_context::RequestContext;
+use crate::shell::jobs;
+use crate::shell::state::AppState;
+
+pub const JOB_KIND: &str = "user_data_export";
+
+const NDJSON: &str = "application/x-ndjson";
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExportUserData {
+    pub user_id: String,
+}
+
+/// One line of the bundle.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum BundleRecord {
+    Export {
+        user_id: String,
+        exported_at: i64,
+    },
+    TimeEntryEvent {
+        stream_id: String,
+        stream_version: i64,
+        event: TimeEntryEvent,
+    },
+    ContractEvent {
+        stream_id: String,
+        stream_version: i64,
+        event: ContractEvent,
+    },
+    TimeEntry {
+        row: TimeEntryRow,
+    },
+    AuditRecord {
+        record: AuditRecord,
+    },
+}
+
+/// Collects `command.user_id`'s data. Their time entry streams are the ones initiated for
+/// them; events appear in log order, list rows by start time, audit records newest first.
+pub async fn export_user_data(
+    state: &AppState,
+    command: ExportUserData,
+) -> anyhow::Result<Vec<BundleRecord>> {
+    let user_id = command.user_id;
+    let log = state.event_store.load_all_from(0).await?;
+    let owned: BTreeSet<&str> = log
+        .iter()
+        .filter_map(|stored| match &stored.event {
+            TimeEntryEvent::TimeEntryInitiatedV1(e) if e.user_id == user_id => {
+                Some(stored.stream_id.as_str())
+            }
+            _ => None,
+        })
+        .collect();
+    let contract_stream = contract_stream_id(&user_id);
+    let contract = state.contract_event_store.load(&contract_stream).await?;
+    let mut rows: Vec<TimeEntryRow> = state
+        .list_time_entries_handler
+        .store()
+        .state()
+        .await?
+        .unwrap_or_default()
+        .rows
+        .into_values()
+        .filter(|row| row.user_id == user_id)
+        .collect();
+    rows.sort_by(|a, b| (a.started_at, &a.time_entry_id).cmp(&(b.started_at, &b.time_entry_id)));
+    let audit_records = state
+        .audit_store
+        .list(&AuditQuery {
+            actor: Some(user_id.clone()),
+            limit: usize::MAX,
+        })
+        .await?;
+
+    let mut records = vec![BundleRecord::Export {
+        user_id,
+        exported_at: Utc::now().timestamp_millis(),
+    }];
+    records.extend(
+        log.iter()
+            .filter(|stored| owned.contains(stored.stream_id.as_str()))
+            .map(|stored| BundleRecord::TimeEntryEvent {
+                stream_id: stored.stream_id.clone(),
+                stream_version: stored.stream_version,
+                event: stored.event.clone(),
+            }),
+    );
+    records.extend(contract.events.into_iter().enumerate().map(|(i, event)| {
+        BundleRecord::ContractEvent {
+            stream_id: contract_stream.clone(),
+            stream_version: i as i64 + 1,
+            event,
+        }
+    }));
+    records.extend(rows.into_iter().map(|row| BundleRecord::TimeEntry { row }));
+    records.extend(
+        audit_records
+            .into_iter()
+            .map(|record| BundleRecord::AuditRecord { record }),
+    );
+    Ok(records)
+}
+
+fn bundle_lines(records: &[BundleRecord]) -> Vec<String> {
+    records
+        .iter()
+        .map(|record| serde_json::to_string(record).expect("bundle records serialize to JSON"))
+        .collect()
+}
+
+/// Streams `lines` one record per chunk, so large bundles are never copied into one body.
+fn ndjson(user_id: &str, lines: Arc<Vec<String>>) -> Response {
+    let chunks =
+        (0..lines.len()).map(move |i| Ok::<_, Infallible>(Bytes::from(format!("{}\n", lines[i]))));
+    (
+        [
+            (header::CONTENT_TYPE, NDJSON.to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"user-data-{user_id}.ndjson\""),
+            ),
+        ],
+        Body::from_stream(stream::iter(chunks)),
+    )
+        .into_response()
+}
+
+/// Where an export job leaves its bundle in cold storage.
+pub fn bundle_key(job_id: &str) -> String {
+    format!("user-data-exports/{job_id}.ndjson")
+}
+
+/// Runs export jobs: builds the bundle of the user in the payload and stores it under the
+/// job's `bundle_key`, so it outlives the process that built it. A retried job overwrites it.
+#[derive(Clone)]
+pub struct ExportUserDataJob {
+    state: AppState,
+}
+
+impl ExportUserDataJob {
+    pub fn new(state: AppState) -> Self {
+        Self { state }
+    }
+}
+
+#[async_trait]
+impl JobHandler for ExportUserDataJob {
+    async fn run(&self, job: &Job, ctx: &JobContext<'_>) -> anyhow::Result<Value> {
+        let command: ExportUserData = serde_json::from_value(job.payload.clone())?;
+        let user_id = command.user_id.clone();
+        let lines = bundle_lines(&export_user_data(&self.state, command).await?);
+        let records = lines.len();
+        let key = bundle_key(&job.job_id);
+        self.state.cold_storage.write_lines(&key, lines).await?;
+        ctx.report_progress(records as u64, Some(records as u64))
+            .await?;
+        Ok(json!({ "user_id": user_id, "key": key, "records": records }))
+    }
+}
+
+/// GET /admin/users/{user_id}/data-export — the user's bundle, built while the caller waits.
+/// Use the export jobs for users with a long history. Admins only.
+pub async fn handle_export(
+    State(state): State<AppState>,
+    request_ctx: RequestContext,
+    Path(user_id): Path<String>,
+) -> impl IntoResponse {
+    if !request_ctx.principal().can_administer() {
+        return StatusCode::FORBIDDEN.into_response();
+    }
+    let command = ExportUserData {
+        user_id: user_id.clone(),
+    };
+    match export_user_data(&state, command).await {
+        Ok(records) => ndjson(&user_id, Arc::new(bundle_lines(&records))),
+        Err(_) => StatusCode::INTERNAL_SERVER_ERROR.into_response(),
+    }
+}
+
+/// POST /admin/users/{user_id}/data-export — queues an export job; poll the `Location` it
+/// answers with until the job has succeeded, then download its bundle. Admins only.
+pub async fn handle_start_export(
+    State(state): State<AppState>,
+    request_ctx: RequestContext,
+    Path(user_id): Path<String>,
+) -> impl IntoResponse {
+    if !request_ctx.principal().can_administer() {
+        return StatusCode::FORBIDDEN.into_response();
+    }
+    let payload = serde_json::to_value(ExportUserData { user_id })
+        .expect("export commands serialize to JSON");
+    let job = Job::queued(JOB_KIND, payload, Utc::now().timestamp_millis());
+    jobs::accept(&state, job).await
+}
+
+/// GET /admin/data-exports/{job_id}/bundle — the bundle of an export job that succeeded;
+/// `409 Conflict` with the job while it is queued or running, or after it failed. Admins only.
+pub async fn handle_job_bundle(
+    State(state): State<AppState>,
+    request_ctx: RequestContext,
+    Path(job_id): Path<String>,
+) -> impl IntoResponse {
+    if !request_ctx.principal().can_administer() {
+        return StatusCode::FORBIDDEN.into_response();
+    }
+    let job = match state.job_store.get(&job_id).await {
+        Ok(Some(job)) if job.kind == JOB_KIND => job,
+        Ok(_) => return StatusCode::NOT_FOUND.into_response(),
+        Err(_) => return StatusCode::INTERNAL_SERVER_ERROR.into_response(),
+    };
+    if job.status != JobStatus::Succeeded {
+        return (StatusCode::CONFLICT, Json(job)).into_response();
+    }
+    let Ok(command) = serde_json::from_value::<ExportUserData>(job.payload) else {
+        return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+    };
+    match state.cold_storage.read_lines(&bundle_key(&job_id)).await {
+        Ok(lines) => ndjson(&command.user_id, Arc::new(lines)),
+        Err(_) => StatusCode::INTERNAL_SERVER_ERROR.into_response(),
+    }
+}
+
+#[cfg(test)]
+mod user_data_export_tests {
+    use super::*;
+    use crate::modules::contracts::use_cases::set_contract::command::SetContract;
+    use crate::modules::time_entries::use_cases::list_time_entries::projection::{
+        ListTimeEntriesState, TimeEntryStatus,
+    };
+    use crate::modules::time_entries::use_cases::set_started_at::command::SetStartedAt;
+    use crate::shared::application::jobs::JobRunner;
+    use crate::shared::infrastructure::event_store::in_memory::InMemoryEventStore;
+    use crate::shared::infrastructure::projection_store::in_memory::InMemoryProjectionStore;
+    use crate::tests::fixtures::tags::{make_test_app_state, make_test_app_state_with};
+    use axum::{Router, http::Request, routing::get};
+    use chrono::NaiveDate;
+    use http_body_util::BodyExt;
+    use rstest::rstest;
+    use tower::ServiceExt;
+
+    fn app(state: AppState) -> Router {
+        Router::new()
+            .route(
+                "/admin/users/{user_id}/data-export",
+                get(handle_export).post(handle_start_export),
+            )
+            .route(
+                "/admin/data-exports/{job_id}/bundle",
+                get(handle_job_bundle),
+            )
+            .with_state(state)
+    }
+
+    async fn send(state: &AppState, method: &str, uri: &str, role: &str) -> (StatusCode, String) {
+        let response = app(state.clone())
+            .oneshot(
+                Request::builder()
+                    .method(method)
+                    .uri(uri)
+                    .header("x-user-id", "admin-1")
+                    .header("x-tenant-id", "tenant-test")
+                    .header("x-user-role", role)
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        let status = response.status();
+        let bytes = response.into_body().collect().await.unwrap().to_bytes();
+        (status, String::from_utf8(bytes.to_vec()).unwrap())
+    }
+
+    fn kinds(body: &str) -> Vec<String> {
+        body.lines()
+            .map(|line| serde_json::from_str::<Value>(line).unwrap()["kind"].to_string())
+            .map(|kind| kind.trim_matches('"').to_string())
+            .collect()
+    }
+
+    fn row(time_entry_id: &str, user_id: &str) -> TimeEntryRow {
+        TimeEntryRow {
+            time_entry_id: time_entry_id.to_string(),
+            user_id: user_id.to_string(),
+            started_at: Some(1_000),
+            ended_at: None,
+            tag_ids: vec![],
+            status: TimeEntryStatus::Draft,
+            created_at: 1_000,
+            created_by: user_id.to_string(),
+            updated_at: 1_000,
+            updated_by: user_id.to_string(),
+            deleted_at: None,
+            hourly_rate: None,
+            last_event_id: None,
+            breaks: vec![],
+        }
+    }
+
+    async fn start_entry(state: &AppState, time_entry_id: &str, user_id: &str) {
+        state
+            .set_started_at_handler
+            .handle(
+                &format!("TimeEntry-{time_entry_id}"),
+                SetStartedAt {
+                    time_entry_id: time_entry_id.into(),
+                    user_id: user_id.into(),
+                    started_at: 1_000,
+                    updated_at: 1_000,
+                    updated_by: user_id.into(),
+                    rounding: None,
+                },
+            )
+            .await
+            .unwrap();
+    }
+
+    /// Two entries of `u-1`, one of `u-2`, a contract and an audited mutation for `u-1`.
+    async fn make_history() -> AppState {
+        make_history_over(InMemoryEventStore::<TimeEntryEvent>::new()).await
+    }
+
+    async fn make_history_over(event_store: InMemoryEventStore<TimeEntryEvent>) -> AppState {
+        let projection_store = InMemoryProjectionStore::<ListTimeEntriesState>::new();
+        let state = make_test_app_state_with(event_store, projection_store.clone());
+        start_entry(&state, "te-1", "u-1").await;
+        start_entry(&state, "te-2", "u-2").await;
+        state
+            .set_contract_handler
+            .handle(SetContract {
+                user_id: "u-1".to_string(),
+                minutes_per_week: 40 * 60,
+                start_date: NaiveDate::from_ymd_opt(2026, 1, 5).unwrap(),
+                set_at: 1_000,
+                set_by: "admin-1".to_string(),
+            })
+            .await
+            .unwrap();
+        let mut list = ListTimeEntriesState::default();
+        for row in [row("te-1", "u-1"), row("te-2", "u-2")] {
+            list.rows.insert(row.time_entry_id.clone(), row);
+        }
+        projection_store.save(list, 1).await.unwrap();
+        state
+            .audit_store
+            .record(AuditRecord {
+                occurred_at: 1_000,
+                actor: Some("u-1".to_string()),
+                tenant_id: Some("tenant-test".to_string()),
+                operation: "PUT /api/v1/time-entries/{id}/start".to_string(),
+                arguments_hash: "0".to_string(),
+                status: 200,
+                latency_ms: 1,
+            })
+            .await
+            .unwrap();
+        state
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_bundle_only_the_users_own_data() {
+        let state = make_history().await;
+
+        let records = export_user_data(
+            &state,
+            ExportUserData {
+                user_id: "u-1".to_string(),
+            },
+        )
+        .await
+        .unwrap();
+
+        let time_entry_streams: BTreeSet<&str> = records
+            .iter()
+            .filter_map(|record| match record {
+                BundleRecord::TimeEntryEvent { stream_id, .. } => Some(stream_id.as_str()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(time_entry_streams, BTreeSet::from(["TimeEntry-te-1"]));
+        assert!(matches!(&records[0], BundleRecord::Export { user_id, .. } if user_id == "u-1"));
+        assert!(records.iter().any(|record| matches!(
+            record,
+            BundleRecord::ContractEvent { stream_id, stream_version: 1, .. } if stream_id == "Contract-u-1"
+        )));
+        let rows: Vec<&TimeEntryRow> = records
+            .iter()
+            .filter_map(|record| match record {
+                BundleRecord::TimeEntry { row } => Some(row),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(rows, vec![&row("te-1", "u-1")]);
+        assert!(
+            records
+                .iter()
+                .any(|record| matches!(record, BundleRecord::AuditRecord { .. }))
+        );
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_stream_the_bundle_as_ndjson() {
+        let state = make_history().await;
+
+        let (status, body) = send(&state, "GET", "/admin/users/u-2/data-export", "admin").await;
+
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(
+            kinds(&body),
+            vec![
+                "export",
+                "time_entry_event",
+                "time_entry_event",
+                "time_entry"
+            ]
+        );
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_export_through_a_job() {
+        let state = make_history().await;
+
+        let (status, body) = send(&state, "POST", "/admin/users/u-1/data-export", "admin").await;
+        assert_eq!(status, StatusCode::ACCEPTED);
+        let job: Value = serde_json::from_str(&body).unwrap();
+        let job_id = job["job_id"].as_str().unwrap().to_string();
+        assert_eq!(job["kind"], JOB_KIND);
+
+        JobRunner::new(state.job_store.clone())
+            .with_handler(JOB_KIND, Arc::new(ExportUserDataJob::new(state.clone())))
+            .run_due()
+            .await
+            .unwrap();
+
+        let job = state.job_store.get(&job_id).await.unwrap().unwrap();
+        assert_eq!(job.status, JobStatus::Succeeded);
+        let result = job.result.unwrap();
+        assert_eq!(result["key"], bundle_key(&job_id));
+        let uri = format!("/admin/data-exports/{job_id}/bundle");
+        let (status, body) = send(&state, "GET", &uri, "admin").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(
+            body.lines().count() as u64,
+            result["records"].as_u64().unwrap()
+        );
+        assert_eq!(kinds(&body)[0], "export");
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_refuse_bundles_of_unfinished_jobs() {
+        let state = make_test_app_state();
+        let payload = json!({ "user_id": "u-1" });
+        let (running, failed) = (
+            Job::queued(JOB_KIND, payload.clone(), 0),
+            Job::queued(JOB_KIND, payload, 0),
+        );
+        let other = Job::queued("other", json!({}), 0);
+        for job in [&running, &failed, &other] {
+            state.job_store.enqueue(job.clone()).await.unwrap();
+        }
+        state.job_store.claim_due(0, 3).await.unwrap();
+        state
+            .job_store
+            .fail(&failed.job_id, "store offline", None, 1)
+            .await
+            .unwrap();
+
+        let bundle = |job_id: &str| format!("/admin/data-exports/{job_id}/bundle");
+        let (status, _) = send(&state, "GET", &bundle(&running.job_id), "admin").await;
+        assert_eq!(status, StatusCode::CONFLICT);
+        let (status, body) = send(&state, "GET", &bundle(&failed.job_id), "admin").await;
+        assert_eq!(status, StatusCode::CONFLICT);
+        assert!(body.contains("store offline"));
+        let (status, _) = send(&state, "GET", &bundle(&other.job_id), "admin").await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        let (status, _) = send(&state, "GET", &bundle("unknown"), "admin").await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_fail_the_export_when_the_event_store_is_offline() {
+        let event_store = InMemoryEventStore::<TimeEntryEvent>::new();
+        let state = make_history_over(event_store.clone()).await;
+        event_store.toggle_offline();
+
+        let (status, _) = send(&state, "GET", "/admin/users/u-1/data-export", "admin").await;
+
+        assert_eq!(status, StatusCode::INTERNAL_SERVER_ERROR);
+    }
+
+    #[rstest]
+    #[case::export("GET", "/admin/users/u-1/data-export")]
+    #[case::start("POST", "/admin/users/u-1/data-export")]
+    #[case::bundle("GET", "/admin/data-exports/j-1/bundle")]
+    #[tokio::test]
+    async fn it_should_be_reserved_for_admins(#[case] method: &str, #[case] uri: &str) {
+        let state = make_history().await;
+        let (status, _) = send(&state, method, uri, "employee").await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+    }
+}
@@ -0,0 +1,87 @@
+// Runs the time entry archiver on a fixed interval.
+//
+// Each tick archives entries that ended before `now - retention`. Failed runs are retried
+// on the next tick; cold storage is written before rows are removed, so nothing is lost.
+
+use crate::modules::time_entries::use_cases::archive_time_entries::archiver::TimeEntryArchiver;
+use crate::modules::time_entries::use_cases::list_time_entries::projection::ListTimeEntriesState;
+use crate::shared::infrastructure::cold_storage::ColdStorage;
+use crate::shared::infrastructure::projection_store::ProjectionStore;
+use std::time::Duration;
+use tokio::task::JoinHandle;
+
+pub fn spawn<TStore, TColdStorage>(
+    archiver: TimeEntryArchiver<TStore, TColdStorage>,
+    every: Duration,
+) -> JoinHandle<()>
+where
+    TStore: ProjectionStore<ListTimeEntriesState> + Send + Sync + 'static,
+    TColdStorage: ColdStorage + 'static,
+{
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(every);
+        loop {
+            interval.tick().await;
+            if let Err(reason) = archiver
+                .archive(chrono::Utc::now().timestamp_millis())
+                .await
+            {
+                tracing::warn!(%reason, "time entry archive run failed");
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod archiver_runner_tests {
+    use super::*;
+    use crate::modules::time_entries::use_cases::list_time_entries::projection::{
+        TimeEntryRow, TimeEntryStatus,
+    };
+    use crate::shared::infrastructure::cold_storage::in_memory::InMemoryColdStorage;
+    use crate::shared::infrastructure::projection_store::in_memory::InMemoryProjectionStore;
+    use rstest::rstest;
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_archive_on_every_tick_and_survive_failed_runs() {
+        let store = InMemoryProjectionStore::<ListTimeEntriesState>::new();
+        let mut state = ListTimeEntriesState::default();
+        state.rows.insert(
+            "te-old".to_string(),
+            TimeEntryRow {
+                time_entry_id: "te-old".to_string(),
+                user_id: "u1".to_string(),
+                started_at: Some(0),
+                ended_at: Some(1),
+                tag_ids: vec![],
+                status: TimeEntryStatus::Registered,
+                created_at: 0,
+                created_by: "u1".to_string(),
+                updated_at: 0,
+                updated_by: "u1".to_string(),
+                deleted_at: None,
+                hourly_rate: None,
+                last_event_id: None,
+                breaks: vec![],
+            },
+        );
+        store.save(state, 1).await.unwrap();
+        let cold_storage = InMemoryColdStorage::new();
+        cold_storage.toggle_offline();
+
+        let handle = spawn(
+            TimeEntryArchiver::new(store.clone(), cold_storage.clone(), 1_000),
+            Duration::from_millis(10),
+        );
+        tokio::time::sleep(Duration::from_millis(15)).await;
+        assert_eq!(store.state().await.unwrap().unwrap().rows.len(), 1);
+
+        cold_storage.toggle_offline();
+        tokio::time::sleep(Duration::from_millis(40)).await;
+        handle.abort();
+
+        assert!(store.state().await.unwrap().unwrap().rows.is_empty());
+        assert_eq!(cold_storage.keys().await, vec!["time_entries/u1.jsonl"]);
+    }
+}
@@ -0,0 +1,67 @@
+// Records the lag of the broker consumers on a fixed interval.
+//
+// Each tick fetches the offsets of every consumer into `ConsumerLag`, which the admin API and
+// the metrics endpoint read. A consumer whose offsets cannot be fetched keeps its last report
+// and is retried on the next tick. Stalled partitions are logged on every tick until they
+// move again.
+
+use crate::shared::infrastructure::message_broker::consumer_lag::{
+    ConsumerLag, ConsumerOffsets, observe,
+};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::task::JoinHandle;
+
+pub fn spawn(
+    consumers: Vec<Arc<dyn ConsumerOffsets>>,
+    lag: ConsumerLag,
+    every: Duration,
+) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(every);
+        loop {
+            interval.tick().await;
+            let now = chrono::Utc::now().timestamp_millis();
+            for (consumer, reason) in observe(&consumers, &lag, now).await {
+                tracing::warn!(consumer, %reason, "consumer offsets fetch failed");
+            }
+            for partition in lag.partitions(now).iter().filter(|p| p.stalled) {
+                tracing::warn!(
+                    consumer = partition.consumer,
+                    topic = partition.topic,
+                    partition = partition.partition,
+                    lag = partition.lag,
+                    "consumer stalled"
+                );
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod consumer_lag_runner_tests {
+    use super::*;
+    use crate::shared::infrastructure::message_broker::in_memory::InMemoryConsumer;
+    use rstest::rstest;
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_record_the_lag_on_every_tick() {
+        let consumer = InMemoryConsumer::new("billing");
+        consumer.set_head("time-entries.v1", 0, 4).await;
+        let lag = ConsumerLag::new();
+
+        let handle = spawn(
+            vec![Arc::new(consumer.clone())],
+            lag.clone(),
+            Duration::from_millis(10),
+        );
+        tokio::time::sleep(Duration::from_millis(5)).await;
+        assert_eq!(lag.partitions(0)[0].lag, 4);
+        consumer.commit("time-entries.v1", 0, 3).await;
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        handle.abort();
+
+        assert_eq!(lag.partitions(0)[0].lag, 1);
+    }
+}
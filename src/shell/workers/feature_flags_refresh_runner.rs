@@ -0,0 +1,61 @@
+// Refreshes the Unleash toggles on a fixed interval.
+//
+// Commands evaluate flags against the toggles held in memory, so a change in Unleash takes
+// effect within one interval. A failed refresh keeps the previous toggles and is retried on
+// the next tick.
+
+use crate::shared::infrastructure::feature_flags::unleash::{UnleashFeatureFlags, UnleashHttp};
+use std::time::Duration;
+use tokio::task::JoinHandle;
+
+pub fn spawn<TClient>(flags: UnleashFeatureFlags<TClient>, every: Duration) -> JoinHandle<()>
+where
+    TClient: UnleashHttp + 'static,
+{
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(every);
+        loop {
+            interval.tick().await;
+            if let Err(reason) = flags.refresh().await {
+                tracing::warn!(%reason, "feature flag refresh failed");
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod feature_flags_refresh_runner_tests {
+    use super::*;
+    use crate::shared::infrastructure::calendar::http::HttpResponse;
+    use crate::shared::infrastructure::feature_flags::FeatureFlags;
+    use async_trait::async_trait;
+    use rstest::rstest;
+
+    #[derive(Clone)]
+    struct StubClient;
+
+    #[async_trait]
+    impl UnleashHttp for StubClient {
+        async fn get(&self, _url: &str, _api_token: &str) -> Result<HttpResponse, String> {
+            Ok(HttpResponse {
+                status: 200,
+                body: r#"{"features":[{"name":"max_entry_duration","enabled":true}]}"#.to_string(),
+            })
+        }
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_load_the_toggles() {
+        let flags = UnleashFeatureFlags::new("https://unleash.test/api", "token", StubClient);
+
+        let handle = spawn(flags.clone(), Duration::from_millis(10));
+        tokio::time::sleep(Duration::from_millis(25)).await;
+        handle.abort();
+
+        assert_eq!(
+            flags.lookup("max_entry_duration", Some("t-1")).await,
+            Ok(Some(true))
+        );
+    }
+}
@@ -0,0 +1,52 @@
+// Purges old inbox entries on a fixed interval.
+//
+// Each tick forgets messages processed more than `retention_ms` ago. Failed runs are retried
+// on the next tick; entries only grow in the meantime.
+
+use crate::shared::infrastructure::inbox::Inbox;
+use std::time::Duration;
+use tokio::task::JoinHandle;
+
+pub fn spawn<TInbox>(inbox: TInbox, retention_ms: i64, every: Duration) -> JoinHandle<()>
+where
+    TInbox: Inbox + 'static,
+{
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(every);
+        loop {
+            interval.tick().await;
+            let cutoff = chrono::Utc::now().timestamp_millis() - retention_ms;
+            match inbox.purge_before(cutoff).await {
+                Ok(0) => {}
+                Ok(purged) => tracing::info!(purged, "purged old inbox entries"),
+                Err(reason) => tracing::warn!(%reason, "inbox cleanup run failed"),
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod inbox_cleanup_runner_tests {
+    use super::*;
+    use crate::shared::infrastructure::inbox::in_memory::InMemoryInbox;
+    use rstest::rstest;
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_purge_on_every_tick_and_survive_failed_runs() {
+        let inbox = InMemoryInbox::new();
+        let now = chrono::Utc::now().timestamp_millis();
+        inbox.record("billing", "m-old", 0).await.unwrap();
+        inbox.record("billing", "m-new", now).await.unwrap();
+        inbox.toggle_offline();
+
+        let handle = spawn(inbox.clone(), 60_000, Duration::from_millis(10));
+        tokio::time::sleep(Duration::from_millis(15)).await;
+        inbox.toggle_offline();
+        tokio::time::sleep(Duration::from_millis(30)).await;
+        handle.abort();
+
+        assert!(!inbox.contains("billing", "m-old").await.unwrap());
+        assert!(inbox.contains("billing", "m-new").await.unwrap());
+    }
+}
@@ -0,0 +1,79 @@
+// Drives the job runner on a fixed interval.
+//
+// On start it requeues jobs a previous process left running, then each tick runs the jobs
+// that are due. Failed ticks are retried on the next one; job failures are the runner's to
+// retry and never stop the loop.
+
+use crate::shared::application::jobs::JobRunner;
+use crate::shared::infrastructure::job_store::JobStore;
+use std::time::Duration;
+use tokio::task::JoinHandle;
+
+pub fn spawn<TStore>(runner: JobRunner<TStore>, every: Duration) -> JoinHandle<()>
+where
+    TStore: JobStore + 'static,
+{
+    tokio::spawn(async move {
+        match runner.recover().await {
+            Ok(0) => {}
+            Ok(requeued) => tracing::info!(requeued, "requeued jobs left running"),
+            Err(reason) => tracing::warn!(%reason, "job recovery failed"),
+        }
+        let mut interval = tokio::time::interval(every);
+        loop {
+            interval.tick().await;
+            if let Err(reason) = runner.run_due().await {
+                tracing::warn!(%reason, "job run failed");
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod job_runner_tests {
+    use super::*;
+    use crate::shared::application::jobs::{JobContext, JobHandler};
+    use crate::shared::infrastructure::job_store::in_memory::InMemoryJobStore;
+    use crate::shared::infrastructure::job_store::{Job, JobStatus};
+    use rstest::rstest;
+    use serde_json::{Value as Json, json};
+    use std::sync::Arc;
+
+    struct Echo;
+
+    #[async_trait::async_trait]
+    impl JobHandler for Echo {
+        async fn run(&self, job: &Job, _ctx: &JobContext<'_>) -> anyhow::Result<Json> {
+            Ok(job.payload.clone())
+        }
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_resume_orphaned_jobs_and_run_new_ones_while_the_store_recovers() {
+        let store = InMemoryJobStore::new();
+        let orphaned = Job::queued("echo", json!(1), 0);
+        store.enqueue(orphaned.clone()).await.unwrap();
+        store.claim_due(0, 1).await.unwrap();
+
+        let handle = spawn(
+            JobRunner::new(store.clone())
+                .with_handler("echo", Arc::new(Echo))
+                .with_stale_after(Duration::ZERO),
+            Duration::from_millis(10),
+        );
+        tokio::time::sleep(Duration::from_millis(15)).await;
+        store.toggle_offline();
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        store.toggle_offline();
+        let queued = Job::queued("echo", json!(2), 0);
+        store.enqueue(queued.clone()).await.unwrap();
+        tokio::time::sleep(Duration::from_millis(40)).await;
+        handle.abort();
+
+        for job_id in [orphaned.job_id, queued.job_id] {
+            let job = store.get(&job_id).await.unwrap().unwrap();
+            assert_eq!(job.status, JobStatus::Succeeded);
+        }
+    }
+}
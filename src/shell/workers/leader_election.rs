@@ -0,0 +1,203 @@
+// Runs a background worker only while this instance holds its lease.
+//
+// Every instance of the service campaigns for the same lease name; the holder starts the
+// work and renews the lease every third of its TTL. When a renewal is refused or the lease
+// store fails, the work future is dropped and the instance goes back to campaigning. A leader
+// that dies simply stops renewing, so a standby takes over once the lease expires. Work that
+// finishes on its own releases the lease for an immediate handover.
+//
+// Projectors and relays resume from their own checkpoints, so a handover may repeat work but
+// never skips it.
+
+use crate::shared::infrastructure::lease_store::LeaseStore;
+use std::future::Future;
+use std::time::Duration;
+
+pub struct LeaderElection<TStore> {
+    store: TStore,
+    name: String,
+    holder: String,
+    ttl: Duration,
+}
+
+impl<TStore> LeaderElection<TStore>
+where
+    TStore: LeaseStore,
+{
+    pub fn new(
+        store: TStore,
+        name: impl Into<String>,
+        holder: impl Into<String>,
+        ttl: Duration,
+    ) -> Self {
+        Self {
+            store,
+            name: name.into(),
+            holder: holder.into(),
+            ttl,
+        }
+    }
+
+    fn renew_every(&self) -> Duration {
+        self.ttl / 3
+    }
+
+    async fn try_lead(&self) -> bool {
+        let now = chrono::Utc::now().timestamp_millis();
+        match self
+            .store
+            .try_acquire(&self.name, &self.holder, now, self.ttl.as_millis() as i64)
+            .await
+        {
+            Ok(granted) => granted,
+            Err(reason) => {
+                tracing::warn!(%reason, lease = %self.name, "lease store unavailable");
+                false
+            }
+        }
+    }
+
+    /// Renews until the lease is lost.
+    async fn hold(&self) {
+        loop {
+            tokio::time::sleep(self.renew_every()).await;
+            if !self.try_lead().await {
+                return;
+            }
+        }
+    }
+
+    /// Runs a fresh `start()` future each time this instance becomes leader. Returns once a
+    /// started future completes.
+    pub async fn run<F, Fut>(self, mut start: F)
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = ()>,
+    {
+        loop {
+            if !self.try_lead().await {
+                tokio::time::sleep(self.renew_every()).await;
+                continue;
+            }
+            tracing::info!(lease = %self.name, holder = %self.holder, "acquired lease");
+            tokio::select! {
+                _ = start() => {
+                    if let Err(reason) = self.store.release(&self.name, &self.holder).await {
+                        tracing::warn!(%reason, lease = %self.name, "failed to release lease");
+                    }
+                    return;
+                }
+                _ = self.hold() => {
+                    tracing::warn!(lease = %self.name, holder = %self.holder, "lost lease, stopping work");
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod leader_election_tests {
+    use super::*;
+    use crate::shared::infrastructure::lease_store::in_memory::InMemoryLeaseStore;
+    use rstest::rstest;
+    use std::pin::Pin;
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    const TTL: Duration = Duration::from_millis(60);
+
+    type Work = Pin<Box<dyn Future<Output = ()> + Send>>;
+
+    /// Work that never finishes, counting its ticks.
+    fn ticking(counter: Arc<AtomicU32>) -> impl FnMut() -> Work {
+        move || {
+            let counter = counter.clone();
+            Box::pin(async move {
+                loop {
+                    counter.fetch_add(1, Ordering::SeqCst);
+                    tokio::time::sleep(Duration::from_millis(5)).await;
+                }
+            })
+        }
+    }
+
+    fn spawn_instance(
+        store: &InMemoryLeaseStore,
+        holder: &str,
+        counter: Arc<AtomicU32>,
+    ) -> tokio::task::JoinHandle<()> {
+        let election = LeaderElection::new(store.clone(), "relay", holder, TTL);
+        tokio::spawn(election.run(ticking(counter)))
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_run_work_on_one_instance_and_fail_over_when_it_dies() {
+        let store = InMemoryLeaseStore::new();
+        let a = Arc::new(AtomicU32::new(0));
+        let b = Arc::new(AtomicU32::new(0));
+        let leader = spawn_instance(&store, "a", a.clone());
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        let standby = spawn_instance(&store, "b", b.clone());
+
+        tokio::time::sleep(Duration::from_millis(60)).await;
+        assert!(a.load(Ordering::SeqCst) > 0);
+        assert_eq!(b.load(Ordering::SeqCst), 0);
+        assert_eq!(store.holder("relay").await.as_deref(), Some("a"));
+
+        leader.abort();
+        tokio::time::sleep(Duration::from_millis(120)).await;
+        standby.abort();
+
+        assert!(b.load(Ordering::SeqCst) > 0);
+        assert_eq!(store.holder("relay").await.as_deref(), Some("b"));
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_stop_work_when_the_lease_cannot_be_renewed() {
+        let store = InMemoryLeaseStore::new();
+        let a = Arc::new(AtomicU32::new(0));
+        let instance = spawn_instance(&store, "a", a.clone());
+        tokio::time::sleep(Duration::from_millis(10)).await;
+
+        store.toggle_offline();
+        tokio::time::sleep(Duration::from_millis(40)).await;
+        let stopped_at = a.load(Ordering::SeqCst);
+        tokio::time::sleep(Duration::from_millis(30)).await;
+        assert_eq!(a.load(Ordering::SeqCst), stopped_at);
+
+        store.toggle_offline();
+        tokio::time::sleep(Duration::from_millis(40)).await;
+        instance.abort();
+        assert!(a.load(Ordering::SeqCst) > stopped_at);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_release_the_lease_when_work_completes() {
+        let store = InMemoryLeaseStore::new();
+        let election = LeaderElection::new(store.clone(), "relay", "a", TTL);
+
+        election.run(|| async {}).await;
+
+        assert_eq!(store.holder("relay").await, None);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_return_even_if_the_release_fails() {
+        let store = InMemoryLeaseStore::new();
+        let election = LeaderElection::new(store.clone(), "relay", "a", TTL);
+
+        election
+            .run(|| {
+                let store = store.clone();
+                async move { store.toggle_offline() }
+            })
+            .await;
+
+        store.toggle_offline();
+        assert_eq!(store.holder("relay").await.as_deref(), Some("a"));
+    }
+}
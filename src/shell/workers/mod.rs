@@ -1 +1,14 @@
+pub mod archiver_runner;
+pub mod consumer_lag_runner;
+pub mod feature_flags_refresh_runner;
+pub mod inbox_cleanup_runner;
+pub mod job_runner;
+pub mod leader_election;
 pub mod projector_runner;
+pub mod revealed_feed_runner;
+pub mod schedule_runner;
+pub mod sealed_feed_runner;
+pub mod secrets_renewal_runner;
+pub mod shadow_runner;
+pub mod timer_auto_stop_runner;
+pub mod watchdog_runner;
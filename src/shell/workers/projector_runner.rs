@@ -60,7 +60,7 @@ mod project_runner {
         let receiver = event_tx.subscribe();
         spawn(projector, receiver);
 
-        let handler = SetStartedAtHandler::new("t", event_store, outbox);
+        let handler = SetStartedAtHandler::new(event_store, outbox);
         handler
             .handle(
                 "TimeEntry-te-1",
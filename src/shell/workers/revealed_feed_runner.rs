@@ -0,0 +1,102 @@
+// Forwards the time entry store's live feed to the projectors with personal data decrypted.
+//
+// The store broadcasts events as it stored them, encrypted for crypto-shredding. Projectors
+// subscribe to the revealed feed instead. Events this task misses, because it fell behind or
+// a key could not be read, show up as a gap in positions, which the projectors close by
+// catching up from the revealing log.
+
+use crate::modules::time_entries::core::events::TimeEntryEvent;
+use crate::shared::infrastructure::event_store::StoredEvent;
+use crate::shared::infrastructure::event_store::crypto_shredding::{
+    CryptoShreddingEventStore, FieldCipher,
+};
+use crate::shared::infrastructure::key_store::KeyStore;
+use tokio::sync::broadcast::{self, error::RecvError};
+use tokio::task::JoinHandle;
+
+pub fn spawn<TInner, TKeyStore, TCipher>(
+    store: CryptoShreddingEventStore<TInner, TKeyStore, TCipher>,
+    mut stored: broadcast::Receiver<StoredEvent<TimeEntryEvent>>,
+    revealed: broadcast::Sender<StoredEvent<TimeEntryEvent>>,
+) -> JoinHandle<()>
+where
+    TInner: Send + Sync + 'static,
+    TKeyStore: KeyStore + 'static,
+    TCipher: FieldCipher + 'static,
+{
+    tokio::spawn(async move {
+        loop {
+            match stored.recv().await {
+                Ok(event) => {
+                    let position = event.global_position;
+                    match store.reveal_stored(event).await {
+                        // Nobody subscribed yet is not an error.
+                        Ok(event) => drop(revealed.send(event)),
+                        Err(reason) => {
+                            tracing::warn!(%reason, position, "time entry event not revealed");
+                        }
+                    }
+                }
+                Err(RecvError::Lagged(skipped)) => {
+                    tracing::warn!(skipped, "revealed time entry feed fell behind");
+                }
+                Err(RecvError::Closed) => break,
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod revealed_feed_runner_tests {
+    use super::*;
+    use crate::shared::infrastructure::event_store::EventStore;
+    use crate::shared::infrastructure::event_store::crypto_shredding::{
+        AesGcmFieldCipher, ENCRYPTED_PREFIX,
+    };
+    use crate::shared::infrastructure::event_store::in_memory::InMemoryEventStore;
+    use crate::shared::infrastructure::key_store::in_memory::InMemoryKeyStore;
+    use crate::tests::fixtures::events::time_entry_initiated_v1::make_time_entry_initiated_v1_event;
+    use rstest::rstest;
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_forward_events_with_personal_data_revealed() {
+        let (stored_tx, _) = broadcast::channel(16);
+        let (revealed_tx, _) = broadcast::channel(16);
+        let store = CryptoShreddingEventStore::new(
+            InMemoryEventStore::<TimeEntryEvent>::new_with_sender(stored_tx.clone()),
+            InMemoryKeyStore::new(),
+            AesGcmFieldCipher,
+        );
+        let mut stored = stored_tx.subscribe();
+        let mut revealed = revealed_tx.subscribe();
+        spawn(store.clone(), stored_tx.subscribe(), revealed_tx);
+        let initiated = make_time_entry_initiated_v1_event();
+
+        store
+            .append(
+                "TimeEntry-1",
+                0,
+                &[TimeEntryEvent::TimeEntryInitiatedV1(initiated.clone())],
+            )
+            .await
+            .unwrap();
+
+        let TimeEntryEvent::TimeEntryInitiatedV1(concealed) = stored.recv().await.unwrap().event
+        else {
+            panic!("expected the initiated event");
+        };
+        assert!(
+            concealed
+                .created_by
+                .to_string()
+                .starts_with(ENCRYPTED_PREFIX)
+        );
+        let forwarded = revealed.recv().await.unwrap();
+        assert_eq!(forwarded.global_position, 0);
+        assert_eq!(
+            forwarded.event,
+            TimeEntryEvent::TimeEntryInitiatedV1(initiated)
+        );
+    }
+}
@@ -0,0 +1,52 @@
+// Runs the schedule materializer on a fixed interval.
+//
+// Each tick registers the entries of recurring schedules whose occurrences have ended, or
+// records them as skipped when the user already logged that time. Failed runs are retried on
+// the next tick; occurrences are only recorded once, as the decider re-checks each against
+// its schedule's stream.
+
+use crate::modules::schedules::core::events::ScheduleEvent;
+use crate::modules::schedules::use_cases::list_schedules::projection::ListSchedulesState;
+use crate::modules::schedules::use_cases::record_occurrence::materializer::{
+    MaterializeRun, ScheduleMaterializer,
+};
+use crate::modules::time_entries::core::events::TimeEntryEvent;
+use crate::modules::time_entries::use_cases::list_time_entries::projection::ListTimeEntriesState;
+use crate::shared::infrastructure::event_store::EventStore;
+use crate::shared::infrastructure::intent_outbox::DomainOutbox;
+use crate::shared::infrastructure::projection_store::ProjectionStore;
+use std::time::Duration;
+use tokio::task::JoinHandle;
+
+pub fn spawn<TSchedules, TEntries, TScheduleEvents, TEventStore, TOutbox>(
+    materializer: ScheduleMaterializer<TSchedules, TEntries, TScheduleEvents, TEventStore, TOutbox>,
+    every: Duration,
+) -> JoinHandle<()>
+where
+    TSchedules: ProjectionStore<ListSchedulesState> + Send + Sync + 'static,
+    TEntries: ProjectionStore<ListTimeEntriesState> + Send + Sync + 'static,
+    TScheduleEvents: EventStore<ScheduleEvent> + Clone + Send + Sync + 'static,
+    TEventStore: EventStore<TimeEntryEvent> + Send + Sync + 'static,
+    TOutbox: DomainOutbox + Send + Sync + 'static,
+{
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(every);
+        loop {
+            interval.tick().await;
+            match materializer
+                .materialize_due(chrono::Utc::now().timestamp_millis())
+                .await
+            {
+                Ok(MaterializeRun {
+                    materialized: 0,
+                    skipped: 0,
+                }) => {}
+                Ok(MaterializeRun {
+                    materialized,
+                    skipped,
+                }) => tracing::info!(materialized, skipped, "materialized scheduled entries"),
+                Err(reason) => tracing::warn!(%reason, "schedule run failed"),
+            }
+        }
+    })
+}
@@ -0,0 +1,80 @@
+// Forwards the live feed of an encrypted time entry store with its payloads opened.
+//
+// With encryption at rest the inner store broadcasts sealed events. This task opens each one
+// and sends it on as the plain feed the rest of the pipeline expects. Events it cannot open,
+// or misses by falling behind, leave a gap in positions, which the projectors close by
+// catching up from the log.
+
+use crate::shared::infrastructure::event_store::StoredEvent;
+use crate::shared::infrastructure::event_store::encrypted::{EventCodec, SealedEvent};
+use crate::shared::infrastructure::key_provider::KeyProvider;
+use serde::de::DeserializeOwned;
+use tokio::sync::broadcast::{self, error::RecvError};
+use tokio::task::JoinHandle;
+
+pub fn spawn<E, TKeys>(
+    codec: EventCodec<TKeys>,
+    mut sealed: broadcast::Receiver<StoredEvent<SealedEvent>>,
+    opened: broadcast::Sender<StoredEvent<E>>,
+) -> JoinHandle<()>
+where
+    E: DeserializeOwned + Clone + Send + Sync + 'static,
+    TKeys: KeyProvider + 'static,
+{
+    tokio::spawn(async move {
+        loop {
+            match sealed.recv().await {
+                Ok(event) => {
+                    let position = event.global_position;
+                    match codec.open_stored(event).await {
+                        // Nobody subscribed yet is not an error.
+                        Ok(event) => drop(opened.send(event)),
+                        Err(reason) => {
+                            tracing::warn!(%reason, position, "sealed event not opened");
+                        }
+                    }
+                }
+                Err(RecvError::Lagged(skipped)) => {
+                    tracing::warn!(skipped, "opened event feed fell behind");
+                }
+                Err(RecvError::Closed) => break,
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod sealed_feed_runner_tests {
+    use super::*;
+    use crate::modules::time_entries::core::events::TimeEntryEvent;
+    use crate::shared::infrastructure::event_store::EventStore;
+    use crate::shared::infrastructure::event_store::encrypted::EncryptedEventStore;
+    use crate::shared::infrastructure::event_store::in_memory::InMemoryEventStore;
+    use crate::shared::infrastructure::key_provider::env::EnvKeyProvider;
+    use crate::tests::fixtures::events::time_entry_initiated_v1::make_time_entry_initiated_v1_event;
+    use rstest::rstest;
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_forward_events_with_their_payloads_opened() {
+        let (sealed_tx, _) = broadcast::channel(16);
+        let (opened_tx, _) = broadcast::channel::<StoredEvent<TimeEntryEvent>>(16);
+        let store = EncryptedEventStore::new(
+            InMemoryEventStore::<SealedEvent>::new_with_sender(sealed_tx.clone()),
+            EnvKeyProvider::parse(&format!("k-1:{}", "01".repeat(32))).unwrap(),
+        );
+        let mut opened = opened_tx.subscribe();
+        spawn(store.codec().clone(), sealed_tx.subscribe(), opened_tx);
+        let initiated = TimeEntryEvent::TimeEntryInitiatedV1(make_time_entry_initiated_v1_event());
+
+        store
+            .append("TimeEntry-1", 0, std::slice::from_ref(&initiated))
+            .await
+            .unwrap();
+
+        let forwarded = opened.recv().await.unwrap();
+        assert_eq!(forwarded.global_position, 0);
+        assert_eq!(forwarded.stream_id, "TimeEntry-1");
+        assert_eq!(forwarded.event, initiated);
+    }
+}
@@ -0,0 +1,72 @@
+// Renews cached secrets on a fixed interval.
+//
+// Each tick refetches the secrets within their renewal window, so leased credentials are
+// replaced before they expire rather than on the first request after. Failed renewals are
+// retried on the next tick; the cache serves the old value until it expires.
+
+use crate::shared::infrastructure::secrets::SecretsProvider;
+use crate::shared::infrastructure::secrets::cached::CachedSecrets;
+use std::time::Duration;
+use tokio::task::JoinHandle;
+
+pub fn spawn<TProvider>(secrets: CachedSecrets<TProvider>, every: Duration) -> JoinHandle<()>
+where
+    TProvider: SecretsProvider + 'static,
+{
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(every);
+        loop {
+            interval.tick().await;
+            match secrets.renew_expiring().await {
+                (0, failed) if failed.is_empty() => {}
+                (renewed, failed) => {
+                    tracing::info!(renewed, failed = failed.len(), "renewed secrets")
+                }
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod secrets_renewal_runner_tests {
+    use super::*;
+    use crate::shared::infrastructure::clock::FixedClock;
+    use crate::shared::infrastructure::secrets::{Secret, SecretsError};
+    use async_trait::async_trait;
+    use rstest::rstest;
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[derive(Clone, Default)]
+    struct RotatingProvider {
+        fetches: Arc<AtomicUsize>,
+    }
+
+    #[async_trait]
+    impl SecretsProvider for RotatingProvider {
+        async fn get(&self, _name: &str) -> Result<Secret, SecretsError> {
+            let fetch = self.fetches.fetch_add(1, Ordering::SeqCst) + 1;
+            Ok(Secret {
+                value: format!("password-{fetch}"),
+                ttl: Some(Duration::from_secs(60)),
+            })
+        }
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_renew_secrets_before_they_expire() {
+        let clock = FixedClock::at(0);
+        let secrets = CachedSecrets::new(RotatingProvider::default())
+            .with_renew_before(Duration::from_secs(10))
+            .with_clock(Arc::new(clock.clone()));
+        secrets.get("broker").await.unwrap();
+
+        let handle = spawn(secrets.clone(), Duration::from_millis(10));
+        clock.advance(55_000);
+        tokio::time::sleep(Duration::from_millis(25)).await;
+        handle.abort();
+
+        assert_eq!(secrets.get("broker").await.unwrap().value, "password-2");
+    }
+}
@@ -0,0 +1,76 @@
+// Compares the list projection against a shadow replay on a fixed interval.
+//
+// Each tick diffs every partition store against its own shadow and logs what diverged. It
+// only reports; the live read model is never touched.
+
+use crate::modules::time_entries::use_cases::list_time_entries::projection::ListTimeEntriesState;
+use crate::modules::time_entries::use_cases::list_time_entries::shadow::ShadowProjector;
+use crate::shared::infrastructure::projection_store::ProjectionStore;
+use std::time::Duration;
+use tokio::task::JoinHandle;
+
+/// Divergences logged individually per run; the rest are only counted.
+const LOGGED_DIVERGENCES: usize = 10;
+
+pub fn spawn<TStore>(shadows: Vec<(ShadowProjector, TStore)>, every: Duration) -> JoinHandle<()>
+where
+    TStore: ProjectionStore<ListTimeEntriesState> + 'static,
+{
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(every);
+        loop {
+            interval.tick().await;
+            for (shadow, live) in &shadows {
+                match shadow.compare(live).await {
+                    Ok(report) if report.is_clean() => tracing::debug!(
+                        projection = %shadow.name,
+                        checkpoint = report.checkpoint,
+                        rows = report.rows_compared,
+                        "shadow projection matches"
+                    ),
+                    Ok(report) => {
+                        tracing::warn!(
+                            projection = %shadow.name,
+                            checkpoint = report.checkpoint,
+                            rows = report.rows_compared,
+                            divergences = report.divergences.len(),
+                            "shadow projection diverges from the live read model"
+                        );
+                        for divergence in report.divergences.iter().take(LOGGED_DIVERGENCES) {
+                            tracing::warn!(projection = %shadow.name, ?divergence);
+                        }
+                    }
+                    Err(reason) => {
+                        tracing::warn!(projection = %shadow.name, %reason, "shadow run failed")
+                    }
+                }
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod shadow_runner_tests {
+    use super::*;
+    use crate::modules::time_entries::core::events::TimeEntryEvent;
+    use crate::shared::infrastructure::event_store::in_memory::InMemoryEventStore;
+    use crate::shared::infrastructure::projection_store::in_memory::InMemoryProjectionStore;
+    use rstest::rstest;
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_keep_comparing_after_failed_runs() {
+        let mut live = InMemoryProjectionStore::<ListTimeEntriesState>::new();
+        live.toggle_offline();
+        let shadow = ShadowProjector::new(
+            "list_time_entries",
+            InMemoryEventStore::<TimeEntryEvent>::new(),
+        );
+
+        let handle = spawn(vec![(shadow, live.clone())], Duration::from_millis(5));
+        tokio::time::sleep(Duration::from_millis(20)).await;
+
+        assert!(!handle.is_finished());
+        handle.abort();
+    }
+}
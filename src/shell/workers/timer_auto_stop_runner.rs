@@ -0,0 +1,135 @@
+// Runs the timer auto-stopper on a fixed interval.
+//
+// Each tick stops timers that have been running for longer than the maximum. Failed runs
+// are retried on the next tick; timers are only stopped once, as the decider re-checks
+// each against its stream.
+
+use crate::modules::time_entries::core::events::TimeEntryEvent;
+use crate::modules::time_entries::use_cases::auto_stop_timers::stopper::TimerAutoStopper;
+use crate::modules::time_entries::use_cases::list_time_entries::projection::ListTimeEntriesState;
+use crate::shared::infrastructure::event_store::EventStore;
+use crate::shared::infrastructure::intent_outbox::DomainOutbox;
+use crate::shared::infrastructure::projection_store::ProjectionStore;
+use std::time::Duration;
+use tokio::task::JoinHandle;
+
+pub fn spawn<TStore, TEventStore, TOutbox>(
+    stopper: TimerAutoStopper<TStore, TEventStore, TOutbox>,
+    every: Duration,
+) -> JoinHandle<()>
+where
+    TStore: ProjectionStore<ListTimeEntriesState> + Send + Sync + 'static,
+    TEventStore: EventStore<TimeEntryEvent> + Send + Sync + 'static,
+    TOutbox: DomainOutbox + Send + Sync + 'static,
+{
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(every);
+        loop {
+            interval.tick().await;
+            match stopper
+                .stop_overdue(chrono::Utc::now().timestamp_millis())
+                .await
+            {
+                Ok(0) => {}
+                Ok(stopped) => tracing::info!(stopped, "auto-stopped running timers"),
+                Err(reason) => tracing::warn!(%reason, "timer auto-stop run failed"),
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod timer_auto_stop_runner_tests {
+    use super::*;
+    use crate::modules::time_entries::core::events::v1::time_entry_initiated::TimeEntryInitiatedV1;
+    use crate::modules::time_entries::core::events::v1::time_entry_start_set::TimeEntryStartSetV1;
+    use crate::modules::time_entries::use_cases::auto_stop_timers::handler::AutoStopTimerHandler;
+    use crate::modules::time_entries::use_cases::list_time_entries::projection::{
+        TimeEntryRow, TimeEntryStatus,
+    };
+    use crate::shared::infrastructure::event_store::in_memory::InMemoryEventStore;
+    use crate::shared::infrastructure::intent_outbox::in_memory::InMemoryDomainOutbox;
+    use crate::shared::infrastructure::projection_store::in_memory::InMemoryProjectionStore;
+    use rstest::rstest;
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_stop_timers_on_every_tick_and_survive_failed_runs() {
+        let mut store = InMemoryProjectionStore::<ListTimeEntriesState>::new();
+        let mut state = ListTimeEntriesState::default();
+        state.rows.insert(
+            "te-1".to_string(),
+            TimeEntryRow {
+                time_entry_id: "te-1".to_string(),
+                user_id: "u1".to_string(),
+                started_at: Some(0),
+                ended_at: None,
+                tag_ids: vec![],
+                status: TimeEntryStatus::Draft,
+                created_at: 0,
+                created_by: "u1".to_string(),
+                updated_at: 0,
+                updated_by: "u1".to_string(),
+                deleted_at: None,
+                hourly_rate: None,
+                last_event_id: None,
+                breaks: vec![],
+            },
+        );
+        store.save(state, 1).await.unwrap();
+        let event_store = InMemoryEventStore::<TimeEntryEvent>::new();
+        event_store
+            .append(
+                "TimeEntry-te-1",
+                0,
+                &[
+                    TimeEntryEvent::TimeEntryInitiatedV1(TimeEntryInitiatedV1 {
+                        time_entry_id: "te-1".into(),
+                        user_id: "u1".into(),
+                        created_at: 0,
+                        created_by: "u1".into(),
+                    }),
+                    TimeEntryEvent::TimeEntryStartSetV1(TimeEntryStartSetV1 {
+                        time_entry_id: "te-1".into(),
+                        started_at: 0,
+                        updated_at: 0,
+                        updated_by: "u1".into(),
+                        raw_started_at: None,
+                    }),
+                ],
+            )
+            .await
+            .unwrap();
+        let handler = AutoStopTimerHandler::new(event_store.clone(), InMemoryDomainOutbox::new());
+        store.toggle_offline();
+
+        let handle = spawn(
+            TimerAutoStopper::new(store.clone(), handler),
+            Duration::from_millis(10),
+        );
+        tokio::time::sleep(Duration::from_millis(15)).await;
+        assert_eq!(
+            event_store
+                .load("TimeEntry-te-1")
+                .await
+                .unwrap()
+                .events
+                .len(),
+            2
+        );
+
+        store.toggle_offline();
+        tokio::time::sleep(Duration::from_millis(40)).await;
+        handle.abort();
+
+        assert_eq!(
+            event_store
+                .load("TimeEntry-te-1")
+                .await
+                .unwrap()
+                .events
+                .len(),
+            4
+        );
+    }
+}
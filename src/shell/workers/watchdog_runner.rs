@@ -0,0 +1,89 @@
+// Runs the watchdog on a fixed interval.
+//
+// Each tick reads every probe, hands the readings to the watchdog and sends the alerts it
+// raises or resolves to the notifier. A probe that cannot be read is logged and left out of
+// the tick; an alert the notifier fails to send is logged and not retried, as the watchdog
+// only alerts once per stall.
+
+use crate::shared::application::watchdog::{Probe, Watchdog};
+use crate::shared::infrastructure::notifier::Notifier;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::task::JoinHandle;
+
+pub fn spawn(
+    probes: Vec<Arc<dyn Probe>>,
+    mut watchdog: Watchdog,
+    notifier: Arc<dyn Notifier>,
+    every: Duration,
+) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(every);
+        loop {
+            interval.tick().await;
+            let mut readings = Vec::with_capacity(probes.len());
+            for probe in &probes {
+                match probe.read().await {
+                    Ok(reading) => readings.push(reading),
+                    Err(error) => tracing::warn!(%error, "watchdog probe failed"),
+                }
+            }
+            let now = chrono::Utc::now().timestamp_millis();
+            for alert in watchdog.observe(&readings, now) {
+                if let Err(error) = notifier.notify(&alert).await {
+                    tracing::warn!(key = alert.key, %error, "watchdog alert not sent");
+                }
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod watchdog_runner_tests {
+    use super::*;
+    use crate::shared::application::watchdog::OutboxProbe;
+    use crate::shared::infrastructure::intent_outbox::in_memory::InMemoryDomainOutbox;
+    use crate::shared::infrastructure::intent_outbox::{DomainOutbox, OutboxRow, OutboxStatus};
+    use crate::shared::infrastructure::notifier::AlertStatus;
+    use crate::shared::infrastructure::notifier::in_memory::InMemoryNotifier;
+    use rstest::rstest;
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_alert_about_an_outbox_nothing_relays() {
+        let outbox = InMemoryDomainOutbox::new();
+        outbox
+            .enqueue(OutboxRow {
+                topic: "a".to_string(),
+                event_type: "e".to_string(),
+                event_version: 1,
+                stream_id: "s-1".to_string(),
+                stream_version: 1,
+                intent_no: 0,
+                occurred_at: 0,
+                payload: serde_json::Value::Null,
+                content_encoding: None,
+                status: OutboxStatus::Pending,
+                attempts: 0,
+                last_error: None,
+                published_at: None,
+            })
+            .await
+            .unwrap();
+        let notifier = InMemoryNotifier::new();
+
+        let handle = spawn(
+            vec![Arc::new(OutboxProbe::new("a", outbox, 0))],
+            Watchdog::new(0),
+            Arc::new(notifier.clone()),
+            Duration::from_millis(10),
+        );
+        tokio::time::sleep(Duration::from_millis(35)).await;
+        handle.abort();
+
+        let alerts = notifier.alerts();
+        assert_eq!(alerts.len(), 1);
+        assert_eq!(alerts[0].key, "outbox:a");
+        assert_eq!(alerts[0].status, AlertStatus::Firing);
+    }
+}
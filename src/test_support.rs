@@ -0,0 +1,69 @@
+// Test support for the crates building on this one: adapter crates checking their adapters
+// against the ports' contracts and the API crate testing against the in-memory adapters.
+//
+// Built for this crate's own tests and, through the `test-util` feature, for dependents, so
+// they reuse the fixtures this crate tests with instead of copying them:
+//
+//     [dev-dependencies]
+//     time_entries = { path = "..", features = ["test-util"] }
+
+/// Command builders and canonical events, built in code with their JSON embedded.
+pub use crate::tests::fixtures;
+
+/// Suites every event store and outbox adapter must pass, written against the ports.
+pub use crate::tests::contracts as conformance;
+
+/// A clock that only moves when told to.
+pub use crate::shared::infrastructure::clock::FixedClock;
+
+/// The in-memory adapters of every port.
+pub mod in_memory {
+    pub use crate::shared::infrastructure::api_audit_store::in_memory::InMemoryApiAuditStore;
+    pub use crate::shared::infrastructure::api_key_store::in_memory::InMemoryApiKeyStore;
+    pub use crate::shared::infrastructure::attachment_storage::in_memory::InMemoryAttachmentStorage;
+    pub use crate::shared::infrastructure::cold_storage::in_memory::InMemoryColdStorage;
+    pub use crate::shared::infrastructure::control_store::in_memory::InMemoryControlStore;
+    pub use crate::shared::infrastructure::event_store::in_memory::InMemoryEventStore;
+    pub use crate::shared::infrastructure::feature_flags::in_memory::InMemoryFeatureFlags;
+    pub use crate::shared::infrastructure::inbox::in_memory::InMemoryInbox;
+    pub use crate::shared::infrastructure::intent_outbox::in_memory::InMemoryDomainOutbox;
+    pub use crate::shared::infrastructure::job_store::in_memory::InMemoryJobStore;
+    pub use crate::shared::infrastructure::key_store::in_memory::InMemoryKeyStore;
+    pub use crate::shared::infrastructure::lease_store::in_memory::InMemoryLeaseStore;
+    pub use crate::shared::infrastructure::message_broker::in_memory::{
+        InMemoryConsumer, InMemoryMessageBroker,
+    };
+    pub use crate::shared::infrastructure::notifier::in_memory::InMemoryNotifier;
+    pub use crate::shared::infrastructure::policy_store::in_memory::InMemoryPolicyStore;
+    pub use crate::shared::infrastructure::projection_store::in_memory::InMemoryProjectionStore;
+    pub use crate::shared::infrastructure::query_cache::in_memory::InMemoryQueryCache;
+    pub use crate::shared::infrastructure::user_directory::in_memory::InMemoryUserDirectory;
+}
+
+#[cfg(test)]
+mod test_support_tests {
+    use super::fixtures::commands::set_started_at::SetStartedAtBuilder;
+    use super::in_memory::{InMemoryDomainOutbox, InMemoryEventStore};
+    use crate::modules::time_entries::use_cases::set_started_at::handler::SetStartedAtHandler;
+    use rstest::rstest;
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_run_a_handler_on_the_exported_adapters_and_fixtures() {
+        let event_store = InMemoryEventStore::new();
+        let handler = SetStartedAtHandler::new(event_store.clone(), InMemoryDomainOutbox::new());
+
+        handler
+            .handle("TimeEntry-te-1", SetStartedAtBuilder::new().build())
+            .await
+            .unwrap();
+
+        assert!(event_store.head().await.unwrap() > 0);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_expose_the_conformance_suites() {
+        super::conformance::parallel_appends_admit_exactly_one(InMemoryEventStore::new()).await;
+    }
+}
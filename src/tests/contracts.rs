@@ -0,0 +1,123 @@
+// Concurrency contracts every event store and outbox adapter must honour, written against the
+// ports so each adapter's suite can run them. The in-memory adapters run them here; the Postgres
+// adapters run them in their integration suites, against a database started through
+// testcontainers, where row locks and unique keys decide whether they hold.
+
+use crate::shared::infrastructure::event_store::{EventStore, EventStoreError};
+use crate::shared::infrastructure::intent_outbox::{DomainOutbox, OutboxError, OutboxRow};
+use std::sync::Arc;
+use tokio::task::JoinSet;
+
+pub const RACERS: usize = 16;
+
+/// `RACERS` tasks append to the same fresh stream at expected version 0: exactly one wins,
+/// every other gets a version mismatch against the winner's version, and only the winner's
+/// event is stored.
+pub async fn parallel_appends_admit_exactly_one<TStore>(store: TStore)
+where
+    TStore: EventStore<String> + 'static,
+{
+    let store = Arc::new(store);
+    let mut racers = JoinSet::new();
+    for racer in 0..RACERS {
+        let store = store.clone();
+        racers.spawn(async move {
+            let event = format!("appended-by-{racer}");
+            (event.clone(), store.append("Race-1", 0, &[event]).await)
+        });
+    }
+
+    let mut winners = Vec::new();
+    while let Some(joined) = racers.join_next().await {
+        match joined.unwrap() {
+            (event, Ok(appended)) => {
+                assert_eq!(appended.next_version, 1);
+                winners.push(event);
+            }
+            (_, Err(EventStoreError::VersionMismatch { expected, actual })) => {
+                assert_eq!((expected, actual), (0, 1));
+            }
+            (_, Err(other)) => panic!("unexpected append error: {other}"),
+        }
+    }
+
+    assert_eq!(winners.len(), 1, "exactly one append must win");
+    let stream = store.load("Race-1").await.unwrap();
+    assert_eq!(stream.version, 1);
+    assert_eq!(stream.events, winners);
+}
+
+/// `RACERS` tasks enqueue a row with the same `(stream_id, stream_version)`: exactly one is
+/// kept and every other is rejected as a duplicate.
+pub async fn parallel_enqueues_admit_exactly_one<TOutbox>(
+    outbox: TOutbox,
+    row: OutboxRow,
+) -> TOutbox
+where
+    TOutbox: DomainOutbox + 'static,
+{
+    let outbox = Arc::new(outbox);
+    let mut racers = JoinSet::new();
+    for _ in 0..RACERS {
+        let outbox = outbox.clone();
+        let row = row.clone();
+        racers.spawn(async move { outbox.enqueue(row).await });
+    }
+
+    let mut enqueued = 0;
+    while let Some(joined) = racers.join_next().await {
+        match joined.unwrap() {
+            Ok(()) => enqueued += 1,
+            Err(OutboxError::Duplicate {
+                stream_id,
+                stream_version,
+                intent_no,
+            }) => {
+                assert_eq!(
+                    (stream_id, stream_version, intent_no),
+                    (row.stream_id.clone(), row.stream_version, row.intent_no)
+                );
+            }
+            Err(other) => panic!("unexpected enqueue error: {other}"),
+        }
+    }
+
+    assert_eq!(enqueued, 1, "exactly one enqueue must win");
+    Arc::into_inner(outbox).expect("every racer has finished")
+}
+
+#[cfg(test)]
+mod in_memory_contracts_tests {
+    use super::*;
+    use crate::shared::infrastructure::event_store::in_memory::InMemoryEventStore;
+    use crate::shared::infrastructure::intent_outbox::OutboxStatus;
+    use crate::shared::infrastructure::intent_outbox::in_memory::InMemoryDomainOutbox;
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn in_memory_event_store_admits_exactly_one_parallel_append() {
+        parallel_appends_admit_exactly_one(InMemoryEventStore::<String>::new()).await;
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn in_memory_outbox_admits_exactly_one_parallel_enqueue() {
+        let row = OutboxRow {
+            topic: "time-entries.v1".to_string(),
+            event_type: "TimeEntryTagsSet".to_string(),
+            event_version: 1,
+            stream_id: "TimeEntry-te-1".to_string(),
+            stream_version: 1,
+            intent_no: 0,
+            occurred_at: 0,
+            payload: serde_json::Value::Null,
+            content_encoding: None,
+            status: OutboxStatus::Pending,
+            attempts: 0,
+            last_error: None,
+            published_at: None,
+        };
+
+        let outbox = parallel_enqueues_admit_exactly_one(InMemoryDomainOutbox::new(), row).await;
+
+        assert_eq!(outbox.rows().await.len(), 1);
+    }
+}
@@ -34,8 +34,8 @@ async fn lists_time_entries_by_user() {
     let receiver = event_tx.subscribe();
     tokio::spawn(projector.run(receiver));
 
-    let set_started_at = SetStartedAtHandler::new("time-entries", store.clone(), outbox.clone());
-    let set_ended_at = SetEndedAtHandler::new("time-entries", store.clone(), outbox);
+    let set_started_at = SetStartedAtHandler::new(store.clone(), outbox.clone());
+    let set_ended_at = SetEndedAtHandler::new(store.clone(), outbox);
 
     // Three entries with different started_at values
     let entries: Vec<(i64, i64)> = vec![(1_000, 61_000), (2_000, 62_000), (1_500, 61_500)];
@@ -82,7 +82,8 @@ async fn lists_time_entries_by_user() {
     let list = query_handler
         .list_by_user_id("user-fixed-0001", 0, 10, true)
         .await
-        .unwrap();
+        .unwrap()
+        .items;
 
     assert_eq!(list.len(), 3);
     // Descending by started_at: 2000 > 1500 > 1000
@@ -0,0 +1,251 @@
+use crate::shared::infrastructure::projection_store::ProjectionStore;
+use crate::shell::graphql::WsConfig;
+use crate::tests::e2e::test_app::{TENANT_ID, TestApp, WsFrame};
+use axum::http::StatusCode;
+use serde_json::json;
+use std::time::Duration;
+use uuid::Uuid;
+
+#[tokio::test]
+async fn registers_and_lists_entries_over_http() {
+    let app = TestApp::spawn().await;
+    let first = Uuid::now_v7().to_string();
+    let second = Uuid::now_v7().to_string();
+
+    assert_eq!(
+        app.register_entry("user-1", &first, 1_000, 61_000).await,
+        StatusCode::OK
+    );
+    assert_eq!(
+        app.register_entry("user-1", &second, 120_000, 180_000)
+            .await,
+        StatusCode::OK
+    );
+
+    let entries = app.list_entries("user-1").await;
+    let ids: Vec<&str> = entries.iter().map(|e| e.time_entry_id.as_str()).collect();
+    assert_eq!(ids, vec![second.as_str(), first.as_str()]);
+    assert!(app.list_entries("user-2").await.is_empty());
+}
+
+#[tokio::test]
+async fn rejects_entries_without_a_uuid_v7_id() {
+    let app = TestApp::spawn().await;
+
+    let status = app.register_entry("user-1", "te-1", 1_000, 61_000).await;
+
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+}
+
+#[tokio::test]
+async fn serves_the_same_entries_over_graphql() {
+    let app = TestApp::spawn().await;
+    let id = Uuid::now_v7().to_string();
+    app.register_entry("user-1", &id, 1_000, 61_000).await;
+
+    let body = app
+        .graphql(
+            "user-1",
+            "{ listTimeEntries { timeEntryId startedAt endedAt } }",
+        )
+        .await;
+
+    assert_eq!(
+        body,
+        json!({
+            "data": {
+                "listTimeEntries": [
+                    { "timeEntryId": id, "startedAt": 1_000, "endedAt": 61_000 }
+                ]
+            }
+        })
+    );
+}
+
+#[tokio::test]
+async fn shreds_the_personal_data_of_forgotten_users() {
+    let app = TestApp::spawn().await;
+    let id = Uuid::now_v7().to_string();
+    app.register_entry("user-1", &id, 1_000, 61_000).await;
+    assert_eq!(app.list_entries("user-1").await[0].created_by, "user-1");
+    let stored =
+        serde_json::to_string(&app.event_store.load_all_from(0).await.unwrap()[0].event).unwrap();
+    assert!(stored.contains(r#""user_id":"pii:"#), "{stored}");
+    assert!(stored.contains(r#""created_by":"pii:"#), "{stored}");
+
+    let forgotten = app
+        .admin_request("POST", "/api/v1/admin/users/user-1/forget")
+        .await;
+    assert_eq!(forgotten.status, StatusCode::NO_CONTENT);
+
+    for stored in app.event_store.load_all_from(0).await.unwrap() {
+        let event = serde_json::to_string(&stored.event).unwrap();
+        assert!(
+            !format!("{} {event}", stored.stream_id).contains("user-1"),
+            "{event}"
+        );
+    }
+    let rows = app
+        .state
+        .list_time_entries_handler
+        .store()
+        .state()
+        .await
+        .unwrap()
+        .unwrap()
+        .rows;
+    assert_eq!(rows[&id].user_id, "[forgotten]");
+    assert!(!serde_json::to_string(&rows).unwrap().contains("user-1"));
+    assert!(app.list_entries("user-1").await.is_empty());
+    let export = app
+        .admin_request("GET", "/api/v1/admin/users/user-1/data-export")
+        .await;
+    assert_eq!(export.status, StatusCode::OK);
+    let exported = export.body.as_str().unwrap();
+    assert!(!exported.contains(r#""kind":"time_entry"#), "{exported}");
+}
+
+#[tokio::test]
+async fn syncs_offline_changes_and_pulls_them_back() {
+    let app = TestApp::spawn().await;
+    let id = Uuid::now_v7().to_string();
+
+    let pushed = app
+        .request(
+            "POST",
+            "/api/v1/sync",
+            "user-1",
+            Some(json!({ "mutations": [
+                { "op": "set_started_at", "time_entry_id": id, "base_version": 0, "started_at": 1_000 },
+                { "op": "set_ended_at", "time_entry_id": id, "base_version": 0, "ended_at": 61_000 },
+            ]})),
+        )
+        .await;
+    assert_eq!(pushed.status, StatusCode::OK);
+    assert_eq!(pushed.body["results"][1]["status"], "applied");
+
+    app.list_entries("user-1").await;
+    let pulled = app
+        .request(
+            "POST",
+            "/api/v1/sync",
+            "user-1",
+            Some(json!({ "since": 0 })),
+        )
+        .await;
+    assert_eq!(pulled.body["changes"][0]["time_entry_id"], id.as_str());
+    assert_eq!(pulled.body["changes"][0]["ended_at"], 61_000);
+
+    let again = app
+        .request(
+            "POST",
+            "/api/v1/sync",
+            "user-1",
+            Some(json!({ "since": pulled.body["watermark"] })),
+        )
+        .await;
+    assert_eq!(again.body["changes"], json!([]));
+}
+
+#[tokio::test]
+async fn pushes_subscription_updates_over_websocket() {
+    let app = TestApp::spawn().await;
+    let mut ws = app.graphql_ws().await;
+    ws.send(json!({
+        "type": "connection_init",
+        "payload": { "x-user-id": "user-1", "x-tenant-id": TENANT_ID },
+    }))
+    .await;
+    assert_eq!(
+        ws.receive().await,
+        WsFrame::Text(json!({ "type": "connection_ack" }))
+    );
+    ws.send(json!({
+        "id": "1",
+        "type": "subscribe",
+        "payload": { "query": "subscription { timeEntryUpdated { timeEntryId endedAt } }" },
+    }))
+    .await;
+    // Answered once the subscribe before it is being served.
+    ws.send(json!({ "type": "ping" })).await;
+    assert_eq!(ws.receive().await, WsFrame::Text(json!({ "type": "pong" })));
+
+    let id = Uuid::now_v7().to_string();
+    app.register_entry("user-1", &id, 1_000, 61_000).await;
+
+    let mut ended_at = Vec::new();
+    while ended_at.last() != Some(&json!(61_000)) {
+        let WsFrame::Text(message) = ws.receive().await else {
+            panic!("connection closed");
+        };
+        assert_eq!(message["type"], "next", "{message}");
+        let entry = &message["payload"]["data"]["timeEntryUpdated"];
+        assert_eq!(entry["timeEntryId"], id.as_str());
+        ended_at.push(entry["endedAt"].clone());
+    }
+}
+
+#[tokio::test]
+async fn closes_websockets_without_identity_on_connection_init() {
+    let app = TestApp::spawn().await;
+    let mut ws = app.graphql_ws().await;
+
+    ws.send(json!({ "type": "connection_init", "payload": {} }))
+        .await;
+
+    assert_eq!(
+        ws.receive().await,
+        WsFrame::Close(1002, "Unauthorized".to_string())
+    );
+}
+
+#[tokio::test]
+async fn closes_websockets_that_stop_pinging() {
+    let app = TestApp::spawn_with(WsConfig {
+        keepalive_timeout: Some(Duration::from_millis(100)),
+    })
+    .await;
+    let mut ws = app.graphql_ws().await;
+    ws.send(json!({
+        "type": "connection_init",
+        "payload": { "x-user-id": "user-1", "x-tenant-id": TENANT_ID },
+    }))
+    .await;
+    assert_eq!(
+        ws.receive().await,
+        WsFrame::Text(json!({ "type": "connection_ack" }))
+    );
+
+    assert_eq!(
+        ws.receive().await,
+        WsFrame::Close(3008, "timeout".to_string())
+    );
+}
+
+#[tokio::test]
+async fn keeps_mutations_off_the_websocket() {
+    let app = TestApp::spawn().await;
+    let mut ws = app.graphql_ws().await;
+    ws.send(json!({
+        "type": "connection_init",
+        "payload": { "x-user-id": "user-1", "x-tenant-id": TENANT_ID },
+    }))
+    .await;
+    ws.receive().await;
+
+    ws.send(json!({
+        "id": "1",
+        "type": "subscribe",
+        "payload": { "query": "mutation { createTag(name: \"ci\") { tagId } }" },
+    }))
+    .await;
+
+    let WsFrame::Text(message) = ws.receive().await else {
+        panic!("connection closed");
+    };
+    assert_eq!(message["id"], "1");
+    assert_eq!(
+        message["payload"]["errors"][0]["message"],
+        "Schema is not configured for mutations."
+    );
+}
@@ -0,0 +1,388 @@
+// The full shell (REST and GraphQL routers, middleware, list projector) served on an ephemeral
+// port over in-memory adapters, with typed helpers speaking plain HTTP/1.1 to it. E2e tests
+// use this instead of hand-wiring AppState and calling handlers directly.
+//
+// Time entry events are stored crypto-shredded and the projector follows the revealed feed,
+// as in `main`; forgetting a user erases the read models of the state too. There are no database adapters yet; once there are, `spawn` is the place to
+// swap them in.
+
+use crate::modules::time_entries::core::events::TimeEntryEvent;
+use crate::modules::time_entries::use_cases::erase_personal_data::eraser::TimeEntriesEraser;
+use crate::modules::time_entries::use_cases::list_time_entries::inbound::api_v1::TimeEntryV1;
+use crate::modules::time_entries::use_cases::list_time_entries::projection::ListTimeEntriesState;
+use crate::modules::time_entries::use_cases::list_time_entries::projector::{
+    ListTimeEntriesProjector, ProjectionTechnicalEvent,
+};
+use crate::shared::application::forget_user::ForgetUserHandler;
+use crate::shared::infrastructure::event_store::StoredEvent;
+use crate::shared::infrastructure::event_store::crypto_shredding::{
+    AesGcmFieldCipher, CryptoShreddingEventStore,
+};
+use crate::shared::infrastructure::event_store::in_memory::InMemoryEventStore;
+use crate::shared::infrastructure::key_store::in_memory::InMemoryKeyStore;
+use crate::shared::infrastructure::projection_store::ProjectionStore;
+use crate::shared::infrastructure::projection_store::in_memory::InMemoryProjectionStore;
+use crate::shell::graphql::WsConfig;
+use crate::shell::http::routes::{API_PREFIX, RouterBuilder};
+use crate::shell::state::AppState;
+use crate::shell::workers::revealed_feed_runner;
+use crate::tests::fixtures::tags::make_test_app_state_with;
+use axum::http::StatusCode;
+use serde_json::{Value, json};
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::broadcast;
+use tokio::task::JoinHandle;
+
+pub const TENANT_ID: &str = "tenant-e2e";
+
+#[derive(Debug)]
+pub struct TestResponse {
+    pub status: StatusCode,
+    pub body: Value,
+}
+
+pub struct TestApp {
+    pub address: SocketAddr,
+    pub state: AppState,
+    /// The time entry events as stored, with personal data encrypted.
+    pub event_store: InMemoryEventStore<TimeEntryEvent>,
+    projection_store: InMemoryProjectionStore<ListTimeEntriesState>,
+    tasks: Vec<JoinHandle<()>>,
+}
+
+/// A frame the server sent over a WebSocket.
+#[derive(Debug, PartialEq)]
+pub enum WsFrame {
+    Text(Value),
+    Close(u16, String),
+}
+
+/// A client end of a WebSocket: masked frames out, unfragmented frames in.
+pub struct TestWebSocket {
+    stream: TcpStream,
+}
+
+impl TestApp {
+    pub async fn spawn() -> Self {
+        Self::spawn_with(WsConfig::default()).await
+    }
+
+    pub async fn spawn_with(ws: WsConfig) -> Self {
+        let (stored_event_tx, _) = broadcast::channel::<StoredEvent<TimeEntryEvent>>(1024);
+        let event_store =
+            InMemoryEventStore::<TimeEntryEvent>::new_with_sender(stored_event_tx.clone());
+        let key_store = InMemoryKeyStore::new();
+        let shredding_store = CryptoShreddingEventStore::new(
+            event_store.clone(),
+            key_store.clone(),
+            AesGcmFieldCipher,
+        );
+        let (event_tx, _) = broadcast::channel::<StoredEvent<TimeEntryEvent>>(1024);
+        let feed_task = revealed_feed_runner::spawn(
+            shredding_store.clone(),
+            stored_event_tx.subscribe(),
+            event_tx.clone(),
+        );
+        let projection_store = InMemoryProjectionStore::<ListTimeEntriesState>::new();
+        let mut state = make_test_app_state_with(shredding_store.clone(), projection_store.clone());
+        let eraser = TimeEntriesEraser::new(
+            vec![projection_store.clone()],
+            state.user_stats_handler.store().clone(),
+            state.time_entry_comments_handler.store().clone(),
+            state.time_entry_attachments_handler.store().clone(),
+        );
+        state.forget_user_handler = ForgetUserHandler::new(key_store).with_eraser(Arc::new(eraser));
+
+        let (tech_tx, _) = broadcast::channel::<ProjectionTechnicalEvent>(256);
+        let projector = ListTimeEntriesProjector::new(
+            "list_time_entries",
+            projection_store.clone(),
+            shredding_store,
+            tech_tx,
+        )
+        .with_updates(state.time_entry_updates.clone());
+        let projector_task = tokio::spawn(projector.run(event_tx.subscribe()));
+
+        let app = RouterBuilder::new(state.clone()).with_ws(ws).build();
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        let server_task = tokio::spawn(async move {
+            axum::serve(listener, app).await.unwrap();
+        });
+
+        Self {
+            address,
+            state,
+            event_store,
+            projection_store,
+            tasks: vec![feed_task, projector_task, server_task],
+        }
+    }
+
+    /// Starts and ends entry `time_entry_id` (a UUID v7) as `user_id`. Returns the first
+    /// non-200 status, or 200 when both succeeded.
+    pub async fn register_entry(
+        &self,
+        user_id: &str,
+        time_entry_id: &str,
+        started_at: i64,
+        ended_at: i64,
+    ) -> StatusCode {
+        let started = self
+            .request(
+                "PUT",
+                &format!("{API_PREFIX}/time-entries/{time_entry_id}/start"),
+                user_id,
+                Some(json!({ "started_at": started_at })),
+            )
+            .await;
+        if started.status != StatusCode::OK {
+            return started.status;
+        }
+        self.request(
+            "PUT",
+            &format!("{API_PREFIX}/time-entries/{time_entry_id}/end"),
+            user_id,
+            Some(json!({ "ended_at": ended_at })),
+        )
+        .await
+        .status
+    }
+
+    /// `user_id`'s entries, newest first, once the projector has caught up with every
+    /// appended event.
+    pub async fn list_entries(&self, user_id: &str) -> Vec<TimeEntryV1> {
+        self.wait_for_projection().await;
+        let response = self
+            .request(
+                "GET",
+                &format!("{API_PREFIX}/list-time-entries"),
+                user_id,
+                None,
+            )
+            .await;
+        assert_eq!(response.status, StatusCode::OK, "{:?}", response.body);
+        serde_json::from_value(response.body["items"].clone()).unwrap()
+    }
+
+    /// Runs `query` as `user_id` and returns the GraphQL response body.
+    pub async fn graphql(&self, user_id: &str, query: &str) -> Value {
+        self.wait_for_projection().await;
+        let response = self
+            .request("POST", "/gql", user_id, Some(json!({ "query": query })))
+            .await;
+        assert_eq!(response.status, StatusCode::OK, "{:?}", response.body);
+        response.body
+    }
+
+    /// Sends one request with the identity headers of `user_id` in `TENANT_ID`.
+    pub async fn request(
+        &self,
+        method: &str,
+        path: &str,
+        user_id: &str,
+        body: Option<Value>,
+    ) -> TestResponse {
+        self.request_as(method, path, user_id, "employee", body)
+            .await
+    }
+
+    /// Sends one request as an admin of `TENANT_ID`.
+    pub async fn admin_request(&self, method: &str, path: &str) -> TestResponse {
+        self.request_as(method, path, "admin-e2e", "admin", None)
+            .await
+    }
+
+    async fn request_as(
+        &self,
+        method: &str,
+        path: &str,
+        user_id: &str,
+        role: &str,
+        body: Option<Value>,
+    ) -> TestResponse {
+        let body = body.map(|body| body.to_string()).unwrap_or_default();
+        let request = format!(
+            "{method} {path} HTTP/1.1\r\n\
+             host: {address}\r\n\
+             connection: close\r\n\
+             x-user-id: {user_id}\r\n\
+             x-user-role: {role}\r\n\
+             x-tenant-id: {TENANT_ID}\r\n\
+             content-type: application/json\r\n\
+             content-length: {length}\r\n\
+             \r\n\
+             {body}",
+            address = self.address,
+            length = body.len(),
+        );
+        let mut stream = TcpStream::connect(self.address).await.unwrap();
+        stream.write_all(request.as_bytes()).await.unwrap();
+        let mut raw = Vec::new();
+        stream.read_to_end(&mut raw).await.unwrap();
+        parse_response(&raw)
+    }
+
+    /// Upgrades a connection to `/gql/ws` speaking `graphql-transport-ws`; nothing is sent
+    /// yet, not even `connection_init`.
+    pub async fn graphql_ws(&self) -> TestWebSocket {
+        let request = format!(
+            "GET /gql/ws HTTP/1.1\r\n\
+             host: {address}\r\n\
+             connection: Upgrade\r\n\
+             upgrade: websocket\r\n\
+             sec-websocket-version: 13\r\n\
+             sec-websocket-key: dGhlIHNhbXBsZSBub25jZQ==\r\n\
+             sec-websocket-protocol: graphql-transport-ws\r\n\
+             \r\n",
+            address = self.address,
+        );
+        let mut stream = TcpStream::connect(self.address).await.unwrap();
+        stream.write_all(request.as_bytes()).await.unwrap();
+        let mut head = Vec::new();
+        while !head.ends_with(b"\r\n\r\n") {
+            head.push(stream.read_u8().await.unwrap());
+        }
+        let head = String::from_utf8(head).unwrap();
+        assert!(head.starts_with("HTTP/1.1 101"), "{head}");
+        TestWebSocket { stream }
+    }
+
+    async fn wait_for_projection(&self) {
+        let appended = self.event_store.load_all_from(0).await.unwrap().len() as u64;
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while self.projection_store.checkpoint().await.unwrap() < appended {
+            assert!(
+                Instant::now() < deadline,
+                "projector did not catch up in time"
+            );
+            tokio::time::sleep(Duration::from_millis(5)).await;
+        }
+    }
+}
+
+impl Drop for TestApp {
+    fn drop(&mut self) {
+        for task in &self.tasks {
+            task.abort();
+        }
+    }
+}
+
+impl TestWebSocket {
+    pub async fn send(&mut self, message: Value) {
+        const MASK: [u8; 4] = [0x1f, 0x2e, 0x3d, 0x4c];
+        let payload = message.to_string().into_bytes();
+        let mut frame = vec![0x81];
+        match payload.len() {
+            len @ 0..126 => frame.push(0x80 | len as u8),
+            len @ 126..65_536 => {
+                frame.push(0x80 | 126);
+                frame.extend((len as u16).to_be_bytes());
+            }
+            len => {
+                frame.push(0x80 | 127);
+                frame.extend((len as u64).to_be_bytes());
+            }
+        }
+        frame.extend(MASK);
+        frame.extend(payload.iter().zip(MASK.iter().cycle()).map(|(b, m)| b ^ m));
+        self.stream.write_all(&frame).await.unwrap();
+    }
+
+    /// The next text or close frame, failing after five seconds.
+    pub async fn receive(&mut self) -> WsFrame {
+        tokio::time::timeout(Duration::from_secs(5), self.read_frame())
+            .await
+            .expect("no WebSocket frame within five seconds")
+    }
+
+    async fn read_frame(&mut self) -> WsFrame {
+        let opcode = self.stream.read_u8().await.unwrap() & 0x0f;
+        let len = match self.stream.read_u8().await.unwrap() & 0x7f {
+            126 => self.stream.read_u16().await.unwrap() as usize,
+            127 => self.stream.read_u64().await.unwrap() as usize,
+            len => len as usize,
+        };
+        let mut payload = vec![0; len];
+        self.stream.read_exact(&mut payload).await.unwrap();
+        match opcode {
+            0x1 => WsFrame::Text(serde_json::from_slice(&payload).unwrap()),
+            0x8 => WsFrame::Close(
+                u16::from_be_bytes([payload[0], payload[1]]),
+                String::from_utf8_lossy(&payload[2..]).to_string(),
+            ),
+            other => panic!("unexpected WebSocket opcode {other:#x}"),
+        }
+    }
+}
+
+/// Parses a `connection: close` response: status line, headers, then the rest as the body,
+/// de-chunked when needed. Empty bodies become `Value::Null`, non-JSON ones a string.
+fn parse_response(raw: &[u8]) -> TestResponse {
+    let raw = String::from_utf8_lossy(raw);
+    let (head, body) = raw.split_once("\r\n\r\n").unwrap();
+    let mut lines = head.lines();
+    let status = lines.next().unwrap().split(' ').nth(1).unwrap();
+    let chunked = lines.any(|line| line.eq_ignore_ascii_case("transfer-encoding: chunked"));
+    let body = if chunked {
+        dechunk(body)
+    } else {
+        body.to_string()
+    };
+    TestResponse {
+        status: StatusCode::from_u16(status.parse().unwrap()).unwrap(),
+        body: if body.is_empty() {
+            Value::Null
+        } else {
+            serde_json::from_str(&body).unwrap_or(Value::String(body))
+        },
+    }
+}
+
+fn dechunk(mut body: &str) -> String {
+    let mut out = String::new();
+    loop {
+        let (size, rest) = body.split_once("\r\n").unwrap();
+        let size = usize::from_str_radix(size.trim(), 16).unwrap();
+        if size == 0 {
+            return out;
+        }
+        out.push_str(&rest[..size]);
+        body = &rest[size + 2..];
+    }
+}
+
+#[cfg(test)]
+mod test_app_tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(
+        "HTTP/1.1 204 No Content\r\ncontent-length: 0\r\n\r\n",
+        StatusCode::NO_CONTENT,
+        Value::Null
+    )]
+    #[case("HTTP/1.1 200 OK\r\n\r\n{\"a\":1}", StatusCode::OK, json!({ "a": 1 }))]
+    #[case("HTTP/1.1 422 Unprocessable Entity\r\n\r\nnope", StatusCode::UNPROCESSABLE_ENTITY, json!("nope"))]
+    #[case(
+        "HTTP/1.1 200 OK\r\ntransfer-encoding: chunked\r\n\r\n3\r\n[1,\r\n2\r\n2]\r\n0\r\n\r\n",
+        StatusCode::OK,
+        json!([1, 2])
+    )]
+    fn it_should_parse_responses(
+        #[case] raw: &str,
+        #[case] status: StatusCode,
+        #[case] body: Value,
+    ) {
+        let response = parse_response(raw.as_bytes());
+
+        assert_eq!(response.status, status);
+        assert_eq!(response.body, body);
+    }
+}
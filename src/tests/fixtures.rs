@@ -6,8 +6,16 @@ pub mod events {
     pub mod time_entry_start_set_v1;
 }
 pub mod commands {
+    pub mod add_time_entry_attachment;
+    pub mod add_time_entry_comment;
+    pub mod approve_time_entry;
+    pub mod reject_time_entry;
+    pub mod set_breaks;
     pub mod set_ended_at;
+    pub mod set_hourly_rate;
     pub mod set_started_at;
     pub mod set_time_entry_tags;
 }
+pub mod crypto;
+pub mod projections;
 pub mod tags;
@@ -0,0 +1,152 @@
+use crate::modules::time_entries::use_cases::add_time_entry_attachment::command::AddTimeEntryAttachment;
+use crate::shared::auth::rbac::{Principal, Role};
+use crate::shared::core::primitives::TimeEntryId;
+use serde::Deserialize;
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct AddTimeEntryAttachmentDto {
+    pub time_entry_id: String,
+    pub attachment_id: String,
+    pub author_id: String,
+    pub file_name: String,
+    pub content_type: String,
+    pub size_bytes: u64,
+}
+
+/// The fixture as JSON, embedded so the builder works from any crate or working directory.
+pub const ADD_TIME_ENTRY_ATTACHMENT_JSON: &str =
+    include_str!("json/add_time_entry_attachment.json");
+
+pub struct AddTimeEntryAttachmentBuilder {
+    inner: AddTimeEntryAttachment,
+}
+
+impl Default for AddTimeEntryAttachmentBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[allow(dead_code)]
+impl AddTimeEntryAttachmentBuilder {
+    /// The canonical command, as `ADD_TIME_ENTRY_ATTACHMENT_JSON` describes it.
+    pub fn new() -> Self {
+        Self::from_dto(AddTimeEntryAttachmentDto {
+            time_entry_id: "te-fixed-0001".to_string(),
+            attachment_id: "attachment-fixed-0001".to_string(),
+            author_id: "user-fixed-0001".to_string(),
+            file_name: "receipt.pdf".to_string(),
+            content_type: "application/pdf".to_string(),
+            size_bytes: 48213,
+        })
+    }
+
+    /// Starts from a fixture shaped like `ADD_TIME_ENTRY_ATTACHMENT_JSON`.
+    pub fn from_json_str(json: &str) -> serde_json::Result<Self> {
+        serde_json::from_str(json).map(Self::from_dto)
+    }
+
+    fn from_dto(dto: AddTimeEntryAttachmentDto) -> Self {
+        Self {
+            inner: AddTimeEntryAttachment {
+                time_entry_id: dto.time_entry_id.into(),
+                attachment_id: dto.attachment_id,
+                author: Principal::new(dto.author_id, Role::Employee),
+                file_name: dto.file_name,
+                content_type: dto.content_type,
+                size_bytes: Some(dto.size_bytes),
+                added_at: 1700000000000,
+            },
+        }
+    }
+
+    pub fn time_entry_id(mut self, v: impl Into<TimeEntryId>) -> Self {
+        self.inner.time_entry_id = v.into();
+        self
+    }
+
+    pub fn attachment_id(mut self, v: impl Into<String>) -> Self {
+        self.inner.attachment_id = v.into();
+        self
+    }
+
+    pub fn author(mut self, v: Principal) -> Self {
+        self.inner.author = v;
+        self
+    }
+
+    pub fn file_name(mut self, v: impl Into<String>) -> Self {
+        self.inner.file_name = v.into();
+        self
+    }
+
+    pub fn content_type(mut self, v: impl Into<String>) -> Self {
+        self.inner.content_type = v.into();
+        self
+    }
+
+    pub fn size_bytes(mut self, v: Option<u64>) -> Self {
+        self.inner.size_bytes = v;
+        self
+    }
+
+    pub fn added_at(mut self, v: i64) -> Self {
+        self.inner.added_at = v;
+        self
+    }
+
+    pub fn build(self) -> AddTimeEntryAttachment {
+        self.inner
+    }
+}
+
+#[cfg(test)]
+mod add_time_entry_attachment_builder_tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    fn new_matches_the_embedded_json() {
+        let from_json =
+            AddTimeEntryAttachmentBuilder::from_json_str(ADD_TIME_ENTRY_ATTACHMENT_JSON)
+                .unwrap()
+                .build();
+        assert_eq!(from_json, AddTimeEntryAttachmentBuilder::new().build());
+    }
+
+    #[rstest]
+    fn default_delegates_to_new_and_parses_json() {
+        let built = AddTimeEntryAttachmentBuilder::default().build();
+        assert_eq!(built.time_entry_id, "te-fixed-0001");
+        assert_eq!(built.attachment_id, "attachment-fixed-0001");
+        assert_eq!(
+            built.author,
+            Principal::new("user-fixed-0001", Role::Employee)
+        );
+        assert_eq!(built.file_name, "receipt.pdf");
+        assert_eq!(built.content_type, "application/pdf");
+        assert_eq!(built.size_bytes, Some(48_213));
+        assert_eq!(built.added_at, 1700000000000);
+    }
+
+    #[rstest]
+    fn setters_override_all_fields() {
+        let custom = AddTimeEntryAttachmentBuilder::new()
+            .time_entry_id("tid-123")
+            .attachment_id("a-123")
+            .author(Principal::new("manager-1", Role::Manager))
+            .file_name("screen.png")
+            .content_type("image/png")
+            .size_bytes(None)
+            .added_at(2222)
+            .build();
+
+        assert_eq!(custom.time_entry_id, "tid-123");
+        assert_eq!(custom.attachment_id, "a-123");
+        assert_eq!(custom.author, Principal::new("manager-1", Role::Manager));
+        assert_eq!(custom.file_name, "screen.png");
+        assert_eq!(custom.content_type, "image/png");
+        assert_eq!(custom.size_bytes, None);
+        assert_eq!(custom.added_at, 2222);
+    }
+}
@@ -0,0 +1,128 @@
+use crate::modules::time_entries::use_cases::add_time_entry_comment::command::AddTimeEntryComment;
+use crate::shared::auth::rbac::{Principal, Role};
+use crate::shared::core::primitives::TimeEntryId;
+use serde::Deserialize;
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct AddTimeEntryCommentDto {
+    pub time_entry_id: String,
+    pub comment_id: String,
+    pub author_id: String,
+    pub body: String,
+}
+
+/// The fixture as JSON, embedded so the builder works from any crate or working directory.
+pub const ADD_TIME_ENTRY_COMMENT_JSON: &str = include_str!("json/add_time_entry_comment.json");
+
+pub struct AddTimeEntryCommentBuilder {
+    inner: AddTimeEntryComment,
+}
+
+impl Default for AddTimeEntryCommentBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[allow(dead_code)]
+impl AddTimeEntryCommentBuilder {
+    /// The canonical command, as `ADD_TIME_ENTRY_COMMENT_JSON` describes it.
+    pub fn new() -> Self {
+        Self::from_dto(AddTimeEntryCommentDto {
+            time_entry_id: "te-fixed-0001".to_string(),
+            comment_id: "comment-fixed-0001".to_string(),
+            author_id: "manager-fixed-0001".to_string(),
+            body: "Please split this entry per project.".to_string(),
+        })
+    }
+
+    /// Starts from a fixture shaped like `ADD_TIME_ENTRY_COMMENT_JSON`.
+    pub fn from_json_str(json: &str) -> serde_json::Result<Self> {
+        serde_json::from_str(json).map(Self::from_dto)
+    }
+
+    fn from_dto(dto: AddTimeEntryCommentDto) -> Self {
+        Self {
+            inner: AddTimeEntryComment {
+                time_entry_id: dto.time_entry_id.into(),
+                comment_id: dto.comment_id,
+                author: Principal::new(dto.author_id, Role::Manager),
+                body: dto.body,
+                added_at: 1700000000000,
+            },
+        }
+    }
+
+    pub fn time_entry_id(mut self, v: impl Into<TimeEntryId>) -> Self {
+        self.inner.time_entry_id = v.into();
+        self
+    }
+
+    pub fn comment_id(mut self, v: impl Into<String>) -> Self {
+        self.inner.comment_id = v.into();
+        self
+    }
+
+    pub fn author(mut self, v: Principal) -> Self {
+        self.inner.author = v;
+        self
+    }
+
+    pub fn body(mut self, v: impl Into<String>) -> Self {
+        self.inner.body = v.into();
+        self
+    }
+
+    pub fn added_at(mut self, v: i64) -> Self {
+        self.inner.added_at = v;
+        self
+    }
+
+    pub fn build(self) -> AddTimeEntryComment {
+        self.inner
+    }
+}
+
+#[cfg(test)]
+mod add_time_entry_comment_builder_tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    fn new_matches_the_embedded_json() {
+        let from_json = AddTimeEntryCommentBuilder::from_json_str(ADD_TIME_ENTRY_COMMENT_JSON)
+            .unwrap()
+            .build();
+        assert_eq!(from_json, AddTimeEntryCommentBuilder::new().build());
+    }
+
+    #[rstest]
+    fn default_delegates_to_new_and_parses_json() {
+        let built = AddTimeEntryCommentBuilder::default().build();
+        assert_eq!(built.time_entry_id, "te-fixed-0001");
+        assert_eq!(built.comment_id, "comment-fixed-0001");
+        assert_eq!(
+            built.author,
+            Principal::new("manager-fixed-0001", Role::Manager)
+        );
+        assert_eq!(built.body, "Please split this entry per project.");
+        assert_eq!(built.added_at, 1700000000000);
+    }
+
+    #[rstest]
+    fn setters_override_all_fields() {
+        let custom = AddTimeEntryCommentBuilder::new()
+            .time_entry_id("tid-123")
+            .comment_id("c-123")
+            .author(Principal::new("u-1", Role::Employee))
+            .body("Done.")
+            .added_at(2222)
+            .build();
+
+        assert_eq!(custom.time_entry_id, "tid-123");
+        assert_eq!(custom.comment_id, "c-123");
+        assert_eq!(custom.author, Principal::new("u-1", Role::Employee));
+        assert_eq!(custom.body, "Done.");
+        assert_eq!(custom.added_at, 2222);
+    }
+}
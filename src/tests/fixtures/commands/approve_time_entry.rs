@@ -0,0 +1,106 @@
+use crate::modules::time_entries::use_cases::approve_time_entry::command::ApproveTimeEntry;
+use crate::shared::auth::rbac::{Principal, Role};
+use crate::shared::core::primitives::TimeEntryId;
+use serde::Deserialize;
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ApproveTimeEntryDto {
+    pub time_entry_id: String,
+    pub approver_id: String,
+}
+
+/// The fixture as JSON, embedded so the builder works from any crate or working directory.
+pub const APPROVE_TIME_ENTRY_JSON: &str = include_str!("json/approve_time_entry.json");
+
+pub struct ApproveTimeEntryBuilder {
+    inner: ApproveTimeEntry,
+}
+
+impl Default for ApproveTimeEntryBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[allow(dead_code)]
+impl ApproveTimeEntryBuilder {
+    /// The canonical command, as `APPROVE_TIME_ENTRY_JSON` describes it.
+    pub fn new() -> Self {
+        Self::from_dto(ApproveTimeEntryDto {
+            time_entry_id: "te-fixed-0001".to_string(),
+            approver_id: "manager-fixed-0001".to_string(),
+        })
+    }
+
+    /// Starts from a fixture shaped like `APPROVE_TIME_ENTRY_JSON`.
+    pub fn from_json_str(json: &str) -> serde_json::Result<Self> {
+        serde_json::from_str(json).map(Self::from_dto)
+    }
+
+    fn from_dto(dto: ApproveTimeEntryDto) -> Self {
+        Self {
+            inner: ApproveTimeEntry {
+                time_entry_id: dto.time_entry_id.into(),
+                approver: Principal::new(dto.approver_id, Role::Manager),
+                approved_at: 1700000000000,
+            },
+        }
+    }
+
+    pub fn time_entry_id(mut self, v: impl Into<TimeEntryId>) -> Self {
+        self.inner.time_entry_id = v.into();
+        self
+    }
+
+    pub fn approver(mut self, v: Principal) -> Self {
+        self.inner.approver = v;
+        self
+    }
+
+    pub fn approved_at(mut self, v: i64) -> Self {
+        self.inner.approved_at = v;
+        self
+    }
+
+    pub fn build(self) -> ApproveTimeEntry {
+        self.inner
+    }
+}
+
+#[cfg(test)]
+mod approve_time_entry_builder_tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    fn new_matches_the_embedded_json() {
+        let from_json = ApproveTimeEntryBuilder::from_json_str(APPROVE_TIME_ENTRY_JSON)
+            .unwrap()
+            .build();
+        assert_eq!(from_json, ApproveTimeEntryBuilder::new().build());
+    }
+
+    #[rstest]
+    fn default_delegates_to_new_and_parses_json() {
+        let built = ApproveTimeEntryBuilder::default().build();
+        assert_eq!(built.time_entry_id, "te-fixed-0001");
+        assert_eq!(
+            built.approver,
+            Principal::new("manager-fixed-0001", Role::Manager)
+        );
+        assert_eq!(built.approved_at, 1700000000000);
+    }
+
+    #[rstest]
+    fn setters_override_all_fields() {
+        let custom = ApproveTimeEntryBuilder::new()
+            .time_entry_id("tid-123")
+            .approver(Principal::new("admin-1", Role::Admin))
+            .approved_at(2222)
+            .build();
+
+        assert_eq!(custom.time_entry_id, "tid-123");
+        assert_eq!(custom.approver, Principal::new("admin-1", Role::Admin));
+        assert_eq!(custom.approved_at, 2222);
+    }
+}
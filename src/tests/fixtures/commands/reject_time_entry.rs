@@ -0,0 +1,105 @@
+use crate::modules::time_entries::use_cases::reject_time_entry::command::RejectTimeEntry;
+use crate::shared::auth::rbac::{Principal, Role};
+use crate::shared::core::primitives::TimeEntryId;
+use serde::Deserialize;
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct RejectTimeEntryDto {
+    pub time_entry_id: String,
+    pub reviewer_id: String,
+    pub reason: Option<String>,
+}
+
+/// The fixture as JSON, embedded so the builder works from any crate or working directory.
+pub const REJECT_TIME_ENTRY_JSON: &str = include_str!("json/reject_time_entry.json");
+
+pub struct RejectTimeEntryBuilder {
+    inner: RejectTimeEntry,
+}
+
+impl Default for RejectTimeEntryBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[allow(dead_code)]
+impl RejectTimeEntryBuilder {
+    /// The canonical command, as `REJECT_TIME_ENTRY_JSON` describes it.
+    pub fn new() -> Self {
+        Self::from_dto(RejectTimeEntryDto {
+            time_entry_id: "te-fixed-0001".to_string(),
+            reviewer_id: "manager-fixed-0001".to_string(),
+            reason: Some("Booked on the wrong project".to_string()),
+        })
+    }
+
+    /// Starts from a fixture shaped like `REJECT_TIME_ENTRY_JSON`.
+    pub fn from_json_str(json: &str) -> serde_json::Result<Self> {
+        serde_json::from_str(json).map(Self::from_dto)
+    }
+
+    fn from_dto(dto: RejectTimeEntryDto) -> Self {
+        Self {
+            inner: RejectTimeEntry {
+                time_entry_id: dto.time_entry_id.into(),
+                reviewer: Principal::new(dto.reviewer_id, Role::Manager),
+                rejected_at: 1700000000000,
+                reason: dto.reason,
+            },
+        }
+    }
+
+    pub fn time_entry_id(mut self, v: impl Into<TimeEntryId>) -> Self {
+        self.inner.time_entry_id = v.into();
+        self
+    }
+
+    pub fn reviewer(mut self, v: Principal) -> Self {
+        self.inner.reviewer = v;
+        self
+    }
+
+    pub fn rejected_at(mut self, v: i64) -> Self {
+        self.inner.rejected_at = v;
+        self
+    }
+
+    pub fn reason(mut self, v: Option<String>) -> Self {
+        self.inner.reason = v;
+        self
+    }
+
+    pub fn build(self) -> RejectTimeEntry {
+        self.inner
+    }
+}
+
+#[cfg(test)]
+mod reject_time_entry_builder_tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    fn new_matches_the_embedded_json() {
+        let from_json = RejectTimeEntryBuilder::from_json_str(REJECT_TIME_ENTRY_JSON)
+            .unwrap()
+            .build();
+        assert_eq!(from_json, RejectTimeEntryBuilder::new().build());
+    }
+
+    #[rstest]
+    fn setters_override_all_fields() {
+        let custom = RejectTimeEntryBuilder::default()
+            .time_entry_id("tid-123")
+            .reviewer(Principal::new("admin-1", Role::Admin))
+            .rejected_at(2222)
+            .reason(None)
+            .build();
+
+        assert_eq!(custom.time_entry_id, "tid-123");
+        assert_eq!(custom.reviewer, Principal::new("admin-1", Role::Admin));
+        assert_eq!(custom.rejected_at, 2222);
+        assert_eq!(custom.reason, None);
+    }
+}
@@ -0,0 +1,132 @@
+use crate::modules::time_entries::core::breaks::Break;
+use crate::modules::time_entries::use_cases::set_breaks::command::SetBreaks;
+use crate::shared::core::primitives::{TimeEntryId, UserId};
+use serde::Deserialize;
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct SetBreaksDto {
+    pub time_entry_id: String,
+    pub user_id: String,
+    pub breaks: Vec<Break>,
+}
+
+/// The fixture as JSON, embedded so the builder works from any crate or working directory.
+pub const SET_BREAKS_JSON: &str = include_str!("json/set_breaks.json");
+
+pub struct SetBreaksBuilder {
+    inner: SetBreaks,
+}
+
+impl Default for SetBreaksBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[allow(dead_code)]
+impl SetBreaksBuilder {
+    /// The canonical command, as `SET_BREAKS_JSON` describes it.
+    pub fn new() -> Self {
+        Self::from_dto(SetBreaksDto {
+            time_entry_id: "te-fixed-0001".to_string(),
+            user_id: "user-fixed-0001".to_string(),
+            breaks: vec![Break {
+                started_at: 1700000120000,
+                ended_at: 1700000180000,
+            }],
+        })
+    }
+
+    /// Starts from a fixture shaped like `SET_BREAKS_JSON`.
+    pub fn from_json_str(json: &str) -> serde_json::Result<Self> {
+        serde_json::from_str(json).map(Self::from_dto)
+    }
+
+    fn from_dto(dto: SetBreaksDto) -> Self {
+        Self {
+            inner: SetBreaks {
+                time_entry_id: dto.time_entry_id.into(),
+                user_id: dto.user_id.into(),
+                breaks: dto.breaks,
+                updated_at: 1700000000000,
+                updated_by: "user-fixed-0001".into(),
+            },
+        }
+    }
+
+    pub fn time_entry_id(mut self, v: impl Into<TimeEntryId>) -> Self {
+        self.inner.time_entry_id = v.into();
+        self
+    }
+
+    pub fn user_id(mut self, v: impl Into<UserId>) -> Self {
+        self.inner.user_id = v.into();
+        self
+    }
+
+    pub fn breaks(mut self, v: Vec<Break>) -> Self {
+        self.inner.breaks = v;
+        self
+    }
+
+    pub fn updated_at(mut self, v: i64) -> Self {
+        self.inner.updated_at = v;
+        self
+    }
+
+    pub fn updated_by(mut self, v: impl Into<UserId>) -> Self {
+        self.inner.updated_by = v.into();
+        self
+    }
+
+    pub fn build(self) -> SetBreaks {
+        self.inner
+    }
+}
+
+#[cfg(test)]
+mod set_breaks_builder_tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    fn new_matches_the_embedded_json() {
+        let from_json = SetBreaksBuilder::from_json_str(SET_BREAKS_JSON)
+            .unwrap()
+            .build();
+        assert_eq!(from_json, SetBreaksBuilder::new().build());
+    }
+
+    #[rstest]
+    fn default_delegates_to_new_and_parses_json() {
+        let built = SetBreaksBuilder::default().build();
+        assert_eq!(built.time_entry_id, "te-fixed-0001");
+        assert_eq!(built.user_id, "user-fixed-0001");
+        assert_eq!(
+            built.breaks,
+            vec![Break {
+                started_at: 1700000120000,
+                ended_at: 1700000180000,
+            }]
+        );
+        assert_eq!(built.updated_at, 1700000000000);
+        assert_eq!(built.updated_by, "user-fixed-0001");
+    }
+
+    #[rstest]
+    fn setters_override_all_fields() {
+        let custom = SetBreaksBuilder::new()
+            .time_entry_id("tid-123")
+            .user_id("uid-456")
+            .breaks(vec![])
+            .updated_at(2222)
+            .updated_by("tester")
+            .build();
+
+        assert_eq!(custom.time_entry_id, "tid-123");
+        assert_eq!(custom.user_id, "uid-456");
+        assert!(custom.breaks.is_empty());
+        assert_eq!(custom.updated_at, 2222);
+        assert_eq!(custom.updated_by, "tester");
+    }
+}
@@ -1,6 +1,7 @@
+use crate::modules::time_entries::core::policies::Rounding;
 use crate::modules::time_entries::use_cases::set_ended_at::command::SetEndedAt;
+use crate::shared::core::primitives::{TimeEntryId, UserId};
 use serde::Deserialize;
-use std::fs;
 
 #[derive(Debug, Clone, Deserialize)]
 pub struct SetEndedAtDto {
@@ -9,6 +10,9 @@ pub struct SetEndedAtDto {
     pub ended_at: i64,
 }
 
+/// The fixture as JSON, embedded so the builder works from any crate or working directory.
+pub const SET_ENDED_AT_JSON: &str = include_str!("json/set_ended_at.json");
+
 pub struct SetEndedAtBuilder {
     inner: SetEndedAt,
 }
@@ -21,28 +25,39 @@ impl Default for SetEndedAtBuilder {
 
 #[allow(dead_code)]
 impl SetEndedAtBuilder {
+    /// The canonical command, as `SET_ENDED_AT_JSON` describes it.
     pub fn new() -> Self {
-        let json_str =
-            fs::read_to_string("./src/tests/fixtures/commands/json/set_ended_at.json").unwrap();
-        let dto: SetEndedAtDto = serde_json::from_str(&json_str).unwrap();
+        Self::from_dto(SetEndedAtDto {
+            time_entry_id: "te-fixed-0001".to_string(),
+            user_id: "user-fixed-0001".to_string(),
+            ended_at: 1700000360000,
+        })
+    }
+
+    /// Starts from a fixture shaped like `SET_ENDED_AT_JSON`.
+    pub fn from_json_str(json: &str) -> serde_json::Result<Self> {
+        serde_json::from_str(json).map(Self::from_dto)
+    }
 
+    fn from_dto(dto: SetEndedAtDto) -> Self {
         Self {
             inner: SetEndedAt {
-                time_entry_id: dto.time_entry_id,
-                user_id: dto.user_id,
+                time_entry_id: dto.time_entry_id.into(),
+                user_id: dto.user_id.into(),
                 ended_at: dto.ended_at,
                 updated_at: 1700000360000,
-                updated_by: "user-fixed-0001".to_string(),
+                updated_by: "user-fixed-0001".into(),
+                rounding: None,
             },
         }
     }
 
-    pub fn time_entry_id(mut self, v: impl Into<String>) -> Self {
+    pub fn time_entry_id(mut self, v: impl Into<TimeEntryId>) -> Self {
         self.inner.time_entry_id = v.into();
         self
     }
 
-    pub fn user_id(mut self, v: impl Into<String>) -> Self {
+    pub fn user_id(mut self, v: impl Into<UserId>) -> Self {
         self.inner.user_id = v.into();
         self
     }
@@ -52,12 +67,17 @@ impl SetEndedAtBuilder {
         self
     }
 
+    pub fn rounding(mut self, v: Rounding) -> Self {
+        self.inner.rounding = Some(v);
+        self
+    }
+
     pub fn updated_at(mut self, v: i64) -> Self {
         self.inner.updated_at = v;
         self
     }
 
-    pub fn updated_by(mut self, v: impl Into<String>) -> Self {
+    pub fn updated_by(mut self, v: impl Into<UserId>) -> Self {
         self.inner.updated_by = v.into();
         self
     }
@@ -72,6 +92,14 @@ mod set_ended_at_builder_tests {
     use super::*;
     use rstest::rstest;
 
+    #[rstest]
+    fn new_matches_the_embedded_json() {
+        let from_json = SetEndedAtBuilder::from_json_str(SET_ENDED_AT_JSON)
+            .unwrap()
+            .build();
+        assert_eq!(from_json, SetEndedAtBuilder::new().build());
+    }
+
     #[rstest]
     fn default_delegates_to_new_and_parses_json() {
         let built = SetEndedAtBuilder::default().build();
@@ -0,0 +1,133 @@
+use crate::modules::time_entries::use_cases::set_hourly_rate::command::SetHourlyRate;
+use crate::shared::core::primitives::{TimeEntryId, UserId};
+use serde::Deserialize;
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct SetHourlyRateDto {
+    pub time_entry_id: String,
+    pub user_id: String,
+    pub hourly_rate_cents: i64,
+    pub currency: String,
+}
+
+/// The fixture as JSON, embedded so the builder works from any crate or working directory.
+pub const SET_HOURLY_RATE_JSON: &str = include_str!("json/set_hourly_rate.json");
+
+pub struct SetHourlyRateBuilder {
+    inner: SetHourlyRate,
+}
+
+impl Default for SetHourlyRateBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[allow(dead_code)]
+impl SetHourlyRateBuilder {
+    /// The canonical command, as `SET_HOURLY_RATE_JSON` describes it.
+    pub fn new() -> Self {
+        Self::from_dto(SetHourlyRateDto {
+            time_entry_id: "te-fixed-0001".to_string(),
+            user_id: "user-fixed-0001".to_string(),
+            hourly_rate_cents: 9500,
+            currency: "EUR".to_string(),
+        })
+    }
+
+    /// Starts from a fixture shaped like `SET_HOURLY_RATE_JSON`.
+    pub fn from_json_str(json: &str) -> serde_json::Result<Self> {
+        serde_json::from_str(json).map(Self::from_dto)
+    }
+
+    fn from_dto(dto: SetHourlyRateDto) -> Self {
+        Self {
+            inner: SetHourlyRate {
+                time_entry_id: dto.time_entry_id.into(),
+                user_id: dto.user_id.into(),
+                hourly_rate_cents: dto.hourly_rate_cents,
+                currency: dto.currency,
+                updated_at: 1700000000000,
+                updated_by: "user-fixed-0001".into(),
+            },
+        }
+    }
+
+    pub fn time_entry_id(mut self, v: impl Into<TimeEntryId>) -> Self {
+        self.inner.time_entry_id = v.into();
+        self
+    }
+
+    pub fn user_id(mut self, v: impl Into<UserId>) -> Self {
+        self.inner.user_id = v.into();
+        self
+    }
+
+    pub fn hourly_rate_cents(mut self, v: i64) -> Self {
+        self.inner.hourly_rate_cents = v;
+        self
+    }
+
+    pub fn currency(mut self, v: impl Into<String>) -> Self {
+        self.inner.currency = v.into();
+        self
+    }
+
+    pub fn updated_at(mut self, v: i64) -> Self {
+        self.inner.updated_at = v;
+        self
+    }
+
+    pub fn updated_by(mut self, v: impl Into<UserId>) -> Self {
+        self.inner.updated_by = v.into();
+        self
+    }
+
+    pub fn build(self) -> SetHourlyRate {
+        self.inner
+    }
+}
+
+#[cfg(test)]
+mod set_hourly_rate_builder_tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    fn new_matches_the_embedded_json() {
+        let from_json = SetHourlyRateBuilder::from_json_str(SET_HOURLY_RATE_JSON)
+            .unwrap()
+            .build();
+        assert_eq!(from_json, SetHourlyRateBuilder::new().build());
+    }
+
+    #[rstest]
+    fn default_delegates_to_new_and_parses_json() {
+        let built = SetHourlyRateBuilder::default().build();
+        assert_eq!(built.time_entry_id, "te-fixed-0001");
+        assert_eq!(built.user_id, "user-fixed-0001");
+        assert_eq!(built.hourly_rate_cents, 9500);
+        assert_eq!(built.currency, "EUR");
+        assert_eq!(built.updated_at, 1700000000000);
+        assert_eq!(built.updated_by, "user-fixed-0001");
+    }
+
+    #[rstest]
+    fn setters_override_all_fields() {
+        let custom = SetHourlyRateBuilder::new()
+            .time_entry_id("tid-123")
+            .user_id("uid-456")
+            .hourly_rate_cents(12_000)
+            .currency("USD")
+            .updated_at(2222)
+            .updated_by("tester")
+            .build();
+
+        assert_eq!(custom.time_entry_id, "tid-123");
+        assert_eq!(custom.user_id, "uid-456");
+        assert_eq!(custom.hourly_rate_cents, 12_000);
+        assert_eq!(custom.currency, "USD");
+        assert_eq!(custom.updated_at, 2222);
+        assert_eq!(custom.updated_by, "tester");
+    }
+}
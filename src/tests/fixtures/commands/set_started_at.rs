@@ -1,6 +1,7 @@
+use crate::modules::time_entries::core::policies::Rounding;
 use crate::modules::time_entries::use_cases::set_started_at::command::SetStartedAt;
+use crate::shared::core::primitives::{TimeEntryId, UserId};
 use serde::Deserialize;
-use std::fs;
 
 #[derive(Debug, Clone, Deserialize)]
 pub struct SetStartedAtDto {
@@ -9,6 +10,9 @@ pub struct SetStartedAtDto {
     pub started_at: i64,
 }
 
+/// The fixture as JSON, embedded so the builder works from any crate or working directory.
+pub const SET_STARTED_AT_JSON: &str = include_str!("json/set_started_at.json");
+
 pub struct SetStartedAtBuilder {
     inner: SetStartedAt,
 }
@@ -21,28 +25,39 @@ impl Default for SetStartedAtBuilder {
 
 #[allow(dead_code)]
 impl SetStartedAtBuilder {
+    /// The canonical command, as `SET_STARTED_AT_JSON` describes it.
     pub fn new() -> Self {
-        let json_str =
-            fs::read_to_string("./src/tests/fixtures/commands/json/set_started_at.json").unwrap();
-        let dto: SetStartedAtDto = serde_json::from_str(&json_str).unwrap();
+        Self::from_dto(SetStartedAtDto {
+            time_entry_id: "te-fixed-0001".to_string(),
+            user_id: "user-fixed-0001".to_string(),
+            started_at: 1700000000000,
+        })
+    }
+
+    /// Starts from a fixture shaped like `SET_STARTED_AT_JSON`.
+    pub fn from_json_str(json: &str) -> serde_json::Result<Self> {
+        serde_json::from_str(json).map(Self::from_dto)
+    }
 
+    fn from_dto(dto: SetStartedAtDto) -> Self {
         Self {
             inner: SetStartedAt {
-                time_entry_id: dto.time_entry_id,
-                user_id: dto.user_id,
+                time_entry_id: dto.time_entry_id.into(),
+                user_id: dto.user_id.into(),
                 started_at: dto.started_at,
                 updated_at: 1700000000000,
-                updated_by: "user-fixed-0001".to_string(),
+                updated_by: "user-fixed-0001".into(),
+                rounding: None,
             },
         }
     }
 
-    pub fn time_entry_id(mut self, v: impl Into<String>) -> Self {
+    pub fn time_entry_id(mut self, v: impl Into<TimeEntryId>) -> Self {
         self.inner.time_entry_id = v.into();
         self
     }
 
-    pub fn user_id(mut self, v: impl Into<String>) -> Self {
+    pub fn user_id(mut self, v: impl Into<UserId>) -> Self {
         self.inner.user_id = v.into();
         self
     }
@@ -52,12 +67,17 @@ impl SetStartedAtBuilder {
         self
     }
 
+    pub fn rounding(mut self, v: Rounding) -> Self {
+        self.inner.rounding = Some(v);
+        self
+    }
+
     pub fn updated_at(mut self, v: i64) -> Self {
         self.inner.updated_at = v;
         self
     }
 
-    pub fn updated_by(mut self, v: impl Into<String>) -> Self {
+    pub fn updated_by(mut self, v: impl Into<UserId>) -> Self {
         self.inner.updated_by = v.into();
         self
     }
@@ -72,6 +92,14 @@ mod set_started_at_builder_tests {
     use super::*;
     use rstest::rstest;
 
+    #[rstest]
+    fn new_matches_the_embedded_json() {
+        let from_json = SetStartedAtBuilder::from_json_str(SET_STARTED_AT_JSON)
+            .unwrap()
+            .build();
+        assert_eq!(from_json, SetStartedAtBuilder::new().build());
+    }
+
     #[rstest]
     fn default_delegates_to_new_and_parses_json() {
         let built = SetStartedAtBuilder::default().build();
@@ -1,6 +1,7 @@
+use crate::modules::time_entries::core::tag::Tag;
 use crate::modules::time_entries::use_cases::set_time_entry_tags::command::SetTimeEntryTags;
+use crate::shared::core::primitives::{TimeEntryId, UserId};
 use serde::Deserialize;
-use std::fs;
 
 #[derive(Debug, Clone, Deserialize)]
 pub struct SetTimeEntryTagsDto {
@@ -9,6 +10,9 @@ pub struct SetTimeEntryTagsDto {
     pub tag_ids: Vec<String>,
 }
 
+/// The fixture as JSON, embedded so the builder works from any crate or working directory.
+pub const SET_TIME_ENTRY_TAGS_JSON: &str = include_str!("json/set_time_entry_tags.json");
+
 pub struct SetTimeEntryTagsBuilder {
     inner: SetTimeEntryTags,
 }
@@ -21,35 +25,45 @@ impl Default for SetTimeEntryTagsBuilder {
 
 #[allow(dead_code)]
 impl SetTimeEntryTagsBuilder {
+    /// The canonical command, as `SET_TIME_ENTRY_TAGS_JSON` describes it.
     pub fn new() -> Self {
-        let json_str =
-            fs::read_to_string("./src/tests/fixtures/commands/json/set_time_entry_tags.json")
-                .unwrap();
-        let dto: SetTimeEntryTagsDto = serde_json::from_str(&json_str).unwrap();
+        Self::from_dto(SetTimeEntryTagsDto {
+            time_entry_id: "te-fixed-0001".to_string(),
+            user_id: "user-fixed-0001".to_string(),
+            tag_ids: vec!["tag-fixed-0001".to_string()],
+        })
+    }
+
+    /// Starts from a fixture shaped like `SET_TIME_ENTRY_TAGS_JSON`.
+    pub fn from_json_str(json: &str) -> serde_json::Result<Self> {
+        serde_json::from_str(json).map(Self::from_dto)
+    }
 
+    fn from_dto(dto: SetTimeEntryTagsDto) -> Self {
         Self {
             inner: SetTimeEntryTags {
-                time_entry_id: dto.time_entry_id,
-                user_id: dto.user_id,
-                tag_ids: dto.tag_ids,
+                time_entry_id: dto.time_entry_id.into(),
+                user_id: dto.user_id.into(),
+                tag_ids: Tag::parse_all(&dto.tag_ids).unwrap(),
                 updated_at: 1700000000000,
-                updated_by: "user-fixed-0001".to_string(),
+                updated_by: "user-fixed-0001".into(),
             },
         }
     }
 
-    pub fn time_entry_id(mut self, v: impl Into<String>) -> Self {
+    pub fn time_entry_id(mut self, v: impl Into<TimeEntryId>) -> Self {
         self.inner.time_entry_id = v.into();
         self
     }
 
-    pub fn user_id(mut self, v: impl Into<String>) -> Self {
+    pub fn user_id(mut self, v: impl Into<UserId>) -> Self {
         self.inner.user_id = v.into();
         self
     }
 
+    /// Parses `v`; tests pass valid tags.
     pub fn tag_ids(mut self, v: Vec<String>) -> Self {
-        self.inner.tag_ids = v;
+        self.inner.tag_ids = Tag::parse_all(&v).unwrap();
         self
     }
 
@@ -58,7 +72,7 @@ impl SetTimeEntryTagsBuilder {
         self
     }
 
-    pub fn updated_by(mut self, v: impl Into<String>) -> Self {
+    pub fn updated_by(mut self, v: impl Into<UserId>) -> Self {
         self.inner.updated_by = v.into();
         self
     }
@@ -73,6 +87,14 @@ mod set_time_entry_tags_builder_tests {
     use super::*;
     use rstest::rstest;
 
+    #[rstest]
+    fn new_matches_the_embedded_json() {
+        let from_json = SetTimeEntryTagsBuilder::from_json_str(SET_TIME_ENTRY_TAGS_JSON)
+            .unwrap()
+            .build();
+        assert_eq!(from_json, SetTimeEntryTagsBuilder::new().build());
+    }
+
     #[rstest]
     fn default_delegates_to_new_and_parses_json() {
         let built = SetTimeEntryTagsBuilder::default().build();
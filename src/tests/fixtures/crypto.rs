@@ -0,0 +1,62 @@
+// Reversible stand-in cipher for tests. XORs the plaintext with the key material and
+// hex-encodes the result. It offers no security and must never be wired into the shell.
+
+use crate::shared::infrastructure::event_store::crypto_shredding::FieldCipher;
+use crate::shared::infrastructure::key_store::DataKey;
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct XorHexCipher;
+
+impl FieldCipher for XorHexCipher {
+    fn encrypt(&self, key: &DataKey, plaintext: &str) -> String {
+        plaintext
+            .bytes()
+            .zip(key.material.iter().cycle())
+            .map(|(byte, k)| format!("{:02x}", byte ^ k))
+            .collect()
+    }
+
+    fn decrypt(&self, key: &DataKey, ciphertext: &str) -> Option<String> {
+        let bytes = (0..ciphertext.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(ciphertext.get(i..i + 2)?, 16).ok())
+            .collect::<Option<Vec<u8>>>()?;
+        let plain = bytes
+            .iter()
+            .zip(key.material.iter().cycle())
+            .map(|(byte, k)| byte ^ k)
+            .collect();
+        String::from_utf8(plain).ok()
+    }
+}
+
+#[cfg(test)]
+mod xor_hex_cipher_tests {
+    use super::*;
+    use rstest::rstest;
+
+    fn key() -> DataKey {
+        DataKey {
+            id: "k-1".to_string(),
+            material: vec![0x0f, 0xf0],
+        }
+    }
+
+    #[test]
+    fn it_should_round_trip() {
+        let ciphertext = XorHexCipher.encrypt(&key(), "alice");
+        assert_ne!(ciphertext, "alice");
+        assert_eq!(
+            XorHexCipher.decrypt(&key(), &ciphertext),
+            Some("alice".to_string())
+        );
+    }
+
+    #[rstest]
+    #[case::odd_length("abc")]
+    #[case::not_hex("zz")]
+    #[case::not_utf8("f0")]
+    fn it_should_reject_malformed_ciphertexts(#[case] ciphertext: &str) {
+        assert_eq!(XorHexCipher.decrypt(&key(), ciphertext), None);
+    }
+}
@@ -1,6 +1,5 @@
 use crate::modules::time_entries::core::events::v1::time_entry_end_set::TimeEntryEndSetV1;
 use serde::Deserialize;
-use std::fs;
 
 #[derive(Debug, Deserialize)]
 struct TimeEntryEndSetV1Dto {
@@ -10,15 +9,31 @@ struct TimeEntryEndSetV1Dto {
     updated_by: String,
 }
 
+/// The fixture as JSON, embedded so it works from any crate or working directory.
+pub const END_SET_EVENT_V1_JSON: &str = include_str!("json/end_set_event_v1.json");
+
+/// The canonical event, as `END_SET_EVENT_V1_JSON` describes it.
 pub fn make_time_entry_end_set_v1_event() -> TimeEntryEndSetV1 {
-    let json_str =
-        fs::read_to_string("./src/tests/fixtures/events/json/end_set_event_v1.json").unwrap();
-    let dto: TimeEntryEndSetV1Dto = serde_json::from_str(&json_str).unwrap();
+    from_dto(TimeEntryEndSetV1Dto {
+        time_entry_id: "te-fixed-0001".to_string(),
+        ended_at: 1700000360000,
+        updated_at: 1700000000000,
+        updated_by: "user-fixed-0001".to_string(),
+    })
+}
+
+/// An event from a fixture shaped like `END_SET_EVENT_V1_JSON`.
+pub fn time_entry_end_set_v1_from_json_str(json: &str) -> serde_json::Result<TimeEntryEndSetV1> {
+    serde_json::from_str(json).map(from_dto)
+}
+
+fn from_dto(dto: TimeEntryEndSetV1Dto) -> TimeEntryEndSetV1 {
     TimeEntryEndSetV1 {
-        time_entry_id: dto.time_entry_id,
+        time_entry_id: dto.time_entry_id.into(),
         ended_at: dto.ended_at,
         updated_at: dto.updated_at,
-        updated_by: dto.updated_by,
+        updated_by: dto.updated_by.into(),
+        raw_ended_at: None,
     }
 }
 
@@ -27,6 +42,14 @@ mod time_entry_end_set_v1_fixture_tests {
     use super::*;
     use rstest::rstest;
 
+    #[rstest]
+    fn fixture_matches_the_embedded_json() {
+        assert_eq!(
+            time_entry_end_set_v1_from_json_str(END_SET_EVENT_V1_JSON).unwrap(),
+            make_time_entry_end_set_v1_event()
+        );
+    }
+
     #[rstest]
     fn fixture_loads_from_json() {
         let event = make_time_entry_end_set_v1_event();
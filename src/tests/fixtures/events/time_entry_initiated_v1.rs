@@ -1,6 +1,5 @@
 use crate::modules::time_entries::core::events::v1::time_entry_initiated::TimeEntryInitiatedV1;
 use serde::Deserialize;
-use std::fs;
 
 #[derive(Debug, Deserialize)]
 struct TimeEntryInitiatedV1Dto {
@@ -10,15 +9,32 @@ struct TimeEntryInitiatedV1Dto {
     created_by: String,
 }
 
+/// The fixture as JSON, embedded so it works from any crate or working directory.
+pub const INITIATED_EVENT_V1_JSON: &str = include_str!("json/initiated_event_v1.json");
+
+/// The canonical event, as `INITIATED_EVENT_V1_JSON` describes it.
 pub fn make_time_entry_initiated_v1_event() -> TimeEntryInitiatedV1 {
-    let json_str =
-        fs::read_to_string("./src/tests/fixtures/events/json/initiated_event_v1.json").unwrap();
-    let dto: TimeEntryInitiatedV1Dto = serde_json::from_str(&json_str).unwrap();
+    from_dto(TimeEntryInitiatedV1Dto {
+        time_entry_id: "te-fixed-0001".to_string(),
+        user_id: "user-fixed-0001".to_string(),
+        created_at: 1700000000000,
+        created_by: "user-fixed-0001".to_string(),
+    })
+}
+
+/// An event from a fixture shaped like `INITIATED_EVENT_V1_JSON`.
+pub fn time_entry_initiated_v1_from_json_str(
+    json: &str,
+) -> serde_json::Result<TimeEntryInitiatedV1> {
+    serde_json::from_str(json).map(from_dto)
+}
+
+fn from_dto(dto: TimeEntryInitiatedV1Dto) -> TimeEntryInitiatedV1 {
     TimeEntryInitiatedV1 {
-        time_entry_id: dto.time_entry_id,
-        user_id: dto.user_id,
+        time_entry_id: dto.time_entry_id.into(),
+        user_id: dto.user_id.into(),
         created_at: dto.created_at,
-        created_by: dto.created_by,
+        created_by: dto.created_by.into(),
     }
 }
 
@@ -27,6 +43,14 @@ mod time_entry_initiated_v1_fixture_tests {
     use super::*;
     use rstest::rstest;
 
+    #[rstest]
+    fn fixture_matches_the_embedded_json() {
+        assert_eq!(
+            time_entry_initiated_v1_from_json_str(INITIATED_EVENT_V1_JSON).unwrap(),
+            make_time_entry_initiated_v1_event()
+        );
+    }
+
     #[rstest]
     fn fixture_loads_from_json() {
         let event = make_time_entry_initiated_v1_event();
@@ -1,6 +1,5 @@
 use crate::modules::time_entries::core::events::v1::time_entry_registered::TimeEntryRegisteredV1;
 use serde::Deserialize;
-use std::fs;
 
 #[derive(Debug, Deserialize)]
 struct TimeEntryRegisteredV1Dto {
@@ -8,12 +7,27 @@ struct TimeEntryRegisteredV1Dto {
     occurred_at: i64,
 }
 
+/// The fixture as JSON, embedded so it works from any crate or working directory.
+pub const REGISTERED_EVENT_V1_JSON: &str = include_str!("json/registered_event_v1.json");
+
+/// The canonical event, as `REGISTERED_EVENT_V1_JSON` describes it.
 pub fn make_time_entry_registered_v1_event() -> TimeEntryRegisteredV1 {
-    let json_str =
-        fs::read_to_string("./src/tests/fixtures/events/json/registered_event_v1.json").unwrap();
-    let dto: TimeEntryRegisteredV1Dto = serde_json::from_str(&json_str).unwrap();
+    from_dto(TimeEntryRegisteredV1Dto {
+        time_entry_id: "te-fixed-0001".to_string(),
+        occurred_at: 1700000000000,
+    })
+}
+
+/// An event from a fixture shaped like `REGISTERED_EVENT_V1_JSON`.
+pub fn time_entry_registered_v1_from_json_str(
+    json: &str,
+) -> serde_json::Result<TimeEntryRegisteredV1> {
+    serde_json::from_str(json).map(from_dto)
+}
+
+fn from_dto(dto: TimeEntryRegisteredV1Dto) -> TimeEntryRegisteredV1 {
     TimeEntryRegisteredV1 {
-        time_entry_id: dto.time_entry_id,
+        time_entry_id: dto.time_entry_id.into(),
         occurred_at: dto.occurred_at,
     }
 }
@@ -23,6 +37,14 @@ mod time_entry_registered_v1_fixture_tests {
     use super::*;
     use rstest::rstest;
 
+    #[rstest]
+    fn fixture_matches_the_embedded_json() {
+        assert_eq!(
+            time_entry_registered_v1_from_json_str(REGISTERED_EVENT_V1_JSON).unwrap(),
+            make_time_entry_registered_v1_event()
+        );
+    }
+
     #[rstest]
     fn fixture_loads_from_json() {
         let event = make_time_entry_registered_v1_event();
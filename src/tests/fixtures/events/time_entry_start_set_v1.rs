@@ -1,6 +1,5 @@
 use crate::modules::time_entries::core::events::v1::time_entry_start_set::TimeEntryStartSetV1;
 use serde::Deserialize;
-use std::fs;
 
 #[derive(Debug, Deserialize)]
 struct TimeEntryStartSetV1Dto {
@@ -10,15 +9,33 @@ struct TimeEntryStartSetV1Dto {
     updated_by: String,
 }
 
+/// The fixture as JSON, embedded so it works from any crate or working directory.
+pub const START_SET_EVENT_V1_JSON: &str = include_str!("json/start_set_event_v1.json");
+
+/// The canonical event, as `START_SET_EVENT_V1_JSON` describes it.
 pub fn make_time_entry_start_set_v1_event() -> TimeEntryStartSetV1 {
-    let json_str =
-        fs::read_to_string("./src/tests/fixtures/events/json/start_set_event_v1.json").unwrap();
-    let dto: TimeEntryStartSetV1Dto = serde_json::from_str(&json_str).unwrap();
+    from_dto(TimeEntryStartSetV1Dto {
+        time_entry_id: "te-fixed-0001".to_string(),
+        started_at: 1700000000000,
+        updated_at: 1700000000000,
+        updated_by: "user-fixed-0001".to_string(),
+    })
+}
+
+/// An event from a fixture shaped like `START_SET_EVENT_V1_JSON`.
+pub fn time_entry_start_set_v1_from_json_str(
+    json: &str,
+) -> serde_json::Result<TimeEntryStartSetV1> {
+    serde_json::from_str(json).map(from_dto)
+}
+
+fn from_dto(dto: TimeEntryStartSetV1Dto) -> TimeEntryStartSetV1 {
     TimeEntryStartSetV1 {
-        time_entry_id: dto.time_entry_id,
+        time_entry_id: dto.time_entry_id.into(),
         started_at: dto.started_at,
         updated_at: dto.updated_at,
-        updated_by: dto.updated_by,
+        updated_by: dto.updated_by.into(),
+        raw_started_at: None,
     }
 }
 
@@ -27,6 +44,14 @@ mod time_entry_start_set_v1_fixture_tests {
     use super::*;
     use rstest::rstest;
 
+    #[rstest]
+    fn fixture_matches_the_embedded_json() {
+        assert_eq!(
+            time_entry_start_set_v1_from_json_str(START_SET_EVENT_V1_JSON).unwrap(),
+            make_time_entry_start_set_v1_event()
+        );
+    }
+
     #[rstest]
     fn fixture_loads_from_json() {
         let event = make_time_entry_start_set_v1_event();
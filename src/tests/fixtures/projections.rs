@@ -0,0 +1,90 @@
+// Test kit for projector crash/resume scenarios.
+//
+// `CrashingProjectionStore` wraps a real projection store and lets a fixed number of
+// saves through. The next save is torn: the new state is written but the checkpoint is
+// left behind, exactly like a process dying between writing rows and committing the
+// checkpoint. From then on every call fails until a fresh projector is started against
+// the inner store.
+
+use crate::shared::infrastructure::projection_store::ProjectionStore;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+
+#[derive(Clone)]
+pub struct CrashingProjectionStore<TStore> {
+    inner: TStore,
+    saves_before_crash: Arc<AtomicU64>,
+    crashed: Arc<AtomicBool>,
+}
+
+impl<TStore> CrashingProjectionStore<TStore> {
+    pub fn new(inner: TStore, saves_before_crash: u64) -> Self {
+        Self {
+            inner,
+            saves_before_crash: Arc::new(AtomicU64::new(saves_before_crash)),
+            crashed: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    pub fn has_crashed(&self) -> bool {
+        self.crashed.load(Ordering::SeqCst)
+    }
+
+    fn ensure_alive(&self) -> anyhow::Result<()> {
+        if self.has_crashed() {
+            return Err(anyhow::anyhow!("Projector crashed"));
+        }
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl<P, TStore> ProjectionStore<P> for CrashingProjectionStore<TStore>
+where
+    P: Clone + Send + Sync + 'static,
+    TStore: ProjectionStore<P>,
+{
+    async fn state(&self) -> anyhow::Result<Option<P>> {
+        self.ensure_alive()?;
+        self.inner.state().await
+    }
+
+    async fn checkpoint(&self) -> anyhow::Result<u64> {
+        self.ensure_alive()?;
+        self.inner.checkpoint().await
+    }
+
+    async fn schema_version(&self) -> anyhow::Result<Option<u32>> {
+        self.ensure_alive()?;
+        self.inner.schema_version().await
+    }
+
+    async fn save(&self, state: P, checkpoint: u64) -> anyhow::Result<()> {
+        self.ensure_alive()?;
+        let remaining = self.saves_before_crash.load(Ordering::SeqCst);
+        if remaining > 0 {
+            self.saves_before_crash
+                .store(remaining - 1, Ordering::SeqCst);
+            return self.inner.save(state, checkpoint).await;
+        }
+        let stale_checkpoint = self.inner.checkpoint().await?;
+        self.inner.save(state, stale_checkpoint).await?;
+        self.crashed.store(true, Ordering::SeqCst);
+        Err(anyhow::anyhow!("Projector crashed"))
+    }
+
+    async fn save_if_unchanged(&self, state: P, checkpoint: u64) -> anyhow::Result<bool> {
+        self.ensure_alive()?;
+        self.inner.save_if_unchanged(state, checkpoint).await
+    }
+
+    async fn save_schema_version(&self, version: u32) -> anyhow::Result<()> {
+        self.ensure_alive()?;
+        self.inner.save_schema_version(version).await
+    }
+
+    async fn clear(&self) -> anyhow::Result<()> {
+        self.ensure_alive()?;
+        self.inner.clear().await
+    }
+}
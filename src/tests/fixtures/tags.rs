@@ -1,3 +1,13 @@
+use std::sync::Arc;
+
+use crate::modules::contracts::core::events::ContractEvent;
+use crate::modules::contracts::use_cases::set_contract::handler::SetContractHandler;
+use crate::modules::contracts::use_cases::utilization::queries::UtilizationQueryHandler;
+use crate::modules::schedules::core::events::ScheduleEvent;
+use crate::modules::schedules::use_cases::cancel_schedule::handler::CancelScheduleHandler;
+use crate::modules::schedules::use_cases::define_schedule::handler::DefineScheduleHandler;
+use crate::modules::schedules::use_cases::list_schedules::projection::ListSchedulesState;
+use crate::modules::schedules::use_cases::list_schedules::queries::ListSchedulesQueryHandler;
 use crate::modules::tags::core::events::TagEvent;
 use crate::modules::tags::use_cases::create_tag::handler::CreateTagHandler;
 use crate::modules::tags::use_cases::delete_tag::handler::DeleteTagHandler;
@@ -6,28 +16,156 @@ use crate::modules::tags::use_cases::list_tags::queries::ListTagsQueryHandler;
 use crate::modules::tags::use_cases::set_tag_color::handler::SetTagColorHandler;
 use crate::modules::tags::use_cases::set_tag_description::handler::SetTagDescriptionHandler;
 use crate::modules::tags::use_cases::set_tag_name::handler::SetTagNameHandler;
+use crate::modules::templates::core::events::TemplateEvent;
+use crate::modules::templates::use_cases::list_templates::projection::ListTemplatesState;
+use crate::modules::templates::use_cases::list_templates::queries::ListTemplatesQueryHandler;
+use crate::modules::templates::use_cases::save_template::handler::SaveTemplateHandler;
 use crate::modules::time_entries::core::events::TimeEntryEvent;
+use crate::modules::time_entries::core::period_locks::PeriodLocksEvent;
+use crate::modules::time_entries::core::policies::Policies;
+use crate::modules::time_entries::core::user_time_entries::UserTimeEntriesEvent;
+use crate::modules::time_entries::use_cases::add_time_entry_attachment::handler::AddTimeEntryAttachmentHandler;
+use crate::modules::time_entries::use_cases::add_time_entry_comment::handler::AddTimeEntryCommentHandler;
+use crate::modules::time_entries::use_cases::approve_time_entry::handler::ApproveTimeEntryHandler;
+use crate::modules::time_entries::use_cases::bulk_delete_time_entries::confirmations::BulkDeleteConfirmations;
+use crate::modules::time_entries::use_cases::delete_time_entry::handler::DeleteTimeEntryHandler;
+use crate::modules::time_entries::use_cases::hours_balance::queries::HoursBalanceQueryHandler;
 use crate::modules::time_entries::use_cases::list_time_entries::projection::ListTimeEntriesState;
 use crate::modules::time_entries::use_cases::list_time_entries::queries::ListTimeEntriesQueryHandler;
+use crate::modules::time_entries::use_cases::list_time_entries::updates::TimeEntryUpdates;
+use crate::modules::time_entries::use_cases::period_locks::handler::PeriodLocksHandler;
+use crate::modules::time_entries::use_cases::reject_time_entry::handler::RejectTimeEntryHandler;
+use crate::modules::time_entries::use_cases::set_breaks::handler::SetBreaksHandler;
 use crate::modules::time_entries::use_cases::set_ended_at::handler::SetEndedAtHandler;
+use crate::modules::time_entries::use_cases::set_hourly_rate::handler::SetHourlyRateHandler;
 use crate::modules::time_entries::use_cases::set_started_at::handler::SetStartedAtHandler;
 use crate::modules::time_entries::use_cases::set_time_entry_tags::handler::SetTimeEntryTagsHandler;
+use crate::modules::time_entries::use_cases::time_entry_attachments::projection::TimeEntryAttachmentsState;
+use crate::modules::time_entries::use_cases::time_entry_attachments::queries::TimeEntryAttachmentsQueryHandler;
+use crate::modules::time_entries::use_cases::time_entry_comments::projection::TimeEntryCommentsState;
+use crate::modules::time_entries::use_cases::time_entry_comments::queries::TimeEntryCommentsQueryHandler;
+use crate::modules::time_entries::use_cases::update_time_entry::handler::UpdateTimeEntryHandler;
+use crate::modules::time_entries::use_cases::user_stats::projection::UserStatsState;
+use crate::modules::time_entries::use_cases::user_stats::queries::UserStatsQueryHandler;
+use crate::modules::time_entries::use_cases::user_time_entries::sharded_handler::UserStreams;
+use crate::shared::application::command_bus::pipeline::CommandPipeline;
+use crate::shared::application::forget_user::ForgetUserHandler;
+use crate::shared::application::slo::WriteSlo;
+use crate::shared::core::stream_naming::DefaultStreamNaming;
+use crate::shared::infrastructure::api_audit_store::in_memory::InMemoryApiAuditStore;
+use crate::shared::infrastructure::api_key_store::in_memory::InMemoryApiKeyStore;
+use crate::shared::infrastructure::attachment_storage::in_memory::InMemoryAttachmentStorage;
+use crate::shared::infrastructure::calendar::static_config::StaticCalendar;
+use crate::shared::infrastructure::capacity::CapacityGauges;
+use crate::shared::infrastructure::cold_storage::in_memory::InMemoryColdStorage;
+use crate::shared::infrastructure::control_store::in_memory::InMemoryControlStore;
+use crate::shared::infrastructure::event_store::EventLog;
 use crate::shared::infrastructure::event_store::in_memory::InMemoryEventStore;
+use crate::shared::infrastructure::feature_flags::in_memory::InMemoryFeatureFlags;
+use crate::shared::infrastructure::intent_outbox::compression::CompressingOutbox;
 use crate::shared::infrastructure::intent_outbox::in_memory::InMemoryDomainOutbox;
+use crate::shared::infrastructure::job_store::in_memory::InMemoryJobStore;
+use crate::shared::infrastructure::key_store::in_memory::InMemoryKeyStore;
+use crate::shared::infrastructure::message_broker::consumer_lag::ConsumerLag;
+use crate::shared::infrastructure::outbox_relay::RelayMetrics;
+use crate::shared::infrastructure::policy_store::in_memory::InMemoryPolicyStore;
 use crate::shared::infrastructure::projection_store::in_memory::InMemoryProjectionStore;
-use crate::shell::state::AppState;
+use crate::shared::infrastructure::projection_store::partitioned::PartitionedProjectionStore;
+use crate::shared::infrastructure::user_directory::SharedUserDirectory;
+use crate::shared::infrastructure::user_directory::in_memory::InMemoryUserDirectory;
+use crate::shared::infrastructure::user_directory::loader::UserDisplayNameLoader;
+use crate::shell::state::{AppState, TimeEntryEventStore};
+use crate::shell::tuning::WorkerTuning;
 
 pub fn make_test_app_state() -> AppState {
+    make_test_app_state_with(
+        InMemoryEventStore::<TimeEntryEvent>::new(),
+        InMemoryProjectionStore::<ListTimeEntriesState>::new(),
+    )
+}
+
+/// Like `make_test_app_state`, with a handle on the time entry store, e.g. to take it offline.
+pub fn make_test_app_state_with_store() -> (AppState, InMemoryEventStore<TimeEntryEvent>) {
     let event_store = InMemoryEventStore::<TimeEntryEvent>::new();
+    let state = make_test_app_state_with(
+        event_store.clone(),
+        InMemoryProjectionStore::<ListTimeEntriesState>::new(),
+    );
+    (state, event_store)
+}
+
+/// Like `make_test_app_state`, but over the given time entry stores, so a caller can broadcast
+/// appended events and run a projector into the store the list queries read.
+pub fn make_test_app_state_with(
+    event_store: impl EventLog<TimeEntryEvent> + 'static,
+    time_entry_projection_store: InMemoryProjectionStore<ListTimeEntriesState>,
+) -> AppState {
+    let event_store: TimeEntryEventStore = Arc::new(event_store);
     let outbox = InMemoryDomainOutbox::new();
-    let time_entry_projection_store = InMemoryProjectionStore::<ListTimeEntriesState>::new();
+    let domain_outbox = CompressingOutbox::uncompressed(outbox.clone());
+    let user_streams: UserStreams = Arc::new(InMemoryEventStore::<UserTimeEntriesEvent>::new());
+    let period_lock_store = InMemoryEventStore::<PeriodLocksEvent>::new();
+    let period_locks_handler = PeriodLocksHandler::new(period_lock_store.clone());
+    let feature_flags = InMemoryFeatureFlags::new();
+    let policy_store = InMemoryPolicyStore::new();
+    let write_slo = WriteSlo::default();
     let set_started_at_handler =
-        SetStartedAtHandler::new("time-entries", event_store.clone(), outbox.clone());
-    let set_ended_at_handler =
-        SetEndedAtHandler::new("time-entries", event_store.clone(), outbox.clone());
+        SetStartedAtHandler::new(event_store.clone(), domain_outbox.clone())
+            .with_user_streams(user_streams.clone())
+            .with_period_locks(Arc::new(period_lock_store.clone()))
+            .with_feature_flags(Arc::new(feature_flags.clone()))
+            .with_policies(Arc::new(policy_store.clone()), Policies::default())
+            .with_slo(write_slo.clone());
+    let set_ended_at_handler = SetEndedAtHandler::new(event_store.clone(), domain_outbox.clone())
+        .with_user_streams(user_streams.clone())
+        .with_period_locks(Arc::new(period_lock_store.clone()))
+        .with_feature_flags(Arc::new(feature_flags.clone()))
+        .with_policies(Arc::new(policy_store.clone()), Policies::default())
+        .with_slo(write_slo.clone());
     let set_time_entry_tags_handler =
-        SetTimeEntryTagsHandler::new("time-entries", event_store.clone(), outbox.clone());
-    let list_time_entries_handler = ListTimeEntriesQueryHandler::new(time_entry_projection_store);
+        SetTimeEntryTagsHandler::new(event_store.clone(), domain_outbox.clone())
+            .with_period_locks(Arc::new(period_lock_store.clone()))
+            .with_policies(Arc::new(policy_store.clone()), Policies::default());
+    let set_hourly_rate_handler =
+        SetHourlyRateHandler::new(event_store.clone(), domain_outbox.clone())
+            .with_period_locks(Arc::new(period_lock_store.clone()));
+    let set_breaks_handler = SetBreaksHandler::new(event_store.clone(), domain_outbox.clone())
+        .with_period_locks(Arc::new(period_lock_store.clone()))
+        .with_policies(Arc::new(policy_store.clone()), Policies::default());
+    let update_time_entry_handler =
+        UpdateTimeEntryHandler::new(event_store.clone(), domain_outbox.clone())
+            .with_user_streams(user_streams.clone())
+            .with_period_locks(Arc::new(period_lock_store.clone()))
+            .with_feature_flags(Arc::new(feature_flags.clone()))
+            .with_policies(Arc::new(policy_store.clone()), Policies::default());
+    let delete_time_entry_handler =
+        DeleteTimeEntryHandler::new(event_store.clone(), domain_outbox.clone())
+            .with_user_streams(user_streams)
+            .with_period_locks(Arc::new(period_lock_store.clone()))
+            .with_policies(Arc::new(policy_store.clone()), Policies::default());
+    let approve_time_entry_handler =
+        ApproveTimeEntryHandler::new(event_store.clone(), domain_outbox.clone());
+    let reject_time_entry_handler =
+        RejectTimeEntryHandler::new(event_store.clone(), domain_outbox.clone());
+    let add_time_entry_comment_handler =
+        AddTimeEntryCommentHandler::new(event_store.clone(), domain_outbox.clone());
+    let add_time_entry_attachment_handler =
+        AddTimeEntryAttachmentHandler::new(event_store.clone(), domain_outbox.clone());
+    let list_time_entries_handler = ListTimeEntriesQueryHandler::new(
+        PartitionedProjectionStore::single(time_entry_projection_store.clone()),
+    );
+    let calendar = StaticCalendar::default();
+    let hours_balance_handler = HoursBalanceQueryHandler::new(
+        PartitionedProjectionStore::single(time_entry_projection_store.clone()),
+        Arc::new(calendar.clone()),
+    );
+    let contract_event_store = InMemoryEventStore::<ContractEvent>::new();
+    let set_contract_handler = SetContractHandler::new(contract_event_store.clone());
+    let utilization_handler = UtilizationQueryHandler::new(
+        contract_event_store.clone(),
+        PartitionedProjectionStore::single(time_entry_projection_store),
+        Arc::new(calendar.clone()),
+    );
 
     let tag_event_store = InMemoryEventStore::<TagEvent>::new();
     let create_tag_handler = CreateTagHandler::new(tag_event_store.clone());
@@ -38,13 +176,53 @@ pub fn make_test_app_state() -> AppState {
     let tag_projection_store = InMemoryProjectionStore::<ListTagsState>::new();
     let list_tags_handler = ListTagsQueryHandler::new(tag_projection_store.clone());
 
+    let template_event_store = InMemoryEventStore::<TemplateEvent>::new();
+    let save_template_handler = SaveTemplateHandler::new(template_event_store.clone());
+    let list_templates_handler =
+        ListTemplatesQueryHandler::new(InMemoryProjectionStore::<ListTemplatesState>::new());
+
+    let schedule_event_store = InMemoryEventStore::<ScheduleEvent>::new();
+    let define_schedule_handler = DefineScheduleHandler::new(schedule_event_store.clone());
+    let cancel_schedule_handler = CancelScheduleHandler::new(schedule_event_store.clone());
+    let list_schedules_handler =
+        ListSchedulesQueryHandler::new(InMemoryProjectionStore::<ListSchedulesState>::new());
+
+    let user_directory: SharedUserDirectory = Arc::new(InMemoryUserDirectory::new());
+    let user_display_name_loader = UserDisplayNameLoader::new(user_directory);
+
     AppState {
         set_started_at_handler,
         set_ended_at_handler,
         set_time_entry_tags_handler,
+        set_hourly_rate_handler,
+        set_breaks_handler,
+        command_pipeline: CommandPipeline::new(),
+        update_time_entry_handler,
+        delete_time_entry_handler,
+        approve_time_entry_handler,
+        reject_time_entry_handler,
+        add_time_entry_comment_handler,
+        add_time_entry_attachment_handler,
+        period_locks_handler,
+        period_lock_store,
         event_store,
         outbox,
+        forget_user_handler: ForgetUserHandler::new(InMemoryKeyStore::new()),
         list_time_entries_handler,
+        time_entry_updates: TimeEntryUpdates::default(),
+        hours_balance_handler,
+        user_stats_handler: UserStatsQueryHandler::new(
+            InMemoryProjectionStore::<UserStatsState>::new(),
+        ),
+        time_entry_comments_handler: TimeEntryCommentsQueryHandler::new(InMemoryProjectionStore::<
+            TimeEntryCommentsState,
+        >::new()),
+        time_entry_attachments_handler: TimeEntryAttachmentsQueryHandler::new(
+            InMemoryProjectionStore::<TimeEntryAttachmentsState>::new(),
+        ),
+        contract_event_store,
+        set_contract_handler,
+        utilization_handler,
         tag_event_store,
         create_tag_handler,
         delete_tag_handler,
@@ -53,5 +231,29 @@ pub fn make_test_app_state() -> AppState {
         set_tag_description_handler,
         list_tags_handler,
         tag_projection_store,
+        template_event_store,
+        save_template_handler,
+        list_templates_handler,
+        schedule_event_store,
+        define_schedule_handler,
+        cancel_schedule_handler,
+        list_schedules_handler,
+        user_display_name_loader,
+        api_key_store: InMemoryApiKeyStore::new(),
+        audit_store: InMemoryApiAuditStore::new(),
+        job_store: InMemoryJobStore::new(),
+        bulk_delete_confirmations: BulkDeleteConfirmations::new(),
+        cold_storage: Arc::new(InMemoryColdStorage::new()),
+        attachment_storage: InMemoryAttachmentStorage::new(),
+        control_store: InMemoryControlStore::new(),
+        consumer_lag: ConsumerLag::new(),
+        relay_metrics: RelayMetrics::new(),
+        write_slo,
+        in_memory_capacity: CapacityGauges::new(),
+        stream_naming: Arc::new(DefaultStreamNaming),
+        feature_flags,
+        policy_store,
+        default_policies: Policies::default(),
+        tuning: WorkerTuning::default(),
     }
 }
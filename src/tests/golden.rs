@@ -0,0 +1,321 @@
+// Schema compatibility of stored events.
+//
+// Every version of a stream's event enum has a golden payload in
+// `src/tests/fixtures/events/golden/<stream>/<Version>.json`, written exactly as the event
+// store persists it (`type` tag included). `assert_event_goldens` checks that:
+//
+// - every version the enum declares has a golden, so a new version cannot ship without one;
+// - every golden, including those of versions no longer in the enum, still deserializes
+//   through the enum's `DomainEvent::from_stored`, which is where upcasting from older
+//   versions happens;
+// - re-serializing a current version reproduces its golden exactly. A golden whose version
+//   is upcast to a newer one is instead compared with `<Version>.upcasted.json`.
+//
+// Goldens are never edited or deleted once a version has been released; a changed payload
+// shape is a new version.
+
+use crate::shared::core::domain_event::DomainEvent;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use serde_json::{Value, json};
+use std::fmt::Debug;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+pub const GOLDEN_DIR: &str = "./src/tests/fixtures/events/golden";
+
+const UPCASTED_SUFFIX: &str = ".upcasted.json";
+
+/// Checks the goldens of `stream` against `TEvent`. Panics with every violation found.
+pub fn assert_event_goldens<TEvent>(stream: &str)
+where
+    TEvent: Serialize + DomainEvent + Debug,
+{
+    let dir = Path::new(GOLDEN_DIR).join(stream);
+    let mut violations = Vec::new();
+
+    for version in versions_of::<TEvent>() {
+        if !golden_path(&dir, &version).exists() {
+            violations.push(format!("{version}: no golden at {}", dir.display()));
+        }
+    }
+
+    for path in goldens_in(&dir) {
+        if let Err(violation) = check_golden::<TEvent>(&path) {
+            violations.push(violation);
+        }
+    }
+
+    assert!(
+        violations.is_empty(),
+        "event schema compatibility of `{stream}` broken:\n  {}",
+        violations.join("\n  ")
+    );
+}
+
+/// Checks that the `DomainEvent` catalog of `TEvent` lists exactly the versions serde accepts.
+pub fn assert_catalog<TEvent: DomainEvent>() {
+    let cataloged: Vec<&str> = TEvent::CATALOG.iter().map(|r| r.tag).collect();
+    assert_eq!(cataloged, versions_of::<TEvent>());
+}
+
+/// The `type` tags `TEvent` accepts, read from serde's rejection of an unknown one.
+pub fn versions_of<TEvent: DeserializeOwned>() -> Vec<String> {
+    let error = serde_json::from_value::<TEvent>(json!({ "type": "" }))
+        .err()
+        .expect("an empty type tag is never a version");
+    let message = error.to_string();
+    let (_, expected) = message
+        .split_once("expected")
+        .unwrap_or_else(|| panic!("not an internally tagged enum: {message}"));
+    expected
+        .split('`')
+        .skip(1)
+        .step_by(2)
+        .map(str::to_string)
+        .collect()
+}
+
+fn golden_path(dir: &Path, version: &str) -> PathBuf {
+    dir.join(format!("{version}.json"))
+}
+
+fn goldens_in(dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut paths: Vec<PathBuf> = entries
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| {
+            let name = path.file_name().unwrap().to_string_lossy();
+            name.ends_with(".json") && !name.ends_with(UPCASTED_SUFFIX)
+        })
+        .collect();
+    paths.sort();
+    paths
+}
+
+fn read_json(path: &Path) -> Result<Value, String> {
+    let text = fs::read_to_string(path).map_err(|e| format!("{}: {e}", path.display()))?;
+    serde_json::from_str(&text).map_err(|e| format!("{}: {e}", path.display()))
+}
+
+fn check_golden<TEvent>(path: &Path) -> Result<(), String>
+where
+    TEvent: Serialize + DomainEvent + Debug,
+{
+    let version = path.file_stem().unwrap().to_string_lossy().to_string();
+    let stored = read_json(path)?;
+    if stored["type"] != version.as_str() {
+        return Err(format!("{version}: golden is tagged {}", stored["type"]));
+    }
+
+    let event = TEvent::from_stored(stored.clone())
+        .map_err(|e| format!("{version}: no longer deserializes: {e}"))?;
+    let reserialized = serde_json::to_value(&event).unwrap();
+
+    let expected = if reserialized["type"] == stored["type"] {
+        stored
+    } else {
+        read_json(&path.with_file_name(format!("{version}{UPCASTED_SUFFIX}")))
+            .map_err(|e| format!("{version}: upcast to {}, {e}", reserialized["type"]))?
+    };
+    if reserialized != expected {
+        return Err(format!(
+            "{version}: serializes as {reserialized}, golden is {expected}"
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod golden_tests {
+    use super::*;
+    use rstest::rstest;
+    use serde::Deserialize;
+
+    #[derive(Debug, Serialize, Deserialize)]
+    struct A {
+        n: i64,
+    }
+
+    #[derive(Debug, Serialize, Deserialize)]
+    struct B {
+        n: i64,
+    }
+
+    #[derive(Debug, Serialize, Deserialize)]
+    struct C {
+        n: i64,
+    }
+
+    #[derive(Debug, Serialize, Deserialize, DomainEvent)]
+    #[serde(tag = "type")]
+    enum Single {
+        OnlyV1(A),
+    }
+
+    #[derive(Debug, Serialize, Deserialize, DomainEvent)]
+    #[serde(tag = "type")]
+    enum Pair {
+        AV1(A),
+        BV1(B),
+    }
+
+    #[derive(Debug, Serialize, Deserialize, DomainEvent)]
+    #[serde(tag = "type")]
+    enum Many {
+        AV1(A),
+        BV1(B),
+        CV1(C),
+    }
+
+    /// V1 is upcast to V2 on the way in, as a retired version would be.
+    #[derive(Debug, Serialize, Deserialize, DomainEvent)]
+    #[serde(tag = "type")]
+    enum Upcasting {
+        #[serde(alias = "ItemV1")]
+        ItemV2(A),
+    }
+
+    struct Scratch(PathBuf);
+
+    impl Scratch {
+        fn new(name: &str, files: &[(&str, Value)]) -> Self {
+            let dir = Path::new(GOLDEN_DIR).join(format!("__scratch_{name}"));
+            let _ = fs::remove_dir_all(&dir);
+            fs::create_dir_all(&dir).unwrap();
+            for (file, value) in files {
+                fs::write(dir.join(file), value.to_string()).unwrap();
+            }
+            Self(dir)
+        }
+
+        fn stream(&self) -> String {
+            self.0.file_name().unwrap().to_string_lossy().to_string()
+        }
+    }
+
+    impl Drop for Scratch {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.0);
+        }
+    }
+
+    #[rstest]
+    fn it_should_list_every_version_of_an_enum() {
+        assert_eq!(versions_of::<Single>(), vec!["OnlyV1"]);
+        assert_eq!(versions_of::<Pair>(), vec!["AV1", "BV1"]);
+        assert_eq!(versions_of::<Many>(), vec!["AV1", "BV1", "CV1"]);
+    }
+
+    #[rstest]
+    fn it_should_accept_stable_goldens_for_every_version() {
+        let scratch = Scratch::new(
+            "stable",
+            &[
+                ("AV1.json", json!({ "type": "AV1", "n": 1 })),
+                ("BV1.json", json!({ "type": "BV1", "n": 2 })),
+            ],
+        );
+
+        assert_event_goldens::<Pair>(&scratch.stream());
+    }
+
+    #[rstest]
+    fn it_should_accept_retired_versions_matching_their_upcast() {
+        let scratch = Scratch::new(
+            "upcast",
+            &[
+                ("ItemV1.json", json!({ "type": "ItemV1", "n": 1 })),
+                ("ItemV1.upcasted.json", json!({ "type": "ItemV2", "n": 1 })),
+                ("ItemV2.json", json!({ "type": "ItemV2", "n": 1 })),
+            ],
+        );
+
+        assert_event_goldens::<Upcasting>(&scratch.stream());
+    }
+
+    #[rstest]
+    #[case::missing_golden(
+        "missing",
+        vec![("AV1.json", json!({ "type": "AV1", "n": 1 }))],
+        "BV1: no golden"
+    )]
+    #[case::mistagged(
+        "mistagged",
+        vec![
+            ("AV1.json", json!({ "type": "BV1", "n": 1 })),
+            ("BV1.json", json!({ "type": "BV1", "n": 1 })),
+        ],
+        "AV1: golden is tagged \"BV1\""
+    )]
+    #[case::undeserializable(
+        "undeserializable",
+        vec![
+            ("AV1.json", json!({ "type": "AV1", "n": "one" })),
+            ("BV1.json", json!({ "type": "BV1", "n": 1 })),
+        ],
+        "AV1: no longer deserializes"
+    )]
+    #[case::unstable(
+        "unstable",
+        vec![
+            ("AV1.json", json!({ "type": "AV1", "n": 1, "dropped": true })),
+            ("BV1.json", json!({ "type": "BV1", "n": 1 })),
+        ],
+        "AV1: serializes as"
+    )]
+    #[case::unparseable(
+        "unparseable",
+        vec![
+            ("AV1.json", json!("not an object")),
+            ("BV1.json", json!({ "type": "BV1", "n": 1 })),
+        ],
+        "AV1: golden is tagged null"
+    )]
+    fn it_should_report_broken_goldens(
+        #[case] name: &str,
+        #[case] files: Vec<(&str, Value)>,
+        #[case] expected: &str,
+    ) {
+        let scratch = Scratch::new(name, &files);
+
+        let failure = std::panic::catch_unwind(|| assert_event_goldens::<Pair>(&scratch.stream()))
+            .unwrap_err();
+
+        let message = failure.downcast_ref::<String>().unwrap();
+        assert!(message.contains(expected), "{message}");
+    }
+
+    #[rstest]
+    fn it_should_report_retired_versions_without_an_upcast_golden() {
+        let scratch = Scratch::new(
+            "no_upcast",
+            &[
+                ("ItemV1.json", json!({ "type": "ItemV1", "n": 1 })),
+                ("ItemV2.json", json!({ "type": "ItemV2", "n": 1 })),
+            ],
+        );
+
+        let failure =
+            std::panic::catch_unwind(|| assert_event_goldens::<Upcasting>(&scratch.stream()))
+                .unwrap_err();
+
+        let message = failure.downcast_ref::<String>().unwrap();
+        assert!(
+            message.contains("ItemV1: upcast to \"ItemV2\""),
+            "{message}"
+        );
+    }
+
+    #[rstest]
+    fn it_should_report_every_version_when_the_stream_has_no_goldens() {
+        let failure = std::panic::catch_unwind(|| assert_event_goldens::<Pair>("__scratch_absent"))
+            .unwrap_err();
+
+        let message = failure.downcast_ref::<String>().unwrap();
+        assert!(message.contains("AV1: no golden"), "{message}");
+        assert!(message.contains("BV1: no golden"), "{message}");
+    }
+}
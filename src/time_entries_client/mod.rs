@@ -0,0 +1,653 @@
+// Typed client for the REST API, for other Rust services that register time or manage tags.
+//
+// Requests and responses reuse the handlers' own body types, so a route change that breaks
+// callers breaks this module's build first. The client is independent of an HTTP crate: the
+// caller supplies an `HttpTransport`, as for the calendar, KMS and Vault adapters.
+//
+// Every mutating call sends an `Idempotency-Key` header that stays the same across its
+// retries. Only idempotent requests are retried: the PUT routes, reads, and tag creation with
+// a client-chosen tag id.
+
+use crate::modules::tags::use_cases::create_tag::inbound::http::{
+    CreateTagBody, CreateTagResponse,
+};
+use crate::modules::time_entries::use_cases::list_time_entries::inbound::http::ListTimeEntriesPage;
+use crate::modules::time_entries::use_cases::set_ended_at::inbound::http::SetEndedAtBody;
+use crate::modules::time_entries::use_cases::set_started_at::inbound::http::SetStartedAtBody;
+use crate::modules::time_entries::use_cases::set_time_entry_tags::inbound::http::SetTimeEntryTagsBody;
+use crate::shared::infrastructure::request_context::API_KEY_HEADER;
+pub use crate::shared::infrastructure::request_context::IDEMPOTENCY_KEY_HEADER;
+use crate::shell::http::routes::API_PREFIX;
+use async_trait::async_trait;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use std::time::Duration;
+use thiserror::Error;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Method {
+    Get,
+    Post,
+    Put,
+}
+
+impl Method {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Method::Get => "GET",
+            Method::Post => "POST",
+            Method::Put => "PUT",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClientRequest {
+    pub method: Method,
+    /// Absolute URL, query string included.
+    pub url: String,
+    pub headers: Vec<(String, String)>,
+    /// JSON body, sent with `content-type: application/json`.
+    pub body: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClientResponse {
+    pub status: u16,
+    pub body: String,
+}
+
+/// Sends one request. `Err` is a failure to get any response, such as a refused connection or
+/// a timeout; every status code is an `Ok`.
+#[async_trait]
+pub trait HttpTransport: Send + Sync {
+    async fn send(&self, request: ClientRequest) -> Result<ClientResponse, String>;
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum ClientError {
+    #[error("request failed: {0}")]
+    Transport(String),
+
+    #[error("unauthorized")]
+    Unauthorized,
+
+    #[error("forbidden")]
+    Forbidden,
+
+    #[error("not found")]
+    NotFound,
+
+    #[error("conflict: {0}")]
+    Conflict(String),
+
+    #[error("invalid request: {0}")]
+    Invalid(String),
+
+    #[error("rate limited")]
+    RateLimited,
+
+    #[error("server error {status}: {body}")]
+    Server { status: u16, body: String },
+
+    #[error("unexpected response: {0}")]
+    Decode(String),
+}
+
+impl ClientError {
+    fn from_status(response: ClientResponse) -> Self {
+        match response.status {
+            401 => ClientError::Unauthorized,
+            403 => ClientError::Forbidden,
+            404 => ClientError::NotFound,
+            409 => ClientError::Conflict(response.body),
+            400 | 422 => ClientError::Invalid(response.body),
+            429 => ClientError::RateLimited,
+            status => ClientError::Server {
+                status,
+                body: response.body,
+            },
+        }
+    }
+
+    /// Whether another attempt may succeed: the request never got an answer, or the service
+    /// or a proxy in front of it was briefly unavailable.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            ClientError::Transport(_) | ClientError::RateLimited => true,
+            ClientError::Server { status, .. } => matches!(status, 502..=504),
+            _ => false,
+        }
+    }
+}
+
+/// Who the client calls as.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Credentials {
+    /// A service API key, sent as `x-api-key`.
+    ApiKey(String),
+    /// A user, as the gateway forwards them after authenticating the caller.
+    User {
+        user_id: String,
+        tenant_id: String,
+        role: Option<String>,
+    },
+}
+
+impl Credentials {
+    fn headers(&self) -> Vec<(String, String)> {
+        match self {
+            Credentials::ApiKey(key) => vec![(API_KEY_HEADER.to_string(), key.clone())],
+            Credentials::User {
+                user_id,
+                tenant_id,
+                role,
+            } => {
+                let mut headers = vec![
+                    ("x-user-id".to_string(), user_id.clone()),
+                    ("x-tenant-id".to_string(), tenant_id.clone()),
+                ];
+                headers.extend(
+                    role.iter()
+                        .map(|role| ("x-user-role".to_string(), role.clone())),
+                );
+                headers
+            }
+        }
+    }
+}
+
+/// Up to `max_attempts` tries per call, waiting `backoff * 2^n` before the n-th retry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    pub max_attempts: u32,
+    pub backoff: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            backoff: Duration::from_millis(100),
+        }
+    }
+}
+
+/// Query for `list_time_entries`; unset fields take the server's defaults.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ListTimeEntriesQuery {
+    pub offset: Option<u64>,
+    pub limit: Option<u64>,
+    pub sort_desc: Option<bool>,
+    pub user_id: Option<String>,
+}
+
+impl ListTimeEntriesQuery {
+    fn to_query_string(&self) -> String {
+        let pairs: Vec<String> = [
+            self.offset.map(|offset| format!("offset={offset}")),
+            self.limit.map(|limit| format!("limit={limit}")),
+            self.sort_desc.map(|desc| format!("sort_desc={desc}")),
+            self.user_id
+                .as_deref()
+                .map(|user_id| format!("user_id={}", encode(user_id))),
+        ]
+        .into_iter()
+        .flatten()
+        .collect();
+        match pairs.is_empty() {
+            true => String::new(),
+            false => format!("?{}", pairs.join("&")),
+        }
+    }
+}
+
+/// The fields of a new tag; `create_tag` picks its id.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct NewTag {
+    pub name: String,
+    pub color: Option<String>,
+    pub description: Option<String>,
+}
+
+#[derive(Clone)]
+pub struct TimeEntriesClient<TTransport> {
+    base_url: String,
+    credentials: Credentials,
+    retry: RetryPolicy,
+    transport: TTransport,
+}
+
+impl<TTransport: HttpTransport> TimeEntriesClient<TTransport> {
+    /// A client for the service at `base_url`, such as `http://time-entries:8080`.
+    pub fn new(
+        base_url: impl Into<String>,
+        credentials: Credentials,
+        transport: TTransport,
+    ) -> Self {
+        Self {
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+            credentials,
+            retry: RetryPolicy::default(),
+            transport,
+        }
+    }
+
+    pub fn with_retry_policy(mut self, retry: RetryPolicy) -> Self {
+        self.retry = retry;
+        self
+    }
+
+    /// PUT /time-entries/{id}/start. Creates the entry if it does not exist yet.
+    pub async fn set_started_at(
+        &self,
+        time_entry_id: &str,
+        started_at: i64,
+    ) -> Result<(), ClientError> {
+        let path = format!("/time-entries/{}/start", encode(time_entry_id));
+        self.put(&path, &SetStartedAtBody { started_at }).await
+    }
+
+    /// PUT /time-entries/{id}/end. Creates the entry if it does not exist yet.
+    pub async fn set_ended_at(
+        &self,
+        time_entry_id: &str,
+        ended_at: i64,
+    ) -> Result<(), ClientError> {
+        let path = format!("/time-entries/{}/end", encode(time_entry_id));
+        self.put(&path, &SetEndedAtBody { ended_at }).await
+    }
+
+    /// PUT /time-entries/{id}/tags. Replaces the entry's tags.
+    pub async fn set_time_entry_tags(
+        &self,
+        time_entry_id: &str,
+        tag_ids: Vec<String>,
+    ) -> Result<(), ClientError> {
+        let path = format!("/time-entries/{}/tags", encode(time_entry_id));
+        self.put(&path, &SetTimeEntryTagsBody { tag_ids }).await
+    }
+
+    /// GET /list-time-entries.
+    pub async fn list_time_entries(
+        &self,
+        query: &ListTimeEntriesQuery,
+    ) -> Result<ListTimeEntriesPage, ClientError> {
+        let url = self.url(&format!("/list-time-entries{}", query.to_query_string()));
+        let response = self.send(Method::Get, url, None, None).await?;
+        decode(&response)
+    }
+
+    /// POST /tags. Returns the new tag's id. The id is chosen here and sent with the tag, so a
+    /// retry after a lost response finds the tag already created; that retry's 409 counts as
+    /// success. A 409 on the first attempt is a real conflict, such as a duplicate name.
+    pub async fn create_tag(&self, tag: NewTag) -> Result<String, ClientError> {
+        let tag_id = uuid::Uuid::now_v7().to_string();
+        let body = encode_body(&CreateTagBody {
+            tag_id: Some(tag_id.clone()),
+            name: tag.name,
+            color: tag.color,
+            description: tag.description,
+        })?;
+        let url = self.url("/tags");
+        match self
+            .send_with_attempts(Method::Post, url, Some(body), Some(tag_id.clone()))
+            .await
+        {
+            Ok((response, _)) => Ok(decode::<CreateTagResponse>(&response)?.tag_id),
+            Err((ClientError::Conflict(_), attempts)) if attempts > 1 => Ok(tag_id),
+            Err((error, _)) => Err(error),
+        }
+    }
+
+    async fn put(&self, path: &str, body: &impl Serialize) -> Result<(), ClientError> {
+        let body = encode_body(body)?;
+        let key = uuid::Uuid::now_v7().to_string();
+        self.send(Method::Put, self.url(path), Some(body), Some(key))
+            .await
+            .map(|_| ())
+    }
+
+    fn url(&self, path: &str) -> String {
+        format!("{}{API_PREFIX}{path}", self.base_url)
+    }
+
+    async fn send(
+        &self,
+        method: Method,
+        url: String,
+        body: Option<String>,
+        idempotency_key: Option<String>,
+    ) -> Result<ClientResponse, ClientError> {
+        self.send_with_attempts(method, url, body, idempotency_key)
+            .await
+            .map(|(response, _)| response)
+            .map_err(|(error, _)| error)
+    }
+
+    /// Sends the request, retrying per the policy, and returns the outcome with the number of
+    /// attempts it took.
+    async fn send_with_attempts(
+        &self,
+        method: Method,
+        url: String,
+        body: Option<String>,
+        idempotency_key: Option<String>,
+    ) -> Result<(ClientResponse, u32), (ClientError, u32)> {
+        let mut headers = self.credentials.headers();
+        if body.is_some() {
+            headers.push(("content-type".to_string(), "application/json".to_string()));
+        }
+        if let Some(key) = idempotency_key {
+            headers.push((IDEMPOTENCY_KEY_HEADER.to_string(), key));
+        }
+        let request = ClientRequest {
+            method,
+            url,
+            headers,
+            body,
+        };
+        let max_attempts = self.retry.max_attempts.max(1);
+        let mut attempt = 1;
+        loop {
+            let outcome = match self.transport.send(request.clone()).await {
+                Ok(response) if (200..300).contains(&response.status) => {
+                    return Ok((response, attempt));
+                }
+                Ok(response) => ClientError::from_status(response),
+                Err(reason) => ClientError::Transport(reason),
+            };
+            if attempt >= max_attempts || !outcome.is_retryable() {
+                return Err((outcome, attempt));
+            }
+            tracing::warn!(
+                method = method.as_str(),
+                url = %request.url,
+                attempt,
+                error = %outcome,
+                "retrying time entries API request"
+            );
+            tokio::time::sleep(self.retry.backoff * 2u32.pow(attempt - 1)).await;
+            attempt += 1;
+        }
+    }
+}
+
+fn encode_body(body: &impl Serialize) -> Result<String, ClientError> {
+    serde_json::to_string(body).map_err(|error| ClientError::Decode(error.to_string()))
+}
+
+fn decode<T: DeserializeOwned>(response: &ClientResponse) -> Result<T, ClientError> {
+    serde_json::from_str(&response.body).map_err(|error| ClientError::Decode(error.to_string()))
+}
+
+/// Percent-encodes everything but RFC 3986 unreserved characters, for path segments and
+/// query values.
+fn encode(value: &str) -> String {
+    value
+        .bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                (byte as char).to_string()
+            }
+            _ => format!("%{byte:02X}"),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod time_entries_client_tests {
+    use super::*;
+    use rstest::rstest;
+    use std::collections::VecDeque;
+    use std::sync::{Arc, Mutex};
+
+    /// Answers with the queued outcomes in order, then 200 with an empty object.
+    #[derive(Clone, Default)]
+    struct StubTransport {
+        outcomes: Arc<Mutex<VecDeque<Result<ClientResponse, String>>>>,
+        requests: Arc<Mutex<Vec<ClientRequest>>>,
+    }
+
+    impl StubTransport {
+        fn answering(outcomes: Vec<Result<ClientResponse, String>>) -> Self {
+            Self {
+                outcomes: Arc::new(Mutex::new(outcomes.into())),
+                requests: Arc::default(),
+            }
+        }
+
+        fn requests(&self) -> Vec<ClientRequest> {
+            self.requests.lock().unwrap().clone()
+        }
+    }
+
+    #[async_trait]
+    impl HttpTransport for StubTransport {
+        async fn send(&self, request: ClientRequest) -> Result<ClientResponse, String> {
+            self.requests.lock().unwrap().push(request);
+            self.outcomes
+                .lock()
+                .unwrap()
+                .pop_front()
+                .unwrap_or_else(|| Ok(status(200, "{}")))
+        }
+    }
+
+    fn status(status: u16, body: &str) -> ClientResponse {
+        ClientResponse {
+            status,
+            body: body.to_string(),
+        }
+    }
+
+    fn client(transport: &StubTransport) -> TimeEntriesClient<StubTransport> {
+        TimeEntriesClient::new(
+            "http://time-entries.test/",
+            Credentials::ApiKey("k-1".to_string()),
+            transport.clone(),
+        )
+        .with_retry_policy(RetryPolicy {
+            max_attempts: 3,
+            backoff: Duration::ZERO,
+        })
+    }
+
+    fn header<'a>(request: &'a ClientRequest, name: &str) -> Option<&'a str> {
+        request
+            .headers
+            .iter()
+            .find(|(header, _)| header == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_put_started_at_with_the_api_key() {
+        let transport = StubTransport::default();
+
+        client(&transport)
+            .set_started_at("te-1", 1_000)
+            .await
+            .unwrap();
+
+        let [request] = transport.requests().try_into().unwrap();
+        assert_eq!(request.method, Method::Put);
+        assert_eq!(
+            request.url,
+            "http://time-entries.test/api/v1/time-entries/te-1/start"
+        );
+        assert_eq!(request.body.as_deref(), Some(r#"{"started_at":1000}"#));
+        assert_eq!(header(&request, "x-api-key"), Some("k-1"));
+        assert!(header(&request, IDEMPOTENCY_KEY_HEADER).is_some());
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_send_user_headers() {
+        let transport = StubTransport::default();
+        let client = TimeEntriesClient::new(
+            "http://time-entries.test",
+            Credentials::User {
+                user_id: "u-1".to_string(),
+                tenant_id: "t-1".to_string(),
+                role: Some("manager".to_string()),
+            },
+            transport.clone(),
+        );
+
+        client.set_ended_at("te-1", 2_000).await.unwrap();
+
+        let [request] = transport.requests().try_into().unwrap();
+        assert_eq!(header(&request, "x-user-id"), Some("u-1"));
+        assert_eq!(header(&request, "x-tenant-id"), Some("t-1"));
+        assert_eq!(header(&request, "x-user-role"), Some("manager"));
+        assert_eq!(header(&request, "x-api-key"), None);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_retry_with_the_same_idempotency_key() {
+        let transport = StubTransport::answering(vec![
+            Err("connection reset".to_string()),
+            Ok(status(503, "")),
+        ]);
+
+        client(&transport)
+            .set_time_entry_tags("te-1", vec!["tag-1".to_string()])
+            .await
+            .unwrap();
+
+        let requests = transport.requests();
+        assert_eq!(requests.len(), 3);
+        let keys: Vec<_> = requests
+            .iter()
+            .map(|request| header(request, IDEMPOTENCY_KEY_HEADER))
+            .collect();
+        assert!(keys.iter().all(|key| *key == keys[0]));
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_give_up_after_the_last_attempt() {
+        let transport = StubTransport::answering(vec![Ok(status(503, "")); 3]);
+
+        assert_eq!(
+            client(&transport).set_started_at("te-1", 1_000).await,
+            Err(ClientError::Server {
+                status: 503,
+                body: String::new(),
+            })
+        );
+        assert_eq!(transport.requests().len(), 3);
+    }
+
+    #[rstest]
+    #[case::unauthorized(401, ClientError::Unauthorized)]
+    #[case::forbidden(403, ClientError::Forbidden)]
+    #[case::not_found(404, ClientError::NotFound)]
+    #[case::conflict(409, ClientError::Conflict("locked".to_string()))]
+    #[case::invalid(422, ClientError::Invalid("locked".to_string()))]
+    #[case::internal(500, ClientError::Server { status: 500, body: "locked".to_string() })]
+    #[tokio::test]
+    async fn it_should_not_retry_client_and_internal_errors(
+        #[case] code: u16,
+        #[case] expected: ClientError,
+    ) {
+        let transport = StubTransport::answering(vec![Ok(status(code, "locked"))]);
+
+        assert_eq!(
+            client(&transport).set_started_at("te-1", 1_000).await,
+            Err(expected)
+        );
+        assert_eq!(transport.requests().len(), 1);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_list_time_entries_with_the_query() {
+        let transport = StubTransport::answering(vec![Ok(status(
+            200,
+            r#"{"items":[],"total":0,"offset":20,"limit":10,"has_more":false}"#,
+        ))]);
+        let query = ListTimeEntriesQuery {
+            offset: Some(20),
+            limit: Some(10),
+            user_id: Some("u 2".to_string()),
+            ..ListTimeEntriesQuery::default()
+        };
+
+        let page = client(&transport).list_time_entries(&query).await.unwrap();
+
+        assert_eq!((page.total, page.offset, page.limit), (0, 20, 10));
+        let [request] = transport.requests().try_into().unwrap();
+        assert_eq!(
+            request.url,
+            "http://time-entries.test/api/v1/list-time-entries?offset=20&limit=10&user_id=u%202"
+        );
+        assert_eq!(header(&request, IDEMPOTENCY_KEY_HEADER), None);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_report_undecodable_responses() {
+        let transport = StubTransport::answering(vec![Ok(status(200, "<html>"))]);
+
+        assert!(matches!(
+            client(&transport)
+                .list_time_entries(&ListTimeEntriesQuery::default())
+                .await,
+            Err(ClientError::Decode(_))
+        ));
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn it_should_create_tags_with_a_client_chosen_id() {
+        let transport =
+            StubTransport::answering(vec![Ok(status(201, r#"{"tag_id":"from-server"}"#))]);
+        let tag = NewTag {
+            name: "Billable".to_string(),
+            ..NewTag::default()
+        };
+
+        let tag_id = client(&transport).create_tag(tag).await.unwrap();
+
+        let [request] = transport.requests().try_into().unwrap();
+        let body: serde_json::Value =
+            serde_json::from_str(request.body.as_deref().unwrap()).unwrap();
+        assert_eq!(tag_id, "from-server");
+        assert_eq!(
+            header(&request, IDEMPOTENCY_KEY_HEADER),
+            body["tag_id"].as_str()
+        );
+    }
+
+    #[rstest]
+    #[case::after_a_lost_response(vec![Err("timeout".to_string()), Ok(status(409, ""))], true)]
+    #[case::on_the_first_attempt(vec![Ok(status(409, "name taken"))], false)]
+    #[tokio::test]
+    async fn it_should_treat_a_retried_conflict_as_created(
+        #[case] outcomes: Vec<Result<ClientResponse, String>>,
+        #[case] is_created: bool,
+    ) {
+        let transport = StubTransport::answering(outcomes);
+        let tag = NewTag {
+            name: "Billable".to_string(),
+            ..NewTag::default()
+        };
+
+        let created = client(&transport).create_tag(tag).await;
+
+        let requests = transport.requests();
+        let body: serde_json::Value =
+            serde_json::from_str(requests[0].body.as_deref().unwrap()).unwrap();
+        match is_created {
+            true => assert_eq!(created.as_deref().ok(), body["tag_id"].as_str()),
+            false => assert_eq!(
+                created,
+                Err(ClientError::Conflict("name taken".to_string()))
+            ),
+        }
+    }
+}